@@ -1,11 +1,19 @@
 use clap::{Parser, Subcommand};
 
+use conductor_core::agent::{AgentPriority, CostGroupBy};
+use conductor_core::api_token::ApiTokenScope;
+use conductor_core::config::SandboxMode;
+use conductor_core::instructions::InstructionsFile;
+
 /// Environment variable name used to pass the current agent run ID to subprocesses.
 pub const CONDUCTOR_RUN_ID_ENV: &str = "CONDUCTOR_RUN_ID";
 
 #[derive(Parser)]
 #[command(name = "conductor", about = "Multi-repo orchestration tool", version)]
 pub struct Cli {
+    /// Print machine-readable JSON instead of text from list and show commands
+    #[arg(long, global = true)]
+    pub json: bool,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -62,6 +70,255 @@ pub enum Commands {
         #[command(subcommand)]
         command: ConversationCommands,
     },
+    /// Group worktrees across repos that ship together
+    Changeset {
+        #[command(subcommand)]
+        command: ChangesetCommands,
+    },
+    /// Manage API tokens for the web server
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+    /// Manage the tmux session conductor opens its windows in
+    Tmux {
+        #[command(subcommand)]
+        command: TmuxCommands,
+    },
+    /// List and check workspace roots, or update paths after moving one
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+    /// Manage the conductor database file
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+    /// Check the environment conductor depends on
+    Doctor {
+        #[command(subcommand)]
+        command: DoctorCommands,
+    },
+    /// Write project settings to .conductor/config.toml in the current repo,
+    /// register the repo if needed, and validate the settings
+    #[command(
+        after_help = "Examples:\n  conductor init\n  conductor init --test-command \"make check\" --setup \"make deps\" --protect migrations/"
+    )]
+    Init {
+        /// Command agents run to test changes (detected from the project when omitted)
+        #[arg(long)]
+        test_command: Option<String>,
+        /// Command to run in each new worktree after checkout (repeatable)
+        #[arg(long = "setup")]
+        setup: Vec<String>,
+        /// Instructions prepended to every agent prompt
+        #[arg(long)]
+        preamble: Option<String>,
+        /// Branch prefix for new worktrees (default: feat)
+        #[arg(long)]
+        branch_prefix: Option<String>,
+        /// Repo-relative path agents must not modify (repeatable)
+        #[arg(long = "protect")]
+        protected_paths: Vec<String>,
+        /// Slug to register the repo under (default: derived from the origin URL)
+        #[arg(long)]
+        slug: Option<String>,
+    },
+    /// Summarize running agents, worktrees needing attention, and the current session
+    #[command(
+        after_help = "Porcelain format (stable; new fields are only ever appended):\n  v1 agents=<n> attention=<n> session=<repo>/<worktree>:<state>\n\n  session=- when the current directory is not inside a worktree.\n\nExamples:\n  conductor status\n  set -g status-right '#(conductor status --porcelain)'   # tmux"
+    )]
+    Status {
+        /// Print one machine-readable line for status bars and shell prompts
+        #[arg(long)]
+        porcelain: bool,
+    },
+    /// Delete finished worktrees, agent runs and activity older than the [retention] windows
+    #[command(
+        after_help = "Retention windows are set in ~/.conductor/config.toml:\n  [retention]\n  worktree_days = 30\n  agent_run_days = 90\n  event_days = 14\n\nExamples:\n  conductor prune --dry-run\n  conductor prune"
+    )]
+    Prune {
+        /// Report per-table counts without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Summarize agent cost, turns and time
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+    /// Run the background service that syncs tickets, reaps dead runs, starts queued agents and prunes old rows
+    #[command(
+        after_help = "While a daemon is running, the TUI and web server leave this work to it, so tickets\nkeep syncing with neither of them open. The daemon serves the current database on a\nsocket beside it (~/.conductor/conductor.sock by default).\n\nExamples:\n  conductor daemon\n  conductor daemon status\n  conductor daemon sync"
+    )]
+    Daemon {
+        #[command(subcommand)]
+        command: Option<DaemonCommands>,
+    },
+    /// Print a shell completion script, with repo and worktree slugs completed from the database
+    #[command(
+        after_help = "Examples:\n  conductor completions bash > ~/.local/share/bash-completion/completions/conductor\n  conductor completions zsh > \"${fpath[1]}/_conductor\"\n  conductor completions fish > ~/.config/fish/completions/conductor.fish"
+    )]
+    Completions {
+        /// Shell to generate the script for
+        shell: CompletionShell,
+    },
+    /// List repo or worktree slugs for the word being completed (called by completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Words after `conductor`, ending with the one being completed
+        #[arg(last = true)]
+        words: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum ConflictAction {
+    Show,
+    Agent,
+    Verify,
+    Continue,
+    Abort,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Roll up agent cost, turns and duration by repo, ticket, worktree or day
+    #[command(
+        after_help = "Every run started inside the window counts, including failed and cancelled ones.\n\nExamples:\n  conductor report costs\n  conductor report costs --since 7d --group-by day\n  conductor report costs --since 2026-01-01 --group-by ticket --json"
+    )]
+    Costs {
+        /// Window to report on: 12h, 30d, 2w, or a YYYY-MM-DD start date
+        #[arg(long, default_value = "30d")]
+        since: String,
+        /// Grouping: repo, ticket, worktree or day
+        #[arg(long, default_value_t = CostGroupBy::Repo)]
+        group_by: CostGroupBy,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ChangesetCommands {
+    /// Create a changeset from worktrees given as <repo>/<worktree>, in PR order
+    #[command(
+        after_help = "Examples:\n  conductor changeset create \"Rename user field\" api/feat-rename web/feat-rename"
+    )]
+    Create {
+        /// Changeset title (used as the PR title)
+        title: String,
+        /// Member worktrees as <repo>/<worktree>
+        #[arg(required = true)]
+        worktrees: Vec<String>,
+    },
+    /// Append a worktree (<repo>/<worktree>) to a changeset
+    Add {
+        /// Changeset ID
+        id: String,
+        /// Worktree as <repo>/<worktree>
+        worktree: String,
+    },
+    /// List changesets
+    List,
+    /// Show a changeset's worktrees and PRs
+    Show {
+        /// Changeset ID
+        id: String,
+    },
+    /// Push all branches and open cross-linked PRs in each repo
+    Pr {
+        /// Changeset ID
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TmuxCommands {
+    /// Attach to (or switch to) the managed session, creating it if needed
+    Attach,
+    /// Close windows whose agent run finished or whose worktree or repo is gone
+    Cleanup,
+}
+
+#[derive(Subcommand)]
+pub enum TokenCommands {
+    /// Create a token and print its secret (shown only once)
+    #[command(
+        after_help = "Scopes:\n  read-only      read any resource\n  agent-control  read, plus start, stop and answer agents\n  admin          everything, including managing tokens\n\nExamples:\n  conductor token create ci-dashboard --scope read-only\n  curl -H \"Authorization: Bearer $TOKEN\" http://127.0.0.1:3000/api/repos"
+    )]
+    Create {
+        /// Name to tell the token apart in listings
+        name: String,
+        /// Scope: read-only, agent-control or admin
+        #[arg(long)]
+        scope: ApiTokenScope,
+    },
+    /// List tokens with their scope and when they were last used
+    List,
+    /// Revoke a token so it no longer authenticates
+    Revoke {
+        /// Token ID
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceCommands {
+    /// List workspace roots, the repos under each, and whether they are usable
+    List,
+    /// Update stored repo and worktree paths after moving a workspace root
+    #[command(
+        after_help = "Move the directory first, then run migrate. Paths may use ~ and $VAR.\n\nExamples:\n  mv ~/.conductor/workspaces /mnt/fast/workspaces\n  conductor workspace migrate ~/.conductor/workspaces /mnt/fast/workspaces --dry-run\n  conductor workspace migrate ~/.conductor/workspaces /mnt/fast/workspaces"
+    )]
+    Migrate {
+        /// Old location of the workspace root
+        from: String,
+        /// New location the directory was moved to
+        to: String,
+        /// Report what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DoctorCommands {
+    /// Show which external tools are installed, with their versions
+    #[command(
+        after_help = "Results are cached in ~/.conductor/tools.json for a day; startup checks read the cache.\n\nExamples:\n  conductor doctor tools\n  conductor doctor tools --refresh --json"
+    )]
+    Tools {
+        /// Probe again instead of reading the cache
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Encrypt an existing plaintext database with SQLCipher and turn on
+    /// [database] encryption in config.toml
+    #[command(
+        after_help = "Needs a build with `--features sqlcipher`. Stop the TUI, web server and daemon first;\nthe database is rewritten in place. With the default keychain key source a random key\nis generated and stored in the OS keychain.\n\nExamples:\n  conductor db encrypt\n  CONDUCTOR_DB_KEY=... conductor db encrypt --key-source env"
+    )]
+    Encrypt {
+        /// Where the key comes from (default: [database] key_source, else keychain)
+        #[arg(long, value_enum)]
+        key_source: Option<DbKeySource>,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum DbKeySource {
+    Keychain,
+    Env,
 }
 
 #[derive(Subcommand)]
@@ -83,7 +340,8 @@ pub enum NotificationsCommands {
     /// Fire a synthetic notification event through all configured hooks (for testing)
     Test {
         /// Event name to fire. Valid values: workflow_run.completed, workflow_run.failed,
-        /// agent_run.completed, agent_run.failed, gate.waiting, feedback.requested
+        /// agent_run.completed, agent_run.failed, gate.waiting, feedback.requested,
+        /// watch.state_changed, watch.pr_updated
         #[arg(default_value = "workflow_run.completed")]
         event: String,
     },
@@ -113,9 +371,17 @@ pub enum SetupCommands {
     Uninstall,
 }
 
+#[derive(Subcommand)]
+pub enum DaemonCommands {
+    /// Show whether a daemon is running and what it last did
+    Status,
+    /// Ask the running daemon to sync tickets now
+    Sync,
+}
+
 #[derive(Subcommand)]
 pub enum AgentCommands {
-    /// Run a Claude agent for a worktree (spawned as a headless subprocess)
+    /// Run an agent for a worktree (spawned as a headless subprocess)
     Run {
         /// Agent run ID (from agent_runs table)
         #[arg(long)]
@@ -144,6 +410,12 @@ pub enum AgentCommands {
         /// Additional plugin directories to pass to the Claude CLI
         #[arg(long = "plugin-dir")]
         plugin_dirs: Vec<String>,
+        /// Agent CLI to drive: "claude", "codex" or "aider". Overrides repo and global defaults.
+        #[arg(long)]
+        backend: Option<String>,
+        /// Where to run the agent: "host", "docker" or "devcontainer". Overrides repo and global defaults.
+        #[arg(long)]
+        sandbox: Option<SandboxMode>,
     },
     /// Create a new GitHub issue (called by agents during a run)
     CreateIssue {
@@ -157,6 +429,101 @@ pub enum AgentCommands {
         #[arg(long)]
         run_id: Option<String>,
     },
+    /// Start an agent on a worktree as a background process and print its run ID
+    #[command(
+        after_help = "The agent keeps running after this command exits; no tmux is needed.\nFollow it with `conductor agent attach <run_id>` and stop it with `conductor agent stop <run_id>`.\n\nExamples:\n  conductor agent start api feat-rename --prompt \"Fix the failing tests\"\n  conductor agent start api feat-rename --prompt \"Implement #42\" --attach"
+    )]
+    Start {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        worktree: String,
+        /// Prompt for the agent
+        #[arg(long)]
+        prompt: String,
+        /// Model to use. Overrides per-worktree and global defaults.
+        #[arg(long)]
+        model: Option<String>,
+        /// Follow the run's output until it finishes
+        #[arg(long)]
+        attach: bool,
+    },
+    /// Queue agent runs to start when a slot is free (dispatched by `conductor-web`)
+    Queue {
+        #[command(subcommand)]
+        command: AgentQueueCommands,
+    },
+    /// Cancel a running agent run, or every one with --all
+    #[command(
+        after_help = "Examples:\n  conductor agent stop 01HXYZ...\n  conductor agent stop --all        # stop every running agent\n  conductor agent stop --all --yes"
+    )]
+    Stop {
+        /// Agent run ID
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        run_id: Option<String>,
+        /// Stop every running or feedback-waiting agent run
+        #[arg(long)]
+        all: bool,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Show the chain of follow-up runs an agent run belongs to
+    Chain {
+        /// Agent run ID
+        run_id: String,
+    },
+    /// Jump to the run's window in the managed tmux session, creating it if needed
+    #[command(
+        after_help = "The window is named run-<run_id>, starts in the run's worktree and follows its log.\nInside tmux the client switches to the managed session; otherwise it is attached.\nWithout tmux installed, the run's output is followed in this terminal until it finishes.\n\nExamples:\n  conductor agent attach 01J9Z8X4K2M3N5P6Q7R8S9T0V1"
+    )]
+    Attach {
+        /// Agent run ID
+        run_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AgentQueueCommands {
+    /// Queue an agent run for a worktree
+    #[command(
+        after_help = "Examples:\n  conductor agent queue add api feat-rename --prompt \"Fix the failing tests\" --priority urgent\n  conductor agent queue add api feat-rename --prompt \"Implement #42\" --on-success \"Add tests\" --notify-on-failure"
+    )]
+    Add {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        worktree: String,
+        /// Prompt for the agent
+        #[arg(long)]
+        prompt: String,
+        /// Priority: urgent, normal or background
+        #[arg(long, default_value = "normal")]
+        priority: AgentPriority,
+        /// Queue a follow-up run with this prompt when the run succeeds
+        #[arg(long)]
+        on_success: Option<String>,
+        /// Queue a follow-up run with this prompt when the run fails
+        #[arg(long, conflicts_with = "notify_on_failure")]
+        on_failure: Option<String>,
+        /// Record a notification when the run fails
+        #[arg(long)]
+        notify_on_failure: bool,
+    },
+    /// List queued runs in dispatch order
+    List,
+    /// Change the priority of a queued run
+    Priority {
+        /// Queued run ID
+        id: String,
+        /// New priority: urgent, normal or background
+        priority: AgentPriority,
+    },
+    /// Remove a run from the queue without starting it
+    Remove {
+        /// Queued run ID
+        id: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -184,17 +551,21 @@ pub enum WorkflowCommands {
     },
     /// Run a workflow
     #[command(
-        after_help = "Examples:\n  conductor workflow run ticket-to-pr my-repo my-worktree\n  conductor workflow run ticket-to-pr my-repo my-worktree --input key=value\n  conductor workflow run draft-release-notes --pr https://github.com/org/repo/pull/42\n  conductor workflow run publish-docs --pr org/repo#42 --input force=true\n  conductor workflow run draft-release-notes --repo my-repo\n  conductor workflow run workflow-postmortem --workflow-run 01ABC123\n  conductor workflow run ticket-to-pr --ticket 01KKFYDVE7F0X5KPR9Q7CX6SJ3"
+        after_help = "Examples:\n  conductor workflow run ticket-to-pr my-repo my-worktree\n  conductor workflow run ticket-to-pr --worktree my-worktree\n  conductor workflow run ticket-to-pr my-repo my-worktree --input key=value\n  conductor workflow run draft-release-notes --pr https://github.com/org/repo/pull/42\n  conductor workflow run publish-docs --pr org/repo#42 --input force=true\n  conductor workflow run draft-release-notes --repo my-repo\n  conductor workflow run workflow-postmortem --workflow-run 01ABC123\n  conductor workflow run ticket-to-pr --ticket 01KKFYDVE7F0X5KPR9Q7CX6SJ3"
     )]
     Run {
         /// Workflow name (must match a .conductor/workflows/<name>.wf file)
         name: String,
-        /// Repo slug (required unless --pr, --repo, --workflow-run, or --ticket is used)
-        #[arg(required_unless_present_any = &["pr", "repo_flag", "workflow_run", "ticket"])]
+        /// Repo slug (required unless --pr, --repo, --workflow-run, --ticket or --worktree is used)
+        #[arg(required_unless_present_any = &["pr", "repo_flag", "workflow_run", "ticket", "worktree_flag"])]
         repo: Option<String>,
-        /// Worktree slug (required unless --pr, --repo, --workflow-run, or --ticket is used)
-        #[arg(required_unless_present_any = &["pr", "repo_flag", "workflow_run", "ticket"])]
+        /// Worktree slug (required unless --pr, --repo, --workflow-run, --ticket or --worktree is used)
+        #[arg(required_unless_present_any = &["pr", "repo_flag", "workflow_run", "ticket", "worktree_flag"])]
         worktree: Option<String>,
+        /// Run against a worktree by slug alone; the repo is found from the
+        /// slug unless several repos have a live worktree with it
+        #[arg(long = "worktree", conflicts_with_all = &["worktree", "pr", "repo_flag", "workflow_run", "ticket"])]
+        worktree_flag: Option<String>,
         /// Run the workflow against a GitHub PR URL or reference (e.g. https://github.com/owner/repo/pull/123)
         #[arg(long, conflicts_with_all = &["repo", "worktree", "repo_flag", "workflow_run", "ticket"])]
         pr: Option<String>,
@@ -280,6 +651,9 @@ pub enum WorkflowCommands {
         /// Workflow run ID — find it with: conductor workflow runs <repo>
         #[arg(value_name = "RUN_ID")]
         run_id: String,
+        /// Feedback passed to the next agent step as {{gate_feedback}}
+        #[arg(long)]
+        feedback: Option<String>,
     },
     /// Reject a pending human gate (fails the workflow)
     #[command(
@@ -386,6 +760,37 @@ pub enum RepoCommands {
         /// Model alias or ID (e.g. "sonnet", "claude-opus-4-6"). Omit to clear.
         model: Option<String>,
     },
+    /// Point a repo's worktrees at another directory (e.g. a faster drive).
+    /// Paths may use ~ and $VAR. Omit the path to reset to the default root.
+    SetWorkspace {
+        /// Repo slug
+        slug: String,
+        /// Absolute workspace directory for new worktrees. Omit to reset.
+        path: Option<String>,
+    },
+    /// Re-detect a repo's language, package manager and test framework from
+    /// its checkout
+    Detect {
+        /// Repo slug
+        slug: String,
+    },
+    /// Write CLAUDE.md/AGENTS.md agent instructions assembled from the repo's
+    /// .conductor/config.toml (test command, setup, branch conventions,
+    /// guardrails). Only conductor's marked section is replaced.
+    #[command(
+        after_help = "Examples:\n  conductor repo gen-instructions my-repo\n  conductor repo gen-instructions my-repo --file AGENTS.md --file CLAUDE.md\n  conductor repo gen-instructions my-repo --print\n\nSet `instructions_files = [\"CLAUDE.md\"]` under [project] to refresh them in every new worktree."
+    )]
+    GenInstructions {
+        /// Repo slug
+        slug: String,
+        /// File to write: CLAUDE.md or AGENTS.md (repeatable; default: the
+        /// [project] instructions_files, else CLAUDE.md)
+        #[arg(long = "file")]
+        files: Vec<InstructionsFile>,
+        /// Print the generated section instead of writing files
+        #[arg(long)]
+        print: bool,
+    },
     /// Manage issue sources for a repository
     Sources {
         #[command(subcommand)]
@@ -399,6 +804,19 @@ pub enum RepoCommands {
         #[arg(long, default_value = "true")]
         allow: bool,
     },
+    /// Show or change who sees a repo in a shared web instance. Without
+    /// flags, prints the current visibility.
+    Visibility {
+        /// Repo slug
+        slug: String,
+        /// Make the repo, and its worktrees, tickets and agent runs, visible
+        /// only to this API token (see `conductor token list`)
+        #[arg(long, value_name = "TOKEN_ID", conflicts_with = "shared")]
+        private_to: Option<String>,
+        /// Make the repo visible to every web user again
+        #[arg(long)]
+        shared: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -407,10 +825,11 @@ pub enum SourceCommands {
     Add {
         /// Repo slug
         slug: String,
-        /// Source type (github or jira)
+        /// Source type (github, gitlab, jira, linear, bitbucket, asana or webhook)
         #[arg(long = "type")]
         source_type: String,
-        /// JSON config (auto-inferred for github from remote URL if omitted)
+        /// JSON config (auto-inferred for github, gitlab and bitbucket from remote URL if omitted;
+        /// set `"sync_prs": true` to also sync open pull requests; not needed for webhook)
         #[arg(long)]
         config: Option<String>,
     },
@@ -423,7 +842,7 @@ pub enum SourceCommands {
     Remove {
         /// Repo slug
         slug: String,
-        /// Source type to remove (github or jira)
+        /// Source type to remove (github, gitlab, jira, linear, bitbucket, asana or webhook)
         #[arg(long = "type")]
         source_type: String,
     },
@@ -433,7 +852,7 @@ pub enum SourceCommands {
 pub enum WorktreeCommands {
     /// Create a new worktree
     #[command(
-        after_help = "Examples:\n  conductor worktree create my-repo --ticket PROJ-42\n  conductor worktree create my-repo --from main\n  conductor worktree create my-repo --ticket PROJ-42 --auto-agent\n  conductor worktree create my-repo pr-42-fix --from-pr 42"
+        after_help = "Examples:\n  conductor worktree create my-repo --ticket PROJ-42\n  conductor worktree create my-repo --from main\n  conductor worktree create my-repo --ticket PROJ-42 --auto-agent\n  conductor worktree create my-repo pr-42-fix --from-pr 42\n  conductor worktree create my-repo api-client --stack-on feat-api\n  conductor worktree create my-repo fix-typo --offline"
     )]
     Create {
         /// Repo slug
@@ -441,11 +860,15 @@ pub enum WorktreeCommands {
         /// Worktree name (e.g., smart-playlists, fix-scan-crash)
         name: String,
         /// Base branch
-        #[arg(long, short, conflicts_with_all = &["from_pr"])]
+        #[arg(long, short, conflicts_with_all = &["from_pr", "stack_on"])]
         from: Option<String>,
         /// Checkout an existing PR branch by PR number
-        #[arg(long, conflicts_with_all = &["from"])]
+        #[arg(long, conflicts_with_all = &["from", "stack_on"])]
         from_pr: Option<u32>,
+        /// Stack on another worktree (slug or branch): branch from its branch
+        /// and track it as the parent
+        #[arg(long)]
+        stack_on: Option<String>,
         /// Link to a ticket ID
         #[arg(long)]
         ticket: Option<String>,
@@ -455,18 +878,105 @@ pub enum WorktreeCommands {
         /// Proceed even if the base branch has uncommitted changes
         #[arg(long)]
         force: bool,
+        /// Don't fetch from origin; branch from the last-fetched local state
+        #[arg(long, conflicts_with = "from_pr")]
+        offline: bool,
+    },
+    /// Create a worktree for a ticket, named after its labels and title
+    #[command(
+        after_help = "Examples:\n  conductor worktree create-from-ticket my-repo 42\n  conductor worktree create-from-ticket my-repo PROJ-42 --agent\n  conductor worktree create-from-ticket my-repo 42 --name fix-42-login"
+    )]
+    CreateFromTicket {
+        /// Repo slug
+        repo: String,
+        /// Ticket ID, issue number or key
+        ticket: String,
+        /// Worktree name (defaults to one derived from the ticket, e.g. feat-42-add-login)
+        #[arg(long)]
+        name: Option<String>,
+        /// Base branch (defaults to the ticket's resolved base)
+        #[arg(long, short)]
+        from: Option<String>,
+        /// Start an agent on the ticket once the worktree exists
+        #[arg(long)]
+        agent: bool,
+        /// Proceed even if the base branch has uncommitted changes
+        #[arg(long)]
+        force: bool,
+        /// Don't fetch from origin; branch from the last-fetched local state
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Create a worktree from a GitHub issue/PR, GitLab issue or Jira issue URL
+    #[command(
+        after_help = "The repo is picked by matching its remote (or, for Jira, its issue source)\nagainst the URL. Unsynced issues are fetched first; pull request URLs check\nout the PR's branch.\n\nExamples:\n  conductor worktree from-url https://github.com/acme/widget/issues/42\n  conductor worktree from-url https://github.com/acme/widget/pull/57\n  conductor worktree from-url https://acme.atlassian.net/browse/PROJ-12 --agent"
+    )]
+    FromUrl {
+        /// Issue or pull request URL
+        url: String,
+        /// Worktree name (defaults to one derived from the ticket, or pr-<n>)
+        #[arg(long)]
+        name: Option<String>,
+        /// Base branch (defaults to the ticket's resolved base; not for PR URLs)
+        #[arg(long, short)]
+        from: Option<String>,
+        /// Start an agent on the ticket once the worktree exists
+        #[arg(long)]
+        agent: bool,
+        /// Proceed even if the base branch has uncommitted changes
+        #[arg(long)]
+        force: bool,
+        /// Don't fetch from origin; branch from the last-fetched local state
+        #[arg(long)]
+        offline: bool,
     },
     /// List worktrees
+    #[command(
+        after_help = "Examples:\n  conductor worktree list my-repo --status active,parked\n  conductor worktree list --ticket 42\n  conductor worktree list --stale --sort activity\n  conductor worktree list --sort cost --format tsv"
+    )]
     List {
         /// Filter by repo slug
         repo: Option<String>,
+        /// Only these statuses, comma-separated (e.g. active,parked)
+        #[arg(long)]
+        status: Option<String>,
+        /// Only worktrees linked to this ticket (ID, issue number or key)
+        #[arg(long)]
+        ticket: Option<String>,
+        /// Only live worktrees with no agent activity for DAYS (default 7)
+        #[arg(long, value_name = "DAYS", num_args = 0..=1, default_missing_value = "7")]
+        stale: Option<u32>,
+        /// Sort by: created (default), slug, status, activity or cost
+        #[arg(long, default_value = "created")]
+        sort: String,
+        /// Output format: "table" (default), "json" or "tsv"
+        #[arg(long, default_value = "table")]
+        format: String,
     },
     /// Delete a worktree (soft-delete: marks as merged or abandoned)
+    #[command(
+        after_help = "Examples:\n  conductor worktree delete my-repo fix-typo\n  conductor worktree delete my-repo spike-cache --reason experiment\n  conductor worktree delete --merged            # every worktree whose work has landed\n  conductor worktree delete --merged my-repo"
+    )]
     Delete {
-        /// Repo slug
-        repo: String,
+        /// Repo slug (with --merged: limit to this repo)
+        #[arg(required_unless_present = "merged")]
+        repo: Option<String>,
         /// Worktree slug
-        name: String,
+        #[arg(required_unless_present = "merged", conflicts_with = "merged")]
+        name: Option<String>,
+        /// Why unmerged work is being dropped: superseded, wontfix, blocked or
+        /// experiment. Ignored when the branch is merged.
+        #[arg(long, conflicts_with = "merged")]
+        reason: Option<String>,
+        /// Delete every live worktree whose branch is merged or whose ticket
+        /// is closed, skipping ones with uncommitted changes
+        #[arg(long)]
+        merged: bool,
+    },
+    /// Count finished worktrees by outcome, with abandon reasons
+    Stats {
+        /// Repo slug (all repos if omitted)
+        repo: Option<String>,
     },
     /// Permanently remove completed worktree records
     Purge {
@@ -475,13 +985,58 @@ pub enum WorktreeCommands {
         /// Specific worktree slug (purges all completed if omitted)
         name: Option<String>,
     },
-    /// Push worktree branch to origin
-    Push {
+    /// Check live worktrees for deleted directories and list the missing ones
+    Missing {
+        /// Repo slug (all repos if omitted)
+        repo: Option<String>,
+    },
+    /// Recreate a missing worktree directory from its branch
+    #[command(after_help = "Examples:\n  conductor worktree restore my-repo fix-typo")]
+    Restore {
         /// Repo slug
         repo: String,
         /// Worktree slug
         name: String,
     },
+    /// Commit the worktree's changes on its branch
+    #[command(
+        after_help = "Examples:\n  conductor worktree commit my-repo fix-typo --all -m \"Fix typo in README\"\n  conductor worktree commit my-repo fix-typo --all --from-agent"
+    )]
+    Commit {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        name: String,
+        /// Commit message
+        #[arg(short, long, required_unless_present = "from_agent")]
+        message: Option<String>,
+        /// Title the commit after the linked ticket and use the latest agent
+        /// run's result as its body
+        #[arg(long, conflicts_with = "message")]
+        from_agent: bool,
+        /// Stage every change first, untracked files included, instead of
+        /// committing only what is already staged
+        #[arg(short, long)]
+        all: bool,
+    },
+    /// Push worktree branch to origin
+    #[command(
+        after_help = "Examples:\n  conductor worktree push my-repo fix-typo\n  conductor worktree push --all            # every worktree with unpushed commits\n  conductor worktree push --all my-repo"
+    )]
+    Push {
+        /// Repo slug (with --all: limit to this repo)
+        #[arg(required_unless_present = "all")]
+        repo: Option<String>,
+        /// Worktree slug
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Skip the repo's `[project] pre_push` checks
+        #[arg(long)]
+        no_verify: bool,
+        /// Push every live worktree that has commits origin lacks
+        #[arg(long)]
+        all: bool,
+    },
     /// Create a pull request for the worktree branch
     Pr {
         /// Repo slug
@@ -491,6 +1046,18 @@ pub enum WorktreeCommands {
         /// Create as draft PR
         #[arg(long)]
         draft: bool,
+        /// Title the PR after the linked ticket and use the latest agent
+        /// run's result as its body (with `Closes #N` for GitHub issues and a
+        /// checklist of TODOs the branch added) instead of the commit messages
+        #[arg(long)]
+        from_agent: bool,
+    },
+    /// Scan the worktree branch for TODO and FIXME comments it added
+    Todos {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        name: String,
     },
     /// Set (or clear) the per-worktree default model for agent runs
     SetModel {
@@ -517,6 +1084,46 @@ pub enum WorktreeCommands {
         /// Rebase the worktree branch onto the new base (default: reject if not ancestor)
         #[arg(long)]
         rebase: bool,
+        /// Leave a conflicting rebase in progress for `worktree conflicts` instead of aborting it
+        #[arg(long, requires = "rebase")]
+        keep_conflicts: bool,
+    },
+    /// Rebase a stacked worktree onto its parent (or onto the parent's base once the parent is merged)
+    Restack {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        name: String,
+        /// Leave a conflicting rebase in progress for `worktree conflicts` instead of aborting it
+        #[arg(long)]
+        keep_conflicts: bool,
+    },
+    /// Fetch origin and bring a worktree branch up to date with its base branch.
+    /// Conflicts abort the rebase or merge and are listed; nothing is changed.
+    Sync {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        name: String,
+        /// rebase (default) or merge
+        #[arg(long, default_value = "rebase")]
+        strategy: String,
+    },
+    /// Resolve a rebase or merge that stopped on conflicts, by hand or with an agent
+    #[command(
+        after_help = "Actions:\n  show      list the conflicting files and the resolution's status (default)\n  agent     run a conflict resolution agent on the conflicting hunks, then verify\n  verify    run the project's test command on the staged resolution\n  continue  continue the rebase; refused until verify passed on what is staged\n  abort     abort the rebase and restore the branch\n\nExamples:\n  conductor worktree restack my-repo feat-child --keep-conflicts\n  conductor worktree conflicts my-repo feat-child agent\n  conductor worktree conflicts my-repo feat-child continue"
+    )]
+    Conflicts {
+        /// Repo slug
+        repo: String,
+        /// Worktree slug
+        name: String,
+        /// What to do
+        #[arg(value_enum, default_value = "show")]
+        action: ConflictAction,
+        /// Start the agent even if the worktree has spent its `[budget]`
+        #[arg(long)]
+        over_budget: bool,
     },
     /// Register an existing git worktree in conductor without recreating it
     #[command(
@@ -565,18 +1172,59 @@ pub enum TicketCommands {
     List {
         /// Filter by repo slug
         repo: Option<String>,
+        /// Only show tickets in the current sprint / milestone
+        #[arg(long)]
+        current_sprint: bool,
+        /// Only show tickets assigned to or mentioning your `[identity]`
+        #[arg(long)]
+        mine: bool,
     },
     /// Get a single ticket by ID (ULID or source_id)
     Get {
         /// Ticket ID — internal ULID or source_id (falls back to source_id search)
         id: String,
-        /// Output as JSON
-        #[arg(long)]
-        json: bool,
-        /// Output format: "text" (default) or "json"
+        /// Output format: "text" (default) or "json" (same as --json)
         #[arg(long, default_value = "text")]
         format: String,
     },
+    /// Show a ticket in full: body, labels, linked worktrees and agent totals
+    #[command(
+        after_help = "The ticket can be given as its ID, <source>:<id> (github:42, jira:PROJ-12),\nor a bare source id (#42, PROJ-12) when that is unambiguous."
+    )]
+    Show {
+        /// Ticket ID, <source>:<source_id>, or source id
+        id: String,
+        /// Only look in this repo (repo slug)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Comment on a ticket in its upstream tracker (GitHub or Jira)
+    #[command(
+        after_help = "Examples:\n  conductor tickets comment github:42 \"Fixed in #57\"\n  conductor tickets comment PROJ-12 \"Deployed to staging\" --repo widget"
+    )]
+    Comment {
+        /// Ticket ID, <source>:<source_id>, or source id
+        id: String,
+        /// Comment text (Markdown for GitHub)
+        body: String,
+        /// Only look in this repo (repo slug)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Close a ticket in its upstream tracker (GitHub or Jira), then locally
+    #[command(
+        after_help = "Jira issues move to the status mapped to \"closed\" in the source's \"statuses\" config\n(default \"Done\").\n\nExamples:\n  conductor tickets close github:42 --comment \"Fixed in #57\"\n  conductor tickets close PROJ-12"
+    )]
+    Close {
+        /// Ticket ID, <source>:<source_id>, or source id
+        id: String,
+        /// Only look in this repo (repo slug)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Post this comment before closing
+        #[arg(long)]
+        comment: Option<String>,
+    },
     /// Link a ticket to a worktree
     Link {
         /// Ticket source ID (e.g., GitHub issue number)
@@ -643,6 +1291,26 @@ pub enum TicketCommands {
         #[arg(long)]
         parent: Option<String>,
     },
+    /// Import tickets from a CSV or JSON file, e.g. a spreadsheet export
+    Import {
+        /// Path to a .csv file or a .json array of objects
+        file: String,
+        /// Repo slug
+        #[arg(long)]
+        repo: String,
+        /// Source type for the imported tickets; "manual" tickets never auto-close
+        #[arg(long, default_value = "manual")]
+        source: String,
+        /// File format: "csv" or "json" (inferred from the extension if omitted)
+        #[arg(long)]
+        format: Option<String>,
+        /// Map a ticket field to a column, e.g. `--map "title=Task Name"` (repeatable)
+        #[arg(long = "map", value_name = "FIELD=COLUMN")]
+        columns: Vec<String>,
+        /// Parse the file and list the tickets without importing them
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Update a ticket's state, workflow, or agent_map
     Update {
         /// Ticket ID (ULID from `conductor tickets list`)
@@ -657,4 +1325,28 @@ pub enum TicketCommands {
         #[arg(long)]
         agent_map: Option<String>,
     },
+    /// Ask a cheap agent to propose labels, estimates, duplicates and a
+    /// workflow for tickets not groomed yet (within `[grooming]` budget)
+    Groom {
+        /// Repo slug (all repos if omitted)
+        repo: Option<String>,
+    },
+    /// List grooming proposals
+    Proposals {
+        /// Filter by repo slug
+        repo: Option<String>,
+        /// Include accepted and dismissed proposals
+        #[arg(long)]
+        all: bool,
+    },
+    /// Apply a pending grooming proposal to its ticket
+    AcceptProposal {
+        /// Proposal ID (from `conductor tickets proposals`)
+        id: String,
+    },
+    /// Dismiss a pending grooming proposal without changing the ticket
+    DismissProposal {
+        /// Proposal ID (from `conductor tickets proposals`)
+        id: String,
+    },
 }
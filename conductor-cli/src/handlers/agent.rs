@@ -1,22 +1,34 @@
 use std::io::{BufRead, Write};
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 
 use anyhow::Result;
 use rusqlite::Connection;
 
+use conductor_core::agent::backend::{resolve_backend, BackendRequest, DEFAULT_BACKEND};
+use conductor_core::agent::sandbox::{self, Sandbox};
+use conductor_core::agent::supervisor::AgentSupervisor;
 use conductor_core::agent::{
-    build_startup_context, parse_events_from_line, AgentManager, PlanStep,
+    build_session_recap, build_startup_context, parse_events_from_line, AgentChain, AgentChains,
+    AgentManager, AgentQueue, ChainLink, ChainStep, PlanStep, QueuedAgentRun, RunEnvironment,
+    StopAllSummary,
 };
-use conductor_core::config::{load_config, Config};
+use conductor_core::config::{load_config, Config, SandboxMode};
 use conductor_core::github;
 use conductor_core::github_app;
+use conductor_core::redact::{RedactionReport, Redactor};
 use conductor_core::repo::RepoManager;
 use conductor_core::worktree::WorktreeManager;
 
-use crate::commands::{AgentCommands, CONDUCTOR_RUN_ID_ENV};
-use crate::helpers::{generate_plan, read_and_maybe_cleanup_prompt_file};
+use crate::commands::{AgentCommands, AgentQueueCommands, CONDUCTOR_RUN_ID_ENV};
+use crate::helpers::{generate_plan, print_json, read_and_maybe_cleanup_prompt_file};
 
-pub fn handle_agent(command: AgentCommands, conn: &Connection, config: &Config) -> Result<()> {
+pub fn handle_agent(
+    command: AgentCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
     // Reap orphaned runs before handling any agent command.
     {
         let agent_mgr = AgentManager::new(conn);
@@ -45,6 +57,8 @@ pub fn handle_agent(command: AgentCommands, conn: &Connection, config: &Config)
             bot_name,
             permission_mode,
             plugin_dirs,
+            backend,
+            sandbox,
         } => {
             let resolved_prompt = match (prompt, prompt_file) {
                 (Some(p), _) => p,
@@ -64,7 +78,7 @@ pub fn handle_agent(command: AgentCommands, conn: &Connection, config: &Config)
                 }
                 None => None,
             };
-            run_agent(
+            let result = run_agent(
                 conn,
                 &run_id,
                 &worktree_path,
@@ -74,7 +88,15 @@ pub fn handle_agent(command: AgentCommands, conn: &Connection, config: &Config)
                 bot_name.as_deref(),
                 perm_mode.as_ref(),
                 &plugin_dirs,
-            )?;
+                backend.as_deref(),
+                sandbox,
+            );
+            // Queue whatever the run's chain declares for its outcome right
+            // away rather than waiting for the next daemon or server sweep.
+            if let Err(e) = AgentChains::new(conn).evaluate(&run_id) {
+                eprintln!("Warning: evaluating the agent chain failed: {e}");
+            }
+            result?;
         }
         AgentCommands::CreateIssue {
             title,
@@ -138,14 +160,298 @@ pub fn handle_agent(command: AgentCommands, conn: &Connection, config: &Config)
 
             println!("Created issue #{source_id}: {url}");
         }
+        AgentCommands::Queue { command } => handle_queue(command, conn, config, json)?,
+        AgentCommands::Stop { all: true, yes, .. } => {
+            let mgr = AgentManager::new(conn);
+            let active = mgr.list_active_runs()?.len();
+            if active == 0 {
+                println!("No running agents.");
+                return Ok(());
+            }
+            if !yes {
+                eprint!("Stop {active} running agent(s)? [y/N] ");
+                let mut input = String::new();
+                std::io::stdin().lock().read_line(&mut input)?;
+                if !input.trim().eq_ignore_ascii_case("y") {
+                    eprintln!("Aborted.");
+                    return Ok(());
+                }
+            }
+            print_stop_summary(&mgr.stop_all()?);
+        }
+        AgentCommands::Stop { run_id, .. } => {
+            let Some(run_id) = run_id else {
+                anyhow::bail!("a run ID is required unless --all is given");
+            };
+            let mgr = AgentManager::new(conn);
+            let run = mgr
+                .get_run(&run_id)?
+                .ok_or_else(|| anyhow::anyhow!("agent run {run_id} not found"))?;
+            if !run.is_active() {
+                anyhow::bail!("agent run {run_id} is not running ({})", run.status);
+            }
+            AgentSupervisor::new(conn).stop(&run_id)?;
+            println!("Agent run {run_id} cancelled");
+        }
+        AgentCommands::Chain { run_id } => {
+            let links = AgentChains::new(conn).chain_for_run(&run_id)?;
+            if json {
+                return print_json(&links);
+            }
+            if links.is_empty() {
+                println!("Agent run {run_id} is not part of a chain.");
+            }
+            for link in &links {
+                print_chain_link(link);
+            }
+        }
+        AgentCommands::Start {
+            repo,
+            worktree,
+            prompt,
+            model,
+            attach,
+        } => {
+            let run_id = start_detached_agent(conn, config, &repo, &worktree, &prompt, model)?;
+            println!("Started agent run {run_id}");
+            if attach {
+                follow_run(conn, &run_id)?;
+            } else {
+                println!("Follow it with `conductor agent attach {run_id}`.");
+            }
+        }
+        AgentCommands::Attach { run_id } => {
+            if !conductor_core::tmux::is_available() {
+                return follow_run(conn, &run_id);
+            }
+            let (session, window) = conductor_core::tmux::open_run_window(conn, config, &run_id)?;
+            conductor_core::tmux::select_window(&window)?;
+            super::tmux::attach(&session)?;
+        }
+    }
+    Ok(())
+}
+
+/// Create a run on `repo`/`worktree` and hand it to the supervisor, which
+/// starts `conductor agent run` detached from this process.
+fn start_detached_agent(
+    conn: &Connection,
+    config: &Config,
+    repo: &str,
+    worktree: &str,
+    prompt: &str,
+    model: Option<String>,
+) -> Result<String> {
+    let repo = RepoManager::new(conn, config).get_by_slug(repo)?;
+    let wt = WorktreeManager::new(conn, config).get_by_slug(&repo.id, worktree)?;
+    let mgr = AgentManager::new(conn);
+    if let Some(active) = mgr
+        .latest_runs_by_worktree_for_repo(&repo.id)?
+        .get(&wt.id)
+        .filter(|r| r.is_active())
+    {
+        anyhow::bail!(
+            "{} already has an active agent run ({}); stop it first",
+            wt.slug,
+            active.id
+        );
+    }
+    if let Some(exceeded) = mgr.budget_exceeded(&config.budget, Some(&wt.id), None)? {
+        anyhow::bail!("{exceeded}");
+    }
+    let model = conductor_core::models::resolve_model(
+        model.as_deref().or(wt.model.as_deref()),
+        repo.model.as_deref(),
+        config.general.model.as_deref(),
+    );
+    let run = mgr.create_run(Some(&wt.id), prompt, model.as_deref())?;
+    let params = conductor_core::agent_runtime::SpawnHeadlessParams {
+        run_id: &run.id,
+        working_dir: &wt.path,
+        prompt,
+        resume_session_id: None,
+        model: model.as_deref(),
+        extra_cli_args: &[],
+        permission_mode: None,
+        plugin_dirs: &[],
+    };
+    AgentSupervisor::new(conn).start(&params, &Default::default())?;
+    Ok(run.id)
+}
+
+/// Print a run's output as it is written, until it finishes.
+fn follow_run(conn: &Connection, run_id: &str) -> Result<()> {
+    let run = AgentSupervisor::new(conn).follow(
+        run_id,
+        std::time::Duration::from_millis(500),
+        |event| println!("[{}] {}", event.kind, event.summary),
+    )?;
+    println!("Agent run {run_id}: {}", run.status);
+    Ok(())
+}
+
+/// Report of `conductor agent stop --all`: one line per stopped run, then
+/// totals and any queued runs that will still start.
+fn print_stop_summary(summary: &StopAllSummary) {
+    for run in &summary.stopped {
+        let target = match (&run.repo_slug, &run.worktree_slug) {
+            (Some(repo), Some(wt)) => format!("{repo}/{wt}"),
+            (Some(repo), None) => repo.clone(),
+            _ => run.worktree_id.clone().unwrap_or_else(|| "-".into()),
+        };
+        let prompt = run.prompt.lines().next().unwrap_or_default();
+        println!(
+            "✓ {target}  {}  {} turn(s)  \"{}\"",
+            run.run_id,
+            run.turns,
+            conductor_core::text_util::truncate_str(prompt, 60)
+        );
+        if let Some(log) = &run.log_file {
+            println!("    log: {log}");
+        }
+    }
+    let cost: f64 = summary.stopped.iter().filter_map(|r| r.cost_usd).sum();
+    println!(
+        "Stopped {} agent run(s){}.",
+        summary.stopped.len(),
+        if cost > 0.0 {
+            format!(", ${cost:.2} spent so far")
+        } else {
+            String::new()
+        }
+    );
+    if summary.queued > 0 {
+        println!(
+            "{} queued run(s) will still start; see `conductor agent queue list`.",
+            summary.queued
+        );
+    }
+}
+
+/// One link of `conductor agent chain`, indented by its depth, followed by
+/// its outcome or the follow-ups still declared on it.
+fn print_chain_link(link: &ChainLink) {
+    let indent = "  ".repeat(link.depth as usize);
+    let run = link.run_id.as_deref().unwrap_or("(queued)");
+    let status = link
+        .run_status
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "queued".into());
+    let trigger = link
+        .trigger
+        .map(|t| format!("on {t}: "))
+        .unwrap_or_default();
+    let prompt = link.prompt.lines().next().unwrap_or_default();
+    println!("{indent}{trigger}{run}  {status}  {prompt}");
+    if let Some(outcome) = link.outcome {
+        println!("{indent}  -> {outcome}");
+        return;
+    }
+    for (label, step) in [("success", &link.on_success), ("failure", &link.on_failure)] {
+        match step {
+            Some(ChainStep::Run { prompt, .. }) => {
+                let prompt = prompt.lines().next().unwrap_or_default();
+                println!("{indent}  if {label}: run \"{prompt}\"");
+            }
+            Some(ChainStep::Notify) => println!("{indent}  if {label}: notify"),
+            None => {}
+        }
+    }
+}
+
+/// The chain declared by `conductor agent queue add` flags, if any.
+fn chain_from_flags(
+    on_success: Option<String>,
+    on_failure: Option<String>,
+    notify_on_failure: bool,
+) -> Option<AgentChain> {
+    let run = |prompt| {
+        Box::new(ChainStep::Run {
+            prompt,
+            then: AgentChain::default(),
+        })
+    };
+    let chain = AgentChain {
+        on_success: on_success.map(run),
+        on_failure: match on_failure {
+            Some(prompt) => Some(run(prompt)),
+            None if notify_on_failure => Some(Box::new(ChainStep::Notify)),
+            None => None,
+        },
+    };
+    (!chain.is_empty()).then_some(chain)
+}
+
+fn print_queued(q: &QueuedAgentRun) {
+    let prompt = q.prompt.lines().next().unwrap_or_default();
+    println!(
+        "{}  {:<10}  {}/{}  {prompt}",
+        q.id,
+        q.priority.to_string(),
+        q.repo_slug,
+        q.worktree_slug
+    );
+}
+
+fn handle_queue(
+    command: AgentQueueCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
+    let queue = AgentQueue::new(conn);
+    match command {
+        AgentQueueCommands::Add {
+            repo,
+            worktree,
+            prompt,
+            priority,
+            on_success,
+            on_failure,
+            notify_on_failure,
+        } => {
+            let repo = RepoManager::new(conn, config).get_by_slug(&repo)?;
+            let wt = WorktreeManager::new(conn, config).get_by_slug(&repo.id, &worktree)?;
+            let chain = chain_from_flags(on_success, on_failure, notify_on_failure);
+            if let Some(ref chain) = chain {
+                chain.validate()?;
+            }
+            let queued = queue.enqueue(&wt.id, &prompt, priority)?;
+            if let Some(chain) = chain {
+                AgentChains::new(conn).attach_queued(&queued.id, &chain)?;
+            }
+            println!("Queued:");
+            print_queued(&queued);
+        }
+        AgentQueueCommands::List => {
+            let items = queue.list()?;
+            if json {
+                return print_json(&items);
+            }
+            if items.is_empty() {
+                println!("No queued agent runs.");
+            }
+            for q in &items {
+                print_queued(q);
+            }
+        }
+        AgentQueueCommands::Priority { id, priority } => {
+            let queued = queue.set_priority(&id, priority)?;
+            print_queued(&queued);
+        }
+        AgentQueueCommands::Remove { id } => {
+            queue.remove(&id)?;
+            println!("Removed {id} from the queue");
+        }
     }
     Ok(())
 }
 
-/// Run a Claude agent for a worktree as a headless subprocess.
+/// Run an agent for a worktree as a headless subprocess.
 ///
-/// Uses `--output-format stream-json` to emit structured events. Claude's output is streamed
-/// and parsed for result metadata; a human-readable summary is printed to stderr.
+/// The agent CLI comes from `backend_name` (or the repo/global default). Its output is
+/// normalized to Claude's `stream-json` events, streamed and parsed for result metadata;
+/// a human-readable summary is printed to stderr.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_agent(
     conn: &rusqlite::Connection,
@@ -157,6 +463,8 @@ pub(crate) fn run_agent(
     bot_name: Option<&str>,
     permission_mode_override: Option<&conductor_core::config::AgentPermissionMode>,
     extra_plugin_dirs: &[String],
+    backend_name: Option<&str>,
+    sandbox_mode: Option<SandboxMode>,
 ) -> Result<()> {
     let mgr = AgentManager::new(conn);
 
@@ -177,6 +485,117 @@ pub(crate) fn run_agent(
             conductor_core::config::Config::default()
         }
     };
+    let repo_path = run
+        .worktree_id
+        .as_deref()
+        .and_then(|wt_id| WorktreeManager::new(conn, &config).get_by_id(wt_id).ok())
+        .map(|wt| wt.repo_id)
+        .or_else(|| run.repo_id.clone())
+        .and_then(|repo_id| RepoManager::new(conn, &config).get_by_id(&repo_id).ok())
+        .map(|repo| PathBuf::from(repo.local_path));
+    let mut backend = match resolve_backend(backend_name, repo_path.as_deref(), &config) {
+        Ok(b) => b,
+        Err(e) => {
+            let error_msg = e.to_string();
+            mgr.update_run_failed(run_id, &error_msg)?;
+            eprintln!("[conductor] {error_msg}");
+            return Ok(());
+        }
+    };
+    eprintln!("[conductor] Agent backend: {}", backend.name());
+
+    // Per-repo `[agent]` settings from `.conductor/config.toml`.
+    let repo_agent = match repo_path
+        .as_deref()
+        .map(conductor_core::config::RepoConfig::load)
+    {
+        Some(Ok(rc)) => rc.agent,
+        Some(Err(e)) => {
+            eprintln!("[conductor] Warning: ignoring repo [agent] settings: {e}");
+            Default::default()
+        }
+        None => Default::default(),
+    };
+    let problems = repo_agent.validate();
+    if !problems.is_empty() {
+        let error_msg = format!(
+            "invalid [agent] settings in .conductor/config.toml: {}",
+            problems.join("; ")
+        );
+        mgr.update_run_failed(run_id, &error_msg)?;
+        eprintln!("[conductor] {error_msg}");
+        return Ok(());
+    }
+    let disallowed = repo_agent.disallowed_extra_args(&config.agents.allowed_repo_extra_args);
+    if !disallowed.is_empty() {
+        let error_msg = format!(
+            "[agent] extra_args in .conductor/config.toml are not allowed: {}; \
+             list the flags in [agents] allowed_repo_extra_args of your config.toml to allow them",
+            disallowed.join(" ")
+        );
+        mgr.update_run_failed(run_id, &error_msg)?;
+        eprintln!("[conductor] {error_msg}");
+        return Ok(());
+    }
+    if !repo_agent.is_empty() {
+        eprintln!("[conductor] Repo agent settings: {repo_agent:?}");
+    }
+    let allowed_tools = repo_agent.permitted_tools(&config.agents.allowed_repo_tools);
+    if allowed_tools.len() < repo_agent.allowed_tools.len() {
+        eprintln!(
+            "[conductor] Warning: ignoring [agent] allowed_tools entries not listed in \
+             [agents] allowed_repo_tools of your config.toml"
+        );
+    }
+
+    let sandbox_mode =
+        sandbox::resolve_mode(sandbox_mode, repo_agent.sandbox, &config.agents.sandbox);
+    let sandbox = match Sandbox::prepare(
+        sandbox_mode,
+        &config.agents.sandbox,
+        Path::new(worktree_path),
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            let error_msg = format!("Could not prepare the {sandbox_mode} sandbox: {e}");
+            mgr.update_run_failed(run_id, &error_msg)?;
+            eprintln!("[conductor] {error_msg}");
+            return Ok(());
+        }
+    };
+    if let Some(desc) = sandbox.describe() {
+        eprintln!(
+            "[conductor] Sandbox: {desc} (container {})",
+            sandbox::container_name(run_id)
+        );
+    }
+
+    let mut environment = RunEnvironment::capture(
+        worktree_path,
+        backend.as_ref(),
+        model.or(run.model.as_deref()),
+    );
+    environment.sandbox = sandbox.describe();
+    eprintln!("[conductor] Environment: {}", environment.summary());
+    if let Err(e) = mgr.record_environment(run_id, &environment) {
+        eprintln!("[conductor] Warning: could not save run environment: {e}");
+    }
+
+    // Scrub credentials from the prompt before it is persisted or sent to the agent.
+    let redactor = Redactor::from_config(&config.redaction).unwrap_or_else(|e| {
+        eprintln!("[conductor] Warning: {e}; using built-in redaction patterns only");
+        Redactor::builtin()
+    });
+    let mut redaction_report = RedactionReport::default();
+    let (prompt, report) = redactor.redact(prompt);
+    if !report.is_empty() {
+        if let Err(e) = mgr.update_run_prompt(run_id, &prompt) {
+            eprintln!("[conductor] Warning: could not save redacted prompt: {e}");
+        }
+        redaction_report.merge(report);
+    }
+    let prompt = prompt.as_str();
+
     let effective_prompt = if config.general.inject_startup_context {
         let context = build_startup_context(
             conn,
@@ -186,15 +605,49 @@ pub(crate) fn run_agent(
             worktree_path,
         );
         eprintln!("[conductor] Injecting session context into prompt");
+        let (context, report) = redactor.redact(&context);
+        redaction_report.merge(report);
         format!("{context}\n\n---\n\n{prompt}")
     } else {
         prompt.to_string()
     };
 
-    // Phase 1: Plan generation (only for new runs, not resumes)
-    if resume_session_id.is_none() {
+    // Every resume replays the whole session history, so a session that has
+    // piled up enough turns is rotated: start fresh from a recap instead.
+    let (resume_session_id, effective_prompt) = match resume_session_id {
+        Some(sid) => {
+            let limit = i64::from(config.general.session_rotation_turns);
+            let turns = mgr.session_turns(sid).unwrap_or_else(|e| {
+                eprintln!("[conductor] Warning: could not count session turns: {e}");
+                0
+            });
+            if limit > 0 && turns >= limit {
+                eprintln!(
+                    "[conductor] Session {sid} has {turns} turns (limit {limit}); rotating to a fresh session"
+                );
+                if let Err(e) = mgr.mark_session_rotated(run_id, sid) {
+                    eprintln!("[conductor] Warning: could not record session rotation: {e}");
+                }
+                let (recap, report) = redactor.redact(&build_session_recap(conn, sid, turns));
+                redaction_report.merge(report);
+                (None, format!("{recap}\n\n---\n\n{effective_prompt}"))
+            } else {
+                (Some(sid), effective_prompt)
+            }
+        }
+        None => (None, effective_prompt),
+    };
+
+    // Phase 1: Plan generation (only for new runs, not resumes). Planning
+    // always goes through Claude, so other backends run without a plan.
+    if resume_session_id.is_none() && backend.name() != DEFAULT_BACKEND {
+        eprintln!(
+            "[conductor] Plan generation skipped ({} backend)",
+            backend.name()
+        );
+    } else if resume_session_id.is_none() {
         eprintln!("[conductor] Phase 1: Generating plan...");
-        match generate_plan(worktree_path, &effective_prompt, &config) {
+        match generate_plan(worktree_path, &effective_prompt, &config, &sandbox, run_id) {
             Some(steps) => {
                 eprintln!("[conductor] Plan ({} steps):", steps.len());
                 for (i, step) in steps.iter().enumerate() {
@@ -294,35 +747,47 @@ pub(crate) fn run_agent(
         // ── build command for this turn ───────────────────────────────────────
         // stdout: stream-json events (piped, parsed for result metadata)
         // stderr: verbose turn-by-turn output (inherited, visible in the terminal)
-        let mut cmd = Command::new("claude");
-        if let Some(ref feedback) = feedback_response_for_resume {
+        let effective_perm_mode = permission_mode_override.copied().unwrap_or_else(|| {
+            config
+                .general
+                .agent_permission_mode
+                .tightened_by(repo_agent.permission_mode)
+        });
+        let (turn_prompt, turn_resume) = match feedback_response_for_resume {
             // Feedback resume turn: deliver the human response as the next message
-            let sid = session_id_parsed
-                .as_deref()
-                .expect("session_id always captured before a feedback resume");
-            cmd.arg("-p").arg(feedback).arg("--resume").arg(sid);
-        } else {
-            cmd.arg("-p").arg(&effective_prompt);
-            if let Some(ref sid) = resume_session_id {
-                cmd.arg("--resume").arg(sid);
-            }
-        }
-        let effective_perm_mode =
-            permission_mode_override.unwrap_or(&config.general.agent_permission_mode);
-        cmd.arg("--output-format")
-            .arg("stream-json")
-            .arg("--verbose")
-            .arg(effective_perm_mode.claude_permission_flag());
-        if let Some(val) = effective_perm_mode.claude_permission_flag_value() {
-            cmd.arg(val);
-        }
-        if let Some(pattern) = effective_perm_mode.allowed_tools() {
-            cmd.arg("--allowedTools").arg(pattern);
-        }
-        cmd.env(CONDUCTOR_RUN_ID_ENV, run_id)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::inherit())
-            .current_dir(worktree_path);
+            Some(ref feedback) => (
+                feedback.as_str(),
+                Some(
+                    session_id_parsed
+                        .as_deref()
+                        .expect("session_id always captured before a feedback resume"),
+                ),
+            ),
+            None => (effective_prompt.as_str(), resume_session_id),
+        };
+        let request = BackendRequest {
+            prompt: turn_prompt,
+            model,
+            permission_mode: effective_perm_mode,
+            plugin_dirs: extra_plugin_dirs,
+            allowed_tools: &allowed_tools,
+            max_turns: repo_agent.max_turns,
+            extra_args: &repo_agent.extra_args,
+        };
+        let mut cmd = match turn_resume {
+            Some(sid) => match backend.resume_command(&request, sid) {
+                Some(cmd) => cmd,
+                None => {
+                    let error_msg =
+                        format!("The {} backend cannot resume sessions", backend.name());
+                    mgr.update_run_failed(run_id, &error_msg)?;
+                    eprintln!("[conductor] {error_msg}");
+                    return Ok(());
+                }
+            },
+            None => backend.spawn_command(&request),
+        };
+        cmd.env(CONDUCTOR_RUN_ID_ENV, run_id);
 
         // Pass CLAUDE_CONFIG_DIR when explicitly configured so agent runs use
         // the custom Claude config directory instead of the default ~/.claude.
@@ -370,14 +835,6 @@ pub(crate) fn run_agent(
             }
         }
 
-        if let Some(m) = model {
-            cmd.arg("--model").arg(m);
-        }
-
-        for dir in extra_plugin_dirs {
-            cmd.arg("--plugin-dir").arg(dir);
-        }
-
         // ── open log file (create on turn 0, append on feedback resume turns) ─
         let mut log_file = if feedback_response_for_resume.is_some() {
             std::fs::OpenOptions::new()
@@ -397,10 +854,18 @@ pub(crate) fn run_agent(
         };
 
         // ── spawn ─────────────────────────────────────────────────────────────
+        // Wrapped last so the environment set above reaches a sandboxed agent.
+        let mut cmd = sandbox.wrap(cmd, run_id, Path::new(worktree_path));
+        cmd.stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .current_dir(worktree_path);
         let mut child = match cmd.spawn() {
             Ok(child) => child,
             Err(e) => {
-                let error_msg = format!("Failed to spawn claude: {e}");
+                let error_msg = format!(
+                    "Failed to spawn {}: {e}",
+                    cmd.get_program().to_string_lossy()
+                );
                 mgr.update_run_failed(run_id, &error_msg)?;
                 eprintln!("[conductor] {}", error_msg);
                 return Ok(());
@@ -412,139 +877,150 @@ pub(crate) fn run_agent(
             let reader = std::io::BufReader::new(stdout);
             for line in reader.lines() {
                 let Ok(line) = line else { continue };
+                // Translate the backend's native output into stream-json lines.
+                for line in backend.parse_event(&line) {
+                    let (line, report) = redactor.redact(&line);
+                    redaction_report.merge(report);
 
-                // Write every line to the log file
-                if let Some(ref mut f) = log_file {
-                    let _ = writeln!(f, "{line}");
-                }
+                    // Write every line to the log file
+                    if let Some(ref mut f) = log_file {
+                        let _ = writeln!(f, "{line}");
+                    }
 
-                // Relay every line to our own stdout so the parent workflow
-                // executor (call.rs drain_stream_json) can process events in
-                // real-time without polling the log file.
-                {
-                    use std::io::Write;
-                    let stdout_handle = std::io::stdout();
-                    let mut out = stdout_handle.lock();
-                    let _ = writeln!(out, "{line}");
-                }
+                    // Relay every line to our own stdout so the parent workflow
+                    // executor (call.rs drain_stream_json) can process events in
+                    // real-time without polling the log file.
+                    {
+                        use std::io::Write;
+                        let stdout_handle = std::io::stdout();
+                        let mut out = stdout_handle.lock();
+                        let _ = writeln!(out, "{line}");
+                    }
 
-                let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) else {
-                    continue;
-                };
+                    let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) else {
+                        continue;
+                    };
 
-                // Display human-readable activity on stderr
-                print_event_summary(&event);
+                    // Display human-readable activity on stderr
+                    print_event_summary(&event);
 
-                // Capture session_id from init message and save immediately for resume
-                if let Some(sid) = event.get("session_id").and_then(|v| v.as_str()) {
-                    session_id_parsed = Some(sid.to_string());
-                    if let Err(e) = mgr.update_run_session_id(run_id, sid) {
-                        eprintln!("[conductor] Warning: could not save session_id: {e}");
+                    // Capture session_id from init message and save immediately for resume
+                    if let Some(sid) = event.get("session_id").and_then(|v| v.as_str()) {
+                        session_id_parsed = Some(sid.to_string());
+                        if let Err(e) = mgr.update_run_session_id(run_id, sid) {
+                            eprintln!("[conductor] Warning: could not save session_id: {e}");
+                        }
                     }
-                }
 
-                // Capture result from final message and eagerly update DB to
-                // narrow the race window if the process is killed before child.wait().
-                // Skip the eager update when feedback is pending — we must stay in
-                // waiting_for_feedback until the human responds.
-                if event.get("result").is_some() {
-                    let parsed = conductor_core::agent::parse_result_event(&event);
-                    result_text = parsed.result_text;
-                    cost_usd = parsed.cost_usd;
-                    num_turns = parsed.num_turns;
-                    duration_ms = parsed.duration_ms;
-                    is_error = parsed.is_error;
-                    input_tokens = parsed.input_tokens;
-                    output_tokens = parsed.output_tokens;
-                    cache_read_input_tokens = parsed.cache_read_input_tokens;
-                    cache_creation_input_tokens = parsed.cache_creation_input_tokens;
-
-                    // Only eagerly mark complete/failed when no feedback is pending.
-                    // If feedback was requested, the run must stay in waiting_for_feedback
-                    // until the human responds; resume_run_after_feedback() transitions it
-                    // back to running when the TUI submits the response.
-                    if pending_feedback_id.is_none() {
-                        if is_error {
-                            let error_msg = result_text
-                                .as_deref()
-                                .unwrap_or(conductor_core::agent::DEFAULT_AGENT_ERROR_MSG);
-                            if let Err(e) = mgr.update_run_failed_with_session(
+                    // Capture result from final message and eagerly update DB to
+                    // narrow the race window if the process is killed before child.wait().
+                    // Skip the eager update when feedback is pending — we must stay in
+                    // waiting_for_feedback until the human responds.
+                    if event.get("result").is_some() {
+                        let parsed = conductor_core::agent::parse_result_event(&event);
+                        result_text = parsed.result_text;
+                        cost_usd = parsed.cost_usd;
+                        num_turns = parsed.num_turns;
+                        duration_ms = parsed.duration_ms;
+                        is_error = parsed.is_error;
+                        input_tokens = parsed.input_tokens;
+                        output_tokens = parsed.output_tokens;
+                        cache_read_input_tokens = parsed.cache_read_input_tokens;
+                        cache_creation_input_tokens = parsed.cache_creation_input_tokens;
+
+                        // Only eagerly mark complete/failed when no feedback is pending.
+                        // If feedback was requested, the run must stay in waiting_for_feedback
+                        // until the human responds; resume_run_after_feedback() transitions it
+                        // back to running when the TUI submits the response.
+                        if pending_feedback_id.is_none() {
+                            if is_error {
+                                let error_msg = result_text
+                                    .as_deref()
+                                    .unwrap_or(conductor_core::agent::DEFAULT_AGENT_ERROR_MSG);
+                                if let Err(e) = mgr.update_run_failed_with_session(
+                                    run_id,
+                                    error_msg,
+                                    session_id_parsed.as_deref(),
+                                ) {
+                                    eprintln!("[conductor] Warning: eager DB update failed: {e}");
+                                }
+                            } else if let Err(e) = mgr.update_run_completed(
                                 run_id,
-                                error_msg,
                                 session_id_parsed.as_deref(),
+                                result_text.as_deref(),
+                                cost_usd,
+                                num_turns,
+                                duration_ms,
+                                input_tokens,
+                                output_tokens,
+                                cache_read_input_tokens,
+                                cache_creation_input_tokens,
                             ) {
                                 eprintln!("[conductor] Warning: eager DB update failed: {e}");
                             }
-                        } else if let Err(e) = mgr.update_run_completed(
-                            run_id,
-                            session_id_parsed.as_deref(),
-                            result_text.as_deref(),
-                            cost_usd,
-                            num_turns,
-                            duration_ms,
-                            input_tokens,
-                            output_tokens,
-                            cache_read_input_tokens,
-                            cache_creation_input_tokens,
-                        ) {
-                            eprintln!("[conductor] Warning: eager DB update failed: {e}");
+                            db_updated_eagerly = true;
                         }
-                        db_updated_eagerly = true;
                     }
-                }
 
-                // Persist parsed events to DB as spans
-                let parsed = parse_events_from_line(&line);
-                if !parsed.is_empty() {
-                    let now = chrono::Utc::now().to_rfc3339();
-                    // Close the previous span
-                    if let Some(ref prev_id) = last_event_id {
-                        let _ = mgr.update_event_ended_at(prev_id, &now);
-                    }
-                    // Create a new span for each parsed event; only the last one stays open
-                    for ev in &parsed {
-                        match mgr.create_event(
-                            run_id,
-                            &ev.kind,
-                            &ev.summary,
-                            &now,
-                            ev.metadata.as_deref(),
-                        ) {
-                            Ok(db_ev) => last_event_id = Some(db_ev.id),
-                            Err(e) => {
-                                eprintln!("[conductor] Warning: could not persist event: {e}")
-                            }
+                    // Persist parsed events to DB as spans
+                    let parsed = parse_events_from_line(&line);
+                    if !parsed.is_empty() {
+                        let now = chrono::Utc::now().to_rfc3339();
+                        // Close the previous span
+                        if let Some(ref prev_id) = last_event_id {
+                            let _ = mgr.update_event_ended_at(prev_id, &now);
                         }
+                        // Create a new span for each parsed event; only the last one stays open
+                        for ev in &parsed {
+                            match mgr.create_event(
+                                run_id,
+                                &ev.kind,
+                                &ev.summary,
+                                &now,
+                                ev.metadata.as_deref(),
+                            ) {
+                                Ok(db_ev) => last_event_id = Some(db_ev.id),
+                                Err(e) => {
+                                    eprintln!("[conductor] Warning: could not persist event: {e}")
+                                }
+                            }
 
-                        // Detect feedback request markers in agent text output.
-                        // Record the pending feedback id but do NOT block here — blocking
-                        // would fill the OS pipe buffer (64 KB) if Claude keeps writing,
-                        // causing a deadlock. We wait after child.wait() instead.
-                        if ev.kind == "text" {
-                            if let Some(parsed) =
-                                conductor_core::agent::parse_feedback_marker_structured(&ev.summary)
-                            {
-                                eprintln!(
-                                    "[conductor] Agent requesting feedback: {}",
-                                    parsed.prompt
-                                );
-                                let params = conductor_core::agent::FeedbackRequestParams {
-                                    feedback_type: parsed.feedback_type,
-                                    options: parsed.options,
-                                    timeout_secs: parsed.timeout_secs,
-                                };
-                                match mgr.request_feedback(run_id, &parsed.prompt, Some(&params)) {
-                                    Ok(fb) => {
-                                        eprintln!(
-                                            "[conductor] Feedback requested (id: {}), will wait after turn completes",
-                                            fb.id
-                                        );
-                                        pending_feedback_id = Some(fb.id);
-                                    }
-                                    Err(e) => {
-                                        eprintln!(
-                                            "[conductor] Warning: could not create feedback request: {e}"
-                                        );
+                            // Detect feedback request markers in agent text output.
+                            // Record the pending feedback id but do NOT block here — blocking
+                            // would fill the OS pipe buffer (64 KB) if Claude keeps writing,
+                            // causing a deadlock. We wait after child.wait() instead.
+                            if ev.kind == "text" {
+                                if let Some(parsed) =
+                                    conductor_core::agent::parse_feedback_marker_structured(
+                                        &ev.summary,
+                                    )
+                                {
+                                    eprintln!(
+                                        "[conductor] Agent requesting feedback: {}",
+                                        parsed.prompt
+                                    );
+                                    let params = conductor_core::agent::FeedbackRequestParams {
+                                        feedback_type: parsed.feedback_type,
+                                        options: parsed.options,
+                                        timeout_secs: parsed.timeout_secs,
+                                    };
+                                    match mgr.request_feedback(
+                                        run_id,
+                                        &parsed.prompt,
+                                        Some(&params),
+                                    ) {
+                                        Ok(fb) => {
+                                            eprintln!(
+                                                "[conductor] Feedback requested (id: {}), will wait after turn completes",
+                                                fb.id
+                                            );
+                                            pending_feedback_id = Some(fb.id);
+                                        }
+                                        Err(e) => {
+                                            eprintln!(
+                                                "[conductor] Warning: could not create feedback request: {e}"
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -579,6 +1055,8 @@ pub(crate) fn run_agent(
             if !is_error {
                 eprintln!("[conductor] Waiting for human feedback (id: {feedback_id})...");
                 if let Some(response) = wait_for_feedback_response(&mgr, feedback_id) {
+                    let (response, report) = redactor.redact(&response);
+                    redaction_report.merge(report);
                     eprintln!("[conductor] Feedback received, spawning resume turn...");
                     let fb_now = chrono::Utc::now().to_rfc3339();
                     let _ = mgr.create_event(
@@ -594,7 +1072,7 @@ pub(crate) fn run_agent(
                         continue; // spawn the resume turn
                     } else {
                         let msg =
-                            "Cannot deliver feedback: no session_id captured from Claude output";
+                            "Cannot deliver feedback: no session_id captured from agent output";
                         eprintln!("[conductor] Error: {msg}");
                         mgr.update_run_failed(run_id, msg)?;
                         return Ok(());
@@ -685,6 +1163,29 @@ pub(crate) fn run_agent(
                         "[conductor] in: {in_str}  out: {out_str}  cache_r: {cache_r_str}  cache_w: {cache_w_str}  turns: {turns}  duration: {dur:.1}s"
                     );
                 }
+                if let Some(wt_id) = run.worktree_id.as_deref() {
+                    match WorktreeManager::new(conn, &config).scan_todos_by_id(wt_id) {
+                        Ok(todos) if !todos.is_empty() => eprintln!(
+                            "[conductor] {} TODO/FIXME comment(s) added on this branch",
+                            todos.len()
+                        ),
+                        Ok(_) => {}
+                        Err(e) => {
+                            eprintln!(
+                                "[conductor] Warning: could not scan the branch for TODOs: {e}"
+                            )
+                        }
+                    }
+                }
+                match conductor_core::tickets::post_run_summary(conn, &config, run_id) {
+                    Ok(true) => eprintln!("[conductor] Posted the result to the linked ticket"),
+                    Ok(false) => {}
+                    Err(e) => {
+                        eprintln!(
+                            "[conductor] Warning: could not post the result to the ticket: {e}"
+                        )
+                    }
+                }
             }
             Ok(_) if is_error => {
                 let error_msg = result_text
@@ -723,6 +1224,21 @@ pub(crate) fn run_agent(
         break;
     } // end multi-turn loop
 
+    if !redaction_report.is_empty() {
+        eprintln!("[conductor] {redaction_report}");
+        let now = chrono::Utc::now().to_rfc3339();
+        let metadata = serde_json::to_string(&redaction_report).ok();
+        if let Err(e) = mgr.create_event(
+            run_id,
+            "redaction",
+            &redaction_report.to_string(),
+            &now,
+            metadata.as_deref(),
+        ) {
+            eprintln!("[conductor] Warning: could not persist redaction event: {e}");
+        }
+    }
+
     eprintln!(
         "[conductor] Agent log saved to {}",
         log_path.to_string_lossy()
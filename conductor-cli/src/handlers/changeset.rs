@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+
+use conductor_core::changeset::{ChangesetManager, ChangesetWithMembers};
+use conductor_core::config::Config;
+
+use crate::commands::ChangesetCommands;
+use crate::helpers::print_json;
+
+/// Split a `<repo>/<worktree>` argument.
+fn parse_member(spec: &str) -> Result<(String, String)> {
+    match spec.split_once('/') {
+        Some((repo, wt)) if !repo.is_empty() && !wt.is_empty() => {
+            Ok((repo.to_string(), wt.to_string()))
+        }
+        _ => Err(anyhow!(
+            "invalid worktree '{spec}': expected <repo>/<worktree>"
+        )),
+    }
+}
+
+fn print_changeset(cs: &ChangesetWithMembers) {
+    println!("{}  {}", cs.changeset.id, cs.changeset.title);
+    for m in &cs.members {
+        println!(
+            "  {}. {}/{}  [{}]  {}",
+            m.position,
+            m.repo_slug,
+            m.worktree_slug,
+            m.status,
+            m.pr_url.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+pub fn handle_changeset(
+    command: ChangesetCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
+    let mgr = ChangesetManager::new(conn, config);
+    match command {
+        ChangesetCommands::Create { title, worktrees } => {
+            let members = worktrees
+                .iter()
+                .map(|w| parse_member(w))
+                .collect::<Result<Vec<_>>>()?;
+            let cs = mgr.create(&title, &members)?;
+            println!("Created changeset:");
+            print_changeset(&cs);
+        }
+        ChangesetCommands::Add { id, worktree } => {
+            let (repo, wt) = parse_member(&worktree)?;
+            mgr.add(&id, &repo, &wt)?;
+            println!("Added {repo}/{wt} to changeset {id}");
+        }
+        ChangesetCommands::List => {
+            let changesets = mgr.list()?;
+            if json {
+                return print_json(&changesets);
+            }
+            if changesets.is_empty() {
+                println!("No changesets.");
+            }
+            for cs in changesets {
+                println!("{}  {}  ({})", cs.id, cs.title, cs.created_at);
+            }
+        }
+        ChangesetCommands::Show { id } => {
+            let cs = mgr.get(&id)?;
+            if json {
+                return print_json(&cs);
+            }
+            print_changeset(&cs);
+        }
+        ChangesetCommands::Pr { id } => {
+            let cs = mgr.open_prs(&id)?;
+            println!(
+                "Opened {} cross-linked PR(s) for \"{}\":",
+                cs.members.len(),
+                cs.changeset.title
+            );
+            for m in &cs.members {
+                println!(
+                    "  {}/{}  {}",
+                    m.repo_slug,
+                    m.worktree_slug,
+                    m.pr_url.as_deref().unwrap_or("-")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_member_splits_repo_and_worktree() {
+        assert_eq!(
+            parse_member("web/feat-x").unwrap(),
+            ("web".to_string(), "feat-x".to_string())
+        );
+        assert!(parse_member("feat-x").is_err());
+        assert!(parse_member("/feat-x").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! `conductor completions` prints the script clap_complete builds from the
+//! command definitions, plus a small shell wrapper that completes repo and
+//! worktree arguments from the database through the hidden
+//! `conductor __complete` subcommand.
+
+use std::io::Write;
+
+use anyhow::Result;
+use clap::{Arg, Command, CommandFactory};
+
+use conductor_core::config::{db_path, load_config};
+use conductor_core::db::open_database_readonly;
+use conductor_core::repo::RepoManager;
+use conductor_core::worktree::WorktreeManager;
+
+use crate::commands::{Cli, CompletionShell};
+
+/// The tail clap_complete ends its zsh script with. The wrapper replaces it so
+/// both autoloading and sourcing bind `conductor` to the dynamic function.
+const ZSH_STATIC_BINDING: &str = r#"if [ "$funcstack[1]" = "_conductor" ]; then
+    _conductor "$@"
+else
+    compdef _conductor conductor
+fi
+"#;
+
+const BASH_DYNAMIC: &str = r#"
+_conductor_dynamic() {
+    local slugs
+    slugs=$(conductor __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null)
+    if [[ -n "$slugs" ]]; then
+        COMPREPLY=($(compgen -W "$slugs" -- "${COMP_WORDS[COMP_CWORD]}"))
+        return 0
+    fi
+    _conductor "$@"
+}
+
+complete -F _conductor_dynamic -o bashdefault -o default conductor
+"#;
+
+const ZSH_DYNAMIC: &str = r#"_conductor_dynamic() {
+    local -a slugs
+    slugs=(${(f)"$(conductor __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)"})
+    if (( ${#slugs} )); then
+        compadd -a slugs
+    else
+        _conductor "$@"
+    fi
+}
+
+compdef _conductor_dynamic conductor
+if [ "$funcstack[1]" = "_conductor" ]; then
+    _conductor_dynamic "$@"
+fi
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+complete -c conductor -f -a '(conductor __complete -- (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)'
+"#;
+
+pub fn handle_completions(shell: CompletionShell) -> Result<()> {
+    let generator = match shell {
+        CompletionShell::Bash => clap_complete::Shell::Bash,
+        CompletionShell::Zsh => clap_complete::Shell::Zsh,
+        CompletionShell::Fish => clap_complete::Shell::Fish,
+    };
+    let mut buf = Vec::new();
+    clap_complete::generate(generator, &mut Cli::command(), "conductor", &mut buf);
+    let script = String::from_utf8(buf)?;
+    let script = match shell {
+        CompletionShell::Bash => script + BASH_DYNAMIC,
+        CompletionShell::Zsh => script.replace(ZSH_STATIC_BINDING, ZSH_DYNAMIC),
+        CompletionShell::Fish => script + FISH_DYNAMIC,
+    };
+    std::io::stdout().write_all(script.as_bytes())?;
+    Ok(())
+}
+
+/// What an argument names, when it is something stored in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slug {
+    Repo,
+    Worktree,
+}
+
+/// Print the repo or worktree slugs that fit the last of `words`, one per
+/// line. Prints nothing when that word is something else, so the shell falls
+/// back to its static completions.
+pub fn handle_complete(words: &[String]) -> Result<()> {
+    let Some((kind, repo)) = slug_kind(&Cli::command(), words) else {
+        return Ok(());
+    };
+    // Never create the database from a Tab press.
+    let path = db_path();
+    if !path.exists() {
+        return Ok(());
+    }
+    // Loading the config first installs any [database] encryption key.
+    let config = load_config()?;
+    let conn = open_database_readonly(&path)?;
+    let mut slugs: Vec<String> = match kind {
+        Slug::Repo => RepoManager::new(&conn, &config)
+            .list()?
+            .into_iter()
+            .map(|r| r.slug)
+            .collect(),
+        Slug::Worktree => WorktreeManager::new(&conn, &config)
+            .list(repo.as_deref(), true)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|wt| wt.slug)
+            .collect(),
+    };
+    slugs.sort();
+    slugs.dedup();
+    for slug in slugs {
+        println!("{slug}");
+    }
+    Ok(())
+}
+
+/// Walk `words` through the command tree and decide what the last word is,
+/// along with the repo named earlier on the line (worktrees are limited to
+/// it). Arguments are recognised by their help text ("Repo slug", "Worktree
+/// slug") or a `--repo`/`--worktree` flag, so new subcommands pick this up
+/// for free.
+fn slug_kind(root: &Command, words: &[String]) -> Option<(Slug, Option<String>)> {
+    let (current, before) = words.split_last()?;
+    if current.starts_with('-') {
+        return None;
+    }
+
+    let mut cmd = root;
+    let mut positionals = 0;
+    let mut repo = None;
+    // An option whose value is the next word.
+    let mut pending: Option<&Arg> = None;
+    for word in before {
+        if let Some(arg) = pending.take() {
+            if classify(arg) == Some(Slug::Repo) {
+                repo = Some(word.clone());
+            }
+            continue;
+        }
+        if let Some(flag) = word.strip_prefix("--") {
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (flag, None),
+            };
+            if let Some(arg) = cmd.get_arguments().find(|a| a.get_long() == Some(name)) {
+                match inline {
+                    Some(value) if classify(arg) == Some(Slug::Repo) => {
+                        repo = Some(value.to_string())
+                    }
+                    None if arg.get_action().takes_values() => pending = Some(arg),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        if let Some(short) = word.strip_prefix('-').filter(|s| s.len() == 1) {
+            let short = short.chars().next();
+            if let Some(arg) = cmd.get_arguments().find(|a| a.get_short() == short) {
+                if arg.get_action().takes_values() {
+                    pending = Some(arg);
+                }
+            }
+            continue;
+        }
+        if positionals == 0 {
+            if let Some(sub) = cmd.find_subcommand(word) {
+                cmd = sub;
+                continue;
+            }
+        }
+        if let Some(arg) = cmd.get_positionals().nth(positionals) {
+            if classify(arg) == Some(Slug::Repo) {
+                repo = Some(word.clone());
+            }
+        }
+        positionals += 1;
+    }
+
+    let arg = match pending {
+        Some(arg) => arg,
+        None => cmd.get_positionals().nth(positionals)?,
+    };
+    Some((classify(arg)?, repo))
+}
+
+fn classify(arg: &Arg) -> Option<Slug> {
+    let help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+    if help.starts_with("Repo slug") || arg.get_long() == Some("repo") {
+        Some(Slug::Repo)
+    } else if help.starts_with("Worktree slug") || arg.get_long() == Some("worktree") {
+        Some(Slug::Worktree)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Split like a shell would; a trailing space leaves an empty word to complete.
+    fn kind(line: &str) -> Option<(Slug, Option<String>)> {
+        let words: Vec<String> = line.split(' ').map(String::from).collect();
+        slug_kind(&Cli::command(), &words)
+    }
+
+    #[test]
+    fn completes_repo_then_worktree_positionals() {
+        assert_eq!(kind("worktree delete "), Some((Slug::Repo, None)));
+        assert_eq!(
+            kind("worktree delete my-repo fe"),
+            Some((Slug::Worktree, Some("my-repo".into())))
+        );
+    }
+
+    #[test]
+    fn completes_worktree_flag_value() {
+        assert_eq!(
+            kind("workflow run ticket-to-pr --worktree "),
+            Some((Slug::Worktree, None))
+        );
+    }
+
+    #[test]
+    fn ignores_other_arguments() {
+        // The workflow name comes before the repo slug.
+        assert_eq!(kind("workflow run "), None);
+        assert_eq!(kind("worktree delete my-repo --"), None);
+        assert_eq!(kind("wor"), None);
+    }
+}
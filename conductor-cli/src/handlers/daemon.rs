@@ -0,0 +1,355 @@
+//! `conductor daemon`: a long-running service for one database. It syncs
+//! tickets on `[general] sync_interval_minutes`, reaps orphaned runs and stale
+//! worktrees, starts queued agent runs as slots free up and applies the
+//! `[retention]` windows, then tells subscribers what changed over the socket
+//! from [`conductor_core::daemon`].
+
+use std::io::BufReader;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::Connection;
+
+use conductor_core::agent::{AgentChains, AgentManager, AgentQueue};
+use conductor_core::config::{db_path, load_config, Config};
+use conductor_core::daemon::{
+    self, read_line, write_line, DaemonClient, DaemonEvent, DaemonMessage, DaemonRequest,
+    DaemonStatus,
+};
+use conductor_core::db::open_database;
+use conductor_core::repo::RepoManager;
+use conductor_core::worktree::WorktreeManager;
+
+use crate::commands::{DaemonCommands, TicketCommands};
+use crate::handlers::tickets::handle_tickets;
+use crate::helpers::print_json;
+
+/// How often the maintenance loop wakes up. Queue dispatch runs every tick.
+const TICK: Duration = Duration::from_secs(10);
+const REAP_EVERY: Duration = Duration::from_secs(30);
+const PRUNE_EVERY: Duration = Duration::from_secs(3600);
+
+/// State shared by the socket, sync and maintenance threads.
+struct Shared {
+    status: Mutex<DaemonStatus>,
+    subscribers: Mutex<Vec<UnixStream>>,
+    sync_requested: AtomicBool,
+}
+
+impl Shared {
+    /// Send `event` to every subscriber, dropping the ones that hung up.
+    fn emit(&self, event: DaemonEvent) {
+        let message = DaemonMessage::Event(event);
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain_mut(|stream| write_line(stream, &message).is_ok());
+    }
+
+    fn update(&self, f: impl FnOnce(&mut DaemonStatus)) {
+        f(&mut self.status.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+/// `conductor daemon status` and `conductor daemon sync`, which talk to a
+/// running daemon and never open the database.
+pub fn handle_daemon_client(command: DaemonCommands, json: bool) -> Result<()> {
+    let mut client = DaemonClient::connect().map_err(|_| {
+        anyhow::anyhow!(
+            "no daemon is running for this database (start one with `conductor daemon`)"
+        )
+    })?;
+    match command {
+        DaemonCommands::Status => match client.request(&DaemonRequest::Status)? {
+            DaemonMessage::Status(s) if json => print_json(&s)?,
+            DaemonMessage::Status(s) => {
+                println!("Daemon running (pid {}) since {}", s.pid, s.started_at);
+                println!(
+                    "  last ticket sync:  {}",
+                    s.last_sync_at.as_deref().unwrap_or("-")
+                );
+                println!(
+                    "  last maintenance:  {}",
+                    s.last_maintenance_at.as_deref().unwrap_or("-")
+                );
+                println!("  queued runs started: {}", s.dispatched_runs);
+                println!("  subscribers: {}", s.subscribers);
+            }
+            other => anyhow::bail!("unexpected daemon reply: {other:?}"),
+        },
+        DaemonCommands::Sync => match client.request(&DaemonRequest::SyncTickets)? {
+            DaemonMessage::Ok => println!("Ticket sync requested."),
+            other => anyhow::bail!("unexpected daemon reply: {other:?}"),
+        },
+    }
+    Ok(())
+}
+
+/// Run the daemon in the foreground until it is killed.
+pub fn run_daemon(conn: Connection, config: &Config) -> Result<()> {
+    let socket = daemon::socket_path();
+    if daemon::is_running() {
+        anyhow::bail!("a daemon is already running on {}", socket.display());
+    }
+    // Nothing answered, so a leftover socket is from a daemon that died.
+    if socket.exists() {
+        std::fs::remove_file(&socket)
+            .with_context(|| format!("removing stale socket {}", socket.display()))?;
+    }
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("binding daemon socket {}", socket.display()))?;
+
+    let shared = Arc::new(Shared {
+        status: Mutex::new(DaemonStatus {
+            pid: std::process::id(),
+            started_at: Utc::now().to_rfc3339(),
+            last_sync_at: None,
+            last_maintenance_at: None,
+            dispatched_runs: 0,
+            subscribers: 0,
+        }),
+        subscribers: Mutex::new(Vec::new()),
+        // Sync once at startup, like the TUI does when it opens.
+        sync_requested: AtomicBool::new(true),
+    });
+    println!(
+        "conductor daemon (pid {}) serving {} on {}",
+        std::process::id(),
+        db_path().display(),
+        socket.display()
+    );
+
+    {
+        let shared = Arc::clone(&shared);
+        let interval = Duration::from_secs(u64::from(config.general.sync_interval_minutes) * 60);
+        thread::spawn(move || sync_loop(&shared, interval));
+    }
+    {
+        let shared = Arc::clone(&shared);
+        thread::spawn(move || maintenance_loop(conn, &shared));
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    if let Err(e) = serve_client(stream, &shared) {
+                        tracing::debug!("daemon client error: {e}");
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("daemon accept failed: {e}"),
+        }
+    }
+    Ok(())
+}
+
+fn serve_client(stream: UnixStream, shared: &Shared) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+    let request = match read_line::<DaemonRequest>(&mut reader) {
+        Ok(Some(request)) => request,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            let message = format!("bad request: {e}");
+            return Ok(write_line(&mut stream, &DaemonMessage::Error { message })?);
+        }
+    };
+    let reply = match request {
+        DaemonRequest::Ping => DaemonMessage::Pong,
+        DaemonRequest::Status => {
+            let mut status = shared
+                .status
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            status.subscribers = shared
+                .subscribers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .len();
+            DaemonMessage::Status(status)
+        }
+        DaemonRequest::SyncTickets => {
+            shared.sync_requested.store(true, Ordering::Relaxed);
+            DaemonMessage::Ok
+        }
+        DaemonRequest::Subscribe => {
+            // A subscriber that stops reading is dropped rather than stalling `emit`.
+            stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+            write_line(&mut stream, &DaemonMessage::Ok)?;
+            shared
+                .subscribers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(stream);
+            return Ok(());
+        }
+    };
+    write_line(&mut stream, &reply)?;
+    Ok(())
+}
+
+/// Sync all repos every `interval`, or sooner when a client asks.
+fn sync_loop(shared: &Shared, interval: Duration) {
+    let mut last = Instant::now();
+    loop {
+        thread::sleep(Duration::from_secs(1));
+        let requested = shared.sync_requested.swap(false, Ordering::Relaxed);
+        if !requested && last.elapsed() < interval {
+            continue;
+        }
+        last = Instant::now();
+        shared.emit(DaemonEvent::TicketSyncStarted);
+        let result = open_database(&db_path())
+            .map_err(anyhow::Error::from)
+            .and_then(|conn| {
+                let config = load_config()?;
+                handle_tickets(TicketCommands::Sync { repo: None }, &conn, &config, false)
+            });
+        if let Err(e) = &result {
+            tracing::warn!("daemon ticket sync failed: {e:#}");
+        }
+        shared.update(|s| s.last_sync_at = Some(Utc::now().to_rfc3339()));
+        shared.emit(DaemonEvent::TicketSyncFinished {
+            error: result.err().map(|e| format!("{e:#}")),
+        });
+    }
+}
+
+fn maintenance_loop(conn: Connection, shared: &Shared) {
+    let mut children: Vec<Child> = Vec::new();
+    let mut last_reap: Option<Instant> = None;
+    let mut last_prune: Option<Instant> = None;
+    loop {
+        // Config is reloaded each tick so edits apply without a restart.
+        let config = match load_config() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!("daemon could not load config: {e}");
+                thread::sleep(TICK);
+                continue;
+            }
+        };
+
+        // Collect agent processes that have exited.
+        children.retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+        // Queue follow-ups of chained runs that ended, then start queued runs.
+        if let Err(e) = AgentChains::new(&conn).evaluate_due() {
+            tracing::warn!("agent chains: evaluation failed: {e}");
+        }
+        dispatch_queue(&conn, &config, shared, &mut children);
+
+        let mut reaped_runs = 0;
+        let mut pruned_rows = 0;
+        let mut ran = false;
+        if last_reap.is_none_or(|t| t.elapsed() >= REAP_EVERY) {
+            last_reap = Some(Instant::now());
+            reaped_runs = conductor_core::maintenance::reap(&conn, &db_path(), &config);
+            ran = true;
+        }
+        if last_prune.is_none_or(|t| t.elapsed() >= PRUNE_EVERY) {
+            last_prune = Some(Instant::now());
+            match conductor_core::retention::prune(&conn, &config.retention, false) {
+                Ok(r) => pruned_rows = r.total(),
+                Err(e) => tracing::warn!("retention prune failed: {e}"),
+            }
+        }
+        if ran {
+            shared.update(|s| s.last_maintenance_at = Some(Utc::now().to_rfc3339()));
+            if reaped_runs > 0 || pruned_rows > 0 {
+                shared.emit(DaemonEvent::MaintenanceFinished {
+                    reaped_runs,
+                    pruned_rows,
+                });
+            }
+        }
+        thread::sleep(TICK);
+    }
+}
+
+/// Start queued agent runs while `[agents] max_concurrent_runs` allows.
+/// Each run is a detached `conductor agent run`, which records its own
+/// progress and log in the database.
+fn dispatch_queue(conn: &Connection, config: &Config, shared: &Shared, children: &mut Vec<Child>) {
+    loop {
+        let queued = match AgentQueue::new(conn).take_next(config.agents.max_concurrent_runs) {
+            Ok(Some(q)) => q,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("agent queue: failed to take next run: {e}");
+                return;
+            }
+        };
+        match start_queued_run(conn, config, &queued.worktree_id, &queued.prompt) {
+            Ok((run_id, child)) => {
+                if let Err(e) = AgentChains::new(conn).bind_queued(&queued.id, &run_id) {
+                    tracing::warn!("agent chains: failed to link queued run: {e}");
+                }
+                children.push(child);
+                shared.update(|s| s.dispatched_runs += 1);
+                shared.emit(DaemonEvent::AgentDispatched {
+                    run_id,
+                    worktree_id: queued.worktree_id,
+                });
+            }
+            Err(e) => tracing::warn!(
+                worktree = %queued.worktree_slug,
+                "agent queue: failed to start queued run: {e:#}"
+            ),
+        }
+    }
+}
+
+fn start_queued_run(
+    conn: &Connection,
+    config: &Config,
+    worktree_id: &str,
+    prompt: &str,
+) -> Result<(String, Child)> {
+    let wt = WorktreeManager::new(conn, config).get_by_id(worktree_id)?;
+    let repo = RepoManager::new(conn, config).get_by_id(&wt.repo_id)?;
+    let model = conductor_core::models::resolve_model(
+        wt.model.as_deref(),
+        repo.model.as_deref(),
+        config.general.model.as_deref(),
+    );
+    let agents = AgentManager::new(conn);
+    // Nobody is there to override the budget, so the queued prompt is dropped.
+    agents.check_budget(&config.budget, Some(&wt.id), None)?;
+    let run = agents.create_run(Some(&wt.id), prompt, model.as_deref())?;
+
+    let spawn = || -> Result<Child> {
+        let mut cmd = Command::new(conductor_core::agent_runtime::resolve_conductor_bin());
+        cmd.args([
+            "agent",
+            "run",
+            "--run-id",
+            &run.id,
+            "--worktree-path",
+            &wt.path,
+        ])
+        .args(["--prompt", prompt])
+        .current_dir(&wt.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+        if let Some(model) = &model {
+            cmd.args(["--model", model]);
+        }
+        Ok(cmd.spawn()?)
+    };
+    match spawn() {
+        Ok(child) => Ok((run.id, child)),
+        Err(e) => {
+            agents.update_run_failed(&run.id, &format!("daemon could not start agent: {e:#}"))?;
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+
+use conductor_core::config::{
+    db_path, load_config, save_config, DatabaseEncryption, DatabaseKeySource,
+};
+use conductor_core::db::encryption::encrypt_database;
+
+use crate::commands::{DbCommands, DbKeySource};
+
+pub fn handle_db(command: DbCommands) -> Result<()> {
+    match command {
+        DbCommands::Encrypt { key_source } => handle_encrypt(key_source),
+    }
+}
+
+fn handle_encrypt(key_source: Option<DbKeySource>) -> Result<()> {
+    #[cfg(unix)]
+    if conductor_core::daemon::is_running() {
+        anyhow::bail!("the conductor daemon has the database open; stop it before encrypting");
+    }
+    let mut config = load_config()?;
+    if let Some(source) = key_source {
+        config.database.key_source = match source {
+            DbKeySource::Keychain => DatabaseKeySource::Keychain,
+            DbKeySource::Env => DatabaseKeySource::Env,
+        };
+    }
+
+    let path = db_path();
+    if path.exists() {
+        encrypt_database(&path, &config.database)?;
+        println!("Encrypted {}", path.display());
+    } else {
+        println!(
+            "No database yet; {} will be created encrypted.",
+            path.display()
+        );
+    }
+
+    config.database.encryption = DatabaseEncryption::Sqlcipher;
+    save_config(&config)?;
+    match config.database.key_source {
+        DatabaseKeySource::Keychain => println!(
+            "The key is in the OS keychain (service 'conductor', account 'database'). \
+             Back it up: without it the database cannot be read."
+        ),
+        DatabaseKeySource::Env => println!(
+            "Export ${} with the same key wherever conductor runs.",
+            config.database.key_env()
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+
+use conductor_core::tools::{self, ToolReport};
+
+use crate::commands::DoctorCommands;
+use crate::helpers::print_json;
+
+pub fn handle_doctor(command: DoctorCommands, json: bool) -> Result<()> {
+    match command {
+        DoctorCommands::Tools { refresh } => {
+            let report = if refresh {
+                ToolReport::refresh()
+            } else {
+                ToolReport::current(tools::CACHE_TTL)
+            };
+            if json {
+                return print_json(&report);
+            }
+            print_tools(&report);
+        }
+    }
+    Ok(())
+}
+
+fn print_tools(report: &ToolReport) {
+    let width = report.tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    for tool in &report.tools {
+        let (mark, detail) = match (&tool.version, tool.available) {
+            (Some(version), _) => ("✓", version.clone()),
+            (None, true) => ("✓", "installed".to_string()),
+            (None, false) if tool.required => ("✗", format!("missing — {}", tool.install)),
+            (None, false) => ("-", format!("not installed — {}", tool.install)),
+        };
+        println!("{mark} {:<width$}  {detail}", tool.name);
+        if !tool.available {
+            println!("  {:<width$}  needed for {}", "", tool.purpose);
+        }
+    }
+    let missing = report.missing_required().count();
+    println!(
+        "\nChecked {}.{}",
+        report
+            .probed_at_time()
+            .map_or(report.probed_at.clone(), |t| t
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string()),
+        if missing > 0 {
+            format!(" {missing} required tool(s) missing.")
+        } else {
+            String::new()
+        }
+    );
+}
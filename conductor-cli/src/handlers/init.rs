@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Result};
+use rusqlite::Connection;
+
+use conductor_core::config::{Config, ProjectSettings, RepoConfig};
+use conductor_core::repo::{derive_slug_from_url, RepoManager};
+use conductor_core::toolchain::Toolchain;
+
+/// Flags passed to `conductor init`.
+pub struct InitArgs {
+    pub test_command: Option<String>,
+    pub setup: Vec<String>,
+    pub preamble: Option<String>,
+    pub branch_prefix: Option<String>,
+    pub protected_paths: Vec<String>,
+    pub slug: Option<String>,
+}
+
+pub fn handle_init(args: InitArgs, conn: &Connection, config: &Config) -> Result<()> {
+    let root = git_toplevel(&std::env::current_dir()?)?;
+
+    let mut repo_config = RepoConfig::load(&root)?;
+    apply_args(&mut repo_config.project, &args);
+    if repo_config.project.test_command.is_none() {
+        repo_config.project.test_command = detect_test_command(&root);
+    }
+
+    let mut problems = repo_config.project.validate();
+    problems.extend(repo_config.hooks.validate());
+    if !problems.is_empty() {
+        bail!(
+            "invalid project settings, nothing written:\n  - {}",
+            problems.join("\n  - ")
+        );
+    }
+    repo_config.save(&root)?;
+    println!(
+        "Wrote {}",
+        root.join(".conductor").join("config.toml").display()
+    );
+    print_settings(&repo_config.project);
+
+    let mgr = RepoManager::new(conn, config);
+    match mgr
+        .list()?
+        .into_iter()
+        .find(|r| same_path(Path::new(&r.local_path), &root))
+    {
+        Some(repo) => println!("Repo already registered as '{}'.", repo.slug),
+        None => match git_origin_url(&root) {
+            Some(url) => {
+                let slug = args.slug.unwrap_or_else(|| derive_slug_from_url(&url));
+                let repo = mgr.register(&slug, &root.to_string_lossy(), &url, None)?;
+                println!("Registered repo: {} ({})", repo.slug, repo.remote_url);
+            }
+            None => println!(
+                "No 'origin' remote; skipped registration. \
+                 Run `conductor repo register` once the repo has a remote."
+            ),
+        },
+    }
+    println!("Commit .conductor/config.toml to share these settings.");
+    Ok(())
+}
+
+/// Overlay the flags on the existing settings. List flags replace the stored
+/// list rather than appending, so re-running `init` is idempotent.
+fn apply_args(project: &mut ProjectSettings, args: &InitArgs) {
+    if let Some(cmd) = &args.test_command {
+        project.test_command = Some(cmd.clone());
+    }
+    if !args.setup.is_empty() {
+        project.setup = args.setup.clone();
+    }
+    if let Some(preamble) = &args.preamble {
+        project.agent_preamble = Some(preamble.clone());
+    }
+    if let Some(prefix) = &args.branch_prefix {
+        project.branch_prefix = Some(prefix.clone());
+    }
+    if !args.protected_paths.is_empty() {
+        project.protected_paths = args.protected_paths.clone();
+    }
+}
+
+/// Guess the project's test command from its build files.
+fn detect_test_command(root: &Path) -> Option<String> {
+    let makefile = std::fs::read_to_string(root.join("Makefile")).unwrap_or_default();
+    if makefile.lines().any(|l| l.starts_with("test:")) {
+        return Some("make test".into());
+    }
+    Toolchain::detect(root).test_command()
+}
+
+fn print_settings(project: &ProjectSettings) {
+    let or_unset = |v: Option<&str>| v.unwrap_or("(not set)").to_string();
+    println!(
+        "  test command:    {}",
+        or_unset(project.test_command.as_deref())
+    );
+    println!(
+        "  setup:           {}",
+        if project.setup.is_empty() {
+            "(none)".to_string()
+        } else {
+            project.setup.join("; ")
+        }
+    );
+    println!(
+        "  agent preamble:  {}",
+        or_unset(project.agent_preamble.as_deref())
+    );
+    println!(
+        "  branch prefix:   {}",
+        project.branch_prefix.as_deref().unwrap_or("feat (default)")
+    );
+    println!(
+        "  protected paths: {}",
+        if project.protected_paths.is_empty() {
+            "(none)".to_string()
+        } else {
+            project.protected_paths.join(", ")
+        }
+    );
+}
+
+fn git_toplevel(dir: &Path) -> Result<PathBuf> {
+    let out = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()?;
+    if !out.status.success() {
+        return Err(anyhow!(
+            "{} is not inside a git repository; run `conductor init` from a repo checkout",
+            dir.display()
+        ));
+    }
+    Ok(PathBuf::from(String::from_utf8_lossy(&out.stdout).trim()))
+}
+
+fn git_origin_url(root: &Path) -> Option<String> {
+    Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> InitArgs {
+        InitArgs {
+            test_command: None,
+            setup: Vec::new(),
+            preamble: None,
+            branch_prefix: None,
+            protected_paths: Vec::new(),
+            slug: None,
+        }
+    }
+
+    #[test]
+    fn apply_args_overrides_only_given_flags() {
+        let mut project = ProjectSettings {
+            test_command: Some("make test".into()),
+            setup: vec!["make deps".into()],
+            ..Default::default()
+        };
+        apply_args(
+            &mut project,
+            &InitArgs {
+                branch_prefix: Some("dev".into()),
+                protected_paths: vec!["vendor/".into()],
+                ..args()
+            },
+        );
+        assert_eq!(project.test_command.as_deref(), Some("make test"));
+        assert_eq!(project.setup, ["make deps"]);
+        assert_eq!(project.branch_prefix.as_deref(), Some("dev"));
+        assert_eq!(project.protected_paths, ["vendor/"]);
+    }
+
+    #[test]
+    fn detect_test_command_from_build_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_test_command(dir.path()), None);
+
+        std::fs::write(dir.path().join("package.json"), r#"{"scripts":{}}"#).unwrap();
+        assert_eq!(detect_test_command(dir.path()), None, "no test script");
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts":{"test":"vitest"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("yarn.lock"), "").unwrap();
+        assert_eq!(
+            detect_test_command(dir.path()).as_deref(),
+            Some("yarn test")
+        );
+
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(
+            detect_test_command(dir.path()).as_deref(),
+            Some("cargo test")
+        );
+
+        std::fs::write(
+            dir.path().join("Makefile"),
+            "build:\n\ttrue\ntest:\n\ttrue\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_test_command(dir.path()).as_deref(),
+            Some("make test")
+        );
+    }
+}
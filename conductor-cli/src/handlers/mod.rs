@@ -1,10 +1,23 @@
 pub mod agent;
+pub mod changeset;
+pub mod completions;
 pub mod conversation;
+#[cfg(unix)]
+pub mod daemon;
+pub mod db;
 pub mod dev;
+pub mod doctor;
+pub mod init;
 pub mod mcp;
 pub mod notifications;
+pub mod prune;
 pub mod repo;
+pub mod report;
 pub mod setup;
+pub mod status;
 pub mod tickets;
+pub mod tmux;
+pub mod token;
 pub mod workflow;
+pub mod workspace;
 pub mod worktree;
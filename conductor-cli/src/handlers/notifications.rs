@@ -46,6 +46,8 @@ mod tests {
             "agent_run.failed",
             "gate.waiting",
             "feedback.requested",
+            "watch.state_changed",
+            "watch.pr_updated",
         ];
         for name in names {
             let result = build_synthetic_event(name, "2024-01-01T00:00:00Z");
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use conductor_core::config::Config;
+use conductor_core::retention;
+
+pub fn handle_prune(dry_run: bool, conn: &Connection, config: &Config) -> Result<()> {
+    let policy = &config.retention;
+    if policy.worktree_days == 0 && policy.agent_run_days == 0 && policy.event_days == 0 {
+        println!("No retention windows configured; set [retention] in config.toml to prune.");
+        return Ok(());
+    }
+
+    let report = retention::prune(conn, policy, dry_run)?;
+    let verb = if dry_run { "Would delete" } else { "Deleted" };
+    for (table, days, count) in [
+        ("worktrees", policy.worktree_days, report.worktrees),
+        ("agent_runs", policy.agent_run_days, report.agent_runs),
+        (
+            "agent_run_events",
+            policy.event_days,
+            report.agent_run_events,
+        ),
+    ] {
+        if days == 0 {
+            println!("{table:<18} kept (no retention window)");
+        } else {
+            println!("{table:<18} {verb} {count} older than {days}d");
+        }
+    }
+    Ok(())
+}
@@ -1,15 +1,25 @@
 use anyhow::Result;
 use rusqlite::Connection;
 
-use conductor_core::config::Config;
+use conductor_core::api_token::ApiTokenManager;
+use conductor_core::config::{Config, RepoConfig};
 use conductor_core::github;
+use conductor_core::instructions::{self, InstructionsFile};
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::{derive_local_path, derive_slug_from_url, RepoManager};
+use conductor_core::repo_visibility::RepoVisibility;
 use conductor_core::ticket_source::TicketSource;
+use conductor_core::workspace::{check_root, WorkspaceRootStatus};
 
 use crate::commands::{RepoCommands, SourceCommands};
+use crate::helpers::print_json;
 
-pub fn handle_repo(command: RepoCommands, conn: &Connection, config: &Config) -> Result<()> {
+pub fn handle_repo(
+    command: RepoCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
     match command {
         RepoCommands::Register {
             remote_url,
@@ -24,15 +34,20 @@ pub fn handle_repo(command: RepoCommands, conn: &Connection, config: &Config) ->
             let mgr = RepoManager::new(conn, config);
             let repo = mgr.register(&slug, &local, &remote_url, workspace.as_deref())?;
             println!("Registered repo: {} ({})", repo.slug, repo.remote_url);
+            if !repo.toolchain.is_empty() {
+                println!("  toolchain: {}", repo.toolchain);
+            }
         }
         RepoCommands::List => {
             let mgr = RepoManager::new(conn, config);
             let repos = mgr.list()?;
-            if repos.is_empty() {
+            if json {
+                print_json(&repos)?;
+            } else if repos.is_empty() {
                 println!("No repos registered. Use `conductor repo register` to register one.");
             } else {
                 for repo in repos {
-                    println!("  {}  {}", repo.slug, repo.remote_url);
+                    println!("  {}  {}  [{}]", repo.slug, repo.remote_url, repo.toolchain);
                 }
             }
         }
@@ -101,6 +116,53 @@ pub fn handle_repo(command: RepoCommands, conn: &Connection, config: &Config) ->
                 None => println!("Cleared model override for {slug} (will use global default)"),
             }
         }
+        RepoCommands::SetWorkspace { slug, path } => {
+            let mgr = RepoManager::new(conn, config);
+            let repo = mgr.set_workspace_dir(&slug, path.as_deref())?;
+            println!("Workspace for {slug}: {}", repo.workspace_dir);
+            println!("Existing worktrees stay where they are; new ones are created here.");
+            let status = check_root(std::path::Path::new(&repo.workspace_dir));
+            if status != WorkspaceRootStatus::Ok {
+                eprintln!("warning: {} is {status}", repo.workspace_dir);
+            }
+        }
+        RepoCommands::Detect { slug } => {
+            let repo = RepoManager::new(conn, config).detect_toolchain(&slug)?;
+            println!("Toolchain for {slug}: {}", repo.toolchain);
+            if let Some(cmd) = repo.toolchain.test_command() {
+                println!("  test command: {cmd}");
+            }
+        }
+        RepoCommands::GenInstructions { slug, files, print } => {
+            let repo = RepoManager::new(conn, config).get_by_slug(&slug)?;
+            let root = std::path::Path::new(&repo.local_path);
+            let repo_config = RepoConfig::load(root)?;
+            let toolchain = if repo.toolchain.is_empty() {
+                conductor_core::toolchain::Toolchain::detect(root)
+            } else {
+                repo.toolchain.clone()
+            };
+            let section = instructions::render(&repo_config, &toolchain);
+            if print {
+                print!("{section}");
+                return Ok(());
+            }
+            let files = if !files.is_empty() {
+                files
+            } else if !repo_config.project.instructions_files.is_empty() {
+                repo_config.project.instructions_files.clone()
+            } else {
+                vec![InstructionsFile::ClaudeMd]
+            };
+            for file in files {
+                let path = root.join(file.file_name());
+                if instructions::write(root, file, &section)? {
+                    println!("Wrote {}", path.display());
+                } else {
+                    println!("{} is up to date", path.display());
+                }
+            }
+        }
         RepoCommands::AllowAgentIssues { slug, allow } => {
             let mgr = RepoManager::new(conn, config);
             let repo = mgr.get_by_slug(&slug)?;
@@ -111,6 +173,36 @@ pub fn handle_repo(command: RepoCommands, conn: &Connection, config: &Config) ->
                 println!("Disabled agent issue creation for {slug}");
             }
         }
+        RepoCommands::Visibility {
+            slug,
+            private_to,
+            shared,
+        } => {
+            let repo = RepoManager::new(conn, config).get_by_slug(&slug)?;
+            let visibility = RepoVisibility::new(conn);
+            if let Some(token_id) = private_to {
+                let token = ApiTokenManager::new(conn).get(&token_id)?;
+                visibility.set_private(&repo.id, &token.id)?;
+                println!(
+                    "{slug} is now private to token {} ({})",
+                    token.name, token.prefix
+                );
+            } else if shared {
+                visibility.set_shared(&repo.id)?;
+                println!("{slug} is now shared");
+            } else if json {
+                let private = visibility.get(&repo.id)?;
+                print_json(&serde_json::json!({
+                    "slug": slug,
+                    "private_to": private.map(|p| p.owner),
+                }))?;
+            } else {
+                match visibility.get(&repo.id)? {
+                    Some(private) => println!("{slug}: private to token {}", private.owner),
+                    None => println!("{slug}: shared"),
+                }
+            }
+        }
         RepoCommands::Sources { command } => {
             let repo_mgr = RepoManager::new(conn, config);
             let source_mgr = IssueSourceManager::new(conn);
@@ -138,7 +230,9 @@ pub fn handle_repo(command: RepoCommands, conn: &Connection, config: &Config) ->
                 SourceCommands::List { slug } => {
                     let repo = repo_mgr.get_by_slug(&slug)?;
                     let sources = source_mgr.list(&repo.id)?;
-                    if sources.is_empty() {
+                    if json {
+                        print_json(&sources)?;
+                    } else if sources.is_empty() {
                         println!("No issue sources configured for {slug}.");
                     } else {
                         for s in sources {
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use conductor_core::agent::{parse_since, AgentManager};
+
+use crate::commands::ReportCommands;
+use crate::helpers::{print_json, truncate_str};
+
+pub fn handle_report(command: ReportCommands, conn: &Connection, json: bool) -> Result<()> {
+    match command {
+        ReportCommands::Costs { since, group_by } => {
+            let start = parse_since(&since)?;
+            let rows = AgentManager::new(conn).cost_report(Some(start), group_by)?;
+            if json {
+                return print_json(&rows);
+            }
+            if rows.is_empty() {
+                println!("No agent runs since {}.", start.format("%Y-%m-%d %H:%M"));
+                return Ok(());
+            }
+
+            println!(
+                "{:<40} {:>5} {:>10} {:>7} {:>10}",
+                group_by.to_string().to_uppercase(),
+                "RUNS",
+                "COST",
+                "TURNS",
+                "DURATION"
+            );
+            for row in &rows {
+                println!(
+                    "{:<40} {:>5} {:>10} {:>7} {:>10}",
+                    truncate_str(&row.label, 40),
+                    row.runs,
+                    format!("${:.2}", row.cost_usd),
+                    row.turns,
+                    format_duration(row.duration_ms)
+                );
+            }
+            println!(
+                "{:<40} {:>5} {:>10} {:>7} {:>10}",
+                "TOTAL",
+                rows.iter().map(|r| r.runs).sum::<i64>(),
+                format!("${:.2}", rows.iter().map(|r| r.cost_usd).sum::<f64>()),
+                rows.iter().map(|r| r.turns).sum::<i64>(),
+                format_duration(rows.iter().map(|r| r.duration_ms).sum())
+            );
+        }
+    }
+    Ok(())
+}
+
+fn format_duration(ms: i64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+
+use conductor_core::config::{db_path, load_config};
+use conductor_core::db::open_database_readonly;
+use conductor_core::status::{StatusManager, StatusSummary};
+
+pub fn handle_status(porcelain: bool, json: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    // No database yet means nothing has ever run; report zeros rather than
+    // creating the file from a prompt hook.
+    let path = db_path();
+    let summary = if path.exists() {
+        // Only for the [database] encryption key; nothing else is read.
+        load_config()?;
+        let conn = open_database_readonly(&path)?;
+        StatusManager::new(&conn).summary(&cwd)?
+    } else {
+        StatusSummary::default()
+    };
+
+    if porcelain {
+        println!("{}", summary.porcelain());
+        return Ok(());
+    }
+    if json {
+        return crate::helpers::print_json(&summary);
+    }
+    println!("Running agents:  {}", summary.running_agents);
+    println!("Need attention:  {}", summary.attention);
+    match summary.session {
+        Some(s) => println!(
+            "Current session: {}/{} ({})",
+            s.repo_slug, s.worktree_slug, s.state
+        ),
+        None => println!("Current session: (not in a worktree)"),
+    }
+    Ok(())
+}
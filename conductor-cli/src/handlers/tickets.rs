@@ -1,20 +1,42 @@
 use anyhow::Result;
 use rusqlite::Connection;
 
-use conductor_core::agent::AgentManager;
+use conductor_core::agent::{AgentManager, TicketAgentTotals};
 use conductor_core::config::Config;
 use conductor_core::github;
 use conductor_core::github_app;
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::RepoManager;
-use conductor_core::ticket_source::TicketSource;
-use conductor_core::tickets::TicketSyncer;
-use conductor_core::worktree::WorktreeManager;
+use conductor_core::ticket_source::{SourceContext, TicketSourceRegistry};
+use conductor_core::tickets::grooming::run_grooming_agent;
+use conductor_core::tickets::import::{parse_tickets, ColumnMap, ImportFormat};
+use conductor_core::tickets::{
+    queue_reasons, require_identity, GroomingPass, GroomingProposal, ProposalStatus, QueueReason,
+    Ticket, TicketAttachments, TicketGrooming, TicketSyncer,
+};
+use conductor_core::worktree::{Worktree, WorktreeManager};
+use serde::Serialize;
 
 use crate::commands::TicketCommands;
-use crate::helpers::{sync_repo, truncate_str};
+use crate::helpers::{print_json, render_markdown, sync_repo, truncate_str};
 
-pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Config) -> Result<()> {
+/// `conductor tickets show --json` output: the ticket plus what links to it.
+#[derive(Serialize)]
+struct TicketShow {
+    #[serde(flatten)]
+    ticket: Ticket,
+    repo_slug: String,
+    label_names: Vec<String>,
+    worktrees: Vec<Worktree>,
+    agent_totals: Option<TicketAgentTotals>,
+}
+
+pub fn handle_tickets(
+    command: TicketCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
     match command {
         TicketCommands::Sync { repo } => {
             let repo_mgr = RepoManager::new(conn, config);
@@ -26,6 +48,7 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
 
             let syncer = TicketSyncer::new(conn);
             let source_mgr = IssueSourceManager::new(conn);
+            let registry = TicketSourceRegistry::default();
 
             for r in repos {
                 let repo_owner = github::parse_github_remote(&r.remote_url)
@@ -39,43 +62,40 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
                 );
                 let token = token_res.token();
                 let sources = source_mgr.list(&r.id)?;
+                let ctx = SourceContext {
+                    repo_slug: &r.slug,
+                    remote_url: &r.remote_url,
+                    token,
+                };
 
-                if sources.is_empty() {
-                    // Backward compat: auto-detect GitHub from remote_url
-                    if let Some((owner, name)) = github::parse_github_remote(&r.remote_url) {
-                        sync_repo(&syncer, &r.id, &r.slug, "github", "GitHub issues", || {
-                            github::sync_github_issues(&owner, &name, token)
-                        });
-                    }
-                } else {
-                    for source in sources {
-                        match TicketSource::from_issue_source(&source) {
-                            Ok(ts) => {
-                                let ts = ts.with_repo_slug(&r.slug);
-                                let label = match ts.source_type_str() {
-                                    "github" => "GitHub issues",
-                                    "jira" => "Jira issues",
-                                    "vantage" => "Vantage deliverables",
-                                    other => other,
-                                };
-                                sync_repo(
-                                    &syncer,
-                                    &r.id,
-                                    &r.slug,
-                                    ts.source_type_str(),
-                                    label,
-                                    || ts.sync(token),
-                                );
-                            }
-                            Err(e) => {
-                                eprintln!("  {} — {e}", r.slug);
-                            }
+                for fetcher in registry.fetchers(&sources, &ctx) {
+                    match fetcher {
+                        Ok(f) => {
+                            sync_repo(&syncer, &r.id, &r.slug, f.source_type(), f.label(), || {
+                                f.fetch()
+                            })
                         }
+                        Err(e) => eprintln!("  {} — {e}", r.slug),
                     }
                 }
             }
+
+            let done = TicketAttachments::new(conn).maintain(&config.attachments)?;
+            if done.downloaded > 0 || done.removed > 0 {
+                println!(
+                    "  attachments — downloaded {}, removed {}",
+                    done.downloaded, done.removed
+                );
+            }
+            if config.grooming.enabled {
+                report_grooming(&groom(conn, config, None)?);
+            }
         }
-        TicketCommands::List { repo } => {
+        TicketCommands::List {
+            repo,
+            current_sprint,
+            mine,
+        } => {
             let repo_mgr = RepoManager::new(conn, config);
             let repo_id = if let Some(slug) = &repo {
                 Some(repo_mgr.get_by_slug(slug)?.id)
@@ -84,13 +104,42 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
             };
 
             let syncer = TicketSyncer::new(conn);
-            let tickets = syncer.list(repo_id.as_deref())?;
-            if tickets.is_empty() {
-                println!("No tickets. Run `conductor tickets sync` first.");
+            let mut tickets = syncer.list(repo_id.as_deref())?;
+            if current_sprint {
+                tickets.retain(|t| t.sprint_current);
+            }
+            if mine {
+                require_identity(&config.identity)?;
+                tickets.retain(|t| {
+                    t.state != "closed" && !queue_reasons(t, &config.identity).is_empty()
+                });
+            }
+            if json {
+                print_json(&tickets)?;
+            } else if tickets.is_empty() {
+                if mine {
+                    println!("Nothing is assigned to or mentions you.");
+                } else if current_sprint {
+                    println!("No tickets in the current sprint.");
+                } else {
+                    println!("No tickets. Run `conductor tickets sync` first.");
+                }
             } else {
                 for t in tickets {
+                    let sprint = t
+                        .sprint
+                        .as_deref()
+                        .map(|s| format!(" ({s})"))
+                        .unwrap_or_default();
+                    let mention = if mine
+                        && queue_reasons(&t, &config.identity).contains(&QueueReason::Mentioned)
+                    {
+                        " (mentions you)"
+                    } else {
+                        ""
+                    };
                     println!(
-                        "  {} #{} — {} [{}]",
+                        "  {} #{} — {} [{}]{sprint}{mention}",
                         t.source_type, t.source_id, t.title, t.state
                     );
                 }
@@ -138,7 +187,44 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
                 source_type, source_id, repo
             );
         }
-        TicketCommands::Get { id, json, format } => {
+        TicketCommands::Import {
+            file,
+            repo,
+            source,
+            format,
+            columns,
+            dry_run,
+        } => {
+            let repo_obj = RepoManager::new(conn, config).get_by_slug(&repo)?;
+            let path = std::path::Path::new(&file);
+            let format = match format {
+                Some(f) => f.parse::<ImportFormat>()?,
+                None => ImportFormat::from_path(path).ok_or_else(|| {
+                    anyhow::anyhow!("cannot infer the format of {file}; pass --format csv|json")
+                })?,
+            };
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("failed to read {file}: {e}"))?;
+            let columns = ColumnMap::parse(&columns)?;
+            let tickets = parse_tickets(&content, format, &source, &columns)?;
+
+            if dry_run {
+                for t in &tickets {
+                    println!(
+                        "{}#{}  [{}]  {}",
+                        t.source_type,
+                        t.source_id,
+                        t.state,
+                        truncate_str(&t.title, 60)
+                    );
+                }
+                println!("{} tickets would be imported into {repo}.", tickets.len());
+            } else {
+                let count = TicketSyncer::new(conn).upsert_tickets(&repo_obj.id, &tickets)?;
+                println!("Imported {count} tickets into {repo} as {source}.");
+            }
+        }
+        TicketCommands::Get { id, format } => {
             let syncer = TicketSyncer::new(conn);
             let ticket = syncer.get_by_id(&id).or_else(|_| {
                 // Fall back to searching by source_id across all repos
@@ -149,7 +235,7 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
             })?;
 
             if json || format == "json" {
-                println!("{}", serde_json::to_string_pretty(&ticket)?);
+                print_json(&ticket)?;
             } else {
                 println!("ID:         {}", ticket.id);
                 println!("Source:     {} #{}", ticket.source_type, ticket.source_id);
@@ -178,6 +264,38 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
                 }
             }
         }
+        TicketCommands::Show { id, repo } => {
+            let repo_mgr = RepoManager::new(conn, config);
+            let ticket = find_ticket(conn, config, repo, &id)?;
+            let show = TicketShow {
+                repo_slug: repo_mgr.get_by_id(&ticket.repo_id)?.slug,
+                label_names: ticket.label_names(),
+                worktrees: WorktreeManager::new(conn, config).list_by_ticket(&ticket.id)?,
+                agent_totals: AgentManager::new(conn)
+                    .totals_by_ticket_ids(None, std::slice::from_ref(&ticket.id))?
+                    .remove(&ticket.id),
+                ticket,
+            };
+            if json {
+                print_json(&show)?;
+            } else {
+                print_ticket_show(&show);
+            }
+        }
+        TicketCommands::Comment { id, body, repo } => {
+            let ticket = find_ticket(conn, config, repo, &id)?;
+            TicketSyncer::new(conn).post_comment(&ticket.id, &body)?;
+            println!("Commented on {}:{}", ticket.source_type, ticket.source_id);
+        }
+        TicketCommands::Close { id, repo, comment } => {
+            let ticket = find_ticket(conn, config, repo, &id)?;
+            let syncer = TicketSyncer::new(conn);
+            if let Some(body) = comment {
+                syncer.post_comment(&ticket.id, &body)?;
+            }
+            syncer.transition_state(&ticket.id, "closed")?;
+            println!("Closed {}:{}", ticket.source_type, ticket.source_id);
+        }
         TicketCommands::Upsert {
             repo,
             source_type,
@@ -217,6 +335,8 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
                 blocked_by: vec![],
                 children: vec![],
                 parent,
+                sprint: None,
+                sprint_current: false,
             };
 
             let syncer = TicketSyncer::new(conn);
@@ -284,6 +404,21 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
             let agent_mgr = AgentManager::new(conn);
             let totals = agent_mgr.totals_by_ticket_all()?;
 
+            if json {
+                let rows: Vec<_> = tickets
+                    .iter()
+                    .filter_map(|t| {
+                        let stats = totals.get(&t.id)?;
+                        Some(serde_json::json!({
+                            "ticket_id": t.id,
+                            "source_id": t.source_id,
+                            "title": t.title,
+                            "totals": stats,
+                        }))
+                    })
+                    .collect();
+                return print_json(&rows);
+            }
             let mut found = false;
             for t in &tickets {
                 if let Some(stats) = totals.get(&t.id) {
@@ -306,6 +441,160 @@ pub fn handle_tickets(command: TicketCommands, conn: &Connection, config: &Confi
                 println!("No agent stats. Run agents on ticket-linked worktrees first.");
             }
         }
+        TicketCommands::Groom { repo } => {
+            let repo_id = repo
+                .map(|slug| RepoManager::new(conn, config).get_by_slug(&slug))
+                .transpose()?
+                .map(|r| r.id);
+            // Asking explicitly grooms even when the post-sync pass is off.
+            let mut config = config.clone();
+            config.grooming.enabled = true;
+            let pass = groom(conn, &config, repo_id.as_deref())?;
+            if pass.groomed == 0 && pass.failed == 0 && !pass.budget_exhausted {
+                println!("No tickets to groom.");
+            } else {
+                report_grooming(&pass);
+            }
+        }
+        TicketCommands::Proposals { repo, all } => {
+            let repo_id = repo
+                .map(|slug| RepoManager::new(conn, config).get_by_slug(&slug))
+                .transpose()?
+                .map(|r| r.id);
+            let status = (!all).then_some(ProposalStatus::Pending);
+            let proposals = TicketGrooming::new(conn).list(repo_id.as_deref(), status)?;
+            if json {
+                return print_json(&proposals);
+            }
+            if proposals.is_empty() {
+                println!("No grooming proposals. Run `conductor tickets groom` first.");
+            }
+            for p in &proposals {
+                print_proposal(p);
+            }
+        }
+        TicketCommands::AcceptProposal { id } => {
+            let p = TicketGrooming::new(conn).accept(&id)?;
+            println!(
+                "Accepted proposal for #{} — {}",
+                p.ticket_source_id, p.ticket_title
+            );
+        }
+        TicketCommands::DismissProposal { id } => {
+            let p = TicketGrooming::new(conn).dismiss(&id)?;
+            println!(
+                "Dismissed proposal for #{} — {}",
+                p.ticket_source_id, p.ticket_title
+            );
+        }
     }
     Ok(())
 }
+
+/// Resolve a ticket reference (ID, `<source>:<id>` or bare source id),
+/// optionally within the repo with slug `repo`.
+fn find_ticket(
+    conn: &Connection,
+    config: &Config,
+    repo: Option<String>,
+    reference: &str,
+) -> Result<Ticket> {
+    let repo_id = repo
+        .map(|slug| RepoManager::new(conn, config).get_by_slug(&slug))
+        .transpose()?
+        .map(|r| r.id);
+    Ok(TicketSyncer::new(conn).find_by_reference(repo_id.as_deref(), reference)?)
+}
+
+/// Run a grooming pass with the configured model.
+fn groom(conn: &Connection, config: &Config, repo_id: Option<&str>) -> Result<GroomingPass> {
+    let model = config.grooming.model.clone();
+    Ok(
+        TicketGrooming::new(conn).run_pass(config, repo_id, |prompt| {
+            run_grooming_agent(&model, prompt, None)
+        })?,
+    )
+}
+
+fn report_grooming(pass: &GroomingPass) {
+    println!(
+        "  grooming — {} proposed, {} failed, ${:.2} spent",
+        pass.groomed, pass.failed, pass.spent_usd
+    );
+    if pass.budget_exhausted {
+        println!("  grooming — daily budget reached; remaining tickets wait until tomorrow");
+    }
+}
+
+fn print_proposal(p: &GroomingProposal) {
+    println!(
+        "  {}  #{} {} [{}]",
+        p.id,
+        p.ticket_source_id,
+        truncate_str(&p.ticket_title, 50),
+        p.status
+    );
+    if !p.labels.is_empty() {
+        println!("      labels:     {}", p.labels.join(", "));
+    }
+    if let Some(estimate) = &p.estimate {
+        println!("      estimate:   {estimate}");
+    }
+    if !p.duplicates.is_empty() {
+        let ids: Vec<String> = p.duplicates.iter().map(|d| format!("#{d}")).collect();
+        println!("      duplicates: {}", ids.join(", "));
+    }
+    if let Some(workflow) = &p.workflow {
+        println!("      workflow:   {workflow}");
+    }
+    if let Some(rationale) = &p.rationale {
+        println!("      why:        {rationale}");
+    }
+}
+
+fn print_ticket_show(show: &TicketShow) {
+    let t = &show.ticket;
+    println!("{} #{}  {}", t.source_type, t.source_id, t.title);
+    println!("State:      {}", t.state);
+    println!("Repo:       {}", show.repo_slug);
+    if !show.label_names.is_empty() {
+        println!("Labels:     {}", show.label_names.join(", "));
+    }
+    if let Some(ref a) = t.assignee {
+        println!("Assignee:   {a}");
+    }
+    if let Some(ref p) = t.priority {
+        println!("Priority:   {p}");
+    }
+    if let Some(ref s) = t.sprint {
+        let current = if t.sprint_current { " (current)" } else { "" };
+        println!("Sprint:     {s}{current}");
+    }
+    if !t.url.is_empty() {
+        println!("URL:        {}", t.url);
+    }
+    println!("ID:         {}", t.id);
+
+    if !show.worktrees.is_empty() {
+        println!("\nWorktrees:");
+        for wt in &show.worktrees {
+            println!("  {:<30} {:<40} {}", wt.slug, wt.branch, wt.status);
+        }
+    }
+    if let Some(ref stats) = show.agent_totals {
+        let secs = stats.total_duration_ms / 1000;
+        println!(
+            "\nAgent runs: {} runs  {} turns  {}m{:02}s  ${:.2}",
+            stats.total_runs,
+            stats.total_turns,
+            secs / 60,
+            secs % 60,
+            stats.total_cost,
+        );
+    }
+
+    let body = render_markdown(&t.body);
+    if !body.is_empty() {
+        println!("\n{body}");
+    }
+}
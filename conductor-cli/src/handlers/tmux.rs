@@ -0,0 +1,41 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use conductor_core::config::Config;
+use conductor_core::tmux;
+
+use crate::commands::TmuxCommands;
+
+pub fn handle_tmux(command: TmuxCommands, conn: &Connection, config: &Config) -> Result<()> {
+    match command {
+        TmuxCommands::Attach => {
+            let session = tmux::session_name(config);
+            tmux::ensure_session(&session, &config.general.workspace_root)?;
+            attach(&session)?;
+        }
+        TmuxCommands::Cleanup => {
+            let closed = tmux::cleanup_orphan_windows(conn, config)?;
+            if closed.is_empty() {
+                println!("No orphaned windows in {}.", tmux::session_name(config));
+            }
+            for name in closed {
+                println!("Closed {name}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bring the terminal to `session`: switch the client inside tmux, attach
+/// outside it.
+pub(crate) fn attach(session: &str) -> Result<()> {
+    if tmux::inside_tmux() {
+        tmux::switch_client(session)?;
+        return Ok(());
+    }
+    let status = tmux::attach_command(session).status()?;
+    if !status.success() {
+        anyhow::bail!("tmux attach-session exited with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use conductor_core::api_token::ApiTokenManager;
+
+use crate::commands::TokenCommands;
+use crate::helpers::print_json;
+
+pub fn handle_token(command: TokenCommands, conn: &Connection, json: bool) -> Result<()> {
+    let mgr = ApiTokenManager::new(conn);
+    match command {
+        TokenCommands::Create { name, scope } => {
+            let (token, secret) = mgr.create(&name, scope)?;
+            println!(
+                "Created {} token \"{}\" ({})",
+                token.scope, token.name, token.id
+            );
+            println!();
+            println!("  {secret}");
+            println!();
+            println!("Store it now: the secret cannot be shown again.");
+        }
+        TokenCommands::List => {
+            let tokens = mgr.list()?;
+            if json {
+                return print_json(&tokens);
+            }
+            if tokens.is_empty() {
+                println!("No API tokens.");
+            }
+            for t in tokens {
+                let state = match (&t.revoked_at, &t.last_used_at) {
+                    (Some(at), _) => format!("revoked {at}"),
+                    (None, Some(at)) => format!("last used {at}"),
+                    (None, None) => "never used".to_string(),
+                };
+                println!(
+                    "{}  {:<13}  {}…  {}  ({state})",
+                    t.id, t.scope, t.prefix, t.name
+                );
+            }
+        }
+        TokenCommands::Revoke { id } => {
+            let token = mgr.revoke(&id)?;
+            println!("Revoked token \"{}\" ({})", token.name, token.id);
+        }
+    }
+    Ok(())
+}
@@ -9,12 +9,13 @@ use conductor_core::workflow_config;
 use conductor_core::worktree::WorktreeManager;
 
 use crate::commands::WorkflowCommands;
-use crate::helpers::{report_workflow_result, truncate_str};
+use crate::helpers::{print_json, report_workflow_result, truncate_str};
 
 pub fn handle_workflow(
     command: WorkflowCommands,
     conn: &Connection,
     config: &Config,
+    json: bool,
 ) -> Result<()> {
     // Finalize and resume stuck workflow runs before handling any workflow command.
     {
@@ -26,7 +27,9 @@ pub fn handle_workflow(
         WorkflowCommands::Active => {
             let runs = conductor_core::workflow::list_active_workflow_runs(conn, &[])?;
 
-            if runs.is_empty() {
+            if json {
+                print_json(&runs)?;
+            } else if runs.is_empty() {
                 println!("No active workflow runs.");
             } else {
                 for run in &runs {
@@ -54,7 +57,9 @@ pub fn handle_workflow(
                 agent_mgr.list_for_repo(&r.id)?
             };
 
-            if runs.is_empty() {
+            if json {
+                print_json(&runs)?;
+            } else if runs.is_empty() {
                 println!("No workflow runs found.");
             } else {
                 println!(
@@ -98,7 +103,9 @@ pub fn handle_workflow(
             for w in &wf_warnings {
                 eprintln!("warning: Failed to parse {}: {}", w.file, w.message);
             }
-            if !wf_defs.is_empty() {
+            if json && !wf_defs.is_empty() {
+                print_json(&wf_defs)?;
+            } else if !wf_defs.is_empty() {
                 for def in &wf_defs {
                     let node_count = def.total_nodes();
                     let display = if def.title.is_some() {
@@ -113,7 +120,9 @@ pub fn handle_workflow(
                 }
             } else {
                 let defs = workflow_config::load_workflow_defs(&wt_path, &repo_path)?;
-                if defs.is_empty() {
+                if json {
+                    print_json(&defs)?;
+                } else if defs.is_empty() {
                     println!(
                         "No workflows found. Create .conductor/workflows/<name>.wf in your repo."
                     );
@@ -133,6 +142,7 @@ pub fn handle_workflow(
         WorkflowCommands::Run {
             repo,
             worktree,
+            worktree_flag,
             name,
             pr,
             repo_flag,
@@ -342,11 +352,17 @@ pub fn handle_workflow(
                 })?;
             } else {
                 // Normal registered repo/worktree run
-                let repo_slug = repo
-                    .ok_or_else(|| anyhow::anyhow!("--repo is required when --pr is not used"))?;
-                let worktree_slug = worktree.ok_or_else(|| {
-                    anyhow::anyhow!("--worktree is required when --pr is not used")
-                })?;
+                let (repo_slug, worktree_slug) = match worktree_flag {
+                    Some(slug) => locate_worktree(conn, config, repo.as_deref(), &slug)?,
+                    None => (
+                        repo.ok_or_else(|| {
+                            anyhow::anyhow!("--repo is required when --pr is not used")
+                        })?,
+                        worktree.ok_or_else(|| {
+                            anyhow::anyhow!("--worktree is required when --pr is not used")
+                        })?,
+                    ),
+                };
 
                 let repo_mgr = RepoManager::new(conn, config);
                 let r = repo_mgr.get_by_slug(&repo_slug)?;
@@ -432,6 +448,10 @@ pub fn handle_workflow(
         }
         WorkflowCommands::RunShow { id } => {
             match conductor_core::workflow::get_workflow_run(conn, &id)? {
+                Some(run) if json => {
+                    let steps = conductor_core::workflow::get_workflow_steps(conn, &run.id)?;
+                    print_json(&serde_json::json!({ "run": run, "steps": steps }))?;
+                }
                 Some(run) => {
                     println!("Workflow Run: {}", run.id);
                     println!("  Name:    {}", run.workflow_name);
@@ -728,10 +748,25 @@ pub fn handle_workflow(
                 }
             }
         }
-        WorkflowCommands::GateApprove { run_id } => {
+        WorkflowCommands::GateApprove { run_id, feedback } => {
+            let feedback = feedback.filter(|f| !f.trim().is_empty());
             with_waiting_gate(conn, &run_id, |step, user| {
-                conductor_core::workflow::approve_gate(conn, &step.id, user, None, None, None)?;
-                println!("Gate '{}' approved by {user}.", step.step_name);
+                conductor_core::workflow::approve_gate(
+                    conn,
+                    &step.id,
+                    user,
+                    feedback.as_deref(),
+                    None,
+                    None,
+                )?;
+                if feedback.is_some() {
+                    println!(
+                        "Gate '{}' approved with feedback by {user}.",
+                        step.step_name
+                    );
+                } else {
+                    println!("Gate '{}' approved by {user}.", step.step_name);
+                }
                 Ok(())
             })?;
         }
@@ -803,7 +838,9 @@ pub fn handle_workflow(
         WorkflowCommands::TemplateList => {
             use conductor_core::workflow_template::list_embedded_templates;
             let templates = list_embedded_templates();
-            if templates.is_empty() {
+            if json {
+                print_json(&templates)?;
+            } else if templates.is_empty() {
                 println!("No workflow templates available.");
             } else {
                 println!(
@@ -826,6 +863,7 @@ pub fn handle_workflow(
         WorkflowCommands::TemplateShow { name } => {
             use conductor_core::workflow_template::get_embedded_template;
             match get_embedded_template(&name) {
+                Some(t) if json => print_json(&t)?,
                 Some(t) => {
                     println!("Name:        {}", t.metadata.name);
                     println!("Version:     {}", t.metadata.version);
@@ -977,6 +1015,40 @@ pub fn handle_workflow(
     Ok(())
 }
 
+/// Resolve `workflow run --worktree <slug>` to a (repo, worktree) slug pair.
+/// An explicit repo is used as given; otherwise the slug must match exactly
+/// one live worktree across all registered repos.
+fn locate_worktree(
+    conn: &Connection,
+    config: &Config,
+    repo: Option<&str>,
+    slug: &str,
+) -> Result<(String, String)> {
+    if let Some(repo) = repo {
+        return Ok((repo.to_string(), slug.to_string()));
+    }
+    let repo_mgr = RepoManager::new(conn, config);
+    let matches: Vec<_> = WorktreeManager::new(conn, config)
+        .list(None, true)?
+        .into_iter()
+        .filter(|wt| wt.slug == slug)
+        .collect();
+    match matches.as_slice() {
+        [] => anyhow::bail!("no active worktree named '{slug}'"),
+        [wt] => Ok((repo_mgr.get_by_id(&wt.repo_id)?.slug, slug.to_string())),
+        _ => {
+            let repos = matches
+                .iter()
+                .map(|wt| repo_mgr.get_by_id(&wt.repo_id).map(|r| r.slug))
+                .collect::<conductor_core::error::Result<Vec<_>>>()?;
+            anyhow::bail!(
+                "worktree '{slug}' exists in several repos ({}); pass the repo slug too",
+                repos.join(", ")
+            )
+        }
+    }
+}
+
 /// Execute a workflow and report the result.
 fn run_and_report(input: conductor_core::workflow::WorkflowExecStandalone) -> Result<()> {
     let result = conductor_core::workflow::execute_workflow_standalone(&input)
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use conductor_core::config::{save_config, Config};
+use conductor_core::workspace::{expand_path, WorkspaceManager};
+
+use crate::commands::WorkspaceCommands;
+use crate::helpers::print_json;
+
+pub fn handle_workspace(
+    command: WorkspaceCommands,
+    conn: &Connection,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
+    let mgr = WorkspaceManager::new(conn, config);
+    match command {
+        WorkspaceCommands::List => {
+            let roots = mgr.list_roots()?;
+            if json {
+                return print_json(&roots);
+            }
+            for root in roots {
+                let label = if root.is_default { "  (default)" } else { "" };
+                println!("{}{label}", root.path);
+                let repos = if root.repos.is_empty() {
+                    "-".to_string()
+                } else {
+                    root.repos.join(", ")
+                };
+                println!("  status: {}", root.status);
+                println!("  repos:  {repos}");
+            }
+        }
+        WorkspaceCommands::Migrate { from, to, dry_run } => {
+            let from = expand_path(&from).map_err(anyhow::Error::msg)?;
+            let to = expand_path(&to).map_err(anyhow::Error::msg)?;
+            let report = mgr.migrate(&from, &to, dry_run)?;
+            let verb = if dry_run { "Would update" } else { "Updated" };
+            println!(
+                "{verb} {} repo(s) and {} worktree(s): {} -> {}",
+                report.repos,
+                report.worktrees,
+                from.display(),
+                to.display()
+            );
+            for (slug, err) in &report.repair_failures {
+                eprintln!("warning: git worktree repair failed for {slug}: {err}");
+            }
+            if report.default_root_moved {
+                let new_root = to.join(
+                    config
+                        .general
+                        .workspace_root
+                        .strip_prefix(&from)
+                        .unwrap_or(std::path::Path::new("")),
+                );
+                if dry_run {
+                    println!("Would set general.workspace_root to {}", new_root.display());
+                } else {
+                    let mut config = config.clone();
+                    config.general.workspace_root = new_root;
+                    save_config(&config)?;
+                    println!(
+                        "Set general.workspace_root to {}",
+                        config.general.workspace_root.display()
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
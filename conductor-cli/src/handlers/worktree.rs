@@ -1,19 +1,29 @@
 use anyhow::Result;
 use rusqlite::Connection;
 
-use conductor_core::agent::AgentManager;
+use conductor_core::agent::{
+    AgentManager, ConflictResolution, ConflictResolutionStatus, ConflictResolutions,
+};
 use conductor_core::config::Config;
 use conductor_core::repo::RepoManager;
-use conductor_core::tickets::{build_agent_prompt, TicketSyncer};
-use conductor_core::worktree::{WorktreeAdoptOptions, WorktreeCreateOptions, WorktreeManager};
+use conductor_core::tickets::{build_agent_prompt, TicketAttachments, TicketSyncer};
+use conductor_core::worktree::{
+    conflict_state, derive_worktree_slug, parse_status_list, stack_order, AbandonReason,
+    BaseBranchSource, BatchItem, BatchOutcome, CreateProgress, PrOptions, PushOptions,
+    RestackOptions, SyncStatus, SyncStrategy, Worktree, WorktreeAdoptOptions,
+    WorktreeCreateOptions, WorktreeListEntry, WorktreeListFilter, WorktreeManager, WorktreeSort,
+    WorktreeStatus,
+};
 
-use crate::commands::WorktreeCommands;
+use crate::commands::{ConflictAction, WorktreeCommands};
 use crate::handlers::agent::run_agent;
+use crate::helpers::print_json;
 
 pub fn handle_worktree(
     command: WorktreeCommands,
     conn: &Connection,
     config: &Config,
+    json: bool,
 ) -> Result<()> {
     // Reap stale worktrees before handling any worktree command.
     {
@@ -28,13 +38,26 @@ pub fn handle_worktree(
             name,
             from,
             from_pr,
+            stack_on,
             ticket,
             auto_agent,
             force,
+            offline,
         } => {
-            let effective_from = from;
-
             let mgr = WorktreeManager::new(conn, config);
+            let effective_from = match stack_on {
+                Some(parent) => Some(mgr.stack_base(&repo, &parent)?),
+                None => from,
+            };
+            // Show which base a ticket resolves to before any git work starts.
+            let effective_from = match (&effective_from, &ticket, from_pr) {
+                (None, Some(ticket_id), None) => {
+                    let base = mgr.resolve_base(&repo, Some(ticket_id))?;
+                    eprintln!("Base branch: {base}");
+                    (base.source != BaseBranchSource::RepoDefault).then_some(base.branch)
+                }
+                _ => effective_from,
+            };
 
             // Run health check before creation (skip for --from-pr paths since
             // staleness is irrelevant; dirty check still applies).
@@ -78,6 +101,10 @@ pub fn handle_worktree(
                     from_pr,
                     force_dirty,
                     pre_health,
+                    progress: Some(CreateProgress::new(|stage| {
+                        eprintln!("  {}...", stage.label())
+                    })),
+                    offline,
                 },
             )?;
 
@@ -92,7 +119,9 @@ pub fn handle_worktree(
                     let syncer = TicketSyncer::new(conn);
                     match syncer.get_by_id(tid) {
                         Ok(t) => {
-                            let prompt = build_agent_prompt(&t);
+                            let attachments =
+                                TicketAttachments::new(conn).list(&t.id).unwrap_or_default();
+                            let prompt = build_agent_prompt(&t, &attachments);
                             println!("Starting agent...");
                             // Resolve model: per-worktree → per-repo config → global config
                             let repo_mgr = RepoManager::new(conn, config);
@@ -115,6 +144,8 @@ pub fn handle_worktree(
                                 None,
                                 None,
                                 &[],
+                                None,
+                                None,
                             )?;
                         }
                         Err(e) => {
@@ -126,21 +157,185 @@ pub fn handle_worktree(
                 }
             }
         }
-        WorktreeCommands::List { repo } => {
+        WorktreeCommands::CreateFromTicket {
+            repo,
+            ticket,
+            name,
+            from,
+            agent,
+            force,
+            offline,
+        } => {
+            let repo_id = RepoManager::new(conn, config).get_by_slug(&repo)?.id;
+            let t = TicketSyncer::new(conn)
+                .resolve_tickets_in_repo(&repo_id, std::slice::from_ref(&ticket))?
+                .remove(0);
+            let name = name.unwrap_or_else(|| {
+                let labels: Vec<String> = serde_json::from_str(&t.labels).unwrap_or_default();
+                derive_worktree_slug(&t.source_id, &t.title, &labels)
+            });
+            println!("Ticket #{}: {}", t.source_id, t.title);
+            handle_worktree(
+                WorktreeCommands::Create {
+                    repo,
+                    name,
+                    from,
+                    from_pr: None,
+                    stack_on: None,
+                    ticket: Some(t.id),
+                    auto_agent: agent,
+                    force,
+                    offline,
+                },
+                conn,
+                config,
+                json,
+            )?;
+        }
+        WorktreeCommands::FromUrl {
+            url,
+            name,
+            from,
+            agent,
+            force,
+            offline,
+        } => {
+            let target = WorktreeManager::new(conn, config).resolve_url(&url)?;
+            if target.from_pr.is_some() && (from.is_some() || offline) {
+                anyhow::bail!("--from and --offline can't be used with a pull request URL");
+            }
+            match &target.ticket {
+                Some(t) => println!("{}: ticket #{}: {}", target.repo.slug, t.source_id, t.title),
+                None => println!(
+                    "{}: pull request #{}",
+                    target.repo.slug,
+                    target.from_pr.unwrap_or_default()
+                ),
+            }
+            handle_worktree(
+                WorktreeCommands::Create {
+                    repo: target.repo.slug,
+                    name: name.unwrap_or(target.name),
+                    from,
+                    from_pr: target.from_pr,
+                    stack_on: None,
+                    ticket: target.ticket.map(|t| t.id),
+                    auto_agent: agent,
+                    force,
+                    offline,
+                },
+                conn,
+                config,
+                json,
+            )?;
+        }
+        WorktreeCommands::List {
+            repo,
+            status,
+            ticket,
+            stale,
+            sort,
+            format,
+        } => {
+            let format = if json { "json".to_string() } else { format };
+            if !matches!(format.as_str(), "table" | "json" | "tsv") {
+                anyhow::bail!("unknown format '{format}' (expected table, json or tsv)");
+            }
+            let filter = WorktreeListFilter {
+                repo_id: repo
+                    .as_deref()
+                    .map(|slug| RepoManager::new(conn, config).get_by_slug(slug))
+                    .transpose()?
+                    .map(|r| r.id),
+                statuses: status
+                    .as_deref()
+                    .map(parse_status_list)
+                    .transpose()?
+                    .unwrap_or_default(),
+                ticket,
+                stale_days: stale,
+                sort: sort.parse::<WorktreeSort>().map_err(anyhow::Error::msg)?,
+                ..Default::default()
+            };
             let mgr = WorktreeManager::new(conn, config);
-            let worktrees = mgr.list(repo.as_deref(), false)?;
-            if worktrees.is_empty() {
-                println!("No worktrees.");
-            } else {
-                for wt in worktrees {
-                    println!("  {}  {}  [{}]", wt.slug, wt.branch, wt.status);
+            let mut entries = mgr.list_entries(&filter)?;
+            mgr.fill_ahead_behind(&mut entries)?;
+
+            match format.as_str() {
+                "json" => print_json(&entries)?,
+                "tsv" => {
+                    println!("repo\tworktree\tstatus\tticket\tbranch\tahead\tbehind\tlast_agent\tlast_agent_at\tcost_usd");
+                    for e in &entries {
+                        let wt = &e.worktree.worktree;
+                        let (ahead, behind) = e
+                            .ahead_behind
+                            .map(|ab| (ab.ahead.to_string(), ab.behind.to_string()))
+                            .unwrap_or_default();
+                        println!(
+                            "{}\t{}\t{}\t{}\t{}\t{ahead}\t{behind}\t{}\t{}\t{:.2}",
+                            e.repo_slug,
+                            wt.slug,
+                            wt.status,
+                            e.worktree.ticket_number.as_deref().unwrap_or(""),
+                            wt.branch,
+                            e.worktree
+                                .agent_status
+                                .map(|s| s.to_string())
+                                .unwrap_or_default(),
+                            e.last_agent_at.as_deref().unwrap_or(""),
+                            e.total_cost_usd,
+                        );
+                    }
                 }
+                _ if entries.is_empty() => println!("No worktrees."),
+                _ => print_worktree_table(&mgr, &entries, filter.sort == WorktreeSort::Created)?,
             }
         }
-        WorktreeCommands::Delete { repo, name } => {
+        WorktreeCommands::Delete {
+            repo, merged: true, ..
+        } => {
+            let items = WorktreeManager::new(conn, config).delete_merged(repo.as_deref())?;
+            if items.is_empty() {
+                println!("No merged worktrees to delete.");
+            }
+            report_batch(&items)?;
+        }
+        WorktreeCommands::Delete {
+            repo, name, reason, ..
+        } => {
+            let (Some(repo), Some(name)) = (repo, name) else {
+                anyhow::bail!("a repo and worktree are required unless --merged is given");
+            };
+            let reason = reason
+                .as_deref()
+                .map(str::parse::<AbandonReason>)
+                .transpose()
+                .map_err(anyhow::Error::msg)?;
             let mgr = WorktreeManager::new(conn, config);
-            let wt = mgr.delete(&repo, &name)?;
-            println!("Worktree {name} marked as {} ✓", wt.status);
+            let wt = mgr.delete(&repo, &name, reason)?;
+            println!("Worktree {name} marked as {} ✓", wt.status_label());
+            if wt.status == WorktreeStatus::Abandoned && wt.abandon_reason.is_none() {
+                println!(
+                    "  tip: pass --reason superseded|wontfix|blocked|experiment to record why"
+                );
+            }
+        }
+        WorktreeCommands::Stats { repo } => {
+            let repo_id = match repo {
+                Some(slug) => Some(RepoManager::new(conn, config).get_by_slug(&slug)?.id),
+                None => None,
+            };
+            let outcomes = WorktreeManager::new(conn, config).outcomes(repo_id.as_deref())?;
+            if json {
+                return print_json(&outcomes);
+            }
+            println!("Merged:     {}", outcomes.merged);
+            println!("Promoted:   {}", outcomes.promoted);
+            println!("Abandoned:  {}", outcomes.abandoned);
+            for c in &outcomes.by_reason {
+                println!("  {:<12}{}", c.reason.as_str(), c.count);
+            }
+            println!("  {:<12}{}", "no reason", outcomes.unspecified);
         }
         WorktreeCommands::Purge { repo, name } => {
             let mgr = WorktreeManager::new(conn, config);
@@ -151,16 +346,119 @@ pub fn handle_worktree(
                 println!("Purged {count} completed worktree record(s).");
             }
         }
-        WorktreeCommands::Push { repo, name } => {
+        WorktreeCommands::Missing { repo } => {
+            let repo_mgr = RepoManager::new(conn, config);
+            let repo_id = match &repo {
+                Some(slug) => Some(repo_mgr.get_by_slug(slug)?.id),
+                None => None,
+            };
+            let mgr = WorktreeManager::new(conn, config);
+            mgr.check_missing_paths()?;
+            let missing = mgr.list_missing(repo_id.as_deref())?;
+            if json {
+                return print_json(&missing);
+            }
+            if missing.is_empty() {
+                println!("No missing worktrees.");
+                return Ok(());
+            }
+            let repos = repo_mgr.list()?;
+            for wt in &missing {
+                let repo_slug = repos
+                    .iter()
+                    .find(|r| r.id == wt.repo_id)
+                    .map_or(wt.repo_id.as_str(), |r| r.slug.as_str());
+                println!(
+                    "{repo_slug}/{}  {}  (missing since {})",
+                    wt.slug,
+                    wt.path,
+                    wt.missing_since.as_deref().unwrap_or("-")
+                );
+            }
+            println!(
+                "\nRestore with `conductor worktree restore <repo> <name>` \
+                 or drop with `conductor worktree delete <repo> <name>`."
+            );
+        }
+        WorktreeCommands::Restore { repo, name } => {
+            let wt = WorktreeManager::new(conn, config).restore(&repo, &name)?;
+            println!("Restored {} at {} from {} ✓", wt.slug, wt.path, wt.branch);
+        }
+        WorktreeCommands::Commit {
+            repo,
+            name,
+            message,
+            all,
+            ..
+        } => {
+            let commit =
+                WorktreeManager::new(conn, config).commit(&repo, &name, message.as_deref(), all)?;
+            println!(
+                "Committed {} file(s) as {}: {}",
+                commit.files.len(),
+                &commit.sha[..commit.sha.len().min(8)],
+                commit.subject
+            );
+        }
+        WorktreeCommands::Push {
+            repo,
+            no_verify,
+            all: true,
+            ..
+        } => {
+            let items = WorktreeManager::new(conn, config).push_all(
+                repo.as_deref(),
+                PushOptions {
+                    skip_checks: no_verify,
+                },
+            )?;
+            if items.is_empty() {
+                println!("No live worktrees.");
+            }
+            report_batch(&items)?;
+        }
+        WorktreeCommands::Push {
+            repo,
+            name,
+            no_verify,
+            ..
+        } => {
+            let (Some(repo), Some(name)) = (repo, name) else {
+                anyhow::bail!("a repo and worktree are required unless --all is given");
+            };
             let mgr = WorktreeManager::new(conn, config);
-            let msg = mgr.push(&repo, &name)?;
+            let msg = mgr.push(
+                &repo,
+                &name,
+                PushOptions {
+                    skip_checks: no_verify,
+                },
+            )?;
             println!("{msg}");
         }
-        WorktreeCommands::Pr { repo, name, draft } => {
+        WorktreeCommands::Pr {
+            repo,
+            name,
+            draft,
+            from_agent,
+        } => {
             let mgr = WorktreeManager::new(conn, config);
-            let url = mgr.create_pr(&repo, &name, draft)?;
+            let url = mgr.create_pr(&repo, &name, PrOptions { draft, from_agent })?;
             println!("PR created: {url}");
         }
+        WorktreeCommands::Todos { repo, name } => {
+            let mgr = WorktreeManager::new(conn, config);
+            let todos = mgr.scan_todos(&repo, &name)?;
+            if json {
+                return print_json(&todos);
+            }
+            if todos.is_empty() {
+                println!("No TODO or FIXME comments added on this branch.");
+            }
+            for todo in todos {
+                println!("{todo}");
+            }
+        }
         WorktreeCommands::SetModel { repo, name, model } => {
             let mgr = WorktreeManager::new(conn, config);
             mgr.set_model(&repo, &name, model.as_deref())?;
@@ -185,19 +483,62 @@ pub fn handle_worktree(
             name,
             base_branch,
             rebase,
+            keep_conflicts,
         } => {
             let mgr = WorktreeManager::new(conn, config);
             mgr.set_base_branch(
                 &repo,
                 &name,
                 base_branch.as_deref(),
-                conductor_core::worktree::SetBaseBranchOptions { rebase },
+                conductor_core::worktree::SetBaseBranchOptions {
+                    rebase,
+                    keep_conflicts,
+                },
             )?;
             match base_branch {
                 Some(b) => println!("Base branch for {name} set to: {b}"),
                 None => println!("Base branch for {name} cleared (will use repo default)"),
             }
         }
+        WorktreeCommands::Restack {
+            repo,
+            name,
+            keep_conflicts,
+        } => {
+            let mgr = WorktreeManager::new(conn, config);
+            let onto = mgr.restack(&repo, &name, RestackOptions { keep_conflicts })?;
+            println!("Rebased {name} onto {onto} ✓");
+        }
+        WorktreeCommands::Sync {
+            repo,
+            name,
+            strategy,
+        } => {
+            let strategy = strategy
+                .parse::<SyncStrategy>()
+                .map_err(anyhow::Error::msg)?;
+            let result =
+                WorktreeManager::new(conn, config).sync_with_base(&repo, &name, strategy)?;
+            if let SyncStatus::Conflicts { ref files } = result.status {
+                eprintln!(
+                    "{} of {name} onto {} conflicts; nothing was changed.",
+                    result.strategy, result.onto
+                );
+                for file in files {
+                    eprintln!("  {file}");
+                }
+                anyhow::bail!("sync of {name} stopped on conflicts");
+            }
+            println!("{result} ✓");
+        }
+        WorktreeCommands::Conflicts {
+            repo,
+            name,
+            action,
+            over_budget,
+        } => {
+            handle_conflicts(conn, config, &repo, &name, action, over_budget)?;
+        }
         WorktreeCommands::Adopt {
             repo,
             path,
@@ -274,6 +615,257 @@ pub fn handle_worktree(
     Ok(())
 }
 
+/// `conductor worktree conflicts`: inspect, resolve (with an agent), verify,
+/// continue or abort a rebase or merge stopped on conflicts.
+fn handle_conflicts(
+    conn: &Connection,
+    config: &Config,
+    repo_slug: &str,
+    name: &str,
+    action: ConflictAction,
+    over_budget: bool,
+) -> Result<()> {
+    let repo = RepoManager::new(conn, config).get_by_slug(repo_slug)?;
+    let wt = WorktreeManager::new(conn, config).get_by_slug(&repo.id, name)?;
+    let resolutions = ConflictResolutions::new(conn, config);
+    let print_check = |r: &ConflictResolution| match r.status {
+        ConflictResolutionStatus::Verified => {
+            println!(
+                "Check passed ✓ — run `conductor worktree conflicts {repo_slug} {name} continue`"
+            )
+        }
+        ConflictResolutionStatus::CheckFailed => {
+            println!("Check failed ✗");
+            if let Some(output) = &r.check_output {
+                println!("{output}");
+            }
+        }
+        _ => {}
+    };
+
+    match action {
+        ConflictAction::Show => {
+            let Some(state) = conflict_state(std::path::Path::new(&wt.path))? else {
+                println!("No rebase or merge in progress in {name}.");
+                return Ok(());
+            };
+            let onto = state.onto.as_deref().unwrap_or("?");
+            println!("{} onto {onto} in progress", state.operation);
+            if state.files.is_empty() {
+                println!("  All conflicts resolved and staged.");
+            }
+            for file in &state.files {
+                println!("  {} ({} conflict(s))", file.path, file.hunks.len());
+            }
+            if let Some(r) = resolutions
+                .latest_for_worktree(&wt.id)?
+                .filter(|r| r.status.is_open())
+            {
+                let run = r
+                    .run_id
+                    .as_deref()
+                    .map(|id| format!(", agent run {id}"))
+                    .unwrap_or_default();
+                println!("Resolution: {}{run}", r.status);
+                print_check(&r);
+            }
+        }
+        ConflictAction::Agent => {
+            let agents = AgentManager::new(conn);
+            if !over_budget {
+                agents.check_budget(&config.budget, Some(&wt.id), None)?;
+            }
+            let request = resolutions.prepare(&wt.id)?;
+            let repo_model = repo.model.as_deref();
+            let model = conductor_core::models::resolve_model(
+                wt.model.as_deref(),
+                repo_model,
+                config.general.model.as_deref(),
+            );
+            let run = agents.create_run(Some(&wt.id), &request.prompt, model.as_deref())?;
+            let resolution = resolutions.record(&request, Some(&run.id))?;
+            println!(
+                "Resolving {} conflicting file(s) with agent run {}...",
+                request.state.files.len(),
+                run.id
+            );
+            run_agent(
+                conn,
+                &run.id,
+                &wt.path,
+                &request.prompt,
+                None,
+                model.as_deref(),
+                None,
+                None,
+                &[],
+                None,
+                None,
+            )?;
+            match resolutions.verify(&resolution.id) {
+                Ok(r) => print_check(&r),
+                Err(e) => println!("Not ready to verify: {e}"),
+            }
+        }
+        ConflictAction::Verify => {
+            let resolution = resolutions.current(&wt.id)?;
+            print_check(&resolutions.verify(&resolution.id)?);
+        }
+        ConflictAction::Continue => {
+            let resolution = resolutions.current(&wt.id)?;
+            let next = resolutions.continue_operation(&resolution.id)?;
+            if next.id == resolution.id {
+                println!("Continued the {} of {name} ✓", resolution.operation);
+            } else {
+                println!(
+                    "The {} stopped on more conflicts: {}",
+                    next.operation,
+                    next.files.join(", ")
+                );
+            }
+        }
+        ConflictAction::Abort => {
+            let resolution = resolutions.current(&wt.id)?;
+            resolutions.abort(&resolution.id)?;
+            println!("Aborted the {} of {name}", resolution.operation);
+        }
+    }
+    Ok(())
+}
+
+/// Print one line per worktree of a batch operation, then fail if any of
+/// them failed so scripts see a non-zero exit.
+fn report_batch(items: &[BatchItem]) -> Result<()> {
+    for item in items {
+        let (mark, message) = match &item.outcome {
+            BatchOutcome::Done(msg) => ("✓", msg),
+            BatchOutcome::Skipped(msg) => ("-", msg),
+            BatchOutcome::Failed(msg) => ("✗", msg),
+        };
+        println!("{mark} {}/{}: {message}", item.repo_slug, item.slug);
+    }
+    let failed = items.iter().filter(|i| i.is_failed()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} worktree(s) failed", items.len());
+    }
+    Ok(())
+}
+
+/// Print `entries` as an aligned table. With `stacked`, stacked worktrees are
+/// indented under the worktree they build on.
+fn print_worktree_table(
+    mgr: &WorktreeManager,
+    entries: &[WorktreeListEntry],
+    stacked: bool,
+) -> Result<()> {
+    let mut repo_ids: Vec<&str> = entries
+        .iter()
+        .map(|e| e.worktree.worktree.repo_id.as_str())
+        .collect();
+    repo_ids.sort_unstable();
+    repo_ids.dedup();
+    let mut stacks = std::collections::HashMap::new();
+    for repo_id in repo_ids {
+        for info in mgr.stack_infos_for_repo(repo_id)? {
+            stacks.insert(info.worktree_id.clone(), info);
+        }
+    }
+
+    let order: Vec<(usize, usize)> = if stacked {
+        let worktrees: Vec<Worktree> = entries
+            .iter()
+            .map(|e| e.worktree.worktree.clone())
+            .collect();
+        stack_order(&worktrees)
+    } else {
+        (0..entries.len()).map(|i| (i, 0)).collect()
+    };
+
+    let rows: Vec<[String; 8]> = order
+        .into_iter()
+        .map(|(i, depth)| {
+            let e = &entries[i];
+            let wt = &e.worktree.worktree;
+            let indent = if depth == 0 {
+                String::new()
+            } else {
+                format!("{}└ ", "  ".repeat(depth - 1))
+            };
+            let status = match stacks.get(&wt.id) {
+                Some(info) if info.state.needs_restack() => {
+                    format!("{} ({})", wt.status, info.state.label())
+                }
+                _ => wt.status_label(),
+            };
+            let ticket = e
+                .worktree
+                .ticket_number
+                .as_deref()
+                .map(|n| {
+                    if n.chars().all(|c| c.is_ascii_digit()) {
+                        format!("#{n}")
+                    } else {
+                        n.to_string()
+                    }
+                })
+                .unwrap_or_else(|| "-".into());
+            let ahead_behind = e
+                .ahead_behind
+                .map(|ab| format!("+{}/-{}", ab.ahead, ab.behind))
+                .unwrap_or_else(|| "-".into());
+            let last_agent = match (e.worktree.agent_status, e.last_agent_at.as_deref()) {
+                (Some(s), Some(at)) => format!("{s} {}", &at[..16.min(at.len())].replace('T', " ")),
+                _ => "-".into(),
+            };
+            let cost = if e.total_cost_usd > 0.0 {
+                format!("${:.2}", e.total_cost_usd)
+            } else {
+                "-".into()
+            };
+            [
+                format!("{indent}{}", wt.slug),
+                e.repo_slug.clone(),
+                status,
+                ticket,
+                wt.branch.clone(),
+                ahead_behind,
+                last_agent,
+                cost,
+            ]
+        })
+        .collect();
+
+    let header = [
+        "WORKTREE",
+        "REPO",
+        "STATUS",
+        "TICKET",
+        "BRANCH",
+        "+/-",
+        "LAST AGENT",
+        "COST",
+    ];
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let print_row = |cells: &[&str]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+    print_row(&header);
+    for row in &rows {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use conductor_core::worktree::{SetBaseBranchOptions, WorktreeManager};
@@ -321,7 +913,10 @@ mod tests {
             "test-repo",
             "feat-test",
             Some("release/v1"),
-            SetBaseBranchOptions { rebase: true },
+            SetBaseBranchOptions {
+                rebase: true,
+                ..Default::default()
+            },
         );
         assert!(
             result.is_err(),
@@ -1,8 +1,17 @@
+use std::path::Path;
 use std::process::Command;
 
+use conductor_core::agent::sandbox::Sandbox;
 use conductor_core::agent::PlanStep;
 use conductor_core::error::ConductorError;
 use conductor_core::tickets::TicketInput;
+use conductor_core::tools::{self, ToolReport};
+
+/// Print `value` as pretty JSON, the output of list and show commands under `--json`.
+pub(crate) fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
 
 /// Returns `true` if `s` looks like a ULID: exactly 26 uppercase alphanumeric chars.
 /// Used to distinguish internal ULIDs (e.g. "01HXYZ...") from external source IDs (e.g. "680").
@@ -10,21 +19,20 @@ pub(crate) fn looks_like_ulid(s: &str) -> bool {
     s.len() == 26 && s.chars().all(|c| c.is_ascii_alphanumeric())
 }
 
+/// Warn about missing required tools, from the cached tool report (probed
+/// again once it is a day old).
 pub(crate) fn check_prerequisites() {
-    let mut missing = Vec::new();
-    if Command::new("gh").arg("--version").output().is_err() {
-        missing.push("  - gh (GitHub CLI): https://cli.github.com");
-    }
-    if Command::new("tmux").arg("-V").output().is_err() {
-        missing.push("  - tmux: https://github.com/tmux/tmux");
-    }
-    if Command::new("claude").arg("--version").output().is_err() {
-        missing
-            .push("  - claude (Claude Code CLI): https://docs.anthropic.com/en/docs/claude-code");
-    }
+    let report = ToolReport::current(tools::CACHE_TTL);
+    let missing: Vec<String> = report
+        .missing_required()
+        .map(|t| format!("  - {} ({}): {}", t.name, t.purpose, t.install))
+        .collect();
     if !missing.is_empty() {
         eprintln!("conductor: missing prerequisites:\n{}", missing.join("\n"));
-        eprintln!("Some commands may not work until these are resolved.\n");
+        eprintln!(
+            "Some commands may not work until these are resolved; \
+             re-check with `conductor doctor tools --refresh`.\n"
+        );
     }
 }
 
@@ -62,6 +70,8 @@ pub(crate) fn generate_plan(
     worktree_path: &str,
     prompt: &str,
     config: &conductor_core::config::Config,
+    sandbox: &Sandbox,
+    run_id: &str,
 ) -> Option<Vec<PlanStep>> {
     let plan_prompt = format!(
         "You are planning a software development task. \
@@ -83,7 +93,11 @@ pub(crate) fn generate_plan(
     if let Some(val) = config.general.agent_permission_mode.cli_flag_value() {
         cmd.arg(val);
     }
-    let output = cmd.current_dir(worktree_path).output().ok()?;
+    let output = sandbox
+        .wrap(cmd, run_id, Path::new(worktree_path))
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
 
     if !output.status.success() {
         return None;
@@ -193,9 +207,163 @@ pub(crate) fn sync_repo(
     }
 }
 
+/// Render a markdown ticket body as plain terminal text: headings are
+/// underlined, list bullets and quotes are drawn, code blocks are indented,
+/// and inline emphasis, code ticks, links and HTML comments are flattened.
+pub(crate) fn render_markdown(text: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut in_code = false;
+    let mut in_comment = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            out.push(format!("    {line}"));
+            continue;
+        }
+        let mut line = line.to_string();
+        let had_comment = in_comment || line.contains("<!--");
+        if in_comment {
+            match line.find("-->") {
+                Some(end) => {
+                    line = line[end + 3..].to_string();
+                    in_comment = false;
+                }
+                None => continue,
+            }
+        }
+        while let Some(start) = line.find("<!--") {
+            match line[start..].find("-->") {
+                Some(end) => line.replace_range(start..start + end + 3, ""),
+                None => {
+                    line.truncate(start);
+                    in_comment = true;
+                }
+            }
+        }
+        if had_comment && line.trim().is_empty() {
+            continue;
+        }
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let rest = line.trim_start();
+
+        let heading = rest.trim_start_matches('#');
+        if rest.starts_with('#') && rest.len() - heading.len() <= 6 && heading.starts_with(' ') {
+            let title = render_inline(heading.trim());
+            let rule = if rest.starts_with("##") { '-' } else { '=' };
+            out.push(title.clone());
+            out.push(rule.to_string().repeat(title.chars().count()));
+            continue;
+        }
+        if ["---", "***", "___"].contains(&rest.trim_end()) {
+            out.push("─".repeat(40));
+            continue;
+        }
+        if let Some(quoted) = rest.strip_prefix('>') {
+            out.push(format!("{indent}│ {}", render_inline(quoted.trim_start())));
+            continue;
+        }
+        let item = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| rest.strip_prefix(bullet));
+        if let Some(item) = item {
+            let (mark, item) = if let Some(done) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                ("☑", done)
+            } else if let Some(todo) = item.strip_prefix("[ ] ") {
+                ("☐", todo)
+            } else {
+                ("•", item)
+            };
+            out.push(format!("{indent}  {mark} {}", render_inline(item)));
+            continue;
+        }
+        out.push(format!("{indent}{}", render_inline(rest)));
+    }
+
+    // Collapse runs of blank lines left by comments and markup.
+    let mut rendered = String::new();
+    let mut blank = true;
+    for line in out {
+        let is_blank = line.trim().is_empty();
+        if is_blank && blank {
+            continue;
+        }
+        blank = is_blank;
+        rendered.push_str(line.trim_end());
+        rendered.push('\n');
+    }
+    rendered.trim_end().to_string()
+}
+
+/// Flatten inline markdown: `![alt](url)` and `[text](url)` links,
+/// `**bold**` and `` `code` `` markers.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let link = rest[open + 1..].find("](").and_then(|close| {
+            let url_start = open + 1 + close + 2;
+            rest[url_start..]
+                .find(')')
+                .map(|end| (close, url_start, url_start + end))
+        });
+        let Some((close, url_start, url_end)) = link else {
+            out.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        };
+        let is_image = rest[..open].ends_with('!');
+        out.push_str(&rest[..if is_image { open - 1 } else { open }]);
+        let label = &rest[open + 1..open + 1 + close];
+        let url = &rest[url_start..url_end];
+        if is_image {
+            out.push_str(&format!("[image: {label}]"));
+        } else if label == url {
+            out.push_str(url);
+        } else {
+            out.push_str(&format!("{label} <{url}>"));
+        }
+        rest = &rest[url_end + 1..];
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace('`', "")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{read_and_maybe_cleanup_prompt_file, truncate_str};
+    use super::{read_and_maybe_cleanup_prompt_file, render_markdown, truncate_str};
+
+    #[test]
+    fn render_markdown_flattens_common_markup() {
+        let body = "## Summary\n\
+                    <!-- template hint -->\n\
+                    Fix the **login** flow, see [the doc](https://x.dev/d).\n\
+                    \n\
+                    \n\
+                    - [x] repro\n\
+                    - [ ] fix `auth.rs`\n\
+                    > quoted\n\
+                    ```rust\n\
+                    let a = 1;\n\
+                    ```\n\
+                    ![shot](https://x.dev/s.png)";
+        assert_eq!(
+            render_markdown(body),
+            "Summary\n\
+             -------\n\
+             Fix the login flow, see the doc <https://x.dev/d>.\n\
+             \n  ☑ repro\n  ☐ fix auth.rs\n\
+             │ quoted\n\
+             \x20   let a = 1;\n\
+             [image: shot]"
+        );
+    }
 
     #[test]
     fn internal_temp_file_is_deleted_after_read() {
@@ -25,6 +25,27 @@ fn main() -> Result<()> {
         .init();
 
     let cli = Cli::parse();
+    let json = cli.json;
+
+    // `status` runs from shell prompts and tmux status refreshes, so it skips
+    // migrations and the prerequisite checks below.
+    // Completion runs on every Tab press, for the same reason.
+    match cli.command {
+        Commands::Status { porcelain } => return handlers::status::handle_status(porcelain, json),
+        Commands::Completions { shell } => return handlers::completions::handle_completions(shell),
+        Commands::Complete { ref words } => return handlers::completions::handle_complete(words),
+        // Encrypting rewrites the database file, so it must not hold it open.
+        Commands::Db { command } => return handlers::db::handle_db(command),
+        // Diagnoses the environment, so it must work before the database can open.
+        Commands::Doctor { command } => return handlers::doctor::handle_doctor(command, json),
+        #[cfg(unix)]
+        Commands::Daemon {
+            command: Some(command),
+        } => return handlers::daemon::handle_daemon_client(command, json),
+        #[cfg(not(unix))]
+        Commands::Daemon { .. } => anyhow::bail!("conductor daemon requires a unix platform"),
+        _ => {}
+    }
 
     // Headless agent subprocesses use compat mode so they tolerate a DB schema
     // that is ahead of this binary (e.g. after an implement step applied a new
@@ -47,19 +68,19 @@ fn main() -> Result<()> {
 
     match cli.command {
         Commands::Repo { command } => {
-            handlers::repo::handle_repo(command, &conductor.conn, &conductor.config)?
+            handlers::repo::handle_repo(command, &conductor.conn, &conductor.config, json)?
         }
         Commands::Worktree { command } => {
-            handlers::worktree::handle_worktree(command, &conductor.conn, &conductor.config)?
+            handlers::worktree::handle_worktree(command, &conductor.conn, &conductor.config, json)?
         }
         Commands::Agent { command } => {
-            handlers::agent::handle_agent(command, &conductor.conn, &conductor.config)?
+            handlers::agent::handle_agent(command, &conductor.conn, &conductor.config, json)?
         }
         Commands::Tickets { command } => {
-            handlers::tickets::handle_tickets(command, &conductor.conn, &conductor.config)?
+            handlers::tickets::handle_tickets(command, &conductor.conn, &conductor.config, json)?
         }
         Commands::Workflow { command } => {
-            handlers::workflow::handle_workflow(command, &conductor.conn, &conductor.config)?
+            handlers::workflow::handle_workflow(command, &conductor.conn, &conductor.config, json)?
         }
         Commands::Setup { command } => handlers::setup::handle_setup(command)?,
         Commands::Mcp { command } => handlers::mcp::handle_mcp(command)?,
@@ -72,6 +93,66 @@ fn main() -> Result<()> {
             &conductor.conn,
             &conductor.config,
         )?,
+        Commands::Changeset { command } => handlers::changeset::handle_changeset(
+            command,
+            &conductor.conn,
+            &conductor.config,
+            json,
+        )?,
+        Commands::Token { command } => {
+            handlers::token::handle_token(command, &conductor.conn, json)?
+        }
+        Commands::Tmux { command } => {
+            handlers::tmux::handle_tmux(command, &conductor.conn, &conductor.config)?
+        }
+        Commands::Workspace { command } => handlers::workspace::handle_workspace(
+            command,
+            &conductor.conn,
+            &conductor.config,
+            json,
+        )?,
+        Commands::Init {
+            test_command,
+            setup,
+            preamble,
+            branch_prefix,
+            protected_paths,
+            slug,
+        } => handlers::init::handle_init(
+            handlers::init::InitArgs {
+                test_command,
+                setup,
+                preamble,
+                branch_prefix,
+                protected_paths,
+                slug,
+            },
+            &conductor.conn,
+            &conductor.config,
+        )?,
+        Commands::Report { command } => {
+            handlers::report::handle_report(command, &conductor.conn, json)?
+        }
+        Commands::Prune { dry_run } => {
+            handlers::prune::handle_prune(dry_run, &conductor.conn, &conductor.config)?
+        }
+        #[cfg(unix)]
+        Commands::Daemon { command: None } => {
+            handlers::daemon::run_daemon(conductor.conn, &conductor.config)?
+        }
+        #[cfg(unix)]
+        Commands::Daemon { command: Some(_) } => {
+            unreachable!("handled before opening the database")
+        }
+        #[cfg(not(unix))]
+        Commands::Daemon { .. } => unreachable!("rejected before opening the database"),
+        Commands::Status { .. }
+        | Commands::Completions { .. }
+        | Commands::Complete { .. }
+        | Commands::Db { .. }
+        | Commands::Doctor { .. } => {
+            unreachable!("handled before opening the database")
+        }
     }
 
     Ok(())
@@ -472,13 +472,10 @@ pub(crate) fn conversation_log_tail(
     worktree_path: &Path,
     claude_config_dir: Option<&Path>,
 ) -> Option<String> {
-    let escaped = worktree_path.to_str()?.replace('/', "-");
+    let escaped = worktree_path.to_str()?.replace(['/', '\\', ':'], "-");
     let base = match claude_config_dir {
         Some(dir) => dir.to_path_buf(),
-        None => {
-            let home = std::env::var("HOME").ok()?;
-            PathBuf::from(home).join(".claude")
-        }
+        None => dirs::home_dir()?.join(".claude"),
     };
     let projects_dir = base.join("projects").join(&escaped);
     conversation_log_tail_from_dir(&projects_dir)
@@ -24,7 +24,7 @@ pub(super) fn conductor_tools() -> Vec<Tool> {
     vec![
         Tool::new(
             "conductor_list_tickets",
-            "List tickets for a repo. Filters: label, search, include_closed. \
+            "List tickets for a repo. Filters: label, search, include_closed, current_sprint. \
              Individual tickets with full body available at `conductor://ticket/{repo}/{id}`.",
             schema(&[
                 ("repo", "Repo slug (e.g. my-repo)", true),
@@ -39,6 +39,11 @@ pub(super) fn conductor_tools() -> Vec<Tool> {
                     "Set to 'true' to include closed tickets (default: open only)",
                     false,
                 ),
+                (
+                    "current_sprint",
+                    "Set to 'true' to only list tickets in the current sprint / milestone",
+                    false,
+                ),
             ]),
         ),
         Tool::new(
@@ -48,7 +53,7 @@ pub(super) fn conductor_tools() -> Vec<Tool> {
              Supports pagination via limit (default 50) and offset (default 0).",
             schema(&[
                 ("repo", "Repo slug", true),
-                ("status", "Filter by status: 'active' (default) or 'all'", false),
+                ("status", "Filter by status: 'active' (default; includes needs_review and parked) or 'all'", false),
                 ("limit", "Max worktrees to return (default 50)", false),
                 (
                     "offset",
@@ -131,6 +136,12 @@ pub(super) fn conductor_tools() -> Vec<Tool> {
             schema(&[
                 ("repo", "Repo slug", true),
                 ("slug", "Worktree slug to delete", true),
+                (
+                    "reason",
+                    "Optional: why unmerged work is being dropped — superseded, wontfix, \
+                     blocked or experiment. Ignored when the branch is merged.",
+                    false,
+                ),
             ]),
         )
         .with_annotations(ToolAnnotations::new().destructive(true).read_only(false)),
@@ -27,12 +27,14 @@ pub(super) fn tool_list_tickets(
     let labels = parse_comma_arg(args, "label");
     let search = get_arg(args, "search").map(|s| s.to_string());
     let include_closed = get_arg(args, "include_closed") == Some("true");
+    let current_sprint_only = get_arg(args, "current_sprint") == Some("true");
 
     let filter = TicketFilter {
         labels,
         search,
         include_closed,
         unlabeled_only: false,
+        current_sprint_only,
     };
 
     let conn = &conductor.conn;
@@ -65,7 +67,7 @@ pub(super) fn tool_sync_tickets(
 ) -> CallToolResult {
     use conductor_core::issue_source::IssueSourceManager;
     use conductor_core::repo::RepoManager;
-    use conductor_core::ticket_source::TicketSource;
+    use conductor_core::ticket_source::{SourceContext, TicketSource, TicketSourceRegistry};
     use conductor_core::tickets::TicketSyncer;
     let repo_slug = require_arg!(args, "repo");
     let ticket_id_arg = get_arg(args, "ticket_id");
@@ -124,28 +126,32 @@ pub(super) fn tool_sync_tickets(
         ));
     }
 
-    // Full-sync path (unchanged)
+    // Full-sync path
     let mut total_synced = 0usize;
     let mut total_closed = 0usize;
     let mut errors = Vec::new();
 
-    for source in sources {
-        let ts = match TicketSource::from_issue_source(&source) {
-            Ok(t) => t,
+    let ctx = SourceContext {
+        repo_slug: &repo.slug,
+        remote_url: &repo.remote_url,
+        token: None,
+    };
+    for fetcher in TicketSourceRegistry::default().fetchers(&sources, &ctx) {
+        let f = match fetcher {
+            Ok(f) => f,
             Err(e) => {
                 errors.push(e.to_string());
                 continue;
             }
         };
-        let fetch_result = ts.with_repo_slug(&repo.slug).sync(None);
-        match fetch_result {
+        match f.fetch() {
             Ok(tickets) => {
                 let (synced, closed) =
-                    syncer.sync_and_close_tickets(&repo.id, &source.source_type, &tickets);
+                    syncer.sync_and_close_tickets(&repo.id, f.source_type(), &tickets);
                 total_synced += synced;
                 total_closed += closed;
             }
-            Err(e) => errors.push(format!("{}: {e}", source.source_type)),
+            Err(e) => errors.push(format!("{}: {e}", f.source_type())),
         }
     }
     if errors.is_empty() {
@@ -206,6 +212,8 @@ pub(super) fn tool_upsert_ticket(
         blocked_by,
         children,
         parent,
+        sprint: None,
+        sprint_current: false,
     };
 
     let syncer = TicketSyncer::new(conn);
@@ -432,6 +440,8 @@ mod tests {
             blocked_by: vec![],
             children: vec![],
             parent: None,
+            sprint: None,
+            sprint_current: false,
         };
         let syncer = TicketSyncer::new(&conn);
         syncer.sync_and_close_tickets(&repo.id, "github", &[ticket]);
@@ -1433,6 +1433,8 @@ workflow wt-only-wf {
             blocked_by: vec![],
             children: vec![],
             parent: None,
+            sprint: None,
+            sprint_current: false,
         };
         let syncer = TicketSyncer::new(&conn);
         syncer.sync_and_close_tickets(&repo.id, "github", &[ticket]);
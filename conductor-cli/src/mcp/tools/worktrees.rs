@@ -39,7 +39,10 @@ pub(super) fn tool_list_worktrees(
     for wt in &worktrees {
         out.push_str(&format!(
             "slug: {}\nbranch: {}\nstatus: {}\npath: {}\n\n",
-            wt.slug, wt.branch, wt.status, wt.path
+            wt.slug,
+            wt.branch,
+            wt.status_label(),
+            wt.path
         ));
     }
     if worktrees.len() == limit {
@@ -78,7 +81,7 @@ pub(super) fn tool_get_worktree(
         "slug: {}\nbranch: {}\nstatus: {}\npath: {}\nmodel: {}\ncreated_at: {}\n",
         wt.slug,
         wt.branch,
-        wt.status,
+        wt.status_label(),
         wt.path,
         wt.model.as_deref().unwrap_or("default"),
         wt.created_at,
@@ -206,15 +209,24 @@ pub(super) fn tool_delete_worktree(
     conductor: &Conductor,
     args: &serde_json::Map<String, Value>,
 ) -> CallToolResult {
-    use conductor_core::worktree::WorktreeManager;
+    use conductor_core::worktree::{AbandonReason, WorktreeManager};
 
     let repo_slug = require_arg!(args, "repo");
     let slug = require_arg!(args, "slug");
+    let reason = match get_arg(args, "reason").map(str::parse::<AbandonReason>) {
+        Some(Ok(r)) => Some(r),
+        Some(Err(e)) => return tool_err(e),
+        None => None,
+    };
     let conn = &conductor.conn;
     let config = &conductor.config;
     let wt_mgr = WorktreeManager::new(conn, config);
-    match wt_mgr.delete(repo_slug, slug) {
-        Ok(wt) => tool_ok(format!("Deleted worktree {}.", wt.slug)),
+    match wt_mgr.delete(repo_slug, slug, reason) {
+        Ok(wt) => tool_ok(format!(
+            "Deleted worktree {} (marked {}).",
+            wt.slug,
+            wt.status_label()
+        )),
         Err(e) => tool_err(e),
     }
 }
@@ -233,7 +245,10 @@ pub(super) fn tool_set_base_branch(
     let conn = &conductor.conn;
     let config = &conductor.config;
     let wt_mgr = WorktreeManager::new(conn, config);
-    let opts = conductor_core::worktree::SetBaseBranchOptions { rebase };
+    let opts = conductor_core::worktree::SetBaseBranchOptions {
+        rebase,
+        ..Default::default()
+    };
     match wt_mgr.set_base_branch(repo_slug, name, base_branch, opts) {
         Ok(()) => {
             let label = base_branch.unwrap_or("(repo default)");
@@ -307,7 +322,7 @@ pub(super) fn tool_push_worktree(
     let conn = &conductor.conn;
     let config = &conductor.config;
     let wt_mgr = WorktreeManager::new(conn, config);
-    match wt_mgr.push(repo_slug, slug) {
+    match wt_mgr.push(repo_slug, slug, Default::default()) {
         Ok(msg) => tool_ok(msg),
         Err(e) => tool_err(e),
     }
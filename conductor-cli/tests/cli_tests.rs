@@ -49,6 +49,50 @@ fn worktree_list_empty() {
         .stdout(predicate::str::contains("No worktrees"));
 }
 
+#[test]
+fn worktree_list_json_and_format_validation() {
+    let dir = tempfile::tempdir().unwrap();
+    conductor_cmd(dir.path())
+        .args(["worktree", "list", "--format", "json", "--stale"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[]"));
+    conductor_cmd(dir.path())
+        .args(["worktree", "list", "--format", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown format"));
+}
+
+#[test]
+fn global_json_flag_prints_parseable_lists() {
+    let dir = tempfile::tempdir().unwrap();
+    conductor_cmd(dir.path())
+        .args(["repo", "register", "https://github.com/example/myrepo.git"])
+        .assert()
+        .success();
+
+    // Accepted before or after the subcommand.
+    for args in [["--json", "repo", "list"], ["repo", "list", "--json"]] {
+        let out = conductor_cmd(dir.path()).args(args).output().unwrap();
+        assert!(out.status.success());
+        let repos: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+        assert_eq!(repos[0]["slug"], "myrepo");
+    }
+
+    // Empty lists are `[]`, not the human "nothing here" message.
+    for args in [
+        ["tickets", "list", "--json"],
+        ["worktree", "list", "--json"],
+        ["token", "list", "--json"],
+    ] {
+        let out = conductor_cmd(dir.path()).args(args).output().unwrap();
+        assert!(out.status.success(), "{args:?}");
+        let value: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+        assert_eq!(value, serde_json::json!([]), "{args:?}");
+    }
+}
+
 #[test]
 fn ticket_list_empty() {
     let dir = tempfile::tempdir().unwrap();
@@ -59,6 +103,56 @@ fn ticket_list_empty() {
         .stdout(predicate::str::contains("No tickets"));
 }
 
+#[test]
+fn ticket_show_renders_body_and_resolves_source_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    conductor_cmd(dir.path())
+        .args(["repo", "register", "https://github.com/example/myrepo.git"])
+        .assert()
+        .success();
+    conductor_cmd(dir.path())
+        .args([
+            "tickets",
+            "upsert",
+            "myrepo",
+            "--source-type",
+            "jira",
+            "--source-id",
+            "PROJ-7",
+            "--title",
+            "Fix login",
+            "--state",
+            "open",
+            "--body",
+            "## Steps\n- [ ] reproduce the **bug**",
+            "--labels",
+            "bug,auth",
+        ])
+        .assert()
+        .success();
+
+    conductor_cmd(dir.path())
+        .args(["tickets", "show", "jira:PROJ-7"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("jira #PROJ-7  Fix login"))
+        .stdout(predicate::str::contains("Labels:     bug, auth"))
+        .stdout(predicate::str::contains(
+            "Steps\n-----\n  ☐ reproduce the bug",
+        ));
+
+    conductor_cmd(dir.path())
+        .args(["tickets", "show", "PROJ-7", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"repo_slug\": \"myrepo\""));
+
+    conductor_cmd(dir.path())
+        .args(["tickets", "show", "github:PROJ-7"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn invalid_subcommand_nonzero() {
     let dir = tempfile::tempdir().unwrap();
@@ -261,3 +355,99 @@ fn workflow_validate_all_parse_error() {
         .failure()
         .stdout(predicate::str::contains("FAIL"));
 }
+
+#[test]
+fn token_create_list_and_revoke() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let out = conductor_cmd(dir.path())
+        .args(["token", "create", "ci", "--scope", "read-only"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("cdt_"), "secret missing from: {stdout}");
+    let id = stdout
+        .split(['(', ')'])
+        .nth(1)
+        .expect("token id in output")
+        .to_string();
+
+    conductor_cmd(dir.path())
+        .args(["token", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("read-only").and(predicate::str::contains("never used")));
+
+    conductor_cmd(dir.path())
+        .args(["token", "revoke", &id])
+        .assert()
+        .success();
+
+    conductor_cmd(dir.path())
+        .args(["token", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("revoked"));
+
+    conductor_cmd(dir.path())
+        .args(["token", "create", "bad", "--scope", "superuser"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn repo_set_workspace_and_workspace_list() {
+    let dir = tempfile::tempdir().unwrap();
+    let fast = dir.path().join("fast");
+    std::fs::create_dir_all(&fast).unwrap();
+
+    conductor_cmd(dir.path())
+        .args(["repo", "register", "https://github.com/example/myrepo.git"])
+        .assert()
+        .success();
+
+    conductor_cmd(dir.path())
+        .env("CONDUCTOR_TEST_FAST_DRIVE", &fast)
+        .args([
+            "repo",
+            "set-workspace",
+            "myrepo",
+            "$CONDUCTOR_TEST_FAST_DRIVE/myrepo",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            fast.join("myrepo").to_string_lossy().to_string(),
+        ));
+
+    // The override directory has not been created yet, so it is flagged.
+    conductor_cmd(dir.path())
+        .args(["workspace", "list"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("(default)")
+                .and(predicate::str::contains("status: missing"))
+                .and(predicate::str::contains("repos:  myrepo")),
+        );
+
+    conductor_cmd(dir.path())
+        .args(["repo", "set-workspace", "myrepo", "relative/dir"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn agent_stop_all_with_nothing_running() {
+    let dir = tempfile::tempdir().unwrap();
+    conductor_cmd(dir.path())
+        .args(["agent", "stop", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No running agents"));
+    conductor_cmd(dir.path())
+        .args(["agent", "stop"])
+        .assert()
+        .failure();
+}
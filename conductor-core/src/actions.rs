@@ -0,0 +1,197 @@
+//! The user-facing actions conductor offers, described independently of any
+//! front end. The web server lists them at `GET /api/commands` for its
+//! command palette, pairing each with the endpoint that performs it.
+
+use serde::Serialize;
+
+/// Stable identifier of an action. Front ends match on it exhaustively to
+/// decide how to perform the action, so a new variant does not compile until
+/// each of them knows what to do with it.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionId {
+    SyncTickets,
+    GroomTickets,
+    CreateWorktree,
+    DeleteWorktree,
+    CommitWorktree,
+    PushWorktree,
+    CreatePr,
+    StartAgent,
+    StopAgent,
+    RunWorkflow,
+    CancelWorkflowRun,
+    ResumeWorkflowRun,
+    ApproveGate,
+    RejectGate,
+}
+
+/// What a parameter refers to, so a front end can offer a picker instead of
+/// a free-text field.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    Repo,
+    Worktree,
+    WorkflowRun,
+    /// A workflow definition name, available in the chosen worktree.
+    Workflow,
+    Text,
+    Bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActionParam {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: ParamKind,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActionSpec {
+    pub id: ActionId,
+    pub label: &'static str,
+    pub description: &'static str,
+    pub params: &'static [ActionParam],
+}
+
+const fn required(name: &'static str, label: &'static str, kind: ParamKind) -> ActionParam {
+    ActionParam {
+        name,
+        label,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, label: &'static str, kind: ParamKind) -> ActionParam {
+    ActionParam {
+        name,
+        label,
+        kind,
+        required: false,
+    }
+}
+
+const REPO: ActionParam = required("repo_id", "Repo", ParamKind::Repo);
+const WORKTREE: ActionParam = required("worktree_id", "Worktree", ParamKind::Worktree);
+const RUN: ActionParam = required("run_id", "Workflow run", ParamKind::WorkflowRun);
+
+/// Every action, in the order a palette should list them.
+pub const ACTIONS: &[ActionSpec] = &[
+    ActionSpec {
+        id: ActionId::SyncTickets,
+        label: "Sync tickets",
+        description: "Fetch tickets from the repo's issue sources",
+        params: &[REPO],
+    },
+    ActionSpec {
+        id: ActionId::GroomTickets,
+        label: "Groom new tickets",
+        description: "Propose labels, estimates and workflows for ungroomed tickets",
+        params: &[REPO],
+    },
+    ActionSpec {
+        id: ActionId::CreateWorktree,
+        label: "Create worktree",
+        description: "Branch a new worktree off the repo's base branch",
+        params: &[
+            REPO,
+            required("name", "Name", ParamKind::Text),
+            optional("from_branch", "Base branch", ParamKind::Text),
+        ],
+    },
+    ActionSpec {
+        id: ActionId::DeleteWorktree,
+        label: "Delete worktree",
+        description: "Remove the worktree and its local branch",
+        params: &[WORKTREE],
+    },
+    ActionSpec {
+        id: ActionId::CommitWorktree,
+        label: "Commit worktree changes",
+        description:
+            "Commit the worktree's changes, described by the agent's result if no message is given",
+        params: &[
+            WORKTREE,
+            optional("message", "Message", ParamKind::Text),
+            optional("all", "Stage all changes", ParamKind::Bool),
+        ],
+    },
+    ActionSpec {
+        id: ActionId::PushWorktree,
+        label: "Push worktree",
+        description: "Push the worktree branch to origin",
+        params: &[WORKTREE],
+    },
+    ActionSpec {
+        id: ActionId::CreatePr,
+        label: "Open pull request",
+        description: "Create a pull request for the worktree branch",
+        params: &[WORKTREE, optional("draft", "Draft", ParamKind::Bool)],
+    },
+    ActionSpec {
+        id: ActionId::StartAgent,
+        label: "Start agent",
+        description: "Start an agent run in the worktree",
+        params: &[WORKTREE, required("prompt", "Prompt", ParamKind::Text)],
+    },
+    ActionSpec {
+        id: ActionId::StopAgent,
+        label: "Stop agent",
+        description: "Stop the worktree's running agent",
+        params: &[WORKTREE],
+    },
+    ActionSpec {
+        id: ActionId::RunWorkflow,
+        label: "Run workflow",
+        description: "Start a workflow in the worktree",
+        params: &[WORKTREE, required("name", "Workflow", ParamKind::Workflow)],
+    },
+    ActionSpec {
+        id: ActionId::CancelWorkflowRun,
+        label: "Cancel workflow run",
+        description: "Stop a running workflow",
+        params: &[RUN],
+    },
+    ActionSpec {
+        id: ActionId::ResumeWorkflowRun,
+        label: "Resume workflow run",
+        description: "Resume a failed or interrupted workflow from where it stopped",
+        params: &[RUN],
+    },
+    ActionSpec {
+        id: ActionId::ApproveGate,
+        label: "Approve gate",
+        description: "Approve the workflow's waiting gate and continue",
+        params: &[RUN, optional("feedback", "Feedback", ParamKind::Text)],
+    },
+    ActionSpec {
+        id: ActionId::RejectGate,
+        label: "Reject gate",
+        description: "Reject the workflow's waiting gate, failing the run",
+        params: &[RUN],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn action_ids_and_param_names_are_unique() {
+        let mut ids = HashSet::new();
+        for action in ACTIONS {
+            assert!(ids.insert(action.id), "{:?} listed twice", action.id);
+            let mut names = HashSet::new();
+            for param in action.params {
+                assert!(names.insert(param.name), "{:?}.{}", action.id, param.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Per-day activity of each repo or worktree — commits, agent runs and agent
+//! cost — behind the heat map in `/api/reports/activity` and the TUI Reports
+//! view.
+//!
+//! Runs and cost come from `agent_runs`. Commits are counted with `git log` in
+//! each repo's main checkout: every local branch for a repo, and the commits a
+//! worktree's branch adds over its base for a worktree. A checkout or branch
+//! that is gone simply contributes no commits. Repos private to someone other
+//! than the viewer are left out entirely.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+use crate::repo::RepoManager;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+use crate::worktree::WorktreeManager;
+
+/// Days covered when the caller does not pick a window.
+pub const DEFAULT_ACTIVITY_DAYS: i64 = 90;
+
+/// Longest window [`activity_heatmap`] accepts.
+const MAX_ACTIVITY_DAYS: i64 = 366;
+
+/// What each heat map row stands for.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityGroupBy {
+    #[default]
+    Repo,
+    Worktree,
+}
+
+impl ActivityGroupBy {
+    pub const ALL: [Self; 2] = [Self::Repo, Self::Worktree];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Repo => "repo",
+            Self::Worktree => "worktree",
+        }
+    }
+}
+
+impl fmt::Display for ActivityGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ActivityGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.as_str() == s)
+            .ok_or_else(|| format!("unknown grouping '{s}' (expected repo or worktree)"))
+    }
+}
+
+/// Activity of one repo or worktree on one day.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActivityDay {
+    /// `YYYY-MM-DD`, UTC.
+    pub day: String,
+    pub commits: i64,
+    pub runs: i64,
+    pub cost_usd: f64,
+}
+
+impl ActivityDay {
+    /// Commits plus agent runs; what the heat map shades by.
+    pub fn events(&self) -> i64 {
+        self.commits + self.runs
+    }
+}
+
+/// One heat map row: a repo or worktree with its totals and active days.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityRow {
+    /// Repo or worktree ID.
+    pub key: String,
+    /// Repo slug, or `repo/worktree` slug.
+    pub label: String,
+    pub commits: i64,
+    pub runs: i64,
+    pub cost_usd: f64,
+    /// Days with any activity, oldest first. Idle days are omitted.
+    pub days: Vec<ActivityDay>,
+}
+
+/// Heat map over the `days` days ending today, starting at `start`.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityHeatmap {
+    /// First day covered, `YYYY-MM-DD` (UTC).
+    pub start: String,
+    pub days: i64,
+    /// Rows with any activity, most expensive first.
+    pub rows: Vec<ActivityRow>,
+}
+
+/// Where to count a row's commits: a checkout and the `git log` revisions.
+struct CommitSource {
+    key: String,
+    label: String,
+    dir: String,
+    revs: Vec<String>,
+}
+
+/// Build the activity heat map for the last `days` days (today included),
+/// over the repos `viewer` can see.
+pub fn activity_heatmap(
+    conn: &Connection,
+    config: &Config,
+    viewer: &Viewer,
+    days: i64,
+    group_by: ActivityGroupBy,
+) -> Result<ActivityHeatmap> {
+    if !(1..=MAX_ACTIVITY_DAYS).contains(&days) {
+        return Err(ConductorError::InvalidInput(format!(
+            "days must be between 1 and {MAX_ACTIVITY_DAYS}, got {days}"
+        )));
+    }
+    let start = (Utc::now().date_naive() - Duration::days(days - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut cells: BTreeMap<String, BTreeMap<String, ActivityDay>> = BTreeMap::new();
+    let mut labels: BTreeMap<String, String> = BTreeMap::new();
+
+    for (key, day, runs, cost_usd) in run_counts(conn, viewer, &start, group_by)? {
+        let cell = cells
+            .entry(key)
+            .or_default()
+            .entry(day.clone())
+            .or_default();
+        cell.day = day;
+        cell.runs += runs;
+        cell.cost_usd += cost_usd;
+    }
+
+    for source in commit_sources(conn, config, viewer, &start, group_by, &cells)? {
+        for day in commit_days(&source, &start) {
+            let cell = cells
+                .entry(source.key.clone())
+                .or_default()
+                .entry(day.clone())
+                .or_default();
+            cell.day = day;
+            cell.commits += 1;
+        }
+        labels.insert(source.key, source.label);
+    }
+
+    let mut rows: Vec<ActivityRow> = cells
+        .into_iter()
+        .map(|(key, by_day)| {
+            let days: Vec<ActivityDay> = by_day.into_values().collect();
+            ActivityRow {
+                label: labels.get(&key).cloned().unwrap_or_else(|| key.clone()),
+                key,
+                commits: days.iter().map(|d| d.commits).sum(),
+                runs: days.iter().map(|d| d.runs).sum(),
+                cost_usd: days.iter().map(|d| d.cost_usd).sum(),
+                days,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.cost_usd
+            .total_cmp(&a.cost_usd)
+            .then(b.commits.cmp(&a.commits))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    Ok(ActivityHeatmap { start, days, rows })
+}
+
+/// `(key, day, runs, cost)` for agent runs started on or after `start`.
+/// Runs outside any repo (or worktree) are left out.
+fn run_counts(
+    conn: &Connection,
+    viewer: &Viewer,
+    start: &str,
+    group_by: ActivityGroupBy,
+) -> Result<Vec<(String, String, i64, f64)>> {
+    let key = match group_by {
+        ActivityGroupBy::Repo => "COALESCE(w.repo_id, a.repo_id)",
+        ActivityGroupBy::Worktree => "a.worktree_id",
+    };
+    let owner = viewer.owner_param();
+    let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":start", &start)];
+    let visible = match visible_repo_sql(viewer, "COALESCE(w.repo_id, a.repo_id, '')", ":viewer") {
+        Some(condition) => {
+            params.push((":viewer", &owner));
+            format!(" AND {condition}")
+        }
+        None => String::new(),
+    };
+    query_collect(
+        conn,
+        &format!(
+            "SELECT {key} AS key, date(a.started_at) AS day, COUNT(*) AS runs, \
+                    COALESCE(SUM(a.cost_usd), 0.0) AS cost_usd \
+             FROM agent_runs a \
+             LEFT JOIN worktrees w ON w.id = a.worktree_id \
+             WHERE a.started_at >= :start AND {key} IS NOT NULL{visible} \
+             GROUP BY 1, 2"
+        ),
+        params.as_slice(),
+        |row| {
+            Ok((
+                row.get("key")?,
+                row.get("day")?,
+                row.get("runs")?,
+                row.get("cost_usd")?,
+            ))
+        },
+    )
+}
+
+/// Every repo, or every worktree that is live, finished inside the window or
+/// already has runs in it.
+fn commit_sources(
+    conn: &Connection,
+    config: &Config,
+    viewer: &Viewer,
+    start: &str,
+    group_by: ActivityGroupBy,
+    with_runs: &BTreeMap<String, BTreeMap<String, ActivityDay>>,
+) -> Result<Vec<CommitSource>> {
+    let repos = RepoManager::new(conn, config).list_visible(viewer)?;
+    if group_by == ActivityGroupBy::Repo {
+        return Ok(repos
+            .into_iter()
+            .map(|r| CommitSource {
+                key: r.id,
+                label: r.slug,
+                dir: r.local_path,
+                revs: vec!["--branches".to_string()],
+            })
+            .collect());
+    }
+    let repos: BTreeMap<String, _> = repos.into_iter().map(|r| (r.id.clone(), r)).collect();
+    Ok(WorktreeManager::new(conn, config)
+        .list(None, false)?
+        .into_iter()
+        .filter(|w| {
+            w.is_active()
+                || w.completed_at.as_deref().is_some_and(|c| c >= start)
+                || with_runs.contains_key(&w.id)
+        })
+        .filter_map(|w| {
+            let repo = repos.get(&w.repo_id)?;
+            let base = w
+                .base_branch
+                .clone()
+                .unwrap_or_else(|| repo.default_branch.clone());
+            Some(CommitSource {
+                key: w.id,
+                label: format!("{}/{}", repo.slug, w.slug),
+                dir: repo.local_path.clone(),
+                revs: vec![w.branch, "--not".to_string(), base],
+            })
+        })
+        .collect())
+}
+
+/// Author day (UTC) of each non-merge commit in `source` since `start`.
+fn commit_days(source: &CommitSource, start: &str) -> Vec<String> {
+    let mut cmd = git_in(&source.dir);
+    cmd.env("TZ", "UTC")
+        .args([
+            "log",
+            "--no-merges",
+            "--date=format-local:%Y-%m-%d",
+            "--format=%ad",
+        ])
+        .arg(format!("--since={start}T00:00:00"))
+        .args(&source.revs)
+        .arg("--");
+    let Ok(output) = check_output(&mut cmd) else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|day| *day >= start)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentManager;
+    use crate::test_helpers::{create_test_conn, insert_test_repo, insert_test_worktree};
+
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let out = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@test.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@test.com")
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?} failed: {out:?}");
+    }
+
+    #[test]
+    fn heatmap_counts_commits_runs_and_cost_per_day() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_dir = tmp.path();
+        git(repo_dir, &["init", "-b", "main"]);
+        git(repo_dir, &["commit", "--allow-empty", "-m", "base"]);
+        git(repo_dir, &["checkout", "-b", "feat/test"]);
+        git(repo_dir, &["commit", "--allow-empty", "-m", "one"]);
+        git(repo_dir, &["commit", "--allow-empty", "-m", "two"]);
+        git(repo_dir, &["checkout", "main"]);
+
+        let conn = create_test_conn();
+        insert_test_repo(&conn, "r1", "test-repo", repo_dir.to_str().unwrap());
+        insert_test_worktree(&conn, "w1", "r1", "feat-test", "/tmp/ws/feat-test");
+        insert_test_repo(&conn, "r2", "idle-repo", "/nonexistent/idle");
+        let run = AgentManager::new(&conn)
+            .create_run(Some("w1"), "task", None)
+            .unwrap();
+        conn.execute(
+            "UPDATE agent_runs SET cost_usd = 1.5 WHERE id = ?1",
+            [&run.id],
+        )
+        .unwrap();
+        let config = Config::default();
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+
+        let by_repo =
+            activity_heatmap(&conn, &config, &Viewer::Local, 90, ActivityGroupBy::Repo).unwrap();
+        assert_eq!(by_repo.days, 90);
+        assert_eq!(by_repo.rows.len(), 1, "idle repo should be left out");
+        let row = &by_repo.rows[0];
+        assert_eq!(row.label, "test-repo");
+        assert_eq!((row.commits, row.runs, row.cost_usd), (3, 1, 1.5));
+        assert_eq!(row.days.len(), 1);
+        assert_eq!(row.days[0].day, today);
+        assert_eq!(row.days[0].events(), 4);
+
+        let by_worktree = activity_heatmap(
+            &conn,
+            &config,
+            &Viewer::Local,
+            90,
+            ActivityGroupBy::Worktree,
+        )
+        .unwrap();
+        assert_eq!(by_worktree.rows.len(), 1);
+        let row = &by_worktree.rows[0];
+        assert_eq!(row.label, "test-repo/feat-test");
+        assert_eq!((row.commits, row.runs), (2, 1));
+
+        crate::repo_visibility::RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let other = Viewer::web(Some("tok-b"));
+        for group_by in ActivityGroupBy::ALL {
+            let hidden = activity_heatmap(&conn, &config, &other, 90, group_by).unwrap();
+            assert!(hidden.rows.is_empty(), "{group_by}: {:?}", hidden.rows);
+        }
+        let owner = Viewer::web(Some("tok-a"));
+        let shown = activity_heatmap(&conn, &config, &owner, 90, ActivityGroupBy::Repo).unwrap();
+        assert_eq!(shown.rows.len(), 1);
+
+        assert!(
+            activity_heatmap(&conn, &config, &Viewer::Local, 0, ActivityGroupBy::Repo).is_err()
+        );
+        assert_eq!("worktree".parse(), Ok(ActivityGroupBy::Worktree));
+        assert!("day".parse::<ActivityGroupBy>().is_err());
+    }
+}
@@ -0,0 +1,502 @@
+//! Threshold alerts on agent spend and progress.
+//!
+//! [`AgentAlerts::check`] compares recent runs against the thresholds in
+//! `[notifications.cost_alerts]` and records each alert on the run it is
+//! about, so the pollers can notify once per alert and the UI can flag the
+//! run. Only runs started in the last [`ALERT_WINDOW_HOURS`] hours are
+//! checked, so turning a threshold on does not alert on old history.
+
+use chrono::{Duration, Utc};
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::config::CostAlertConfig;
+use crate::db::query_collect;
+use crate::error::Result;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+use super::log_parsing::count_turns_in_log;
+
+/// How far back [`AgentAlerts::check`] looks for runs to alert on.
+pub const ALERT_WINDOW_HOURS: i64 = 24;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentAlertKind {
+    /// A single run cost more than `run_cost_usd`.
+    RunCost,
+    /// Total agent spend for the UTC day passed `daily_spend_usd`. Recorded on
+    /// the run that was most recently active when it happened.
+    DailySpend,
+    /// A run took more than `turns_without_commits` turns and its worktree
+    /// has no commits since it started.
+    TurnsWithoutCommits,
+}
+
+impl AgentAlertKind {
+    /// Notification event kind, e.g. `agent_run.cost_exceeded`.
+    pub fn event_kind(self) -> &'static str {
+        match self {
+            Self::RunCost => "agent_run.cost_exceeded",
+            Self::DailySpend => "agent_run.daily_spend_exceeded",
+            Self::TurnsWithoutCommits => "agent_run.turns_without_commits",
+        }
+    }
+}
+
+impl std::fmt::Display for AgentAlertKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::RunCost => "run_cost",
+            Self::DailySpend => "daily_spend",
+            Self::TurnsWithoutCommits => "turns_without_commits",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for AgentAlertKind {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "run_cost" => Ok(Self::RunCost),
+            "daily_spend" => Ok(Self::DailySpend),
+            "turns_without_commits" => Ok(Self::TurnsWithoutCommits),
+            _ => Err(format!("unknown agent alert kind: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(AgentAlertKind);
+
+/// An alert recorded on an agent run, joined with its worktree for display.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentRunAlert {
+    pub run_id: String,
+    pub kind: AgentAlertKind,
+    /// Why the alert fired, e.g. `run cost $7.20 is over $5.00`.
+    pub detail: String,
+    pub created_at: String,
+    pub worktree_id: Option<String>,
+    pub repo_id: Option<String>,
+    pub repo_slug: Option<String>,
+    pub worktree_slug: Option<String>,
+    pub branch: Option<String>,
+}
+
+const ALERT_SELECT: &str = "SELECT al.run_id, al.kind, al.detail, al.created_at, \
+            a.worktree_id, r.id AS repo_id, r.slug AS repo_slug, \
+            w.slug AS worktree_slug, w.branch \
+     FROM agent_run_alerts al \
+     JOIN agent_runs a ON a.id = al.run_id \
+     LEFT JOIN worktrees w ON w.id = a.worktree_id \
+     LEFT JOIN repos r ON r.id = COALESCE(w.repo_id, a.repo_id)";
+
+/// A run over the turn threshold, pending the commit check.
+struct TurnCandidate {
+    id: String,
+    status: String,
+    num_turns: Option<i64>,
+    log_file: Option<String>,
+    started_at: String,
+    ended_at: Option<String>,
+    worktree_path: String,
+}
+
+pub struct AgentAlerts<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AgentAlerts<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Record alerts for recent runs that cross a configured threshold.
+    /// Returns only the alerts raised by this call; each kind is raised at
+    /// most once per run, and the daily spend alert at most once per day.
+    pub fn check(&self, config: &CostAlertConfig) -> Result<Vec<AgentRunAlert>> {
+        if !config.is_enabled() {
+            return Ok(Vec::new());
+        }
+        let now = Utc::now();
+        let since = (now - Duration::hours(ALERT_WINDOW_HOURS)).to_rfc3339();
+        let mut raised = Vec::new();
+
+        if let Some(limit) = config.run_cost_usd {
+            let over: Vec<(String, f64)> = query_collect(
+                self.conn,
+                "SELECT id, cost_usd FROM agent_runs a \
+                 WHERE cost_usd > :limit AND started_at >= :since \
+                   AND NOT EXISTS (SELECT 1 FROM agent_run_alerts al \
+                                   WHERE al.run_id = a.id AND al.kind = 'run_cost') \
+                 ORDER BY started_at",
+                named_params! { ":limit": limit, ":since": since },
+                |row| Ok((row.get("id")?, row.get("cost_usd")?)),
+            )?;
+            for (run_id, cost) in over {
+                let detail = format!("run cost ${cost:.2} is over ${limit:.2}");
+                self.raise(&run_id, AgentAlertKind::RunCost, &detail, &mut raised)?;
+            }
+        }
+
+        if let Some(limit) = config.daily_spend_usd {
+            let day_start = now
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .expect("midnight is a valid time")
+                .and_utc()
+                .to_rfc3339();
+            let (spent, already): (f64, bool) = self.conn.query_row(
+                "SELECT COALESCE(SUM(cost_usd), 0.0), \
+                        EXISTS (SELECT 1 FROM agent_run_alerts \
+                                WHERE kind = 'daily_spend' AND created_at >= :day_start) \
+                 FROM agent_runs WHERE started_at >= :day_start",
+                named_params! { ":day_start": day_start },
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            if spent > limit && !already {
+                let latest: Option<String> = self
+                    .conn
+                    .query_row(
+                        "SELECT id FROM agent_runs \
+                         WHERE started_at >= :day_start AND cost_usd IS NOT NULL \
+                         ORDER BY COALESCE(ended_at, started_at) DESC, id DESC LIMIT 1",
+                        named_params! { ":day_start": day_start },
+                        |row| row.get("id"),
+                    )
+                    .optional()?;
+                if let Some(run_id) = latest {
+                    let detail = format!("agent spend today is ${spent:.2}, over ${limit:.2}");
+                    self.raise(&run_id, AgentAlertKind::DailySpend, &detail, &mut raised)?;
+                }
+            }
+        }
+
+        if let Some(limit) = config.turns_without_commits {
+            let candidates = query_collect(
+                self.conn,
+                "SELECT a.id, a.status, a.num_turns, a.log_file, a.started_at, a.ended_at, \
+                        w.path AS worktree_path \
+                 FROM agent_runs a JOIN worktrees w ON w.id = a.worktree_id \
+                 WHERE a.started_at >= :since \
+                   AND (a.num_turns > :limit \
+                        OR (a.num_turns IS NULL AND a.status IN ('running', 'waiting_for_feedback'))) \
+                   AND NOT EXISTS (SELECT 1 FROM agent_run_alerts al \
+                                   WHERE al.run_id = a.id AND al.kind = 'turns_without_commits') \
+                 ORDER BY a.started_at",
+                named_params! { ":since": since, ":limit": limit },
+                |row| {
+                    Ok(TurnCandidate {
+                        id: row.get("id")?,
+                        status: row.get("status")?,
+                        num_turns: row.get("num_turns")?,
+                        log_file: row.get("log_file")?,
+                        started_at: row.get("started_at")?,
+                        ended_at: row.get("ended_at")?,
+                        worktree_path: row.get("worktree_path")?,
+                    })
+                },
+            )?;
+            for run in candidates {
+                // Live runs only record their turn count when they finish, so
+                // count the turns in the log so far.
+                let turns = match (run.num_turns, run.log_file.as_deref()) {
+                    (Some(n), _) => n,
+                    (None, Some(log)) => count_turns_in_log(log),
+                    (None, None) => continue,
+                };
+                if turns <= limit {
+                    continue;
+                }
+                let until = run.ended_at.as_deref();
+                if has_commits_between(&run.worktree_path, &run.started_at, until) != Some(false) {
+                    continue;
+                }
+                let detail = if run.status == "running" {
+                    format!("{turns} turns so far without a commit (limit {limit})")
+                } else {
+                    format!("{turns} turns without a commit (limit {limit})")
+                };
+                self.raise(
+                    &run.id,
+                    AgentAlertKind::TurnsWithoutCommits,
+                    &detail,
+                    &mut raised,
+                )?;
+            }
+        }
+
+        Ok(raised)
+    }
+
+    /// Alerts raised on one run, oldest first.
+    pub fn list_for_run(&self, run_id: &str) -> Result<Vec<AgentRunAlert>> {
+        query_collect(
+            self.conn,
+            &format!("{ALERT_SELECT} WHERE al.run_id = :run_id ORDER BY al.created_at, al.kind"),
+            named_params! { ":run_id": run_id },
+            row_to_alert,
+        )
+    }
+
+    /// The most recent alerts across all runs, newest first.
+    pub fn list_recent(&self, limit: usize) -> Result<Vec<AgentRunAlert>> {
+        self.list_recent_visible(&Viewer::Local, limit)
+    }
+
+    /// Like [`Self::list_recent`], leaving out alerts on runs in repos that
+    /// are private to someone other than `viewer`.
+    pub fn list_recent_visible(&self, viewer: &Viewer, limit: usize) -> Result<Vec<AgentRunAlert>> {
+        let limit = limit as i64;
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":limit", &limit)];
+        let condition = visible_repo_sql(viewer, "COALESCE(w.repo_id, a.repo_id, '')", ":viewer");
+        if condition.is_some() {
+            params.push((":viewer", &owner));
+        }
+        let visible = condition.map(|c| format!(" WHERE {c}")).unwrap_or_default();
+        query_collect(
+            self.conn,
+            &format!("{ALERT_SELECT}{visible} ORDER BY al.created_at DESC, al.run_id LIMIT :limit"),
+            params.as_slice(),
+            row_to_alert,
+        )
+    }
+
+    fn raise(
+        &self,
+        run_id: &str,
+        kind: AgentAlertKind,
+        detail: &str,
+        raised: &mut Vec<AgentRunAlert>,
+    ) -> Result<()> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO agent_run_alerts (run_id, kind, detail, created_at) \
+             VALUES (:run_id, :kind, :detail, :created_at)",
+            named_params! {
+                ":run_id": run_id,
+                ":kind": kind,
+                ":detail": detail,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        if inserted > 0 {
+            raised.extend(
+                self.list_for_run(run_id)?
+                    .into_iter()
+                    .filter(|a| a.kind == kind),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Whether the worktree at `path` has commits in the given window, or `None`
+/// when git cannot tell (e.g. the worktree was removed).
+fn has_commits_between(path: &str, since: &str, until: Option<&str>) -> Option<bool> {
+    if !std::path::Path::new(path).is_dir() {
+        return None;
+    }
+    let mut cmd = crate::git::git_in(path);
+    cmd.args(["log", "-1", "--format=%H", &format!("--since={since}")]);
+    if let Some(until) = until {
+        cmd.arg(format!("--until={until}"));
+    }
+    let output = crate::git::check_output(&mut cmd).ok()?;
+    Some(!output.stdout.trim_ascii().is_empty())
+}
+
+fn row_to_alert(row: &rusqlite::Row<'_>) -> rusqlite::Result<AgentRunAlert> {
+    Ok(AgentRunAlert {
+        run_id: row.get("run_id")?,
+        kind: row.get("kind")?,
+        detail: row.get("detail")?,
+        created_at: row.get("created_at")?,
+        worktree_id: row.get("worktree_id")?,
+        repo_id: row.get("repo_id")?,
+        repo_slug: row.get("repo_slug")?,
+        worktree_slug: row.get("worktree_slug")?,
+        branch: row.get("branch")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+    use crate::test_helpers::insert_test_agent_run;
+
+    fn setup() -> Connection {
+        super::super::manager::setup_db()
+    }
+
+    fn hours_ago(hours: i64) -> String {
+        (Utc::now() - Duration::hours(hours)).to_rfc3339()
+    }
+
+    fn set_run(conn: &Connection, id: &str, started_at: &str, cost: Option<f64>) {
+        conn.execute(
+            "UPDATE agent_runs SET started_at = :started_at, cost_usd = :cost WHERE id = :id",
+            named_params! { ":id": id, ":started_at": started_at, ":cost": cost },
+        )
+        .unwrap();
+    }
+
+    fn kinds(alerts: &[AgentRunAlert]) -> Vec<(&str, AgentAlertKind)> {
+        alerts.iter().map(|a| (a.run_id.as_str(), a.kind)).collect()
+    }
+
+    fn git(dir: &std::path::Path, args: &[&str], date: Option<&str>) {
+        let mut cmd = Command::new("git");
+        cmd.args(args).current_dir(dir);
+        if let Some(date) = date {
+            cmd.env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date);
+        }
+        assert!(cmd.output().unwrap().status.success(), "git {args:?}");
+    }
+
+    #[test]
+    fn disabled_config_raises_nothing() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "a", "w1");
+        set_run(&conn, "a", &hours_ago(1), Some(100.0));
+        let alerts = AgentAlerts::new(&conn)
+            .check(&CostAlertConfig::default())
+            .unwrap();
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn run_cost_alerts_once_per_recent_run() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "cheap", "w1");
+        insert_test_agent_run(&conn, "pricey", "w1");
+        insert_test_agent_run(&conn, "old", "w1");
+        set_run(&conn, "cheap", &hours_ago(1), Some(1.0));
+        set_run(&conn, "pricey", &hours_ago(1), Some(7.2));
+        set_run(&conn, "old", &hours_ago(48), Some(9.0));
+        let config = CostAlertConfig {
+            run_cost_usd: Some(5.0),
+            ..Default::default()
+        };
+
+        let alerts = AgentAlerts::new(&conn);
+        let raised = alerts.check(&config).unwrap();
+        assert_eq!(kinds(&raised), [("pricey", AgentAlertKind::RunCost)]);
+        assert_eq!(raised[0].detail, "run cost $7.20 is over $5.00");
+        assert_eq!(raised[0].repo_slug.as_deref(), Some("test-repo"));
+        assert!(alerts.check(&config).unwrap().is_empty(), "raised once");
+        assert_eq!(alerts.list_for_run("pricey").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn daily_spend_alerts_once_per_day() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "a", "w1");
+        insert_test_agent_run(&conn, "b", "w1");
+        let now = Utc::now().to_rfc3339();
+        set_run(&conn, "a", &now, Some(30.0));
+        let config = CostAlertConfig {
+            daily_spend_usd: Some(50.0),
+            ..Default::default()
+        };
+
+        let alerts = AgentAlerts::new(&conn);
+        assert!(alerts.check(&config).unwrap().is_empty());
+
+        set_run(&conn, "b", &now, Some(25.0));
+        let raised = alerts.check(&config).unwrap();
+        assert_eq!(kinds(&raised), [("b", AgentAlertKind::DailySpend)]);
+        assert_eq!(raised[0].detail, "agent spend today is $55.00, over $50.00");
+
+        insert_test_agent_run(&conn, "c", "w1");
+        set_run(&conn, "c", &now, Some(10.0));
+        assert!(alerts.check(&config).unwrap().is_empty(), "once per day");
+    }
+
+    #[test]
+    fn turns_without_commits_checks_the_worktree() {
+        let conn = setup();
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        git(repo, &["init", "-q"], None);
+        git(repo, &["config", "user.email", "test@test.com"], None);
+        git(repo, &["config", "user.name", "Test"], None);
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", "old"],
+            Some("2020-01-01T00:00:00Z"),
+        );
+        conn.execute(
+            "UPDATE worktrees SET path = :path WHERE id = 'w1'",
+            named_params! { ":path": repo.to_str().unwrap() },
+        )
+        .unwrap();
+
+        insert_test_agent_run(&conn, "idle", "w1");
+        insert_test_agent_run(&conn, "short", "w1");
+        set_run(&conn, "idle", &hours_ago(2), None);
+        set_run(&conn, "short", &hours_ago(2), None);
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', num_turns = 50, ended_at = :now \
+             WHERE id = 'idle'",
+            named_params! { ":now": Utc::now().to_rfc3339() },
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', num_turns = 3 WHERE id = 'short'",
+            [],
+        )
+        .unwrap();
+        let config = CostAlertConfig {
+            turns_without_commits: Some(40),
+            ..Default::default()
+        };
+
+        let raised = AgentAlerts::new(&conn).check(&config).unwrap();
+        assert_eq!(
+            kinds(&raised),
+            [("idle", AgentAlertKind::TurnsWithoutCommits)]
+        );
+        assert_eq!(raised[0].detail, "50 turns without a commit (limit 40)");
+
+        // A run that committed is not flagged.
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "work"], None);
+        insert_test_agent_run(&conn, "busy", "w1");
+        set_run(&conn, "busy", &hours_ago(1), None);
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', num_turns = 60 WHERE id = 'busy'",
+            [],
+        )
+        .unwrap();
+        assert!(AgentAlerts::new(&conn).check(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_recent_is_newest_first() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "a", "w1");
+        for (kind, at) in [("run_cost", "2024-01-01"), ("daily_spend", "2024-01-02")] {
+            conn.execute(
+                "INSERT INTO agent_run_alerts (run_id, kind, detail, created_at) \
+                 VALUES ('a', :kind, 'x', :at)",
+                named_params! { ":kind": kind, ":at": at },
+            )
+            .unwrap();
+        }
+        let recent = AgentAlerts::new(&conn).list_recent(10).unwrap();
+        assert_eq!(
+            kinds(&recent),
+            [
+                ("a", AgentAlertKind::DailySpend),
+                ("a", AgentAlertKind::RunCost)
+            ]
+        );
+        assert_eq!(AgentAlerts::new(&conn).list_recent(1).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,524 @@
+//! Agent CLIs that `conductor agent run` can drive.
+//!
+//! Every backend's output is translated into Claude's `stream-json` event
+//! shape, which the rest of conductor (log files, the activity feed, workflow
+//! executors reading the relayed stdout) already understands.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::json;
+
+use crate::config::{AgentPermissionMode, Config, RepoConfig};
+use crate::error::{ConductorError, Result};
+
+/// Backend used when neither the run, the repo nor the global config picks one.
+pub const DEFAULT_BACKEND: &str = "claude";
+
+/// Names accepted by [`backend_by_name`].
+pub const BACKEND_NAMES: &[&str] = &["claude", "codex", "aider"];
+
+/// What to ask the agent for, independent of the backend's CLI syntax.
+pub struct BackendRequest<'a> {
+    pub prompt: &'a str,
+    pub model: Option<&'a str>,
+    pub permission_mode: AgentPermissionMode,
+    /// Claude plugin directories; ignored by other backends.
+    pub plugin_dirs: &'a [String],
+    /// Claude `--allowedTools` patterns, used unless the permission mode
+    /// brings its own; ignored by other backends.
+    pub allowed_tools: &'a [String],
+    /// Claude `--max-turns`; ignored by other backends.
+    pub max_turns: Option<u32>,
+    /// Appended to the backend's own arguments.
+    pub extra_args: &'a [String],
+}
+
+pub trait AgentBackend {
+    /// Name used in config and on the command line, e.g. `codex`.
+    fn name(&self) -> &'static str;
+
+    /// Executable the backend runs; also probed with `--version`.
+    fn binary(&self) -> &'static str;
+
+    /// Command that starts a new session for `req`. The caller sets the
+    /// working directory, environment and stdio before spawning it.
+    fn spawn_command(&self, req: &BackendRequest<'_>) -> Command;
+
+    /// Command that continues `session_id` with `req.prompt`, or `None` if
+    /// the backend cannot resume sessions.
+    fn resume_command(&self, req: &BackendRequest<'_>, session_id: &str) -> Option<Command>;
+
+    /// Translate one line of the agent's stdout into zero or more
+    /// `stream-json` lines. Takes `&mut self` so backends can carry state
+    /// (session ID, last message) across lines of a run.
+    fn parse_event(&mut self, line: &str) -> Vec<String>;
+}
+
+/// Construct the backend called `name`.
+pub fn backend_by_name(name: &str) -> Result<Box<dyn AgentBackend>> {
+    match name {
+        "claude" => Ok(Box::new(ClaudeBackend)),
+        "codex" => Ok(Box::new(CodexBackend::default())),
+        "aider" => Ok(Box::new(AiderBackend)),
+        other => Err(ConductorError::Config(format!(
+            "unknown agent backend '{other}' (expected one of: {})",
+            BACKEND_NAMES.join(", ")
+        ))),
+    }
+}
+
+/// Pick the backend for a run: `requested` (e.g. `--backend`) wins, then
+/// `agent_backend` in the repo's `.conductor/config.toml`, then
+/// `[general] agent_backend`, then [`DEFAULT_BACKEND`].
+pub fn resolve_backend(
+    requested: Option<&str>,
+    repo_path: Option<&Path>,
+    config: &Config,
+) -> Result<Box<dyn AgentBackend>> {
+    let repo_backend = repo_path
+        .and_then(|p| RepoConfig::load(p).ok())
+        .and_then(|rc| rc.defaults.agent_backend);
+    let name = requested
+        .map(str::to_string)
+        .or(repo_backend)
+        .or_else(|| config.general.agent_backend.clone())
+        .unwrap_or_else(|| DEFAULT_BACKEND.to_string());
+    backend_by_name(&name)
+}
+
+/// Claude Code, which already speaks `stream-json`.
+pub struct ClaudeBackend;
+
+impl ClaudeBackend {
+    fn command(req: &BackendRequest<'_>, resume: Option<&str>) -> Command {
+        let mut cmd = Command::new("claude");
+        cmd.arg("-p").arg(req.prompt);
+        if let Some(sid) = resume {
+            cmd.arg("--resume").arg(sid);
+        }
+        let perm = req.permission_mode;
+        cmd.arg("--output-format")
+            .arg("stream-json")
+            .arg("--verbose")
+            .arg(perm.claude_permission_flag());
+        if let Some(val) = perm.claude_permission_flag_value() {
+            cmd.arg(val);
+        }
+        if let Some(pattern) = perm.allowed_tools() {
+            cmd.arg("--allowedTools").arg(pattern);
+        } else if !req.allowed_tools.is_empty() {
+            cmd.arg("--allowedTools").arg(req.allowed_tools.join(","));
+        }
+        if let Some(m) = req.model {
+            cmd.arg("--model").arg(m);
+        }
+        if let Some(n) = req.max_turns {
+            cmd.arg("--max-turns").arg(n.to_string());
+        }
+        for dir in req.plugin_dirs {
+            cmd.arg("--plugin-dir").arg(dir);
+        }
+        cmd.args(req.extra_args);
+        cmd
+    }
+}
+
+impl AgentBackend for ClaudeBackend {
+    fn name(&self) -> &'static str {
+        "claude"
+    }
+
+    fn binary(&self) -> &'static str {
+        "claude"
+    }
+
+    fn spawn_command(&self, req: &BackendRequest<'_>) -> Command {
+        Self::command(req, None)
+    }
+
+    fn resume_command(&self, req: &BackendRequest<'_>, session_id: &str) -> Option<Command> {
+        Some(Self::command(req, Some(session_id)))
+    }
+
+    fn parse_event(&mut self, line: &str) -> Vec<String> {
+        vec![line.to_string()]
+    }
+}
+
+/// OpenAI Codex CLI via `codex exec --json`.
+///
+/// Codex reports one `turn.completed` per run; it becomes the `result` event,
+/// carrying the last agent message and the thread ID as the session ID.
+#[derive(Default)]
+pub struct CodexBackend {
+    thread_id: Option<String>,
+    last_message: Option<String>,
+}
+
+impl CodexBackend {
+    fn command(req: &BackendRequest<'_>, resume: Option<&str>) -> Command {
+        let mut cmd = Command::new("codex");
+        cmd.arg("exec").arg("--json").arg("--skip-git-repo-check");
+        match req.permission_mode {
+            AgentPermissionMode::SkipPermissions => {
+                cmd.arg("--dangerously-bypass-approvals-and-sandbox");
+            }
+            AgentPermissionMode::AutoMode => {
+                cmd.arg("--full-auto");
+            }
+            AgentPermissionMode::Plan | AgentPermissionMode::RepoSafe => {
+                cmd.arg("--sandbox").arg("read-only");
+            }
+        }
+        if let Some(m) = req.model {
+            cmd.arg("--model").arg(m);
+        }
+        // Options must precede the `resume` subcommand.
+        cmd.args(req.extra_args);
+        if let Some(sid) = resume {
+            cmd.arg("resume").arg(sid);
+        }
+        cmd.arg(req.prompt);
+        cmd
+    }
+
+    fn result(&self, is_error: bool, text: Option<String>, usage: serde_json::Value) -> String {
+        json!({
+            "type": "result",
+            "subtype": if is_error { "error" } else { "success" },
+            "is_error": is_error,
+            "result": text,
+            "session_id": self.thread_id,
+            "num_turns": 1,
+            "usage": usage,
+        })
+        .to_string()
+    }
+}
+
+impl AgentBackend for CodexBackend {
+    fn name(&self) -> &'static str {
+        "codex"
+    }
+
+    fn binary(&self) -> &'static str {
+        "codex"
+    }
+
+    fn spawn_command(&self, req: &BackendRequest<'_>) -> Command {
+        Self::command(req, None)
+    }
+
+    fn resume_command(&self, req: &BackendRequest<'_>, session_id: &str) -> Option<Command> {
+        Some(Self::command(req, Some(session_id)))
+    }
+
+    fn parse_event(&mut self, line: &str) -> Vec<String> {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            return vec![];
+        };
+        let item = &event["item"];
+        match (event["type"].as_str().unwrap_or(""), item["type"].as_str()) {
+            ("thread.started", _) => {
+                self.thread_id = event["thread_id"].as_str().map(str::to_string);
+                vec![json!({
+                    "type": "system",
+                    "subtype": "init",
+                    "session_id": self.thread_id,
+                })
+                .to_string()]
+            }
+            ("item.started", Some("command_execution")) => {
+                vec![tool_use("Bash", json!({ "command": item["command"] }))]
+            }
+            ("item.completed", Some("agent_message")) => {
+                let text = item["text"].as_str().unwrap_or_default().to_string();
+                self.last_message = Some(text.clone());
+                vec![assistant_text(&text)]
+            }
+            ("item.completed", Some("file_change")) => item["changes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|c| tool_use("Edit", json!({ "file_path": c["path"] })))
+                .collect(),
+            ("error", _) => event["message"]
+                .as_str()
+                .map(|m| vec![assistant_text(&format!("Error: {m}"))])
+                .unwrap_or_default(),
+            ("turn.completed", _) => {
+                let usage = &event["usage"];
+                let cached = usage["cached_input_tokens"].as_i64().unwrap_or(0);
+                let input = usage["input_tokens"].as_i64().unwrap_or(0) - cached;
+                let text = self.last_message.take();
+                vec![self.result(
+                    false,
+                    text,
+                    json!({
+                        "input_tokens": input.max(0),
+                        "output_tokens": usage["output_tokens"],
+                        "cache_read_input_tokens": cached,
+                    }),
+                )]
+            }
+            ("turn.failed", _) => {
+                let message = event["error"]["message"]
+                    .as_str()
+                    .unwrap_or("codex turn failed")
+                    .to_string();
+                vec![self.result(true, Some(message), json!({}))]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Aider in one-shot mode (`aider --message`). Aider prints plain text and
+/// keeps no resumable session, so each line becomes an assistant message
+/// and feedback turns are not supported.
+pub struct AiderBackend;
+
+impl AgentBackend for AiderBackend {
+    fn name(&self) -> &'static str {
+        "aider"
+    }
+
+    fn binary(&self) -> &'static str {
+        "aider"
+    }
+
+    fn spawn_command(&self, req: &BackendRequest<'_>) -> Command {
+        let mut cmd = Command::new("aider");
+        cmd.arg("--message")
+            .arg(req.prompt)
+            .arg("--yes-always")
+            .arg("--no-pretty")
+            .arg("--no-stream");
+        if matches!(
+            req.permission_mode,
+            AgentPermissionMode::Plan | AgentPermissionMode::RepoSafe
+        ) {
+            cmd.arg("--chat-mode").arg("ask");
+        }
+        if let Some(m) = req.model {
+            cmd.arg("--model").arg(m);
+        }
+        cmd.args(req.extra_args);
+        cmd
+    }
+
+    fn resume_command(&self, _req: &BackendRequest<'_>, _session_id: &str) -> Option<Command> {
+        None
+    }
+
+    fn parse_event(&mut self, line: &str) -> Vec<String> {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            return vec![];
+        }
+        vec![assistant_text(line)]
+    }
+}
+
+fn assistant_text(text: &str) -> String {
+    json!({
+        "type": "assistant",
+        "message": { "content": [{ "type": "text", "text": text }] },
+    })
+    .to_string()
+}
+
+fn tool_use(name: &str, input: serde_json::Value) -> String {
+    json!({
+        "type": "assistant",
+        "message": { "content": [{ "type": "tool_use", "name": name, "input": input }] },
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{parse_events_from_line, parse_result_event};
+
+    fn request(mode: AgentPermissionMode) -> BackendRequest<'static> {
+        BackendRequest {
+            prompt: "fix it",
+            model: Some("m1"),
+            permission_mode: mode,
+            plugin_dirs: &[],
+            allowed_tools: &[],
+            max_turns: None,
+            extra_args: &[],
+        }
+    }
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn claude_resume_adds_session_and_keeps_stream_json() {
+        let backend = ClaudeBackend;
+        let cmd = backend
+            .resume_command(&request(AgentPermissionMode::Plan), "sid-1")
+            .unwrap();
+        assert_eq!(cmd.get_program(), "claude");
+        assert_eq!(
+            args(&cmd),
+            [
+                "-p",
+                "fix it",
+                "--resume",
+                "sid-1",
+                "--output-format",
+                "stream-json",
+                "--verbose",
+                "--permission-mode",
+                "plan",
+                "--allowedTools",
+                "Bash,Glob,Grep,Read,WebFetch,WebSearch,mcp__conductor__*,mcp__*",
+                "--model",
+                "m1",
+            ]
+        );
+    }
+
+    #[test]
+    fn claude_applies_repo_agent_settings() {
+        let tools = ["Read".to_string(), "Bash(cargo test:*)".to_string()];
+        let extra = ["--add-dir".to_string(), "../shared".to_string()];
+        let req = BackendRequest {
+            allowed_tools: &tools,
+            max_turns: Some(30),
+            extra_args: &extra,
+            ..request(AgentPermissionMode::AutoMode)
+        };
+        let args = args(&ClaudeBackend.spawn_command(&req));
+        let after = |flag: &str| {
+            let i = args.iter().position(|a| a == flag).unwrap();
+            args[i + 1].clone()
+        };
+        assert_eq!(after("--allowedTools"), "Read,Bash(cargo test:*)");
+        assert_eq!(after("--max-turns"), "30");
+        assert_eq!(args[args.len() - 2..], ["--add-dir", "../shared"]);
+
+        // A read-only permission mode keeps its own tool list.
+        let req = BackendRequest {
+            allowed_tools: &tools,
+            ..request(AgentPermissionMode::RepoSafe)
+        };
+        let args = self::args(&ClaudeBackend.spawn_command(&req));
+        assert!(!args.iter().any(|a| a == "Read,Bash(cargo test:*)"));
+    }
+
+    #[test]
+    fn codex_resume_puts_subcommand_before_prompt() {
+        let cmd = CodexBackend::default()
+            .resume_command(&request(AgentPermissionMode::SkipPermissions), "t-1")
+            .unwrap();
+        let args = args(&cmd);
+        assert_eq!(args[..3], ["exec", "--json", "--skip-git-repo-check"]);
+        assert!(args.contains(&"--dangerously-bypass-approvals-and-sandbox".to_string()));
+        assert_eq!(args[args.len() - 3..], ["resume", "t-1", "fix it"]);
+    }
+
+    #[test]
+    fn codex_events_translate_to_stream_json() {
+        let mut backend = CodexBackend::default();
+        let lines: Vec<String> = [
+            r#"{"type":"thread.started","thread_id":"t-9"}"#,
+            r#"{"type":"turn.started"}"#,
+            r#"{"type":"item.started","item":{"type":"command_execution","command":"ls"}}"#,
+            r#"{"type":"item.completed","item":{"type":"agent_message","text":"Done."}}"#,
+            r#"{"type":"turn.completed","usage":{"input_tokens":100,"cached_input_tokens":40,"output_tokens":7}}"#,
+        ]
+        .iter()
+        .flat_map(|l| backend.parse_event(l))
+        .collect();
+        assert_eq!(lines.len(), 4, "turn.started has no equivalent");
+
+        let init: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(init["session_id"], "t-9");
+        assert_eq!(parse_events_from_line(&lines[1])[0].kind, "tool");
+        assert_eq!(parse_events_from_line(&lines[2])[0].summary, "Done.");
+
+        let result = parse_result_event(&serde_json::from_str(&lines[3]).unwrap());
+        assert!(!result.is_error);
+        assert_eq!(result.result_text.as_deref(), Some("Done."));
+        assert_eq!(result.session_id.as_deref(), Some("t-9"));
+        assert_eq!(result.input_tokens, Some(60));
+        assert_eq!(result.cache_read_input_tokens, Some(40));
+        assert_eq!(result.output_tokens, Some(7));
+    }
+
+    #[test]
+    fn codex_turn_failure_is_an_error_result() {
+        let mut backend = CodexBackend::default();
+        let lines =
+            backend.parse_event(r#"{"type":"turn.failed","error":{"message":"quota exceeded"}}"#);
+        let result = parse_result_event(&serde_json::from_str(&lines[0]).unwrap());
+        assert!(result.is_error);
+        assert_eq!(result.result_text.as_deref(), Some("quota exceeded"));
+    }
+
+    #[test]
+    fn aider_lines_become_text_and_cannot_resume() {
+        let mut backend = AiderBackend;
+        assert!(backend.parse_event("   ").is_empty());
+        let lines = backend.parse_event("Applied edit to src/main.rs");
+        assert_eq!(
+            parse_events_from_line(&lines[0])[0].summary,
+            "Applied edit to src/main.rs"
+        );
+        assert!(backend
+            .resume_command(&request(AgentPermissionMode::AutoMode), "x")
+            .is_none());
+        let cmd = backend.spawn_command(&request(AgentPermissionMode::RepoSafe));
+        assert!(args(&cmd).windows(2).any(|w| w == ["--chat-mode", "ask"]));
+    }
+
+    #[test]
+    fn resolve_prefers_request_then_repo_then_global() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        assert_eq!(
+            resolve_backend(None, None, &config).unwrap().name(),
+            "claude"
+        );
+
+        config.general.agent_backend = Some("aider".into());
+        assert_eq!(
+            resolve_backend(None, Some(dir.path()), &config)
+                .unwrap()
+                .name(),
+            "aider"
+        );
+
+        std::fs::create_dir_all(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            "[defaults]\nagent_backend = \"codex\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            resolve_backend(None, Some(dir.path()), &config)
+                .unwrap()
+                .name(),
+            "codex"
+        );
+        assert_eq!(
+            resolve_backend(Some("claude"), Some(dir.path()), &config)
+                .unwrap()
+                .name(),
+            "claude"
+        );
+        assert!(matches!(
+            resolve_backend(Some("gpt-pilot"), None, &config),
+            Err(ConductorError::Config(_))
+        ));
+    }
+}
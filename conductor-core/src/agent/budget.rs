@@ -0,0 +1,295 @@
+//! Agent spend caps from `[budget]`.
+//!
+//! Spend is the sum of `cost_usd` over a worktree's runs, or over the runs of
+//! one Claude session. Launchers call [`AgentManager::check_budget`] before
+//! creating a run and skip it only when the user overrides the budget;
+//! [`AgentManager::budget_crossed_by`] reports the caps a finished run pushed
+//! its worktree or session over, so servers can announce it once.
+
+use rusqlite::{named_params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::config::BudgetConfig;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+use crate::worktree::LIVE_STATUSES_SQL;
+
+use super::manager::AgentManager;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// `[budget] worktree_usd`: all runs in one worktree.
+    Worktree,
+    /// `[budget] session_usd`: the runs of one resumed Claude session.
+    Session,
+}
+
+impl std::fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Worktree => "worktree",
+            Self::Session => "session",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A worktree or session that has spent its budget.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetExceeded {
+    pub scope: BudgetScope,
+    /// Worktree ID or Claude session ID.
+    pub target_id: String,
+    /// Worktree slug, or the session ID again.
+    pub target: String,
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} has spent ${:.2} of its ${:.2} budget",
+            self.scope, self.target, self.spent_usd, self.limit_usd
+        )
+    }
+}
+
+impl AgentManager<'_> {
+    /// Total cost of every run in the worktree.
+    pub fn worktree_spend(&self, worktree_id: &str) -> Result<f64> {
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM agent_runs WHERE worktree_id = :id",
+            named_params! { ":id": worktree_id },
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Total cost of the runs that belong to a Claude session.
+    pub fn session_spend(&self, session_id: &str) -> Result<f64> {
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM agent_runs WHERE session_id = :id",
+            named_params! { ":id": session_id },
+            |row| row.get(0),
+        )?)
+    }
+
+    /// The first cap in `budget` that the worktree, or the session being
+    /// resumed, has reached.
+    pub fn budget_exceeded(
+        &self,
+        budget: &BudgetConfig,
+        worktree_id: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Option<BudgetExceeded>> {
+        if let (Some(limit), Some(id)) = (budget.worktree_usd, worktree_id) {
+            let spent = self.worktree_spend(id)?;
+            if spent >= limit {
+                return Ok(Some(BudgetExceeded {
+                    scope: BudgetScope::Worktree,
+                    target_id: id.to_string(),
+                    target: self.worktree_slug(id)?.unwrap_or_else(|| id.to_string()),
+                    spent_usd: spent,
+                    limit_usd: limit,
+                }));
+            }
+        }
+        if let (Some(limit), Some(id)) = (budget.session_usd, session_id) {
+            let spent = self.session_spend(id)?;
+            if spent >= limit {
+                return Ok(Some(BudgetExceeded {
+                    scope: BudgetScope::Session,
+                    target_id: id.to_string(),
+                    target: id.to_string(),
+                    spent_usd: spent,
+                    limit_usd: limit,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Refuse a launch with [`ConductorError::AgentBudgetExceeded`] once the
+    /// worktree or the resumed session has reached its cap.
+    pub fn check_budget(
+        &self,
+        budget: &BudgetConfig,
+        worktree_id: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        match self.budget_exceeded(budget, worktree_id, session_id)? {
+            Some(exceeded) => Err(ConductorError::AgentBudgetExceeded(exceeded)),
+            None => Ok(()),
+        }
+    }
+
+    /// Live worktrees that have reached `[budget] worktree_usd`, biggest
+    /// spenders first.
+    pub fn worktrees_over_budget(&self, budget: &BudgetConfig) -> Result<Vec<BudgetExceeded>> {
+        self.visible_worktrees_over_budget(&Viewer::Local, budget)
+    }
+
+    /// Like [`Self::worktrees_over_budget`], leaving out worktrees of repos
+    /// that are private to someone other than `viewer`.
+    pub fn visible_worktrees_over_budget(
+        &self,
+        viewer: &Viewer,
+        budget: &BudgetConfig,
+    ) -> Result<Vec<BudgetExceeded>> {
+        let Some(limit) = budget.worktree_usd else {
+            return Ok(Vec::new());
+        };
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":limit", &limit)];
+        let condition = visible_repo_sql(viewer, "w.repo_id", ":viewer");
+        if condition.is_some() {
+            params.push((":viewer", &owner));
+        }
+        let visible = condition.map(|c| format!(" AND {c}")).unwrap_or_default();
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT w.id, w.slug, SUM(a.cost_usd) AS spent \
+                 FROM agent_runs a JOIN worktrees w ON w.id = a.worktree_id \
+                 WHERE w.status IN {LIVE_STATUSES_SQL}{visible} \
+                 GROUP BY w.id HAVING spent >= :limit \
+                 ORDER BY spent DESC"
+            ),
+            params.as_slice(),
+            |row| {
+                Ok(BudgetExceeded {
+                    scope: BudgetScope::Worktree,
+                    target_id: row.get("id")?,
+                    target: row.get("slug")?,
+                    spent_usd: row.get("spent")?,
+                    limit_usd: limit,
+                })
+            },
+        )
+    }
+
+    /// Caps that `run_id`'s cost pushed its worktree or session over: spend
+    /// reached the cap with the run and was below it without.
+    pub fn budget_crossed_by(
+        &self,
+        budget: &BudgetConfig,
+        run_id: &str,
+    ) -> Result<Vec<BudgetExceeded>> {
+        let run: Option<(Option<String>, Option<String>, Option<f64>)> = self
+            .conn
+            .query_row(
+                "SELECT worktree_id, session_id, cost_usd FROM agent_runs WHERE id = :id",
+                named_params! { ":id": run_id },
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let Some((worktree_id, session_id, Some(cost))) = run else {
+            return Ok(Vec::new());
+        };
+        // Check each cap on its own so a worktree already over budget does
+        // not hide a session crossing its cap.
+        let caps = [
+            (
+                BudgetConfig {
+                    worktree_usd: budget.worktree_usd,
+                    session_usd: None,
+                },
+                worktree_id.as_deref(),
+                None,
+            ),
+            (
+                BudgetConfig {
+                    worktree_usd: None,
+                    session_usd: budget.session_usd,
+                },
+                None,
+                session_id.as_deref(),
+            ),
+        ];
+        let mut crossed = Vec::new();
+        for (cap, worktree_id, session_id) in caps {
+            if let Some(exceeded) = self.budget_exceeded(&cap, worktree_id, session_id)? {
+                if exceeded.spent_usd - cost < exceeded.limit_usd {
+                    crossed.push(exceeded);
+                }
+            }
+        }
+        Ok(crossed)
+    }
+
+    fn worktree_slug(&self, worktree_id: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT slug FROM worktrees WHERE id = :id",
+                named_params! { ":id": worktree_id },
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_cost(conn: &rusqlite::Connection, run_id: &str, cost: f64, session: &str) {
+        conn.execute(
+            "UPDATE agent_runs SET cost_usd = :cost, session_id = :session WHERE id = :id",
+            named_params! { ":cost": cost, ":session": session, ":id": run_id },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn budget_blocks_worktree_and_session_once_spent() {
+        let conn = super::super::manager::setup_db();
+        let mgr = AgentManager::new(&conn);
+        let budget = BudgetConfig {
+            worktree_usd: Some(10.0),
+            session_usd: Some(4.0),
+        };
+        let first = mgr.create_run(Some("w1"), "one", None).unwrap();
+        set_cost(&conn, &first.id, 3.0, "s1");
+        assert!(mgr.check_budget(&budget, Some("w1"), Some("s1")).is_ok());
+        assert!(mgr
+            .budget_crossed_by(&budget, &first.id)
+            .unwrap()
+            .is_empty());
+
+        let second = mgr.create_run(Some("w1"), "two", None).unwrap();
+        set_cost(&conn, &second.id, 1.5, "s1");
+        let crossed = mgr.budget_crossed_by(&budget, &second.id).unwrap();
+        assert_eq!(crossed.len(), 1);
+        assert_eq!(crossed[0].scope, BudgetScope::Session);
+        // A fresh session in the same worktree is still within budget.
+        assert!(mgr.check_budget(&budget, Some("w1"), None).is_ok());
+        match mgr.check_budget(&budget, Some("w1"), Some("s1")) {
+            Err(ConductorError::AgentBudgetExceeded(e)) => {
+                assert_eq!(e.scope, BudgetScope::Session);
+                assert_eq!(e.spent_usd, 4.5);
+            }
+            other => panic!("expected AgentBudgetExceeded, got {other:?}"),
+        }
+
+        let third = mgr.create_run(Some("w1"), "three", None).unwrap();
+        set_cost(&conn, &third.id, 6.0, "s2");
+        let over = mgr.worktrees_over_budget(&budget).unwrap();
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].target, "feat-test");
+        assert_eq!(
+            over[0].to_string(),
+            "worktree feat-test has spent $10.50 of its $10.00 budget"
+        );
+        assert!(mgr.check_budget(&budget, Some("w1"), None).is_err());
+        assert!(mgr.check_budget(&budget, Some("w2"), None).is_ok());
+        assert!(mgr
+            .check_budget(&BudgetConfig::default(), Some("w1"), Some("s1"))
+            .is_ok());
+    }
+}
@@ -0,0 +1,664 @@
+//! Follow-up runs chained on an agent run's outcome.
+//!
+//! A run can be launched with an [`AgentChain`]: "if it succeeds run prompt B,
+//! if it fails run prompt C (or notify)". Each follow-up can declare its own
+//! chain in turn. When a chained run ends, [`AgentChains::evaluate`] picks the
+//! branch for its outcome and queues the follow-up in the same worktree, where
+//! the usual dispatcher starts it once a slot is free. The runner calls it as
+//! soon as its run ends, and the daemon and web server sweep for runs that
+//! ended some other way ([`AgentChains::evaluate_due`]).
+//!
+//! Chains cannot loop: a declared chain is a finite tree, it may not nest
+//! deeper than [`MAX_CHAIN_DEPTH`] runs, and each run's outcome is acted on
+//! exactly once no matter how many processes evaluate it.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::notifications::{NewNotification, NotificationManager, NotificationSeverity};
+
+use super::manager::AgentManager;
+use super::queue::{AgentPriority, AgentQueue};
+use super::status::AgentRunStatus;
+
+/// Most runs one chain may hold, the first run included.
+pub const MAX_CHAIN_DEPTH: u32 = 5;
+
+/// What to do after a run ends with a given outcome.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ChainStep {
+    /// Queue another run in the same worktree.
+    Run {
+        prompt: String,
+        /// Follow-ups of that run.
+        #[serde(default, skip_serializing_if = "AgentChain::is_empty")]
+        then: AgentChain,
+    },
+    /// Record a notification and end the chain.
+    Notify,
+}
+
+/// Follow-ups of one run, by outcome. A cancelled run ends its chain.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentChain {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub on_success: Option<Box<ChainStep>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub on_failure: Option<Box<ChainStep>>,
+}
+
+impl AgentChain {
+    pub fn is_empty(&self) -> bool {
+        self.on_success.is_none() && self.on_failure.is_none()
+    }
+
+    /// Runs this chain adds along its longest branch.
+    pub fn depth(&self) -> u32 {
+        [&self.on_success, &self.on_failure]
+            .into_iter()
+            .flatten()
+            .map(|step| match step.as_ref() {
+                ChainStep::Run { then, .. } => 1 + then.depth(),
+                ChainStep::Notify => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check the chain declares something, has no empty prompts and fits in
+    /// [`MAX_CHAIN_DEPTH`]. Done by [`AgentChains::attach`] too; call it
+    /// before launching a run so a bad chain does not leave the run started.
+    pub fn validate(&self) -> Result<()> {
+        if self.is_empty() {
+            return Err(ConductorError::InvalidInput(
+                "agent chain declares no follow-up".into(),
+            ));
+        }
+        if 1 + self.depth() > MAX_CHAIN_DEPTH {
+            return Err(ConductorError::InvalidInput(format!(
+                "agent chain is too long: at most {MAX_CHAIN_DEPTH} runs per chain"
+            )));
+        }
+        let mut pending: Vec<&ChainStep> = self.steps().collect();
+        while let Some(step) = pending.pop() {
+            if let ChainStep::Run { prompt, then } = step {
+                if prompt.trim().is_empty() {
+                    return Err(ConductorError::InvalidInput(
+                        "agent chain prompts must not be empty".into(),
+                    ));
+                }
+                pending.extend(then.steps());
+            }
+        }
+        Ok(())
+    }
+
+    fn steps(&self) -> impl Iterator<Item = &ChainStep> {
+        [&self.on_success, &self.on_failure]
+            .into_iter()
+            .flatten()
+            .map(|step| step.as_ref())
+    }
+}
+
+/// The outcome that led to a follow-up.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainTrigger {
+    Success,
+    Failure,
+}
+
+impl std::fmt::Display for ChainTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        })
+    }
+}
+
+impl std::str::FromStr for ChainTrigger {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(Self::Success),
+            "failure" => Ok(Self::Failure),
+            _ => Err(format!("unknown chain trigger: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(ChainTrigger);
+
+/// What evaluating a run's outcome did.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainOutcome {
+    /// The follow-up run was queued.
+    Queued,
+    /// A notification was recorded.
+    Notified,
+    /// Nothing was declared for this outcome.
+    NoFollowUp,
+    /// The run was cancelled, which ends the chain.
+    Cancelled,
+    /// The follow-up would have exceeded [`MAX_CHAIN_DEPTH`].
+    DepthLimit,
+}
+
+impl std::fmt::Display for ChainOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Queued => "queued",
+            Self::Notified => "notified",
+            Self::NoFollowUp => "no_follow_up",
+            Self::Cancelled => "cancelled",
+            Self::DepthLimit => "depth_limit",
+        })
+    }
+}
+
+impl std::str::FromStr for ChainOutcome {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(Self::Queued),
+            "notified" => Ok(Self::Notified),
+            "no_follow_up" => Ok(Self::NoFollowUp),
+            "cancelled" => Ok(Self::Cancelled),
+            "depth_limit" => Ok(Self::DepthLimit),
+            _ => Err(format!("unknown chain outcome: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(ChainOutcome);
+
+/// One run in a chain, with the follow-ups still declared below it.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainLink {
+    pub id: String,
+    /// Id of the chain's first link.
+    pub chain_id: String,
+    /// `None` while the run waits in the agent queue.
+    pub run_id: Option<String>,
+    pub queue_id: Option<String>,
+    pub previous_run_id: Option<String>,
+    /// 0 for the run the chain was declared on.
+    pub depth: u32,
+    /// The previous run's outcome that queued this one.
+    pub trigger: Option<ChainTrigger>,
+    pub prompt: String,
+    /// Status of the run, once it exists.
+    pub run_status: Option<AgentRunStatus>,
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub on_success: Option<ChainStep>,
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub on_failure: Option<ChainStep>,
+    pub evaluated_at: Option<String>,
+    pub outcome: Option<ChainOutcome>,
+    pub created_at: String,
+}
+
+const LINK_SELECT: &str = "SELECT c.id, c.chain_id, c.run_id, c.queue_id, c.previous_run_id, \
+            c.depth, c.trigger, c.prompt, c.on_success, c.on_failure, c.evaluated_at, \
+            c.outcome, c.created_at, a.status AS run_status \
+     FROM agent_run_chains c \
+     LEFT JOIN agent_runs a ON a.id = c.run_id";
+
+fn row_to_link(row: &rusqlite::Row<'_>) -> rusqlite::Result<ChainLink> {
+    let step = |col: &str| -> rusqlite::Result<Option<ChainStep>> {
+        let json: Option<String> = row.get(col)?;
+        Ok(json.and_then(|j| serde_json::from_str(&j).ok()))
+    };
+    Ok(ChainLink {
+        id: row.get("id")?,
+        chain_id: row.get("chain_id")?,
+        run_id: row.get("run_id")?,
+        queue_id: row.get("queue_id")?,
+        previous_run_id: row.get("previous_run_id")?,
+        depth: row.get("depth")?,
+        trigger: row.get("trigger")?,
+        prompt: row.get("prompt")?,
+        run_status: row.get("run_status")?,
+        on_success: step("on_success")?,
+        on_failure: step("on_failure")?,
+        evaluated_at: row.get("evaluated_at")?,
+        outcome: row.get("outcome")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+fn step_json(step: &Option<Box<ChainStep>>) -> Result<Option<String>> {
+    step.as_deref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| ConductorError::InvalidInput(format!("invalid agent chain: {e}")))
+}
+
+/// Where a new link's run is, or will be.
+enum LinkTarget<'s> {
+    Run(&'s str),
+    Queued(&'s str),
+}
+
+pub struct AgentChains<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AgentChains<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Declare `chain` on a run that was just launched in a worktree.
+    pub fn attach(&self, run_id: &str, chain: &AgentChain) -> Result<ChainLink> {
+        let run = AgentManager::new(self.conn)
+            .get_run(run_id)?
+            .ok_or_else(|| ConductorError::AgentRunNotFound {
+                id: run_id.to_string(),
+            })?;
+        if run.worktree_id.is_none() {
+            return Err(ConductorError::InvalidInput(
+                "follow-up runs need a worktree; repo-scoped runs cannot be chained".into(),
+            ));
+        }
+        if self.link_for_run(run_id)?.is_some() {
+            return Err(ConductorError::InvalidInput(format!(
+                "agent run {run_id} already has a chain"
+            )));
+        }
+        self.insert_root(LinkTarget::Run(run_id), &run.prompt, chain)
+    }
+
+    /// Declare `chain` on a run still waiting in the agent queue. The link is
+    /// moved to the run by [`Self::bind_queued`] when it is dispatched.
+    pub fn attach_queued(&self, queue_id: &str, chain: &AgentChain) -> Result<ChainLink> {
+        let queued = AgentQueue::new(self.conn).get(queue_id)?;
+        self.insert_root(LinkTarget::Queued(queue_id), &queued.prompt, chain)
+    }
+
+    fn insert_root(
+        &self,
+        target: LinkTarget<'_>,
+        prompt: &str,
+        chain: &AgentChain,
+    ) -> Result<ChainLink> {
+        chain.validate()?;
+        let id = crate::new_id();
+        self.insert_link(&id, &id, target, None, 0, None, prompt, chain)?;
+        self.get(&id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn insert_link(
+        &self,
+        id: &str,
+        chain_id: &str,
+        target: LinkTarget<'_>,
+        previous_run_id: Option<&str>,
+        depth: u32,
+        trigger: Option<ChainTrigger>,
+        prompt: &str,
+        chain: &AgentChain,
+    ) -> Result<()> {
+        let (run_id, queue_id) = match target {
+            LinkTarget::Run(id) => (Some(id), None),
+            LinkTarget::Queued(id) => (None, Some(id)),
+        };
+        self.conn.execute(
+            "INSERT INTO agent_run_chains \
+               (id, chain_id, run_id, queue_id, previous_run_id, depth, trigger, prompt, \
+                on_success, on_failure, created_at) \
+             VALUES (:id, :chain_id, :run_id, :queue_id, :previous_run_id, :depth, :trigger, \
+                     :prompt, :on_success, :on_failure, :created_at)",
+            named_params! {
+                ":id": id,
+                ":chain_id": chain_id,
+                ":run_id": run_id,
+                ":queue_id": queue_id,
+                ":previous_run_id": previous_run_id,
+                ":depth": depth,
+                ":trigger": trigger,
+                ":prompt": prompt,
+                ":on_success": step_json(&chain.on_success)?,
+                ":on_failure": step_json(&chain.on_failure)?,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Record that the queued run `queue_id` was dispatched as `run_id`.
+    /// Does nothing for queued runs that are not part of a chain.
+    pub fn bind_queued(&self, queue_id: &str, run_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE agent_run_chains SET run_id = :run_id \
+             WHERE queue_id = :queue_id AND run_id IS NULL",
+            named_params! { ":queue_id": queue_id, ":run_id": run_id },
+        )?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<ChainLink> {
+        Ok(self.conn.query_row(
+            &format!("{LINK_SELECT} WHERE c.id = :id"),
+            named_params! { ":id": id },
+            row_to_link,
+        )?)
+    }
+
+    fn link_for_run(&self, run_id: &str) -> Result<Option<ChainLink>> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!("{LINK_SELECT} WHERE c.run_id = :run_id"),
+                named_params! { ":run_id": run_id },
+                row_to_link,
+            )
+            .optional()?)
+    }
+
+    /// Every link of the chain `run_id` belongs to, first run first. Empty
+    /// when the run is not part of a chain.
+    pub fn chain_for_run(&self, run_id: &str) -> Result<Vec<ChainLink>> {
+        let Some(link) = self.link_for_run(run_id)? else {
+            return Ok(Vec::new());
+        };
+        query_collect(
+            self.conn,
+            &format!("{LINK_SELECT} WHERE c.chain_id = :chain_id ORDER BY c.depth, c.created_at"),
+            named_params! { ":chain_id": link.chain_id },
+            row_to_link,
+        )
+    }
+
+    /// Act on the outcome of `run_id` if it ended and its chain has not been
+    /// evaluated yet. Returns what was done, or `None` when there was nothing
+    /// to do (not chained, still active, or already evaluated).
+    pub fn evaluate(&self, run_id: &str) -> Result<Option<ChainOutcome>> {
+        let Some(link) = self.link_for_run(run_id)? else {
+            return Ok(None);
+        };
+        if link.evaluated_at.is_some() {
+            return Ok(None);
+        }
+        let step = match link.run_status {
+            Some(AgentRunStatus::Completed) => {
+                link.on_success.clone().map(|s| (ChainTrigger::Success, s))
+            }
+            Some(AgentRunStatus::Failed) => {
+                link.on_failure.clone().map(|s| (ChainTrigger::Failure, s))
+            }
+            Some(AgentRunStatus::Cancelled) => None,
+            _ => return Ok(None),
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        // Claim the evaluation so concurrent evaluators act on it only once.
+        let claimed = tx.execute(
+            "UPDATE agent_run_chains SET evaluated_at = :now \
+             WHERE id = :id AND evaluated_at IS NULL",
+            named_params! { ":id": link.id, ":now": Utc::now().to_rfc3339() },
+        )?;
+        if claimed == 0 {
+            return Ok(None);
+        }
+        let outcome = match step {
+            None if link.run_status == Some(AgentRunStatus::Cancelled) => ChainOutcome::Cancelled,
+            None => ChainOutcome::NoFollowUp,
+            Some((trigger, ChainStep::Notify)) => {
+                self.notify(run_id, &link, trigger)?;
+                ChainOutcome::Notified
+            }
+            Some((_, ChainStep::Run { .. })) if link.depth + 1 >= MAX_CHAIN_DEPTH => {
+                tracing::warn!(run_id, "agent chain stopped at the depth limit");
+                ChainOutcome::DepthLimit
+            }
+            Some((trigger, ChainStep::Run { prompt, then })) => {
+                self.queue_follow_up(run_id, &link, trigger, &prompt, &then)?;
+                ChainOutcome::Queued
+            }
+        };
+        tx.execute(
+            "UPDATE agent_run_chains SET outcome = :outcome WHERE id = :id",
+            named_params! { ":id": link.id, ":outcome": outcome },
+        )?;
+        tx.commit()?;
+        Ok(Some(outcome))
+    }
+
+    /// Evaluate every chained run that has ended but was not evaluated yet,
+    /// e.g. because it was reaped or ended outside `conductor agent run`.
+    /// Returns how many were acted on.
+    pub fn evaluate_due(&self) -> Result<usize> {
+        let due: Vec<String> = query_collect(
+            self.conn,
+            "SELECT c.run_id FROM agent_run_chains c \
+             JOIN agent_runs a ON a.id = c.run_id \
+             WHERE c.evaluated_at IS NULL \
+               AND a.status IN ('completed', 'failed', 'cancelled')",
+            [],
+            |row| row.get(0),
+        )?;
+        let mut evaluated = 0;
+        for run_id in due {
+            match self.evaluate(&run_id) {
+                Ok(Some(_)) => evaluated += 1,
+                Ok(None) => {}
+                Err(e) => tracing::warn!(run_id, "agent chain evaluation failed: {e}"),
+            }
+        }
+        Ok(evaluated)
+    }
+
+    fn queue_follow_up(
+        &self,
+        run_id: &str,
+        link: &ChainLink,
+        trigger: ChainTrigger,
+        prompt: &str,
+        then: &AgentChain,
+    ) -> Result<()> {
+        let worktree_id: Option<String> = self.conn.query_row(
+            "SELECT worktree_id FROM agent_runs WHERE id = :id",
+            named_params! { ":id": run_id },
+            |row| row.get(0),
+        )?;
+        let worktree_id = worktree_id.ok_or_else(|| {
+            ConductorError::InvalidInput(format!("agent run {run_id} has no worktree"))
+        })?;
+        let queued =
+            AgentQueue::new(self.conn).enqueue(&worktree_id, prompt, AgentPriority::Normal)?;
+        self.insert_link(
+            &crate::new_id(),
+            &link.chain_id,
+            LinkTarget::Queued(&queued.id),
+            Some(run_id),
+            link.depth + 1,
+            Some(trigger),
+            prompt,
+            then,
+        )
+    }
+
+    fn notify(&self, run_id: &str, link: &ChainLink, trigger: ChainTrigger) -> Result<()> {
+        let (url, repo_id): (Option<String>, Option<String>) = self
+            .conn
+            .query_row(
+                "SELECT '/repos/' || w.repo_id || '/worktrees/' || w.id, \
+                        COALESCE(w.repo_id, a.repo_id) \
+                 FROM agent_runs a LEFT JOIN worktrees w ON w.id = a.worktree_id \
+                 WHERE a.id = :id",
+                named_params! { ":id": run_id },
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .unwrap_or_default();
+        let (kind, severity, verb) = match trigger {
+            ChainTrigger::Success => (
+                "agent_chain_succeeded",
+                NotificationSeverity::Success,
+                "succeeded",
+            ),
+            ChainTrigger::Failure => ("agent_chain_failed", NotificationSeverity::Error, "failed"),
+        };
+        NotificationManager::new(self.conn).record(&NewNotification {
+            kind: kind.into(),
+            severity,
+            title: format!("Chained agent run {verb}"),
+            body: crate::text_util::truncate_str(&link.prompt, 200).to_string(),
+            url,
+            repo_id,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        super::super::manager::setup_db()
+    }
+
+    fn run(prompt: &str, then: AgentChain) -> Option<Box<ChainStep>> {
+        Some(Box::new(ChainStep::Run {
+            prompt: prompt.into(),
+            then,
+        }))
+    }
+
+    /// Dispatch the next queued run as the daemon would and end it.
+    fn dispatch_and_end(conn: &Connection, status: AgentRunStatus) -> String {
+        let queued = AgentQueue::new(conn).take_next(None).unwrap().unwrap();
+        let agents = AgentManager::new(conn);
+        let run = agents
+            .create_run(Some(&queued.worktree_id), &queued.prompt, None)
+            .unwrap();
+        AgentChains::new(conn)
+            .bind_queued(&queued.id, &run.id)
+            .unwrap();
+        end(conn, &run.id, status);
+        run.id
+    }
+
+    fn end(conn: &Connection, run_id: &str, status: AgentRunStatus) {
+        conn.execute(
+            "UPDATE agent_runs SET status = :status WHERE id = :id",
+            named_params! { ":id": run_id, ":status": status },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn follow_ups_run_on_the_matching_outcome() {
+        let conn = setup();
+        let chains = AgentChains::new(&conn);
+        let agents = AgentManager::new(&conn);
+        let first = agents.create_run(Some("w1"), "implement", None).unwrap();
+        let chain = AgentChain {
+            on_success: run(
+                "write tests",
+                AgentChain {
+                    on_failure: Some(Box::new(ChainStep::Notify)),
+                    ..Default::default()
+                },
+            ),
+            on_failure: run("diagnose", AgentChain::default()),
+        };
+        chains.attach(&first.id, &chain).unwrap();
+
+        // Still running: nothing to do yet.
+        assert_eq!(chains.evaluate(&first.id).unwrap(), None);
+        end(&conn, &first.id, AgentRunStatus::Completed);
+        assert_eq!(
+            chains.evaluate(&first.id).unwrap(),
+            Some(ChainOutcome::Queued)
+        );
+        // Evaluated once only.
+        assert_eq!(chains.evaluate(&first.id).unwrap(), None);
+        assert_eq!(chains.evaluate_due().unwrap(), 0);
+        let queued = AgentQueue::new(&conn).list().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].prompt, "write tests");
+
+        let second = dispatch_and_end(&conn, AgentRunStatus::Failed);
+        assert_eq!(chains.evaluate_due().unwrap(), 1);
+        let unread = NotificationManager::new(&conn)
+            .list(crate::notifications::LOCAL_READER, true, 10)
+            .unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].kind, "agent_chain_failed");
+
+        let links = chains.chain_for_run(&second).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].run_id.as_deref(), Some(first.id.as_str()));
+        assert_eq!(links[0].outcome, Some(ChainOutcome::Queued));
+        assert_eq!(links[1].run_id.as_deref(), Some(second.as_str()));
+        assert_eq!(links[1].trigger, Some(ChainTrigger::Success));
+        assert_eq!(links[1].run_status, Some(AgentRunStatus::Failed));
+        assert_eq!(links[1].outcome, Some(ChainOutcome::Notified));
+    }
+
+    #[test]
+    fn chains_are_bounded_and_cancelled_runs_end_them() {
+        let conn = setup();
+        let chains = AgentChains::new(&conn);
+        let agents = AgentManager::new(&conn);
+        let first = agents.create_run(Some("w1"), "go", None).unwrap();
+
+        let mut too_long = AgentChain::default();
+        for i in 0..MAX_CHAIN_DEPTH {
+            too_long = AgentChain {
+                on_success: run(&format!("step {i}"), too_long),
+                ..Default::default()
+            };
+        }
+        assert!(matches!(
+            chains.attach(&first.id, &too_long),
+            Err(ConductorError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            chains.attach(&first.id, &AgentChain::default()),
+            Err(ConductorError::InvalidInput(_))
+        ));
+
+        let chain = AgentChain {
+            on_success: run("next", AgentChain::default()),
+            ..Default::default()
+        };
+        chains.attach(&first.id, &chain).unwrap();
+        assert!(chains.attach(&first.id, &chain).is_err());
+        end(&conn, &first.id, AgentRunStatus::Cancelled);
+        assert_eq!(
+            chains.evaluate(&first.id).unwrap(),
+            Some(ChainOutcome::Cancelled)
+        );
+        assert!(AgentQueue::new(&conn).list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn chain_step_json_shape() {
+        let chain: AgentChain = serde_json::from_str(
+            r#"{"on_success":{"action":"run","prompt":"B","then":{"on_failure":{"action":"notify"}}},
+                "on_failure":{"action":"notify"}}"#,
+        )
+        .unwrap();
+        assert_eq!(chain.depth(), 1);
+        assert_eq!(chain.on_failure.as_deref(), Some(&ChainStep::Notify));
+    }
+}
@@ -0,0 +1,597 @@
+//! Guided resolution of a rebase or merge that stopped on conflicts.
+//!
+//! [`ConflictResolutions::prepare`] reads the conflicts git left in a worktree
+//! and builds a prompt for a conflict resolution agent: the conflicting hunks
+//! plus what the agent may and may not do. [`ConflictResolutions::record`]
+//! tracks the resolution (with or without an agent run). Before the rebase
+//! may continue, [`ConflictResolutions::verify`] must have run the project's
+//! check command on the staged result and seen it pass; continuing re-checks
+//! that nothing was staged since.
+
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, RepoConfig};
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::text_util::tail_lines;
+use crate::toolchain::Toolchain;
+use crate::worktree::{
+    abort_operation, conflict_state, continue_operation as git_continue, index_tree,
+    paths_with_markers, unmerged_paths, ConflictOperation, ConflictState, WorktreeManager,
+};
+
+use super::status::AgentRunStatus;
+
+/// Check output kept on a resolution, from the end.
+const CHECK_OUTPUT_MAX_BYTES: usize = 4_000;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolutionStatus {
+    /// Conflicts are being resolved; nothing was checked yet.
+    Resolving,
+    /// The check passed on the staged tree in `verified_tree`.
+    Verified,
+    /// The check failed; fix and verify again.
+    CheckFailed,
+    /// The rebase or merge was continued.
+    Continued,
+    /// The rebase or merge was aborted.
+    Aborted,
+}
+
+impl ConflictResolutionStatus {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Resolving | Self::Verified | Self::CheckFailed)
+    }
+}
+
+impl std::fmt::Display for ConflictResolutionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Resolving => "resolving",
+            Self::Verified => "verified",
+            Self::CheckFailed => "check_failed",
+            Self::Continued => "continued",
+            Self::Aborted => "aborted",
+        })
+    }
+}
+
+impl std::str::FromStr for ConflictResolutionStatus {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "resolving" => Ok(Self::Resolving),
+            "verified" => Ok(Self::Verified),
+            "check_failed" => Ok(Self::CheckFailed),
+            "continued" => Ok(Self::Continued),
+            "aborted" => Ok(Self::Aborted),
+            _ => Err(format!("unknown conflict resolution status: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(ConflictResolutionStatus);
+
+/// A conflict resolution prompt built from a worktree's conflicts.
+#[derive(Debug, Clone)]
+pub struct ConflictResolutionRequest {
+    pub worktree_id: String,
+    pub state: ConflictState,
+    /// Command that must pass before the operation may continue.
+    pub check_command: Option<String>,
+    pub prompt: String,
+}
+
+/// A tracked resolution joined with the status of its agent run.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictResolution {
+    pub id: String,
+    pub worktree_id: String,
+    pub run_id: Option<String>,
+    pub run_status: Option<AgentRunStatus>,
+    pub operation: ConflictOperation,
+    pub onto: Option<String>,
+    /// Files that were in conflict when the resolution started.
+    pub files: Vec<String>,
+    pub status: ConflictResolutionStatus,
+    pub check_command: Option<String>,
+    /// Tail of the last check's output.
+    pub check_output: Option<String>,
+    /// Staged tree the check passed on.
+    pub verified_tree: Option<String>,
+    pub created_at: String,
+    pub checked_at: Option<String>,
+    pub ended_at: Option<String>,
+}
+
+pub struct ConflictResolutions<'a> {
+    conn: &'a Connection,
+    config: &'a Config,
+}
+
+impl<'a> ConflictResolutions<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self { conn, config }
+    }
+
+    /// Build a resolution prompt from the conflicts in the worktree. Fails
+    /// with [`ConductorError::InvalidInput`] when no rebase or merge is
+    /// stopped on conflicts there.
+    pub fn prepare(&self, worktree_id: &str) -> Result<ConflictResolutionRequest> {
+        let wt = WorktreeManager::new(self.conn, self.config).get_by_id(worktree_id)?;
+        let path = Path::new(&wt.path);
+        let state = conflict_state(path)?
+            .filter(|s| !s.files.is_empty())
+            .ok_or_else(|| {
+                ConductorError::InvalidInput(format!(
+                    "worktree '{}' has no rebase or merge stopped on conflicts",
+                    wt.slug
+                ))
+            })?;
+        Ok(self.request_for(wt.id, &wt.repo_id, path, state))
+    }
+
+    /// The open resolution in the worktree. When a rebase or merge is
+    /// stopped there but nothing tracks it yet (e.g. the conflicts were
+    /// resolved by hand), one is started without an agent run.
+    pub fn current(&self, worktree_id: &str) -> Result<ConflictResolution> {
+        if let Some(open) = self
+            .latest_for_worktree(worktree_id)?
+            .filter(|r| r.status.is_open())
+        {
+            return Ok(open);
+        }
+        let wt = WorktreeManager::new(self.conn, self.config).get_by_id(worktree_id)?;
+        let path = Path::new(&wt.path);
+        let state = conflict_state(path)?.ok_or_else(|| {
+            ConductorError::InvalidInput(format!(
+                "worktree '{}' has no rebase or merge in progress",
+                wt.slug
+            ))
+        })?;
+        let request = self.request_for(wt.id, &wt.repo_id, path, state);
+        self.record(&request, None)
+    }
+
+    fn request_for(
+        &self,
+        worktree_id: String,
+        repo_id: &str,
+        path: &Path,
+        state: ConflictState,
+    ) -> ConflictResolutionRequest {
+        // Prefer the checkout's configured test command, then the one
+        // implied by the repo's detected toolchain.
+        let toolchain = RepoManager::new(self.conn, self.config)
+            .get_by_id(repo_id)
+            .map(|r| r.toolchain)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| Toolchain::detect(path));
+        let check_command = RepoConfig::load(path)
+            .ok()
+            .and_then(|rc| rc.project.test_command)
+            .or_else(|| toolchain.test_command());
+        ConflictResolutionRequest {
+            worktree_id,
+            prompt: build_resolution_prompt(&state, check_command.as_deref()),
+            state,
+            check_command,
+        }
+    }
+
+    /// Start tracking the resolution of `request`, resolved by the agent run
+    /// `run_id` or by hand when `None`. Open resolutions of the worktree are
+    /// superseded.
+    pub fn record(
+        &self,
+        request: &ConflictResolutionRequest,
+        run_id: Option<&str>,
+    ) -> Result<ConflictResolution> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE worktree_conflict_resolutions SET status = 'aborted', ended_at = :now \
+             WHERE worktree_id = :worktree_id \
+               AND status IN ('resolving', 'verified', 'check_failed')",
+            named_params! { ":worktree_id": request.worktree_id, ":now": now },
+        )?;
+        let id = crate::new_id();
+        let files: Vec<&str> = request
+            .state
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        self.conn.execute(
+            "INSERT INTO worktree_conflict_resolutions \
+                 (id, worktree_id, run_id, operation, onto, files, check_command, created_at) \
+             VALUES (:id, :worktree_id, :run_id, :operation, :onto, :files, :check_command, \
+                     :created_at)",
+            named_params! {
+                ":id": id,
+                ":worktree_id": request.worktree_id,
+                ":run_id": run_id,
+                ":operation": request.state.operation,
+                ":onto": request.state.onto,
+                ":files": serde_json::to_string(&files).unwrap_or_else(|_| "[]".into()),
+                ":check_command": request.check_command,
+                ":created_at": now,
+            },
+        )?;
+        self.get(&id)
+    }
+
+    pub fn get(&self, id: &str) -> Result<ConflictResolution> {
+        self.conn
+            .query_row(
+                &format!("{RESOLUTION_SELECT} WHERE c.id = :id"),
+                named_params! { ":id": id },
+                row_to_resolution,
+            )
+            .optional()?
+            .ok_or_else(|| {
+                ConductorError::InvalidInput(format!("conflict resolution not found: {id}"))
+            })
+    }
+
+    /// The most recent resolution in a worktree, if any.
+    pub fn latest_for_worktree(&self, worktree_id: &str) -> Result<Option<ConflictResolution>> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!(
+                    "{RESOLUTION_SELECT} WHERE c.worktree_id = :worktree_id \
+                     ORDER BY c.created_at DESC, c.rowid DESC LIMIT 1"
+                ),
+                named_params! { ":worktree_id": worktree_id },
+                row_to_resolution,
+            )
+            .optional()?)
+    }
+
+    /// Run the check command on the staged resolution and record the
+    /// outcome. Every conflict must be resolved and staged, with no
+    /// unstaged changes left, so the checked tree is exactly what continuing
+    /// will commit.
+    pub fn verify(&self, id: &str) -> Result<ConflictResolution> {
+        let (resolution, path) = self.open_resolution(id)?;
+        let path = Path::new(&path);
+        if resolution.run_status.is_some_and(|s| {
+            matches!(
+                s,
+                AgentRunStatus::Running | AgentRunStatus::WaitingForFeedback
+            )
+        }) {
+            return Err(ConductorError::InvalidInput(
+                "the conflict resolution agent is still running".into(),
+            ));
+        }
+        let unmerged = unmerged_paths(path)?;
+        if !unmerged.is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "still unmerged: {}",
+                unmerged.join(", ")
+            )));
+        }
+        let marked = paths_with_markers(path, &resolution.files);
+        if !marked.is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "conflict markers left in: {}",
+                marked.join(", ")
+            )));
+        }
+        let unstaged =
+            crate::git::check_output(crate::git::git_in(path).args(["diff", "--name-only"]))?;
+        let unstaged = String::from_utf8_lossy(&unstaged.stdout).trim().to_string();
+        if !unstaged.is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "stage or discard unstaged changes before verifying: {}",
+                unstaged.replace('\n', ", ")
+            )));
+        }
+        let command = resolution.check_command.clone().ok_or_else(|| {
+            ConductorError::InvalidInput(
+                "no check command: set [project] test_command in .conductor/config.toml".into(),
+            )
+        })?;
+        let tree = index_tree(path)?;
+
+        let (passed, output) = match crate::command::output_with_timeout(
+            crate::platform::shell_command(&command).current_dir(path),
+            crate::command::timeouts().setup(),
+        ) {
+            Ok(out) => {
+                let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&out.stderr));
+                (out.status.success(), text)
+            }
+            Err(e) => (false, format!("could not run `{command}`: {e}")),
+        };
+        let status = if passed {
+            ConflictResolutionStatus::Verified
+        } else {
+            ConflictResolutionStatus::CheckFailed
+        };
+        self.conn.execute(
+            "UPDATE worktree_conflict_resolutions \
+             SET status = :status, check_output = :output, verified_tree = :tree, \
+                 checked_at = :now \
+             WHERE id = :id",
+            named_params! {
+                ":id": id,
+                ":status": status,
+                ":output": tail_lines(&output, CHECK_OUTPUT_MAX_BYTES),
+                ":tree": passed.then_some(tree),
+                ":now": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.get(id)
+    }
+
+    /// Continue the rebase or merge with the verified resolution. Refused
+    /// unless the last check passed on exactly the tree that is staged now.
+    ///
+    /// When a later commit of the rebase conflicts too, a new resolution is
+    /// recorded for those conflicts and returned.
+    pub fn continue_operation(&self, id: &str) -> Result<ConflictResolution> {
+        let (resolution, path) = self.open_resolution(id)?;
+        let path = Path::new(&path);
+        if resolution.status != ConflictResolutionStatus::Verified {
+            return Err(ConductorError::InvalidInput(format!(
+                "the resolution has not passed its check (status: {}); verify it first",
+                resolution.status
+            )));
+        }
+        if index_tree(path).ok() != resolution.verified_tree {
+            return Err(ConductorError::InvalidInput(
+                "the staged resolution changed since it was verified; verify it again".into(),
+            ));
+        }
+        let next = git_continue(path, resolution.operation)?;
+        self.finish(id, ConflictResolutionStatus::Continued)?;
+        match next.filter(|s| !s.files.is_empty()) {
+            Some(state) => {
+                let request = ConflictResolutionRequest {
+                    worktree_id: resolution.worktree_id.clone(),
+                    prompt: build_resolution_prompt(&state, resolution.check_command.as_deref()),
+                    state,
+                    check_command: resolution.check_command,
+                };
+                self.record(&request, None)
+            }
+            None => self.get(id),
+        }
+    }
+
+    /// Abort the rebase or merge, restoring the branch to where it was.
+    pub fn abort(&self, id: &str) -> Result<ConflictResolution> {
+        let (resolution, path) = self.open_resolution(id)?;
+        abort_operation(Path::new(&path), resolution.operation)?;
+        self.finish(id, ConflictResolutionStatus::Aborted)?;
+        self.get(id)
+    }
+
+    fn open_resolution(&self, id: &str) -> Result<(ConflictResolution, String)> {
+        let resolution = self.get(id)?;
+        if !resolution.status.is_open() {
+            return Err(ConductorError::InvalidInput(format!(
+                "conflict resolution {id} is already {}",
+                resolution.status
+            )));
+        }
+        let wt = WorktreeManager::new(self.conn, self.config).get_by_id(&resolution.worktree_id)?;
+        Ok((resolution, wt.path))
+    }
+
+    fn finish(&self, id: &str, status: ConflictResolutionStatus) -> Result<()> {
+        self.conn.execute(
+            "UPDATE worktree_conflict_resolutions SET status = :status, ended_at = :now \
+             WHERE id = :id",
+            named_params! { ":id": id, ":status": status, ":now": Utc::now().to_rfc3339() },
+        )?;
+        Ok(())
+    }
+}
+
+const RESOLUTION_SELECT: &str = "SELECT c.id, c.worktree_id, c.run_id, c.operation, c.onto, \
+            c.files, c.status, c.check_command, c.check_output, c.verified_tree, \
+            c.created_at, c.checked_at, c.ended_at, a.status AS run_status \
+     FROM worktree_conflict_resolutions c \
+     LEFT JOIN agent_runs a ON a.id = c.run_id";
+
+fn row_to_resolution(row: &rusqlite::Row<'_>) -> rusqlite::Result<ConflictResolution> {
+    let files: String = row.get("files")?;
+    Ok(ConflictResolution {
+        id: row.get("id")?,
+        worktree_id: row.get("worktree_id")?,
+        run_id: row.get("run_id")?,
+        run_status: row.get("run_status")?,
+        operation: row.get("operation")?,
+        onto: row.get("onto")?,
+        files: serde_json::from_str(&files).unwrap_or_default(),
+        status: row.get("status")?,
+        check_command: row.get("check_command")?,
+        check_output: row.get("check_output")?,
+        verified_tree: row.get("verified_tree")?,
+        created_at: row.get("created_at")?,
+        checked_at: row.get("checked_at")?,
+        ended_at: row.get("ended_at")?,
+    })
+}
+
+/// Instructions for a conflict resolution agent followed by every
+/// conflicting hunk.
+pub fn build_resolution_prompt(state: &ConflictState, check_command: Option<&str>) -> String {
+    let onto = state
+        .onto
+        .as_deref()
+        .map(|o| format!(" onto `{o}`"))
+        .unwrap_or_default();
+    let mut prompt = format!(
+        "A `git {}`{onto} in this worktree stopped on conflicts. Resolve them.\n\n\
+         Constraints:\n\
+         - Keep the intent of both sides. Do not drop changes from either side unless they \
+         are truly superseded, and make no changes beyond what resolving needs.\n\
+         - Remove every conflict marker and stage each resolved file with `git add <file>`.\n\
+         - Do not run `git rebase --continue`, `git rebase --abort`, `git merge --abort`, \
+         `git commit`, `git push` or switch branches. Conductor continues the {} only after \
+         the resolution passes its check.\n",
+        state.operation, state.operation
+    );
+    match check_command {
+        Some(cmd) => prompt.push_str(&format!(
+            "- Run `{cmd}` and make sure it passes before finishing. The same command is run \
+             again before the {} continues.\n",
+            state.operation
+        )),
+        None => {
+            prompt.push_str("- Make sure the project builds and its tests pass before finishing.\n")
+        }
+    }
+    prompt.push_str(
+        "- If a conflict cannot be resolved without a product decision, ask for feedback \
+         instead of guessing.\n\nConflicting files:\n",
+    );
+    for file in &state.files {
+        prompt.push_str(&format!("\n### {}\n", file.path));
+        if file.hunks.is_empty() {
+            prompt.push_str(
+                "(no conflict markers: the file was added, deleted or renamed on one side)\n",
+            );
+        }
+        for hunk in &file.hunks {
+            prompt.push_str("```\n");
+            prompt.push_str(hunk);
+            prompt.push_str("```\n");
+        }
+        if file.truncated {
+            prompt.push_str("... [more conflicts in this file; read it in the working tree]\n");
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{create_test_conn, insert_test_repo, insert_test_worktree};
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    }
+
+    /// A repo whose `feat` branch is mid-rebase onto `main`, conflicting in `a.txt`.
+    fn conflicted_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init", "-q", "-b", "main"]);
+        git(path, &["config", "user.name", "t"]);
+        git(path, &["config", "user.email", "t@t"]);
+        std::fs::write(path.join("a.txt"), "base\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-qm", "base"]);
+        git(path, &["checkout", "-qb", "feat"]);
+        std::fs::write(path.join("a.txt"), "feat\n").unwrap();
+        git(path, &["commit", "-qam", "feat"]);
+        git(path, &["checkout", "-q", "main"]);
+        std::fs::write(path.join("a.txt"), "main\n").unwrap();
+        git(path, &["commit", "-qam", "main"]);
+        git(path, &["checkout", "-q", "feat"]);
+        let out = Command::new("git")
+            .args(["rebase", "main"])
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(!out.status.success());
+        dir
+    }
+
+    #[test]
+    fn resolution_must_pass_its_check_before_continuing() {
+        let dir = conflicted_repo();
+        let path = dir.path();
+        let conn = create_test_conn();
+        let config = Config::default();
+        insert_test_repo(&conn, "r1", "repo", &path.to_string_lossy());
+        insert_test_worktree(&conn, "w1", "r1", "feat", &path.to_string_lossy());
+        let resolutions = ConflictResolutions::new(&conn, &config);
+
+        let mut request = resolutions.prepare("w1").unwrap();
+        assert_eq!(request.state.operation, ConflictOperation::Rebase);
+        assert_eq!(request.state.files[0].path, "a.txt");
+        assert!(request.prompt.contains(">>>>>>>"));
+        assert!(request
+            .prompt
+            .contains("Do not run `git rebase --continue`"));
+        request.check_command = Some("grep -q resolved a.txt".into());
+        let resolution = resolutions.record(&request, None).unwrap();
+        assert_eq!(resolution.files, ["a.txt"]);
+
+        // Unresolved conflicts and unverified resolutions are refused.
+        assert!(resolutions.verify(&resolution.id).is_err());
+        assert!(resolutions.continue_operation(&resolution.id).is_err());
+
+        std::fs::write(path.join("a.txt"), "wrong\n").unwrap();
+        git(path, &["add", "a.txt"]);
+        let failed = resolutions.verify(&resolution.id).unwrap();
+        assert_eq!(failed.status, ConflictResolutionStatus::CheckFailed);
+        assert!(resolutions.continue_operation(&resolution.id).is_err());
+
+        std::fs::write(path.join("a.txt"), "resolved\n").unwrap();
+        git(path, &["add", "a.txt"]);
+        let verified = resolutions.verify(&resolution.id).unwrap();
+        assert_eq!(verified.status, ConflictResolutionStatus::Verified);
+
+        let done = resolutions.continue_operation(&resolution.id).unwrap();
+        assert_eq!(done.status, ConflictResolutionStatus::Continued);
+        assert!(conflict_state(path).unwrap().is_none());
+        assert_eq!(
+            std::fs::read_to_string(path.join("a.txt")).unwrap(),
+            "resolved\n"
+        );
+    }
+
+    #[test]
+    fn restaging_after_verify_requires_a_new_check() {
+        let dir = conflicted_repo();
+        let path = dir.path();
+        let conn = create_test_conn();
+        let config = Config::default();
+        insert_test_repo(&conn, "r1", "repo", &path.to_string_lossy());
+        insert_test_worktree(&conn, "w1", "r1", "feat", &path.to_string_lossy());
+        let resolutions = ConflictResolutions::new(&conn, &config);
+
+        let mut request = resolutions.prepare("w1").unwrap();
+        request.check_command = Some("true".into());
+        let resolution = resolutions.record(&request, None).unwrap();
+        std::fs::write(path.join("a.txt"), "resolved\n").unwrap();
+        git(path, &["add", "a.txt"]);
+        resolutions.verify(&resolution.id).unwrap();
+
+        std::fs::write(path.join("a.txt"), "changed\n").unwrap();
+        git(path, &["add", "a.txt"]);
+        assert!(resolutions.continue_operation(&resolution.id).is_err());
+
+        let aborted = resolutions.abort(&resolution.id).unwrap();
+        assert_eq!(aborted.status, ConflictResolutionStatus::Aborted);
+        assert!(conflict_state(path).unwrap().is_none());
+        assert_eq!(
+            std::fs::read_to_string(path.join("a.txt")).unwrap(),
+            "feat\n"
+        );
+    }
+}
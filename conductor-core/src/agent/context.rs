@@ -1,11 +1,14 @@
+use std::path::Path;
 use std::process::Command;
 
 use rusqlite::Connection;
 
 use super::manager::AgentManager;
 use super::status::{AgentRunStatus, FeedbackStatus};
-use crate::config::Config;
+use crate::config::{Config, RepoConfig};
+use crate::repo::RepoManager;
 use crate::tickets::TicketSyncer;
+use crate::toolchain::Toolchain;
 use crate::worktree::WorktreeManager;
 
 /// Prefix used for the parent run prompt when launching a PR review swarm.
@@ -21,32 +24,54 @@ const FEEDBACK_PROTOCOL: &str = "**Feedback protocol:** If you need human input
      The conductor will pause your run and surface the question to \
      the user. When they respond, your run will resume with their answer.";
 
+/// Branch TODOs listed in the startup context with `[agents] todos_in_prompt`.
+const MAX_CONTEXT_TODOS: usize = 30;
+
 /// Run `git log --oneline -10` in `worktree_path` and return the commit lines.
 ///
 /// Returns an empty `Vec` when git is unavailable, the directory is not a
 /// repository, or the output contains invalid UTF-8.  Uses `from_utf8_lossy`
 /// so partial output is never silently discarded.
 fn git_recent_commits(worktree_path: &str) -> Vec<String> {
-    Command::new("git")
-        .args(["log", "--oneline", "-10"])
-        .current_dir(worktree_path)
-        .output()
-        .ok()
-        .filter(|o| o.status.success())
-        .map(|o| {
-            String::from_utf8_lossy(&o.stdout)
-                .lines()
-                .map(str::to_owned)
-                .collect()
-        })
-        .unwrap_or_default()
+    crate::command::output(
+        Command::new("git")
+            .args(["log", "--oneline", "-10"])
+            .current_dir(worktree_path),
+    )
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| {
+        String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Project conventions from the checkout's `.conductor/config.toml`, followed
+/// by the detected toolchain. The toolchain's test command stands in when the
+/// project does not configure one.
+fn project_instructions(worktree_path: &str, toolchain: &Toolchain) -> Vec<String> {
+    let mut project = RepoConfig::load(Path::new(worktree_path))
+        .map(|rc| rc.project)
+        .unwrap_or_default();
+    if project.test_command.is_none() {
+        project.test_command = toolchain.test_command();
+    }
+    let mut sections: Vec<String> = project.agent_instructions().into_iter().collect();
+    if !toolchain.is_empty() {
+        sections.push(format!("**Toolchain:** {toolchain}"));
+    }
+    sections
 }
 
 /// Build a startup context block to prepend to the agent prompt.
 ///
-/// Pulls worktree info, linked ticket, prior run plans, recent commits,
-/// and prior run summaries from the database. Always includes the feedback
-/// protocol so agents know how to request human input mid-run.
+/// Pulls the project's conventions, worktree info, linked ticket, prior run
+/// plans, recent commits, prior run summaries and, with
+/// `[agents] todos_in_prompt`, the TODOs the branch added. Always includes the
+/// feedback protocol so agents know how to request human input mid-run.
 pub fn build_startup_context(
     conn: &Connection,
     config: &Config,
@@ -54,7 +79,17 @@ pub fn build_startup_context(
     current_run_id: &str,
     worktree_path: &str,
 ) -> String {
-    let mut sections = Vec::new();
+    let wt_mgr = WorktreeManager::new(conn, config);
+    let worktree = worktree_id.and_then(|id| wt_mgr.get_by_id(id).ok());
+    // Prefer the toolchain stored on the repo; detect from the checkout for
+    // ephemeral runs and repos that have not been detected yet.
+    let toolchain = worktree
+        .as_ref()
+        .and_then(|w| RepoManager::new(conn, config).get_by_id(&w.repo_id).ok())
+        .map(|r| r.toolchain)
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| Toolchain::detect(Path::new(worktree_path)));
+    let mut sections = project_instructions(worktree_path, &toolchain);
 
     // For ephemeral runs (no worktree), skip worktree-specific context
     let Some(wt_id) = worktree_id else {
@@ -69,8 +104,6 @@ pub fn build_startup_context(
     };
 
     // 1. Worktree branch
-    let wt_mgr = WorktreeManager::new(conn, config);
-    let worktree = wt_mgr.get_by_id(wt_id).ok();
     let branch = worktree.as_ref().map(|w| w.branch.clone());
 
     if let Some(ref branch) = branch {
@@ -162,12 +195,95 @@ pub fn build_startup_context(
         ));
     }
 
+    // 5. TODOs the branch added, from the last scan
+    if config.agents.todos_in_prompt {
+        let todos = wt_mgr.todos(wt_id).unwrap_or_default();
+        if !todos.is_empty() {
+            let mut lines: Vec<String> = todos
+                .iter()
+                .take(MAX_CONTEXT_TODOS)
+                .map(|t| format!("- {t}"))
+                .collect();
+            if todos.len() > MAX_CONTEXT_TODOS {
+                lines.push(format!("- …and {} more", todos.len() - MAX_CONTEXT_TODOS));
+            }
+            sections.push(format!(
+                "**Unfinished items (TODO/FIXME comments added on this branch):**\n{}",
+                lines.join("\n")
+            ));
+        }
+    }
+
     // Always include the feedback protocol so agents know how to request input.
     sections.push(FEEDBACK_PROTOCOL.to_string());
 
     format!("## Session Context\n\n{}", sections.join("\n\n"))
 }
 
+/// Prior results carried into a rotation recap, newest last.
+const RECAP_MAX_RESULTS: usize = 3;
+
+/// Build the recap that seeds a fresh session when `session_id` is rotated.
+///
+/// Summarises the runs that shared the old session: the task that started it,
+/// the most recent outcomes, and any plan steps still left to do. The caller
+/// prepends this to the prompt in place of `--resume`.
+pub fn build_session_recap(conn: &Connection, session_id: &str, turns: i64) -> String {
+    let runs = AgentManager::new(conn)
+        .list_for_session(session_id)
+        .unwrap_or_default();
+
+    let mut sections = vec![format!(
+        "## Session Recap
+
+This run continues earlier work in a fresh session. The previous \
+         session reached {turns} turns and was rotated to keep its context small; \
+         the summary below replaces its history."
+    )];
+
+    if let Some(first) = runs.first() {
+        let task = crate::text_util::cap_with_suffix(&first.prompt, 1000, "…");
+        sections.push(format!("**Original task:** {task}"));
+    }
+
+    let results: Vec<String> = runs
+        .iter()
+        .filter_map(|r| {
+            r.result_text.as_deref().map(|text| {
+                format!(
+                    "- ({}) {}",
+                    r.status,
+                    crate::text_util::cap_with_suffix(text, 500, "…")
+                )
+            })
+        })
+        .collect();
+    if !results.is_empty() {
+        let skip = results.len().saturating_sub(RECAP_MAX_RESULTS);
+        sections.push(format!(
+            "**Results so far:**\n{}",
+            results[skip..].join("\n")
+        ));
+    }
+
+    if let Some(run) = runs.iter().rev().find(|r| r.plan.is_some()) {
+        let remaining: Vec<String> = run
+            .incomplete_plan_steps()
+            .iter()
+            .enumerate()
+            .map(|(i, step)| format!("{}. {}", i + 1, step.description))
+            .collect();
+        if !remaining.is_empty() {
+            sections.push(format!(
+                "**Remaining plan steps:**\n{}",
+                remaining.join("\n")
+            ));
+        }
+    }
+
+    sections.join("\n\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,6 +311,44 @@ mod tests {
         assert!(!ctx.contains("**Ticket:**"));
     }
 
+    #[test]
+    fn test_build_startup_context_includes_project_conventions() {
+        let conn = setup_conn();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            "[project]\ntest_command = \"make check\"\nagent_preamble = \"Keep diffs small.\"\nprotected_paths = [\"vendor/\"]\n",
+        )
+        .unwrap();
+        let ctx = build_startup_context(
+            &conn,
+            &Config::default(),
+            None,
+            "run-1",
+            dir.path().to_str().unwrap(),
+        );
+        assert!(ctx.starts_with("**Project conventions:**\nKeep diffs small."));
+        assert!(ctx.contains("Run `make check`"));
+        assert!(ctx.contains("Do not modify: `vendor/`."));
+    }
+
+    #[test]
+    fn test_build_startup_context_uses_toolchain_test_command() {
+        let conn = setup_conn();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        let ctx = build_startup_context(
+            &conn,
+            &Config::default(),
+            None,
+            "run-1",
+            dir.path().to_str().unwrap(),
+        );
+        assert!(ctx.contains("Run `cargo test` to test your changes"));
+        assert!(ctx.contains("**Toolchain:** rust (cargo, libtest)"));
+    }
+
     #[test]
     fn test_build_startup_context_with_worktree_no_ticket() {
         let conn = setup_conn();
@@ -275,6 +429,28 @@ mod tests {
         assert!(ctx.contains("**Ticket:** #42 — Fix payment bug"));
     }
 
+    #[test]
+    fn test_startup_context_lists_branch_todos_when_enabled() {
+        let conn = setup_conn();
+        conn.execute(
+            "INSERT INTO worktree_todos (worktree_id, file, line, marker, text, found_at) \
+             VALUES ('w1', 'src/a.rs', 7, 'TODO', 'retry on timeout', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        let current = AgentManager::new(&conn)
+            .create_run(Some("w1"), "Continue", None)
+            .unwrap();
+
+        let ctx = build_startup_context(&conn, &Config::default(), Some("w1"), &current.id, "/tmp");
+        assert!(!ctx.contains("Unfinished items"));
+
+        let mut config = Config::default();
+        config.agents.todos_in_prompt = true;
+        let ctx = build_startup_context(&conn, &config, Some("w1"), &current.id, "/tmp");
+        assert!(ctx.contains("- src/a.rs:7 TODO retry on timeout"));
+    }
+
     #[test]
     fn test_startup_context_includes_prior_plan_steps() {
         let conn = setup_conn();
@@ -325,6 +501,56 @@ mod tests {
         assert!(ctx.contains("3. ⏳ Implement feature"));
     }
 
+    #[test]
+    fn test_build_session_recap_summarises_session_runs() {
+        let conn = setup_conn();
+        let mgr = AgentManager::new(&conn);
+
+        let first = mgr
+            .create_run(Some("w1"), "Migrate the parser", None)
+            .unwrap();
+        mgr.update_run_plan(
+            &first.id,
+            &[
+                PlanStep {
+                    description: "Port the lexer".to_string(),
+                    done: true,
+                    status: StepStatus::Completed,
+                    ..Default::default()
+                },
+                PlanStep {
+                    description: "Port the grammar".to_string(),
+                    ..Default::default()
+                },
+            ],
+        )
+        .unwrap();
+        mgr.update_run_completed(
+            &first.id,
+            Some("sess-1"),
+            Some("Lexer ported"),
+            None,
+            Some(40),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let unrelated = mgr.create_run(Some("w1"), "Unrelated", None).unwrap();
+        mgr.update_run_session_id(&unrelated.id, "sess-2").unwrap();
+
+        let recap = build_session_recap(&conn, "sess-1", 40);
+        assert!(recap.starts_with("## Session Recap"));
+        assert!(recap.contains("reached 40 turns"));
+        assert!(recap.contains("**Original task:** Migrate the parser"));
+        assert!(recap.contains("- (completed) Lexer ported"));
+        assert!(recap.contains("**Remaining plan steps:**\n1. Port the grammar"));
+        assert!(!recap.contains("Port the lexer"));
+        assert!(!recap.contains("Unrelated"));
+    }
+
     #[test]
     fn test_startup_context_truncates_long_result() {
         let conn = setup_conn();
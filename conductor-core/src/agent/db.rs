@@ -1,3 +1,4 @@
+use super::environment::RunEnvironment;
 use super::status::StepStatus;
 use super::types::{
     AgentCreatedIssue, AgentRun, AgentRunEvent, FeedbackOption, FeedbackRequest, PlanStep,
@@ -16,7 +17,7 @@ pub(super) const AGENT_RUN_SELECT: &str =
      model, plan, parent_run_id, \
      input_tokens, output_tokens, cache_read_input_tokens, cache_creation_input_tokens, \
      bot_name, conversation_id, subprocess_pid, \
-     COALESCE(runtime, 'claude') AS runtime FROM agent_runs";
+     COALESCE(runtime, 'claude') AS runtime, rotated_from_session_id, environment FROM agent_runs";
 
 /// Generate an `agent_runs` column list with a given table alias.
 ///
@@ -78,7 +79,11 @@ macro_rules! agent_run_cols {
             "subprocess_pid, ",
             "COALESCE(",
             $alias,
-            "runtime, 'claude') AS runtime"
+            "runtime, 'claude') AS runtime, ",
+            $alias,
+            "rotated_from_session_id, ",
+            $alias,
+            "environment"
         )
     };
     ($alias:literal, null_plan) => {
@@ -130,7 +135,11 @@ macro_rules! agent_run_cols {
             "subprocess_pid, ",
             "COALESCE(",
             $alias,
-            "runtime, 'claude') AS runtime"
+            "runtime, 'claude') AS runtime, ",
+            $alias,
+            "rotated_from_session_id, ",
+            $alias,
+            "environment"
         )
     };
 }
@@ -213,6 +222,9 @@ pub(super) fn row_to_agent_created_issue(
 pub(super) fn row_to_agent_run(row: &rusqlite::Row) -> rusqlite::Result<AgentRun> {
     // Plan is populated separately from agent_run_steps table by the caller.
     // The "plan" column is still selected for SQL compatibility but ignored here.
+    let environment_json: Option<String> = row.get("environment")?;
+    let environment: Option<RunEnvironment> =
+        environment_json.and_then(|j| serde_json::from_str(&j).ok());
     Ok(AgentRun {
         id: row.get("id")?,
         worktree_id: row.get("worktree_id")?,
@@ -238,6 +250,8 @@ pub(super) fn row_to_agent_run(row: &rusqlite::Row) -> rusqlite::Result<AgentRun
         conversation_id: row.get("conversation_id")?,
         subprocess_pid: row.get("subprocess_pid")?,
         runtime: row.get("runtime")?,
+        rotated_from_session_id: row.get("rotated_from_session_id")?,
+        environment,
     })
 }
 
@@ -296,6 +310,8 @@ mod tests {
         "conversation_id",
         "subprocess_pid",
         "runtime",
+        "rotated_from_session_id",
+        "environment",
     ];
 
     #[test]
@@ -0,0 +1,462 @@
+//! Agent code reviews of a worktree's diff.
+//!
+//! [`DiffReviews::prepare`] turns the worktree's diff against its base into a
+//! review prompt that fits the prompt budget. Once the caller has started an
+//! agent run with that prompt, [`DiffReviews::record`] links the run to the
+//! worktree. The review itself is the run's result text, so it shows up as
+//! soon as the run finishes.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::worktree::WorktreeManager;
+
+use super::status::AgentRunStatus;
+
+/// Diff bytes included in a review prompt before files are cut short.
+pub const DIFF_REVIEW_MAX_BYTES: usize = 60_000;
+
+/// Generated files whose diffs are noise to a reviewer. They are listed by
+/// name but their contents are left out.
+const GENERATED_FILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+    "poetry.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+];
+const GENERATED_SUFFIXES: &[&str] = &[".min.js", ".min.css", ".map"];
+
+/// A review prompt built from a worktree's diff, ready to hand to an agent.
+#[derive(Debug, Clone)]
+pub struct DiffReviewRequest {
+    pub worktree_id: String,
+    pub base_ref: String,
+    pub prompt: String,
+    pub files_changed: usize,
+    /// Whether any file was cut short or left out to fit the budget.
+    pub truncated: bool,
+}
+
+/// A recorded review joined with the agent run that produced it.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffReview {
+    pub id: String,
+    pub worktree_id: String,
+    pub run_id: String,
+    pub base_ref: String,
+    pub files_changed: i64,
+    pub truncated: bool,
+    pub status: AgentRunStatus,
+    /// The agent's review; `None` until the run finishes.
+    pub review: Option<String>,
+    pub created_at: String,
+    pub ended_at: Option<String>,
+}
+
+/// A diff trimmed to fit a byte budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrimmedDiff {
+    pub text: String,
+    pub files_changed: usize,
+    /// Generated files whose contents were left out.
+    pub omitted: Vec<String>,
+    pub truncated: bool,
+}
+
+pub struct DiffReviews<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> DiffReviews<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Build a review prompt from the worktree's diff against its base.
+    /// Fails with [`ConductorError::InvalidInput`] when there is nothing to review.
+    pub fn prepare(&self, config: &Config, worktree_id: &str) -> Result<DiffReviewRequest> {
+        let wt_mgr = WorktreeManager::new(self.conn, config);
+        let wt = wt_mgr.get_by_id(worktree_id)?;
+        let repo = RepoManager::new(self.conn, config).get_by_id(&wt.repo_id)?;
+        let base_ref = wt.effective_base(&repo.default_branch).to_string();
+
+        let diff = wt_mgr.diff(&repo.slug, &wt.slug)?;
+        if diff.trim().is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "worktree '{}' has no changes against {base_ref} to review",
+                wt.slug
+            )));
+        }
+
+        let trimmed = trim_diff(&diff, DIFF_REVIEW_MAX_BYTES);
+        Ok(DiffReviewRequest {
+            worktree_id: wt.id,
+            prompt: build_review_prompt(&trimmed, &base_ref),
+            base_ref,
+            files_changed: trimmed.files_changed,
+            truncated: trimmed.truncated,
+        })
+    }
+
+    /// Link the agent run started for `request` to its worktree.
+    pub fn record(&self, request: &DiffReviewRequest, run_id: &str) -> Result<DiffReview> {
+        let id = crate::new_id();
+        self.conn.execute(
+            "INSERT INTO worktree_diff_reviews \
+                 (id, worktree_id, run_id, base_ref, files_changed, truncated, created_at) \
+             VALUES (:id, :worktree_id, :run_id, :base_ref, :files_changed, :truncated, :created_at)",
+            named_params! {
+                ":id": id,
+                ":worktree_id": request.worktree_id,
+                ":run_id": run_id,
+                ":base_ref": request.base_ref,
+                ":files_changed": request.files_changed as i64,
+                ":truncated": request.truncated,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.conn
+            .query_row(
+                &format!("{REVIEW_SELECT} WHERE d.id = :id"),
+                named_params! { ":id": id },
+                row_to_diff_review,
+            )
+            .map_err(ConductorError::from)
+    }
+
+    /// The most recent review of a worktree, if any.
+    pub fn latest_for_worktree(&self, worktree_id: &str) -> Result<Option<DiffReview>> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!(
+                    "{REVIEW_SELECT} WHERE d.worktree_id = :worktree_id \
+                     ORDER BY d.created_at DESC, d.id DESC LIMIT 1"
+                ),
+                named_params! { ":worktree_id": worktree_id },
+                row_to_diff_review,
+            )
+            .optional()?)
+    }
+}
+
+const REVIEW_SELECT: &str = "SELECT d.id, d.worktree_id, d.run_id, d.base_ref, d.files_changed, \
+            d.truncated, d.created_at, a.status, a.result_text, a.ended_at \
+     FROM worktree_diff_reviews d \
+     JOIN agent_runs a ON a.id = d.run_id";
+
+fn row_to_diff_review(row: &rusqlite::Row<'_>) -> rusqlite::Result<DiffReview> {
+    let status: AgentRunStatus = row.get("status")?;
+    // Only a completed run's result is a review; a failed run's result is
+    // its error message.
+    let review = if status == AgentRunStatus::Completed {
+        row.get("result_text")?
+    } else {
+        None
+    };
+    Ok(DiffReview {
+        id: row.get("id")?,
+        worktree_id: row.get("worktree_id")?,
+        run_id: row.get("run_id")?,
+        base_ref: row.get("base_ref")?,
+        files_changed: row.get("files_changed")?,
+        truncated: row.get("truncated")?,
+        status,
+        review,
+        created_at: row.get("created_at")?,
+        ended_at: row.get("ended_at")?,
+    })
+}
+
+/// The review instructions followed by the (possibly trimmed) diff.
+pub fn build_review_prompt(diff: &TrimmedDiff, base_ref: &str) -> String {
+    let mut prompt = format!(
+        "Review the changes on this branch against `{base_ref}` before a pull request is opened.\n\n\
+         Look for bugs, unhandled errors and edge cases, missing tests, security problems and \
+         anything a future reader would find confusing. Report findings most important first, \
+         each with its file and line, and say plainly if the change looks good. This is a \
+         review only: do not modify, commit or push anything.\n"
+    );
+    if diff.truncated {
+        prompt.push_str(
+            "\nThe diff below was trimmed to fit. Read the files in the working tree \
+             wherever you need more context.\n",
+        );
+    }
+    if !diff.omitted.is_empty() {
+        prompt.push_str(&format!(
+            "\nGenerated files changed (contents not shown): {}\n",
+            diff.omitted.join(", ")
+        ));
+    }
+    prompt.push_str("\n```diff\n");
+    prompt.push_str(&diff.text);
+    if !diff.text.ends_with('\n') {
+        prompt.push('\n');
+    }
+    prompt.push_str("```\n");
+    prompt
+}
+
+/// Trim a unified diff to roughly `max_bytes`.
+///
+/// Generated files are dropped first. The remaining budget is shared so that
+/// small files are always shown whole and only the largest files are cut,
+/// each keeping its header and as many leading lines as fit.
+pub fn trim_diff(diff: &str, max_bytes: usize) -> TrimmedDiff {
+    let files = split_files(diff);
+    let files_changed = files.len();
+
+    let mut omitted = Vec::new();
+    let mut kept = Vec::new();
+    for file in files {
+        let path = file_path(file);
+        if is_generated(path) {
+            omitted.push(path.to_string());
+        } else {
+            kept.push(file);
+        }
+    }
+
+    // Water-fill: visit files smallest first, giving each an equal share of
+    // what is left, so leftovers from small files flow to the large ones.
+    let mut order: Vec<usize> = (0..kept.len()).collect();
+    order.sort_by_key(|&i| kept[i].len());
+    let mut budgets = vec![0; kept.len()];
+    let mut remaining = max_bytes;
+    for (n, &i) in order.iter().enumerate() {
+        let share = remaining / (kept.len() - n);
+        budgets[i] = kept[i].len().min(share);
+        remaining -= budgets[i];
+    }
+
+    let mut truncated = !omitted.is_empty();
+    let mut text = String::new();
+    for (file, budget) in kept.into_iter().zip(budgets) {
+        if file.len() <= budget {
+            text.push_str(file);
+            continue;
+        }
+        truncated = true;
+        let cut = cut_file(file, budget);
+        text.push_str(cut);
+        if !cut.ends_with('\n') {
+            text.push('\n');
+        }
+        let omitted_lines = file[cut.len()..].lines().count();
+        text.push_str(&format!(
+            "... [{omitted_lines} more lines of this file omitted]\n"
+        ));
+    }
+
+    TrimmedDiff {
+        text,
+        files_changed,
+        omitted,
+        truncated,
+    }
+}
+
+/// One file's part of a unified diff.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileDiff {
+    /// Post-image path.
+    pub path: String,
+    pub added: usize,
+    pub removed: usize,
+    /// The file's chunk, from its `diff --git` line to the next file's.
+    pub text: String,
+}
+
+/// Split a unified diff into per-file diffs, in diff order.
+pub fn split_diff(diff: &str) -> Vec<FileDiff> {
+    split_files(diff)
+        .into_iter()
+        .map(|chunk| {
+            let (mut added, mut removed) = (0, 0);
+            for line in chunk.lines() {
+                if line.starts_with('+') && !line.starts_with("+++") {
+                    added += 1;
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    removed += 1;
+                }
+            }
+            FileDiff {
+                path: file_path(chunk).to_string(),
+                added,
+                removed,
+                text: chunk.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Split a unified diff into one chunk per file. Text before the first
+/// `diff --git` line (there normally is none) stays with the first chunk.
+fn split_files(diff: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = diff
+        .match_indices("diff --git ")
+        .map(|(i, _)| i)
+        .filter(|&i| i == 0 || diff.as_bytes()[i - 1] == b'\n')
+        .collect();
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain(std::iter::once(&diff.len())))
+        .map(|(&start, &end)| &diff[start..end])
+        .filter(|chunk| !chunk.trim().is_empty())
+        .collect()
+}
+
+/// The post-image path from a file chunk's `diff --git a/… b/…` line.
+fn file_path(chunk: &str) -> &str {
+    let header = chunk.lines().next().unwrap_or_default();
+    header
+        .rsplit_once(" b/")
+        .map(|(_, path)| path)
+        .unwrap_or(header)
+}
+
+fn is_generated(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    GENERATED_FILES.contains(&name) || GENERATED_SUFFIXES.iter().any(|s| name.ends_with(s))
+}
+
+/// The longest prefix of `file` that ends on a line boundary and fits in
+/// `budget`, but never shorter than the file header (everything before the
+/// first hunk).
+fn cut_file(file: &str, budget: usize) -> &str {
+    let header_end = file.find("\n@@").map(|i| i + 1).unwrap_or(file.len());
+    let limit = budget.max(header_end).min(file.len());
+    let end = file[..limit].rfind('\n').map(|i| i + 1).unwrap_or(limit);
+    &file[..end.max(header_end)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::insert_test_agent_run;
+
+    fn file_diff(path: &str, lines: usize) -> String {
+        let mut s = format!(
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n@@ -1,0 +1,{lines} @@\n"
+        );
+        for i in 0..lines {
+            s.push_str(&format!("+line {i} of {path}\n"));
+        }
+        s
+    }
+
+    #[test]
+    fn split_diff_counts_lines_per_file() {
+        let removal = "diff --git a/old.txt b/old.txt\ndeleted file mode 100644\n--- a/old.txt\n+++ /dev/null\n@@ -1,2 +0,0 @@\n-a\n-b\n";
+        let diff = format!("{}{removal}", file_diff("src/a.rs", 3));
+        let files = split_diff(&diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            (files[0].path.as_str(), files[0].added, files[0].removed),
+            ("src/a.rs", 3, 0)
+        );
+        assert_eq!(
+            (files[1].path.as_str(), files[1].added, files[1].removed),
+            ("old.txt", 0, 2)
+        );
+        assert_eq!(files[1].text, removal);
+        assert!(split_diff("").is_empty());
+    }
+
+    #[test]
+    fn small_diffs_are_kept_whole() {
+        let diff = format!("{}{}", file_diff("src/a.rs", 3), file_diff("src/b.rs", 2));
+        let trimmed = trim_diff(&diff, 10_000);
+        assert_eq!(trimmed.text, diff);
+        assert_eq!(trimmed.files_changed, 2);
+        assert!(!trimmed.truncated);
+        assert!(trimmed.omitted.is_empty());
+    }
+
+    #[test]
+    fn large_files_are_cut_while_small_files_survive() {
+        let small = file_diff("src/small.rs", 2);
+        let big = file_diff("src/big.rs", 500);
+        let diff = format!("{big}{small}");
+        let trimmed = trim_diff(&diff, 2_000);
+
+        assert!(trimmed.truncated);
+        assert!(trimmed.text.contains(&small));
+        assert!(trimmed
+            .text
+            .contains("diff --git a/src/big.rs b/src/big.rs"));
+        assert!(trimmed.text.contains("+line 0 of src/big.rs\n"));
+        assert!(!trimmed.text.contains("+line 499 of src/big.rs"));
+        assert!(trimmed.text.contains("more lines of this file omitted]"));
+        // Files keep their original order.
+        assert!(trimmed.text.find("big.rs").unwrap() < trimmed.text.find("small.rs").unwrap());
+        assert!(trimmed.text.len() < 2_200);
+    }
+
+    #[test]
+    fn generated_files_are_listed_but_not_shown() {
+        let diff = format!(
+            "{}{}",
+            file_diff("Cargo.lock", 50),
+            file_diff("src/lib.rs", 1)
+        );
+        let trimmed = trim_diff(&diff, 10_000);
+        assert_eq!(trimmed.omitted, ["Cargo.lock"]);
+        assert_eq!(trimmed.files_changed, 2);
+        assert!(!trimmed.text.contains("Cargo.lock"));
+
+        let prompt = build_review_prompt(&trimmed, "main");
+        assert!(prompt.contains("against `main`"));
+        assert!(prompt.contains("Generated files changed (contents not shown): Cargo.lock"));
+        assert!(prompt.contains("+line 0 of src/lib.rs"));
+    }
+
+    #[test]
+    fn record_and_latest_follow_the_run() {
+        let conn = crate::test_helpers::setup_db();
+        insert_test_agent_run(&conn, "run1", "w1");
+        insert_test_agent_run(&conn, "run2", "w1");
+        let reviews = DiffReviews::new(&conn);
+        assert!(reviews.latest_for_worktree("w1").unwrap().is_none());
+
+        let request = DiffReviewRequest {
+            worktree_id: "w1".into(),
+            base_ref: "main".into(),
+            prompt: "review".into(),
+            files_changed: 3,
+            truncated: true,
+        };
+        reviews.record(&request, "run1").unwrap();
+        let second = reviews.record(&request, "run2").unwrap();
+        assert_eq!(second.status, AgentRunStatus::Running);
+        assert!(second.review.is_none());
+
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', result_text = 'Looks good.' \
+             WHERE id = 'run2'",
+            [],
+        )
+        .unwrap();
+        let latest = reviews.latest_for_worktree("w1").unwrap().unwrap();
+        assert_eq!(latest.run_id, "run2");
+        assert_eq!(latest.review.as_deref(), Some("Looks good."));
+        assert_eq!(latest.files_changed, 3);
+        assert!(latest.truncated);
+    }
+}
@@ -0,0 +1,188 @@
+//! Facts about where an agent run executed, captured once when it starts so
+//! that differences between runs ("it worked last week") can be traced to a
+//! CLI upgrade, a model change or a different base commit.
+
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::backend::{AgentBackend, DEFAULT_BACKEND};
+use crate::git::git_in;
+
+/// Environment an agent run started in. Stored as JSON in
+/// `agent_runs.environment`; fields are optional because any probe can fail.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunEnvironment {
+    /// Version of conductor that launched the run.
+    pub conductor_version: String,
+    /// Agent CLI that ran, e.g. `claude` or `codex`.
+    #[serde(default = "default_backend")]
+    pub backend: String,
+    /// Output of `<backend> --version`, e.g. `1.0.51 (Claude Code)`.
+    #[serde(default, alias = "claude_version")]
+    pub agent_version: Option<String>,
+    /// Model passed to the CLI; `None` means the CLI default.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Worktree `HEAD` when the run started.
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// `std::env::consts::OS`, e.g. `linux` or `macos`.
+    pub os: String,
+    /// `std::env::consts::ARCH`, e.g. `x86_64` or `aarch64`.
+    pub arch: String,
+    /// Container the agent ran in, e.g. `docker ghcr.io/acme/agent:1`;
+    /// `None` when it ran on the host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<String>,
+}
+
+impl RunEnvironment {
+    /// Probe the current process, the agent CLI and the worktree at
+    /// `worktree_path`. Never fails; unavailable facts are left as `None`.
+    pub fn capture(worktree_path: &str, backend: &dyn AgentBackend, model: Option<&str>) -> Self {
+        Self {
+            conductor_version: env!("CARGO_PKG_VERSION").to_string(),
+            backend: backend.name().to_string(),
+            agent_version: stdout_line(Command::new(backend.binary()).arg("--version")),
+            model: model.map(str::to_string),
+            git_commit: stdout_line(git_in(worktree_path).args(["rev-parse", "HEAD"])),
+            git_branch: stdout_line(git_in(worktree_path).args([
+                "rev-parse",
+                "--abbrev-ref",
+                "HEAD",
+            ])),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            sandbox: None,
+        }
+    }
+
+    /// One-line summary for status lines and logs, e.g.
+    /// `claude 1.0.51 · sonnet · a1b2c3d4 (main) · linux/x86_64 · conductor 0.1.0`.
+    pub fn summary(&self) -> String {
+        let version = self
+            .agent_version
+            .as_deref()
+            .map(|v| v.trim_end_matches(" (Claude Code)"))
+            .unwrap_or("unknown");
+        let model = self.model.as_deref().unwrap_or("default model");
+        let commit = match (&self.git_commit, &self.git_branch) {
+            (Some(sha), Some(branch)) => format!("{} ({branch})", short_sha(sha)),
+            (Some(sha), None) => short_sha(sha).to_string(),
+            (None, _) => "no commit".to_string(),
+        };
+        let mut summary = format!(
+            "{} {version} · {model} · {commit} · {}/{} · conductor {}",
+            self.backend, self.os, self.arch, self.conductor_version
+        );
+        if let Some(sandbox) = &self.sandbox {
+            summary.push_str(&format!(" · in {sandbox}"));
+        }
+        summary
+    }
+}
+
+fn default_backend() -> String {
+    DEFAULT_BACKEND.to_string()
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..8.min(sha.len())]
+}
+
+/// First line of a successful command's stdout, or `None` if it could not be
+/// run, failed, or printed nothing.
+fn stdout_line(cmd: &mut Command) -> Option<String> {
+    let out = crate::command::output(cmd).ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let line = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .to_string();
+    (!line.is_empty()).then_some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::backend::{AiderBackend, ClaudeBackend};
+
+    #[test]
+    fn capture_records_worktree_head_and_platform() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            let status = git_in(path).args(args).output().unwrap().status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+
+        let env = RunEnvironment::capture(path, &ClaudeBackend, Some("sonnet"));
+        assert_eq!(env.git_commit.as_deref().map(str::len), Some(40));
+        assert_eq!(env.git_branch.as_deref(), Some("main"));
+        assert_eq!(env.model.as_deref(), Some("sonnet"));
+        assert_eq!(env.os, std::env::consts::OS);
+        assert_eq!(env.conductor_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn capture_outside_a_repo_leaves_git_fields_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = RunEnvironment::capture(dir.path().to_str().unwrap(), &AiderBackend, None);
+        assert_eq!(env.backend, "aider");
+        assert!(env.git_commit.is_none());
+        assert!(env.git_branch.is_none());
+    }
+
+    #[test]
+    fn summary_shortens_commit_and_cli_version() {
+        let env = RunEnvironment {
+            conductor_version: "0.3.0".into(),
+            backend: "claude".into(),
+            agent_version: Some("1.0.51 (Claude Code)".into()),
+            model: None,
+            git_commit: Some("a1b2c3d4e5f6a7b8c9d0a1b2c3d4e5f6a7b8c9d0".into()),
+            git_branch: Some("feat/x".into()),
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            sandbox: None,
+        };
+        assert_eq!(
+            env.summary(),
+            "claude 1.0.51 · default model · a1b2c3d4 (feat/x) · linux/x86_64 · conductor 0.3.0"
+        );
+        let env = RunEnvironment {
+            sandbox: Some("docker agent:1".into()),
+            ..env
+        };
+        assert!(env.summary().ends_with(" · in docker agent:1"));
+    }
+
+    #[test]
+    fn environments_recorded_before_backends_default_to_claude() {
+        let env: RunEnvironment = serde_json::from_str(
+            r#"{"conductor_version":"0.3.0","claude_version":"1.0.51 (Claude Code)","os":"linux","arch":"x86_64"}"#,
+        )
+        .unwrap();
+        assert_eq!(env.backend, "claude");
+        assert_eq!(env.agent_version.as_deref(), Some("1.0.51 (Claude Code)"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Finished agent runs the user has not reviewed yet.
+//!
+//! A run lands in the inbox when it completes or fails in a live worktree and
+//! leaves once it is marked reviewed. Only top-level runs started directly on
+//! a worktree are listed: workflow steps and child runs are reported through
+//! their workflow or parent instead.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+use crate::worktree::LIVE_STATUSES_SQL;
+
+use super::status::AgentRunStatus;
+
+/// An unreviewed finished run, joined with its worktree for display.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxItem {
+    pub run_id: String,
+    pub worktree_id: String,
+    pub repo_id: String,
+    pub repo_slug: String,
+    pub worktree_slug: String,
+    pub branch: String,
+    pub status: AgentRunStatus,
+    pub prompt: String,
+    pub result_text: Option<String>,
+    pub cost_usd: Option<f64>,
+    pub num_turns: Option<i64>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+}
+
+/// The inbox SELECT for `viewer`; binds `:viewer` unless the viewer is local.
+fn inbox_query(viewer: &Viewer) -> String {
+    let visible = visible_repo_sql(viewer, "w.repo_id", ":viewer")
+        .map(|v| format!(" AND {v}"))
+        .unwrap_or_default();
+    format!(
+        "SELECT a.id AS run_id, a.worktree_id, a.status, a.prompt, a.result_text, \
+                a.cost_usd, a.num_turns, a.started_at, a.ended_at, \
+                w.repo_id, w.slug AS worktree_slug, w.branch, r.slug AS repo_slug \
+         FROM agent_runs a \
+         JOIN worktrees w ON w.id = a.worktree_id \
+         JOIN repos r ON r.id = w.repo_id \
+         WHERE a.status IN ('completed', 'failed') \
+           AND a.parent_run_id IS NULL \
+           AND w.status IN {LIVE_STATUSES_SQL} \
+           AND NOT EXISTS (SELECT 1 FROM agent_run_reviews v WHERE v.run_id = a.id) \
+           AND NOT EXISTS (SELECT 1 FROM workflow_run_steps s WHERE s.child_run_id = a.id) \
+           AND NOT EXISTS (SELECT 1 FROM workflow_runs wr WHERE wr.parent_run_id = a.id)\
+           {visible}"
+    )
+}
+
+pub struct AgentInbox<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AgentInbox<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Unreviewed runs, most recently finished first.
+    pub fn list(&self) -> Result<Vec<InboxItem>> {
+        self.list_visible(&Viewer::Local)
+    }
+
+    /// Like [`Self::list`], leaving out runs in repos that are private to
+    /// someone other than `viewer`.
+    pub fn list_visible(&self, viewer: &Viewer) -> Result<Vec<InboxItem>> {
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = Vec::new();
+        if *viewer != Viewer::Local {
+            params.push((":viewer", &owner));
+        }
+        query_collect(
+            self.conn,
+            &format!(
+                "{} ORDER BY COALESCE(a.ended_at, a.started_at) DESC, a.id",
+                inbox_query(viewer)
+            ),
+            params.as_slice(),
+            row_to_inbox_item,
+        )
+    }
+
+    /// Mark a finished run as reviewed. Marking a run twice is a no-op.
+    pub fn mark_reviewed(&self, run_id: &str) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM agent_runs WHERE id = :id)",
+            named_params! { ":id": run_id },
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(ConductorError::AgentRunNotFound {
+                id: run_id.to_string(),
+            });
+        }
+        self.conn.execute(
+            "INSERT OR IGNORE INTO agent_run_reviews (run_id, reviewed_at) \
+             VALUES (:run_id, :reviewed_at)",
+            named_params! { ":run_id": run_id, ":reviewed_at": Utc::now().to_rfc3339() },
+        )?;
+        Ok(())
+    }
+
+    /// Mark everything currently in the inbox as reviewed. Returns how many
+    /// runs were cleared.
+    pub fn mark_all_reviewed(&self) -> Result<usize> {
+        self.mark_all_visible_reviewed(&Viewer::Local)
+    }
+
+    /// Like [`Self::mark_all_reviewed`], only clearing the runs `viewer` can
+    /// see in [`Self::list_visible`].
+    pub fn mark_all_visible_reviewed(&self, viewer: &Viewer) -> Result<usize> {
+        let reviewed_at = Utc::now().to_rfc3339();
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":reviewed_at", &reviewed_at)];
+        if *viewer != Viewer::Local {
+            params.push((":viewer", &owner));
+        }
+        let cleared = self.conn.execute(
+            &format!(
+                "INSERT OR IGNORE INTO agent_run_reviews (run_id, reviewed_at) \
+                 SELECT run_id, :reviewed_at FROM ({})",
+                inbox_query(viewer)
+            ),
+            params.as_slice(),
+        )?;
+        Ok(cleared)
+    }
+}
+
+fn row_to_inbox_item(row: &rusqlite::Row<'_>) -> rusqlite::Result<InboxItem> {
+    Ok(InboxItem {
+        run_id: row.get("run_id")?,
+        worktree_id: row.get("worktree_id")?,
+        repo_id: row.get("repo_id")?,
+        repo_slug: row.get("repo_slug")?,
+        worktree_slug: row.get("worktree_slug")?,
+        branch: row.get("branch")?,
+        status: row.get("status")?,
+        prompt: row.get("prompt")?,
+        result_text: row.get("result_text")?,
+        cost_usd: row.get("cost_usd")?,
+        num_turns: row.get("num_turns")?,
+        started_at: row.get("started_at")?,
+        ended_at: row.get("ended_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::insert_test_agent_run;
+
+    fn setup() -> Connection {
+        super::super::manager::setup_db()
+    }
+
+    fn finish(conn: &Connection, id: &str, status: &str, ended_at: &str) {
+        conn.execute(
+            "UPDATE agent_runs SET status = :status, ended_at = :ended_at WHERE id = :id",
+            named_params! { ":id": id, ":status": status, ":ended_at": ended_at },
+        )
+        .unwrap();
+    }
+
+    fn run_ids(items: &[InboxItem]) -> Vec<&str> {
+        items.iter().map(|i| i.run_id.as_str()).collect()
+    }
+
+    #[test]
+    fn list_shows_finished_unreviewed_runs_newest_first() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "done", "w1");
+        insert_test_agent_run(&conn, "failed", "w2");
+        insert_test_agent_run(&conn, "cancelled", "w1");
+        insert_test_agent_run(&conn, "running", "w2");
+        insert_test_agent_run(&conn, "child", "w1");
+        finish(&conn, "done", "completed", "2024-01-02T00:00:00Z");
+        finish(&conn, "failed", "failed", "2024-01-03T00:00:00Z");
+        finish(&conn, "cancelled", "cancelled", "2024-01-04T00:00:00Z");
+        finish(&conn, "child", "completed", "2024-01-05T00:00:00Z");
+        conn.execute(
+            "UPDATE agent_runs SET parent_run_id = 'done' WHERE id = 'child'",
+            [],
+        )
+        .unwrap();
+
+        let items = AgentInbox::new(&conn).list().unwrap();
+        assert_eq!(run_ids(&items), ["failed", "done"]);
+        assert_eq!(items[0].worktree_slug, "fix-bug");
+        assert_eq!(items[1].repo_slug, "test-repo");
+    }
+
+    #[test]
+    fn mark_reviewed_removes_runs_from_inbox() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "a", "w1");
+        insert_test_agent_run(&conn, "b", "w2");
+        insert_test_agent_run(&conn, "c", "w2");
+        finish(&conn, "a", "completed", "2024-01-02T00:00:00Z");
+        finish(&conn, "b", "completed", "2024-01-03T00:00:00Z");
+        finish(&conn, "c", "failed", "2024-01-04T00:00:00Z");
+
+        let inbox = AgentInbox::new(&conn);
+        inbox.mark_reviewed("b").unwrap();
+        inbox.mark_reviewed("b").unwrap();
+        assert_eq!(run_ids(&inbox.list().unwrap()), ["c", "a"]);
+
+        assert!(matches!(
+            inbox.mark_reviewed("missing"),
+            Err(ConductorError::AgentRunNotFound { .. })
+        ));
+
+        assert_eq!(inbox.mark_all_reviewed().unwrap(), 2);
+        assert!(inbox.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn runs_in_removed_worktrees_are_hidden() {
+        let conn = setup();
+        insert_test_agent_run(&conn, "a", "w2");
+        finish(&conn, "a", "completed", "2024-01-02T00:00:00Z");
+        conn.execute("UPDATE worktrees SET status = 'merged' WHERE id = 'w2'", [])
+            .unwrap();
+
+        assert!(AgentInbox::new(&conn).list().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+//! Protect uncommitted manual edits when an agent is launched on a worktree.
+//!
+//! Before launching, callers check [`dirty_files`]. When the worktree is dirty
+//! the user picks a [`DirtyWorktreeChoice`]; [`prepare`] carries it out
+//! (stashing or committing the edits) and [`LaunchGuards::record`] stores it
+//! against the new run. Stashes are popped again by
+//! [`LaunchGuards::restore_finished`] once the run has ended.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+
+/// Message of the stash conductor creates before a run.
+const STASH_MESSAGE: &str = "conductor: manual changes before agent run";
+/// Message of the commit conductor creates for [`DirtyWorktreeChoice::CommitWip`].
+const WIP_COMMIT_MESSAGE: &str = "WIP: manual changes before agent run";
+
+/// What to do with uncommitted changes when launching an agent.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirtyWorktreeChoice {
+    /// Stash the changes (including untracked files) and pop them after the run.
+    Stash,
+    /// Commit everything as a WIP commit on the worktree branch.
+    CommitWip,
+    /// Leave the changes in place for the agent to work alongside.
+    Proceed,
+}
+
+impl std::fmt::Display for DirtyWorktreeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Stash => "stash",
+            Self::CommitWip => "commit_wip",
+            Self::Proceed => "proceed",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for DirtyWorktreeChoice {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "stash" => Ok(Self::Stash),
+            "commit_wip" => Ok(Self::CommitWip),
+            "proceed" => Ok(Self::Proceed),
+            _ => Err(format!("unknown dirty worktree choice: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(DirtyWorktreeChoice);
+
+/// A dirty worktree that has been dealt with, ready to be recorded against a run.
+#[derive(Debug, Clone)]
+pub struct PreparedLaunch {
+    pub choice: DirtyWorktreeChoice,
+    pub dirty_files: Vec<String>,
+    /// Stash or WIP commit SHA.
+    pub saved_ref: Option<String>,
+}
+
+/// The choice recorded for a run that was launched on a dirty worktree.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchGuard {
+    pub run_id: String,
+    pub worktree_id: String,
+    pub choice: DirtyWorktreeChoice,
+    pub dirty_files: Vec<String>,
+    pub saved_ref: Option<String>,
+    /// When the stash was popped after the run.
+    pub restored_at: Option<String>,
+    /// Why the stash could not be popped; it is left in `git stash list`.
+    pub restore_error: Option<String>,
+    pub created_at: String,
+}
+
+/// Paths with uncommitted changes in the worktree, untracked files included.
+pub fn dirty_files(worktree_path: &str) -> Result<Vec<String>> {
+    let out = check_output(git_in(worktree_path).args(["status", "--porcelain"]))?;
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Carry out `choice` on the worktree's uncommitted changes.
+pub fn prepare(
+    worktree_path: &str,
+    choice: DirtyWorktreeChoice,
+    dirty_files: Vec<String>,
+) -> Result<PreparedLaunch> {
+    let saved_ref = match choice {
+        DirtyWorktreeChoice::Stash => {
+            check_output(git_in(worktree_path).args([
+                "stash",
+                "push",
+                "--include-untracked",
+                "-m",
+                STASH_MESSAGE,
+            ]))?;
+            Some(rev_parse(worktree_path, "stash@{0}")?)
+        }
+        DirtyWorktreeChoice::CommitWip => {
+            check_output(git_in(worktree_path).args(["add", "-A"]))?;
+            check_output(git_in(worktree_path).args([
+                "commit",
+                "--no-verify",
+                "-m",
+                WIP_COMMIT_MESSAGE,
+            ]))?;
+            Some(rev_parse(worktree_path, "HEAD")?)
+        }
+        DirtyWorktreeChoice::Proceed => None,
+    };
+    Ok(PreparedLaunch {
+        choice,
+        dirty_files,
+        saved_ref,
+    })
+}
+
+fn rev_parse(worktree_path: &str, rev: &str) -> Result<String> {
+    let out = check_output(git_in(worktree_path).args(["rev-parse", rev]))?;
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Pop the stash whose commit is `sha`. Fails if it is gone or does not
+/// apply cleanly, in which case git keeps it in the stash list.
+fn pop_stash(worktree_path: &str, sha: &str) -> Result<()> {
+    let out = check_output(git_in(worktree_path).args(["stash", "list", "--format=%H"]))?;
+    let index = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .position(|line| line.trim() == sha)
+        .ok_or_else(|| {
+            ConductorError::InvalidInput(format!("stash {sha} is no longer in the stash list"))
+        })?;
+    check_output(git_in(worktree_path).args(["stash", "pop", &format!("stash@{{{index}}}")]))?;
+    Ok(())
+}
+
+const GUARD_COLUMNS: &str = "g.run_id, g.worktree_id, g.choice, g.dirty_files, g.saved_ref, \
+     g.restored_at, g.restore_error, g.created_at";
+
+fn row_to_guard(row: &rusqlite::Row) -> rusqlite::Result<LaunchGuard> {
+    let files: String = row.get("dirty_files")?;
+    Ok(LaunchGuard {
+        run_id: row.get("run_id")?,
+        worktree_id: row.get("worktree_id")?,
+        choice: row.get("choice")?,
+        dirty_files: serde_json::from_str(&files).unwrap_or_default(),
+        saved_ref: row.get("saved_ref")?,
+        restored_at: row.get("restored_at")?,
+        restore_error: row.get("restore_error")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+pub struct LaunchGuards<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> LaunchGuards<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Store the choice made for `run_id`.
+    pub fn record(
+        &self,
+        run_id: &str,
+        worktree_id: &str,
+        prepared: &PreparedLaunch,
+    ) -> Result<LaunchGuard> {
+        let files = serde_json::to_string(&prepared.dirty_files)
+            .map_err(|e| ConductorError::InvalidInput(e.to_string()))?;
+        self.conn.execute(
+            "INSERT INTO agent_run_launch_guards \
+                 (run_id, worktree_id, choice, dirty_files, saved_ref, created_at) \
+             VALUES (:run_id, :worktree_id, :choice, :dirty_files, :saved_ref, :created_at)",
+            named_params! {
+                ":run_id": run_id,
+                ":worktree_id": worktree_id,
+                ":choice": prepared.choice,
+                ":dirty_files": files,
+                ":saved_ref": prepared.saved_ref,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.for_run(run_id)?
+            .ok_or_else(|| ConductorError::AgentRunNotFound { id: run_id.into() })
+    }
+
+    pub fn for_run(&self, run_id: &str) -> Result<Option<LaunchGuard>> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {GUARD_COLUMNS} FROM agent_run_launch_guards g WHERE g.run_id = :run_id"
+                ),
+                named_params! { ":run_id": run_id },
+                row_to_guard,
+            )
+            .optional()?)
+    }
+
+    /// Pop the stashes of runs that have ended. Each stash is tried once; a
+    /// failure is recorded on the guard and the stash is left for the user.
+    /// Returns the guards that were processed.
+    pub fn restore_finished(&self) -> Result<Vec<LaunchGuard>> {
+        let pending = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {GUARD_COLUMNS}, w.path FROM agent_run_launch_guards g \
+                 JOIN agent_runs r ON r.id = g.run_id \
+                 JOIN worktrees w ON w.id = g.worktree_id \
+                 WHERE g.choice = 'stash' AND g.saved_ref IS NOT NULL \
+                   AND g.restored_at IS NULL AND g.restore_error IS NULL \
+                   AND r.status IN ('completed', 'failed', 'cancelled')"
+            ),
+            [],
+            |row| Ok((row_to_guard(row)?, row.get::<_, String>("path")?)),
+        )?;
+
+        let mut processed = Vec::with_capacity(pending.len());
+        for (mut guard, path) in pending {
+            // Claim the guard first so two pollers never pop the same stash.
+            let now = Utc::now().to_rfc3339();
+            let claimed = self.conn.execute(
+                "UPDATE agent_run_launch_guards SET restored_at = :now \
+                 WHERE run_id = :run_id AND restored_at IS NULL AND restore_error IS NULL",
+                named_params! { ":now": now, ":run_id": guard.run_id },
+            )?;
+            if claimed == 0 {
+                continue;
+            }
+            let sha = guard.saved_ref.as_deref().unwrap_or_default();
+            match pop_stash(&path, sha) {
+                Ok(()) => guard.restored_at = Some(now),
+                Err(e) => {
+                    tracing::warn!(
+                        "could not restore stash {sha} for run {}: {e}",
+                        guard.run_id
+                    );
+                    self.conn.execute(
+                        "UPDATE agent_run_launch_guards \
+                         SET restored_at = NULL, restore_error = :error WHERE run_id = :run_id",
+                        named_params! { ":error": e.to_string(), ":run_id": guard.run_id },
+                    )?;
+                    guard.restore_error = Some(e.to_string());
+                }
+            }
+            processed.push(guard);
+        }
+        Ok(processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentManager;
+    use crate::test_helpers::{create_test_conn, insert_test_repo, insert_test_worktree};
+    use std::process::Command;
+
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {args:?} failed");
+    }
+
+    /// A git repo with one commit and a modified tracked file plus an untracked one.
+    fn dirty_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.email", "t@t"]);
+        git(dir.path(), &["config", "user.name", "t"]);
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-qm", "init"]);
+        std::fs::write(dir.path().join("a.txt"), "two\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn dirty_files_lists_tracked_and_untracked_changes() {
+        let dir = dirty_repo();
+        let mut files = dirty_files(dir.path().to_str().unwrap()).unwrap();
+        files.sort();
+        assert_eq!(files, vec!["a.txt", "new.txt"]);
+    }
+
+    #[test]
+    fn commit_wip_leaves_a_clean_worktree() {
+        let dir = dirty_repo();
+        let path = dir.path().to_str().unwrap();
+        let prepared = prepare(path, DirtyWorktreeChoice::CommitWip, vec![]).unwrap();
+        assert_eq!(prepared.saved_ref.unwrap().len(), 40);
+        assert!(dirty_files(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn stash_is_restored_after_the_run_ends() {
+        let dir = dirty_repo();
+        let path = dir.path().to_str().unwrap();
+        let conn = create_test_conn();
+        insert_test_repo(&conn, "r1", "test-repo", "/tmp/repo");
+        insert_test_worktree(&conn, "w1", "r1", "feat-test", path);
+        let run = AgentManager::new(&conn)
+            .create_run(Some("w1"), "do it", None)
+            .unwrap();
+
+        let files = dirty_files(path).unwrap();
+        let prepared = prepare(path, DirtyWorktreeChoice::Stash, files).unwrap();
+        assert!(dirty_files(path).unwrap().is_empty());
+        let guards = LaunchGuards::new(&conn);
+        guards.record(&run.id, "w1", &prepared).unwrap();
+
+        // Still running: nothing to restore yet.
+        assert!(guards.restore_finished().unwrap().is_empty());
+
+        AgentManager::new(&conn)
+            .update_run_completed_if_running(&run.id, "done")
+            .unwrap();
+        let restored = guards.restore_finished().unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(restored[0].restored_at.is_some());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "two\n"
+        );
+        assert!(dir.path().join("new.txt").exists());
+        // Only tried once.
+        assert!(guards.restore_finished().unwrap().is_empty());
+    }
+}
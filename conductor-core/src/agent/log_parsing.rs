@@ -302,6 +302,49 @@ pub fn parse_agent_log(path: &str) -> Vec<AgentEvent> {
     events
 }
 
+/// Parse the display events in the complete lines appended to a stream-json
+/// log since byte `offset`, for tailing a log that is still being written.
+///
+/// Each event is paired with the offset just past its line, so a reader that
+/// stops part-way can resume from the last event it handled. Returns the
+/// offset to resume from next time: the end of the last complete line, or
+/// `offset` unchanged when nothing new is complete. A file shorter than
+/// `offset` (truncated or replaced) is read again from the start.
+pub fn parse_events_since(path: &str, offset: u64) -> (u64, Vec<(u64, AgentEvent)>) {
+    use std::io::{Read as _, Seek, SeekFrom};
+
+    let Ok(mut file) = fs::File::open(Path::new(path)) else {
+        return (offset, Vec::new());
+    };
+    let Ok(len) = file.metadata().map(|m| m.len()) else {
+        return (offset, Vec::new());
+    };
+    let offset = if len < offset { 0 } else { offset };
+    if offset >= len || file.seek(SeekFrom::Start(offset)).is_err() {
+        return (offset, Vec::new());
+    }
+    let mut buf = Vec::new();
+    if file.read_to_end(&mut buf).is_err() {
+        return (offset, Vec::new());
+    }
+
+    let mut events = Vec::new();
+    let mut end = offset;
+    let mut rest = buf.as_slice();
+    // A trailing line without '\n' is still being written; leave it for later.
+    while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+        let (line, tail) = rest.split_at(pos + 1);
+        end += line.len() as u64;
+        events.extend(
+            parse_events_from_line(&String::from_utf8_lossy(line))
+                .into_iter()
+                .map(|e| (end, e)),
+        );
+        rest = tail;
+    }
+    (end, events)
+}
+
 /// Count the number of assistant turns in a stream-json agent log file.
 /// Each JSON line with `"type": "assistant"` counts as one turn.
 pub fn count_turns_in_log(path: &str) -> i64 {
@@ -898,6 +941,40 @@ mod tests {
         assert_eq!(offset2, finished.len() as u64);
     }
 
+    #[test]
+    fn test_parse_events_since_tails_complete_lines() {
+        let init = r#"{"type":"system","subtype":"init","model":"claude-3"}"#;
+        let partial = r#"{"type":"system","subtype":"init","#;
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let path = tmp.path().to_string_lossy().to_string();
+
+        let first = format!("{init}\n{partial}");
+        std::fs::write(tmp.path(), &first).unwrap();
+        let (offset, events) = parse_events_since(&path, 0);
+        assert_eq!(offset, init.len() as u64 + 1);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, offset);
+        assert_eq!(events[0].1.kind, "system");
+
+        // Nothing new until the partial line is finished.
+        let (same, none) = parse_events_since(&path, offset);
+        assert_eq!(same, offset);
+        assert!(none.is_empty());
+        let full = format!("{init}\n{init}\n");
+        std::fs::write(tmp.path(), &full).unwrap();
+        let (offset2, events2) = parse_events_since(&path, offset);
+        assert_eq!(offset2, full.len() as u64);
+        assert_eq!(events2.len(), 1);
+
+        // A shorter file is read again from the start.
+        let (offset3, events3) = parse_events_since(&path, offset2 + 100);
+        assert_eq!(offset3, full.len() as u64);
+        assert_eq!(events3.len(), 2);
+        let (missing, none) = parse_events_since("/nonexistent/path.log", 7);
+        assert_eq!(missing, 7);
+        assert!(none.is_empty());
+    }
+
     #[test]
     fn test_count_turns_incremental_only_partial_line() {
         // File contains only a partial line (no newline at all).
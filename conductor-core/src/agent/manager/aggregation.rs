@@ -1,28 +1,56 @@
 use std::collections::HashMap;
 
 use crate::error::Result;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
 
 use super::super::context::PR_REVIEW_SWARM_PROMPT_PREFIX;
-use super::super::types::{ActiveAgentCounts, CostPhase, RunTreeTotals, TicketAgentTotals};
+use super::super::types::{
+    ActiveAgentCounts, CostPhase, RunTreeTotals, TicketAgentTotals, TicketTotalsVersion,
+};
 use super::AgentManager;
 
+/// Which tickets [`AgentManager::totals_by_ticket_inner`] aggregates.
+enum TicketScope<'s> {
+    All,
+    Repo(&'s str),
+    Tickets {
+        repo_id: Option<&'s str>,
+        ids: &'s [String],
+    },
+}
+
 impl<'a> AgentManager<'a> {
-    /// Shared implementation for ticket-level aggregation with optional repo filter.
+    /// Shared implementation for ticket-level aggregation.
     fn totals_by_ticket_inner(
         &self,
-        repo_id: Option<&str>,
+        viewer: &Viewer,
+        scope: TicketScope<'_>,
     ) -> Result<HashMap<String, TicketAgentTotals>> {
-        let (where_clause, param_values): (&str, Vec<Box<dyn rusqlite::types::ToSql>>) =
-            match repo_id {
-                Some(id) => (
-                    "WHERE w.ticket_id IS NOT NULL AND a.status = 'completed' AND w.repo_id = ?",
-                    vec![Box::new(id.to_string())],
-                ),
-                None => (
-                    "WHERE w.ticket_id IS NOT NULL AND a.status = 'completed'",
-                    vec![],
-                ),
+        let (mut filter, mut param_values): (String, Vec<Box<dyn rusqlite::types::ToSql>>) =
+            match scope {
+                TicketScope::All => (String::new(), vec![]),
+                TicketScope::Repo(id) => {
+                    (" AND w.repo_id = ?".into(), vec![Box::new(id.to_string())])
+                }
+                TicketScope::Tickets { ids: [], .. } => return Ok(HashMap::new()),
+                TicketScope::Tickets { repo_id, ids } => {
+                    let mut filter =
+                        format!(" AND w.ticket_id IN ({})", vec!["?"; ids.len()].join(", "));
+                    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = ids
+                        .iter()
+                        .map(|id| Box::new(id.clone()) as Box<dyn rusqlite::types::ToSql>)
+                        .collect();
+                    if let Some(repo_id) = repo_id {
+                        filter.push_str(" AND w.repo_id = ?");
+                        values.push(Box::new(repo_id.to_string()));
+                    }
+                    (filter, values)
+                }
             };
+        if let Some(visible) = visible_repo_sql(viewer, "w.repo_id", "?") {
+            filter.push_str(&format!(" AND {visible}"));
+            param_values.push(Box::new(viewer.owner_param().map(str::to_string)));
+        }
 
         let sql = format!(
             "SELECT w.ticket_id, \
@@ -36,7 +64,7 @@ impl<'a> AgentManager<'a> {
                     COALESCE(SUM(a.cache_creation_input_tokens), 0) AS total_cache_creation_tokens \
              FROM agent_runs a \
              JOIN worktrees w ON a.worktree_id = w.id \
-             {where_clause} \
+             WHERE w.ticket_id IS NOT NULL AND a.status = 'completed'{filter} \
              GROUP BY w.ticket_id"
         );
 
@@ -68,7 +96,7 @@ impl<'a> AgentManager<'a> {
     /// Returns aggregated agent stats per ticket (across all linked worktrees).
     /// Only includes completed runs with recorded metrics.
     pub fn totals_by_ticket_all(&self) -> Result<HashMap<String, TicketAgentTotals>> {
-        self.totals_by_ticket_inner(None)
+        self.totals_by_ticket_inner(&Viewer::Local, TicketScope::All)
     }
 
     /// Returns aggregated agent stats per ticket for a specific repo.
@@ -77,7 +105,73 @@ impl<'a> AgentManager<'a> {
         &self,
         repo_id: &str,
     ) -> Result<HashMap<String, TicketAgentTotals>> {
-        self.totals_by_ticket_inner(Some(repo_id))
+        self.totals_by_ticket_inner(&Viewer::Local, TicketScope::Repo(repo_id))
+    }
+
+    /// Returns aggregated agent stats for the given tickets only, optionally
+    /// limited to one repo. Tickets without completed runs are absent from
+    /// the map.
+    pub fn totals_by_ticket_ids(
+        &self,
+        repo_id: Option<&str>,
+        ticket_ids: &[String],
+    ) -> Result<HashMap<String, TicketAgentTotals>> {
+        self.visible_totals_by_ticket_ids(&Viewer::Local, repo_id, ticket_ids)
+    }
+
+    /// Like [`Self::totals_by_ticket_all`] (or, with `repo_id`,
+    /// [`Self::totals_by_ticket_for_repo`]), leaving out worktrees of repos
+    /// that are private to someone other than `viewer`.
+    pub fn visible_totals_by_ticket(
+        &self,
+        viewer: &Viewer,
+        repo_id: Option<&str>,
+    ) -> Result<HashMap<String, TicketAgentTotals>> {
+        let scope = match repo_id {
+            Some(id) => TicketScope::Repo(id),
+            None => TicketScope::All,
+        };
+        self.totals_by_ticket_inner(viewer, scope)
+    }
+
+    /// Like [`Self::totals_by_ticket_ids`], leaving out worktrees of repos
+    /// that are private to someone other than `viewer`.
+    pub fn visible_totals_by_ticket_ids(
+        &self,
+        viewer: &Viewer,
+        repo_id: Option<&str>,
+        ticket_ids: &[String],
+    ) -> Result<HashMap<String, TicketAgentTotals>> {
+        self.totals_by_ticket_inner(
+            viewer,
+            TicketScope::Tickets {
+                repo_id,
+                ids: ticket_ids,
+            },
+        )
+    }
+
+    /// Current [`TicketTotalsVersion`]; per-ticket totals computed under an
+    /// equal version are still accurate.
+    pub fn ticket_totals_version(&self) -> Result<TicketTotalsVersion> {
+        Ok(self.conn.query_row(
+            "SELECT (SELECT MAX(ended_at) FROM agent_runs WHERE status = 'completed'), \
+                    (SELECT COUNT(*) FROM agent_runs WHERE status = 'completed'), \
+                    (SELECT group_concat(id || '=' || ticket_id, ',') FROM \
+                        (SELECT id, ticket_id FROM worktrees \
+                         WHERE ticket_id IS NOT NULL ORDER BY id)), \
+                    (SELECT group_concat(repo_id || '=' || owner, ',') FROM \
+                        (SELECT repo_id, owner FROM repo_visibility ORDER BY repo_id))",
+            [],
+            |row| {
+                Ok(TicketTotalsVersion {
+                    last_ended_at: row.get(0)?,
+                    completed_runs: row.get(1)?,
+                    ticket_links: row.get(2)?,
+                    private_repos: row.get(3)?,
+                })
+            },
+        )?)
     }
 
     /// Build a per-phase cost breakdown for all runs in a worktree.
@@ -251,6 +345,72 @@ mod tests {
     use super::super::setup_db;
     use super::super::AgentManager;
 
+    #[test]
+    fn test_totals_by_ticket_scopes_and_version() {
+        let conn = setup_db();
+        for (ticket, wt) in [("t1", "w1"), ("t2", "w2")] {
+            conn.execute(
+                "INSERT INTO tickets (id, repo_id, source_type, source_id, title, synced_at) \
+                 VALUES (?1, 'r1', 'github', ?1, 'Ticket', '2024-01-01T00:00:00Z')",
+                [ticket],
+            )
+            .unwrap();
+            conn.execute(
+                "UPDATE worktrees SET ticket_id = ?1 WHERE id = ?2",
+                [ticket, wt],
+            )
+            .unwrap();
+        }
+        let mgr = AgentManager::new(&conn);
+        let before = mgr.ticket_totals_version().unwrap();
+        assert_eq!(before.completed_runs, 0);
+
+        for wt in ["w1", "w2"] {
+            let run = mgr.create_run(Some(wt), "Task", None).unwrap();
+            mgr.update_run_completed(
+                &run.id,
+                None,
+                None,
+                Some(0.1),
+                Some(2),
+                Some(1000),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        let after = mgr.ticket_totals_version().unwrap();
+        assert_ne!(before, after);
+        assert_eq!(after.completed_runs, 2);
+        assert!(after.last_ended_at.is_some());
+
+        // Unlinking (or relinking) a worktree moves its runs between tickets.
+        conn.execute("UPDATE worktrees SET ticket_id = NULL WHERE id = 'w2'", [])
+            .unwrap();
+        let unlinked = mgr.ticket_totals_version().unwrap();
+        assert_ne!(after, unlinked);
+        conn.execute("UPDATE worktrees SET ticket_id = 't2' WHERE id = 'w2'", [])
+            .unwrap();
+        assert_eq!(mgr.ticket_totals_version().unwrap(), after);
+        crate::repo_visibility::RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        assert_ne!(mgr.ticket_totals_version().unwrap(), after);
+
+        assert_eq!(mgr.totals_by_ticket_all().unwrap().len(), 2);
+        assert_eq!(mgr.totals_by_ticket_for_repo("r1").unwrap().len(), 2);
+        let scoped = mgr.totals_by_ticket_ids(None, &["t2".to_string()]).unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped["t2"].total_runs, 1);
+        assert!(mgr
+            .totals_by_ticket_ids(Some("other"), &["t2".to_string()])
+            .unwrap()
+            .is_empty());
+        assert!(mgr.totals_by_ticket_ids(None, &[]).unwrap().is_empty());
+    }
+
     #[test]
     fn test_totals_by_worktree_empty() {
         let conn = setup_db();
@@ -1,8 +1,9 @@
 use chrono::Utc;
 use rusqlite::named_params;
 
-use crate::error::Result;
+use crate::error::{ConductorError, Result};
 
+use super::super::environment::RunEnvironment;
 use super::super::status::AgentRunStatus;
 use super::super::types::{AgentRun, LogResult};
 use super::AgentManager;
@@ -127,6 +128,8 @@ impl<'a> AgentManager<'a> {
             conversation_id: conversation_id.map(String::from),
             subprocess_pid: None,
             runtime: "claude".to_string(),
+            rotated_from_session_id: None,
+            environment: None,
         };
 
         self.conn.execute(
@@ -328,6 +331,10 @@ impl<'a> AgentManager<'a> {
             // realistic PID values.
             crate::process_utils::cancel_subprocess(pid as u32);
         }
+        // Killing the `docker` client can leave a sandboxed agent running.
+        if let Ok(Some(run)) = self.get_run(run_id) {
+            crate::agent::sandbox::remove_run_container(&run);
+        }
 
         Ok(())
     }
@@ -422,6 +429,37 @@ impl<'a> AgentManager<'a> {
         Ok(())
     }
 
+    /// Record that `run_id` started a fresh session instead of resuming
+    /// `from_session_id` because that session reached the rotation limit.
+    pub fn mark_session_rotated(&self, run_id: &str, from_session_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE agent_runs SET rotated_from_session_id = :from_session_id WHERE id = :id",
+            named_params! { ":from_session_id": from_session_id, ":id": run_id },
+        )?;
+        Ok(())
+    }
+
+    /// Store the environment captured when `run_id` started.
+    pub fn record_environment(&self, run_id: &str, environment: &RunEnvironment) -> Result<()> {
+        let json = serde_json::to_string(environment).map_err(|e| {
+            ConductorError::Agent(format!("Failed to serialize run environment: {e}"))
+        })?;
+        self.conn.execute(
+            "UPDATE agent_runs SET environment = :environment WHERE id = :id",
+            named_params! { ":environment": json, ":id": run_id },
+        )?;
+        Ok(())
+    }
+
+    /// Overwrite the stored prompt (used to persist the redacted form).
+    pub fn update_run_prompt(&self, run_id: &str, prompt: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE agent_runs SET prompt = :prompt WHERE id = :id",
+            named_params! { ":prompt": prompt, ":id": run_id },
+        )?;
+        Ok(())
+    }
+
     pub fn update_run_log_file(&self, run_id: &str, path: &str) -> Result<()> {
         self.conn.execute(
             "UPDATE agent_runs SET log_file = :path WHERE id = :id",
@@ -522,6 +560,46 @@ mod tests {
         assert_eq!(runs[0].id, run.id);
     }
 
+    #[test]
+    fn test_mark_session_rotated() {
+        let conn = setup_db();
+        let mgr = AgentManager::new(&conn);
+
+        let run = mgr.create_run(Some("w1"), "Keep going", None).unwrap();
+        assert!(run.rotated_from_session_id.is_none());
+
+        mgr.mark_session_rotated(&run.id, "sess-old").unwrap();
+        let fetched = mgr.get_run(&run.id).unwrap().unwrap();
+        assert_eq!(fetched.rotated_from_session_id.as_deref(), Some("sess-old"));
+    }
+
+    #[test]
+    fn test_record_environment() {
+        let conn = setup_db();
+        let mgr = AgentManager::new(&conn);
+
+        let run = mgr.create_run(Some("w1"), "Fix the bug", None).unwrap();
+        assert!(run.environment.is_none());
+
+        let env = crate::agent::RunEnvironment {
+            conductor_version: "0.3.0".into(),
+            backend: "claude".into(),
+            agent_version: Some("1.0.51 (Claude Code)".into()),
+            model: Some("sonnet".into()),
+            git_commit: Some("abc123".into()),
+            git_branch: Some("feat/test".into()),
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            sandbox: Some("docker agent:1".into()),
+        };
+        mgr.record_environment(&run.id, &env).unwrap();
+        let fetched = mgr.get_run(&run.id).unwrap().unwrap();
+        assert_eq!(fetched.environment, Some(env.clone()));
+        // Runs loaded through the aliased column lists carry it too.
+        let listed = mgr.list_for_worktree("w1").unwrap();
+        assert_eq!(listed[0].environment, Some(env));
+    }
+
     #[test]
     fn test_update_completed() {
         let conn = setup_db();
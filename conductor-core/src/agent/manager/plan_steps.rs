@@ -525,6 +525,8 @@ mod tests {
             conversation_id: None,
             subprocess_pid: None,
             runtime: "claude".to_string(),
+            rotated_from_session_id: None,
+            environment: None,
         };
 
         let prompt = run.build_resume_prompt();
@@ -5,6 +5,7 @@ use rusqlite::named_params;
 
 use crate::db::query_collect;
 use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
 
 use super::super::db::{
     row_to_agent_run, AGENT_RUN_COLS_A, AGENT_RUN_COLS_AR, AGENT_RUN_COLS_A_NULL_PLAN,
@@ -127,6 +128,33 @@ impl<'a> AgentManager<'a> {
         }
     }
 
+    /// Total turns taken across every run that used `session_id`.
+    ///
+    /// This is the size signal session rotation is based on: each resume of a
+    /// Claude session replays its full history, so the cost of the next turn
+    /// grows with the sum, not with any single run.
+    pub fn session_turns(&self, session_id: &str) -> Result<i64> {
+        let turns: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(num_turns), 0) AS turns FROM agent_runs \
+             WHERE session_id = :session_id",
+            named_params! { ":session_id": session_id },
+            |row| row.get("turns"),
+        )?;
+        Ok(turns)
+    }
+
+    /// List all agent runs that used `session_id`, oldest first.
+    pub fn list_for_session(&self, session_id: &str) -> Result<Vec<AgentRun>> {
+        let mut runs = query_collect(
+            self.conn,
+            &format!("{AGENT_RUN_SELECT} WHERE session_id = :session_id ORDER BY started_at ASC"),
+            named_params! { ":session_id": session_id },
+            row_to_agent_run,
+        )?;
+        self.populate_plans(&mut runs)?;
+        Ok(runs)
+    }
+
     /// List all agent runs for a repo (across all its worktrees), newest first.
     pub fn list_for_repo(&self, repo_id: &str) -> Result<Vec<AgentRun>> {
         // Cannot use AGENT_RUN_SELECT here: the JOIN requires the `a.` alias.
@@ -209,6 +237,24 @@ impl<'a> AgentManager<'a> {
 
     /// Returns the latest agent run for each worktree, keyed by worktree_id.
     pub fn latest_runs_by_worktree(&self) -> Result<HashMap<String, AgentRun>> {
+        self.latest_visible_runs_by_worktree(&Viewer::Local)
+    }
+
+    /// Like [`Self::latest_runs_by_worktree`], leaving out worktrees of repos
+    /// that are private to someone other than `viewer`.
+    pub fn latest_visible_runs_by_worktree(
+        &self,
+        viewer: &Viewer,
+    ) -> Result<HashMap<String, AgentRun>> {
+        let visible = visible_repo_sql(viewer, "w.repo_id", ":viewer");
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = Vec::new();
+        if visible.is_some() {
+            params.push((":viewer", &owner));
+        }
+        let visible = visible
+            .map(|v| format!(" JOIN worktrees w ON w.id = a.worktree_id AND {v}"))
+            .unwrap_or_default();
         let runs = query_collect(
             self.conn,
             &format!(
@@ -217,9 +263,10 @@ impl<'a> AgentManager<'a> {
                  INNER JOIN ( \
                      SELECT worktree_id, MAX(started_at) AS max_started \
                      FROM agent_runs GROUP BY worktree_id \
-                 ) latest ON a.worktree_id = latest.worktree_id AND a.started_at = latest.max_started"
+                 ) latest ON a.worktree_id = latest.worktree_id AND a.started_at = latest.max_started\
+                 {visible}"
             ),
-            [],
+            params.as_slice(),
             row_to_agent_run,
         )?;
         self.runs_to_worktree_map(runs)
@@ -371,12 +418,28 @@ impl<'a> AgentManager<'a> {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<AgentRun>> {
-        // repo_id filter requires a JOIN; worktree_id/status use the plain SELECT.
-        let use_join = repo_id.is_some();
+        self.list_visible_agent_runs(&Viewer::Local, worktree_id, repo_id, status, limit, offset)
+    }
+
+    /// Like [`Self::list_agent_runs`], leaving out runs in repos that are
+    /// private to someone other than `viewer`.
+    pub fn list_visible_agent_runs(
+        &self,
+        viewer: &Viewer,
+        worktree_id: Option<&str>,
+        repo_id: Option<&str>,
+        status: Option<&AgentRunStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AgentRun>> {
+        // Repo filters (by repo_id or visibility) need the worktree JOIN;
+        // worktree_id/status alone use the plain SELECT.
+        let visible = visible_repo_sql(viewer, "COALESCE(ar.repo_id, w.repo_id)", "?");
+        let use_join = repo_id.is_some() || visible.is_some();
         let sql_base = if use_join {
             format!(
                 "SELECT {AGENT_RUN_COLS_AR} FROM agent_runs ar \
-                 JOIN worktrees w ON w.id = ar.worktree_id"
+                 LEFT JOIN worktrees w ON w.id = ar.worktree_id"
             )
         } else {
             AGENT_RUN_SELECT.to_string()
@@ -388,7 +451,12 @@ impl<'a> AgentManager<'a> {
 
         if let Some(wt_id) = worktree_id {
             param_values.push(rusqlite::types::Value::Text(wt_id.to_owned()));
-            where_parts.push("worktree_id = ?".to_string());
+            let col = if use_join {
+                "ar.worktree_id"
+            } else {
+                "worktree_id"
+            };
+            where_parts.push(format!("{col} = ?"));
         } else if let Some(r_id) = repo_id {
             param_values.push(rusqlite::types::Value::Text(r_id.to_owned()));
             where_parts.push("w.repo_id = ?".to_string());
@@ -400,6 +468,14 @@ impl<'a> AgentManager<'a> {
             where_parts.push(format!("{col} = ?"));
         }
 
+        if let Some(visible) = visible {
+            param_values.push(match viewer.owner_param() {
+                Some(owner) => rusqlite::types::Value::Text(owner.to_owned()),
+                None => rusqlite::types::Value::Null,
+            });
+            where_parts.push(visible);
+        }
+
         let where_clause = if where_parts.is_empty() {
             String::new()
         } else {
@@ -499,6 +575,40 @@ mod tests {
         assert!(err.to_string().contains("agent run not found"));
     }
 
+    #[test]
+    fn test_session_turns_sums_runs_sharing_a_session() {
+        let conn = setup_db();
+        let mgr = AgentManager::new(&conn);
+        assert_eq!(mgr.session_turns("sess-1").unwrap(), 0);
+
+        for turns in [12, 30] {
+            let run = mgr.create_run(Some("w1"), "task", None).unwrap();
+            mgr.update_run_completed(
+                &run.id,
+                Some("sess-1"),
+                Some("done"),
+                None,
+                Some(turns),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        let other = mgr.create_run(Some("w1"), "other", None).unwrap();
+        mgr.update_run_session_id(&other.id, "sess-2").unwrap();
+
+        assert_eq!(mgr.session_turns("sess-1").unwrap(), 42);
+        assert_eq!(mgr.session_turns("sess-2").unwrap(), 0);
+        let runs = mgr.list_for_session("sess-1").unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs
+            .iter()
+            .all(|r| r.session_id.as_deref() == Some("sess-1")));
+    }
+
     #[test]
     fn test_get_run() {
         let conn = setup_db();
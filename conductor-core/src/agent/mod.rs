@@ -1,22 +1,67 @@
+pub mod alerts;
+pub mod backend;
+pub mod budget;
+pub mod chain;
+pub mod conflict_resolution;
 pub(crate) mod context;
 pub(crate) mod db;
+pub mod diff_review;
+pub mod environment;
+pub mod inbox;
+pub mod launch_guard;
 pub(crate) mod log_parsing;
 pub(crate) mod manager;
+pub mod queue;
+pub mod report;
+pub mod sandbox;
+pub mod share;
 pub(crate) mod status;
+pub mod stop_all;
+pub mod supervisor;
 pub(crate) mod types;
 
 // Re-export everything that was public in the old agent.rs
 
-pub use context::{build_startup_context, PR_REVIEW_SWARM_PROMPT_PREFIX};
+pub use context::{build_session_recap, build_startup_context, PR_REVIEW_SWARM_PROMPT_PREFIX};
 
 pub use log_parsing::{
     count_turns_in_log, count_turns_incremental, parse_agent_log, parse_events_from_line,
-    parse_events_from_value, parse_result_event,
+    parse_events_from_value, parse_events_since, parse_result_event,
 };
 
 pub use manager::feedback::normalize_feedback_response;
 pub use manager::AgentManager;
 
+pub use alerts::{AgentAlertKind, AgentAlerts, AgentRunAlert};
+
+pub use backend::{AgentBackend, BackendRequest};
+
+pub use budget::{BudgetExceeded, BudgetScope};
+
+pub use report::{parse_since, CostGroupBy, CostReportRow};
+
+pub use share::{RunShare, RunShares, SharedRun, SharedRunEvent};
+
+pub use chain::{
+    AgentChain, AgentChains, ChainLink, ChainOutcome, ChainStep, ChainTrigger, MAX_CHAIN_DEPTH,
+};
+
+pub use conflict_resolution::{
+    ConflictResolution, ConflictResolutionRequest, ConflictResolutionStatus, ConflictResolutions,
+};
+
+pub use diff_review::{split_diff, DiffReview, DiffReviewRequest, DiffReviews, FileDiff};
+
+pub use environment::RunEnvironment;
+
+pub use inbox::{AgentInbox, InboxItem};
+
+pub use launch_guard::{DirtyWorktreeChoice, LaunchGuard, LaunchGuards, PreparedLaunch};
+
+pub use queue::{AgentPriority, AgentQueue, QueuedAgentRun};
+
+pub use stop_all::{StopAllSummary, StoppedRun};
+
 pub use status::{
     parse_feedback_marker, parse_feedback_marker_structured, AgentRunStatus, FeedbackStatus,
     FeedbackType, ParsedFeedbackMarker, StepStatus, DEFAULT_AGENT_ERROR_MSG, FEEDBACK_MARKER,
@@ -26,7 +71,8 @@ pub use status::{
 pub use types::{
     ActiveAgentCounts, AgentCreatedIssue, AgentEvent, AgentRun, AgentRunEvent, ClaudeJsonResult,
     CostPhase, FeedbackOption, FeedbackRequest, FeedbackRequestParams, LogResult, PlanStep,
-    RunTreeTotals, TicketAgentTotals, EVENT_KIND_TOOL_ERROR, META_KEY_ERROR_TEXT,
+    RunTreeTotals, TicketAgentTotals, TicketTotalsVersion, EVENT_KIND_TOOL_ERROR,
+    META_KEY_ERROR_TEXT,
 };
 
 #[cfg(test)]
@@ -0,0 +1,312 @@
+//! Agent runs waiting for a free slot.
+//!
+//! Queued runs are dispatched highest priority first, oldest first within a
+//! priority. Priority only reorders the queue: a newly queued `urgent` run
+//! jumps ahead of waiting `normal` runs but never interrupts a run that is
+//! already active.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentPriority {
+    Urgent,
+    #[default]
+    Normal,
+    Background,
+}
+
+impl std::fmt::Display for AgentPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Urgent => "urgent",
+            Self::Normal => "normal",
+            Self::Background => "background",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for AgentPriority {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "urgent" => Ok(Self::Urgent),
+            "normal" => Ok(Self::Normal),
+            "background" => Ok(Self::Background),
+            _ => Err(format!(
+                "unknown agent priority: {s} (expected urgent, normal or background)"
+            )),
+        }
+    }
+}
+
+crate::impl_sql_enum!(AgentPriority);
+
+/// An agent run waiting in the queue, joined with its worktree for display.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedAgentRun {
+    pub id: String,
+    pub worktree_id: String,
+    pub repo_slug: String,
+    pub worktree_slug: String,
+    pub prompt: String,
+    pub priority: AgentPriority,
+    pub created_at: String,
+}
+
+const QUEUE_QUERY: &str = "SELECT q.id, q.worktree_id, q.prompt, q.priority, q.created_at, \
+            w.slug AS worktree_slug, r.slug AS repo_slug \
+     FROM agent_queue q \
+     JOIN worktrees w ON w.id = q.worktree_id \
+     JOIN repos r ON r.id = w.repo_id";
+
+const QUEUE_ORDER: &str = "ORDER BY CASE q.priority \
+         WHEN 'urgent' THEN 0 WHEN 'normal' THEN 1 ELSE 2 END, \
+     q.created_at, q.id";
+
+pub struct AgentQueue<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AgentQueue<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Add a run for `worktree_id` to the queue.
+    pub fn enqueue(
+        &self,
+        worktree_id: &str,
+        prompt: &str,
+        priority: AgentPriority,
+    ) -> Result<QueuedAgentRun> {
+        if prompt.trim().is_empty() {
+            return Err(ConductorError::InvalidInput(
+                "agent prompt must not be empty".into(),
+            ));
+        }
+        let id = crate::new_id();
+        self.conn.execute(
+            "INSERT INTO agent_queue (id, worktree_id, prompt, priority, created_at) \
+             VALUES (:id, :worktree_id, :prompt, :priority, :created_at)",
+            named_params! {
+                ":id": id,
+                ":worktree_id": worktree_id,
+                ":prompt": prompt,
+                ":priority": priority,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.get(&id)
+    }
+
+    /// All queued runs in dispatch order.
+    pub fn list(&self) -> Result<Vec<QueuedAgentRun>> {
+        self.list_visible(&Viewer::Local)
+    }
+
+    /// Like [`Self::list`], leaving out runs queued in repos that are private
+    /// to someone other than `viewer`.
+    pub fn list_visible(&self, viewer: &Viewer) -> Result<Vec<QueuedAgentRun>> {
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = Vec::new();
+        let condition = visible_repo_sql(viewer, "w.repo_id", ":viewer");
+        if condition.is_some() {
+            params.push((":viewer", &owner));
+        }
+        let visible = condition.map(|c| format!(" WHERE {c}")).unwrap_or_default();
+        query_collect(
+            self.conn,
+            &format!("{QUEUE_QUERY}{visible} {QUEUE_ORDER}"),
+            params.as_slice(),
+            row_to_queued,
+        )
+    }
+
+    pub fn get(&self, id: &str) -> Result<QueuedAgentRun> {
+        self.conn
+            .query_row(
+                &format!("{QUEUE_QUERY} WHERE q.id = :id"),
+                named_params! { ":id": id },
+                row_to_queued,
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::QueuedAgentRunNotFound { id: id.to_string() })
+    }
+
+    /// Change the priority of a run that is still queued. Runs that have
+    /// already been dispatched are no longer in the queue and cannot be changed.
+    pub fn set_priority(&self, id: &str, priority: AgentPriority) -> Result<QueuedAgentRun> {
+        let updated = self.conn.execute(
+            "UPDATE agent_queue SET priority = :priority WHERE id = :id",
+            named_params! { ":id": id, ":priority": priority },
+        )?;
+        if updated == 0 {
+            return Err(ConductorError::QueuedAgentRunNotFound { id: id.to_string() });
+        }
+        self.get(id)
+    }
+
+    /// Drop a run from the queue without starting it.
+    pub fn remove(&self, id: &str) -> Result<()> {
+        let removed = self.conn.execute(
+            "DELETE FROM agent_queue WHERE id = :id",
+            named_params! { ":id": id },
+        )?;
+        if removed == 0 {
+            return Err(ConductorError::QueuedAgentRunNotFound { id: id.to_string() });
+        }
+        Ok(())
+    }
+
+    /// Remove and return the next run to dispatch, if a slot is free.
+    ///
+    /// Returns `None` when `max_concurrent` active runs already exist. Runs
+    /// whose worktree already has an active agent are skipped (a worktree runs
+    /// one agent at a time) and stay queued in place.
+    pub fn take_next(&self, max_concurrent: Option<u32>) -> Result<Option<QueuedAgentRun>> {
+        let tx = self.conn.unchecked_transaction()?;
+        if let Some(limit) = max_concurrent {
+            let active: u32 = tx.query_row(
+                "SELECT COUNT(*) FROM agent_runs \
+                 WHERE status IN ('running', 'waiting_for_feedback')",
+                [],
+                |row| row.get(0),
+            )?;
+            if active >= limit {
+                return Ok(None);
+            }
+        }
+        let next = tx
+            .query_row(
+                &format!(
+                    "{QUEUE_QUERY} WHERE NOT EXISTS ( \
+                         SELECT 1 FROM agent_runs a \
+                         WHERE a.worktree_id = q.worktree_id \
+                           AND a.status IN ('running', 'waiting_for_feedback')) \
+                     {QUEUE_ORDER} LIMIT 1"
+                ),
+                [],
+                row_to_queued,
+            )
+            .optional()?;
+        if let Some(ref item) = next {
+            tx.execute(
+                "DELETE FROM agent_queue WHERE id = :id",
+                named_params! { ":id": item.id },
+            )?;
+        }
+        tx.commit()?;
+        Ok(next)
+    }
+}
+
+fn row_to_queued(row: &rusqlite::Row<'_>) -> rusqlite::Result<QueuedAgentRun> {
+    Ok(QueuedAgentRun {
+        id: row.get("id")?,
+        worktree_id: row.get("worktree_id")?,
+        repo_slug: row.get("repo_slug")?,
+        worktree_slug: row.get("worktree_slug")?,
+        prompt: row.get("prompt")?,
+        priority: row.get("priority")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentManager;
+
+    fn setup() -> Connection {
+        super::super::manager::setup_db()
+    }
+
+    fn prompts(items: &[QueuedAgentRun]) -> Vec<&str> {
+        items.iter().map(|q| q.prompt.as_str()).collect()
+    }
+
+    #[test]
+    fn list_orders_by_priority_then_age() {
+        let conn = setup();
+        let queue = AgentQueue::new(&conn);
+        queue
+            .enqueue("w1", "bg", AgentPriority::Background)
+            .unwrap();
+        queue.enqueue("w1", "n1", AgentPriority::Normal).unwrap();
+        queue
+            .enqueue("w2", "urgent", AgentPriority::Urgent)
+            .unwrap();
+        queue.enqueue("w2", "n2", AgentPriority::Normal).unwrap();
+
+        let items = queue.list().unwrap();
+        assert_eq!(prompts(&items), ["urgent", "n1", "n2", "bg"]);
+        assert_eq!(items[0].worktree_slug, "fix-bug");
+    }
+
+    #[test]
+    fn set_priority_reorders_queued_runs_only() {
+        let conn = setup();
+        let queue = AgentQueue::new(&conn);
+        let first = queue.enqueue("w1", "first", AgentPriority::Normal).unwrap();
+        let second = queue
+            .enqueue("w2", "second", AgentPriority::Normal)
+            .unwrap();
+
+        let bumped = queue
+            .set_priority(&second.id, AgentPriority::Urgent)
+            .unwrap();
+        assert_eq!(bumped.priority, AgentPriority::Urgent);
+        assert_eq!(prompts(&queue.list().unwrap()), ["second", "first"]);
+
+        let taken = queue.take_next(None).unwrap().unwrap();
+        assert_eq!(taken.id, second.id);
+        let err = queue
+            .set_priority(&second.id, AgentPriority::Background)
+            .unwrap_err();
+        assert!(matches!(err, ConductorError::QueuedAgentRunNotFound { .. }));
+        queue.remove(&first.id).unwrap();
+        assert!(queue.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn take_next_respects_limit_and_busy_worktrees() {
+        let conn = setup();
+        let queue = AgentQueue::new(&conn);
+        queue
+            .enqueue("w1", "on-busy", AgentPriority::Urgent)
+            .unwrap();
+        queue
+            .enqueue("w2", "on-idle", AgentPriority::Background)
+            .unwrap();
+        AgentManager::new(&conn)
+            .create_run(Some("w1"), "already running", None)
+            .unwrap();
+
+        assert!(queue.take_next(Some(1)).unwrap().is_none(), "no free slot");
+        let taken = queue.take_next(Some(2)).unwrap().unwrap();
+        assert_eq!(taken.prompt, "on-idle", "busy worktree is skipped");
+        assert_eq!(prompts(&queue.list().unwrap()), ["on-busy"]);
+    }
+
+    #[test]
+    fn priority_round_trips_through_strings() {
+        for p in [
+            AgentPriority::Urgent,
+            AgentPriority::Normal,
+            AgentPriority::Background,
+        ] {
+            assert_eq!(p.to_string().parse::<AgentPriority>().unwrap(), p);
+        }
+        assert!("high".parse::<AgentPriority>().is_err());
+    }
+}
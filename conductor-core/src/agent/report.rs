@@ -0,0 +1,272 @@
+//! Agent cost and usage rollups for `conductor report costs`, the
+//! `/api/reports/costs` endpoint and the TUI Reports view.
+//!
+//! Every run started inside the window counts, whatever its status: a failed
+//! or cancelled run still spent what it recorded.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+use super::manager::AgentManager;
+
+/// How [`AgentManager::cost_report`] groups runs.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroupBy {
+    #[default]
+    Repo,
+    Ticket,
+    Worktree,
+    Day,
+}
+
+impl CostGroupBy {
+    pub const ALL: [Self; 4] = [Self::Repo, Self::Ticket, Self::Worktree, Self::Day];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Repo => "repo",
+            Self::Ticket => "ticket",
+            Self::Worktree => "worktree",
+            Self::Day => "day",
+        }
+    }
+}
+
+impl fmt::Display for CostGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CostGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.as_str() == s)
+            .ok_or_else(|| {
+                format!("unknown grouping '{s}' (expected repo, ticket, worktree or day)")
+            })
+    }
+}
+
+/// Cost, turns and time of the runs in one group.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostReportRow {
+    /// Repo, ticket or worktree ID, or the `YYYY-MM-DD` day. Empty for runs
+    /// outside any repo, ticket or worktree.
+    pub key: String,
+    /// Human-readable name for `key`.
+    pub label: String,
+    pub runs: i64,
+    pub cost_usd: f64,
+    pub turns: i64,
+    pub duration_ms: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
+/// Parse a `--since` value: a relative window such as `30d`, `12h` or `2w`,
+/// or a `YYYY-MM-DD` date (midnight UTC).
+pub fn parse_since(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    let invalid = || {
+        ConductorError::InvalidInput(format!(
+            "invalid --since '{s}': use a window like 30d, 12h or 2w, or a YYYY-MM-DD date"
+        ))
+    };
+    let (num, unit) = s.split_at(s.len().saturating_sub(1));
+    let n: i64 = num.parse().map_err(|_| invalid())?;
+    let window = match unit {
+        "h" => Duration::try_hours(n),
+        "d" => Duration::try_days(n),
+        "w" => Duration::try_weeks(n),
+        _ => None,
+    }
+    .filter(|_| n > 0)
+    .ok_or_else(invalid)?;
+    Ok(Utc::now() - window)
+}
+
+impl AgentManager<'_> {
+    /// Roll up agent runs started at or after `since` (all runs when `None`).
+    /// Days come out oldest first; other groupings most expensive first.
+    pub fn cost_report(
+        &self,
+        since: Option<DateTime<Utc>>,
+        group_by: CostGroupBy,
+    ) -> Result<Vec<CostReportRow>> {
+        self.visible_cost_report(&Viewer::Local, since, group_by)
+    }
+
+    /// Like [`Self::cost_report`], leaving out runs in repos that are private
+    /// to someone other than `viewer`.
+    pub fn visible_cost_report(
+        &self,
+        viewer: &Viewer,
+        since: Option<DateTime<Utc>>,
+        group_by: CostGroupBy,
+    ) -> Result<Vec<CostReportRow>> {
+        let (key, label, order) = match group_by {
+            CostGroupBy::Repo => (
+                "COALESCE(w.repo_id, a.repo_id, '')",
+                "COALESCE(r.slug, '(no repo)')",
+                "cost_usd DESC, label",
+            ),
+            CostGroupBy::Ticket => (
+                "COALESCE(w.ticket_id, '')",
+                "COALESCE('#' || t.source_id || ' ' || t.title, '(no ticket)')",
+                "cost_usd DESC, label",
+            ),
+            CostGroupBy::Worktree => (
+                "COALESCE(a.worktree_id, '')",
+                "COALESCE(r.slug || '/' || w.slug, '(no worktree)')",
+                "cost_usd DESC, label",
+            ),
+            CostGroupBy::Day => ("date(a.started_at)", "date(a.started_at)", "key"),
+        };
+        let since = since.map(|t| t.to_rfc3339()).unwrap_or_default();
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":since", &since)];
+        let visible =
+            match visible_repo_sql(viewer, "COALESCE(w.repo_id, a.repo_id, '')", ":viewer") {
+                Some(condition) => {
+                    params.push((":viewer", &owner));
+                    format!(" AND {condition}")
+                }
+                None => String::new(),
+            };
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT {key} AS key, {label} AS label, \
+                        COUNT(*) AS runs, \
+                        COALESCE(SUM(a.cost_usd), 0.0) AS cost_usd, \
+                        COALESCE(SUM(a.num_turns), 0) AS turns, \
+                        COALESCE(SUM(a.duration_ms), 0) AS duration_ms, \
+                        COALESCE(SUM(a.input_tokens), 0) AS input_tokens, \
+                        COALESCE(SUM(a.output_tokens), 0) AS output_tokens \
+                 FROM agent_runs a \
+                 LEFT JOIN worktrees w ON w.id = a.worktree_id \
+                 LEFT JOIN repos r ON r.id = COALESCE(w.repo_id, a.repo_id) \
+                 LEFT JOIN tickets t ON t.id = w.ticket_id \
+                 WHERE a.started_at >= :since{visible} \
+                 GROUP BY 1 \
+                 ORDER BY {order}"
+            ),
+            params.as_slice(),
+            |row| {
+                Ok(CostReportRow {
+                    key: row.get("key")?,
+                    label: row.get("label")?,
+                    runs: row.get("runs")?,
+                    cost_usd: row.get("cost_usd")?,
+                    turns: row.get("turns")?,
+                    duration_ms: row.get("duration_ms")?,
+                    input_tokens: row.get("input_tokens")?,
+                    output_tokens: row.get("output_tokens")?,
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::named_params;
+
+    use super::*;
+
+    fn add_run(conn: &rusqlite::Connection, worktree: &str, started: &str, cost: f64, turns: i64) {
+        let run = AgentManager::new(conn)
+            .create_run(Some(worktree), "task", None)
+            .unwrap();
+        conn.execute(
+            "UPDATE agent_runs SET started_at = :started, cost_usd = :cost, num_turns = :turns, \
+             duration_ms = 1000 WHERE id = :id",
+            named_params! { ":started": started, ":cost": cost, ":turns": turns, ":id": run.id },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn cost_report_groups_and_filters_by_start() {
+        let conn = super::super::manager::setup_db();
+        add_run(&conn, "w1", "2026-01-01T10:00:00Z", 1.0, 4);
+        add_run(&conn, "w1", "2026-01-02T10:00:00Z", 2.5, 6);
+        add_run(&conn, "w2", "2026-01-02T11:00:00Z", 0.5, 1);
+        let mgr = AgentManager::new(&conn);
+
+        let by_worktree = mgr.cost_report(None, CostGroupBy::Worktree).unwrap();
+        assert_eq!(by_worktree.len(), 2);
+        assert_eq!(by_worktree[0].label, "test-repo/feat-test");
+        assert_eq!((by_worktree[0].runs, by_worktree[0].turns), (2, 10));
+        assert_eq!(by_worktree[0].cost_usd, 3.5);
+
+        let by_repo = mgr.cost_report(None, CostGroupBy::Repo).unwrap();
+        assert_eq!(by_repo.len(), 1);
+        assert_eq!((by_repo[0].key.as_str(), by_repo[0].runs), ("r1", 3));
+
+        let by_ticket = mgr.cost_report(None, CostGroupBy::Ticket).unwrap();
+        assert_eq!(by_ticket[0].label, "(no ticket)");
+
+        let since = parse_since("2026-01-02").unwrap();
+        let by_day = mgr.cost_report(Some(since), CostGroupBy::Day).unwrap();
+        assert_eq!(by_day.len(), 1);
+        assert_eq!((by_day[0].key.as_str(), by_day[0].runs), ("2026-01-02", 2));
+        assert_eq!(by_day[0].duration_ms, 2000);
+
+        let by_day = mgr.cost_report(None, CostGroupBy::Day).unwrap();
+        let days: Vec<&str> = by_day.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(days, ["2026-01-01", "2026-01-02"]);
+    }
+
+    #[test]
+    fn visible_cost_report_leaves_out_other_users_private_repos() {
+        let conn = super::super::manager::setup_db();
+        add_run(&conn, "w1", "2026-01-01T10:00:00Z", 1.0, 4);
+        crate::repo_visibility::RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let mgr = AgentManager::new(&conn);
+
+        let owner = Viewer::web(Some("tok-a"));
+        let rows = mgr
+            .visible_cost_report(&owner, None, CostGroupBy::Repo)
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        let other = Viewer::web(Some("tok-b"));
+        for group_by in [CostGroupBy::Repo, CostGroupBy::Day] {
+            let rows = mgr.visible_cost_report(&other, None, group_by).unwrap();
+            assert!(rows.is_empty(), "{group_by:?}: {rows:?}");
+        }
+    }
+
+    #[test]
+    fn parse_since_accepts_windows_and_dates() {
+        let now = Utc::now();
+        let month = parse_since("30d").unwrap();
+        assert!((now - month - Duration::days(30)).num_seconds().abs() < 5);
+        assert!(parse_since("2w").is_ok());
+        assert!(parse_since("12h").is_ok());
+        assert!(parse_since("0d").is_err());
+        assert!(parse_since("30x").is_err());
+        assert!(parse_since("d").is_err());
+        assert_eq!("day".parse(), Ok(CostGroupBy::Day));
+        assert!("month".parse::<CostGroupBy>().is_err());
+    }
+}
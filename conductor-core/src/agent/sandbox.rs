@@ -0,0 +1,551 @@
+//! Where `conductor agent run` starts the agent CLI: on the host, or inside a
+//! docker container with the worktree bind-mounted.
+//!
+//! The backend builds its [`Command`] as usual; [`Sandbox::wrap`] turns it
+//! into a `docker run` of the same program and arguments. The container is
+//! named `conductor-run-<run_id>` and labelled with the run ID, removed when
+//! the agent exits (`--rm`) and force-removed when the run is cancelled
+//! ([`remove_run_container`]). Containers whose run is no longer active are
+//! swept by [`cleanup_orphan_containers`]. The agent's stdout and stderr pass
+//! through the `docker` client, so logs are captured exactly as on the host.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::command::run_command;
+use crate::config::{SandboxConfig, SandboxMode};
+use crate::error::{ConductorError, Result};
+use crate::git::git_in;
+
+use super::manager::AgentManager;
+use super::types::AgentRun;
+
+const CONTAINER_PREFIX: &str = "conductor-run-";
+const RUN_LABEL: &str = "conductor.run_id";
+
+/// Devcontainer files checked in the worktree, in order.
+const DEVCONTAINER_FILES: &[&str] = &[".devcontainer/devcontainer.json", ".devcontainer.json"];
+
+/// How the agent of one run is started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sandbox {
+    Host,
+    Container(ContainerSpec),
+}
+
+/// A container to run the agent in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerSpec {
+    pub image: String,
+    /// `--user`; the invoking user's `uid:gid` when unset, so files the
+    /// agent writes in the worktree stay owned by them.
+    pub user: Option<String>,
+    /// Fixed variables set in the container (devcontainer `containerEnv`).
+    pub env: Vec<(String, String)>,
+    /// Host variables passed through by name.
+    pub pass_env: Vec<String>,
+    /// Extra `-v` mounts.
+    pub mounts: Vec<String>,
+    pub docker_args: Vec<String>,
+    /// What the spec came from, e.g. `docker` or `.devcontainer/devcontainer.json`.
+    pub source: String,
+}
+
+/// Name of the container `run_id`'s agent runs in.
+pub fn container_name(run_id: &str) -> String {
+    format!("{CONTAINER_PREFIX}{run_id}")
+}
+
+/// Pick the sandbox mode for a run: `requested` (e.g. `--sandbox`) wins,
+/// otherwise `[agents.sandbox] mode` as tightened by `[agent] sandbox` in the
+/// repo's `.conductor/config.toml`.
+pub fn resolve_mode(
+    requested: Option<SandboxMode>,
+    repo_mode: Option<SandboxMode>,
+    config: &SandboxConfig,
+) -> SandboxMode {
+    requested.unwrap_or_else(|| config.mode.tightened_by(repo_mode))
+}
+
+impl Sandbox {
+    /// Build the sandbox for `mode` and the worktree at `worktree_path`.
+    /// `devcontainer` mode builds the image first when the spec names a
+    /// Dockerfile instead of an image.
+    pub fn prepare(
+        mode: SandboxMode,
+        config: &SandboxConfig,
+        worktree_path: &Path,
+    ) -> Result<Self> {
+        let spec = match mode {
+            SandboxMode::Host => return Ok(Self::Host),
+            SandboxMode::Docker => {
+                let image = config.image.clone().ok_or_else(|| {
+                    ConductorError::Config(
+                        "[agents.sandbox] image is required for docker mode".to_string(),
+                    )
+                })?;
+                ContainerSpec {
+                    image,
+                    user: None,
+                    env: Vec::new(),
+                    pass_env: Vec::new(),
+                    mounts: Vec::new(),
+                    docker_args: Vec::new(),
+                    source: "docker".to_string(),
+                }
+            }
+            SandboxMode::Devcontainer => devcontainer_spec(worktree_path)?,
+        };
+        let mut mounts = Vec::with_capacity(config.mounts.len());
+        for mount in &config.mounts {
+            mounts.push(expand_mount(mount)?);
+        }
+        Ok(Self::Container(ContainerSpec {
+            pass_env: config.env.clone(),
+            mounts,
+            docker_args: [spec.docker_args.as_slice(), config.docker_args.as_slice()].concat(),
+            ..spec
+        }))
+    }
+
+    /// One-line description for logs and the run environment, or `None` on
+    /// the host.
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            Self::Host => None,
+            Self::Container(spec) if spec.source == "docker" => {
+                Some(format!("docker {}", spec.image))
+            }
+            Self::Container(spec) => Some(format!("devcontainer {} ({})", spec.image, spec.source)),
+        }
+    }
+
+    /// Turn `cmd`, built to run the agent on the host in `worktree_path`,
+    /// into the command that runs it for `run_id`. Environment set on `cmd`
+    /// is passed into the container by name; the caller sets stdio.
+    pub fn wrap(&self, cmd: Command, run_id: &str, worktree_path: &Path) -> Command {
+        let Self::Container(spec) = self else {
+            return cmd;
+        };
+        let mut docker = Command::new("docker");
+        docker
+            .args(["run", "--rm", "--init", "--name"])
+            .arg(container_name(run_id))
+            .arg("--label")
+            .arg(format!("{RUN_LABEL}={run_id}"));
+        match &spec.user {
+            Some(user) => {
+                docker.arg("--user").arg(user);
+            }
+            None => {
+                if let Some(user) = host_user() {
+                    docker.arg("--user").arg(user);
+                }
+            }
+        }
+        for dir in bind_dirs(worktree_path) {
+            docker.arg("-v").arg(format!("{0}:{0}", dir.display()));
+        }
+        for mount in &spec.mounts {
+            docker.arg("-v").arg(mount);
+        }
+        docker.arg("-w").arg(worktree_path);
+        for (key, value) in &spec.env {
+            docker.arg("-e").arg(format!("{key}={value}"));
+        }
+        for key in &spec.pass_env {
+            if std::env::var_os(key).is_some() {
+                docker.arg("-e").arg(key);
+            }
+        }
+        for (key, value) in cmd.get_envs() {
+            if let Some(value) = value {
+                docker.env(key, value);
+                docker.arg("-e").arg(key);
+            }
+        }
+        docker
+            .args(&spec.docker_args)
+            .arg(&spec.image)
+            .arg(cmd.get_program())
+            .args(cmd.get_args());
+        docker
+    }
+}
+
+/// Directories mounted at their host path: the worktree, and the git
+/// directory its `.git` file points into, so git works inside the container.
+fn bind_dirs(worktree_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![worktree_path.to_path_buf()];
+    let common = crate::command::output(git_in(worktree_path).args([
+        "rev-parse",
+        "--path-format=absolute",
+        "--git-common-dir",
+    ]))
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()));
+    if let Some(common) = common {
+        if !common.as_os_str().is_empty() && !common.starts_with(worktree_path) {
+            dirs.push(common);
+        }
+    }
+    dirs
+}
+
+#[cfg(unix)]
+fn host_user() -> Option<String> {
+    // SAFETY: getuid and getgid cannot fail and have no preconditions.
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    Some(format!("{uid}:{gid}"))
+}
+
+#[cfg(not(unix))]
+fn host_user() -> Option<String> {
+    None
+}
+
+fn expand_mount(mount: &str) -> Result<String> {
+    let (host, rest) = mount.split_once(':').unwrap_or((mount, ""));
+    let host = crate::workspace::expand_path(host)
+        .map_err(|e| ConductorError::Config(format!("[agents.sandbox] mounts: {e}")))?;
+    Ok(if rest.is_empty() {
+        host.display().to_string()
+    } else {
+        format!("{}:{rest}", host.display())
+    })
+}
+
+/// The fields of `devcontainer.json` conductor understands.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DevcontainerJson {
+    image: Option<String>,
+    build: Option<DevcontainerBuild>,
+    #[serde(default)]
+    container_env: std::collections::BTreeMap<String, String>,
+    container_user: Option<String>,
+    remote_user: Option<String>,
+    #[serde(default)]
+    run_args: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DevcontainerBuild {
+    dockerfile: Option<String>,
+    context: Option<String>,
+}
+
+/// The devcontainer spec in the worktree, with its image built if needed.
+fn devcontainer_spec(worktree_path: &Path) -> Result<ContainerSpec> {
+    let Some(rel) = DEVCONTAINER_FILES
+        .iter()
+        .find(|f| worktree_path.join(f).is_file())
+    else {
+        return Err(ConductorError::Config(format!(
+            "devcontainer sandbox: none of {} exists in {}",
+            DEVCONTAINER_FILES.join(", "),
+            worktree_path.display()
+        )));
+    };
+    let path = worktree_path.join(rel);
+    let raw = std::fs::read_to_string(&path)?;
+    let json: DevcontainerJson = serde_json::from_str(&strip_jsonc(&raw))
+        .map_err(|e| ConductorError::Config(format!("{rel}: {e}")))?;
+    let image = match (
+        json.image,
+        json.build
+            .and_then(|b| b.dockerfile.map(|d| (d, b.context))),
+    ) {
+        (Some(image), _) => image,
+        (None, Some((dockerfile, context))) => {
+            // Paths in devcontainer.json are relative to the file's directory.
+            let base = path.parent().unwrap_or(worktree_path);
+            build_image(
+                worktree_path,
+                &base.join(dockerfile),
+                &base.join(context.as_deref().unwrap_or(".")),
+            )?
+        }
+        (None, None) => {
+            return Err(ConductorError::Config(format!(
+                "{rel}: set `image` or `build.dockerfile`"
+            )))
+        }
+    };
+    Ok(ContainerSpec {
+        image,
+        user: json.container_user.or(json.remote_user),
+        env: json.container_env.into_iter().collect(),
+        pass_env: Vec::new(),
+        mounts: Vec::new(),
+        docker_args: json.run_args,
+        source: rel.to_string(),
+    })
+}
+
+/// Build `dockerfile` and return the image tag, which is derived from the
+/// worktree path so rebuilds reuse docker's layer cache.
+fn build_image(worktree_path: &Path, dockerfile: &Path, context: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(worktree_path.to_string_lossy().as_bytes());
+    let tag = format!("conductor-devcontainer:{}", &hex::encode(digest)[..12]);
+    run_command(
+        Command::new("docker")
+            .args(["build", "-t", &tag, "-f"])
+            .arg(dockerfile)
+            .arg(context),
+        crate::command::timeouts().setup(),
+        ConductorError::Docker,
+    )?;
+    Ok(tag)
+}
+
+/// Drop `//` and `/* */` comments and trailing commas from JSONC text,
+/// leaving string contents alone.
+fn strip_jsonc(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut chars = src.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            ']' | '}' => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn docker_available() -> bool {
+    crate::command::output(Command::new("docker").arg("version")).is_ok_and(|o| o.status.success())
+}
+
+fn docker(args: &[&str]) -> Result<std::process::Output> {
+    run_command(
+        Command::new("docker").args(args),
+        crate::command::timeouts().local(),
+        ConductorError::Docker,
+    )
+}
+
+/// Force-remove the container of `run` if it ran in one. Best-effort; used
+/// when a run is cancelled, since killing the `docker` client may leave the
+/// container running.
+pub fn remove_run_container(run: &AgentRun) {
+    let sandboxed = run
+        .environment
+        .as_ref()
+        .is_some_and(|e| e.sandbox.is_some());
+    if !sandboxed {
+        return;
+    }
+    if let Err(e) = docker(&["rm", "-f", &container_name(&run.id)]) {
+        tracing::warn!(run_id = %run.id, "could not remove agent container: {e}");
+    }
+}
+
+/// Remove containers labelled with a run that is no longer active, and
+/// return their names. A no-op when docker is not installed.
+pub fn cleanup_orphan_containers(conn: &Connection) -> Result<Vec<String>> {
+    if !docker_available() {
+        return Ok(Vec::new());
+    }
+    let out = docker(&[
+        "ps",
+        "-a",
+        "--filter",
+        &format!("label={RUN_LABEL}"),
+        "--format",
+        &format!("{{{{.Names}}}}\t{{{{.Label \"{RUN_LABEL}\"}}}}"),
+    ])?;
+    let agents = AgentManager::new(conn);
+    let mut removed = Vec::new();
+    for line in String::from_utf8_lossy(&out.stdout).lines() {
+        let Some((name, run_id)) = line.split_once('\t') else {
+            continue;
+        };
+        // A failed lookup keeps the container; only a known end removes it.
+        let orphan = agents
+            .get_run(run_id)
+            .is_ok_and(|run| run.is_none_or(|r| !r.is_active()));
+        if orphan {
+            docker(&["rm", "-f", name])?;
+            removed.push(name.to_string());
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn wrap_runs_the_agent_in_docker_with_the_worktree_mounted() {
+        let dir = tempfile::tempdir().unwrap();
+        let sandbox = Sandbox::Container(ContainerSpec {
+            image: "agent:latest".into(),
+            user: Some("agent".into()),
+            env: vec![("LANG".into(), "C.UTF-8".into())],
+            pass_env: Vec::new(),
+            mounts: vec!["/cache:/cache".into()],
+            docker_args: vec!["--network".into(), "none".into()],
+            source: "docker".into(),
+        });
+        let mut cmd = Command::new("claude");
+        cmd.args(["-p", "fix it"]).env("GH_TOKEN", "secret");
+
+        let wrapped = sandbox.wrap(cmd, "01RUN", dir.path());
+        assert_eq!(wrapped.get_program(), "docker");
+        let args = args(&wrapped);
+        let wt = dir.path().display().to_string();
+        let joined = args.join(" ");
+        assert!(
+            joined.starts_with("run --rm --init --name conductor-run-01RUN"),
+            "{joined}"
+        );
+        assert!(joined.contains(&format!("-v {wt}:{wt}")), "{joined}");
+        assert!(joined.contains(&format!("-w {wt}")), "{joined}");
+        assert!(joined.contains("--user agent"), "{joined}");
+        assert!(joined.contains("-e LANG=C.UTF-8"), "{joined}");
+        assert!(
+            joined.ends_with("--network none agent:latest claude -p fix it"),
+            "{joined}"
+        );
+        // Secrets set on the host command go in by name only.
+        assert!(joined.contains("-e GH_TOKEN"), "{joined}");
+        assert!(!joined.contains("secret"), "{joined}");
+        assert!(wrapped
+            .get_envs()
+            .any(|(k, v)| k == "GH_TOKEN" && v.is_some_and(|v| v == "secret")));
+
+        let host = Sandbox::Host.wrap(Command::new("claude"), "01RUN", dir.path());
+        assert_eq!(host.get_program(), "claude");
+    }
+
+    #[test]
+    fn prepare_reads_devcontainer_json_with_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".devcontainer")).unwrap();
+        std::fs::write(
+            dir.path().join(".devcontainer/devcontainer.json"),
+            r#"{
+  // Base image
+  "image": "mcr.microsoft.com/devcontainers/rust:1", /* pinned */
+  "remoteUser": "vscode",
+  "containerEnv": { "URL": "http://example.com//x" },
+  "runArgs": ["--cap-drop=ALL",],
+}"#,
+        )
+        .unwrap();
+        let config = SandboxConfig {
+            docker_args: vec!["--memory=4g".into()],
+            ..Default::default()
+        };
+
+        let Sandbox::Container(spec) =
+            Sandbox::prepare(SandboxMode::Devcontainer, &config, dir.path()).unwrap()
+        else {
+            panic!("expected a container");
+        };
+        assert_eq!(spec.image, "mcr.microsoft.com/devcontainers/rust:1");
+        assert_eq!(spec.user.as_deref(), Some("vscode"));
+        assert_eq!(spec.env, [("URL".into(), "http://example.com//x".into())]);
+        assert_eq!(spec.docker_args, ["--cap-drop=ALL", "--memory=4g"]);
+        assert_eq!(spec.pass_env, config.env);
+        assert_eq!(spec.source, ".devcontainer/devcontainer.json");
+    }
+
+    #[test]
+    fn prepare_reports_missing_image_and_devcontainer() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SandboxConfig::default();
+        assert_eq!(
+            Sandbox::prepare(SandboxMode::Host, &config, dir.path()).unwrap(),
+            Sandbox::Host
+        );
+        let err = Sandbox::prepare(SandboxMode::Docker, &config, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("image"), "{err}");
+        let err = Sandbox::prepare(SandboxMode::Devcontainer, &config, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("devcontainer.json"), "{err}");
+
+        assert_eq!(
+            resolve_mode(None, Some(SandboxMode::Docker), &config),
+            SandboxMode::Docker
+        );
+        assert_eq!(
+            resolve_mode(Some(SandboxMode::Host), Some(SandboxMode::Docker), &config),
+            SandboxMode::Host
+        );
+    }
+
+    #[test]
+    fn repo_sandbox_can_only_tighten_the_user_mode() {
+        let config = SandboxConfig {
+            mode: SandboxMode::Docker,
+            ..SandboxConfig::default()
+        };
+        assert_eq!(
+            resolve_mode(None, Some(SandboxMode::Host), &config),
+            SandboxMode::Docker
+        );
+        assert_eq!(
+            resolve_mode(None, Some(SandboxMode::Devcontainer), &config),
+            SandboxMode::Docker
+        );
+        assert_eq!(resolve_mode(None, None, &config), SandboxMode::Docker);
+
+        let config = SandboxConfig::default();
+        assert_eq!(
+            resolve_mode(None, Some(SandboxMode::Devcontainer), &config),
+            SandboxMode::Devcontainer
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Expiring read-only share links to a single agent run.
+//!
+//! A link carries a random token; like API tokens, only its SHA-256 hash is
+//! stored and the token itself is shown once, when the link is created.
+//! Anyone holding the token can read the run's prompt, transcript summary,
+//! diffstat and result until the link expires or is revoked.
+
+use chrono::{Duration, Utc};
+use rand_core::{OsRng, RngCore};
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::worktree::WorktreeManager;
+
+use super::manager::AgentManager;
+use super::status::AgentRunStatus;
+use super::types::PlanStep;
+
+/// Marks a string as a run share token.
+const TOKEN_PREFIX: &str = "crs_";
+
+/// Lifetime of a link when the caller does not pick one.
+pub const DEFAULT_SHARE_TTL_HOURS: u32 = 24;
+
+/// Longest lifetime a link may be given (30 days).
+pub const MAX_SHARE_TTL_HOURS: u32 = 24 * 30;
+
+/// Transcript events included in a shared run; long runs keep the latest.
+const MAX_SHARED_EVENTS: usize = 500;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunShare {
+    pub id: String,
+    pub run_id: String,
+    pub created_at: String,
+    pub expires_at: String,
+    pub revoked_at: Option<String>,
+}
+
+/// One line of a shared run's transcript summary.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedRunEvent {
+    pub kind: String,
+    pub summary: String,
+    pub started_at: String,
+}
+
+/// What a share link shows: the run without cost, logs or local paths.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedRun {
+    pub prompt: String,
+    pub status: AgentRunStatus,
+    pub model: Option<String>,
+    /// Branch of the run's worktree; `None` for repo-scoped runs.
+    pub branch: Option<String>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub num_turns: Option<i64>,
+    pub plan: Option<Vec<PlanStep>>,
+    pub events: Vec<SharedRunEvent>,
+    /// `git diff --stat` of the worktree when the link was created.
+    pub diffstat: Option<String>,
+    pub result_text: Option<String>,
+    pub expires_at: String,
+}
+
+const SHARE_COLUMNS: &str = "id, run_id, created_at, expires_at, revoked_at";
+
+pub struct RunShares<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> RunShares<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a link to run `run_id` that expires after `ttl_hours`. Returns
+    /// the stored record and the token, which is not recoverable afterwards.
+    pub fn create(
+        &self,
+        config: &Config,
+        run_id: &str,
+        ttl_hours: u32,
+    ) -> Result<(RunShare, String)> {
+        if !(1..=MAX_SHARE_TTL_HOURS).contains(&ttl_hours) {
+            return Err(ConductorError::InvalidInput(format!(
+                "share link lifetime must be between 1 and {MAX_SHARE_TTL_HOURS} hours"
+            )));
+        }
+        let run = AgentManager::new(self.conn)
+            .get_run(run_id)?
+            .ok_or_else(|| ConductorError::AgentRunNotFound {
+                id: run_id.to_string(),
+            })?;
+        let diffstat = run
+            .worktree_id
+            .as_deref()
+            .and_then(|wt_id| self.worktree_diffstat(config, wt_id));
+
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = format!("{TOKEN_PREFIX}{}", hex::encode(bytes));
+        let now = Utc::now();
+        let share = RunShare {
+            id: crate::new_id(),
+            run_id: run.id,
+            created_at: now.to_rfc3339(),
+            expires_at: (now + Duration::hours(i64::from(ttl_hours))).to_rfc3339(),
+            revoked_at: None,
+        };
+        self.conn.execute(
+            "INSERT INTO agent_run_shares \
+             (id, run_id, token_hash, diffstat, created_at, expires_at) \
+             VALUES (:id, :run_id, :token_hash, :diffstat, :created_at, :expires_at)",
+            named_params! {
+                ":id": share.id,
+                ":run_id": share.run_id,
+                ":token_hash": hash_token(&token),
+                ":diffstat": diffstat,
+                ":created_at": share.created_at,
+                ":expires_at": share.expires_at,
+            },
+        )?;
+        Ok((share, token))
+    }
+
+    /// Links to run `run_id`, expired and revoked ones included, newest first.
+    pub fn list_for_run(&self, run_id: &str) -> Result<Vec<RunShare>> {
+        crate::db::query_collect(
+            self.conn,
+            &format!(
+                "SELECT {SHARE_COLUMNS} FROM agent_run_shares WHERE run_id = :run_id \
+                 ORDER BY created_at DESC, id DESC"
+            ),
+            named_params! { ":run_id": run_id },
+            row_to_share,
+        )
+    }
+
+    pub fn get(&self, id: &str) -> Result<RunShare> {
+        self.conn
+            .query_row(
+                &format!("SELECT {SHARE_COLUMNS} FROM agent_run_shares WHERE id = :id"),
+                named_params! { ":id": id },
+                row_to_share,
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::RunShareNotFound { id: id.to_string() })
+    }
+
+    /// Revoke a link. Revoking an already revoked link keeps the original
+    /// revocation time.
+    pub fn revoke(&self, id: &str) -> Result<RunShare> {
+        self.get(id)?;
+        self.conn.execute(
+            "UPDATE agent_run_shares SET revoked_at = :now WHERE id = :id AND revoked_at IS NULL",
+            named_params! { ":id": id, ":now": Utc::now().to_rfc3339() },
+        )?;
+        self.get(id)
+    }
+
+    /// The run behind a live link. Returns `None` for unknown, expired or
+    /// revoked tokens.
+    pub fn resolve(&self, token: &str) -> Result<Option<SharedRun>> {
+        if !token.starts_with(TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let share = self
+            .conn
+            .query_row(
+                "SELECT run_id, diffstat, expires_at FROM agent_run_shares \
+                 WHERE token_hash = :hash AND revoked_at IS NULL AND expires_at > :now",
+                named_params! {
+                    ":hash": hash_token(token),
+                    ":now": Utc::now().to_rfc3339(),
+                },
+                |row| {
+                    Ok((
+                        row.get::<_, String>("run_id")?,
+                        row.get::<_, Option<String>>("diffstat")?,
+                        row.get::<_, String>("expires_at")?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((run_id, diffstat, expires_at)) = share else {
+            return Ok(None);
+        };
+
+        let mgr = AgentManager::new(self.conn);
+        let Some(run) = mgr.get_run(&run_id)? else {
+            return Ok(None);
+        };
+        let branch = match run.worktree_id.as_deref() {
+            Some(wt_id) => self
+                .conn
+                .query_row(
+                    "SELECT branch FROM worktrees WHERE id = :id",
+                    named_params! { ":id": wt_id },
+                    |row| row.get("branch"),
+                )
+                .optional()?,
+            None => None,
+        };
+        let mut events = mgr.list_events_for_run(&run_id)?;
+        let skip = events.len().saturating_sub(MAX_SHARED_EVENTS);
+        let events = events
+            .drain(skip..)
+            .map(|e| SharedRunEvent {
+                kind: e.kind,
+                summary: e.summary,
+                started_at: e.started_at,
+            })
+            .collect();
+
+        Ok(Some(SharedRun {
+            prompt: run.prompt,
+            status: run.status,
+            model: run.model,
+            branch,
+            started_at: run.started_at,
+            ended_at: run.ended_at,
+            duration_ms: run.duration_ms,
+            num_turns: run.num_turns,
+            plan: run.plan,
+            events,
+            diffstat,
+            result_text: run.result_text,
+            expires_at,
+        }))
+    }
+
+    /// The diffstat of live worktree `worktree_id`, or `None` when it is gone
+    /// or git fails; a link is still worth creating without it.
+    fn worktree_diffstat(&self, config: &Config, worktree_id: &str) -> Option<String> {
+        let wt_mgr = WorktreeManager::new(self.conn, config);
+        let result = wt_mgr.get_by_id(worktree_id).and_then(|wt| {
+            let repo = RepoManager::new(self.conn, config).get_by_id(&wt.repo_id)?;
+            wt_mgr.diff_stat(&repo.slug, &wt.slug)
+        });
+        match result {
+            Ok(stat) if !stat.trim().is_empty() => Some(stat),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::debug!(worktree_id, "no diffstat for shared run: {e}");
+                None
+            }
+        }
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn row_to_share(row: &rusqlite::Row<'_>) -> rusqlite::Result<RunShare> {
+    Ok(RunShare {
+        id: row.get("id")?,
+        run_id: row.get("run_id")?,
+        created_at: row.get("created_at")?,
+        expires_at: row.get("expires_at")?,
+        revoked_at: row.get("revoked_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{insert_test_agent_run, setup_db};
+
+    #[test]
+    fn links_resolve_until_revoked_or_expired() {
+        let conn = setup_db();
+        insert_test_agent_run(&conn, "run1", "w1");
+        let shares = RunShares::new(&conn);
+        let config = Config::default();
+
+        let (share, token) = shares.create(&config, "run1", 2).unwrap();
+        let stored: String = conn
+            .query_row("SELECT token_hash FROM agent_run_shares", [], |r| r.get(0))
+            .unwrap();
+        assert_ne!(stored, token);
+
+        let shared = shares.resolve(&token).unwrap().unwrap();
+        assert_eq!(shared.branch.as_deref(), Some("feat/test"));
+        assert_eq!(shared.expires_at, share.expires_at);
+        assert!(shares.resolve("crs_nope").unwrap().is_none());
+        assert!(shares.resolve("").unwrap().is_none());
+
+        shares.revoke(&share.id).unwrap();
+        assert!(shares.resolve(&token).unwrap().is_none());
+
+        let (_, expired) = shares.create(&config, "run1", 1).unwrap();
+        conn.execute(
+            "UPDATE agent_run_shares SET expires_at = '2000-01-01T00:00:00+00:00' \
+             WHERE revoked_at IS NULL",
+            [],
+        )
+        .unwrap();
+        assert!(shares.resolve(&expired).unwrap().is_none());
+        assert_eq!(shares.list_for_run("run1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn create_validates_run_and_lifetime() {
+        let conn = setup_db();
+        insert_test_agent_run(&conn, "run1", "w1");
+        let shares = RunShares::new(&conn);
+        let config = Config::default();
+        assert!(matches!(
+            shares.create(&config, "missing", 1),
+            Err(ConductorError::AgentRunNotFound { .. })
+        ));
+        for ttl in [0, MAX_SHARE_TTL_HOURS + 1] {
+            assert!(matches!(
+                shares.create(&config, "run1", ttl),
+                Err(ConductorError::InvalidInput(_))
+            ));
+        }
+        assert!(matches!(
+            shares.revoke("missing"),
+            Err(ConductorError::RunShareNotFound { .. })
+        ));
+    }
+}
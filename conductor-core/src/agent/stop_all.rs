@@ -0,0 +1,188 @@
+//! Stop every active agent run at once — the panic button for a batch of runs
+//! started with the wrong prompt.
+
+use chrono::Utc;
+use rusqlite::{named_params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::Result;
+
+use super::db::{row_to_agent_run, AGENT_RUN_SELECT};
+use super::log_parsing::count_turns_in_log;
+use super::manager::AgentManager;
+use super::queue::AgentQueue;
+use super::types::AgentRun;
+
+/// One run cancelled by [`AgentManager::stop_all`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoppedRun {
+    pub run_id: String,
+    pub worktree_id: Option<String>,
+    /// Set for repo-scoped runs.
+    pub repo_id: Option<String>,
+    pub repo_slug: Option<String>,
+    /// `None` for repo-scoped runs.
+    pub worktree_slug: Option<String>,
+    pub prompt: String,
+    /// The run's output up to the moment it was stopped.
+    pub log_file: Option<String>,
+    /// Assistant turns the run got through, counted from its log.
+    pub turns: i64,
+    pub cost_usd: Option<f64>,
+}
+
+/// What [`AgentManager::stop_all`] did.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StopAllSummary {
+    pub stopped: Vec<StoppedRun>,
+    /// Queued runs left untouched; they start as soon as a slot frees up
+    /// unless removed from the queue.
+    pub queued: usize,
+}
+
+impl AgentManager<'_> {
+    /// Every running or feedback-waiting run, oldest first. Plan steps are
+    /// not loaded.
+    pub fn list_active_runs(&self) -> Result<Vec<AgentRun>> {
+        query_collect(
+            self.conn,
+            &format!(
+                "{AGENT_RUN_SELECT} WHERE status IN ('running', 'waiting_for_feedback') \
+                 ORDER BY started_at"
+            ),
+            [],
+            row_to_agent_run,
+        )
+    }
+
+    /// Cancel every running or feedback-waiting agent run.
+    ///
+    /// All runs are marked cancelled first, so their drains cannot record a
+    /// different outcome, then their subprocesses are sent SIGTERM together
+    /// and killed after the usual grace period. Each run's turn count is read
+    /// back from its log, which stays on disk.
+    pub fn stop_all(&self) -> Result<StopAllSummary> {
+        let runs = self.list_active_runs()?;
+        let now = Utc::now().to_rfc3339();
+        let mut cancelled = Vec::new();
+        for run in runs {
+            // Guarded so a run that finished since the SELECT keeps its outcome.
+            let changed = self.conn.execute(
+                "UPDATE agent_runs SET status = 'cancelled', ended_at = :ended_at \
+                 WHERE id = :id AND status IN ('running', 'waiting_for_feedback')",
+                named_params! { ":ended_at": now, ":id": run.id },
+            )?;
+            if changed > 0 {
+                cancelled.push(run);
+            }
+        }
+
+        // Signal concurrently: cancel_subprocess blocks for its grace period.
+        std::thread::scope(|s| {
+            for pid in cancelled.iter().filter_map(|r| r.subprocess_pid) {
+                s.spawn(move || crate::process_utils::cancel_subprocess(pid as u32));
+            }
+        });
+        for run in &cancelled {
+            super::sandbox::remove_run_container(run);
+        }
+
+        let mut stopped = Vec::with_capacity(cancelled.len());
+        for run in cancelled {
+            let turns = run
+                .log_path()
+                .ok()
+                .and_then(|p| p.to_str().map(count_turns_in_log))
+                .unwrap_or(0);
+            self.conn.execute(
+                "UPDATE agent_runs SET num_turns = COALESCE(num_turns, :turns) WHERE id = :id",
+                named_params! { ":turns": turns, ":id": run.id },
+            )?;
+            let (repo_slug, worktree_slug) = self.run_target_slugs(&run)?;
+            stopped.push(StoppedRun {
+                run_id: run.id,
+                worktree_id: run.worktree_id,
+                repo_id: run.repo_id,
+                repo_slug,
+                worktree_slug,
+                prompt: run.prompt,
+                log_file: run.log_file,
+                turns,
+                cost_usd: run.cost_usd,
+            });
+        }
+
+        Ok(StopAllSummary {
+            stopped,
+            queued: AgentQueue::new(self.conn).list()?.len(),
+        })
+    }
+
+    fn run_target_slugs(&self, run: &AgentRun) -> Result<(Option<String>, Option<String>)> {
+        if let Some(worktree_id) = &run.worktree_id {
+            let slugs = self
+                .conn
+                .query_row(
+                    "SELECT r.slug, w.slug FROM worktrees w JOIN repos r ON r.id = w.repo_id \
+                     WHERE w.id = :id",
+                    named_params! { ":id": worktree_id },
+                    |row| Ok((Some(row.get(0)?), Some(row.get(1)?))),
+                )
+                .optional()?;
+            return Ok(slugs.unwrap_or((None, None)));
+        }
+        let repo_slug = match &run.repo_id {
+            Some(repo_id) => self
+                .conn
+                .query_row(
+                    "SELECT slug FROM repos WHERE id = :id",
+                    named_params! { ":id": repo_id },
+                    |row| row.get(0),
+                )
+                .optional()?,
+            None => None,
+        };
+        Ok((repo_slug, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentRunStatus;
+
+    #[test]
+    fn stop_all_cancels_active_runs_only() {
+        let conn = super::super::manager::setup_db();
+        let mgr = AgentManager::new(&conn);
+        let running = mgr.create_run(Some("w1"), "wrong prompt", None).unwrap();
+        let waiting = mgr.create_run(Some("w2"), "also wrong", None).unwrap();
+        mgr.request_feedback(&waiting.id, "continue?", None)
+            .unwrap();
+        let done = mgr.create_run(Some("w1"), "fine", None).unwrap();
+        mgr.update_run_cancelled(&done.id).unwrap();
+
+        let summary = mgr.stop_all().unwrap();
+        let mut ids: Vec<&str> = summary.stopped.iter().map(|r| r.run_id.as_str()).collect();
+        ids.sort();
+        let mut expected = vec![running.id.as_str(), waiting.id.as_str()];
+        expected.sort();
+        assert_eq!(ids, expected);
+        let first = summary
+            .stopped
+            .iter()
+            .find(|r| r.run_id == running.id)
+            .unwrap();
+        assert_eq!(first.repo_slug.as_deref(), Some("test-repo"));
+        assert_eq!(first.worktree_slug.as_deref(), Some("feat-test"));
+        for id in expected {
+            let run = mgr.get_run(id).unwrap().unwrap();
+            assert_eq!(run.status, AgentRunStatus::Cancelled);
+            assert!(run.ended_at.is_some());
+        }
+        assert!(mgr.stop_all().unwrap().stopped.is_empty());
+    }
+}
@@ -0,0 +1,327 @@
+//! Process-based agent supervisor: runs `conductor agent run` as a detached
+//! child instead of inside a tmux window or a pipe owned by the launcher.
+//!
+//! The child gets its own process group and no stdio tied to the caller, so
+//! it keeps running after the TUI or CLI that started it exits. Its PID is
+//! recorded on the run, which is how [`AgentSupervisor::stop`] signals it
+//! and how the orphan reaper notices it died. Output is read back from the
+//! run's log file, which `conductor agent run` writes itself.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::Connection;
+
+use crate::agent_runtime::{build_headless_agent_args, resolve_conductor_bin, SpawnHeadlessParams};
+use crate::error::{ConductorError, Result};
+use crate::process_utils::pid_is_alive;
+
+use super::log_parsing::parse_events_since;
+use super::manager::AgentManager;
+use super::types::{AgentEvent, AgentRun};
+
+/// Starts, stops and follows detached agent runs.
+pub struct AgentSupervisor<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AgentSupervisor<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Start `conductor agent run` for `params.run_id` as a detached process
+    /// and record its PID on the run. Returns the PID.
+    ///
+    /// The agent's stderr goes to [`stderr_log_path`]; its stream-json output
+    /// is in the run's usual log. A run that cannot be started is marked
+    /// failed.
+    pub fn start(
+        &self,
+        params: &SpawnHeadlessParams<'_>,
+        env: &HashMap<String, String>,
+    ) -> Result<u32> {
+        let (args, prompt_file) =
+            build_headless_agent_args(params).map_err(ConductorError::Agent)?;
+        let stderr_log = stderr_log_path(params.run_id);
+        if let Some(dir) = stderr_log.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        self.start_command(
+            params.run_id,
+            &resolve_conductor_bin(),
+            &args,
+            Path::new(params.working_dir),
+            env,
+            &stderr_log,
+        )
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&prompt_file);
+        })
+    }
+
+    fn start_command(
+        &self,
+        run_id: &str,
+        binary: &str,
+        args: &[Cow<'static, str>],
+        working_dir: &Path,
+        env: &HashMap<String, String>,
+        stderr_log: &Path,
+    ) -> Result<u32> {
+        let mgr = AgentManager::new(self.conn);
+        let pid = match spawn_detached(binary, args, working_dir, env, stderr_log) {
+            Ok(pid) => pid,
+            Err(e) => {
+                let message = format!("failed to start agent run {run_id}: {e}");
+                if let Err(mark_err) = mgr.update_run_failed(run_id, &message) {
+                    tracing::warn!("failed to mark run {run_id} failed: {mark_err}");
+                }
+                return Err(ConductorError::Agent(message));
+            }
+        };
+        if let Err(e) = mgr.update_run_subprocess_pid(run_id, pid) {
+            // Untracked, it could not be stopped or reaped; don't leave it running.
+            crate::process_utils::cancel_subprocess(pid);
+            return Err(e);
+        }
+        Ok(pid)
+    }
+
+    /// Cancel an active run and signal its process group. Returns the run as
+    /// it was before cancelling.
+    pub fn stop(&self, run_id: &str) -> Result<AgentRun> {
+        let mgr = AgentManager::new(self.conn);
+        let run = mgr
+            .get_run(run_id)?
+            .ok_or_else(|| ConductorError::AgentRunNotFound {
+                id: run_id.to_string(),
+            })?;
+        if !run.is_active() {
+            return Err(ConductorError::Agent(format!(
+                "agent run {run_id} is not running ({})",
+                run.status
+            )));
+        }
+        mgr.cancel_run(&run.id, run.subprocess_pid)?;
+        Ok(run)
+    }
+
+    /// Stream `run_id`'s log events to `on_event`, polling every
+    /// `poll_interval`, until the run is no longer active or its process has
+    /// gone. Returns the run as last read.
+    pub fn follow(
+        &self,
+        run_id: &str,
+        poll_interval: Duration,
+        mut on_event: impl FnMut(&AgentEvent),
+    ) -> Result<AgentRun> {
+        let mgr = AgentManager::new(self.conn);
+        let mut log = LogFollower::new(mgr.log_path_for_run(run_id)?);
+        loop {
+            let run = mgr
+                .get_run(run_id)?
+                .ok_or_else(|| ConductorError::AgentRunNotFound {
+                    id: run_id.to_string(),
+                })?;
+            log.poll().iter().for_each(&mut on_event);
+            let exited = run
+                .subprocess_pid
+                .is_some_and(|pid| !pid_is_alive(pid as u32));
+            if !run.is_active() || exited {
+                // Pick up anything written between the poll and the exit.
+                log.poll().iter().for_each(&mut on_event);
+                return Ok(run);
+            }
+            std::thread::sleep(poll_interval);
+        }
+    }
+}
+
+/// Incremental reader of a run's stream-json log.
+pub struct LogFollower {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl LogFollower {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, offset: 0 }
+    }
+
+    /// Display events from the complete lines written since the last poll.
+    pub fn poll(&mut self) -> Vec<AgentEvent> {
+        let (offset, events) = parse_events_since(&self.path.to_string_lossy(), self.offset);
+        self.offset = offset;
+        events.into_iter().map(|(_, e)| e).collect()
+    }
+}
+
+/// Where a supervised run's stderr is written, next to its log.
+pub fn stderr_log_path(run_id: &str) -> PathBuf {
+    crate::config::agent_log_dir().join(format!("{run_id}.stderr.log"))
+}
+
+fn spawn_detached(
+    binary: &str,
+    args: &[Cow<'static, str>],
+    working_dir: &Path,
+    env: &HashMap<String, String>,
+    stderr_log: &Path,
+) -> std::io::Result<u32> {
+    use std::process::{Command, Stdio};
+
+    let stderr = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(stderr_log)?;
+    let mut cmd = Command::new(binary);
+    cmd.args(args.iter().map(|a| a.as_ref()))
+        .current_dir(working_dir)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(stderr);
+    // Own process group: no terminal signals from the launcher, and
+    // `cancel_subprocess` can signal the agent CLI it starts too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    // No console shared with the launcher, so closing its window doesn't
+    // end the run.
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+    let mut child = cmd.spawn()?;
+    let pid = child.id();
+    // Reap it on exit so it doesn't linger as a zombie that still looks alive.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(pid)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::agent::status::AgentRunStatus;
+
+    fn wait_for_exit(pid: u32) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while pid_is_alive(pid) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "pid {pid} did not exit"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_start_command_records_pid_and_captures_stderr() {
+        let conn = crate::test_helpers::setup_db();
+        let run = AgentManager::new(&conn)
+            .create_run(Some("w1"), "prompt", None)
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let stderr_log = dir.path().join("stderr.log");
+        let args = [
+            Cow::Borrowed("-c"),
+            Cow::Borrowed("echo \"started $CONDUCTOR_TEST_VAR\" >&2"),
+        ];
+        let env = HashMap::from([("CONDUCTOR_TEST_VAR".to_string(), "ok".to_string())]);
+
+        let pid = AgentSupervisor::new(&conn)
+            .start_command(&run.id, "/bin/sh", &args, dir.path(), &env, &stderr_log)
+            .unwrap();
+        wait_for_exit(pid);
+
+        let run = AgentManager::new(&conn).get_run(&run.id).unwrap().unwrap();
+        assert_eq!(run.subprocess_pid, Some(pid as i64));
+        assert_eq!(
+            std::fs::read_to_string(&stderr_log).unwrap().trim(),
+            "started ok"
+        );
+    }
+
+    #[test]
+    fn test_start_command_marks_run_failed_when_spawn_fails() {
+        let conn = crate::test_helpers::setup_db();
+        let run = AgentManager::new(&conn)
+            .create_run(Some("w1"), "prompt", None)
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = AgentSupervisor::new(&conn)
+            .start_command(
+                &run.id,
+                "/nonexistent/conductor",
+                &[],
+                dir.path(),
+                &HashMap::new(),
+                &dir.path().join("stderr.log"),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains(&run.id), "{err}");
+
+        let run = AgentManager::new(&conn).get_run(&run.id).unwrap().unwrap();
+        assert_eq!(run.status, AgentRunStatus::Failed);
+        assert!(run.subprocess_pid.is_none());
+    }
+
+    #[test]
+    fn test_stop_rejects_finished_run() {
+        let conn = crate::test_helpers::setup_db();
+        let mgr = AgentManager::new(&conn);
+        let run = mgr.create_run(Some("w1"), "prompt", None).unwrap();
+        mgr.update_run_failed(&run.id, "boom").unwrap();
+
+        let err = AgentSupervisor::new(&conn).stop(&run.id).unwrap_err();
+        assert!(err.to_string().contains("not running"), "{err}");
+        assert!(AgentSupervisor::new(&conn).stop("missing").is_err());
+    }
+
+    #[test]
+    fn test_log_follower_returns_only_new_complete_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.log");
+        let text = |t: &str| {
+            format!(
+                "{{\"type\":\"assistant\",\"message\":{{\"content\":[{{\"type\":\"text\",\"text\":\"{t}\"}}]}}}}\n"
+            )
+        };
+        let mut log = LogFollower::new(path.clone());
+        assert!(log.poll().is_empty(), "missing file has no events");
+
+        std::fs::write(&path, text("first")).unwrap();
+        let events = log.poll();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].summary.contains("first"));
+        assert!(log.poll().is_empty());
+
+        let partial = text("second");
+        let (head, tail) = partial.split_at(10);
+        let append = |s: &str| {
+            use std::io::Write;
+            let mut f = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            f.write_all(s.as_bytes()).unwrap();
+        };
+        append(head);
+        assert!(log.poll().is_empty(), "incomplete line is held back");
+        append(tail);
+        let events = log.poll();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].summary.contains("second"));
+    }
+}
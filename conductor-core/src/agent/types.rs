@@ -2,6 +2,7 @@ use std::path::{Component, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use super::environment::RunEnvironment;
 use super::status::{AgentRunStatus, FeedbackStatus, FeedbackType, StepStatus};
 use crate::error::Result;
 
@@ -81,6 +82,15 @@ pub struct AgentRun {
     pub subprocess_pid: Option<i64>,
     #[serde(default = "default_runtime_field")]
     pub runtime: String,
+    /// Claude session this run rotated away from after it reached
+    /// `general.session_rotation_turns`; the run started a fresh session
+    /// seeded with a recap instead of resuming.
+    #[serde(default)]
+    pub rotated_from_session_id: Option<String>,
+    /// CLI version, model, worktree commit and platform captured when the
+    /// run started. `None` for runs that predate capture.
+    #[serde(default)]
+    pub environment: Option<RunEnvironment>,
 }
 
 fn default_runtime_field() -> String {
@@ -217,6 +227,7 @@ fn lexical_normalize(path: PathBuf) -> PathBuf {
 pub struct ClaudeJsonResult {
     pub session_id: Option<String>,
     pub result: Option<String>,
+    #[serde(alias = "total_cost_usd")]
     pub cost_usd: Option<f64>,
     pub num_turns: Option<i64>,
     pub duration_ms: Option<i64>,
@@ -344,6 +355,19 @@ pub struct TicketAgentTotals {
     pub total_cache_creation_tokens: i64,
 }
 
+/// Cache key for per-ticket totals: it changes whenever a run completes or a
+/// completed run is deleted, a worktree is linked to or unlinked from a
+/// ticket, or a repo is made private or shared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketTotalsVersion {
+    pub last_ended_at: Option<String>,
+    pub completed_runs: i64,
+    /// Every `worktree=ticket` link, in worktree order.
+    pub ticket_links: Option<String>,
+    /// Every `repo=owner` of a private repo, in repo order.
+    pub private_repos: Option<String>,
+}
+
 /// Aggregated stats for a run tree (parent + all descendants).
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -417,6 +441,8 @@ mod tests {
             conversation_id: None,
             subprocess_pid: None,
             runtime: "claude".into(),
+            rotated_from_session_id: None,
+            environment: None,
         }
     }
 
@@ -448,6 +474,8 @@ mod tests {
             conversation_id: Some("conv-1".into()),
             subprocess_pid: Some(12345),
             runtime: "claude".into(),
+            rotated_from_session_id: None,
+            environment: None,
         }
     }
 
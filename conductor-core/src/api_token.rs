@@ -0,0 +1,294 @@
+//! Scoped API tokens for the web API.
+//!
+//! A token's secret is shown once, when it is created. Only its SHA-256 hash
+//! is stored, along with a short prefix so tokens can be told apart in
+//! listings. Scopes are ordered: each one grants everything the previous one
+//! does.
+
+use chrono::{Duration, Utc};
+use rand_core::{OsRng, RngCore};
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+
+/// Marks a string as a conductor API token secret.
+const SECRET_PREFIX: &str = "cdt_";
+
+/// Characters of the secret kept in `prefix` for display.
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// `last_used_at` is refreshed at most this often, so a busy client does not
+/// turn every read into a write.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiTokenScope {
+    /// Read any resource; no mutations.
+    ReadOnly,
+    /// Read access plus starting, stopping and answering agents.
+    AgentControl,
+    /// Full access, including managing tokens.
+    Admin,
+}
+
+impl ApiTokenScope {
+    /// Whether a token with this scope may perform an action needing `required`.
+    pub fn allows(self, required: ApiTokenScope) -> bool {
+        self >= required
+    }
+}
+
+impl std::fmt::Display for ApiTokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::ReadOnly => "read-only",
+            Self::AgentControl => "agent-control",
+            Self::Admin => "admin",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for ApiTokenScope {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(Self::ReadOnly),
+            "agent-control" => Ok(Self::AgentControl),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "unknown token scope: {s} (expected read-only, agent-control or admin)"
+            )),
+        }
+    }
+}
+
+crate::impl_sql_enum!(ApiTokenScope);
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scope: ApiTokenScope,
+    /// Leading characters of the secret, e.g. `cdt_1a2b3c4d`.
+    pub prefix: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+const TOKEN_COLUMNS: &str = "id, name, scope, prefix, created_at, last_used_at, revoked_at";
+
+pub struct ApiTokenManager<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ApiTokenManager<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a token. Returns the stored record and the secret, which is not
+    /// recoverable afterwards.
+    pub fn create(&self, name: &str, scope: ApiTokenScope) -> Result<(ApiToken, String)> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ConductorError::InvalidInput(
+                "token name must not be empty".to_string(),
+            ));
+        }
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let secret = format!("{SECRET_PREFIX}{}", hex::encode(bytes));
+        let token = ApiToken {
+            id: crate::new_id(),
+            name: name.to_string(),
+            scope,
+            prefix: secret[..DISPLAY_PREFIX_LEN].to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        self.conn.execute(
+            "INSERT INTO api_tokens (id, name, scope, token_hash, prefix, created_at) \
+             VALUES (:id, :name, :scope, :token_hash, :prefix, :created_at)",
+            named_params! {
+                ":id": token.id,
+                ":name": token.name,
+                ":scope": token.scope,
+                ":token_hash": hash_secret(&secret),
+                ":prefix": token.prefix,
+                ":created_at": token.created_at,
+            },
+        )?;
+        Ok((token, secret))
+    }
+
+    /// All tokens, revoked ones included, newest first.
+    pub fn list(&self) -> Result<Vec<ApiToken>> {
+        query_collect(
+            self.conn,
+            &format!("SELECT {TOKEN_COLUMNS} FROM api_tokens ORDER BY created_at DESC, id DESC"),
+            [],
+            row_to_token,
+        )
+    }
+
+    pub fn get(&self, id: &str) -> Result<ApiToken> {
+        self.conn
+            .query_row(
+                &format!("SELECT {TOKEN_COLUMNS} FROM api_tokens WHERE id = :id"),
+                named_params! { ":id": id },
+                row_to_token,
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::ApiTokenNotFound { id: id.to_string() })
+    }
+
+    /// Revoke a token. Revoking an already revoked token keeps the original
+    /// revocation time.
+    pub fn revoke(&self, id: &str) -> Result<ApiToken> {
+        self.get(id)?;
+        self.conn.execute(
+            "UPDATE api_tokens SET revoked_at = :now WHERE id = :id AND revoked_at IS NULL",
+            named_params! { ":id": id, ":now": Utc::now().to_rfc3339() },
+        )?;
+        self.get(id)
+    }
+
+    /// Look up the live token matching `secret` and record that it was used.
+    /// Returns `None` for unknown or revoked secrets.
+    pub fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>> {
+        if !secret.starts_with(SECRET_PREFIX) {
+            return Ok(None);
+        }
+        let token = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {TOKEN_COLUMNS} FROM api_tokens \
+                     WHERE token_hash = :hash AND revoked_at IS NULL"
+                ),
+                named_params! { ":hash": hash_secret(secret) },
+                row_to_token,
+            )
+            .optional()?;
+        let Some(mut token) = token else {
+            return Ok(None);
+        };
+        let now = Utc::now();
+        let stale_before = (now - Duration::seconds(LAST_USED_RESOLUTION_SECS)).to_rfc3339();
+        let now = now.to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE api_tokens SET last_used_at = :now \
+             WHERE id = :id AND (last_used_at IS NULL OR last_used_at < :stale_before)",
+            named_params! { ":id": token.id, ":now": now, ":stale_before": stale_before },
+        )?;
+        if updated > 0 {
+            token.last_used_at = Some(now);
+        }
+        Ok(Some(token))
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn row_to_token(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiToken> {
+    Ok(ApiToken {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        scope: row.get("scope")?,
+        prefix: row.get("prefix")?,
+        created_at: row.get("created_at")?,
+        last_used_at: row.get("last_used_at")?,
+        revoked_at: row.get("revoked_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        crate::test_helpers::setup_db()
+    }
+
+    #[test]
+    fn secrets_are_stored_hashed_and_authenticate() {
+        let conn = setup();
+        let mgr = ApiTokenManager::new(&conn);
+        let (token, secret) = mgr.create("  ci  ", ApiTokenScope::ReadOnly).unwrap();
+        assert_eq!(token.name, "ci");
+        assert!(secret.starts_with(&token.prefix));
+
+        let stored: String = conn
+            .query_row("SELECT token_hash FROM api_tokens", [], |r| r.get(0))
+            .unwrap();
+        assert_ne!(stored, secret);
+        assert!(!stored.contains(&secret[SECRET_PREFIX.len()..]));
+
+        let authed = mgr.authenticate(&secret).unwrap().unwrap();
+        assert_eq!(authed.id, token.id);
+        assert!(authed.last_used_at.is_some());
+        assert!(mgr.authenticate("cdt_nope").unwrap().is_none());
+        assert!(mgr.authenticate("").unwrap().is_none());
+    }
+
+    #[test]
+    fn last_used_is_throttled() {
+        let conn = setup();
+        let mgr = ApiTokenManager::new(&conn);
+        let (_, secret) = mgr.create("bot", ApiTokenScope::Admin).unwrap();
+        let first = mgr.authenticate(&secret).unwrap().unwrap().last_used_at;
+        let second = mgr.authenticate(&secret).unwrap().unwrap();
+        assert_eq!(mgr.get(&second.id).unwrap().last_used_at, first);
+    }
+
+    #[test]
+    fn revoked_tokens_stop_authenticating() {
+        let conn = setup();
+        let mgr = ApiTokenManager::new(&conn);
+        let (token, secret) = mgr.create("bot", ApiTokenScope::AgentControl).unwrap();
+        let revoked = mgr.revoke(&token.id).unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert_eq!(
+            mgr.revoke(&token.id).unwrap().revoked_at,
+            revoked.revoked_at
+        );
+        assert!(mgr.authenticate(&secret).unwrap().is_none());
+        assert_eq!(mgr.list().unwrap().len(), 1);
+        assert!(matches!(
+            mgr.revoke("missing"),
+            Err(ConductorError::ApiTokenNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn empty_name_is_rejected() {
+        let conn = setup();
+        assert!(matches!(
+            ApiTokenManager::new(&conn).create(" ", ApiTokenScope::Admin),
+            Err(ConductorError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn scopes_are_ordered() {
+        assert!(ApiTokenScope::Admin.allows(ApiTokenScope::AgentControl));
+        assert!(ApiTokenScope::AgentControl.allows(ApiTokenScope::ReadOnly));
+        assert!(!ApiTokenScope::AgentControl.allows(ApiTokenScope::Admin));
+        assert!(!ApiTokenScope::ReadOnly.allows(ApiTokenScope::AgentControl));
+        assert_eq!(
+            "agent-control".parse::<ApiTokenScope>().unwrap(),
+            ApiTokenScope::AgentControl
+        );
+    }
+}
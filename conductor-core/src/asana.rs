@@ -0,0 +1,222 @@
+use crate::error::{ConductorError, Result};
+use crate::issue_source::AsanaConfig;
+use crate::tickets::{TicketInput, TicketLabelInput};
+
+const API_URL: &str = "https://app.asana.com/api/1.0";
+/// Tasks requested per page; Asana caps `limit` at 100.
+const PAGE_LIMIT: u32 = 100;
+/// Upper bound on pages fetched per sync (1000 open tasks).
+const MAX_PAGES: u32 = 10;
+/// Task fields requested from the API; everything [`build_ticket_input`] reads.
+const TASK_FIELDS: &str = "name,notes,completed,assignee.name,tags.name,\
+                           permalink_url,parent.gid";
+
+/// Default environment variable holding an Asana personal access token.
+pub const DEFAULT_TOKEN_ENV: &str = "ASANA_TOKEN";
+
+/// Send a GET to the Asana API with the personal access token read from
+/// `cfg.token_env`, returning the parsed JSON body.
+fn get(cfg: &AsanaConfig, url: &str) -> Result<serde_json::Value> {
+    let token = std::env::var(&cfg.token_env).map_err(|_| {
+        ConductorError::TicketSync(format!(
+            "Asana token not set; export it in ${}",
+            cfg.token_env
+        ))
+    })?;
+    let resp = ureq::get(url)
+        .set("User-Agent", "conductor-ai")
+        .set("Authorization", &format!("Bearer {token}"))
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(401, _) => ConductorError::TicketSync(format!(
+                "Asana rejected the token in ${} (401)",
+                cfg.token_env
+            )),
+            ureq::Error::Status(404, _) => ConductorError::TicketSync(format!(
+                "Asana project {} not found (or the token cannot see it)",
+                cfg.project_gid
+            )),
+            e => ConductorError::TicketSync(format!("Asana request failed: {e}")),
+        })?;
+    resp.into_json()
+        .map_err(|e| ConductorError::TicketSync(format!("failed to parse Asana response: {e}")))
+}
+
+/// Build a [`TicketInput`] from an Asana task JSON value.
+///
+/// The task `gid` is the source ID and tags become labels.
+fn build_ticket_input(task: &serde_json::Value) -> Result<TicketInput> {
+    let gid = task["gid"].as_str().ok_or_else(|| {
+        ConductorError::TicketSync(format!("Asana task is missing a 'gid' field: {task}"))
+    })?;
+    let label_details: Vec<TicketLabelInput> = task["tags"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|t| {
+                    Some(TicketLabelInput {
+                        name: t["name"].as_str()?.to_string(),
+                        color: None,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let state = if task["completed"].as_bool().unwrap_or(false) {
+        "closed"
+    } else {
+        "open"
+    };
+    Ok(TicketInput {
+        source_type: "asana".to_string(),
+        source_id: gid.to_string(),
+        title: task["name"].as_str().unwrap_or("").to_string(),
+        body: task["notes"].as_str().unwrap_or("").to_string(),
+        state: state.to_string(),
+        labels: label_details.iter().map(|l| l.name.clone()).collect(),
+        label_details,
+        assignee: task["assignee"]["name"].as_str().map(|s| s.to_string()),
+        priority: None,
+        url: task["permalink_url"].as_str().unwrap_or("").to_string(),
+        raw_json: serde_json::to_string(task).ok(),
+        blocked_by: vec![],
+        children: vec![],
+        parent: task["parent"]["gid"].as_str().map(|s| s.to_string()),
+        sprint: None,
+        sprint_current: false,
+    })
+}
+
+/// Sync incomplete tasks of an Asana project via the REST API.
+/// Returns a list of normalized TicketInputs ready for upsert.
+///
+/// Completed tasks are excluded (`completed_since=now`) so that
+/// `close_missing_tickets` closes them locally, as for GitHub.
+pub fn sync_asana_tasks(cfg: &AsanaConfig) -> Result<Vec<TicketInput>> {
+    sync_asana_tasks_at(cfg, API_URL)
+}
+
+fn sync_asana_tasks_at(cfg: &AsanaConfig, api_url: &str) -> Result<Vec<TicketInput>> {
+    let mut url = format!(
+        "{api_url}/projects/{}/tasks?completed_since=now&limit={PAGE_LIMIT}&opt_fields={TASK_FIELDS}",
+        cfg.project_gid
+    );
+    let mut tickets = Vec::new();
+    for _ in 0..MAX_PAGES {
+        let page = get(cfg, &url)?;
+        for task in page["data"].as_array().into_iter().flatten() {
+            tickets.push(build_ticket_input(task)?);
+        }
+        match page["next_page"]["uri"].as_str() {
+            Some(next) => url = next.to_string(),
+            None => break,
+        }
+    }
+    Ok(tickets)
+}
+
+/// Fetch a single Asana task by `gid` and return its current state.
+pub fn fetch_asana_task(cfg: &AsanaConfig, gid: &str) -> Result<TicketInput> {
+    fetch_asana_task_at(cfg, API_URL, gid)
+}
+
+fn fetch_asana_task_at(cfg: &AsanaConfig, api_url: &str, gid: &str) -> Result<TicketInput> {
+    let body = get(
+        cfg,
+        &format!("{api_url}/tasks/{gid}?opt_fields={TASK_FIELDS}"),
+    )?;
+    build_ticket_input(&body["data"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(token_env: &str) -> AsanaConfig {
+        AsanaConfig {
+            project_gid: "1200".to_string(),
+            token_env: token_env.to_string(),
+        }
+    }
+
+    #[test]
+    fn sync_follows_next_page_and_maps_tasks() {
+        std::env::set_var("CONDUCTOR_TEST_ASANA_TOKEN_SYNC", "asana-secret");
+        let mut server = mockito::Server::new();
+        let next = format!("{}/projects/1200/tasks?offset=abc", server.url());
+        let _p1 = server
+            .mock("GET", "/projects/1200/tasks")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "completed_since".into(),
+                "now".into(),
+            ))
+            .match_header("Authorization", "Bearer asana-secret")
+            .with_body(
+                serde_json::json!({
+                    "data": [{
+                        "gid": "42", "name": "Ship onboarding", "notes": "Steps...",
+                        "completed": false,
+                        "assignee": {"name": "Alice"},
+                        "tags": [{"name": "growth"}],
+                        "permalink_url": "https://app.asana.com/0/1200/42",
+                        "parent": {"gid": "41"}
+                    }],
+                    "next_page": {"offset": "abc", "uri": next},
+                })
+                .to_string(),
+            )
+            .create();
+        let _p2 = server
+            .mock("GET", "/projects/1200/tasks")
+            .match_query(mockito::Matcher::UrlEncoded("offset".into(), "abc".into()))
+            .with_body(
+                r#"{"data":[{"gid":"43","name":"Docs","completed":false}],"next_page":null}"#,
+            )
+            .create();
+
+        let tickets =
+            sync_asana_tasks_at(&config("CONDUCTOR_TEST_ASANA_TOKEN_SYNC"), &server.url()).unwrap();
+        assert_eq!(tickets.len(), 2);
+        let t = &tickets[0];
+        assert_eq!(t.source_type, "asana");
+        assert_eq!(t.source_id, "42");
+        assert_eq!(t.state, "open");
+        assert_eq!(t.body, "Steps...");
+        assert_eq!(t.labels, vec!["growth"]);
+        assert_eq!(t.assignee.as_deref(), Some("Alice"));
+        assert_eq!(t.parent.as_deref(), Some("41"));
+        assert_eq!(tickets[1].source_id, "43");
+    }
+
+    #[test]
+    fn fetch_one_maps_completed_task_to_closed() {
+        std::env::set_var("CONDUCTOR_TEST_ASANA_TOKEN_FETCH", "asana-secret");
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("GET", "/tasks/42")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"data":{"gid":"42","name":"Done","completed":true}}"#)
+            .create();
+        let t = fetch_asana_task_at(
+            &config("CONDUCTOR_TEST_ASANA_TOKEN_FETCH"),
+            &server.url(),
+            "42",
+        )
+        .unwrap();
+        assert_eq!(t.state, "closed");
+    }
+
+    #[test]
+    fn missing_token_is_reported_before_any_request() {
+        let err = sync_asana_tasks_at(
+            &config("CONDUCTOR_TEST_ASANA_TOKEN_UNSET"),
+            "http://127.0.0.1:9",
+        )
+        .err()
+        .expect("expected error");
+        assert!(
+            matches!(&err, ConductorError::TicketSync(msg) if msg.contains("$CONDUCTOR_TEST_ASANA_TOKEN_UNSET")),
+            "unexpected error: {err:?}"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::error::{ConductorError, Result};
+use crate::issue_source::BitbucketConfig;
+use crate::tickets::{TicketInput, TicketLabelInput};
+
+/// Issues requested per page; Bitbucket caps `pagelen` at 50 for issues.
+const PAGE_LEN: u32 = 50;
+/// Upper bound on pages fetched per sync (1000 open issues).
+const MAX_PAGES: u32 = 20;
+
+/// Default environment variable holding a Bitbucket API token.
+pub const DEFAULT_TOKEN_ENV: &str = "BITBUCKET_TOKEN";
+
+/// Default Bitbucket Cloud REST API root.
+pub const DEFAULT_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Issue states Bitbucket treats as still needing work.
+const OPEN_STATES: &[&str] = &["new", "open", "on hold"];
+
+fn api_url(cfg: &BitbucketConfig, path: &str) -> String {
+    format!(
+        "{}/repositories/{}/{}{path}",
+        cfg.api_url.trim_end_matches('/'),
+        cfg.workspace,
+        cfg.repo_slug
+    )
+}
+
+/// Send a GET to the Bitbucket API. The token in `cfg.token_env` is sent as
+/// HTTP Basic credentials with `cfg.email` when an email is configured (API
+/// tokens tied to an Atlassian account), and as a Bearer token otherwise
+/// (repository or workspace access tokens). Without a token only public
+/// repositories are readable.
+fn get(cfg: &BitbucketConfig, url: &str) -> Result<ureq::Response> {
+    let mut req = ureq::get(url).set("User-Agent", "conductor-ai");
+    if let Ok(token) = std::env::var(&cfg.token_env) {
+        let auth = match &cfg.email {
+            Some(email) => format!("Basic {}", STANDARD.encode(format!("{email}:{token}"))),
+            None => format!("Bearer {token}"),
+        };
+        req = req.set("Authorization", &auth);
+    }
+    req.call().map_err(|e| match e {
+        ureq::Error::Status(401, _) => ConductorError::TicketSync(format!(
+            "Bitbucket rejected the request (401); check the token in ${}",
+            cfg.token_env
+        )),
+        ureq::Error::Status(404, _) => ConductorError::TicketSync(format!(
+            "Bitbucket repository '{}/{}' not found, has no issue tracker, \
+             or the token cannot see it",
+            cfg.workspace, cfg.repo_slug
+        )),
+        e => ConductorError::TicketSync(format!("Bitbucket request failed: {e}")),
+    })
+}
+
+/// Map a Bitbucket issue state to a ticket state. `new`, `open` and
+/// `on hold` are open; `resolved`, `invalid`, `duplicate`, `wontfix` and
+/// `closed` are closed.
+fn map_bitbucket_state(state: &str) -> &'static str {
+    if OPEN_STATES.contains(&state) {
+        "open"
+    } else {
+        "closed"
+    }
+}
+
+/// Build a [`TicketInput`] from a Bitbucket issue JSON value.
+///
+/// Bitbucket issues have no free-form labels, so the issue `kind` (bug,
+/// enhancement, …) and `component` become labels.
+fn build_ticket_input(issue: &serde_json::Value) -> Result<TicketInput> {
+    let id = issue["id"].as_u64().ok_or_else(|| {
+        ConductorError::TicketSync(format!(
+            "Bitbucket issue is missing a valid 'id' field: {issue}"
+        ))
+    })?;
+    let labels: Vec<String> = [issue["kind"].as_str(), issue["component"]["name"].as_str()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect();
+    let assignee = issue["assignee"]["nickname"]
+        .as_str()
+        .or_else(|| issue["assignee"]["display_name"].as_str())
+        .map(|s| s.to_string());
+    Ok(TicketInput {
+        source_type: "bitbucket".to_string(),
+        source_id: id.to_string(),
+        title: issue["title"].as_str().unwrap_or("").to_string(),
+        body: issue["content"]["raw"].as_str().unwrap_or("").to_string(),
+        state: map_bitbucket_state(issue["state"].as_str().unwrap_or("new")).to_string(),
+        label_details: labels
+            .iter()
+            .map(|name| TicketLabelInput {
+                name: name.clone(),
+                color: None,
+            })
+            .collect(),
+        labels,
+        assignee,
+        priority: issue["priority"].as_str().map(|s| s.to_string()),
+        url: issue["links"]["html"]["href"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        raw_json: serde_json::to_string(issue).ok(),
+        blocked_by: vec![],
+        children: vec![],
+        parent: None,
+        sprint: issue["milestone"]["name"]
+            .as_str()
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string()),
+        sprint_current: false,
+    })
+}
+
+/// Sync open issues of a Bitbucket Cloud repository via the REST API (2.0).
+/// Returns a list of normalized TicketInputs ready for upsert.
+///
+/// Follows the `next` link of each page for up to [`MAX_PAGES`] pages.
+pub fn sync_bitbucket_issues(cfg: &BitbucketConfig) -> Result<Vec<TicketInput>> {
+    let query = OPEN_STATES
+        .iter()
+        .map(|s| format!("state=\"{s}\""))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let first = ureq::get(&api_url(cfg, "/issues"))
+        .query("q", &query)
+        .query("pagelen", &PAGE_LEN.to_string())
+        .url()
+        .to_string();
+
+    let mut tickets = Vec::new();
+    let mut next = Some(first);
+    let mut pages = 0;
+    while let Some(url) = next.take() {
+        let page: serde_json::Value = get(cfg, &url)?.into_json().map_err(|e| {
+            ConductorError::TicketSync(format!("failed to parse Bitbucket issues response: {e}"))
+        })?;
+        for issue in page["values"].as_array().into_iter().flatten() {
+            tickets.push(build_ticket_input(issue)?);
+        }
+        pages += 1;
+        if pages < MAX_PAGES {
+            next = page["next"].as_str().map(|s| s.to_string());
+        }
+    }
+    Ok(tickets)
+}
+
+/// Fetch a single Bitbucket issue by its repository-scoped ID and return its
+/// current state.
+pub fn fetch_bitbucket_issue(cfg: &BitbucketConfig, id: u64) -> Result<TicketInput> {
+    let resp = get(cfg, &api_url(cfg, &format!("/issues/{id}")))?;
+    let issue: serde_json::Value = resp.into_json().map_err(|e| {
+        ConductorError::TicketSync(format!("failed to parse Bitbucket issue response: {e}"))
+    })?;
+    build_ticket_input(&issue)
+}
+
+/// Parse a Bitbucket Cloud remote URL into `(workspace, repo_slug)`.
+///
+/// Handles SSH (`git@bitbucket.org:workspace/repo.git`) and HTTPS
+/// (`https://user@bitbucket.org/workspace/repo.git`).
+pub fn parse_bitbucket_remote(remote_url: &str) -> Option<(String, String)> {
+    let (host, path) = if let Some(rest) = remote_url.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else {
+        let rest = remote_url
+            .strip_prefix("https://")
+            .or_else(|| remote_url.strip_prefix("ssh://git@"))?;
+        rest.split_once('/')?
+    };
+    let host = host.rsplit('@').next().unwrap_or(host);
+    if host != "bitbucket.org" {
+        return None;
+    }
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_matches('/');
+    let (workspace, repo) = path.split_once('/')?;
+    if workspace.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((workspace.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(api_url: &str, token_env: &str, email: Option<&str>) -> BitbucketConfig {
+        BitbucketConfig {
+            workspace: "acme".to_string(),
+            repo_slug: "widget".to_string(),
+            token_env: token_env.to_string(),
+            email: email.map(|e| e.to_string()),
+            api_url: api_url.to_string(),
+        }
+    }
+
+    #[test]
+    fn sync_follows_next_links_and_sends_basic_auth() {
+        std::env::set_var("CONDUCTOR_TEST_BITBUCKET_TOKEN_SYNC", "tok");
+        let mut server = mockito::Server::new();
+        let next = format!("{}/repositories/acme/widget/issues?page=2", server.url());
+        let _p1 = server
+            .mock("GET", "/repositories/acme/widget/issues")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "q".into(),
+                    r#"state="new" OR state="open" OR state="on hold""#.into(),
+                ),
+                mockito::Matcher::UrlEncoded("pagelen".into(), "50".into()),
+            ]))
+            .match_header("Authorization", "Basic bWVAeC5pbzp0b2s=")
+            .with_body(
+                serde_json::json!({
+                    "values": [{
+                        "id": 7, "title": "Crash on save", "state": "open",
+                        "kind": "bug", "priority": "major",
+                        "content": {"raw": "Steps..."},
+                        "component": {"name": "editor"},
+                        "assignee": {"nickname": "alice"},
+                        "milestone": {"name": "v2"},
+                        "links": {"html": {"href": "https://bitbucket.org/acme/widget/issues/7"}}
+                    }],
+                    "next": next,
+                })
+                .to_string(),
+            )
+            .create();
+        let _p2 = server
+            .mock("GET", "/repositories/acme/widget/issues")
+            .match_query(mockito::Matcher::UrlEncoded("page".into(), "2".into()))
+            .with_body(r#"{"values":[{"id":8,"title":"Docs","state":"new"}]}"#)
+            .create();
+
+        let cfg = config(
+            &server.url(),
+            "CONDUCTOR_TEST_BITBUCKET_TOKEN_SYNC",
+            Some("me@x.io"),
+        );
+        let tickets = sync_bitbucket_issues(&cfg).unwrap();
+        assert_eq!(tickets.len(), 2);
+        let t = &tickets[0];
+        assert_eq!(t.source_type, "bitbucket");
+        assert_eq!(t.source_id, "7");
+        assert_eq!(t.state, "open");
+        assert_eq!(t.body, "Steps...");
+        assert_eq!(t.labels, vec!["bug", "editor"]);
+        assert_eq!(t.priority.as_deref(), Some("major"));
+        assert_eq!(t.assignee.as_deref(), Some("alice"));
+        assert_eq!(t.sprint.as_deref(), Some("v2"));
+        assert_eq!(t.url, "https://bitbucket.org/acme/widget/issues/7");
+        assert_eq!(tickets[1].source_id, "8");
+        assert!(tickets[1].labels.is_empty());
+    }
+
+    #[test]
+    fn fetch_one_maps_resolved_state_and_sends_bearer_token() {
+        std::env::set_var("CONDUCTOR_TEST_BITBUCKET_TOKEN_FETCH", "repo-tok");
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("GET", "/repositories/acme/widget/issues/7")
+            .match_header("Authorization", "Bearer repo-tok")
+            .with_body(r#"{"id":7,"title":"Crash","state":"resolved"}"#)
+            .create();
+        let cfg = config(&server.url(), "CONDUCTOR_TEST_BITBUCKET_TOKEN_FETCH", None);
+        let t = fetch_bitbucket_issue(&cfg, 7).unwrap();
+        assert_eq!(t.state, "closed");
+    }
+
+    #[test]
+    fn not_found_is_reported_as_ticket_sync_error() {
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("GET", "/repositories/acme/widget/issues/9")
+            .with_status(404)
+            .create();
+        let cfg = config(&server.url(), "CONDUCTOR_TEST_BITBUCKET_TOKEN_UNSET", None);
+        let err = fetch_bitbucket_issue(&cfg, 9)
+            .err()
+            .expect("expected error");
+        assert!(
+            matches!(&err, ConductorError::TicketSync(msg) if msg.contains("not found")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn parse_bitbucket_remote_formats() {
+        let expected = Some(("acme".to_string(), "widget".to_string()));
+        assert_eq!(
+            parse_bitbucket_remote("git@bitbucket.org:acme/widget.git"),
+            expected
+        );
+        assert_eq!(
+            parse_bitbucket_remote("https://alice@bitbucket.org/acme/widget.git"),
+            expected
+        );
+        assert_eq!(
+            parse_bitbucket_remote("https://bitbucket.org/acme/widget"),
+            expected
+        );
+        assert_eq!(
+            parse_bitbucket_remote("git@github.com:acme/widget.git"),
+            None
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Changelog entries for merged worktrees.
+//!
+//! A repo opts in with a `[changelog]` section in `.conductor/config.toml`.
+//! When a worktree's PR is found merged, an entry built from the ticket title,
+//! the PR link and the branch prefix is either written to `changelog.d/` as a
+//! fragment or added under `## Unreleased` in `CHANGELOG.md`. The change is
+//! made in a scratch checkout of the base branch and delivered as a direct
+//! commit or a follow-up PR.
+//!
+//! ```toml
+//! [changelog]
+//! mode = "fragment"      # or "file"
+//! dir = "changelog.d"    # fragment directory
+//! file = "CHANGELOG.md"  # file appended to in "file" mode
+//! delivery = "pr"        # or "commit" to push straight to the base branch
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+use crate::github;
+
+/// Heading entries are added under in [`ChangelogMode::File`] mode.
+const UNRELEASED_HEADING: &str = "## Unreleased";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogMode {
+    /// One file per change in [`ChangelogConfig::dir`].
+    #[default]
+    Fragment,
+    /// A line under `## Unreleased` in [`ChangelogConfig::file`].
+    File,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogDelivery {
+    /// Push the branch `changelog/<slug>` and open a PR against the base branch.
+    #[default]
+    Pr,
+    /// Push the commit straight to the base branch.
+    Commit,
+}
+
+/// The `[changelog]` section of `.conductor/config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogConfig {
+    #[serde(default)]
+    pub mode: ChangelogMode,
+    #[serde(default = "default_fragment_dir")]
+    pub dir: String,
+    #[serde(default = "default_changelog_file")]
+    pub file: String,
+    #[serde(default)]
+    pub delivery: ChangelogDelivery,
+}
+
+fn default_fragment_dir() -> String {
+    "changelog.d".to_string()
+}
+
+fn default_changelog_file() -> String {
+    "CHANGELOG.md".to_string()
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            mode: ChangelogMode::default(),
+            dir: default_fragment_dir(),
+            file: default_changelog_file(),
+            delivery: ChangelogDelivery::default(),
+        }
+    }
+}
+
+/// One merged worktree's changelog entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    /// Worktree slug; names the fragment file and follow-up branch.
+    pub slug: String,
+    /// Change type from the branch prefix, e.g. `feature` or `fix`.
+    pub kind: &'static str,
+    pub title: String,
+    /// Ticket reference as shown to readers, e.g. `#42` or `PROJ-42`.
+    pub ticket: Option<String>,
+    pub pr_url: Option<String>,
+}
+
+impl ChangelogEntry {
+    pub fn new(slug: &str, branch: &str, title: &str) -> Self {
+        Self {
+            slug: slug.to_string(),
+            kind: change_kind(branch),
+            title: title.trim().to_string(),
+            ticket: None,
+            pr_url: None,
+        }
+    }
+
+    /// The entry as a markdown list item.
+    pub fn render(&self) -> String {
+        let mut refs = Vec::new();
+        if let Some(ref url) = self.pr_url {
+            let label = github::parse_pr_number_from_url(url)
+                .map(|n| format!("#{n}"))
+                .unwrap_or_else(|| "PR".to_string());
+            refs.push(format!("[{label}]({url})"));
+        }
+        if let Some(ref ticket) = self.ticket {
+            if !refs.iter().any(|r| r.starts_with(&format!("[{ticket}]"))) {
+                refs.push(ticket.clone());
+            }
+        }
+        let refs = if refs.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", refs.join(", "))
+        };
+        format!("- **{}:** {}{refs}", self.kind, self.title)
+    }
+}
+
+/// Change type for a branch, from its conventional prefix (`feat/…` →
+/// `feature`). Branches without a known prefix are a plain `change`.
+pub fn change_kind(branch: &str) -> &'static str {
+    let prefix = branch.split_once('/').map(|(p, _)| p).unwrap_or_default();
+    match prefix {
+        "feat" | "feature" => "feature",
+        "fix" | "bug" | "hotfix" => "fix",
+        "docs" => "docs",
+        "perf" => "perf",
+        "refactor" => "refactor",
+        "chore" => "chore",
+        "release" => "release",
+        _ => "change",
+    }
+}
+
+/// Write `entry` into the checkout at `root` as `config` says. Returns the
+/// repo-relative path that changed.
+pub fn write_entry(
+    root: &Path,
+    config: &ChangelogConfig,
+    entry: &ChangelogEntry,
+) -> Result<PathBuf> {
+    match config.mode {
+        ChangelogMode::Fragment => {
+            let rel = Path::new(&config.dir).join(format!("{}.{}.md", entry.slug, entry.kind));
+            let path = root.join(&rel);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, format!("{}\n", entry.render()))?;
+            Ok(rel)
+        }
+        ChangelogMode::File => {
+            let rel = PathBuf::from(&config.file);
+            let path = root.join(&rel);
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(s) => s,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+            std::fs::write(&path, insert_unreleased(&existing, &entry.render()))?;
+            Ok(rel)
+        }
+    }
+}
+
+/// Add `line` at the top of the `## Unreleased` section, creating the section
+/// below the document title when it is missing.
+fn insert_unreleased(changelog: &str, line: &str) -> String {
+    let mut lines: Vec<&str> = changelog.lines().collect();
+    match lines.iter().position(|l| l.trim() == UNRELEASED_HEADING) {
+        Some(idx) => {
+            // Keep a blank line between the heading and the first entry.
+            if !lines.get(idx + 1).is_some_and(|l| l.trim().is_empty()) {
+                lines.insert(idx + 1, "");
+            }
+            lines.insert(idx + 2, line);
+        }
+        None => {
+            let at = match lines.first() {
+                Some(first) if first.starts_with("# ") => {
+                    let mut at = 1;
+                    while lines.get(at).is_some_and(|l| !l.starts_with("## ")) {
+                        at += 1;
+                    }
+                    at
+                }
+                _ => 0,
+            };
+            let mut section = vec![UNRELEASED_HEADING, "", line, ""];
+            if at > 0 && !lines[at - 1].trim().is_empty() {
+                section.insert(0, "");
+            }
+            lines.splice(at..at, section);
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Where a published entry ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangelogOutcome {
+    /// Committed and pushed to the base branch.
+    Committed { path: PathBuf },
+    /// Opened as a follow-up PR.
+    PullRequest { url: String },
+}
+
+/// Commit `entry` on top of `origin/<base_branch>` in a scratch worktree of
+/// the repo at `repo_path` and deliver it per `config.delivery`.
+pub fn publish_entry(
+    repo_path: &str,
+    remote_url: &str,
+    base_branch: &str,
+    config: &ChangelogConfig,
+    entry: &ChangelogEntry,
+) -> Result<ChangelogOutcome> {
+    check_output(git_in(repo_path).args(["fetch", "origin", base_branch]))?;
+
+    let scratch = tempfile::Builder::new()
+        .prefix("conductor-changelog-")
+        .tempdir()?;
+    let checkout = scratch.path().join("checkout");
+    let checkout_str = checkout.to_string_lossy().to_string();
+    check_output(git_in(repo_path).args([
+        "worktree",
+        "add",
+        "--detach",
+        &checkout_str,
+        &format!("origin/{base_branch}"),
+    ]))?;
+
+    let result = commit_and_deliver(&checkout_str, remote_url, base_branch, config, entry);
+
+    let _ = crate::command::output(git_in(repo_path).args([
+        "worktree",
+        "remove",
+        "--force",
+        &checkout_str,
+    ]));
+    result
+}
+
+fn commit_and_deliver(
+    checkout: &str,
+    remote_url: &str,
+    base_branch: &str,
+    config: &ChangelogConfig,
+    entry: &ChangelogEntry,
+) -> Result<ChangelogOutcome> {
+    let rel = write_entry(Path::new(checkout), config, entry)?;
+    let rel_str = rel.to_string_lossy().to_string();
+    let message = format!("changelog: {}", entry.title);
+    check_output(git_in(checkout).args(["add", "--", &rel_str]))?;
+    check_output(git_in(checkout).args(["commit", "-m", &message]))?;
+
+    match config.delivery {
+        ChangelogDelivery::Commit => {
+            check_output(git_in(checkout).args([
+                "push",
+                "origin",
+                &format!("HEAD:refs/heads/{base_branch}"),
+            ]))?;
+            Ok(ChangelogOutcome::Committed { path: rel })
+        }
+        ChangelogDelivery::Pr => {
+            let (owner, repo) = github::parse_github_remote(remote_url).ok_or_else(|| {
+                ConductorError::InvalidInput(format!(
+                    "changelog PRs need a GitHub remote, got {remote_url}"
+                ))
+            })?;
+            let branch = format!("changelog/{}", entry.slug);
+            check_output(git_in(checkout).args([
+                "push",
+                "--force",
+                "origin",
+                &format!("HEAD:refs/heads/{branch}"),
+            ]))?;
+            let body = format!(
+                "Changelog entry for a merged change.\n\n{}\n",
+                entry.render()
+            );
+            let url = github::create_pr_with_body(
+                &owner,
+                &repo,
+                &branch,
+                &message,
+                &body,
+                Some(base_branch),
+            )?;
+            Ok(ChangelogOutcome::PullRequest { url })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn entry() -> ChangelogEntry {
+        ChangelogEntry {
+            ticket: Some("#42".into()),
+            pr_url: Some("https://github.com/acme/app/pull/7".into()),
+            ..ChangelogEntry::new("fix-login", "fix/login", "Fix login crash ")
+        }
+    }
+
+    #[test]
+    fn kind_follows_branch_prefix() {
+        assert_eq!(change_kind("feat/smart-playlists"), "feature");
+        assert_eq!(change_kind("fix/login"), "fix");
+        assert_eq!(change_kind("docs/readme"), "docs");
+        assert_eq!(change_kind("my-branch"), "change");
+    }
+
+    #[test]
+    fn render_links_pr_and_ticket() {
+        assert_eq!(
+            entry().render(),
+            "- **fix:** Fix login crash ([#7](https://github.com/acme/app/pull/7), #42)"
+        );
+        let bare = ChangelogEntry::new("x", "chore/x", "Bump deps");
+        assert_eq!(bare.render(), "- **chore:** Bump deps");
+    }
+
+    #[test]
+    fn fragment_mode_writes_one_file_per_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let rel = write_entry(dir.path(), &ChangelogConfig::default(), &entry()).unwrap();
+        assert_eq!(rel, Path::new("changelog.d/fix-login.fix.md"));
+        let content = std::fs::read_to_string(dir.path().join(&rel)).unwrap();
+        assert!(content.starts_with("- **fix:** Fix login crash"));
+    }
+
+    #[test]
+    fn file_mode_adds_entries_under_unreleased() {
+        let config = ChangelogConfig {
+            mode: ChangelogMode::File,
+            ..Default::default()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        std::fs::write(
+            &path,
+            "# Changelog\n\nAll notable changes.\n\n## 1.0.0\n\n- First\n",
+        )
+        .unwrap();
+
+        write_entry(dir.path(), &config, &entry()).unwrap();
+        let second = ChangelogEntry::new("feat-a", "feat/a", "Add A");
+        write_entry(dir.path(), &config, &second).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Changelog\n\nAll notable changes.\n\n## Unreleased\n\n\
+             - **feature:** Add A\n\
+             - **fix:** Fix login crash ([#7](https://github.com/acme/app/pull/7), #42)\n\n\
+             ## 1.0.0\n\n- First\n"
+        );
+    }
+
+    #[test]
+    fn file_mode_creates_missing_changelog() {
+        let config = ChangelogConfig {
+            mode: ChangelogMode::File,
+            ..Default::default()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        write_entry(
+            dir.path(),
+            &config,
+            &ChangelogEntry::new("a", "feat/a", "Add A"),
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap(),
+            "## Unreleased\n\n- **feature:** Add A\n\n"
+        );
+    }
+
+    fn git(args: &[&str], dir: &Path) -> String {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    }
+
+    #[test]
+    fn publish_commit_pushes_to_base_branch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let remote = tmp.path().join("remote.git");
+        let local = tmp.path().join("local");
+        std::fs::create_dir_all(&remote).unwrap();
+        git(&["init", "--bare", "-b", "main"], &remote);
+        git(
+            &["clone", &remote.to_string_lossy(), &local.to_string_lossy()],
+            tmp.path(),
+        );
+        git(&["config", "user.email", "test@test.com"], &local);
+        git(&["config", "user.name", "Test"], &local);
+        git(&["checkout", "-b", "main"], &local);
+        std::fs::write(local.join("README.md"), "hi").unwrap();
+        git(&["add", "README.md"], &local);
+        git(&["commit", "-m", "initial"], &local);
+        git(&["push", "-u", "origin", "main"], &local);
+
+        let config = ChangelogConfig {
+            delivery: ChangelogDelivery::Commit,
+            ..Default::default()
+        };
+        let outcome = publish_entry(
+            &local.to_string_lossy(),
+            "https://github.com/acme/app.git",
+            "main",
+            &config,
+            &entry(),
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            ChangelogOutcome::Committed {
+                path: PathBuf::from("changelog.d/fix-login.fix.md")
+            }
+        );
+
+        let log = git(&["log", "--format=%s", "main"], &remote);
+        assert!(log.starts_with("changelog: Fix login crash"), "{log}");
+        let worktrees = git(&["worktree", "list"], &local);
+        assert_eq!(worktrees.lines().count(), 1, "scratch checkout is removed");
+    }
+}
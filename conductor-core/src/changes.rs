@@ -0,0 +1,149 @@
+//! Cross-process change feed. Triggers on the main tables append to
+//! `change_log` whenever a row is created, deleted or changes status, whatever
+//! process wrote it. A consumer remembers the last [`Change::id`] it read and
+//! polls [`since`] to learn what the TUI, CLI, daemon or web server changed.
+//!
+//! Rows are only kept for [`RETENTION_SECS`]; consumers poll far more often
+//! than that.
+
+use rusqlite::{named_params, Connection, Row};
+use serde::Serialize;
+
+use crate::error::Result;
+
+/// How long `change_log` rows are kept before [`prune`] drops them.
+pub const RETENTION_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// One row of `change_log`. Which context ids are set depends on `table`:
+/// worktrees carry their repo, agent and workflow runs their worktree or repo,
+/// workflow steps and feedback requests their run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub id: i64,
+    pub table: String,
+    pub op: ChangeOp,
+    pub row_id: String,
+    pub repo_id: Option<String>,
+    pub worktree_id: Option<String>,
+    pub run_id: Option<String>,
+    /// The row's status (or ticket state) after the change.
+    pub status: Option<String>,
+}
+
+impl Change {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let op: String = row.get("op")?;
+        Ok(Self {
+            id: row.get("id")?,
+            table: row.get("table_name")?,
+            op: match op.as_str() {
+                "insert" => ChangeOp::Insert,
+                "delete" => ChangeOp::Delete,
+                _ => ChangeOp::Update,
+            },
+            row_id: row.get("row_id")?,
+            repo_id: row.get("repo_id")?,
+            worktree_id: row.get("worktree_id")?,
+            run_id: row.get("run_id")?,
+            status: row.get("status")?,
+        })
+    }
+}
+
+/// Id of the newest change, or 0 when the log is empty. A consumer starts
+/// from here so it does not replay changes made before it started.
+pub fn latest_id(conn: &Connection) -> Result<i64> {
+    Ok(
+        conn.query_row("SELECT COALESCE(MAX(id), 0) FROM change_log", [], |r| {
+            r.get(0)
+        })?,
+    )
+}
+
+/// Up to `limit` changes after `after_id`, oldest first.
+pub fn since(conn: &Connection, after_id: i64, limit: usize) -> Result<Vec<Change>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id, table_name, op, row_id, repo_id, worktree_id, run_id, status \
+         FROM change_log WHERE id > :after ORDER BY id LIMIT :limit",
+    )?;
+    let rows = stmt.query_map(
+        named_params! { ":after": after_id, ":limit": limit as i64 },
+        Change::from_row,
+    )?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Drop changes older than [`RETENTION_SECS`]. Returns how many were removed.
+pub fn prune(conn: &Connection) -> Result<usize> {
+    Ok(conn.execute(
+        "DELETE FROM change_log \
+         WHERE changed_at < strftime('%Y-%m-%dT%H:%M:%fZ', 'now', :age)",
+        named_params! { ":age": format!("-{RETENTION_SECS} seconds") },
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{create_test_conn, insert_test_repo, insert_test_worktree};
+
+    #[test]
+    fn triggers_log_inserts_status_changes_and_deletes() {
+        let conn = create_test_conn();
+        let start = latest_id(&conn).unwrap();
+        insert_test_repo(&conn, "r1", "repo", "/tmp/repo");
+        insert_test_worktree(&conn, "w1", "r1", "feat-x", "/tmp/repo/feat-x");
+        // Rewriting a column without changing status is not logged.
+        conn.execute("UPDATE worktrees SET status = 'active' WHERE id = 'w1'", [])
+            .unwrap();
+        conn.execute("UPDATE worktrees SET status = 'merged' WHERE id = 'w1'", [])
+            .unwrap();
+        conn.execute("DELETE FROM worktrees WHERE id = 'w1'", [])
+            .unwrap();
+
+        let changes = since(&conn, start, 100).unwrap();
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.table.as_str(), c.op, c.status.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("repos", ChangeOp::Insert, None),
+                ("worktrees", ChangeOp::Insert, Some("active")),
+                ("worktrees", ChangeOp::Update, Some("merged")),
+                ("worktrees", ChangeOp::Delete, None),
+            ]
+        );
+        assert!(changes[1..]
+            .iter()
+            .all(|c| c.repo_id.as_deref() == Some("r1")));
+        assert_eq!(latest_id(&conn).unwrap(), changes[3].id);
+        assert!(since(&conn, changes[3].id, 100).unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_keeps_recent_changes() {
+        let conn = create_test_conn();
+        insert_test_repo(&conn, "r1", "repo", "/tmp/repo");
+        conn.execute(
+            "UPDATE change_log SET changed_at = '2020-01-01T00:00:00.000Z'",
+            [],
+        )
+        .unwrap();
+        insert_test_repo(&conn, "r2", "repo2", "/tmp/repo2");
+
+        assert_eq!(prune(&conn).unwrap(), 1);
+        let left = since(&conn, 0, 100).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].row_id, "r2");
+    }
+}
@@ -0,0 +1,412 @@
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::worktree::WorktreeManager;
+
+use super::types::{Changeset, ChangesetMember, ChangesetWithMembers};
+
+const MEMBER_QUERY: &str = "SELECT cw.worktree_id, cw.position, cw.pr_url, \
+            w.slug, w.branch, w.base_branch, w.status, \
+            r.slug AS repo_slug, r.remote_url \
+     FROM changeset_worktrees cw \
+     JOIN worktrees w ON w.id = cw.worktree_id \
+     JOIN repos r ON r.id = w.repo_id \
+     WHERE cw.changeset_id = :id \
+     ORDER BY cw.position";
+
+pub struct ChangesetManager<'a> {
+    conn: &'a Connection,
+    config: &'a Config,
+}
+
+impl<'a> ChangesetManager<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self { conn, config }
+    }
+
+    /// Create a changeset from `(repo_slug, worktree_slug)` pairs, in PR order.
+    pub fn create(
+        &self,
+        title: &str,
+        members: &[(String, String)],
+    ) -> Result<ChangesetWithMembers> {
+        if title.trim().is_empty() {
+            return Err(ConductorError::InvalidInput(
+                "changeset title must not be empty".into(),
+            ));
+        }
+        let worktree_ids = members
+            .iter()
+            .map(|(repo, wt)| self.resolve_worktree(repo, wt))
+            .collect::<Result<Vec<_>>>()?;
+
+        let changeset = Changeset {
+            id: crate::new_id(),
+            title: title.trim().to_string(),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO changesets (id, title, created_at) VALUES (:id, :title, :created_at)",
+            named_params! {
+                ":id": changeset.id,
+                ":title": changeset.title,
+                ":created_at": changeset.created_at,
+            },
+        )?;
+        for (i, worktree_id) in worktree_ids.iter().enumerate() {
+            insert_member(&tx, &changeset.id, worktree_id, i as i64 + 1)?;
+        }
+        tx.commit()?;
+
+        self.get(&changeset.id)
+    }
+
+    /// Append a worktree to an existing changeset.
+    pub fn add(&self, id: &str, repo_slug: &str, worktree_slug: &str) -> Result<()> {
+        self.get_changeset(id)?;
+        let worktree_id = self.resolve_worktree(repo_slug, worktree_slug)?;
+        let next: i64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(position), 0) + 1 FROM changeset_worktrees WHERE changeset_id = :id",
+            named_params! { ":id": id },
+            |row| row.get(0),
+        )?;
+        insert_member(self.conn, id, &worktree_id, next)
+    }
+
+    /// List all changesets, newest first.
+    pub fn list(&self) -> Result<Vec<Changeset>> {
+        query_collect(
+            self.conn,
+            "SELECT id, title, created_at FROM changesets ORDER BY created_at DESC",
+            [],
+            row_to_changeset,
+        )
+    }
+
+    /// Fetch a changeset with its members in PR order.
+    pub fn get(&self, id: &str) -> Result<ChangesetWithMembers> {
+        let changeset = self.get_changeset(id)?;
+        let mut members = query_collect(
+            self.conn,
+            MEMBER_QUERY,
+            named_params! { ":id": id },
+            row_to_member,
+        )?;
+        // The repo default branch comes from config, not the DB row.
+        let repo_mgr = RepoManager::new(self.conn, self.config);
+        for m in members.iter_mut().filter(|m| m.base_branch.is_empty()) {
+            m.base_branch = repo_mgr.get_by_slug(&m.repo_slug)?.default_branch;
+        }
+        Ok(ChangesetWithMembers { changeset, members })
+    }
+
+    /// Push every member branch and open its PR, then rewrite each PR
+    /// description so it links to the other PRs in the changeset.
+    ///
+    /// Members that already have a recorded PR are not pushed or reopened, so a
+    /// run interrupted by a failure can simply be retried.
+    pub fn open_prs(&self, id: &str) -> Result<ChangesetWithMembers> {
+        let wt_mgr = WorktreeManager::new(self.conn, self.config);
+        self.open_prs_with(
+            id,
+            |m| {
+                wt_mgr.push(&m.repo_slug, &m.worktree_slug, Default::default())?;
+                Ok(())
+            },
+            |m, title, body| {
+                let (owner, repo) = github_remote(m)?;
+                crate::github::create_pr_with_body(
+                    &owner,
+                    &repo,
+                    &m.branch,
+                    title,
+                    body,
+                    Some(&m.base_branch),
+                )
+            },
+            |m, body| {
+                let (owner, repo) = github_remote(m)?;
+                let url = m.pr_url.as_deref().unwrap_or_default();
+                let number = crate::github::parse_pr_number_from_url(url).ok_or_else(|| {
+                    ConductorError::InvalidInput(format!("cannot parse PR number from '{url}'"))
+                })?;
+                crate::github::edit_pr_body(&owner, &repo, number, body)
+            },
+        )
+    }
+
+    pub(crate) fn open_prs_with(
+        &self,
+        id: &str,
+        push: impl Fn(&ChangesetMember) -> Result<()>,
+        open_pr: impl Fn(&ChangesetMember, &str, &str) -> Result<String>,
+        edit_body: impl Fn(&ChangesetMember, &str) -> Result<()>,
+    ) -> Result<ChangesetWithMembers> {
+        let mut cs = self.get(id)?;
+        if cs.members.is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "changeset {id} has no worktrees"
+            )));
+        }
+        let inactive: Vec<String> = cs
+            .members
+            .iter()
+            .filter(|m| m.pr_url.is_none() && !m.status.is_live())
+            .map(|m| format!("{}/{} ({})", m.repo_slug, m.worktree_slug, m.status))
+            .collect();
+        if !inactive.is_empty() {
+            return Err(ConductorError::InvalidInput(format!(
+                "changeset {id} has worktrees that are no longer active: {}",
+                inactive.join(", ")
+            )));
+        }
+
+        let no_refs = vec![None; cs.members.len()];
+        for i in 0..cs.members.len() {
+            if cs.members[i].pr_url.is_some() {
+                continue;
+            }
+            let member = &cs.members[i];
+            push(member)?;
+            let body = pr_body(&cs.changeset.title, i, &no_refs);
+            let url = open_pr(member, &cs.changeset.title, &body)?;
+            // Persist immediately so a later failure does not lose this PR.
+            self.conn.execute(
+                "UPDATE changeset_worktrees SET pr_url = :url \
+                 WHERE changeset_id = :id AND worktree_id = :worktree_id",
+                named_params! { ":url": url, ":id": id, ":worktree_id": member.worktree_id },
+            )?;
+            cs.members[i].pr_url = Some(url);
+        }
+
+        let refs: Vec<Option<String>> = cs.members.iter().map(|m| m.pr_ref()).collect();
+        for (i, member) in cs.members.iter().enumerate() {
+            edit_body(member, &pr_body(&cs.changeset.title, i, &refs))?;
+        }
+
+        Ok(cs)
+    }
+
+    fn get_changeset(&self, id: &str) -> Result<Changeset> {
+        self.conn
+            .query_row(
+                "SELECT id, title, created_at FROM changesets WHERE id = :id",
+                named_params! { ":id": id },
+                row_to_changeset,
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::ChangesetNotFound { id: id.to_string() })
+    }
+
+    fn resolve_worktree(&self, repo_slug: &str, worktree_slug: &str) -> Result<String> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        let wt =
+            WorktreeManager::new(self.conn, self.config).get_by_slug(&repo.id, worktree_slug)?;
+        if !wt.is_active() {
+            return Err(ConductorError::InvalidInput(format!(
+                "worktree '{repo_slug}/{worktree_slug}' is not active (status: {})",
+                wt.status
+            )));
+        }
+        Ok(wt.id)
+    }
+}
+
+/// Build the description for the PR at `index`, cross-linking the other PRs.
+///
+/// `refs` holds one entry per member (`org/repo#N` once known); unknown refs
+/// are skipped. The first line reads e.g. `Part 1/2: see org/web#45`.
+pub fn pr_body(title: &str, index: usize, refs: &[Option<String>]) -> String {
+    let total = refs.len();
+    let others: Vec<&str> = refs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .filter_map(|(_, r)| r.as_deref())
+        .collect();
+    let mut body = format!("Part {}/{total}", index + 1);
+    if !others.is_empty() {
+        body.push_str(&format!(": see {}", others.join(", ")));
+    }
+    body.push_str(&format!(
+        "\n\nThis PR is one of {total} in the changeset \"{title}\"."
+    ));
+    if refs.iter().any(Option::is_some) {
+        body.push('\n');
+        for (i, r) in refs.iter().enumerate() {
+            let label = r.as_deref().unwrap_or("(not yet opened)");
+            let this = if i == index { " (this PR)" } else { "" };
+            body.push_str(&format!("\n{}. {label}{this}", i + 1));
+        }
+    }
+    body
+}
+
+fn insert_member(conn: &Connection, id: &str, worktree_id: &str, position: i64) -> Result<()> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO changeset_worktrees (changeset_id, worktree_id, position) \
+         VALUES (:id, :worktree_id, :position)",
+        named_params! { ":id": id, ":worktree_id": worktree_id, ":position": position },
+    )?;
+    if inserted == 0 {
+        return Err(ConductorError::InvalidInput(format!(
+            "worktree {worktree_id} is already in changeset {id}"
+        )));
+    }
+    Ok(())
+}
+
+fn github_remote(member: &ChangesetMember) -> Result<(String, String)> {
+    crate::github::parse_github_remote(&member.remote_url).ok_or_else(|| {
+        ConductorError::InvalidInput(format!(
+            "repo '{}' does not have a GitHub remote: {}",
+            member.repo_slug, member.remote_url
+        ))
+    })
+}
+
+fn row_to_changeset(row: &rusqlite::Row) -> rusqlite::Result<Changeset> {
+    Ok(Changeset {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+fn row_to_member(row: &rusqlite::Row) -> rusqlite::Result<ChangesetMember> {
+    let base: Option<String> = row.get("base_branch")?;
+    Ok(ChangesetMember {
+        worktree_id: row.get("worktree_id")?,
+        repo_slug: row.get("repo_slug")?,
+        worktree_slug: row.get("slug")?,
+        branch: row.get("branch")?,
+        base_branch: base.unwrap_or_default(),
+        remote_url: row.get("remote_url")?,
+        status: row.get("status")?,
+        position: row.get("position")?,
+        pr_url: row.get("pr_url")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::test_helpers::{insert_test_repo, insert_test_worktree};
+
+    fn setup() -> (Connection, Config) {
+        let conn = crate::test_helpers::setup_db();
+        conn.execute(
+            "UPDATE repos SET remote_url = 'https://github.com/org/api.git' WHERE id = 'r1'",
+            [],
+        )
+        .unwrap();
+        insert_test_repo(&conn, "r2", "web", "/tmp/web");
+        conn.execute(
+            "UPDATE repos SET remote_url = 'https://github.com/org/web.git' WHERE id = 'r2'",
+            [],
+        )
+        .unwrap();
+        insert_test_worktree(&conn, "w2", "r2", "feat-web", "/tmp/ws/feat-web");
+        (conn, Config::default())
+    }
+
+    fn pair(repo: &str, wt: &str) -> (String, String) {
+        (repo.to_string(), wt.to_string())
+    }
+
+    #[test]
+    fn create_orders_members_and_rejects_duplicates() {
+        let (conn, config) = setup();
+        let mgr = ChangesetManager::new(&conn, &config);
+        let cs = mgr
+            .create(
+                "Rename user field",
+                &[pair("test-repo", "feat-test"), pair("web", "feat-web")],
+            )
+            .unwrap();
+        let slugs: Vec<&str> = cs.members.iter().map(|m| m.repo_slug.as_str()).collect();
+        assert_eq!(slugs, vec!["test-repo", "web"]);
+        assert_eq!(cs.members[1].position, 2);
+        assert_eq!(cs.members[0].base_branch, "main");
+
+        let err = mgr.add(&cs.changeset.id, "web", "feat-web").unwrap_err();
+        assert!(matches!(err, ConductorError::InvalidInput(_)));
+        assert!(matches!(
+            mgr.get("missing").unwrap_err(),
+            ConductorError::ChangesetNotFound { .. }
+        ));
+    }
+
+    #[test]
+    fn open_prs_records_urls_and_cross_links_descriptions() {
+        let (conn, config) = setup();
+        let mgr = ChangesetManager::new(&conn, &config);
+        let cs = mgr
+            .create(
+                "Rename user field",
+                &[pair("test-repo", "feat-test"), pair("web", "feat-web")],
+            )
+            .unwrap();
+
+        let pushed = RefCell::new(Vec::new());
+        let edits = RefCell::new(Vec::new());
+        let result = mgr
+            .open_prs_with(
+                &cs.changeset.id,
+                |m| {
+                    pushed.borrow_mut().push(m.worktree_slug.clone());
+                    Ok(())
+                },
+                |m, _title, body| {
+                    assert!(body.starts_with("Part "), "provisional body: {body}");
+                    let n = if m.repo_slug == "web" { 45 } else { 12 };
+                    let repo = if m.repo_slug == "web" { "web" } else { "api" };
+                    Ok(format!("https://github.com/org/{repo}/pull/{n}"))
+                },
+                |m, body| {
+                    edits
+                        .borrow_mut()
+                        .push((m.repo_slug.clone(), body.to_string()));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        assert_eq!(*pushed.borrow(), vec!["feat-test", "feat-web"]);
+        let edits = edits.into_inner();
+        assert!(edits[0].1.starts_with("Part 1/2: see org/web#45"));
+        assert!(edits[1].1.starts_with("Part 2/2: see org/api#12"));
+        assert!(edits[1].1.contains("2. org/web#45 (this PR)"));
+
+        let stored = mgr.get(&result.changeset.id).unwrap();
+        assert_eq!(
+            stored.members[1].pr_url.as_deref(),
+            Some("https://github.com/org/web/pull/45")
+        );
+
+        // Re-running does not push or open PRs again.
+        mgr.open_prs_with(
+            &cs.changeset.id,
+            |_| panic!("must not push again"),
+            |_, _, _| panic!("must not reopen"),
+            |_, _| Ok(()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn pr_body_without_known_refs_has_no_links() {
+        let body = pr_body("T", 0, &[None, None]);
+        assert_eq!(
+            body,
+            "Part 1/2\n\nThis PR is one of 2 in the changeset \"T\"."
+        );
+    }
+}
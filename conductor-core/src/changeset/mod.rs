@@ -0,0 +1,8 @@
+//! Changesets: a named group of worktrees — usually in different repos — that
+//! ship together, e.g. an API change plus the web client that consumes it.
+
+pub mod manager;
+pub mod types;
+
+pub use manager::{pr_body, ChangesetManager};
+pub use types::{Changeset, ChangesetMember, ChangesetWithMembers};
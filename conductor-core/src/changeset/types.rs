@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::worktree::WorktreeStatus;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Changeset {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+}
+
+/// A worktree that belongs to a changeset, joined with its repo details.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangesetMember {
+    pub worktree_id: String,
+    pub repo_slug: String,
+    pub worktree_slug: String,
+    pub branch: String,
+    /// The worktree's base branch, falling back to the repo default.
+    pub base_branch: String,
+    pub remote_url: String,
+    pub status: WorktreeStatus,
+    /// 1-based position within the changeset; PRs are numbered in this order.
+    pub position: i64,
+    pub pr_url: Option<String>,
+}
+
+impl ChangesetMember {
+    /// Short GitHub reference for this member's PR, e.g. `org/web#45`.
+    pub fn pr_ref(&self) -> Option<String> {
+        let url = self.pr_url.as_deref()?;
+        let number = crate::github::parse_pr_number_from_url(url)?;
+        let (owner, repo) = crate::github::parse_github_remote(&self.remote_url)?;
+        Some(format!("{owner}/{repo}#{number}"))
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangesetWithMembers {
+    #[serde(flatten)]
+    pub changeset: Changeset,
+    pub members: Vec<ChangesetMember>,
+}
@@ -0,0 +1,312 @@
+//! Running external commands under a deadline.
+//!
+//! `git fetch`, `git clone`, dependency installs and `gh` calls can hang on a
+//! dead network or a stuck credential helper, holding whichever TUI action or
+//! web handler started them. Everything here runs the child in its own process
+//! group with stdin closed and stdout/stderr captured, and kills the whole
+//! group once the deadline passes, so grandchildren (`ssh` under git, `npm`
+//! under `sh -c`) go with it.
+//!
+//! Deadlines come from the `[timeouts]` config section, installed by
+//! [`crate::config::load_config`].
+
+use std::io::{self, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::RwLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::TimeoutsConfig;
+use crate::error::{ConductorError, Result, SubprocessFailure};
+
+static TIMEOUTS: RwLock<TimeoutsConfig> = RwLock::new(TimeoutsConfig::DEFAULT);
+
+/// Programs whose every call goes over the network.
+const NETWORK_PROGRAMS: &[&str] = &["gh", "claude", "acli", "sdlc"];
+
+/// Git subcommands that talk to a remote and get the network deadline.
+const NETWORK_GIT_SUBCOMMANDS: &[&str] = &["clone", "fetch", "pull", "push", "ls-remote"];
+
+/// Replace the deadlines used by every command started after this call.
+pub fn set_timeouts(timeouts: &TimeoutsConfig) {
+    *TIMEOUTS.write().unwrap_or_else(|e| e.into_inner()) = timeouts.clone();
+}
+
+/// The deadlines currently in effect.
+pub fn timeouts() -> TimeoutsConfig {
+    TIMEOUTS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// The deadline for `cmd` when the caller does not pick one: the network
+/// deadline for CLIs that call an API and for remote git subcommands, the
+/// local one otherwise.
+pub fn default_timeout(cmd: &Command) -> Option<Duration> {
+    let timeouts = timeouts();
+    let program = cmd.get_program().to_string_lossy();
+    let program = program.rsplit('/').next().unwrap_or_default();
+    let remote = match program {
+        p if NETWORK_PROGRAMS.contains(&p) => true,
+        "git" => {
+            git_subcommand(cmd).is_some_and(|sub| NETWORK_GIT_SUBCOMMANDS.contains(&sub.as_str()))
+        }
+        _ => false,
+    };
+    if remote {
+        timeouts.network()
+    } else {
+        timeouts.local()
+    }
+}
+
+/// The git subcommand of `cmd`, skipping global options such as `-C <dir>`.
+fn git_subcommand(cmd: &Command) -> Option<String> {
+    let mut args = cmd.get_args().map(|a| a.to_string_lossy());
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "-C" | "-c" => {
+                args.next();
+            }
+            a if a.starts_with('-') => {}
+            a => return Some(a.to_string()),
+        }
+    }
+    None
+}
+
+/// Render `cmd` as `` `program arg…` `` for error messages.
+pub fn describe(cmd: &Command) -> String {
+    let program = cmd.get_program().to_string_lossy();
+    let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
+    if args.is_empty() {
+        format!("`{program}`")
+    } else {
+        format!("`{program} {}`", args.join(" "))
+    }
+}
+
+/// Drop-in replacement for [`Command::output`] using [`default_timeout`].
+/// A timeout is reported as an [`io::ErrorKind::TimedOut`] error.
+pub fn output(cmd: &mut Command) -> io::Result<Output> {
+    let timeout = default_timeout(cmd);
+    output_with_timeout(cmd, timeout)
+}
+
+/// Run `cmd` to completion and capture its output, killing its process group
+/// if it is still running after `timeout` (`None` waits forever).
+pub fn output_with_timeout(cmd: &mut Command, timeout: Option<Duration>) -> io::Result<Output> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = match wait_until(&mut child, timeout.map(|t| Instant::now() + t)) {
+        Ok(Some(status)) => status,
+        Ok(None) => {
+            kill_group(&mut child);
+            let _ = child.wait();
+            let secs = timeout.unwrap_or_default().as_secs();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("{} timed out after {secs}s", describe(cmd)),
+            ));
+        }
+        Err(e) => {
+            kill_group(&mut child);
+            let _ = child.wait();
+            return Err(e);
+        }
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Run `cmd` with `timeout`, returning its `Output` on success. A non-zero
+/// exit or spawn failure becomes `make_err` with the captured streams; a
+/// timeout becomes [`ConductorError::CommandTimedOut`].
+pub fn run_command(
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+    make_err: fn(SubprocessFailure) -> ConductorError,
+) -> Result<Output> {
+    let cmd_str = describe(cmd);
+    let output = output_with_timeout(cmd, timeout).map_err(|e| {
+        if e.kind() == io::ErrorKind::TimedOut {
+            ConductorError::CommandTimedOut {
+                command: cmd_str.clone(),
+                timeout_secs: timeout.unwrap_or_default().as_secs(),
+            }
+        } else if let Some(tool) = missing_tool(cmd, &e) {
+            ConductorError::ToolNotInstalled {
+                tool: tool.name.to_string(),
+                purpose: tool.purpose.to_string(),
+                install: tool.install.to_string(),
+            }
+        } else {
+            make_err(SubprocessFailure::from_message(
+                &cmd_str,
+                format!("failed to spawn {cmd_str}: {e}"),
+            ))
+        }
+    })?;
+    if !output.status.success() {
+        return Err(make_err(SubprocessFailure {
+            command: cmd_str,
+            exit_code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        }));
+    }
+    Ok(output)
+}
+
+/// The known tool `cmd` runs, when spawning it failed because the binary is
+/// missing. A missing working directory also spawns with `NotFound`, so that
+/// case is left to the caller's error.
+fn missing_tool(cmd: &Command, e: &io::Error) -> Option<crate::tools::Tool> {
+    if e.kind() != io::ErrorKind::NotFound || cmd.get_current_dir().is_some_and(|d| !d.is_dir()) {
+        return None;
+    }
+    crate::tools::find(&cmd.get_program().to_string_lossy())
+}
+
+/// Read a pipe to the end on a separate thread so a chatty child never blocks
+/// on a full pipe while we wait for it.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Wait for `child` to exit, or until `deadline`. `Ok(None)` means the
+/// deadline passed first.
+fn wait_until(
+    child: &mut Child,
+    deadline: Option<Instant>,
+) -> io::Result<Option<std::process::ExitStatus>> {
+    let Some(deadline) = deadline else {
+        return child.wait().map(Some);
+    };
+    let mut pause = Duration::from_millis(5);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(pause.min(deadline - now));
+        pause = (pause * 2).min(Duration::from_millis(100));
+    }
+}
+
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // The child leads its own group (see `process_group(0)` above), so its
+        // pid is the group id.
+        unsafe {
+            libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_captures_streams_and_status() {
+        let out = output_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"]),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert_eq!(out.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "err\n");
+    }
+
+    #[test]
+    fn missing_known_binary_is_reported_as_not_installed() {
+        let empty = tempfile::tempdir().unwrap();
+        let err = run_command(
+            Command::new("acli")
+                .arg("--version")
+                .env("PATH", empty.path()),
+            None,
+            ConductorError::Git,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::ToolNotInstalled { tool, .. } if tool == "acli"),
+            "{err}"
+        );
+
+        // A missing working directory is not a missing tool.
+        let err = run_command(
+            Command::new("git")
+                .arg("status")
+                .current_dir(empty.path().join("gone")),
+            None,
+            ConductorError::Git,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConductorError::Git(_)), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_kills_the_whole_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("survived");
+        let script = format!("(sleep 1; touch {}) & sleep 30", marker.display());
+        let started = Instant::now();
+        let err = run_command(
+            Command::new("sh").args(["-c", &script]),
+            Some(Duration::from_millis(200)),
+            ConductorError::Git,
+        )
+        .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(
+            matches!(&err, ConductorError::CommandTimedOut { command, .. } if command.starts_with("`sh -c")),
+            "expected CommandTimedOut, got: {err:?}"
+        );
+        thread::sleep(Duration::from_millis(1500));
+        assert!(
+            !marker.exists(),
+            "background grandchild outlived the timeout"
+        );
+    }
+
+    #[test]
+    fn default_timeout_picks_network_for_remote_commands() {
+        let t = TimeoutsConfig::DEFAULT;
+        let mut fetch = Command::new("git");
+        fetch.args(["-C", "repo", "fetch", "origin"]);
+        assert_eq!(default_timeout(&fetch), t.network());
+        assert_eq!(default_timeout(&Command::new("gh")), t.network());
+        let mut status = Command::new("git");
+        status.args(["status", "--porcelain"]);
+        assert_eq!(default_timeout(&status), t.local());
+    }
+}
@@ -5,6 +5,7 @@ use rusqlite::Connection;
 use crate::config::{db_path as config_db_path, ensure_dirs, load_config, Config};
 use crate::db::{open_database, open_database_compat};
 use crate::error::Result;
+use crate::workspace::WorkspaceManager;
 
 pub struct Conductor {
     pub conn: Connection,
@@ -16,6 +17,7 @@ impl Conductor {
         let config = load_config()?;
         ensure_dirs(&config)?;
         let conn = open_database(&config_db_path())?;
+        warn_invalid_workspace_roots(&conn, &config);
         Ok(Self { conn, config })
     }
 
@@ -23,6 +25,7 @@ impl Conductor {
         let config = load_config()?;
         ensure_dirs(&config)?;
         let conn = open_database_compat(&config_db_path())?;
+        warn_invalid_workspace_roots(&conn, &config);
         Ok(Self { conn, config })
     }
 
@@ -31,6 +34,25 @@ impl Conductor {
     }
 }
 
+/// Log each workspace root that is missing or not writable, so a moved or
+/// unmounted drive shows up at startup rather than on the next worktree
+/// create.
+fn warn_invalid_workspace_roots(conn: &Connection, config: &Config) {
+    match WorkspaceManager::new(conn, config).invalid_roots() {
+        Ok(roots) => {
+            for root in roots {
+                tracing::warn!(
+                    path = %root.path,
+                    repos = %root.repos.join(", "),
+                    "workspace root is {}; run `conductor workspace list` for details",
+                    root.status
+                );
+            }
+        }
+        Err(e) => tracing::warn!("workspace root check failed: {e}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Mutex;
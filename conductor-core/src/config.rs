@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use crate::changelog::ChangelogConfig;
 use crate::error::{ConductorError, Result};
+use crate::instructions::InstructionsFile;
 
 // Re-export moved types from runkon-runtimes
 pub use runkon_runtimes::config::RuntimeConfig;
@@ -87,6 +89,26 @@ impl AgentPermissionMode {
             None => runkon_runtimes::permission::PermissionMode::Default,
         }
     }
+
+    /// How far this mode restricts the agent; higher is stricter.
+    fn strictness(self) -> u8 {
+        match self {
+            Self::SkipPermissions => 0,
+            Self::AutoMode => 1,
+            Self::RepoSafe => 2,
+            Self::Plan => 3,
+        }
+    }
+
+    /// The stricter of `self` and `other`. A repo's committed `[agent]
+    /// permission_mode` goes through this, so cloning a repo can tighten the
+    /// user's mode but never loosen it.
+    pub fn tightened_by(self, other: Option<Self>) -> Self {
+        match other {
+            Some(other) if other.strictness() > self.strictness() => other,
+            _ => self,
+        }
+    }
 }
 
 /// Controls whether an agent is auto-started after creating a worktree from a ticket.
@@ -102,6 +124,19 @@ pub enum AutoStartAgent {
     Never,
 }
 
+/// Controls whether creating a worktree fetches `origin` to update its base branch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseFetch {
+    /// Always fetch before branching (default)
+    #[default]
+    Always,
+    /// Fetch only when origin's host accepts a connection; otherwise use local refs
+    Auto,
+    /// Never fetch; branch from the last-fetched local state
+    Never,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NotificationConfig {
     #[serde(default)]
@@ -116,6 +151,72 @@ pub struct NotificationConfig {
     /// `url` field of the form `{web_url}/repos/{repo_id}/worktrees/{worktree_id}/workflows/runs/{run_id}`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub web_url: Option<String>,
+    /// Notifications for watched tickets and worktrees (`[notifications.watch]`).
+    #[serde(default)]
+    pub watch: WatchNotificationConfig,
+    /// Threshold alerts on agent spend and progress (`[notifications.cost_alerts]`).
+    #[serde(default)]
+    pub cost_alerts: CostAlertConfig,
+}
+
+/// Thresholds for agent cost alerts. Each alert is off unless its threshold
+/// is set.
+///
+/// ```toml
+/// [notifications.cost_alerts]
+/// run_cost_usd = 5.0           # a single run costs more than $5
+/// daily_spend_usd = 50.0       # agents spent more than $50 today (UTC)
+/// turns_without_commits = 40   # a run took 40 turns without committing
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostAlertConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_spend_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turns_without_commits: Option<i64>,
+}
+
+impl CostAlertConfig {
+    /// Whether any threshold is set.
+    pub fn is_enabled(&self) -> bool {
+        self.run_cost_usd.is_some()
+            || self.daily_spend_usd.is_some()
+            || self.turns_without_commits.is_some()
+    }
+}
+
+/// Notification preferences for watch subscriptions.
+///
+/// ```toml
+/// [notifications.watch]
+/// only_watched = true   # agent/workflow notifications only for watched worktrees
+/// pr_updates = false
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchNotificationConfig {
+    /// Suppress agent run and workflow notifications for worktrees that are
+    /// not watched, directly or through their linked ticket. Defaults to false.
+    #[serde(default)]
+    pub only_watched: bool,
+    /// Notify when a watched worktree's status or ticket's state changes.
+    #[serde(default = "default_true")]
+    pub state_changes: bool,
+    /// Notify when the pull request of a watched item changes state, draft
+    /// status or review decision.
+    #[serde(default = "default_true")]
+    pub pr_updates: bool,
+}
+
+impl Default for WatchNotificationConfig {
+    fn default() -> Self {
+        Self {
+            only_watched: false,
+            state_changes: true,
+            pr_updates: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -290,6 +391,30 @@ pub fn hooks_as_runkon(hooks: &[HookConfig]) -> Vec<runkon_notify::HookConfig> {
     hooks.iter().map(|h| h.to_runkon_hook_config()).collect()
 }
 
+/// Top-level `[budget]` section: caps on agent spend. Once a worktree or a
+/// Claude session has cost its cap, new agent runs there are refused unless
+/// the launch overrides the budget. Each cap is off unless set.
+///
+/// ```toml
+/// [budget]
+/// worktree_usd = 20.0   # total cost of all runs in one worktree
+/// session_usd = 5.0     # total cost of the runs in one resumed Claude session
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_usd: Option<f64>,
+}
+
+impl BudgetConfig {
+    /// Whether any cap is set.
+    pub fn is_enabled(&self) -> bool {
+        self.worktree_usd.is_some() || self.session_usd.is_some()
+    }
+}
+
 /// Top-level `[notify]` section containing user-configured notification hooks.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NotifyConfig {
@@ -297,6 +422,387 @@ pub struct NotifyConfig {
     pub hooks: Vec<HookConfig>,
 }
 
+/// Top-level `[quota]` section: disk quota applied to each repo's workspace directory.
+///
+/// ```toml
+/// [quota]
+/// max_gb = 20.0       # omit to disable quota enforcement
+/// warn_percent = 80   # soft warning threshold
+///
+/// [quota.repos]
+/// big-monorepo = 80.0 # per-repo override (GB)
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Hard limit in gigabytes for a single repo's workspace directory.
+    /// New worktrees are refused once usage reaches this value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gb: Option<f64>,
+    /// Percentage of the hard limit at which worktree creation starts warning.
+    #[serde(default = "default_quota_warn_percent")]
+    pub warn_percent: u8,
+    /// Per-repo overrides of `max_gb`, keyed by repo slug.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub repos: HashMap<String, f64>,
+}
+
+fn default_quota_warn_percent() -> u8 {
+    80
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            max_gb: None,
+            warn_percent: default_quota_warn_percent(),
+            repos: HashMap::new(),
+        }
+    }
+}
+
+impl QuotaConfig {
+    /// Hard limit in bytes for `repo_slug`, or `None` when no quota applies.
+    pub fn limit_bytes_for(&self, repo_slug: &str) -> Option<u64> {
+        self.repos
+            .get(repo_slug)
+            .copied()
+            .or(self.max_gb)
+            .filter(|gb| *gb > 0.0)
+            .map(|gb| (gb * 1024.0 * 1024.0 * 1024.0) as u64)
+    }
+
+    /// Soft warning threshold in bytes derived from `limit` and `warn_percent`.
+    pub fn warn_bytes(&self, limit: u64) -> u64 {
+        let pct = u128::from(self.warn_percent.min(100));
+        (u128::from(limit) * pct / 100) as u64
+    }
+}
+
+/// Top-level `[redaction]` section: scrubbing of secrets from agent prompts and
+/// persisted agent output. Built-in credential patterns are always applied when
+/// enabled; `patterns` adds extra ones.
+///
+/// ```toml
+/// [redaction]
+/// enabled = true
+///
+/// [[redaction.patterns]]
+/// name = "internal-token"
+/// regex = "itk_[a-z0-9]{32}"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<RedactionPattern>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Top-level `[attachments]` section: files and images referenced by synced
+/// tickets. Metadata is always recorded; downloading is opt-in.
+///
+/// ```toml
+/// [attachments]
+/// download = true        # fetch small images so agents can open them
+/// max_download_kb = 2048 # larger files are listed by URL only
+/// retention_days = 7     # delete downloads this long after the ticket closes
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentsConfig {
+    #[serde(default)]
+    pub download: bool,
+    #[serde(default = "default_attachment_max_download_kb")]
+    pub max_download_kb: u64,
+    #[serde(default = "default_attachment_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_attachment_max_download_kb() -> u64 {
+    2048
+}
+
+fn default_attachment_retention_days() -> u32 {
+    7
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            download: false,
+            max_download_kb: default_attachment_max_download_kb(),
+            retention_days: default_attachment_retention_days(),
+        }
+    }
+}
+
+/// Top-level `[grooming]` section: a cheap agent pass over newly synced
+/// tickets that proposes labels, an estimate, duplicates and a workflow.
+/// Proposals are stored for review and never applied on their own.
+///
+/// ```toml
+/// [grooming]
+/// enabled = true
+/// model = "haiku"
+/// daily_budget_usd = 0.50  # no more grooming runs today once this is spent
+/// max_per_pass = 20        # tickets groomed per sync
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroomingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_grooming_model")]
+    pub model: String,
+    #[serde(default = "default_grooming_daily_budget_usd")]
+    pub daily_budget_usd: f64,
+    #[serde(default = "default_grooming_max_per_pass")]
+    pub max_per_pass: u32,
+}
+
+fn default_grooming_model() -> String {
+    "haiku".to_string()
+}
+
+fn default_grooming_daily_budget_usd() -> f64 {
+    0.5
+}
+
+fn default_grooming_max_per_pass() -> u32 {
+    20
+}
+
+impl Default for GroomingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: default_grooming_model(),
+            daily_budget_usd: default_grooming_daily_budget_usd(),
+            max_per_pass: default_grooming_max_per_pass(),
+        }
+    }
+}
+
+/// Top-level `[retention]` section: how long finished rows are kept before
+/// the maintenance pass (or `conductor prune`) deletes them. `0` keeps them
+/// forever, which is the default for every table.
+///
+/// ```toml
+/// [retention]
+/// worktree_days = 30   # merged/abandoned worktrees, counted from completion
+/// agent_run_days = 90  # finished agent runs outside any workflow
+/// event_days = 14      # activity log entries of finished agent runs
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    #[serde(default)]
+    pub worktree_days: u32,
+    #[serde(default)]
+    pub agent_run_days: u32,
+    #[serde(default)]
+    pub event_days: u32,
+}
+
+/// Top-level `[sla]` section: how long an open ticket may wait, by priority,
+/// before the today view flags it. Age counts from when conductor first
+/// synced the ticket open, or saw it reopen. Without any entries no ticket
+/// has an SLA.
+///
+/// ```toml
+/// [sla]
+/// default_hours = 120   # tickets whose priority has no entry below
+///
+/// [sla.priority_hours]
+/// urgent = 4            # matched against the ticket priority, ignoring case
+/// high = 24
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlaConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub priority_hours: HashMap<String, u64>,
+}
+
+impl SlaConfig {
+    /// Hours allowed for a ticket with `priority`, or `None` when it has no SLA.
+    pub fn hours_for(&self, priority: Option<&str>) -> Option<u64> {
+        priority
+            .and_then(|p| {
+                self.priority_hours
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(p.trim()))
+                    .map(|(_, hours)| *hours)
+            })
+            .or(self.default_hours)
+    }
+}
+
+/// Top-level `[identity]` section: who "me" is in each tracker, for the
+/// personal ticket queue (tickets assigned to or mentioning you).
+///
+/// ```toml
+/// [identity]
+/// github = "octocat"                      # GitHub username
+/// gitlab = "octocat"                      # GitLab username
+/// jira = "5b10a2844c20165700ede21g"       # Jira account id
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira: Option<String>,
+}
+
+impl IdentityConfig {
+    /// The configured identity for tickets from `source_type`, if any.
+    pub fn for_source(&self, source_type: &str) -> Option<&str> {
+        let id = match source_type {
+            "github" | crate::tickets::GITHUB_PR_SOURCE_TYPE => self.github.as_deref(),
+            "gitlab" => self.gitlab.as_deref(),
+            "jira" => self.jira.as_deref(),
+            _ => None,
+        };
+        id.map(str::trim).filter(|id| !id.is_empty())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        ["github", "gitlab", "jira"]
+            .iter()
+            .all(|source| self.for_source(source).is_none())
+    }
+}
+
+/// Top-level `[timeouts]` section: how long an external command may run
+/// before its whole process group is killed. `0` waits forever.
+///
+/// ```toml
+/// [timeouts]
+/// local_secs = 120     # local git commands: status, worktree add, rebase, ...
+/// network_secs = 300   # git clone/fetch/pull/push and gh calls
+/// setup_secs = 1800    # dependency installs, [project] setup and pre-push checks
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeoutsConfig {
+    pub local_secs: u64,
+    pub network_secs: u64,
+    pub setup_secs: u64,
+}
+
+impl TimeoutsConfig {
+    pub const DEFAULT: Self = Self {
+        local_secs: 120,
+        network_secs: 300,
+        setup_secs: 1800,
+    };
+
+    pub fn local(&self) -> Option<std::time::Duration> {
+        secs(self.local_secs)
+    }
+
+    pub fn network(&self) -> Option<std::time::Duration> {
+        secs(self.network_secs)
+    }
+
+    pub fn setup(&self) -> Option<std::time::Duration> {
+        secs(self.setup_secs)
+    }
+}
+
+fn secs(n: u64) -> Option<std::time::Duration> {
+    (n > 0).then(|| std::time::Duration::from_secs(n))
+}
+
+impl Default for TimeoutsConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Top-level `[database]` section: at-rest encryption of `conductor.db` with
+/// SQLCipher. Needs a build with the `sqlcipher` feature; see
+/// [`crate::db::encryption`].
+///
+/// ```toml
+/// [database]
+/// encryption = "sqlcipher"    # default "none"
+/// key_source = "keychain"     # or "env" to read the key from key_env
+/// key_env = "CONDUCTOR_DB_KEY"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub encryption: DatabaseEncryption,
+    pub key_source: DatabaseKeySource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_env: Option<String>,
+}
+
+impl DatabaseConfig {
+    pub const DEFAULT: Self = Self {
+        encryption: DatabaseEncryption::None,
+        key_source: DatabaseKeySource::Keychain,
+        key_env: None,
+    };
+
+    pub fn encrypted(&self) -> bool {
+        self.encryption == DatabaseEncryption::Sqlcipher
+    }
+
+    /// Environment variable holding the key when `key_source = "env"`.
+    pub fn key_env(&self) -> &str {
+        self.key_env.as_deref().unwrap_or("CONDUCTOR_DB_KEY")
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How `conductor.db` is stored on disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseEncryption {
+    /// Plain SQLite (default)
+    #[default]
+    None,
+    /// Encrypted with SQLCipher
+    Sqlcipher,
+}
+
+/// Where the SQLCipher key for `conductor.db` comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseKeySource {
+    /// The OS keychain (macOS Keychain, or the Secret Service via `secret-tool`
+    /// on Linux); generated and stored there when the database is created
+    #[default]
+    Keychain,
+    /// The environment variable named by `key_env`
+    Env,
+}
+
+/// A user-supplied redaction pattern. `name` appears in the `[REDACTED:<name>]`
+/// placeholder and in redaction reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionPattern {
+    pub name: String,
+    pub regex: String,
+}
+
 /// Per-agent execution settings (global, not per-run).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentsConfig {
@@ -316,18 +822,183 @@ pub struct AgentsConfig {
     /// Defaults to `true` to preserve existing safety behavior.
     #[serde(default = "default_enforce_turn_limit")]
     pub enforce_turn_limit: bool,
+
+    /// Maximum number of agent runs active at once before queued runs wait.
+    /// Unset means queued runs are dispatched as soon as their worktree is idle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_runs: Option<u32>,
+
+    /// Comment a summary of each standalone agent run (result, cost, branch
+    /// and PR link) on the GitHub or Jira ticket linked to its worktree when
+    /// the run completes.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub post_result_to_ticket: bool,
+
+    /// List the TODO and FIXME comments a worktree's branch added (as of the
+    /// last scan) as unfinished items in the startup context of its next
+    /// agent run.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub todos_in_prompt: bool,
+
+    /// Run `conductor agent run` inside a container instead of on the host.
+    #[serde(default, skip_serializing_if = "SandboxConfig::is_default")]
+    pub sandbox: SandboxConfig,
+
+    /// Agent CLI flags a repo's `[agent] extra_args` may pass, e.g.
+    /// `["--add-dir"]`. Repo config is committed by whoever controls the
+    /// repo, so its extra arguments are refused unless listed here.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_repo_extra_args: Vec<String>,
+
+    /// Claude `--allowedTools` patterns a repo's `[agent] allowed_tools` may
+    /// pre-approve, e.g. `["Read", "Grep"]`. Entries of the repo's list not
+    /// named here are dropped, so a committed config cannot approve tools
+    /// you have not.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_repo_tools: Vec<String>,
 }
 
 fn default_enforce_turn_limit() -> bool {
     true
 }
 
+/// Where `conductor agent run` starts the agent CLI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxMode {
+    /// Directly on the host (default)
+    #[default]
+    Host,
+    /// In a container of `[agents.sandbox] image`
+    Docker,
+    /// In a container described by the worktree's `devcontainer.json`
+    Devcontainer,
+}
+
+impl SandboxMode {
+    pub const NAMES: &'static [&'static str] = &["host", "docker", "devcontainer"];
+
+    /// How far this mode isolates the agent; higher is stricter. A
+    /// devcontainer takes its `runArgs` from the repo, so it ranks below the
+    /// user's own `docker` image.
+    fn strictness(self) -> u8 {
+        match self {
+            Self::Host => 0,
+            Self::Devcontainer => 1,
+            Self::Docker => 2,
+        }
+    }
+
+    /// The stricter of `self` and `other`. A repo's committed `[agent]
+    /// sandbox` goes through this, so it can move a run off the host but
+    /// never back onto it.
+    pub fn tightened_by(self, other: Option<Self>) -> Self {
+        match other {
+            Some(other) if other.strictness() > self.strictness() => other,
+            _ => self,
+        }
+    }
+}
+
+impl std::fmt::Display for SandboxMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Host => "host",
+            Self::Docker => "docker",
+            Self::Devcontainer => "devcontainer",
+        })
+    }
+}
+
+impl std::str::FromStr for SandboxMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "host" => Ok(Self::Host),
+            "docker" => Ok(Self::Docker),
+            "devcontainer" => Ok(Self::Devcontainer),
+            other => Err(format!(
+                "unknown sandbox mode '{other}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// The `[agents.sandbox]` section.
+///
+/// ```toml
+/// [agents.sandbox]
+/// mode = "docker"                 # or "devcontainer", or "host" (default)
+/// image = "ghcr.io/acme/agent:latest"
+/// env = ["ANTHROPIC_API_KEY"]     # host variables passed into the container
+/// mounts = ["~/.claude:/home/agent/.claude"]
+/// docker_args = ["--network", "bridge"]
+/// ```
+///
+/// The worktree (and its repo's git directory) is bind-mounted at the same
+/// path as on the host, so paths in logs and git metadata stay valid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    #[serde(default)]
+    pub mode: SandboxMode,
+    /// Image for `docker` mode; `devcontainer` mode takes it from
+    /// `devcontainer.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Names of host environment variables passed into the container; unset
+    /// ones are skipped. Values never appear on the `docker` command line.
+    #[serde(default = "default_sandbox_env")]
+    pub env: Vec<String>,
+    /// Extra `docker run -v` mounts (`host:container[:opts]`); `~` is expanded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mounts: Vec<String>,
+    /// Extra arguments for `docker run`, e.g. `["--network", "none"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docker_args: Vec<String>,
+}
+
+fn default_sandbox_env() -> Vec<String> {
+    [
+        "ANTHROPIC_API_KEY",
+        "CLAUDE_CODE_OAUTH_TOKEN",
+        "OPENAI_API_KEY",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            mode: SandboxMode::Host,
+            image: None,
+            env: default_sandbox_env(),
+            mounts: Vec::new(),
+            docker_args: Vec::new(),
+        }
+    }
+}
+
+impl SandboxConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 impl Default for AgentsConfig {
     fn default() -> Self {
         Self {
             stall_threshold_secs: None,
             max_turns: None,
             enforce_turn_limit: true,
+            max_concurrent_runs: None,
+            post_result_to_ticket: false,
+            todos_in_prompt: false,
+            sandbox: SandboxConfig::default(),
+            allowed_repo_extra_args: Vec::new(),
+            allowed_repo_tools: Vec::new(),
         }
     }
 }
@@ -373,6 +1044,26 @@ pub struct Config {
     /// The built-in "claude" runtime does not require an entry here.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub runtimes: HashMap<String, RuntimeConfig>,
+    #[serde(default)]
+    pub quota: QuotaConfig,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    #[serde(default)]
+    pub attachments: AttachmentsConfig,
+    #[serde(default)]
+    pub grooming: GroomingConfig,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    #[serde(default)]
+    pub timeouts: TimeoutsConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    #[serde(default)]
+    pub sla: SlaConfig,
+    #[serde(default)]
+    pub identity: IdentityConfig,
 }
 
 /// Top-level `[github]` section.
@@ -459,6 +1150,11 @@ pub struct GeneralConfig {
     /// after orphan detection. Set to 0 to disable automatic resume. Defaults to 3.
     #[serde(default = "default_auto_resume_limit")]
     pub auto_resume_limit: u32,
+    /// Cumulative turns a Claude session may accumulate across resumes before
+    /// the next resume starts a fresh session seeded with a recap of the old
+    /// one. Set to 0 to always resume in place. Defaults to 200.
+    #[serde(default = "default_session_rotation_turns")]
+    pub session_rotation_turns: u32,
     /// User-defined custom model IDs stored in insertion order.
     /// Displayed in the model picker after built-in models.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -468,6 +1164,15 @@ pub struct GeneralConfig {
     /// When unset, runkon-flow-executors falls back to its own system default ("claude").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_runtime: Option<String>,
+    /// Whether worktree creation fetches `origin` first. Set to `auto` or
+    /// `never` on offline or metered connections. Defaults to `always`.
+    #[serde(default)]
+    pub base_fetch: BaseFetch,
+    /// Agent CLI driven by `conductor agent run`: `claude`, `codex` or `aider`.
+    /// Overridden per repo (`agent_backend` in `.conductor/config.toml`) and
+    /// per run (`--backend`). Defaults to `claude`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_backend: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -512,6 +1217,10 @@ fn default_auto_resume_limit() -> u32 {
     3
 }
 
+fn default_session_rotation_turns() -> u32 {
+    200
+}
+
 impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
@@ -526,8 +1235,11 @@ impl Default for GeneralConfig {
             stale_workflow_minutes: default_stale_workflow_minutes(),
             claude_config_dir: None,
             auto_resume_limit: default_auto_resume_limit(),
+            session_rotation_turns: default_session_rotation_turns(),
             custom_models: Vec::new(),
             default_runtime: None,
+            base_fetch: BaseFetch::default(),
+            agent_backend: None,
         }
     }
 }
@@ -645,6 +1357,11 @@ pub fn agent_log_dir() -> PathBuf {
     conductor_dir().join("agent-logs")
 }
 
+/// Returns the directory for downloaded ticket attachments: ~/.conductor/ticket-attachments/
+pub fn ticket_attachments_dir() -> PathBuf {
+    conductor_dir().join("ticket-attachments")
+}
+
 /// Returns the directory for user-supplied hook scripts: ~/.conductor/hooks/
 pub fn hooks_dir() -> PathBuf {
     conductor_dir().join("hooks")
@@ -696,7 +1413,10 @@ impl Config {
 
 /// Load config from disk, returning defaults if the file doesn't exist.
 pub fn load_config() -> Result<Config> {
-    load_config_from(&config_path())
+    let config = load_config_from(&config_path())?;
+    crate::command::set_timeouts(&config.timeouts);
+    crate::db::encryption::set_settings(&config.database);
+    Ok(config)
 }
 
 /// Migrate `general.custom_models` into `runtimes.claude.supported_models`.
@@ -741,13 +1461,28 @@ fn load_config_from(path: &std::path::Path) -> Result<Config> {
     if !path.exists() {
         return Ok(Config::default());
     }
-    let contents = std::fs::read_to_string(path)?;
+    let mut contents = std::fs::read_to_string(path)?;
+
+    // Parse raw TOML once for schema migration, deprecation checks and
+    // github.app validation.
+    let mut raw: toml::Value =
+        toml::from_str(&contents).map_err(|e| ConductorError::Config(e.to_string()))?;
+    if let Some(from) = crate::config_migration::migrate(&mut raw)? {
+        contents = toml::to_string_pretty(&raw)
+            .map_err(|e| ConductorError::Config(format!("serialize config: {e}")))?;
+        persist_migrated_config(path, from, &contents)?;
+    }
+
     let mut config: Config =
         toml::from_str(&contents).map_err(|e| ConductorError::Config(e.to_string()))?;
 
-    // Parse raw TOML once for migration checks and github.app validation.
-    let raw: toml::Value =
-        toml::from_str(&contents).map_err(|e| ConductorError::Config(e.to_string()))?;
+    // `workspace_root` may use `~` and `$VAR`, e.g. `$HOME/code` or `~/ws`.
+    if let Some(raw_root) = config.general.workspace_root.to_str() {
+        if raw_root.starts_with('~') || raw_root.contains('$') {
+            config.general.workspace_root = crate::workspace::expand_path(raw_root)
+                .map_err(|e| ConductorError::Config(format!("general.workspace_root: {e}")))?;
+        }
+    }
 
     // Deprecation: warn if webhook_url is still present in config.toml.
     if raw
@@ -773,19 +1508,6 @@ fn load_config_from(path: &std::path::Path) -> Result<Config> {
         );
     }
 
-    // Deprecation: warn if [general].theme is still present — it moved to [tui].theme.
-    if raw.get("general").and_then(|g| g.get("theme")).is_some() {
-        tracing::warn!(
-            "[general].theme is deprecated — move to [tui].theme; conductor-core no longer reads it"
-        );
-    }
-
-    // Deprecation: warn if top-level [web_push] is present — VAPID keys moved to [web].push.
-    // Key values are intentionally not logged here (they are secrets).
-    if raw.get("web_push").is_some() {
-        tracing::warn!("top-level [web_push] section is deprecated; move to [web].push");
-    }
-
     // Deprecation: warn if installation_id is set in [github.app] or [github.apps.*].
     if let Some(app) = config.github.app.as_ref() {
         if app.installation_id.is_some() {
@@ -845,6 +1567,31 @@ fn load_config_from(path: &std::path::Path) -> Result<Config> {
     Ok(config)
 }
 
+/// Back up the pre-migration file next to it as `config.toml.v<from>.bak`,
+/// then write the migrated `contents`. Nothing is rewritten without a backup;
+/// a failed rewrite only warns, since the migrated config is still used.
+fn persist_migrated_config(path: &std::path::Path, from: u32, contents: &str) -> Result<()> {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".v{from}.bak"));
+    let backup = PathBuf::from(backup);
+    std::fs::copy(path, &backup).map_err(|e| {
+        ConductorError::Config(format!(
+            "cannot back up {} before migrating it: {e}",
+            path.display()
+        ))
+    })?;
+    match std::fs::write(path, contents) {
+        Ok(()) => tracing::info!(
+            "migrated {} from config_version {from} to {}; backup at {}",
+            path.display(),
+            crate::config_migration::CURRENT_CONFIG_VERSION,
+            backup.display()
+        ),
+        Err(e) => tracing::warn!("failed to write migrated config to {}: {e}", path.display()),
+    }
+    Ok(())
+}
+
 /// Save config to disk.
 ///
 /// Performs a patch-write: reads the existing file as `toml::Value`, merges
@@ -896,6 +1643,12 @@ fn save_config_to(config: &Config, path: &std::path::Path) -> Result<()> {
         .map_err(|e| ConductorError::Config(format!("serialize config: {e}")))?;
 
     merge_toml(&mut merged, new_value);
+    if let toml::Value::Table(ref mut table) = merged {
+        table.insert(
+            crate::config_migration::VERSION_KEY.to_string(),
+            toml::Value::Integer(crate::config_migration::CURRENT_CONFIG_VERSION.into()),
+        );
+    }
 
     let contents = toml::to_string_pretty(&merged)
         .map_err(|e| ConductorError::Config(format!("serialize config: {e}")))?;
@@ -921,6 +1674,294 @@ pub fn ensure_dirs(config: &Config) -> Result<()> {
 pub struct RepoConfig {
     #[serde(default)]
     pub defaults: RepoDefaults,
+    /// Project conventions written by `conductor init` and meant to be
+    /// committed, so every clone gets the same agent and worktree behavior.
+    #[serde(default, skip_serializing_if = "ProjectSettings::is_empty")]
+    pub project: ProjectSettings,
+    /// How agents run in this repo; absent keys fall through to the global
+    /// settings.
+    #[serde(default, skip_serializing_if = "RepoAgentSettings::is_empty")]
+    pub agent: RepoAgentSettings,
+    /// Changelog entries for merged worktrees; absent means none are written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<ChangelogConfig>,
+    /// Base branches for worktrees created from labelled tickets, checked in
+    /// order; tickets matching no rule branch from the repo default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub base_branch_rules: Vec<BaseBranchRule>,
+    /// Scripts run when worktrees are created, deleted and merged.
+    #[serde(default, skip_serializing_if = "WorktreeHooks::is_empty")]
+    pub hooks: WorktreeHooks,
+}
+
+/// One `[[base_branch_rules]]` entry in `.conductor/config.toml`.
+///
+/// ```toml
+/// [[base_branch_rules]]
+/// label = "hotfix"
+/// branch = "release/1.x"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaseBranchRule {
+    /// Ticket label to match, ignoring case. Ticket types such as Jira issue
+    /// types arrive as labels, so they match here too.
+    pub label: String,
+    pub branch: String,
+}
+
+/// The `[project]` section of `.conductor/config.toml`.
+///
+/// ```toml
+/// [project]
+/// test_command = "cargo test --workspace"
+/// setup = ["npm ci --prefix frontend"]
+/// agent_preamble = "Follow the conventions in CONTRIBUTING.md."
+/// branch_prefix = "dev"
+/// protected_paths = ["migrations/", "Cargo.lock"]
+/// pre_push = ["cargo fmt --check", "cargo clippy -- -D warnings"]
+/// instructions_files = ["CLAUDE.md"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    /// Command agents are told to run to test their changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_command: Option<String>,
+    /// Shell commands run, in order, in each new worktree after checkout.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup: Vec<String>,
+    /// Instructions prepended to every agent prompt in this repo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_preamble: Option<String>,
+    /// Branch prefix for worktree names without a recognized prefix such as
+    /// `fix-` (defaults to `feat`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_prefix: Option<String>,
+    /// Repo-relative paths agents are told not to modify.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_paths: Vec<String>,
+    /// Shell commands run, in order, in the worktree before a push. The first
+    /// failing command blocks the push unless checks are skipped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_push: Vec<String>,
+    /// Agent instructions files to regenerate in each new worktree from these
+    /// settings (see `conductor repo gen-instructions`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instructions_files: Vec<InstructionsFile>,
+}
+
+impl ProjectSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check the settings and return a description of each problem found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self
+            .test_command
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            problems.push("test_command must not be empty".to_string());
+        }
+        if self.setup.iter().any(|c| c.trim().is_empty()) {
+            problems.push("setup commands must not be empty".to_string());
+        }
+        if self.pre_push.iter().any(|c| c.trim().is_empty()) {
+            problems.push("pre_push commands must not be empty".to_string());
+        }
+        if let Some(prefix) = &self.branch_prefix {
+            let invalid = prefix.is_empty()
+                || prefix.starts_with(['-', '.'])
+                || prefix.ends_with(".lock")
+                || prefix.contains("..")
+                || prefix
+                    .chars()
+                    .any(|c| c.is_whitespace() || c.is_control() || "/~^:?*[\\@{".contains(c));
+            if invalid {
+                problems.push(format!(
+                    "branch_prefix '{prefix}' is not usable as a git branch prefix"
+                ));
+            }
+        }
+        for path in &self.protected_paths {
+            if path.trim().is_empty()
+                || Path::new(path).is_absolute()
+                || Path::new(path)
+                    .components()
+                    .any(|c| c == std::path::Component::ParentDir)
+            {
+                problems.push(format!(
+                    "protected path '{path}' must be relative to the repo root"
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Render the settings as a prompt section for agents, or `None` when
+    /// nothing applies to agents.
+    pub fn agent_instructions(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(preamble) = self.agent_preamble.as_deref().map(str::trim) {
+            if !preamble.is_empty() {
+                lines.push(preamble.to_string());
+            }
+        }
+        if let Some(cmd) = &self.test_command {
+            lines.push(format!(
+                "Run `{cmd}` to test your changes before finishing."
+            ));
+        }
+        if !self.protected_paths.is_empty() {
+            let paths: Vec<String> = self
+                .protected_paths
+                .iter()
+                .map(|p| format!("`{p}`"))
+                .collect();
+            lines.push(format!("Do not modify: {}.", paths.join(", ")));
+        }
+        (!lines.is_empty()).then(|| format!("**Project conventions:**\n{}", lines.join("\n")))
+    }
+}
+
+/// The `[hooks]` section of `.conductor/config.toml`: shell commands run at
+/// points in a worktree's life. Each runs with `sh -c` and sees the worktree
+/// through `CONDUCTOR_WORKTREE_PATH`, `CONDUCTOR_BRANCH` and
+/// `CONDUCTOR_TICKET_ID` (empty when no ticket is linked). A failing command
+/// is reported as a warning and never blocks the operation.
+///
+/// ```toml
+/// [hooks]
+/// post_create = ["cargo fetch", "uv sync"]
+/// pre_delete = ["docker compose down"]
+/// post_merge = ["./scripts/notify-deploy.sh"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeHooks {
+    /// Run in a new worktree after checkout, in place of the toolchain's
+    /// default dependency install. `[project] setup` still runs after.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+    /// Run in the worktree before its directory and branch are removed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_delete: Vec<String>,
+    /// Run in the repo checkout once a worktree is found merged and removed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_merge: Vec<String>,
+}
+
+impl WorktreeHooks {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check the hooks and return a description of each problem found.
+    pub fn validate(&self) -> Vec<String> {
+        [
+            ("post_create", &self.post_create),
+            ("pre_delete", &self.pre_delete),
+            ("post_merge", &self.post_merge),
+        ]
+        .into_iter()
+        .filter(|(_, commands)| commands.iter().any(|c| c.trim().is_empty()))
+        .map(|(hook, _)| format!("{hook} commands must not be empty"))
+        .collect()
+    }
+}
+
+/// The `[agent]` section of `.conductor/config.toml`, applied to every
+/// `conductor agent run` in the repo. The model is set with `[defaults] model`
+/// (`conductor repo set-model`).
+///
+/// ```toml
+/// [agent]
+/// permission_mode = "auto-mode"
+/// allowed_tools = ["Read", "Grep", "Edit", "Bash(cargo test:*)"]
+/// max_turns = 40
+/// extra_args = ["--add-dir", "../shared"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoAgentSettings {
+    /// Tightens `[general] agent_permission_mode` (see
+    /// [`AgentPermissionMode::tightened_by`]); a run's own `--permission-mode`
+    /// still wins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_mode: Option<AgentPermissionMode>,
+    /// Tools the agent may use (Claude `--allowedTools` patterns). Only
+    /// entries also in the user's `[agents] allowed_repo_tools` are passed;
+    /// ignored when the permission mode brings its own read-only list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Stop the agent after this many turns (Claude only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+    /// Extra arguments appended to the agent CLI's command line. Each flag
+    /// must be in the user's `[agents] allowed_repo_extra_args`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+    /// Tightens `[agents.sandbox] mode` (host < devcontainer < docker) but
+    /// never loosens it; a run's own `--sandbox` still wins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxMode>,
+}
+
+impl RepoAgentSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check the settings and return a description of each problem found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.max_turns == Some(0) {
+            problems.push("max_turns must be at least 1".to_string());
+        }
+        if self.allowed_tools.iter().any(|t| t.trim().is_empty()) {
+            problems.push("allowed_tools entries must not be empty".to_string());
+        }
+        if self.extra_args.iter().any(|a| a.trim().is_empty()) {
+            problems.push("extra_args entries must not be empty".to_string());
+        }
+        problems
+    }
+
+    /// Entries of `allowed_tools` that are also in `allowed`, the user's
+    /// `[agents] allowed_repo_tools`, in the repo's order.
+    pub fn permitted_tools(&self, allowed: &[String]) -> Vec<String> {
+        self.allowed_tools
+            .iter()
+            .filter(|tool| allowed.contains(tool))
+            .cloned()
+            .collect()
+    }
+
+    /// Entries of `extra_args` that `allowed` flags do not cover. A flag
+    /// (`--add-dir` or `--add-dir=../x`) is covered when its name is listed; a
+    /// value is covered when it follows a covered flag without `=`.
+    pub fn disallowed_extra_args(&self, allowed: &[String]) -> Vec<&str> {
+        let mut disallowed = Vec::new();
+        let mut takes_value = false;
+        for arg in &self.extra_args {
+            if arg.starts_with('-') {
+                let (name, inline_value) = match arg.split_once('=') {
+                    Some((name, _)) => (name, true),
+                    None => (arg.as_str(), false),
+                };
+                let ok = allowed.iter().any(|a| a == name);
+                if !ok {
+                    disallowed.push(arg.as_str());
+                }
+                takes_value = ok && !inline_value;
+            } else {
+                if !takes_value {
+                    disallowed.push(arg.as_str());
+                }
+                takes_value = false;
+            }
+        }
+        disallowed
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -933,6 +1974,8 @@ pub struct RepoDefaults {
     pub bot_name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub feature_merge_strategy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_backend: Option<String>,
 }
 
 impl RepoConfig {
@@ -949,6 +1992,13 @@ impl RepoConfig {
         Ok(config)
     }
 
+    /// First base-branch rule matching any of `labels`.
+    pub fn base_branch_rule(&self, labels: &[&str]) -> Option<&BaseBranchRule> {
+        self.base_branch_rules
+            .iter()
+            .find(|rule| labels.iter().any(|l| l.eq_ignore_ascii_case(&rule.label)))
+    }
+
     /// Save repo-level config to `<repo_root>/.conductor/config.toml`.
     /// Creates the `.conductor/` directory if needed.
     pub fn save(&self, repo_path: &Path) -> Result<()> {
@@ -986,6 +2036,31 @@ impl RepoConfig {
                 defaults.remove("feature_merge_strategy");
             }
         }
+        // `[project]`, `[agent]` and `[changelog]` are always written whole so cleared
+        // fields do not linger.
+        if let Some(table) = merged.as_table_mut() {
+            table.remove("project");
+            if !self.project.is_empty() {
+                let project = toml::Value::try_from(&self.project)
+                    .map_err(|e| ConductorError::Config(format!("serialize repo config: {e}")))?;
+                table.insert("project".into(), project);
+            }
+            table.remove("agent");
+            if !self.agent.is_empty() {
+                let agent = toml::Value::try_from(&self.agent)
+                    .map_err(|e| ConductorError::Config(format!("serialize repo config: {e}")))?;
+                table.insert("agent".into(), agent);
+            }
+            if self.base_branch_rules.is_empty() {
+                table.remove("base_branch_rules");
+            }
+            table.remove("changelog");
+            if let Some(ref changelog) = self.changelog {
+                let changelog = toml::Value::try_from(changelog)
+                    .map_err(|e| ConductorError::Config(format!("serialize repo config: {e}")))?;
+                table.insert("changelog".into(), changelog);
+            }
+        }
 
         let contents = toml::to_string_pretty(&merged)
             .map_err(|e| ConductorError::Config(format!("serialize repo config: {e}")))?;
@@ -1066,6 +2141,21 @@ mod tests {
         assert_eq!(config.general.auto_start_agent, AutoStartAgent::Never);
     }
 
+    #[test]
+    fn test_base_fetch_defaults_to_always_and_parses() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.general.base_fetch, BaseFetch::Always);
+
+        let config: Config = toml::from_str(
+            r#"
+            [general]
+            base_fetch = "auto"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.general.base_fetch, BaseFetch::Auto);
+    }
+
     #[test]
     fn test_agent_permission_mode_default() {
         let config: Config = toml::from_str("").unwrap();
@@ -1298,6 +2388,21 @@ mod tests {
         assert!(!config.general.auto_cleanup_merged_branches);
     }
 
+    #[test]
+    fn test_session_rotation_turns_default_and_opt_out() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.general.session_rotation_turns, 200);
+
+        let config: Config = toml::from_str(
+            r#"
+            [general]
+            session_rotation_turns = 0
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.general.session_rotation_turns, 0);
+    }
+
     #[test]
     fn test_inject_startup_context_opt_out() {
         let config: Config = toml::from_str(
@@ -1591,6 +2696,190 @@ app_id = 123456
         assert!(!wf.on_gate_pr_review);
     }
 
+    #[test]
+    fn test_notification_watch_section() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.notifications.watch.only_watched);
+        assert!(config.notifications.watch.pr_updates);
+
+        let config: Config = toml::from_str(
+            r#"
+            [notifications.watch]
+            only_watched = true
+            pr_updates = false
+        "#,
+        )
+        .unwrap();
+        let watch = &config.notifications.watch;
+        assert!(watch.only_watched);
+        assert!(watch.state_changes);
+        assert!(!watch.pr_updates);
+    }
+
+    #[test]
+    fn test_repo_config_project_round_trip_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rc = RepoConfig::default();
+        rc.defaults.model = Some("sonnet".into());
+        rc.project = ProjectSettings {
+            test_command: Some("cargo test".into()),
+            setup: vec!["make deps".into()],
+            branch_prefix: Some("dev".into()),
+            ..Default::default()
+        };
+        rc.save(dir.path()).unwrap();
+        let loaded = RepoConfig::load(dir.path()).unwrap();
+        assert_eq!(loaded.project, rc.project);
+        assert_eq!(loaded.defaults.model.as_deref(), Some("sonnet"));
+
+        rc.project.setup.clear();
+        rc.save(dir.path()).unwrap();
+        let text = std::fs::read_to_string(dir.path().join(".conductor/config.toml")).unwrap();
+        assert!(!text.contains("setup"), "cleared field is removed: {text}");
+
+        rc.project = ProjectSettings::default();
+        rc.save(dir.path()).unwrap();
+        let text = std::fs::read_to_string(dir.path().join(".conductor/config.toml")).unwrap();
+        assert!(!text.contains("[project]"));
+    }
+
+    #[test]
+    fn test_project_settings_validate() {
+        assert!(ProjectSettings::default().validate().is_empty());
+        let bad = ProjectSettings {
+            test_command: Some("  ".into()),
+            setup: vec!["".into()],
+            branch_prefix: Some("my feature".into()),
+            protected_paths: vec!["/etc".into(), "../outside".into(), "src/db".into()],
+            pre_push: vec![" ".into()],
+            ..Default::default()
+        };
+        let problems = bad.validate();
+        assert_eq!(problems.len(), 6, "{problems:?}");
+        for prefix in ["team/x", "-x", "a..b", "x.lock"] {
+            let settings = ProjectSettings {
+                branch_prefix: Some(prefix.into()),
+                ..Default::default()
+            };
+            assert_eq!(settings.validate().len(), 1, "{prefix}");
+        }
+    }
+
+    #[test]
+    fn test_worktree_hooks_load_and_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            r#"
+[hooks]
+post_create = ["cargo fetch"]
+post_merge = ["./notify.sh", " "]
+"#,
+        )
+        .unwrap();
+        let rc = RepoConfig::load(dir.path()).unwrap();
+        assert_eq!(rc.hooks.post_create, ["cargo fetch"]);
+        assert!(rc.hooks.pre_delete.is_empty());
+        assert_eq!(
+            rc.hooks.validate(),
+            ["post_merge commands must not be empty"]
+        );
+        assert!(WorktreeHooks::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_repo_agent_settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            r#"
+[agent]
+permission_mode = "repo-safe"
+allowed_tools = ["Read", "Grep"]
+max_turns = 20
+extra_args = ["--add-dir", "../shared"]
+"#,
+        )
+        .unwrap();
+        let mut rc = RepoConfig::load(dir.path()).unwrap();
+        assert_eq!(
+            rc.agent.permission_mode,
+            Some(AgentPermissionMode::RepoSafe)
+        );
+        assert_eq!(rc.agent.allowed_tools, ["Read", "Grep"]);
+        assert_eq!(rc.agent.max_turns, Some(20));
+        assert!(rc.agent.validate().is_empty());
+
+        rc.agent.max_turns = None;
+        rc.save(dir.path()).unwrap();
+        let reloaded = RepoConfig::load(dir.path()).unwrap();
+        assert_eq!(reloaded.agent.max_turns, None);
+        assert_eq!(reloaded.agent.extra_args, ["--add-dir", "../shared"]);
+
+        let bad = RepoAgentSettings {
+            max_turns: Some(0),
+            allowed_tools: vec![" ".into()],
+            ..Default::default()
+        };
+        assert_eq!(bad.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_repo_permission_mode_only_tightens() {
+        use AgentPermissionMode::*;
+        assert_eq!(SkipPermissions.tightened_by(Some(Plan)), Plan);
+        assert_eq!(SkipPermissions.tightened_by(Some(AutoMode)), AutoMode);
+        assert_eq!(AutoMode.tightened_by(Some(RepoSafe)), RepoSafe);
+        assert_eq!(RepoSafe.tightened_by(Some(SkipPermissions)), RepoSafe);
+        assert_eq!(Plan.tightened_by(Some(RepoSafe)), Plan);
+        assert_eq!(AutoMode.tightened_by(Some(SkipPermissions)), AutoMode);
+        assert_eq!(AutoMode.tightened_by(None), AutoMode);
+    }
+
+    #[test]
+    fn test_repo_extra_args_need_the_user_allowlist() {
+        let settings = RepoAgentSettings {
+            extra_args: vec![
+                "--add-dir".into(),
+                "../shared".into(),
+                "--add-dir=../more".into(),
+                "--dangerously-skip-permissions".into(),
+                "stray".into(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.disallowed_extra_args(&[]),
+            settings
+                .extra_args
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            settings.disallowed_extra_args(&["--add-dir".to_string()]),
+            ["--dangerously-skip-permissions", "stray"]
+        );
+        assert!(RepoAgentSettings::default()
+            .disallowed_extra_args(&[])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_repo_agent_tools_are_limited_to_the_user_list() {
+        let settings = RepoAgentSettings {
+            allowed_tools: vec!["Read".into(), "Bash(*)".into(), "Grep".into()],
+            ..Default::default()
+        };
+        assert!(settings.permitted_tools(&[]).is_empty());
+        assert_eq!(
+            settings.permitted_tools(&["Grep".into(), "Read".into(), "Edit".into()]),
+            ["Read", "Grep"]
+        );
+    }
+
     #[test]
     fn test_save_config_preserves_unknown_sections() {
         let dir = tempfile::tempdir().unwrap();
@@ -1688,7 +2977,13 @@ bot_name = "my-bot"
                 default_branch: Some("main".to_string()),
                 bot_name: None,
                 feature_merge_strategy: Some("merge".to_string()),
+                agent_backend: None,
             },
+            project: ProjectSettings::default(),
+            agent: RepoAgentSettings::default(),
+            changelog: None,
+            base_branch_rules: vec![],
+            hooks: WorktreeHooks::default(),
         };
         rc.save(dir.path()).unwrap();
 
@@ -1702,6 +2997,66 @@ bot_name = "my-bot"
         );
     }
 
+    #[test]
+    fn test_repo_config_changelog_section() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            "[changelog]\nmode = \"file\"\ndelivery = \"commit\"\n",
+        )
+        .unwrap();
+        let loaded = RepoConfig::load(dir.path()).unwrap();
+        let changelog = loaded.changelog.clone().unwrap();
+        assert_eq!(changelog.mode, crate::changelog::ChangelogMode::File);
+        assert_eq!(
+            changelog.delivery,
+            crate::changelog::ChangelogDelivery::Commit
+        );
+        assert_eq!(changelog.file, "CHANGELOG.md");
+
+        // Saving without the section removes it.
+        RepoConfig {
+            changelog: None,
+            ..loaded
+        }
+        .save(dir.path())
+        .unwrap();
+        assert!(RepoConfig::load(dir.path()).unwrap().changelog.is_none());
+    }
+
+    #[test]
+    fn test_repo_config_base_branch_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".conductor")).unwrap();
+        std::fs::write(
+            dir.path().join(".conductor/config.toml"),
+            "[[base_branch_rules]]\nlabel = \"hotfix\"\nbranch = \"release/1.x\"\n\n\
+             [[base_branch_rules]]\nlabel = \"Bug\"\nbranch = \"develop\"\n",
+        )
+        .unwrap();
+        let loaded = RepoConfig::load(dir.path()).unwrap();
+        assert_eq!(loaded.base_branch_rules.len(), 2);
+
+        // Earlier rules win; labels match ignoring case.
+        let rule = loaded.base_branch_rule(&["bug", "HOTFIX"]).unwrap();
+        assert_eq!(rule.branch, "release/1.x");
+        assert_eq!(loaded.base_branch_rule(&["bug"]).unwrap().branch, "develop");
+        assert!(loaded.base_branch_rule(&["docs"]).is_none());
+
+        // Saving without rules removes them.
+        RepoConfig {
+            base_branch_rules: vec![],
+            ..loaded
+        }
+        .save(dir.path())
+        .unwrap();
+        assert!(RepoConfig::load(dir.path())
+            .unwrap()
+            .base_branch_rules
+            .is_empty());
+    }
+
     #[test]
     fn test_repo_config_save_creates_conductor_dir() {
         let dir = tempfile::tempdir().unwrap();
@@ -1720,7 +3075,13 @@ bot_name = "my-bot"
                 default_branch: Some("develop".to_string()),
                 bot_name: None,
                 feature_merge_strategy: None,
+                agent_backend: None,
             },
+            project: ProjectSettings::default(),
+            agent: RepoAgentSettings::default(),
+            changelog: None,
+            base_branch_rules: vec![],
+            hooks: WorktreeHooks::default(),
         };
         rc.save(dir.path()).unwrap();
         let loaded = RepoConfig::load(dir.path()).unwrap();
@@ -1734,7 +3095,13 @@ bot_name = "my-bot"
                 default_branch: Some("develop".to_string()),
                 bot_name: None,
                 feature_merge_strategy: None,
+                agent_backend: None,
             },
+            project: ProjectSettings::default(),
+            agent: RepoAgentSettings::default(),
+            changelog: None,
+            base_branch_rules: vec![],
+            hooks: WorktreeHooks::default(),
         };
         rc2.save(dir.path()).unwrap();
         let loaded2 = RepoConfig::load(dir.path()).unwrap();
@@ -2163,29 +3530,57 @@ bot_name = "my-bot"
     // -----------------------------------------------------------------------
 
     #[test]
-    fn test_load_config_deprecation_warn_path_for_web_push() {
-        // Exercises the [web_push] deprecation-warn branch in load_config_from.
-        // The warn fires as a side-effect; this test verifies the branch is reached
-        // without error and that the rest of the config loads correctly.
+    fn test_load_config_migrates_legacy_web_push_with_backup() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.toml");
-        std::fs::write(
-            &path,
-            "[web_push]\nvapid_public_key = \"pub\"\nvapid_private_key = \"priv\"\n",
-        )
-        .unwrap();
-        let result = load_config_from(&path);
-        assert!(
-            result.is_ok(),
-            "load_config_from must succeed when [web_push] is present (only warns, not errors)"
+        let legacy = "[web_push]\nvapid_public_key = \"pub\"\nvapid_private_key = \"priv\"\n";
+        std::fs::write(&path, legacy).unwrap();
+        load_config_from(&path).expect("[web_push] is migrated, not an error");
+
+        let backup = dir.path().join("config.toml.v0.bak");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), legacy);
+        let raw: toml::Value = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(raw.get("web_push").is_none());
+        assert_eq!(
+            raw["web"]["push"]["vapid_private_key"].as_str(),
+            Some("priv")
+        );
+        assert_eq!(
+            raw["config_version"].as_integer(),
+            Some(crate::config_migration::CURRENT_CONFIG_VERSION.into())
         );
     }
 
+    #[test]
+    fn test_load_config_keeps_unversioned_current_file_byte_identical() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let current = "# my settings\n[general]\nsync_interval_minutes = 5 # often\n\n\
+                       [tui]\n# dark theme\ntheme = \"nord\"\n";
+        std::fs::write(&path, current).unwrap();
+        load_config_from(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), current);
+        assert!(!dir.path().join("config.toml.v0.bak").exists());
+    }
+
+    #[test]
+    fn test_load_config_errors_on_unmigratable_content_without_rewriting() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let conflicting = "[general]\ntheme = \"nord\"\n\n[tui]\ntheme = \"gruvbox\"\n";
+        std::fs::write(&path, conflicting).unwrap();
+        let err = load_config_from(&path).unwrap_err().to_string();
+        assert!(err.contains("[tui].theme"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), conflicting);
+        assert!(!dir.path().join("config.toml.v0.bak").exists());
+    }
+
     #[test]
     fn test_save_preserves_user_web_push_section() {
-        // Removing `web_push` from Config must NOT strip a user's existing [web_push]
-        // section from disk when other binaries (conductor-cli, conductor-tui) call
-        // save_config without knowledge of the field.
+        // Removing `web_push` from Config must NOT strip a user's push settings
+        // (migrated from [web_push] to [web].push on load) from disk when other
+        // binaries (conductor-cli, conductor-tui) call save_config without
+        // knowledge of the field.
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.toml");
         std::fs::write(
@@ -2202,18 +3597,19 @@ vapid_subject = "mailto:test@example.com"
         // Load the config (web_push field no longer exists on Config)
         let config = load_config_from(&path).unwrap();
 
-        // Save — patch-write must preserve [web_push] even though Config has no field for it
+        // Save — patch-write must preserve [web].push even though Config has no field for it
         save_config_to(&config, &path).unwrap();
 
-        // Re-read raw TOML and assert [web_push] is still intact
+        // Re-read raw TOML and assert the migrated [web].push is still intact
         let raw_contents = std::fs::read_to_string(&path).unwrap();
         let raw: toml::Value = toml::from_str(&raw_contents).unwrap();
         assert!(
-            raw.get("web_push").is_some(),
-            "[web_push] section should survive save when Config no longer has the field"
+            raw.get("web").and_then(|w| w.get("push")).is_some(),
+            "[web].push section should survive save when Config has no field for it"
         );
         assert_eq!(
-            raw.get("web_push")
+            raw.get("web")
+                .and_then(|w| w.get("push"))
                 .and_then(|wp| wp.get("vapid_subject"))
                 .and_then(|v| v.as_str()),
             Some("mailto:test@example.com"),
@@ -2343,4 +3739,47 @@ custom_models = ["model-a"]
         // and delegates to the env.)
         let _ = config.gemini_api_key(); // must compile and not panic
     }
+
+    #[test]
+    fn quota_config_defaults_to_disabled() {
+        let config = Config::default();
+        assert_eq!(config.quota.max_gb, None);
+        assert_eq!(config.quota.warn_percent, 80);
+        assert_eq!(config.quota.limit_bytes_for("any-repo"), None);
+    }
+
+    #[test]
+    fn quota_config_per_repo_override_wins() {
+        let config: Config = toml::from_str(
+            r#"
+[quota]
+max_gb = 1.0
+warn_percent = 50
+
+[quota.repos]
+big = 2.0
+"#,
+        )
+        .unwrap();
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(config.quota.limit_bytes_for("small"), Some(gib));
+        assert_eq!(config.quota.limit_bytes_for("big"), Some(2 * gib));
+        assert_eq!(config.quota.warn_bytes(gib), gib / 2);
+    }
+
+    #[test]
+    fn redaction_config_parses_custom_patterns() {
+        assert!(Config::default().redaction.enabled);
+        let config: Config = toml::from_str(
+            r#"
+[[redaction.patterns]]
+name = "internal"
+regex = "itk_[a-z0-9]+"
+"#,
+        )
+        .unwrap();
+        assert!(config.redaction.enabled);
+        assert_eq!(config.redaction.patterns.len(), 1);
+        assert_eq!(config.redaction.patterns[0].name, "internal");
+    }
 }
@@ -0,0 +1,206 @@
+//! Versioned schema for `~/.conductor/config.toml`.
+//!
+//! The file records the schema it was written for in a top-level
+//! `config_version` key (absent means `0`). On load, [`migrate`] upgrades
+//! older files step by step to [`CURRENT_CONFIG_VERSION`] so settings that
+//! moved are carried over instead of silently ignored. Content that cannot be
+//! moved safely — the old and new key both set to different values, or a file
+//! written by a newer conductor — is a load error naming the keys involved.
+
+use toml::map::Map;
+use toml::Value;
+
+use crate::error::{ConductorError, Result};
+
+/// Schema version written by this build.
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// Top-level key holding the schema version.
+pub const VERSION_KEY: &str = "config_version";
+
+type Table = Map<String, Value>;
+
+/// Migration steps in order; step `n` upgrades a version `n - 1` file to `n`.
+const STEPS: &[fn(&mut Table) -> Result<()>] = &[v1_move_frontend_sections];
+const _: () = assert!(STEPS.len() == CURRENT_CONFIG_VERSION as usize);
+
+/// The schema version recorded in `raw`.
+pub fn config_version(raw: &Value) -> Result<u32> {
+    match raw.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(v) => v
+            .as_integer()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                ConductorError::Config(format!(
+                    "{VERSION_KEY} must be a non-negative integer, got {v}"
+                ))
+            }),
+    }
+}
+
+/// Upgrade `raw` in place to [`CURRENT_CONFIG_VERSION`].
+///
+/// Returns the version the file was at when a step changed its content, or
+/// `None` when there was nothing to move. An unversioned file that no step
+/// touches is left as is rather than stamped, so callers never rewrite it
+/// (and lose its comments) just to add [`VERSION_KEY`]. `raw` is left
+/// untouched on error.
+pub fn migrate(raw: &mut Value) -> Result<Option<u32>> {
+    let from = config_version(raw)?;
+    if from > CURRENT_CONFIG_VERSION {
+        return Err(ConductorError::Config(format!(
+            "config.toml has {VERSION_KEY} = {from}, but this conductor only understands \
+             up to {CURRENT_CONFIG_VERSION}; upgrade conductor or restore a backup"
+        )));
+    }
+    if from == CURRENT_CONFIG_VERSION {
+        return Ok(None);
+    }
+    let Value::Table(table) = raw else {
+        return Err(ConductorError::Config(
+            "config.toml must be a table".to_string(),
+        ));
+    };
+    let mut upgraded = table.clone();
+    for (i, step) in STEPS.iter().enumerate().skip(from as usize) {
+        step(&mut upgraded).map_err(|e| match e {
+            ConductorError::Config(msg) => ConductorError::Config(format!(
+                "cannot migrate config.toml to {VERSION_KEY} {}: {msg}",
+                i + 1
+            )),
+            other => other,
+        })?;
+    }
+    if upgraded == *table {
+        return Ok(None);
+    }
+    upgraded.insert(
+        VERSION_KEY.to_string(),
+        Value::Integer(CURRENT_CONFIG_VERSION.into()),
+    );
+    *table = upgraded;
+    Ok(Some(from))
+}
+
+/// Version 1: `[general].theme` moved to `[tui].theme` and top-level
+/// `[web_push]` moved to `[web].push`.
+fn v1_move_frontend_sections(table: &mut Table) -> Result<()> {
+    let theme = table
+        .get_mut("general")
+        .and_then(Value::as_table_mut)
+        .and_then(|general| general.remove("theme"));
+    if let Some(theme) = theme {
+        move_into(
+            table,
+            "tui",
+            "theme",
+            theme,
+            "[general].theme",
+            "[tui].theme",
+        )?;
+    }
+    if let Some(push) = table.remove("web_push") {
+        move_into(table, "web", "push", push, "[web_push]", "[web].push")?;
+    }
+    Ok(())
+}
+
+/// Put `value` at `table[section][key]`, unless that already holds a
+/// different value.
+fn move_into(
+    table: &mut Table,
+    section: &str,
+    key: &str,
+    value: Value,
+    old_name: &str,
+    new_name: &str,
+) -> Result<()> {
+    let section = table
+        .entry(section.to_string())
+        .or_insert_with(|| Value::Table(Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| ConductorError::Config(format!("[{section}] must be a table")))?;
+    match section.get(key) {
+        Some(existing) if *existing != value => Err(ConductorError::Config(format!(
+            "both {old_name} and {new_name} are set to different values; \
+             keep {new_name} and delete {old_name}"
+        ))),
+        Some(_) => Ok(()),
+        None => {
+            section.insert(key.to_string(), value);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Value {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_moves_legacy_sections_and_stamps_version() {
+        let mut raw = parse(
+            r#"
+[general]
+theme = "nord"
+sync_interval_minutes = 5
+
+[web_push]
+vapid_public_key = "pub"
+"#,
+        );
+        assert_eq!(migrate(&mut raw).unwrap(), Some(0));
+        assert_eq!(config_version(&raw).unwrap(), CURRENT_CONFIG_VERSION);
+        assert!(raw["general"].get("theme").is_none());
+        assert_eq!(
+            raw["general"]["sync_interval_minutes"].as_integer(),
+            Some(5)
+        );
+        assert_eq!(raw["tui"]["theme"].as_str(), Some("nord"));
+        assert!(raw.get("web_push").is_none());
+        assert_eq!(raw["web"]["push"]["vapid_public_key"].as_str(), Some("pub"));
+
+        // Already current: nothing to do.
+        assert_eq!(migrate(&mut raw).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_allows_matching_duplicate_and_rejects_conflict() {
+        let mut raw = parse("[general]\ntheme = \"nord\"\n\n[tui]\ntheme = \"nord\"\n");
+        assert_eq!(migrate(&mut raw).unwrap(), Some(0));
+        assert_eq!(raw["tui"]["theme"].as_str(), Some("nord"));
+
+        let original = parse("[general]\ntheme = \"nord\"\n\n[tui]\ntheme = \"gruvbox\"\n");
+        let mut raw = original.clone();
+        let err = migrate(&mut raw).unwrap_err().to_string();
+        assert!(err.contains("[general].theme"), "{err}");
+        assert!(err.contains("[tui].theme"), "{err}");
+        assert_eq!(
+            raw, original,
+            "a failed migration must not modify the input"
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_and_malformed_versions() {
+        let mut raw = parse(&format!("{VERSION_KEY} = {}\n", CURRENT_CONFIG_VERSION + 1));
+        let err = migrate(&mut raw).unwrap_err().to_string();
+        assert!(err.contains("upgrade conductor"), "{err}");
+
+        let mut raw = parse(&format!("{VERSION_KEY} = \"one\"\n"));
+        assert!(migrate(&mut raw).is_err());
+    }
+
+    #[test]
+    fn test_migrate_leaves_unversioned_current_file_unstamped() {
+        let original = parse("[tui]\ntheme = \"nord\"\n\n[web.push]\nvapid_public_key = \"pub\"\n");
+        let mut raw = original.clone();
+        assert_eq!(migrate(&mut raw).unwrap(), None);
+        assert_eq!(raw, original);
+    }
+}
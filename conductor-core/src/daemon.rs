@@ -0,0 +1,188 @@
+//! Protocol for `conductor daemon`, the background service that owns ticket
+//! syncing, agent health checks, queue dispatch and retention for one
+//! database.
+//!
+//! The daemon listens on a unix socket next to the database. Each connection
+//! sends one JSON [`DaemonRequest`] line and reads [`DaemonMessage`] lines
+//! back: a single reply, or for [`DaemonRequest::Subscribe`] a stream of
+//! [`DaemonEvent`]s until either side hangs up. The TUI and web server probe
+//! the socket with [`is_running`] and leave that periodic work to the daemon
+//! while it is up.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConductorError, Result};
+
+/// How long a client waits on the socket before giving up on the daemon.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Socket the daemon for the current database listens on, e.g.
+/// `~/.conductor/conductor.sock` for `~/.conductor/conductor.db`.
+pub fn socket_path() -> PathBuf {
+    crate::config::db_path().with_extension("sock")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    Ping,
+    Status,
+    /// Sync every repo's tickets now instead of waiting for the next interval.
+    SyncTickets,
+    /// Keep the connection open and stream [`DaemonEvent`]s.
+    Subscribe,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonMessage {
+    Pong,
+    Status(DaemonStatus),
+    /// The request was accepted.
+    Ok,
+    Error {
+        message: String,
+    },
+    Event(DaemonEvent),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: String,
+    pub last_sync_at: Option<String>,
+    pub last_maintenance_at: Option<String>,
+    /// Queued agent runs started since the daemon came up.
+    pub dispatched_runs: u64,
+    pub subscribers: usize,
+}
+
+/// Something the daemon changed in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DaemonEvent {
+    TicketSyncStarted,
+    TicketSyncFinished {
+        error: Option<String>,
+    },
+    /// A queued agent run was started.
+    AgentDispatched {
+        run_id: String,
+        worktree_id: String,
+    },
+    /// Orphaned runs, stale worktrees or expired rows were cleaned up.
+    MaintenanceFinished {
+        reaped_runs: usize,
+        pruned_rows: usize,
+    },
+}
+
+/// A connection to a running daemon.
+pub struct DaemonClient {
+    reader: BufReader<UnixStream>,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon for the current database. Fails when none is
+    /// listening.
+    pub fn connect() -> Result<Self> {
+        let stream = UnixStream::connect(socket_path())?;
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        Ok(Self {
+            reader: BufReader::new(stream),
+        })
+    }
+
+    /// Send `request` and wait for the first reply.
+    pub fn request(&mut self, request: &DaemonRequest) -> Result<DaemonMessage> {
+        write_line(self.reader.get_mut(), request)?;
+        read_line(&mut self.reader)?
+            .ok_or_else(|| ConductorError::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    }
+
+    /// Subscribe to daemon events. The iterator ends when the daemon goes
+    /// away.
+    pub fn subscribe(mut self) -> Result<impl Iterator<Item = DaemonEvent>> {
+        match self.request(&DaemonRequest::Subscribe)? {
+            DaemonMessage::Ok => {}
+            other => return Err(unexpected(other)),
+        }
+        // Events can be minutes apart.
+        self.reader.get_ref().set_read_timeout(None)?;
+        let mut reader = self.reader;
+        Ok(std::iter::from_fn(move || loop {
+            match read_line::<DaemonMessage>(&mut reader) {
+                Ok(Some(DaemonMessage::Event(event))) => return Some(event),
+                Ok(Some(_)) => continue,
+                Ok(None) | Err(_) => return None,
+            }
+        }))
+    }
+}
+
+/// Whether a daemon is serving the current database.
+pub fn is_running() -> bool {
+    DaemonClient::connect()
+        .and_then(|mut c| c.request(&DaemonRequest::Ping))
+        .is_ok_and(|reply| reply == DaemonMessage::Pong)
+}
+
+/// Write `value` as one JSON line.
+pub fn write_line(stream: &mut impl Write, value: &impl Serialize) -> Result<()> {
+    let mut line = serde_json::to_string(value).map_err(std::io::Error::other)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Read one JSON line, or `None` at end of stream.
+pub fn read_line<T: serde::de::DeserializeOwned>(reader: &mut impl BufRead) -> Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let value = serde_json::from_str(line.trim_end()).map_err(std::io::Error::other)?;
+    Ok(Some(value))
+}
+
+fn unexpected(reply: DaemonMessage) -> ConductorError {
+    let message = match reply {
+        DaemonMessage::Error { message } => message,
+        other => format!("unexpected daemon reply: {other:?}"),
+    };
+    ConductorError::Io(std::io::Error::other(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_round_trip_as_json_lines() {
+        let mut buf = Vec::new();
+        write_line(&mut buf, &DaemonRequest::SyncTickets).unwrap();
+        let event = DaemonMessage::Event(DaemonEvent::AgentDispatched {
+            run_id: "r1".into(),
+            worktree_id: "w1".into(),
+        });
+        write_line(&mut buf, &event).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf).unwrap().lines().next(),
+            Some(r#"{"type":"sync_tickets"}"#)
+        );
+
+        let mut reader = &buf[..];
+        let request: Option<DaemonRequest> = read_line(&mut reader).unwrap();
+        assert_eq!(request, Some(DaemonRequest::SyncTickets));
+        let message: Option<DaemonMessage> = read_line(&mut reader).unwrap();
+        assert_eq!(message, Some(event));
+        assert_eq!(read_line::<DaemonMessage>(&mut reader).unwrap(), None);
+    }
+}
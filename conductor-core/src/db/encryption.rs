@@ -0,0 +1,412 @@
+//! Optional at-rest encryption of the conductor database with SQLCipher.
+//!
+//! Ticket bodies and agent results can hold sensitive internal data, so
+//! `[database] encryption = "sqlcipher"` keys every connection opened through
+//! [`super::open_connection`] before anything reads the file. The key comes
+//! from the OS keychain, where a random one is generated and stored the first
+//! time the database is created, or from an environment variable.
+//! [`encrypt_database`] converts an existing plaintext database
+//! (`conductor db encrypt`).
+//!
+//! SQLCipher is only linked into builds with the `sqlcipher` feature; a plain
+//! build refuses to open a database configured as encrypted rather than
+//! silently writing plaintext.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, RwLock};
+
+use rand_core::{OsRng, RngCore};
+use rusqlite::{Connection, ErrorCode, OptionalExtension};
+
+use crate::config::{DatabaseConfig, DatabaseKeySource};
+use crate::error::{ConductorError, Result};
+
+static SETTINGS: RwLock<DatabaseConfig> = RwLock::new(DatabaseConfig::DEFAULT);
+
+/// Key read from the keychain, cached so opening a connection does not shell
+/// out every time.
+static KEYCHAIN_KEY: Mutex<Option<String>> = Mutex::new(None);
+
+/// Keychain service and account the generated key is stored under.
+const KEYCHAIN_SERVICE: &str = "conductor";
+const KEYCHAIN_ACCOUNT: &str = "database";
+const KEYCHAIN_LABEL: &str = "conductor database key";
+
+/// `security` exit status for "The specified item could not be found".
+const MACOS_ITEM_NOT_FOUND: i32 = 44;
+
+/// Replace the encryption settings used by every connection opened after
+/// this call.
+pub fn set_settings(settings: &DatabaseConfig) {
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+}
+
+/// The encryption settings currently in effect.
+pub fn settings() -> DatabaseConfig {
+    SETTINGS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Key `conn` per `settings` and check the key opens the file. `creating`
+/// means the database file did not exist before `conn` was opened, so a
+/// missing keychain key may be generated.
+pub(super) fn apply_key(
+    conn: &Connection,
+    path: &Path,
+    settings: &DatabaseConfig,
+    creating: bool,
+) -> Result<()> {
+    if !settings.encrypted() {
+        return Ok(());
+    }
+    ensure_sqlcipher(conn)?;
+    let key = resolve_key(settings, creating)?;
+    conn.pragma_update(None, "key", key_pragma(&key))?;
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|e| match e.sqlite_error_code() {
+            Some(ErrorCode::NotADatabase) => ConductorError::Config(format!(
+                "cannot decrypt {}: the key is wrong, or the database is still plaintext \
+                 (run `conductor db encrypt` to convert it)",
+                path.display()
+            )),
+            _ => ConductorError::Database(e),
+        })
+}
+
+/// Encrypt the plaintext database at `path` in place with the key from
+/// `settings`, generating a keychain key if there is none yet.
+///
+/// The database is exported to a sibling file which then replaces the
+/// original, so nothing else may have it open: stop the TUI, web server and
+/// daemon first.
+pub fn encrypt_database(path: &Path, settings: &DatabaseConfig) -> Result<()> {
+    let conn = Connection::open(path)?;
+    ensure_sqlcipher(&conn)?;
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|_| {
+            ConductorError::Config(format!(
+                "{} is not a plaintext SQLite database; is it already encrypted?",
+                path.display()
+            ))
+        })?;
+    let key = resolve_key(settings, true)?;
+
+    let target = sibling(path, "encrypting");
+    remove_if_exists(&target)?;
+    conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        rusqlite::params![target.to_string_lossy(), key_pragma(&key)],
+    )?;
+    let exported = conn
+        .query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+        .and_then(|()| conn.execute_batch("DETACH DATABASE encrypted"));
+    drop(conn);
+    if let Err(e) = exported {
+        let _ = std::fs::remove_file(&target);
+        return Err(e.into());
+    }
+
+    // The export read through the WAL, so the plaintext WAL and shared-memory
+    // files are stale and must not be replayed against the encrypted file.
+    std::fs::rename(&target, path)?;
+    remove_if_exists(&sibling(path, "-wal"))?;
+    remove_if_exists(&sibling(path, "-shm"))?;
+    Ok(())
+}
+
+/// Fail unless this build links SQLCipher; a plain SQLite build ignores
+/// `PRAGMA key` and would read and write plaintext.
+fn ensure_sqlcipher(conn: &Connection) -> Result<()> {
+    let version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    match version {
+        Some(_) => Ok(()),
+        None => Err(ConductorError::Config(
+            "database encryption needs SQLCipher, but this build does not include it; \
+             rebuild with `--features sqlcipher`"
+                .into(),
+        )),
+    }
+}
+
+fn resolve_key(settings: &DatabaseConfig, create: bool) -> Result<String> {
+    match settings.key_source {
+        DatabaseKeySource::Env => {
+            let var = settings.key_env();
+            match std::env::var(var) {
+                Ok(key) if !key.is_empty() => Ok(key),
+                _ => Err(ConductorError::Config(format!(
+                    "database encryption is on but ${var} is not set"
+                ))),
+            }
+        }
+        DatabaseKeySource::Keychain => {
+            let mut cached = KEYCHAIN_KEY.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(key) = cached.as_ref() {
+                return Ok(key.clone());
+            }
+            let key = match keychain_get()? {
+                Some(key) => key,
+                None if create => {
+                    let key = generate_key();
+                    keychain_set(&key)?;
+                    key
+                }
+                None => {
+                    return Err(ConductorError::Config(format!(
+                        "no database key in the OS keychain (service '{KEYCHAIN_SERVICE}', \
+                         account '{KEYCHAIN_ACCOUNT}')"
+                    )))
+                }
+            };
+            *cached = Some(key.clone());
+            Ok(key)
+        }
+    }
+}
+
+/// A random 256-bit key as 64 hex digits.
+fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// The `PRAGMA key` value for `key`. 64 hex digits are used as the raw key,
+/// skipping SQLCipher's deliberately slow passphrase derivation on every
+/// open; anything else is a passphrase.
+fn key_pragma(key: &str) -> String {
+    if key.len() == 64 && key.bytes().all(|b| b.is_ascii_hexdigit()) {
+        format!("x'{key}'")
+    } else {
+        key.to_string()
+    }
+}
+
+fn keychain_get() -> Result<Option<String>> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("security");
+        cmd.args(["find-generic-password", "-s", KEYCHAIN_SERVICE])
+            .args(["-a", KEYCHAIN_ACCOUNT, "-w"]);
+        cmd
+    } else {
+        let mut cmd = Command::new("secret-tool");
+        cmd.args(["lookup", "service", KEYCHAIN_SERVICE])
+            .args(["account", KEYCHAIN_ACCOUNT]);
+        cmd
+    };
+    let output = crate::command::output(&mut cmd).map_err(|e| keychain_error(&cmd, e))?;
+    lookup_result(&output)
+}
+
+/// The key from a keychain lookup's output. Only a missing item is `None`;
+/// a locked keychain, denied access or any other failure is an error, so a
+/// new key is never generated over an existing one that could not be read.
+fn lookup_result(output: &std::process::Output) -> Result<Option<String>> {
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() {
+        return Ok((!key.is_empty()).then_some(key));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    // `security` has a dedicated status for a missing item; `secret-tool
+    // lookup` exits 1 without a message.
+    let not_found = if cfg!(target_os = "macos") {
+        output.status.code() == Some(MACOS_ITEM_NOT_FOUND)
+    } else {
+        output.status.code() == Some(1) && stderr.is_empty()
+    };
+    if not_found {
+        return Ok(None);
+    }
+    Err(ConductorError::Config(format!(
+        "could not read the database key from the OS keychain ({}): {stderr}",
+        output.status
+    )))
+}
+
+/// Store `key`, passing it on stdin so it never shows up in the process
+/// list.
+fn keychain_set(key: &str) -> Result<()> {
+    let (mut cmd, input) = if cfg!(target_os = "macos") {
+        // `security -i` reads its commands from stdin, so the key is only
+        // part of the command text, not of `security`'s argv.
+        let mut cmd = Command::new("security");
+        cmd.arg("-i");
+        let input = format!(
+            "add-generic-password -U -s {KEYCHAIN_SERVICE} -a {KEYCHAIN_ACCOUNT} \
+             -l \"{KEYCHAIN_LABEL}\" -w {key}\n"
+        );
+        (cmd, input)
+    } else {
+        // secret-tool reads the secret from stdin, keeping it off the command line.
+        let mut cmd = Command::new("secret-tool");
+        cmd.args([
+            "store",
+            "--label",
+            KEYCHAIN_LABEL,
+            "service",
+            KEYCHAIN_SERVICE,
+        ])
+        .args(["account", KEYCHAIN_ACCOUNT]);
+        (cmd, key.to_string())
+    };
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| keychain_error(&cmd, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(ConductorError::Config(format!(
+            "could not store the database key in the OS keychain: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+fn keychain_error(cmd: &Command, e: std::io::Error) -> ConductorError {
+    ConductorError::Config(format!(
+        "OS keychain unavailable ({}: {e}); set [database] key_source = \"env\" instead",
+        crate::command::describe(cmd)
+    ))
+}
+
+/// `path` with `suffix` appended to its file name: `conductor.db-wal`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    if !suffix.starts_with('-') {
+        name.push(".");
+    }
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseEncryption;
+
+    fn env_settings(var: &str) -> DatabaseConfig {
+        DatabaseConfig {
+            encryption: DatabaseEncryption::Sqlcipher,
+            key_source: DatabaseKeySource::Env,
+            key_env: Some(var.to_string()),
+        }
+    }
+
+    #[test]
+    fn key_pragma_uses_hex_keys_raw() {
+        let hex = "ab".repeat(32);
+        assert_eq!(key_pragma(&hex), format!("x'{hex}'"));
+        assert_eq!(key_pragma("correct horse"), "correct horse");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keychain_lookup_only_treats_a_missing_item_as_none() {
+        use std::os::unix::process::ExitStatusExt;
+        let output = |code: i32, stdout: &str, stderr: &str| std::process::Output {
+            status: std::process::ExitStatus::from_raw(code << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        };
+        assert_eq!(
+            lookup_result(&output(0, "abc\n", "")).unwrap().as_deref(),
+            Some("abc")
+        );
+        let missing = if cfg!(target_os = "macos") {
+            output(MACOS_ITEM_NOT_FOUND, "", "item could not be found")
+        } else {
+            output(1, "", "")
+        };
+        assert_eq!(lookup_result(&missing).unwrap(), None);
+        let err = lookup_result(&output(1, "", "keychain is locked")).unwrap_err();
+        assert!(err.to_string().contains("keychain is locked"), "{err}");
+        assert!(lookup_result(&output(2, "", "")).is_err());
+    }
+
+    #[test]
+    fn sibling_appends_to_the_file_name() {
+        let path = Path::new("/tmp/conductor.db");
+        assert_eq!(sibling(path, "-wal"), Path::new("/tmp/conductor.db-wal"));
+        assert_eq!(
+            sibling(path, "encrypting"),
+            Path::new("/tmp/conductor.db.encrypting")
+        );
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn plain_build_refuses_an_encrypted_config() {
+        std::env::set_var("CONDUCTOR_TEST_DB_KEY_PLAIN", "secret");
+        let conn = Connection::open_in_memory().unwrap();
+        let err = apply_key(
+            &conn,
+            Path::new(":memory:"),
+            &env_settings("CONDUCTOR_TEST_DB_KEY_PLAIN"),
+            true,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::Config(msg) if msg.contains("--features sqlcipher")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn encrypt_database_converts_plaintext_and_needs_the_key() {
+        std::env::set_var("CONDUCTOR_TEST_DB_KEY_ENCRYPT", "ab".repeat(32));
+        let settings = env_settings("CONDUCTOR_TEST_DB_KEY_ENCRYPT");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conductor.db");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "PRAGMA journal_mode = wal;
+                 CREATE TABLE t (v TEXT);
+                 INSERT INTO t VALUES ('secret ticket body');",
+            )
+            .unwrap();
+        }
+
+        encrypt_database(&path, &settings).unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!raw.windows(18).any(|w| w == b"secret ticket body"));
+        assert!(!sibling(&path, "-wal").exists());
+
+        let conn = Connection::open(&path).unwrap();
+        apply_key(&conn, &path, &settings, false).unwrap();
+        let v: String = conn.query_row("SELECT v FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(v, "secret ticket body");
+
+        std::env::set_var("CONDUCTOR_TEST_DB_KEY_WRONG", "cd".repeat(32));
+        let conn = Connection::open(&path).unwrap();
+        let err = apply_key(
+            &conn,
+            &path,
+            &env_settings("CONDUCTOR_TEST_DB_KEY_WRONG"),
+            false,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::Config(msg) if msg.contains("cannot decrypt")),
+            "unexpected error: {err:?}"
+        );
+    }
+}
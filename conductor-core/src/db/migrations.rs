@@ -5,7 +5,7 @@ use crate::error::{ConductorError, Result};
 
 /// The highest migration version this binary knows about.
 /// **When adding a new migration, update this constant to match the new version.**
-pub const LATEST_SCHEMA_VERSION: u32 = 86;
+pub const LATEST_SCHEMA_VERSION: u32 = 114;
 
 /// Legacy plan step shape used only for migrating JSON data from agent_runs.plan.
 #[derive(Deserialize)]
@@ -1315,6 +1315,311 @@ pub fn run(conn: &Connection) -> Result<()> {
         bump_version(conn, 86)?;
     }
 
+    // Migration 087: add sprint / sprint_current columns to tickets so sprint,
+    // iteration, and milestone metadata from the source can drive filtering.
+    // Guarded like 085/086 for minimal unit-test fixtures without `tickets`.
+    if version < 87 {
+        if table_exists(conn, "tickets")? {
+            let has_sprint: bool = conn.prepare("SELECT sprint FROM tickets LIMIT 0").is_ok();
+            if !has_sprint {
+                conn.execute_batch(include_str!("migrations/087_ticket_sprint.sql"))?;
+            }
+        }
+        bump_version(conn, 87)?;
+    }
+
+    // Migration 088: widen the worktrees.status CHECK constraint with the
+    // needs_review / parked / promoted states. Guard skips when the table is
+    // absent or already accepts the new states.
+    if version < 88 {
+        // Minimal fixture DBs may lack the table or its later columns; skip those.
+        let needs_widening = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master \
+             WHERE type='table' AND name='worktrees' AND sql NOT LIKE '%needs_review%'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        let has_columns = needs_widening
+            && conn
+                .prepare(
+                    "SELECT ticket_id, completed_at, model, base_branch FROM worktrees LIMIT 0",
+                )
+                .is_ok();
+        if has_columns {
+            with_foreign_keys_off(conn, || {
+                conn.execute_batch(include_str!("migrations/088_worktree_status_states.sql"))?;
+                Ok(())
+            })?;
+        }
+        bump_version(conn, 88)?;
+    }
+
+    // Migration 089: changesets — groups of worktrees across repos that ship together.
+    if version < 89 {
+        if !table_exists(conn, "changesets")? {
+            conn.execute_batch(include_str!("migrations/089_changesets.sql"))?;
+        }
+        bump_version(conn, 89)?;
+    }
+
+    // Migration 090: agent_queue — agent runs waiting for a free slot, by priority.
+    if version < 90 {
+        if !table_exists(conn, "agent_queue")? {
+            conn.execute_batch(include_str!("migrations/090_agent_queue.sql"))?;
+        }
+        bump_version(conn, 90)?;
+    }
+
+    // Migration 091: watches — ticket/worktree watch subscriptions.
+    if version < 91 {
+        if !table_exists(conn, "watches")? {
+            conn.execute_batch(include_str!("migrations/091_watches.sql"))?;
+        }
+        bump_version(conn, 91)?;
+    }
+
+    // Migration 092: agent_run_reviews — inbox of unreviewed finished runs.
+    if version < 92 {
+        if !table_exists(conn, "agent_run_reviews")? {
+            conn.execute_batch(include_str!("migrations/092_agent_run_reviews.sql"))?;
+            // Runs that finished before the inbox existed count as reviewed so
+            // upgrading does not flood it.
+            // Minimal fixture DBs may lack the table or its columns; skip those.
+            if conn
+                .prepare("SELECT id, status FROM agent_runs LIMIT 0")
+                .is_ok()
+            {
+                conn.execute_batch(
+                    "INSERT INTO agent_run_reviews (run_id, reviewed_at) \
+                     SELECT id, strftime('%Y-%m-%dT%H:%M:%SZ', 'now') FROM agent_runs \
+                     WHERE status IN ('completed', 'failed', 'cancelled')",
+                )?;
+            }
+        }
+        bump_version(conn, 92)?;
+    }
+
+    // Migration 093: api_tokens — hashed, scoped tokens for the web API.
+    if version < 93 {
+        if !table_exists(conn, "api_tokens")? {
+            conn.execute_batch(include_str!("migrations/093_api_tokens.sql"))?;
+        }
+        bump_version(conn, 93)?;
+    }
+
+    // Migration 094: agent_run_alerts — cost and progress threshold alerts.
+    if version < 94 {
+        if !table_exists(conn, "agent_run_alerts")? {
+            conn.execute_batch(include_str!("migrations/094_agent_run_alerts.sql"))?;
+        }
+        bump_version(conn, 94)?;
+    }
+
+    // Migration 095: worktree_slug_reservations — slugs held by in-flight creates.
+    if version < 95 {
+        if !table_exists(conn, "worktree_slug_reservations")? {
+            conn.execute_batch(include_str!(
+                "migrations/095_worktree_slug_reservations.sql"
+            ))?;
+        }
+        bump_version(conn, 95)?;
+    }
+
+    // Migration 096: add rotated_from_session_id to agent_runs.
+    if version < 96 {
+        let has_rotated_from: bool = conn
+            .prepare("SELECT rotated_from_session_id FROM agent_runs LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "agent_runs")? && !has_rotated_from {
+            conn.execute_batch(include_str!(
+                "migrations/096_agent_run_session_rotation.sql"
+            ))?;
+        }
+        bump_version(conn, 96)?;
+    }
+
+    // Migration 097: worktree_diff_reviews — agent reviews of a worktree's diff.
+    if version < 97 {
+        if !table_exists(conn, "worktree_diff_reviews")? {
+            conn.execute_batch(include_str!("migrations/097_worktree_diff_reviews.sql"))?;
+        }
+        bump_version(conn, 97)?;
+    }
+
+    // Migration 098: detected toolchain columns on repos.
+    if version < 98 {
+        let has_language: bool = conn.prepare("SELECT language FROM repos LIMIT 0").is_ok();
+        if table_exists(conn, "repos")? && !has_language {
+            conn.execute_batch(include_str!("migrations/098_repo_toolchain.sql"))?;
+        }
+        bump_version(conn, 98)?;
+    }
+
+    // Migration 099: notifications + per-reader receipts for the web notification center.
+    if version < 99 {
+        if !table_exists(conn, "notifications")? {
+            conn.execute_batch(include_str!("migrations/099_notifications.sql"))?;
+        }
+        bump_version(conn, 99)?;
+    }
+
+    // Migration 100: agent_run_launch_guards — dirty-worktree choices made at agent launch.
+    if version < 100 {
+        if !table_exists(conn, "agent_run_launch_guards")? {
+            conn.execute_batch(include_str!("migrations/100_agent_run_launch_guards.sql"))?;
+        }
+        bump_version(conn, 100)?;
+    }
+
+    // Migration 101: ticket_attachments — images and files referenced by synced tickets.
+    if version < 101 {
+        if !table_exists(conn, "ticket_attachments")? {
+            conn.execute_batch(include_str!("migrations/101_ticket_attachments.sql"))?;
+        }
+        bump_version(conn, 101)?;
+    }
+
+    // Migration 102: add environment to agent_runs.
+    if version < 102 {
+        let has_environment: bool = conn
+            .prepare("SELECT environment FROM agent_runs LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "agent_runs")? && !has_environment {
+            conn.execute_batch(include_str!("migrations/102_agent_run_environment.sql"))?;
+        }
+        bump_version(conn, 102)?;
+    }
+
+    // Migration 103: add abandon_reason to worktrees.
+    if version < 103 {
+        let has_reason: bool = conn
+            .prepare("SELECT abandon_reason FROM worktrees LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "worktrees")? && !has_reason {
+            conn.execute_batch(include_str!("migrations/103_worktree_abandon_reason.sql"))?;
+        }
+        bump_version(conn, 103)?;
+    }
+
+    // Migration 104: ticket_grooming_proposals — suggestions from the grooming pass.
+    if version < 104 {
+        if !table_exists(conn, "ticket_grooming_proposals")? {
+            conn.execute_batch(include_str!("migrations/104_ticket_grooming.sql"))?;
+        }
+        bump_version(conn, 104)?;
+    }
+
+    // Migration 105: change_log and the triggers that fill it. The triggers
+    // need every watched table; some unit tests build minimal fixtures that
+    // omit a few of them.
+    if version < 105 {
+        if !table_exists(conn, "change_log")? {
+            conn.execute_batch(include_str!("migrations/105_change_log.sql"))?;
+        }
+        let mut has_watched_tables = true;
+        for table in [
+            "repos",
+            "worktrees",
+            "tickets",
+            "agent_runs",
+            "workflow_runs",
+            "workflow_run_steps",
+            "feedback_requests",
+        ] {
+            has_watched_tables &= table_exists(conn, table)?;
+        }
+        if has_watched_tables {
+            conn.execute_batch(include_str!("migrations/105_change_log_triggers.sql"))?;
+        }
+        bump_version(conn, 105)?;
+    }
+
+    // Migration 106: conflict resolutions for rebases stopped on conflicts.
+    if version < 106 {
+        if !table_exists(conn, "worktree_conflict_resolutions")? {
+            conn.execute_batch(include_str!(
+                "migrations/106_worktree_conflict_resolutions.sql"
+            ))?;
+        }
+        bump_version(conn, 106)?;
+    }
+
+    // Migration 107: repos private to one API token.
+    if version < 107 {
+        if !table_exists(conn, "repo_visibility")? {
+            conn.execute_batch(include_str!("migrations/107_repo_visibility.sql"))?;
+        }
+        bump_version(conn, 107)?;
+    }
+
+    // Migration 108: follow-up runs chained on an agent run's outcome.
+    if version < 108 {
+        if !table_exists(conn, "agent_run_chains")? {
+            conn.execute_batch(include_str!("migrations/108_agent_run_chains.sql"))?;
+        }
+        bump_version(conn, 108)?;
+    }
+
+    // Migration 109: flag live worktrees whose checkout directory disappeared.
+    if version < 109 {
+        let has_column: bool = conn
+            .prepare("SELECT missing_since FROM worktrees LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "worktrees")? && !has_column {
+            conn.execute_batch(include_str!("migrations/109_worktree_missing_since.sql"))?;
+        }
+        bump_version(conn, 109)?;
+    }
+
+    // Migration 110: unacknowledged upstream changes to a worktree's ticket.
+    if version < 110 {
+        let has_column: bool = conn
+            .prepare("SELECT ticket_change FROM worktrees LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "worktrees")? && !has_column {
+            conn.execute_batch(include_str!("migrations/110_worktree_ticket_change.sql"))?;
+        }
+        bump_version(conn, 110)?;
+    }
+
+    // Migration 111: expiring read-only share links to agent runs.
+    if version < 111 {
+        if !table_exists(conn, "agent_run_shares")? {
+            conn.execute_batch(include_str!("migrations/111_agent_run_shares.sql"))?;
+        }
+        bump_version(conn, 111)?;
+    }
+
+    // Migration 112: TODO/FIXME comments introduced on a worktree's branch.
+    if version < 112 {
+        if !table_exists(conn, "worktree_todos")? {
+            conn.execute_batch(include_str!("migrations/112_worktree_todos.sql"))?;
+        }
+        bump_version(conn, 112)?;
+    }
+
+    // Migration 113: when each ticket was last seen opening, for SLA ages.
+    if version < 113 {
+        let has_column: bool = conn
+            .prepare("SELECT opened_at FROM tickets LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "tickets")? && !has_column {
+            conn.execute_batch(include_str!("migrations/113_ticket_opened_at.sql"))?;
+        }
+        bump_version(conn, 113)?;
+    }
+
+    // Migration 114: the repo each notification is about, for visibility.
+    if version < 114 {
+        let has_column: bool = conn
+            .prepare("SELECT repo_id FROM notifications LIMIT 0")
+            .is_ok();
+        if table_exists(conn, "notifications")? && !has_column {
+            conn.execute_batch(include_str!("migrations/114_notification_repo.sql"))?;
+        }
+        bump_version(conn, 114)?;
+    }
+
     Ok(())
 }
 
@@ -2929,4 +3234,64 @@ mod tests {
             "schema_version must still be bumped to LATEST_SCHEMA_VERSION"
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Migration 088 tests
+    // -----------------------------------------------------------------------
+
+    /// Verifies that migration 088 widens the worktrees status CHECK so the new
+    /// lifecycle states can be stored, and that existing rows survive the rebuild.
+    #[test]
+    fn test_migration_088_allows_new_worktree_statuses() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE _conductor_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             CREATE TABLE repos (id TEXT PRIMARY KEY);
+             CREATE TABLE tickets (id TEXT PRIMARY KEY);
+             INSERT INTO repos VALUES ('r1');
+             CREATE TABLE worktrees (
+                 id TEXT PRIMARY KEY,
+                 repo_id TEXT NOT NULL,
+                 slug TEXT NOT NULL,
+                 branch TEXT NOT NULL,
+                 path TEXT NOT NULL,
+                 ticket_id TEXT,
+                 status TEXT NOT NULL DEFAULT 'active'
+                     CHECK (status IN ('active', 'merged', 'abandoned')),
+                 created_at TEXT NOT NULL,
+                 completed_at TEXT,
+                 model TEXT,
+                 base_branch TEXT,
+                 UNIQUE(repo_id, slug)
+             );
+             INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at)
+             VALUES ('w1', 'r1', 'feat-a', 'feat/a', '/tmp/a', 'merged', '2024-01-01T00:00:00Z');
+             INSERT INTO _conductor_meta VALUES ('schema_version', '87');",
+        )
+        .unwrap();
+
+        run(&conn).expect("migration 088 must succeed");
+
+        for (id, status) in [("w2", "needs_review"), ("w3", "parked"), ("w4", "promoted")] {
+            conn.execute(
+                "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at) \
+                 VALUES (?1, 'r1', ?1, 'b', '/tmp/x', ?2, '2024-01-01T00:00:00Z')",
+                rusqlite::params![id, status],
+            )
+            .unwrap_or_else(|e| panic!("status '{status}' must be accepted: {e}"));
+        }
+        let bogus = conn.execute(
+            "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at) \
+             VALUES ('w5', 'r1', 'w5', 'b', '/tmp/x', 'bogus', '2024-01-01T00:00:00Z')",
+            [],
+        );
+        assert!(bogus.is_err(), "unknown statuses must still be rejected");
+
+        let kept: String = conn
+            .query_row("SELECT status FROM worktrees WHERE id = 'w1'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(kept, "merged");
+    }
 }
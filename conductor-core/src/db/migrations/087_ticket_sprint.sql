@@ -0,0 +1,2 @@
+ALTER TABLE tickets ADD COLUMN sprint TEXT;
+ALTER TABLE tickets ADD COLUMN sprint_current INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,40 @@
+-- Migration 088: widen the worktrees.status CHECK constraint with the
+-- 'needs_review', 'parked', and 'promoted' lifecycle states.
+-- SQLite cannot ALTER CHECK constraints in-place; table-recreation pattern required.
+-- Must be run with PRAGMA foreign_keys = OFF (handled in Rust via with_foreign_keys_off).
+--
+-- Columns preserved verbatim:
+--   base set (001): id, repo_id, slug, branch, path, ticket_id, status, created_at
+--   002: completed_at
+--   008: model
+--   022: base_branch
+
+BEGIN;
+
+CREATE TABLE worktrees_new (
+    id           TEXT PRIMARY KEY,
+    repo_id      TEXT NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    slug         TEXT NOT NULL,
+    branch       TEXT NOT NULL,
+    path         TEXT NOT NULL,
+    ticket_id    TEXT REFERENCES tickets(id) ON DELETE SET NULL,
+    status       TEXT NOT NULL DEFAULT 'active'
+                 CHECK (status IN ('active','merged','abandoned','needs_review','parked','promoted')),
+    created_at   TEXT NOT NULL,
+    completed_at TEXT,
+    model        TEXT,
+    base_branch  TEXT,
+    UNIQUE(repo_id, slug)
+);
+
+INSERT INTO worktrees_new SELECT
+    id, repo_id, slug, branch, path, ticket_id, status, created_at,
+    completed_at, model, base_branch
+    FROM worktrees;
+
+DROP TABLE worktrees;
+ALTER TABLE worktrees_new RENAME TO worktrees;
+
+CREATE INDEX IF NOT EXISTS idx_worktrees_repo_base_branch ON worktrees(repo_id, base_branch);
+
+COMMIT;
@@ -0,0 +1,15 @@
+CREATE TABLE changesets (
+  id         TEXT PRIMARY KEY,
+  title      TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
+
+-- One row per worktree in a changeset. `pr_url` is filled in by
+-- `conductor changeset pr` once the member's PR has been opened.
+CREATE TABLE changeset_worktrees (
+  changeset_id TEXT NOT NULL REFERENCES changesets(id) ON DELETE CASCADE,
+  worktree_id  TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  position     INTEGER NOT NULL,
+  pr_url       TEXT,
+  PRIMARY KEY (changeset_id, worktree_id)
+);
@@ -0,0 +1,10 @@
+-- Agent runs waiting for a free slot. Rows are deleted when dispatched, at
+-- which point the run gets a normal `agent_runs` row.
+CREATE TABLE agent_queue (
+  id          TEXT PRIMARY KEY,
+  worktree_id TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  prompt      TEXT NOT NULL,
+  priority    TEXT NOT NULL DEFAULT 'normal'
+              CHECK (priority IN ('urgent', 'normal', 'background')),
+  created_at  TEXT NOT NULL
+);
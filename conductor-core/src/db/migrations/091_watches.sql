@@ -0,0 +1,12 @@
+-- Tickets and worktrees the user watches. `last_state` and `last_pr` hold the
+-- values seen at the previous notification poll so each change fires once.
+-- No foreign key: target_id points at either table, and watches on deleted
+-- items are pruned by the poller.
+CREATE TABLE watches (
+  target_type TEXT NOT NULL CHECK (target_type IN ('ticket', 'worktree')),
+  target_id   TEXT NOT NULL,
+  last_state  TEXT,
+  last_pr     TEXT,
+  created_at  TEXT NOT NULL,
+  PRIMARY KEY (target_type, target_id)
+);
@@ -0,0 +1,6 @@
+-- Finished agent runs the user has looked at. A finished run without a row
+-- here is listed in the agent inbox.
+CREATE TABLE agent_run_reviews (
+  run_id      TEXT PRIMARY KEY REFERENCES agent_runs(id) ON DELETE CASCADE,
+  reviewed_at TEXT NOT NULL
+);
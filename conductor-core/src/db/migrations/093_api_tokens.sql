@@ -0,0 +1,12 @@
+-- API tokens for conductor-web. Only the SHA-256 hash of the secret is stored;
+-- `prefix` keeps the first characters so users can tell tokens apart.
+CREATE TABLE api_tokens (
+  id           TEXT PRIMARY KEY,
+  name         TEXT NOT NULL,
+  scope        TEXT NOT NULL CHECK (scope IN ('read-only', 'agent-control', 'admin')),
+  token_hash   TEXT NOT NULL UNIQUE,
+  prefix       TEXT NOT NULL,
+  created_at   TEXT NOT NULL,
+  last_used_at TEXT,
+  revoked_at   TEXT
+);
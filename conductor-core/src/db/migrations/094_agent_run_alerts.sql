@@ -0,0 +1,11 @@
+-- Cost and progress alerts raised on agent runs. Each kind is raised at most
+-- once per run; `detail` is the human-readable reason shown in notifications.
+CREATE TABLE agent_run_alerts (
+  run_id     TEXT NOT NULL REFERENCES agent_runs(id) ON DELETE CASCADE,
+  kind       TEXT NOT NULL
+             CHECK (kind IN ('run_cost', 'daily_spend', 'turns_without_commits')),
+  detail     TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (run_id, kind)
+);
+CREATE INDEX idx_agent_run_alerts_created ON agent_run_alerts(created_at);
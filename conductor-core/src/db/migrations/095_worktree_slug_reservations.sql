@@ -0,0 +1,9 @@
+-- Slugs held by worktree creations in progress. A create inserts its slug here
+-- before any git work, so a concurrent create for the same name hits the
+-- primary key and moves on to `<slug>-2` instead of racing on the branch.
+CREATE TABLE worktree_slug_reservations (
+  repo_id     TEXT NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+  slug        TEXT NOT NULL,
+  reserved_at TEXT NOT NULL,
+  PRIMARY KEY (repo_id, slug)
+);
@@ -0,0 +1,3 @@
+-- Session this run rotated away from when the resumed Claude session hit the
+-- configured turn limit. NULL when the run resumed (or started) normally.
+ALTER TABLE agent_runs ADD COLUMN rotated_from_session_id TEXT;
@@ -0,0 +1,14 @@
+-- Agent code reviews of a worktree's diff against its base. The review text is
+-- the linked run's result; this table records what the agent was shown.
+CREATE TABLE worktree_diff_reviews (
+  id            TEXT PRIMARY KEY,
+  worktree_id   TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  run_id        TEXT NOT NULL REFERENCES agent_runs(id) ON DELETE CASCADE,
+  base_ref      TEXT NOT NULL,
+  files_changed INTEGER NOT NULL,
+  truncated     INTEGER NOT NULL DEFAULT 0,
+  created_at    TEXT NOT NULL
+);
+
+CREATE INDEX idx_worktree_diff_reviews_worktree
+  ON worktree_diff_reviews (worktree_id, created_at);
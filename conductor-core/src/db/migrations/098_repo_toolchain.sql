@@ -0,0 +1,5 @@
+-- Toolchain detected from the repo's marker files (see toolchain.rs). NULL
+-- until detection runs or when nothing recognizable was found.
+ALTER TABLE repos ADD COLUMN language TEXT;
+ALTER TABLE repos ADD COLUMN package_manager TEXT;
+ALTER TABLE repos ADD COLUMN test_framework TEXT;
@@ -0,0 +1,21 @@
+-- Notification center shown in the web UI. Notifications are shared; read and
+-- cleared state is kept per reader (an API token id, or 'local' without one).
+CREATE TABLE notifications (
+  id         TEXT PRIMARY KEY,
+  kind       TEXT NOT NULL,
+  severity   TEXT NOT NULL CHECK (severity IN ('info', 'success', 'warning', 'error')),
+  title      TEXT NOT NULL,
+  body       TEXT NOT NULL DEFAULT '',
+  url        TEXT,
+  created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_notifications_created_at ON notifications (created_at);
+
+CREATE TABLE notification_receipts (
+  notification_id TEXT NOT NULL REFERENCES notifications(id) ON DELETE CASCADE,
+  reader          TEXT NOT NULL,
+  read_at         TEXT,
+  cleared_at      TEXT,
+  PRIMARY KEY (notification_id, reader)
+);
@@ -0,0 +1,13 @@
+-- Uncommitted changes found in a worktree when an agent was launched on it,
+-- and what the user chose to do about them. `saved_ref` is the stash or WIP
+-- commit SHA; stashes are popped once the run ends.
+CREATE TABLE agent_run_launch_guards (
+  run_id        TEXT PRIMARY KEY REFERENCES agent_runs(id) ON DELETE CASCADE,
+  worktree_id   TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  choice        TEXT NOT NULL CHECK (choice IN ('stash', 'commit_wip', 'proceed')),
+  dirty_files   TEXT NOT NULL DEFAULT '[]',
+  saved_ref     TEXT,
+  restored_at   TEXT,
+  restore_error TEXT,
+  created_at    TEXT NOT NULL
+);
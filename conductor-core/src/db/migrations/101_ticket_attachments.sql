@@ -0,0 +1,16 @@
+-- Files referenced by a ticket: images embedded in its body and attachments
+-- reported by the source (e.g. Jira). `local_path` is set once a small image
+-- has been downloaded; `download_error` stops it being retried every sync.
+CREATE TABLE ticket_attachments (
+  id             TEXT PRIMARY KEY,
+  ticket_id      TEXT NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+  url            TEXT NOT NULL,
+  filename       TEXT NOT NULL,
+  mime_type      TEXT,
+  size_bytes     INTEGER,
+  local_path     TEXT,
+  downloaded_at  TEXT,
+  download_error TEXT,
+  created_at     TEXT NOT NULL,
+  UNIQUE(ticket_id, url)
+);
@@ -0,0 +1,3 @@
+-- JSON-encoded RunEnvironment (claude CLI version, model, worktree HEAD, OS,
+-- conductor version) captured when the run started. NULL for older runs.
+ALTER TABLE agent_runs ADD COLUMN environment TEXT;
@@ -0,0 +1,3 @@
+-- Why an unmerged worktree was deleted: superseded, wontfix, blocked or
+-- experiment. NULL for merged worktrees and for ones deleted without a reason.
+ALTER TABLE worktrees ADD COLUMN abandon_reason TEXT;
@@ -0,0 +1,20 @@
+-- Grooming proposals from the cheap agent pass over newly synced tickets.
+-- Proposals are only suggestions: `status` stays 'pending' until someone
+-- accepts or dismisses them. `cost_usd` feeds the per-day budget.
+CREATE TABLE ticket_grooming_proposals (
+  id              TEXT PRIMARY KEY,
+  ticket_id       TEXT NOT NULL UNIQUE REFERENCES tickets(id) ON DELETE CASCADE,
+  labels          TEXT NOT NULL DEFAULT '[]',
+  estimate        TEXT,
+  duplicates      TEXT NOT NULL DEFAULT '[]',
+  workflow        TEXT,
+  rationale       TEXT,
+  model           TEXT,
+  cost_usd        REAL NOT NULL DEFAULT 0,
+  status          TEXT NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'accepted', 'dismissed')),
+  created_at      TEXT NOT NULL,
+  decided_at      TEXT
+);
+
+CREATE INDEX idx_ticket_grooming_status ON ticket_grooming_proposals(status, created_at);
@@ -0,0 +1,17 @@
+-- Change log filled by triggers, so every process sharing the database (TUI,
+-- CLI, daemon, web server) can see what the others changed by polling for
+-- rows past the last id it read. Rows are short-lived: `changes::prune`
+-- drops them after a few minutes.
+--
+-- The triggers that fill it are in 105_change_log_triggers.sql.
+CREATE TABLE change_log (
+  id           INTEGER PRIMARY KEY AUTOINCREMENT,
+  table_name   TEXT NOT NULL,
+  op           TEXT NOT NULL CHECK (op IN ('insert', 'update', 'delete')),
+  row_id       TEXT NOT NULL,
+  repo_id      TEXT,
+  worktree_id  TEXT,
+  run_id       TEXT,
+  status       TEXT,
+  changed_at   TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,83 @@
+-- Triggers that fill change_log (see 105_change_log.sql). Triggers are
+-- dropped with their table, so a migration that rebuilds one of these tables
+-- must recreate its triggers.
+
+CREATE TRIGGER change_log_repos_insert AFTER INSERT ON repos BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id)
+  VALUES ('repos', 'insert', NEW.id, NEW.id);
+END;
+
+CREATE TRIGGER change_log_repos_delete AFTER DELETE ON repos BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id)
+  VALUES ('repos', 'delete', OLD.id, OLD.id);
+END;
+
+CREATE TRIGGER change_log_worktrees_insert AFTER INSERT ON worktrees BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, status)
+  VALUES ('worktrees', 'insert', NEW.id, NEW.repo_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_worktrees_update AFTER UPDATE OF status ON worktrees
+WHEN OLD.status IS NOT NEW.status BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, status)
+  VALUES ('worktrees', 'update', NEW.id, NEW.repo_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_worktrees_delete AFTER DELETE ON worktrees BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id)
+  VALUES ('worktrees', 'delete', OLD.id, OLD.repo_id, OLD.id);
+END;
+
+-- Ticket sync rewrites every row, so only changes a user would see are logged.
+CREATE TRIGGER change_log_tickets_insert AFTER INSERT ON tickets BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, status)
+  VALUES ('tickets', 'insert', NEW.id, NEW.repo_id, NEW.state);
+END;
+
+CREATE TRIGGER change_log_tickets_update AFTER UPDATE ON tickets
+WHEN OLD.state IS NOT NEW.state OR OLD.title IS NOT NEW.title
+  OR OLD.labels IS NOT NEW.labels OR OLD.assignee IS NOT NEW.assignee BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, status)
+  VALUES ('tickets', 'update', NEW.id, NEW.repo_id, NEW.state);
+END;
+
+CREATE TRIGGER change_log_agent_runs_insert AFTER INSERT ON agent_runs BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, run_id, status)
+  VALUES ('agent_runs', 'insert', NEW.id, NEW.repo_id, NEW.worktree_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_agent_runs_update AFTER UPDATE OF status ON agent_runs
+WHEN OLD.status IS NOT NEW.status BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, run_id, status)
+  VALUES ('agent_runs', 'update', NEW.id, NEW.repo_id, NEW.worktree_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_workflow_runs_insert AFTER INSERT ON workflow_runs BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, run_id, status)
+  VALUES ('workflow_runs', 'insert', NEW.id, NEW.repo_id, NEW.worktree_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_workflow_runs_update AFTER UPDATE OF status ON workflow_runs
+WHEN OLD.status IS NOT NEW.status BEGIN
+  INSERT INTO change_log (table_name, op, row_id, repo_id, worktree_id, run_id, status)
+  VALUES ('workflow_runs', 'update', NEW.id, NEW.repo_id, NEW.worktree_id, NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_workflow_run_steps_update AFTER UPDATE OF status ON workflow_run_steps
+WHEN OLD.status IS NOT NEW.status BEGIN
+  INSERT INTO change_log (table_name, op, row_id, run_id, status)
+  VALUES ('workflow_run_steps', 'update', NEW.id, NEW.workflow_run_id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_feedback_requests_insert AFTER INSERT ON feedback_requests BEGIN
+  INSERT INTO change_log (table_name, op, row_id, worktree_id, run_id, status)
+  VALUES ('feedback_requests', 'insert', NEW.id,
+          (SELECT worktree_id FROM agent_runs WHERE id = NEW.run_id), NEW.run_id, NEW.status);
+END;
+
+CREATE TRIGGER change_log_feedback_requests_update AFTER UPDATE OF status ON feedback_requests
+WHEN OLD.status IS NOT NEW.status BEGIN
+  INSERT INTO change_log (table_name, op, row_id, worktree_id, run_id, status)
+  VALUES ('feedback_requests', 'update', NEW.id,
+          (SELECT worktree_id FROM agent_runs WHERE id = NEW.run_id), NEW.run_id, NEW.status);
+END;
@@ -0,0 +1,23 @@
+-- Conflict resolutions: a rebase or merge left stopped on conflicts, the
+-- agent run (if any) asked to resolve it, and the check run on the result.
+-- The operation may only be continued once the check passed on exactly the
+-- staged tree recorded in verified_tree.
+CREATE TABLE worktree_conflict_resolutions (
+  id             TEXT PRIMARY KEY,
+  worktree_id    TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  run_id         TEXT REFERENCES agent_runs(id) ON DELETE SET NULL,
+  operation      TEXT NOT NULL CHECK (operation IN ('rebase', 'merge')),
+  onto           TEXT,
+  files          TEXT NOT NULL DEFAULT '[]',
+  status         TEXT NOT NULL DEFAULT 'resolving'
+                 CHECK (status IN ('resolving', 'verified', 'check_failed', 'continued', 'aborted')),
+  check_command  TEXT,
+  check_output   TEXT,
+  verified_tree  TEXT,
+  created_at     TEXT NOT NULL,
+  checked_at     TEXT,
+  ended_at       TEXT
+);
+
+CREATE INDEX idx_worktree_conflict_resolutions_worktree
+  ON worktree_conflict_resolutions (worktree_id, created_at);
@@ -0,0 +1,13 @@
+-- Repos marked private to one API token. A repo without a row is shared:
+-- every user of the web instance sees it. A private repo, and the worktrees,
+-- tickets and agent runs under it, is only listed for its owner.
+--
+-- owner is an api_tokens id. It is deliberately not a foreign key that
+-- cascades: revoking the owner's token must not make the repo shared.
+CREATE TABLE repo_visibility (
+  repo_id     TEXT PRIMARY KEY REFERENCES repos(id) ON DELETE CASCADE,
+  owner       TEXT NOT NULL,
+  created_at  TEXT NOT NULL
+);
+
+CREATE INDEX idx_repo_visibility_owner ON repo_visibility (owner);
@@ -0,0 +1,26 @@
+-- Follow-ups declared when launching an agent run: "if it succeeds run prompt
+-- B, if it fails run prompt C (or notify)". One row per run in a chain. The
+-- first run's row id is the chain_id shared by every later link.
+--
+-- A follow-up waits in agent_queue until a slot is free, so a link starts with
+-- queue_id set and gets its run_id once the queued run is dispatched.
+-- on_success / on_failure hold the JSON ChainStep still to come from this run;
+-- evaluated_at is set exactly once, when the run's outcome was acted on.
+CREATE TABLE agent_run_chains (
+  id               TEXT PRIMARY KEY,
+  chain_id         TEXT NOT NULL,
+  run_id           TEXT UNIQUE REFERENCES agent_runs(id) ON DELETE CASCADE,
+  queue_id         TEXT,
+  previous_run_id  TEXT REFERENCES agent_runs(id) ON DELETE SET NULL,
+  depth            INTEGER NOT NULL,
+  trigger          TEXT,
+  prompt           TEXT NOT NULL,
+  on_success       TEXT,
+  on_failure       TEXT,
+  evaluated_at     TEXT,
+  outcome          TEXT,
+  created_at       TEXT NOT NULL
+);
+
+CREATE INDEX idx_agent_run_chains_chain ON agent_run_chains (chain_id, depth);
+CREATE INDEX idx_agent_run_chains_queue ON agent_run_chains (queue_id);
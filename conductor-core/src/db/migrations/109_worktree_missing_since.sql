@@ -0,0 +1,4 @@
+-- When the periodic health check found a live worktree's checkout directory
+-- gone (e.g. removed by hand). NULL while the directory exists; cleared when
+-- the worktree is restored.
+ALTER TABLE worktrees ADD COLUMN missing_since TEXT;
@@ -0,0 +1,4 @@
+-- Upstream changes to a live worktree's linked ticket (reopened, priority,
+-- assignee) since its owner last acknowledged them, e.g.
+-- "reopened, priority P2 → P1". NULL when there is nothing to acknowledge.
+ALTER TABLE worktrees ADD COLUMN ticket_change TEXT;
@@ -0,0 +1,14 @@
+-- Read-only share links to a single agent run. Only the SHA-256 hash of the
+-- link token is stored. `diffstat` is the worktree's `git diff --stat` when
+-- the link was created, so the page stays stable as the branch moves on.
+CREATE TABLE agent_run_shares (
+  id          TEXT PRIMARY KEY,
+  run_id      TEXT NOT NULL REFERENCES agent_runs(id) ON DELETE CASCADE,
+  token_hash  TEXT NOT NULL UNIQUE,
+  diffstat    TEXT,
+  created_at  TEXT NOT NULL,
+  expires_at  TEXT NOT NULL,
+  revoked_at  TEXT
+);
+
+CREATE INDEX idx_agent_run_shares_run ON agent_run_shares (run_id);
@@ -0,0 +1,12 @@
+-- TODO and FIXME comments added on a worktree's branch, as of its last scan.
+-- A scan replaces all of a worktree's rows.
+CREATE TABLE worktree_todos (
+  worktree_id  TEXT NOT NULL REFERENCES worktrees(id) ON DELETE CASCADE,
+  file         TEXT NOT NULL,
+  line         INTEGER NOT NULL,
+  marker       TEXT NOT NULL,
+  text         TEXT NOT NULL,
+  found_at     TEXT NOT NULL
+);
+
+CREATE INDEX idx_worktree_todos_worktree ON worktree_todos (worktree_id);
@@ -0,0 +1,4 @@
+-- When conductor first synced the ticket open, or saw it reopen after being
+-- closed. SLA ages in the today view count from here; tickets synced before
+-- this column existed fall back to their last sync time.
+ALTER TABLE tickets ADD COLUMN opened_at TEXT;
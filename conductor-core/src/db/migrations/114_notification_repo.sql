@@ -0,0 +1,3 @@
+-- The repo a notification is about, so readers only see notifications for
+-- repos visible to them. NULL for notifications not tied to a repo.
+ALTER TABLE notifications ADD COLUMN repo_id TEXT;
@@ -1,16 +1,17 @@
+pub mod encryption;
 pub mod migrations;
 pub mod seed;
 
 use rusqlite::types::ToSql;
-use rusqlite::Connection;
+use rusqlite::{Connection, ErrorCode};
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::error::Result;
+use crate::error::{ConductorError, Result};
 
 /// Open (or create) the SQLite database with WAL mode enabled.
 pub fn open_database(path: &Path) -> Result<Connection> {
-    let conn = Connection::open(path)?;
+    let conn = open_connection(path)?;
     conn.pragma_update(None, "journal_mode", "wal")?;
     conn.pragma_update(None, "foreign_keys", "on")?;
     conn.pragma_update(None, "busy_timeout", 5000)?;
@@ -25,7 +26,7 @@ pub fn open_database(path: &Path) -> Result<Connection> {
 /// error.  Use this in headless subprocesses and drain threads that must keep
 /// running after an `implement` agent step has applied a newer migration.
 pub fn open_database_compat(path: &Path) -> Result<Connection> {
-    let conn = Connection::open(path)?;
+    let conn = open_connection(path)?;
     conn.pragma_update(None, "journal_mode", "wal")?;
     conn.pragma_update(None, "foreign_keys", "on")?;
     conn.pragma_update(None, "busy_timeout", 5000)?;
@@ -33,6 +34,41 @@ pub fn open_database_compat(path: &Path) -> Result<Connection> {
     Ok(conn)
 }
 
+/// Open an existing database read-only, without running migrations.
+///
+/// For hot paths such as `conductor status --porcelain` that only read a few
+/// counters and must not pay for schema checks or take a write lock.
+pub fn open_database_readonly(path: &Path) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    encryption::apply_key(&conn, path, &encryption::settings(), false)?;
+    conn.pragma_update(None, "busy_timeout", 1000)?;
+    Ok(conn)
+}
+
+/// Open `path` without pragmas or migrations, keyed first when
+/// `[database] encryption` is on. For stores that open the conductor database
+/// per call rather than holding a connection.
+pub fn open_connection(path: &Path) -> Result<Connection> {
+    let creating = !path.exists();
+    let conn = Connection::open(path)?;
+    encryption::apply_key(&conn, path, &encryption::settings(), creating)?;
+    Ok(conn)
+}
+
+/// True when `err` means another connection holds the database lock (a long
+/// migration or a busy writer) rather than anything being wrong with it.
+/// Callers can retry later or fall back to [`open_database_readonly`].
+pub fn is_busy(err: &ConductorError) -> bool {
+    matches!(
+        err,
+        ConductorError::Database(rusqlite::Error::SqliteFailure(e, _))
+            if matches!(e.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
 /// Prepend `prefix` to every column token in a comma-separated column list.
 ///
 /// Splits `cols` on `','`, trims whitespace from each token, prepends `prefix`,
@@ -161,4 +197,28 @@ mod tests {
         let bad = std::path::Path::new("/tmp/conductor_no_such_dir_xyz/test.db");
         assert!(open_database_compat(bad).is_err());
     }
+
+    #[test]
+    fn readonly_reads_while_writer_holds_lock() {
+        let tmp = NamedTempFile::new().unwrap();
+        let writer = open_database(tmp.path()).unwrap();
+        writer.execute_batch("BEGIN EXCLUSIVE;").unwrap();
+
+        let other = Connection::open(tmp.path()).unwrap();
+        let err: ConductorError = other.execute_batch("BEGIN IMMEDIATE;").unwrap_err().into();
+        assert!(is_busy(&err));
+
+        let reader = open_database_readonly(tmp.path()).unwrap();
+        let n: i64 = reader
+            .query_row("SELECT COUNT(*) FROM repos", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(n, 0);
+    }
+
+    #[test]
+    fn is_busy_ignores_other_errors() {
+        assert!(!is_busy(&ConductorError::Database(
+            rusqlite::Error::InvalidQuery
+        )));
+    }
 }
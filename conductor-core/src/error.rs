@@ -57,12 +57,49 @@ pub enum ConductorError {
     #[error("worktree already exists: {slug}")]
     WorktreeAlreadyExists { slug: String },
 
+    #[error("worktree {slug} is already being created; try again when that finishes")]
+    WorktreeCreationInProgress { slug: String },
+
     #[error("git error: {0}")]
     Git(SubprocessFailure),
 
     #[error("gh cli error: {0}")]
     GhCli(SubprocessFailure),
 
+    #[error("tmux error: {0}")]
+    Tmux(SubprocessFailure),
+
+    #[error("docker error: {0}")]
+    Docker(SubprocessFailure),
+
+    /// A command's binary is not on `PATH`; see [`crate::tools`].
+    #[error(
+        "{tool} is not installed or not on PATH; it is needed for {purpose} (install: {install})"
+    )]
+    ToolNotInstalled {
+        tool: String,
+        purpose: String,
+        install: String,
+    },
+
+    /// An external command ran past its `[timeouts]` deadline and was killed.
+    #[error("{command} timed out after {timeout_secs}s")]
+    CommandTimedOut { command: String, timeout_secs: u64 },
+
+    #[error(
+        "rebase of {slug} stopped on conflicts in {}; the rebase is still in progress",
+        files.join(", ")
+    )]
+    RebaseConflict { slug: String, files: Vec<String> },
+
+    #[error("pre-push check `{command}` failed for {slug}; nothing was pushed\n{output}")]
+    PrePushCheckFailed {
+        slug: String,
+        command: String,
+        /// The end of the check's combined stdout and stderr.
+        output: String,
+    },
+
     #[error("config error: {0}")]
     Config(String),
 
@@ -146,6 +183,40 @@ pub enum ConductorError {
 
     #[error("notification error: {0}")]
     Notification(String),
+
+    #[error("changeset not found: {id}")]
+    ChangesetNotFound { id: String },
+
+    #[error("queued agent run not found: {id} (it may already have been dispatched)")]
+    QueuedAgentRunNotFound { id: String },
+
+    #[error("API token not found: {id}")]
+    ApiTokenNotFound { id: String },
+
+    #[error("notification not found: {id}")]
+    NotificationNotFound { id: String },
+
+    #[error("grooming proposal not found: {id}")]
+    GroomingProposalNotFound { id: String },
+
+    #[error("agent run share link not found: {id}")]
+    RunShareNotFound { id: String },
+
+    #[error(
+        "workspace quota exceeded for repo '{repo_slug}': {} used of {} limit{hint}",
+        crate::worktree::format_bytes(*used_bytes),
+        crate::worktree::format_bytes(*limit_bytes)
+    )]
+    WorkspaceQuotaExceeded {
+        repo_slug: String,
+        used_bytes: u64,
+        limit_bytes: u64,
+        /// Remediation text (leading newline included) listing reclaimable directories.
+        hint: String,
+    },
+
+    #[error("agent budget exceeded: {0}")]
+    AgentBudgetExceeded(crate::agent::BudgetExceeded),
 }
 
 impl From<runkon_runtimes::RuntimeError> for ConductorError {
@@ -177,6 +248,8 @@ impl ConductorError {
         match self {
             Self::Database(_) => 10,
             Self::Io(_) => 11,
+            Self::WorkspaceQuotaExceeded { .. } => 12,
+            Self::AgentBudgetExceeded(_) => 13,
             Self::RepoNotFound { .. } => 20,
             Self::RepoAlreadyExists { .. } => 21,
             Self::WorktreeNotFound { .. } => 22,
@@ -185,9 +258,18 @@ impl ConductorError {
             Self::TicketNotFound { .. } => 25,
             Self::TicketAlreadyLinked => 26,
             Self::InvalidInput(_) => 27,
+            Self::ChangesetNotFound { .. } => 28,
+            Self::ApiTokenNotFound { .. } => 29,
             Self::Git(_) => 30,
             Self::GhCli(_) => 31,
             Self::TicketSync(_) => 32,
+            Self::WorktreeCreationInProgress { .. } => 33,
+            Self::RebaseConflict { .. } => 34,
+            Self::PrePushCheckFailed { .. } => 35,
+            Self::CommandTimedOut { .. } => 36,
+            Self::Tmux(_) => 37,
+            Self::Docker(_) => 38,
+            Self::ToolNotInstalled { .. } => 39,
             Self::Config(_) => 40,
             Self::AgentConfig(_) => 41,
             Self::Schema(_) => 42,
@@ -207,7 +289,11 @@ impl ConductorError {
             Self::UnknownSourceType(_) => 43,
             Self::ConversationNotFound { .. } => 57,
             Self::ConversationHasActiveRun { .. } => 58,
+            Self::QueuedAgentRunNotFound { .. } => 59,
             Self::Notification(_) => 70,
+            Self::NotificationNotFound { .. } => 71,
+            Self::GroomingProposalNotFound { .. } => 72,
+            Self::RunShareNotFound { .. } => 73,
         }
     }
 }
@@ -227,6 +313,7 @@ mod tests {
             ConductorError::RepoAlreadyExists { slug: "r".into() },
             ConductorError::WorktreeNotFound { slug: "w".into() },
             ConductorError::WorktreeAlreadyExists { slug: "w".into() },
+            ConductorError::WorktreeCreationInProgress { slug: "w".into() },
             ConductorError::IssueSourceAlreadyExists {
                 repo_slug: "r".into(),
                 source_type: "github".into(),
@@ -236,6 +323,26 @@ mod tests {
             ConductorError::InvalidInput("bad".into()),
             ConductorError::Git(SubprocessFailure::from_message("git", "err".into())),
             ConductorError::GhCli(SubprocessFailure::from_message("gh", "err".into())),
+            ConductorError::Tmux(SubprocessFailure::from_message("tmux", "err".into())),
+            ConductorError::Docker(SubprocessFailure::from_message("docker", "err".into())),
+            ConductorError::ToolNotInstalled {
+                tool: "gh".into(),
+                purpose: "GitHub issues".into(),
+                install: "https://cli.github.com".into(),
+            },
+            ConductorError::CommandTimedOut {
+                command: "`git fetch`".into(),
+                timeout_secs: 300,
+            },
+            ConductorError::RebaseConflict {
+                slug: "w".into(),
+                files: vec!["src/lib.rs".into()],
+            },
+            ConductorError::PrePushCheckFailed {
+                slug: "w".into(),
+                command: "cargo fmt --check".into(),
+                output: "Diff in src/lib.rs".into(),
+            },
             ConductorError::TicketSync("sync".into()),
             ConductorError::Config("cfg".into()),
             ConductorError::AgentConfig("acfg".into()),
@@ -264,6 +371,25 @@ mod tests {
             ConductorError::ConversationNotFound { id: "id".into() },
             ConductorError::ConversationHasActiveRun { id: "id".into() },
             ConductorError::Notification("notif".into()),
+            ConductorError::ChangesetNotFound { id: "c".into() },
+            ConductorError::QueuedAgentRunNotFound { id: "q".into() },
+            ConductorError::ApiTokenNotFound { id: "t".into() },
+            ConductorError::NotificationNotFound { id: "n".into() },
+            ConductorError::GroomingProposalNotFound { id: "g".into() },
+            ConductorError::RunShareNotFound { id: "s".into() },
+            ConductorError::WorkspaceQuotaExceeded {
+                repo_slug: "r".into(),
+                used_bytes: 2,
+                limit_bytes: 1,
+                hint: String::new(),
+            },
+            ConductorError::AgentBudgetExceeded(crate::agent::BudgetExceeded {
+                scope: crate::agent::BudgetScope::Worktree,
+                target_id: "w".into(),
+                target: "feat-x".into(),
+                spent_usd: 2.0,
+                limit_usd: 1.0,
+            }),
         ]
     }
 
@@ -1,5 +1,6 @@
 use std::process::Command;
 
+use crate::command::run_command;
 use crate::error::{ConductorError, Result, SubprocessFailure};
 
 /// Return a `Command` for `git` rooted at `dir`.
@@ -14,43 +15,18 @@ pub(crate) fn git_in(dir: impl AsRef<std::path::Path>) -> Command {
 }
 
 /// Run `cmd`, returning its `Output` on success or a `ConductorError::Git` on non-zero exit.
+///
+/// Runs under [`crate::command::default_timeout`]: the network deadline for
+/// `clone`/`fetch`/`pull`/`push`, the local one otherwise.
 pub(crate) fn check_output(cmd: &mut Command) -> Result<std::process::Output> {
-    run_command(cmd, ConductorError::Git)
+    let timeout = crate::command::default_timeout(cmd);
+    run_command(cmd, timeout, ConductorError::Git)
 }
 
 /// Run `cmd`, returning its `Output` on success or a `ConductorError::GhCli` on non-zero exit.
 pub(crate) fn check_gh_output(cmd: &mut Command) -> Result<std::process::Output> {
-    run_command(cmd, ConductorError::GhCli)
-}
-
-/// Shared implementation: run a command and map failures using the given error constructor.
-///
-/// Constructs a `SubprocessFailure` with structured data (exit code, stderr, stdout)
-/// so callers can programmatically classify and handle errors.
-fn run_command(
-    cmd: &mut Command,
-    make_err: fn(SubprocessFailure) -> ConductorError,
-) -> Result<std::process::Output> {
-    let program = cmd.get_program().to_string_lossy().to_string();
-    let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
-    let cmd_str = format!("`{program} {}`", args.join(" "));
-    let output = cmd.output().map_err(|e| {
-        make_err(SubprocessFailure::from_message(
-            &cmd_str,
-            format!("failed to spawn {cmd_str}: {e}"),
-        ))
-    })?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        return Err(make_err(SubprocessFailure {
-            command: cmd_str,
-            exit_code: output.status.code(),
-            stderr,
-            stdout,
-        }));
-    }
-    Ok(output)
+    let timeout = crate::command::timeouts().network();
+    run_command(cmd, timeout, ConductorError::GhCli)
 }
 
 /// Run a git command with bounded retry for transient failures.
@@ -59,7 +35,6 @@ fn run_command(
 /// (since `Command::output()` consumes the command's state).
 ///
 /// Part of: bounded-retry-with-escalation@1.0.0
-#[allow(dead_code)]
 pub(crate) fn check_output_with_retry<F>(
     config: &crate::retry::RetryConfig,
     build_cmd: F,
@@ -84,7 +59,6 @@ where
     check_with_retry(config, build_cmd, ConductorError::GhCli)
 }
 
-#[allow(dead_code)]
 fn check_with_retry<F>(
     config: &crate::retry::RetryConfig,
     build_cmd: F,
@@ -95,9 +69,14 @@ where
 {
     let outcome = crate::retry::retry_with_backoff(
         config,
-        || run_command(&mut build_cmd(), make_err),
+        || {
+            let mut cmd = build_cmd();
+            let timeout = crate::command::default_timeout(&cmd);
+            run_command(&mut cmd, timeout, make_err)
+        },
         |err: &ConductorError| match err {
             ConductorError::Git(f) | ConductorError::GhCli(f) => crate::retry::is_transient(f),
+            ConductorError::CommandTimedOut { .. } => true,
             _ => false,
         },
         || false, // No cancellation support at this level; callers run in background threads
@@ -114,15 +93,21 @@ where
 /// Check if `branch` has been merged into `default_branch` using local refs
 /// (`git branch --merged`). Fast but may be stale if the remote has advanced.
 pub(crate) fn is_branch_merged_local(repo_path: &str, branch: &str, default_branch: &str) -> bool {
-    let output = git_in(repo_path)
-        .args(["branch", &format!("--merged={default_branch}")])
-        .output();
+    let output = crate::command::output(
+        git_in(repo_path).args(["branch", &format!("--merged={default_branch}")]),
+    );
     match output {
         Ok(o) if o.status.success() => {
             let stdout = String::from_utf8_lossy(&o.stdout);
-            stdout
-                .lines()
-                .any(|line| line.trim().trim_start_matches("* ") == branch)
+            // `*` marks the current branch, `+` one checked out in another worktree.
+            stdout.lines().any(|line| {
+                let name = line.trim();
+                let name = name
+                    .strip_prefix("* ")
+                    .or_else(|| name.strip_prefix("+ "))
+                    .unwrap_or(name);
+                name == branch
+            })
         }
         _ => false,
     }
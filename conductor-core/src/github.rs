@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::process::{Command, Output};
 
 use serde::{Deserialize, Serialize};
@@ -34,8 +35,7 @@ pub(crate) fn build_gh_cmd(args: &[&str], token: Option<&str>) -> Command {
 /// When `token` is `Some`, the `GH_TOKEN` env var is set so that
 /// `gh` authenticates as that identity (e.g. a GitHub App installation).
 fn run_gh_with_token(args: &[&str], token: Option<&str>) -> Result<Output> {
-    let output = build_gh_cmd(args, token)
-        .output()
+    let output = crate::command::output(&mut build_gh_cmd(args, token))
         .map_err(|e| ConductorError::TicketSync(format!("failed to run gh: {e}")))?;
 
     if !output.status.success() {
@@ -251,6 +251,48 @@ fn parse_issue_metadata(issue: &serde_json::Value) -> (Vec<TicketLabelInput>, Op
     (label_details, assignee)
 }
 
+/// Milestone `(title, due date)` for a GitHub issue JSON value.
+///
+/// `gh issue list --json milestone` reports the due date as `dueOn`; the REST
+/// API (`gh api .../issues`) uses `due_on`. Both are accepted.
+fn issue_milestone(issue: &serde_json::Value) -> Option<(String, Option<String>)> {
+    let m = &issue["milestone"];
+    let title = m["title"].as_str().filter(|t| !t.is_empty())?.to_string();
+    let due = m["dueOn"]
+        .as_str()
+        .or_else(|| m["due_on"].as_str())
+        .map(|d| d.to_string());
+    Some((title, due))
+}
+
+/// Pick the "current" milestone among a set of issues: the one with the
+/// earliest due date that has not yet passed as of `today` (`YYYY-MM-DD`).
+///
+/// GitHub has no notion of an active milestone, so milestones without a due
+/// date are never considered current.
+fn current_milestone(issues: &[serde_json::Value], today: &str) -> Option<String> {
+    issues
+        .iter()
+        .filter_map(issue_milestone)
+        .filter_map(|(title, due)| {
+            let day = due?.get(..10)?.to_string();
+            (day.as_str() >= today).then_some((day, title))
+        })
+        .min()
+        .map(|(_, title)| title)
+}
+
+/// Flag tickets whose milestone is the current one (see [`current_milestone`]).
+fn mark_current_milestone(tickets: &mut [TicketInput], issues: &[serde_json::Value]) {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let Some(current) = current_milestone(issues, &today) else {
+        return;
+    };
+    for t in tickets {
+        t.sprint_current = t.sprint.as_deref() == Some(current.as_str());
+    }
+}
+
 /// Build a [`TicketInput`] from a GitHub issue JSON value.
 ///
 /// `url` is passed explicitly because the caller chooses which field to use:
@@ -284,6 +326,8 @@ fn build_ticket_input(issue: &serde_json::Value, url: &str) -> Result<TicketInpu
         blocked_by: vec![],
         children: vec![],
         parent: None,
+        sprint: issue_milestone(issue).map(|(title, _)| title),
+        sprint_current: false,
     })
 }
 
@@ -319,18 +363,95 @@ pub fn sync_github_issues(
     let issues: Vec<serde_json::Value> = serde_json::from_str(&json_str)
         .map_err(|e| ConductorError::TicketSync(format!("failed to parse gh output: {e}")))?;
 
-    let tickets = issues
-        .into_iter()
+    let mut tickets = issues
+        .iter()
         .map(|issue| {
             // `gh issue list --json url` returns the HTML URL directly.
             let url = issue["url"].as_str().unwrap_or("").to_string();
-            build_ticket_input(&issue, &url)
+            build_ticket_input(issue, &url)
         })
         .collect::<Result<Vec<_>>>()?;
+    mark_current_milestone(&mut tickets, &issues);
+
+    // Sub-issue links are best-effort: an older GitHub Enterprise server or a
+    // token without GraphQL access should not fail the whole sync.
+    match fetch_issue_parents(owner, repo, token) {
+        Ok(parents) => {
+            for t in &mut tickets {
+                t.parent = parents.get(&t.source_id).cloned();
+            }
+        }
+        Err(e) => tracing::warn!("sync_github_issues: skipping sub-issue parents: {e}"),
+    }
 
     Ok(tickets)
 }
 
+const ISSUE_PARENTS_QUERY: &str = "query($owner: String!, $name: String!, $endCursor: String) {
+  repository(owner: $owner, name: $name) {
+    issues(states: OPEN, first: 100, after: $endCursor) {
+      nodes { number parent { number repository { nameWithOwner } } }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}";
+
+/// Map each open issue number to its parent issue number (GitHub sub-issues).
+fn fetch_issue_parents(
+    owner: &str,
+    repo: &str,
+    token: Option<&str>,
+) -> Result<HashMap<String, String>> {
+    let output = run_gh_with_token(
+        &[
+            "api",
+            "graphql",
+            "--paginate",
+            "-f",
+            &format!("query={ISSUE_PARENTS_QUERY}"),
+            "-F",
+            &format!("owner={owner}"),
+            "-F",
+            &format!("name={repo}"),
+        ],
+        token,
+    )?;
+    parse_issue_parents(
+        &String::from_utf8_lossy(&output.stdout),
+        &repo_slug(owner, repo),
+    )
+}
+
+/// Parse the pages printed by `gh api graphql --paginate` for
+/// [`ISSUE_PARENTS_QUERY`]. Parents in other repositories are skipped, since
+/// their tickets belong to a different repo.
+fn parse_issue_parents(raw: &str, repo_slug: &str) -> Result<HashMap<String, String>> {
+    let mut parents = HashMap::new();
+    for page in serde_json::Deserializer::from_str(raw).into_iter::<serde_json::Value>() {
+        let page = page.map_err(|e| {
+            ConductorError::TicketSync(format!("failed to parse gh graphql output: {e}"))
+        })?;
+        let nodes = page["data"]["repository"]["issues"]["nodes"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for node in nodes {
+            let parent = &node["parent"];
+            let same_repo = parent["repository"]["nameWithOwner"]
+                .as_str()
+                .is_some_and(|r| r.eq_ignore_ascii_case(repo_slug));
+            if let (Some(child), Some(parent), true) = (
+                node["number"].as_u64(),
+                parent["number"].as_u64(),
+                same_repo,
+            ) {
+                parents.insert(child.to_string(), parent.to_string());
+            }
+        }
+    }
+    Ok(parents)
+}
+
 /// Fetch open issues for a GitHub milestone via `gh api` (REST endpoint).
 ///
 /// Returns a list of [`TicketInput`] values with `source_type = "github"` (each
@@ -369,15 +490,16 @@ pub fn fetch_milestone_issues(
         all_issues.extend(page);
     }
 
-    let tickets = all_issues
-        .into_iter()
+    let mut tickets = all_issues
+        .iter()
         .map(|issue| {
             // `gh api /repos/.../issues` returns REST API JSON where `url` is
             // the API endpoint URL. Use `html_url` for the human-facing URL.
             let url = issue["html_url"].as_str().unwrap_or("").to_string();
-            build_ticket_input(&issue, &url)
+            build_ticket_input(issue, &url)
         })
         .collect::<Result<Vec<_>>>()?;
+    mark_current_milestone(&mut tickets, &all_issues);
 
     Ok(tickets)
 }
@@ -430,7 +552,7 @@ pub fn fetch_github_issue(
         "closed".to_string()
     };
 
-    Ok(TicketInput {
+    let mut ticket = TicketInput {
         source_type: "github".to_string(),
         source_id: number.to_string(),
         title: issue["title"].as_str().unwrap_or("").to_string(),
@@ -445,9 +567,130 @@ pub fn fetch_github_issue(
         blocked_by: vec![],
         children: vec![],
         parent: None,
+        sprint: issue_milestone(&issue).map(|(title, _)| title),
+        sprint_current: false,
+    };
+    mark_current_milestone(
+        std::slice::from_mut(&mut ticket),
+        std::slice::from_ref(&issue),
+    );
+    Ok(ticket)
+}
+
+/// `gh --json` fields requested for pull requests synced as tickets.
+const PR_TICKET_FIELDS: &str = "number,title,body,labels,assignees,reviewRequests,author,state,url,headRefName,baseRefName,isDraft,reviewDecision,milestone";
+
+/// Build a [`TicketInput`] (`source_type = "github_pr"`) from a `gh pr` JSON value.
+///
+/// The assignee is the first PR assignee, falling back to the first user whose
+/// review was requested, so review-assigned PRs land in that reviewer's queue.
+fn build_pr_ticket_input(pr: &serde_json::Value) -> Result<TicketInput> {
+    let number = pr["number"].as_u64().ok_or_else(|| {
+        ConductorError::TicketSync(format!(
+            "GitHub pull request is missing a valid 'number' field: {}",
+            pr
+        ))
+    })?;
+    let (label_details, assignee) = parse_issue_metadata(pr);
+    let assignee = assignee.or_else(|| {
+        pr["reviewRequests"]
+            .as_array()
+            .and_then(|arr| arr.iter().find_map(|r| r["login"].as_str()))
+            .map(|s| s.to_string())
+    });
+    let label_names: Vec<String> = label_details.iter().map(|l| l.name.clone()).collect();
+    // gh reports OPEN / CLOSED / MERGED; anything not open is closed for tickets.
+    let state = if pr["state"]
+        .as_str()
+        .unwrap_or("OPEN")
+        .eq_ignore_ascii_case("open")
+    {
+        "open"
+    } else {
+        "closed"
+    };
+    Ok(TicketInput {
+        source_type: crate::tickets::GITHUB_PR_SOURCE_TYPE.to_string(),
+        source_id: number.to_string(),
+        title: pr["title"].as_str().unwrap_or("").to_string(),
+        body: pr["body"].as_str().unwrap_or("").to_string(),
+        state: state.to_string(),
+        labels: label_names,
+        assignee,
+        priority: None,
+        url: pr["url"].as_str().unwrap_or("").to_string(),
+        raw_json: serde_json::to_string(pr).ok(),
+        label_details,
+        blocked_by: vec![],
+        children: vec![],
+        parent: None,
+        sprint: issue_milestone(pr).map(|(title, _)| title),
+        sprint_current: false,
     })
 }
 
+/// Sync open GitHub pull requests for a repo as `github_pr` tickets.
+///
+/// Enabled per source via `"sync_prs": true` in the GitHub issue-source config.
+/// When `token` is `Some`, the sync runs under that identity.
+pub fn sync_github_prs(owner: &str, repo: &str, token: Option<&str>) -> Result<Vec<TicketInput>> {
+    let repo_slug = repo_slug(owner, repo);
+    let output = run_gh_with_token(
+        &[
+            "pr",
+            "list",
+            "--repo",
+            &repo_slug,
+            "--state",
+            "open",
+            "--limit",
+            "200",
+            "--json",
+            PR_TICKET_FIELDS,
+        ],
+        token,
+    )?;
+
+    let json_str = String::from_utf8_lossy(&output.stdout);
+    let prs: Vec<serde_json::Value> = serde_json::from_str(&json_str)
+        .map_err(|e| ConductorError::TicketSync(format!("failed to parse gh output: {e}")))?;
+
+    let mut tickets = prs
+        .iter()
+        .map(build_pr_ticket_input)
+        .collect::<Result<Vec<_>>>()?;
+    mark_current_milestone(&mut tickets, &prs);
+    Ok(tickets)
+}
+
+/// Fetch a single GitHub pull request as a `github_pr` ticket, with its real state.
+pub fn fetch_github_pr(
+    owner: &str,
+    repo: &str,
+    pr_number: i64,
+    token: Option<&str>,
+) -> Result<TicketInput> {
+    let repo_slug = repo_slug(owner, repo);
+    let number_str = pr_number.to_string();
+    let output = run_gh_with_token(
+        &[
+            "pr",
+            "view",
+            &number_str,
+            "--repo",
+            &repo_slug,
+            "--json",
+            PR_TICKET_FIELDS,
+        ],
+        token,
+    )?;
+
+    let json_str = String::from_utf8_lossy(&output.stdout);
+    let pr: serde_json::Value = serde_json::from_str(&json_str)
+        .map_err(|e| ConductorError::TicketSync(format!("failed to parse gh output: {e}")))?;
+    build_pr_ticket_input(&pr)
+}
+
 /// Create a new GitHub issue via the `gh` CLI.
 /// Returns `(source_id, url)` where `source_id` is the issue number as a string.
 ///
@@ -765,6 +1008,42 @@ pub fn close_github_issue(owner: &str, repo: &str, issue_number: &str) -> Result
     Ok(())
 }
 
+/// Reopen a closed GitHub issue via the `gh` CLI.
+pub fn reopen_github_issue(owner: &str, repo: &str, issue_number: &str) -> Result<()> {
+    let repo_slug = repo_slug(owner, repo);
+    run_gh(&["issue", "reopen", issue_number, "--repo", &repo_slug])?;
+    Ok(())
+}
+
+/// Comment on a GitHub issue via the `gh` CLI.
+pub fn comment_on_github_issue(
+    owner: &str,
+    repo: &str,
+    issue_number: &str,
+    body: &str,
+) -> Result<()> {
+    let repo_slug = repo_slug(owner, repo);
+    run_gh(&[
+        "issue",
+        "comment",
+        issue_number,
+        "--repo",
+        &repo_slug,
+        "--body",
+        body,
+    ])?;
+    Ok(())
+}
+
+/// Comment on a GitHub PR via the `gh` CLI.
+pub fn comment_on_github_pr(owner: &str, repo: &str, pr_number: &str, body: &str) -> Result<()> {
+    let repo_slug = repo_slug(owner, repo);
+    run_gh(&[
+        "pr", "comment", pr_number, "--repo", &repo_slug, "--body", body,
+    ])?;
+    Ok(())
+}
+
 /// Squash-merge a PR via the `gh` CLI. Deletes the remote branch after merge.
 pub fn squash_merge_pr(owner: &str, repo: &str, pr_number: i64) -> Result<()> {
     let repo_slug = repo_slug(owner, repo);
@@ -797,6 +1076,14 @@ pub fn add_pr_label(owner: &str, repo: &str, pr_number: i64, label: &str) -> Res
     Ok(())
 }
 
+/// Replace a PR's description via the `gh` CLI.
+pub fn edit_pr_body(owner: &str, repo: &str, pr_number: i64, body: &str) -> Result<()> {
+    let repo_slug = repo_slug(owner, repo);
+    let pr_str = pr_number.to_string();
+    run_gh(&["pr", "edit", &pr_str, "--repo", &repo_slug, "--body", body])?;
+    Ok(())
+}
+
 /// Create a PR with a specific title and body via the `gh` CLI.
 /// When `base` is `Some`, the PR targets that branch instead of the repo default.
 /// Returns the PR URL.
@@ -1027,6 +1314,26 @@ mod tests {
         assert!(parse_github_remote("https://gitlab.com/user/repo.git").is_none());
     }
 
+    #[test]
+    fn test_parse_issue_parents_across_pages() {
+        let page = |nodes: &str| {
+            format!(r#"{{"data":{{"repository":{{"issues":{{"nodes":[{nodes}]}}}}}}}}"#)
+        };
+        let raw = format!(
+            "{}\n{}",
+            page(
+                r#"{"number":2,"parent":{"number":1,"repository":{"nameWithOwner":"Acme/App"}}},
+                   {"number":1,"parent":null}"#
+            ),
+            page(
+                r#"{"number":3,"parent":{"number":9,"repository":{"nameWithOwner":"acme/other"}}}"#
+            ),
+        );
+        let parents = parse_issue_parents(&raw, "acme/app").unwrap();
+        assert_eq!(parents.len(), 1);
+        assert_eq!(parents.get("2").map(String::as_str), Some("1"));
+    }
+
     #[test]
     fn test_parse_discovered_repo_json() {
         // Simulate the JSON output from `gh repo list --json ...`
@@ -1232,6 +1539,63 @@ mod tests {
         assert_eq!(ticket.labels, vec!["bug"]);
     }
 
+    #[test]
+    fn test_build_ticket_input_reads_milestone_as_sprint() {
+        let mut issue = make_issue_json(5, "Milestoned", "https://github.com/o/r/issues/5");
+        issue["milestone"] = serde_json::json!({"title": "v1.2", "dueOn": "2030-01-31T00:00:00Z"});
+        let ticket = build_ticket_input(&issue, "https://github.com/o/r/issues/5").unwrap();
+        assert_eq!(ticket.sprint.as_deref(), Some("v1.2"));
+        assert!(!ticket.sprint_current);
+    }
+
+    #[test]
+    fn test_current_milestone_picks_earliest_upcoming_due_date() {
+        let issues = vec![
+            serde_json::json!({"milestone": {"title": "past", "dueOn": "2024-01-01T00:00:00Z"}}),
+            serde_json::json!({"milestone": {"title": "later", "due_on": "2024-03-01T00:00:00Z"}}),
+            serde_json::json!({"milestone": {"title": "next", "dueOn": "2024-02-01T00:00:00Z"}}),
+            serde_json::json!({"milestone": {"title": "undated"}}),
+            serde_json::json!({"milestone": null}),
+        ];
+        assert_eq!(
+            current_milestone(&issues, "2024-01-15").as_deref(),
+            Some("next")
+        );
+        // Due today still counts as current.
+        assert_eq!(
+            current_milestone(&issues, "2024-02-01").as_deref(),
+            Some("next")
+        );
+        assert_eq!(current_milestone(&issues, "2025-01-01"), None);
+    }
+
+    #[test]
+    fn test_build_pr_ticket_input_falls_back_to_requested_reviewer() {
+        let pr = serde_json::json!({
+            "number": 77,
+            "title": "Add retries",
+            "body": "PR body",
+            "state": "OPEN",
+            "url": "https://github.com/o/r/pull/77",
+            "labels": [],
+            "assignees": [],
+            "reviewRequests": [{"slug": "core-team"}, {"login": "bob"}],
+            "headRefName": "feat/retries"
+        });
+        let ticket = build_pr_ticket_input(&pr).unwrap();
+        assert_eq!(ticket.source_type, crate::tickets::GITHUB_PR_SOURCE_TYPE);
+        assert_eq!(ticket.source_id, "77");
+        assert_eq!(ticket.state, "open");
+        assert_eq!(ticket.assignee.as_deref(), Some("bob"));
+        assert_eq!(ticket.url, "https://github.com/o/r/pull/77");
+
+        let merged =
+            serde_json::json!({"number": 78, "state": "MERGED", "assignees": [{"login": "amy"}]});
+        let ticket = build_pr_ticket_input(&merged).unwrap();
+        assert_eq!(ticket.state, "closed");
+        assert_eq!(ticket.assignee.as_deref(), Some("amy"));
+    }
+
     #[test]
     fn test_build_ticket_input_missing_number_returns_error() {
         let issue = serde_json::json!({
@@ -0,0 +1,279 @@
+use crate::error::{ConductorError, Result};
+use crate::issue_source::GitLabConfig;
+use crate::tickets::{TicketInput, TicketLabelInput};
+
+/// Issues requested per page; GitLab caps `per_page` at 100.
+const PER_PAGE: u32 = 100;
+/// Upper bound on pages fetched per sync (1000 open issues).
+const MAX_PAGES: u32 = 10;
+
+/// Default environment variable holding a GitLab personal access token.
+pub const DEFAULT_TOKEN_ENV: &str = "GITLAB_TOKEN";
+
+/// Percent-encode a project ID or `group/project` path for use as a single
+/// URL path segment, as the GitLab API requires (`group%2Fproject`).
+fn encode_project_id(project_id: &str) -> String {
+    project_id
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn api_url(cfg: &GitLabConfig, path: &str) -> String {
+    format!(
+        "{}/api/v4/projects/{}{path}",
+        cfg.base_url.trim_end_matches('/'),
+        encode_project_id(&cfg.project_id)
+    )
+}
+
+/// Send a GET to the GitLab API, authenticating with the token read from
+/// `cfg.token_env` when that variable is set. Without a token only public
+/// projects are readable.
+fn get(cfg: &GitLabConfig, url: &str) -> Result<ureq::Response> {
+    let mut req = ureq::get(url).set("User-Agent", "conductor-ai");
+    if let Ok(token) = std::env::var(&cfg.token_env) {
+        req = req.set("PRIVATE-TOKEN", &token);
+    }
+    req.call().map_err(|e| match e {
+        ureq::Error::Status(401, _) => ConductorError::TicketSync(format!(
+            "GitLab rejected the request (401); check the token in ${}",
+            cfg.token_env
+        )),
+        ureq::Error::Status(404, _) => ConductorError::TicketSync(format!(
+            "GitLab project '{}' not found at {} (or the token cannot see it)",
+            cfg.project_id, cfg.base_url
+        )),
+        e => ConductorError::TicketSync(format!("GitLab request failed: {e}")),
+    })
+}
+
+/// Map a GitLab issue state (`opened` / `closed`) to a ticket state.
+fn map_gitlab_state(state: &str) -> &'static str {
+    match state {
+        "closed" => "closed",
+        _ => "open",
+    }
+}
+
+/// Build a [`TicketInput`] from a GitLab issue JSON value.
+///
+/// The project-scoped `iid` (the `#123` users see) is the source ID, not the
+/// instance-wide `id`.
+fn build_ticket_input(issue: &serde_json::Value) -> Result<TicketInput> {
+    let iid = issue["iid"].as_u64().ok_or_else(|| {
+        ConductorError::TicketSync(format!(
+            "GitLab issue is missing a valid 'iid' field: {issue}"
+        ))
+    })?;
+    let labels: Vec<String> = issue["labels"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|l| l.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let assignee = issue["assignees"]
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|a| a["username"].as_str())
+        .or_else(|| issue["assignee"]["username"].as_str())
+        .map(|s| s.to_string());
+    Ok(TicketInput {
+        source_type: "gitlab".to_string(),
+        source_id: iid.to_string(),
+        title: issue["title"].as_str().unwrap_or("").to_string(),
+        body: issue["description"].as_str().unwrap_or("").to_string(),
+        state: map_gitlab_state(issue["state"].as_str().unwrap_or("opened")).to_string(),
+        label_details: labels
+            .iter()
+            .map(|name| TicketLabelInput {
+                name: name.clone(),
+                color: None,
+            })
+            .collect(),
+        labels,
+        assignee,
+        priority: None,
+        url: issue["web_url"].as_str().unwrap_or("").to_string(),
+        raw_json: serde_json::to_string(issue).ok(),
+        blocked_by: vec![],
+        children: vec![],
+        parent: None,
+        sprint: issue["milestone"]["title"]
+            .as_str()
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string()),
+        sprint_current: false,
+    })
+}
+
+/// Sync open issues of a GitLab project via the REST API (v4).
+/// Returns a list of normalized TicketInputs ready for upsert.
+///
+/// Follows GitLab's `X-Next-Page` header for up to [`MAX_PAGES`] pages.
+pub fn sync_gitlab_issues(cfg: &GitLabConfig) -> Result<Vec<TicketInput>> {
+    let mut tickets = Vec::new();
+    let mut page = 1;
+    loop {
+        let url = api_url(
+            cfg,
+            &format!("/issues?state=opened&per_page={PER_PAGE}&page={page}"),
+        );
+        let resp = get(cfg, &url)?;
+        let next_page = resp
+            .header("X-Next-Page")
+            .and_then(|p| p.trim().parse::<u32>().ok());
+        let issues: Vec<serde_json::Value> = resp.into_json().map_err(|e| {
+            ConductorError::TicketSync(format!("failed to parse GitLab issues response: {e}"))
+        })?;
+        for issue in &issues {
+            tickets.push(build_ticket_input(issue)?);
+        }
+        match next_page {
+            Some(next) if next > page && next <= MAX_PAGES => page = next,
+            _ => break,
+        }
+    }
+    Ok(tickets)
+}
+
+/// Fetch a single GitLab issue by its project-scoped `iid` and return its
+/// current state.
+pub fn fetch_gitlab_issue(cfg: &GitLabConfig, iid: u64) -> Result<TicketInput> {
+    let resp = get(cfg, &api_url(cfg, &format!("/issues/{iid}")))?;
+    let issue: serde_json::Value = resp.into_json().map_err(|e| {
+        ConductorError::TicketSync(format!("failed to parse GitLab issue response: {e}"))
+    })?;
+    build_ticket_input(&issue)
+}
+
+/// Parse a GitLab remote URL into `(base_url, project path)`.
+///
+/// Only hosts whose name contains `gitlab` are recognised, since any other
+/// host is as likely to be a GitHub Enterprise or Gitea instance. Handles
+/// SSH (`git@gitlab.example.com:group/sub/project.git`) and HTTPS
+/// (`https://gitlab.example.com/group/sub/project.git`).
+pub fn parse_gitlab_remote(remote_url: &str) -> Option<(String, String)> {
+    let (host, path) = if let Some(rest) = remote_url.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else {
+        let rest = remote_url
+            .strip_prefix("https://")
+            .or_else(|| remote_url.strip_prefix("ssh://git@"))?;
+        rest.split_once('/')?
+    };
+    if !host.contains("gitlab") {
+        return None;
+    }
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_matches('/');
+    if !path.contains('/') {
+        return None;
+    }
+    Some((format!("https://{host}"), path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_url: &str, token_env: &str) -> GitLabConfig {
+        GitLabConfig {
+            project_id: "acme/widget".to_string(),
+            base_url: base_url.to_string(),
+            token_env: token_env.to_string(),
+        }
+    }
+
+    #[test]
+    fn encode_project_id_escapes_path_separators() {
+        assert_eq!(encode_project_id("group/sub/proj"), "group%2Fsub%2Fproj");
+        assert_eq!(encode_project_id("42"), "42");
+    }
+
+    #[test]
+    fn sync_follows_pages_and_sends_token() {
+        std::env::set_var("CONDUCTOR_TEST_GITLAB_TOKEN_SYNC", "glpat-secret");
+        let mut server = mockito::Server::new();
+        let _p1 = server
+            .mock("GET", "/api/v4/projects/acme%2Fwidget/issues")
+            .match_query(mockito::Matcher::UrlEncoded("page".into(), "1".into()))
+            .match_header("PRIVATE-TOKEN", "glpat-secret")
+            .with_header("X-Next-Page", "2")
+            .with_body(
+                r#"[{"iid":7,"title":"Crash on save","description":"Steps...","state":"opened",
+                     "labels":["bug"],"assignees":[{"username":"alice"}],
+                     "web_url":"https://gitlab.example.com/acme/widget/-/issues/7",
+                     "milestone":{"title":"v2"}}]"#,
+            )
+            .create();
+        let _p2 = server
+            .mock("GET", "/api/v4/projects/acme%2Fwidget/issues")
+            .match_query(mockito::Matcher::UrlEncoded("page".into(), "2".into()))
+            .with_header("X-Next-Page", "")
+            .with_body(r#"[{"iid":8,"title":"Docs","state":"opened","labels":[]}]"#)
+            .create();
+
+        let cfg = config(&server.url(), "CONDUCTOR_TEST_GITLAB_TOKEN_SYNC");
+        let tickets = sync_gitlab_issues(&cfg).unwrap();
+        assert_eq!(tickets.len(), 2);
+        let t = &tickets[0];
+        assert_eq!(t.source_type, "gitlab");
+        assert_eq!(t.source_id, "7");
+        assert_eq!(t.state, "open");
+        assert_eq!(t.labels, vec!["bug"]);
+        assert_eq!(t.assignee.as_deref(), Some("alice"));
+        assert_eq!(t.sprint.as_deref(), Some("v2"));
+        assert_eq!(tickets[1].source_id, "8");
+        assert_eq!(tickets[1].body, "");
+    }
+
+    #[test]
+    fn fetch_one_maps_closed_state() {
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("GET", "/api/v4/projects/acme%2Fwidget/issues/7")
+            .with_body(r#"{"iid":7,"title":"Crash","state":"closed"}"#)
+            .create();
+        let cfg = config(&server.url(), "CONDUCTOR_TEST_GITLAB_TOKEN_UNSET");
+        let t = fetch_gitlab_issue(&cfg, 7).unwrap();
+        assert_eq!(t.state, "closed");
+    }
+
+    #[test]
+    fn not_found_is_reported_as_ticket_sync_error() {
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("GET", "/api/v4/projects/acme%2Fwidget/issues/9")
+            .with_status(404)
+            .create();
+        let cfg = config(&server.url(), "CONDUCTOR_TEST_GITLAB_TOKEN_UNSET");
+        let err = fetch_gitlab_issue(&cfg, 9).err().expect("expected error");
+        assert!(
+            matches!(&err, ConductorError::TicketSync(msg) if msg.contains("not found")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn parse_gitlab_remote_formats() {
+        assert_eq!(
+            parse_gitlab_remote("git@gitlab.example.com:group/sub/proj.git"),
+            Some((
+                "https://gitlab.example.com".to_string(),
+                "group/sub/proj".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_gitlab_remote("https://gitlab.com/acme/widget.git"),
+            Some(("https://gitlab.com".to_string(), "acme/widget".to_string()))
+        );
+        assert_eq!(parse_gitlab_remote("git@github.com:acme/widget.git"), None);
+    }
+}
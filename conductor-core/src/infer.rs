@@ -21,11 +21,12 @@ pub fn infer_base_branch(raw_json: &str, repo_path: &str) -> Option<(String, Str
     let branch = format!("release/{title}");
 
     // Verify the branch exists on the remote via `git ls-remote --heads origin <branch>`.
-    let output = std::process::Command::new("git")
-        .args(["ls-remote", "--heads", "origin", &branch])
-        .current_dir(repo_path)
-        .output()
-        .ok()?;
+    let output = crate::command::output(
+        std::process::Command::new("git")
+            .args(["ls-remote", "--heads", "origin", &branch])
+            .current_dir(repo_path),
+    )
+    .ok()?;
 
     if output.stdout.is_empty() {
         None
@@ -0,0 +1,260 @@
+//! Agent instructions files (`CLAUDE.md`, `AGENTS.md`) generated from what
+//! conductor knows about a repo: its `.conductor/config.toml` and detected
+//! toolchain.
+//!
+//! The generated text sits between marker comments, so regenerating replaces
+//! only conductor's section and keeps whatever the team wrote around it.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RepoConfig;
+use crate::error::Result;
+use crate::toolchain::Toolchain;
+use crate::worktree::SLUG_PREFIXES;
+
+const BEGIN_MARKER: &str = "<!-- conductor:begin";
+const END_MARKER: &str = "<!-- conductor:end -->";
+
+/// An instructions file agents read from the repo root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstructionsFile {
+    /// Read by Claude Code.
+    #[serde(rename = "CLAUDE.md")]
+    ClaudeMd,
+    /// Read by Codex and other agents following the AGENTS.md convention.
+    #[serde(rename = "AGENTS.md")]
+    AgentsMd,
+}
+
+impl InstructionsFile {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::ClaudeMd => "CLAUDE.md",
+            Self::AgentsMd => "AGENTS.md",
+        }
+    }
+}
+
+impl fmt::Display for InstructionsFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.file_name())
+    }
+}
+
+impl FromStr for InstructionsFile {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "claude" | "claude.md" => Ok(Self::ClaudeMd),
+            "agents" | "agents.md" => Ok(Self::AgentsMd),
+            _ => Err(format!(
+                "unknown instructions file '{s}' (expected CLAUDE.md or AGENTS.md)"
+            )),
+        }
+    }
+}
+
+/// Render conductor's section of an instructions file, markers included.
+/// The test command falls back to the one implied by `toolchain`.
+pub fn render(repo_config: &RepoConfig, toolchain: &Toolchain) -> String {
+    let project = &repo_config.project;
+    let mut out = format!(
+        "{BEGIN_MARKER} generated by `conductor repo gen-instructions` from \
+         .conductor/config.toml; edits inside are overwritten -->\n\
+         ## Project conventions\n"
+    );
+    if let Some(preamble) = project.agent_preamble.as_deref().map(str::trim) {
+        if !preamble.is_empty() {
+            out.push_str(&format!("\n{preamble}\n"));
+        }
+    }
+    if !toolchain.is_empty() {
+        out.push_str(&format!("\nToolchain: {toolchain}.\n"));
+    }
+
+    let test_command = project
+        .test_command
+        .clone()
+        .or_else(|| toolchain.test_command());
+    if let Some(cmd) = test_command {
+        out.push_str(&format!(
+            "\n### Testing\n\nRun `{cmd}` to test your changes before finishing.\n"
+        ));
+    }
+
+    if !project.setup.is_empty() {
+        out.push_str("\n### Setup\n\nEach new worktree runs these commands after checkout:\n\n");
+        for cmd in &project.setup {
+            out.push_str(&format!("- `{cmd}`\n"));
+        }
+    }
+
+    let prefix = project.branch_prefix.as_deref().unwrap_or("feat");
+    let mut types: Vec<&str> = SLUG_PREFIXES.iter().map(|&(_, slash)| slash).collect();
+    types.dedup();
+    out.push_str(&format!(
+        "\n### Branches\n\nEach branch is checked out in its own worktree. Branches are \
+         named `<type>/<name>` with type one of {}; other names get `{prefix}/`.\n",
+        types
+            .iter()
+            .map(|t| format!("`{t}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    for rule in &repo_config.base_branch_rules {
+        out.push_str(&format!(
+            "Tickets labelled `{}` branch from `{}`.\n",
+            rule.label, rule.branch
+        ));
+    }
+
+    let agent = &repo_config.agent;
+    let mut guardrails = Vec::new();
+    if !project.protected_paths.is_empty() {
+        guardrails.push(format!(
+            "Do not modify: {}.",
+            backticked(&project.protected_paths)
+        ));
+    }
+    if !project.pre_push.is_empty() {
+        guardrails.push(format!(
+            "Pushes are blocked until these pass: {}.",
+            backticked(&project.pre_push)
+        ));
+    }
+    if !agent.allowed_tools.is_empty() {
+        guardrails.push(format!(
+            "Agents may only use: {}.",
+            backticked(&agent.allowed_tools)
+        ));
+    }
+    if let Some(turns) = agent.max_turns {
+        guardrails.push(format!("Agent runs stop after {turns} turns."));
+    }
+    if !guardrails.is_empty() {
+        out.push_str("\n### Guardrails\n\n");
+        for line in guardrails {
+            out.push_str(&format!("- {line}\n"));
+        }
+    }
+
+    out.push_str(END_MARKER);
+    out.push('\n');
+    out
+}
+
+fn backticked(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| format!("`{i}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Replace conductor's section of `existing` with `section`, or append it
+/// when there is none yet.
+pub fn merge(existing: &str, section: &str) -> String {
+    if let Some(start) = existing.find(BEGIN_MARKER) {
+        if let Some(end) = existing[start..].find(END_MARKER) {
+            let mut end = start + end + END_MARKER.len();
+            if existing[end..].starts_with('\n') {
+                end += 1;
+            }
+            return format!("{}{section}{}", &existing[..start], &existing[end..]);
+        }
+    }
+    let existing = existing.trim_end();
+    if existing.is_empty() {
+        section.to_string()
+    } else {
+        format!("{existing}\n\n{section}")
+    }
+}
+
+/// Write `section` into `file` under `dir`, keeping the rest of the file.
+/// Returns whether the file changed.
+pub fn write(dir: &Path, file: InstructionsFile, section: &str) -> Result<bool> {
+    let path = dir.join(file.file_name());
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let merged = merge(&existing, section);
+    if merged == existing {
+        return Ok(false);
+    }
+    std::fs::write(&path, merged)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BaseBranchRule, ProjectSettings};
+
+    #[test]
+    fn render_and_merge_keep_hand_written_text() {
+        let repo_config = RepoConfig {
+            project: ProjectSettings {
+                setup: vec!["npm ci".into()],
+                branch_prefix: Some("dev".into()),
+                protected_paths: vec!["migrations/".into()],
+                pre_push: vec!["cargo fmt --check".into()],
+                ..Default::default()
+            },
+            base_branch_rules: vec![BaseBranchRule {
+                label: "hotfix".into(),
+                branch: "release/1.x".into(),
+            }],
+            ..Default::default()
+        };
+        let toolchain = Toolchain {
+            language: Some("rust".into()),
+            package_manager: Some("cargo".into()),
+            test_framework: Some("libtest".into()),
+        };
+        let section = render(&repo_config, &toolchain);
+        assert!(section.contains("Run `cargo test` to test your changes"));
+        assert!(section.contains("- `npm ci`"));
+        assert!(section.contains("other names get `dev/`"));
+        assert!(section.contains("Tickets labelled `hotfix` branch from `release/1.x`."));
+        assert!(section.contains("- Do not modify: `migrations/`."));
+        assert!(section.ends_with("<!-- conductor:end -->\n"));
+
+        let hand_written = "# My project\n\nBe nice.\n";
+        let first = merge(hand_written, &section);
+        assert!(first.starts_with("# My project\n\nBe nice.\n\n<!-- conductor:begin"));
+
+        let mut changed = repo_config.clone();
+        changed.project.test_command = Some("make check".into());
+        let updated = merge(
+            &format!("{first}\nFooter.\n"),
+            &render(&changed, &toolchain),
+        );
+        assert!(updated.starts_with("# My project\n\nBe nice.\n\n"));
+        assert!(updated.ends_with("<!-- conductor:end -->\n\nFooter.\n"));
+        assert!(updated.contains("Run `make check`"));
+        assert!(!updated.contains("cargo test"));
+        assert_eq!(updated.matches(BEGIN_MARKER).count(), 1);
+    }
+
+    #[test]
+    fn write_only_touches_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let section = render(&RepoConfig::default(), &Toolchain::default());
+        assert!(write(dir.path(), InstructionsFile::AgentsMd, &section).unwrap());
+        assert!(!write(dir.path(), InstructionsFile::AgentsMd, &section).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("AGENTS.md")).unwrap(),
+            section
+        );
+        assert_eq!("claude".parse(), Ok(InstructionsFile::ClaudeMd));
+        assert!("README.md".parse::<InstructionsFile>().is_err());
+    }
+}
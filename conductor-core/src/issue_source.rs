@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::db::query_collect;
 use crate::error::{ConductorError, Result};
+use crate::tickets::FieldMap;
 
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +21,9 @@ pub struct IssueSource {
 pub struct GitHubConfig {
     pub owner: String,
     pub repo: String,
+    /// Also sync open pull requests as `github_pr` tickets.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sync_prs: bool,
 }
 
 /// Configuration for a Jira issue source.
@@ -27,6 +31,109 @@ pub struct GitHubConfig {
 pub struct JiraConfig {
     pub jql: String,
     pub url: String,
+    /// Custom field mapping applied to each synced issue; see
+    /// [`crate::tickets::field_map`].
+    #[serde(default, skip_serializing_if = "FieldMap::is_empty")]
+    pub field_map: FieldMap,
+    /// Workflow status to transition to for each conductor ticket state
+    /// (`open`, `in_progress`, `closed`) when writing state back; see
+    /// [`JiraConfig::status_for`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub statuses: HashMap<String, String>,
+}
+
+impl JiraConfig {
+    /// The Jira status `state` maps to: the configured name, else `To Do`,
+    /// `In Progress` or `Done`.
+    pub fn status_for(&self, state: &str) -> Option<&str> {
+        if let Some(status) = self.statuses.get(state) {
+            return Some(status);
+        }
+        match state {
+            "open" => Some("To Do"),
+            "in_progress" => Some("In Progress"),
+            "closed" => Some("Done"),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for a GitLab issue source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabConfig {
+    /// Numeric project ID or full `group/project` path.
+    pub project_id: String,
+    /// Instance root, e.g. `https://gitlab.com` or a self-hosted URL.
+    pub base_url: String,
+    /// Environment variable holding a personal access token with `read_api`.
+    #[serde(default = "default_gitlab_token_env")]
+    pub token_env: String,
+}
+
+pub(crate) fn default_gitlab_token_env() -> String {
+    crate::gitlab::DEFAULT_TOKEN_ENV.to_string()
+}
+
+/// Configuration for a Linear issue source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearConfig {
+    /// Team key, the prefix of issue identifiers (`ENG` for `ENG-123`).
+    pub team_key: String,
+    /// Environment variable holding a Linear personal API key.
+    #[serde(default = "default_linear_api_key_env")]
+    pub api_key_env: String,
+}
+
+fn default_linear_api_key_env() -> String {
+    crate::linear::DEFAULT_API_KEY_ENV.to_string()
+}
+
+/// Configuration for a Bitbucket Cloud issue source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitbucketConfig {
+    /// Workspace ID, the first path segment of the repository URL.
+    pub workspace: String,
+    /// Repository slug, the second path segment of the repository URL.
+    pub repo_slug: String,
+    /// Environment variable holding an API token (or repository access token).
+    #[serde(default = "default_bitbucket_token_env")]
+    pub token_env: String,
+    /// Atlassian account email that owns the API token. When set the token is
+    /// sent as Basic credentials; when unset it is sent as a Bearer token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// REST API root; only overridden in tests.
+    #[serde(
+        default = "default_bitbucket_api_url",
+        skip_serializing_if = "is_default_bitbucket_api_url"
+    )]
+    pub api_url: String,
+}
+
+fn default_bitbucket_token_env() -> String {
+    crate::bitbucket::DEFAULT_TOKEN_ENV.to_string()
+}
+
+fn default_bitbucket_api_url() -> String {
+    crate::bitbucket::DEFAULT_API_URL.to_string()
+}
+
+fn is_default_bitbucket_api_url(url: &str) -> bool {
+    url == crate::bitbucket::DEFAULT_API_URL
+}
+
+/// Configuration for an Asana issue source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsanaConfig {
+    /// Project GID, the numeric segment after `/0/` in a project URL.
+    pub project_gid: String,
+    /// Environment variable holding an Asana personal access token.
+    #[serde(default = "default_asana_token_env")]
+    pub token_env: String,
+}
+
+fn default_asana_token_env() -> String {
+    crate::asana::DEFAULT_TOKEN_ENV.to_string()
 }
 
 /// Configuration for a Vantage (SDLC) issue source.
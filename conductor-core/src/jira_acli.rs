@@ -1,10 +1,11 @@
 use std::process::Command;
 
 use crate::error::{ConductorError, Result};
-use crate::tickets::TicketInput;
+use crate::tickets::{FieldMap, TicketInput};
 
 /// Sync Jira issues matching `jql` using the `acli` CLI.
-/// Returns a list of normalized TicketInputs ready for upsert.
+/// Returns a list of normalized TicketInputs ready for upsert, with
+/// `field_map` applied.
 ///
 /// # Trust model
 ///
@@ -15,10 +16,16 @@ use crate::tickets::TicketInput;
 /// configuration only**, never from end-user input. The only validation
 /// rejects structurally-broken input (empty, NUL, line breaks); JQL is not
 /// parsed semantically.
-pub fn sync_jira_issues_acli(jql: &str, base_url: &str) -> Result<Vec<TicketInput>> {
+pub fn sync_jira_issues_acli(
+    jql: &str,
+    base_url: &str,
+    field_map: &FieldMap,
+) -> Result<Vec<TicketInput>> {
     validate_jql(jql)?;
-    let json_str = run_acli_search(jql, "200")?;
-    parse_jira_issues(&json_str, base_url)
+    let json_str = run_acli_search(jql, "200", field_map)?;
+    let mut tickets = parse_jira_issues(&json_str, base_url)?;
+    tickets.iter_mut().for_each(|t| field_map.apply(t));
+    Ok(tickets)
 }
 
 /// Fetch a single Jira issue by key and return its current state.
@@ -27,43 +34,72 @@ pub fn sync_jira_issues_acli(jql: &str, base_url: &str) -> Result<Vec<TicketInpu
 /// validated against the canonical `PROJECT-123` format before being
 /// interpolated, so this entry point is not subject to the JQL trust
 /// caveat on [`sync_jira_issues_acli`].
-pub fn fetch_jira_issue(issue_key: &str, base_url: &str) -> Result<TicketInput> {
+pub fn fetch_jira_issue(
+    issue_key: &str,
+    base_url: &str,
+    field_map: &FieldMap,
+) -> Result<TicketInput> {
     validate_issue_key(issue_key)?;
     let jql = format!("key = {issue_key}");
-    let json_str = run_acli_search(&jql, "1")?;
-    let mut tickets = parse_jira_issues(&json_str, base_url)?;
-    tickets.pop().ok_or_else(|| ConductorError::TicketNotFound {
-        id: issue_key.to_string(),
-    })
+    let json_str = run_acli_search(&jql, "1", field_map)?;
+    let mut ticket = parse_jira_issues(&json_str, base_url)?
+        .pop()
+        .ok_or_else(|| ConductorError::TicketNotFound {
+            id: issue_key.to_string(),
+        })?;
+    field_map.apply(&mut ticket);
+    Ok(ticket)
 }
 
 /// Run `acli jira workitem search` with a JQL query and return its stdout as
 /// a UTF-8-lossy `String`. Shared between [`sync_jira_issues_acli`] and
-/// [`fetch_jira_issue`].
-fn run_acli_search(jql: &str, limit: &str) -> Result<String> {
-    let output = Command::new("acli")
-        .args([
-            "jira",
-            "workitem",
-            "search",
-            "--jql",
-            jql,
-            "--json",
-            "--limit",
-            limit,
-            "--fields",
-            "key,summary,status,priority,assignee,labels,description",
-        ])
-        .output()
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                ConductorError::TicketSync(
-                    "acli not found. Install the Atlassian CLI (acli) and ensure it is on your PATH.".to_string(),
-                )
-            } else {
-                ConductorError::TicketSync(format!("failed to run acli: {e}"))
-            }
-        })?;
+/// [`fetch_jira_issue`]. Custom fields read by `field_map` are requested on
+/// top of the standard set.
+fn run_acli_search(jql: &str, limit: &str, field_map: &FieldMap) -> Result<String> {
+    let fields = acli_fields(field_map);
+    run_acli(&[
+        "jira", "workitem", "search", "--jql", jql, "--json", "--limit", limit, "--fields", &fields,
+    ])
+}
+
+/// Comment on a Jira issue via `acli jira workitem comment create`.
+pub fn comment_on_jira_issue(issue_key: &str, body: &str) -> Result<()> {
+    validate_issue_key(issue_key)?;
+    run_acli(&[
+        "jira", "workitem", "comment", "create", "--key", issue_key, "--body", body,
+    ])?;
+    Ok(())
+}
+
+/// Move a Jira issue to the workflow status named `status` (e.g. `Done`) via
+/// `acli jira workitem transition`.
+pub fn transition_jira_issue(issue_key: &str, status: &str) -> Result<()> {
+    validate_issue_key(issue_key)?;
+    run_acli(&[
+        "jira",
+        "workitem",
+        "transition",
+        "--key",
+        issue_key,
+        "--status",
+        status,
+        "--yes",
+    ])?;
+    Ok(())
+}
+
+/// Run `acli` with `args` and return its stdout as a UTF-8-lossy `String`.
+fn run_acli(args: &[&str]) -> Result<String> {
+    let output = crate::command::output(Command::new("acli").args(args)).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ConductorError::TicketSync(
+                "acli not found. Install the Atlassian CLI (acli) and ensure it is on your PATH."
+                    .to_string(),
+            )
+        } else {
+            ConductorError::TicketSync(format!("failed to run acli: {e}"))
+        }
+    })?;
 
     if !output.status.success() {
         return Err(ConductorError::TicketSync(
@@ -74,6 +110,21 @@ fn run_acli_search(jql: &str, limit: &str) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Fields always requested from acli.
+const ACLI_FIELDS: &str = "key,summary,status,priority,assignee,labels,description,sprint,customfield_10020,parent,customfield_10014";
+
+/// [`ACLI_FIELDS`] plus any other field `field_map` points into.
+fn acli_fields(field_map: &FieldMap) -> String {
+    let mut fields = ACLI_FIELDS.to_string();
+    for name in field_map.source_fields() {
+        if !ACLI_FIELDS.split(',').any(|f| f == name) {
+            fields.push(',');
+            fields.push_str(name);
+        }
+    }
+    fields
+}
+
 /// Reject obviously-broken JQL input before handing it to acli.
 ///
 /// This is a defense-in-depth check; see [`sync_jira_issues_acli`] for the
@@ -162,6 +213,7 @@ fn parse_jira_issues(json_str: &str, base_url: &str) -> Result<Vec<TicketInput>>
                 .unwrap_or_default();
 
             let url = format!("{base_url}/browse/{key}");
+            let (sprint, sprint_current) = parse_jira_sprint(fields);
 
             TicketInput {
                 source_type: "jira".to_string(),
@@ -177,7 +229,9 @@ fn parse_jira_issues(json_str: &str, base_url: &str) -> Result<Vec<TicketInput>>
                 label_details: vec![],
                 blocked_by: vec![],
                 children: vec![],
-                parent: None,
+                parent: parse_jira_parent(fields),
+                sprint,
+                sprint_current,
             }
         })
         .collect();
@@ -185,6 +239,46 @@ fn parse_jira_issues(json_str: &str, base_url: &str) -> Result<Vec<TicketInput>>
     Ok(tickets)
 }
 
+/// Extract `(sprint name, is_active)` from a Jira issue's fields.
+///
+/// Jira Cloud exposes sprints through the `customfield_10020` array (some
+/// instances surface it as `sprint`). An issue can carry several sprints when it
+/// rolled over; the active one wins, otherwise the most recent entry is used.
+fn parse_jira_sprint(fields: &serde_json::Value) -> (Option<String>, bool) {
+    let sprints = fields["sprint"]
+        .as_array()
+        .or_else(|| fields["customfield_10020"].as_array());
+    let Some(sprints) = sprints else {
+        // Single-object form: `"sprint": {"name": ..., "state": ...}`.
+        let name = fields["sprint"]["name"].as_str().map(|s| s.to_string());
+        let active = name.is_some() && fields["sprint"]["state"].as_str() == Some("active");
+        return (name, active);
+    };
+    if let Some(active) = sprints
+        .iter()
+        .find(|s| s["state"].as_str() == Some("active"))
+    {
+        return (active["name"].as_str().map(|s| s.to_string()), true);
+    }
+    let name = sprints
+        .iter()
+        .rev()
+        .find_map(|s| s["name"].as_str())
+        .map(|s| s.to_string());
+    (name, false)
+}
+
+/// Key of a Jira issue's parent: the `parent` field (epics in team-managed
+/// projects, and every parent since Jira Cloud unified them), falling back to
+/// the classic "Epic Link" field, `customfield_10014`.
+fn parse_jira_parent(fields: &serde_json::Value) -> Option<String> {
+    fields["parent"]["key"]
+        .as_str()
+        .or_else(|| fields["customfield_10014"].as_str())
+        .filter(|k| !k.is_empty())
+        .map(|k| k.to_string())
+}
+
 /// Map a Jira status name to a Conductor state.
 fn map_jira_status(status: &str) -> &str {
     match status.to_lowercase().as_str() {
@@ -251,6 +345,59 @@ mod tests {
         assert_eq!(tickets[1].labels, Vec::<String>::new());
     }
 
+    #[test]
+    fn test_parse_jira_issues_reads_active_sprint() {
+        let json = r#"[{
+            "key": "PROJ-3",
+            "fields": {
+                "summary": "Sprinted",
+                "status": { "name": "To Do" },
+                "customfield_10020": [
+                    { "name": "Sprint 11", "state": "closed" },
+                    { "name": "Sprint 12", "state": "active" }
+                ]
+            }
+        }, {
+            "key": "PROJ-4",
+            "fields": {
+                "summary": "Planned",
+                "status": { "name": "To Do" },
+                "customfield_10020": [{ "name": "Sprint 13", "state": "future" }]
+            }
+        }]"#;
+        let tickets = parse_jira_issues(json, "https://jira.example.com").unwrap();
+        assert_eq!(tickets[0].sprint.as_deref(), Some("Sprint 12"));
+        assert!(tickets[0].sprint_current);
+        assert_eq!(tickets[1].sprint.as_deref(), Some("Sprint 13"));
+        assert!(!tickets[1].sprint_current);
+    }
+
+    #[test]
+    fn test_parse_jira_issues_reads_parent_and_epic_link() {
+        let json = r#"[{
+            "key": "PROJ-5",
+            "fields": {
+                "summary": "Child",
+                "status": { "name": "To Do" },
+                "parent": { "key": "PROJ-1", "fields": { "summary": "Epic" } }
+            }
+        }, {
+            "key": "PROJ-6",
+            "fields": {
+                "summary": "Classic",
+                "status": { "name": "To Do" },
+                "customfield_10014": "PROJ-2"
+            }
+        }, {
+            "key": "PROJ-7",
+            "fields": { "summary": "Orphan", "status": { "name": "To Do" } }
+        }]"#;
+        let tickets = parse_jira_issues(json, "https://jira.example.com").unwrap();
+        assert_eq!(tickets[0].parent.as_deref(), Some("PROJ-1"));
+        assert_eq!(tickets[1].parent.as_deref(), Some("PROJ-2"));
+        assert_eq!(tickets[2].parent, None);
+    }
+
     #[test]
     fn test_parse_jira_issues_empty() {
         let tickets = parse_jira_issues("[]", "https://jira.example.com").unwrap();
@@ -378,7 +525,11 @@ mod tests {
 
     #[test]
     fn test_fetch_jira_issue_rejects_injection_before_acli() {
-        match fetch_jira_issue("PROJ-1 OR key != PROJ-1", "https://jira.example.com") {
+        match fetch_jira_issue(
+            "PROJ-1 OR key != PROJ-1",
+            "https://jira.example.com",
+            &FieldMap::default(),
+        ) {
             Err(e) => assert!(
                 e.to_string().contains("invalid issue key format"),
                 "expected validation error, got: {e}"
@@ -390,7 +541,7 @@ mod tests {
     #[test]
     fn test_fetch_jira_issue_rejects_malformed_key_before_acli() {
         for bad in &["", "NOHYPHEN", "-123", "PROJ-", "proj-1", "PROJ-abc"] {
-            match fetch_jira_issue(bad, "https://jira.example.com") {
+            match fetch_jira_issue(bad, "https://jira.example.com", &FieldMap::default()) {
                 Err(e) => assert!(
                     e.to_string().contains("invalid issue key format"),
                     "key {bad:?}: expected validation error, got: {e}"
@@ -424,14 +575,18 @@ mod tests {
     fn test_sync_jira_issues_acli_rejects_bad_jql_before_acli() {
         // Hitting validate_jql means we never spawn acli, so this works
         // without acli on PATH.
-        match sync_jira_issues_acli("", "https://jira.example.com") {
+        match sync_jira_issues_acli("", "https://jira.example.com", &FieldMap::default()) {
             Err(e) => assert!(
                 e.to_string().contains("JQL must not be empty"),
                 "expected validation error, got: {e}"
             ),
             Ok(_) => panic!("expected empty JQL to be rejected"),
         }
-        match sync_jira_issues_acli("project = FOO\n", "https://jira.example.com") {
+        match sync_jira_issues_acli(
+            "project = FOO\n",
+            "https://jira.example.com",
+            &FieldMap::default(),
+        ) {
             Err(e) => assert!(
                 e.to_string().contains("must not contain NUL or line-break"),
                 "expected validation error, got: {e}"
@@ -452,4 +607,17 @@ mod tests {
             });
         assert!(matches!(result, Err(ConductorError::TicketNotFound { .. })));
     }
+
+    #[test]
+    fn test_acli_fields_adds_mapped_custom_fields() {
+        let map: FieldMap = serde_json::from_str(
+            r#"{"team": "/fields/customfield_10100/value", "sprint": "/fields/customfield_10020"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            acli_fields(&map),
+            format!("{ACLI_FIELDS},customfield_10100")
+        );
+        assert_eq!(acli_fields(&FieldMap::default()), ACLI_FIELDS);
+    }
 }
@@ -29,40 +29,68 @@ pub(crate) use impl_sql_enum;
 pub mod conductor;
 pub use conductor::Conductor;
 
+pub mod actions;
+pub mod activity;
 pub mod agent;
 pub mod agent_config;
 pub mod agent_runtime;
+pub mod api_token;
+pub mod asana;
 pub mod attachments;
+pub mod bitbucket;
+pub mod changelog;
+pub mod changes;
+pub mod changeset;
+pub mod command;
 pub mod config;
+pub mod config_migration;
 pub mod conversation;
+#[cfg(unix)]
+pub mod daemon;
 pub mod db;
 pub mod error;
 pub(crate) mod git;
 pub mod github;
 pub mod github_app;
+pub mod gitlab;
 pub(crate) mod graph;
 pub mod hooks;
 pub mod infer;
+pub mod instructions;
 pub mod issue_source;
 pub mod jira_acli;
+pub mod linear;
+pub mod maintenance;
 pub mod models;
+pub mod notifications;
 pub mod notify;
+pub mod platform;
 pub mod process_utils;
 pub mod prompt_config;
 pub mod push;
+pub mod redact;
 pub mod repo;
+pub mod repo_visibility;
+pub mod retention;
 pub(crate) mod retry;
 pub mod runtime;
 pub mod schema_config;
 pub mod stats;
+pub mod status;
 pub mod text_util;
 pub mod ticket_source;
 pub mod tickets;
+pub mod tmux;
+pub mod today;
+pub mod toolchain;
+pub mod tools;
 pub mod vantage;
+pub mod watch;
 pub mod workflow;
 pub mod workflow_config;
 pub mod workflow_ephemeral;
 pub mod workflow_template;
+pub mod workspace;
 pub mod worktree;
 
 /// Generate a new ULID-based unique ID string.
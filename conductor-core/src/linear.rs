@@ -0,0 +1,354 @@
+use crate::error::{ConductorError, Result};
+use crate::issue_source::LinearConfig;
+use crate::tickets::{TicketInput, TicketLabelInput};
+
+/// Linear's GraphQL endpoint.
+const API_URL: &str = "https://api.linear.app/graphql";
+/// Issues requested per page; Linear caps `first` at 250.
+const PER_PAGE: u32 = 100;
+/// Upper bound on pages fetched per sync (1000 open issues).
+const MAX_PAGES: u32 = 10;
+
+/// Default environment variable holding a Linear personal API key.
+pub const DEFAULT_API_KEY_ENV: &str = "LINEAR_API_KEY";
+
+/// Fields selected for every issue; shared by the list and single-issue queries.
+const ISSUE_FIELDS: &str = "
+    identifier title description url priorityLabel
+    state { type }
+    assignee { displayName }
+    labels { nodes { name color } }
+    cycle { name number isActive }
+    parent { identifier }
+    children { nodes { identifier } }
+    inverseRelations { nodes { type issue { identifier } } }
+";
+
+/// Run a GraphQL query against `endpoint` and return its `data` object.
+///
+/// Linear personal API keys are sent as-is in the `Authorization` header (no
+/// `Bearer` prefix). GraphQL-level errors arrive with HTTP 200 and are
+/// reported from the `errors` array.
+fn graphql(
+    cfg: &LinearConfig,
+    endpoint: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let api_key = std::env::var(&cfg.api_key_env).map_err(|_| {
+        ConductorError::TicketSync(format!(
+            "Linear API key not set; export it in ${}",
+            cfg.api_key_env
+        ))
+    })?;
+    let resp = ureq::post(endpoint)
+        .set("User-Agent", "conductor-ai")
+        .set("Authorization", &api_key)
+        .send_json(serde_json::json!({ "query": query, "variables": variables }))
+        .map_err(|e| match e {
+            ureq::Error::Status(401, _) => ConductorError::TicketSync(format!(
+                "Linear rejected the API key in ${} (401)",
+                cfg.api_key_env
+            )),
+            e => ConductorError::TicketSync(format!("Linear request failed: {e}")),
+        })?;
+    let mut body: serde_json::Value = resp
+        .into_json()
+        .map_err(|e| ConductorError::TicketSync(format!("failed to parse Linear response: {e}")))?;
+    if let Some(errors) = body["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e["message"].as_str())
+            .collect();
+        return Err(ConductorError::TicketSync(format!(
+            "Linear API error: {}",
+            messages.join("; ")
+        )));
+    }
+    Ok(body["data"].take())
+}
+
+/// Map a Linear workflow state type to a ticket state.
+///
+/// `started` becomes `in_progress`; `completed` and `canceled` become
+/// `closed`; `triage`, `backlog` and `unstarted` are `open`.
+fn map_linear_state(state_type: &str) -> &'static str {
+    match state_type {
+        "started" => "in_progress",
+        "completed" | "canceled" => "closed",
+        _ => "open",
+    }
+}
+
+fn identifiers(nodes: &serde_json::Value) -> Vec<String> {
+    nodes
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|n| n["identifier"].as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build a [`TicketInput`] from a Linear issue JSON value.
+///
+/// The team-scoped identifier (`ENG-123`) is the source ID. Priority uses
+/// Linear's label (`Urgent`, `High`, …), with "No priority" mapped to `None`.
+fn build_ticket_input(issue: &serde_json::Value) -> Result<TicketInput> {
+    let identifier = issue["identifier"].as_str().ok_or_else(|| {
+        ConductorError::TicketSync(format!(
+            "Linear issue is missing an 'identifier' field: {issue}"
+        ))
+    })?;
+    let label_details: Vec<TicketLabelInput> = issue["labels"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|l| {
+                    Some(TicketLabelInput {
+                        name: l["name"].as_str()?.to_string(),
+                        color: l["color"].as_str().map(|c| c.to_string()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let blocked_by: Vec<String> = issue["inverseRelations"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter(|r| r["type"].as_str() == Some("blocks"))
+                .filter_map(|r| r["issue"]["identifier"].as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let cycle = &issue["cycle"];
+    let sprint = cycle["name"]
+        .as_str()
+        .filter(|n| !n.is_empty())
+        .map(|n| n.to_string())
+        .or_else(|| cycle["number"].as_u64().map(|n| format!("Cycle {n}")));
+    Ok(TicketInput {
+        source_type: "linear".to_string(),
+        source_id: identifier.to_string(),
+        title: issue["title"].as_str().unwrap_or("").to_string(),
+        body: issue["description"].as_str().unwrap_or("").to_string(),
+        state: map_linear_state(issue["state"]["type"].as_str().unwrap_or("unstarted")).to_string(),
+        labels: label_details.iter().map(|l| l.name.clone()).collect(),
+        label_details,
+        assignee: issue["assignee"]["displayName"]
+            .as_str()
+            .map(|s| s.to_string()),
+        priority: issue["priorityLabel"]
+            .as_str()
+            .filter(|p| *p != "No priority")
+            .map(|p| p.to_string()),
+        url: issue["url"].as_str().unwrap_or("").to_string(),
+        raw_json: serde_json::to_string(issue).ok(),
+        blocked_by,
+        children: identifiers(&issue["children"]["nodes"]),
+        parent: issue["parent"]["identifier"]
+            .as_str()
+            .map(|s| s.to_string()),
+        sprint_current: sprint.is_some() && cycle["isActive"].as_bool().unwrap_or(false),
+        sprint,
+    })
+}
+
+/// Sync open issues of a Linear team via the GraphQL API.
+/// Returns a list of normalized TicketInputs ready for upsert.
+///
+/// Completed and canceled issues are excluded so that
+/// `close_missing_tickets` closes them locally, as for GitHub.
+pub fn sync_linear_issues(cfg: &LinearConfig) -> Result<Vec<TicketInput>> {
+    sync_linear_issues_at(cfg, API_URL)
+}
+
+fn sync_linear_issues_at(cfg: &LinearConfig, endpoint: &str) -> Result<Vec<TicketInput>> {
+    let query = format!(
+        "query($teamKey: String!, $after: String) {{
+            issues(first: {PER_PAGE}, after: $after, filter: {{
+                team: {{ key: {{ eq: $teamKey }} }},
+                state: {{ type: {{ nin: [\"completed\", \"canceled\"] }} }}
+            }}) {{
+                nodes {{ {ISSUE_FIELDS} }}
+                pageInfo {{ hasNextPage endCursor }}
+            }}
+        }}"
+    );
+    let mut tickets = Vec::new();
+    let mut after: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let data = graphql(
+            cfg,
+            endpoint,
+            &query,
+            serde_json::json!({ "teamKey": cfg.team_key, "after": after }),
+        )?;
+        let issues = &data["issues"];
+        for issue in issues["nodes"].as_array().into_iter().flatten() {
+            tickets.push(build_ticket_input(issue)?);
+        }
+        let page = &issues["pageInfo"];
+        match page["endCursor"].as_str() {
+            Some(cursor) if page["hasNextPage"].as_bool().unwrap_or(false) => {
+                after = Some(cursor.to_string())
+            }
+            _ => break,
+        }
+    }
+    Ok(tickets)
+}
+
+/// Fetch a single Linear issue by identifier (e.g. `ENG-123`) and return its
+/// current state.
+pub fn fetch_linear_issue(cfg: &LinearConfig, identifier: &str) -> Result<TicketInput> {
+    fetch_linear_issue_at(cfg, API_URL, identifier)
+}
+
+fn fetch_linear_issue_at(
+    cfg: &LinearConfig,
+    endpoint: &str,
+    identifier: &str,
+) -> Result<TicketInput> {
+    let query = format!("query($id: String!) {{ issue(id: $id) {{ {ISSUE_FIELDS} }} }}");
+    let data = graphql(
+        cfg,
+        endpoint,
+        &query,
+        serde_json::json!({ "id": identifier }),
+    )?;
+    if data["issue"].is_null() {
+        return Err(ConductorError::TicketSync(format!(
+            "Linear issue {identifier} not found"
+        )));
+    }
+    build_ticket_input(&data["issue"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(api_key_env: &str) -> LinearConfig {
+        LinearConfig {
+            team_key: "ENG".to_string(),
+            api_key_env: api_key_env.to_string(),
+        }
+    }
+
+    #[test]
+    fn sync_follows_cursor_and_maps_fields() {
+        std::env::set_var("CONDUCTOR_TEST_LINEAR_KEY_SYNC", "lin_api_secret");
+        let mut server = mockito::Server::new();
+        let _p1 = server
+            .mock("POST", "/graphql")
+            .match_header("Authorization", "lin_api_secret")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "teamKey": "ENG", "after": null }
+            })))
+            .with_body(
+                serde_json::json!({ "data": { "issues": {
+                    "nodes": [{
+                        "identifier": "ENG-7", "title": "Crash on save",
+                        "description": "Steps...", "url": "https://linear.app/acme/issue/ENG-7",
+                        "priorityLabel": "High",
+                        "state": { "type": "started" },
+                        "assignee": { "displayName": "alice" },
+                        "labels": { "nodes": [{ "name": "bug", "color": "#eb5757" }] },
+                        "cycle": { "name": null, "number": 12, "isActive": true },
+                        "parent": { "identifier": "ENG-1" },
+                        "children": { "nodes": [] },
+                        "inverseRelations": { "nodes": [
+                            { "type": "blocks", "issue": { "identifier": "ENG-3" } },
+                            { "type": "related", "issue": { "identifier": "ENG-4" } }
+                        ]}
+                    }],
+                    "pageInfo": { "hasNextPage": true, "endCursor": "c1" }
+                }}})
+                .to_string(),
+            )
+            .create();
+        let _p2 = server
+            .mock("POST", "/graphql")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "after": "c1" }
+            })))
+            .with_body(
+                serde_json::json!({ "data": { "issues": {
+                    "nodes": [{
+                        "identifier": "ENG-8", "title": "Docs",
+                        "priorityLabel": "No priority", "state": { "type": "backlog" }
+                    }],
+                    "pageInfo": { "hasNextPage": false, "endCursor": "c2" }
+                }}})
+                .to_string(),
+            )
+            .create();
+
+        let cfg = config("CONDUCTOR_TEST_LINEAR_KEY_SYNC");
+        let tickets = sync_linear_issues_at(&cfg, &format!("{}/graphql", server.url())).unwrap();
+        assert_eq!(tickets.len(), 2);
+        let t = &tickets[0];
+        assert_eq!(t.source_type, "linear");
+        assert_eq!(t.source_id, "ENG-7");
+        assert_eq!(t.state, "in_progress");
+        assert_eq!(t.priority.as_deref(), Some("High"));
+        assert_eq!(t.assignee.as_deref(), Some("alice"));
+        assert_eq!(t.labels, vec!["bug"]);
+        assert_eq!(t.label_details[0].color.as_deref(), Some("#eb5757"));
+        assert_eq!(t.sprint.as_deref(), Some("Cycle 12"));
+        assert!(t.sprint_current);
+        assert_eq!(t.parent.as_deref(), Some("ENG-1"));
+        assert_eq!(t.blocked_by, vec!["ENG-3"]);
+        assert_eq!(tickets[1].state, "open");
+        assert_eq!(tickets[1].priority, None);
+        assert_eq!(tickets[1].body, "");
+    }
+
+    #[test]
+    fn fetch_one_maps_canceled_to_closed() {
+        std::env::set_var("CONDUCTOR_TEST_LINEAR_KEY_FETCH", "k");
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("POST", "/graphql")
+            .with_body(
+                r#"{"data":{"issue":{"identifier":"ENG-9","title":"Old","state":{"type":"canceled"}}}}"#,
+            )
+            .create();
+        let cfg = config("CONDUCTOR_TEST_LINEAR_KEY_FETCH");
+        let t = fetch_linear_issue_at(&cfg, &format!("{}/graphql", server.url()), "ENG-9").unwrap();
+        assert_eq!(t.state, "closed");
+    }
+
+    #[test]
+    fn graphql_errors_are_reported_as_ticket_sync_error() {
+        std::env::set_var("CONDUCTOR_TEST_LINEAR_KEY_ERR", "k");
+        let mut server = mockito::Server::new();
+        let _m = server
+            .mock("POST", "/graphql")
+            .with_body(r#"{"errors":[{"message":"Entity not found: Issue"}],"data":null}"#)
+            .create();
+        let cfg = config("CONDUCTOR_TEST_LINEAR_KEY_ERR");
+        let err = fetch_linear_issue_at(&cfg, &format!("{}/graphql", server.url()), "ENG-404")
+            .err()
+            .expect("expected error");
+        assert!(
+            matches!(&err, ConductorError::TicketSync(msg) if msg.contains("Entity not found")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn missing_api_key_is_reported() {
+        let cfg = config("CONDUCTOR_TEST_LINEAR_KEY_UNSET");
+        let err = sync_linear_issues_at(&cfg, "http://127.0.0.1:9/graphql")
+            .err()
+            .expect("expected error");
+        assert!(
+            matches!(&err, ConductorError::TicketSync(msg) if msg.contains("CONDUCTOR_TEST_LINEAR_KEY_UNSET")),
+            "unexpected error: {err:?}"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Periodic health checks over agent runs, worktrees and workflows. The TUI
+//! runs [`reap`] from its poller and `conductor daemon` from its maintenance
+//! loop; the web server keeps its own reaper, which also records
+//! notifications.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use rusqlite::Connection;
+
+use crate::agent::AgentManager;
+use crate::config::Config;
+use crate::worktree::WorktreeManager;
+
+/// Reap orphaned agent runs and workflow steps, clean up stale (and, with
+/// `auto_cleanup_merged_branches`, merged) worktrees, flag live worktrees
+/// whose directory disappeared, and claim stuck or
+/// resumable workflow runs, post finished agent results to their tickets
+/// (`[agents] post_result_to_ticket`), drop expired `change_log` rows and close
+/// orphaned windows of the managed tmux session. `db` is the path `conn` was
+/// opened from.
+///
+/// Failures are logged and do not stop the remaining checks. Returns how many
+/// agent and workflow runs were marked dead.
+pub fn reap(conn: &Connection, db: &Path, config: &Config) -> usize {
+    let agent_mgr = AgentManager::new(conn);
+    let wt_mgr = WorktreeManager::new(conn, config);
+    let mut reaped = agent_mgr.reap_orphaned_runs().unwrap_or_else(|e| {
+        tracing::warn!("reap_orphaned_runs failed: {e}");
+        0
+    });
+    if let Err(e) = agent_mgr.dismiss_expired_feedback_requests() {
+        tracing::warn!("dismiss_expired_feedback_requests failed: {e}");
+    }
+    if let Err(e) = wt_mgr.reap_stale_worktrees() {
+        tracing::warn!("reap_stale_worktrees failed: {e}");
+    }
+    if let Err(e) = wt_mgr.check_missing_paths() {
+        tracing::warn!("check_missing_paths failed: {e}");
+    }
+    // Pop stashes for runs that ended while nobody was waiting on them.
+    if let Err(e) = crate::agent::LaunchGuards::new(conn).restore_finished() {
+        tracing::warn!("restoring pre-run stashes failed: {e}");
+    }
+    if config.general.auto_cleanup_merged_branches {
+        match wt_mgr.cleanup_merged_worktrees(None) {
+            Ok(n) if n > 0 => tracing::info!("Auto-cleaned {n} merged worktree(s)"),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("cleanup_merged_worktrees failed: {e}"),
+        }
+    }
+    // recover_stuck_steps_from_db opens its own write connection at `db`,
+    // so `conn` and the write path don't deadlock on the same mutex inside
+    // SqliteWorkflowPersistence.
+    match crate::workflow::recover_stuck_steps_from_db(conn, db) {
+        Ok(n) if n > 0 => tracing::debug!("Recovered {n} stuck workflow step(s)"),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("recover_stuck_steps_from_db failed: {e}"),
+    }
+    match crate::workflow::reap_orphaned_workflow_runs(conn) {
+        Ok(n) if n > 0 => {
+            tracing::debug!("Reaped {n} orphaned workflow run(s)");
+            reaped += n;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("reap_orphaned_workflow_runs failed: {e}"),
+    }
+    match crate::workflow::reap_orphaned_script_steps(conn) {
+        Ok(n) if n > 0 => tracing::debug!("Reaped {n} orphaned script step(s)"),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("reap_orphaned_script_steps failed: {e}"),
+    }
+    match crate::workflow::reap_finalization_stuck_workflow_runs(conn, 60) {
+        Ok(n) if n > 0 => {
+            tracing::info!("Reaper finalized {n} stuck workflow run(s)");
+            reaped += n;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("reap_finalization_stuck_workflow_runs failed: {e}"),
+    }
+    {
+        let conductor_bin_dir = crate::workflow::resolve_conductor_bin_dir();
+        let stale_mins = config.general.stale_workflow_minutes;
+        let configurable_threshold = if stale_mins > 0 {
+            Some((stale_mins * 60) as i64)
+        } else {
+            None
+        };
+        match crate::workflow::claim_stuck_workflows(conn, config, configurable_threshold) {
+            Ok(claimed) => crate::workflow::spawn_claimed_runs(
+                claimed,
+                Arc::new(config.clone()),
+                conductor_bin_dir.clone(),
+            ),
+            Err(e) => tracing::warn!("claim_stuck_workflows failed: {e}"),
+        }
+        let auto_resume_limit = config.general.auto_resume_limit;
+        if auto_resume_limit > 0 {
+            match crate::workflow::classify_resumable_workflows(conn, auto_resume_limit) {
+                Ok(n) if n > 0 => {
+                    tracing::info!("Classifier flagged {n} workflow run(s) for auto-resume")
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("classify_resumable_workflows failed: {e}"),
+            }
+            match crate::workflow::claim_needs_resume_runs(conn, config) {
+                Ok(claimed) => crate::workflow::spawn_claimed_runs(
+                    claimed,
+                    Arc::new(config.clone()),
+                    conductor_bin_dir.clone(),
+                ),
+                Err(e) => tracing::warn!("claim_needs_resume_runs failed: {e}"),
+            }
+        }
+    }
+    let posted = crate::tickets::post_agent_results(conn, config);
+    if posted > 0 {
+        tracing::info!("Posted {posted} agent result(s) to their tickets");
+    }
+    if let Err(e) = crate::changes::prune(conn) {
+        tracing::warn!("pruning change_log failed: {e}");
+    }
+    match crate::tmux::cleanup_orphan_windows(conn, config) {
+        Ok(closed) if !closed.is_empty() => {
+            tracing::info!("Closed {} orphaned tmux window(s)", closed.len())
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("tmux window cleanup failed: {e}"),
+    }
+    match crate::agent::sandbox::cleanup_orphan_containers(conn) {
+        Ok(removed) if !removed.is_empty() => {
+            tracing::info!("Removed {} orphaned agent container(s)", removed.len())
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("agent container cleanup failed: {e}"),
+    }
+    reaped
+}
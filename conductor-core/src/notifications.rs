@@ -0,0 +1,373 @@
+//! Notification center for the web UI.
+//!
+//! Unlike [`crate::notify`], which pushes alerts out to desktop, Slack and
+//! hooks, this stores them so a browser can list recent agent and workflow
+//! outcomes. Notifications are shared by everyone who can see the repo they
+//! are about; whether one has been read or cleared is tracked per reader,
+//! where a reader is an API token id or [`LOCAL_READER`] for unauthenticated
+//! use.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+/// Reader key used when a request carries no API token.
+pub const LOCAL_READER: &str = "local";
+
+/// Only the newest notifications are kept; older ones are pruned on insert.
+const MAX_NOTIFICATIONS: i64 = 500;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for NotificationSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for NotificationSeverity {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "success" => Ok(Self::Success),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("unknown notification severity: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(NotificationSeverity);
+
+/// A notification to record.
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    /// Machine-readable source, e.g. `agent_run_failed`.
+    pub kind: String,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    /// Web UI path to open when the notification is clicked.
+    pub url: Option<String>,
+    /// The repo it is about, if any; hidden from viewers who cannot see it.
+    pub repo_id: Option<String>,
+}
+
+/// A notification as seen by one reader.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub kind: String,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    pub created_at: String,
+    /// Whether this reader has read it.
+    pub read: bool,
+}
+
+fn row_to_notification(row: &rusqlite::Row) -> rusqlite::Result<Notification> {
+    Ok(Notification {
+        id: row.get("id")?,
+        kind: row.get("kind")?,
+        severity: row.get("severity")?,
+        title: row.get("title")?,
+        body: row.get("body")?,
+        url: row.get("url")?,
+        created_at: row.get("created_at")?,
+        read: row.get::<_, Option<String>>("read_at")?.is_some(),
+    })
+}
+
+/// Notifications visible to `:reader`, i.e. not cleared by them.
+const VISIBLE_FROM: &str = "FROM notifications n \
+     LEFT JOIN notification_receipts r ON r.notification_id = n.id AND r.reader = :reader \
+     WHERE r.cleared_at IS NULL";
+
+/// `AND`-prefixed condition keeping notifications about repos `viewer` can
+/// see, binding `:viewer` to `owner` when one is needed.
+fn visible_condition<'p>(
+    viewer: &Viewer,
+    owner: &'p Option<&'p str>,
+    params: &mut Vec<(&'p str, &'p dyn rusqlite::ToSql)>,
+) -> String {
+    match visible_repo_sql(viewer, "COALESCE(n.repo_id, '')", ":viewer") {
+        Some(condition) => {
+            params.push((":viewer", owner));
+            format!(" AND {condition}")
+        }
+        None => String::new(),
+    }
+}
+
+pub struct NotificationManager<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> NotificationManager<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Store a notification, dropping the oldest ones beyond the retention cap.
+    pub fn record(&self, new: &NewNotification) -> Result<Notification> {
+        let notification = Notification {
+            id: crate::new_id(),
+            kind: new.kind.clone(),
+            severity: new.severity,
+            title: new.title.clone(),
+            body: new.body.clone(),
+            url: new.url.clone(),
+            created_at: Utc::now().to_rfc3339(),
+            read: false,
+        };
+        self.conn.execute(
+            "INSERT INTO notifications (id, kind, severity, title, body, url, repo_id, created_at) \
+             VALUES (:id, :kind, :severity, :title, :body, :url, :repo_id, :created_at)",
+            named_params! {
+                ":id": notification.id,
+                ":kind": notification.kind,
+                ":severity": notification.severity,
+                ":title": notification.title,
+                ":body": notification.body,
+                ":url": notification.url,
+                ":repo_id": new.repo_id,
+                ":created_at": notification.created_at,
+            },
+        )?;
+        self.conn.execute(
+            "DELETE FROM notifications WHERE id NOT IN \
+             (SELECT id FROM notifications ORDER BY created_at DESC, id DESC LIMIT :keep)",
+            named_params! { ":keep": MAX_NOTIFICATIONS },
+        )?;
+        Ok(notification)
+    }
+
+    /// The reader's notifications, newest first.
+    pub fn list(&self, reader: &str, unread_only: bool, limit: usize) -> Result<Vec<Notification>> {
+        self.list_visible(&Viewer::Local, reader, unread_only, limit)
+    }
+
+    /// Like [`Self::list`], leaving out notifications about repos that are
+    /// private to someone other than `viewer`.
+    pub fn list_visible(
+        &self,
+        viewer: &Viewer,
+        reader: &str,
+        unread_only: bool,
+        limit: usize,
+    ) -> Result<Vec<Notification>> {
+        let unread = if unread_only {
+            " AND r.read_at IS NULL"
+        } else {
+            ""
+        };
+        let limit = limit as i64;
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> =
+            vec![(":reader", &reader), (":limit", &limit)];
+        let visible = visible_condition(viewer, &owner, &mut params);
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT n.id, n.kind, n.severity, n.title, n.body, n.url, n.created_at, r.read_at \
+                 {VISIBLE_FROM}{visible}{unread} ORDER BY n.created_at DESC, n.id DESC LIMIT :limit"
+            ),
+            params.as_slice(),
+            row_to_notification,
+        )
+    }
+
+    pub fn unread_count(&self, reader: &str) -> Result<i64> {
+        self.unread_count_visible(&Viewer::Local, reader)
+    }
+
+    /// Like [`Self::unread_count`], counting only notifications `viewer` can
+    /// see.
+    pub fn unread_count_visible(&self, viewer: &Viewer, reader: &str) -> Result<i64> {
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":reader", &reader)];
+        let visible = visible_condition(viewer, &owner, &mut params);
+        Ok(self.conn.query_row(
+            &format!("SELECT COUNT(*) {VISIBLE_FROM}{visible} AND r.read_at IS NULL"),
+            params.as_slice(),
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Mark one notification read for the reader.
+    pub fn mark_read(&self, reader: &str, id: &str) -> Result<()> {
+        let exists = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM notifications WHERE id = :id",
+                named_params! { ":id": id },
+                |_| Ok(()),
+            )
+            .optional()?;
+        if exists.is_none() {
+            return Err(ConductorError::NotificationNotFound { id: id.to_string() });
+        }
+        self.upsert_receipts(reader, "read_at", Some(id))?;
+        Ok(())
+    }
+
+    /// Mark every notification read for the reader. Returns how many changed.
+    pub fn mark_all_read(&self, reader: &str) -> Result<usize> {
+        self.upsert_receipts(reader, "read_at", None)
+    }
+
+    /// Hide every current notification from the reader; newer ones still
+    /// appear. Returns how many were cleared.
+    pub fn clear(&self, reader: &str) -> Result<usize> {
+        self.upsert_receipts(reader, "cleared_at", None)
+    }
+
+    /// Set `column` to now on the reader's receipts for notification `id`, or
+    /// for all notifications when `id` is `None`. Receipts where the column is
+    /// already set keep their original time.
+    fn upsert_receipts(&self, reader: &str, column: &str, id: Option<&str>) -> Result<usize> {
+        let changed = self.conn.execute(
+            &format!(
+                "INSERT INTO notification_receipts (notification_id, reader, {column}) \
+                 SELECT id, :reader, :now FROM notifications WHERE :id IS NULL OR id = :id \
+                 ON CONFLICT (notification_id, reader) DO UPDATE SET {column} = excluded.{column} \
+                 WHERE notification_receipts.{column} IS NULL"
+            ),
+            named_params! { ":reader": reader, ":now": Utc::now().to_rfc3339(), ":id": id },
+        )?;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run(&conn).unwrap();
+        conn
+    }
+
+    fn note(title: &str) -> NewNotification {
+        NewNotification {
+            kind: "agent_run_completed".into(),
+            severity: NotificationSeverity::Success,
+            title: title.into(),
+            body: String::new(),
+            url: None,
+            repo_id: None,
+        }
+    }
+
+    #[test]
+    fn read_state_is_per_reader() {
+        let conn = setup();
+        let mgr = NotificationManager::new(&conn);
+        let a = mgr.record(&note("a")).unwrap();
+        mgr.record(&note("b")).unwrap();
+
+        mgr.mark_read("alice", &a.id).unwrap();
+        assert_eq!(mgr.unread_count("alice").unwrap(), 1);
+        assert_eq!(mgr.unread_count(LOCAL_READER).unwrap(), 2);
+
+        let alice = mgr.list("alice", false, 10).unwrap();
+        assert_eq!(alice.len(), 2);
+        assert!(alice.iter().any(|n| n.id == a.id && n.read));
+        assert_eq!(mgr.list("alice", true, 10).unwrap().len(), 1);
+
+        assert_eq!(mgr.mark_all_read("alice").unwrap(), 1);
+        assert_eq!(mgr.unread_count("alice").unwrap(), 0);
+        assert_eq!(mgr.mark_all_read("alice").unwrap(), 0);
+    }
+
+    #[test]
+    fn clear_hides_existing_notifications_only() {
+        let conn = setup();
+        let mgr = NotificationManager::new(&conn);
+        mgr.record(&note("old")).unwrap();
+        assert_eq!(mgr.clear("alice").unwrap(), 1);
+        assert!(mgr.list("alice", false, 10).unwrap().is_empty());
+        assert_eq!(mgr.unread_count("alice").unwrap(), 0);
+        assert_eq!(mgr.list("bob", false, 10).unwrap().len(), 1);
+
+        mgr.record(&note("new")).unwrap();
+        let alice = mgr.list("alice", false, 10).unwrap();
+        assert_eq!(alice.len(), 1);
+        assert_eq!(alice[0].title, "new");
+    }
+
+    #[test]
+    fn notifications_about_private_repos_are_hidden_from_other_viewers() {
+        let conn = crate::test_helpers::setup_db();
+        crate::repo_visibility::RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let mgr = NotificationManager::new(&conn);
+        mgr.record(&NewNotification {
+            repo_id: Some("r1".into()),
+            ..note("private")
+        })
+        .unwrap();
+        mgr.record(&note("shared")).unwrap();
+
+        let titles = |viewer: &Viewer, reader: &str| -> Vec<String> {
+            mgr.list_visible(viewer, reader, false, 10)
+                .unwrap()
+                .into_iter()
+                .map(|n| n.title)
+                .collect()
+        };
+        let owner = Viewer::web(Some("tok-a"));
+        let other = Viewer::web(Some("tok-b"));
+        assert_eq!(titles(&owner, "tok-a"), ["shared", "private"]);
+        assert_eq!(titles(&other, "tok-b"), ["shared"]);
+        assert_eq!(titles(&Viewer::Anonymous, LOCAL_READER), ["shared"]);
+        assert_eq!(mgr.unread_count_visible(&other, "tok-b").unwrap(), 1);
+        assert_eq!(mgr.unread_count(LOCAL_READER).unwrap(), 2);
+    }
+
+    #[test]
+    fn mark_read_unknown_id_is_not_found() {
+        let conn = setup();
+        let err = NotificationManager::new(&conn)
+            .mark_read("alice", "missing")
+            .unwrap_err();
+        assert!(matches!(err, ConductorError::NotificationNotFound { .. }));
+    }
+
+    #[test]
+    fn record_prunes_beyond_retention_cap() {
+        let conn = setup();
+        let mgr = NotificationManager::new(&conn);
+        for i in 0..MAX_NOTIFICATIONS + 3 {
+            mgr.record(&note(&format!("n{i}"))).unwrap();
+        }
+        let total: i64 = conn
+            .query_row("SELECT COUNT(*) FROM notifications", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(total, MAX_NOTIFICATIONS);
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use runkon_notify::{DedupStore, Event, HookRunner, Severity};
+
+use crate::agent::{AgentAlertKind, AgentRunAlert};
+use crate::config::{hooks_as_runkon, HookConfig, NotificationConfig};
+
+/// Fire a notification for a cost or progress alert raised on an agent run.
+///
+/// Deduped on `(run_id, event kind)` via SQLite, so an alert raised while
+/// both the TUI and web pollers run is delivered once.
+pub fn fire_agent_alert_notification(
+    config: &NotificationConfig,
+    notify_hooks: &[HookConfig],
+    dedup_store: Arc<dyn DedupStore>,
+    alert: &AgentRunAlert,
+) {
+    if !config.enabled && notify_hooks.is_empty() {
+        return;
+    }
+    let title = match alert.kind {
+        AgentAlertKind::RunCost => "Conductor \u{2014} Agent Run Over Budget",
+        AgentAlertKind::DailySpend => "Conductor \u{2014} Daily Agent Spend Exceeded",
+        AgentAlertKind::TurnsWithoutCommits => "Conductor \u{2014} Agent Not Committing",
+    };
+    let body = match alert.worktree_slug.as_deref() {
+        Some(slug) => format!("{slug}: {}", alert.detail),
+        None => alert.detail.clone(),
+    };
+    let kind = alert.kind.event_kind();
+
+    let event = Event {
+        kind: kind.into(),
+        title: title.into(),
+        body,
+        severity: Severity::Warning,
+        fields: [
+            ("run_id".into(), alert.run_id.clone()),
+            ("alert".into(), alert.kind.to_string()),
+            ("detail".into(), alert.detail.clone()),
+            (
+                "repo_slug".into(),
+                alert.repo_slug.clone().unwrap_or_default(),
+            ),
+            ("branch".into(), alert.branch.clone().unwrap_or_default()),
+            (
+                "worktree_slug".into(),
+                alert.worktree_slug.clone().unwrap_or_default(),
+            ),
+            ("timestamp".into(), alert.created_at.clone()),
+        ]
+        .into_iter()
+        .collect(),
+    };
+
+    HookRunner::new(&hooks_as_runkon(notify_hooks))
+        .with_dedup_store(dedup_store)
+        .fire_with_dedup(&event, &alert.run_id, kind);
+}
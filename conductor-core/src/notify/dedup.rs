@@ -25,7 +25,7 @@ impl SqliteDedupStore {
 
 impl DedupStore for SqliteDedupStore {
     fn try_claim(&self, entity_id: &str, event_type: &str) -> runkon_notify::Result<bool> {
-        let conn = rusqlite::Connection::open(&self.path)
+        let conn = crate::db::open_connection(&self.path)
             .map_err(|e| NotifyError::Dispatch(format!("dedup DB open: {e}")))?;
 
         let now = chrono::Utc::now().to_rfc3339();
@@ -25,6 +25,8 @@ pub const ALL_EVENTS: &[(&str, &str, bool)] = &[
     ("agent_run.failed", "Agent failed", false),
     ("gate.waiting", "Gate waiting", false),
     ("feedback.requested", "Feedback requested", false),
+    ("watch.state_changed", "Watched item changed", false),
+    ("watch.pr_updated", "Watched PR updated", false),
 ];
 
 const VALID_SYNTHETIC_EVENTS: &[&str] = &[
@@ -37,6 +39,8 @@ const VALID_SYNTHETIC_EVENTS: &[&str] = &[
     "agent_run.failed",
     "gate.waiting",
     "feedback.requested",
+    "watch.state_changed",
+    "watch.pr_updated",
 ];
 
 /// Build a synthetic test [`Event`] for the given concrete event name.
@@ -198,6 +202,44 @@ pub fn build_synthetic_event(name: &str, now: impl Into<String>) -> Result<Event
             .into_iter()
             .collect(),
         },
+        "watch.state_changed" => Event {
+            kind: "watch.state_changed".into(),
+            title: "Conductor \u{2014} Watched Item Changed".into(),
+            body: "test-worktree active \u{2192} merged".into(),
+            severity: Severity::Info,
+            fields: [
+                ("target_type".into(), "worktree".into()),
+                ("target_id".into(), run_id),
+                ("label".into(), "test-worktree".into()),
+                ("repo_slug".into(), "test-repo".into()),
+                ("from".into(), "active".into()),
+                ("to".into(), "merged".into()),
+                ("timestamp".into(), now),
+            ]
+            .into_iter()
+            .collect(),
+        },
+        "watch.pr_updated" => Event {
+            kind: "watch.pr_updated".into(),
+            title: "Conductor \u{2014} Watched PR Updated".into(),
+            body: "test-worktree: PR open, review: APPROVED".into(),
+            severity: Severity::Info,
+            fields: [
+                ("target_type".into(), "worktree".into()),
+                ("target_id".into(), run_id),
+                ("label".into(), "test-worktree".into()),
+                ("repo_slug".into(), "test-repo".into()),
+                ("from".into(), "open, draft".into()),
+                ("to".into(), "open, review: APPROVED".into()),
+                (
+                    "url".into(),
+                    "https://github.com/example-org/example-repo/pull/42".into(),
+                ),
+                ("timestamp".into(), now),
+            ]
+            .into_iter()
+            .collect(),
+        },
         other => {
             return Err(ConductorError::InvalidInput(format!(
                 "unknown event name: '{other}'. Valid events: {}",
@@ -1,5 +1,6 @@
 use crate::config::NotificationConfig;
 
+pub mod alerts;
 pub mod anomalies;
 pub mod dedup;
 pub mod event;
@@ -8,7 +9,9 @@ pub mod runs;
 #[cfg(test)]
 mod tests;
 pub mod transitions;
+pub mod watch;
 
+pub use alerts::*;
 pub use anomalies::*;
 pub use dedup::SqliteDedupStore;
 pub use event::{build_synthetic_event, build_synthetic_for_pattern, ALL_EVENTS};
@@ -16,6 +19,7 @@ pub use gates::*;
 pub use runkon_notify::HookRunner;
 pub use runs::*;
 pub use transitions::*;
+pub use watch::*;
 
 /// Returns `true` if a notification should fire given the config and run outcome.
 ///
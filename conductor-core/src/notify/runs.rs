@@ -4,7 +4,7 @@ use runkon_notify::{DedupStore, Event, HookRunner, Severity};
 
 use crate::config::{hooks_as_runkon, HookConfig, NotificationConfig};
 
-use super::{build_workflow_deep_link, notification_body, should_notify};
+use super::{build_workflow_deep_link, notification_body, should_notify, watch_filter_allows};
 
 /// Narrow context bundle for [`fire_workflow_notification`].
 pub struct NotificationCtx<'a> {
@@ -35,6 +35,8 @@ pub struct WorkflowNotificationArgs<'a> {
 /// Parameters for [`fire_agent_run_notification`].
 pub struct AgentRunNotificationArgs<'a> {
     pub run_id: &'a str,
+    /// Used by `[notifications.watch] only_watched` filtering.
+    pub worktree_id: Option<&'a str>,
     pub worktree_slug: Option<&'a str>,
     pub succeeded: bool,
     pub error_msg: Option<&'a str>,
@@ -63,6 +65,9 @@ pub fn fire_workflow_notification(
     if !should_notify(ctx.config, params.succeeded) && !has_hooks {
         return;
     }
+    if !watch_filter_allows(ctx.conn, ctx.config, params.worktree_id) {
+        return;
+    }
 
     let event_type = if params.succeeded {
         "completed"
@@ -178,7 +183,7 @@ pub fn fire_feedback_notification(
 ///
 /// Deduped on `(run_id, "agent_completed"|"agent_failed")` via SQLite.
 pub fn fire_agent_run_notification(
-    _conn: &rusqlite::Connection,
+    conn: &rusqlite::Connection,
     config: &NotificationConfig,
     notify_hooks: &[HookConfig],
     dedup_store: Arc<dyn DedupStore>,
@@ -188,6 +193,9 @@ pub fn fire_agent_run_notification(
     if !should_notify(config, params.succeeded) && !has_hooks {
         return;
     }
+    if !watch_filter_allows(conn, config, params.worktree_id) {
+        return;
+    }
 
     let event_type = if params.succeeded {
         "agent_completed"
@@ -23,6 +23,8 @@ fn config(enabled: bool, on_success: bool, on_failure: bool) -> NotificationConf
         }),
         slack: SlackConfig::default(),
         web_url: None,
+        watch: Default::default(),
+        cost_alerts: Default::default(),
     }
 }
 
@@ -281,6 +283,8 @@ fn build_synthetic_event_all_valid_kinds() {
         "agent_run.failed",
         "gate.waiting",
         "feedback.requested",
+        "watch.state_changed",
+        "watch.pr_updated",
     ];
     for kind in kinds {
         let result = build_synthetic_event(kind, "2024-01-01T00:00:00Z");
@@ -84,6 +84,7 @@ pub fn detect_workflow_terminal_transitions<'a>(
 /// An agent run that freshly transitioned to a terminal state.
 pub struct AgentTerminalTransition {
     pub run_id: String,
+    pub worktree_id: Option<String>,
     pub worktree_slug: Option<String>,
     pub succeeded: bool,
     pub error_msg: Option<String>,
@@ -120,6 +121,7 @@ pub fn detect_agent_terminal_transitions<'a>(
                 let duration_ms = run.duration_ms.map(|ms| ms as u64);
                 transitions.push(AgentTerminalTransition {
                     run_id: run.id.clone(),
+                    worktree_id: run.worktree_id.clone(),
                     worktree_slug: slug.map(|s| s.to_string()),
                     succeeded,
                     error_msg: if !succeeded {
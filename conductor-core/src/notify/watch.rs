@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use runkon_notify::{DedupStore, Event, HookRunner, Severity};
+
+use crate::config::{hooks_as_runkon, HookConfig, NotificationConfig};
+use crate::watch::{WatchChange, WatchChangeKind, WatchManager};
+
+/// Returns `false` when `[notifications.watch] only_watched` is set and the
+/// worktree is neither watched nor linked to a watched ticket. Runs without a
+/// worktree are never watched.
+pub fn watch_filter_allows(
+    conn: &rusqlite::Connection,
+    config: &NotificationConfig,
+    worktree_id: Option<&str>,
+) -> bool {
+    if !config.watch.only_watched {
+        return true;
+    }
+    let Some(worktree_id) = worktree_id else {
+        return false;
+    };
+    WatchManager::new(conn)
+        .covers_worktree(worktree_id)
+        .unwrap_or_else(|e| {
+            tracing::warn!(worktree_id, "watch filter lookup failed, notifying: {e}");
+            true
+        })
+}
+
+/// Fire a notification for a change on a watched ticket or worktree.
+///
+/// Deduped on `("{target_type}:{target_id}", "{kind}:{from}->{to}")` via SQLite,
+/// so a change picked up by both the TUI and web pollers is delivered once.
+pub fn fire_watch_notification(
+    config: &NotificationConfig,
+    notify_hooks: &[HookConfig],
+    dedup_store: Arc<dyn DedupStore>,
+    change: &WatchChange,
+) {
+    if !config.enabled && notify_hooks.is_empty() {
+        return;
+    }
+    let (kind, title, body, from, to, mut fields) = match &change.kind {
+        WatchChangeKind::StateChanged { from, to } => {
+            if !config.watch.state_changes {
+                return;
+            }
+            (
+                "watch.state_changed",
+                "Conductor \u{2014} Watched Item Changed",
+                format!("{} {from} \u{2192} {to}", change.label),
+                from.clone(),
+                to,
+                Vec::new(),
+            )
+        }
+        WatchChangeKind::PrUpdated { from, to, url } => {
+            if !config.watch.pr_updates {
+                return;
+            }
+            (
+                "watch.pr_updated",
+                "Conductor \u{2014} Watched PR Updated",
+                format!("{}: PR {to}", change.label),
+                from.clone().unwrap_or_default(),
+                to,
+                vec![("url".to_string(), url.clone())],
+            )
+        }
+    };
+    fields.extend([
+        ("target_type".into(), change.target_type.to_string()),
+        ("target_id".into(), change.target_id.clone()),
+        ("label".into(), change.label.clone()),
+        ("repo_slug".into(), change.repo_slug.clone()),
+        ("from".into(), from.clone()),
+        ("to".into(), to.clone()),
+        ("timestamp".into(), chrono::Utc::now().to_rfc3339()),
+    ]);
+
+    let event = Event {
+        kind: kind.into(),
+        title: title.into(),
+        body,
+        severity: Severity::Info,
+        fields: fields.into_iter().collect(),
+    };
+
+    let dedup_key = format!("{}:{}", change.target_type, change.target_id);
+    HookRunner::new(&hooks_as_runkon(notify_hooks))
+        .with_dedup_store(dedup_store)
+        .fire_with_dedup(&event, &dedup_key, &format!("{kind}:{from}->{to}"));
+}
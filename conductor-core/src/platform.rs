@@ -0,0 +1,252 @@
+//! Launching things outside conductor in a platform-appropriate way: URLs and
+//! files, terminal windows, the clipboard, the editor and shell commands.
+//!
+//! macOS, Linux and Windows each get their native tools (`open`, `xdg-open`,
+//! `rundll32`; Terminal.app/iTerm2, Windows Terminal/PowerShell; `pbcopy`,
+//! `xclip`, `clip`). Callers format the returned errors for their own UI.
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Open a URL or file with the system's default handler.
+pub fn open(target: &str) -> io::Result<()> {
+    check_launch(opener(target).output())
+}
+
+fn opener(target: &str) -> Command {
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+    // `start` goes through cmd.exe, which would interpret `&` in URLs.
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("rundll32");
+        cmd.arg("url.dll,FileProtocolHandler");
+        cmd
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut cmd = Command::new("xdg-open");
+    cmd.arg(target);
+    cmd
+}
+
+/// Open a new terminal window at `path`.
+///
+/// Returns `None` when conductor doesn't know how to open a terminal here:
+/// on macOS that is anything but Terminal.app and iTerm2 (per
+/// `TERM_PROGRAM`), and on Linux it is always the case; callers suggest tmux
+/// instead.
+pub fn open_terminal(path: &Path) -> Option<io::Result<()>> {
+    let mut cmd = terminal_command(path)?;
+    Some(check_launch(cmd.output()))
+}
+
+fn terminal_command(path: &Path) -> Option<Command> {
+    #[cfg(target_os = "macos")]
+    {
+        // The path is passed as an argv item rather than interpolated into
+        // the script, so no AppleScript escaping is needed.
+        let script = match std::env::var("TERM_PROGRAM").unwrap_or_default().as_str() {
+            "Apple_Terminal" => {
+                "on run argv\n\
+                 set p to item 1 of argv\n\
+                 tell application \"Terminal\"\n\
+                 \tdo script \"cd \" & quoted form of p\n\
+                 \tactivate\n\
+                 end tell\n\
+                 end run"
+            }
+            "iTerm.app" | "iTerm2" => {
+                "on run argv\n\
+                 set p to item 1 of argv\n\
+                 tell application \"iTerm\"\n\
+                 \tactivate\n\
+                 \tcreate window with default profile\n\
+                 \ttell current session of current window\n\
+                 \t\twrite text \"cd \" & quoted form of p\n\
+                 \tend tell\n\
+                 end tell\n\
+                 end run"
+            }
+            _ => return None,
+        };
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(script).arg("--").arg(path);
+        Some(cmd)
+    }
+    #[cfg(windows)]
+    {
+        Some(windows_terminal_command(
+            path,
+            std::env::var_os("WT_SESSION").is_some(),
+        ))
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// A new Windows Terminal tab when running inside Windows Terminal, otherwise
+/// a new PowerShell window.
+#[cfg(any(windows, test))]
+fn windows_terminal_command(path: &Path, in_windows_terminal: bool) -> Command {
+    if in_windows_terminal {
+        // `;` separates wt subcommands.
+        let dir = path.to_string_lossy().replace(';', "\\;");
+        let mut cmd = Command::new("wt");
+        cmd.args(["-w", "0", "new-tab", "-d", &dir]);
+        cmd
+    } else {
+        // The directory goes through the environment so PowerShell never
+        // parses it.
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-Command",
+            "Start-Process powershell -WorkingDirectory $env:CONDUCTOR_TERMINAL_DIR",
+        ])
+        .env("CONDUCTOR_TERMINAL_DIR", path);
+        cmd
+    }
+}
+
+/// Clipboard tools tried in order, with their arguments.
+#[cfg(target_os = "macos")]
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[("pbcopy", &[])];
+#[cfg(windows)]
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[("clip", &[])];
+#[cfg(all(unix, not(target_os = "macos")))]
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard"]),
+];
+
+/// Copy `text` to the system clipboard with the first available tool.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut child = CLIPBOARD_TOOLS
+        .iter()
+        .find_map(|(program, args)| {
+            Command::new(program)
+                .args(*args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()
+        })
+        .ok_or_else(|| {
+            let names: Vec<_> = CLIPBOARD_TOOLS.iter().map(|(p, _)| *p).collect();
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no clipboard tool found ({})", names.join("/")),
+            )
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    // Fire-and-forget: these tools exit as soon as stdin closes, but xclip
+    // and xsel stay around to serve the selection.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// The user's editor: `$EDITOR`, then `$VISUAL`, then the platform default.
+pub fn editor() -> String {
+    std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// A command that runs `script` with the platform shell: `sh -c` on Unix,
+/// `cmd /C` on Windows.
+pub fn shell_command(script: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", script]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        cmd
+    }
+}
+
+/// Map a launcher's output to `Ok` on success, or an error carrying its
+/// stderr (or exit code when it printed nothing).
+fn check_launch(output: io::Result<Output>) -> io::Result<()> {
+    let output = output?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = match stderr.trim() {
+        "" => match output.status.code() {
+            Some(code) => format!("exit {code}"),
+            None => "killed by a signal".to_string(),
+        },
+        s => s.to_string(),
+    };
+    Err(io::Error::other(detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_opener_passes_target_as_last_argument() {
+        let cmd = opener("https://example.com/?a=1&b=2");
+        assert_eq!(
+            args(&cmd).last().map(String::as_str),
+            Some("https://example.com/?a=1&b=2")
+        );
+    }
+
+    #[test]
+    fn test_windows_terminal_command() {
+        let wt = windows_terminal_command(Path::new(r"C:\src\a;b"), true);
+        assert_eq!(wt.get_program(), "wt");
+        assert_eq!(args(&wt), ["-w", "0", "new-tab", "-d", r"C:\src\a\;b"]);
+
+        let ps = windows_terminal_command(Path::new(r"C:\src\it's"), false);
+        assert_eq!(ps.get_program(), "powershell");
+        assert!(!args(&ps).iter().any(|a| a.contains("it's")));
+        let dir = ps
+            .get_envs()
+            .find(|(k, _)| *k == "CONDUCTOR_TERMINAL_DIR")
+            .and_then(|(_, v)| v);
+        assert_eq!(dir, Some(std::ffi::OsStr::new(r"C:\src\it's")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_command_and_check_launch() {
+        let out = shell_command("echo hi").output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "hi");
+
+        assert!(check_launch(shell_command("true").output()).is_ok());
+        let err = check_launch(shell_command("echo nope >&2; exit 1").output()).unwrap_err();
+        assert_eq!(err.to_string(), "nope");
+        let err = check_launch(shell_command("exit 3").output()).unwrap_err();
+        assert_eq!(err.to_string(), "exit 3");
+    }
+}
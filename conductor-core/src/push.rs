@@ -88,7 +88,7 @@ impl SqlitePushSubscriptionStore {
 impl runkon_notify::PushSubscriptionStore for SqlitePushSubscriptionStore {
     fn list(&self) -> runkon_notify::Result<Vec<runkon_notify::Subscription>> {
         use runkon_notify::NotifyError;
-        let conn = Connection::open(&self.path)
+        let conn = crate::db::open_connection(&self.path)
             .map_err(|e| NotifyError::Subscription(format!("DB open: {e}")))?;
         get_all_subscriptions(&conn)
             .map(|subs| subs.into_iter().map(Into::into).collect())
@@ -102,7 +102,7 @@ impl runkon_notify::PushSubscriptionStore for SqlitePushSubscriptionStore {
         auth: &str,
     ) -> runkon_notify::Result<runkon_notify::Subscription> {
         use runkon_notify::NotifyError;
-        let conn = Connection::open(&self.path)
+        let conn = crate::db::open_connection(&self.path)
             .map_err(|e| NotifyError::Subscription(format!("DB open: {e}")))?;
         upsert_subscription(&conn, endpoint, p256dh, auth)
             .map(Into::into)
@@ -111,7 +111,7 @@ impl runkon_notify::PushSubscriptionStore for SqlitePushSubscriptionStore {
 
     fn delete(&self, endpoint: &str) -> runkon_notify::Result<bool> {
         use runkon_notify::NotifyError;
-        let conn = Connection::open(&self.path)
+        let conn = crate::db::open_connection(&self.path)
             .map_err(|e| NotifyError::Subscription(format!("DB open: {e}")))?;
         delete_subscription(&conn, endpoint).map_err(|e| NotifyError::Subscription(e.to_string()))
     }
@@ -1,6 +1,8 @@
 use crate::config::{Config, RepoConfig};
 use crate::db::query_collect;
 use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+use crate::toolchain::Toolchain;
 use chrono::Utc;
 use rusqlite::{named_params, Connection};
 use serde::{Deserialize, Serialize};
@@ -26,11 +28,14 @@ pub struct Repo {
     /// JSON-serialized per-repo runtime overrides (RFC 007). None means use global config.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub runtime_overrides: Option<String>,
+    /// Language, package manager and test framework detected from the checkout.
+    #[serde(default)]
+    pub toolchain: Toolchain,
 }
 
 const REPO_SELECT: &str = "SELECT id, slug, local_path, remote_url, workspace_dir, created_at, \
      COALESCE(allow_agent_issue_creation, 0) as allow_agent_issue_creation, \
-     runtime_overrides FROM repos";
+     runtime_overrides, language, package_manager, test_framework FROM repos";
 
 fn row_to_repo(row: &rusqlite::Row) -> rusqlite::Result<Repo> {
     Ok(Repo {
@@ -46,6 +51,11 @@ fn row_to_repo(row: &rusqlite::Row) -> rusqlite::Result<Repo> {
             .get::<_, i64>("allow_agent_issue_creation")
             .map(|v| v != 0)?,
         runtime_overrides: row.get("runtime_overrides")?,
+        toolchain: Toolchain {
+            language: row.get("language")?,
+            package_manager: row.get("package_manager")?,
+            test_framework: row.get("test_framework")?,
+        },
     })
 }
 
@@ -111,14 +121,16 @@ impl<'a> RepoManager<'a> {
 
         let id = crate::new_id();
         let now = Utc::now().to_rfc3339();
-        let ws_dir = workspace_dir.map(|s| s.to_string()).unwrap_or_else(|| {
-            self.config
+        let ws_dir = match workspace_dir {
+            Some(dir) => expand_workspace_dir(dir)?,
+            None => self
+                .config
                 .general
                 .workspace_root
                 .join(slug)
                 .to_string_lossy()
-                .to_string()
-        });
+                .to_string(),
+        };
 
         let repo = Repo {
             id: id.clone(),
@@ -131,11 +143,14 @@ impl<'a> RepoManager<'a> {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Toolchain::detect(Path::new(local_path)),
         };
 
         self.conn.execute(
-            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at)
-             VALUES (:id, :slug, :local_path, :remote_url, :workspace_dir, :created_at)",
+            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at,
+                                language, package_manager, test_framework)
+             VALUES (:id, :slug, :local_path, :remote_url, :workspace_dir, :created_at,
+                     :language, :package_manager, :test_framework)",
             named_params! {
                 ":id": repo.id,
                 ":slug": repo.slug,
@@ -143,6 +158,9 @@ impl<'a> RepoManager<'a> {
                 ":remote_url": repo.remote_url,
                 ":workspace_dir": repo.workspace_dir,
                 ":created_at": repo.created_at,
+                ":language": repo.toolchain.language,
+                ":package_manager": repo.toolchain.package_manager,
+                ":test_framework": repo.toolchain.test_framework,
             },
         )?;
 
@@ -159,6 +177,20 @@ impl<'a> RepoManager<'a> {
         Ok(repos.into_iter().map(|r| r.enrich(self.config)).collect())
     }
 
+    /// Repos visible to `viewer`; private repos of other users are left out.
+    pub fn list_visible(&self, viewer: &Viewer) -> Result<Vec<Repo>> {
+        let Some(visible) = visible_repo_sql(viewer, "id", ":viewer") else {
+            return self.list();
+        };
+        let repos = query_collect(
+            self.conn,
+            &format!("{REPO_SELECT} WHERE {visible} ORDER BY slug"),
+            named_params! { ":viewer": viewer.owner_param() },
+            row_to_repo,
+        )?;
+        Ok(repos.into_iter().map(|r| r.enrich(self.config)).collect())
+    }
+
     pub fn get_by_id(&self, id: &str) -> Result<Repo> {
         self.conn
             .query_row(
@@ -195,6 +227,63 @@ impl<'a> RepoManager<'a> {
         Ok(())
     }
 
+    /// Point a repo's worktrees at `workspace_dir` (with `~` and `$VAR`
+    /// expanded), or back at `<general.workspace_root>/<slug>` when `None`.
+    /// Existing worktrees stay where they are; only new ones use the new
+    /// location.
+    pub fn set_workspace_dir(&self, slug: &str, workspace_dir: Option<&str>) -> Result<Repo> {
+        let dir = match workspace_dir {
+            Some(dir) => expand_workspace_dir(dir)?,
+            None => self
+                .config
+                .general
+                .workspace_root
+                .join(slug)
+                .to_string_lossy()
+                .to_string(),
+        };
+        let affected = self.conn.execute(
+            "UPDATE repos SET workspace_dir = :dir WHERE slug = :slug",
+            named_params! { ":dir": dir, ":slug": slug },
+        )?;
+        if affected == 0 {
+            return Err(ConductorError::RepoNotFound {
+                slug: slug.to_string(),
+            });
+        }
+        self.get_by_slug(slug)
+    }
+
+    /// Re-run toolchain detection against the repo's checkout and store the
+    /// result. Registration detects once; call this after the project
+    /// changes its build setup or when the checkout did not exist yet.
+    pub fn detect_toolchain(&self, slug: &str) -> Result<Repo> {
+        let repo = self.get_by_slug(slug)?;
+        let toolchain = Toolchain::detect(Path::new(&repo.local_path));
+        self.set_toolchain(&repo.id, &toolchain)?;
+        Ok(Repo { toolchain, ..repo })
+    }
+
+    /// Store a detected toolchain for the repo with `repo_id`.
+    pub fn set_toolchain(&self, repo_id: &str, toolchain: &Toolchain) -> Result<()> {
+        let affected = self.conn.execute(
+            "UPDATE repos SET language = :language, package_manager = :package_manager, \
+             test_framework = :test_framework WHERE id = :id",
+            named_params! {
+                ":language": toolchain.language,
+                ":package_manager": toolchain.package_manager,
+                ":test_framework": toolchain.test_framework,
+                ":id": repo_id,
+            },
+        )?;
+        if affected == 0 {
+            return Err(ConductorError::RepoNotFound {
+                slug: repo_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Set the per-repo model override in `.conductor/config.toml`.
     /// Pass `None` to clear the override.
     pub fn set_model(&self, slug: &str, model: Option<&str>) -> Result<()> {
@@ -239,6 +328,18 @@ impl<'a> RepoManager<'a> {
     }
 }
 
+/// Expand `~` and `$VAR` in a user-supplied workspace directory. The result
+/// must be absolute so it does not depend on where conductor was started.
+fn expand_workspace_dir(raw: &str) -> Result<String> {
+    let path = crate::workspace::expand_path(raw).map_err(ConductorError::InvalidInput)?;
+    if !path.is_absolute() {
+        return Err(ConductorError::InvalidInput(format!(
+            "workspace directory must be an absolute path: {raw}"
+        )));
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
 /// Derive a repo slug from a remote URL (e.g. "https://github.com/org/repo.git" → "repo").
 pub fn derive_slug_from_url(remote_url: &str) -> String {
     let last = remote_url.rsplit('/').next().unwrap_or("repo");
@@ -329,6 +430,38 @@ mod tests {
         assert!(!repo.created_at.is_empty());
     }
 
+    #[test]
+    fn test_register_detects_toolchain_and_refresh_updates_it() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("go.mod"), "module example.com/x\n").unwrap();
+        let conn = setup_db();
+        let config = Config::default();
+        let mgr = RepoManager::new(&conn, &config);
+
+        let repo = mgr
+            .register(
+                "tc-repo",
+                dir.path().to_str().unwrap(),
+                "https://github.com/org/tc.git",
+                None,
+            )
+            .unwrap();
+        assert_eq!(repo.toolchain.language.as_deref(), Some("go"));
+        assert_eq!(
+            mgr.get_by_slug("tc-repo").unwrap().toolchain,
+            repo.toolchain
+        );
+
+        std::fs::remove_file(dir.path().join("go.mod")).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        let refreshed = mgr.detect_toolchain("tc-repo").unwrap();
+        assert_eq!(refreshed.toolchain.language.as_deref(), Some("rust"));
+        assert_eq!(
+            mgr.get_by_slug("tc-repo").unwrap().toolchain,
+            refreshed.toolchain
+        );
+    }
+
     #[test]
     fn test_register_duplicate_slug_error() {
         let conn = setup_db();
@@ -380,6 +513,37 @@ mod tests {
         assert_eq!(repo.workspace_dir, "/custom/workspace");
     }
 
+    #[test]
+    fn test_set_workspace_dir_expands_and_resets() {
+        let conn = setup_db();
+        let config = Config::default();
+        let mgr = RepoManager::new(&conn, &config);
+        mgr.register("ws", "/tmp/ws", "https://github.com/org/ws.git", None)
+            .unwrap();
+
+        let home = dirs::home_dir().unwrap();
+        let repo = mgr.set_workspace_dir("ws", Some("~/fast/ws")).unwrap();
+        assert_eq!(
+            repo.workspace_dir,
+            home.join("fast/ws").to_string_lossy().to_string()
+        );
+
+        let repo = mgr.set_workspace_dir("ws", None).unwrap();
+        assert_eq!(
+            repo.workspace_dir,
+            config.general.workspace_root.join("ws").to_string_lossy()
+        );
+
+        assert!(matches!(
+            mgr.set_workspace_dir("ws", Some("relative/dir")),
+            Err(ConductorError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            mgr.set_workspace_dir("missing", Some("/tmp/x")),
+            Err(ConductorError::RepoNotFound { .. })
+        ));
+    }
+
     // ── list ──────────────────────────────────────────────────────────
 
     #[test]
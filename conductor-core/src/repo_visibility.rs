@@ -0,0 +1,441 @@
+//! Per-repo visibility for shared web deployments.
+//!
+//! A repo is shared by default. Marking it private to an API token hides it,
+//! along with its worktrees, tickets and agent runs, from every other
+//! web user. The filtering happens in the queries that list those rows (see
+//! [`visible_repo_sql`]), so a client cannot opt out of it. The local CLI and
+//! TUI are [`Viewer::Local`] and see everything.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+
+/// Who a listing is for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Viewer {
+    /// The local operator (CLI, TUI): every repo is visible.
+    #[default]
+    Local,
+    /// A web request authenticated with this API token id: shared repos and
+    /// the token's own private repos are visible.
+    Token(String),
+    /// A web request without a token: only shared repos are visible.
+    Anonymous,
+}
+
+impl Viewer {
+    /// The viewer for a web request carrying `token_id`, if any.
+    pub fn web(token_id: Option<&str>) -> Self {
+        match token_id {
+            Some(id) => Self::Token(id.to_string()),
+            None => Self::Anonymous,
+        }
+    }
+
+    /// Value bound to the placeholder of [`visible_repo_sql`]. `NULL` for an
+    /// anonymous viewer, which matches no owner.
+    pub(crate) fn owner_param(&self) -> Option<&str> {
+        match self {
+            Self::Token(id) => Some(id),
+            Self::Local | Self::Anonymous => None,
+        }
+    }
+}
+
+/// SQL condition that holds when the repo id in `repo_column` is visible to
+/// the viewer bound at `placeholder` (via [`Viewer::owner_param`]). `None`
+/// for [`Viewer::Local`], which needs no condition.
+pub(crate) fn visible_repo_sql(
+    viewer: &Viewer,
+    repo_column: &str,
+    placeholder: &str,
+) -> Option<String> {
+    if *viewer == Viewer::Local {
+        return None;
+    }
+    Some(format!(
+        "{repo_column} NOT IN \
+         (SELECT repo_id FROM repo_visibility WHERE owner IS NOT {placeholder})"
+    ))
+}
+
+/// A row that belongs to a repo, for [`RepoVisibility::can_see`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoScoped<'s> {
+    Repo(&'s str),
+    Worktree(&'s str),
+    Ticket(&'s str),
+    AgentRun(&'s str),
+    WorkflowRun(&'s str),
+}
+
+/// A repo that is private to one API token.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateRepo {
+    pub repo_id: String,
+    /// API token id of the only user who sees the repo.
+    pub owner: String,
+    pub created_at: String,
+}
+
+pub struct RepoVisibility<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> RepoVisibility<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Make the repo visible only to the API token `owner`. Replaces any
+    /// previous owner.
+    pub fn set_private(&self, repo_id: &str, owner: &str) -> Result<PrivateRepo> {
+        self.ensure_repo(repo_id)?;
+        let private = PrivateRepo {
+            repo_id: repo_id.to_string(),
+            owner: owner.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        self.conn.execute(
+            "INSERT INTO repo_visibility (repo_id, owner, created_at) \
+             VALUES (:repo_id, :owner, :created_at) \
+             ON CONFLICT(repo_id) DO UPDATE SET owner = excluded.owner, \
+             created_at = excluded.created_at",
+            named_params! {
+                ":repo_id": private.repo_id,
+                ":owner": private.owner,
+                ":created_at": private.created_at,
+            },
+        )?;
+        Ok(private)
+    }
+
+    /// Make the repo visible to everyone again.
+    pub fn set_shared(&self, repo_id: &str) -> Result<()> {
+        self.ensure_repo(repo_id)?;
+        self.conn.execute(
+            "DELETE FROM repo_visibility WHERE repo_id = :repo_id",
+            named_params! { ":repo_id": repo_id },
+        )?;
+        Ok(())
+    }
+
+    /// The repo's private owner, or `None` when it is shared.
+    pub fn get(&self, repo_id: &str) -> Result<Option<PrivateRepo>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT repo_id, owner, created_at FROM repo_visibility WHERE repo_id = :repo_id",
+                named_params! { ":repo_id": repo_id },
+                row_to_private_repo,
+            )
+            .optional()?)
+    }
+
+    /// Every private repo.
+    pub fn list_private(&self) -> Result<Vec<PrivateRepo>> {
+        query_collect(
+            self.conn,
+            "SELECT repo_id, owner, created_at FROM repo_visibility ORDER BY created_at",
+            [],
+            row_to_private_repo,
+        )
+    }
+
+    /// Whether the row's repo is visible to `viewer`. Rows that do not exist
+    /// count as visible, so callers still report them as not found.
+    pub fn can_see(&self, viewer: &Viewer, target: RepoScoped<'_>) -> Result<bool> {
+        let Some(condition) = visible_repo_sql(viewer, "repo_id", ":viewer") else {
+            return Ok(true);
+        };
+        let (owning_repo, id) = owning_repo_sql(target);
+        let hidden = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT 1 FROM ({owning_repo}) \
+                     WHERE repo_id IS NOT NULL AND NOT ({condition})"
+                ),
+                named_params! { ":id": id, ":viewer": viewer.owner_param() },
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(hidden.is_none())
+    }
+
+    /// The repo the row belongs to; `None` when the row does not exist or
+    /// belongs to no repo.
+    pub fn repo_of(&self, target: RepoScoped<'_>) -> Result<Option<String>> {
+        let (owning_repo, id) = owning_repo_sql(target);
+        Ok(self
+            .conn
+            .query_row(owning_repo, named_params! { ":id": id }, |row| row.get(0))
+            .optional()?
+            .flatten())
+    }
+
+    fn ensure_repo(&self, repo_id: &str) -> Result<()> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM repos WHERE id = :id",
+                named_params! { ":id": repo_id },
+                |_| Ok(()),
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::RepoNotFound {
+                slug: repo_id.to_string(),
+            })
+    }
+}
+
+/// A query selecting the `repo_id` of `target`, and the id bound to its
+/// `:id` placeholder.
+fn owning_repo_sql(target: RepoScoped<'_>) -> (&'static str, &str) {
+    match target {
+        RepoScoped::Repo(id) => ("SELECT :id AS repo_id", id),
+        RepoScoped::Worktree(id) => ("SELECT repo_id FROM worktrees WHERE id = :id", id),
+        RepoScoped::Ticket(id) => ("SELECT repo_id FROM tickets WHERE id = :id", id),
+        RepoScoped::AgentRun(id) => (
+            "SELECT COALESCE(a.repo_id, w.repo_id) AS repo_id FROM agent_runs a \
+             LEFT JOIN worktrees w ON w.id = a.worktree_id WHERE a.id = :id",
+            id,
+        ),
+        RepoScoped::WorkflowRun(id) => (
+            "SELECT COALESCE(r.repo_id, w.repo_id) AS repo_id FROM workflow_runs r \
+             LEFT JOIN worktrees w ON w.id = r.worktree_id WHERE r.id = :id",
+            id,
+        ),
+    }
+}
+
+fn row_to_private_repo(row: &rusqlite::Row<'_>) -> rusqlite::Result<PrivateRepo> {
+    Ok(PrivateRepo {
+        repo_id: row.get(0)?,
+        owner: row.get(1)?,
+        created_at: row.get(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{insert_test_agent_run, insert_test_repo, setup_db};
+
+    #[test]
+    fn private_repo_is_hidden_from_other_viewers() {
+        let conn = setup_db();
+        insert_test_repo(&conn, "r2", "other-repo", "/tmp/other");
+        insert_test_agent_run(&conn, "run1", "w1");
+        let parent = crate::test_helpers::make_agent_parent_id(&conn);
+        let workflow_run = crate::workflow::create_workflow_run(
+            &conn,
+            "wf",
+            Some("w1"),
+            &parent,
+            false,
+            "manual",
+            None,
+        )
+        .unwrap();
+        let vis = RepoVisibility::new(&conn);
+        vis.set_private("r1", "tok-a").unwrap();
+
+        let owner = Viewer::web(Some("tok-a"));
+        let other = Viewer::web(Some("tok-b"));
+        assert_eq!(
+            vis.repo_of(RepoScoped::WorkflowRun(&workflow_run.id))
+                .unwrap()
+                .as_deref(),
+            Some("r1")
+        );
+        assert_eq!(vis.repo_of(RepoScoped::Ticket("missing")).unwrap(), None);
+        for target in [
+            RepoScoped::Repo("r1"),
+            RepoScoped::Worktree("w1"),
+            RepoScoped::AgentRun("run1"),
+            RepoScoped::WorkflowRun(&workflow_run.id),
+        ] {
+            assert!(vis.can_see(&owner, target).unwrap(), "{target:?}");
+            assert!(vis.can_see(&Viewer::Local, target).unwrap(), "{target:?}");
+            assert!(!vis.can_see(&other, target).unwrap(), "{target:?}");
+            assert!(
+                !vis.can_see(&Viewer::Anonymous, target).unwrap(),
+                "{target:?}"
+            );
+        }
+        assert!(vis.can_see(&other, RepoScoped::Repo("r2")).unwrap());
+        assert!(vis
+            .can_see(&other, RepoScoped::Worktree("missing"))
+            .unwrap());
+
+        vis.set_shared("r1").unwrap();
+        assert!(vis.get("r1").unwrap().is_none());
+        assert!(vis.can_see(&other, RepoScoped::Worktree("w1")).unwrap());
+        assert!(matches!(
+            vis.set_private("nope", "tok-a"),
+            Err(ConductorError::RepoNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn listings_leave_out_other_users_private_repos() {
+        use crate::agent::{AgentInbox, AgentManager};
+        use crate::config::Config;
+        use crate::repo::RepoManager;
+        use crate::tickets::TicketSyncer;
+        use crate::worktree::{WorktreeListFilter, WorktreeManager};
+
+        let conn = setup_db();
+        insert_test_repo(&conn, "r2", "other-repo", "/tmp/other");
+        insert_test_agent_run(&conn, "run1", "w1");
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', ended_at = started_at WHERE id = 'run1'",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, \
+             labels, url, synced_at, raw_json) \
+             VALUES ('t1', 'r1', 'github', '1', 'T', '', 'open', '[]', '', '2024-01-01T00:00:00Z', '{}')",
+            [],
+        )
+        .unwrap();
+        RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let config = Config::default();
+
+        for (viewer, sees_r1) in [
+            (Viewer::Local, true),
+            (Viewer::web(Some("tok-a")), true),
+            (Viewer::web(Some("tok-b")), false),
+            (Viewer::Anonymous, false),
+        ] {
+            let repos = RepoManager::new(&conn, &config)
+                .list_visible(&viewer)
+                .unwrap();
+            let slugs: Vec<_> = repos.iter().map(|r| r.slug.as_str()).collect();
+            let expected: &[&str] = if sees_r1 {
+                &["other-repo", "test-repo"]
+            } else {
+                &["other-repo"]
+            };
+            assert_eq!(slugs, expected, "{viewer:?}");
+
+            let worktrees = WorktreeManager::new(&conn, &config)
+                .list_entries(&WorktreeListFilter {
+                    viewer: viewer.clone(),
+                    ..Default::default()
+                })
+                .unwrap();
+            assert_eq!(worktrees.len(), usize::from(sees_r1), "{viewer:?}");
+
+            let tickets = TicketSyncer::new(&conn)
+                .list_visible(None, &viewer)
+                .unwrap();
+            assert_eq!(tickets.len(), usize::from(sees_r1), "{viewer:?}");
+
+            let agents = AgentManager::new(&conn);
+            let runs = agents
+                .list_visible_agent_runs(&viewer, None, None, None, 50, 0)
+                .unwrap();
+            assert_eq!(runs.len(), usize::from(sees_r1), "{viewer:?}");
+            let latest = agents.latest_visible_runs_by_worktree(&viewer).unwrap();
+            assert_eq!(latest.len(), usize::from(sees_r1), "{viewer:?}");
+
+            let inbox = AgentInbox::new(&conn).list_visible(&viewer).unwrap();
+            assert_eq!(inbox.len(), usize::from(sees_r1), "{viewer:?}");
+
+            let roadmap = TicketSyncer::new(&conn).roadmap(None, &viewer).unwrap();
+            let total: usize = roadmap.iter().map(|g| g.total).sum();
+            assert_eq!(total, usize::from(sees_r1), "{viewer:?}");
+        }
+    }
+
+    #[test]
+    fn aggregates_leave_out_other_users_private_repos() {
+        use crate::agent::{AgentAlerts, AgentManager, AgentPriority, AgentQueue};
+        use crate::config::BudgetConfig;
+        use crate::tickets::{TicketGrooming, TicketSyncer};
+        use crate::watch::{WatchManager, WatchTarget};
+
+        let conn = setup_db();
+        insert_test_repo(&conn, "r2", "other-repo", "/tmp/other");
+        insert_test_agent_run(&conn, "run1", "w1");
+        conn.execute_batch(
+            "UPDATE agent_runs SET status = 'completed', ended_at = started_at, cost_usd = 5.0 \
+                 WHERE id = 'run1'; \
+             INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, \
+                 labels, url, synced_at, raw_json) VALUES \
+                 ('t1', 'r1', 'github', '1', 'T', '', 'open', '[]', '', '2024-01-01T00:00:00Z', '{}'), \
+                 ('t2', 'r2', 'github', '2', 'U', '', 'open', '[]', '', '2024-01-01T00:00:00Z', '{}'); \
+             UPDATE worktrees SET ticket_id = 't1' WHERE id = 'w1'; \
+             INSERT INTO ticket_labels (ticket_id, label) VALUES ('t1', 'bug'); \
+             INSERT INTO ticket_dependencies (from_ticket_id, to_ticket_id, dep_type) \
+                 VALUES ('t2', 't1', 'blocks'); \
+             INSERT INTO ticket_grooming_proposals (id, ticket_id, created_at) \
+                 VALUES ('g1', 't1', '2024-01-01T00:00:00Z'); \
+             INSERT INTO agent_run_alerts (run_id, kind, detail, created_at) \
+                 VALUES ('run1', 'run_cost', 'expensive', '2024-01-01T00:00:00Z');",
+        )
+        .unwrap();
+        AgentQueue::new(&conn)
+            .enqueue("w1", "next", AgentPriority::Normal)
+            .unwrap();
+        WatchManager::new(&conn)
+            .watch(WatchTarget::Ticket, "t1")
+            .unwrap();
+        RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let budget = BudgetConfig {
+            worktree_usd: Some(1.0),
+            ..Default::default()
+        };
+
+        for (viewer, sees_r1) in [
+            (Viewer::Local, true),
+            (Viewer::web(Some("tok-a")), true),
+            (Viewer::web(Some("tok-b")), false),
+            (Viewer::Anonymous, false),
+        ] {
+            let expected = usize::from(sees_r1);
+            let queue = AgentQueue::new(&conn).list_visible(&viewer).unwrap();
+            assert_eq!(queue.len(), expected, "{viewer:?}");
+            let alerts = AgentAlerts::new(&conn)
+                .list_recent_visible(&viewer, 10)
+                .unwrap();
+            assert_eq!(alerts.len(), expected, "{viewer:?}");
+
+            let agents = AgentManager::new(&conn);
+            let totals = agents.visible_totals_by_ticket(&viewer, None).unwrap();
+            assert_eq!(totals.len(), expected, "{viewer:?}");
+            let totals = agents
+                .visible_totals_by_ticket_ids(&viewer, None, &["t1".to_string()])
+                .unwrap();
+            assert_eq!(totals.len(), expected, "{viewer:?}");
+            let over = agents
+                .visible_worktrees_over_budget(&viewer, &budget)
+                .unwrap();
+            assert_eq!(over.len(), expected, "{viewer:?}");
+
+            let syncer = TicketSyncer::new(&conn);
+            let labels = syncer.get_visible_labels(&viewer).unwrap();
+            assert_eq!(labels.len(), expected, "{viewer:?}");
+            // The edge touches t1, so it goes with it from both ends.
+            let deps = syncer.get_visible_dependencies(&viewer).unwrap();
+            assert_eq!(deps.len(), 2 * expected, "{viewer:?}");
+            let proposals = TicketGrooming::new(&conn)
+                .list_visible(&viewer, None, None)
+                .unwrap();
+            assert_eq!(proposals.len(), expected, "{viewer:?}");
+
+            let watches = WatchManager::new(&conn).list_visible(&viewer).unwrap();
+            assert_eq!(watches.len(), expected, "{viewer:?}");
+        }
+    }
+}
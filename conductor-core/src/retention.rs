@@ -0,0 +1,251 @@
+//! Retention policies for finished rows, configured by the `[retention]`
+//! section. [`prune`] runs from the periodic maintenance pass in the TUI and
+//! web server, and from `conductor prune` (which can preview with
+//! `--dry-run`).
+//!
+//! Only the database is touched: worktree directories are already gone once a
+//! worktree is merged or abandoned, and agent log files are left in place.
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection};
+use serde::Serialize;
+
+use crate::config::RetentionConfig;
+use crate::error::Result;
+use crate::worktree::LIVE_STATUSES_SQL;
+
+/// Rows deleted (or, for a dry run, that would be deleted) per table.
+///
+/// Counts are for the table itself; rows removed by `ON DELETE CASCADE`
+/// (e.g. the agent runs of a purged worktree) are not counted again.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PruneReport {
+    pub worktrees: usize,
+    pub agent_runs: usize,
+    pub agent_run_events: usize,
+}
+
+impl PruneReport {
+    pub fn total(&self) -> usize {
+        self.worktrees + self.agent_runs + self.agent_run_events
+    }
+}
+
+/// Delete rows older than the configured retention windows. A window of `0`
+/// keeps that table untouched.
+///
+/// With `dry_run` the deletes run inside a transaction that is rolled back,
+/// so the preview counts match exactly what a real prune would remove.
+pub fn prune(conn: &Connection, config: &RetentionConfig, dry_run: bool) -> Result<PruneReport> {
+    let tx = conn.unchecked_transaction()?;
+    let mut report = PruneReport::default();
+
+    // Worktrees with workflow history are kept too: their workflow runs, and
+    // the agent runs parenting them, would go with them through the cascade.
+    if let Some(cutoff) = cutoff(config.worktree_days) {
+        report.worktrees = tx.execute(
+            &format!(
+                "DELETE FROM worktrees WHERE status NOT IN {LIVE_STATUSES_SQL} \
+                 AND COALESCE(completed_at, created_at) < :cutoff \
+                 AND id NOT IN (SELECT worktree_id FROM workflow_runs \
+                     WHERE worktree_id IS NOT NULL) \
+                 AND id NOT IN (SELECT a.worktree_id FROM agent_runs a \
+                     JOIN workflow_runs wr ON wr.parent_run_id = a.id \
+                     WHERE a.worktree_id IS NOT NULL)"
+            ),
+            named_params! { ":cutoff": cutoff },
+        )?;
+    }
+
+    // Runs that parent a workflow run are kept: deleting them would cascade
+    // into the workflow history, which has no retention policy of its own.
+    if let Some(cutoff) = cutoff(config.agent_run_days) {
+        report.agent_runs = tx.execute(
+            "DELETE FROM agent_runs WHERE status IN ('completed', 'failed', 'cancelled') \
+             AND COALESCE(ended_at, started_at) < :cutoff \
+             AND id NOT IN (SELECT parent_run_id FROM workflow_runs)",
+            named_params! { ":cutoff": cutoff },
+        )?;
+    }
+
+    if let Some(cutoff) = cutoff(config.event_days) {
+        report.agent_run_events = tx.execute(
+            "DELETE FROM agent_run_events WHERE started_at < :cutoff AND run_id IN \
+                 (SELECT id FROM agent_runs WHERE status IN ('completed', 'failed', 'cancelled'))",
+            named_params! { ":cutoff": cutoff },
+        )?;
+    }
+
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+fn cutoff(days: u32) -> Option<String> {
+    (days > 0).then(|| (Utc::now() - chrono::Duration::days(i64::from(days))).to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{insert_test_agent_run, insert_test_worktree, setup_db};
+
+    fn all_days(days: u32) -> RetentionConfig {
+        RetentionConfig {
+            worktree_days: days,
+            agent_run_days: days,
+            event_days: days,
+        }
+    }
+
+    fn count(conn: &Connection, table: &str) -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |r| r.get(0))
+            .unwrap()
+    }
+
+    /// `w1` stays active; `w2` was merged long ago; `ar1` finished long ago
+    /// on `w1` with one event; `ar2` is still running with one old event.
+    fn seed(conn: &Connection) {
+        insert_test_worktree(conn, "w2", "r1", "old", "/tmp/ws/old");
+        conn.execute(
+            "UPDATE worktrees SET status = 'merged', completed_at = '2024-01-02T00:00:00Z' \
+             WHERE id = 'w2'",
+            [],
+        )
+        .unwrap();
+        insert_test_agent_run(conn, "ar1", "w1");
+        insert_test_agent_run(conn, "ar2", "w1");
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', ended_at = '2024-01-02T00:00:00Z' \
+             WHERE id = 'ar1'",
+            [],
+        )
+        .unwrap();
+        for (id, run) in [("e1", "ar1"), ("e2", "ar2")] {
+            conn.execute(
+                "INSERT INTO agent_run_events (id, run_id, kind, summary, started_at) \
+                 VALUES (:id, :run, 'text', 'hi', '2024-01-01T00:00:00Z')",
+                named_params! { ":id": id, ":run": run },
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn prune_removes_only_finished_rows_past_retention() {
+        let conn = setup_db();
+        seed(&conn);
+
+        let report = prune(&conn, &all_days(30), false).unwrap();
+
+        // `ar1`'s event goes with the run and running `ar2`'s event is
+        // exempt, so the events policy itself deletes nothing.
+        assert_eq!(
+            report,
+            PruneReport {
+                worktrees: 1,
+                agent_runs: 1,
+                agent_run_events: 0,
+            }
+        );
+        assert_eq!(count(&conn, "worktrees"), 1);
+        assert_eq!(count(&conn, "agent_runs"), 1);
+        assert_eq!(count(&conn, "agent_run_events"), 1);
+    }
+
+    #[test]
+    fn prune_events_keeps_finished_runs() {
+        let conn = setup_db();
+        seed(&conn);
+        let config = RetentionConfig {
+            event_days: 30,
+            ..Default::default()
+        };
+
+        let report = prune(&conn, &config, false).unwrap();
+
+        assert_eq!(report.agent_run_events, 1);
+        assert_eq!(report.total(), 1);
+        assert_eq!(count(&conn, "agent_runs"), 2);
+    }
+
+    #[test]
+    fn dry_run_reports_counts_without_deleting() {
+        let conn = setup_db();
+        seed(&conn);
+
+        let report = prune(&conn, &all_days(30), true).unwrap();
+
+        assert_eq!(report.total(), 2);
+        assert_eq!(count(&conn, "worktrees"), 2);
+        assert_eq!(count(&conn, "agent_runs"), 2);
+        assert_eq!(count(&conn, "agent_run_events"), 2);
+    }
+
+    #[test]
+    fn zero_days_keeps_everything() {
+        let conn = setup_db();
+        seed(&conn);
+
+        assert_eq!(
+            prune(&conn, &all_days(0), false).unwrap(),
+            PruneReport::default()
+        );
+        assert_eq!(count(&conn, "worktrees"), 2);
+    }
+
+    #[test]
+    fn prune_keeps_workflow_parent_runs() {
+        let conn = setup_db();
+        // `w2` ran a workflow, `w3` only parented a repo-level one, and `w4`
+        // has no workflow history; all three were merged long ago.
+        for id in ["w2", "w3", "w4"] {
+            insert_test_worktree(&conn, id, "r1", id, &format!("/tmp/ws/{id}"));
+        }
+        conn.execute(
+            "UPDATE worktrees SET status = 'merged', completed_at = '2024-01-02T00:00:00Z' \
+             WHERE id != 'w1'",
+            [],
+        )
+        .unwrap();
+        let agents = crate::agent::AgentManager::new(&conn);
+        for (worktree, workflow_worktree) in [("w1", Some("w1")), ("w2", Some("w2")), ("w3", None)]
+        {
+            let parent = agents.create_run(Some(worktree), "workflow", None).unwrap();
+            crate::workflow::create_workflow_run(
+                &conn,
+                "wf",
+                workflow_worktree,
+                &parent.id,
+                false,
+                "manual",
+                None,
+            )
+            .unwrap();
+        }
+        insert_test_agent_run(&conn, "ar4", "w4");
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed', ended_at = '2024-01-02T00:00:00Z'",
+            [],
+        )
+        .unwrap();
+
+        let report = prune(&conn, &all_days(30), false).unwrap();
+
+        assert_eq!(
+            report,
+            PruneReport {
+                worktrees: 1,
+                agent_runs: 0,
+                agent_run_events: 0,
+            }
+        );
+        assert_eq!(count(&conn, "worktrees"), 3);
+        assert_eq!(count(&conn, "workflow_runs"), 3);
+        assert_eq!(count(&conn, "agent_runs"), 3, "only w4's run went");
+    }
+}
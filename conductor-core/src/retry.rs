@@ -147,6 +147,16 @@ pub fn is_transient(failure: &SubprocessFailure) -> bool {
         return true;
     }
 
+    // Git lock contention: another git process in the same repo holds
+    // `.git/config.lock`, `index.lock` or a ref's `.lock` file for a moment.
+    // Other "cannot lock ref" failures, such as a branch that already
+    // exists, are permanent and surface immediately.
+    if stderr.contains("could not lock config file")
+        || (stderr.contains(".lock") && stderr.contains("file exists"))
+    {
+        return true;
+    }
+
     false
 }
 
@@ -319,6 +329,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_transient_git_lock_contention() {
+        for msg in [
+            "error: could not lock config file /repo/.git/config: File exists",
+            "fatal: Unable to create '/repo/.git/index.lock': File exists.",
+            "error: cannot lock ref 'refs/heads/feat/x': Unable to create \
+             '/repo/.git/refs/heads/feat/x.lock': File exists.",
+        ] {
+            let f = SubprocessFailure::from_message("git", msg.to_string());
+            assert!(is_transient(&f), "expected transient for: {msg}");
+        }
+
+        let f = SubprocessFailure::from_message(
+            "git",
+            "error: cannot lock ref 'refs/heads/feat/x': reference already exists".to_string(),
+        );
+        assert!(
+            !is_transient(&f),
+            "an existing branch is not lock contention"
+        );
+    }
+
     #[test]
     fn is_transient_git_remote_hung_up() {
         let f = SubprocessFailure::from_message(
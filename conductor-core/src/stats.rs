@@ -28,8 +28,8 @@ impl<'a> StatsManager<'a> {
             .query_row("SELECT COUNT(*) FROM repos", [], |r| r.get(0))?;
 
         let prs_merged: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM worktrees WHERE status = 'merged'",
-            [],
+            "SELECT COUNT(*) FROM worktrees WHERE status = ?1",
+            [crate::worktree::WorktreeStatus::Merged],
             |r| r.get(0),
         )?;
 
@@ -0,0 +1,198 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::worktree::LIVE_STATUSES_SQL;
+
+/// Version tag leading every porcelain line. Bumped only if existing fields
+/// change meaning; new fields are appended without a bump.
+pub const PORCELAIN_VERSION: &str = "v1";
+
+/// Cross-repo summary for status bars and shell prompts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StatusSummary {
+    /// Agent runs in `running` or `waiting_for_feedback`.
+    pub running_agents: usize,
+    /// Live worktrees that are blocked on the user: a pending feedback request,
+    /// a waiting workflow gate, a failed latest agent run, or `needs_review`.
+    pub attention: usize,
+    /// The worktree containing the current directory, if any.
+    pub session: Option<SessionStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionStatus {
+    pub repo_slug: String,
+    pub worktree_slug: String,
+    /// Status of the latest agent run in the worktree, or `idle` if none.
+    pub state: String,
+}
+
+impl StatusSummary {
+    /// Render the stable one-line format:
+    ///
+    /// ```text
+    /// v1 agents=<n> attention=<n> session=<repo>/<worktree>:<state>
+    /// ```
+    ///
+    /// `session=-` when the current directory is not inside a worktree.
+    pub fn porcelain(&self) -> String {
+        let session = match &self.session {
+            Some(s) => format!("{}/{}:{}", s.repo_slug, s.worktree_slug, s.state),
+            None => "-".to_string(),
+        };
+        format!(
+            "{PORCELAIN_VERSION} agents={} attention={} session={session}",
+            self.running_agents, self.attention
+        )
+    }
+}
+
+/// Read-only queries behind `conductor status`. Kept to three indexed
+/// statements so the command stays fast enough to run from a prompt hook.
+pub struct StatusManager<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> StatusManager<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn summary(&self, cwd: &Path) -> Result<StatusSummary> {
+        let running_agents: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM agent_runs \
+             WHERE status IN ('running', 'waiting_for_feedback')",
+            [],
+            |r| r.get(0),
+        )?;
+
+        let attention: i64 = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM worktrees w \
+                 WHERE w.status IN {LIVE_STATUSES_SQL} AND ( \
+                   w.status = 'needs_review' \
+                   OR EXISTS (SELECT 1 FROM feedback_requests f \
+                              JOIN agent_runs a ON a.id = f.run_id \
+                              WHERE a.worktree_id = w.id AND f.status = 'pending') \
+                   OR EXISTS (SELECT 1 FROM workflow_run_steps s \
+                              JOIN workflow_runs r ON r.id = s.workflow_run_id \
+                              WHERE r.worktree_id = w.id AND s.gate_type IS NOT NULL \
+                                AND s.status = 'waiting' \
+                                AND r.status IN ('pending', 'running', 'waiting')) \
+                   OR (SELECT a.status FROM agent_runs a WHERE a.worktree_id = w.id \
+                       ORDER BY a.started_at DESC LIMIT 1) = 'failed')"
+            ),
+            [],
+            |r| r.get(0),
+        )?;
+
+        Ok(StatusSummary {
+            running_agents: running_agents as usize,
+            attention: attention as usize,
+            session: self.session_for(cwd)?,
+        })
+    }
+
+    /// Find the live worktree whose path contains `cwd` (the deepest match
+    /// wins) and report its latest agent run status.
+    fn session_for(&self, cwd: &Path) -> Result<Option<SessionStatus>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT r.slug, w.slug, w.path, \
+               (SELECT a.status FROM agent_runs a WHERE a.worktree_id = w.id \
+                ORDER BY a.started_at DESC LIMIT 1) \
+             FROM worktrees w JOIN repos r ON r.id = w.repo_id \
+             WHERE w.status IN {LIVE_STATUSES_SQL}"
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+
+        let mut best: Option<(usize, SessionStatus)> = None;
+        for row in rows {
+            let (repo_slug, worktree_slug, path, state) = row?;
+            let path = Path::new(&path);
+            if !cwd.starts_with(path) {
+                continue;
+            }
+            let depth = path.components().count();
+            if best.as_ref().is_some_and(|(d, _)| *d >= depth) {
+                continue;
+            }
+            best = Some((
+                depth,
+                SessionStatus {
+                    repo_slug,
+                    worktree_slug,
+                    state: state.unwrap_or_else(|| "idle".to_string()),
+                },
+            ));
+        }
+        Ok(best.map(|(_, s)| s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{insert_test_agent_run, insert_test_worktree, setup_db};
+
+    #[test]
+    fn test_summary_counts_agents_attention_and_session() {
+        let conn = setup_db();
+        insert_test_worktree(&conn, "w2", "r1", "feat-other", "/tmp/ws/feat-other");
+        insert_test_worktree(&conn, "w3", "r1", "feat-review", "/tmp/ws/feat-review");
+        conn.execute(
+            "UPDATE worktrees SET status = 'needs_review' WHERE id = 'w3'",
+            [],
+        )
+        .unwrap();
+        insert_test_agent_run(&conn, "a1", "w1");
+        insert_test_agent_run(&conn, "a2", "w2");
+        conn.execute(
+            "INSERT INTO feedback_requests (id, run_id, prompt, status, created_at) \
+             VALUES ('f1', 'a2', 'Which approach?', 'pending', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+
+        let mgr = StatusManager::new(&conn);
+        let summary = mgr.summary(Path::new("/tmp/ws/feat-test/src")).unwrap();
+        assert_eq!(summary.running_agents, 2);
+        assert_eq!(summary.attention, 2, "feedback on w2, needs_review on w3");
+        assert_eq!(
+            summary.porcelain(),
+            "v1 agents=2 attention=2 session=test-repo/feat-test:running"
+        );
+
+        let outside = mgr.summary(Path::new("/tmp/ws/feat-testing")).unwrap();
+        assert_eq!(
+            outside.session, None,
+            "path prefix must match whole components"
+        );
+        assert_eq!(outside.porcelain(), "v1 agents=2 attention=2 session=-");
+    }
+
+    #[test]
+    fn test_summary_flags_failed_latest_run_and_idle_session() {
+        let conn = setup_db();
+        insert_test_worktree(&conn, "w2", "r1", "feat-idle", "/tmp/ws/feat-idle");
+        insert_test_agent_run(&conn, "a1", "w1");
+        conn.execute("UPDATE agent_runs SET status = 'failed'", [])
+            .unwrap();
+
+        let summary = StatusManager::new(&conn)
+            .summary(Path::new("/tmp/ws/feat-idle"))
+            .unwrap();
+        assert_eq!(summary.running_agents, 0);
+        assert_eq!(summary.attention, 1);
+        assert_eq!(summary.session.map(|s| s.state).as_deref(), Some("idle"));
+    }
+}
@@ -85,6 +85,8 @@ pub fn make_ticket(source_id: &str, title: &str) -> TicketInput {
         blocked_by: vec![],
         children: vec![],
         parent: None,
+        sprint: None,
+        sprint_current: false,
     }
 }
 
@@ -149,6 +149,23 @@ pub fn cap_with_suffix(s: &str, max: usize, suffix: &str) -> String {
     }
 }
 
+/// The last `max_bytes` of `text`, cut at a line boundary. Used to keep the
+/// end of command output, where failures are reported.
+pub fn tail_lines(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut start = text.len() - max_bytes;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let cut = &text[start..];
+    cut.find('\n')
+        .map(|i| &cut[i + 1..])
+        .unwrap_or(cut)
+        .to_string()
+}
+
 /// Split a file's content into (frontmatter_yaml, body).
 ///
 /// Returns `None` if the content doesn't start with `---` or has no closing `---`.
@@ -1,8 +1,17 @@
+use std::collections::HashMap;
+
+use crate::asana;
+use crate::bitbucket;
 use crate::error::{ConductorError, Result};
 use crate::github;
-use crate::issue_source::{GitHubConfig, IssueSource, JiraConfig, VantageConfig};
+use crate::gitlab;
+use crate::issue_source::{
+    AsanaConfig, BitbucketConfig, GitHubConfig, GitLabConfig, IssueSource, JiraConfig,
+    LinearConfig, VantageConfig,
+};
 use crate::jira_acli;
-use crate::tickets::TicketInput;
+use crate::linear;
+use crate::tickets::{FieldMap, TicketInput, WEBHOOK_SOURCE_TYPE};
 use crate::vantage;
 
 /// Typed dispatch for ticket sources.
@@ -13,7 +22,18 @@ use crate::vantage;
 #[derive(Debug)]
 pub enum TicketSource {
     GitHub(GitHubConfig),
+    /// Open pull requests of a GitHub repo, synced as `github_pr` tickets.
+    /// Not stored on its own; derived via [`TicketSource::pull_request_source`].
+    GitHubPrs(GitHubConfig),
     Jira(JiraConfig),
+    GitLab(GitLabConfig),
+    Linear(LinearConfig),
+    Bitbucket(BitbucketConfig),
+    Asana(AsanaConfig),
+    /// Tickets pushed by an external system to `POST /api/repos/{id}/tickets/ingest`.
+    /// There is nothing to pull, so [`TicketSource::sync`] and
+    /// [`TicketSource::fetch_one`] both fail.
+    Webhook,
     /// `(config, repo_slug)` — `repo_slug` filters deliverables by codebase on sync.
     /// Starts as `None`; call [`TicketSource::with_repo_slug`] before [`TicketSource::sync`].
     Vantage(VantageConfig, Option<String>),
@@ -34,8 +54,36 @@ impl TicketSource {
             "jira" => {
                 let cfg = serde_json::from_str::<JiraConfig>(&s.config_json)
                     .map_err(|e| ConductorError::TicketSync(format!("invalid jira config: {e}")))?;
+                cfg.field_map
+                    .validate()
+                    .map_err(|e| ConductorError::TicketSync(format!("invalid jira config: {e}")))?;
                 Ok(Self::Jira(cfg))
             }
+            "gitlab" => {
+                let cfg = serde_json::from_str::<GitLabConfig>(&s.config_json).map_err(|e| {
+                    ConductorError::TicketSync(format!("invalid gitlab config: {e}"))
+                })?;
+                Ok(Self::GitLab(cfg))
+            }
+            "linear" => {
+                let cfg = serde_json::from_str::<LinearConfig>(&s.config_json).map_err(|e| {
+                    ConductorError::TicketSync(format!("invalid linear config: {e}"))
+                })?;
+                Ok(Self::Linear(cfg))
+            }
+            "bitbucket" => {
+                let cfg = serde_json::from_str::<BitbucketConfig>(&s.config_json).map_err(|e| {
+                    ConductorError::TicketSync(format!("invalid bitbucket config: {e}"))
+                })?;
+                Ok(Self::Bitbucket(cfg))
+            }
+            "asana" => {
+                let cfg = serde_json::from_str::<AsanaConfig>(&s.config_json).map_err(|e| {
+                    ConductorError::TicketSync(format!("invalid asana config: {e}"))
+                })?;
+                Ok(Self::Asana(cfg))
+            }
+            WEBHOOK_SOURCE_TYPE => Ok(Self::Webhook),
             "vantage" => {
                 let cfg = serde_json::from_str::<VantageConfig>(&s.config_json).map_err(|e| {
                     ConductorError::TicketSync(format!("invalid vantage config: {e}"))
@@ -48,7 +96,7 @@ impl TicketSource {
 
     /// Set the `repo_slug` used by Vantage syncs to filter deliverables by codebase.
     ///
-    /// No-op for GitHub and Jira sources. Must be called before [`Self::sync`] on a
+    /// No-op for every source except Vantage. Must be called before [`Self::sync`] on a
     /// Vantage source, otherwise sync returns an error.
     pub fn with_repo_slug(self, slug: &str) -> Self {
         match self {
@@ -57,14 +105,31 @@ impl TicketSource {
         }
     }
 
+    /// Companion source that syncs this repo's pull requests, when the GitHub
+    /// config opts in with `"sync_prs": true`. `None` for every other source.
+    pub fn pull_request_source(&self) -> Option<Self> {
+        match self {
+            Self::GitHub(cfg) if cfg.sync_prs => Some(Self::GitHubPrs(cfg.clone())),
+            _ => None,
+        }
+    }
+
     /// Sync all tickets for this source.
     ///
-    /// `token` is an optional auth token passed to GitHub syncs; Jira/Vantage ignore it.
+    /// `token` is an optional auth token passed to GitHub syncs; Jira/Vantage ignore it
+    /// and GitLab, Linear, Bitbucket and Asana read their token from the environment
+    /// variable named in their config. Webhook sources cannot be synced.
     /// For Vantage sources, call [`Self::with_repo_slug`] first to set the codebase filter.
     pub fn sync(&self, token: Option<&str>) -> Result<Vec<TicketInput>> {
         match self {
             Self::GitHub(cfg) => github::sync_github_issues(&cfg.owner, &cfg.repo, token),
-            Self::Jira(cfg) => jira_acli::sync_jira_issues_acli(&cfg.jql, &cfg.url),
+            Self::GitHubPrs(cfg) => github::sync_github_prs(&cfg.owner, &cfg.repo, token),
+            Self::Jira(cfg) => jira_acli::sync_jira_issues_acli(&cfg.jql, &cfg.url, &cfg.field_map),
+            Self::GitLab(cfg) => gitlab::sync_gitlab_issues(cfg),
+            Self::Linear(cfg) => linear::sync_linear_issues(cfg),
+            Self::Bitbucket(cfg) => bitbucket::sync_bitbucket_issues(cfg),
+            Self::Asana(cfg) => asana::sync_asana_tasks(cfg),
+            Self::Webhook => Err(push_only_error()),
             Self::Vantage(cfg, repo_slug) => {
                 let slug = repo_slug.as_deref().ok_or_else(|| {
                     ConductorError::InvalidInput(
@@ -79,7 +144,10 @@ impl TicketSource {
 
     /// Fetch a single ticket by its source-specific ID string.
     ///
-    /// For GitHub the `source_id` is an issue number; for Jira it is an issue key.
+    /// For GitHub the `source_id` is an issue (or PR) number; for GitLab it is the
+    /// project-scoped issue `iid`; for Bitbucket the repository-scoped issue ID;
+    /// for Asana a task GID; for Jira it is an issue key and for Linear an issue
+    /// identifier such as `ENG-123`.
     pub fn fetch_one(&self, source_id: &str) -> Result<TicketInput> {
         match self {
             Self::GitHub(cfg) => {
@@ -90,18 +158,89 @@ impl TicketSource {
                 })?;
                 github::fetch_github_issue(&cfg.owner, &cfg.repo, issue_number, None)
             }
-            Self::Jira(cfg) => jira_acli::fetch_jira_issue(source_id, &cfg.url),
+            Self::GitHubPrs(cfg) => {
+                let pr_number: i64 = source_id.parse().map_err(|_| {
+                    ConductorError::InvalidInput(format!("invalid GitHub PR number: {source_id}"))
+                })?;
+                github::fetch_github_pr(&cfg.owner, &cfg.repo, pr_number, None)
+            }
+            Self::Jira(cfg) => jira_acli::fetch_jira_issue(source_id, &cfg.url, &cfg.field_map),
+            Self::GitLab(cfg) => {
+                let iid: u64 = source_id.parse().map_err(|_| {
+                    ConductorError::InvalidInput(format!("invalid GitLab issue iid: {source_id}"))
+                })?;
+                gitlab::fetch_gitlab_issue(cfg, iid)
+            }
+            Self::Linear(cfg) => linear::fetch_linear_issue(cfg, source_id),
+            Self::Bitbucket(cfg) => {
+                let id: u64 = source_id.parse().map_err(|_| {
+                    ConductorError::InvalidInput(format!("invalid Bitbucket issue ID: {source_id}"))
+                })?;
+                bitbucket::fetch_bitbucket_issue(cfg, id)
+            }
+            Self::Asana(cfg) => asana::fetch_asana_task(cfg, source_id),
+            Self::Webhook => Err(push_only_error()),
             Self::Vantage(cfg, _) => vantage::fetch_vantage_deliverable(source_id, &cfg.sdlc_root),
         }
     }
 
-    /// Returns the canonical source-type string (`"github"` / `"jira"` / `"vantage"`).
+    /// Post `body` as a comment on ticket `source_id` upstream.
+    ///
+    /// Supported for GitHub issues and PRs (via `gh`) and Jira (via `acli`);
+    /// every other source returns `InvalidInput`.
+    pub fn post_comment(&self, source_id: &str, body: &str) -> Result<()> {
+        match self {
+            Self::GitHub(cfg) => {
+                github::comment_on_github_issue(&cfg.owner, &cfg.repo, source_id, body)
+            }
+            Self::GitHubPrs(cfg) => {
+                github::comment_on_github_pr(&cfg.owner, &cfg.repo, source_id, body)
+            }
+            Self::Jira(_) => jira_acli::comment_on_jira_issue(source_id, body),
+            _ => Err(write_back_unsupported(self.source_type_str())),
+        }
+    }
+
+    /// Move ticket `source_id` to `state` (`open`, `in_progress` or `closed`)
+    /// upstream.
+    ///
+    /// GitHub issues can be closed and reopened but have no in-progress state;
+    /// Jira transitions to [`JiraConfig::status_for`] the state. Every other
+    /// source returns `InvalidInput`.
+    pub fn transition_state(&self, source_id: &str, state: &str) -> Result<()> {
+        match (self, state) {
+            (Self::GitHub(cfg), "closed") => {
+                github::close_github_issue(&cfg.owner, &cfg.repo, source_id)
+            }
+            (Self::GitHub(cfg), "open") => {
+                github::reopen_github_issue(&cfg.owner, &cfg.repo, source_id)
+            }
+            (Self::GitHub(_), _) => Err(ConductorError::InvalidInput(format!(
+                "GitHub issues cannot be moved to '{state}'; only open and closed"
+            ))),
+            (Self::Jira(cfg), _) => {
+                let status = cfg.status_for(state).ok_or_else(|| {
+                    ConductorError::InvalidInput(format!("no Jira status for state '{state}'"))
+                })?;
+                jira_acli::transition_jira_issue(source_id, status)
+            }
+            _ => Err(write_back_unsupported(self.source_type_str())),
+        }
+    }
+
+    /// Returns the canonical source-type string (`"github"` / `"github_pr"` / `"jira"` / `"gitlab"` / `"linear"` / `"bitbucket"` / `"asana"` / `"webhook"` / `"vantage"`).
     ///
     /// Used when passing `source_type` to `sync_and_close_tickets`.
     pub fn source_type_str(&self) -> &'static str {
         match self {
             Self::GitHub(_) => "github",
+            Self::GitHubPrs(_) => crate::tickets::GITHUB_PR_SOURCE_TYPE,
             Self::Jira(_) => "jira",
+            Self::GitLab(_) => "gitlab",
+            Self::Linear(_) => "linear",
+            Self::Bitbucket(_) => "bitbucket",
+            Self::Asana(_) => "asana",
+            Self::Webhook => WEBHOOK_SOURCE_TYPE,
             Self::Vantage(_, _) => "vantage",
         }
     }
@@ -112,6 +251,18 @@ impl TicketSource {
     /// - `"github"` with `Some(json)`: validates it parses as JSON and returns it.
     /// - `"jira"` with `None`: returns an error (config is required).
     /// - `"jira"` with `Some(json)`: validates and returns it.
+    /// - `"gitlab"` with `None`: infers `{"project_id":…,"base_url":…}` from a remote
+    ///   whose host contains `gitlab`; otherwise returns an error.
+    /// - `"gitlab"` with `Some(json)`: validates it as a GitLab config and returns it.
+    /// - `"linear"` with `None`: returns an error (the team key is required).
+    /// - `"linear"` with `Some(json)`: validates it as a Linear config and returns it.
+    /// - `"bitbucket"` with `None`: infers `{"workspace":…,"repo_slug":…}` from a
+    ///   `bitbucket.org` remote; otherwise returns an error.
+    /// - `"bitbucket"` with `Some(json)`: validates it as a Bitbucket config and returns it.
+    /// - `"asana"` with `None`: returns an error (the project GID is required).
+    /// - `"asana"` with `Some(json)`: validates it as an Asana config and returns it.
+    /// - `"webhook"` with `None`: returns `{}` (there is nothing to configure).
+    /// - `"webhook"` with `Some(json)`: validates it parses as JSON and returns it.
     /// - `"vantage"` with `None`: returns an error (config is required).
     /// - `"vantage"` with `Some(json)`: validates and returns it.
     /// - Any other type: returns `UnknownSourceType`.
@@ -121,10 +272,38 @@ impl TicketSource {
         remote_url: &str,
     ) -> Result<String> {
         match (source_type, config_json) {
-            ("github" | "jira" | "vantage", Some(json)) => {
-                serde_json::from_str::<serde_json::Value>(json).map_err(|e| {
+            (
+                "github" | "jira" | "gitlab" | "linear" | "bitbucket" | "asana" | "webhook"
+                | "vantage",
+                Some(json),
+            ) => {
+                let value = serde_json::from_str::<serde_json::Value>(json).map_err(|e| {
                     ConductorError::InvalidInput(format!("invalid JSON config: {e}"))
                 })?;
+                if let Some(map) = value.get("field_map").filter(|_| source_type == "jira") {
+                    serde_json::from_value::<FieldMap>(map.clone())
+                        .map_err(|e| {
+                            ConductorError::InvalidInput(format!("invalid field_map: {e}"))
+                        })?
+                        .validate()?;
+                }
+                if source_type == "gitlab" {
+                    serde_json::from_value::<GitLabConfig>(value).map_err(|e| {
+                        ConductorError::InvalidInput(format!("invalid gitlab config: {e}"))
+                    })?;
+                } else if source_type == "linear" {
+                    serde_json::from_value::<LinearConfig>(value).map_err(|e| {
+                        ConductorError::InvalidInput(format!("invalid linear config: {e}"))
+                    })?;
+                } else if source_type == "bitbucket" {
+                    serde_json::from_value::<BitbucketConfig>(value).map_err(|e| {
+                        ConductorError::InvalidInput(format!("invalid bitbucket config: {e}"))
+                    })?;
+                } else if source_type == "asana" {
+                    serde_json::from_value::<AsanaConfig>(value).map_err(|e| {
+                        ConductorError::InvalidInput(format!("invalid asana config: {e}"))
+                    })?;
+                }
                 Ok(json.to_string())
             }
             ("github", None) => {
@@ -134,7 +313,11 @@ impl TicketSource {
                          Use --config to specify manually."
                     ))
                 })?;
-                serde_json::to_string(&GitHubConfig { owner, repo }).map_err(|e| {
+                serde_json::to_string(&GitHubConfig {
+                    owner,
+                    repo,
+                    sync_prs: false,
+                }).map_err(|e| {
                     ConductorError::Config(format!("failed to serialize github config: {e}"))
                 })
             }
@@ -143,6 +326,55 @@ impl TicketSource {
                  (e.g. --config '{\"jql\":\"project = KEY AND status != Done\",\"url\":\"https://...\"}')"
                     .to_string(),
             )),
+            ("gitlab", None) => {
+                let (base_url, project_id) =
+                    gitlab::parse_gitlab_remote(remote_url).ok_or_else(|| {
+                        ConductorError::InvalidInput(format!(
+                            "cannot infer GitLab config from remote URL: {remote_url}. \
+                             Use --config to specify manually \
+                             (e.g. --config '{{\"project_id\":\"group/project\",\"base_url\":\"https://gitlab.example.com\"}}')"
+                        ))
+                    })?;
+                serde_json::to_string(&GitLabConfig {
+                    project_id,
+                    base_url,
+                    token_env: gitlab::DEFAULT_TOKEN_ENV.to_string(),
+                })
+                .map_err(|e| {
+                    ConductorError::Config(format!("failed to serialize gitlab config: {e}"))
+                })
+            }
+            ("linear", None) => Err(ConductorError::InvalidInput(
+                "--config is required for linear sources \
+                 (e.g. --config '{\"team_key\":\"ENG\"}'; the API key is read from $LINEAR_API_KEY)"
+                    .to_string(),
+            )),
+            ("bitbucket", None) => {
+                let (workspace, repo_slug) = bitbucket::parse_bitbucket_remote(remote_url)
+                    .ok_or_else(|| {
+                        ConductorError::InvalidInput(format!(
+                            "cannot infer Bitbucket config from remote URL: {remote_url}. \
+                             Use --config to specify manually \
+                             (e.g. --config '{{\"workspace\":\"acme\",\"repo_slug\":\"widget\"}}')"
+                        ))
+                    })?;
+                serde_json::to_string(&BitbucketConfig {
+                    workspace,
+                    repo_slug,
+                    token_env: bitbucket::DEFAULT_TOKEN_ENV.to_string(),
+                    email: None,
+                    api_url: bitbucket::DEFAULT_API_URL.to_string(),
+                })
+                .map_err(|e| {
+                    ConductorError::Config(format!("failed to serialize bitbucket config: {e}"))
+                })
+            }
+            ("asana", None) => Err(ConductorError::InvalidInput(
+                "--config is required for asana sources \
+                 (e.g. --config '{\"project_gid\":\"1200000000000000\"}'; the token is read from $ASANA_TOKEN)"
+                    .to_string(),
+            )),
+            ("webhook", None) => Ok("{}".to_string()),
             ("vantage", None) => Err(ConductorError::InvalidInput(
                 "--config is required for vantage sources \
                  (e.g. --config '{\"project_id\":\"PROJ-001\",\"sdlc_root\":\"/path/to/sdlc\"}')"
@@ -151,6 +383,176 @@ impl TicketSource {
             (other, _) => Err(ConductorError::UnknownSourceType(other.to_string())),
         }
     }
+
+    /// Human-readable name of what this source syncs, e.g. `"Jira issues"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::GitHub(_) => "GitHub issues",
+            Self::GitHubPrs(_) => "GitHub pull requests",
+            Self::Jira(_) => "Jira issues",
+            Self::GitLab(_) => "GitLab issues",
+            Self::Linear(_) => "Linear issues",
+            Self::Bitbucket(_) => "Bitbucket issues",
+            Self::Asana(_) => "Asana tasks",
+            Self::Webhook => "Webhook tickets",
+            Self::Vantage(_, _) => "Vantage deliverables",
+        }
+    }
+}
+
+/// A configured ticket source bound to everything it needs to fetch.
+///
+/// Frontends sync through `dyn TicketFetcher` values obtained from
+/// [`TicketSourceRegistry::fetchers`], so they never match on `source_type`.
+pub trait TicketFetcher {
+    /// `source_type` of the fetched tickets; passed to `close_missing_tickets`.
+    fn source_type(&self) -> &str;
+    /// Human-readable name for progress output, e.g. `"GitHub issues"`.
+    fn label(&self) -> &str;
+    /// Fetch every ticket this source currently considers open.
+    fn fetch(&self) -> Result<Vec<TicketInput>>;
+}
+
+/// Per-repo context handed to a [`SourceFactory`].
+pub struct SourceContext<'a> {
+    pub repo_slug: &'a str,
+    pub remote_url: &'a str,
+    /// GitHub token resolved for this repo, if any.
+    pub token: Option<&'a str>,
+}
+
+/// Builds the fetchers for one stored issue source. A single source may yield
+/// several fetchers (GitHub issues plus pull requests).
+pub type SourceFactory =
+    fn(&IssueSource, &SourceContext<'_>) -> Result<Vec<Box<dyn TicketFetcher>>>;
+
+/// Maps `source_type` strings to the factories that build their fetchers.
+///
+/// [`Default`] registers every built-in source; [`Self::register`] adds or
+/// replaces one.
+pub struct TicketSourceRegistry {
+    factories: HashMap<String, SourceFactory>,
+}
+
+impl Default for TicketSourceRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        for source_type in [
+            "github",
+            "jira",
+            "gitlab",
+            "linear",
+            "bitbucket",
+            "asana",
+            "vantage",
+        ] {
+            registry.register(source_type, builtin_fetchers);
+        }
+        registry.register(WEBHOOK_SOURCE_TYPE, |_, _| Ok(Vec::new()));
+        registry
+    }
+}
+
+impl TicketSourceRegistry {
+    pub fn register(&mut self, source_type: &str, factory: SourceFactory) {
+        self.factories.insert(source_type.to_string(), factory);
+    }
+
+    /// Fetchers for a repo's configured `sources`, in order.
+    ///
+    /// A repo with no sources falls back to its GitHub remote, if it has one.
+    /// A source that cannot be built yields an `Err` in its place so callers can
+    /// report it and carry on with the rest.
+    pub fn fetchers(
+        &self,
+        sources: &[IssueSource],
+        ctx: &SourceContext<'_>,
+    ) -> Vec<Result<Box<dyn TicketFetcher>>> {
+        if sources.is_empty() {
+            return github::parse_github_remote(ctx.remote_url)
+                .map(|(owner, repo)| {
+                    let cfg = GitHubConfig {
+                        owner,
+                        repo,
+                        sync_prs: false,
+                    };
+                    Ok(bind(TicketSource::GitHub(cfg), ctx))
+                })
+                .into_iter()
+                .collect();
+        }
+        let mut fetchers = Vec::new();
+        for source in sources {
+            let built = match self.factories.get(&source.source_type) {
+                Some(factory) => factory(source, ctx),
+                None => Err(ConductorError::UnknownSourceType(
+                    source.source_type.clone(),
+                )),
+            };
+            match built {
+                Ok(built) => fetchers.extend(built.into_iter().map(Ok)),
+                Err(e) => fetchers.push(Err(e)),
+            }
+        }
+        fetchers
+    }
+}
+
+/// [`TicketSource`] paired with the GitHub token its sync needs.
+struct BoundSource {
+    source: TicketSource,
+    token: Option<String>,
+}
+
+impl TicketFetcher for BoundSource {
+    fn source_type(&self) -> &str {
+        self.source.source_type_str()
+    }
+
+    fn label(&self) -> &str {
+        self.source.label()
+    }
+
+    fn fetch(&self) -> Result<Vec<TicketInput>> {
+        self.source.sync(self.token.as_deref())
+    }
+}
+
+fn bind(source: TicketSource, ctx: &SourceContext<'_>) -> Box<dyn TicketFetcher> {
+    Box::new(BoundSource {
+        source,
+        token: ctx.token.map(str::to_string),
+    })
+}
+
+fn builtin_fetchers(
+    source: &IssueSource,
+    ctx: &SourceContext<'_>,
+) -> Result<Vec<Box<dyn TicketFetcher>>> {
+    let ts = TicketSource::from_issue_source(source)?.with_repo_slug(ctx.repo_slug);
+    let prs = ts.pull_request_source();
+    Ok(std::iter::once(ts)
+        .chain(prs)
+        .map(|ts| bind(ts, ctx))
+        .collect())
+}
+
+/// Webhook sources only receive tickets; see [`TicketSource::Webhook`].
+fn push_only_error() -> ConductorError {
+    ConductorError::InvalidInput(
+        "webhook sources cannot be synced; tickets are pushed to \
+         POST /api/repos/{id}/tickets/ingest"
+            .to_string(),
+    )
+}
+
+fn write_back_unsupported(source_type: &str) -> ConductorError {
+    ConductorError::InvalidInput(format!(
+        "{source_type} tickets cannot be commented on or transitioned from conductor; \
+         only github and jira support write-back"
+    ))
 }
 
 /// Return the ticket IDs that the given ticket depends on, based on its source type.
@@ -226,6 +628,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pull_request_source_only_when_github_opts_in() {
+        let plain = make_issue_source("github", r#"{"owner":"acme","repo":"widget"}"#);
+        let ts = TicketSource::from_issue_source(&plain).unwrap();
+        assert!(ts.pull_request_source().is_none());
+
+        let opted = make_issue_source(
+            "github",
+            r#"{"owner":"acme","repo":"widget","sync_prs":true}"#,
+        );
+        let prs = TicketSource::from_issue_source(&opted)
+            .unwrap()
+            .pull_request_source()
+            .expect("sync_prs enables the PR source");
+        assert_eq!(prs.source_type_str(), "github_pr");
+        assert!(matches!(prs, TicketSource::GitHubPrs(cfg) if cfg.repo == "widget"));
+    }
+
     #[test]
     fn with_repo_slug_is_noop_for_github() {
         let src = make_issue_source("github", r#"{"owner":"acme","repo":"widget"}"#);
@@ -280,6 +700,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_issue_source_valid_gitlab_defaults_token_env() {
+        let src = make_issue_source(
+            "gitlab",
+            r#"{"project_id":"acme/widget","base_url":"https://gitlab.example.com"}"#,
+        );
+        let ts = TicketSource::from_issue_source(&src).unwrap();
+        assert_eq!(ts.source_type_str(), "gitlab");
+        match ts {
+            TicketSource::GitLab(cfg) => {
+                assert_eq!(cfg.project_id, "acme/widget");
+                assert_eq!(cfg.token_env, "GITLAB_TOKEN");
+            }
+            _ => panic!("expected GitLab variant"),
+        }
+    }
+
+    #[test]
+    fn from_issue_source_valid_linear_defaults_api_key_env() {
+        let src = make_issue_source("linear", r#"{"team_key":"ENG"}"#);
+        let ts = TicketSource::from_issue_source(&src).unwrap();
+        assert_eq!(ts.source_type_str(), "linear");
+        match ts {
+            TicketSource::Linear(cfg) => {
+                assert_eq!(cfg.team_key, "ENG");
+                assert_eq!(cfg.api_key_env, "LINEAR_API_KEY");
+            }
+            _ => panic!("expected Linear variant"),
+        }
+    }
+
+    #[test]
+    fn from_issue_source_valid_bitbucket_defaults_token_env_and_api_url() {
+        let src = make_issue_source("bitbucket", r#"{"workspace":"acme","repo_slug":"widget"}"#);
+        let ts = TicketSource::from_issue_source(&src).unwrap();
+        assert_eq!(ts.source_type_str(), "bitbucket");
+        match ts {
+            TicketSource::Bitbucket(cfg) => {
+                assert_eq!(
+                    (cfg.workspace.as_str(), cfg.repo_slug.as_str()),
+                    ("acme", "widget")
+                );
+                assert_eq!(cfg.token_env, "BITBUCKET_TOKEN");
+                assert_eq!(cfg.api_url, "https://api.bitbucket.org/2.0");
+                assert_eq!(cfg.email, None);
+            }
+            _ => panic!("expected Bitbucket variant"),
+        }
+    }
+
     #[test]
     fn from_issue_source_invalid_github_config() {
         let src = make_issue_source("github", "not-json");
@@ -309,10 +779,10 @@ mod tests {
 
     #[test]
     fn from_issue_source_unknown_source_type() {
-        let src = make_issue_source("linear", r#"{}"#);
+        let src = make_issue_source("trello", r#"{}"#);
         let err = TicketSource::from_issue_source(&src).unwrap_err();
         match err {
-            ConductorError::UnknownSourceType(t) => assert_eq!(t, "linear"),
+            ConductorError::UnknownSourceType(t) => assert_eq!(t, "trello"),
             _ => panic!("expected UnknownSourceType error, got {err:?}"),
         }
     }
@@ -333,6 +803,20 @@ mod tests {
         assert_eq!(result, json);
     }
 
+    #[test]
+    fn default_config_jira_validates_field_map() {
+        let ok = r#"{"jql":"project = FOO","url":"https://x","field_map":{"team":"/fields/customfield_1/value"}}"#;
+        assert!(TicketSource::default_config("jira", Some(ok), "").is_ok());
+
+        let bad =
+            r#"{"jql":"project = FOO","url":"https://x","field_map":{"team":"customfield_1"}}"#;
+        let err = TicketSource::default_config("jira", Some(bad), "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("JSON pointer")),
+            "unexpected error: {err:?}"
+        );
+    }
+
     #[test]
     fn default_config_github_with_invalid_json() {
         let err = TicketSource::default_config("github", Some("not-json"), "").unwrap_err();
@@ -402,6 +886,139 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_config_gitlab_requires_project_and_base_url() {
+        let ok = r#"{"project_id":"42","base_url":"https://gitlab.example.com","token_env":"GL"}"#;
+        assert_eq!(
+            TicketSource::default_config("gitlab", Some(ok), "").unwrap(),
+            ok
+        );
+
+        let err =
+            TicketSource::default_config("gitlab", Some(r#"{"project_id":"42"}"#), "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("invalid gitlab config")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn default_config_gitlab_infers_from_remote() {
+        let result =
+            TicketSource::default_config("gitlab", None, "git@gitlab.example.com:platform/api.git")
+                .unwrap();
+        let v: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(v["project_id"], "platform/api");
+        assert_eq!(v["base_url"], "https://gitlab.example.com");
+        assert_eq!(v["token_env"], "GITLAB_TOKEN");
+
+        let err =
+            TicketSource::default_config("gitlab", None, "git@github.com:a/b.git").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("cannot infer GitLab")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn default_config_linear_requires_team_key() {
+        let ok = r#"{"team_key":"ENG"}"#;
+        assert_eq!(
+            TicketSource::default_config("linear", Some(ok), "").unwrap(),
+            ok
+        );
+
+        let err = TicketSource::default_config("linear", Some("{}"), "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("invalid linear config")),
+            "unexpected error: {err:?}"
+        );
+        let err = TicketSource::default_config("linear", None, "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("--config is required for linear")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn default_config_bitbucket_validates_or_infers_from_remote() {
+        let ok = r#"{"workspace":"acme","repo_slug":"widget","email":"me@acme.io"}"#;
+        assert_eq!(
+            TicketSource::default_config("bitbucket", Some(ok), "").unwrap(),
+            ok
+        );
+        let err = TicketSource::default_config("bitbucket", Some(r#"{"workspace":"acme"}"#), "")
+            .unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("invalid bitbucket config")),
+            "unexpected error: {err:?}"
+        );
+
+        let result =
+            TicketSource::default_config("bitbucket", None, "git@bitbucket.org:acme/widget.git")
+                .unwrap();
+        let v: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(v["workspace"], "acme");
+        assert_eq!(v["repo_slug"], "widget");
+        assert_eq!(v["token_env"], "BITBUCKET_TOKEN");
+        assert!(v.get("api_url").is_none());
+
+        let err =
+            TicketSource::default_config("bitbucket", None, "git@github.com:a/b.git").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("cannot infer Bitbucket")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn default_config_asana_requires_project_gid() {
+        let err = TicketSource::default_config("asana", None, "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("--config is required for asana")),
+            "unexpected error: {err:?}"
+        );
+        let err = TicketSource::default_config("asana", Some("{}"), "").unwrap_err();
+        assert!(
+            matches!(&err, ConductorError::InvalidInput(msg) if msg.contains("invalid asana config")),
+            "unexpected error: {err:?}"
+        );
+
+        let json = r#"{"project_gid":"1200"}"#;
+        let result = TicketSource::default_config("asana", Some(json), "").unwrap();
+        let ts = TicketSource::from_issue_source(&make_issue_source("asana", &result)).unwrap();
+        match &ts {
+            TicketSource::Asana(cfg) => {
+                assert_eq!(cfg.project_gid, "1200");
+                assert_eq!(cfg.token_env, "ASANA_TOKEN");
+            }
+            _ => panic!("expected Asana variant"),
+        }
+        assert_eq!(ts.source_type_str(), "asana");
+    }
+
+    #[test]
+    fn webhook_source_needs_no_config_and_cannot_be_pulled() {
+        let config = TicketSource::default_config("webhook", None, "").unwrap();
+        assert_eq!(config, "{}");
+        let ts = TicketSource::from_issue_source(&make_issue_source("webhook", &config)).unwrap();
+        assert_eq!(ts.source_type_str(), WEBHOOK_SOURCE_TYPE);
+        assert!(matches!(
+            ts.sync(None),
+            Err(ConductorError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            ts.fetch_one("1"),
+            Err(ConductorError::InvalidInput(msg)) if msg.contains("/tickets/ingest")
+        ));
+
+        // Pushed tickets must not be closed by a pull sync, so the registry
+        // builds no fetcher for the source.
+        let fetchers = TicketSourceRegistry::default()
+            .fetchers(&[make_issue_source("webhook", "{}")], &ctx("", None));
+        assert!(fetchers.is_empty());
+    }
+
     #[test]
     fn default_config_vantage_with_valid_json() {
         let json = r#"{"project_id":"PROJ-001","sdlc_root":"/path/to/sdlc"}"#;
@@ -425,9 +1042,9 @@ mod tests {
 
     #[test]
     fn default_config_unknown_source_type() {
-        let err = TicketSource::default_config("linear", Some("{}"), "").unwrap_err();
+        let err = TicketSource::default_config("trello", Some("{}"), "").unwrap_err();
         match err {
-            ConductorError::UnknownSourceType(t) => assert_eq!(t, "linear"),
+            ConductorError::UnknownSourceType(t) => assert_eq!(t, "trello"),
             _ => panic!("expected UnknownSourceType error, got {err:?}"),
         }
     }
@@ -464,4 +1081,85 @@ mod tests {
         let ids = super::get_dependency_ids(&serde_json::to_string(&json).unwrap(), "github");
         assert!(ids.is_empty());
     }
+
+    // --- TicketSourceRegistry ---
+
+    fn ctx<'a>(remote_url: &'a str, token: Option<&'a str>) -> SourceContext<'a> {
+        SourceContext {
+            repo_slug: "my-repo",
+            remote_url,
+            token,
+        }
+    }
+
+    fn kinds(fetchers: &[Result<Box<dyn TicketFetcher>>]) -> Vec<String> {
+        fetchers
+            .iter()
+            .map(|f| match f {
+                Ok(f) => f.source_type().to_string(),
+                Err(e) => format!("error: {e}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn registry_falls_back_to_github_remote_without_sources() {
+        let registry = TicketSourceRegistry::default();
+        let fetchers = registry.fetchers(&[], &ctx("https://github.com/acme/app.git", None));
+        assert_eq!(kinds(&fetchers), ["github"]);
+        assert_eq!(fetchers[0].as_ref().unwrap().label(), "GitHub issues");
+
+        let none = registry.fetchers(&[], &ctx("https://gitlab.com/acme/app.git", None));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn registry_expands_pull_request_companion_and_keeps_order() {
+        let sources = [
+            make_issue_source("jira", r#"{"jql":"project = X","url":"https://j"}"#),
+            make_issue_source("github", r#"{"owner":"o","repo":"r","sync_prs":true}"#),
+        ];
+        let fetchers = TicketSourceRegistry::default().fetchers(&sources, &ctx("", Some("tok")));
+        assert_eq!(kinds(&fetchers), ["jira", "github", "github_pr"]);
+    }
+
+    #[test]
+    fn registry_reports_unbuildable_sources_in_place() {
+        let sources = [
+            make_issue_source("trello", "{}"),
+            make_issue_source("gitlab", "not-json"),
+            make_issue_source("linear", r#"{"team_key":"ENG"}"#),
+        ];
+        let fetchers = TicketSourceRegistry::default().fetchers(&sources, &ctx("", None));
+        assert_eq!(fetchers.len(), 3);
+        assert!(matches!(
+            &fetchers[0],
+            Err(ConductorError::UnknownSourceType(t)) if t == "trello"
+        ));
+        assert!(fetchers[1].is_err());
+        assert_eq!(fetchers[2].as_ref().unwrap().source_type(), "linear");
+    }
+
+    #[test]
+    fn registry_accepts_custom_sources() {
+        struct Static;
+        impl TicketFetcher for Static {
+            fn source_type(&self) -> &str {
+                "static"
+            }
+            fn label(&self) -> &str {
+                "static tickets"
+            }
+            fn fetch(&self) -> Result<Vec<TicketInput>> {
+                Ok(vec![])
+            }
+        }
+
+        let mut registry = TicketSourceRegistry::default();
+        registry.register("static", |_, _| Ok(vec![Box::new(Static)]));
+        let fetchers = registry.fetchers(&[make_issue_source("static", "{}")], &ctx("", None));
+        let fetcher = fetchers[0].as_ref().unwrap();
+        assert_eq!(fetcher.label(), "static tickets");
+        assert!(fetcher.fetch().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,627 @@
+//! Files referenced by tickets: images embedded in a ticket body and
+//! attachments reported by the source (Jira's `fields.attachment`).
+//!
+//! Metadata is recorded on every sync by [`super::TicketSyncer::upsert_tickets`].
+//! When `[attachments] download` is enabled, [`TicketAttachments::maintain`]
+//! fetches small images into [`crate::config::ticket_attachments_dir`] so agent
+//! prompts can point at local copies, and removes them again once the ticket
+//! has been closed for `retention_days`.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use chrono::Utc;
+use regex::Regex;
+use rusqlite::{named_params, Connection};
+use serde::{Deserialize, Serialize};
+
+use super::TicketInput;
+use crate::config::AttachmentsConfig;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+
+/// `![alt](url "title")` in Markdown bodies.
+static MARKDOWN_IMAGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"!\[([^\]]*)\]\((https?://[^)\s]+)(?:\s+"[^"]*")?\)"#).expect("valid regex")
+});
+/// `<img src="url">` in HTML bodies (GitHub pastes screenshots this way).
+static HTML_IMAGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<img\b[^>]*\bsrc\s*=\s*["'](https?://[^"']+)["']"#).expect("valid regex")
+});
+/// `[name](url)` links to files uploaded to GitHub (`/user-attachments/` or `/assets/`).
+static GITHUB_FILE_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^!])\[([^\]]+)\]\((https://github\.com/(?:[^)\s]*/)?(?:user-attachments|assets)/[^)\s]+)\)")
+        .expect("valid regex")
+});
+
+/// An attachment found on a ticket during sync, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentRef {
+    pub url: String,
+    pub filename: String,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<i64>,
+}
+
+/// A stored ticket attachment.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketAttachment {
+    pub id: String,
+    pub ticket_id: String,
+    pub url: String,
+    pub filename: String,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    /// Path of the downloaded copy, when there is one.
+    pub local_path: Option<String>,
+    pub downloaded_at: Option<String>,
+    /// Why the download failed or was skipped; it is not retried.
+    pub download_error: Option<String>,
+    pub created_at: String,
+}
+
+/// Counts reported by [`TicketAttachments::maintain`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AttachmentMaintenance {
+    pub downloaded: usize,
+    pub removed: usize,
+}
+
+/// Guess a MIME type from a file extension. Only image types matter here:
+/// they decide what is worth downloading.
+fn mime_from_filename(filename: &str) -> Option<&'static str> {
+    let ext = filename.rsplit_once('.')?.1.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "zip" => "application/zip",
+        _ => return None,
+    })
+}
+
+/// Last path segment of `url`, without query string or fragment.
+fn filename_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/')
+        .find(|s| !s.is_empty())
+        .unwrap_or("attachment")
+        .to_string()
+}
+
+fn body_ref(url: &str, label: &str) -> AttachmentRef {
+    let from_url = filename_from_url(url);
+    // GitHub asset URLs end in an opaque UUID; the link text is the real name.
+    let filename = if mime_from_filename(&from_url).is_none() && !label.trim().is_empty() {
+        label.trim().to_string()
+    } else {
+        from_url
+    };
+    AttachmentRef {
+        url: url.to_string(),
+        mime_type: mime_from_filename(&filename).map(str::to_string),
+        filename,
+        size_bytes: None,
+    }
+}
+
+/// Collect the attachments referenced by a ticket, deduplicated by URL in
+/// order of first appearance.
+pub fn extract_attachments(ticket: &TicketInput) -> Vec<AttachmentRef> {
+    let mut refs: Vec<AttachmentRef> = Vec::new();
+    for cap in MARKDOWN_IMAGE.captures_iter(&ticket.body) {
+        refs.push(body_ref(&cap[2], &cap[1]));
+    }
+    for cap in HTML_IMAGE.captures_iter(&ticket.body) {
+        refs.push(body_ref(&cap[1], ""));
+    }
+    for cap in GITHUB_FILE_LINK.captures_iter(&ticket.body) {
+        refs.push(body_ref(&cap[2], &cap[1]));
+    }
+
+    if ticket.source_type == "jira" {
+        let raw: serde_json::Value = ticket
+            .raw_json
+            .as_deref()
+            .and_then(|r| serde_json::from_str(r).ok())
+            .unwrap_or_default();
+        for att in raw["fields"]["attachment"].as_array().into_iter().flatten() {
+            let Some(url) = att["content"].as_str() else {
+                continue;
+            };
+            let filename = att["filename"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| filename_from_url(url));
+            refs.push(AttachmentRef {
+                url: url.to_string(),
+                mime_type: att["mimeType"]
+                    .as_str()
+                    .map(str::to_string)
+                    .or_else(|| mime_from_filename(&filename).map(str::to_string)),
+                filename,
+                size_bytes: att["size"].as_i64(),
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    refs.retain(|r| seen.insert(r.url.clone()));
+    refs
+}
+
+/// Replace the stored attachments of `ticket_id` with `refs`. Rows whose URL
+/// is still referenced keep their download state. Runs inside the caller's
+/// sync transaction.
+pub(super) fn replace_for_ticket(
+    conn: &Connection,
+    ticket_id: &str,
+    refs: &[AttachmentRef],
+) -> Result<()> {
+    let urls: Vec<String> = refs.iter().map(|r| r.url.clone()).collect();
+    if urls.is_empty() {
+        conn.execute(
+            "DELETE FROM ticket_attachments WHERE ticket_id = :ticket_id",
+            named_params! { ":ticket_id": ticket_id },
+        )?;
+    } else {
+        crate::db::with_in_clause(
+            "DELETE FROM ticket_attachments WHERE ticket_id = ?1 AND url NOT IN",
+            &[&ticket_id as &dyn rusqlite::types::ToSql],
+            &urls,
+            |sql, params| -> Result<usize> { Ok(conn.prepare(sql)?.execute(params)?) },
+        )?;
+    }
+    let now = Utc::now().to_rfc3339();
+    for r in refs {
+        conn.execute(
+            "INSERT INTO ticket_attachments \
+                 (id, ticket_id, url, filename, mime_type, size_bytes, created_at) \
+             VALUES (:id, :ticket_id, :url, :filename, :mime_type, :size_bytes, :created_at) \
+             ON CONFLICT(ticket_id, url) DO UPDATE SET \
+                 filename = excluded.filename, \
+                 mime_type = COALESCE(excluded.mime_type, ticket_attachments.mime_type), \
+                 size_bytes = COALESCE(excluded.size_bytes, ticket_attachments.size_bytes)",
+            named_params! {
+                ":id": crate::new_id(),
+                ":ticket_id": ticket_id,
+                ":url": r.url,
+                ":filename": r.filename,
+                ":mime_type": r.mime_type,
+                ":size_bytes": r.size_bytes,
+                ":created_at": now,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Append a list of the ticket's attachments to an agent prompt, pointing at
+/// local copies where they exist. Returns `prompt` unchanged when there are none.
+pub(super) fn append_to_prompt(prompt: String, attachments: &[TicketAttachment]) -> String {
+    if attachments.is_empty() {
+        return prompt;
+    }
+    let mut out = prompt;
+    out.push_str("\n\nAttachments (open the local files to view them):\n");
+    for a in attachments {
+        let location = a.local_path.as_deref().unwrap_or(&a.url);
+        out.push_str(&format!("- {}: {location}\n", a.filename));
+    }
+    out
+}
+
+/// Keep only characters that are safe in a file name.
+fn sanitize_filename(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "attachment".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Download `url` if it is an image of at most `max_bytes`. Returns the bytes
+/// and the response's MIME type.
+fn fetch_image(url: &str, max_bytes: u64) -> std::result::Result<(Vec<u8>, String), String> {
+    let resp = ureq::get(url)
+        .set("User-Agent", "conductor-ai")
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|e| format!("download failed: {e}"))?;
+    let mime = resp
+        .header("Content-Type")
+        .unwrap_or("")
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if !mime.starts_with("image/") {
+        return Err(format!("not an image ({mime})"));
+    }
+    if let Some(len) = resp
+        .header("Content-Length")
+        .and_then(|l| l.parse::<u64>().ok())
+    {
+        if len > max_bytes {
+            return Err(format!("larger than {} KB", max_bytes / 1024));
+        }
+    }
+    let mut bytes = Vec::new();
+    resp.into_reader()
+        .take(max_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("download failed: {e}"))?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(format!("larger than {} KB", max_bytes / 1024));
+    }
+    Ok((bytes, mime))
+}
+
+const ATTACHMENT_COLUMNS: &str = "a.id, a.ticket_id, a.url, a.filename, a.mime_type, \
+     a.size_bytes, a.local_path, a.downloaded_at, a.download_error, a.created_at";
+
+fn row_to_attachment(row: &rusqlite::Row) -> rusqlite::Result<TicketAttachment> {
+    Ok(TicketAttachment {
+        id: row.get("id")?,
+        ticket_id: row.get("ticket_id")?,
+        url: row.get("url")?,
+        filename: row.get("filename")?,
+        mime_type: row.get("mime_type")?,
+        size_bytes: row.get("size_bytes")?,
+        local_path: row.get("local_path")?,
+        downloaded_at: row.get("downloaded_at")?,
+        download_error: row.get("download_error")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+pub struct TicketAttachments<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> TicketAttachments<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Attachments of one ticket, in the order they were first seen.
+    pub fn list(&self, ticket_id: &str) -> Result<Vec<TicketAttachment>> {
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT {ATTACHMENT_COLUMNS} FROM ticket_attachments a \
+                 WHERE a.ticket_id = :ticket_id ORDER BY a.created_at, a.rowid"
+            ),
+            named_params! { ":ticket_id": ticket_id },
+            row_to_attachment,
+        )
+    }
+
+    /// All attachments keyed by ticket ID. Tickets without any are absent.
+    pub fn all_by_ticket(&self) -> Result<HashMap<String, Vec<TicketAttachment>>> {
+        let rows = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {ATTACHMENT_COLUMNS} FROM ticket_attachments a \
+                 ORDER BY a.created_at, a.rowid"
+            ),
+            [],
+            row_to_attachment,
+        )?;
+        let mut map: HashMap<String, Vec<TicketAttachment>> = HashMap::new();
+        for a in rows {
+            map.entry(a.ticket_id.clone()).or_default().push(a);
+        }
+        Ok(map)
+    }
+
+    /// Download pending images of open tickets into `dir/<ticket_id>/` and
+    /// apply the cleanup policy. Does nothing but cleanup when downloading is
+    /// disabled.
+    pub fn maintain(&self, config: &AttachmentsConfig) -> Result<AttachmentMaintenance> {
+        self.maintain_in(config, &crate::config::ticket_attachments_dir())
+    }
+
+    fn maintain_in(&self, config: &AttachmentsConfig, dir: &Path) -> Result<AttachmentMaintenance> {
+        let downloaded = if config.download {
+            self.download_pending(dir, config.max_download_kb.saturating_mul(1024))?
+        } else {
+            0
+        };
+        let removed = self.cleanup(dir, config.retention_days)?;
+        Ok(AttachmentMaintenance {
+            downloaded,
+            removed,
+        })
+    }
+
+    fn download_pending(&self, dir: &Path, max_bytes: u64) -> Result<usize> {
+        let pending = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {ATTACHMENT_COLUMNS} FROM ticket_attachments a \
+                 JOIN tickets t ON t.id = a.ticket_id \
+                 WHERE t.state != 'closed' AND a.local_path IS NULL \
+                   AND a.download_error IS NULL \
+                   AND (a.mime_type IS NULL OR a.mime_type LIKE 'image/%') \
+                   AND (a.size_bytes IS NULL OR a.size_bytes <= :max_bytes)"
+            ),
+            named_params! { ":max_bytes": max_bytes as i64 },
+            row_to_attachment,
+        )?;
+
+        let mut downloaded = 0;
+        for a in pending {
+            let result = fetch_image(&a.url, max_bytes).and_then(|(bytes, mime)| {
+                let ticket_dir = dir.join(&a.ticket_id);
+                std::fs::create_dir_all(&ticket_dir).map_err(|e| e.to_string())?;
+                let path = ticket_dir.join(format!("{}-{}", a.id, sanitize_filename(&a.filename)));
+                std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+                Ok((path, mime, bytes.len() as i64))
+            });
+            match result {
+                Ok((path, mime, size)) => {
+                    self.conn.execute(
+                        "UPDATE ticket_attachments SET local_path = :path, downloaded_at = :now, \
+                             mime_type = :mime, size_bytes = :size WHERE id = :id",
+                        named_params! {
+                            ":path": path.to_string_lossy(),
+                            ":now": Utc::now().to_rfc3339(),
+                            ":mime": mime,
+                            ":size": size,
+                            ":id": a.id,
+                        },
+                    )?;
+                    downloaded += 1;
+                }
+                Err(e) => {
+                    tracing::debug!("skipping ticket attachment {}: {e}", a.url);
+                    self.conn.execute(
+                        "UPDATE ticket_attachments SET download_error = :error WHERE id = :id",
+                        named_params! { ":error": e, ":id": a.id },
+                    )?;
+                }
+            }
+        }
+        Ok(downloaded)
+    }
+
+    /// Forget downloads of tickets closed more than `retention_days` ago, then
+    /// delete every file under `dir` that no attachment points at (this also
+    /// covers deleted tickets and attachments dropped from a ticket body).
+    /// Returns the number of files deleted.
+    fn cleanup(&self, dir: &Path, retention_days: u32) -> Result<usize> {
+        let cutoff = (Utc::now() - chrono::Duration::days(i64::from(retention_days))).to_rfc3339();
+        self.conn.execute(
+            "UPDATE ticket_attachments SET local_path = NULL, downloaded_at = NULL \
+             WHERE local_path IS NOT NULL AND ticket_id IN \
+                 (SELECT id FROM tickets WHERE state = 'closed' AND synced_at < :cutoff)",
+            named_params! { ":cutoff": cutoff },
+        )?;
+
+        let Ok(ticket_dirs) = std::fs::read_dir(dir) else {
+            return Ok(0);
+        };
+        let keep: HashSet<PathBuf> = query_collect(
+            self.conn,
+            "SELECT local_path FROM ticket_attachments WHERE local_path IS NOT NULL",
+            [],
+            |row| row.get::<_, String>(0),
+        )?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let mut removed = 0;
+        for ticket_dir in ticket_dirs.flatten().map(|e| e.path()) {
+            if !ticket_dir.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(&ticket_dir)?.flatten().map(|e| e.path()) {
+                if keep.contains(&file) {
+                    continue;
+                }
+                std::fs::remove_file(&file).map_err(|e| {
+                    ConductorError::Io(std::io::Error::new(
+                        e.kind(),
+                        format!("failed to remove '{}': {e}", file.display()),
+                    ))
+                })?;
+                removed += 1;
+            }
+            // Only succeeds once the directory is empty.
+            let _ = std::fs::remove_dir(&ticket_dir);
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tickets::TicketSyncer;
+
+    fn ticket(source_type: &str, source_id: &str, body: &str) -> TicketInput {
+        TicketInput {
+            source_type: source_type.to_string(),
+            source_id: source_id.to_string(),
+            title: format!("Ticket {source_id}"),
+            body: body.to_string(),
+            state: "open".to_string(),
+            labels: vec![],
+            assignee: None,
+            priority: None,
+            url: String::new(),
+            raw_json: None,
+            label_details: vec![],
+            blocked_by: vec![],
+            children: vec![],
+            parent: None,
+            sprint: None,
+            sprint_current: false,
+        }
+    }
+
+    #[test]
+    fn extracts_markdown_html_and_github_file_links() {
+        let body = "Crash:\n![screenshot](https://example.com/img/crash.png \"title\")\n\
+                    <img width=\"400\" src=\"https://github.com/user-attachments/assets/1a2b\" />\n\
+                    Logs: [server.log](https://github.com/user-attachments/files/99/server.log)\n\
+                    again ![dup](https://example.com/img/crash.png)";
+        let refs = extract_attachments(&ticket("github", "1", body));
+        let names: Vec<&str> = refs.iter().map(|r| r.filename.as_str()).collect();
+        assert_eq!(names, vec!["crash.png", "1a2b", "server.log"]);
+        assert_eq!(refs[0].mime_type.as_deref(), Some("image/png"));
+        assert_eq!(refs[1].mime_type, None);
+        assert_eq!(refs[2].mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn extracts_jira_attachment_field() {
+        let mut t = ticket("jira", "PAY-1", "");
+        t.raw_json = Some(
+            serde_json::json!({ "fields": { "attachment": [
+                { "filename": "trace.txt", "content": "https://jira.example.com/att/1",
+                  "mimeType": "text/plain", "size": 120 }
+            ]}})
+            .to_string(),
+        );
+        let refs = extract_attachments(&t);
+        assert_eq!(
+            refs,
+            vec![AttachmentRef {
+                url: "https://jira.example.com/att/1".into(),
+                filename: "trace.txt".into(),
+                mime_type: Some("text/plain".into()),
+                size_bytes: Some(120),
+            }]
+        );
+    }
+
+    #[test]
+    fn resync_keeps_download_state_and_drops_removed_urls() {
+        let conn = crate::test_helpers::setup_db();
+        let syncer = TicketSyncer::new(&conn);
+        let body = "![a](https://x.test/a.png) ![b](https://x.test/b.png)";
+        syncer
+            .upsert_tickets("r1", &[ticket("github", "1", body)])
+            .unwrap();
+        let ticket_id = syncer.get_by_source_id("r1", "1").unwrap().id;
+        let atts = TicketAttachments::new(&conn);
+        assert_eq!(atts.list(&ticket_id).unwrap().len(), 2);
+
+        conn.execute(
+            "UPDATE ticket_attachments SET local_path = '/tmp/a.png' WHERE url = 'https://x.test/a.png'",
+            [],
+        )
+        .unwrap();
+        syncer
+            .upsert_tickets("r1", &[ticket("github", "1", "![a](https://x.test/a.png)")])
+            .unwrap();
+        let list = atts.list(&ticket_id).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].local_path.as_deref(), Some("/tmp/a.png"));
+    }
+
+    #[test]
+    fn downloads_images_only_and_cleans_up_after_close() {
+        let mut server = mockito::Server::new();
+        let _img = server
+            .mock("GET", "/shot.png")
+            .with_header("Content-Type", "image/png")
+            .with_body([0x89, b'P', b'N', b'G'])
+            .create();
+        let _html = server
+            .mock("GET", "/page")
+            .with_header("Content-Type", "text/html")
+            .with_body("<html/>")
+            .create();
+        let url = server.url();
+
+        let conn = crate::test_helpers::setup_db();
+        let syncer = TicketSyncer::new(&conn);
+        let body = format!("![s]({url}/shot.png) ![p]({url}/page)");
+        syncer
+            .upsert_tickets("r1", &[ticket("github", "1", &body)])
+            .unwrap();
+        let ticket_id = syncer.get_by_source_id("r1", "1").unwrap().id;
+
+        let dir = tempfile::tempdir().unwrap();
+        let atts = TicketAttachments::new(&conn);
+        let config = AttachmentsConfig {
+            download: true,
+            retention_days: 0,
+            ..AttachmentsConfig::default()
+        };
+        let done = atts.maintain_in(&config, dir.path()).unwrap();
+        assert_eq!(done.downloaded, 1);
+
+        let list = atts.list(&ticket_id).unwrap();
+        let shot = list.iter().find(|a| a.filename == "shot.png").unwrap();
+        let local = PathBuf::from(shot.local_path.as_deref().unwrap());
+        assert_eq!(std::fs::read(&local).unwrap(), b"\x89PNG");
+        let page = list.iter().find(|a| a.filename == "p").unwrap();
+        assert!(page
+            .download_error
+            .as_deref()
+            .unwrap()
+            .contains("not an image"));
+
+        // Closing the ticket makes the copy eligible for removal.
+        syncer
+            .close_missing_tickets("r1", "github", &["other"])
+            .unwrap();
+        let done = atts.maintain_in(&config, dir.path()).unwrap();
+        assert_eq!(done.removed, 1);
+        assert!(!local.exists());
+        assert!(atts.list(&ticket_id).unwrap()[0].local_path.is_none());
+    }
+
+    #[test]
+    fn prompt_lists_local_copies_before_urls() {
+        let make = |filename: &str, local: Option<&str>| TicketAttachment {
+            id: "a".into(),
+            ticket_id: "t".into(),
+            url: format!("https://x.test/{filename}"),
+            filename: filename.into(),
+            mime_type: None,
+            size_bytes: None,
+            local_path: local.map(str::to_string),
+            downloaded_at: None,
+            download_error: None,
+            created_at: String::new(),
+        };
+        let prompt = append_to_prompt(
+            "Fix it.".into(),
+            &[
+                make(
+                    "a.png",
+                    Some("/home/u/.conductor/ticket-attachments/t/a.png"),
+                ),
+                make("b.log", None),
+            ],
+        );
+        assert!(prompt.contains("- a.png: /home/u/.conductor/ticket-attachments/t/a.png\n"));
+        assert!(prompt.contains("- b.log: https://x.test/b.log\n"));
+        assert_eq!(append_to_prompt("Fix it.".into(), &[]), "Fix it.");
+    }
+}
@@ -0,0 +1,189 @@
+//! Upstream changes to tickets that live worktrees are working on.
+//!
+//! When a sync reopens, closes, re-prioritises or reassigns a ticket,
+//! [`TicketSyncer::upsert_tickets`](super::TicketSyncer::upsert_tickets)
+//! appends a summary to the `ticket_change` of each live worktree linked to
+//! it and records a notification. The summary stays on the worktree until
+//! [`WorktreeManager::acknowledge_ticket_change`](crate::worktree::WorktreeManager::acknowledge_ticket_change)
+//! clears it.
+
+use std::collections::HashMap;
+
+use rusqlite::{named_params, Connection};
+
+use crate::db::{query_collect, with_in_clause};
+use crate::error::Result;
+use crate::notifications::{NewNotification, NotificationManager, NotificationSeverity};
+use crate::worktree::LIVE_STATUSES_SQL;
+
+use super::TicketInput;
+
+/// The fields of a stored ticket whose upstream changes are surfaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TrackedFields {
+    pub state: String,
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+}
+
+/// Stored [`TrackedFields`] of the tickets in `tickets` that already exist,
+/// keyed by `(source_type, source_id)`.
+pub(super) fn snapshot(
+    conn: &Connection,
+    repo_id: &str,
+    tickets: &[TicketInput],
+) -> Result<HashMap<(String, String), TrackedFields>> {
+    let mut stored = HashMap::new();
+    if tickets.is_empty() {
+        return Ok(stored);
+    }
+    let source_ids: Vec<String> = tickets.iter().map(|t| t.source_id.clone()).collect();
+    with_in_clause(
+        "SELECT source_type, source_id, state, priority, assignee FROM tickets \
+         WHERE repo_id = ?1 AND source_id IN",
+        &[&repo_id as &dyn rusqlite::types::ToSql],
+        &source_ids,
+        |sql, params| -> Result<()> {
+            let mut stmt = conn.prepare(sql)?;
+            let rows = stmt.query_map(params, |row| {
+                Ok((
+                    (row.get("source_type")?, row.get("source_id")?),
+                    TrackedFields {
+                        state: row.get("state")?,
+                        priority: row.get("priority")?,
+                        assignee: row.get("assignee")?,
+                    },
+                ))
+            })?;
+            for row in rows {
+                let (key, fields) = row?;
+                stored.insert(key, fields);
+            }
+            Ok(())
+        },
+    )?;
+    Ok(stored)
+}
+
+/// Human-readable changes from `old` to `new`, e.g. `["reopened",
+/// "priority P2 → P1"]`. Empty when nothing tracked changed.
+pub(super) fn describe(old: &TrackedFields, new: &TicketInput) -> Vec<String> {
+    let mut changes = Vec::new();
+    if old.state != new.state {
+        changes.push(match (old.state.as_str(), new.state.as_str()) {
+            ("closed", _) => "reopened".to_string(),
+            (_, "closed") => "closed".to_string(),
+            (from, to) => format!("state {from} → {to}"),
+        });
+    }
+    if old.priority != new.priority {
+        changes.push(format!(
+            "priority {} → {}",
+            old.priority.as_deref().unwrap_or("none"),
+            new.priority.as_deref().unwrap_or("none")
+        ));
+    }
+    if old.assignee != new.assignee {
+        changes.push(match &new.assignee {
+            Some(assignee) => format!("assigned to {assignee}"),
+            None => "unassigned".to_string(),
+        });
+    }
+    changes
+}
+
+/// A synced ticket whose tracked fields changed.
+pub(super) struct ChangedTicket {
+    pub ticket_id: String,
+    pub source_id: String,
+    pub title: String,
+    pub changes: Vec<String>,
+}
+
+/// Flag the live worktrees linked to each changed ticket and record one
+/// notification per worktree. Returns the number of worktrees flagged.
+pub(super) fn flag_linked_worktrees(conn: &Connection, changed: &[ChangedTicket]) -> Result<usize> {
+    let mut flagged = 0;
+    for ticket in changed {
+        let summary = ticket.changes.join(", ");
+        let worktrees: Vec<(String, String, String)> = query_collect(
+            conn,
+            &format!(
+                "SELECT id, repo_id, slug FROM worktrees \
+                 WHERE ticket_id = :ticket_id AND status IN {LIVE_STATUSES_SQL}"
+            ),
+            named_params! { ":ticket_id": ticket.ticket_id },
+            |row| Ok((row.get("id")?, row.get("repo_id")?, row.get("slug")?)),
+        )?;
+        for (id, repo_id, slug) in worktrees {
+            // Earlier unacknowledged changes are kept ahead of the new ones.
+            conn.execute(
+                "UPDATE worktrees SET ticket_change = \
+                     CASE WHEN ticket_change IS NULL THEN :summary \
+                          ELSE ticket_change || '; ' || :summary END \
+                 WHERE id = :id",
+                named_params! { ":summary": summary, ":id": id },
+            )?;
+            NotificationManager::new(conn).record(&NewNotification {
+                kind: "ticket_changed".into(),
+                severity: NotificationSeverity::Info,
+                title: format!("Ticket #{} changed: {summary}", ticket.source_id),
+                body: format!("{} — linked to worktree {slug}", ticket.title),
+                url: Some(format!("/repos/{repo_id}/worktrees/{id}")),
+                repo_id: Some(repo_id),
+            })?;
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(state: &str, priority: Option<&str>, assignee: Option<&str>) -> TrackedFields {
+        TrackedFields {
+            state: state.to_string(),
+            priority: priority.map(String::from),
+            assignee: assignee.map(String::from),
+        }
+    }
+
+    fn input(state: &str, priority: Option<&str>, assignee: Option<&str>) -> TicketInput {
+        let f = fields(state, priority, assignee);
+        TicketInput {
+            source_type: "github".to_string(),
+            source_id: "1".to_string(),
+            title: "t".to_string(),
+            body: String::new(),
+            state: f.state,
+            labels: vec![],
+            assignee: f.assignee,
+            priority: f.priority,
+            url: String::new(),
+            raw_json: None,
+            label_details: vec![],
+            blocked_by: vec![],
+            children: vec![],
+            parent: None,
+            sprint: None,
+            sprint_current: false,
+        }
+    }
+
+    #[test]
+    fn describe_names_each_tracked_change() {
+        let old = fields("closed", Some("P2"), None);
+        assert_eq!(
+            describe(&old, &input("open", Some("P1"), Some("alice"))),
+            ["reopened", "priority P2 → P1", "assigned to alice"]
+        );
+        let old = fields("open", None, Some("alice"));
+        assert_eq!(
+            describe(&old, &input("in_progress", None, None)),
+            ["state open → in_progress", "unassigned"]
+        );
+        assert!(describe(&old, &input("open", None, Some("alice"))).is_empty());
+    }
+}
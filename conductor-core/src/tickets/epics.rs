@@ -0,0 +1,305 @@
+//! Tickets nested under their epic or parent ticket, with progress and agent
+//! spend rolled up from each parent's descendants.
+//!
+//! Parent links are the `parent_of` edges in `ticket_dependencies`, synced
+//! from Jira parents/epics, GitHub sub-issues and Linear parents.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::TicketAgentTotals;
+
+use super::roadmap::RoadmapStage;
+use super::{Ticket, TicketDependencies, TicketProgress};
+
+/// Progress and agent spend across a parent ticket's descendants.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EpicRollup {
+    /// Children, grandchildren and so on.
+    pub total: usize,
+    pub done: usize,
+    pub in_review: usize,
+    pub in_progress: usize,
+    /// Completed agent runs on the parent and all its descendants.
+    pub agent_runs: i64,
+    /// Cost of those runs, in USD.
+    pub agent_cost: f64,
+}
+
+impl EpicRollup {
+    /// Roll up everything below `ticket_id`. A ticket reachable by more than
+    /// one path, or through a cycle, is counted once.
+    pub fn compute(
+        ticket_id: &str,
+        dependencies: &HashMap<String, TicketDependencies>,
+        progress: &HashMap<String, TicketProgress>,
+        totals: &HashMap<String, TicketAgentTotals>,
+    ) -> Self {
+        let mut rollup = Self::default();
+        rollup.add_runs(totals.get(ticket_id));
+        let mut seen: HashSet<&str> = HashSet::from([ticket_id]);
+        let mut stack: Vec<&Ticket> = children(ticket_id, dependencies).iter().collect();
+        while let Some(child) = stack.pop() {
+            if !seen.insert(child.id.as_str()) {
+                continue;
+            }
+            rollup.total += 1;
+            match RoadmapStage::of(child, progress.get(&child.id)) {
+                RoadmapStage::Done => rollup.done += 1,
+                RoadmapStage::InReview => rollup.in_review += 1,
+                RoadmapStage::InProgress => rollup.in_progress += 1,
+                RoadmapStage::NotStarted => {}
+            }
+            rollup.add_runs(totals.get(&child.id));
+            stack.extend(children(&child.id, dependencies));
+        }
+        rollup
+    }
+
+    fn add_runs(&mut self, totals: Option<&TicketAgentTotals>) {
+        if let Some(t) = totals {
+            self.agent_runs += t.total_runs;
+            self.agent_cost += t.total_cost;
+        }
+    }
+
+    /// Share of descendants that are done, rounded down to a whole percent.
+    pub fn percent_done(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+fn children<'d>(
+    ticket_id: &str,
+    dependencies: &'d HashMap<String, TicketDependencies>,
+) -> &'d [Ticket] {
+    dependencies
+        .get(ticket_id)
+        .map_or(&[], |d| d.children.as_slice())
+}
+
+/// A ticket with the tickets nested under it.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketNode {
+    pub ticket: Ticket,
+    /// Parent ticket ID, even when the parent is not in the tree (e.g. it was
+    /// filtered out and this ticket became a root).
+    pub parent_id: Option<String>,
+    pub stage: RoadmapStage,
+    /// Set on tickets that have children.
+    pub rollup: Option<EpicRollup>,
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub children: Vec<TicketNode>,
+}
+
+/// Nest `tickets` under their parents. Tickets whose parent is not in
+/// `tickets` are roots. Order within each level follows `tickets`.
+/// Rollups cover all descendants in `dependencies`, not just those in
+/// `tickets`.
+pub fn build_ticket_tree(
+    tickets: &[Ticket],
+    dependencies: &HashMap<String, TicketDependencies>,
+    progress: &HashMap<String, TicketProgress>,
+    totals: &HashMap<String, TicketAgentTotals>,
+) -> Vec<TicketNode> {
+    let present: HashSet<&str> = tickets.iter().map(|t| t.id.as_str()).collect();
+    let mut builder = TreeBuilder {
+        dependencies,
+        progress,
+        totals,
+        children_of: HashMap::new(),
+        placed: HashSet::new(),
+    };
+    let mut roots = Vec::new();
+    for ticket in tickets {
+        match builder
+            .parent_id(ticket)
+            .filter(|p| *p != ticket.id && present.contains(p.as_str()))
+        {
+            Some(parent) => builder.children_of.entry(parent).or_default().push(ticket),
+            None => roots.push(ticket),
+        }
+    }
+
+    let mut tree: Vec<TicketNode> = roots.into_iter().filter_map(|t| builder.node(t)).collect();
+    // Tickets in a parent cycle have no root above them; list them at the
+    // top level rather than dropping them.
+    for ticket in tickets {
+        tree.extend(builder.node(ticket));
+    }
+    tree
+}
+
+struct TreeBuilder<'a> {
+    dependencies: &'a HashMap<String, TicketDependencies>,
+    progress: &'a HashMap<String, TicketProgress>,
+    totals: &'a HashMap<String, TicketAgentTotals>,
+    children_of: HashMap<String, Vec<&'a Ticket>>,
+    placed: HashSet<String>,
+}
+
+impl TreeBuilder<'_> {
+    fn parent_id(&self, ticket: &Ticket) -> Option<String> {
+        self.dependencies
+            .get(&ticket.id)
+            .and_then(|d| d.parent.as_ref())
+            .map(|p| p.id.clone())
+    }
+
+    /// `ticket` and its subtree, or `None` if it is already in the tree.
+    fn node(&mut self, ticket: &Ticket) -> Option<TicketNode> {
+        if !self.placed.insert(ticket.id.clone()) {
+            return None;
+        }
+        let kids = self
+            .children_of
+            .get(&ticket.id)
+            .cloned()
+            .unwrap_or_default();
+        let nodes = kids.into_iter().filter_map(|c| self.node(c)).collect();
+        let has_children = !children(&ticket.id, self.dependencies).is_empty();
+        Some(TicketNode {
+            parent_id: self.parent_id(ticket),
+            stage: RoadmapStage::of(ticket, self.progress.get(&ticket.id)),
+            rollup: has_children.then(|| {
+                EpicRollup::compute(&ticket.id, self.dependencies, self.progress, self.totals)
+            }),
+            ticket: ticket.clone(),
+            children: nodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tickets::TicketPrState;
+
+    fn ticket(id: &str, state: &str) -> Ticket {
+        Ticket {
+            id: id.into(),
+            repo_id: "r1".into(),
+            source_type: "jira".into(),
+            source_id: id.into(),
+            title: format!("Ticket {id}"),
+            body: String::new(),
+            state: state.into(),
+            labels: "[]".into(),
+            assignee: None,
+            priority: None,
+            url: String::new(),
+            synced_at: String::new(),
+            raw_json: "{}".into(),
+            workflow: None,
+            agent_map: None,
+            sprint: None,
+            sprint_current: false,
+        }
+    }
+
+    /// Dependencies for `(parent, child)` edges over `tickets`.
+    fn deps(tickets: &[Ticket], edges: &[(&str, &str)]) -> HashMap<String, TicketDependencies> {
+        let by_id = |id: &str| tickets.iter().find(|t| t.id == id).unwrap().clone();
+        let mut deps: HashMap<String, TicketDependencies> = HashMap::new();
+        for (parent, child) in edges {
+            deps.entry(parent.to_string())
+                .or_default()
+                .children
+                .push(by_id(child));
+            deps.entry(child.to_string()).or_default().parent = Some(by_id(parent));
+        }
+        deps
+    }
+
+    fn runs(id: &str, total_runs: i64, total_cost: f64) -> (String, TicketAgentTotals) {
+        let totals = TicketAgentTotals {
+            ticket_id: id.into(),
+            total_runs,
+            total_cost,
+            ..Default::default()
+        };
+        (id.to_string(), totals)
+    }
+
+    #[test]
+    fn rollup_counts_all_descendants_and_their_spend() {
+        let tickets = [
+            ticket("epic", "open"),
+            ticket("a", "closed"),
+            ticket("b", "open"),
+            ticket("b1", "open"),
+        ];
+        let deps = deps(&tickets, &[("epic", "a"), ("epic", "b"), ("b", "b1")]);
+        let progress = HashMap::from([(
+            "b1".to_string(),
+            TicketProgress {
+                worktree_status: None,
+                agent_status: None,
+                pr_state: Some(TicketPrState::Open),
+            },
+        )]);
+        let totals = HashMap::from([runs("epic", 1, 0.5), runs("b1", 2, 1.25)]);
+
+        let rollup = EpicRollup::compute("epic", &deps, &progress, &totals);
+        assert_eq!(rollup.total, 3);
+        assert_eq!(rollup.done, 1);
+        assert_eq!(rollup.in_review, 1);
+        assert_eq!(rollup.in_progress, 0);
+        assert_eq!(rollup.agent_runs, 3);
+        assert!((rollup.agent_cost - 1.75).abs() < f64::EPSILON);
+        assert_eq!(rollup.percent_done(), 33);
+        assert_eq!(EpicRollup::default().percent_done(), 0);
+    }
+
+    #[test]
+    fn rollup_survives_cycles() {
+        let tickets = [ticket("a", "open"), ticket("b", "closed")];
+        let deps = deps(&tickets, &[("a", "b"), ("b", "a")]);
+        let rollup = EpicRollup::compute("a", &deps, &HashMap::new(), &HashMap::new());
+        assert_eq!((rollup.total, rollup.done), (1, 1));
+    }
+
+    #[test]
+    fn tree_nests_children_and_keeps_orphans_as_roots() {
+        let tickets = vec![
+            ticket("child", "open"),
+            ticket("epic", "open"),
+            ticket("grandchild", "closed"),
+            ticket("orphan", "open"),
+        ];
+        let mut deps = deps(&tickets, &[("epic", "child"), ("child", "grandchild")]);
+        // The orphan's parent exists but was filtered out of `tickets`.
+        deps.entry("orphan".into()).or_default().parent = Some(ticket("gone", "open"));
+
+        let tree = build_ticket_tree(&tickets, &deps, &HashMap::new(), &HashMap::new());
+        let roots: Vec<&str> = tree.iter().map(|n| n.ticket.id.as_str()).collect();
+        assert_eq!(roots, ["epic", "orphan"]);
+
+        let epic = &tree[0];
+        assert_eq!(
+            epic.rollup.as_ref().map(|r| (r.total, r.done)),
+            Some((2, 1))
+        );
+        let child = &epic.children[0];
+        assert_eq!(child.ticket.id, "child");
+        assert_eq!(child.parent_id.as_deref(), Some("epic"));
+        assert_eq!(child.children[0].ticket.id, "grandchild");
+        assert_eq!(child.children[0].stage, RoadmapStage::Done);
+        assert!(child.children[0].rollup.is_none());
+
+        assert_eq!(tree[1].parent_id.as_deref(), Some("gone"));
+    }
+
+    #[test]
+    fn tree_lists_parent_cycles_at_the_top_level() {
+        let tickets = vec![ticket("a", "open"), ticket("b", "open")];
+        let deps = deps(&tickets, &[("a", "b"), ("b", "a")]);
+        let tree = build_ticket_tree(&tickets, &deps, &HashMap::new(), &HashMap::new());
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].ticket.id, "a");
+        assert_eq!(tree[0].children[0].ticket.id, "b");
+    }
+}
@@ -0,0 +1,266 @@
+//! Per-source field mapping applied to synced tickets.
+//!
+//! Jira instances keep priority, severity, team and the like in numbered
+//! custom fields that differ from one instance to the next. A source config
+//! can carry a `field_map` of name → [JSON pointer] into the issue payload:
+//!
+//! ```json
+//! "field_map": {
+//!   "priority": "/fields/customfield_10050/value",
+//!   "team": "/fields/customfield_10100/value"
+//! }
+//! ```
+//!
+//! The names `title`, `body`, `priority`, `assignee`, `labels` and `sprint`
+//! replace that [`TicketInput`] field. Any other name is a custom field: its
+//! value is stored in `raw_json` under [`CUSTOM_FIELDS_KEY`] and injected
+//! into workflow runs as `ticket_field_<name>`.
+//!
+//! [JSON pointer]: https://www.rfc-editor.org/rfc/rfc6901
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::TicketInput;
+use crate::error::{ConductorError, Result};
+
+/// Key in a ticket's `raw_json` holding resolved custom field values.
+pub const CUSTOM_FIELDS_KEY: &str = "conductor_fields";
+
+/// Prefix of the workflow variables custom fields are exposed as.
+pub const CUSTOM_FIELD_VAR_PREFIX: &str = "ticket_field_";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FieldMap(pub BTreeMap<String, String>);
+
+impl FieldMap {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Reject pointers that are not JSON pointers and custom field names
+    /// that cannot be used in a `{{ticket_field_<name>}}` variable.
+    pub fn validate(&self) -> Result<()> {
+        for (name, pointer) in &self.0 {
+            if !pointer.starts_with('/') {
+                return Err(ConductorError::InvalidInput(format!(
+                    "field_map.{name}: '{pointer}' is not a JSON pointer (must start with '/')"
+                )));
+            }
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if !valid_name {
+                return Err(ConductorError::InvalidInput(format!(
+                    "field_map: '{name}' must use only lowercase letters, digits and '_'"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Top-level names under `/fields/` the mapping reads, so sources that
+    /// fetch a fixed field list can ask for them too.
+    pub fn source_fields(&self) -> Vec<&str> {
+        let mut fields: Vec<&str> = self
+            .0
+            .values()
+            .filter_map(|p| p.strip_prefix("/fields/"))
+            .map(|rest| rest.split('/').next().unwrap_or(rest))
+            .filter(|f| !f.is_empty())
+            .collect();
+        fields.sort_unstable();
+        fields.dedup();
+        fields
+    }
+
+    /// Apply the mapping to `ticket` using its `raw_json` payload. Pointers
+    /// that resolve to nothing (or `null`) leave the field as the source
+    /// set it.
+    pub fn apply(&self, ticket: &mut TicketInput) {
+        if self.is_empty() {
+            return;
+        }
+        let Some(mut raw) = ticket
+            .raw_json
+            .as_deref()
+            .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        else {
+            return;
+        };
+
+        let mut custom = serde_json::Map::new();
+        for (name, pointer) in &self.0 {
+            let Some(value) = raw.pointer(pointer).filter(|v| !v.is_null()) else {
+                continue;
+            };
+            match name.as_str() {
+                "labels" => ticket.labels = value_to_list(value),
+                other => {
+                    let Some(text) = value_to_string(value) else {
+                        continue;
+                    };
+                    match other {
+                        "title" => ticket.title = text,
+                        "body" => ticket.body = text,
+                        "priority" => ticket.priority = Some(text),
+                        "assignee" => ticket.assignee = Some(text),
+                        "sprint" => ticket.sprint = Some(text),
+                        _ => {
+                            custom.insert(name.clone(), Value::String(text));
+                        }
+                    }
+                }
+            }
+        }
+
+        if !custom.is_empty() {
+            if let Some(obj) = raw.as_object_mut() {
+                obj.insert(CUSTOM_FIELDS_KEY.to_string(), Value::Object(custom));
+                ticket.raw_json = serde_json::to_string(&raw).ok();
+            }
+        }
+    }
+}
+
+/// Custom field values stored in a ticket's `raw_json` by [`FieldMap::apply`].
+pub fn custom_fields(raw_json: &str) -> BTreeMap<String, String> {
+    serde_json::from_str::<Value>(raw_json)
+        .ok()
+        .and_then(|v| v.get(CUSTOM_FIELDS_KEY).cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Flatten a field value to text. Jira option, user and named objects carry
+/// their display text in `value`, `displayName` or `name`.
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Object(obj) => ["value", "displayName", "name"]
+            .iter()
+            .find_map(|k| obj.get(*k).and_then(Value::as_str))
+            .map(str::to_string),
+        Value::Array(items) => {
+            let parts: Vec<String> = items.iter().filter_map(value_to_string).collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        }
+        Value::Null => None,
+    }
+}
+
+fn value_to_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().filter_map(value_to_string).collect(),
+        other => value_to_string(other).into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> FieldMap {
+        FieldMap(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    fn jira_ticket() -> TicketInput {
+        let raw = serde_json::json!({
+            "key": "PROJ-1",
+            "fields": {
+                "summary": "Crash on login",
+                "priority": { "name": "Medium" },
+                "customfield_10050": { "value": "Blocker" },
+                "customfield_10100": { "value": "Payments", "id": "42" },
+                "customfield_10200": [{ "value": "ios" }, { "value": "android" }],
+                "customfield_10300": null
+            }
+        });
+        TicketInput {
+            source_type: "jira".into(),
+            source_id: "PROJ-1".into(),
+            title: "Crash on login".into(),
+            body: String::new(),
+            state: "open".into(),
+            labels: vec!["bug".into()],
+            assignee: None,
+            priority: Some("Medium".into()),
+            url: String::new(),
+            raw_json: Some(raw.to_string()),
+            label_details: vec![],
+            blocked_by: vec![],
+            children: vec![],
+            parent: None,
+            sprint: None,
+            sprint_current: false,
+        }
+    }
+
+    #[test]
+    fn apply_overrides_fields_and_stores_custom_ones() {
+        let mut ticket = jira_ticket();
+        map(&[
+            ("priority", "/fields/customfield_10050"),
+            ("labels", "/fields/customfield_10200"),
+            ("team", "/fields/customfield_10100/value"),
+            ("severity", "/fields/customfield_10300"),
+            ("assignee", "/fields/missing"),
+        ])
+        .apply(&mut ticket);
+
+        assert_eq!(ticket.priority.as_deref(), Some("Blocker"));
+        assert_eq!(ticket.labels, ["ios", "android"]);
+        assert_eq!(ticket.assignee, None, "missing pointer leaves the field");
+
+        let raw = ticket.raw_json.unwrap();
+        let custom = custom_fields(&raw);
+        assert_eq!(custom.get("team").map(String::as_str), Some("Payments"));
+        assert!(!custom.contains_key("severity"), "null values are skipped");
+        // The original payload, unmapped custom fields included, is kept.
+        let value: Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(value["fields"]["customfield_10100"]["id"], "42");
+    }
+
+    #[test]
+    fn empty_map_leaves_ticket_untouched() {
+        let mut ticket = jira_ticket();
+        let before = ticket.raw_json.clone();
+        FieldMap::default().apply(&mut ticket);
+        assert_eq!(ticket.raw_json, before);
+        assert!(custom_fields(before.as_deref().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_bad_pointers_and_names() {
+        assert!(map(&[("team", "/fields/customfield_1/value")])
+            .validate()
+            .is_ok());
+        assert!(map(&[("team", "fields.customfield_1")]).validate().is_err());
+        assert!(map(&[("Team-Name", "/fields/x")]).validate().is_err());
+    }
+
+    #[test]
+    fn source_fields_lists_top_level_field_names() {
+        let m = map(&[
+            ("team", "/fields/customfield_10100/value"),
+            ("priority", "/fields/customfield_10050"),
+            ("other", "/fields/customfield_10100/id"),
+            ("key", "/key"),
+        ]);
+        assert_eq!(
+            m.source_fields(),
+            ["customfield_10050", "customfield_10100"]
+        );
+    }
+}
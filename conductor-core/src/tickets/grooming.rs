@@ -0,0 +1,744 @@
+//! Agent-assisted grooming of newly synced tickets.
+//!
+//! When `[grooming] enabled` is set, [`TicketGrooming::run_pass`] runs after a
+//! sync and asks a cheap model about each open ticket that has not been
+//! groomed yet: which labels it should carry, a rough size, which other open
+//! tickets look like duplicates, and which of the repo's workflows fits it.
+//! The answers are stored as proposals and only change the ticket when
+//! someone accepts them. Runs stop for the day once `daily_budget_usd` has
+//! been spent.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::query::map_ticket_row;
+use super::{Ticket, TicketSyncer};
+use crate::agent::ClaudeJsonResult;
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+/// Other open tickets listed in the prompt as duplicate candidates.
+const MAX_DUPLICATE_CANDIDATES: usize = 200;
+/// Ticket body characters included in the prompt.
+const MAX_BODY_CHARS: usize = 4_000;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Accepted,
+    Dismissed,
+}
+
+impl ProposalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl std::fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ProposalStatus {
+    type Err = ConductorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "accepted" => Ok(Self::Accepted),
+            "dismissed" => Ok(Self::Dismissed),
+            other => Err(ConductorError::InvalidInput(format!(
+                "unknown proposal status '{other}' (expected pending, accepted or dismissed)"
+            ))),
+        }
+    }
+}
+
+/// What the agent suggested for one ticket, as parsed from its reply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProposalDraft {
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    /// `source_id`s of open tickets in the same repo that look like duplicates.
+    #[serde(default)]
+    pub duplicates: Vec<String>,
+    /// Name of the repo workflow that fits the ticket.
+    #[serde(default)]
+    pub workflow: Option<String>,
+    #[serde(default)]
+    pub rationale: Option<String>,
+}
+
+/// A stored grooming proposal joined with its ticket.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroomingProposal {
+    pub id: String,
+    pub ticket_id: String,
+    pub repo_id: String,
+    pub ticket_source_id: String,
+    pub ticket_title: String,
+    pub labels: Vec<String>,
+    pub estimate: Option<String>,
+    pub duplicates: Vec<String>,
+    pub workflow: Option<String>,
+    pub rationale: Option<String>,
+    pub model: Option<String>,
+    pub cost_usd: f64,
+    pub status: ProposalStatus,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+}
+
+/// The reply from one grooming run.
+#[derive(Debug, Clone)]
+pub struct GroomingReply {
+    pub text: String,
+    pub cost_usd: f64,
+}
+
+/// Counts reported by [`TicketGrooming::run_pass`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct GroomingPass {
+    pub groomed: usize,
+    pub failed: usize,
+    pub spent_usd: f64,
+    /// Whether the pass stopped early because the daily budget ran out.
+    pub budget_exhausted: bool,
+}
+
+pub struct TicketGrooming<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> TicketGrooming<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Dollars spent on grooming runs since midnight UTC.
+    pub fn spent_today(&self) -> Result<f64> {
+        let midnight = Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc()
+            .to_rfc3339();
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0) FROM ticket_grooming_proposals \
+             WHERE created_at >= :since",
+            named_params! { ":since": midnight },
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Open tickets that have no proposal yet, most recently synced first.
+    pub fn ungroomed(&self, repo_id: Option<&str>, limit: usize) -> Result<Vec<Ticket>> {
+        query_collect(
+            self.conn,
+            "SELECT t.id, t.repo_id, t.source_type, t.source_id, t.title, t.body, \
+                    t.state, t.labels, t.assignee, t.priority, t.url, t.synced_at, t.raw_json, \
+                    t.workflow, t.agent_map, t.sprint, t.sprint_current \
+             FROM tickets t \
+             WHERE t.state = 'open' \
+               AND (:repo_id IS NULL OR t.repo_id = :repo_id) \
+               AND NOT EXISTS (SELECT 1 FROM ticket_grooming_proposals p WHERE p.ticket_id = t.id) \
+             ORDER BY t.synced_at DESC, t.id DESC LIMIT :limit",
+            named_params! { ":repo_id": repo_id, ":limit": limit as i64 },
+            map_ticket_row,
+        )
+    }
+
+    /// Groom up to `max_per_pass` ungroomed tickets with `runner`, stopping
+    /// once the day's budget is spent. Does nothing unless grooming is enabled.
+    ///
+    /// A ticket whose run fails or whose reply does not parse is skipped and
+    /// tried again on the next pass.
+    pub fn run_pass(
+        &self,
+        config: &Config,
+        repo_id: Option<&str>,
+        mut runner: impl FnMut(&str) -> Result<GroomingReply>,
+    ) -> Result<GroomingPass> {
+        let settings = &config.grooming;
+        let mut pass = GroomingPass::default();
+        if !settings.enabled {
+            return Ok(pass);
+        }
+        let spent_before = self.spent_today()?;
+        let tickets = self.ungroomed(repo_id, settings.max_per_pass as usize)?;
+        let syncer = TicketSyncer::new(self.conn);
+        let repo_mgr = RepoManager::new(self.conn, config);
+
+        for ticket in tickets {
+            // Failed runs are not recorded, so their cost is tracked here.
+            if spent_before + pass.spent_usd >= settings.daily_budget_usd {
+                pass.budget_exhausted = true;
+                break;
+            }
+            let others: Vec<Ticket> = syncer
+                .list(Some(&ticket.repo_id))?
+                .into_iter()
+                .filter(|t| t.state == "open" && t.id != ticket.id)
+                .take(MAX_DUPLICATE_CANDIDATES)
+                .collect();
+            let labels = self.repo_labels(&ticket.repo_id)?;
+            let workflows = repo_mgr
+                .get_by_id(&ticket.repo_id)
+                .ok()
+                .and_then(|r| crate::workflow::list_defs(&r.local_path, &r.local_path).ok())
+                .map(|(defs, _)| defs.into_iter().map(|d| d.name).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let prompt = build_grooming_prompt(&ticket, &others, &labels, &workflows);
+
+            let reply = match runner(&prompt) {
+                Ok(reply) => reply,
+                Err(e) => {
+                    warn!("grooming ticket #{} failed: {e}", ticket.source_id);
+                    pass.failed += 1;
+                    continue;
+                }
+            };
+            pass.spent_usd += reply.cost_usd;
+            match parse_proposal(&reply.text) {
+                Ok(draft) => {
+                    let draft = restrict_to_known(draft, &others, &workflows);
+                    self.record(&ticket.id, &draft, Some(&settings.model), reply.cost_usd)?;
+                    pass.groomed += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "grooming reply for ticket #{} ignored: {e}",
+                        ticket.source_id
+                    );
+                    pass.failed += 1;
+                }
+            }
+        }
+        Ok(pass)
+    }
+
+    /// Store a proposal for `ticket_id`, replacing any earlier one.
+    pub fn record(
+        &self,
+        ticket_id: &str,
+        draft: &ProposalDraft,
+        model: Option<&str>,
+        cost_usd: f64,
+    ) -> Result<GroomingProposal> {
+        let id = crate::new_id();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO ticket_grooming_proposals \
+                 (id, ticket_id, labels, estimate, duplicates, workflow, rationale, model, \
+                  cost_usd, status, created_at) \
+             VALUES (:id, :ticket_id, :labels, :estimate, :duplicates, :workflow, :rationale, \
+                     :model, :cost_usd, 'pending', :created_at)",
+            named_params! {
+                ":id": id,
+                ":ticket_id": ticket_id,
+                ":labels": serde_json::to_string(&draft.labels).unwrap_or_else(|_| "[]".into()),
+                ":estimate": draft.estimate,
+                ":duplicates": serde_json::to_string(&draft.duplicates).unwrap_or_else(|_| "[]".into()),
+                ":workflow": draft.workflow,
+                ":rationale": draft.rationale,
+                ":model": model,
+                ":cost_usd": cost_usd,
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.get(&id)
+    }
+
+    pub fn get(&self, id: &str) -> Result<GroomingProposal> {
+        self.conn
+            .query_row(
+                &format!("{PROPOSAL_SELECT} WHERE p.id = :id"),
+                named_params! { ":id": id },
+                row_to_proposal,
+            )
+            .optional()?
+            .ok_or_else(|| ConductorError::GroomingProposalNotFound { id: id.to_string() })
+    }
+
+    /// Proposals, newest first, optionally limited to one repo and status.
+    pub fn list(
+        &self,
+        repo_id: Option<&str>,
+        status: Option<ProposalStatus>,
+    ) -> Result<Vec<GroomingProposal>> {
+        self.list_visible(&Viewer::Local, repo_id, status)
+    }
+
+    /// Like [`Self::list`], leaving out proposals for tickets of repos that
+    /// are private to someone other than `viewer`.
+    pub fn list_visible(
+        &self,
+        viewer: &Viewer,
+        repo_id: Option<&str>,
+        status: Option<ProposalStatus>,
+    ) -> Result<Vec<GroomingProposal>> {
+        let status = status.map(ProposalStatus::as_str);
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> =
+            vec![(":repo_id", &repo_id), (":status", &status)];
+        let condition = visible_repo_sql(viewer, "t.repo_id", ":viewer");
+        if condition.is_some() {
+            params.push((":viewer", &owner));
+        }
+        let visible = condition.map(|c| format!(" AND {c}")).unwrap_or_default();
+        query_collect(
+            self.conn,
+            &format!(
+                "{PROPOSAL_SELECT} \
+                 WHERE (:repo_id IS NULL OR t.repo_id = :repo_id) \
+                   AND (:status IS NULL OR p.status = :status){visible} \
+                 ORDER BY p.created_at DESC, p.id DESC"
+            ),
+            params.as_slice(),
+            row_to_proposal,
+        )
+    }
+
+    /// Apply a pending proposal: add its labels to the ticket and set the
+    /// ticket's workflow when one was proposed. The estimate and duplicates
+    /// stay on the accepted proposal for reference.
+    pub fn accept(&self, id: &str) -> Result<GroomingProposal> {
+        let proposal = self.pending(id)?;
+        let tx = self.conn.unchecked_transaction()?;
+        apply_labels(&tx, &proposal.ticket_id, &proposal.labels)?;
+        if let Some(workflow) = &proposal.workflow {
+            tx.execute(
+                "UPDATE tickets SET workflow = :workflow WHERE id = :id",
+                named_params! { ":workflow": workflow, ":id": proposal.ticket_id },
+            )?;
+        }
+        self.decide(&tx, id, ProposalStatus::Accepted)?;
+        tx.commit()?;
+        self.get(id)
+    }
+
+    /// Mark a pending proposal as dismissed without changing the ticket.
+    pub fn dismiss(&self, id: &str) -> Result<GroomingProposal> {
+        self.pending(id)?;
+        self.decide(self.conn, id, ProposalStatus::Dismissed)?;
+        self.get(id)
+    }
+
+    fn pending(&self, id: &str) -> Result<GroomingProposal> {
+        let proposal = self.get(id)?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(ConductorError::InvalidInput(format!(
+                "grooming proposal {id} is already {}",
+                proposal.status
+            )));
+        }
+        Ok(proposal)
+    }
+
+    fn decide(&self, conn: &Connection, id: &str, status: ProposalStatus) -> Result<()> {
+        conn.execute(
+            "UPDATE ticket_grooming_proposals SET status = :status, decided_at = :now \
+             WHERE id = :id",
+            named_params! {
+                ":status": status.as_str(),
+                ":now": Utc::now().to_rfc3339(),
+                ":id": id,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Distinct labels already used by the repo's tickets.
+    fn repo_labels(&self, repo_id: &str) -> Result<Vec<String>> {
+        query_collect(
+            self.conn,
+            "SELECT DISTINCT tl.label FROM ticket_labels tl \
+             JOIN tickets t ON t.id = tl.ticket_id \
+             WHERE t.repo_id = :repo_id ORDER BY tl.label",
+            named_params! { ":repo_id": repo_id },
+            |row| row.get(0),
+        )
+    }
+}
+
+/// Re-add labels from accepted proposals after a sync has replaced a
+/// ticket's labels with the source's.
+pub(super) fn reapply_accepted_labels(conn: &Connection, ticket_id: &str) -> Result<()> {
+    let accepted: Option<String> = conn
+        .query_row(
+            "SELECT labels FROM ticket_grooming_proposals \
+             WHERE ticket_id = :ticket_id AND status = 'accepted'",
+            named_params! { ":ticket_id": ticket_id },
+            |row| row.get(0),
+        )
+        .optional()?;
+    let labels: Vec<String> = accepted
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    apply_labels(conn, ticket_id, &labels)
+}
+
+/// Add `labels` to a ticket's label JSON and `ticket_labels` rows, keeping
+/// the labels it already has.
+fn apply_labels(conn: &Connection, ticket_id: &str, labels: &[String]) -> Result<()> {
+    if labels.is_empty() {
+        return Ok(());
+    }
+    let current: String = conn.query_row(
+        "SELECT labels FROM tickets WHERE id = :id",
+        named_params! { ":id": ticket_id },
+        |row| row.get(0),
+    )?;
+    let mut merged: Vec<String> = serde_json::from_str(&current).unwrap_or_default();
+    for label in labels {
+        if !merged.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            merged.push(label.clone());
+        }
+        conn.execute(
+            "INSERT OR IGNORE INTO ticket_labels (ticket_id, label, color) \
+             VALUES (:ticket_id, :label, NULL)",
+            named_params! { ":ticket_id": ticket_id, ":label": label },
+        )?;
+    }
+    conn.execute(
+        "UPDATE tickets SET labels = :labels WHERE id = :id",
+        named_params! {
+            ":labels": serde_json::to_string(&merged).unwrap_or_else(|_| "[]".into()),
+            ":id": ticket_id,
+        },
+    )?;
+    Ok(())
+}
+
+const PROPOSAL_SELECT: &str = "SELECT p.id, p.ticket_id, t.repo_id, t.source_id, t.title, \
+            p.labels, p.estimate, p.duplicates, p.workflow, p.rationale, p.model, p.cost_usd, \
+            p.status, p.created_at, p.decided_at \
+     FROM ticket_grooming_proposals p \
+     JOIN tickets t ON t.id = p.ticket_id";
+
+fn row_to_proposal(row: &rusqlite::Row<'_>) -> rusqlite::Result<GroomingProposal> {
+    let json_list = |col: &str| -> rusqlite::Result<Vec<String>> {
+        let raw: String = row.get(col)?;
+        Ok(serde_json::from_str(&raw).unwrap_or_default())
+    };
+    let status: String = row.get("status")?;
+    Ok(GroomingProposal {
+        id: row.get("id")?,
+        ticket_id: row.get("ticket_id")?,
+        repo_id: row.get("repo_id")?,
+        ticket_source_id: row.get("source_id")?,
+        ticket_title: row.get("title")?,
+        labels: json_list("labels")?,
+        estimate: row.get("estimate")?,
+        duplicates: json_list("duplicates")?,
+        workflow: row.get("workflow")?,
+        rationale: row.get("rationale")?,
+        model: row.get("model")?,
+        cost_usd: row.get("cost_usd")?,
+        status: status.parse().unwrap_or(ProposalStatus::Pending),
+        created_at: row.get("created_at")?,
+        decided_at: row.get("decided_at")?,
+    })
+}
+
+/// The grooming instructions, the ticket, and what the agent may choose from.
+pub fn build_grooming_prompt(
+    ticket: &Ticket,
+    others: &[Ticket],
+    labels: &[String],
+    workflows: &[String],
+) -> String {
+    let body: String = ticket.body.chars().take(MAX_BODY_CHARS).collect();
+    let mut prompt = format!(
+        "You are grooming a newly synced ticket. Do not use any tools. Reply with one JSON \
+         object and nothing else, with these keys:\n\
+         - \"labels\": labels the ticket should have, preferring existing ones\n\
+         - \"estimate\": one of \"XS\", \"S\", \"M\", \"L\", \"XL\"\n\
+         - \"duplicates\": ids of the open tickets below that describe the same work\n\
+         - \"workflow\": the name of the workflow below that fits best, or null\n\
+         - \"rationale\": one sentence explaining the suggestions\n\n\
+         Ticket #{}: {}\nCurrent labels: {}\n\n{}\n",
+        ticket.source_id,
+        ticket.title,
+        ticket.label_names().join(", "),
+        body.trim(),
+    );
+    if !labels.is_empty() {
+        prompt.push_str(&format!(
+            "\nLabels used in this repo: {}\n",
+            labels.join(", ")
+        ));
+    }
+    if !workflows.is_empty() {
+        prompt.push_str(&format!("\nWorkflows: {}\n", workflows.join(", ")));
+    }
+    if !others.is_empty() {
+        prompt.push_str("\nOther open tickets:\n");
+        for t in others {
+            prompt.push_str(&format!("- #{}: {}\n", t.source_id, t.title));
+        }
+    }
+    prompt
+}
+
+/// Parse the JSON object in an agent reply, tolerating prose or a code fence
+/// around it.
+pub fn parse_proposal(text: &str) -> Result<ProposalDraft> {
+    let start = text.find('{');
+    let end = text.rfind('}');
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ConductorError::InvalidInput(
+            "grooming reply has no JSON object".to_string(),
+        ));
+    };
+    if end < start {
+        return Err(ConductorError::InvalidInput(
+            "grooming reply has no JSON object".to_string(),
+        ));
+    }
+    serde_json::from_str(&text[start..=end])
+        .map_err(|e| ConductorError::InvalidInput(format!("grooming reply is not valid: {e}")))
+}
+
+/// Drop duplicate ids and workflow names the agent made up.
+fn restrict_to_known(
+    mut draft: ProposalDraft,
+    others: &[Ticket],
+    workflows: &[String],
+) -> ProposalDraft {
+    let ids: HashSet<&str> = others.iter().map(|t| t.source_id.as_str()).collect();
+    draft
+        .duplicates
+        .retain(|d| ids.contains(d.trim_start_matches('#')));
+    for d in &mut draft.duplicates {
+        *d = d.trim_start_matches('#').to_string();
+    }
+    if draft
+        .workflow
+        .as_ref()
+        .is_some_and(|w| !workflows.contains(w))
+    {
+        draft.workflow = None;
+    }
+    draft.labels.retain(|l| !l.trim().is_empty());
+    draft
+}
+
+/// Run one grooming prompt through `claude -p` with the configured model.
+pub fn run_grooming_agent(
+    model: &str,
+    prompt: &str,
+    working_dir: Option<&Path>,
+) -> Result<GroomingReply> {
+    let mut cmd = Command::new("claude");
+    cmd.arg("-p").arg(prompt).args([
+        "--output-format",
+        "json",
+        "--max-turns",
+        "1",
+        "--model",
+        model,
+    ]);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    let output = crate::command::output(&mut cmd)
+        .map_err(|e| ConductorError::Agent(format!("failed to run claude: {e}")))?;
+    if !output.status.success() {
+        return Err(ConductorError::Agent(format!(
+            "claude exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let result: ClaudeJsonResult = serde_json::from_slice(&output.stdout)
+        .map_err(|e| ConductorError::Agent(format!("unexpected claude output: {e}")))?;
+    if result.is_error == Some(true) {
+        return Err(ConductorError::Agent(
+            result
+                .result
+                .unwrap_or_else(|| "claude reported an error".to_string()),
+        ));
+    }
+    Ok(GroomingReply {
+        text: result.result.unwrap_or_default(),
+        cost_usd: result.cost_usd.unwrap_or(0.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{make_ticket, setup_db};
+
+    fn enabled_config(budget: f64) -> Config {
+        let mut config = Config::default();
+        config.grooming.enabled = true;
+        config.grooming.daily_budget_usd = budget;
+        config
+    }
+
+    fn seed(conn: &Connection) {
+        TicketSyncer::new(conn)
+            .upsert_tickets(
+                "r1",
+                &[
+                    make_ticket("1", "Login fails on Safari"),
+                    make_ticket("2", "Safari login broken"),
+                ],
+            )
+            .unwrap();
+    }
+
+    fn reply(text: &str, cost_usd: f64) -> Result<GroomingReply> {
+        Ok(GroomingReply {
+            text: text.to_string(),
+            cost_usd,
+        })
+    }
+
+    #[test]
+    fn parse_proposal_accepts_fenced_json() {
+        let draft = parse_proposal(
+            "Here you go:\n```json\n{\"labels\": [\"bug\"], \"estimate\": \"S\", \"duplicates\": [\"2\"]}\n```",
+        )
+        .unwrap();
+        assert_eq!(draft.labels, vec!["bug"]);
+        assert_eq!(draft.estimate.as_deref(), Some("S"));
+        assert_eq!(draft.duplicates, vec!["2"]);
+        assert!(parse_proposal("no json here").is_err());
+    }
+
+    #[test]
+    fn run_pass_is_a_no_op_when_disabled() {
+        let conn = setup_db();
+        seed(&conn);
+        let pass = TicketGrooming::new(&conn)
+            .run_pass(&Config::default(), None, |_| {
+                panic!("runner must not be called")
+            })
+            .unwrap();
+        assert_eq!(pass, GroomingPass::default());
+    }
+
+    #[test]
+    fn run_pass_records_proposals_once_per_ticket() {
+        let conn = setup_db();
+        seed(&conn);
+        let grooming = TicketGrooming::new(&conn);
+        let config = enabled_config(1.0);
+        let pass = grooming
+            .run_pass(&config, None, |_| {
+                reply(
+                    r##"{"labels": ["bug"], "duplicates": ["#1", "#2", "99"], "workflow": "made-up"}"##,
+                    0.01,
+                )
+            })
+            .unwrap();
+        assert_eq!(pass.groomed, 2);
+
+        let proposals = grooming
+            .list(Some("r1"), Some(ProposalStatus::Pending))
+            .unwrap();
+        assert_eq!(proposals.len(), 2);
+        for p in &proposals {
+            // A ticket is never its own duplicate, unknown ids are dropped,
+            // and so is a workflow the repo does not have.
+            assert_eq!(p.duplicates.len(), 1);
+            assert_ne!(p.duplicates[0], p.ticket_source_id);
+            assert_eq!(p.workflow, None);
+        }
+
+        let again = grooming
+            .run_pass(&config, None, |_| panic!("already groomed"))
+            .unwrap();
+        assert_eq!(again.groomed, 0);
+    }
+
+    #[test]
+    fn run_pass_stops_at_the_daily_budget() {
+        let conn = setup_db();
+        seed(&conn);
+        let grooming = TicketGrooming::new(&conn);
+        let pass = grooming
+            .run_pass(&enabled_config(0.05), None, |_| reply("{}", 0.05))
+            .unwrap();
+        assert_eq!(pass.groomed, 1);
+        assert!(pass.budget_exhausted);
+        assert!((grooming.spent_today().unwrap() - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn accept_adds_labels_that_survive_a_sync() {
+        let conn = setup_db();
+        seed(&conn);
+        let grooming = TicketGrooming::new(&conn);
+        let ticket = TicketSyncer::new(&conn)
+            .get_by_source_id("r1", "1")
+            .unwrap();
+        let draft = ProposalDraft {
+            labels: vec!["bug".to_string()],
+            ..Default::default()
+        };
+        let proposal = grooming.record(&ticket.id, &draft, None, 0.0).unwrap();
+
+        let accepted = grooming.accept(&proposal.id).unwrap();
+        assert_eq!(accepted.status, ProposalStatus::Accepted);
+        assert!(grooming.accept(&proposal.id).is_err());
+
+        seed(&conn);
+        let syncer = TicketSyncer::new(&conn);
+        let ticket = syncer.get_by_source_id("r1", "1").unwrap();
+        assert_eq!(ticket.label_names(), vec!["bug"]);
+        let labels: Vec<String> = syncer
+            .get_labels(&ticket.id)
+            .unwrap()
+            .into_iter()
+            .map(|l| l.label)
+            .collect();
+        assert_eq!(labels, vec!["bug"]);
+    }
+
+    #[test]
+    fn dismiss_leaves_the_ticket_alone() {
+        let conn = setup_db();
+        seed(&conn);
+        let grooming = TicketGrooming::new(&conn);
+        let ticket = TicketSyncer::new(&conn)
+            .get_by_source_id("r1", "1")
+            .unwrap();
+        let draft = ProposalDraft {
+            labels: vec!["bug".to_string()],
+            ..Default::default()
+        };
+        let proposal = grooming.record(&ticket.id, &draft, None, 0.0).unwrap();
+        assert_eq!(
+            grooming.dismiss(&proposal.id).unwrap().status,
+            ProposalStatus::Dismissed
+        );
+        let ticket = TicketSyncer::new(&conn)
+            .get_by_source_id("r1", "1")
+            .unwrap();
+        assert!(ticket.label_names().is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+//! Bulk ticket import from CSV or JSON files.
+//!
+//! Each CSV row, or each object of a JSON array, becomes one [`TicketInput`].
+//! Columns are matched to ticket fields by header name, ignoring case, with a
+//! few common spreadsheet aliases (`summary` for `title`, `status` for
+//! `state`, …). A [`ColumnMap`] overrides the match for any field.
+//!
+//! Rows without an `id` column are keyed by a short hash of their title, so
+//! importing the same sheet twice updates its tickets instead of duplicating
+//! them.
+//!
+//! [`parse_json_tickets`] applies the same rules to tickets pushed to the
+//! webhook ingest endpoint.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::TicketInput;
+use crate::error::{ConductorError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// Infer the format from a file extension (`.csv` or `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ImportFormat {
+    type Err = ConductorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(ConductorError::InvalidInput(format!(
+                "unknown import format '{other}' (expected csv or json)"
+            ))),
+        }
+    }
+}
+
+/// Importable ticket fields and the column names matched to each by default.
+/// Names are compared after [`normalize_column`].
+const FIELDS: &[(&str, &[&str])] = &[
+    ("id", &["id", "source_id", "key", "number"]),
+    ("title", &["title", "summary", "name"]),
+    ("body", &["body", "description", "details", "notes"]),
+    ("state", &["state", "status"]),
+    ("labels", &["labels", "tags"]),
+    ("assignee", &["assignee", "owner"]),
+    ("priority", &["priority"]),
+    ("url", &["url", "link"]),
+    ("parent", &["parent"]),
+    ("blocked_by", &["blocked_by", "depends_on"]),
+    ("sprint", &["sprint", "milestone", "iteration"]),
+];
+
+/// Explicit `field → column` overrides, parsed from `field=Column` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMap(BTreeMap<String, String>);
+
+impl ColumnMap {
+    /// Parse `field=Column` pairs (e.g. `title=Task Name`), rejecting fields
+    /// that cannot be imported.
+    pub fn parse(pairs: &[String]) -> Result<Self> {
+        let mut map = BTreeMap::new();
+        for pair in pairs {
+            let (field, column) = pair.split_once('=').ok_or_else(|| {
+                ConductorError::InvalidInput(format!(
+                    "invalid column mapping '{pair}' (expected field=Column)"
+                ))
+            })?;
+            let field = field.trim().to_ascii_lowercase();
+            if !FIELDS.iter().any(|(name, _)| *name == field) {
+                let known: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+                return Err(ConductorError::InvalidInput(format!(
+                    "unknown ticket field '{field}' in column mapping (expected one of: {})",
+                    known.join(", ")
+                )));
+            }
+            map.insert(field, column.trim().to_string());
+        }
+        Ok(Self(map))
+    }
+}
+
+/// One input record: original column name → cell value.
+type Record = BTreeMap<String, String>;
+
+/// Parse `content` into tickets of `source_type`.
+///
+/// Fails on the first invalid row, naming it, so a bad sheet imports nothing.
+pub fn parse_tickets(
+    content: &str,
+    format: ImportFormat,
+    source_type: &str,
+    columns: &ColumnMap,
+) -> Result<Vec<TicketInput>> {
+    // Spreadsheet exports often start with a byte-order mark.
+    let content = content.trim_start_matches('\u{feff}');
+    let records = match format {
+        ImportFormat::Csv => read_csv(content)?,
+        ImportFormat::Json => read_json(content)?,
+    };
+
+    let present: HashSet<String> = records
+        .iter()
+        .flat_map(|r| r.keys().map(|k| normalize_column(k)))
+        .collect();
+    for (field, column) in &columns.0 {
+        if !present.contains(&normalize_column(column)) {
+            return Err(ConductorError::InvalidInput(format!(
+                "column '{column}' (mapped to {field}) not found in the file"
+            )));
+        }
+    }
+
+    records_to_tickets(&records, source_type, columns)
+}
+
+/// Parse a JSON body pushed by an external system: one ticket object or an
+/// array of them, with the same field names and aliases as a JSON file import.
+pub fn parse_json_tickets(body: Value, source_type: &str) -> Result<Vec<TicketInput>> {
+    let items = match body {
+        Value::Array(items) => items,
+        object @ Value::Object(_) => vec![object],
+        _ => {
+            return Err(ConductorError::InvalidInput(
+                "expected a ticket object or an array of them".to_string(),
+            ))
+        }
+    };
+    let items = items
+        .into_iter()
+        .map(|item| match item {
+            Value::Object(map) => Ok(map),
+            _ => Err(ConductorError::InvalidInput(
+                "expected every ticket to be a JSON object".to_string(),
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    records_to_tickets(&json_records(items), source_type, &ColumnMap::default())
+}
+
+/// Convert every record, failing on the first invalid or duplicate one.
+fn records_to_tickets(
+    records: &[Record],
+    source_type: &str,
+    columns: &ColumnMap,
+) -> Result<Vec<TicketInput>> {
+    let mut seen = HashSet::new();
+    let mut tickets = Vec::with_capacity(records.len());
+    for (i, record) in records.iter().enumerate() {
+        let row = i + 1;
+        let ticket = record_to_ticket(record, source_type, columns)
+            .map_err(|e| ConductorError::InvalidInput(format!("row {row}: {e}")))?;
+        if !seen.insert(ticket.source_id.clone()) {
+            return Err(ConductorError::InvalidInput(format!(
+                "row {row}: duplicate ticket id '{}'",
+                ticket.source_id
+            )));
+        }
+        tickets.push(ticket);
+    }
+    Ok(tickets)
+}
+
+fn read_csv(content: &str) -> Result<Vec<Record>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| ConductorError::InvalidInput(format!("invalid CSV: {e}")))?
+        .clone();
+    reader
+        .records()
+        .map(|record| {
+            let record =
+                record.map_err(|e| ConductorError::InvalidInput(format!("invalid CSV: {e}")))?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(h, v)| (h.to_string(), v.to_string()))
+                .collect())
+        })
+        .collect()
+}
+
+fn read_json(content: &str) -> Result<Vec<Record>> {
+    let items: Vec<serde_json::Map<String, Value>> =
+        serde_json::from_str(content).map_err(|e| {
+            ConductorError::InvalidInput(format!("expected a JSON array of objects: {e}"))
+        })?;
+    Ok(json_records(items))
+}
+
+fn json_records(items: Vec<serde_json::Map<String, Value>>) -> Vec<Record> {
+    items
+        .into_iter()
+        .map(|item| {
+            item.into_iter()
+                .filter_map(|(key, value)| json_cell(value).map(|v| (key, v)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Flatten a JSON value to the string a CSV cell would hold; arrays become
+/// comma-separated lists.
+fn json_cell(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s),
+        Value::Array(items) => Some(
+            items
+                .into_iter()
+                .filter_map(json_cell)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Lowercase and treat spaces and hyphens as underscores, so `Blocked By`
+/// matches `blocked_by`.
+fn normalize_column(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+fn record_to_ticket(
+    record: &Record,
+    source_type: &str,
+    columns: &ColumnMap,
+) -> std::result::Result<TicketInput, String> {
+    let cell = |column: &str| -> Option<&str> {
+        let column = normalize_column(column);
+        record
+            .iter()
+            .find(|(k, _)| normalize_column(k) == column)
+            .map(|(_, v)| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+    let get = |field: &str| -> Option<&str> {
+        match columns.0.get(field) {
+            Some(column) => cell(column),
+            None => FIELDS
+                .iter()
+                .find(|(name, _)| *name == field)
+                .and_then(|(_, aliases)| aliases.iter().find_map(|a| cell(a))),
+        }
+    };
+
+    let title = get("title").ok_or("missing title")?;
+    let state = match get("state") {
+        Some(s) => normalize_state(s)
+            .ok_or_else(|| format!("unrecognised state '{s}' (use open, in progress or closed)"))?,
+        None => "open",
+    };
+
+    Ok(TicketInput {
+        source_type: source_type.to_string(),
+        source_id: get("id").map_or_else(|| title_key(title), String::from),
+        title: title.to_string(),
+        body: get("body").unwrap_or_default().to_string(),
+        state: state.to_string(),
+        labels: split_list(get("labels")),
+        label_details: vec![],
+        assignee: get("assignee").map(String::from),
+        priority: get("priority").map(String::from),
+        url: get("url").unwrap_or_default().to_string(),
+        raw_json: serde_json::to_string(record).ok(),
+        blocked_by: split_list(get("blocked_by")),
+        children: vec![],
+        parent: get("parent").map(String::from),
+        sprint: get("sprint").map(String::from),
+        sprint_current: false,
+    })
+}
+
+/// Map the status vocabularies spreadsheets and trackers use onto conductor's
+/// three ticket states.
+fn normalize_state(state: &str) -> Option<&'static str> {
+    match state.to_lowercase().replace(['_', '-'], " ").trim() {
+        "open" | "todo" | "to do" | "new" | "backlog" => Some("open"),
+        "in progress" | "doing" | "started" | "wip" => Some("in_progress"),
+        "closed" | "done" | "complete" | "completed" | "resolved" => Some("closed"),
+        _ => None,
+    }
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Stable id for a row without one: the first 8 hex digits of the title's
+/// SHA-256.
+fn title_key(title: &str) -> String {
+    hex::encode(Sha256::digest(title.as_bytes()))[..8].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_csv(content: &str) -> Result<Vec<TicketInput>> {
+        parse_tickets(content, ImportFormat::Csv, "manual", &ColumnMap::default())
+    }
+
+    fn parse_csv_err(content: &str) -> String {
+        parse_csv(content)
+            .err()
+            .expect("expected an import error")
+            .to_string()
+    }
+
+    #[test]
+    fn csv_maps_aliased_columns() {
+        let tickets = parse_csv(
+            "Key,Summary,Description,Status,Tags,Owner,Blocked By\n\
+             T-1,Set up CI,\"Runs tests, lints\",Done,\"infra; ci\",sam,\n\
+             T-2,Ship it,,In Progress,,,T-1\n",
+        )
+        .unwrap();
+        assert_eq!(tickets.len(), 2);
+        let first = &tickets[0];
+        assert_eq!(first.source_type, "manual");
+        assert_eq!(first.source_id, "T-1");
+        assert_eq!(first.title, "Set up CI");
+        assert_eq!(first.body, "Runs tests, lints");
+        assert_eq!(first.state, "closed");
+        assert_eq!(first.labels, ["infra", "ci"]);
+        assert_eq!(first.assignee.as_deref(), Some("sam"));
+        assert_eq!(tickets[1].state, "in_progress");
+        assert_eq!(tickets[1].blocked_by, ["T-1"]);
+        assert!(tickets[1].assignee.is_none());
+    }
+
+    #[test]
+    fn rows_without_id_are_keyed_by_title() {
+        let a = parse_csv("title\nWrite docs\n").unwrap();
+        let b = parse_csv("title,state\nWrite docs,open\n").unwrap();
+        assert_eq!(a[0].source_id.len(), 8);
+        assert_eq!(a[0].source_id, b[0].source_id);
+        assert_eq!(a[0].state, "open");
+    }
+
+    #[test]
+    fn column_map_overrides_matching() {
+        let columns = ColumnMap::parse(&["title=Task Name".into(), "id=Ref".into()]).unwrap();
+        let tickets = parse_tickets(
+            "Ref,Task Name,Name\n7,Real title,Someone\n",
+            ImportFormat::Csv,
+            "manual",
+            &columns,
+        )
+        .unwrap();
+        assert_eq!(tickets[0].source_id, "7");
+        assert_eq!(tickets[0].title, "Real title");
+
+        assert!(ColumnMap::parse(&["owner=Who".into()]).is_err());
+        let err = parse_tickets("Ref,title\n1,x\n", ImportFormat::Csv, "manual", &columns)
+            .err()
+            .expect("mapped column is missing");
+        assert!(
+            err.to_string()
+                .contains("column 'Task Name' (mapped to title) not found"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn json_arrays_become_lists() {
+        let tickets = parse_tickets(
+            r#"[{"id": 12, "title": "Fix login", "labels": ["bug", "auth"], "priority": null}]"#,
+            ImportFormat::Json,
+            "manual",
+            &ColumnMap::default(),
+        )
+        .unwrap();
+        assert_eq!(tickets[0].source_id, "12");
+        assert_eq!(tickets[0].labels, ["bug", "auth"]);
+        assert!(tickets[0].priority.is_none());
+    }
+
+    #[test]
+    fn invalid_rows_name_the_row() {
+        let err = parse_csv_err("id,title\n1,ok\n2,\n");
+        assert!(err.contains("row 2: missing title"), "{err}");
+        let err = parse_csv_err("id,title,status\n1,ok,blocked\n");
+        assert!(err.contains("unrecognised state 'blocked'"), "{err}");
+        let err = parse_csv_err("id,title\n1,a\n1,b\n");
+        assert!(err.contains("duplicate ticket id '1'"), "{err}");
+    }
+
+    #[test]
+    fn pushed_json_accepts_one_ticket_or_many() {
+        let one = parse_json_tickets(
+            serde_json::json!({"source_id": "T-1", "title": "Ship it", "state": "done"}),
+            "webhook",
+        )
+        .unwrap();
+        assert_eq!(one.len(), 1);
+        assert_eq!(
+            (one[0].source_type.as_str(), one[0].source_id.as_str()),
+            ("webhook", "T-1")
+        );
+        assert_eq!(one[0].state, "closed");
+
+        let many = parse_json_tickets(
+            serde_json::json!([{"id": 1, "title": "a"}, {"id": 2, "title": "b"}]),
+            "webhook",
+        )
+        .unwrap();
+        assert_eq!(many.len(), 2);
+
+        let err = parse_json_tickets(serde_json::json!([{"id": 1, "title": "a"}, 3]), "webhook")
+            .err()
+            .expect("expected an error")
+            .to_string();
+        assert!(err.contains("JSON object"), "{err}");
+        let err = parse_json_tickets(serde_json::json!({"id": 1}), "webhook")
+            .err()
+            .expect("expected an error")
+            .to_string();
+        assert!(err.contains("row 1: missing title"), "{err}");
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(
+            ImportFormat::from_path(Path::new("tasks.CSV")),
+            Some(ImportFormat::Csv)
+        );
+        assert_eq!(
+            ImportFormat::from_path(Path::new("tasks.json")),
+            Some(ImportFormat::Json)
+        );
+        assert_eq!(ImportFormat::from_path(Path::new("tasks.xlsx")), None);
+    }
+}
@@ -1,12 +1,41 @@
+pub mod attachments;
+mod changes;
+pub mod epics;
+pub mod field_map;
+pub mod grooming;
+pub mod import;
 mod query;
+pub mod queue;
+pub mod roadmap;
+pub mod search;
 mod syncer;
+mod write_back;
 
+pub use attachments::{TicketAttachment, TicketAttachments};
+pub use epics::{build_ticket_tree, EpicRollup, TicketNode};
+pub use field_map::FieldMap;
+pub use grooming::{GroomingPass, GroomingProposal, ProposalStatus, TicketGrooming};
+pub use queue::{queue_reasons, require_identity, QueueReason, QueueScope, QueueTicket};
+pub use roadmap::{build_roadmap, RoadmapGroup, RoadmapItem, RoadmapStage};
 pub use syncer::TicketSyncer;
+pub use write_back::{post_agent_results, post_run_summary};
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{ConductorError, Result};
 
+/// `source_type` for GitHub pull requests synced as tickets (see
+/// [`crate::github::sync_github_prs`]). `source_id` is the PR number.
+pub const GITHUB_PR_SOURCE_TYPE: &str = "github_pr";
+
+/// `source_type` for tickets created by hand or imported from a file (see
+/// [`import`]). No sync owns them, so they are never closed automatically.
+pub const MANUAL_SOURCE_TYPE: &str = "manual";
+
+/// `source_type` for tickets pushed to `POST /api/repos/{id}/tickets/ingest`
+/// by an external system (see [`import::parse_json_tickets`]).
+pub const WEBHOOK_SOURCE_TYPE: &str = "webhook";
+
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ticket {
@@ -25,6 +54,26 @@ pub struct Ticket {
     pub raw_json: String,
     pub workflow: Option<String>,
     pub agent_map: Option<String>,
+    /// Sprint, iteration, or milestone name from the source, if any.
+    pub sprint: Option<String>,
+    /// Whether `sprint` is the source's currently active sprint.
+    pub sprint_current: bool,
+}
+
+impl Ticket {
+    /// PR number when this ticket is a synced GitHub pull request.
+    pub fn pr_number(&self) -> Option<u32> {
+        if self.source_type == GITHUB_PR_SOURCE_TYPE {
+            self.source_id.parse().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Label names from the stored JSON array; empty if it does not parse.
+    pub fn label_names(&self) -> Vec<String> {
+        serde_json::from_str(&self.labels).unwrap_or_default()
+    }
 }
 
 /// A normalized ticket from any source, ready to be upserted into the database.
@@ -52,6 +101,11 @@ pub struct TicketInput {
     /// Resolved and written to ticket_dependencies during upsert.
     /// Setting this replaces any existing parent relationship for this ticket.
     pub parent: Option<String>,
+    /// Sprint, iteration, or milestone name (Jira sprint, GitHub milestone).
+    pub sprint: Option<String>,
+    /// Whether `sprint` is the currently active one. Sources decide what
+    /// "current" means; see each sync function.
+    pub sprint_current: bool,
 }
 
 pub(super) const VALID_TICKET_STATES: &[&str] = &["open", "in_progress", "closed"];
@@ -114,6 +168,32 @@ impl TicketDependencies {
     }
 }
 
+/// State of the pull request associated with a ticket.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TicketPrState {
+    Draft,
+    Open,
+    Merged,
+    Closed,
+}
+
+/// How far work on a ticket has got, computed from its linked worktrees,
+/// their agent runs, and any synced pull request.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketProgress {
+    /// Status of the most recently created linked worktree.
+    pub worktree_status: Option<crate::worktree::WorktreeStatus>,
+    /// Status of the latest agent run in any linked worktree.
+    pub agent_status: Option<crate::agent::AgentRunStatus>,
+    /// The ticket's own PR when it is a synced pull request, otherwise the
+    /// synced PR opened from a linked worktree's branch. A merged worktree
+    /// without a synced PR counts as merged.
+    pub pr_state: Option<TicketPrState>,
+}
+
 /// A ticket that is ready to be worked on: not closed, has no unresolved blockers,
 /// and is not already linked to an active workflow run.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +222,8 @@ pub struct TicketFilter {
     pub include_closed: bool,
     /// When `true`, only include tickets with no entries in `ticket_labels`.
     pub unlabeled_only: bool,
+    /// When `true`, only include tickets in the current sprint (`sprint_current`).
+    pub current_sprint_only: bool,
 }
 
 impl Ticket {
@@ -162,8 +244,9 @@ pub(super) fn ticket_not_found(
     }
 }
 
-/// Build a rich agent prompt from a ticket's context.
-pub fn build_agent_prompt(ticket: &Ticket) -> String {
+/// Build a rich agent prompt from a ticket's context. Attachments are listed
+/// at the end, pointing at downloaded copies where available.
+pub fn build_agent_prompt(ticket: &Ticket, attachments: &[TicketAttachment]) -> String {
     let labels_display = if ticket.labels.is_empty() || ticket.labels == "[]" {
         "None".to_string()
     } else {
@@ -176,7 +259,7 @@ pub fn build_agent_prompt(ticket: &Ticket) -> String {
         ticket.body.clone()
     };
 
-    format!(
+    let prompt = format!(
         "Work on the following GitHub issue in this repository.\n\
          \n\
          Issue: #{source_id} — {title}\n\
@@ -192,7 +275,8 @@ pub fn build_agent_prompt(ticket: &Ticket) -> String {
         state = ticket.state,
         labels = labels_display,
         body = body_display,
-    )
+    );
+    attachments::append_to_prompt(prompt, attachments)
 }
 
 #[cfg(test)]
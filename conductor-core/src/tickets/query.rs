@@ -2,13 +2,14 @@ use rusqlite::Connection;
 
 use crate::db::query_collect;
 use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
 
 use super::Ticket;
 
 /// Ticket columns for SELECT queries that join `tickets` with alias `t`.
-pub(super) const TICKET_COLS: &str = "t.id, t.repo_id, t.source_type, t.source_id, t.title, t.body, t.state, t.labels, t.assignee, t.priority, t.url, t.synced_at, t.raw_json, t.workflow, t.agent_map";
+pub(super) const TICKET_COLS: &str = "t.id, t.repo_id, t.source_type, t.source_id, t.title, t.body, t.state, t.labels, t.assignee, t.priority, t.url, t.synced_at, t.raw_json, t.workflow, t.agent_map, t.sprint, t.sprint_current";
 /// Ticket columns for SELECT queries without a table alias.
-pub(super) const TICKET_COLS_BARE: &str = "id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map";
+pub(super) const TICKET_COLS_BARE: &str = "id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map, sprint, sprint_current";
 /// Aliased column projection for dep-pair JOIN queries (tf_ prefix = from, tt_ prefix = to).
 /// Shared between query_dep_pairs and query_dep_pairs_for_repo so adding a Ticket field
 /// only requires updating this one constant.
@@ -18,11 +19,13 @@ const DEP_PAIR_COLS: &str = "d.from_ticket_id, d.to_ticket_id, \
      tf.labels AS tf_labels, tf.assignee AS tf_assignee, tf.priority AS tf_priority, \
      tf.url AS tf_url, tf.synced_at AS tf_synced_at, tf.raw_json AS tf_raw_json, \
      tf.workflow AS tf_workflow, tf.agent_map AS tf_agent_map, \
+     tf.sprint AS tf_sprint, tf.sprint_current AS tf_sprint_current, \
      tt.id AS tt_id, tt.repo_id AS tt_repo_id, tt.source_type AS tt_source_type, tt.source_id AS tt_source_id, \
      tt.title AS tt_title, tt.body AS tt_body, tt.state AS tt_state, \
      tt.labels AS tt_labels, tt.assignee AS tt_assignee, tt.priority AS tt_priority, \
      tt.url AS tt_url, tt.synced_at AS tt_synced_at, tt.raw_json AS tt_raw_json, \
-     tt.workflow AS tt_workflow, tt.agent_map AS tt_agent_map";
+     tt.workflow AS tt_workflow, tt.agent_map AS tt_agent_map, \
+     tt.sprint AS tt_sprint, tt.sprint_current AS tt_sprint_current";
 
 pub(super) fn map_ticket_row(row: &rusqlite::Row) -> rusqlite::Result<Ticket> {
     Ok(Ticket {
@@ -41,30 +44,47 @@ pub(super) fn map_ticket_row(row: &rusqlite::Row) -> rusqlite::Result<Ticket> {
         raw_json: row.get("raw_json")?,
         workflow: row.get("workflow")?,
         agent_map: row.get("agent_map")?,
+        sprint: row.get("sprint")?,
+        sprint_current: row.get("sprint_current")?,
     })
 }
 
 /// Runs the shared double-join query for a single `dep_type` and returns
 /// `(from_ticket_id, to_ticket_id, from_ticket, to_ticket)` for every row.
 /// Used by `get_all_dependencies` to eliminate query/mapper duplication.
+/// Edges touching a ticket in a repo private to someone other than `viewer`
+/// are left out.
 pub(super) fn query_dep_pairs(
     conn: &Connection,
     dep_type: &str,
+    viewer: &Viewer,
 ) -> Result<Vec<(String, String, Ticket, Ticket)>> {
     // Use LEFT JOIN so orphaned edges (referencing deleted tickets) still
     // produce rows — we detect them via a NULL tf.id / tt.id and return
     // TicketNotFound instead of silently dropping the edge.
+    let visible = |alias: &str| {
+        visible_repo_sql(viewer, &format!("{alias}.repo_id"), ":viewer")
+            .map(|c| format!(" AND ({alias}.id IS NULL OR {c})"))
+            .unwrap_or_default()
+    };
     let sql = format!(
         "SELECT {} FROM ticket_dependencies d \
          LEFT JOIN tickets tf ON tf.id = d.from_ticket_id \
          LEFT JOIN tickets tt ON tt.id = d.to_ticket_id \
-         WHERE d.dep_type = :dep_type",
-        DEP_PAIR_COLS
+         WHERE d.dep_type = :dep_type{}{}",
+        DEP_PAIR_COLS,
+        visible("tf"),
+        visible("tt"),
     );
     let mut stmt = conn.prepare(&sql).map_err(ConductorError::Database)?;
 
+    let owner = viewer.owner_param();
+    let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = vec![(":dep_type", &dep_type)];
+    if *viewer != Viewer::Local {
+        params.push((":viewer", &owner));
+    }
     let rows = stmt
-        .query_map(rusqlite::named_params! { ":dep_type": dep_type }, |row| {
+        .query_map(params.as_slice(), |row| {
             let from_id: String = row.get("from_ticket_id")?;
             let to_id: String = row.get("to_ticket_id")?;
             let tf_id: Option<String> = row.get("tf_id")?;
@@ -155,5 +175,7 @@ pub(super) fn map_ticket_row_aliased(
         raw_json: row.get(col!("raw_json"))?,
         workflow: row.get(col!("workflow"))?,
         agent_map: row.get(col!("agent_map"))?,
+        sprint: row.get(col!("sprint"))?,
+        sprint_current: row.get(col!("sprint_current"))?,
     })
 }
@@ -0,0 +1,157 @@
+//! The personal ticket queue: open tickets, across every repo, that are
+//! assigned to or mention one of the identities in `[identity]`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::IdentityConfig;
+use crate::error::{ConductorError, Result};
+
+use super::{Ticket, TicketFilter, TicketSyncer};
+
+/// Why a ticket is in the queue.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueReason {
+    /// Assigned to you, or (for pull requests) waiting on your review.
+    Assigned,
+    /// Mentions you in its description.
+    Mentioned,
+}
+
+/// Which part of the queue to include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueScope {
+    #[default]
+    All,
+    Assigned,
+    Mentioned,
+}
+
+impl QueueScope {
+    /// Whether a ticket in the queue for `reasons` belongs to this scope.
+    pub fn admits(self, reasons: &[QueueReason]) -> bool {
+        match self {
+            QueueScope::All => !reasons.is_empty(),
+            QueueScope::Assigned => reasons.contains(&QueueReason::Assigned),
+            QueueScope::Mentioned => reasons.contains(&QueueReason::Mentioned),
+        }
+    }
+}
+
+/// A ticket in the queue and why it is there.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueTicket {
+    pub ticket: Ticket,
+    /// Every reason that applies, `Assigned` first. Never empty.
+    pub reasons: Vec<QueueReason>,
+}
+
+impl QueueTicket {
+    /// The reason shown for the ticket: assignment wins over a mention.
+    pub fn reason(&self) -> QueueReason {
+        self.reasons[0]
+    }
+}
+
+/// Every reason `ticket` is in the queue of `identity`, `Assigned` first;
+/// empty if it is not in the queue.
+///
+/// Assignment is read from the synced assignee and the source's raw JSON
+/// (every GitHub/GitLab assignee and requested reviewer, the Jira assignee's
+/// account id). Mentions are `@username` in the description, or
+/// `[~accountid:…]` for Jira. Sources without an identity never match.
+pub fn queue_reasons(ticket: &Ticket, identity: &IdentityConfig) -> Vec<QueueReason> {
+    let Some(me) = identity.for_source(&ticket.source_type) else {
+        return Vec::new();
+    };
+    let mut reasons = Vec::new();
+    if is_assigned(ticket, me) {
+        reasons.push(QueueReason::Assigned);
+    }
+    if mentions(ticket, me) {
+        reasons.push(QueueReason::Mentioned);
+    }
+    reasons
+}
+
+fn is_assigned(ticket: &Ticket, me: &str) -> bool {
+    if ticket
+        .assignee
+        .as_deref()
+        .is_some_and(|a| a.eq_ignore_ascii_case(me))
+    {
+        return true;
+    }
+    let Ok(raw) = serde_json::from_str::<serde_json::Value>(&ticket.raw_json) else {
+        return false;
+    };
+    if ticket.source_type == "jira" {
+        return raw["fields"]["assignee"]["accountId"].as_str() == Some(me);
+    }
+    let is_me = |user: &serde_json::Value| {
+        ["login", "username"]
+            .iter()
+            .filter_map(|key| user[key].as_str())
+            .any(|name| name.eq_ignore_ascii_case(me))
+    };
+    ["assignees", "reviewRequests"]
+        .iter()
+        .filter_map(|key| raw[key].as_array())
+        .flatten()
+        .any(is_me)
+        || is_me(&raw["assignee"])
+}
+
+fn mentions(ticket: &Ticket, me: &str) -> bool {
+    if ticket.source_type == "jira" {
+        return ticket
+            .body
+            .to_ascii_lowercase()
+            .contains(&format!("accountid:{}", me.to_ascii_lowercase()));
+    }
+    let body = ticket.body.to_ascii_lowercase();
+    let needle = format!("@{}", me.to_ascii_lowercase());
+    body.match_indices(&needle).any(|(i, _)| {
+        let before = body[..i].chars().next_back();
+        let after = body[i + needle.len()..].chars().next();
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
+
+/// Fails with a hint at `config.toml` when no identity is configured.
+pub fn require_identity(identity: &IdentityConfig) -> Result<()> {
+    if identity.is_empty() {
+        return Err(ConductorError::InvalidInput(
+            "no identity configured; set github, gitlab or jira under [identity] in config.toml"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+impl TicketSyncer<'_> {
+    /// Open tickets across all repos in `identity`'s queue, assigned ones
+    /// first. Fails when no identity is configured.
+    pub fn my_queue(
+        &self,
+        identity: &IdentityConfig,
+        scope: QueueScope,
+    ) -> Result<Vec<QueueTicket>> {
+        require_identity(identity)?;
+        let mut queue: Vec<QueueTicket> = self
+            .list_filtered(None, &TicketFilter::default())?
+            .into_iter()
+            .filter_map(|ticket| {
+                let reasons = queue_reasons(&ticket, identity);
+                scope
+                    .admits(&reasons)
+                    .then_some(QueueTicket { ticket, reasons })
+            })
+            .collect();
+        queue.sort_by_key(QueueTicket::reason);
+        Ok(queue)
+    }
+}
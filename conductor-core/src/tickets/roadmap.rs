@@ -0,0 +1,274 @@
+//! Tickets grouped by sprint or milestone, with progress computed from each
+//! ticket's linked worktrees and pull requests.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentRunStatus;
+use crate::worktree::WorktreeStatus;
+
+use super::{Ticket, TicketPrState, TicketProgress};
+
+/// How far a single ticket has got. Ordered from least to most done.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoadmapStage {
+    NotStarted,
+    InProgress,
+    InReview,
+    Done,
+}
+
+impl RoadmapStage {
+    /// Stage of `ticket` given its progress, if it has any linked work.
+    /// A closed ticket or merged PR is done; an open PR or a worktree waiting
+    /// on review is in review; any other linked work is in progress.
+    pub fn of(ticket: &Ticket, progress: Option<&TicketProgress>) -> Self {
+        let pr_state = progress.and_then(|p| p.pr_state);
+        let worktree_status = progress.and_then(|p| p.worktree_status);
+        let agent_status = progress.and_then(|p| p.agent_status);
+
+        if ticket.state == "closed" || pr_state == Some(TicketPrState::Merged) {
+            RoadmapStage::Done
+        } else if matches!(pr_state, Some(TicketPrState::Open | TicketPrState::Draft))
+            || matches!(
+                worktree_status,
+                Some(WorktreeStatus::NeedsReview | WorktreeStatus::Promoted)
+            )
+        {
+            RoadmapStage::InReview
+        } else if ticket.state == "in_progress"
+            || matches!(
+                worktree_status,
+                Some(WorktreeStatus::Active | WorktreeStatus::Parked)
+            )
+            || matches!(
+                agent_status,
+                Some(AgentRunStatus::Running | AgentRunStatus::WaitingForFeedback)
+            )
+        {
+            RoadmapStage::InProgress
+        } else {
+            RoadmapStage::NotStarted
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RoadmapStage::NotStarted => "not started",
+            RoadmapStage::InProgress => "in progress",
+            RoadmapStage::InReview => "in review",
+            RoadmapStage::Done => "done",
+        }
+    }
+}
+
+/// One ticket on the roadmap.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoadmapItem {
+    pub ticket_id: String,
+    pub source_type: String,
+    pub source_id: String,
+    pub title: String,
+    pub stage: RoadmapStage,
+}
+
+/// Tickets sharing a sprint or milestone, with a count per stage.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoadmapGroup {
+    /// Sprint or milestone name; `None` collects unscheduled tickets.
+    pub name: Option<String>,
+    /// Whether this is the source's currently active sprint.
+    pub current: bool,
+    pub total: usize,
+    pub done: usize,
+    pub in_review: usize,
+    pub in_progress: usize,
+    pub not_started: usize,
+    pub items: Vec<RoadmapItem>,
+}
+
+impl RoadmapGroup {
+    fn new(name: Option<String>) -> Self {
+        Self {
+            name,
+            current: false,
+            total: 0,
+            done: 0,
+            in_review: 0,
+            in_progress: 0,
+            not_started: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, item: RoadmapItem) {
+        self.total += 1;
+        match item.stage {
+            RoadmapStage::Done => self.done += 1,
+            RoadmapStage::InReview => self.in_review += 1,
+            RoadmapStage::InProgress => self.in_progress += 1,
+            RoadmapStage::NotStarted => self.not_started += 1,
+        }
+        self.items.push(item);
+    }
+
+    /// Share of tickets that are done, rounded down to a whole percent.
+    pub fn percent_done(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+
+    /// Display name, with unscheduled tickets labelled as such.
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("Unscheduled")
+    }
+}
+
+/// Group `tickets` by sprint/milestone. The current sprint comes first, then
+/// the rest by name, with unscheduled tickets last. Tickets keep their input
+/// order within a group.
+pub fn build_roadmap(
+    tickets: &[Ticket],
+    progress: &HashMap<String, TicketProgress>,
+) -> Vec<RoadmapGroup> {
+    let mut groups: Vec<RoadmapGroup> = Vec::new();
+    for ticket in tickets {
+        let name = ticket.sprint.clone().filter(|s| !s.is_empty());
+        let idx = match groups.iter().position(|g| g.name == name) {
+            Some(idx) => idx,
+            None => {
+                groups.push(RoadmapGroup::new(name));
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[idx];
+        group.current |= ticket.sprint_current && group.name.is_some();
+        group.push(RoadmapItem {
+            ticket_id: ticket.id.clone(),
+            source_type: ticket.source_type.clone(),
+            source_id: ticket.source_id.clone(),
+            title: ticket.title.clone(),
+            stage: RoadmapStage::of(ticket, progress.get(&ticket.id)),
+        });
+    }
+    groups.sort_by(|a, b| {
+        b.current
+            .cmp(&a.current)
+            .then(a.name.is_none().cmp(&b.name.is_none()))
+            .then(a.name.cmp(&b.name))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(id: &str, state: &str, sprint: Option<&str>, current: bool) -> Ticket {
+        Ticket {
+            id: id.into(),
+            repo_id: "r1".into(),
+            source_type: "github".into(),
+            source_id: id.into(),
+            title: format!("Ticket {id}"),
+            body: String::new(),
+            state: state.into(),
+            labels: "[]".into(),
+            assignee: None,
+            priority: None,
+            url: String::new(),
+            synced_at: String::new(),
+            raw_json: "{}".into(),
+            workflow: None,
+            agent_map: None,
+            sprint: sprint.map(String::from),
+            sprint_current: current,
+        }
+    }
+
+    fn progress(
+        worktree_status: Option<WorktreeStatus>,
+        pr_state: Option<TicketPrState>,
+    ) -> TicketProgress {
+        TicketProgress {
+            worktree_status,
+            agent_status: None,
+            pr_state,
+        }
+    }
+
+    #[test]
+    fn stage_prefers_the_furthest_signal() {
+        let open = ticket("1", "open", None, false);
+        assert_eq!(RoadmapStage::of(&open, None), RoadmapStage::NotStarted);
+        assert_eq!(
+            RoadmapStage::of(&open, Some(&progress(Some(WorktreeStatus::Active), None))),
+            RoadmapStage::InProgress
+        );
+        assert_eq!(
+            RoadmapStage::of(
+                &open,
+                Some(&progress(
+                    Some(WorktreeStatus::Active),
+                    Some(TicketPrState::Draft)
+                ))
+            ),
+            RoadmapStage::InReview
+        );
+        assert_eq!(
+            RoadmapStage::of(
+                &open,
+                Some(&progress(
+                    Some(WorktreeStatus::Merged),
+                    Some(TicketPrState::Merged)
+                ))
+            ),
+            RoadmapStage::Done
+        );
+        // Abandoned work does not count as progress.
+        assert_eq!(
+            RoadmapStage::of(
+                &open,
+                Some(&progress(Some(WorktreeStatus::Abandoned), None))
+            ),
+            RoadmapStage::NotStarted
+        );
+        let closed = ticket("2", "closed", None, false);
+        assert_eq!(RoadmapStage::of(&closed, None), RoadmapStage::Done);
+    }
+
+    #[test]
+    fn groups_current_first_and_unscheduled_last() {
+        let tickets = vec![
+            ticket("1", "open", None, false),
+            ticket("2", "closed", Some("2.0"), false),
+            ticket("3", "open", Some("2.0"), false),
+            ticket("4", "open", Some("Sprint 5"), true),
+            ticket("5", "open", Some("1.9"), false),
+            ticket("6", "open", Some(""), false),
+        ];
+        let mut prog = HashMap::new();
+        prog.insert(
+            "3".to_string(),
+            progress(Some(WorktreeStatus::Active), Some(TicketPrState::Open)),
+        );
+
+        let roadmap = build_roadmap(&tickets, &prog);
+        let names: Vec<&str> = roadmap.iter().map(|g| g.display_name()).collect();
+        assert_eq!(names, ["Sprint 5", "1.9", "2.0", "Unscheduled"]);
+        assert!(roadmap[0].current);
+
+        let two = &roadmap[2];
+        assert_eq!((two.total, two.done, two.in_review), (2, 1, 1));
+        assert_eq!(two.percent_done(), 50);
+        assert_eq!(two.items[0].source_id, "2");
+
+        // An empty sprint name is unscheduled.
+        assert_eq!(roadmap[3].total, 2);
+        assert_eq!(roadmap[3].percent_done(), 0);
+    }
+}
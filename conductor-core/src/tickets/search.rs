@@ -0,0 +1,110 @@
+//! Fuzzy ticket search for pickers: type part of a number or a few letters of
+//! the title and get the best matches first.
+
+use super::Ticket;
+
+/// How well `ticket` matches `query`, higher is better; `None` when it does
+/// not match at all. `query` is matched case-insensitively against the
+/// source ID (a leading `#` is ignored) and the title, whose letters only
+/// need to appear in order.
+pub fn fuzzy_score(ticket: &Ticket, query: &str) -> Option<u32> {
+    let query = query.trim().trim_start_matches('#').to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let source_id = ticket.source_id.to_lowercase();
+    if source_id == query {
+        return Some(1000);
+    }
+    if source_id.starts_with(&query) {
+        return Some(800);
+    }
+    let title = ticket.title.to_lowercase();
+    if let Some(pos) = title.find(&query) {
+        // Earlier substring hits rank higher, word starts highest.
+        let word_start = pos == 0 || !title[..pos].ends_with(char::is_alphanumeric);
+        return Some(600 + if word_start { 100 } else { 0 } - pos.min(99) as u32);
+    }
+    subsequence_score(&title, &query)
+}
+
+/// Score an in-order match of `query`'s characters in `text`, rewarding runs
+/// of adjacent characters. At most 500, so it ranks below substring hits.
+fn subsequence_score(text: &str, query: &str) -> Option<u32> {
+    let mut chars = text.chars().enumerate();
+    let mut score = 0u32;
+    let mut last: Option<usize> = None;
+    for qc in query.chars().filter(|c| !c.is_whitespace()) {
+        let (i, _) = chars.by_ref().find(|&(_, c)| c == qc)?;
+        score += match last {
+            Some(prev) if i == prev + 1 => 10,
+            _ => 1,
+        };
+        last = Some(i);
+    }
+    Some(score.min(500))
+}
+
+/// `tickets` matching `query`, best first. Open tickets come before closed
+/// ones with the same score; otherwise the input order is kept, so an empty
+/// query lists open tickets first.
+pub fn search<'a>(tickets: impl IntoIterator<Item = &'a Ticket>, query: &str) -> Vec<&'a Ticket> {
+    let mut scored: Vec<(u32, &Ticket)> = tickets
+        .into_iter()
+        .filter_map(|t| fuzzy_score(t, query).map(|s| (s, t)))
+        .collect();
+    scored.sort_by(|(sa, a), (sb, b)| {
+        sb.cmp(sa)
+            .then_with(|| (a.state == "closed").cmp(&(b.state == "closed")))
+    });
+    scored.into_iter().map(|(_, t)| t).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(source_id: &str, title: &str, state: &str) -> Ticket {
+        Ticket {
+            id: format!("t{source_id}"),
+            repo_id: "r1".into(),
+            source_type: "github".into(),
+            source_id: source_id.into(),
+            title: title.into(),
+            body: String::new(),
+            state: state.into(),
+            labels: "[]".into(),
+            assignee: None,
+            priority: None,
+            url: String::new(),
+            synced_at: String::new(),
+            raw_json: "{}".into(),
+            workflow: None,
+            agent_map: None,
+            sprint: None,
+            sprint_current: false,
+        }
+    }
+
+    fn ids(found: Vec<&Ticket>) -> Vec<&str> {
+        found.into_iter().map(|t| t.source_id.as_str()).collect()
+    }
+
+    #[test]
+    fn search_ranks_numbers_then_substrings_then_subsequences() {
+        let tickets = [
+            ticket("7", "Fix login redirect", "open"),
+            ticket("42", "Add dark mode", "open"),
+            ticket("420", "Login page crashes", "closed"),
+            ticket("43", "Refactor logging", "open"),
+        ];
+        assert_eq!(ids(search(&tickets, "#42")), ["42", "420"]);
+        assert_eq!(ids(search(&tickets, "login")), ["420", "7", "43"]);
+        // Equal scores: open before closed.
+        assert_eq!(ids(search(&tickets, "lgn")), ["7", "43", "420"]);
+        assert_eq!(ids(search(&tickets, "drkmd")), ["42"]);
+        assert!(search(&tickets, "zzz").is_empty());
+        // An empty query keeps every ticket, open ones first.
+        assert_eq!(ids(search(&tickets, " ")), ["7", "42", "43", "420"]);
+    }
+}
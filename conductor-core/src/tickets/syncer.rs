@@ -7,25 +7,29 @@ use tracing::warn;
 use crate::db::{query_collect, sql_placeholders, with_in_clause};
 use crate::error::{ConductorError, Result};
 use crate::github::merged_branches_for_repo;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
 
 use super::query::{
     map_ticket_row, query_dep_pairs, query_dep_pairs_for_repo, TICKET_COLS, TICKET_COLS_BARE,
 };
 use super::{
-    ticket_not_found, ReadyTicket, Ticket, TicketDependencies, TicketFilter, TicketInput,
-    TicketLabel, VALID_TICKET_STATES,
+    build_roadmap, ticket_not_found, ReadyTicket, RoadmapGroup, Ticket, TicketDependencies,
+    TicketFilter, TicketInput, TicketLabel, TicketPrState, TicketProgress, MANUAL_SOURCE_TYPE,
+    VALID_TICKET_STATES,
 };
+use crate::agent::AgentRunStatus;
+use crate::worktree::WorktreeStatus;
 
 pub struct TicketSyncer<'a> {
     pub(super) conn: &'a Connection,
 }
 
 pub(in crate::tickets) const CLOSED_TICKET_ARTIFACTS_SQL: &str =
-    "SELECT r.local_path, w.path, w.branch, r.remote_url
+    "SELECT r.local_path, w.path, w.branch, r.remote_url, w.ticket_id
      FROM worktrees w
      JOIN repos r ON r.id = w.repo_id
      WHERE w.repo_id = :repo_id
-       AND w.status != 'merged'
+       AND w.status NOT IN ('merged', 'promoted')
        AND w.ticket_id IS NOT NULL
        AND w.ticket_id IN (SELECT id FROM tickets WHERE state = 'closed')";
 
@@ -75,6 +79,8 @@ impl<'a> TicketSyncer<'a> {
 
         let tx = self.conn.unchecked_transaction()?;
         let now = Utc::now().to_rfc3339();
+        let stored = super::changes::snapshot(&tx, repo_id, tickets)?;
+        let mut changed = Vec::new();
 
         // Pre-fetch existing raw_json for all tickets that don't supply one,
         // replacing the per-ticket SELECT with a single bulk query.
@@ -107,7 +113,7 @@ impl<'a> TicketSyncer<'a> {
             )?;
         }
 
-        // First pass: upsert tickets and their labels, collecting internal IDs.
+        // First pass: upsert tickets, their labels and attachments, collecting internal IDs.
         let mut ticket_ids: Vec<(&TicketInput, String)> = Vec::with_capacity(tickets.len());
         for ticket in tickets {
             let id = crate::new_id();
@@ -123,9 +129,11 @@ impl<'a> TicketSyncer<'a> {
                     .unwrap_or_else(|| "{}".to_string()),
             };
             let ticket_id: String = tx.query_row(
-                "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json)
-                 VALUES (:id, :repo_id, :source_type, :source_id, :title, :body, :state, :labels, :assignee, :priority, :url, :synced_at, :raw_json)
+                "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, sprint, sprint_current, opened_at)
+                 VALUES (:id, :repo_id, :source_type, :source_id, :title, :body, :state, :labels, :assignee, :priority, :url, :synced_at, :raw_json, :sprint, :sprint_current, :synced_at)
                  ON CONFLICT(repo_id, source_type, source_id) DO UPDATE SET
+                     opened_at = CASE WHEN tickets.state = 'closed' AND excluded.state != 'closed'
+                                      THEN excluded.synced_at ELSE tickets.opened_at END,
                      title = excluded.title,
                      body = excluded.body,
                      state = excluded.state,
@@ -134,7 +142,9 @@ impl<'a> TicketSyncer<'a> {
                      priority = excluded.priority,
                      url = excluded.url,
                      synced_at = excluded.synced_at,
-                     raw_json = excluded.raw_json
+                     raw_json = excluded.raw_json,
+                     sprint = excluded.sprint,
+                     sprint_current = excluded.sprint_current
                  RETURNING id",
                 named_params! {
                     ":id": id,
@@ -150,6 +160,8 @@ impl<'a> TicketSyncer<'a> {
                     ":url": ticket.url,
                     ":synced_at": now,
                     ":raw_json": raw_json,
+                    ":sprint": ticket.sprint,
+                    ":sprint_current": ticket.sprint_current,
                 },
                 |row| row.get("id"),
             )?;
@@ -163,6 +175,28 @@ impl<'a> TicketSyncer<'a> {
                     named_params! { ":ticket_id": ticket_id, ":label": ld.name, ":color": ld.color },
                 )?;
             }
+            super::grooming::reapply_accepted_labels(&tx, &ticket_id)?;
+            super::attachments::replace_for_ticket(
+                &tx,
+                &ticket_id,
+                &super::attachments::extract_attachments(ticket),
+            )?;
+            // Hand-made tickets change only through conductor, so there is
+            // nothing upstream to surface.
+            if ticket.source_type != MANUAL_SOURCE_TYPE {
+                let key = (ticket.source_type.clone(), ticket.source_id.clone());
+                if let Some(old) = stored.get(&key) {
+                    let changes = super::changes::describe(old, ticket);
+                    if !changes.is_empty() {
+                        changed.push(super::changes::ChangedTicket {
+                            ticket_id: ticket_id.clone(),
+                            source_id: ticket.source_id.clone(),
+                            title: ticket.title.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
             ticket_ids.push((ticket, ticket_id));
         }
 
@@ -257,6 +291,7 @@ impl<'a> TicketSyncer<'a> {
             }
         }
 
+        super::changes::flag_linked_worktrees(&tx, &changed)?;
         tx.commit()?;
         Ok(tickets.len())
     }
@@ -275,6 +310,10 @@ impl<'a> TicketSyncer<'a> {
             // Nothing was synced — don't mark everything as closed
             return Ok(0);
         }
+        if source_type == MANUAL_SOURCE_TYPE {
+            // Manual tickets are only ever closed by hand.
+            return Ok(0);
+        }
 
         let now = Utc::now().to_rfc3339();
         let ids: Vec<String> = synced_source_ids.iter().map(|s| s.to_string()).collect();
@@ -309,34 +348,44 @@ impl<'a> TicketSyncer<'a> {
     /// Non-numeric `source_id` values (e.g. Jira keys like `PROJ-123`) cast to 0
     /// and sort after all numeric IDs, ordered among themselves by string comparison.
     pub fn list(&self, repo_id: Option<&str>) -> Result<Vec<Ticket>> {
-        let query = match repo_id {
-            Some(_) => {
-                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map
-                 FROM tickets WHERE repo_id = :repo_id ORDER BY CAST(source_id AS INTEGER) DESC, source_id DESC"
-            }
-            None => {
-                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map
-                 FROM tickets ORDER BY CAST(source_id AS INTEGER) DESC, source_id DESC"
-            }
-        };
+        self.list_visible(repo_id, &Viewer::Local)
+    }
 
-        let tickets = if let Some(rid) = repo_id {
-            query_collect(
-                self.conn,
-                query,
-                rusqlite::named_params! { ":repo_id": rid },
-                map_ticket_row,
-            )?
+    /// Like [`Self::list`], leaving out tickets of repos that are private to
+    /// someone other than `viewer`.
+    pub fn list_visible(&self, repo_id: Option<&str>, viewer: &Viewer) -> Result<Vec<Ticket>> {
+        let mut clauses = Vec::new();
+        if repo_id.is_some() {
+            clauses.push("repo_id = :repo_id".to_string());
+        }
+        let visible = visible_repo_sql(viewer, "repo_id", ":viewer");
+        clauses.extend(visible.clone());
+        let where_sql = if clauses.is_empty() {
+            String::new()
         } else {
-            query_collect(self.conn, query, [], map_ticket_row)?
+            format!(" WHERE {}", clauses.join(" AND "))
         };
-        Ok(tickets)
+        let query = format!(
+            "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map, sprint, sprint_current
+             FROM tickets{where_sql} ORDER BY CAST(source_id AS INTEGER) DESC, source_id DESC"
+        );
+
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = Vec::new();
+        if let Some(ref rid) = repo_id {
+            params.push((":repo_id", rid));
+        }
+        if visible.is_some() {
+            params.push((":viewer", &owner));
+        }
+        query_collect(self.conn, &query, params.as_slice(), map_ticket_row)
     }
 
     /// Shared SELECT clause for ticket queries.
     fn ticket_select() -> &'static str {
         "SELECT t.id, t.repo_id, t.source_type, t.source_id, t.title, t.body, \
-         t.state, t.labels, t.assignee, t.priority, t.url, t.synced_at, t.raw_json, t.workflow, t.agent_map \
+         t.state, t.labels, t.assignee, t.priority, t.url, t.synced_at, t.raw_json, t.workflow, t.agent_map, \
+         t.sprint, t.sprint_current \
          FROM tickets t"
     }
 
@@ -365,6 +414,7 @@ impl<'a> TicketSyncer<'a> {
     /// - `filter.include_closed`: when `false`, restricts to `state = 'open'`.
     /// - `filter.labels`: ALL listed labels must be present (AND semantics via EXISTS subqueries).
     /// - `filter.search`: `LIKE %term%` on title and body (case-insensitive for ASCII).
+    /// - `filter.current_sprint_only`: restricts to tickets in the source's current sprint.
     pub fn list_filtered(
         &self,
         repo_id: Option<&str>,
@@ -396,6 +446,10 @@ impl<'a> TicketSyncer<'a> {
             param_values.push(Box::new(pattern));
         }
 
+        if filter.current_sprint_only {
+            conditions.push("t.sprint_current = 1".to_string());
+        }
+
         if filter.unlabeled_only {
             conditions.push(
                 "NOT EXISTS (SELECT 1 FROM ticket_labels tl WHERE tl.ticket_id = t.id)".to_string(),
@@ -437,7 +491,7 @@ impl<'a> TicketSyncer<'a> {
     pub fn get_by_source_id(&self, repo_id: &str, source_id: &str) -> Result<Ticket> {
         self.conn
             .query_row(
-                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map
+                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map, sprint, sprint_current
                  FROM tickets WHERE repo_id = :repo_id AND source_id = :source_id",
                 named_params! { ":repo_id": repo_id, ":source_id": source_id },
                 map_ticket_row,
@@ -450,7 +504,7 @@ impl<'a> TicketSyncer<'a> {
     pub fn get_by_source_id_any_repo(&self, source_id: &str) -> Result<Ticket> {
         self.conn
             .query_row(
-                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map
+                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map, sprint, sprint_current
                  FROM tickets WHERE source_id = :source_id LIMIT 1",
                 named_params! { ":source_id": source_id },
                 map_ticket_row,
@@ -458,11 +512,62 @@ impl<'a> TicketSyncer<'a> {
             .map_err(ticket_not_found(source_id))
     }
 
+    /// Resolve a ticket reference given by a user, across sources and, unless
+    /// `repo_id` is given, across repos.
+    ///
+    /// Accepts the internal ULID, `<source_type>:<source_id>` (`github:42`,
+    /// `jira:PROJ-12`), or a bare source id with an optional leading `#`
+    /// (`#42`, `PROJ-12`). A bare id matching tickets from more than one
+    /// source or repo is refused with the candidates, so the caller can
+    /// qualify it.
+    pub fn find_by_reference(&self, repo_id: Option<&str>, reference: &str) -> Result<Ticket> {
+        let reference = reference.trim();
+        if let Ok(ticket) = self.get_by_id(reference) {
+            if repo_id.is_none_or(|r| r == ticket.repo_id) {
+                return Ok(ticket);
+            }
+        }
+        let (source_type, source_id) = match reference.split_once(':') {
+            Some((st, sid)) if !st.is_empty() && !sid.is_empty() && !st.contains('/') => {
+                (Some(st), sid)
+            }
+            _ => (None, reference.strip_prefix('#').unwrap_or(reference)),
+        };
+        let candidates: Vec<(Ticket, String)> = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {TICKET_COLS}, r.slug AS repo_slug FROM tickets t                  JOIN repos r ON r.id = t.repo_id                  WHERE t.source_id = :source_id                    AND (:source_type IS NULL OR t.source_type = :source_type)                    AND (:repo_id IS NULL OR t.repo_id = :repo_id)                  ORDER BY r.slug, t.source_type"
+            ),
+            named_params! {
+                ":source_id": source_id,
+                ":source_type": source_type,
+                ":repo_id": repo_id,
+            },
+            |row| Ok((map_ticket_row(row)?, row.get("repo_slug")?)),
+        )?;
+        match candidates.len() {
+            0 => Err(ConductorError::TicketNotFound {
+                id: reference.to_string(),
+            }),
+            1 => Ok(candidates.into_iter().next().unwrap().0),
+            _ => {
+                let names: Vec<String> = candidates
+                    .iter()
+                    .map(|(t, slug)| format!("{}:{} in {slug}", t.source_type, t.source_id))
+                    .collect();
+                Err(ConductorError::InvalidInput(format!(
+                    "ticket {reference} is ambiguous ({}); qualify it with its source or repo",
+                    names.join(", ")
+                )))
+            }
+        }
+    }
+
     /// Fetch a single ticket by its internal (ULID) ID.
     pub fn get_by_id(&self, ticket_id: &str) -> Result<Ticket> {
         self.conn
             .query_row(
-                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map
+                "SELECT id, repo_id, source_type, source_id, title, body, state, labels, assignee, priority, url, synced_at, raw_json, workflow, agent_map, sprint, sprint_current
                  FROM tickets WHERE id = :id",
                 named_params! { ":id": ticket_id },
                 map_ticket_row,
@@ -664,10 +769,28 @@ impl<'a> TicketSyncer<'a> {
     /// Returns a `HashMap<ticket_id, Vec<TicketLabel>>` in a single query,
     /// avoiding N+1 per-ticket queries.
     pub fn get_all_labels(&self) -> Result<HashMap<String, Vec<TicketLabel>>> {
+        self.get_visible_labels(&Viewer::Local)
+    }
+
+    /// Like [`Self::get_all_labels`], leaving out tickets of repos that are
+    /// private to someone other than `viewer`.
+    pub fn get_visible_labels(&self, viewer: &Viewer) -> Result<HashMap<String, Vec<TicketLabel>>> {
+        let owner = viewer.owner_param();
+        let mut params: Vec<(&str, &dyn rusqlite::ToSql)> = Vec::new();
+        let visible = match visible_repo_sql(viewer, "t.repo_id", ":viewer") {
+            Some(condition) => {
+                params.push((":viewer", &owner));
+                format!(" JOIN tickets t ON t.id = l.ticket_id WHERE {condition}")
+            }
+            None => String::new(),
+        };
         let all = query_collect(
             self.conn,
-            "SELECT ticket_id, label, color FROM ticket_labels ORDER BY ticket_id, label",
-            [],
+            &format!(
+                "SELECT l.ticket_id, l.label, l.color FROM ticket_labels l{visible} \
+                 ORDER BY l.ticket_id, l.label"
+            ),
+            params.as_slice(),
             |row| {
                 Ok(TicketLabel {
                     ticket_id: row.get("ticket_id")?,
@@ -772,8 +895,17 @@ impl<'a> TicketSyncer<'a> {
     /// Returns `ticket_id → TicketDependencies`. Used by the TUI background
     /// poller to avoid N+1 queries.
     pub fn get_all_dependencies(&self) -> Result<HashMap<String, TicketDependencies>> {
-        let blocks_rows = query_dep_pairs(self.conn, "blocks")?;
-        let parent_rows = query_dep_pairs(self.conn, "parent_of")?;
+        self.get_visible_dependencies(&Viewer::Local)
+    }
+
+    /// Like [`Self::get_all_dependencies`], leaving out edges that touch a
+    /// ticket in a repo private to someone other than `viewer`.
+    pub fn get_visible_dependencies(
+        &self,
+        viewer: &Viewer,
+    ) -> Result<HashMap<String, TicketDependencies>> {
+        let blocks_rows = query_dep_pairs(self.conn, "blocks", viewer)?;
+        let parent_rows = query_dep_pairs(self.conn, "parent_of", viewer)?;
 
         let mut map: HashMap<String, TicketDependencies> = HashMap::new();
 
@@ -794,6 +926,59 @@ impl<'a> TicketSyncer<'a> {
         Ok(map)
     }
 
+    /// Progress of every ticket that has a linked worktree or is itself a
+    /// synced PR, keyed by ticket_id. Tickets with neither are omitted.
+    pub fn progress_by_ticket(&self) -> Result<HashMap<String, TicketProgress>> {
+        let rows = query_collect(
+            self.conn,
+            "SELECT t.id AS ticket_id, \
+                    (SELECT w.status FROM worktrees w WHERE w.ticket_id = t.id \
+                     ORDER BY w.created_at DESC LIMIT 1) AS worktree_status, \
+                    (SELECT a.status FROM agent_runs a JOIN worktrees w ON w.id = a.worktree_id \
+                     WHERE w.ticket_id = t.id ORDER BY a.started_at DESC LIMIT 1) AS agent_status, \
+                    p.state AS pr_ticket_state, p.raw_json AS pr_json \
+             FROM tickets t \
+             LEFT JOIN tickets p ON p.id = CASE WHEN t.source_type = 'github_pr' THEN t.id ELSE ( \
+                 SELECT p2.id FROM tickets p2 JOIN worktrees w ON w.ticket_id = t.id \
+                 WHERE p2.repo_id = t.repo_id AND p2.source_type = 'github_pr' \
+                   AND CASE WHEN json_valid(p2.raw_json) \
+                            THEN json_extract(p2.raw_json, '$.headRefName') END = w.branch \
+                 ORDER BY p2.synced_at DESC LIMIT 1) END \
+             WHERE t.source_type = 'github_pr' \
+                OR EXISTS (SELECT 1 FROM worktrees w WHERE w.ticket_id = t.id)",
+            [],
+            |row| {
+                let worktree_status: Option<WorktreeStatus> = row.get("worktree_status")?;
+                let pr_ticket_state: Option<String> = row.get("pr_ticket_state")?;
+                let pr_json: Option<String> = row.get("pr_json")?;
+                let merged = worktree_status == Some(WorktreeStatus::Merged);
+                let pr_state = match pr_ticket_state {
+                    Some(state) => match pr_state_from_ticket(&state, pr_json.as_deref()) {
+                        TicketPrState::Closed if merged => Some(TicketPrState::Merged),
+                        pr_state => Some(pr_state),
+                    },
+                    None => merged.then_some(TicketPrState::Merged),
+                };
+                let progress = TicketProgress {
+                    worktree_status,
+                    agent_status: row.get::<_, Option<AgentRunStatus>>("agent_status")?,
+                    pr_state,
+                };
+                Ok((row.get::<_, String>("ticket_id")?, progress))
+            },
+        )?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Tickets grouped by sprint/milestone with per-stage counts; see
+    /// [`build_roadmap`]. Closed tickets are included so progress adds up.
+    /// Tickets of repos private to someone other than `viewer` are left out.
+    pub fn roadmap(&self, repo_id: Option<&str>, viewer: &Viewer) -> Result<Vec<RoadmapGroup>> {
+        let tickets = self.list_visible(repo_id, viewer)?;
+        let progress = self.progress_by_ticket()?;
+        Ok(build_roadmap(&tickets, &progress))
+    }
+
     /// Batch-loads `blocks` edges for a specific set of ticket IDs.
     /// Returns `(from_ticket_id, to_ticket_id)` pairs — i.e. (blocker, blocked).
     /// Callers must guard against an empty `ticket_ids` slice before calling.
@@ -878,7 +1063,7 @@ impl<'a> TicketSyncer<'a> {
         merge_check: impl Fn(&str, &[String]) -> HashMap<String, String>,
     ) -> Result<usize> {
         // Collect git paths before updating so we can clean up worktree dirs and branches.
-        let artifacts: Vec<(String, String, String, String)> = query_collect(
+        let artifacts: Vec<(String, String, String, String, String)> = query_collect(
             self.conn,
             CLOSED_TICKET_ARTIFACTS_SQL,
             named_params! { ":repo_id": repo_id },
@@ -888,13 +1073,14 @@ impl<'a> TicketSyncer<'a> {
                     row.get("path")?,
                     row.get("branch")?,
                     row.get("remote_url")?,
+                    row.get("ticket_id")?,
                 ))
             },
         )?;
 
         // Group branches by remote_url and batch-check merged status per repo.
         let mut branches_by_remote: HashMap<&str, Vec<String>> = HashMap::new();
-        for (_, _, branch, remote_url) in &artifacts {
+        for (_, _, branch, remote_url, _) in &artifacts {
             branches_by_remote
                 .entry(remote_url.as_str())
                 .or_default()
@@ -908,18 +1094,41 @@ impl<'a> TicketSyncer<'a> {
         let now = Utc::now().to_rfc3339();
         let mut count = 0usize;
 
-        for (repo_path, worktree_path, branch, _remote_url) in &artifacts {
+        for (repo_path, worktree_path, branch, _remote_url, ticket_id) in &artifacts {
             if !merged_branches.contains(branch) {
                 // Ticket is closed but PR not yet merged — leave the worktree alone.
                 continue;
             }
             self.conn.execute(
-                "UPDATE worktrees SET status = 'merged', completed_at = :now
-                 WHERE path = :path AND status != 'merged'",
-                named_params! { ":now": now, ":path": worktree_path },
+                "UPDATE worktrees SET status = :status, completed_at = :now
+                 WHERE path = :path AND status NOT IN ('merged', 'promoted')",
+                named_params! {
+                    ":status": crate::worktree::WorktreeStatus::Merged,
+                    ":now": now,
+                    ":path": worktree_path,
+                },
             )?;
             count += 1;
+            let hooks = crate::worktree::load_hooks(repo_path);
+            let hook_target = crate::worktree::HookTarget {
+                repo_path,
+                worktree_path,
+                branch,
+                ticket_id: Some(ticket_id),
+            };
+            crate::worktree::run_hook(
+                self.conn,
+                &hooks,
+                crate::worktree::WorktreeHook::PreDelete,
+                &hook_target,
+            );
             crate::worktree::WorktreeManager::remove_artifacts(repo_path, worktree_path, branch);
+            crate::worktree::run_hook(
+                self.conn,
+                &hooks,
+                crate::worktree::WorktreeHook::PostMerge,
+                &hook_target,
+            );
         }
 
         Ok(count)
@@ -1050,3 +1259,19 @@ impl<'a> TicketSyncer<'a> {
         Ok((ticket.source_type, ticket.source_id))
     }
 }
+
+/// PR state of a synced `github_pr` ticket. `raw_json` carries gh's
+/// `OPEN`/`CLOSED`/`MERGED` and `isDraft`; a PR that dropped out of the open-PR
+/// sync is closed even if its stored JSON still says open.
+fn pr_state_from_ticket(ticket_state: &str, raw_json: Option<&str>) -> TicketPrState {
+    let pr: serde_json::Value = raw_json
+        .and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default();
+    match pr["state"].as_str().map(str::to_ascii_uppercase).as_deref() {
+        Some("MERGED") => TicketPrState::Merged,
+        Some("CLOSED") => TicketPrState::Closed,
+        _ if ticket_state == "closed" => TicketPrState::Closed,
+        _ if pr["isDraft"].as_bool() == Some(true) => TicketPrState::Draft,
+        _ => TicketPrState::Open,
+    }
+}
@@ -1,6 +1,7 @@
 use super::query::{query_dep_pairs, query_dep_pairs_for_repo};
 use super::syncer::CLOSED_TICKET_ARTIFACTS_SQL;
 use super::*;
+use crate::repo_visibility::Viewer;
 use rusqlite::Connection;
 use std::collections::HashMap;
 
@@ -38,6 +39,8 @@ fn make_ticket_stub(state: &str) -> Ticket {
         raw_json: "{}".to_string(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     }
 }
 
@@ -207,6 +210,27 @@ fn test_close_missing_tickets_marks_absent_as_closed() {
     assert_eq!(get_ticket_state(&conn, "3"), "open");
 }
 
+#[test]
+fn test_close_missing_never_closes_manual_tickets() {
+    let conn = setup_db();
+    let syncer = TicketSyncer::new(&conn);
+
+    let tickets: Vec<TicketInput> = ["1", "2"]
+        .into_iter()
+        .map(|id| TicketInput {
+            source_type: MANUAL_SOURCE_TYPE.to_string(),
+            ..make_ticket(id, "Imported task")
+        })
+        .collect();
+    syncer.upsert_tickets("r1", &tickets).unwrap();
+
+    let closed = syncer
+        .close_missing_tickets("r1", MANUAL_SOURCE_TYPE, &["1"])
+        .unwrap();
+    assert_eq!(closed, 0);
+    assert_eq!(get_ticket_state(&conn, "2"), "open");
+}
+
 #[test]
 fn test_close_missing_does_not_reclose_already_closed() {
     let conn = setup_db();
@@ -730,6 +754,65 @@ fn test_mark_worktrees_removes_when_pr_merged() {
     assert_eq!(get_worktree_status(&conn, "wt1"), "merged");
 }
 
+#[test]
+fn test_upstream_changes_flag_live_linked_worktrees_until_acknowledged() {
+    let conn = setup_db();
+    let syncer = TicketSyncer::new(&conn);
+    syncer
+        .upsert_tickets("r1", &[make_ticket("1", "Issue 1")])
+        .unwrap();
+    let ticket_id = syncer.get_by_source_id("r1", "1").unwrap().id;
+    insert_worktree(&conn, "wt1", "r1", Some(&ticket_id), "active");
+    insert_worktree(&conn, "wt2", "r1", Some(&ticket_id), "merged");
+    let ticket_change = |id: &str| -> Option<String> {
+        conn.query_row(
+            "SELECT ticket_change FROM worktrees WHERE id = :id",
+            rusqlite::named_params! { ":id": id },
+            |row| row.get("ticket_change"),
+        )
+        .unwrap()
+    };
+
+    // A resync with nothing tracked changed flags nothing.
+    let mut ticket = make_ticket("1", "Issue 1 (renamed)");
+    syncer
+        .upsert_tickets("r1", std::slice::from_ref(&ticket))
+        .unwrap();
+    assert_eq!(ticket_change("wt1"), None);
+
+    ticket.priority = Some("P1".to_string());
+    syncer
+        .upsert_tickets("r1", std::slice::from_ref(&ticket))
+        .unwrap();
+    ticket.assignee = Some("alice".to_string());
+    syncer.upsert_tickets("r1", &[ticket]).unwrap();
+    assert_eq!(
+        ticket_change("wt1").as_deref(),
+        Some("priority none → P1; assigned to alice")
+    );
+    assert_eq!(
+        ticket_change("wt2"),
+        None,
+        "merged worktrees are not flagged"
+    );
+    let notifications = crate::notifications::NotificationManager::new(&conn)
+        .list(crate::notifications::LOCAL_READER, true, 10)
+        .unwrap();
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(notifications[0].kind, "ticket_changed");
+    assert_eq!(
+        notifications[0].url.as_deref(),
+        Some("/repos/r1/worktrees/wt1")
+    );
+
+    let config = crate::config::Config::default();
+    let wt = crate::worktree::WorktreeManager::new(&conn, &config)
+        .acknowledge_ticket_change("wt1")
+        .unwrap();
+    assert_eq!(wt.ticket_change, None);
+    assert_eq!(ticket_change("wt1"), None);
+}
+
 #[test]
 fn test_link_to_worktree_success() {
     let conn = setup_db();
@@ -807,6 +890,42 @@ fn test_get_by_id_not_found() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_upsert_tickets_restarts_opened_at_on_reopen() {
+    let conn = setup_db();
+    let syncer = TicketSyncer::new(&conn);
+    let opened_at = || -> String {
+        conn.query_row(
+            "SELECT opened_at FROM tickets WHERE source_id = '1'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    let set_opened_at = |value: &str| {
+        conn.execute("UPDATE tickets SET opened_at = ?1", [value])
+            .unwrap();
+    };
+
+    let mut ticket = make_ticket("1", "Issue 1");
+    syncer
+        .upsert_tickets("r1", &[make_ticket("1", "Issue 1")])
+        .unwrap();
+    set_opened_at("2024-01-01T00:00:00Z");
+    syncer
+        .upsert_tickets("r1", &[make_ticket("1", "Renamed")])
+        .unwrap();
+    assert_eq!(opened_at(), "2024-01-01T00:00:00Z");
+
+    ticket.state = "closed".into();
+    syncer.upsert_tickets("r1", &[ticket]).unwrap();
+    assert_eq!(opened_at(), "2024-01-01T00:00:00Z");
+    syncer
+        .upsert_tickets("r1", &[make_ticket("1", "Issue 1")])
+        .unwrap();
+    assert_ne!(opened_at(), "2024-01-01T00:00:00Z");
+}
+
 #[test]
 fn test_upsert_tickets_stores_label_details() {
     let conn = setup_db();
@@ -926,9 +1045,11 @@ fn test_build_agent_prompt_full_ticket() {
         raw_json: "{}".to_string(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     };
 
-    let prompt = build_agent_prompt(&ticket);
+    let prompt = build_agent_prompt(&ticket, &[]);
     assert!(prompt.contains("Issue: #42 — Add dark mode support"));
     assert!(prompt.contains("State: open"));
     assert!(prompt.contains("Labels: enhancement, ui"));
@@ -954,9 +1075,11 @@ fn test_build_agent_prompt_empty_body_and_labels() {
         raw_json: "{}".to_string(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     };
 
-    let prompt = build_agent_prompt(&ticket);
+    let prompt = build_agent_prompt(&ticket, &[]);
     assert!(prompt.contains("Issue: #7 — Fix typo"));
     assert!(prompt.contains("Labels: None"));
     assert!(prompt.contains("(No description provided)"));
@@ -1094,6 +1217,8 @@ fn make_ticket_with_body(source_id: &str, title: &str, body: &str) -> TicketInpu
         blocked_by: vec![],
         children: vec![],
         parent: None,
+        sprint: None,
+        sprint_current: false,
     }
 }
 
@@ -1116,6 +1241,7 @@ fn test_list_filtered_defaults_to_open_only() {
         search: None,
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
@@ -1141,6 +1267,7 @@ fn test_list_filtered_include_closed() {
         search: None,
         include_closed: true,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 2);
@@ -1165,6 +1292,7 @@ fn test_list_filtered_by_label() {
         search: None,
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
@@ -1203,6 +1331,7 @@ fn test_list_filtered_by_multiple_labels_and_semantics() {
         search: None,
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
@@ -1229,6 +1358,7 @@ fn test_list_filtered_by_search_title() {
         search: Some("login".to_string()),
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
@@ -1255,6 +1385,7 @@ fn test_list_filtered_by_search_body() {
         search: Some("xyz".to_string()),
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
@@ -1284,6 +1415,7 @@ fn test_list_filtered_no_repo_scope() {
         search: None,
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(None, &filter).unwrap();
     assert_eq!(results.len(), 2);
@@ -1303,6 +1435,7 @@ fn make_repo() -> crate::repo::Repo {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }
 }
 
@@ -1470,6 +1603,7 @@ fn test_list_filtered_sorts_by_issue_number_descending() {
         search: None,
         include_closed: false,
         unlabeled_only: false,
+        current_sprint_only: false,
     };
     let result = syncer.list_filtered(Some("r1"), &filter).unwrap();
     let ids: Vec<&str> = result.iter().map(|t| t.source_id.as_str()).collect();
@@ -1497,6 +1631,7 @@ fn test_list_filtered_unlabeled_only() {
         search: None,
         include_closed: false,
         unlabeled_only: true,
+        current_sprint_only: false,
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     let ids: Vec<&str> = results.iter().map(|t| t.source_id.as_str()).collect();
@@ -1522,10 +1657,43 @@ fn test_list_filtered_unlabeled_only_excludes_closed() {
         search: None,
         include_closed: false,
         unlabeled_only: true,
+        current_sprint_only: false,
+    };
+    let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].source_id, "1");
+}
+
+#[test]
+fn test_list_filtered_current_sprint_only() {
+    let conn = setup_db();
+    let syncer = TicketSyncer::new(&conn);
+
+    let mut t1 = make_ticket("1", "In current sprint");
+    t1.sprint = Some("Sprint 12".to_string());
+    t1.sprint_current = true;
+    let mut t2 = make_ticket("2", "In next sprint");
+    t2.sprint = Some("Sprint 13".to_string());
+    let t3 = make_ticket("3", "Backlog");
+    syncer.upsert_tickets("r1", &[t1, t2, t3]).unwrap();
+
+    let filter = TicketFilter {
+        current_sprint_only: true,
+        ..TicketFilter::default()
     };
     let results = syncer.list_filtered(Some("r1"), &filter).unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].source_id, "1");
+    assert_eq!(results[0].sprint.as_deref(), Some("Sprint 12"));
+    assert!(results[0].sprint_current);
+
+    // A re-sync that moves the ticket out of the sprint clears the metadata.
+    syncer
+        .upsert_tickets("r1", &[make_ticket("1", "In current sprint")])
+        .unwrap();
+    let t = syncer.get_by_source_id("r1", "1").unwrap();
+    assert_eq!(t.sprint, None);
+    assert!(!t.sprint_current);
 }
 
 #[test]
@@ -2300,7 +2468,7 @@ fn test_query_dep_pairs_orphaned_ticket_returns_error() {
     .unwrap();
     conn.execute_batch("PRAGMA foreign_keys = ON").unwrap();
 
-    let result = query_dep_pairs(&conn, "blocks");
+    let result = query_dep_pairs(&conn, "blocks", &Viewer::Local);
     assert!(
         result.is_err(),
         "query_dep_pairs must return Err when a referenced ticket is missing"
@@ -2336,7 +2504,7 @@ fn test_query_dep_pairs_orphaned_from_ticket_returns_error() {
     .unwrap();
     conn.execute_batch("PRAGMA foreign_keys = ON").unwrap();
 
-    let result = query_dep_pairs(&conn, "blocks");
+    let result = query_dep_pairs(&conn, "blocks", &Viewer::Local);
     assert!(
         result.is_err(),
         "query_dep_pairs must return Err when from_ticket is missing"
@@ -2891,7 +3059,7 @@ fn test_query_dep_pairs_returns_ticket_pair() {
     )
     .unwrap();
 
-    let pairs = query_dep_pairs(&conn, "blocks").unwrap();
+    let pairs = query_dep_pairs(&conn, "blocks", &Viewer::Local).unwrap();
     assert_eq!(pairs.len(), 1);
     let (got_from_id, got_to_id, from_ticket, to_ticket) = &pairs[0];
     assert_eq!(got_from_id, &from_id);
@@ -2959,3 +3127,316 @@ fn test_query_dep_pairs_for_repo_cross_repo() {
     assert_eq!(r3_pairs.len(), 1);
     assert_eq!(r3_pairs[0].2.source_id, "c1");
 }
+
+// --- progress_by_ticket tests ---
+
+#[test]
+fn test_progress_by_ticket_joins_worktree_agent_and_pr() {
+    let conn = setup_db();
+    insert_ticket_with_source(&conn, "t1", "r1", "1");
+    insert_ticket_with_source(&conn, "t2", "r1", "2");
+    insert_ticket_with_source(&conn, "t3", "r1", "3");
+    conn.execute_batch(
+        "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, raw_json) \
+         VALUES ('pr1', 'r1', 'github_pr', '7', 'PR', 'open', '2024-01-01T00:00:00Z', \
+                 '{\"state\":\"OPEN\",\"isDraft\":true,\"headRefName\":\"feat/test\"}'); \
+         UPDATE worktrees SET ticket_id = 't1' WHERE id = 'w1'; \
+         INSERT INTO worktrees (id, repo_id, slug, branch, path, ticket_id, status, created_at) \
+         VALUES ('w2', 'r1', 'feat-done', 'feat/done', '/tmp/ws/feat-done', 't3', 'merged', \
+                 '2024-01-01T00:00:00Z');",
+    )
+    .unwrap();
+    crate::test_helpers::insert_test_agent_run(&conn, "ar1", "w1");
+
+    let progress = TicketSyncer::new(&conn).progress_by_ticket().unwrap();
+
+    assert_eq!(
+        progress["t1"],
+        TicketProgress {
+            worktree_status: Some(crate::worktree::WorktreeStatus::Active),
+            agent_status: Some(crate::agent::AgentRunStatus::Running),
+            pr_state: Some(TicketPrState::Draft),
+        }
+    );
+    assert!(!progress.contains_key("t2"));
+    assert_eq!(progress["t3"].pr_state, Some(TicketPrState::Merged));
+    assert_eq!(progress["t3"].agent_status, None);
+    assert_eq!(progress["pr1"].worktree_status, None);
+    assert_eq!(progress["pr1"].pr_state, Some(TicketPrState::Draft));
+
+    // A PR that dropped out of the open-PR sync reads as closed.
+    conn.execute("UPDATE tickets SET state = 'closed' WHERE id = 'pr1'", [])
+        .unwrap();
+    let progress = TicketSyncer::new(&conn).progress_by_ticket().unwrap();
+    assert_eq!(progress["t1"].pr_state, Some(TicketPrState::Closed));
+}
+
+#[test]
+fn find_by_reference_resolves_across_sources_and_repos() {
+    let conn = setup_db();
+    crate::test_helpers::insert_test_repo(&conn, "r2", "other-repo", "/tmp/other");
+    let syncer = TicketSyncer::new(&conn);
+    insert_ticket_with_source(&conn, "t-gh", "r1", "42");
+    insert_ticket_with_source(&conn, "t-other", "r2", "42");
+    conn.execute(
+        "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, raw_json) \
+         VALUES ('t-jira', 'r1', 'jira', 'PROJ-7', 'jira', 'open', '2024-01-01T00:00:00Z', '{}')",
+        [],
+    )
+    .unwrap();
+
+    assert_eq!(
+        syncer.find_by_reference(None, "t-jira").unwrap().id,
+        "t-jira"
+    );
+    assert_eq!(
+        syncer.find_by_reference(None, "PROJ-7").unwrap().id,
+        "t-jira"
+    );
+    assert_eq!(
+        syncer.find_by_reference(None, "jira:PROJ-7").unwrap().id,
+        "t-jira"
+    );
+    assert_eq!(
+        syncer.find_by_reference(Some("r2"), "#42").unwrap().id,
+        "t-other"
+    );
+
+    let err = syncer
+        .find_by_reference(None, "#42")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("github:42 in other-repo"), "{err}");
+    assert!(err.contains("github:42 in test-repo"), "{err}");
+    assert!(matches!(
+        syncer.find_by_reference(None, "jira:42"),
+        Err(ConductorError::TicketNotFound { .. })
+    ));
+}
+
+#[test]
+fn write_back_rejects_tickets_without_a_writable_source() {
+    let conn = setup_db();
+    let syncer = TicketSyncer::new(&conn);
+    conn.execute_batch(
+        "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, raw_json) VALUES
+           ('t-manual', 'r1', 'manual', 'm1', 'manual', 'open', '2024-01-01T00:00:00Z', '{}'),
+           ('t-linear', 'r1', 'linear', 'ENG-1', 'linear', 'open', '2024-01-01T00:00:00Z', '{}'),
+           ('t-jira', 'r1', 'jira', 'PROJ-1', 'jira', 'open', '2024-01-01T00:00:00Z', '{}');",
+    )
+    .unwrap();
+    crate::issue_source::IssueSourceManager::new(&conn)
+        .add("r1", "linear", r#"{"team_key":"ENG"}"#, "test-repo")
+        .unwrap();
+
+    let err = |r: Result<()>| match r {
+        Err(ConductorError::InvalidInput(msg)) => msg,
+        other => panic!("expected InvalidInput, got {other:?}"),
+    };
+    assert!(err(syncer.post_comment("t-manual", "hi")).contains("manual tickets"));
+    assert!(err(syncer.post_comment("t-linear", "hi")).contains("only github and jira"));
+    assert!(err(syncer.post_comment("t-jira", "hi")).contains("no jira issue source"));
+    assert!(err(syncer.post_comment("t-jira", "  ")).contains("must not be empty"));
+    assert!(err(syncer.transition_state("t-jira", "done")).contains("Invalid ticket state"));
+    // Nothing was written back, so the local state is unchanged.
+    assert!(err(syncer.transition_state("t-linear", "closed")).contains("only github"));
+    assert_eq!(syncer.get_by_id("t-linear").unwrap().state, "open");
+}
+
+#[test]
+fn post_agent_results_claims_each_finished_run_once() {
+    let conn = setup_db();
+    let mut config = crate::config::Config::default();
+    conn.execute_batch(
+        "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, raw_json)
+           VALUES ('t1', 'r1', 'linear', 'ENG-1', 'linear', 'open', '2024-01-01T00:00:00Z', '{}');
+         UPDATE worktrees SET ticket_id = 't1' WHERE id = 'w1';",
+    )
+    .unwrap();
+    crate::test_helpers::insert_test_agent_run(&conn, "run-done", "w1");
+    crate::test_helpers::insert_test_agent_run(&conn, "run-old", "w1");
+    crate::test_helpers::insert_test_agent_run(&conn, "run-live", "w1");
+    conn.execute(
+        "UPDATE agent_runs SET status = 'completed', result_text = 'Done.', ended_at = :now
+         WHERE id = 'run-done'",
+        rusqlite::named_params! { ":now": chrono::Utc::now().to_rfc3339() },
+    )
+    .unwrap();
+    conn.execute_batch(
+        "UPDATE agent_runs SET status = 'completed', result_text = 'Done.',
+           ended_at = '2024-01-01T00:00:00Z' WHERE id = 'run-old';",
+    )
+    .unwrap();
+    let claimed = |conn: &Connection| -> Vec<String> {
+        crate::db::query_collect(
+            conn,
+            "SELECT entity_id FROM notification_log WHERE event_type = 'ticket_result_posted'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(post_agent_results(&conn, &config), 0);
+    assert!(claimed(&conn).is_empty(), "off by default");
+
+    // Linear has no write-back, so the post fails, but the run stays claimed
+    // and is not retried.
+    config.agents.post_result_to_ticket = true;
+    assert_eq!(post_agent_results(&conn, &config), 0);
+    assert_eq!(claimed(&conn), ["run-done"]);
+    assert_eq!(post_agent_results(&conn, &config), 0);
+    assert_eq!(claimed(&conn), ["run-done"]);
+
+    // The completion hook skips runs that are still going or already claimed.
+    assert!(!post_run_summary(&conn, &config, "run-live").unwrap());
+    assert!(!post_run_summary(&conn, &config, "run-done").unwrap());
+}
+
+fn github_identity() -> crate::config::IdentityConfig {
+    crate::config::IdentityConfig {
+        github: Some("octocat".to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_queue_reason_github_assignment_and_review_requests() {
+    let identity = github_identity();
+    let mut t = make_ticket_stub("open");
+    assert_eq!(queue_reasons(&t, &identity), []);
+
+    t.assignee = Some("OctoCat".to_string());
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Assigned]);
+
+    t.assignee = Some("someone".to_string());
+    t.raw_json = r#"{"assignees":[{"login":"someone"},{"login":"octocat"}]}"#.to_string();
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Assigned]);
+
+    t.source_type = GITHUB_PR_SOURCE_TYPE.to_string();
+    t.raw_json = r#"{"reviewRequests":[{"login":"octocat"}]}"#.to_string();
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Assigned]);
+}
+
+#[test]
+fn test_queue_reason_mentions_need_a_whole_username() {
+    let identity = github_identity();
+    let mut t = make_ticket_stub("open");
+    t.body = "cc @octocat-bot and email octocat@example.com".to_string();
+    assert_eq!(queue_reasons(&t, &identity), []);
+
+    t.body = "Could @OctoCat take a look?".to_string();
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Mentioned]);
+
+    // Assigned and mentioned: both reasons, assignment first.
+    t.assignee = Some("octocat".to_string());
+    assert_eq!(
+        queue_reasons(&t, &identity),
+        [QueueReason::Assigned, QueueReason::Mentioned]
+    );
+}
+
+#[test]
+fn test_queue_reason_jira_uses_account_id() {
+    let identity = crate::config::IdentityConfig {
+        github: Some("octocat".to_string()),
+        jira: Some("5b10ac8d82e05b22cc7d4ef5".to_string()),
+        ..Default::default()
+    };
+    let mut t = make_ticket_stub("open");
+    t.source_type = "jira".to_string();
+    t.assignee = Some("octocat".to_string());
+    assert_eq!(
+        queue_reasons(&t, &identity),
+        [],
+        "GitHub login is not checked"
+    );
+
+    t.raw_json = r#"{"fields":{"assignee":{"accountId":"5b10ac8d82e05b22cc7d4ef5"}}}"#.to_string();
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Assigned]);
+
+    t.raw_json = "{}".to_string();
+    t.body = "[~accountid:5b10ac8d82e05b22cc7d4ef5] please review".to_string();
+    assert_eq!(queue_reasons(&t, &identity), [QueueReason::Mentioned]);
+
+    // No identity for the source: never in the queue.
+    t.source_type = "linear".to_string();
+    assert_eq!(queue_reasons(&t, &identity), []);
+}
+
+#[test]
+fn test_my_queue_lists_open_tickets_assigned_first() {
+    let conn = setup_db();
+    crate::test_helpers::insert_test_repo(&conn, "r2", "other-repo", "/tmp/other");
+    let mut mentioned = make_ticket("1", "Mentioned");
+    mentioned.body = "ping @octocat".to_string();
+    let mut assigned = make_ticket("2", "Assigned");
+    assigned.assignee = Some("octocat".to_string());
+    let mut closed = make_ticket("3", "Closed");
+    closed.assignee = Some("octocat".to_string());
+    closed.state = "closed".to_string();
+    let mut both = make_ticket("5", "Both");
+    both.assignee = Some("octocat".to_string());
+    both.body = "@octocat over to you".to_string();
+    let syncer = TicketSyncer::new(&conn);
+    syncer
+        .upsert_tickets("r1", &[mentioned, make_ticket("4", "Unrelated"), both])
+        .unwrap();
+    syncer.upsert_tickets("r2", &[assigned, closed]).unwrap();
+
+    let queue = syncer
+        .my_queue(&github_identity(), QueueScope::All)
+        .unwrap();
+    let mut titles: Vec<(&str, QueueReason)> = queue
+        .iter()
+        .map(|q| (q.ticket.title.as_str(), q.reason()))
+        .collect();
+    titles.sort();
+    assert_eq!(
+        titles,
+        [
+            ("Assigned", QueueReason::Assigned),
+            ("Both", QueueReason::Assigned),
+            ("Mentioned", QueueReason::Mentioned)
+        ]
+    );
+    assert_eq!(queue.last().unwrap().ticket.title, "Mentioned");
+
+    let assigned_only = syncer
+        .my_queue(&github_identity(), QueueScope::Assigned)
+        .unwrap();
+    let mut repos: Vec<&str> = assigned_only
+        .iter()
+        .map(|q| q.ticket.repo_id.as_str())
+        .collect();
+    repos.sort();
+    assert_eq!(repos, ["r1", "r2"]);
+
+    // A ticket both assigned and mentioning stays in the mentions scope.
+    let mentioned_only = syncer
+        .my_queue(&github_identity(), QueueScope::Mentioned)
+        .unwrap();
+    let mut titles: Vec<&str> = mentioned_only
+        .iter()
+        .map(|q| q.ticket.title.as_str())
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["Both", "Mentioned"]);
+    assert_eq!(
+        mentioned_only
+            .iter()
+            .find(|q| q.ticket.title == "Both")
+            .unwrap()
+            .reasons,
+        [QueueReason::Assigned, QueueReason::Mentioned]
+    );
+}
+
+#[test]
+fn test_my_queue_requires_an_identity() {
+    let conn = setup_db();
+    let err = TicketSyncer::new(&conn)
+        .my_queue(&crate::config::IdentityConfig::default(), QueueScope::All)
+        .unwrap_err();
+    assert!(matches!(err, crate::error::ConductorError::InvalidInput(_)));
+}
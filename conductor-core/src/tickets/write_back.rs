@@ -0,0 +1,250 @@
+//! Writing back to the tracker a ticket came from: comments and state
+//! transitions, plus posting a summary of each finished agent run to its
+//! ticket when `[agents] post_result_to_ticket` is on.
+
+use chrono::{Duration, Utc};
+use rusqlite::{named_params, Connection, OptionalExtension};
+
+use super::{Ticket, TicketSyncer, GITHUB_PR_SOURCE_TYPE, MANUAL_SOURCE_TYPE, VALID_TICKET_STATES};
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::github;
+use crate::issue_source::IssueSourceManager;
+use crate::text_util::cap_with_suffix;
+use crate::ticket_source::TicketSource;
+
+/// `notification_log` event type claimed once per agent run whose result was
+/// posted, so the TUI, web server and daemon never post the same run twice.
+const RESULT_POSTED_EVENT: &str = "ticket_result_posted";
+
+/// Only runs that finished this recently are posted; turning the setting on
+/// does not flood trackers with old results.
+const RESULT_POST_WINDOW_HOURS: i64 = 1;
+
+/// GitHub rejects comments over 65536 characters; stay well under it.
+const MAX_COMMENT_BYTES: usize = 60_000;
+
+impl TicketSyncer<'_> {
+    /// Post `body` as a comment on the upstream issue of ticket `ticket_id`.
+    pub fn post_comment(&self, ticket_id: &str, body: &str) -> Result<()> {
+        if body.trim().is_empty() {
+            return Err(ConductorError::InvalidInput(
+                "comment body must not be empty".to_string(),
+            ));
+        }
+        let ticket = self.get_by_id(ticket_id)?;
+        self.upstream_source(&ticket)?
+            .post_comment(&ticket.source_id, body)
+    }
+
+    /// Move ticket `ticket_id` to `state` upstream, then locally once the
+    /// tracker accepted it.
+    pub fn transition_state(&self, ticket_id: &str, state: &str) -> Result<()> {
+        if !VALID_TICKET_STATES.contains(&state) {
+            return Err(ConductorError::InvalidInput(format!(
+                "Invalid ticket state '{state}'. Must be one of: open, in_progress, closed."
+            )));
+        }
+        let ticket = self.get_by_id(ticket_id)?;
+        self.upstream_source(&ticket)?
+            .transition_state(&ticket.source_id, state)?;
+        self.update_ticket(ticket_id, Some(state), None, None)
+    }
+
+    /// The configured source `ticket` was synced from. Synced pull requests
+    /// are written back through their repo's GitHub source.
+    fn upstream_source(&self, ticket: &Ticket) -> Result<TicketSource> {
+        if ticket.source_type == MANUAL_SOURCE_TYPE {
+            return Err(ConductorError::InvalidInput(
+                "manual tickets have no upstream tracker to write back to".to_string(),
+            ));
+        }
+        let is_pr = ticket.source_type == GITHUB_PR_SOURCE_TYPE;
+        let wanted = if is_pr { "github" } else { &ticket.source_type };
+        let source = IssueSourceManager::new(self.conn)
+            .list(&ticket.repo_id)?
+            .into_iter()
+            .find(|s| s.source_type == wanted)
+            .ok_or_else(|| {
+                ConductorError::InvalidInput(format!(
+                    "no {wanted} issue source is configured for this ticket's repo"
+                ))
+            })?;
+        Ok(match TicketSource::from_issue_source(&source)? {
+            TicketSource::GitHub(cfg) if is_pr => TicketSource::GitHubPrs(cfg),
+            other => other,
+        })
+    }
+}
+
+/// A finished run whose summary may be posted to its worktree's ticket.
+struct RunSummary {
+    result: String,
+    cost_usd: Option<f64>,
+    ticket_id: String,
+    branch: String,
+    remote_url: String,
+}
+
+/// Runs eligible for a ticket summary: completed standalone runs with result
+/// text, on a worktree linked to a non-manual ticket, not yet claimed.
+const ELIGIBLE_RUNS: &str = "FROM agent_runs ar
+     JOIN worktrees w ON w.id = ar.worktree_id
+     JOIN repos r ON r.id = w.repo_id
+     JOIN tickets t ON t.id = w.ticket_id
+     WHERE ar.status = 'completed'
+       AND ar.result_text IS NOT NULL AND ar.result_text != ''
+       AND t.source_type != :manual
+       AND NOT EXISTS (SELECT 1 FROM workflow_run_steps s WHERE s.child_run_id = ar.id)
+       AND NOT EXISTS (SELECT 1 FROM notification_log n
+                       WHERE n.entity_id = ar.id AND n.event_type = :event)";
+
+/// Post a summary of finished agent run `run_id` (result, cost, branch and
+/// PR link) to the ticket linked to its worktree. Called when the run
+/// completes; no-op unless `[agents] post_result_to_ticket` is on, or when
+/// the run is not eligible or was already posted. The run is claimed before
+/// posting and not retried if the tracker rejects the comment. Returns
+/// whether a comment was posted.
+pub fn post_run_summary(conn: &Connection, config: &Config, run_id: &str) -> Result<bool> {
+    if !config.agents.post_result_to_ticket {
+        return Ok(false);
+    }
+    let summary = conn
+        .query_row(
+            &format!(
+                "SELECT ar.result_text, ar.cost_usd, w.ticket_id, w.branch, r.remote_url \
+                 {ELIGIBLE_RUNS} AND ar.id = :id"
+            ),
+            named_params! {
+                ":id": run_id,
+                ":manual": MANUAL_SOURCE_TYPE,
+                ":event": RESULT_POSTED_EVENT,
+            },
+            |row| {
+                Ok(RunSummary {
+                    result: row.get("result_text")?,
+                    cost_usd: row.get("cost_usd")?,
+                    ticket_id: row.get("ticket_id")?,
+                    branch: row.get("branch")?,
+                    remote_url: row.get("remote_url")?,
+                })
+            },
+        )
+        .optional()?;
+    let Some(summary) = summary else {
+        return Ok(false);
+    };
+
+    // Claim before posting: the completing process and the reaper may both
+    // get here for the same run.
+    let claimed = conn.execute(
+        "INSERT OR IGNORE INTO notification_log (entity_id, event_type, fired_at) \
+         VALUES (:id, :event, :now)",
+        named_params! {
+            ":id": run_id,
+            ":event": RESULT_POSTED_EVENT,
+            ":now": Utc::now().to_rfc3339(),
+        },
+    )?;
+    if claimed == 0 {
+        return Ok(false);
+    }
+
+    let syncer = TicketSyncer::new(conn);
+    let ticket = syncer.get_by_id(&summary.ticket_id)?;
+    let source = syncer.upstream_source(&ticket)?;
+    let pr_url = github::get_pr_detail(&summary.remote_url, &summary.branch).map(|pr| pr.url);
+    source.post_comment(
+        &ticket.source_id,
+        &summary_comment(
+            &summary.branch,
+            &summary.result,
+            summary.cost_usd,
+            pr_url.as_deref(),
+        ),
+    )?;
+    Ok(true)
+}
+
+/// Post summaries for eligible runs that completed within the last hour but
+/// were missed when they finished, e.g. because the completing process died.
+/// No-op unless `[agents] post_result_to_ticket` is on. Failures are logged.
+/// Returns how many summaries were posted.
+pub fn post_agent_results(conn: &Connection, config: &Config) -> usize {
+    if !config.agents.post_result_to_ticket {
+        return 0;
+    }
+    let since = (Utc::now() - Duration::hours(RESULT_POST_WINDOW_HOURS)).to_rfc3339();
+    let pending: Vec<String> = match query_collect(
+        conn,
+        &format!("SELECT ar.id {ELIGIBLE_RUNS} AND ar.ended_at >= :since"),
+        named_params! {
+            ":since": since,
+            ":manual": MANUAL_SOURCE_TYPE,
+            ":event": RESULT_POSTED_EVENT,
+        },
+        |row| row.get("id"),
+    ) {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!("listing agent results to post failed: {e}");
+            return 0;
+        }
+    };
+
+    let mut posted = 0;
+    for run_id in pending {
+        match post_run_summary(conn, config, &run_id) {
+            Ok(true) => posted += 1,
+            Ok(false) => {}
+            Err(e) => tracing::warn!(run_id, "posting agent result failed: {e}"),
+        }
+    }
+    posted
+}
+
+/// The comment posted for an agent run on `branch` that finished with `result`.
+fn summary_comment(
+    branch: &str,
+    result: &str,
+    cost_usd: Option<f64>,
+    pr_url: Option<&str>,
+) -> String {
+    let mut footer = vec![format!("Branch: `{branch}`")];
+    if let Some(cost) = cost_usd {
+        footer.push(format!("Cost: ${cost:.2}"));
+    }
+    if let Some(url) = pr_url {
+        footer.push(format!("PR: {url}"));
+    }
+    format!(
+        "**Conductor agent finished**\n\n{}\n\n---\n{}",
+        cap_with_suffix(result.trim(), MAX_COMMENT_BYTES, "\n\n… (truncated)"),
+        footer.join(" · ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_comment_lists_branch_cost_and_pr() {
+        let comment = summary_comment(
+            "feat/login",
+            "  Fixed it.\n",
+            Some(0.4213),
+            Some("https://github.com/o/r/pull/7"),
+        );
+        assert_eq!(
+            comment,
+            "**Conductor agent finished**\n\nFixed it.\n\n---\n\
+             Branch: `feat/login` · Cost: $0.42 · PR: https://github.com/o/r/pull/7"
+        );
+        assert!(summary_comment("b", "ok", None, None).ends_with("---\nBranch: `b`"));
+        let long = summary_comment("b", &"x".repeat(MAX_COMMENT_BYTES + 10), None, None);
+        assert!(long.contains("… (truncated)"));
+        assert!(long.len() < MAX_COMMENT_BYTES + 100);
+    }
+}
@@ -0,0 +1,369 @@
+//! The tmux session conductor opens its windows in.
+//!
+//! Windows go into one managed session per workspace, created on demand and
+//! named after the workspace root (`conductor-<dir>`), instead of whichever
+//! session happens to be current. Window names say what a window is for:
+//!
+//! - `run-<run_id>`: follows an agent run's log ([`run_window_name`])
+//! - `wt-<repo>/<worktree>`: a shell in a worktree ([`worktree_window_name`])
+//! - `repo-<repo>` and `repo-<repo>/…`: a shell in a repo checkout or its
+//!   worktrees directory ([`repo_window_name`])
+//!
+//! A window is reused when one with the same name exists. Windows whose run
+//! finished or whose worktree or repo is gone are orphans and are closed by
+//! [`cleanup_orphan_windows`].
+
+use std::path::Path;
+use std::process::Command;
+
+use rusqlite::Connection;
+
+use crate::agent::AgentManager;
+use crate::command::run_command;
+use crate::config::Config;
+use crate::error::{ConductorError, Result};
+use crate::repo::RepoManager;
+use crate::worktree::WorktreeManager;
+
+const SESSION_PREFIX: &str = "conductor-";
+const RUN_WINDOW_PREFIX: &str = "run-";
+const WORKTREE_WINDOW_PREFIX: &str = "wt-";
+const REPO_WINDOW_PREFIX: &str = "repo-";
+
+/// Name of the window a new managed session starts with; never cleaned up.
+const HOME_WINDOW: &str = "home";
+
+/// A window of the managed session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxWindow {
+    /// tmux window ID, e.g. `@3`; unique across sessions.
+    pub id: String,
+    pub name: String,
+}
+
+/// Name of the managed session for the workspace in `config`.
+pub fn session_name(config: &Config) -> String {
+    let dir = config
+        .general
+        .workspace_root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // tmux rewrites `.` and `:` in session names; keep the name predictable.
+    let dir: String = dir
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if dir.is_empty() {
+        "conductor".to_string()
+    } else {
+        format!("{SESSION_PREFIX}{dir}")
+    }
+}
+
+pub fn run_window_name(run_id: &str) -> String {
+    format!("{RUN_WINDOW_PREFIX}{run_id}")
+}
+
+pub fn worktree_window_name(repo_slug: &str, wt_slug: &str) -> String {
+    format!("{WORKTREE_WINDOW_PREFIX}{repo_slug}/{wt_slug}")
+}
+
+/// Window for repo `repo_slug`; `suffix` tells apart several windows of the
+/// same repo, e.g. `Some("worktrees")` for its worktrees directory.
+pub fn repo_window_name(repo_slug: &str, suffix: Option<&str>) -> String {
+    match suffix {
+        Some(s) => format!("{REPO_WINDOW_PREFIX}{repo_slug}/{s}"),
+        None => format!("{REPO_WINDOW_PREFIX}{repo_slug}"),
+    }
+}
+
+/// Whether the current process runs inside a tmux client.
+pub fn inside_tmux() -> bool {
+    std::env::var_os("TMUX").is_some()
+}
+
+/// Whether a tmux binary is on `PATH`.
+pub fn is_available() -> bool {
+    crate::command::output(Command::new("tmux").arg("-V")).is_ok_and(|o| o.status.success())
+}
+
+fn check_tmux_output(cmd: &mut Command) -> Result<std::process::Output> {
+    let timeout = crate::command::timeouts().local();
+    run_command(cmd, timeout, ConductorError::Tmux)
+}
+
+fn tmux() -> Command {
+    Command::new("tmux")
+}
+
+pub fn has_session(session: &str) -> bool {
+    crate::command::output(tmux().args(["has-session", "-t", &format!("={session}")]))
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Create detached session `session` in `start_dir` unless it exists.
+pub fn ensure_session(session: &str, start_dir: &Path) -> Result<()> {
+    if has_session(session) {
+        return Ok(());
+    }
+    check_tmux_output(
+        tmux()
+            .args(["new-session", "-d", "-s", session, "-n", HOME_WINDOW])
+            .arg("-c")
+            .arg(start_dir),
+    )?;
+    Ok(())
+}
+
+/// Windows of `session`; empty when the session does not exist.
+pub fn list_windows(session: &str) -> Result<Vec<TmuxWindow>> {
+    if !has_session(session) {
+        return Ok(Vec::new());
+    }
+    let output = check_tmux_output(tmux().args([
+        "list-windows",
+        "-t",
+        &format!("={session}"),
+        "-F",
+        "#{window_id} #{window_name}",
+    ]))?;
+    Ok(parse_windows(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_windows(output: &str) -> Vec<TmuxWindow> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, name) = line.split_once(' ')?;
+            Some(TmuxWindow {
+                id: id.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The ID of window `name` in `session`, creating the session and the
+/// window when missing. A new window starts in `dir` and runs `command`
+/// (a shell command line) or the default shell.
+pub fn open_window(
+    session: &str,
+    session_dir: &Path,
+    name: &str,
+    dir: &Path,
+    command: Option<&str>,
+) -> Result<String> {
+    ensure_session(session, session_dir)?;
+    if let Some(existing) = list_windows(session)?.into_iter().find(|w| w.name == name) {
+        return Ok(existing.id);
+    }
+    let mut cmd = tmux();
+    cmd.args(["new-window", "-d", "-P", "-F", "#{window_id}", "-t"])
+        .arg(format!("={session}:"))
+        .args(["-n", name, "-c"])
+        .arg(dir);
+    if let Some(command) = command {
+        cmd.arg(command);
+    }
+    let output = check_tmux_output(&mut cmd)?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Make `window_id` the current window of its session.
+pub fn select_window(window_id: &str) -> Result<()> {
+    check_tmux_output(tmux().args(["select-window", "-t", window_id]))?;
+    Ok(())
+}
+
+/// Switch the current tmux client to `session`; only meaningful inside tmux.
+pub fn switch_client(session: &str) -> Result<()> {
+    check_tmux_output(tmux().args(["switch-client", "-t", &format!("={session}")]))?;
+    Ok(())
+}
+
+/// `tmux attach-session` for `session`, to run with the terminal attached
+/// when outside tmux.
+pub fn attach_command(session: &str) -> Command {
+    let mut cmd = tmux();
+    cmd.args(["attach-session", "-t", &format!("={session}")]);
+    cmd
+}
+
+/// Open (or reuse) the managed session's window for agent run `run_id`. It
+/// starts in the run's worktree, or its repo for repo-scoped runs, and
+/// follows the run's log. Returns the session name and window ID.
+pub fn open_run_window(
+    conn: &Connection,
+    config: &Config,
+    run_id: &str,
+) -> Result<(String, String)> {
+    let agents = AgentManager::new(conn);
+    let run = agents
+        .get_run(run_id)?
+        .ok_or_else(|| ConductorError::AgentRunNotFound {
+            id: run_id.to_string(),
+        })?;
+    let worktree_path = run
+        .worktree_id
+        .as_deref()
+        .and_then(|id| WorktreeManager::new(conn, config).get_by_id(id).ok())
+        .map(|wt| wt.path);
+    let repo_path = || {
+        run.repo_id
+            .as_deref()
+            .and_then(|id| RepoManager::new(conn, config).get_by_id(id).ok())
+            .map(|repo| repo.local_path)
+    };
+    let workspace = config.general.workspace_root.clone();
+    let dir = worktree_path
+        .or_else(repo_path)
+        .map(std::path::PathBuf::from)
+        .filter(|p| p.is_dir())
+        .unwrap_or_else(|| workspace.clone());
+    let log = agents.log_path_for_run(run_id)?;
+    let command = format!("tail -n +1 -F {}", shell_quote(&log.to_string_lossy()));
+
+    let session = session_name(config);
+    let window = open_window(
+        &session,
+        &workspace,
+        &run_window_name(run_id),
+        &dir,
+        Some(&command),
+    )?;
+    Ok((session, window))
+}
+
+/// Quote `s` for a POSIX shell command line.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Windows among `windows` whose run is no longer active, or whose
+/// worktree is no longer live or repo no longer registered. Windows that
+/// do not follow the naming conventions are left alone.
+pub fn orphan_windows<'w>(
+    conn: &Connection,
+    config: &Config,
+    windows: &'w [TmuxWindow],
+) -> Vec<&'w TmuxWindow> {
+    let agents = AgentManager::new(conn);
+    let repos = RepoManager::new(conn, config);
+    let worktrees = WorktreeManager::new(conn, config);
+    windows
+        .iter()
+        .filter(|w| {
+            if let Some(run_id) = w.name.strip_prefix(RUN_WINDOW_PREFIX) {
+                // A failed lookup keeps the window; only a known end closes it.
+                agents
+                    .get_run(run_id)
+                    .is_ok_and(|run| run.is_none_or(|r| !r.is_active()))
+            } else if let Some(rest) = w.name.strip_prefix(WORKTREE_WINDOW_PREFIX) {
+                let Some((repo_slug, wt_slug)) = rest.split_once('/') else {
+                    return false;
+                };
+                match repos
+                    .get_by_slug(repo_slug)
+                    .and_then(|repo| worktrees.get_by_slug(&repo.id, wt_slug))
+                {
+                    Ok(wt) => !wt.is_active(),
+                    Err(e) => is_not_found(&e),
+                }
+            } else if let Some(rest) = w.name.strip_prefix(REPO_WINDOW_PREFIX) {
+                let repo_slug = rest.split('/').next().unwrap_or(rest);
+                repos
+                    .get_by_slug(repo_slug)
+                    .is_err_and(|e| is_not_found(&e))
+            } else {
+                false
+            }
+        })
+        .collect()
+}
+
+fn is_not_found(e: &ConductorError) -> bool {
+    matches!(
+        e,
+        ConductorError::RepoNotFound { .. } | ConductorError::WorktreeNotFound { .. }
+    )
+}
+
+/// Close the orphaned windows of the managed session (see
+/// [`orphan_windows`]) and return their names. A no-op when the session
+/// does not exist.
+pub fn cleanup_orphan_windows(conn: &Connection, config: &Config) -> Result<Vec<String>> {
+    let session = session_name(config);
+    let windows = list_windows(&session)?;
+    let mut closed = Vec::new();
+    for window in orphan_windows(conn, config, &windows) {
+        check_tmux_output(tmux().args(["kill-window", "-t", &window.id]))?;
+        closed.push(window.name.clone());
+    }
+    Ok(closed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{insert_test_agent_run, setup_db};
+
+    fn window(id: &str, name: &str) -> TmuxWindow {
+        TmuxWindow {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+
+    #[test]
+    fn session_name_follows_the_workspace_root() {
+        let mut config = Config::default();
+        config.general.workspace_root = "/home/me/.conductor/work.spaces".into();
+        assert_eq!(session_name(&config), "conductor-work-spaces");
+        assert_eq!(
+            parse_windows("@1 home\n@7 run-01ABC\n"),
+            vec![window("@1", "home"), window("@7", "run-01ABC")]
+        );
+    }
+
+    #[test]
+    fn orphan_windows_are_those_of_finished_runs_and_gone_worktrees() {
+        let conn = setup_db();
+        let config = Config::default();
+        insert_test_agent_run(&conn, "live", "w1");
+        insert_test_agent_run(&conn, "done", "w1");
+        conn.execute(
+            "UPDATE agent_runs SET status = 'completed' WHERE id = 'done'",
+            [],
+        )
+        .unwrap();
+
+        let windows = vec![
+            window("@1", HOME_WINDOW),
+            window("@2", "vim"),
+            window("@3", &run_window_name("live")),
+            window("@4", &run_window_name("done")),
+            window("@5", &run_window_name("unknown")),
+            window("@6", &worktree_window_name("test-repo", "feat-test")),
+            window("@7", &worktree_window_name("test-repo", "gone")),
+            window("@8", &repo_window_name("test-repo", Some("worktrees"))),
+            window("@9", &repo_window_name("unregistered", None)),
+        ];
+        let orphans: Vec<&str> = orphan_windows(&conn, &config, &windows)
+            .into_iter()
+            .map(|w| w.id.as_str())
+            .collect();
+        assert_eq!(orphans, vec!["@4", "@5", "@7", "@9"]);
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("/tmp/it's here"), r"'/tmp/it'\''s here'");
+    }
+}
@@ -0,0 +1,438 @@
+//! What needs attention today, across every repo, as one priority-ordered
+//! list. The TUI's Today view and the web home page both show it.
+//!
+//! Items come from agent runs (waiting for feedback, running, finished but
+//! unreviewed), open tickets past their `[sla]` deadline, PRs of live
+//! worktrees whose CI is failing, and the reader's unread notifications.
+//! Rows of repos private to someone other than the viewer are left out.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentInbox;
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::Result;
+use crate::github::GithubPr;
+use crate::notifications::NotificationManager;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+use crate::worktree::LIVE_STATUSES_SQL;
+
+/// Unread notifications listed at most.
+const MAX_NOTIFICATIONS: usize = 20;
+
+/// Why an item is on the list, in priority order: earlier kinds sort first.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodayItemKind {
+    /// An agent is blocked on a question for the user.
+    AwaitingFeedback,
+    /// CI is failing on the PR of a live worktree.
+    FailingCi,
+    /// An open ticket is past its SLA.
+    SlaBreach,
+    /// An agent run finished and nobody has looked at it yet.
+    NeedsReview,
+    Notification,
+    RunningAgent,
+}
+
+impl TodayItemKind {
+    /// Short label for list rows.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::AwaitingFeedback => "feedback",
+            Self::FailingCi => "ci failing",
+            Self::SlaBreach => "sla",
+            Self::NeedsReview => "review",
+            Self::Notification => "notice",
+            Self::RunningAgent => "running",
+        }
+    }
+}
+
+/// One entry of the today list. The ID fields say where to jump: the
+/// worktree when set, else the repo; `url` is the external page, if any.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodayItem {
+    pub kind: TodayItemKind,
+    pub title: String,
+    pub detail: String,
+    pub repo_id: Option<String>,
+    pub repo_slug: Option<String>,
+    pub worktree_id: Option<String>,
+    pub run_id: Option<String>,
+    pub ticket_id: Option<String>,
+    pub notification_id: Option<String>,
+    pub url: Option<String>,
+    /// When the item started needing attention (RFC 3339). Within a kind,
+    /// items waiting longest come first.
+    pub since: String,
+}
+
+impl TodayItem {
+    fn new(kind: TodayItemKind, title: String, since: String) -> Self {
+        Self {
+            kind,
+            title,
+            detail: String::new(),
+            repo_id: None,
+            repo_slug: None,
+            worktree_id: None,
+            run_id: None,
+            ticket_id: None,
+            notification_id: None,
+            url: None,
+            since,
+        }
+    }
+}
+
+pub struct Today<'a> {
+    conn: &'a Connection,
+    config: &'a Config,
+}
+
+impl<'a> Today<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self { conn, config }
+    }
+
+    /// Everything needing attention for notification reader `reader`, in
+    /// the repos `viewer` can see.
+    /// Looks up open PRs with `gh`, one call per repo with live worktrees.
+    pub fn items(&self, reader: &str, viewer: &Viewer) -> Result<Vec<TodayItem>> {
+        self.items_with_pr_lookup(reader, viewer, |remote_url| {
+            crate::github::list_open_prs(remote_url).unwrap_or_default()
+        })
+    }
+
+    pub(crate) fn items_with_pr_lookup(
+        &self,
+        reader: &str,
+        viewer: &Viewer,
+        open_prs: impl Fn(&str) -> Vec<GithubPr>,
+    ) -> Result<Vec<TodayItem>> {
+        let mut items = self.agent_items(viewer)?;
+        items.extend(self.review_items(viewer)?);
+        items.extend(self.sla_items(viewer, Utc::now())?);
+        items.extend(self.failing_ci_items(viewer, open_prs)?);
+        items.extend(self.notification_items(reader, viewer)?);
+        items.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.since.cmp(&b.since)));
+        Ok(items)
+    }
+
+    fn agent_items(&self, viewer: &Viewer) -> Result<Vec<TodayItem>> {
+        let owner = viewer.owner_param();
+        let (visible, params) = visible_filter(viewer, "COALESCE(w.repo_id, a.repo_id)", &owner);
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT a.id, a.status, a.prompt, a.started_at, \
+                        COALESCE(w.repo_id, a.repo_id) AS repo_id, r.slug AS repo_slug, \
+                        a.worktree_id, w.slug AS worktree_slug \
+                 FROM agent_runs a \
+                 LEFT JOIN worktrees w ON w.id = a.worktree_id \
+                 LEFT JOIN repos r ON r.id = COALESCE(w.repo_id, a.repo_id) \
+                 WHERE a.status IN ('running', 'waiting_for_feedback') \
+                   AND a.parent_run_id IS NULL{visible}"
+            ),
+            params.as_slice(),
+            |row| {
+                let waiting = row.get::<_, String>("status")? == "waiting_for_feedback";
+                let kind = if waiting {
+                    TodayItemKind::AwaitingFeedback
+                } else {
+                    TodayItemKind::RunningAgent
+                };
+                let prompt: String = row.get("prompt")?;
+                let target: Option<String> = row.get("worktree_slug")?;
+                let mut item = TodayItem::new(
+                    kind,
+                    first_line(&prompt).to_string(),
+                    row.get("started_at")?,
+                );
+                item.detail = target.unwrap_or_else(|| "repo agent".to_string());
+                item.repo_id = row.get("repo_id")?;
+                item.repo_slug = row.get("repo_slug")?;
+                item.worktree_id = row.get("worktree_id")?;
+                item.run_id = Some(row.get("id")?);
+                Ok(item)
+            },
+        )
+    }
+
+    /// One item per worktree with unreviewed finished runs, for the latest.
+    fn review_items(&self, viewer: &Viewer) -> Result<Vec<TodayItem>> {
+        let mut seen = std::collections::HashSet::new();
+        Ok(AgentInbox::new(self.conn)
+            .list_visible(viewer)?
+            .into_iter()
+            .filter(|run| seen.insert(run.worktree_id.clone()))
+            .map(|run| {
+                let since = run.ended_at.clone().unwrap_or(run.started_at.clone());
+                let mut item = TodayItem::new(
+                    TodayItemKind::NeedsReview,
+                    first_line(&run.prompt).to_string(),
+                    since,
+                );
+                item.detail = format!("{} {}", run.worktree_slug, run.status);
+                item.repo_id = Some(run.repo_id);
+                item.repo_slug = Some(run.repo_slug);
+                item.worktree_id = Some(run.worktree_id);
+                item.run_id = Some(run.run_id);
+                item
+            })
+            .collect())
+    }
+
+    fn sla_items(&self, viewer: &Viewer, now: DateTime<Utc>) -> Result<Vec<TodayItem>> {
+        let sla = &self.config.sla;
+        if sla.default_hours.is_none() && sla.priority_hours.is_empty() {
+            return Ok(Vec::new());
+        }
+        let owner = viewer.owner_param();
+        let (visible, params) = visible_filter(viewer, "t.repo_id", &owner);
+        let tickets: Vec<(TodayItem, Option<String>)> = query_collect(
+            self.conn,
+            &format!(
+                "SELECT t.id, t.source_id, t.title, t.priority, t.url, \
+                        COALESCE(t.opened_at, t.synced_at) AS opened_at, \
+                        t.repo_id, r.slug AS repo_slug \
+                 FROM tickets t JOIN repos r ON r.id = t.repo_id \
+                 WHERE t.state != 'closed'{visible}"
+            ),
+            params.as_slice(),
+            |row| {
+                let mut item = TodayItem::new(
+                    TodayItemKind::SlaBreach,
+                    row.get("title")?,
+                    row.get("opened_at")?,
+                );
+                item.detail = row.get("source_id")?;
+                item.repo_id = Some(row.get("repo_id")?);
+                item.repo_slug = Some(row.get("repo_slug")?);
+                item.ticket_id = Some(row.get("id")?);
+                item.url = Some(row.get("url")?).filter(|u: &String| !u.is_empty());
+                Ok((item, row.get("priority")?))
+            },
+        )?;
+        Ok(tickets
+            .into_iter()
+            .filter_map(|(mut item, priority)| {
+                let hours = sla.hours_for(priority.as_deref())?;
+                let opened = DateTime::parse_from_rfc3339(&item.since).ok()?;
+                let deadline = opened.with_timezone(&Utc) + Duration::hours(hours as i64);
+                if deadline > now {
+                    return None;
+                }
+                let overdue = (now - deadline).num_hours();
+                let priority = priority.map(|p| format!(" {p}")).unwrap_or_default();
+                item.detail = format!("{}{priority}, {overdue}h over {hours}h SLA", item.detail);
+                Some(item)
+            })
+            .collect())
+    }
+
+    fn failing_ci_items(
+        &self,
+        viewer: &Viewer,
+        open_prs: impl Fn(&str) -> Vec<GithubPr>,
+    ) -> Result<Vec<TodayItem>> {
+        let owner = viewer.owner_param();
+        let (visible, params) = visible_filter(viewer, "w.repo_id", &owner);
+        let worktrees: Vec<(String, String, String, String, String, String)> = query_collect(
+            self.conn,
+            &format!(
+                "SELECT w.id, w.slug, w.branch, w.repo_id, r.slug AS repo_slug, r.remote_url \
+                 FROM worktrees w JOIN repos r ON r.id = w.repo_id \
+                 WHERE w.status IN {LIVE_STATUSES_SQL}{visible}"
+            ),
+            params.as_slice(),
+            |row| {
+                Ok((
+                    row.get("id")?,
+                    row.get("slug")?,
+                    row.get("branch")?,
+                    row.get("repo_id")?,
+                    row.get("repo_slug")?,
+                    row.get("remote_url")?,
+                ))
+            },
+        )?;
+        let mut prs_by_remote: HashMap<&str, Vec<GithubPr>> = HashMap::new();
+        let now = Utc::now().to_rfc3339();
+        let mut items = Vec::new();
+        for (wt_id, wt_slug, branch, repo_id, repo_slug, remote_url) in &worktrees {
+            let prs = prs_by_remote
+                .entry(remote_url.as_str())
+                .or_insert_with(|| open_prs(remote_url));
+            let Some(pr) = prs
+                .iter()
+                .find(|pr| &pr.head_ref_name == branch && pr.ci_status == "failing")
+            else {
+                continue;
+            };
+            let mut item = TodayItem::new(TodayItemKind::FailingCi, pr.title.clone(), now.clone());
+            item.detail = format!("#{} {wt_slug}", pr.number);
+            item.repo_id = Some(repo_id.clone());
+            item.repo_slug = Some(repo_slug.clone());
+            item.worktree_id = Some(wt_id.clone());
+            item.url = Some(pr.url.clone());
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    fn notification_items(&self, reader: &str, viewer: &Viewer) -> Result<Vec<TodayItem>> {
+        Ok(NotificationManager::new(self.conn)
+            .list_visible(viewer, reader, true, MAX_NOTIFICATIONS)?
+            .into_iter()
+            .map(|n| {
+                let mut item = TodayItem::new(TodayItemKind::Notification, n.title, n.created_at);
+                item.detail = first_line(&n.body).to_string();
+                item.notification_id = Some(n.id);
+                item.url = n.url;
+                item
+            })
+            .collect())
+    }
+}
+
+/// An ` AND` condition on `repo_column` for `viewer` and the params it binds;
+/// empty for the local viewer. `owner` is `viewer.owner_param()`.
+fn visible_filter<'p>(
+    viewer: &Viewer,
+    repo_column: &str,
+    owner: &'p Option<&str>,
+) -> (String, Vec<(&'static str, &'p dyn rusqlite::ToSql)>) {
+    match visible_repo_sql(viewer, repo_column, ":viewer") {
+        Some(condition) => (format!(" AND {condition}"), vec![(":viewer", owner)]),
+        None => (String::new(), Vec::new()),
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::named_params;
+
+    use crate::notifications::{NewNotification, NotificationSeverity, LOCAL_READER};
+    use crate::test_helpers::{insert_test_agent_run, setup_db};
+
+    fn set_run_status(conn: &Connection, run_id: &str, status: &str, ended_at: Option<&str>) {
+        conn.execute(
+            "UPDATE agent_runs SET status = :status, ended_at = :ended_at WHERE id = :id",
+            named_params! { ":status": status, ":ended_at": ended_at, ":id": run_id },
+        )
+        .unwrap();
+    }
+
+    fn pr(branch: &str, ci_status: &str) -> GithubPr {
+        GithubPr {
+            number: 7,
+            title: "Add feature".into(),
+            url: "https://github.com/o/r/pull/7".into(),
+            author: "dev".into(),
+            state: "OPEN".into(),
+            head_ref_name: branch.into(),
+            is_draft: false,
+            review_decision: None,
+            ci_status: ci_status.into(),
+        }
+    }
+
+    #[test]
+    fn items_are_ordered_by_kind_then_age() {
+        let conn = setup_db();
+        insert_test_agent_run(&conn, "run-a", "w1");
+        insert_test_agent_run(&conn, "run-b", "w1");
+        set_run_status(&conn, "run-b", "waiting_for_feedback", None);
+        insert_test_agent_run(&conn, "run-c", "w1");
+        set_run_status(&conn, "run-c", "completed", Some("2024-01-02T00:00:00Z"));
+        conn.execute(
+            "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, priority, \
+             synced_at, opened_at, raw_json) VALUES \
+             ('t1', 'r1', 'github', '12', 'Old bug', 'open', 'High', \
+              '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z', '{}'), \
+             ('t2', 'r1', 'github', '13', 'Low thing', 'open', 'low', \
+              '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z', '{}')",
+            [],
+        )
+        .unwrap();
+        NotificationManager::new(&conn)
+            .record(&NewNotification {
+                kind: "agent_run_failed".into(),
+                severity: NotificationSeverity::Error,
+                title: "Run failed".into(),
+                body: String::new(),
+                url: None,
+                repo_id: None,
+            })
+            .unwrap();
+        let mut config = Config::default();
+        config.sla.priority_hours.insert("high".into(), 24);
+
+        let items = Today::new(&conn, &config)
+            .items_with_pr_lookup(LOCAL_READER, &Viewer::Local, |_| {
+                vec![pr("feat/test", "failing")]
+            })
+            .unwrap();
+        let kinds: Vec<TodayItemKind> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TodayItemKind::AwaitingFeedback,
+                TodayItemKind::FailingCi,
+                TodayItemKind::SlaBreach,
+                TodayItemKind::NeedsReview,
+                TodayItemKind::Notification,
+                TodayItemKind::RunningAgent,
+            ]
+        );
+        assert_eq!(items[1].worktree_id.as_deref(), Some("w1"));
+        assert_eq!(items[2].ticket_id.as_deref(), Some("t1"));
+        assert!(
+            items[2].detail.starts_with("12 High"),
+            "{}",
+            items[2].detail
+        );
+        assert_eq!(items[3].run_id.as_deref(), Some("run-c"));
+        assert_eq!(items[5].run_id.as_deref(), Some("run-a"));
+
+        let items = Today::new(&conn, &config)
+            .items_with_pr_lookup(LOCAL_READER, &Viewer::Local, |_| {
+                vec![pr("feat/test", "passing")]
+            })
+            .unwrap();
+        assert!(items.iter().all(|i| i.kind != TodayItemKind::FailingCi));
+
+        // Another web user sees nothing of r1 once it is private, only the
+        // reader's own notifications.
+        crate::repo_visibility::RepoVisibility::new(&conn)
+            .set_private("r1", "tok-a")
+            .unwrap();
+        let items = Today::new(&conn, &config)
+            .items_with_pr_lookup(LOCAL_READER, &Viewer::web(Some("tok-b")), |_| {
+                vec![pr("feat/test", "failing")]
+            })
+            .unwrap();
+        let kinds: Vec<TodayItemKind> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, [TodayItemKind::Notification]);
+        let items = Today::new(&conn, &config)
+            .items_with_pr_lookup(LOCAL_READER, &Viewer::web(Some("tok-a")), |_| {
+                vec![pr("feat/test", "failing")]
+            })
+            .unwrap();
+        assert_eq!(items.len(), 6);
+    }
+}
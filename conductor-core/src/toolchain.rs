@@ -0,0 +1,351 @@
+//! Detection of a repository's language, package manager and test framework.
+//!
+//! Detection only looks at marker files in the repo root (manifests,
+//! lockfiles, test configs), so it is cheap enough to run on registration
+//! and again whenever the user asks for a refresh.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// The toolchain detected for a repo. Every field is `None` when nothing
+/// recognizable was found.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toolchain {
+    /// Primary language, e.g. `rust`, `typescript`, `python`.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Package manager / build tool, e.g. `cargo`, `pnpm`, `uv`.
+    #[serde(default)]
+    pub package_manager: Option<String>,
+    /// Test framework, e.g. `nextest`, `vitest`, `pytest`.
+    #[serde(default)]
+    pub test_framework: Option<String>,
+}
+
+const JS_TEST_FRAMEWORKS: &[&str] = &["vitest", "jest", "mocha", "ava", "@playwright/test"];
+
+impl Toolchain {
+    /// Inspect `root` and return the toolchain of the first ecosystem whose
+    /// manifest is present. Rust, Go, Node, Python, Ruby and JVM projects are
+    /// recognized, checked in that order.
+    pub fn detect(root: &Path) -> Self {
+        let has = |name: &str| root.join(name).exists();
+        let some = |s: &str| Some(s.to_string());
+
+        if has("Cargo.toml") {
+            let nextest = has(".config/nextest.toml");
+            return Self {
+                language: some("rust"),
+                package_manager: some("cargo"),
+                test_framework: some(if nextest { "nextest" } else { "libtest" }),
+            };
+        }
+        if has("go.mod") {
+            return Self {
+                language: some("go"),
+                package_manager: some("go"),
+                test_framework: some("go test"),
+            };
+        }
+        if has("package.json") {
+            return detect_node(root);
+        }
+        let python_markers = [
+            "pyproject.toml",
+            "setup.py",
+            "requirements.txt",
+            "Pipfile",
+            "pytest.ini",
+        ];
+        if python_markers.iter().any(|m| has(m)) {
+            let pyproject =
+                std::fs::read_to_string(root.join("pyproject.toml")).unwrap_or_default();
+            let pm = if has("uv.lock") {
+                "uv"
+            } else if has("poetry.lock") || pyproject.contains("[tool.poetry]") {
+                "poetry"
+            } else if has("Pipfile") {
+                "pipenv"
+            } else {
+                "pip"
+            };
+            return Self {
+                language: some("python"),
+                package_manager: some(pm),
+                test_framework: some("pytest"),
+            };
+        }
+        if has("Gemfile") {
+            let framework = if has(".rspec") || has("spec") {
+                some("rspec")
+            } else if has("test") {
+                some("minitest")
+            } else {
+                None
+            };
+            return Self {
+                language: some("ruby"),
+                package_manager: some("bundler"),
+                test_framework: framework,
+            };
+        }
+        if has("pom.xml") {
+            return Self {
+                language: some("java"),
+                package_manager: some("maven"),
+                test_framework: some("junit"),
+            };
+        }
+        if has("build.gradle.kts") || has("build.gradle") {
+            let lang = if has("build.gradle.kts") {
+                "kotlin"
+            } else {
+                "java"
+            };
+            return Self {
+                language: some(lang),
+                package_manager: some("gradle"),
+                test_framework: some("junit"),
+            };
+        }
+        Self::default()
+    }
+
+    /// True when nothing was detected.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The command that runs the project's tests, if one can be inferred.
+    pub fn test_command(&self) -> Option<String> {
+        let pm = self.package_manager.as_deref()?;
+        let framework = self.test_framework.as_deref()?;
+        let cmd = match (pm, framework) {
+            ("cargo", "nextest") => "cargo nextest run",
+            ("cargo", _) => "cargo test",
+            ("go", _) => "go test ./...",
+            ("npm" | "pnpm" | "yarn" | "bun", _) => return Some(format!("{pm} test")),
+            ("uv", _) => "uv run pytest",
+            ("poetry", _) => "poetry run pytest",
+            ("pipenv", _) => "pipenv run pytest",
+            ("pip", _) => "pytest",
+            ("bundler", "rspec") => "bundle exec rspec",
+            ("bundler", _) => "bundle exec rake test",
+            ("maven", _) => "mvn test",
+            ("gradle", _) => "gradle test",
+            _ => return None,
+        };
+        Some(cmd.to_string())
+    }
+
+    /// The dependency install command to run in a fresh worktree, or `None`
+    /// when the toolchain resolves dependencies on build (cargo, go, maven,
+    /// gradle) or there is nothing to install.
+    pub fn install_command(&self, root: &Path) -> Option<Vec<String>> {
+        let pm = self.package_manager.as_deref()?;
+        let args: &[&str] = match pm {
+            "npm" | "pnpm" | "yarn" | "bun" => {
+                if !has_node_deps(root) {
+                    return None;
+                }
+                &["install"]
+            }
+            "uv" => &["sync"],
+            "poetry" | "pipenv" | "bundler" => &["install"],
+            _ => return None,
+        };
+        let program = if pm == "bundler" { "bundle" } else { pm };
+        Some(
+            std::iter::once(program)
+                .chain(args.iter().copied())
+                .map(String::from)
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Toolchain {
+    /// `rust (cargo, nextest)`, or `unknown` when nothing was detected.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(language) = &self.language else {
+            return f.write_str("unknown");
+        };
+        f.write_str(language)?;
+        let tools: Vec<&str> = [&self.package_manager, &self.test_framework]
+            .into_iter()
+            .filter_map(|t| t.as_deref())
+            .collect();
+        if !tools.is_empty() {
+            write!(f, " ({})", tools.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn detect_node(root: &Path) -> Toolchain {
+    let manifest: serde_json::Value = std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let has_dep = |name: &str| {
+        ["dependencies", "devDependencies"]
+            .iter()
+            .any(|field| manifest[field].get(name).is_some())
+    };
+
+    let pm = if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        "bun"
+    } else if root.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if root.join("yarn.lock").exists() {
+        "yarn"
+    } else {
+        "npm"
+    };
+    let language = if root.join("tsconfig.json").exists() || has_dep("typescript") {
+        "typescript"
+    } else {
+        "javascript"
+    };
+    // Prefer a known framework from the dependencies; otherwise fall back to
+    // whatever the `test` script runs, ignoring npm's placeholder script.
+    let framework = JS_TEST_FRAMEWORKS
+        .iter()
+        .find(|name| has_dep(name))
+        .map(|name| match *name {
+            "@playwright/test" => "playwright".to_string(),
+            name => name.to_string(),
+        })
+        .or_else(|| {
+            let script = manifest["scripts"]["test"].as_str()?;
+            let first = script.split_whitespace().next()?;
+            (first != "echo").then(|| first.to_string())
+        });
+
+    Toolchain {
+        language: Some(language.to_string()),
+        package_manager: Some(pm.to_string()),
+        test_framework: framework,
+    }
+}
+
+/// Whether `package.json` declares anything for the package manager to install.
+fn has_node_deps(root: &Path) -> bool {
+    std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .is_some_and(|v| {
+            v.get("dependencies").is_some()
+                || v.get("devDependencies").is_some()
+                || v.get("peerDependencies").is_some()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn detect_with(files: &[(&str, &str)]) -> Toolchain {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        Toolchain::detect(dir.path())
+    }
+
+    #[test]
+    fn detects_rust_with_and_without_nextest() {
+        let tc = detect_with(&[("Cargo.toml", "")]);
+        assert_eq!(tc.to_string(), "rust (cargo, libtest)");
+        assert_eq!(tc.test_command().as_deref(), Some("cargo test"));
+
+        let tc = detect_with(&[("Cargo.toml", ""), (".config/nextest.toml", "")]);
+        assert_eq!(tc.test_command().as_deref(), Some("cargo nextest run"));
+    }
+
+    #[test]
+    fn detects_node_package_manager_and_framework() {
+        let tc = detect_with(&[
+            (
+                "package.json",
+                r#"{"devDependencies":{"typescript":"^5","vitest":"^1"}}"#,
+            ),
+            ("pnpm-lock.yaml", ""),
+        ]);
+        assert_eq!(tc.to_string(), "typescript (pnpm, vitest)");
+        assert_eq!(tc.test_command().as_deref(), Some("pnpm test"));
+
+        let tc = detect_with(&[(
+            "package.json",
+            r#"{"scripts":{"test":"mocha --recursive"}}"#,
+        )]);
+        assert_eq!(tc.to_string(), "javascript (npm, mocha)");
+
+        let tc = detect_with(&[(
+            "package.json",
+            r#"{"devDependencies":{"@playwright/test":"^1"}}"#,
+        )]);
+        assert_eq!(tc.test_framework.as_deref(), Some("playwright"));
+    }
+
+    #[test]
+    fn node_placeholder_test_script_is_not_a_framework() {
+        let tc = detect_with(&[(
+            "package.json",
+            r#"{"scripts":{"test":"echo \"Error: no test specified\" && exit 1"}}"#,
+        )]);
+        assert_eq!(tc.test_framework, None);
+        assert_eq!(tc.test_command(), None);
+    }
+
+    #[test]
+    fn detects_python_package_managers() {
+        let tc = detect_with(&[("pyproject.toml", ""), ("uv.lock", "")]);
+        assert_eq!(tc.test_command().as_deref(), Some("uv run pytest"));
+
+        let tc = detect_with(&[("pyproject.toml", "[tool.poetry]\nname = \"x\"\n")]);
+        assert_eq!(tc.package_manager.as_deref(), Some("poetry"));
+
+        let tc = detect_with(&[("requirements.txt", "")]);
+        assert_eq!(tc.test_command().as_deref(), Some("pytest"));
+    }
+
+    #[test]
+    fn unrecognized_repo_is_empty() {
+        let tc = detect_with(&[("README.md", "")]);
+        assert!(tc.is_empty());
+        assert_eq!(tc.to_string(), "unknown");
+        assert_eq!(tc.test_command(), None);
+    }
+
+    #[test]
+    fn install_command_skips_node_without_deps() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"name":"x"}"#).unwrap();
+        let tc = Toolchain::detect(dir.path());
+        assert_eq!(tc.install_command(dir.path()), None);
+
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"dependencies":{"left-pad":"1"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("yarn.lock"), "").unwrap();
+        let tc = Toolchain::detect(dir.path());
+        assert_eq!(
+            tc.install_command(dir.path()),
+            Some(vec!["yarn".to_string(), "install".to_string()])
+        );
+    }
+
+    #[test]
+    fn install_command_is_none_for_build_time_resolvers() {
+        let tc = detect_with(&[("Cargo.toml", "")]);
+        assert_eq!(tc.install_command(Path::new("/nonexistent")), None);
+    }
+}
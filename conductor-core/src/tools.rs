@@ -0,0 +1,352 @@
+//! Availability of the external tools conductor shells out to.
+//!
+//! [`ToolReport::probe`] runs each tool's version command once; the result is
+//! cached in `~/.conductor/tools.json` so startup checks don't spawn a dozen
+//! processes on every command. Frontends read the report to hide actions whose
+//! tool is missing, and [`crate::command::run_command`] turns a missing binary
+//! into [`ConductorError::ToolNotInstalled`] instead of a bare spawn error.
+//!
+//! [`ConductorError::ToolNotInstalled`]: crate::error::ConductorError::ToolNotInstalled
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// How long a cached report is trusted before startup probes again.
+pub const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Each version command gets this long before the tool counts as missing.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An external tool conductor can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tool {
+    /// Binary name looked up on `PATH`.
+    pub name: &'static str,
+    version_args: &'static [&'static str],
+    /// What conductor uses it for.
+    pub purpose: &'static str,
+    /// Where to get it.
+    pub install: &'static str,
+    /// Core workflows break without it; startup warns when it is missing.
+    pub required: bool,
+}
+
+pub const GIT: Tool = Tool {
+    name: "git",
+    version_args: &["--version"],
+    purpose: "worktrees, branches and pushes",
+    install: "https://git-scm.com/downloads",
+    required: true,
+};
+pub const GH: Tool = Tool {
+    name: "gh",
+    version_args: &["--version"],
+    purpose: "GitHub issues, pull requests and CI status",
+    install: "https://cli.github.com",
+    required: true,
+};
+pub const CLAUDE: Tool = Tool {
+    name: "claude",
+    version_args: &["--version"],
+    purpose: "running agents",
+    install: "https://docs.anthropic.com/en/docs/claude-code",
+    required: true,
+};
+pub const TMUX: Tool = Tool {
+    name: "tmux",
+    version_args: &["-V"],
+    purpose: "the managed session and `agent attach` windows",
+    install: "https://github.com/tmux/tmux",
+    required: false,
+};
+pub const ACLI: Tool = Tool {
+    name: "acli",
+    version_args: &["--version"],
+    purpose: "Jira issue sources",
+    install: "https://developer.atlassian.com/cloud/acli/",
+    required: false,
+};
+pub const DOCKER: Tool = Tool {
+    name: "docker",
+    version_args: &["--version"],
+    purpose: "docker and devcontainer agent sandboxes",
+    install: "https://docs.docker.com/get-docker/",
+    required: false,
+};
+
+/// Package managers a repo's detected toolchain may call for.
+const PACKAGE_MANAGERS: &[Tool] = &[
+    package_manager("cargo", "https://rustup.rs"),
+    package_manager("npm", "https://nodejs.org"),
+    package_manager("pnpm", "https://pnpm.io/installation"),
+    package_manager("yarn", "https://yarnpkg.com/getting-started/install"),
+    package_manager("bun", "https://bun.sh"),
+    package_manager("uv", "https://docs.astral.sh/uv/"),
+    package_manager("poetry", "https://python-poetry.org/docs/"),
+    Tool {
+        name: "go",
+        version_args: &["version"],
+        purpose: "repo toolchains (package manager)",
+        install: "https://go.dev/dl/",
+        required: false,
+    },
+];
+
+const fn package_manager(name: &'static str, install: &'static str) -> Tool {
+    Tool {
+        name,
+        version_args: &["--version"],
+        purpose: "repo toolchains (package manager)",
+        install,
+        required: false,
+    }
+}
+
+/// Every tool [`ToolReport::probe`] checks, required ones first.
+pub fn all() -> Vec<Tool> {
+    let mut tools = vec![GIT, GH, CLAUDE, TMUX, ACLI, DOCKER];
+    tools.extend_from_slice(PACKAGE_MANAGERS);
+    tools
+}
+
+/// The known tool whose binary is `name`, if any.
+pub fn find(name: &str) -> Option<Tool> {
+    all().into_iter().find(|t| t.name == name)
+}
+
+/// One tool's probe result.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolStatus {
+    pub name: String,
+    pub available: bool,
+    /// First line of the version output, e.g. `gh version 2.62.0 (2024-11-14)`.
+    pub version: Option<String>,
+    pub purpose: String,
+    pub install: String,
+    pub required: bool,
+}
+
+/// Probe results for every known tool.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolReport {
+    /// RFC 3339 timestamp.
+    pub probed_at: String,
+    pub tools: Vec<ToolStatus>,
+}
+
+impl ToolReport {
+    /// Run every tool's version command, in parallel.
+    pub fn probe() -> Self {
+        let tools = std::thread::scope(|s| {
+            all()
+                .into_iter()
+                .map(|tool| s.spawn(move || probe_tool(tool)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|h| h.join().expect("tool probe panicked"))
+                .collect()
+        });
+        Self {
+            probed_at: Utc::now().to_rfc3339(),
+            tools,
+        }
+    }
+
+    /// The cached report if it is younger than `max_age`, otherwise a fresh
+    /// probe, which is written back to the cache.
+    pub fn current(max_age: Duration) -> Self {
+        Self::current_at(&cache_path(), max_age)
+    }
+
+    /// Probe now and replace the cache.
+    pub fn refresh() -> Self {
+        Self::refresh_at(&cache_path())
+    }
+
+    fn current_at(path: &Path, max_age: Duration) -> Self {
+        match Self::load(path) {
+            Some(cached) if !cached.is_stale(max_age) => cached,
+            _ => Self::refresh_at(path),
+        }
+    }
+
+    fn refresh_at(path: &Path) -> Self {
+        let report = Self::probe();
+        if let Err(e) = report.save(path) {
+            tracing::warn!("could not cache tool report at {}: {e}", path.display());
+        }
+        report
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// When the probe ran, or `None` if `probed_at` doesn't parse.
+    pub fn probed_at_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.probed_at)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    fn is_stale(&self, max_age: Duration) -> bool {
+        let Some(probed_at) = self.probed_at_time() else {
+            return true;
+        };
+        let age = Utc::now().signed_duration_since(probed_at);
+        age.to_std().is_ok_and(|age| age > max_age)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ToolStatus> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    /// Whether `tool` was found. Tools the report doesn't cover count as
+    /// available, so a stale cache never hides a feature.
+    pub fn has(&self, tool: Tool) -> bool {
+        self.get(tool.name).is_none_or(|t| t.available)
+    }
+
+    /// Required tools that were not found.
+    pub fn missing_required(&self) -> impl Iterator<Item = &ToolStatus> {
+        self.tools.iter().filter(|t| t.required && !t.available)
+    }
+}
+
+/// `~/.conductor/tools.json`.
+pub fn cache_path() -> PathBuf {
+    crate::config::conductor_dir().join("tools.json")
+}
+
+fn probe_tool(tool: Tool) -> ToolStatus {
+    let mut cmd = Command::new(tool.name);
+    cmd.args(tool.version_args);
+    let version = crate::command::output_with_timeout(&mut cmd, Some(PROBE_TIMEOUT))
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            // Some tools print their version on stderr.
+            let out = if o.stdout.is_empty() {
+                o.stderr
+            } else {
+                o.stdout
+            };
+            String::from_utf8_lossy(&out)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+    ToolStatus {
+        name: tool.name.to_string(),
+        available: version.is_some(),
+        version: version.filter(|v| !v.is_empty()),
+        purpose: tool.purpose.to_string(),
+        install: tool.install.to_string(),
+        required: tool.required,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(name: &str, available: bool, required: bool) -> ToolStatus {
+        ToolStatus {
+            name: name.into(),
+            available,
+            version: available.then(|| format!("{name} 1.0")),
+            purpose: String::new(),
+            install: String::new(),
+            required,
+        }
+    }
+
+    #[test]
+    fn test_probe_finds_sh_and_not_a_missing_binary() {
+        let sh = probe_tool(Tool {
+            name: "sh",
+            version_args: &["-c", "echo 'sh 5.2'"],
+            purpose: "",
+            install: "",
+            required: false,
+        });
+        assert!(sh.available);
+        assert_eq!(sh.version.as_deref(), Some("sh 5.2"));
+
+        let missing = probe_tool(Tool {
+            name: "conductor-no-such-tool",
+            version_args: &["--version"],
+            purpose: "",
+            install: "",
+            required: true,
+        });
+        assert!(!missing.available);
+        assert!(missing.version.is_none());
+    }
+
+    #[test]
+    fn test_has_and_missing_required() {
+        let report = ToolReport {
+            probed_at: Utc::now().to_rfc3339(),
+            tools: vec![
+                status("git", true, true),
+                status("gh", false, true),
+                status("tmux", false, false),
+            ],
+        };
+        assert!(report.has(GIT));
+        assert!(!report.has(GH));
+        assert!(!report.has(TMUX));
+        assert!(report.has(DOCKER), "unprobed tools count as available");
+        let missing: Vec<_> = report.missing_required().map(|t| &t.name).collect();
+        assert_eq!(missing, ["gh"]);
+    }
+
+    #[test]
+    fn test_current_uses_fresh_cache_and_reprobes_stale_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tools.json");
+        let cached = ToolReport {
+            probed_at: Utc::now().to_rfc3339(),
+            tools: vec![status("git", true, true)],
+        };
+        cached.save(&path).unwrap();
+        assert_eq!(ToolReport::current_at(&path, CACHE_TTL), cached);
+
+        let stale = ToolReport {
+            probed_at: (Utc::now() - chrono::Duration::days(2)).to_rfc3339(),
+            ..cached
+        };
+        stale.save(&path).unwrap();
+        let fresh = ToolReport::current_at(&path, CACHE_TTL);
+        assert_eq!(fresh.tools.len(), all().len());
+        assert_eq!(ToolReport::load(&path), Some(fresh));
+    }
+
+    #[test]
+    fn test_find_known_tool() {
+        assert_eq!(find("gh"), Some(GH));
+        assert_eq!(find("pnpm").map(|t| t.required), Some(false));
+        assert!(find("nope").is_none());
+    }
+}
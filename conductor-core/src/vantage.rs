@@ -233,7 +233,7 @@ fn run_sdlc(sdlc_root: &str, args: &[&str]) -> Result<std::process::Output> {
     }
     cmd.args(args);
 
-    let output = cmd.output().map_err(|e| {
+    let output = crate::command::output(&mut cmd).map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             ConductorError::TicketSync(
                 "sdlc not found. Install the Vantage SDLC CLI and ensure it is on your PATH."
@@ -489,6 +489,8 @@ fn parse_vantage_deliverable(d: &serde_json::Value) -> TicketInput {
         blocked_by,
         children,
         parent: None,
+        sprint: None,
+        sprint_current: false,
     }
 }
 
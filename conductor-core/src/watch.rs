@@ -0,0 +1,414 @@
+//! Watch subscriptions on tickets and worktrees.
+//!
+//! Watching an item snapshots its state and PR summary. Each notification poll
+//! ([`WatchManager::poll_changes`]) compares the stored snapshot with the
+//! current values and returns what changed, so a change is reported once no
+//! matter how many pollers are running.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchTarget {
+    Ticket,
+    Worktree,
+}
+
+impl std::fmt::Display for WatchTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ticket => "ticket",
+            Self::Worktree => "worktree",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for WatchTarget {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ticket" => Ok(Self::Ticket),
+            "worktree" => Ok(Self::Worktree),
+            _ => Err(format!(
+                "unknown watch target: {s} (expected ticket or worktree)"
+            )),
+        }
+    }
+}
+
+crate::impl_sql_enum!(WatchTarget);
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watch {
+    pub target_type: WatchTarget,
+    pub target_id: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchChangeKind {
+    /// Worktree status or ticket state changed.
+    StateChanged { from: String, to: String },
+    /// The associated pull request's state, draft flag or review decision changed.
+    PrUpdated {
+        from: Option<String>,
+        to: String,
+        url: String,
+    },
+}
+
+/// A change on a watched item, returned by [`WatchManager::poll_changes`].
+#[derive(Debug, Clone)]
+pub struct WatchChange {
+    pub target_type: WatchTarget,
+    pub target_id: String,
+    /// Worktree slug or ticket `#source_id title`, for notification text.
+    pub label: String,
+    pub repo_slug: String,
+    pub kind: WatchChangeKind,
+}
+
+/// Current values for a watched item.
+struct Snapshot {
+    label: String,
+    repo_slug: String,
+    state: String,
+    pr: Option<(String, String)>,
+}
+
+pub struct WatchManager<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> WatchManager<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Start watching an item. Watching an already-watched item is a no-op.
+    pub fn watch(&self, target: WatchTarget, target_id: &str) -> Result<Watch> {
+        let snap = self
+            .snapshot(target, target_id)?
+            .ok_or_else(|| match target {
+                WatchTarget::Ticket => ConductorError::TicketNotFound {
+                    id: target_id.to_string(),
+                },
+                WatchTarget::Worktree => ConductorError::WorktreeNotFound {
+                    slug: target_id.to_string(),
+                },
+            })?;
+        self.conn.execute(
+            "INSERT OR IGNORE INTO watches \
+                 (target_type, target_id, last_state, last_pr, created_at) \
+             VALUES (:target_type, :target_id, :state, :pr, :created_at)",
+            named_params! {
+                ":target_type": target,
+                ":target_id": target_id,
+                ":state": snap.state,
+                ":pr": snap.pr.map(|(summary, _)| summary),
+                ":created_at": Utc::now().to_rfc3339(),
+            },
+        )?;
+        self.get(target, target_id)?
+            .ok_or_else(|| ConductorError::Database(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// Stop watching an item. Returns `false` when it was not watched.
+    pub fn unwatch(&self, target: WatchTarget, target_id: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM watches WHERE target_type = :target_type AND target_id = :target_id",
+            named_params! { ":target_type": target, ":target_id": target_id },
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Flip the watch on an item. Returns `true` when the item is now watched.
+    pub fn toggle(&self, target: WatchTarget, target_id: &str) -> Result<bool> {
+        if self.unwatch(target, target_id)? {
+            Ok(false)
+        } else {
+            self.watch(target, target_id)?;
+            Ok(true)
+        }
+    }
+
+    pub fn get(&self, target: WatchTarget, target_id: &str) -> Result<Option<Watch>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT target_type, target_id, created_at FROM watches \
+                 WHERE target_type = :target_type AND target_id = :target_id",
+                named_params! { ":target_type": target, ":target_id": target_id },
+                row_to_watch,
+            )
+            .optional()?)
+    }
+
+    pub fn list(&self) -> Result<Vec<Watch>> {
+        self.list_visible(&Viewer::Local)
+    }
+
+    /// Like [`Self::list`], leaving out tickets and worktrees of repos that
+    /// are private to someone other than `viewer`.
+    pub fn list_visible(&self, viewer: &Viewer) -> Result<Vec<Watch>> {
+        let Some(visible) =
+            visible_repo_sql(viewer, "COALESCE(t.repo_id, w.repo_id, '')", ":viewer")
+        else {
+            return query_collect(
+                self.conn,
+                "SELECT target_type, target_id, created_at FROM watches ORDER BY created_at",
+                [],
+                row_to_watch,
+            );
+        };
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT x.target_type, x.target_id, x.created_at FROM watches x \
+                 LEFT JOIN tickets t ON x.target_type = 'ticket' AND t.id = x.target_id \
+                 LEFT JOIN worktrees w ON x.target_type = 'worktree' AND w.id = x.target_id \
+                 WHERE {visible} ORDER BY x.created_at"
+            ),
+            named_params! { ":viewer": viewer.owner_param() },
+            row_to_watch,
+        )
+    }
+
+    /// IDs of watched items of one kind.
+    pub fn watched_ids(&self, target: WatchTarget) -> Result<HashSet<String>> {
+        Ok(query_collect(
+            self.conn,
+            "SELECT target_id FROM watches WHERE target_type = :target_type",
+            named_params! { ":target_type": target },
+            |row| row.get(0),
+        )?
+        .into_iter()
+        .collect())
+    }
+
+    /// Whether a worktree is watched directly or through its linked ticket.
+    pub fn covers_worktree(&self, worktree_id: &str) -> Result<bool> {
+        Ok(self.conn.query_row(
+            "SELECT EXISTS ( \
+                 SELECT 1 FROM watches \
+                 WHERE (target_type = 'worktree' AND target_id = :id) \
+                    OR (target_type = 'ticket' AND target_id = \
+                        (SELECT ticket_id FROM worktrees WHERE id = :id)))",
+            named_params! { ":id": worktree_id },
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Compare every watch against the item's current state, record the new
+    /// values, and return the changes. Watches on deleted items are dropped.
+    pub fn poll_changes(&self) -> Result<Vec<WatchChange>> {
+        let mut changes = Vec::new();
+        let rows: Vec<(WatchTarget, String, Option<String>, Option<String>)> = query_collect(
+            self.conn,
+            "SELECT target_type, target_id, last_state, last_pr FROM watches",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        for (target, target_id, last_state, last_pr) in rows {
+            let Some(snap) = self.snapshot(target, &target_id)? else {
+                self.unwatch(target, &target_id)?;
+                continue;
+            };
+            let pr_summary = snap.pr.as_ref().map(|(summary, _)| summary.clone());
+            if last_state.as_deref() == Some(snap.state.as_str()) && last_pr == pr_summary {
+                continue;
+            }
+            let change = |kind| WatchChange {
+                target_type: target,
+                target_id: target_id.clone(),
+                label: snap.label.clone(),
+                repo_slug: snap.repo_slug.clone(),
+                kind,
+            };
+            if let Some(from) = last_state.filter(|s| *s != snap.state) {
+                changes.push(change(WatchChangeKind::StateChanged {
+                    from,
+                    to: snap.state.clone(),
+                }));
+            }
+            if let Some((to, url)) = snap.pr.clone().filter(|(s, _)| last_pr.as_ref() != Some(s)) {
+                changes.push(change(WatchChangeKind::PrUpdated {
+                    from: last_pr,
+                    to,
+                    url,
+                }));
+            }
+            self.conn.execute(
+                "UPDATE watches SET last_state = :state, last_pr = :pr \
+                 WHERE target_type = :target_type AND target_id = :target_id",
+                named_params! {
+                    ":state": snap.state,
+                    ":pr": pr_summary,
+                    ":target_type": target,
+                    ":target_id": target_id,
+                },
+            )?;
+        }
+        Ok(changes)
+    }
+
+    fn snapshot(&self, target: WatchTarget, target_id: &str) -> Result<Option<Snapshot>> {
+        // The PR for a ticket is the ticket itself when it is a synced PR; for a
+        // worktree it is the synced PR linked to it or opened from its branch.
+        let sql = match target {
+            WatchTarget::Ticket => {
+                "SELECT '#' || t.source_id || ' ' || t.title AS label, r.slug AS repo_slug, \
+                        t.state, \
+                        CASE WHEN t.source_type = 'github_pr' THEN t.raw_json END AS pr_json, \
+                        t.url AS pr_url \
+                 FROM tickets t JOIN repos r ON r.id = t.repo_id \
+                 WHERE t.id = :id"
+            }
+            WatchTarget::Worktree => {
+                "SELECT w.slug AS label, r.slug AS repo_slug, w.status AS state, \
+                        p.raw_json AS pr_json, p.url AS pr_url \
+                 FROM worktrees w JOIN repos r ON r.id = w.repo_id \
+                 LEFT JOIN tickets p ON p.id = ( \
+                     SELECT id FROM tickets \
+                     WHERE repo_id = w.repo_id AND source_type = 'github_pr' \
+                       AND (id = w.ticket_id \
+                            OR json_extract(raw_json, '$.headRefName') = w.branch) \
+                     ORDER BY synced_at DESC LIMIT 1) \
+                 WHERE w.id = :id"
+            }
+        };
+        Ok(self
+            .conn
+            .query_row(sql, named_params! { ":id": target_id }, |row| {
+                let pr_json: Option<String> = row.get("pr_json")?;
+                let pr_url: Option<String> = row.get("pr_url")?;
+                Ok(Snapshot {
+                    label: row.get("label")?,
+                    repo_slug: row.get("repo_slug")?,
+                    state: row.get("state")?,
+                    pr: pr_json
+                        .as_deref()
+                        .and_then(pr_summary)
+                        .map(|s| (s, pr_url.unwrap_or_default())),
+                })
+            })
+            .optional()?)
+    }
+}
+
+/// One-line summary of the PR fields worth notifying on, from a synced
+/// `github_pr` ticket's `raw_json`, e.g. `open, draft, review: APPROVED`.
+fn pr_summary(raw_json: &str) -> Option<String> {
+    let pr: serde_json::Value = serde_json::from_str(raw_json).ok()?;
+    let mut parts = vec![pr["state"].as_str()?.to_lowercase()];
+    if pr["isDraft"].as_bool() == Some(true) {
+        parts.push("draft".into());
+    }
+    if let Some(decision) = pr["reviewDecision"].as_str().filter(|d| !d.is_empty()) {
+        parts.push(format!("review: {decision}"));
+    }
+    Some(parts.join(", "))
+}
+
+fn row_to_watch(row: &rusqlite::Row<'_>) -> rusqlite::Result<Watch> {
+    Ok(Watch {
+        target_type: row.get(0)?,
+        target_id: row.get(1)?,
+        created_at: row.get(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = crate::test_helpers::setup_db();
+        conn.execute(
+            "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, labels, url, synced_at, raw_json) \
+             VALUES ('pr1', 'r1', 'github_pr', '7', 'Add login', '', 'open', '[]', 'https://github.com/o/r/pull/7', \
+                     '2024-01-01T00:00:00Z', '{\"state\":\"OPEN\",\"isDraft\":true,\"headRefName\":\"feat/test\"}')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn watch_toggle_and_coverage() {
+        let conn = setup();
+        let mgr = WatchManager::new(&conn);
+        assert!(!mgr.covers_worktree("w1").unwrap());
+        assert!(mgr.toggle(WatchTarget::Worktree, "w1").unwrap());
+        assert!(mgr.covers_worktree("w1").unwrap());
+        assert_eq!(mgr.watched_ids(WatchTarget::Worktree).unwrap().len(), 1);
+        assert!(!mgr.toggle(WatchTarget::Worktree, "w1").unwrap());
+        assert!(mgr.list().unwrap().is_empty());
+
+        // Watching the linked ticket covers the worktree too.
+        conn.execute("UPDATE worktrees SET ticket_id = 'pr1' WHERE id = 'w1'", [])
+            .unwrap();
+        mgr.watch(WatchTarget::Ticket, "pr1").unwrap();
+        assert!(mgr.covers_worktree("w1").unwrap());
+
+        assert!(matches!(
+            mgr.watch(WatchTarget::Ticket, "missing"),
+            Err(ConductorError::TicketNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn poll_reports_state_and_pr_changes_once() {
+        let conn = setup();
+        let mgr = WatchManager::new(&conn);
+        mgr.watch(WatchTarget::Worktree, "w1").unwrap();
+        assert!(mgr.poll_changes().unwrap().is_empty(), "no change yet");
+
+        conn.execute("UPDATE worktrees SET status = 'merged' WHERE id = 'w1'", [])
+            .unwrap();
+        conn.execute(
+            "UPDATE tickets SET raw_json = '{\"state\":\"OPEN\",\"reviewDecision\":\"APPROVED\",\"headRefName\":\"feat/test\"}' \
+             WHERE id = 'pr1'",
+            [],
+        )
+        .unwrap();
+
+        let changes = mgr.poll_changes().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0].kind,
+            WatchChangeKind::StateChanged {
+                from: "active".into(),
+                to: "merged".into()
+            }
+        );
+        assert_eq!(
+            changes[1].kind,
+            WatchChangeKind::PrUpdated {
+                from: Some("open, draft".into()),
+                to: "open, review: APPROVED".into(),
+                url: "https://github.com/o/r/pull/7".into(),
+            }
+        );
+        assert!(mgr.poll_changes().unwrap().is_empty(), "reported once");
+
+        conn.execute("DELETE FROM worktrees WHERE id = 'w1'", [])
+            .unwrap();
+        assert!(mgr.poll_changes().unwrap().is_empty());
+        assert!(
+            mgr.list().unwrap().is_empty(),
+            "watch on deleted item dropped"
+        );
+    }
+}
@@ -176,6 +176,17 @@ fn inject_ticket_variables(
     set_input(merged_inputs, "ticket_body", ticket.body.clone());
     set_input(merged_inputs, "ticket_url", ticket.url.clone());
     set_input(merged_inputs, "ticket_raw_json", ticket.raw_json.clone());
+    // Custom fields from the source's `field_map`, e.g. `{{ticket_field_team}}`.
+    for (name, value) in crate::tickets::field_map::custom_fields(&ticket.raw_json) {
+        set_input(
+            merged_inputs,
+            &format!(
+                "{}{name}",
+                crate::tickets::field_map::CUSTOM_FIELD_VAR_PREFIX
+            ),
+            value,
+        );
+    }
 }
 
 fn inject_repo_variables(repo: &crate::repo::Repo, merged_inputs: &mut HashMap<String, String>) {
@@ -1711,6 +1722,8 @@ mod tests {
             assignee: None,
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         }
     }
 
@@ -1744,6 +1757,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inject_ticket_variables_exposes_custom_fields() {
+        let mut ticket = make_ticket();
+        ticket.raw_json =
+            r#"{"fields":{},"conductor_fields":{"team":"Payments","severity":"S1"}}"#.into();
+        let mut inputs = HashMap::new();
+        inject_ticket_variables(&ticket, &mut inputs);
+        assert_eq!(inputs["ticket_field_team"], "Payments");
+        assert_eq!(inputs["ticket_field_severity"], "S1");
+    }
+
     #[test]
     fn inject_worktree_variables_populates_branch_and_base() {
         let conn = crate::test_helpers::setup_db();
@@ -152,7 +152,7 @@ pub(super) fn run_gh_json(
     if let Some(t) = token {
         cmd.env("GH_TOKEN", t);
     }
-    let output = match cmd.output() {
+    let output = match crate::command::output(&mut cmd) {
         Ok(o) => o,
         Err(e) => {
             tracing::warn!("gh command failed: {e}");
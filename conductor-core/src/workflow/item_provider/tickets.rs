@@ -60,11 +60,35 @@ impl ItemProvider for TicketsProvider {
         Ok(Some(Box::new(scope)))
     }
 
+    fn validate_filter(&self, filter: &HashMap<String, String>) -> crate::error::Result<()> {
+        for (key, value) in filter {
+            match (key.as_str(), value.as_str()) {
+                ("sprint", "current") | ("assigned", "me") => {}
+                ("sprint", other) => {
+                    return Err(ConductorError::Workflow(format!(
+                        "filter.sprint = '{other}' is not supported — only \"current\" is allowed"
+                    )));
+                }
+                ("assigned", other) => {
+                    return Err(ConductorError::Workflow(format!(
+                        "filter.assigned = '{other}' is not supported — only \"me\" is allowed"
+                    )));
+                }
+                (other, _) => {
+                    return Err(ConductorError::Workflow(format!(
+                        "filter.{other} has no effect when over = tickets (supported: sprint, assigned)"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn items(
         &self,
         ctx: &ProviderContext<'_>,
         scope: Option<&dyn Any>,
-        _filter: &HashMap<String, String>,
+        filter: &HashMap<String, String>,
     ) -> Result<Vec<FanOutItem>> {
         use crate::tickets::TicketSyncer;
 
@@ -87,6 +111,21 @@ impl ItemProvider for TicketsProvider {
         };
 
         let ts_opt = scope.and_then(|s| s.downcast_ref::<TicketScope>());
+        // `filter = { sprint = "current" }` scopes automation to the active sprint.
+        let current_sprint = filter.get("sprint").is_some_and(|v| v == "current");
+        // `filter = { assigned = "me" }` scopes it to tickets assigned to the
+        // configured `[identity]`.
+        let assigned_to_me = filter.get("assigned").is_some_and(|v| v == "me");
+        if assigned_to_me && ctx.config.identity.is_empty() {
+            return Err(ConductorError::Workflow(
+                "filter.assigned = \"me\" needs an [identity] in config.toml".to_string(),
+            ));
+        }
+        let in_scope = |t: &crate::tickets::Ticket| {
+            !assigned_to_me
+                || crate::tickets::queue_reasons(t, &ctx.config.identity)
+                    .contains(&crate::tickets::QueueReason::Assigned)
+        };
 
         let items = match ts_opt {
             Some(TicketScope::TicketId(ticket_id)) => match syncer.get_by_id(ticket_id) {
@@ -100,17 +139,21 @@ impl ItemProvider for TicketsProvider {
                 Err(e) => return Err(e),
             },
             Some(TicketScope::Label(label)) => {
-                let tickets = syncer
-                    .list_filtered(Some(repo_id), &ticket_filter(vec![label.clone()], false))?;
-                collect_fan_out_items(tickets, ticket_item)
+                let tickets = syncer.list_filtered(
+                    Some(repo_id),
+                    &ticket_filter(vec![label.clone()], false, current_sprint),
+                )?;
+                collect_fan_out_items(tickets.into_iter().filter(in_scope), ticket_item)
             }
             Some(TicketScope::Unlabeled) => {
-                let tickets = syncer.list_filtered(Some(repo_id), &ticket_filter(vec![], true))?;
-                collect_fan_out_items(tickets, ticket_item)
+                let tickets = syncer
+                    .list_filtered(Some(repo_id), &ticket_filter(vec![], true, current_sprint))?;
+                collect_fan_out_items(tickets.into_iter().filter(in_scope), ticket_item)
             }
             None => {
-                let tickets = syncer.list_filtered(Some(repo_id), &ticket_filter(vec![], false))?;
-                collect_fan_out_items(tickets, ticket_item)
+                let tickets = syncer
+                    .list_filtered(Some(repo_id), &ticket_filter(vec![], false, current_sprint))?;
+                collect_fan_out_items(tickets.into_iter().filter(in_scope), ticket_item)
             }
         };
 
@@ -147,12 +190,17 @@ impl ItemProvider for TicketsProvider {
     }
 }
 
-fn ticket_filter(labels: Vec<String>, unlabeled_only: bool) -> crate::tickets::TicketFilter {
+fn ticket_filter(
+    labels: Vec<String>,
+    unlabeled_only: bool,
+    current_sprint_only: bool,
+) -> crate::tickets::TicketFilter {
     crate::tickets::TicketFilter {
         labels,
         search: None,
         include_closed: false,
         unlabeled_only,
+        current_sprint_only,
     }
 }
 
@@ -235,6 +283,7 @@ mod tests {
                     search: None,
                     include_closed: false,
                     unlabeled_only: false,
+                    current_sprint_only: false,
                 },
             )
             .unwrap();
@@ -305,6 +354,80 @@ mod tests {
         assert_eq!(items[0].item_ref, "21");
     }
 
+    #[test]
+    fn test_tickets_items_current_sprint_filter() {
+        let conn = test_helpers::setup_db();
+        let config = crate::config::Config::default();
+        let syncer = TicketSyncer::new(&conn);
+        let current = crate::tickets::TicketInput {
+            sprint: Some("Sprint 12".to_string()),
+            sprint_current: true,
+            ..test_helpers::make_ticket("30", "Current")
+        };
+        syncer
+            .upsert_tickets("r1", &[current, test_helpers::make_ticket("31", "Backlog")])
+            .unwrap();
+
+        let provider = TicketsProvider::new(Some("r1".into()));
+        let mut filter = HashMap::new();
+        filter.insert("sprint".to_string(), "current".to_string());
+        provider.validate_filter(&filter).unwrap();
+
+        let ctx = test_helpers::make_provider_ctx(&conn, &config);
+        let items = provider.items(&ctx, None, &filter).unwrap();
+        assert_eq!(items.len(), 1, "only the current-sprint ticket returned");
+        assert_eq!(items[0].item_ref, "30");
+    }
+
+    #[test]
+    fn test_tickets_items_assigned_to_me_filter() {
+        let conn = test_helpers::setup_db();
+        let mut config = crate::config::Config::default();
+        let syncer = TicketSyncer::new(&conn);
+        let mine = crate::tickets::TicketInput {
+            assignee: Some("octocat".to_string()),
+            ..test_helpers::make_ticket("40", "Mine")
+        };
+        let mentioned = crate::tickets::TicketInput {
+            body: "cc @octocat".to_string(),
+            ..test_helpers::make_ticket("41", "Mentioned")
+        };
+        syncer.upsert_tickets("r1", &[mine, mentioned]).unwrap();
+
+        let provider = TicketsProvider::new(Some("r1".into()));
+        let mut filter = HashMap::new();
+        filter.insert("assigned".to_string(), "me".to_string());
+        provider.validate_filter(&filter).unwrap();
+
+        let ctx = test_helpers::make_provider_ctx(&conn, &config);
+        assert!(
+            provider.items(&ctx, None, &filter).is_err(),
+            "no identity configured"
+        );
+
+        config.identity.github = Some("octocat".to_string());
+        let ctx = test_helpers::make_provider_ctx(&conn, &config);
+        let items = provider.items(&ctx, None, &filter).unwrap();
+        assert_eq!(items.len(), 1, "mentions are not assignments");
+        assert_eq!(items[0].item_ref, "40");
+    }
+
+    #[test]
+    fn test_tickets_validate_filter_rejects_unknown_values() {
+        let provider = TicketsProvider::new(Some("r1".into()));
+        let mut filter = HashMap::new();
+        filter.insert("sprint".to_string(), "next".to_string());
+        assert!(provider.validate_filter(&filter).is_err());
+
+        let mut filter = HashMap::new();
+        filter.insert("assigned".to_string(), "alice".to_string());
+        assert!(provider.validate_filter(&filter).is_err());
+
+        let mut filter = HashMap::new();
+        filter.insert("status".to_string(), "open".to_string());
+        assert!(provider.validate_filter(&filter).is_err());
+    }
+
     #[test]
     fn test_tickets_dependencies_empty_when_no_items() {
         let conn = test_helpers::setup_db();
@@ -337,6 +460,7 @@ mod tests {
                     search: None,
                     include_closed: false,
                     unlabeled_only: false,
+                    current_sprint_only: false,
                 },
             )
             .unwrap();
@@ -356,6 +356,9 @@ mod tests {
                 completed_at: None,
                 model: None,
                 base_branch: None,
+                abandon_reason: None,
+                missing_since: None,
+                ticket_change: None,
             },
             Worktree {
                 id: "w2".into(),
@@ -369,6 +372,9 @@ mod tests {
                 completed_at: None,
                 model: None,
                 base_branch: None,
+                abandon_reason: None,
+                missing_since: None,
+                ticket_change: None,
             },
         ];
         let prs = vec![GithubPr {
@@ -424,6 +430,7 @@ mod tests {
                     search: None,
                     include_closed: false,
                     unlabeled_only: false,
+                    current_sprint_only: false,
                 },
             )
             .unwrap();
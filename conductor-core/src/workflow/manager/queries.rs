@@ -26,9 +26,10 @@ static STEP_SELECT_EXPANDED: std::sync::LazyLock<String> = std::sync::LazyLock::
 });
 use crate::workflow::WorkflowRunStatus;
 
-/// SQL fragment that filters to runs whose worktree is active or which have no worktree.
-const ACTIVE_WORKTREE_GUARD: &str =
-    "workflow_runs.worktree_id IS NULL OR worktrees.status = 'active'";
+/// SQL fragment that filters to runs whose worktree is live (see
+/// [`crate::worktree::WorktreeStatus::LIVE`]) or which have no worktree.
+const ACTIVE_WORKTREE_GUARD: &str = "workflow_runs.worktree_id IS NULL \
+     OR worktrees.status IN ('active', 'needs_review', 'parked')";
 use crate::workflow::types::{
     ActiveWorkflowCounts, ConductorWorkflowRun, GateAnalyticsRow, PendingGateAnalyticsRow,
     PendingGateRow, StepFailureHeatmapRow, StepRetryAnalyticsRow, StepTokenHeatmapRow,
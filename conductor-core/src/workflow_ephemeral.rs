@@ -120,10 +120,15 @@ pub fn checkout_pr(pr: &PrRef, dir: &Path) -> Result<String> {
     })?;
 
     // Step 1: clone the repo (shallow)
-    let output = Command::new("gh")
-        .args(["repo", "clone", &repo_slug, dir_str, "--", "--depth=1"])
-        .output()
-        .map_err(|e| ConductorError::Workflow(format!("Failed to run 'gh repo clone': {e}")))?;
+    let output = crate::command::output(Command::new("gh").args([
+        "repo",
+        "clone",
+        &repo_slug,
+        dir_str,
+        "--",
+        "--depth=1",
+    ]))
+    .map_err(|e| ConductorError::Workflow(format!("Failed to run 'gh repo clone': {e}")))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -135,18 +140,19 @@ pub fn checkout_pr(pr: &PrRef, dir: &Path) -> Result<String> {
     // Step 2: checkout the PR branch (detached HEAD avoids tracking-setup failures
     // that occur in shallow clones where the remote-tracking ref exists but git
     // refuses to create a local tracking branch from it).
-    let output = Command::new("gh")
-        .args([
-            "pr",
-            "checkout",
-            &pr.number.to_string(),
-            "--repo",
-            &repo_slug,
-            "--detach",
-        ])
-        .current_dir(dir)
-        .output()
-        .map_err(|e| ConductorError::Workflow(format!("Failed to run 'gh pr checkout': {e}")))?;
+    let output = crate::command::output(
+        Command::new("gh")
+            .args([
+                "pr",
+                "checkout",
+                &pr.number.to_string(),
+                "--repo",
+                &repo_slug,
+                "--detach",
+            ])
+            .current_dir(dir),
+    )
+    .map_err(|e| ConductorError::Workflow(format!("Failed to run 'gh pr checkout': {e}")))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -157,11 +163,12 @@ pub fn checkout_pr(pr: &PrRef, dir: &Path) -> Result<String> {
     }
 
     // Read the current branch name
-    let output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .current_dir(dir)
-        .output()
-        .map_err(|e| ConductorError::Workflow(format!("Failed to get branch name: {e}")))?;
+    let output = crate::command::output(
+        Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .current_dir(dir),
+    )
+    .map_err(|e| ConductorError::Workflow(format!("Failed to get branch name: {e}")))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
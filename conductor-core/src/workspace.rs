@@ -0,0 +1,424 @@
+//! Workspace roots: the directories repos and their worktrees live under.
+//!
+//! Every repo has a `workspace_dir`. By default it is
+//! `<general.workspace_root>/<slug>`, but a repo can point anywhere (e.g. a
+//! faster or larger drive). The distinct roots are listed and checked by
+//! [`WorkspaceManager::list_roots`], and [`WorkspaceManager::migrate`]
+//! rewrites stored paths after a root has been moved on disk.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{named_params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+
+/// Expand a leading `~` and `$VAR` / `${VAR}` references in a path.
+///
+/// Fails when a referenced variable is not set, rather than silently
+/// producing a path with an empty component.
+pub fn expand_path(raw: &str) -> std::result::Result<PathBuf, String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, tail) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| format!("unterminated '${{' in path: {raw}"))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if name.is_empty() {
+            out.push('$');
+        } else {
+            let value = std::env::var(name)
+                .map_err(|_| format!("environment variable {name} is not set (in path {raw})"))?;
+            out.push_str(&value);
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    crate::text_util::expand_tilde(&out)
+}
+
+/// Whether a workspace root can be used.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceRootStatus {
+    Ok,
+    Missing,
+    NotADirectory,
+    NotWritable,
+}
+
+impl std::fmt::Display for WorkspaceRootStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ok => "ok",
+            Self::Missing => "missing",
+            Self::NotADirectory => "not a directory",
+            Self::NotWritable => "not writable",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Check that `path` is an existing, writable directory.
+pub fn check_root(path: &Path) -> WorkspaceRootStatus {
+    if !path.exists() {
+        return WorkspaceRootStatus::Missing;
+    }
+    if !path.is_dir() {
+        return WorkspaceRootStatus::NotADirectory;
+    }
+    match tempfile::Builder::new()
+        .prefix(".conductor-write-check")
+        .tempfile_in(path)
+    {
+        Ok(_) => WorkspaceRootStatus::Ok,
+        Err(_) => WorkspaceRootStatus::NotWritable,
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRoot {
+    pub path: String,
+    /// `true` for `general.workspace_root`; `false` for a directory a repo
+    /// was pointed at directly.
+    pub is_default: bool,
+    /// Slugs of the repos whose workspace lives under this root.
+    pub repos: Vec<String>,
+    pub status: WorkspaceRootStatus,
+}
+
+/// What [`WorkspaceManager::migrate`] changed, or would change on a dry run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceMigration {
+    pub repos: usize,
+    pub worktrees: usize,
+    /// Whether `general.workspace_root` pointed at the old location.
+    pub default_root_moved: bool,
+    /// Repos whose `git worktree repair` failed, with the error.
+    pub repair_failures: Vec<(String, String)>,
+}
+
+pub struct WorkspaceManager<'a> {
+    conn: &'a Connection,
+    config: &'a Config,
+}
+
+impl<'a> WorkspaceManager<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self { conn, config }
+    }
+
+    /// The default root followed by each directory a repo was pointed at
+    /// outside it, with the repos each one holds and whether it is usable.
+    pub fn list_roots(&self) -> Result<Vec<WorkspaceRoot>> {
+        let default_root = &self.config.general.workspace_root;
+        let repos: Vec<(String, String)> = query_collect(
+            self.conn,
+            "SELECT slug, workspace_dir FROM repos ORDER BY slug",
+            [],
+            |row| Ok((row.get("slug")?, row.get("workspace_dir")?)),
+        )?;
+
+        let mut default_repos = Vec::new();
+        let mut overrides: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (slug, dir) in repos {
+            if Path::new(&dir).starts_with(default_root) {
+                default_repos.push(slug);
+            } else {
+                overrides.entry(dir).or_default().push(slug);
+            }
+        }
+
+        let mut roots = vec![WorkspaceRoot {
+            path: default_root.to_string_lossy().to_string(),
+            is_default: true,
+            repos: default_repos,
+            status: check_root(default_root),
+        }];
+        roots.extend(overrides.into_iter().map(|(path, repos)| WorkspaceRoot {
+            status: check_root(Path::new(&path)),
+            path,
+            is_default: false,
+            repos,
+        }));
+        Ok(roots)
+    }
+
+    /// Roots that exist in config or on a repo but are not usable.
+    pub fn invalid_roots(&self) -> Result<Vec<WorkspaceRoot>> {
+        Ok(self
+            .list_roots()?
+            .into_iter()
+            .filter(|r| r.status != WorkspaceRootStatus::Ok)
+            .collect())
+    }
+
+    /// Rewrite stored repo and worktree paths under `from` to live under
+    /// `to`, after the directory has been moved on disk, then run
+    /// `git worktree repair` so git's own links follow. `to` must already
+    /// exist. Does not touch `config.toml`; see
+    /// [`WorkspaceMigration::default_root_moved`].
+    pub fn migrate(&self, from: &Path, to: &Path, dry_run: bool) -> Result<WorkspaceMigration> {
+        if from == to {
+            return Err(ConductorError::InvalidInput(
+                "old and new workspace locations are the same".to_string(),
+            ));
+        }
+        if !dry_run && !to.is_dir() {
+            return Err(ConductorError::InvalidInput(format!(
+                "{} does not exist; move the workspace there first",
+                to.display()
+            )));
+        }
+        let moved = |p: &str| -> Option<String> {
+            Path::new(p)
+                .strip_prefix(from)
+                .ok()
+                .map(|rest| to.join(rest).to_string_lossy().to_string())
+        };
+
+        let repos: Vec<(String, String, String, String)> = query_collect(
+            self.conn,
+            "SELECT id, slug, local_path, workspace_dir FROM repos ORDER BY slug",
+            [],
+            |row| {
+                Ok((
+                    row.get("id")?,
+                    row.get("slug")?,
+                    row.get("local_path")?,
+                    row.get("workspace_dir")?,
+                ))
+            },
+        )?;
+        let worktrees: Vec<(String, String, String)> = query_collect(
+            self.conn,
+            "SELECT id, repo_id, path FROM worktrees",
+            [],
+            |row| Ok((row.get("id")?, row.get("repo_id")?, row.get("path")?)),
+        )?;
+
+        let mut report = WorkspaceMigration {
+            default_root_moved: self.config.general.workspace_root.starts_with(from),
+            ..Default::default()
+        };
+        let tx = self.conn.unchecked_transaction()?;
+        let mut moved_worktrees: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (id, _, local_path, workspace_dir) in &repos {
+            let new_local = moved(local_path);
+            let new_ws = moved(workspace_dir);
+            if new_local.is_none() && new_ws.is_none() {
+                continue;
+            }
+            report.repos += 1;
+            tx.execute(
+                "UPDATE repos SET local_path = :local_path, workspace_dir = :workspace_dir \
+                 WHERE id = :id",
+                named_params! {
+                    ":id": id,
+                    ":local_path": new_local.as_deref().unwrap_or(local_path),
+                    ":workspace_dir": new_ws.as_deref().unwrap_or(workspace_dir),
+                },
+            )?;
+        }
+        for (id, repo_id, path) in &worktrees {
+            let Some(new_path) = moved(path) else {
+                continue;
+            };
+            report.worktrees += 1;
+            tx.execute(
+                "UPDATE worktrees SET path = :path WHERE id = :id",
+                named_params! { ":id": id, ":path": new_path },
+            )?;
+            moved_worktrees
+                .entry(repo_id.clone())
+                .or_default()
+                .push(new_path);
+        }
+        if dry_run {
+            tx.rollback()?;
+            return Ok(report);
+        }
+        tx.commit()?;
+
+        // Worktree checkouts and the main repo point at each other by
+        // absolute path; let git fix both sides.
+        for (id, slug, local_path, _) in &repos {
+            let Some(paths) = moved_worktrees.get(id) else {
+                continue;
+            };
+            let main = moved(local_path).unwrap_or_else(|| local_path.clone());
+            let mut cmd = crate::git::git_in(&main);
+            cmd.args(["worktree", "repair"]).args(paths);
+            if let Err(e) = crate::git::check_output(&mut cmd) {
+                tracing::warn!(repo = %slug, "git worktree repair failed: {e}");
+                report.repair_failures.push((slug.clone(), e.to_string()));
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{create_test_conn, insert_test_repo, insert_test_worktree};
+
+    #[test]
+    fn expand_path_handles_tilde_and_env_vars() {
+        // SAFETY: the variable name is unique to this test.
+        unsafe { std::env::set_var("CONDUCTOR_TEST_WS_DRIVE", "/mnt/fast") };
+        assert_eq!(
+            expand_path("$CONDUCTOR_TEST_WS_DRIVE/ws").unwrap(),
+            PathBuf::from("/mnt/fast/ws")
+        );
+        assert_eq!(
+            expand_path("${CONDUCTOR_TEST_WS_DRIVE}-2/ws").unwrap(),
+            PathBuf::from("/mnt/fast-2/ws")
+        );
+        assert_eq!(expand_path("/a/$/b").unwrap(), PathBuf::from("/a/$/b"));
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_path("~/ws").unwrap(), home.join("ws"));
+        assert!(expand_path("$CONDUCTOR_TEST_WS_UNSET_VAR/ws")
+            .unwrap_err()
+            .contains("CONDUCTOR_TEST_WS_UNSET_VAR"));
+        assert!(expand_path("${OOPS/ws").is_err());
+    }
+
+    #[test]
+    fn check_root_reports_problems() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(check_root(tmp.path()), WorkspaceRootStatus::Ok);
+        assert_eq!(
+            check_root(&tmp.path().join("missing")),
+            WorkspaceRootStatus::Missing
+        );
+        let file = tmp.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert_eq!(check_root(&file), WorkspaceRootStatus::NotADirectory);
+    }
+
+    #[test]
+    fn list_roots_groups_repos_by_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.general.workspace_root = tmp.path().join("default");
+        std::fs::create_dir_all(&config.general.workspace_root).unwrap();
+        let other = tmp.path().join("other");
+
+        let conn = create_test_conn();
+        insert_test_repo(&conn, "r1", "alpha", "/src/alpha");
+        insert_test_repo(&conn, "r2", "beta", "/src/beta");
+        conn.execute(
+            "UPDATE repos SET workspace_dir = :dir WHERE id = 'r1'",
+            named_params! { ":dir": config.general.workspace_root.join("alpha").to_str() },
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE repos SET workspace_dir = :dir WHERE id = 'r2'",
+            named_params! { ":dir": other.to_str() },
+        )
+        .unwrap();
+
+        let mgr = WorkspaceManager::new(&conn, &config);
+        let roots = mgr.list_roots().unwrap();
+        assert_eq!(roots.len(), 2);
+        assert!(roots[0].is_default);
+        assert_eq!(roots[0].repos, ["alpha"]);
+        assert_eq!(roots[0].status, WorkspaceRootStatus::Ok);
+        assert_eq!(roots[1].repos, ["beta"]);
+        assert_eq!(roots[1].status, WorkspaceRootStatus::Missing);
+
+        let invalid = mgr.invalid_roots().unwrap();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].path, other.to_string_lossy());
+    }
+
+    #[test]
+    fn migrate_rewrites_paths_under_the_old_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let old = tmp.path().join("old");
+        let new = tmp.path().join("new");
+        std::fs::create_dir_all(&new).unwrap();
+        let mut config = Config::default();
+        config.general.workspace_root = old.clone();
+
+        let conn = create_test_conn();
+        let old_s = old.to_string_lossy().to_string();
+        insert_test_repo(&conn, "r1", "alpha", &format!("{old_s}/alpha/main"));
+        insert_test_repo(&conn, "r2", "beta", "/elsewhere/beta");
+        conn.execute(
+            "UPDATE repos SET workspace_dir = :dir WHERE id = 'r1'",
+            named_params! { ":dir": format!("{old_s}/alpha") },
+        )
+        .unwrap();
+        insert_test_worktree(
+            &conn,
+            "w1",
+            "r1",
+            "feat-x",
+            &format!("{old_s}/alpha/feat-x"),
+        );
+        insert_test_worktree(&conn, "w2", "r2", "feat-y", "/elsewhere/beta-ws/feat-y");
+        // A sibling directory sharing the prefix as a string is not moved.
+        insert_test_worktree(&conn, "w3", "r2", "feat-z", &format!("{old_s}er/feat-z"));
+
+        let mgr = WorkspaceManager::new(&conn, &config);
+        let dry = mgr.migrate(&old, &new, true).unwrap();
+        assert_eq!((dry.repos, dry.worktrees), (1, 1));
+        assert!(dry.default_root_moved);
+        let path: String = conn
+            .query_row("SELECT path FROM worktrees WHERE id = 'w1'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert!(path.starts_with(&old_s), "dry run changes nothing");
+
+        let report = mgr.migrate(&old, &new, false).unwrap();
+        assert_eq!((report.repos, report.worktrees), (1, 1));
+        let new_s = new.to_string_lossy().to_string();
+        let (local, ws): (String, String) = conn
+            .query_row(
+                "SELECT local_path, workspace_dir FROM repos WHERE id = 'r1'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(local, format!("{new_s}/alpha/main"));
+        assert_eq!(ws, format!("{new_s}/alpha"));
+        let paths: Vec<String> =
+            query_collect(&conn, "SELECT path FROM worktrees ORDER BY id", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(
+            paths,
+            [
+                format!("{new_s}/alpha/feat-x"),
+                "/elsewhere/beta-ws/feat-y".to_string(),
+                format!("{old_s}er/feat-z"),
+            ]
+        );
+        // The repo checkout does not exist here, so repair fails and is reported.
+        assert_eq!(report.repair_failures.len(), 1);
+
+        assert!(matches!(
+            mgr.migrate(&old, &tmp.path().join("absent"), false),
+            Err(ConductorError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! Housekeeping over many worktrees at once.
+//!
+//! Each worktree is handled on its own: a failure is recorded in its
+//! [`BatchItem`] and the batch moves on to the next worktree instead of
+//! aborting.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::git::{check_output, git_in};
+use crate::repo::{Repo, RepoManager};
+
+use super::git_status::git_status;
+use super::listing::ahead_behind;
+use super::manager::{PushOptions, WorktreeManager};
+use super::types::Worktree;
+
+/// What a batch operation did to one worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", content = "message", rename_all = "snake_case")]
+pub enum BatchOutcome {
+    Done(String),
+    /// Left alone, with the reason.
+    Skipped(String),
+    Failed(String),
+}
+
+/// One worktree's line in a batch report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchItem {
+    pub repo_slug: String,
+    pub slug: String,
+    #[serde(flatten)]
+    pub outcome: BatchOutcome,
+}
+
+impl BatchItem {
+    fn new(repo: &Repo, worktree: &Worktree, outcome: BatchOutcome) -> Self {
+        Self {
+            repo_slug: repo.slug.clone(),
+            slug: worktree.slug.clone(),
+            outcome,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.outcome, BatchOutcome::Failed(_))
+    }
+}
+
+impl WorktreeManager<'_> {
+    /// Push every live worktree that has commits origin lacks, in one repo or
+    /// all of them. Worktrees whose branch is already up to date on origin,
+    /// that have no commits beyond their base, or whose checkout is missing
+    /// are skipped.
+    pub fn push_all(&self, repo_slug: Option<&str>, opts: PushOptions) -> Result<Vec<BatchItem>> {
+        let mut items = Vec::new();
+        for (repo, wt) in self.live_worktrees(repo_slug)? {
+            let outcome = if !Path::new(&wt.path).exists() {
+                BatchOutcome::Skipped("checkout missing".into())
+            } else {
+                match git_status(Path::new(&wt.path), &wt.branch) {
+                    Ok(status) if status.upstream.is_some_and(|ab| ab.ahead == 0) => {
+                        BatchOutcome::Skipped("up to date".into())
+                    }
+                    // A branch never pushed needs commits of its own first.
+                    Ok(status)
+                        if status.upstream.is_none()
+                            && ahead_behind(&wt.path, wt.effective_base(&repo.default_branch))
+                                .is_none_or(|ab| ab.ahead == 0) =>
+                    {
+                        BatchOutcome::Skipped("no commits to push".into())
+                    }
+                    Err(e) => BatchOutcome::Failed(e.to_string()),
+                    Ok(_) => match self.push(&repo.slug, &wt.slug, opts) {
+                        Ok(msg) => BatchOutcome::Done(msg),
+                        Err(e) => BatchOutcome::Failed(e.to_string()),
+                    },
+                }
+            };
+            items.push(BatchItem::new(&repo, &wt, outcome));
+        }
+        Ok(items)
+    }
+
+    /// Delete every live worktree whose work has landed, in one repo or all
+    /// of them: its linked ticket is closed, or its branch is merged into the
+    /// repo's default branch and carries commits made after the worktree was
+    /// created (a branch nobody committed to is trivially "merged").
+    /// Worktrees with uncommitted changes are skipped; unmerged ones are not
+    /// reported.
+    pub fn delete_merged(&self, repo_slug: Option<&str>) -> Result<Vec<BatchItem>> {
+        let mut items = Vec::new();
+        for (repo, wt) in self.live_worktrees(repo_slug)? {
+            let closed = self.ticket_closed(&wt);
+            if !self.is_merged(&repo, &wt, Some(closed))
+                || !(closed || has_commits_since(&repo.local_path, &wt.branch, &wt.created_at))
+            {
+                continue;
+            }
+            let dirty = Path::new(&wt.path).exists()
+                && git_status(Path::new(&wt.path), &wt.branch).is_ok_and(|s| s.uncommitted > 0);
+            let outcome = if dirty {
+                BatchOutcome::Skipped("uncommitted changes".into())
+            } else {
+                match self.delete_internal(&repo, wt.clone(), Some(closed), None) {
+                    Ok(deleted) => {
+                        BatchOutcome::Done(format!("marked as {}", deleted.status_label()))
+                    }
+                    Err(e) => BatchOutcome::Failed(e.to_string()),
+                }
+            };
+            items.push(BatchItem::new(&repo, &wt, outcome));
+        }
+        Ok(items)
+    }
+
+    fn live_worktrees(&self, repo_slug: Option<&str>) -> Result<Vec<(Repo, Worktree)>> {
+        let repo_mgr = RepoManager::new(self.conn, self.config);
+        let repos = match repo_slug {
+            Some(slug) => vec![repo_mgr.get_by_slug(slug)?],
+            None => repo_mgr.list()?,
+        };
+        let mut out = Vec::new();
+        for repo in repos {
+            for wt in self.list_by_repo_id(&repo.id, true)? {
+                out.push((repo.clone(), wt));
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Whether the tip of `branch` was committed after `since` (RFC 3339).
+fn has_commits_since(repo_path: &str, branch: &str, since: &str) -> bool {
+    let Ok(since) = DateTime::parse_from_rfc3339(since) else {
+        return false;
+    };
+    check_output(git_in(repo_path).args(["log", "-1", "--format=%cI", branch, "--"]))
+        .ok()
+        .and_then(|out| {
+            DateTime::parse_from_rfc3339(String::from_utf8_lossy(&out.stdout).trim()).ok()
+        })
+        .is_some_and(|tip| tip.with_timezone(&Utc) > since.with_timezone(&Utc))
+}
@@ -0,0 +1,212 @@
+//! Read-only browsing of a worktree's files, for reviewing agent output
+//! without a checkout (e.g. from the web dashboard on a phone).
+
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::{ConductorError, Result};
+
+use super::WorktreeManager;
+
+/// Files larger than this are returned truncated.
+pub const MAX_BROWSE_FILE_BYTES: u64 = 512 * 1024;
+
+/// One entry of a directory listing.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WorktreeDirEntry {
+    pub name: String,
+    /// Path relative to the worktree root, `/`-separated.
+    pub path: String,
+    pub is_dir: bool,
+    /// Size in bytes; `None` for directories.
+    pub size: Option<u64>,
+}
+
+/// What a path in a worktree holds.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorktreeListing {
+    /// Directories first, then files, each sorted by name. `.git` is omitted.
+    Dir {
+        path: String,
+        entries: Vec<WorktreeDirEntry>,
+    },
+    File {
+        path: String,
+        size: u64,
+        /// UTF-8 text, cut at [`MAX_BROWSE_FILE_BYTES`]. `None` for binary files.
+        content: Option<String>,
+        binary: bool,
+        truncated: bool,
+    },
+}
+
+impl WorktreeManager<'_> {
+    /// List a directory or read a file in an active worktree. `path` is
+    /// relative to the worktree root; empty means the root. Paths that
+    /// leave the worktree, directly or through a symlink, are rejected.
+    pub fn browse(&self, repo_slug: &str, name: &str, path: &str) -> Result<WorktreeListing> {
+        let (_, worktree) = self.get_active_worktree(repo_slug, name)?;
+        browse_path(Path::new(&worktree.path), path)
+    }
+}
+
+fn browse_path(root: &Path, path: &str) -> Result<WorktreeListing> {
+    let rel = Path::new(path.trim_start_matches('/'));
+    if rel
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(ConductorError::InvalidInput(format!(
+            "path must stay inside the worktree: {path}"
+        )));
+    }
+    let root = fs::canonicalize(root)?;
+    let target = fs::canonicalize(root.join(rel)).map_err(|_| {
+        ConductorError::InvalidInput(format!("no such file or directory in worktree: {path}"))
+    })?;
+    if !target.starts_with(&root) {
+        return Err(ConductorError::InvalidInput(format!(
+            "path must stay inside the worktree: {path}"
+        )));
+    }
+    let display = relative_display(&root, &target);
+
+    if target.is_dir() {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&target)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name == ".git" {
+                continue;
+            }
+            // Follow symlinks for the type so linked dirs are browsable;
+            // broken links show up as empty files.
+            let meta = fs::metadata(entry.path()).ok();
+            let is_dir = meta.as_ref().is_some_and(|m| m.is_dir());
+            entries.push(WorktreeDirEntry {
+                path: relative_display(&root, &target.join(&name)),
+                name,
+                is_dir,
+                size: if is_dir {
+                    None
+                } else {
+                    Some(meta.map(|m| m.len()).unwrap_or(0))
+                },
+            });
+        }
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        return Ok(WorktreeListing::Dir {
+            path: display,
+            entries,
+        });
+    }
+
+    let size = fs::metadata(&target)?.len();
+    let mut bytes = Vec::new();
+    fs::File::open(&target)?
+        .take(MAX_BROWSE_FILE_BYTES)
+        .read_to_end(&mut bytes)?;
+    let truncated = size > MAX_BROWSE_FILE_BYTES;
+    // Same heuristic as git: a NUL in the first 8000 bytes means binary.
+    let binary = bytes.iter().take(8000).any(|b| *b == 0);
+    let content = (!binary).then(|| String::from_utf8_lossy(&bytes).into_owned());
+    Ok(WorktreeListing::File {
+        path: display,
+        size,
+        content,
+        binary,
+        truncated,
+    })
+}
+
+/// `target` relative to `root`, `/`-separated; empty for the root itself.
+fn relative_display(root: &Path, target: &Path) -> String {
+    target
+        .strip_prefix(root)
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> tempfile::TempDir {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join(".git"), "gitdir: elsewhere").unwrap();
+        fs::write(root.join("README.md"), "hello").unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 1]).unwrap();
+        tmp
+    }
+
+    #[test]
+    fn lists_directories_first_without_git() {
+        let tmp = tree();
+        let WorktreeListing::Dir { path, entries } = browse_path(tmp.path(), "").unwrap() else {
+            panic!("expected a directory");
+        };
+        assert_eq!(path, "");
+        let names: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(names, ["src", "README.md", "logo.png"]);
+        assert_eq!(entries[1].size, Some(5));
+
+        let WorktreeListing::Dir { entries, .. } = browse_path(tmp.path(), "/src").unwrap() else {
+            panic!("expected a directory");
+        };
+        let names: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(names, ["src/nested", "src/main.rs"]);
+    }
+
+    #[test]
+    fn reads_text_and_flags_binary_files() {
+        let tmp = tree();
+        match browse_path(tmp.path(), "src/main.rs").unwrap() {
+            WorktreeListing::File {
+                path,
+                content,
+                binary,
+                truncated,
+                ..
+            } => {
+                assert_eq!(path, "src/main.rs");
+                assert_eq!(content.as_deref(), Some("fn main() {}"));
+                assert!(!binary && !truncated);
+            }
+            other => panic!("expected a file, got {other:?}"),
+        }
+        match browse_path(tmp.path(), "logo.png").unwrap() {
+            WorktreeListing::File {
+                content, binary, ..
+            } => assert!(binary && content.is_none()),
+            other => panic!("expected a file, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_worktree() {
+        let tmp = tree();
+        let outside = tempfile::TempDir::new().unwrap();
+        fs::write(outside.path().join("secret"), "s").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(outside.path(), tmp.path().join("escape")).unwrap();
+
+        for path in ["../secret", "src/../../secret", "missing.txt"] {
+            assert!(browse_path(tmp.path(), path).is_err(), "{path}");
+        }
+        #[cfg(unix)]
+        assert!(browse_path(tmp.path(), "escape/secret").is_err());
+    }
+}
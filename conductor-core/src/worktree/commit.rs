@@ -0,0 +1,122 @@
+//! Committing a worktree's changes from conductor, so work an agent left
+//! uncommitted can be pushed without opening a shell in the worktree.
+
+use serde::Serialize;
+
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+use crate::tickets::Ticket;
+
+use super::WorktreeManager;
+
+/// Generated commit bodies are capped at this many bytes.
+const MAX_COMMIT_BODY_BYTES: usize = 8_000;
+
+/// A commit made by [`WorktreeManager::commit`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSummary {
+    pub sha: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Paths included in the commit.
+    pub files: Vec<String>,
+}
+
+impl WorktreeManager<'_> {
+    /// Commit the worktree's changes on its branch.
+    ///
+    /// With `all`, every change is staged first, untracked files included;
+    /// otherwise only what is already staged is committed. Without a
+    /// `message`, one is built from the linked ticket and the latest agent
+    /// run's result. The repo's commit hooks run as usual.
+    pub fn commit(
+        &self,
+        repo_slug: &str,
+        name: &str,
+        message: Option<&str>,
+        all: bool,
+    ) -> Result<CommitSummary> {
+        let (_, worktree) = self.get_active_worktree(repo_slug, name)?;
+
+        let message = match message.map(str::trim) {
+            Some("") => {
+                return Err(ConductorError::InvalidInput(
+                    "commit message must not be empty".into(),
+                ))
+            }
+            Some(m) => m.to_string(),
+            None => {
+                let (run, ticket) = self.agent_context(&worktree, "the commit")?;
+                agent_commit_message(
+                    ticket.as_ref(),
+                    &run.prompt,
+                    run.result_text.as_deref().unwrap_or_default(),
+                )
+            }
+        };
+
+        if all {
+            // The worktree's own `.conductor.db` is never part of the work.
+            check_output(git_in(&worktree.path).args([
+                "add",
+                "-A",
+                "--",
+                ".",
+                ":(exclude).conductor.db*",
+            ]))?;
+        }
+        let staged =
+            check_output(git_in(&worktree.path).args(["diff", "--cached", "--name-only"]))?;
+        let files: Vec<String> = String::from_utf8_lossy(&staged.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if files.is_empty() {
+            return Err(ConductorError::InvalidInput(if all {
+                format!("worktree '{}' has no changes to commit", worktree.slug)
+            } else {
+                format!(
+                    "worktree '{}' has no staged changes; stage some or commit all changes",
+                    worktree.slug
+                )
+            }));
+        }
+
+        check_output(git_in(&worktree.path).args(["commit", "-m", &message]))?;
+        let sha = check_output(git_in(&worktree.path).args(["rev-parse", "HEAD"]))?;
+        Ok(CommitSummary {
+            sha: String::from_utf8_lossy(&sha.stdout).trim().to_string(),
+            subject: message.lines().next().unwrap_or_default().to_string(),
+            files,
+        })
+    }
+}
+
+/// Commit message for agent work: the ticket title (or the prompt's first
+/// line) as the subject, the agent's result as the body, and a reference to
+/// the ticket.
+pub(super) fn agent_commit_message(ticket: Option<&Ticket>, prompt: &str, result: &str) -> String {
+    let subject = match ticket {
+        Some(t) => t.title.trim(),
+        None => prompt.lines().next().unwrap_or("").trim(),
+    };
+    let mut message = crate::text_util::truncate_str(subject, 72).to_string();
+    let body = crate::text_util::cap_with_suffix(
+        result.trim(),
+        MAX_COMMIT_BODY_BYTES,
+        "\n\n… (truncated)",
+    );
+    if !body.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&body);
+    }
+    match ticket {
+        Some(t) if t.source_type == "github" => {
+            message.push_str(&format!("\n\nRefs #{}", t.source_id))
+        }
+        Some(t) if !t.url.is_empty() => message.push_str(&format!("\n\nTicket: {}", t.url)),
+        _ => {}
+    }
+    message
+}
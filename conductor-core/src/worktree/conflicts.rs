@@ -0,0 +1,255 @@
+//! Rebases and merges that stopped on conflicts.
+//!
+//! A rebase started with `keep_conflicts` is left in progress when it
+//! conflicts, so the conflicts can be resolved in place (by hand or by a
+//! conflict resolution agent) and the rebase continued afterwards. These
+//! helpers read that in-progress state straight from git.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+
+/// Bytes of conflict hunks kept per file; the rest is summarized.
+const MAX_HUNK_BYTES_PER_FILE: usize = 8_000;
+
+/// The git operation that stopped on conflicts.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictOperation {
+    Rebase,
+    Merge,
+}
+
+impl std::fmt::Display for ConflictOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+        })
+    }
+}
+
+impl std::str::FromStr for ConflictOperation {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "rebase" => Ok(Self::Rebase),
+            "merge" => Ok(Self::Merge),
+            _ => Err(format!("unknown conflict operation: {s}")),
+        }
+    }
+}
+
+crate::impl_sql_enum!(ConflictOperation);
+
+/// A file with unresolved conflicts and the conflicting hunks in it.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictedFile {
+    pub path: String,
+    /// Each `<<<<<<<` … `>>>>>>>` block, markers included.
+    pub hunks: Vec<String>,
+    /// Whether hunks were left out to fit the size budget.
+    pub truncated: bool,
+}
+
+/// An in-progress rebase or merge in a worktree.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictState {
+    pub operation: ConflictOperation,
+    /// The ref being rebased onto or merged, when git recorded one.
+    pub onto: Option<String>,
+    /// Files still unmerged. Empty once every conflict is resolved and staged.
+    pub files: Vec<ConflictedFile>,
+}
+
+/// The rebase or merge in progress in `wt_path`, if any.
+pub fn conflict_state(wt_path: &Path) -> Result<Option<ConflictState>> {
+    let rebase_dir = ["rebase-merge", "rebase-apply"]
+        .into_iter()
+        .map(|d| git_path(wt_path, d))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .find(|p| p.exists());
+    let merge_head = git_path(wt_path, "MERGE_HEAD")?;
+    let first_line = |path: &Path| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| s.lines().next().map(|l| l.trim().to_string()))
+            .filter(|s| !s.is_empty())
+    };
+    let (operation, onto) = if let Some(dir) = rebase_dir {
+        (ConflictOperation::Rebase, first_line(&dir.join("onto")))
+    } else if merge_head.exists() {
+        (ConflictOperation::Merge, first_line(&merge_head))
+    } else {
+        return Ok(None);
+    };
+
+    let files = unmerged_paths(wt_path)?
+        .into_iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(wt_path.join(&path)).unwrap_or_default();
+            let (hunks, truncated) = conflict_hunks(&text, MAX_HUNK_BYTES_PER_FILE);
+            ConflictedFile {
+                path,
+                hunks,
+                truncated,
+            }
+        })
+        .collect();
+    Ok(Some(ConflictState {
+        operation,
+        onto,
+        files,
+    }))
+}
+
+/// Paths git still lists as unmerged.
+pub fn unmerged_paths(wt_path: &Path) -> Result<Vec<String>> {
+    let out = check_output(git_in(wt_path).args(["diff", "--name-only", "--diff-filter=U"]))?;
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Paths among `paths` that still contain conflict markers in the working
+/// tree, e.g. because a file was staged without being fixed.
+pub fn paths_with_markers(wt_path: &Path, paths: &[String]) -> Vec<String> {
+    paths
+        .iter()
+        .filter(|p| {
+            std::fs::read_to_string(wt_path.join(p))
+                .is_ok_and(|text| !conflict_hunks(&text, 0).0.is_empty())
+        })
+        .cloned()
+        .collect()
+}
+
+/// Tree id of the index, identifying exactly what continuing would commit.
+/// Fails while any path is still unmerged.
+pub fn index_tree(wt_path: &Path) -> Result<String> {
+    let out = check_output(git_in(wt_path).arg("write-tree"))?;
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Continue the stopped operation with the staged resolution. Returns the
+/// state afterwards: `None` when it finished, or the next conflicts when a
+/// later commit of a rebase conflicts too.
+pub fn continue_operation(
+    wt_path: &Path,
+    operation: ConflictOperation,
+) -> Result<Option<ConflictState>> {
+    let args: &[&str] = match operation {
+        ConflictOperation::Rebase => &["rebase", "--continue"],
+        ConflictOperation::Merge => &["commit", "--no-edit"],
+    };
+    let result = check_output(git_in(wt_path).env("GIT_EDITOR", "true").args(args));
+    match (result, conflict_state(wt_path)?) {
+        (Ok(_), state) => Ok(state),
+        (Err(_), Some(state)) if !state.files.is_empty() => Ok(Some(state)),
+        (Err(e), _) => Err(e),
+    }
+}
+
+/// Abort the stopped operation, restoring the branch to where it was.
+pub fn abort_operation(wt_path: &Path, operation: ConflictOperation) -> Result<()> {
+    check_output(git_in(wt_path).args([&operation.to_string(), "--abort"]))?;
+    Ok(())
+}
+
+/// Run a rebase; on conflicts either leave it stopped and report
+/// [`ConductorError::RebaseConflict`] (`keep_conflicts`) or abort it and
+/// return the git error.
+pub(super) fn rebase_or_stop(
+    wt_path: &Path,
+    args: &[&str],
+    slug: &str,
+    keep_conflicts: bool,
+) -> Result<()> {
+    let Err(e) = check_output(git_in(wt_path).args(args)) else {
+        return Ok(());
+    };
+    if keep_conflicts {
+        let files = unmerged_paths(wt_path).unwrap_or_default();
+        if !files.is_empty() {
+            return Err(ConductorError::RebaseConflict {
+                slug: slug.to_string(),
+                files,
+            });
+        }
+    }
+    let _ = crate::command::output(git_in(wt_path).args(["rebase", "--abort"]));
+    Err(e)
+}
+
+fn git_path(wt_path: &Path, name: &str) -> Result<std::path::PathBuf> {
+    let out = check_output(git_in(wt_path).args(["rev-parse", "--git-path", name]))?;
+    let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    Ok(wt_path.join(path))
+}
+
+/// The conflict blocks in `text`, up to `max_bytes` in total (0 for no
+/// limit). Returns whether any block was left out.
+fn conflict_hunks(text: &str, max_bytes: usize) -> (Vec<String>, bool) {
+    let mut hunks = Vec::new();
+    let mut current: Option<String> = None;
+    let mut used = 0;
+    let mut truncated = false;
+    for line in text.lines() {
+        if line.starts_with("<<<<<<<") {
+            current = Some(String::new());
+        }
+        let Some(hunk) = current.as_mut() else {
+            continue;
+        };
+        hunk.push_str(line);
+        hunk.push('\n');
+        if line.starts_with(">>>>>>>") {
+            let hunk = current.take().unwrap_or_default();
+            if max_bytes > 0 && used + hunk.len() > max_bytes {
+                truncated = true;
+                continue;
+            }
+            used += hunk.len();
+            hunks.push(hunk);
+        }
+    }
+    (hunks, truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "fn main() {\n\
+        <<<<<<< HEAD\n    run(1);\n=======\n    run(2);\n>>>>>>> feat\n\
+        }\n\
+        <<<<<<< HEAD\nconst A: u8 = 1;\n=======\nconst A: u8 = 2;\n>>>>>>> feat\n";
+
+    #[test]
+    fn conflict_hunks_extracts_marked_blocks() {
+        let (hunks, truncated) = conflict_hunks(FILE, 0);
+        assert_eq!(hunks.len(), 2);
+        assert!(!truncated);
+        assert_eq!(
+            hunks[0],
+            "<<<<<<< HEAD\n    run(1);\n=======\n    run(2);\n>>>>>>> feat\n"
+        );
+        assert!(conflict_hunks("no markers\n", 0).0.is_empty());
+    }
+
+    #[test]
+    fn conflict_hunks_respects_budget() {
+        let (hunks, truncated) = conflict_hunks(FILE, 60);
+        assert_eq!(hunks.len(), 1);
+        assert!(truncated);
+    }
+}
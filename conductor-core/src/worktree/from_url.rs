@@ -0,0 +1,446 @@
+//! Starting a worktree from a pasted issue or pull request URL.
+//!
+//! [`TicketUrl::parse`] recognises GitHub issue and PR links, GitLab issue
+//! links and Jira issue links. [`WorktreeManager::resolve_url`] finds the repo
+//! the URL belongs to by its remote (or, for Jira, its issue source), syncs the
+//! ticket if it has not been synced yet, and names the worktree after it.
+
+use crate::error::{ConductorError, Result};
+use crate::github;
+use crate::gitlab;
+use crate::issue_source::{GitHubConfig, GitLabConfig, IssueSourceManager, JiraConfig};
+use crate::repo::{Repo, RepoManager};
+use crate::ticket_source::TicketSource;
+use crate::tickets::{Ticket, TicketSyncer, GITHUB_PR_SOURCE_TYPE};
+
+use super::{derive_worktree_slug, WorktreeManager};
+
+/// What a pasted URL points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TicketUrl {
+    /// `https://github.com/{owner}/{repo}/issues/{n}` or `…/pull/{n}`.
+    GitHub {
+        owner: String,
+        repo: String,
+        number: u32,
+        pull_request: bool,
+    },
+    /// `https://{host}/{group/…/project}/-/issues/{iid}`.
+    GitLab {
+        base_url: String,
+        project: String,
+        iid: u64,
+    },
+    /// `https://{host}/browse/{KEY-1}`, or a board link with `selectedIssue=KEY-1`.
+    Jira { base_url: String, key: String },
+}
+
+impl TicketUrl {
+    /// Parse an issue or pull request URL. Trailing paths (`/files`), query
+    /// strings and fragments are ignored.
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid = || {
+            ConductorError::InvalidInput(format!(
+                "not a GitHub issue/PR, GitLab issue or Jira issue URL: {url}"
+            ))
+        };
+        let url = url.trim();
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .ok_or_else(invalid)?;
+        let rest = rest.split('#').next().unwrap_or(rest);
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = host.to_ascii_lowercase();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let base_url = format!("https://{host}");
+
+        if host == "github.com" || host == "www.github.com" {
+            return match segments.as_slice() {
+                [owner, repo, kind @ ("issues" | "pull"), number, ..] => Ok(Self::GitHub {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    number: number.parse().map_err(|_| invalid())?,
+                    pull_request: *kind == "pull",
+                }),
+                _ => Err(invalid()),
+            };
+        }
+
+        if let Some(dash) = segments.iter().position(|s| *s == "-") {
+            return match &segments[dash + 1..] {
+                ["issues", iid, ..] if dash > 0 => Ok(Self::GitLab {
+                    base_url,
+                    project: segments[..dash].join("/"),
+                    iid: iid.parse().map_err(|_| invalid())?,
+                }),
+                ["merge_requests", ..] => Err(ConductorError::InvalidInput(
+                    "GitLab merge requests are not supported; use the issue URL".to_string(),
+                )),
+                _ => Err(invalid()),
+            };
+        }
+
+        let key = match segments.as_slice() {
+            ["browse", key, ..] => Some(key.to_string()),
+            _ => query
+                .split('&')
+                .find_map(|p| p.strip_prefix("selectedIssue="))
+                .map(str::to_string),
+        };
+        match key {
+            Some(key) if is_jira_key(&key) => Ok(Self::Jira { base_url, key }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Ticket source type and source ID the URL's ticket is stored under.
+    fn ticket_ref(&self) -> (&'static str, String) {
+        match self {
+            Self::GitHub {
+                number,
+                pull_request: false,
+                ..
+            } => ("github", number.to_string()),
+            Self::GitHub { number, .. } => (GITHUB_PR_SOURCE_TYPE, number.to_string()),
+            Self::GitLab { iid, .. } => ("gitlab", iid.to_string()),
+            Self::Jira { key, .. } => ("jira", key.clone()),
+        }
+    }
+}
+
+/// `PROJ-12`: an uppercase project key, a dash and a number.
+fn is_jira_key(key: &str) -> bool {
+    let Some((project, number)) = key.split_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Where a URL's worktree goes and what it is linked to.
+#[derive(Debug, Clone)]
+pub struct UrlTarget {
+    pub repo: Repo,
+    /// `None` for a pull request that is not synced as a `github_pr` ticket.
+    pub ticket: Option<Ticket>,
+    /// Set for pull request URLs: check out the PR's branch.
+    pub from_pr: Option<u32>,
+    /// Worktree name derived from the ticket, or `pr-{n}`.
+    pub name: String,
+}
+
+impl WorktreeManager<'_> {
+    /// Find the repo and ticket behind an issue or pull request URL.
+    ///
+    /// An issue that has not been synced yet is fetched from its source and
+    /// stored. Fails when no registered repo matches, or when a Jira URL
+    /// matches several repos and none of them has the ticket yet.
+    pub fn resolve_url(&self, url: &str) -> Result<UrlTarget> {
+        let parsed = TicketUrl::parse(url)?;
+        let repo = self.repo_for_url(&parsed)?;
+        let (source_type, source_id) = parsed.ticket_ref();
+        let syncer = TicketSyncer::new(self.conn);
+        let reference = format!("{source_type}:{source_id}");
+
+        let ticket = match syncer.find_by_reference(Some(&repo.id), &reference) {
+            Ok(ticket) => Some(ticket),
+            Err(ConductorError::TicketNotFound { .. }) if source_type == GITHUB_PR_SOURCE_TYPE => {
+                None
+            }
+            Err(ConductorError::TicketNotFound { .. }) => {
+                let input = self.ticket_source(&repo, &parsed)?.fetch_one(&source_id)?;
+                syncer.upsert_tickets(&repo.id, &[input])?;
+                Some(syncer.find_by_reference(Some(&repo.id), &reference)?)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let from_pr = match parsed {
+            TicketUrl::GitHub {
+                number,
+                pull_request: true,
+                ..
+            } => Some(number),
+            _ => None,
+        };
+        let name = match (&ticket, from_pr) {
+            (Some(t), _) => {
+                let labels: Vec<String> = serde_json::from_str(&t.labels).unwrap_or_default();
+                derive_worktree_slug(&t.source_id, &t.title, &labels)
+            }
+            (None, Some(n)) => format!("pr-{n}"),
+            (None, None) => unreachable!("only pull requests resolve without a ticket"),
+        };
+        Ok(UrlTarget {
+            repo,
+            ticket,
+            from_pr,
+            name,
+        })
+    }
+
+    fn repo_for_url(&self, url: &TicketUrl) -> Result<Repo> {
+        let repos = RepoManager::new(self.conn, self.config).list()?;
+        let sources = IssueSourceManager::new(self.conn);
+        let mut matches: Vec<Repo> = Vec::new();
+        for repo in repos {
+            let matched = match url {
+                TicketUrl::GitHub {
+                    owner, repo: name, ..
+                } => github::parse_github_remote(&repo.remote_url).is_some_and(|(o, r)| {
+                    o.eq_ignore_ascii_case(owner) && r.eq_ignore_ascii_case(name)
+                }),
+                TicketUrl::GitLab {
+                    base_url, project, ..
+                } => {
+                    gitlab::parse_gitlab_remote(&repo.remote_url).is_some_and(|(b, p)| {
+                        b.eq_ignore_ascii_case(base_url) && p.eq_ignore_ascii_case(project)
+                    }) || sources.list(&repo.id)?.iter().any(|s| {
+                        s.source_type == "gitlab"
+                            && serde_json::from_str::<GitLabConfig>(&s.config_json).is_ok_and(|c| {
+                                same_host(&c.base_url, base_url)
+                                    && c.project_id.eq_ignore_ascii_case(project)
+                            })
+                    })
+                }
+                TicketUrl::Jira { base_url, .. } => sources.list(&repo.id)?.iter().any(|s| {
+                    s.source_type == "jira"
+                        && serde_json::from_str::<JiraConfig>(&s.config_json)
+                            .is_ok_and(|c| same_host(&c.url, base_url))
+                }),
+            };
+            if matched {
+                matches.push(repo);
+            }
+        }
+
+        // Several repos can share a Jira site; prefer the one that already
+        // has the ticket.
+        if matches.len() > 1 {
+            let (source_type, source_id) = url.ticket_ref();
+            let syncer = TicketSyncer::new(self.conn);
+            let reference = format!("{source_type}:{source_id}");
+            let with_ticket: Vec<Repo> = matches
+                .iter()
+                .filter(|r| syncer.find_by_reference(Some(&r.id), &reference).is_ok())
+                .cloned()
+                .collect();
+            if with_ticket.len() == 1 {
+                return Ok(with_ticket.into_iter().next().unwrap());
+            }
+        }
+        match matches.len() {
+            0 => Err(ConductorError::InvalidInput(match url {
+                TicketUrl::Jira { base_url, .. } => {
+                    format!("no repo has a Jira issue source for {base_url}")
+                }
+                TicketUrl::GitHub { owner, repo, .. } => {
+                    format!("no registered repo has the remote {owner}/{repo}")
+                }
+                TicketUrl::GitLab { project, .. } => {
+                    format!("no registered repo has the remote {project}")
+                }
+            })),
+            1 => Ok(matches.into_iter().next().unwrap()),
+            _ => {
+                let slugs: Vec<&str> = matches.iter().map(|r| r.slug.as_str()).collect();
+                Err(ConductorError::InvalidInput(format!(
+                    "URL matches several repos ({}); use `conductor worktree create-from-ticket` \
+                     with the repo instead",
+                    slugs.join(", ")
+                )))
+            }
+        }
+    }
+
+    /// The repo's issue source for the URL's tracker, or for GitHub and
+    /// GitLab one built from the URL when the repo has none.
+    fn ticket_source(&self, repo: &Repo, url: &TicketUrl) -> Result<TicketSource> {
+        let (source_type, _) = url.ticket_ref();
+        let configured = IssueSourceManager::new(self.conn)
+            .list(&repo.id)?
+            .into_iter()
+            .find(|s| s.source_type == source_type);
+        if let Some(source) = configured {
+            return TicketSource::from_issue_source(&source);
+        }
+        match url {
+            TicketUrl::GitHub { owner, repo, .. } => Ok(TicketSource::GitHub(GitHubConfig {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                sync_prs: false,
+            })),
+            TicketUrl::GitLab {
+                base_url, project, ..
+            } => Ok(TicketSource::GitLab(GitLabConfig {
+                project_id: project.clone(),
+                base_url: base_url.clone(),
+                token_env: crate::issue_source::default_gitlab_token_env(),
+            })),
+            TicketUrl::Jira { .. } => Err(ConductorError::InvalidInput(format!(
+                "{} has no Jira issue source",
+                repo.slug
+            ))),
+        }
+    }
+}
+
+/// Whether two URLs are on the same host, ignoring scheme, case and path.
+fn same_host(a: &str, b: &str) -> bool {
+    let host = |u: &str| {
+        let u = u
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        u.split('/').next().unwrap_or(u).to_ascii_lowercase()
+    };
+    host(a) == host(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_helpers::{create_test_conn, make_ticket};
+
+    fn github(owner: &str, repo: &str, number: u32, pull_request: bool) -> TicketUrl {
+        TicketUrl::GitHub {
+            owner: owner.into(),
+            repo: repo.into(),
+            number,
+            pull_request,
+        }
+    }
+
+    #[test]
+    fn parses_tracker_urls() {
+        assert_eq!(
+            TicketUrl::parse("https://github.com/acme/widget/issues/42").unwrap(),
+            github("acme", "widget", 42, false)
+        );
+        assert_eq!(
+            TicketUrl::parse("https://github.com/acme/widget/pull/7/files?w=1#diff").unwrap(),
+            github("acme", "widget", 7, true)
+        );
+        assert_eq!(
+            TicketUrl::parse(" https://gitlab.example.com/group/sub/proj/-/issues/9 ").unwrap(),
+            TicketUrl::GitLab {
+                base_url: "https://gitlab.example.com".into(),
+                project: "group/sub/proj".into(),
+                iid: 9,
+            }
+        );
+        let jira = TicketUrl::Jira {
+            base_url: "https://acme.atlassian.net".into(),
+            key: "PROJ-12".into(),
+        };
+        assert_eq!(
+            TicketUrl::parse("https://acme.atlassian.net/browse/PROJ-12").unwrap(),
+            jira
+        );
+        assert_eq!(
+            TicketUrl::parse(
+                "https://acme.atlassian.net/jira/software/projects/PROJ/boards/1?selectedIssue=PROJ-12"
+            )
+            .unwrap(),
+            jira
+        );
+    }
+
+    #[test]
+    fn rejects_other_urls() {
+        for url in [
+            "github.com/acme/widget/issues/42",
+            "https://github.com/acme/widget",
+            "https://github.com/acme/widget/issues/abc",
+            "https://gitlab.com/acme/widget/-/merge_requests/3",
+            "https://acme.atlassian.net/browse/proj-12",
+            "https://example.com/",
+        ] {
+            assert!(TicketUrl::parse(url).is_err(), "{url}");
+        }
+    }
+
+    fn add_repo(conn: &rusqlite::Connection, id: &str, slug: &str, remote: &str) {
+        conn.execute(
+            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at) \
+             VALUES (?1, ?2, '/tmp/repo', ?3, '/tmp/ws', '2024-01-01T00:00:00Z')",
+            rusqlite::params![id, slug, remote],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn resolves_synced_issue_by_remote() {
+        let conn = create_test_conn();
+        let config = Config::default();
+        add_repo(&conn, "r1", "widget", "git@github.com:acme/widget.git");
+        add_repo(&conn, "r2", "gadget", "https://github.com/acme/gadget.git");
+        let mut ticket = make_ticket("42", "Add login page");
+        ticket.labels = vec!["bug".into()];
+        TicketSyncer::new(&conn)
+            .upsert_tickets("r1", &[ticket])
+            .unwrap();
+
+        let mgr = WorktreeManager::new(&conn, &config);
+        let target = mgr
+            .resolve_url("https://github.com/Acme/Widget/issues/42")
+            .unwrap();
+        assert_eq!(target.repo.slug, "widget");
+        assert_eq!(target.ticket.unwrap().source_id, "42");
+        assert_eq!(target.from_pr, None);
+        assert_eq!(target.name, "fix-42-add-login-page");
+
+        let pr = mgr
+            .resolve_url("https://github.com/acme/gadget/pull/5")
+            .unwrap();
+        assert_eq!(pr.repo.slug, "gadget");
+        assert!(pr.ticket.is_none());
+        assert_eq!((pr.from_pr, pr.name.as_str()), (Some(5), "pr-5"));
+
+        let err = mgr
+            .resolve_url("https://github.com/acme/other/issues/1")
+            .unwrap_err();
+        assert!(err.to_string().contains("acme/other"), "{err}");
+    }
+
+    #[test]
+    fn jira_url_picks_the_repo_that_has_the_ticket() {
+        let conn = create_test_conn();
+        let config = Config::default();
+        let sources = IssueSourceManager::new(&conn);
+        for (id, slug) in [("r1", "web"), ("r2", "api")] {
+            add_repo(&conn, id, slug, &format!("https://github.com/acme/{slug}"));
+            sources
+                .add(
+                    id,
+                    "jira",
+                    r#"{"jql":"project = PROJ","url":"https://acme.atlassian.net"}"#,
+                    slug,
+                )
+                .unwrap();
+        }
+        let mut ticket = make_ticket("PROJ-12", "Rate limit the API");
+        ticket.source_type = "jira".into();
+        TicketSyncer::new(&conn)
+            .upsert_tickets("r2", &[ticket])
+            .unwrap();
+
+        let mgr = WorktreeManager::new(&conn, &config);
+        let target = mgr
+            .resolve_url("https://acme.atlassian.net/browse/PROJ-12")
+            .unwrap();
+        assert_eq!(target.repo.slug, "api");
+
+        let err = mgr
+            .resolve_url("https://acme.atlassian.net/browse/PROJ-99")
+            .unwrap_err();
+        assert!(err.to_string().contains("web, api") || err.to_string().contains("api, web"));
+    }
+}
@@ -1,8 +1,29 @@
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::path::Path;
 use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
 
 use crate::error::{ConductorError, Result, SubprocessFailure};
 use crate::git::{check_gh_output, check_output, git_in};
+use crate::text_util::tail_lines;
+use crate::toolchain::Toolchain;
+
+/// Process-level lock per repo path, held while a worktree create mutates the
+/// main repo (clone, fetch, branch, `worktree add`, config writes).
+///
+/// Git does not guard `.git/worktrees/` metadata against a concurrent
+/// `git worktree add`, and parallel creates for one repo share that directory.
+static REPO_GIT_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+pub(crate) fn repo_git_lock(repo_path: &str) -> Arc<Mutex<()>> {
+    let mut locks = REPO_GIT_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(locks.entry(repo_path.to_string()).or_default())
+}
 
 /// Structured result of a pre-creation health check on the base branch.
 #[derive(Debug, Clone)]
@@ -34,39 +55,43 @@ pub fn check_main_health(repo_path: &str, base_branch: &str) -> MainHealthStatus
     //    untracked files — blocking on them just produces false positives (e.g.
     //    untracked test-helper submodule directories that the user can't easily
     //    commit or stash).
-    let (is_dirty, dirty_files, status_check_failed) = match git_in(repo_path)
-        .args(["status", "--porcelain", "--untracked-files=no"])
-        .output()
-    {
-        Ok(o) if o.status.success() && !o.stdout.is_empty() => {
-            let files: Vec<String> = String::from_utf8_lossy(&o.stdout)
-                .lines()
-                .filter(|l| !l.trim().is_empty())
-                .map(|l| {
-                    l.trim_start_matches(|c: char| !c.is_whitespace())
-                        .trim()
-                        .to_string()
-                })
-                .collect();
-            (true, files, false)
-        }
-        Ok(o) if o.status.success() => {
-            // Empty stdout → working tree is clean
-            (false, Vec::new(), false)
-        }
-        _ => {
-            // Command failed or non-zero exit — cannot determine dirty state
-            (false, Vec::new(), true)
-        }
-    };
+    let (is_dirty, dirty_files, status_check_failed) =
+        match crate::command::output(git_in(repo_path).args([
+            "status",
+            "--porcelain",
+            "--untracked-files=no",
+        ])) {
+            Ok(o) if o.status.success() && !o.stdout.is_empty() => {
+                let files: Vec<String> = String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .filter(|l| !l.trim().is_empty())
+                    .map(|l| {
+                        l.trim_start_matches(|c: char| !c.is_whitespace())
+                            .trim()
+                            .to_string()
+                    })
+                    .collect();
+                (true, files, false)
+            }
+            Ok(o) if o.status.success() => {
+                // Empty stdout → working tree is clean
+                (false, Vec::new(), false)
+            }
+            _ => {
+                // Command failed or non-zero exit — cannot determine dirty state
+                (false, Vec::new(), true)
+            }
+        };
 
     // 2. Count commits behind using cached remote refs (no fetch — avoids double
     //    fetch with the subsequent ensure_base_up_to_date call in create()).
     let remote_ref = format!("origin/{base_branch}");
     let commits_behind = {
-        let count_out = git_in(repo_path)
-            .args(["rev-list", "--count", &format!("HEAD..{remote_ref}")])
-            .output();
+        let count_out = crate::command::output(git_in(repo_path).args([
+            "rev-list",
+            "--count",
+            &format!("HEAD..{remote_ref}"),
+        ]));
         match count_out {
             Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
                 .trim()
@@ -93,45 +118,37 @@ pub fn check_main_health(repo_path: &str, base_branch: &str) -> MainHealthStatus
 ///
 /// If no explicit branch is given, falls back to `resolve_base_branch`.
 ///
-/// The `force_dirty` and `pre_verified_clean` flags are forwarded to
-/// `ensure_base_up_to_date`.
+/// When `fetch` is `false` nothing is fetched and the worktree starts from the
+/// last-fetched `origin/*` refs.
 pub(super) fn resolve_and_update_base(
     repo_path: &str,
     from_branch: Option<&str>,
     configured_default: &str,
-    force_dirty: bool,
-    pre_verified_clean: bool,
-) -> Result<(String, Vec<String>)> {
+    fetch: bool,
+) -> Result<BaseStart> {
     let Some(requested) = from_branch else {
         let base = resolve_base_branch(repo_path, configured_default);
-        let warnings = ensure_base_up_to_date(repo_path, &base, force_dirty, pre_verified_clean)?;
-        return Ok((base, warnings));
+        return ensure_base_up_to_date(repo_path, &base, fetch);
     };
 
     // Perform a single fetch upfront to avoid redundant network calls
     // during prefix fallback attempts
     let mut fetch_warnings = Vec::new();
-    let fetch = git_in(repo_path).args(["fetch", "origin"]).output();
-    match fetch {
-        Ok(o) if o.status.success() => {}
-        _ => {
-            fetch_warnings.push(
-                "could not fetch from origin; creating worktree from local state".to_string(),
-            );
+    if fetch {
+        let fetch = crate::command::output(git_in(repo_path).args(["fetch", "origin"]));
+        match fetch {
+            Ok(o) if o.status.success() => {}
+            _ => {
+                fetch_warnings.push(FETCH_FAILED_WARNING.to_string());
+            }
         }
     }
 
     // Try exact name first (skip fetch since we already did it)
-    match ensure_base_up_to_date_with_fetch_control(
-        repo_path,
-        requested,
-        force_dirty,
-        pre_verified_clean,
-        false,
-    ) {
-        Ok(mut warnings) => {
-            warnings.extend(fetch_warnings);
-            Ok((requested.to_string(), warnings))
+    match ensure_base_up_to_date(repo_path, requested, false) {
+        Ok(mut start) => {
+            start.warnings.extend(fetch_warnings);
+            Ok(start)
         }
         Err(_first_err) => {
             // If the name already has a known prefix, don't try alternatives
@@ -142,15 +159,9 @@ pub(super) fn resolve_and_update_base(
             // Try feat/ and fix/ prefixes (skip fetch since we already did it)
             for prefix in &["feat/", "fix/"] {
                 let candidate = format!("{prefix}{requested}");
-                if let Ok(mut warnings) = ensure_base_up_to_date_with_fetch_control(
-                    repo_path,
-                    &candidate,
-                    force_dirty,
-                    pre_verified_clean,
-                    false,
-                ) {
-                    warnings.extend(fetch_warnings);
-                    return Ok((candidate, warnings));
+                if let Ok(mut start) = ensure_base_up_to_date(repo_path, &candidate, false) {
+                    start.warnings.extend(fetch_warnings);
+                    return Ok(start);
                 }
             }
 
@@ -160,6 +171,103 @@ pub(super) fn resolve_and_update_base(
     }
 }
 
+/// Whether the main checkout has uncommitted changes to tracked files.
+///
+/// Untracked files are excluded — they are the most common source of
+/// false-positive "dirty" reports (e.g. untracked test-helper directories).
+/// `false` when `git status` fails.
+pub(super) fn has_uncommitted_changes(repo_path: &str) -> bool {
+    crate::command::output(git_in(repo_path).args([
+        "status",
+        "--porcelain",
+        "--untracked-files=no",
+    ]))
+    .map(|o| o.status.success() && !o.stdout.is_empty())
+    .unwrap_or(false)
+}
+
+/// Warning returned when the main checkout is dirty. The new worktree
+/// branches from a ref, so the changes are simply not in it and creation
+/// goes ahead.
+pub(super) const DIRTY_CHECKOUT_WARNING: &str =
+    "main checkout has uncommitted changes; they are not in the new worktree";
+
+/// Warning emitted when `git fetch origin` fails during base resolution.
+pub(super) const FETCH_FAILED_WARNING: &str =
+    "could not fetch from origin; creating worktree from local state";
+
+/// How long [`origin_reachable`] waits for a TCP connection to origin's host.
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Cheap network check used by `base_fetch = "auto"`: can we open a TCP
+/// connection to the host behind `origin`?
+///
+/// Remotes without a network host (local paths, `file://`) count as reachable.
+/// A repo without an `origin` remote is reported unreachable.
+pub(super) fn origin_reachable(repo_path: &str) -> bool {
+    let Some(url) = crate::command::output(git_in(repo_path).args(["remote", "get-url", "origin"]))
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    else {
+        return false;
+    };
+    let Some((host, port)) = remote_host_port(&url) else {
+        return true;
+    };
+    let Ok(addrs) = (host.as_str(), port).to_socket_addrs() else {
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, REACHABILITY_TIMEOUT).is_ok())
+}
+
+/// Extract the network host and port from a git remote URL.
+///
+/// Handles `https://`, `http://`, `ssh://`, `git://` and scp-style
+/// (`git@host:owner/repo.git`) remotes. Returns `None` for local remotes.
+pub(super) fn remote_host_port(url: &str) -> Option<(String, u16)> {
+    let (default_port, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (443, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (80, rest)
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        (22, rest)
+    } else if let Some(rest) = url.strip_prefix("git://") {
+        (9418, rest)
+    } else if url.contains("://") || url.starts_with('/') || url.starts_with('.') {
+        return None;
+    } else {
+        // scp-style: [user@]host:path
+        let (host, _) = url.split_once(':')?;
+        let host = host.rsplit('@').next()?;
+        return (!host.is_empty()).then(|| (host.to_string(), 22));
+    };
+    let authority = rest.split('/').next()?;
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().ok()?),
+        None => (authority, default_port),
+    };
+    (!host.is_empty()).then(|| (host.to_string(), port))
+}
+
+/// One-line summary of the commit at `refname`: short hash, subject and
+/// relative commit date (e.g. `a1b2c3d "Fix login" (3 days ago)`).
+pub(super) fn describe_ref_tip(repo_path: &str, refname: &str) -> Option<String> {
+    crate::command::output(git_in(repo_path).args([
+        "log",
+        "-1",
+        "--format=%h \"%s\" (%cr)",
+        refname,
+    ]))
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    .filter(|s| !s.is_empty())
+}
+
 /// Resolve the base branch for a repo using a priority order:
 /// 1. The configured default branch (from DB) if it exists locally
 /// 2. `git symbolic-ref refs/remotes/origin/HEAD` (remote default)
@@ -185,30 +293,30 @@ pub(super) fn resolve_base_branch(repo_path: &str, configured_default: &str) ->
 
 /// Check if a local branch exists.
 pub(super) fn branch_exists(repo_path: &str, branch: &str) -> bool {
-    git_in(repo_path)
-        .args(["rev-parse", "--verify", &format!("refs/heads/{branch}")])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    crate::command::output(git_in(repo_path).args([
+        "rev-parse",
+        "--verify",
+        &format!("refs/heads/{branch}"),
+    ]))
+    .map(|o| o.status.success())
+    .unwrap_or(false)
 }
 
 /// Enumerate remote branches from `refs/remotes/origin/`.
 ///
 /// Returns branch names with the `origin/` prefix stripped, excluding `HEAD`.
 pub fn list_remote_branches(repo_path: &Path) -> Result<Vec<String>> {
-    let output = git_in(repo_path)
-        .args([
-            "for-each-ref",
-            "--format=%(refname:short)",
-            "refs/remotes/origin/",
-        ])
-        .output()
-        .map_err(|e| {
-            ConductorError::Git(SubprocessFailure::from_message(
-                "git for-each-ref",
-                e.to_string(),
-            ))
-        })?;
+    let output = crate::command::output(git_in(repo_path).args([
+        "for-each-ref",
+        "--format=%(refname:short)",
+        "refs/remotes/origin/",
+    ]))
+    .map_err(|e| {
+        ConductorError::Git(SubprocessFailure::from_message(
+            "git for-each-ref",
+            e.to_string(),
+        ))
+    })?;
     if !output.status.success() {
         return Err(ConductorError::Git(SubprocessFailure::from_message(
             "git for-each-ref",
@@ -237,10 +345,10 @@ fn parse_remote_branch_output(stdout: &str) -> Vec<String> {
 
 /// Detect the default branch from the remote's HEAD ref.
 pub(super) fn detect_remote_head(repo_path: &str) -> Option<String> {
-    let output = git_in(repo_path)
-        .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
-        .output()
-        .ok()?;
+    let output = crate::command::output(
+        git_in(repo_path).args(["symbolic-ref", "refs/remotes/origin/HEAD"]),
+    )
+    .ok()?;
     if !output.status.success() {
         return None;
     }
@@ -250,77 +358,56 @@ pub(super) fn detect_remote_head(repo_path: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-/// Ensure the base branch is up to date with the remote before creating a worktree.
+/// Where a new worktree's branch starts, found by [`ensure_base_up_to_date`].
+#[derive(Debug)]
+pub(super) struct BaseStart {
+    /// The base branch name, recorded on the worktree.
+    pub branch: String,
+    /// The ref the new branch is created at: `refs/remotes/origin/<base>`
+    /// when origin has the base, else `refs/heads/<base>`.
+    pub start_point: String,
+    /// Non-fatal problems (fetch failure, local commits left out, etc.).
+    pub warnings: Vec<String>,
+}
+
+/// Fetch the base branch and pick the ref a new worktree branches from.
 ///
-/// Returns a list of non-fatal warnings (fetch failure, diverged branch, etc.).
-/// Returns `Err` only for hard failures like a dirty working tree (unless `force_dirty` is true).
+/// The main checkout is never touched: no checkout, no merge, no change to
+/// its HEAD, index or files. The new branch starts at `origin/<base>` so it
+/// is current even when the main checkout's copy of the base is behind, and
+/// two creates (or a create and someone working in the main checkout) can
+/// run side by side. The local base ref is fast-forwarded as a convenience
+/// when that is a pure ref update; git refuses it while the base is checked
+/// out somewhere, and that is left alone.
 ///
-/// When `force_dirty` is `true`, the dirty-state check is skipped (the caller has
-/// already confirmed the user wants to proceed despite uncommitted changes).
+/// Returns `Err` only for hard failures like a base that exists neither
+/// locally nor on origin. Uncommitted changes in the main checkout are no
+/// obstacle; see [`DIRTY_CHECKOUT_WARNING`].
 ///
-/// When `pre_verified_clean` is `true`, the dirty-state check is also skipped because
-/// the caller has already confirmed the working tree is clean via `check_main_health`.
-/// Use this to avoid running `git status --porcelain` twice on the happy path.
+/// When `should_fetch` is `false`, `origin` is not fetched and the last-fetched
+/// remote-tracking refs are used.
 pub(super) fn ensure_base_up_to_date(
     repo_path: &str,
     base_branch: &str,
-    force_dirty: bool,
-    pre_verified_clean: bool,
-) -> Result<Vec<String>> {
-    ensure_base_up_to_date_with_fetch_control(
-        repo_path,
-        base_branch,
-        force_dirty,
-        pre_verified_clean,
-        true,
-    )
-}
-
-fn ensure_base_up_to_date_with_fetch_control(
-    repo_path: &str,
-    base_branch: &str,
-    force_dirty: bool,
-    pre_verified_clean: bool,
     should_fetch: bool,
-) -> Result<Vec<String>> {
+) -> Result<BaseStart> {
     let mut warnings = Vec::new();
 
-    // 1. Check for uncommitted changes in the repo working tree. Untracked files are
-    //    excluded — they don't affect `git worktree add` (new directory, separate from
-    //    the main working tree) and are the most common source of false-positive
-    //    "dirty" reports (e.g. untracked test-helper directories).
-    if !force_dirty && !pre_verified_clean {
-        let output = git_in(repo_path)
-            .args(["status", "--porcelain", "--untracked-files=no"])
-            .output()?;
-        if output.status.success() && !output.stdout.is_empty() {
-            return Err(ConductorError::InvalidInput(
-                "uncommitted changes on base branch, please commit or stash first".to_string(),
-            ));
-        }
-    }
-
-    // 2. Fetch from remote (soft failure — warn and allow local-only creation).
+    // 1. Fetch from remote (soft failure — warn and use the last-fetched refs).
     if should_fetch {
-        let fetch = git_in(repo_path).args(["fetch", "origin"]).output();
-        match fetch {
-            Ok(o) if o.status.success() => {}
-            _ => {
-                warnings.push(
-                    "could not fetch from origin; creating worktree from local state".to_string(),
-                );
-                return Ok(warnings);
-            }
+        let fetch = crate::command::output(git_in(repo_path).args(["fetch", "origin"]));
+        if !fetch.map(|o| o.status.success()).unwrap_or(false) {
+            warnings.push(FETCH_FAILED_WARNING.to_string());
         }
     }
 
-    // 3. Check if the remote tracking branch exists
+    // 2. Check if the remote tracking branch exists
     let remote_ref = format!("refs/remotes/origin/{base_branch}");
-    let has_remote = git_in(repo_path)
-        .args(["rev-parse", "--verify", &remote_ref])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
+    let local_ref = format!("refs/heads/{base_branch}");
+    let has_remote =
+        crate::command::output(git_in(repo_path).args(["rev-parse", "--verify", &remote_ref]))
+            .map(|o| o.status.success())
+            .unwrap_or(false);
 
     let has_local = branch_exists(repo_path, base_branch);
 
@@ -336,8 +423,17 @@ fn ensure_base_up_to_date_with_fetch_control(
         ));
     }
 
-    // 3b. If the branch exists on the remote but not locally, create a local tracking branch
-    if has_remote && !has_local {
+    if !has_remote {
+        // Local branch exists but no remote tracking — use local state as-is.
+        return Ok(BaseStart {
+            branch: base_branch.to_string(),
+            start_point: local_ref,
+            warnings,
+        });
+    }
+
+    // 2b. If the branch exists on the remote but not locally, create a local tracking branch
+    if !has_local {
         // Validate branch name for security - prevent injection of git options
         if base_branch.starts_with('-') || base_branch.contains('\0') || base_branch.contains('\n')
         {
@@ -347,79 +443,67 @@ fn ensure_base_up_to_date_with_fetch_control(
             )));
         }
 
-        let create = git_in(repo_path)
-            .args([
-                "branch",
-                "--track",
-                "--", // Explicitly separate options from branch names
-                base_branch,
-                &format!("origin/{base_branch}"),
-            ])
-            .output();
-        match create {
-            Ok(o) if o.status.success() => {}
-            _ => {
-                return Err(ConductorError::Git(
-                    crate::error::SubprocessFailure::from_message(
-                        "git",
-                        format!(
-                            "base branch '{}' exists on remote but could not create local tracking branch",
-                            base_branch
-                        ),
+        let create = crate::command::output(git_in(repo_path).args([
+            "branch",
+            "--track",
+            "--", // Explicitly separate options from branch names
+            base_branch,
+            &format!("origin/{base_branch}"),
+        ]));
+        if !create.map(|o| o.status.success()).unwrap_or(false) {
+            return Err(ConductorError::Git(
+                crate::error::SubprocessFailure::from_message(
+                    "git",
+                    format!(
+                        "base branch '{}' exists on remote but could not create local tracking branch",
+                        base_branch
                     ),
-                ));
-            }
+                ),
+            ));
         }
-        // Local branch is now set to the remote tip — no need to fast-forward.
-        return Ok(warnings);
+        return Ok(BaseStart {
+            branch: base_branch.to_string(),
+            start_point: remote_ref,
+            warnings,
+        });
     }
 
-    if !has_remote {
-        // Local branch exists but no remote tracking — use local state as-is.
-        return Ok(warnings);
-    }
-
-    // 4. Determine which branch is currently checked out
-    let current_branch = git_in(repo_path)
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
-        .ok()
-        .filter(|o| o.status.success())
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-        .unwrap_or_default();
-
-    // 5. Fast-forward the base branch
-    let origin_ref = format!("origin/{base_branch}");
-    if current_branch == base_branch {
-        // Base is already checked out — merge directly
-        let merge = git_in(repo_path)
-            .args(["merge", "--ff-only", &origin_ref])
-            .output();
-        if !merge.map(|o| o.status.success()).unwrap_or(false) {
-            warnings.push(format!(
-                "base branch '{}' has diverged from origin; consider `git pull --rebase`",
-                base_branch
-            ));
-        }
+    // 4. Local commits that origin lacks are not in the new worktree; say so.
+    let local_only = crate::command::output(git_in(repo_path).args([
+        "rev-list",
+        "--count",
+        &format!("{remote_ref}..{local_ref}"),
+    ]))
+    .ok()
+    .filter(|o| o.status.success())
+    .and_then(|o| {
+        String::from_utf8_lossy(&o.stdout)
+            .trim()
+            .parse::<u32>()
+            .ok()
+    })
+    .unwrap_or(0);
+    if local_only > 0 {
+        warnings.push(format!(
+            "base branch '{base_branch}' has diverged from origin; the worktree starts from \
+             origin/{base_branch} without {local_only} local commit(s)"
+        ));
     } else {
-        // Base exists locally but is not checked out — update it without checkout
-        // by using `git fetch . origin/{base}:refs/heads/{base}` (local fast-forward).
-        let ff = git_in(repo_path)
-            .args([
-                "fetch",
-                ".",
-                &format!("refs/remotes/origin/{base_branch}:refs/heads/{base_branch}"),
-            ])
-            .output();
-        if !ff.map(|o| o.status.success()).unwrap_or(false) {
-            warnings.push(format!(
-                "base branch '{}' has diverged from origin; consider `git pull --rebase`",
-                base_branch
-            ));
-        }
+        // 5. Fast-forward the local base ref with `git fetch . origin/{base}:{base}`.
+        //    Git refuses while the base is checked out in any worktree (including
+        //    the main checkout), which keeps this from moving anyone's HEAD.
+        let _ = crate::command::output(git_in(repo_path).args([
+            "fetch",
+            ".",
+            &format!("{remote_ref}:{local_ref}"),
+        ]));
     }
 
-    Ok(warnings)
+    Ok(BaseStart {
+        branch: base_branch.to_string(),
+        start_point: remote_ref,
+        warnings,
+    })
 }
 
 /// Fast-forward pull in a worktree directory using `git pull --ff-only`.
@@ -427,11 +511,11 @@ fn ensure_base_up_to_date_with_fetch_control(
 /// Returns `Ok(())` on success, `Err(message)` on failure (non-zero exit or spawn
 /// error). The `branch` parameter is used only for the error message.
 pub(super) fn pull_ff_only(worktree_path: &str, branch: &str) -> std::result::Result<(), String> {
-    match Command::new("git")
-        .args(["pull", "--ff-only"])
-        .current_dir(worktree_path)
-        .output()
-    {
+    match crate::command::output(
+        Command::new("git")
+            .args(["pull", "--ff-only"])
+            .current_dir(worktree_path),
+    ) {
         Err(e) => Err(format!(
             "failed to spawn git pull for branch '{}': {}",
             branch, e
@@ -453,10 +537,12 @@ pub(super) fn pull_ff_only(worktree_path: &str, branch: &str) -> std::result::Re
 /// worktree or branch may already be gone (e.g. manually removed).
 pub(super) fn remove_git_artifacts(repo_path: &str, worktree_path: &str, branch: &str) {
     if Path::new(worktree_path).exists() {
-        match git_in(repo_path)
-            .args(["worktree", "remove", worktree_path, "--force"])
-            .output()
-        {
+        match crate::command::output(git_in(repo_path).args([
+            "worktree",
+            "remove",
+            worktree_path,
+            "--force",
+        ])) {
             Ok(o) if !o.status.success() => {
                 tracing::warn!(
                     repo = repo_path,
@@ -501,10 +587,7 @@ pub(super) fn remove_git_artifacts(repo_path: &str, worktree_path: &str, branch:
     }
 
     if branch_exists(repo_path, branch) {
-        match git_in(repo_path)
-            .args(["branch", "-D", "--", branch])
-            .output()
-        {
+        match crate::command::output(git_in(repo_path).args(["branch", "-D", "--", branch])) {
             Ok(o) if !o.status.success() => {
                 tracing::warn!(
                     repo = repo_path,
@@ -535,10 +618,9 @@ pub(super) fn remove_git_artifacts(repo_path: &str, worktree_path: &str, branch:
 /// Delete a remote branch via `git push origin --delete <branch>`.
 /// Best-effort: failures are logged but not propagated.
 pub(super) fn delete_remote_branch(repo_path: &str, branch: &str) {
-    match git_in(repo_path)
-        .args(["push", "origin", "--delete", "--", branch])
-        .output()
-    {
+    match crate::command::output(
+        git_in(repo_path).args(["push", "origin", "--delete", "--", branch]),
+    ) {
         Ok(o) if !o.status.success() => {
             tracing::warn!(
                 repo = repo_path,
@@ -641,10 +723,9 @@ pub(super) fn fetch_pr_branch(repo_path: &str, pr_number: u32) -> Result<(String
             .to_string();
 
         // Add the remote if it doesn't already exist (ignore failure only if remote already exists)
-        match git_in(repo_path)
-            .args(["remote", "add", fork_owner, &fork_url])
-            .output()
-        {
+        match crate::command::output(
+            git_in(repo_path).args(["remote", "add", fork_owner, &fork_url]),
+        ) {
             Ok(output) if !output.status.success() => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 if !stderr.contains("already exists") {
@@ -737,38 +818,92 @@ pub(super) fn validate_branch_name(name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Detect package manager and install dependencies if applicable.
-pub(super) fn install_deps(worktree_path: &Path) {
-    let pkg = worktree_path.join("package.json");
-    if !pkg.exists() {
+/// Install dependencies with the toolchain's package manager, if it has an
+/// install step. Failures are ignored; a `[hooks] post_create` script takes
+/// over for projects the default does not fit.
+pub(super) fn install_deps(worktree_path: &Path, toolchain: &Toolchain) {
+    let Some(cmd) = toolchain.install_command(worktree_path) else {
         return;
-    }
-    // Skip if the package.json has no dependencies to install.
-    if let Ok(contents) = std::fs::read_to_string(&pkg) {
-        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&contents) {
-            let has_deps = v.get("dependencies").is_some()
-                || v.get("devDependencies").is_some()
-                || v.get("peerDependencies").is_some();
-            if !has_deps {
-                return;
+    };
+    let _ = crate::command::output_with_timeout(
+        Command::new(&cmd[0])
+            .args(&cmd[1..])
+            .current_dir(worktree_path),
+        crate::command::timeouts().setup(),
+    );
+}
+
+/// Run the project's `[project] setup` commands in a new worktree, in order.
+///
+/// A failing command does not abort worktree creation; it is reported as a
+/// warning and the remaining commands still run.
+pub(super) fn run_setup_commands(
+    worktree_path: &Path,
+    commands: &[String],
+    env: &[(&str, &str)],
+) -> Vec<String> {
+    run_shell_commands("setup command", worktree_path, commands, env)
+}
+
+/// Run `commands` with `sh -c` in `dir`, in order, with `env` added to the
+/// environment. Returns one warning per command that failed; `label` names
+/// the commands in them.
+pub(super) fn run_shell_commands(
+    label: &str,
+    dir: &Path,
+    commands: &[String],
+    env: &[(&str, &str)],
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for cmd in commands {
+        match crate::command::output_with_timeout(
+            crate::platform::shell_command(cmd)
+                .current_dir(dir)
+                .envs(env.iter().copied()),
+            crate::command::timeouts().setup(),
+        ) {
+            Ok(out) if out.status.success() => {}
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                let detail = stderr.lines().last().unwrap_or_default().trim();
+                warnings.push(format!("{label} `{cmd}` failed ({}): {detail}", out.status));
             }
+            Err(e) => warnings.push(format!("{label} `{cmd}` could not run: {e}")),
         }
     }
-    // Detect lockfile to choose the right package manager.
-    let pm = if worktree_path.join("bun.lockb").exists() || worktree_path.join("bun.lock").exists()
-    {
-        "bun"
-    } else if worktree_path.join("pnpm-lock.yaml").exists() {
-        "pnpm"
-    } else if worktree_path.join("yarn.lock").exists() {
-        "yarn"
-    } else {
-        "npm"
-    };
-    let _ = Command::new(pm)
-        .arg("install")
-        .current_dir(worktree_path)
-        .output();
+    warnings
+}
+
+/// Bytes of a failing pre-push check's output kept for the error.
+const PRE_PUSH_OUTPUT_MAX_BYTES: usize = 4_000;
+
+/// Run the `[project] pre_push` commands in a worktree, in order, stopping at
+/// the first that fails.
+pub(super) fn run_pre_push_checks(
+    worktree_path: &Path,
+    slug: &str,
+    commands: &[String],
+) -> Result<()> {
+    for cmd in commands {
+        let output = match crate::command::output_with_timeout(
+            crate::platform::shell_command(cmd).current_dir(worktree_path),
+            crate::command::timeouts().setup(),
+        ) {
+            Ok(out) if out.status.success() => continue,
+            Ok(out) => {
+                let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&out.stderr));
+                text
+            }
+            Err(e) => format!("could not run: {e}"),
+        };
+        return Err(ConductorError::PrePushCheckFailed {
+            slug: slug.to_string(),
+            command: cmd.clone(),
+            output: tail_lines(output.trim_end(), PRE_PUSH_OUTPUT_MAX_BYTES),
+        });
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -796,11 +931,73 @@ mod tests {
         false
     }
 
+    #[test]
+    fn run_setup_commands_continues_after_failure() {
+        let dir = TempDir::new().unwrap();
+        let warnings = run_setup_commands(
+            dir.path(),
+            &[
+                "echo broken >&2; exit 3".to_string(),
+                "touch ready".to_string(),
+            ],
+            &[],
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("broken"), "{warnings:?}");
+        assert!(dir.path().join("ready").exists());
+    }
+
+    #[test]
+    fn run_pre_push_checks_stops_at_first_failure() {
+        let dir = TempDir::new().unwrap();
+        run_pre_push_checks(dir.path(), "feat", &["true".to_string()]).unwrap();
+
+        let err = run_pre_push_checks(
+            dir.path(),
+            "feat",
+            &["echo boom; exit 1".to_string(), "touch reached".to_string()],
+        )
+        .unwrap_err();
+        match err {
+            ConductorError::PrePushCheckFailed {
+                command, output, ..
+            } => {
+                assert_eq!(command, "echo boom; exit 1");
+                assert_eq!(output, "boom");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(!dir.path().join("reached").exists());
+    }
+
+    #[test]
+    fn remote_host_port_parses_common_remote_forms() {
+        let hp = |h: &str, p: u16| Some((h.to_string(), p));
+        assert_eq!(
+            remote_host_port("https://github.com/o/r.git"),
+            hp("github.com", 443)
+        );
+        assert_eq!(
+            remote_host_port("http://user@git.local:8080/o/r"),
+            hp("git.local", 8080)
+        );
+        assert_eq!(
+            remote_host_port("ssh://git@github.com:2222/o/r.git"),
+            hp("github.com", 2222)
+        );
+        assert_eq!(
+            remote_host_port("git@github.com:o/r.git"),
+            hp("github.com", 22)
+        );
+        assert_eq!(remote_host_port("/srv/git/r.git"), None);
+        assert_eq!(remote_host_port("file:///srv/git/r.git"), None);
+    }
+
     #[test]
     fn install_deps_no_package_json_returns_early() {
         let dir = TempDir::new().unwrap();
         // No package.json present — install_deps must return without error or panic.
-        install_deps(dir.path());
+        install_deps(dir.path(), &Toolchain::detect(dir.path()));
         // If we get here the early-exit path was taken (no subprocess tried).
     }
 
@@ -813,7 +1010,7 @@ mod tests {
         )
         .unwrap();
         // install_deps should return early because there are no dep fields.
-        install_deps(dir.path());
+        install_deps(dir.path(), &Toolchain::detect(dir.path()));
         // Reaching here means no panic / no subprocess was launched for a
         // package.json that has no installable dependencies.
     }
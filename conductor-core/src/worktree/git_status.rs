@@ -0,0 +1,235 @@
+//! Local git state of worktree checkouts: uncommitted changes, commits not
+//! yet pushed to or pulled from origin, and the last commit.
+//!
+//! Everything is read from the checkout and its cached `origin/*` refs; no
+//! fetch happens, so "behind" is only as fresh as the last fetch.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::git::{check_output, git_in};
+
+use super::listing::{ahead_behind, AheadBehind, WorktreeListEntry};
+use super::manager::WorktreeManager;
+use super::types::Worktree;
+
+/// The most recent commit on a worktree's branch.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastCommit {
+    pub sha: String,
+    /// First line of the commit message.
+    pub summary: String,
+    pub author: String,
+    /// Committer date, RFC 3339.
+    pub committed_at: String,
+}
+
+/// Git state of one worktree checkout.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeGitStatus {
+    /// Modified, staged and untracked paths.
+    pub uncommitted: u32,
+    /// Commits on the branch that `origin/<branch>` lacks (ahead) and the
+    /// reverse (behind). `None` when the branch was never pushed.
+    pub upstream: Option<AheadBehind>,
+    pub last_commit: Option<LastCommit>,
+}
+
+impl WorktreeGitStatus {
+    /// Whether the worktree holds work that is not on origin: uncommitted
+    /// changes, unpushed commits, or commits on a branch never pushed.
+    pub fn has_unpushed_work(&self) -> bool {
+        self.uncommitted > 0
+            || match self.upstream {
+                Some(ab) => ab.ahead > 0,
+                None => self.last_commit.is_some(),
+            }
+    }
+
+    /// Compact summary for lists, e.g. `3 changed ↑2 ↓1`, `not pushed` or
+    /// `clean`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.uncommitted > 0 {
+            parts.push(format!("{} changed", self.uncommitted));
+        }
+        match self.upstream {
+            Some(ab) => {
+                if ab.ahead > 0 {
+                    parts.push(format!("↑{}", ab.ahead));
+                }
+                if ab.behind > 0 {
+                    parts.push(format!("↓{}", ab.behind));
+                }
+            }
+            None => parts.push("not pushed".to_string()),
+        }
+        if parts.is_empty() {
+            "clean".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// Read the git state of the checkout at `path` on `branch`.
+pub fn git_status(path: &Path, branch: &str) -> Result<WorktreeGitStatus> {
+    // Each worktree carries its own `.conductor.db`; it is not the user's work.
+    let porcelain = check_output(git_in(path).args([
+        "status",
+        "--porcelain",
+        "--",
+        ".",
+        ":(exclude).conductor.db*",
+    ]))?;
+    let uncommitted = String::from_utf8_lossy(&porcelain.stdout)
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count() as u32;
+
+    let remote = format!("origin/{branch}");
+    let has_remote = crate::command::output(git_in(path).args([
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("refs/remotes/{remote}"),
+    ]))
+    .is_ok_and(|o| o.status.success());
+    let upstream = if has_remote {
+        ahead_behind(path, &remote)
+    } else {
+        None
+    };
+
+    Ok(WorktreeGitStatus {
+        uncommitted,
+        upstream,
+        last_commit: last_commit(path),
+    })
+}
+
+/// Git state of every live worktree in `worktrees` whose checkout exists,
+/// keyed by worktree id. Worktrees git cannot read are left out.
+pub fn git_statuses(worktrees: &[Worktree]) -> HashMap<String, WorktreeGitStatus> {
+    worktrees
+        .iter()
+        .filter(|wt| wt.is_active() && Path::new(&wt.path).exists())
+        .filter_map(|wt| match git_status(Path::new(&wt.path), &wt.branch) {
+            Ok(status) => Some((wt.id.clone(), status)),
+            Err(e) => {
+                tracing::warn!(worktree = %wt.slug, "git status failed: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+fn last_commit(path: &Path) -> Option<LastCommit> {
+    // An unborn branch has no HEAD to log.
+    let out =
+        check_output(git_in(path).args(["log", "-1", "--format=%H%x00%s%x00%an%x00%cI"])).ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut fields = text.trim_end_matches('\n').splitn(4, '\0');
+    Some(LastCommit {
+        sha: fields.next().filter(|s| !s.is_empty())?.to_string(),
+        summary: fields.next()?.to_string(),
+        author: fields.next()?.to_string(),
+        committed_at: fields.next()?.to_string(),
+    })
+}
+
+impl WorktreeManager<'_> {
+    /// Git state of a live worktree's checkout.
+    pub fn git_status(&self, repo_slug: &str, name: &str) -> Result<WorktreeGitStatus> {
+        let (_, wt) = self.get_active_worktree(repo_slug, name)?;
+        git_status(Path::new(&wt.path), &wt.branch)
+    }
+
+    /// Fill in [`WorktreeListEntry::git_status`] for live worktrees whose
+    /// checkout exists.
+    pub fn fill_git_status(&self, entries: &mut [WorktreeListEntry]) {
+        let worktrees: Vec<Worktree> = entries
+            .iter()
+            .map(|e| e.worktree.worktree.clone())
+            .collect();
+        let mut statuses = git_statuses(&worktrees);
+        for entry in entries {
+            entry.git_status = statuses.remove(&entry.worktree.worktree.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = git_in(dir).args(args).output().unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    }
+
+    #[test]
+    fn git_status_counts_changes_and_unpushed_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = dir.path().join("origin.git");
+        let wt = dir.path().join("wt");
+        git(
+            dir.path(),
+            &["init", "-q", "--bare", origin.to_str().unwrap()],
+        );
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                wt.to_str().unwrap(),
+            ],
+        );
+        git(&wt, &["checkout", "-q", "-b", "feat"]);
+        git(&wt, &["config", "user.email", "t@example.com"]);
+        git(&wt, &["config", "user.name", "t"]);
+
+        let status = git_status(&wt, "feat").unwrap();
+        assert_eq!(status.last_commit, None);
+        assert_eq!(status.upstream, None);
+        assert!(!status.has_unpushed_work());
+
+        std::fs::write(wt.join("a"), "a\n").unwrap();
+        git(&wt, &["add", "a"]);
+        git(&wt, &["commit", "-qm", "first"]);
+        let status = git_status(&wt, "feat").unwrap();
+        assert_eq!(status.upstream, None);
+        assert_eq!(status.last_commit.as_ref().unwrap().summary, "first");
+        assert_eq!(status.last_commit.as_ref().unwrap().author, "t");
+        assert!(status.has_unpushed_work());
+        assert_eq!(status.summary(), "not pushed");
+
+        git(&wt, &["push", "-q", "origin", "feat"]);
+        std::fs::write(wt.join("a"), "b\n").unwrap();
+        git(&wt, &["commit", "-qam", "second"]);
+        std::fs::write(wt.join("untracked"), "").unwrap();
+        std::fs::write(wt.join(".conductor.db"), "").unwrap();
+        let status = git_status(&wt, "feat").unwrap();
+        assert_eq!(status.uncommitted, 1);
+        assert_eq!(
+            status.upstream,
+            Some(AheadBehind {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        assert_eq!(status.summary(), "1 changed ↑1");
+
+        std::fs::remove_file(wt.join("untracked")).unwrap();
+        git(&wt, &["push", "-q", "origin", "feat"]);
+        let status = git_status(&wt, "feat").unwrap();
+        assert!(!status.has_unpushed_work());
+        assert_eq!(status.summary(), "clean");
+    }
+}
@@ -0,0 +1,157 @@
+//! `[hooks]` scripts from a repo's `.conductor/config.toml`, run when a
+//! worktree is created, deleted and merged (see [`WorktreeHooks`]).
+
+use std::path::Path;
+
+use rusqlite::{named_params, Connection, OptionalExtension};
+
+use crate::config::{RepoConfig, WorktreeHooks};
+
+use super::git_helpers::run_shell_commands;
+
+/// A point in a worktree's life that runs configured scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WorktreeHook {
+    PostCreate,
+    PreDelete,
+    PostMerge,
+}
+
+impl WorktreeHook {
+    fn name(self) -> &'static str {
+        match self {
+            Self::PostCreate => "post_create",
+            Self::PreDelete => "pre_delete",
+            Self::PostMerge => "post_merge",
+        }
+    }
+
+    fn commands(self, hooks: &WorktreeHooks) -> &[String] {
+        match self {
+            Self::PostCreate => &hooks.post_create,
+            Self::PreDelete => &hooks.pre_delete,
+            Self::PostMerge => &hooks.post_merge,
+        }
+    }
+}
+
+/// The worktree a hook runs for.
+pub(crate) struct HookTarget<'a> {
+    pub repo_path: &'a str,
+    pub worktree_path: &'a str,
+    pub branch: &'a str,
+    /// Internal ID of the linked ticket.
+    pub ticket_id: Option<&'a str>,
+}
+
+/// The `[hooks]` section of the repo checked out at `repo_path`; empty when
+/// the repo config cannot be read.
+pub(crate) fn load_hooks(repo_path: &str) -> WorktreeHooks {
+    RepoConfig::load(Path::new(repo_path))
+        .map(|rc| rc.hooks)
+        .unwrap_or_else(|e| {
+            tracing::warn!(repo = %repo_path, "ignoring unreadable .conductor/config.toml: {e}");
+            WorktreeHooks::default()
+        })
+}
+
+/// Environment passed to hook and setup commands for `target`. The ticket
+/// is identified by its source ID (`123`, `PROJ-12`), as users know it.
+pub(super) fn hook_env(conn: &Connection, target: &HookTarget<'_>) -> Vec<(&'static str, String)> {
+    let ticket = target
+        .ticket_id
+        .and_then(|id| {
+            conn.query_row(
+                "SELECT source_id FROM tickets WHERE id = :id",
+                named_params! { ":id": id },
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .ok()
+            .flatten()
+        })
+        .unwrap_or_default();
+    vec![
+        ("CONDUCTOR_WORKTREE_PATH", target.worktree_path.to_string()),
+        ("CONDUCTOR_BRANCH", target.branch.to_string()),
+        ("CONDUCTOR_TICKET_ID", ticket),
+    ]
+}
+
+/// Run the `hook` commands in `hooks` for `target` and return a warning per
+/// failed command. Post-create and pre-delete scripts run in the worktree
+/// (pre-delete is skipped when it is already gone); post-merge scripts run
+/// in the repo checkout, since the worktree has been removed by then.
+pub(crate) fn run_hook(
+    conn: &Connection,
+    hooks: &WorktreeHooks,
+    hook: WorktreeHook,
+    target: &HookTarget<'_>,
+) -> Vec<String> {
+    let commands = hook.commands(hooks);
+    if commands.is_empty() {
+        return Vec::new();
+    }
+    let dir = match hook {
+        WorktreeHook::PostCreate | WorktreeHook::PreDelete => Path::new(target.worktree_path),
+        WorktreeHook::PostMerge => Path::new(target.repo_path),
+    };
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+    let env = hook_env(conn, target);
+    let env: Vec<(&str, &str)> = env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let warnings = run_shell_commands(&format!("{} hook", hook.name()), dir, commands, &env);
+    for warning in &warnings {
+        tracing::warn!(branch = %target.branch, "{warning}");
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_hook_passes_worktree_env_and_reports_failures() {
+        let conn = crate::test_helpers::setup_db();
+        conn.execute(
+            "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, \
+             raw_json) VALUES ('t1', 'r1', 'github', '42', 'T', 'open', '2024-01-01T00:00:00Z', '{}')",
+            [],
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let wt_path = dir.path().to_str().unwrap();
+        let hooks = WorktreeHooks {
+            post_create: vec![
+                "echo \"$CONDUCTOR_WORKTREE_PATH $CONDUCTOR_BRANCH $CONDUCTOR_TICKET_ID\" > env.txt"
+                    .into(),
+                "echo nope >&2; exit 1".into(),
+            ],
+            ..Default::default()
+        };
+        let target = HookTarget {
+            repo_path: "/nonexistent",
+            worktree_path: wt_path,
+            branch: "feat/x",
+            ticket_id: Some("t1"),
+        };
+
+        let warnings = run_hook(&conn, &hooks, WorktreeHook::PostCreate, &target);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("post_create hook"), "{warnings:?}");
+        assert!(warnings[0].contains("nope"), "{warnings:?}");
+        let env = std::fs::read_to_string(dir.path().join("env.txt")).unwrap();
+        assert_eq!(env.trim(), format!("{wt_path} feat/x 42"));
+
+        // Nothing is configured for pre_delete, and post_merge's directory
+        // (the repo checkout) does not exist.
+        assert!(run_hook(&conn, &hooks, WorktreeHook::PreDelete, &target).is_empty());
+        let hooks = WorktreeHooks {
+            post_merge: vec!["exit 1".into()],
+            ..Default::default()
+        };
+        assert!(run_hook(&conn, &hooks, WorktreeHook::PostMerge, &target).is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+//! Filtered, sorted worktree listings shared by `conductor worktree list` and
+//! the worktree list endpoints of the web API.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+use rusqlite::ToSql;
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::git::git_in;
+use crate::repo::RepoManager;
+use crate::repo_visibility::{visible_repo_sql, Viewer};
+
+use super::git_helpers::resolve_base_branch;
+use super::git_status::WorktreeGitStatus;
+use super::manager::{enriched_worktree_base, map_enriched_row};
+use super::types::{WorktreeStatus, WorktreeWithStatus};
+use super::{WorktreeManager, LIVE_STATUSES_SQL};
+
+/// Default age, in days, after which a live worktree without agent activity
+/// counts as stale.
+pub const DEFAULT_STALE_DAYS: u32 = 7;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeSort {
+    /// Live worktrees first, then oldest first.
+    #[default]
+    Created,
+    Slug,
+    Status,
+    /// Most recent agent activity first.
+    Activity,
+    /// Highest total agent cost first.
+    Cost,
+}
+
+impl WorktreeSort {
+    pub const ALL: [WorktreeSort; 5] = [
+        Self::Created,
+        Self::Slug,
+        Self::Status,
+        Self::Activity,
+        Self::Cost,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Slug => "slug",
+            Self::Status => "status",
+            Self::Activity => "activity",
+            Self::Cost => "cost",
+        }
+    }
+
+    fn order_by(&self) -> String {
+        let live = LIVE_STATUSES_SQL;
+        match self {
+            Self::Created => {
+                format!("CASE WHEN w.status IN {live} THEN 0 ELSE 1 END, w.created_at")
+            }
+            Self::Slug => "w.slug, w.created_at".to_string(),
+            Self::Status => "w.status, w.created_at".to_string(),
+            Self::Activity => "last_activity_at DESC".to_string(),
+            Self::Cost => "total_cost_usd DESC, w.created_at".to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for WorktreeSort {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::ALL.iter().map(WorktreeSort::as_str).collect();
+                format!("unknown sort '{s}' (expected one of: {})", valid.join(", "))
+            })
+    }
+}
+
+/// Which worktrees a listing returns and in what order. The default lists
+/// every worktree in every repo.
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeListFilter {
+    pub repo_id: Option<String>,
+    /// Only worktrees in one of these statuses; empty means any status.
+    pub statuses: Vec<WorktreeStatus>,
+    /// Only worktrees linked to this ticket, by internal ID or source ID
+    /// (`42`, `#42` or `PROJ-42`).
+    pub ticket: Option<String>,
+    /// Only live worktrees whose last agent run (or creation, if none ran)
+    /// is more than this many days old.
+    pub stale_days: Option<u32>,
+    pub sort: WorktreeSort,
+    /// Leave out worktrees of repos that are private to someone else.
+    pub viewer: Viewer,
+}
+
+/// Commits a worktree's branch has that its base branch lacks, and vice versa.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AheadBehind {
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// One row of a worktree listing.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeListEntry {
+    #[serde(flatten)]
+    pub worktree: WorktreeWithStatus,
+    pub repo_slug: String,
+    /// Start time of the worktree's most recent agent run.
+    pub last_agent_at: Option<String>,
+    /// Summed cost of all agent runs in the worktree.
+    pub total_cost_usd: f64,
+    /// Only filled in by [`WorktreeManager::fill_ahead_behind`], which runs git.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ahead_behind: Option<AheadBehind>,
+    /// Only filled in by [`WorktreeManager::fill_git_status`], which runs git.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_status: Option<WorktreeGitStatus>,
+}
+
+impl WorktreeListEntry {
+    /// Last agent run start, or the creation time for worktrees no agent ran in.
+    pub fn last_activity_at(&self) -> &str {
+        self.last_agent_at
+            .as_deref()
+            .unwrap_or(&self.worktree.worktree.created_at)
+    }
+}
+
+impl WorktreeManager<'_> {
+    /// List worktrees matching `filter` with their repo, agent activity and cost.
+    pub fn list_entries(&self, filter: &WorktreeListFilter) -> Result<Vec<WorktreeListEntry>> {
+        let mut clauses = Vec::new();
+        let mut params: Vec<(&str, &dyn ToSql)> = Vec::new();
+
+        if let Some(ref repo_id) = filter.repo_id {
+            clauses.push("w.repo_id = :repo_id".to_string());
+            params.push((":repo_id", repo_id));
+        }
+        if !filter.statuses.is_empty() {
+            let statuses: Vec<String> = filter
+                .statuses
+                .iter()
+                .map(|s| format!("'{}'", s.as_str()))
+                .collect();
+            clauses.push(format!("w.status IN ({})", statuses.join(", ")));
+        }
+        let ticket = filter
+            .ticket
+            .as_deref()
+            .map(|t| t.trim_start_matches('#').to_string());
+        if let Some(ref ticket) = ticket {
+            clauses.push("(t.id = :ticket OR t.source_id = :ticket)".to_string());
+            params.push((":ticket", ticket));
+        }
+        let stale_cutoff = filter
+            .stale_days
+            .map(|days| (Utc::now() - Duration::days(i64::from(days))).to_rfc3339());
+        if let Some(ref cutoff) = stale_cutoff {
+            clauses.push(format!(
+                "w.status IN {LIVE_STATUSES_SQL} AND last_activity_at < :stale_cutoff"
+            ));
+            params.push((":stale_cutoff", cutoff));
+        }
+        let viewer = filter.viewer.owner_param();
+        if let Some(visible) = visible_repo_sql(&filter.viewer, "w.repo_id", ":viewer") {
+            clauses.push(visible);
+            params.push((":viewer", &viewer));
+        }
+
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT w.* FROM ( \
+               SELECT e.*, r.slug AS repo_slug, stats.last_agent_at, \
+                      COALESCE(stats.total_cost_usd, 0.0) AS total_cost_usd, \
+                      COALESCE(stats.last_agent_at, e.created_at) AS last_activity_at \
+               FROM ({base}) e \
+               JOIN repos r ON r.id = e.repo_id \
+               LEFT JOIN ( \
+                 SELECT worktree_id, MAX(started_at) AS last_agent_at, \
+                        SUM(cost_usd) AS total_cost_usd \
+                 FROM agent_runs WHERE worktree_id IS NOT NULL GROUP BY worktree_id \
+               ) stats ON stats.worktree_id = e.id \
+             ) w \
+             LEFT JOIN tickets t ON t.id = w.ticket_id \
+             {where_sql} \
+             ORDER BY {order_by}",
+            base = enriched_worktree_base(),
+            order_by = filter.sort.order_by(),
+        );
+
+        query_collect(self.conn, &sql, params.as_slice(), |row| {
+            Ok(WorktreeListEntry {
+                worktree: map_enriched_row(row)?,
+                repo_slug: row.get("repo_slug")?,
+                last_agent_at: row.get("last_agent_at")?,
+                total_cost_usd: row.get("total_cost_usd")?,
+                ahead_behind: None,
+                git_status: None,
+            })
+        })
+    }
+
+    /// Fill in [`WorktreeListEntry::ahead_behind`] for live worktrees whose
+    /// directory exists, against the worktree's base branch (or the repo's
+    /// default branch when none was recorded).
+    pub fn fill_ahead_behind(&self, entries: &mut [WorktreeListEntry]) -> Result<()> {
+        let repos: HashMap<String, _> = RepoManager::new(self.conn, self.config)
+            .list()?
+            .into_iter()
+            .map(|r| (r.id.clone(), r))
+            .collect();
+        let mut default_bases: HashMap<&str, String> = HashMap::new();
+
+        for entry in entries.iter_mut() {
+            let wt = &entry.worktree.worktree;
+            if !wt.is_active() || !Path::new(&wt.path).exists() {
+                continue;
+            }
+            let Some(repo) = repos.get(&wt.repo_id) else {
+                continue;
+            };
+            let base = match wt.base_branch {
+                Some(ref base) => base.clone(),
+                None => default_bases
+                    .entry(repo.id.as_str())
+                    .or_insert_with(|| resolve_base_branch(&repo.local_path, &repo.default_branch))
+                    .clone(),
+            };
+            entry.ahead_behind = ahead_behind(&wt.path, &base);
+        }
+        Ok(())
+    }
+}
+
+/// Count commits on `HEAD` not on `base` (ahead) and on `base` not on `HEAD`
+/// (behind) in the checkout at `path`. `None` when git cannot compare them.
+pub(super) fn ahead_behind(path: impl AsRef<Path>, base: &str) -> Option<AheadBehind> {
+    let output = crate::command::output(git_in(path).args([
+        "rev-list",
+        "--left-right",
+        "--count",
+        &format!("HEAD...{base}"),
+    ]))
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split_whitespace().map(str::parse::<u32>);
+    match (counts.next(), counts.next()) {
+        (Some(Ok(ahead)), Some(Ok(behind))) => Some(AheadBehind { ahead, behind }),
+        _ => None,
+    }
+}
+
+/// Parse a comma-separated list of worktree statuses, as accepted by
+/// `--status` and the `status` query parameter.
+pub fn parse_status_list(s: &str) -> Result<Vec<WorktreeStatus>> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<WorktreeStatus>()
+                .map_err(ConductorError::InvalidInput)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_helpers::setup_db;
+
+    fn seed(conn: &rusqlite::Connection) {
+        conn.execute_batch(
+            "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at) VALUES \
+               ('w2', 'r1', 'feat-b', 'feat/b', '/nonexistent/b', 'active', '2024-01-02T00:00:00Z'), \
+               ('w3', 'r1', 'feat-c', 'feat/c', '/nonexistent/c', 'merged', '2024-01-03T00:00:00Z'); \
+             INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, \
+                                  labels, url, synced_at, raw_json) \
+             VALUES ('t1', 'r1', 'github', '42', 'Fix it', '', 'open', '[]', '', \
+                     '2024-01-01T00:00:00Z', '{}'); \
+             UPDATE worktrees SET ticket_id = 't1' WHERE id = 'w2'; \
+             INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, cost_usd) VALUES \
+               ('a1', 'w2', 'p', 'completed', '2099-01-01T00:00:00Z', 1.5), \
+               ('a2', 'w2', 'p', 'completed', '2099-01-02T00:00:00Z', 2.0), \
+               ('a3', 'w1', 'p', 'completed', '2024-01-05T00:00:00Z', 0.25);",
+        )
+        .unwrap();
+    }
+
+    fn slugs(entries: &[WorktreeListEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|e| e.worktree.worktree.slug.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn list_entries_aggregates_cost_and_activity() {
+        let conn = setup_db();
+        seed(&conn);
+        let config = Config::default();
+        let mgr = WorktreeManager::new(&conn, &config);
+
+        let entries = mgr.list_entries(&WorktreeListFilter::default()).unwrap();
+        assert_eq!(slugs(&entries), ["feat-test", "feat-b", "feat-c"]);
+        let b = &entries[1];
+        assert_eq!(b.repo_slug, "test-repo");
+        assert!((b.total_cost_usd - 3.5).abs() < 1e-9);
+        assert_eq!(b.last_activity_at(), "2099-01-02T00:00:00Z");
+        assert_eq!(b.worktree.ticket_number.as_deref(), Some("42"));
+        assert_eq!(entries[2].total_cost_usd, 0.0);
+        assert_eq!(entries[2].last_activity_at(), "2024-01-03T00:00:00Z");
+
+        let by_cost = mgr
+            .list_entries(&WorktreeListFilter {
+                sort: WorktreeSort::Cost,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(slugs(&by_cost), ["feat-b", "feat-test", "feat-c"]);
+    }
+
+    #[test]
+    fn list_entries_filters_by_status_ticket_and_staleness() {
+        let conn = setup_db();
+        seed(&conn);
+        let config = Config::default();
+        let mgr = WorktreeManager::new(&conn, &config);
+        let list = |filter: WorktreeListFilter| mgr.list_entries(&filter).unwrap();
+
+        let merged = list(WorktreeListFilter {
+            statuses: vec![WorktreeStatus::Merged],
+            ..Default::default()
+        });
+        assert_eq!(slugs(&merged), ["feat-c"]);
+
+        for ticket in ["42", "#42", "t1"] {
+            let linked = list(WorktreeListFilter {
+                ticket: Some(ticket.into()),
+                ..Default::default()
+            });
+            assert_eq!(slugs(&linked), ["feat-b"], "ticket {ticket}");
+        }
+
+        // feat-test last ran in 2024; feat-b ran "in the future"; feat-c is merged.
+        let stale = list(WorktreeListFilter {
+            stale_days: Some(DEFAULT_STALE_DAYS),
+            ..Default::default()
+        });
+        assert_eq!(slugs(&stale), ["feat-test"]);
+
+        let other_repo = list(WorktreeListFilter {
+            repo_id: Some("r2".into()),
+            ..Default::default()
+        });
+        assert!(other_repo.is_empty());
+    }
+
+    #[test]
+    fn parses_sorts_and_status_lists() {
+        assert_eq!("cost".parse::<WorktreeSort>(), Ok(WorktreeSort::Cost));
+        assert!("size".parse::<WorktreeSort>().is_err());
+        assert_eq!(
+            parse_status_list("active, parked").unwrap(),
+            [WorktreeStatus::Active, WorktreeStatus::Parked]
+        );
+        assert!(parse_status_list("active,bogus").is_err());
+    }
+}
@@ -4,16 +4,24 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
-use crate::config::Config;
+use crate::changelog::{self, ChangelogEntry, ChangelogOutcome};
+use crate::config::{BaseFetch, Config, RepoConfig};
 use crate::db::query_collect;
 use crate::error::{ConductorError, Result};
-use crate::git::{check_gh_output, check_output, git_in};
+use crate::git::{check_gh_output, check_output, check_output_with_retry, git_in};
 use crate::repo::RepoManager;
+use crate::repo_visibility::Viewer;
 use crate::tickets::TicketSyncer;
+use crate::toolchain::Toolchain;
 
 use super::git_helpers::*;
-use super::types::{map_worktree_row, Worktree, WorktreeStatus, WorktreeWithStatus};
-use super::{WORKTREE_COLUMNS, WORKTREE_COLUMNS_W};
+use super::hooks::{hook_env, load_hooks, run_hook, HookTarget, WorktreeHook};
+use super::quota::{scan_workspace, QuotaLevel, WorkspaceUsage};
+use super::types::{
+    map_worktree_row, AbandonReason, AbandonReasonCount, BaseBranchSource, ResolvedBase, Worktree,
+    WorktreeCreateStage, WorktreeOutcomes, WorktreeStatus, WorktreeWithStatus,
+};
+use super::{LIVE_STATUSES_SQL, WORKTREE_COLUMNS, WORKTREE_COLUMNS_W};
 
 /// Map a ticket label to the conventional-commit branch prefix it implies.
 ///
@@ -35,6 +43,53 @@ pub fn label_to_branch_prefix(labels: &[&str]) -> &'static str {
     "feat"
 }
 
+/// Derive a worktree slug from a ticket's source_id, title, and labels.
+/// Format: `{prefix}-{source_id}-{slugified-title}`, e.g. `feat-15-tui-create-worktree`.
+/// The prefix is inferred from labels via [`label_to_branch_prefix`].
+/// Title portion is truncated to keep the total slug under ~40 chars.
+pub fn derive_worktree_slug(source_id: &str, title: &str, labels: &[String]) -> String {
+    let refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let prefix = label_to_branch_prefix(&refs);
+
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    // Collapse consecutive dashes
+    let mut collapsed = String::with_capacity(slug.len());
+    let mut prev_dash = false;
+    for c in slug.chars() {
+        if c == '-' {
+            if !prev_dash {
+                collapsed.push('-');
+            }
+            prev_dash = true;
+        } else {
+            collapsed.push(c);
+            prev_dash = false;
+        }
+    }
+    let title_slug = collapsed.trim_matches('-');
+
+    // Budget: 40 chars total, minus prefix, separator, source_id, and separator
+    let budget = 40_usize.saturating_sub(prefix.len() + 1 + source_id.len() + 1);
+    let truncated = if title_slug.len() <= budget {
+        title_slug
+    } else {
+        match title_slug[..budget].rfind('-') {
+            Some(pos) => &title_slug[..pos],
+            None => &title_slug[..budget],
+        }
+    };
+
+    if truncated.is_empty() {
+        format!("{prefix}-{source_id}")
+    } else {
+        format!("{prefix}-{source_id}-{truncated}")
+    }
+}
+
 fn worktree_not_found(slug: impl Into<String>) -> impl FnOnce(rusqlite::Error) -> ConductorError {
     let slug = slug.into();
     move |e| match e {
@@ -62,7 +117,7 @@ const AGENT_LATEST_JOIN: &str = "LEFT JOIN (\
 /// Returns the base SELECT+FROM+JOIN fragment for enriched worktree queries.
 /// This includes all worktree columns plus ticket info and latest agent status.
 /// To be used with `map_enriched_row`.
-fn enriched_worktree_base() -> String {
+pub(super) fn enriched_worktree_base() -> String {
     format!(
         "SELECT {cols}, latest.status AS agent_status, \
          t.title AS ticket_title, t.source_id AS ticket_number, t.url AS ticket_url \
@@ -76,7 +131,7 @@ fn enriched_worktree_base() -> String {
 
 /// Map a row that contains the standard worktree columns followed by
 /// `agent_status`, `ticket_title`, `ticket_number`, and `ticket_url`.
-fn map_enriched_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<WorktreeWithStatus> {
+pub(super) fn map_enriched_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<WorktreeWithStatus> {
     let worktree = map_worktree_row(row)?;
     let agent_status: Option<crate::agent::AgentRunStatus> = row.get("agent_status")?;
     let ticket_title: Option<String> = row.get("ticket_title")?;
@@ -91,12 +146,50 @@ fn map_enriched_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<WorktreeWithSta
     })
 }
 
+/// Conventional worktree name prefixes and the branch type each maps to.
+pub(crate) const SLUG_PREFIXES: &[(&str, &str)] = &[
+    ("fix-", "fix"),
+    ("bug-", "fix"),
+    ("feat-", "feat"),
+    ("release-", "release"),
+    ("chore-", "chore"),
+    ("docs-", "docs"),
+    ("refactor-", "refactor"),
+    ("test-", "test"),
+    ("ci-", "ci"),
+    ("perf-", "perf"),
+];
+
+/// Worktree slug and git branch for a create named `name`.
+///
+/// Conventional prefixes are kept in the slug and become a `/` in the branch
+/// (`fix-login` → `fix/login`); "bug-" slugs are preserved as-is but map to
+/// "fix/" in git. Other names get `branch_prefix` (default "feat").
+fn derive_slug_and_branch(name: &str, branch_prefix: Option<&str>) -> (String, String) {
+    if let Some(&(dash, slash)) = SLUG_PREFIXES.iter().find(|(d, _)| name.starts_with(d)) {
+        let clean = name.strip_prefix(dash).unwrap();
+        (format!("{dash}{clean}"), format!("{slash}/{clean}"))
+    } else {
+        let prefix = branch_prefix.unwrap_or("feat");
+        (format!("{prefix}-{name}"), format!("{prefix}/{name}"))
+    }
+}
+
 /// Look up a ticket's dependencies and return the branch of the first parent that has
 /// an active worktree.  Returns `None` if the ticket has no resolvable parent branch
 /// (no dependency metadata for its source type, no deps, or no parent worktree).
 ///
 /// Dependency IDs are extracted via [`crate::ticket_source::get_dependency_ids`];
 /// swap to a `ticket_dependencies` table query once RFC 009 lands.
+/// PR number to check out when the linked ticket is a synced pull request
+/// (`github_pr`), so adopting a PR ticket reviews the PR branch itself.
+fn ticket_pr_number(conn: &Connection, ticket_id: &str) -> Option<u32> {
+    TicketSyncer::new(conn)
+        .get_by_id(ticket_id)
+        .ok()
+        .and_then(|t| t.pr_number())
+}
+
 fn resolve_parent_branch(conn: &Connection, ticket_id: &str, repo_id: &str) -> Option<String> {
     let syncer = TicketSyncer::new(conn);
     let ticket = match syncer.get_by_id(ticket_id) {
@@ -118,9 +211,10 @@ fn resolve_parent_branch(conn: &Connection, ticket_id: &str, repo_id: &str) -> O
     let sql = format!(
         "SELECT w.branch, t.source_id \
          FROM tickets t \
-         LEFT JOIN worktrees w ON w.ticket_id = t.id AND w.status = 'active' \
+         LEFT JOIN worktrees w ON w.ticket_id = t.id AND w.status IN {live} \
          WHERE t.repo_id = ?1 AND t.source_id IN ({placeholders}) \
-         ORDER BY w.created_at DESC"
+         ORDER BY w.created_at DESC",
+        live = LIVE_STATUSES_SQL,
     );
 
     let mut params: Vec<&dyn rusqlite::ToSql> = vec![&repo_id];
@@ -163,8 +257,33 @@ pub struct SetBaseBranchOptions {
     /// When `true`, rebase the worktree branch onto the new base before recording it.
     /// When `false` (default), reject with an error if the new base is not an ancestor of HEAD.
     pub rebase: bool,
+    /// Leave a conflicting rebase in progress and fail with
+    /// [`ConductorError::RebaseConflict`] instead of aborting it.
+    pub keep_conflicts: bool,
 }
 
+/// Options for [`WorktreeManager::push`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PushOptions {
+    /// Push without running the repo's `[project] pre_push` checks.
+    pub skip_checks: bool,
+}
+
+/// Options for [`WorktreeManager::create_pr`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PrOptions {
+    pub draft: bool,
+    /// Build the title and body from the linked ticket and the latest agent
+    /// run's result instead of the branch's commit messages.
+    pub from_agent: bool,
+}
+
+/// GitHub rejects PR bodies over 65536 characters; stay well under it.
+const MAX_PR_BODY_BYTES: usize = 60_000;
+
+/// Branch TODOs listed in a PR checklist; the rest are counted.
+const MAX_PR_TODOS: usize = 50;
+
 /// Options for creating a new worktree.
 ///
 /// Passed to [`WorktreeManager::create`] to avoid a long positional argument list.
@@ -179,13 +298,33 @@ pub struct WorktreeCreateOptions {
     pub from_branch: Option<String>,
     /// Associate the new worktree with this ticket ID.
     pub ticket_id: Option<String>,
-    /// When `true`, skip the dirty-state check. Use only after the caller has
-    /// explicitly confirmed the user wants to proceed with uncommitted changes.
+    /// When `true`, the caller has already shown the user the main checkout's
+    /// uncommitted changes, so no warning about them is returned.
     pub force_dirty: bool,
     /// Pre-computed health status from a prior `check_main_health()` call.
-    /// When `Some` and the working tree is clean, the redundant `git status`
-    /// inside `ensure_base_up_to_date()` is skipped.
+    /// When `Some`, its dirty state is used instead of running `git status`
+    /// again.
     pub pre_health: Option<super::git_helpers::MainHealthStatus>,
+    /// Called as creation moves through each [`WorktreeCreateStage`].
+    pub progress: Option<CreateProgress>,
+    /// Skip `git fetch origin` and branch from the last-fetched local state,
+    /// regardless of the `base_fetch` config. Not valid with `from_pr`.
+    pub offline: bool,
+}
+
+/// Stage callback for [`WorktreeCreateOptions::progress`].
+pub struct CreateProgress(Box<dyn Fn(WorktreeCreateStage) + Send>);
+
+impl CreateProgress {
+    pub fn new(f: impl Fn(WorktreeCreateStage) + Send + 'static) -> Self {
+        Self(Box::new(f))
+    }
+}
+
+impl std::fmt::Debug for CreateProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CreateProgress(..)")
+    }
 }
 
 /// Options for adopting an existing on-disk git worktree into conductor's DB.
@@ -202,13 +341,28 @@ pub struct WorktreeAdoptOptions {
 /// Write `branch.<branch>.remote = origin` and `branch.<branch>.merge = refs/heads/<branch>`
 /// into the git config at `path`. This is the non-network equivalent of `git push -u origin <branch>`,
 /// ensuring bare `git push` inside the worktree always targets the correct remote branch.
+///
+/// Worktrees share the main repo's `.git/config`, so concurrent creates can
+/// briefly contend for its lock; those writes are retried.
 pub(crate) fn set_upstream_tracking(path: &Path, branch: &str) -> Result<()> {
-    check_output(git_in(path).args(["config", &format!("branch.{branch}.remote"), "origin"]))?;
-    check_output(git_in(path).args([
-        "config",
-        &format!("branch.{branch}.merge"),
-        &format!("refs/heads/{branch}"),
-    ]))?;
+    let retry = crate::retry::RetryConfig {
+        max_attempts: 5,
+        initial_backoff: std::time::Duration::from_millis(50),
+        ..Default::default()
+    };
+    let remote_key = format!("branch.{branch}.remote");
+    let merge_key = format!("branch.{branch}.merge");
+    let merge_ref = format!("refs/heads/{branch}");
+    check_output_with_retry(&retry, || {
+        let mut cmd = git_in(path);
+        cmd.args(["config", &remote_key, "origin"]);
+        cmd
+    })?;
+    check_output_with_retry(&retry, || {
+        let mut cmd = git_in(path);
+        cmd.args(["config", &merge_key, &merge_ref]);
+        cmd
+    })?;
     Ok(())
 }
 
@@ -231,8 +385,8 @@ pub fn get_ticket_id_by_branch(
 }
 
 pub struct WorktreeManager<'a> {
-    conn: &'a Connection,
-    config: &'a Config,
+    pub(super) conn: &'a Connection,
+    pub(super) config: &'a Config,
 }
 
 impl<'a> WorktreeManager<'a> {
@@ -257,7 +411,60 @@ impl<'a> WorktreeManager<'a> {
         Ok(check_main_health(&repo.local_path, &base))
     }
 
-    fn check_or_purge_existing_worktree(&self, repo_id: &str, slug: &str) -> Result<()> {
+    /// Base branch [`create`](Self::create) would use for a worktree linked to
+    /// `ticket_id` when no branch is given: the branch of a parent ticket's live
+    /// worktree, then the first `[[base_branch_rules]]` entry matching one of
+    /// the ticket's labels, then the repo default.
+    pub fn resolve_base(&self, repo_slug: &str, ticket_id: Option<&str>) -> Result<ResolvedBase> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        let repo_config = RepoConfig::load(Path::new(&repo.local_path)).unwrap_or_else(|e| {
+            tracing::warn!(repo = %repo.slug, "ignoring unreadable .conductor/config.toml: {e}");
+            Default::default()
+        });
+        Ok(ticket_id
+            .and_then(|tid| self.ticket_base(&repo.id, &repo_config, tid))
+            .unwrap_or(ResolvedBase {
+                branch: repo.default_branch,
+                source: BaseBranchSource::RepoDefault,
+            }))
+    }
+
+    /// Ticket-specific base for `ticket_id`, if a parent worktree or label
+    /// rule gives one.
+    fn ticket_base(
+        &self,
+        repo_id: &str,
+        repo_config: &RepoConfig,
+        ticket_id: &str,
+    ) -> Option<ResolvedBase> {
+        if let Some(branch) = resolve_parent_branch(self.conn, ticket_id, repo_id) {
+            return Some(ResolvedBase {
+                branch,
+                source: BaseBranchSource::ParentTicket,
+            });
+        }
+        if repo_config.base_branch_rules.is_empty() {
+            return None;
+        }
+        let labels = match TicketSyncer::new(self.conn).get_by_id(ticket_id) {
+            Ok(ticket) => ticket.label_names(),
+            Err(e) => {
+                tracing::warn!("ticket_base: failed to look up ticket {ticket_id}: {e}");
+                return None;
+            }
+        };
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        repo_config
+            .base_branch_rule(&labels)
+            .map(|rule| ResolvedBase {
+                branch: rule.branch.clone(),
+                source: BaseBranchSource::LabelRule {
+                    label: rule.label.clone(),
+                },
+            })
+    }
+
+    pub(super) fn check_or_purge_existing_worktree(&self, repo_id: &str, slug: &str) -> Result<()> {
         let existing_status: Option<WorktreeStatus> = self
             .conn
             .query_row(
@@ -268,7 +475,7 @@ impl<'a> WorktreeManager<'a> {
             .optional()?;
 
         match existing_status {
-            Some(WorktreeStatus::Active) => Err(ConductorError::WorktreeAlreadyExists {
+            Some(status) if status.is_live() => Err(ConductorError::WorktreeAlreadyExists {
                 slug: slug.to_string(),
             }),
             Some(_) => {
@@ -282,6 +489,30 @@ impl<'a> WorktreeManager<'a> {
         }
     }
 
+    /// Fail with [`ConductorError::WorktreeAlreadyExists`] if a live worktree
+    /// already uses the slug that [`create`](Self::create) would derive from
+    /// `name`. Lets callers that run creation in the background report the
+    /// conflict up front.
+    pub fn check_slug_available(&self, repo_slug: &str, name: &str) -> Result<()> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        let branch_prefix = RepoConfig::load(Path::new(&repo.local_path))
+            .ok()
+            .and_then(|rc| rc.project.branch_prefix);
+        let (slug, _) = derive_slug_and_branch(name, branch_prefix.as_deref());
+        let live: bool = self.conn.query_row(
+            &format!(
+                "SELECT EXISTS(SELECT 1 FROM worktrees \
+                 WHERE repo_id = :repo_id AND slug = :slug AND status IN {LIVE_STATUSES_SQL})"
+            ),
+            named_params![":repo_id": repo.id, ":slug": slug],
+            |row| row.get(0),
+        )?;
+        if live {
+            return Err(ConductorError::WorktreeAlreadyExists { slug });
+        }
+        Ok(())
+    }
+
     /// Create a new worktree, ensuring the base branch is up to date first.
     ///
     /// Returns the created worktree and a list of non-fatal warnings
@@ -289,14 +520,14 @@ impl<'a> WorktreeManager<'a> {
     ///
     /// When `from_pr` is `Some(n)`, the worktree is backed by the branch of PR #n
     /// instead of a newly-created branch.  `from_branch` is ignored in that case.
+    /// A `ticket_id` pointing at a synced pull request implies `from_pr`.
     ///
-    /// When `force_dirty` is `true`, the dirty-state check inside
-    /// `ensure_base_up_to_date()` is skipped. Use this only after the caller has
-    /// explicitly confirmed the user wants to proceed with uncommitted changes.
-    ///
-    /// When `opts.pre_health` is `Some` and the health status shows a clean working tree,
-    /// the redundant `git status --porcelain` call inside `ensure_base_up_to_date()` is
-    /// skipped. Callers that already ran `check_main_health()` should pass the result here.
+    /// Uncommitted changes in the main checkout never block creation; the new
+    /// branch starts from a ref, so they are left out, with a warning unless
+    /// `force_dirty` says the user has already seen them. When
+    /// `opts.pre_health` is `Some`, its dirty state is used instead of running
+    /// `git status` again. Callers that already ran `check_main_health()`
+    /// should pass the result here.
     pub fn create(
         &self,
         repo_slug: &str,
@@ -309,36 +540,81 @@ impl<'a> WorktreeManager<'a> {
             ticket_id,
             force_dirty,
             pre_health,
+            progress,
+            offline,
         } = opts;
+        let report = |stage: WorktreeCreateStage| {
+            if let Some(p) = &progress {
+                (p.0)(stage);
+            }
+        };
+        let from_pr = from_pr.or_else(|| {
+            ticket_id
+                .as_deref()
+                .and_then(|tid| ticket_pr_number(self.conn, tid))
+        });
+        if offline && from_pr.is_some() {
+            return Err(ConductorError::InvalidInput(
+                "cannot check out a PR branch offline; it must be fetched from origin".into(),
+            ));
+        }
         let repo_mgr = RepoManager::new(self.conn, self.config);
         let repo = repo_mgr.get_by_slug(repo_slug)?;
+        let repo_config = RepoConfig::load(Path::new(&repo.local_path)).unwrap_or_else(|e| {
+            tracing::warn!(repo = %repo.slug, "ignoring unreadable .conductor/config.toml: {e}");
+            Default::default()
+        });
+        let project = &repo_config.project;
+
+        let (wt_slug, branch) = derive_slug_and_branch(name, project.branch_prefix.as_deref());
+
+        // Hold the slug until the row is inserted below. A concurrent create
+        // for the same name moves on to `<slug>-2`; a PR checkout cannot,
+        // since both would need the PR's branch.
+        let reservation = self.reserve_slug(&repo, &wt_slug, &branch, from_pr.is_none())?;
+        let slug_warning = (reservation.slug != wt_slug).then(|| {
+            format!(
+                "worktree '{wt_slug}' is already being created; using '{}'",
+                reservation.slug
+            )
+        });
+        let (wt_slug, branch) = (reservation.slug.clone(), reservation.branch.clone());
+
+        // Enforce the workspace disk quota before doing any git work. Scanning is
+        // skipped entirely when no quota is configured for this repo.
+        let mut quota_warning = None;
+        if self.config.quota.limit_bytes_for(&repo.slug).is_some() {
+            let usage = self.workspace_usage_for_repo(&repo)?;
+            match usage.level {
+                QuotaLevel::Exceeded => {
+                    return Err(ConductorError::WorkspaceQuotaExceeded {
+                        repo_slug: repo.slug.clone(),
+                        used_bytes: usage.used_bytes,
+                        limit_bytes: usage.limit_bytes.unwrap_or_default(),
+                        hint: usage.remediation_hint(),
+                    });
+                }
+                QuotaLevel::Warning => {
+                    quota_warning = Some(format!(
+                        "workspace for '{}' is near its disk quota: {}{}",
+                        repo.slug,
+                        usage.meter_label(),
+                        usage.remediation_hint()
+                    ));
+                }
+                QuotaLevel::Ok => {}
+            }
+        }
 
-        // Determine branch name and worktree slug.
-        // "bug-" slugs are preserved as-is but map to "fix/" in git.
-        const SLUG_PREFIXES: &[(&str, &str)] = &[
-            ("fix-", "fix"),
-            ("bug-", "fix"),
-            ("feat-", "feat"),
-            ("release-", "release"),
-            ("chore-", "chore"),
-            ("docs-", "docs"),
-            ("refactor-", "refactor"),
-            ("test-", "test"),
-            ("ci-", "ci"),
-            ("perf-", "perf"),
-        ];
-        let (wt_slug, branch) =
-            if let Some(&(dash, slash)) = SLUG_PREFIXES.iter().find(|(d, _)| name.starts_with(d)) {
-                let clean = name.strip_prefix(dash).unwrap();
-                (format!("{dash}{clean}"), format!("{slash}/{clean}"))
-            } else {
-                (format!("feat-{name}"), format!("feat/{name}"))
-            };
-
-        self.check_or_purge_existing_worktree(&repo.id, &wt_slug)?;
+        // Hold the repo's git lock until the worktree is checked out and tracking is set.
+        let repo_lock = repo_git_lock(&repo.local_path);
+        let git_guard = repo_lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
 
         // Auto-clone if the local path doesn't exist on disk yet
         if !Path::new(&repo.local_path).exists() {
+            report(WorktreeCreateStage::Cloning);
             clone_repo(&repo.remote_url, &repo.local_path)?;
         }
 
@@ -347,59 +623,150 @@ impl<'a> WorktreeManager<'a> {
         // Ensure the per-repo workspace directory exists
         std::fs::create_dir_all(&repo.workspace_dir)?;
 
-        // (branch_name, base_branch_for_db, warnings)
-        let (branch, base_for_db, warnings) = if let Some(pr_number) = from_pr {
+        // (branch_name, base_branch_for_db, start_point, warnings)
+        report(WorktreeCreateStage::Fetching);
+        let (branch, base_for_db, start_point, mut warnings) = if let Some(pr_number) = from_pr {
             // --from-pr path: fetch the PR branch and record the PR's base branch
             // so that create_pr can target the correct base.
             let (pr_branch, pr_base) = fetch_pr_branch(&repo.local_path, pr_number)?;
-            (pr_branch, Some(pr_base), Vec::new())
+            (pr_branch, Some(pr_base), None, Vec::new())
         } else {
-            // Normal path: resolve base, ensure it's up to date, create a new branch.
+            // Normal path: resolve base, fetch it, and branch from `origin/<base>`.
             //
             // `resolve_and_update_base` handles:
             //   - explicit from_branch with prefix fallback (feat/, fix/)
             //   - auto-creating a local tracking branch from remote
-            //   - non-checkout fast-forward updates
+            //   - picking the start point without touching the main checkout
             let explicit_base = if let Some(b) = from_branch {
                 Some(b)
             } else {
                 ticket_id
                     .as_deref()
-                    .and_then(|tid| resolve_parent_branch(self.conn, tid, &repo.id))
+                    .and_then(|tid| self.ticket_base(&repo.id, &repo_config, tid))
+                    .map(|base| base.branch)
+            };
+            let dirty = !force_dirty
+                && match &pre_health {
+                    Some(h) if !h.status_check_failed => h.is_dirty,
+                    _ => has_uncommitted_changes(&repo.local_path),
+                };
+            let skip_fetch_reason = if offline {
+                Some("--offline")
+            } else {
+                match self.config.general.base_fetch {
+                    BaseFetch::Always => None,
+                    BaseFetch::Never => Some("base_fetch = \"never\""),
+                    BaseFetch::Auto => {
+                        (!origin_reachable(&repo.local_path)).then_some("origin is unreachable")
+                    }
+                }
             };
-            let pre_verified_clean = pre_health
-                .map(|h| !h.is_dirty && !h.status_check_failed)
-                .unwrap_or(false);
-            let (base, warnings) = resolve_and_update_base(
+            let BaseStart {
+                branch: base,
+                start_point,
+                mut warnings,
+            } = resolve_and_update_base(
                 &repo.local_path,
                 explicit_base.as_deref(),
                 &repo.default_branch,
-                force_dirty,
-                pre_verified_clean,
+                skip_fetch_reason.is_none(),
             )?;
-            check_output(git_in(&repo.local_path).args([
-                "branch",
-                "--",
-                &branch,
-                &format!("refs/heads/{base}"),
-            ]))?;
-            (branch, Some(base), warnings)
+            if dirty {
+                warnings.push(DIRTY_CHECKOUT_WARNING.to_string());
+            }
+            // Without a fresh fetch the starting point may be stale; say how stale.
+            let fetch_failed = warnings.iter().any(|w| w == FETCH_FAILED_WARNING);
+            if let Some(reason) = skip_fetch_reason {
+                warnings.push(format!(
+                    "skipped fetch from origin ({reason}); creating worktree from local state"
+                ));
+            }
+            if skip_fetch_reason.is_some() || fetch_failed {
+                if let Some(tip) = describe_ref_tip(&repo.local_path, &start_point) {
+                    warnings.push(format!("base '{base}' is at {tip}"));
+                }
+            }
+            report(WorktreeCreateStage::Branching);
+            (branch, Some(base), Some(start_point), warnings)
         };
 
-        // Create git worktree
-        check_output(git_in(&repo.local_path).args([
-            "worktree",
-            "add",
-            &wt_path.to_string_lossy(),
-            &branch,
-        ]))?;
+        // Create git worktree. A new branch is created by `worktree add -b`
+        // straight from the start point, so the main checkout's HEAD, index
+        // and files are never involved and concurrent creates can't fight
+        // over them.
+        report(WorktreeCreateStage::CheckingOut);
+        let wt_path_arg = wt_path.to_string_lossy();
+        match &start_point {
+            Some(start) => check_output(git_in(&repo.local_path).args([
+                "worktree",
+                "add",
+                "--no-track",
+                "-b",
+                &branch,
+                &wt_path_arg,
+                start,
+            ]))?,
+            None => check_output(git_in(&repo.local_path).args([
+                "worktree",
+                "add",
+                &wt_path_arg,
+                &branch,
+            ]))?,
+        };
 
         // Set upstream tracking config so bare `git push` targets the correct remote branch.
         // This is the non-network equivalent of `git push -u origin <branch>`.
         set_upstream_tracking(&wt_path, &branch)?;
+        drop(git_guard);
 
         // Detect and install deps
-        install_deps(&wt_path);
+        report(WorktreeCreateStage::InstallingDeps);
+        let toolchain = if repo.toolchain.is_empty() {
+            // Repos registered before detection existed (or before their
+            // checkout did) pick up a toolchain on their first worktree.
+            let detected = Toolchain::detect(&wt_path);
+            if !detected.is_empty() {
+                if let Err(e) =
+                    RepoManager::new(self.conn, self.config).set_toolchain(&repo.id, &detected)
+                {
+                    tracing::warn!(repo = %repo.slug, "failed to store detected toolchain: {e}");
+                }
+            }
+            detected
+        } else {
+            repo.toolchain.clone()
+        };
+        let wt_path_str = wt_path.to_string_lossy();
+        let hook_target = HookTarget {
+            repo_path: &repo.local_path,
+            worktree_path: &wt_path_str,
+            branch: &branch,
+            ticket_id: ticket_id.as_deref(),
+        };
+        if repo_config.hooks.post_create.is_empty() {
+            install_deps(&wt_path, &toolchain);
+        } else {
+            warnings.extend(run_hook(
+                self.conn,
+                &repo_config.hooks,
+                WorktreeHook::PostCreate,
+                &hook_target,
+            ));
+        }
+        let setup_env = hook_env(self.conn, &hook_target);
+        let setup_env: Vec<(&str, &str)> =
+            setup_env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        warnings.extend(run_setup_commands(&wt_path, &project.setup, &setup_env));
+        if !project.instructions_files.is_empty() {
+            let section = crate::instructions::render(&repo_config, &toolchain);
+            for &file in &project.instructions_files {
+                if let Err(e) = crate::instructions::write(&wt_path, file, &section) {
+                    warnings.push(format!("Could not write {file}: {e}"));
+                }
+            }
+        }
+
+        report(WorktreeCreateStage::Finalizing);
 
         // Create isolated DB for the worktree (runs migrations + seeds)
         let wt_db_path = wt_path.join(".conductor.db");
@@ -421,6 +788,9 @@ impl<'a> WorktreeManager<'a> {
             completed_at: None,
             model: None,
             base_branch: base_for_db.clone(),
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         };
 
         self.conn.execute(
@@ -439,9 +809,46 @@ impl<'a> WorktreeManager<'a> {
             ],
         )?;
 
+        drop(reservation);
+
+        if let Some(w) = slug_warning {
+            warnings.insert(0, w);
+        }
+        if let Some(w) = quota_warning {
+            warnings.insert(0, w);
+        }
         Ok((worktree, warnings))
     }
 
+    /// Disk usage of `repo_slug`'s workspace directory against the configured quota.
+    pub fn workspace_usage(&self, repo_slug: &str) -> Result<WorkspaceUsage> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        self.workspace_usage_for_repo(&repo)
+    }
+
+    /// Disk usage for every registered repo's workspace directory.
+    ///
+    /// Walks each workspace on disk, so callers on latency-sensitive paths
+    /// should throttle how often this runs.
+    pub fn workspace_usage_all(&self) -> Result<Vec<WorkspaceUsage>> {
+        self.visible_workspace_usage(&Viewer::Local)
+    }
+
+    /// Like [`Self::workspace_usage_all`], leaving out repos that are private
+    /// to someone other than `viewer`.
+    pub fn visible_workspace_usage(&self, viewer: &Viewer) -> Result<Vec<WorkspaceUsage>> {
+        RepoManager::new(self.conn, self.config)
+            .list_visible(viewer)?
+            .iter()
+            .map(|repo| self.workspace_usage_for_repo(repo))
+            .collect()
+    }
+
+    fn workspace_usage_for_repo(&self, repo: &crate::repo::Repo) -> Result<WorkspaceUsage> {
+        let worktrees = self.list_by_repo_id(&repo.id, false)?;
+        Ok(scan_workspace(repo, &worktrees, &self.config.quota))
+    }
+
     /// Register an existing on-disk git worktree into conductor's DB without
     /// creating new git branches or running `git worktree add`.
     ///
@@ -539,6 +946,9 @@ impl<'a> WorktreeManager<'a> {
             completed_at: None,
             model: None,
             base_branch,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         };
 
         self.conn.execute(
@@ -795,13 +1205,14 @@ impl<'a> WorktreeManager<'a> {
     }
 
     pub fn list_by_repo_id(&self, repo_id: &str, active_only: bool) -> Result<Vec<Worktree>> {
+        let live = LIVE_STATUSES_SQL;
         let status_filter = if active_only {
-            " AND status = 'active'"
+            format!(" AND status IN {live}")
         } else {
-            ""
+            String::new()
         };
         let query = format!(
-            "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE repo_id = :repo_id{} ORDER BY CASE WHEN status = 'active' THEN 0 ELSE 1 END, created_at",
+            "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE repo_id = :repo_id{} ORDER BY CASE WHEN status IN {live} THEN 0 ELSE 1 END, created_at",
             status_filter
         );
         query_collect(
@@ -818,7 +1229,8 @@ impl<'a> WorktreeManager<'a> {
         base_branch: &str,
     ) -> Result<Vec<Worktree>> {
         let query = format!(
-            "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE repo_id = :repo_id AND base_branch = :base_branch AND status = 'active' ORDER BY created_at"
+            "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE repo_id = :repo_id AND base_branch = :base_branch AND status IN {live} ORDER BY created_at",
+            live = LIVE_STATUSES_SQL,
         );
         query_collect(
             self.conn,
@@ -837,20 +1249,21 @@ impl<'a> WorktreeManager<'a> {
         active_only: bool,
         pagination: Option<(usize, usize)>,
     ) -> Result<Vec<Worktree>> {
+        let live = LIVE_STATUSES_SQL;
         let status_filter = if active_only {
-            " AND status = 'active'"
+            format!(" AND status IN {live}")
         } else {
-            ""
+            String::new()
         };
 
         let base_query = match repo_slug {
             Some(_) => format!(
-                "SELECT {} FROM worktrees w JOIN repos r ON r.id = w.repo_id WHERE r.slug = :slug{} ORDER BY CASE WHEN w.status = 'active' THEN 0 ELSE 1 END, w.created_at",
+                "SELECT {} FROM worktrees w JOIN repos r ON r.id = w.repo_id WHERE r.slug = :slug{} ORDER BY CASE WHEN w.status IN {live} THEN 0 ELSE 1 END, w.created_at",
                 &*WORKTREE_COLUMNS_W,
                 status_filter,
             ),
             None => format!(
-                "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE 1=1{} ORDER BY CASE WHEN status = 'active' THEN 0 ELSE 1 END, created_at",
+                "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE 1=1{} ORDER BY CASE WHEN status IN {live} THEN 0 ELSE 1 END, created_at",
                 status_filter,
             ),
         };
@@ -904,15 +1317,16 @@ impl<'a> WorktreeManager<'a> {
     /// Uses the INNER JOIN subquery pattern to avoid duplicate rows when two runs share
     /// the same MAX(started_at) timestamp.
     pub fn list_all_with_status(&self, active_only: bool) -> Result<Vec<WorktreeWithStatus>> {
+        let live = LIVE_STATUSES_SQL;
         let status_filter = if active_only {
-            " AND w.status = 'active'"
+            format!(" AND w.status IN {live}")
         } else {
-            ""
+            String::new()
         };
         let sql = format!(
             "{base} \
              WHERE 1=1{status_filter} \
-             ORDER BY CASE WHEN w.status = 'active' THEN 0 ELSE 1 END, w.created_at",
+             ORDER BY CASE WHEN w.status IN {live} THEN 0 ELSE 1 END, w.created_at",
             base = enriched_worktree_base(),
             status_filter = status_filter,
         );
@@ -956,15 +1370,16 @@ impl<'a> WorktreeManager<'a> {
         repo_id: &str,
         active_only: bool,
     ) -> Result<Vec<WorktreeWithStatus>> {
+        let live = LIVE_STATUSES_SQL;
         let status_filter = if active_only {
-            " AND w.status = 'active'"
+            format!(" AND w.status IN {live}")
         } else {
-            ""
+            String::new()
         };
         let sql = format!(
             "{base} \
              WHERE w.repo_id = :repo_id{status_filter} \
-             ORDER BY CASE WHEN w.status = 'active' THEN 0 ELSE 1 END, w.created_at",
+             ORDER BY CASE WHEN w.status IN {live} THEN 0 ELSE 1 END, w.created_at",
             base = enriched_worktree_base(),
             status_filter = status_filter,
         );
@@ -991,7 +1406,14 @@ impl<'a> WorktreeManager<'a> {
         Ok(found)
     }
 
-    pub fn delete(&self, repo_slug: &str, name: &str) -> Result<Worktree> {
+    /// Soft-delete a worktree, marking it merged or abandoned. `reason` is
+    /// recorded only when the work turns out to be unmerged.
+    pub fn delete(
+        &self,
+        repo_slug: &str,
+        name: &str,
+        reason: Option<AbandonReason>,
+    ) -> Result<Worktree> {
         let repo_mgr = RepoManager::new(self.conn, self.config);
         let repo = repo_mgr.get_by_slug(repo_slug)?;
 
@@ -1006,75 +1428,112 @@ impl<'a> WorktreeManager<'a> {
             )
             .map_err(worktree_not_found(name))?;
 
-        self.delete_internal(&repo, worktree, None)
+        self.delete_internal(&repo, worktree, None, reason)
     }
 
-    pub fn delete_by_id(&self, worktree_id: &str) -> Result<Worktree> {
+    pub fn delete_by_id(
+        &self,
+        worktree_id: &str,
+        reason: Option<AbandonReason>,
+    ) -> Result<Worktree> {
         let worktree = self.get_by_id(worktree_id)?;
         let repo_mgr = RepoManager::new(self.conn, self.config);
         let repo = repo_mgr.get_by_id(&worktree.repo_id)?;
-        self.delete_internal(&repo, worktree, None)
+        self.delete_internal(&repo, worktree, None, reason)
     }
 
     /// Delete a worktree by ID, enforcing that it belongs to `repo_id`.
     /// Returns `WorktreeNotFound` if the worktree does not exist or belongs to a different repo.
-    pub fn delete_by_id_for_repo(&self, id: &str, repo_id: &str) -> Result<Worktree> {
+    pub fn delete_by_id_for_repo(
+        &self,
+        id: &str,
+        repo_id: &str,
+        reason: Option<AbandonReason>,
+    ) -> Result<Worktree> {
         let worktree = self.get_by_id_for_repo(id, repo_id)?;
         let repo_mgr = RepoManager::new(self.conn, self.config);
         let repo = repo_mgr.get_by_id(&worktree.repo_id)?;
-        self.delete_internal(&repo, worktree, None)
+        self.delete_internal(&repo, worktree, None, reason)
     }
 
+    /// Whether deleting `worktree` would mark it merged rather than abandoned:
+    /// its linked ticket is closed (covers squash merges git cannot detect) or
+    /// git sees its branch merged into the repo's default branch.
+    ///
     /// `ticket_closed_hint`: when `Some(true)` the caller already knows the
     /// linked ticket is closed; the per-ticket DB query is skipped.
-    fn delete_internal(
+    pub(super) fn is_merged(
         &self,
         repo: &crate::repo::Repo,
-        worktree: Worktree,
+        worktree: &Worktree,
         ticket_closed_hint: Option<bool>,
-    ) -> Result<Worktree> {
-        // Determine merged vs abandoned:
-        // 1. Check if the linked ticket is closed (covers squash merges that git can't detect)
-        // 2. Fall back to git branch --merged (covers cases without a linked ticket)
-        let ticket_closed = ticket_closed_hint.unwrap_or_else(|| {
-            worktree
-                .ticket_id
-                .as_ref()
-                .map(|tid| {
-                    self.conn
-                        .query_row(
-                            "SELECT state = 'closed' FROM tickets WHERE id = :id",
-                            named_params![":id": tid],
-                            |row| row.get::<_, bool>(0),
-                        )
-                        .unwrap_or(false)
-                })
-                .unwrap_or(false)
-        });
-        let is_merged = ticket_closed
+    ) -> bool {
+        let ticket_closed = ticket_closed_hint.unwrap_or_else(|| self.ticket_closed(worktree));
+        ticket_closed
             || crate::git::is_branch_merged_local(
                 &repo.local_path,
                 &worktree.branch,
                 &repo.default_branch,
-            );
-        let new_status = if is_merged {
-            WorktreeStatus::Merged
+            )
+    }
+
+    /// Whether the ticket linked to `worktree`, if any, is closed.
+    pub(super) fn ticket_closed(&self, worktree: &Worktree) -> bool {
+        worktree.ticket_id.as_ref().is_some_and(|tid| {
+            self.conn
+                .query_row(
+                    "SELECT state = 'closed' FROM tickets WHERE id = :id",
+                    named_params![":id": tid],
+                    |row| row.get::<_, bool>(0),
+                )
+                .unwrap_or(false)
+        })
+    }
+
+    /// `ticket_closed_hint`: see [`Self::is_merged`].
+    pub(super) fn delete_internal(
+        &self,
+        repo: &crate::repo::Repo,
+        worktree: Worktree,
+        ticket_closed_hint: Option<bool>,
+        reason: Option<AbandonReason>,
+    ) -> Result<Worktree> {
+        let (new_status, abandon_reason) = if self.is_merged(repo, &worktree, ticket_closed_hint) {
+            (WorktreeStatus::Merged, None)
         } else {
-            WorktreeStatus::Abandoned
+            (WorktreeStatus::Abandoned, reason)
         };
         let now = Utc::now().to_rfc3339();
 
+        let hooks = load_hooks(&repo.local_path);
+        let hook_target = HookTarget {
+            repo_path: &repo.local_path,
+            worktree_path: &worktree.path,
+            branch: &worktree.branch,
+            ticket_id: worktree.ticket_id.as_deref(),
+        };
+        run_hook(self.conn, &hooks, WorktreeHook::PreDelete, &hook_target);
         remove_git_artifacts(&repo.local_path, &worktree.path, &worktree.branch);
+        if new_status == WorktreeStatus::Merged {
+            run_hook(self.conn, &hooks, WorktreeHook::PostMerge, &hook_target);
+        }
 
         // Soft-delete: update status + completed_at instead of deleting the row
         self.conn.execute(
-            "UPDATE worktrees SET status = :status, completed_at = :completed_at WHERE id = :id",
-            named_params![":status": new_status.as_str(), ":completed_at": now, ":id": worktree.id],
+            "UPDATE worktrees SET status = :status, completed_at = :completed_at, \
+             abandon_reason = :abandon_reason WHERE id = :id",
+            named_params![
+                ":status": new_status.as_str(),
+                ":completed_at": now,
+                ":abandon_reason": abandon_reason,
+                ":id": worktree.id,
+            ],
         )?;
 
         let deleted_wt = Worktree {
             status: new_status,
             completed_at: Some(now),
+            abandon_reason,
             ..worktree
         };
 
@@ -1093,7 +1552,7 @@ impl<'a> WorktreeManager<'a> {
     }
 
     pub fn update_status(&self, worktree_id: &str, status: WorktreeStatus) -> Result<()> {
-        let completed_at = if status != WorktreeStatus::Active {
+        let completed_at = if status.is_done() {
             Some(Utc::now().to_rfc3339())
         } else {
             None
@@ -1122,6 +1581,18 @@ impl<'a> WorktreeManager<'a> {
         Ok(())
     }
 
+    /// Clear the upstream ticket changes flagged on a worktree (see
+    /// [`Worktree::ticket_change`]) and return the updated worktree.
+    pub fn acknowledge_ticket_change(&self, id: &str) -> Result<Worktree> {
+        let mut worktree = self.get_by_id(id)?;
+        self.conn.execute(
+            "UPDATE worktrees SET ticket_change = NULL WHERE id = :id",
+            named_params![":id": id],
+        )?;
+        worktree.ticket_change = None;
+        Ok(worktree)
+    }
+
     /// Set (or clear) the worktree's base branch.
     /// Pass `None` to reset to the repo default branch (skips git validation).
     /// When `opts.rebase` is false and the new base is not an ancestor of HEAD, returns an error.
@@ -1149,10 +1620,11 @@ impl<'a> WorktreeManager<'a> {
             let wt_path = std::path::Path::new(&worktree.path);
 
             // Fetch the remote ref so the ancestor check is current.
-            let fetch_result = Command::new("git")
-                .args(["fetch", "origin", new_base])
-                .current_dir(wt_path)
-                .output();
+            let fetch_result = crate::command::output(
+                Command::new("git")
+                    .args(["fetch", "origin", new_base])
+                    .current_dir(wt_path),
+            );
             match fetch_result {
                 Ok(out) if !out.status.success() => {
                     tracing::warn!(
@@ -1193,10 +1665,11 @@ impl<'a> WorktreeManager<'a> {
                     ));
                 }
 
-                check_output(
-                    Command::new("git")
-                        .args(["rebase", &base_ref])
-                        .current_dir(wt_path),
+                super::conflicts::rebase_or_stop(
+                    wt_path,
+                    &["rebase", &base_ref],
+                    name,
+                    opts.keep_conflicts,
                 )?;
             }
         }
@@ -1214,17 +1687,19 @@ impl<'a> WorktreeManager<'a> {
     }
 
     /// Returns true if `base_ref` is an ancestor of HEAD in the given worktree directory.
-    fn is_ancestor(wt_path: &std::path::Path, base_ref: &str) -> Result<bool> {
-        let status = Command::new("git")
-            .args(["merge-base", "--is-ancestor", base_ref, "HEAD"])
-            .current_dir(wt_path)
-            .status()
-            .map_err(|e| {
-                ConductorError::Git(crate::error::SubprocessFailure::from_message(
-                    "git merge-base",
-                    format!("failed to spawn: {e}"),
-                ))
-            })?;
+    pub(super) fn is_ancestor(wt_path: &std::path::Path, base_ref: &str) -> Result<bool> {
+        let status = crate::command::output(
+            Command::new("git")
+                .args(["merge-base", "--is-ancestor", base_ref, "HEAD"])
+                .current_dir(wt_path),
+        )
+        .map(|o| o.status)
+        .map_err(|e| {
+            ConductorError::Git(crate::error::SubprocessFailure::from_message(
+                "git merge-base",
+                format!("failed to spawn: {e}"),
+            ))
+        })?;
         match status.code() {
             Some(0) => Ok(true),
             Some(1) => Ok(false),
@@ -1243,33 +1718,87 @@ impl<'a> WorktreeManager<'a> {
         }
     }
 
+    /// Diff of the worktree against the point where it branched off its base,
+    /// including uncommitted changes to tracked files.
+    pub fn diff(&self, repo_slug: &str, name: &str) -> Result<String> {
+        let (repo, worktree) = self.get_active_worktree(repo_slug, name)?;
+        let base = worktree.effective_base(&repo.default_branch);
+        let merge_base = check_output(git_in(&worktree.path).args(["merge-base", base, "HEAD"]))?;
+        let merge_base = String::from_utf8_lossy(&merge_base.stdout)
+            .trim()
+            .to_string();
+        let output = check_output(git_in(&worktree.path).args(["diff", &merge_base]))?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// `git diff --stat` of the worktree against the point where it branched
+    /// off its base, including uncommitted changes to tracked files.
+    pub fn diff_stat(&self, repo_slug: &str, name: &str) -> Result<String> {
+        let (repo, worktree) = self.get_active_worktree(repo_slug, name)?;
+        let base = worktree.effective_base(&repo.default_branch);
+        let merge_base = check_output(git_in(&worktree.path).args(["merge-base", base, "HEAD"]))?;
+        let merge_base = String::from_utf8_lossy(&merge_base.stdout)
+            .trim()
+            .to_string();
+        let output = check_output(git_in(&worktree.path).args(["diff", "--stat", &merge_base]))?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
     /// Push the worktree branch to origin.
-    pub fn push(&self, repo_slug: &str, name: &str) -> Result<String> {
-        let (_repo, worktree) = self.get_active_worktree(repo_slug, name)?;
+    ///
+    /// The repo's `[project] pre_push` commands run first, in the worktree;
+    /// the first failure blocks the push with
+    /// [`ConductorError::PrePushCheckFailed`] unless `opts.skip_checks` is set.
+    pub fn push(&self, repo_slug: &str, name: &str, opts: PushOptions) -> Result<String> {
+        let (repo, worktree) = self.get_active_worktree(repo_slug, name)?;
+
+        if !opts.skip_checks {
+            // Read from the main checkout, not the branch being pushed, so a
+            // branch cannot switch off its own gate.
+            let checks = RepoConfig::load(Path::new(&repo.local_path))
+                .map(|rc| rc.project.pre_push)
+                .unwrap_or_else(|e| {
+                    tracing::warn!(repo = %repo.slug, "failed to load repo config: {e}");
+                    Vec::new()
+                });
+            run_pre_push_checks(Path::new(&worktree.path), &worktree.slug, &checks)?;
+        }
 
         check_output(git_in(&worktree.path).args(["push", "-u", "origin", &worktree.branch]))?;
 
-        Ok(format!(
-            "Pushed {} to origin/{}",
-            worktree.slug, worktree.branch
-        ))
+        let mut message = format!("Pushed {} to origin/{}", worktree.slug, worktree.branch);
+        // Only commits are pushed; say so when an agent left work uncommitted.
+        let uncommitted = super::git_status(Path::new(&worktree.path), &worktree.branch)
+            .map(|status| status.uncommitted)
+            .unwrap_or(0);
+        if uncommitted > 0 {
+            message.push_str(&format!(
+                " ({uncommitted} uncommitted change{} not included; commit them first)",
+                if uncommitted == 1 { "" } else { "s" }
+            ));
+        }
+        Ok(message)
     }
 
-    /// Create a pull request for the worktree branch using `gh`.
-    pub fn create_pr(&self, repo_slug: &str, name: &str, draft: bool) -> Result<String> {
+    /// Create a pull request for the worktree branch using `gh`. The title
+    /// and body come from the commit messages (`--fill`) unless
+    /// `opts.from_agent` is set.
+    pub fn create_pr(&self, repo_slug: &str, name: &str, opts: PrOptions) -> Result<String> {
         let (repo, worktree) = self.get_active_worktree(repo_slug, name)?;
 
         let base = worktree.effective_base(&repo.default_branch);
-        let mut args = vec![
-            "pr",
-            "create",
-            "--fill",
-            "--head",
-            &worktree.branch,
-            "--base",
-            base,
-        ];
-        if draft {
+        let description = if opts.from_agent {
+            Some(self.agent_pr_description(repo_slug, &worktree)?)
+        } else {
+            None
+        };
+        let mut args = vec!["pr", "create"];
+        match &description {
+            Some((title, body)) => args.extend(["--title", title, "--body", body]),
+            None => args.push("--fill"),
+        }
+        args.extend(["--head", &worktree.branch, "--base", base]);
+        if opts.draft {
             args.push("--draft");
         }
 
@@ -1279,8 +1808,53 @@ impl<'a> WorktreeManager<'a> {
         Ok(url)
     }
 
+    /// PR title and body for `worktree` from its linked ticket, the result
+    /// of its latest top-level agent run and the TODOs its branch added.
+    fn agent_pr_description(
+        &self,
+        repo_slug: &str,
+        worktree: &Worktree,
+    ) -> Result<(String, String)> {
+        let (run, ticket) = self.agent_context(worktree, "the PR")?;
+        let todos = self.scan_todos(repo_slug, &worktree.slug)?;
+        Ok(agent_pr_description(
+            ticket.as_ref(),
+            &run.prompt,
+            run.result_text.as_deref().unwrap_or_default(),
+            &todos,
+        ))
+    }
+
+    /// The latest top-level agent run of `worktree` that left a result, and
+    /// its linked ticket. `what` names the thing being described, for the
+    /// error when there is no such run.
+    pub(super) fn agent_context(
+        &self,
+        worktree: &Worktree,
+        what: &str,
+    ) -> Result<(crate::agent::AgentRun, Option<crate::tickets::Ticket>)> {
+        let run = crate::agent::AgentManager::new(self.conn)
+            .latest_run_for_worktree(&worktree.id)?
+            .filter(|run| {
+                run.result_text
+                    .as_deref()
+                    .is_some_and(|t| !t.trim().is_empty())
+            })
+            .ok_or_else(|| {
+                ConductorError::InvalidInput(format!(
+                    "worktree '{}' has no agent run with a result to describe {what}",
+                    worktree.slug
+                ))
+            })?;
+        let ticket = match &worktree.ticket_id {
+            Some(id) => Some(TicketSyncer::new(self.conn).get_by_id(id)?),
+            None => None,
+        };
+        Ok((run, ticket))
+    }
+
     /// Look up a repo and its active worktree by slugs.
-    fn get_active_worktree(
+    pub(super) fn get_active_worktree(
         &self,
         repo_slug: &str,
         wt_slug: &str,
@@ -1300,7 +1874,7 @@ impl<'a> WorktreeManager<'a> {
         Ok((repo, worktree))
     }
 
-    /// Reap stale worktrees whose status is `merged` or `abandoned` but whose
+    /// Reap stale worktrees in a terminal status (`merged`, `abandoned`, `promoted`) whose
     /// filesystem artifacts still exist. For each stale worktree:
     /// 1. Remove git worktree directory and branch (best-effort)
     /// 2. Run `git worktree prune` on the parent repo
@@ -1310,10 +1884,13 @@ impl<'a> WorktreeManager<'a> {
     pub fn reap_stale_worktrees(&self) -> Result<usize> {
         let stale: Vec<(String, String, String, String, Option<String>)> = query_collect(
             self.conn,
-            "SELECT w.id, r.local_path, w.path, w.branch, w.completed_at
-             FROM worktrees w
-             JOIN repos r ON r.id = w.repo_id
-             WHERE w.status IN ('merged', 'abandoned')",
+            &format!(
+                "SELECT w.id, r.local_path, w.path, w.branch, w.completed_at
+                 FROM worktrees w
+                 JOIN repos r ON r.id = w.repo_id
+                 WHERE w.status NOT IN {live}",
+                live = LIVE_STATUSES_SQL,
+            ),
             [],
             |row| {
                 Ok((
@@ -1352,7 +1929,7 @@ impl<'a> WorktreeManager<'a> {
 
         // Run git worktree prune on each affected repo
         for repo_path in &pruned_repos {
-            let _ = git_in(repo_path).args(["worktree", "prune"]).output();
+            let _ = crate::command::output(git_in(repo_path).args(["worktree", "prune"]));
         }
 
         Ok(reaped)
@@ -1367,19 +1944,73 @@ impl<'a> WorktreeManager<'a> {
         Ok(())
     }
 
-    /// Permanently delete completed (merged/abandoned) worktree records from the database.
+    /// Count finished worktrees by outcome, breaking abandoned ones down by
+    /// reason. All repos when `repo_id` is `None`. Purged worktrees are gone
+    /// and not counted.
+    pub fn outcomes(&self, repo_id: Option<&str>) -> Result<WorktreeOutcomes> {
+        let rows = query_collect(
+            self.conn,
+            "SELECT status, abandon_reason, COUNT(*) FROM worktrees \
+             WHERE status IN ('merged', 'abandoned', 'promoted') \
+               AND (:repo_id IS NULL OR repo_id = :repo_id) \
+             GROUP BY status, abandon_reason",
+            named_params![":repo_id": repo_id],
+            |row| {
+                Ok((
+                    row.get::<_, WorktreeStatus>(0)?,
+                    row.get::<_, Option<AbandonReason>>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+        )?;
+
+        let mut outcomes = WorktreeOutcomes {
+            by_reason: AbandonReason::ALL
+                .into_iter()
+                .map(|reason| AbandonReasonCount { reason, count: 0 })
+                .collect(),
+            ..Default::default()
+        };
+        for (status, reason, count) in rows {
+            match status {
+                WorktreeStatus::Merged => outcomes.merged += count,
+                WorktreeStatus::Promoted => outcomes.promoted += count,
+                WorktreeStatus::Abandoned => {
+                    outcomes.abandoned += count;
+                    match outcomes
+                        .by_reason
+                        .iter_mut()
+                        .find(|c| Some(c.reason) == reason)
+                    {
+                        Some(c) => c.count += count,
+                        None => outcomes.unspecified += count,
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Permanently delete completed (merged/abandoned/promoted) worktree records from the database.
     pub fn purge(&self, repo_slug: &str, name: Option<&str>) -> Result<usize> {
         let repo_mgr = RepoManager::new(self.conn, self.config);
         let repo = repo_mgr.get_by_slug(repo_slug)?;
 
         let count = if let Some(slug) = name {
             self.conn.execute(
-                "DELETE FROM worktrees WHERE repo_id = :repo_id AND slug = :slug AND status != 'active'",
+                &format!(
+                    "DELETE FROM worktrees WHERE repo_id = :repo_id AND slug = :slug AND status NOT IN {}",
+                    LIVE_STATUSES_SQL
+                ),
                 named_params! { ":repo_id": repo.id, ":slug": slug },
             )?
         } else {
             self.conn.execute(
-                "DELETE FROM worktrees WHERE repo_id = :repo_id AND status != 'active'",
+                &format!(
+                    "DELETE FROM worktrees WHERE repo_id = :repo_id AND status NOT IN {}",
+                    LIVE_STATUSES_SQL
+                ),
                 named_params! { ":repo_id": repo.id },
             )?
         };
@@ -1405,6 +2036,63 @@ impl<'a> WorktreeManager<'a> {
         )
     }
 
+    /// Publish a changelog entry for `worktree`, whose PR just merged, when
+    /// the repo's `.conductor/config.toml` has a `[changelog]` section.
+    /// Failures are logged and never block the cleanup.
+    fn record_changelog_entry(
+        &self,
+        worktree: &Worktree,
+        repo_path: &str,
+        remote_url: &str,
+        default_branch: &str,
+    ) {
+        let config = match RepoConfig::load(Path::new(repo_path)) {
+            Ok(rc) => match rc.changelog {
+                Some(config) => config,
+                None => return,
+            },
+            Err(e) => {
+                tracing::warn!(repo = %repo_path, "skipping changelog entry: {e}");
+                return;
+            }
+        };
+
+        let ticket = worktree
+            .ticket_id
+            .as_deref()
+            .and_then(|id| TicketSyncer::new(self.conn).get_by_id(id).ok());
+        let pr = crate::github::get_pr_detail(remote_url, &worktree.branch);
+        let title = ticket
+            .as_ref()
+            .map(|t| t.title.clone())
+            .or_else(|| pr.as_ref().map(|p| p.title.clone()))
+            .unwrap_or_else(|| worktree.slug.replace('-', " "));
+        let entry = ChangelogEntry {
+            ticket: ticket.map(|t| {
+                if t.source_id.chars().all(|c| c.is_ascii_digit()) {
+                    format!("#{}", t.source_id)
+                } else {
+                    t.source_id
+                }
+            }),
+            pr_url: pr.map(|p| p.url),
+            ..ChangelogEntry::new(&worktree.slug, &worktree.branch, &title)
+        };
+        let base = worktree.base_branch.as_deref().unwrap_or(default_branch);
+
+        match changelog::publish_entry(repo_path, remote_url, base, &config, &entry) {
+            Ok(ChangelogOutcome::Committed { path }) => {
+                tracing::info!(worktree = %worktree.slug, path = %path.display(), "changelog entry committed");
+            }
+            Ok(ChangelogOutcome::PullRequest { url }) => {
+                tracing::info!(worktree = %worktree.slug, %url, "changelog entry PR opened");
+            }
+            Err(e) => {
+                tracing::warn!(worktree = %worktree.slug, "failed to write changelog entry: {e}");
+            }
+        }
+    }
+
     pub(crate) fn cleanup_merged_worktrees_with_merge_check(
         &self,
         repo_slug: Option<&str>,
@@ -1412,14 +2100,16 @@ impl<'a> WorktreeManager<'a> {
         merge_check: impl Fn(&str, &[String]) -> std::collections::HashMap<String, String>,
         pull_fn: impl Fn(&str, &str) -> std::result::Result<(), String>,
     ) -> Result<usize> {
-        let base_query =
+        let base_query = format!(
             "SELECT w.id, w.branch, w.path, r.local_path, r.remote_url, w.repo_id, w.base_branch, w.created_at
                  FROM worktrees w
                  JOIN repos r ON r.id = w.repo_id
-                 WHERE w.status = 'active'";
+                 WHERE w.status IN {live}",
+            live = LIVE_STATUSES_SQL,
+        );
         let query = match repo_slug {
             Some(_) => format!("{base_query} AND r.slug = :slug"),
-            None => base_query.to_string(),
+            None => base_query,
         };
 
         let mapper = |row: &rusqlite::Row| -> rusqlite::Result<[String; 8]> {
@@ -1467,7 +2157,7 @@ impl<'a> WorktreeManager<'a> {
             std::collections::HashSet::new();
 
         for row in &rows {
-            let [wt_id, branch, wt_path, repo_path, _remote_url, repo_id, base_branch, wt_created_at] =
+            let [wt_id, branch, wt_path, repo_path, remote_url, repo_id, base_branch, wt_created_at] =
                 row;
             let Some(merged_at) = merged_branches.get(branch) else {
                 continue;
@@ -1497,12 +2187,35 @@ impl<'a> WorktreeManager<'a> {
 
             // Mark as merged
             self.conn.execute(
-                "UPDATE worktrees SET status = 'merged', completed_at = :now WHERE id = :id",
-                named_params! { ":now": now, ":id": wt_id },
+                "UPDATE worktrees SET status = :status, completed_at = :now WHERE id = :id",
+                named_params! { ":status": WorktreeStatus::Merged, ":now": now, ":id": wt_id },
             )?;
 
+            // Stacked children need the parent branch as the rebase cut point,
+            // so move them onto the parent's base before the branch is deleted.
+            let parent = self.get_by_id(wt_id).ok();
+            if let Some(parent) = &parent {
+                let default_branch = RepoManager::new(self.conn, self.config)
+                    .get_by_id(repo_id)
+                    .map(|r| r.default_branch)
+                    .unwrap_or_else(|_| "main".to_string());
+                for warning in self.restack_children_of_merged(parent, &default_branch) {
+                    tracing::warn!(branch = %branch, "{warning}");
+                }
+                self.record_changelog_entry(parent, repo_path, remote_url, &default_branch);
+            }
+
             // Remove local git artifacts
+            let hooks = load_hooks(repo_path);
+            let hook_target = HookTarget {
+                repo_path,
+                worktree_path: wt_path,
+                branch,
+                ticket_id: parent.as_ref().and_then(|p| p.ticket_id.as_deref()),
+            };
+            run_hook(self.conn, &hooks, WorktreeHook::PreDelete, &hook_target);
             remove_git_artifacts(repo_path, wt_path, branch);
+            run_hook(self.conn, &hooks, WorktreeHook::PostMerge, &hook_target);
 
             // Delete remote branch (best-effort)
             delete_remote_branch(repo_path, branch);
@@ -1513,7 +2226,7 @@ impl<'a> WorktreeManager<'a> {
             let pull_key = (repo_id.clone(), base_branch.clone());
             if !base_branch.is_empty() && !pulled_bases.contains(&pull_key) {
                 match self.get_by_branch(repo_id, base_branch) {
-                    Ok(base_wt) if base_wt.status == WorktreeStatus::Active => {
+                    Ok(base_wt) if base_wt.is_active() => {
                         if !base_wt.path.is_empty() {
                             if let Err(e) = pull_fn(&base_wt.path, base_branch) {
                                 tracing::warn!(
@@ -1542,13 +2255,57 @@ impl<'a> WorktreeManager<'a> {
 
         // Run git worktree prune once per unique repo path
         for repo_path in &pruned_repos {
-            let _ = git_in(repo_path).args(["worktree", "prune"]).output();
+            let _ = crate::command::output(git_in(repo_path).args(["worktree", "prune"]));
         }
 
         Ok(cleaned)
     }
 }
 
+/// PR title and body built from the ticket a worktree works on and the result
+/// of its agent run. The title is the ticket's, or the first line of the
+/// run's prompt without one; GitHub issues are closed by the PR. TODOs the
+/// branch added are listed as an unchecked checklist.
+pub(super) fn agent_pr_description(
+    ticket: Option<&crate::tickets::Ticket>,
+    prompt: &str,
+    result: &str,
+    todos: &[super::BranchTodo],
+) -> (String, String) {
+    let title = match ticket {
+        Some(t) => t.title.trim().to_string(),
+        None => crate::text_util::truncate_str(prompt.lines().next().unwrap_or("").trim(), 72)
+            .to_string(),
+    };
+    let mut body =
+        crate::text_util::cap_with_suffix(result.trim(), MAX_PR_BODY_BYTES, "\n\n… (truncated)");
+    match ticket {
+        Some(t) if t.source_type == "github" => {
+            body.push_str(&format!("\n\nCloses #{}", t.source_id))
+        }
+        Some(t) if !t.url.is_empty() => {
+            body.push_str(&format!("\n\nTicket: [{}]({})", t.source_id, t.url))
+        }
+        _ => {}
+    }
+    if !todos.is_empty() {
+        body.push_str("\n\n### Unfinished items\n");
+        for todo in todos.iter().take(MAX_PR_TODOS) {
+            body.push_str(&format!(
+                "\n- [ ] `{}:{}` {}",
+                todo.file, todo.line, todo.marker
+            ));
+            if !todo.text.is_empty() {
+                body.push_str(&format!(" {}", todo.text));
+            }
+        }
+        if todos.len() > MAX_PR_TODOS {
+            body.push_str(&format!("\n\n…and {} more", todos.len() - MAX_PR_TODOS));
+        }
+    }
+    (title, body)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1686,6 +2443,24 @@ mod tests {
         assert!(resolve_parent_branch(&conn, "t1", "r1").is_none());
     }
 
+    #[test]
+    fn ticket_pr_number_only_for_pull_request_tickets() {
+        let conn = create_test_conn();
+        conn.execute(
+            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at) VALUES ('r1','repo','/p','u','/w','2024-01-01T00:00:00Z')",
+            [],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, labels, url, synced_at, raw_json) \
+             VALUES ('t1', 'r1', 'github', '42', 'Issue', '', 'open', '[]', '', '2024-01-01T00:00:00Z', '{}'), \
+                    ('t2', 'r1', 'github_pr', '43', 'PR', '', 'open', '[]', '', '2024-01-01T00:00:00Z', '{}')",
+            [],
+        ).unwrap();
+        assert_eq!(ticket_pr_number(&conn, "t1"), None);
+        assert_eq!(ticket_pr_number(&conn, "t2"), Some(43));
+        assert_eq!(ticket_pr_number(&conn, "missing"), None);
+    }
+
     #[test]
     fn resolve_parent_branch_returns_none_when_no_dependencies() {
         let conn = create_test_conn();
@@ -2398,7 +3173,10 @@ mod tests {
             "test-repo",
             "feat-test",
             Some("newbase"),
-            SetBaseBranchOptions { rebase: true },
+            SetBaseBranchOptions {
+                rebase: true,
+                ..Default::default()
+            },
         );
         assert!(
             result.is_ok(),
@@ -2459,7 +3237,10 @@ mod tests {
             "test-repo",
             "feat-test",
             Some("newbase-dirty"),
-            SetBaseBranchOptions { rebase: true },
+            SetBaseBranchOptions {
+                rebase: true,
+                ..Default::default()
+            },
         );
         assert!(
             matches!(result, Err(ConductorError::InvalidInput(ref msg)) if msg.contains("uncommitted")),
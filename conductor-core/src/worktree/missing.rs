@@ -0,0 +1,155 @@
+//! Live worktrees whose checkout directory disappeared, e.g. after a manual
+//! `rm -rf`.
+//!
+//! The periodic health check ([`crate::maintenance::reap`] and the web
+//! server's reaper) flags them with `missing_since` and records a
+//! notification. A flagged worktree is either restored from its branch with
+//! [`WorktreeManager::restore`] or dropped with the usual delete.
+
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+use crate::notifications::{NewNotification, NotificationManager, NotificationSeverity};
+use crate::repo::{Repo, RepoManager};
+
+use super::git_helpers::branch_exists;
+use super::manager::WorktreeManager;
+use super::types::{map_worktree_row, Worktree};
+use super::{LIVE_STATUSES_SQL, WORKTREE_COLUMNS};
+
+impl WorktreeManager<'_> {
+    /// Flag live worktrees whose directory is gone and clear the flag on
+    /// ones whose directory is back. Each newly flagged worktree gets a
+    /// notification; they are returned.
+    pub fn check_missing_paths(&self) -> Result<Vec<Worktree>> {
+        let live: Vec<Worktree> = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE status IN {LIVE_STATUSES_SQL}"
+            ),
+            [],
+            map_worktree_row,
+        )?;
+        let now = Utc::now().to_rfc3339();
+        let mut flagged = Vec::new();
+        for mut wt in live {
+            let exists = Path::new(&wt.path).is_dir();
+            if exists == wt.missing_since.is_none() {
+                continue;
+            }
+            let missing_since = (!exists).then(|| now.clone());
+            self.conn.execute(
+                "UPDATE worktrees SET missing_since = :missing_since WHERE id = :id",
+                named_params![":missing_since": missing_since, ":id": wt.id],
+            )?;
+            if exists {
+                tracing::info!(worktree = %wt.slug, "worktree directory is back");
+                continue;
+            }
+            tracing::warn!(worktree = %wt.slug, path = %wt.path, "worktree directory is missing");
+            NotificationManager::new(self.conn).record(&NewNotification {
+                kind: "worktree_path_missing".into(),
+                severity: NotificationSeverity::Warning,
+                title: format!("Worktree {} is missing", wt.slug),
+                body: format!(
+                    "{} no longer exists. Restore it from branch {} or delete the worktree.",
+                    wt.path, wt.branch
+                ),
+                url: Some(format!("/repos/{}/worktrees/{}", wt.repo_id, wt.id)),
+                repo_id: Some(wt.repo_id.clone()),
+            })?;
+            wt.missing_since = missing_since;
+            flagged.push(wt);
+        }
+        Ok(flagged)
+    }
+
+    /// Live worktrees flagged missing, in one repo or all of them.
+    pub fn list_missing(&self, repo_id: Option<&str>) -> Result<Vec<Worktree>> {
+        query_collect(
+            self.conn,
+            &format!(
+                "SELECT {WORKTREE_COLUMNS} FROM worktrees \
+                 WHERE status IN {LIVE_STATUSES_SQL} AND missing_since IS NOT NULL \
+                   AND (:repo_id IS NULL OR repo_id = :repo_id) \
+                 ORDER BY missing_since"
+            ),
+            named_params![":repo_id": repo_id],
+            map_worktree_row,
+        )
+    }
+
+    /// Recreate a live worktree's missing directory with `git worktree add`
+    /// from its branch, or from `origin/<branch>` when the local branch is
+    /// gone too, and clear its missing flag.
+    pub fn restore(&self, repo_slug: &str, name: &str) -> Result<Worktree> {
+        let (repo, worktree) = self.get_active_worktree(repo_slug, name)?;
+        self.restore_worktree(&repo, worktree)
+    }
+
+    /// [`Self::restore`] by worktree ID.
+    pub fn restore_by_id(&self, worktree_id: &str) -> Result<Worktree> {
+        let worktree = self.get_by_id(worktree_id)?;
+        let repo = RepoManager::new(self.conn, self.config).get_by_id(&worktree.repo_id)?;
+        self.restore(&repo.slug, &worktree.slug)
+    }
+
+    fn restore_worktree(&self, repo: &Repo, mut worktree: Worktree) -> Result<Worktree> {
+        if Path::new(&worktree.path).exists() {
+            return Err(ConductorError::InvalidInput(format!(
+                "worktree '{}' is not missing: {} exists",
+                worktree.slug, worktree.path
+            )));
+        }
+        // Forget the vanished checkout so git lets the branch be checked out again.
+        check_output(git_in(&repo.local_path).args(["worktree", "prune"]))?;
+        if branch_exists(&repo.local_path, &worktree.branch) {
+            check_output(git_in(&repo.local_path).args([
+                "worktree",
+                "add",
+                &worktree.path,
+                &worktree.branch,
+            ]))?;
+        } else {
+            let remote = format!("origin/{}", worktree.branch);
+            let on_origin = crate::command::output(git_in(&repo.local_path).args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/remotes/{remote}"),
+            ]))
+            .is_ok_and(|o| o.status.success());
+            if !on_origin {
+                return Err(ConductorError::InvalidInput(format!(
+                    "branch {} exists neither locally nor on origin; delete the worktree instead",
+                    worktree.branch
+                )));
+            }
+            check_output(git_in(&repo.local_path).args([
+                "worktree",
+                "add",
+                "--track",
+                "-b",
+                &worktree.branch,
+                &worktree.path,
+                &remote,
+            ]))?;
+        }
+
+        // Recreate the worktree's own DB, as `create` does.
+        let wt_conn = crate::db::open_database(&Path::new(&worktree.path).join(".conductor.db"))?;
+        crate::db::seed::seed_database(&wt_conn)?;
+
+        self.conn.execute(
+            "UPDATE worktrees SET missing_since = NULL WHERE id = :id",
+            named_params![":id": worktree.id],
+        )?;
+        worktree.missing_since = None;
+        Ok(worktree)
+    }
+}
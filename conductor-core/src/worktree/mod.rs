@@ -1,20 +1,63 @@
+mod batch;
+mod browse;
+mod commit;
+mod conflicts;
+mod from_url;
 mod git_helpers;
+mod git_status;
+mod hooks;
+mod listing;
 mod manager;
+mod missing;
+mod quota;
+mod reservation;
+mod stack;
+mod sync;
+mod todos;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
+pub use batch::{BatchItem, BatchOutcome};
+pub use browse::{WorktreeDirEntry, WorktreeListing, MAX_BROWSE_FILE_BYTES};
+pub use commit::CommitSummary;
+pub use conflicts::{
+    abort_operation, conflict_state, continue_operation, index_tree, paths_with_markers,
+    unmerged_paths, ConflictOperation, ConflictState, ConflictedFile,
+};
+pub use from_url::{TicketUrl, UrlTarget};
 pub use git_helpers::{list_remote_branches, MainHealthStatus};
+pub use git_status::{git_status, git_statuses, LastCommit, WorktreeGitStatus};
+pub(crate) use hooks::{load_hooks, run_hook, HookTarget, WorktreeHook};
+pub use listing::{
+    parse_status_list, AheadBehind, WorktreeListEntry, WorktreeListFilter, WorktreeSort,
+    DEFAULT_STALE_DAYS,
+};
+pub(crate) use manager::SLUG_PREFIXES;
 pub use manager::{
-    get_ticket_id_by_branch, label_to_branch_prefix, SetBaseBranchOptions, WorktreeAdoptOptions,
-    WorktreeCreateOptions, WorktreeManager,
+    derive_worktree_slug, get_ticket_id_by_branch, label_to_branch_prefix, CreateProgress,
+    PrOptions, PushOptions, SetBaseBranchOptions, WorktreeAdoptOptions, WorktreeCreateOptions,
+    WorktreeManager,
+};
+pub use quota::{
+    dir_size_bytes, format_bytes, scan_workspace, PurgeCandidate, QuotaLevel, WorkspaceUsage,
+};
+pub use stack::{stack_order, RestackOptions, StackInfo, StackState};
+pub use sync::{SyncResult, SyncStatus, SyncStrategy};
+pub use todos::{parse_branch_todos, BranchTodo};
+pub use types::{
+    AbandonReason, AbandonReasonCount, BaseBranchSource, ResolvedBase, Worktree,
+    WorktreeCreateStage, WorktreeOutcomes, WorktreeStatus, WorktreeWithStatus,
 };
-pub use types::{Worktree, WorktreeStatus, WorktreeWithStatus};
 
 // Column constants used by both types.rs and manager.rs — live here to avoid circular deps.
 const WORKTREE_COLUMNS: &str =
-    "id, repo_id, slug, branch, path, ticket_id, status, created_at, completed_at, model, base_branch";
+    "id, repo_id, slug, branch, path, ticket_id, status, created_at, completed_at, model, base_branch, abandon_reason, missing_since, ticket_change";
 
 static WORKTREE_COLUMNS_W: std::sync::LazyLock<String> =
     std::sync::LazyLock::new(|| crate::db::prefix_columns(WORKTREE_COLUMNS, "w."));
+
+/// SQL list of [`WorktreeStatus::LIVE`] for `status IN` filters. Kept as a literal so
+/// it can be inlined into `const` SQL fragments; a test pins it to the enum.
+pub(crate) const LIVE_STATUSES_SQL: &str = "('active', 'needs_review', 'parked')";
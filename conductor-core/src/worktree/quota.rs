@@ -0,0 +1,317 @@
+//! Disk usage accounting for per-repo workspace directories.
+//!
+//! The scanner walks a repo's `workspace_dir`, attributes each top-level
+//! directory to a tracked worktree (or flags it as untracked), and compares the
+//! total against the `[quota]` limits from the global config.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config::QuotaConfig;
+use crate::repo::Repo;
+
+use super::types::{Worktree, WorktreeStatus};
+
+/// Maximum number of purge candidates surfaced in hints and API responses.
+const MAX_PURGE_CANDIDATES: usize = 5;
+
+/// Where a workspace sits relative to its configured quota.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaLevel {
+    /// Under the soft threshold, or no quota configured.
+    Ok,
+    /// At or above the soft threshold; creation proceeds with a warning.
+    Warning,
+    /// At or above the hard limit; creation is refused.
+    Exceeded,
+}
+
+/// A directory inside the workspace that could be removed to free space.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct PurgeCandidate {
+    pub path: String,
+    /// Worktree slug, when the directory belongs to a tracked worktree.
+    pub slug: Option<String>,
+    /// A [`WorktreeStatus`] string, or `untracked`.
+    pub reason: String,
+    pub bytes: u64,
+}
+
+impl PurgeCandidate {
+    /// Whether the directory belongs to a worktree that is still in use.
+    fn is_live(&self) -> bool {
+        self.reason
+            .parse::<WorktreeStatus>()
+            .is_ok_and(|s| s.is_live())
+    }
+}
+
+/// Disk usage snapshot for a single repo's workspace directory.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceUsage {
+    pub repo_id: String,
+    pub repo_slug: String,
+    pub workspace_dir: String,
+    pub used_bytes: u64,
+    pub limit_bytes: Option<u64>,
+    pub warn_bytes: Option<u64>,
+    pub level: QuotaLevel,
+    /// Largest reclaimable directories, stale entries first.
+    pub purge_candidates: Vec<PurgeCandidate>,
+}
+
+impl WorkspaceUsage {
+    /// Usage as a percentage of the hard limit (may exceed 100).
+    pub fn percent_used(&self) -> Option<u64> {
+        self.limit_bytes
+            .filter(|l| *l > 0)
+            .map(|l| self.used_bytes.saturating_mul(100) / l)
+    }
+
+    /// Short human-readable meter, e.g. `"3.2 GB / 10.0 GB (32%)"`.
+    pub fn meter_label(&self) -> String {
+        match (self.limit_bytes, self.percent_used()) {
+            (Some(limit), Some(pct)) => format!(
+                "{} / {} ({pct}%)",
+                format_bytes(self.used_bytes),
+                format_bytes(limit)
+            ),
+            _ => format_bytes(self.used_bytes),
+        }
+    }
+
+    /// Multi-line remediation text listing the directories worth removing.
+    pub fn remediation_hint(&self) -> String {
+        if self.purge_candidates.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from("\nFree space by removing stale worktrees:");
+        for c in &self.purge_candidates {
+            let size = format_bytes(c.bytes);
+            let line = match (c.is_live(), c.slug.as_deref()) {
+                (true, Some(slug)) => format!(
+                    "\n  {slug} ({size}) — conductor worktree delete {} {slug}",
+                    self.repo_slug
+                ),
+                (_, Some(slug)) => format!(
+                    "\n  {slug} ({}, {size}) — conductor worktree purge {} {slug}",
+                    c.reason, self.repo_slug
+                ),
+                (_, None) => format!("\n  {} (untracked, {size}) — rm -rf {}", c.path, c.path),
+            };
+            out.push_str(&line);
+        }
+        out
+    }
+}
+
+/// Scan `repo.workspace_dir` and classify its usage against `quota`.
+///
+/// `worktrees` should contain every worktree row for the repo (any status) so
+/// that directories can be attributed to a slug.
+pub fn scan_workspace(repo: &Repo, worktrees: &[Worktree], quota: &QuotaConfig) -> WorkspaceUsage {
+    let by_path: HashMap<&str, &Worktree> = worktrees
+        .iter()
+        .filter(|wt| wt.repo_id == repo.id)
+        .map(|wt| (wt.path.as_str(), wt))
+        .collect();
+
+    let mut used_bytes = 0u64;
+    let mut candidates: Vec<PurgeCandidate> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(&repo.workspace_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let bytes = dir_size_bytes(&path);
+            used_bytes = used_bytes.saturating_add(bytes);
+            let Ok(meta) = entry.file_type() else {
+                continue;
+            };
+            if !meta.is_dir() {
+                continue;
+            }
+            let path_str = path.to_string_lossy().to_string();
+            let (slug, reason) = match by_path.get(path_str.as_str()) {
+                Some(wt) => (Some(wt.slug.clone()), wt.status.as_str().to_string()),
+                None => (None, "untracked".to_string()),
+            };
+            candidates.push(PurgeCandidate {
+                path: path_str,
+                slug,
+                reason,
+                bytes,
+            });
+        }
+    }
+
+    // Stale directories (completed or untracked) first, then largest first.
+    candidates.sort_by(|a, b| a.is_live().cmp(&b.is_live()).then(b.bytes.cmp(&a.bytes)));
+    candidates.truncate(MAX_PURGE_CANDIDATES);
+
+    let limit_bytes = quota.limit_bytes_for(&repo.slug);
+    let warn_bytes = limit_bytes.map(|l| quota.warn_bytes(l));
+    let level = match (limit_bytes, warn_bytes) {
+        (Some(limit), _) if used_bytes >= limit => QuotaLevel::Exceeded,
+        (_, Some(warn)) if used_bytes >= warn => QuotaLevel::Warning,
+        _ => QuotaLevel::Ok,
+    };
+
+    WorkspaceUsage {
+        repo_id: repo.id.clone(),
+        repo_slug: repo.slug.clone(),
+        workspace_dir: repo.workspace_dir.clone(),
+        used_bytes,
+        limit_bytes,
+        warn_bytes,
+        level,
+        purge_candidates: candidates,
+    }
+}
+
+/// Recursively sum the apparent size of all regular files under `path`.
+///
+/// Symlinks are not followed. Unreadable entries are skipped silently so a
+/// single permission error does not abort the scan.
+pub fn dir_size_bytes(path: &Path) -> u64 {
+    let mut total = 0u64;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(p) = stack.pop() {
+        let Ok(meta) = std::fs::symlink_metadata(&p) else {
+            continue;
+        };
+        if meta.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(&p) {
+                stack.extend(entries.flatten().map(|e| e.path()));
+            }
+        } else if meta.is_file() {
+            total = total.saturating_add(meta.len());
+        }
+    }
+    total
+}
+
+/// Format a byte count with binary units (`512 B`, `1.5 MB`, `3.2 GB`).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_at(dir: &Path) -> Repo {
+        Repo {
+            id: "r1".into(),
+            slug: "test-repo".into(),
+            local_path: "/tmp/repo".into(),
+            remote_url: "https://github.com/test/repo.git".into(),
+            default_branch: "main".into(),
+            workspace_dir: dir.to_string_lossy().to_string(),
+            created_at: "2024-01-01T00:00:00Z".into(),
+            model: None,
+            allow_agent_issue_creation: false,
+            runtime_overrides: Default::default(),
+            toolchain: Default::default(),
+        }
+    }
+
+    fn wt(slug: &str, path: &Path, status: WorktreeStatus) -> Worktree {
+        Worktree {
+            id: format!("id-{slug}"),
+            repo_id: "r1".into(),
+            slug: slug.into(),
+            branch: format!("feat/{slug}"),
+            path: path.to_string_lossy().to_string(),
+            ticket_id: None,
+            status,
+            created_at: "2024-01-01T00:00:00Z".into(),
+            completed_at: None,
+            model: None,
+            base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
+        }
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn scan_attributes_directories_and_orders_stale_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let active = dir.path().join("feat-active");
+        let abandoned = dir.path().join("feat-old");
+        let stray = dir.path().join("leftover");
+        for (p, n) in [(&active, 4000), (&abandoned, 1000), (&stray, 2000)] {
+            std::fs::create_dir_all(p).unwrap();
+            std::fs::write(p.join("blob"), vec![0u8; n]).unwrap();
+        }
+        let worktrees = vec![
+            wt("feat-active", &active, WorktreeStatus::Active),
+            wt("feat-old", &abandoned, WorktreeStatus::Abandoned),
+        ];
+
+        let usage = scan_workspace(&repo_at(dir.path()), &worktrees, &QuotaConfig::default());
+
+        assert_eq!(usage.used_bytes, 7000);
+        assert_eq!(usage.level, QuotaLevel::Ok);
+        assert_eq!(usage.limit_bytes, None);
+        let reasons: Vec<&str> = usage
+            .purge_candidates
+            .iter()
+            .map(|c| c.reason.as_str())
+            .collect();
+        assert_eq!(reasons, vec!["untracked", "abandoned", "active"]);
+        let hint = usage.remediation_hint();
+        assert!(hint.contains("conductor worktree purge test-repo feat-old"));
+        assert!(hint.contains("conductor worktree delete test-repo feat-active"));
+    }
+
+    #[test]
+    fn scan_classifies_warning_and_exceeded() {
+        let dir = tempfile::tempdir().unwrap();
+        let wt_dir = dir.path().join("feat-a");
+        std::fs::create_dir_all(&wt_dir).unwrap();
+        std::fs::write(wt_dir.join("blob"), vec![0u8; 900]).unwrap();
+        let repo = repo_at(dir.path());
+
+        // 1 KiB limit: 900 bytes is ~88% → warning at the default 80%.
+        let kib = 1.0 / (1024.0 * 1024.0);
+        let mut quota = QuotaConfig {
+            max_gb: Some(kib),
+            ..QuotaConfig::default()
+        };
+        assert_eq!(
+            scan_workspace(&repo, &[], &quota).level,
+            QuotaLevel::Warning
+        );
+
+        quota.repos.insert("test-repo".into(), kib / 2.0);
+        let usage = scan_workspace(&repo, &[], &quota);
+        assert_eq!(usage.level, QuotaLevel::Exceeded);
+        assert_eq!(usage.percent_used(), Some(175));
+    }
+}
@@ -0,0 +1,112 @@
+//! Atomic slug reservation for worktree creation.
+//!
+//! Creating a worktree spends seconds to minutes on fetch, branch and
+//! checkout before its row lands in `worktrees`. Two creates for the same
+//! name in that window would both pass the "already exists" check and then
+//! collide on the git branch. Each create therefore claims its slug in
+//! `worktree_slug_reservations` first; the primary key makes the claim
+//! atomic across connections and processes.
+
+use chrono::{Duration, Utc};
+use rusqlite::{named_params, Connection};
+
+use crate::error::{ConductorError, Result};
+use crate::repo::Repo;
+
+use super::git_helpers::branch_exists;
+use super::WorktreeManager;
+
+/// Reservations older than this were left behind by a create that crashed
+/// and no longer block the slug.
+const RESERVATION_TTL_MINUTES: i64 = 60;
+
+/// Highest `-N` suffix tried before reporting the slug as contended.
+const MAX_SLUG_SUFFIX: u32 = 20;
+
+/// A slug held for the duration of one worktree create. Dropping it releases
+/// the slug, so it must outlive the insert into `worktrees`.
+pub(super) struct SlugReservation<'a> {
+    conn: &'a Connection,
+    repo_id: String,
+    pub slug: String,
+    pub branch: String,
+}
+
+impl Drop for SlugReservation<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.conn.execute(
+            "DELETE FROM worktree_slug_reservations WHERE repo_id = :repo_id AND slug = :slug",
+            named_params! { ":repo_id": self.repo_id, ":slug": self.slug },
+        ) {
+            tracing::warn!(slug = %self.slug, "failed to release worktree slug reservation: {e}");
+        }
+    }
+}
+
+impl<'a> WorktreeManager<'a> {
+    /// Reserve `slug` (with `branch`) in `repo` for a create in progress.
+    ///
+    /// A live worktree already using `slug` is reported as
+    /// [`ConductorError::WorktreeAlreadyExists`]. When `slug` is only held by
+    /// another in-flight create and `allow_suffix` is set, `<slug>-2`,
+    /// `<slug>-3`, … (with matching branches) are tried instead; otherwise the
+    /// result is [`ConductorError::WorktreeCreationInProgress`].
+    pub(super) fn reserve_slug(
+        &self,
+        repo: &Repo,
+        slug: &str,
+        branch: &str,
+        allow_suffix: bool,
+    ) -> Result<SlugReservation<'a>> {
+        let cutoff = (Utc::now() - Duration::minutes(RESERVATION_TTL_MINUTES)).to_rfc3339();
+        self.conn.execute(
+            "DELETE FROM worktree_slug_reservations WHERE reserved_at < :cutoff",
+            named_params! { ":cutoff": cutoff },
+        )?;
+
+        for n in 1..=MAX_SLUG_SUFFIX {
+            let (candidate, candidate_branch) = if n == 1 {
+                (slug.to_string(), branch.to_string())
+            } else {
+                if branch_exists(&repo.local_path, &format!("{branch}-{n}")) {
+                    continue;
+                }
+                (format!("{slug}-{n}"), format!("{branch}-{n}"))
+            };
+
+            let claimed = self.conn.execute(
+                "INSERT OR IGNORE INTO worktree_slug_reservations (repo_id, slug, reserved_at) \
+                 VALUES (:repo_id, :slug, :reserved_at)",
+                named_params! {
+                    ":repo_id": repo.id,
+                    ":slug": candidate,
+                    ":reserved_at": Utc::now().to_rfc3339(),
+                },
+            )?;
+            if claimed == 0 {
+                if !allow_suffix {
+                    return Err(ConductorError::WorktreeCreationInProgress { slug: candidate });
+                }
+                continue;
+            }
+            let reservation = SlugReservation {
+                conn: self.conn,
+                repo_id: repo.id.clone(),
+                slug: candidate,
+                branch: candidate_branch,
+            };
+
+            // Checked only once the slug is held: a create that finished just
+            // before we claimed it has its row in `worktrees` by now.
+            match self.check_or_purge_existing_worktree(&repo.id, &reservation.slug) {
+                Ok(()) => return Ok(reservation),
+                Err(e @ ConductorError::WorktreeAlreadyExists { .. }) if n == 1 => return Err(e),
+                Err(ConductorError::WorktreeAlreadyExists { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(ConductorError::WorktreeCreationInProgress {
+            slug: slug.to_string(),
+        })
+    }
+}
@@ -0,0 +1,379 @@
+//! Stacked worktrees: a worktree whose `base_branch` is another worktree's branch.
+//!
+//! The parent relationship is not stored separately — it is derived from
+//! `base_branch`, the same way feature membership is. Creating a worktree with
+//! `--stack-on <parent>` simply records the parent's branch as its base.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{named_params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::{ConductorError, Result};
+use crate::git::{check_output, git_in};
+use crate::repo::RepoManager;
+
+use super::conflicts::rebase_or_stop;
+use super::git_helpers::branch_exists;
+use super::manager::WorktreeManager;
+use super::types::{map_worktree_row, Worktree, WorktreeStatus};
+use super::{LIVE_STATUSES_SQL, WORKTREE_COLUMNS};
+
+/// Where a stacked worktree stands relative to its parent.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackState {
+    /// The parent's branch tip is already in the child's history.
+    UpToDate,
+    /// The parent has new commits; the child should be rebased onto it.
+    ParentUpdated,
+    /// The parent was merged; the child should move onto the parent's base.
+    ParentMerged,
+}
+
+/// Options for [`WorktreeManager::restack`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RestackOptions {
+    /// Leave a conflicting rebase in progress and fail with
+    /// [`ConductorError::RebaseConflict`] instead of aborting it, so the
+    /// conflicts can be resolved in place.
+    pub keep_conflicts: bool,
+}
+
+impl StackState {
+    pub fn needs_restack(&self) -> bool {
+        !matches!(self, Self::UpToDate)
+    }
+
+    /// Short marker for list views.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::UpToDate => "stacked",
+            Self::ParentUpdated => "parent updated — restack",
+            Self::ParentMerged => "parent merged — restack",
+        }
+    }
+}
+
+/// A live worktree's link to the worktree it is stacked on.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackInfo {
+    pub worktree_id: String,
+    pub parent_id: String,
+    pub parent_slug: String,
+    pub parent_branch: String,
+    pub state: StackState,
+}
+
+impl<'a> WorktreeManager<'a> {
+    /// Return the branch to use as the base for a worktree stacked on `parent`
+    /// (a worktree slug or branch in `repo_slug`). The parent must be live.
+    pub fn stack_base(&self, repo_slug: &str, parent: &str) -> Result<String> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        let parent = self.get_by_slug_or_branch(&repo.id, parent)?;
+        if !parent.is_active() {
+            return Err(ConductorError::InvalidInput(format!(
+                "cannot stack on '{}': worktree is {}",
+                parent.slug, parent.status
+            )));
+        }
+        Ok(parent.branch)
+    }
+
+    /// The worktree `wt` is stacked on, if its base branch belongs to another
+    /// worktree in the same repo. A live parent is preferred over an older,
+    /// completed worktree that reused the branch name.
+    pub fn stack_parent(&self, wt: &Worktree) -> Result<Option<Worktree>> {
+        let Some(base) = wt.base_branch.as_deref() else {
+            return Ok(None);
+        };
+        let parent = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {WORKTREE_COLUMNS} FROM worktrees \
+                     WHERE repo_id = :repo_id AND branch = :branch AND id != :id \
+                     ORDER BY CASE WHEN status IN {LIVE_STATUSES_SQL} THEN 0 ELSE 1 END, \
+                              created_at DESC \
+                     LIMIT 1"
+                ),
+                named_params![":repo_id": wt.repo_id, ":branch": base, ":id": wt.id],
+                map_worktree_row,
+            )
+            .optional()?;
+        Ok(parent)
+    }
+
+    /// Live worktrees stacked directly on `wt`.
+    pub fn stack_children(&self, wt: &Worktree) -> Result<Vec<Worktree>> {
+        let mut children = self.list_by_repo_id_and_base_branch(&wt.repo_id, &wt.branch)?;
+        children.retain(|c| c.id != wt.id);
+        Ok(children)
+    }
+
+    /// Stack link for a single worktree, or `None` when it is not stacked.
+    pub fn stack_info(&self, wt: &Worktree) -> Result<Option<StackInfo>> {
+        Ok(self
+            .stack_parent(wt)?
+            .map(|parent| build_stack_info(wt, &parent)))
+    }
+
+    /// Stack links for every live stacked worktree in a repo. Parents are
+    /// resolved from a single query; only stacked worktrees cost a git call.
+    pub fn stack_infos_for_repo(&self, repo_id: &str) -> Result<Vec<StackInfo>> {
+        let all = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {WORKTREE_COLUMNS} FROM worktrees WHERE repo_id = :repo_id \
+                 ORDER BY CASE WHEN status IN {LIVE_STATUSES_SQL} THEN 0 ELSE 1 END, \
+                          created_at DESC"
+            ),
+            named_params![":repo_id": repo_id],
+            map_worktree_row,
+        )?;
+        let mut infos = collect_stack_infos(&all);
+        infos.sort_by(|a, b| a.worktree_id.cmp(&b.worktree_id));
+        Ok(infos)
+    }
+
+    /// Stack links across all repos, keyed by worktree ID.
+    pub fn stack_infos_by_worktree(&self) -> Result<HashMap<String, StackInfo>> {
+        let all = query_collect(
+            self.conn,
+            &format!(
+                "SELECT {WORKTREE_COLUMNS} FROM worktrees \
+                 ORDER BY CASE WHEN status IN {LIVE_STATUSES_SQL} THEN 0 ELSE 1 END, \
+                          created_at DESC"
+            ),
+            [],
+            map_worktree_row,
+        )?;
+        Ok(collect_stack_infos(&all)
+            .into_iter()
+            .map(|info| (info.worktree_id.clone(), info))
+            .collect())
+    }
+
+    /// Rebase a stacked worktree onto its parent.
+    ///
+    /// While the parent is live the child is rebased onto the parent's local
+    /// branch. Once the parent is merged, the parent's commits are dropped with
+    /// `git rebase --onto origin/<parent base> <parent branch>` and the child's
+    /// recorded base moves to the parent's base. A conflicting rebase is
+    /// aborted and reported, leaving the worktree as it was, unless
+    /// [`RestackOptions::keep_conflicts`] is set.
+    ///
+    /// Returns the ref the worktree was rebased onto.
+    pub fn restack(&self, repo_slug: &str, name: &str, opts: RestackOptions) -> Result<String> {
+        let repo = RepoManager::new(self.conn, self.config).get_by_slug(repo_slug)?;
+        let wt = self.get_by_slug(&repo.id, name)?;
+        let parent = self.stack_parent(&wt)?.ok_or_else(|| {
+            ConductorError::InvalidInput(format!(
+                "'{}' is not stacked on another worktree",
+                wt.slug
+            ))
+        })?;
+        let wt_path = Path::new(&wt.path);
+        ensure_clean(wt_path)?;
+
+        if matches!(
+            parent.status,
+            WorktreeStatus::Merged | WorktreeStatus::Promoted
+        ) {
+            let new_base = parent.effective_base(&repo.default_branch).to_string();
+            let onto = rebase_off_merged_parent(
+                wt_path,
+                &new_base,
+                &parent.branch,
+                &wt.slug,
+                opts.keep_conflicts,
+            )?;
+            self.conn.execute(
+                "UPDATE worktrees SET base_branch = :base WHERE id = :id",
+                named_params![":base": parent.base_branch, ":id": wt.id],
+            )?;
+            Ok(onto)
+        } else {
+            let onto = format!("refs/heads/{}", parent.branch);
+            rebase_or_stop(wt_path, &["rebase", &onto], &wt.slug, opts.keep_conflicts)?;
+            Ok(parent.branch)
+        }
+    }
+
+    /// Move live children of a parent that is about to be cleaned up onto the
+    /// parent's base while its branch still exists. Returns one warning per
+    /// child that could not be restacked automatically.
+    pub(super) fn restack_children_of_merged(
+        &self,
+        parent: &Worktree,
+        repo_default_branch: &str,
+    ) -> Vec<String> {
+        let children = match self.stack_children(parent) {
+            Ok(c) => c,
+            Err(e) => return vec![format!("could not list stacked worktrees: {e}")],
+        };
+        let new_base = parent.effective_base(repo_default_branch).to_string();
+        let mut warnings = Vec::new();
+        for child in children {
+            let path = Path::new(&child.path);
+            let result = ensure_clean(path).and_then(|()| {
+                rebase_off_merged_parent(path, &new_base, &parent.branch, &child.slug, false)
+            });
+            match result {
+                Ok(_) => {
+                    if let Err(e) = self.conn.execute(
+                        "UPDATE worktrees SET base_branch = :base WHERE id = :id",
+                        named_params![":base": parent.base_branch, ":id": child.id],
+                    ) {
+                        warnings.push(format!(
+                            "{}: rebased but base not recorded: {e}",
+                            child.slug
+                        ));
+                    }
+                }
+                Err(e) => warnings.push(format!(
+                    "{}: parent '{}' merged but automatic restack onto '{new_base}' failed ({e}); \
+                     run `git rebase --onto origin/{new_base} <old parent tip>` in {}",
+                    child.slug, parent.branch, child.path
+                )),
+            }
+        }
+        warnings
+    }
+}
+
+/// Pair each live worktree with its parent. `all` must be ordered live-first,
+/// newest-first so the first row per branch matches `stack_parent`.
+fn collect_stack_infos(all: &[Worktree]) -> Vec<StackInfo> {
+    let mut by_branch: HashMap<(&str, &str), &Worktree> = HashMap::new();
+    for wt in all {
+        by_branch
+            .entry((wt.repo_id.as_str(), wt.branch.as_str()))
+            .or_insert(wt);
+    }
+    all.iter()
+        .filter(|wt| wt.is_active())
+        .filter_map(|wt| {
+            let parent = by_branch.get(&(wt.repo_id.as_str(), wt.base_branch.as_deref()?))?;
+            (parent.id != wt.id).then(|| build_stack_info(wt, parent))
+        })
+        .collect()
+}
+
+fn build_stack_info(wt: &Worktree, parent: &Worktree) -> StackInfo {
+    let state = match parent.status {
+        WorktreeStatus::Merged | WorktreeStatus::Promoted => StackState::ParentMerged,
+        _ => {
+            let parent_ref = format!("refs/heads/{}", parent.branch);
+            match WorktreeManager::is_ancestor(Path::new(&wt.path), &parent_ref) {
+                Ok(false) => StackState::ParentUpdated,
+                // A missing checkout or ref is not something a restack can fix.
+                Ok(true) | Err(_) => StackState::UpToDate,
+            }
+        }
+    };
+    StackInfo {
+        worktree_id: wt.id.clone(),
+        parent_id: parent.id.clone(),
+        parent_slug: parent.slug.clone(),
+        parent_branch: parent.branch.clone(),
+        state,
+    }
+}
+
+/// Order worktrees so each stacked worktree follows its parent, returning
+/// `(index into worktrees, depth)` pairs. Roots keep their input order.
+pub fn stack_order(worktrees: &[Worktree]) -> Vec<(usize, usize)> {
+    let by_branch: HashMap<(&str, &str), usize> = worktrees
+        .iter()
+        .enumerate()
+        .map(|(i, w)| ((w.repo_id.as_str(), w.branch.as_str()), i))
+        .collect();
+    let parent_of = |i: usize| -> Option<usize> {
+        let w = &worktrees[i];
+        let p = *by_branch.get(&(w.repo_id.as_str(), w.base_branch.as_deref()?))?;
+        (p != i).then_some(p)
+    };
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); worktrees.len()];
+    let mut roots = Vec::new();
+    for i in 0..worktrees.len() {
+        match parent_of(i) {
+            Some(p) => children[p].push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut out = Vec::with_capacity(worktrees.len());
+    let mut visited = vec![false; worktrees.len()];
+    let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|i| (i, 0)).collect();
+    while let Some((i, depth)) = stack.pop() {
+        if std::mem::replace(&mut visited[i], true) {
+            continue;
+        }
+        out.push((i, depth));
+        stack.extend(children[i].iter().rev().map(|&c| (c, depth + 1)));
+    }
+    // Branch cycles (a → b → a) have no root; emit them flat at the end.
+    out.extend(
+        (0..worktrees.len())
+            .filter(|&i| !visited[i])
+            .map(|i| (i, 0)),
+    );
+    out
+}
+
+pub(super) fn ensure_clean(wt_path: &Path) -> Result<()> {
+    let out =
+        check_output(git_in(wt_path).args(["status", "--porcelain", "--untracked-files=no"]))?;
+    if !String::from_utf8_lossy(&out.stdout).trim().is_empty() {
+        return Err(ConductorError::InvalidInput(
+            "Worktree has uncommitted changes. Stash or commit them before rebasing.".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Drop a merged parent's commits from the child's history and replay the
+/// child onto the parent's base. Falls back to a plain rebase when the
+/// parent branch is already gone.
+fn rebase_off_merged_parent(
+    wt_path: &Path,
+    new_base: &str,
+    parent_branch: &str,
+    slug: &str,
+    keep_conflicts: bool,
+) -> Result<String> {
+    if let Err(e) = check_output(git_in(wt_path).args(["fetch", "origin", new_base])) {
+        tracing::warn!(branch = new_base, "git fetch failed before restack: {e}");
+    }
+    let remote_ref = format!("refs/remotes/origin/{new_base}");
+    let onto = if crate::command::output(git_in(wt_path).args([
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &remote_ref,
+    ]))
+    .is_ok_and(|o| o.status.success())
+    {
+        remote_ref
+    } else {
+        format!("refs/heads/{new_base}")
+    };
+    let path = wt_path.to_string_lossy();
+    if branch_exists(&path, parent_branch) {
+        let upstream = format!("refs/heads/{parent_branch}");
+        rebase_or_stop(
+            wt_path,
+            &["rebase", "--onto", &onto, &upstream],
+            slug,
+            keep_conflicts,
+        )?;
+    } else {
+        rebase_or_stop(wt_path, &["rebase", &onto], slug, keep_conflicts)?;
+    }
+    Ok(new_base.to_string())
+}
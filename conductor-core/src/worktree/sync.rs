@@ -0,0 +1,276 @@
+//! Bringing a worktree branch up to date with its base branch.
+//!
+//! Unlike a restack, which follows a stacked worktree's parent, a sync pulls
+//! in whatever landed on the worktree's base since it branched off: origin's
+//! copy of the base, or the parent's local branch for a stacked worktree.
+//! Conflicts are reported as a [`SyncResult`] listing the conflicting files;
+//! the rebase or merge is aborted so the worktree is left as it was.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::git::{check_output, git_in};
+
+use super::conflicts::unmerged_paths;
+use super::manager::WorktreeManager;
+use super::stack::ensure_clean;
+
+/// How a sync brings in the base branch's new commits.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStrategy {
+    /// Replay the worktree's commits on top of the base.
+    #[default]
+    Rebase,
+    /// Merge the base into the worktree branch.
+    Merge,
+}
+
+impl std::fmt::Display for SyncStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+        })
+    }
+}
+
+impl std::str::FromStr for SyncStrategy {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "rebase" => Ok(Self::Rebase),
+            "merge" => Ok(Self::Merge),
+            _ => Err(format!(
+                "unknown sync strategy '{s}' (expected rebase or merge)"
+            )),
+        }
+    }
+}
+
+/// What a sync did.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SyncStatus {
+    /// The base had nothing the worktree branch lacked.
+    UpToDate,
+    /// The branch now contains the base's new commits.
+    Synced {
+        /// Base commits brought in.
+        commits: u32,
+    },
+    /// The rebase or merge conflicted and was aborted.
+    Conflicts {
+        /// Files that conflicted, relative to the worktree root.
+        files: Vec<String>,
+    },
+}
+
+/// Result of [`WorktreeManager::sync_with_base`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncResult {
+    pub slug: String,
+    /// The ref synced with, e.g. `origin/main`.
+    pub onto: String,
+    pub strategy: SyncStrategy,
+    #[serde(flatten)]
+    pub status: SyncStatus,
+}
+
+impl std::fmt::Display for SyncResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.status {
+            SyncStatus::UpToDate => write!(f, "{} is up to date with {}", self.slug, self.onto),
+            SyncStatus::Synced { commits } => {
+                let plural = if *commits == 1 { "" } else { "s" };
+                match self.strategy {
+                    SyncStrategy::Rebase => write!(f, "Rebased {} onto {}", self.slug, self.onto)?,
+                    SyncStrategy::Merge => write!(f, "Merged {} into {}", self.onto, self.slug)?,
+                }
+                write!(f, " ({commits} new commit{plural})")
+            }
+            SyncStatus::Conflicts { files } => write!(
+                f,
+                "{} of {} onto {} conflicts in {}; nothing was changed",
+                self.strategy,
+                self.slug,
+                self.onto,
+                files.join(", ")
+            ),
+        }
+    }
+}
+
+impl WorktreeManager<'_> {
+    /// Fetch origin, then rebase or merge the worktree branch onto the latest
+    /// base branch. The worktree must be clean. Conflicts are returned as
+    /// [`SyncStatus::Conflicts`] with the rebase or merge aborted; other git
+    /// failures are errors.
+    pub fn sync_with_base(
+        &self,
+        repo_slug: &str,
+        name: &str,
+        strategy: SyncStrategy,
+    ) -> Result<SyncResult> {
+        let (repo, wt) = self.get_active_worktree(repo_slug, name)?;
+        let wt_path = Path::new(&wt.path);
+        ensure_clean(wt_path)?;
+
+        let base = wt.effective_base(&repo.default_branch).to_string();
+        // A stacked worktree follows its parent's local branch, which is
+        // usually ahead of whatever the parent last pushed.
+        let onto = if self.stack_parent(&wt)?.is_some() {
+            base.clone()
+        } else {
+            remote_or_local(wt_path, &base)
+        };
+
+        let commits = count_commits(wt_path, &format!("HEAD..{onto}"))?;
+        let status = if commits == 0 {
+            SyncStatus::UpToDate
+        } else {
+            run_sync(wt_path, strategy, &onto)?.unwrap_or(SyncStatus::Synced { commits })
+        };
+        Ok(SyncResult {
+            slug: wt.slug,
+            onto,
+            strategy,
+            status,
+        })
+    }
+}
+
+/// `origin/<base>` after fetching it, or the local `<base>` when the fetch
+/// fails or origin has no such branch.
+fn remote_or_local(wt_path: &Path, base: &str) -> String {
+    if let Err(e) = check_output(git_in(wt_path).args(["fetch", "origin", base])) {
+        tracing::warn!(branch = base, "git fetch failed before sync: {e}");
+    }
+    let remote = format!("origin/{base}");
+    let has_remote = crate::command::output(git_in(wt_path).args([
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("refs/remotes/{remote}"),
+    ]))
+    .is_ok_and(|o| o.status.success());
+    if has_remote {
+        remote
+    } else {
+        base.to_string()
+    }
+}
+
+fn count_commits(wt_path: &Path, range: &str) -> Result<u32> {
+    let out = check_output(git_in(wt_path).args(["rev-list", "--count", range]))?;
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(0))
+}
+
+/// Rebase or merge onto `onto`. Returns `Some(Conflicts)` when it stopped on
+/// conflicts (after aborting), `None` when it succeeded.
+fn run_sync(wt_path: &Path, strategy: SyncStrategy, onto: &str) -> Result<Option<SyncStatus>> {
+    let args: &[&str] = match strategy {
+        SyncStrategy::Rebase => &["rebase", onto],
+        SyncStrategy::Merge => &["merge", "--no-edit", onto],
+    };
+    let Err(e) = check_output(git_in(wt_path).args(args)) else {
+        return Ok(None);
+    };
+    let files = unmerged_paths(wt_path).unwrap_or_default();
+    let _ = crate::command::output(git_in(wt_path).args([&strategy.to_string(), "--abort"]));
+    if files.is_empty() {
+        return Err(e);
+    }
+    Ok(Some(SyncStatus::Conflicts { files }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = git_in(dir).args(args).output().unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    }
+
+    /// A repo on `main` with a `feat` branch checked out in `wt` that changed
+    /// `file`, after which main moved on by one commit writing `path`.
+    fn setup(path: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "t@example.com"]);
+        git(&repo, &["config", "user.name", "t"]);
+        std::fs::write(repo.join("file"), "base\n").unwrap();
+        git(&repo, &["add", "file"]);
+        git(&repo, &["commit", "-qm", "base"]);
+
+        let wt = dir.path().join("wt");
+        git(
+            &repo,
+            &["worktree", "add", "-q", "-b", "feat", wt.to_str().unwrap()],
+        );
+        std::fs::write(wt.join("file"), "feat\n").unwrap();
+        git(&wt, &["commit", "-qam", "feat"]);
+
+        std::fs::write(repo.join(path), "main\n").unwrap();
+        git(&repo, &["add", "-A"]);
+        git(&repo, &["commit", "-qm", "main moves"]);
+        (dir, wt)
+    }
+
+    #[test]
+    fn run_sync_rebases_and_reports_conflicts() {
+        let (_dir, wt) = setup("other");
+        assert_eq!(count_commits(&wt, "HEAD..main").unwrap(), 1);
+        assert_eq!(run_sync(&wt, SyncStrategy::Rebase, "main").unwrap(), None);
+        assert_eq!(count_commits(&wt, "HEAD..main").unwrap(), 0);
+
+        let (_dir, wt) = setup("file");
+        let head = check_output(git_in(&wt).args(["rev-parse", "HEAD"])).unwrap();
+        for strategy in [SyncStrategy::Rebase, SyncStrategy::Merge] {
+            assert_eq!(
+                run_sync(&wt, strategy, "main").unwrap(),
+                Some(SyncStatus::Conflicts {
+                    files: vec!["file".into()]
+                }),
+                "{strategy}"
+            );
+            // Aborted: back where it started, nothing in progress.
+            let after = check_output(git_in(&wt).args(["rev-parse", "HEAD"])).unwrap();
+            assert_eq!(after.stdout, head.stdout);
+            assert!(super::super::conflict_state(&wt).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn sync_result_describes_outcome() {
+        let result = |strategy, status| SyncResult {
+            slug: "feat".into(),
+            onto: "origin/main".into(),
+            strategy,
+            status,
+        };
+        assert_eq!(
+            result(SyncStrategy::Rebase, SyncStatus::Synced { commits: 2 }).to_string(),
+            "Rebased feat onto origin/main (2 new commits)"
+        );
+        assert_eq!(
+            result(SyncStrategy::Merge, SyncStatus::Synced { commits: 1 }).to_string(),
+            "Merged origin/main into feat (1 new commit)"
+        );
+        assert_eq!(
+            result(SyncStrategy::Rebase, SyncStatus::UpToDate).to_string(),
+            "feat is up to date with origin/main"
+        );
+    }
+}
@@ -23,6 +23,17 @@ fn git(args: &[&str], dir: &std::path::Path) {
     );
 }
 
+/// Helper: run a git command in a directory and return its trimmed stdout.
+fn git_output(args: &[&str], dir: &std::path::Path) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(output.status.success(), "git {} failed", args.join(" "));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 /// Create a bare "remote" repo and a local clone that tracks it.
 /// Returns (tmp_dir, remote_path, local_path). TempDir must be kept alive.
 fn setup_repo_with_remote() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
@@ -122,7 +133,7 @@ fn test_resolve_base_branch_falls_back_to_detection() {
 }
 
 #[test]
-fn test_ensure_base_up_to_date_clean_fast_forward() {
+fn test_ensure_base_up_to_date_starts_from_origin_without_touching_checkout() {
     let (_tmp, remote, local) = setup_repo_with_remote();
 
     // Simulate a new commit on remote by cloning elsewhere and pushing
@@ -133,30 +144,32 @@ fn test_ensure_base_up_to_date_clean_fast_forward() {
     git(&["commit", "-m", "remote commit"], &other);
     git(&["push", "origin", "main"], &other);
 
-    // Local is now behind origin/main
-    let warnings =
-        git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, false).unwrap();
-    assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+    // Local is now behind origin/main, with main checked out
+    let head_before = git_output(&["rev-parse", "HEAD"], &local);
+    let start = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true).unwrap();
+    assert!(
+        start.warnings.is_empty(),
+        "unexpected warnings: {:?}",
+        start.warnings
+    );
+    assert_eq!(start.branch, "main");
+    assert_eq!(start.start_point, "refs/remotes/origin/main");
 
-    // Verify local main now has the new file
-    assert!(local.join("new_file.txt").exists());
+    // The main checkout is left exactly as it was
+    assert_eq!(git_output(&["rev-parse", "HEAD"], &local), head_before);
+    assert!(!local.join("new_file.txt").exists());
 }
 
 #[test]
 fn test_ensure_base_up_to_date_dirty_working_tree() {
     let (_tmp, _, local) = setup_repo_with_remote();
 
-    // Make the working tree dirty by modifying a tracked file (untracked files are
-    // intentionally ignored — see `check_main_health`).
+    // A modified tracked file does not matter: the new branch starts from a ref.
     fs::write(local.join("README.md"), "modified").unwrap();
 
-    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, false);
-    assert!(result.is_err());
-    let err = result.unwrap_err().to_string();
-    assert!(
-        err.contains("uncommitted changes"),
-        "unexpected error: {err}"
-    );
+    let start = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true)
+        .expect("a dirty main checkout must not block the base");
+    assert_eq!(start.start_point, "refs/remotes/origin/main");
 }
 
 #[test]
@@ -168,7 +181,7 @@ fn test_ensure_base_up_to_date_allows_untracked_files() {
     fs::create_dir_all(local.join("untracked_dir")).unwrap();
     fs::write(local.join("untracked_dir").join("file"), "x").unwrap();
 
-    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, false);
+    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true);
     assert!(
         result.is_ok(),
         "untracked files should not block worktree creation; got: {:?}",
@@ -193,8 +206,9 @@ fn test_ensure_base_up_to_date_diverged_branch() {
     git(&["commit", "-m", "local diverge"], &local);
 
     // Now ensure_base_up_to_date should warn about divergence
-    let warnings =
-        git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, false).unwrap();
+    let warnings = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true)
+        .unwrap()
+        .warnings;
     assert!(
         warnings.iter().any(|w| w.contains("diverged")),
         "expected divergence warning, got: {:?}",
@@ -287,37 +301,16 @@ fn test_check_main_health_commits_behind_positive() {
 }
 
 #[test]
-fn test_ensure_base_up_to_date_force_dirty_skips_check() {
+fn test_has_uncommitted_changes_ignores_untracked_files() {
     let (_tmp, _, local) = setup_repo_with_remote();
+    let path = local.to_str().unwrap();
+    assert!(!git_helpers::has_uncommitted_changes(path));
 
-    // Make the working tree dirty via a tracked-file modification.
-    fs::write(local.join("README.md"), "modified").unwrap();
-
-    // With force_dirty=true, the dirty check is skipped — should succeed
-    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true, false);
-    assert!(
-        result.is_ok(),
-        "force_dirty=true should skip dirty check; got: {:?}",
-        result.err()
-    );
-}
-
-#[test]
-fn test_ensure_base_up_to_date_skips_status_when_pre_verified_clean() {
-    let (_tmp, _, local) = setup_repo_with_remote();
+    fs::write(local.join("untracked.txt"), "untracked").unwrap();
+    assert!(!git_helpers::has_uncommitted_changes(path));
 
-    // Make the working tree dirty via a tracked-file modification — would normally
-    // cause an error.
     fs::write(local.join("README.md"), "modified").unwrap();
-
-    // With pre_verified_clean=true, the git status check is skipped
-    // (the fetch may fail too since there's no network, but that's a soft warning)
-    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, true);
-    assert!(
-        result.is_ok(),
-        "pre_verified_clean=true should skip dirty check; got: {:?}",
-        result.err()
-    );
+    assert!(git_helpers::has_uncommitted_changes(path));
 }
 
 #[test]
@@ -393,8 +386,9 @@ fn test_ensure_base_up_to_date_detached_head() {
     // Detach HEAD in local
     git(&["checkout", "--detach", "HEAD"], &local);
 
-    let warnings =
-        git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", false, false).unwrap();
+    let warnings = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "main", true)
+        .unwrap()
+        .warnings;
     // Should succeed (fast-forward refs/heads/main) with no warnings
     assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
 
@@ -417,6 +411,52 @@ fn test_worktree_status_is_done() {
     assert!(!WorktreeStatus::Active.is_done());
     assert!(WorktreeStatus::Merged.is_done());
     assert!(WorktreeStatus::Abandoned.is_done());
+    assert!(WorktreeStatus::Promoted.is_done());
+    assert!(!WorktreeStatus::NeedsReview.is_done());
+    assert!(!WorktreeStatus::Parked.is_done());
+}
+
+#[test]
+fn test_worktree_status_round_trips_all_variants() {
+    for status in WorktreeStatus::ALL {
+        assert_eq!(status.as_str().parse::<WorktreeStatus>().unwrap(), status);
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, format!("\"{}\"", status.as_str()));
+        assert_eq!(status.is_live(), WorktreeStatus::LIVE.contains(&status));
+    }
+    let expected: Vec<String> = WorktreeStatus::LIVE
+        .iter()
+        .map(|s| format!("'{}'", s.as_str()))
+        .collect();
+    assert_eq!(
+        super::LIVE_STATUSES_SQL,
+        format!("({})", expected.join(", "))
+    );
+}
+
+#[test]
+fn test_parked_and_needs_review_worktrees_are_listed_as_active() {
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    conn.execute_batch(
+        "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at) VALUES
+           ('wt1', 'r1', 'feat-a', 'feat/a', '/tmp/ws/feat-a', 'parked', '2024-01-01T00:00:00Z'),
+           ('wt2', 'r1', 'feat-b', 'feat/b', '/tmp/ws/feat-b', 'needs_review', '2024-01-02T00:00:00Z'),
+           ('wt3', 'r1', 'feat-c', 'feat/c', '/tmp/ws/feat-c', 'promoted', '2024-01-03T00:00:00Z')",
+    )
+    .unwrap();
+
+    let mgr = WorktreeManager::new(&conn, &config);
+    let live = mgr.list_by_repo_id("r1", true).unwrap();
+    let ids: Vec<&str> = live.iter().map(|w| w.id.as_str()).collect();
+    assert!(ids.contains(&"wt1") && ids.contains(&"wt2"), "got {ids:?}");
+    assert!(!ids.contains(&"wt3"), "promoted must not be live: {ids:?}");
+    assert!(live.iter().all(|w| w.is_active()));
+
+    mgr.update_status("wt1", WorktreeStatus::Promoted).unwrap();
+    let promoted = mgr.get_by_id("wt1").unwrap();
+    assert!(promoted.completed_at.is_some());
+    assert!(!promoted.is_active());
 }
 
 #[test]
@@ -825,6 +865,50 @@ fn test_create_clone_fails_with_bad_remote() {
     }
 }
 
+#[test]
+fn test_create_blocked_when_workspace_quota_exceeded() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+
+    let conn = crate::test_helpers::setup_db();
+    let mut config = Config::default();
+    config.general.workspace_root = tmp.path().to_path_buf();
+    // 1 KiB quota for this repo only.
+    config
+        .quota
+        .repos
+        .insert("quotarepo".into(), 1.0 / (1024.0 * 1024.0));
+
+    let workspace = tmp.path().join("workspaces/quotarepo");
+    let stray = workspace.join("leftover");
+    fs::create_dir_all(&stray).unwrap();
+    fs::write(stray.join("blob"), vec![0u8; 2048]).unwrap();
+
+    let repo_mgr = crate::repo::RepoManager::new(&conn, &config);
+    repo_mgr
+        .register(
+            "quotarepo",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(workspace.to_str().unwrap()),
+        )
+        .unwrap();
+
+    let mgr = WorktreeManager::new(&conn, &config);
+    let err = mgr
+        .create("quotarepo", "feat-over-quota", Default::default())
+        .unwrap_err();
+    match &err {
+        ConductorError::WorkspaceQuotaExceeded { hint, .. } => {
+            assert!(
+                hint.contains("leftover"),
+                "hint should name stray dir: {hint}"
+            );
+        }
+        other => panic!("expected WorkspaceQuotaExceeded, got: {other:?}"),
+    }
+    assert!(!workspace.join("feat-over-quota").exists());
+}
+
 #[test]
 fn test_reap_stale_worktrees_handles_abandoned() {
     let conn = crate::test_helpers::setup_db();
@@ -987,15 +1071,19 @@ fn test_fetch_pr_branch_fails_without_github_remote() {
     let result = git_helpers::fetch_pr_branch(local.to_str().unwrap(), 999);
     let err = result.unwrap_err();
     assert!(
-        matches!(err, ConductorError::GhCli(_)),
-        "expected GhCli error, got: {err:?}"
+        matches!(
+            err,
+            ConductorError::GhCli(_) | ConductorError::ToolNotInstalled { .. }
+        ),
+        "expected GhCli or ToolNotInstalled error, got: {err:?}"
     );
 }
 
 #[test]
 fn test_create_from_pr_propagates_fetch_error() {
     // Verify that create() with from_pr = Some(n) takes the from_pr branch,
-    // calls fetch_pr_branch, and propagates the error when gh is unavailable.
+    // calls fetch_pr_branch, and propagates the error when gh fails or is
+    // not installed.
     let (_tmp, _, local) = setup_repo_with_remote();
     let local_str = local.to_str().unwrap().to_string();
 
@@ -1021,8 +1109,11 @@ fn test_create_from_pr_propagates_fetch_error() {
     // fetch_pr_branch will fail because the local repo has no GitHub remote
     let err = result.unwrap_err();
     assert!(
-        matches!(err, ConductorError::GhCli(_)),
-        "expected GhCli error, got: {err:?}"
+        matches!(
+            err,
+            ConductorError::GhCli(_) | ConductorError::ToolNotInstalled { .. }
+        ),
+        "expected GhCli or ToolNotInstalled error, got: {err:?}"
     );
 }
 
@@ -1183,6 +1274,9 @@ fn make_worktree_with_base(repo_id: &str, base_branch: Option<&str>) -> Worktree
         completed_at: None,
         model: None,
         base_branch: base_branch.map(String::from),
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }
 }
 
@@ -1606,6 +1700,46 @@ fn test_label_prefix_first_match_wins() {
 // WorktreeManager::create prefix normalization tests
 // -----------------------------------------------------------------------
 
+#[test]
+fn test_create_reports_progress_stages_in_order() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let mut config = Config::default();
+    config.general.workspace_root = tmp.path().to_path_buf();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "myrepo",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/myrepo").to_str().unwrap()),
+        )
+        .unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    WorktreeManager::new(&conn, &config)
+        .create(
+            "myrepo",
+            "feat-progress",
+            WorktreeCreateOptions {
+                progress: Some(CreateProgress::new(move |stage| {
+                    let _ = tx.send(stage);
+                })),
+                ..Default::default()
+            },
+        )
+        .expect("create should succeed");
+    let stages: Vec<WorktreeCreateStage> = rx.try_iter().collect();
+    assert_eq!(
+        stages,
+        vec![
+            WorktreeCreateStage::Fetching,
+            WorktreeCreateStage::Branching,
+            WorktreeCreateStage::CheckingOut,
+            WorktreeCreateStage::InstallingDeps,
+            WorktreeCreateStage::Finalizing,
+        ]
+    );
+}
+
 #[test]
 fn test_create_chore_prefix_produces_correct_branch() {
     let (tmp, remote, local) = setup_repo_with_remote();
@@ -2168,7 +2302,7 @@ fn test_delete_by_id_for_repo_happy_path() {
     let config = Config::default();
     let mgr = WorktreeManager::new(&conn, &config);
     // w1 belongs to r1 — delete should succeed
-    let wt = mgr.delete_by_id_for_repo("w1", "r1").unwrap();
+    let wt = mgr.delete_by_id_for_repo("w1", "r1", None).unwrap();
     assert_eq!(wt.id, "w1");
     // Confirm it is no longer active
     let status: String = conn
@@ -2179,6 +2313,103 @@ fn test_delete_by_id_for_repo_happy_path() {
     assert_ne!(status, "active");
 }
 
+#[test]
+fn test_delete_records_abandon_reason() {
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let wt = mgr
+        .delete_by_id("w1", Some(AbandonReason::Superseded))
+        .unwrap();
+    assert_eq!(wt.status, WorktreeStatus::Abandoned);
+    assert_eq!(wt.abandon_reason, Some(AbandonReason::Superseded));
+    assert_eq!(wt.status_label(), "abandoned (superseded)");
+
+    let stored = mgr.get_by_id("w1").unwrap();
+    assert_eq!(stored.abandon_reason, Some(AbandonReason::Superseded));
+}
+
+#[test]
+fn test_resolve_base_applies_label_rules() {
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    let repo_dir = TempDir::new().unwrap();
+    fs::create_dir_all(repo_dir.path().join(".conductor")).unwrap();
+    fs::write(
+        repo_dir.path().join(".conductor/config.toml"),
+        "[[base_branch_rules]]\nlabel = \"hotfix\"\nbranch = \"release/1.x\"\n",
+    )
+    .unwrap();
+    conn.execute(
+        "UPDATE repos SET local_path = ?1 WHERE id = 'r1'",
+        [repo_dir.path().to_str().unwrap()],
+    )
+    .unwrap();
+    let syncer = crate::tickets::TicketSyncer::new(&conn);
+    let mut hotfix = crate::test_helpers::make_ticket("1", "Crash on start");
+    hotfix.labels = vec!["bug".into(), "Hotfix".into()];
+    let plain = crate::test_helpers::make_ticket("2", "New setting");
+    syncer.upsert_tickets("r1", &[hotfix, plain]).unwrap();
+    let hotfix_id = syncer.get_by_source_id("r1", "1").unwrap().id;
+    let plain_id = syncer.get_by_source_id("r1", "2").unwrap().id;
+
+    let mgr = WorktreeManager::new(&conn, &config);
+    let base = mgr.resolve_base("test-repo", Some(&hotfix_id)).unwrap();
+    assert_eq!(base.branch, "release/1.x");
+    assert_eq!(
+        base.source,
+        BaseBranchSource::LabelRule {
+            label: "hotfix".into()
+        }
+    );
+    assert_eq!(base.to_string(), "release/1.x (label 'hotfix')");
+
+    let base = mgr.resolve_base("test-repo", Some(&plain_id)).unwrap();
+    assert_eq!(base.source, BaseBranchSource::RepoDefault);
+    assert_eq!(base.branch, "main");
+    assert_eq!(
+        mgr.resolve_base("test-repo", None).unwrap().source,
+        BaseBranchSource::RepoDefault
+    );
+}
+
+#[test]
+fn test_outcomes_counts_abandoned_by_reason() {
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    for (id, status, reason) in [
+        ("w2", "merged", None),
+        ("w3", "abandoned", Some("blocked")),
+        ("w4", "abandoned", Some("blocked")),
+        ("w5", "abandoned", None),
+        ("w6", "promoted", None),
+    ] {
+        crate::test_helpers::insert_test_worktree(&conn, id, "r1", id, &format!("/tmp/ws/{id}"));
+        conn.execute(
+            "UPDATE worktrees SET status = ?1, abandon_reason = ?2 WHERE id = ?3",
+            rusqlite::params![status, reason, id],
+        )
+        .unwrap();
+    }
+    let mgr = WorktreeManager::new(&conn, &config);
+
+    let outcomes = mgr.outcomes(Some("r1")).unwrap();
+    assert_eq!(
+        (outcomes.merged, outcomes.promoted, outcomes.abandoned),
+        (1, 1, 3)
+    );
+    assert_eq!(outcomes.unspecified, 1);
+    assert_eq!(outcomes.by_reason.len(), AbandonReason::ALL.len());
+    let blocked = outcomes
+        .by_reason
+        .iter()
+        .find(|c| c.reason == AbandonReason::Blocked)
+        .unwrap();
+    assert_eq!(blocked.count, 2);
+
+    assert_eq!(mgr.outcomes(Some("other")).unwrap().abandoned, 0);
+}
+
 #[test]
 fn test_delete_by_id_for_repo_cross_repo_isolation() {
     let conn = crate::test_helpers::setup_db();
@@ -2192,7 +2423,7 @@ fn test_delete_by_id_for_repo_cross_repo_isolation() {
     .unwrap();
     let mgr = WorktreeManager::new(&conn, &config);
     // w1 belongs to r1 — deleting it against r2 must return WorktreeNotFound
-    let err = mgr.delete_by_id_for_repo("w1", "r2").unwrap_err();
+    let err = mgr.delete_by_id_for_repo("w1", "r2", None).unwrap_err();
     assert!(
         matches!(err, ConductorError::WorktreeNotFound { .. }),
         "expected WorktreeNotFound for cross-repo delete, got: {err:?}"
@@ -2422,15 +2653,14 @@ fn test_resolve_and_update_base_prefix_fallback() {
         local.to_str().unwrap(),
         Some("user-auth"),
         "main",
-        false,
-        false,
+        true,
     );
     assert!(
         result.is_ok(),
         "resolve_and_update_base should succeed: {:?}",
         result.err()
     );
-    let (resolved_branch, _warnings) = result.unwrap();
+    let resolved_branch = result.unwrap().branch;
     assert_eq!(
         resolved_branch, "feat/user-auth",
         "should resolve to feat/ prefixed branch"
@@ -2447,8 +2677,7 @@ fn test_resolve_and_update_base_no_prefix_fallback_when_already_prefixed() {
         local.to_str().unwrap(),
         Some("feat/nonexistent"),
         "main",
-        false,
-        false,
+        true,
     );
     assert!(
         result.is_err(),
@@ -2477,8 +2706,7 @@ fn test_ensure_base_up_to_date_creates_local_tracking_branch_from_remote_only()
 
     // Call ensure_base_up_to_date on the remote-only branch
     // This should create a local tracking branch
-    let result =
-        git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "new-feature", false, false);
+    let result = git_helpers::ensure_base_up_to_date(local.to_str().unwrap(), "new-feature", true);
     assert!(
         result.is_ok(),
         "ensure_base_up_to_date should succeed: {:?}",
@@ -2812,3 +3040,797 @@ fn test_adopt_rejects_detached_head() {
         "error should mention detached HEAD: {msg}"
     );
 }
+
+// -----------------------------------------------------------------------
+// Stacked worktree tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_stack_order_places_children_under_parents() {
+    let mut parent = make_worktree_with_base("repo1", Some("main"));
+    parent.id = "p".into();
+    parent.branch = "feat/parent".into();
+    let mut child = make_worktree_with_base("repo1", Some("feat/parent"));
+    child.id = "c".into();
+    child.branch = "feat/child".into();
+    let mut grandchild = make_worktree_with_base("repo1", Some("feat/child"));
+    grandchild.id = "g".into();
+    grandchild.branch = "feat/grandchild".into();
+    let mut other = make_worktree_with_base("repo1", None);
+    other.id = "o".into();
+    other.branch = "feat/other".into();
+
+    // Children listed before their parents still come out nested.
+    let worktrees = vec![grandchild, other, child, parent];
+    let order: Vec<(&str, usize)> = stack_order(&worktrees)
+        .into_iter()
+        .map(|(i, depth)| (worktrees[i].id.as_str(), depth))
+        .collect();
+    assert_eq!(order, [("o", 0), ("p", 0), ("c", 1), ("g", 2)]);
+}
+
+fn commit_file(dir: &Path, name: &str, contents: &str) {
+    fs::write(dir.join(name), contents).unwrap();
+    git(&["add", name], dir);
+    git(&["commit", "-m", name], dir);
+}
+
+#[test]
+fn test_stacked_worktree_restack_and_parent_merge() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "stacked",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/stacked").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+
+    let (parent, _) = mgr.create("stacked", "parent", Default::default()).unwrap();
+    commit_file(Path::new(&parent.path), "parent.txt", "one");
+    let base = mgr.stack_base("stacked", &parent.slug).unwrap();
+    assert_eq!(base, parent.branch);
+    let (child, _) = mgr
+        .create(
+            "stacked",
+            "child",
+            WorktreeCreateOptions {
+                from_branch: Some(base),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    commit_file(Path::new(&child.path), "child.txt", "child");
+
+    let info = mgr.stack_info(&child).unwrap().expect("child is stacked");
+    assert_eq!(info.parent_id, parent.id);
+    assert_eq!(info.state, StackState::UpToDate);
+    assert_eq!(mgr.stack_children(&parent).unwrap().len(), 1);
+
+    // The parent moves on: the child is flagged, and a restack picks it up.
+    commit_file(Path::new(&parent.path), "parent2.txt", "two");
+    let infos = mgr.stack_infos_for_repo(&parent.repo_id).unwrap();
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].state, StackState::ParentUpdated);
+    mgr.restack("stacked", &child.slug, Default::default())
+        .unwrap();
+    assert!(Path::new(&child.path).join("parent2.txt").exists());
+    assert_eq!(
+        mgr.stack_info(&child).unwrap().unwrap().state,
+        StackState::UpToDate
+    );
+
+    // Squash-merge the parent into main on the remote, then clean it up: the
+    // child is replayed onto main without the parent's original commits.
+    let (_tmp2, other) = setup_second_clone(&remote);
+    git(
+        &[
+            "push",
+            "origin",
+            &format!("{}:{}", parent.branch, parent.branch),
+        ],
+        &local,
+    );
+    git(&["fetch", "origin"], &other);
+    git(
+        &["merge", "--squash", &format!("origin/{}", parent.branch)],
+        &other,
+    );
+    git(&["commit", "-m", "squashed parent"], &other);
+    git(&["push", "origin", "main"], &other);
+
+    let parent_branch = parent.branch.clone();
+    mgr.cleanup_merged_worktrees_with_merge_check(
+        Some("stacked"),
+        move |_, branches| {
+            branches
+                .iter()
+                .filter(|b| **b == parent_branch)
+                .map(|b| (b.clone(), String::new()))
+                .collect()
+        },
+        |_, _| Ok(()),
+    )
+    .unwrap();
+
+    let child = mgr.get_by_id(&child.id).unwrap();
+    assert_eq!(child.base_branch.as_deref(), Some("main"));
+    assert!(mgr.stack_info(&child).unwrap().is_none());
+    let log = Command::new("git")
+        .args(["log", "--format=%s", "origin/main..HEAD"])
+        .current_dir(&child.path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "child.txt");
+}
+
+#[test]
+fn test_diff_covers_commits_and_uncommitted_changes() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "diffed",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/diffed").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, _) = mgr.create("diffed", "feat", Default::default()).unwrap();
+    let path = Path::new(&wt.path);
+
+    assert_eq!(mgr.diff("diffed", &wt.slug).unwrap(), "");
+
+    commit_file(path, "committed.txt", "from a commit\n");
+    fs::write(
+        path.join("committed.txt"),
+        "from a commit\nand the working tree\n",
+    )
+    .unwrap();
+    let diff = mgr.diff("diffed", &wt.slug).unwrap();
+    assert!(diff.contains("+from a commit"), "{diff}");
+    assert!(diff.contains("+and the working tree"), "{diff}");
+}
+
+#[test]
+fn test_create_with_dirty_main_checkout_warns_instead_of_failing() {
+    let (_tmp, _, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    conn.execute(
+        "UPDATE repos SET local_path = :local_path, workspace_dir = :workspace_dir WHERE id = 'r1'",
+        named_params! {
+            ":local_path": local.to_str().unwrap(),
+            ":workspace_dir": local.parent().unwrap().join("ws").to_str().unwrap(),
+        },
+    )
+    .unwrap();
+    fs::write(local.join("README.md"), "work in progress").unwrap();
+
+    let config = Config::default();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, warnings) = mgr
+        .create("test-repo", "dirty", WorktreeCreateOptions::default())
+        .expect("a dirty main checkout must not block creation");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w == git_helpers::DIRTY_CHECKOUT_WARNING),
+        "{warnings:?}"
+    );
+    assert_ne!(
+        fs::read_to_string(Path::new(&wt.path).join("README.md")).unwrap(),
+        "work in progress"
+    );
+
+    // Once the caller has shown the user the changes, no warning repeats them.
+    let (_, warnings) = mgr
+        .create(
+            "test-repo",
+            "dirty-confirmed",
+            WorktreeCreateOptions {
+                force_dirty: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(
+        !warnings
+            .iter()
+            .any(|w| w == git_helpers::DIRTY_CHECKOUT_WARNING),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_create_branches_from_origin_and_leaves_main_checkout_alone() {
+    let (_tmp, remote, local) = setup_repo_with_remote();
+    let (_tmp2, other) = setup_second_clone(&remote);
+    fs::write(other.join("README.md"), "remote change").unwrap();
+    git(&["commit", "-am", "remote change"], &other);
+    git(&["push", "origin", "main"], &other);
+
+    let conn = crate::test_helpers::setup_db();
+    conn.execute(
+        "UPDATE repos SET local_path = :local_path, workspace_dir = :workspace_dir WHERE id = 'r1'",
+        named_params! {
+            ":local_path": local.to_str().unwrap(),
+            ":workspace_dir": local.parent().unwrap().join("ws").to_str().unwrap(),
+        },
+    )
+    .unwrap();
+
+    // Someone is working in the main checkout, on the base branch.
+    let head_before = git_output(&["rev-parse", "HEAD"], &local);
+    fs::write(local.join("README.md"), "work in progress").unwrap();
+
+    let config = Config::default();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, _) = mgr
+        .create(
+            "test-repo",
+            "fresh",
+            WorktreeCreateOptions {
+                force_dirty: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // The worktree has origin's latest; the main checkout is untouched.
+    assert_eq!(
+        fs::read_to_string(Path::new(&wt.path).join("README.md")).unwrap(),
+        "remote change"
+    );
+    assert_eq!(git_output(&["rev-parse", "HEAD"], &local), head_before);
+    assert_eq!(
+        git_output(&["rev-parse", "--abbrev-ref", "HEAD"], &local),
+        "main"
+    );
+    assert_eq!(
+        fs::read_to_string(local.join("README.md")).unwrap(),
+        "work in progress"
+    );
+    // The new branch doesn't track the base; push tracking points at itself.
+    assert_eq!(
+        git_output(&["config", &format!("branch.{}.merge", wt.branch)], &local),
+        format!("refs/heads/{}", wt.branch)
+    );
+}
+
+#[test]
+fn test_create_offline_skips_fetch_and_reports_base_tip() {
+    let (_tmp, remote, local) = setup_repo_with_remote();
+    let (_tmp2, other) = setup_second_clone(&remote);
+    fs::write(other.join("README.md"), "remote change").unwrap();
+    git(&["commit", "-am", "remote change"], &other);
+    git(&["push", "origin", "main"], &other);
+
+    let conn = crate::test_helpers::setup_db();
+    conn.execute(
+        "UPDATE repos SET local_path = :local_path, workspace_dir = :workspace_dir WHERE id = 'r1'",
+        named_params! {
+            ":local_path": local.to_str().unwrap(),
+            ":workspace_dir": local.parent().unwrap().join("ws").to_str().unwrap(),
+        },
+    )
+    .unwrap();
+
+    let mut config = Config::default();
+    config.general.base_fetch = crate::config::BaseFetch::Never;
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, warnings) = mgr
+        .create(
+            "test-repo",
+            "offline",
+            WorktreeCreateOptions {
+                offline: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.starts_with("skipped fetch from origin (--offline)")),
+        "{warnings:?}"
+    );
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.starts_with("base 'main' is at ") && w.contains("\"initial\"")),
+        "{warnings:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(Path::new(&wt.path).join("README.md")).unwrap(),
+        "initial"
+    );
+
+    // The config toggle alone also skips the fetch.
+    let (_, warnings) = mgr
+        .create("test-repo", "never", WorktreeCreateOptions::default())
+        .unwrap();
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.contains("(base_fetch = \"never\")")),
+        "{warnings:?}"
+    );
+}
+
+/// Register `myrepo` backed by a real clone, for slug reservation tests.
+fn setup_reservation_repo() -> (TempDir, Connection, Config) {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let mut config = Config::default();
+    config.general.workspace_root = tmp.path().to_path_buf();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "myrepo",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/myrepo").to_str().unwrap()),
+        )
+        .unwrap();
+    (tmp, conn, config)
+}
+
+fn reserve(conn: &Connection, slug: &str, reserved_at: &str) {
+    conn.execute(
+        "INSERT INTO worktree_slug_reservations (repo_id, slug, reserved_at) \
+         SELECT id, :slug, :reserved_at FROM repos WHERE slug = 'myrepo'",
+        named_params! { ":slug": slug, ":reserved_at": reserved_at },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_create_takes_suffix_when_slug_is_reserved() {
+    let (_tmp, conn, config) = setup_reservation_repo();
+    reserve(&conn, "feat-login", &chrono::Utc::now().to_rfc3339());
+
+    let (wt, warnings) = WorktreeManager::new(&conn, &config)
+        .create("myrepo", "login", Default::default())
+        .expect("create should succeed");
+    assert_eq!(wt.slug, "feat-login-2");
+    assert_eq!(wt.branch, "feat/login-2");
+    assert!(warnings[0].contains("using 'feat-login-2'"), "{warnings:?}");
+
+    // Only the other create's reservation is left; ours was released.
+    let held: Vec<String> = crate::db::query_collect(
+        &conn,
+        "SELECT slug FROM worktree_slug_reservations",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap();
+    assert_eq!(held, ["feat-login"]);
+}
+
+#[test]
+fn test_create_ignores_stale_reservation() {
+    let (_tmp, conn, config) = setup_reservation_repo();
+    reserve(&conn, "feat-login", "2020-01-01T00:00:00Z");
+
+    let (wt, _) = WorktreeManager::new(&conn, &config)
+        .create("myrepo", "login", Default::default())
+        .expect("create should succeed");
+    assert_eq!(wt.slug, "feat-login");
+}
+
+#[test]
+fn test_create_live_slug_is_still_a_conflict() {
+    let (_tmp, conn, config) = setup_reservation_repo();
+    let mgr = WorktreeManager::new(&conn, &config);
+    mgr.create("myrepo", "login", Default::default()).unwrap();
+
+    let err = mgr.check_slug_available("myrepo", "login").unwrap_err();
+    assert!(matches!(err, ConductorError::WorktreeAlreadyExists { .. }));
+    let err = mgr
+        .create("myrepo", "login", Default::default())
+        .unwrap_err();
+    assert!(
+        matches!(err, ConductorError::WorktreeAlreadyExists { ref slug } if slug == "feat-login"),
+        "got: {err:?}"
+    );
+    mgr.check_slug_available("myrepo", "signup").unwrap();
+}
+
+#[test]
+fn test_reserve_slug_without_suffix_reports_in_progress() {
+    let (_tmp, conn, config) = setup_reservation_repo();
+    reserve(&conn, "feat-login", &chrono::Utc::now().to_rfc3339());
+    let repo = crate::repo::RepoManager::new(&conn, &config)
+        .get_by_slug("myrepo")
+        .unwrap();
+
+    let err = WorktreeManager::new(&conn, &config)
+        .reserve_slug(&repo, "feat-login", "feat/login", false)
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, ConductorError::WorktreeCreationInProgress { .. }),
+        "got: {err:?}"
+    );
+}
+
+// -----------------------------------------------------------------------
+// derive_worktree_slug tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_derive_slug_normal() {
+    let slug = manager::derive_worktree_slug("123", "Add login flow", &[]);
+    assert_eq!(slug, "feat-123-add-login-flow");
+}
+
+#[test]
+fn test_derive_slug_special_chars() {
+    let slug = manager::derive_worktree_slug("42", "Fix: null-ptr crash!!", &[]);
+    assert_eq!(slug, "feat-42-fix-null-ptr-crash");
+}
+
+#[test]
+fn test_derive_slug_consecutive_dashes() {
+    let slug = manager::derive_worktree_slug("7", "hello---world   test", &[]);
+    assert_eq!(slug, "feat-7-hello-world-test");
+}
+
+#[test]
+fn test_derive_slug_long_title_truncation() {
+    let long_title = "a".repeat(100);
+    let slug = manager::derive_worktree_slug("99", &long_title, &[]);
+    // Total should be ≤ 40 chars
+    assert!(slug.len() <= 40, "slug too long: {} chars", slug.len());
+    assert!(slug.starts_with("feat-99-"));
+}
+
+#[test]
+fn test_derive_slug_empty_title() {
+    assert_eq!(manager::derive_worktree_slug("123", "", &[]), "feat-123");
+}
+
+#[test]
+fn test_derive_slug_all_special_chars() {
+    assert_eq!(
+        manager::derive_worktree_slug("42", "!!@@##", &[]),
+        "feat-42"
+    );
+}
+
+#[test]
+fn test_derive_slug_whitespace_only() {
+    assert_eq!(manager::derive_worktree_slug("7", "   ", &[]), "feat-7");
+}
+
+#[test]
+fn test_derive_slug_bug_label_produces_fix_prefix() {
+    let slug = manager::derive_worktree_slug("42", "null ptr crash", &["bug".to_string()]);
+    assert!(slug.starts_with("fix-42-"), "got: {slug}");
+}
+
+#[test]
+fn test_derive_slug_chore_label_produces_chore_prefix() {
+    let slug = manager::derive_worktree_slug("7", "clean up deps", &["chore".to_string()]);
+    assert!(slug.starts_with("chore-7-"), "got: {slug}");
+}
+
+#[test]
+fn test_derive_slug_unknown_label_falls_back_to_feat() {
+    let slug = manager::derive_worktree_slug("5", "some work", &["wontfix".to_string()]);
+    assert!(slug.starts_with("feat-5-"), "got: {slug}");
+}
+
+#[test]
+fn test_derive_slug_with_labels_total_length_within_40() {
+    let long_title = "a".repeat(100);
+    let slug = manager::derive_worktree_slug("99", &long_title, &["refactor".to_string()]);
+    assert!(
+        slug.len() <= 40,
+        "slug too long: {} chars: {slug}",
+        slug.len()
+    );
+}
+
+#[test]
+fn test_batch_push_all_and_delete_merged_report_per_worktree() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "batch",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/batch").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (landed, _) = mgr.create("batch", "landed", Default::default()).unwrap();
+    let (wip, _) = mgr.create("batch", "wip", Default::default()).unwrap();
+    let (fresh, _) = mgr.create("batch", "fresh", Default::default()).unwrap();
+    // Commit timestamps have second precision: make sure the commits below
+    // are dated after the worktrees were created.
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    commit_file(Path::new(&landed.path), "landed.txt", "done");
+    commit_file(Path::new(&wip.path), "wip.txt", "wip");
+
+    let pushed = mgr.push_all(Some("batch"), PushOptions::default()).unwrap();
+    let outcome = |items: &[BatchItem], slug: &str| {
+        items
+            .iter()
+            .find(|i| i.slug == slug)
+            .map(|i| i.outcome.clone())
+    };
+    assert!(matches!(
+        outcome(&pushed, &landed.slug),
+        Some(BatchOutcome::Done(_))
+    ));
+    assert!(matches!(
+        outcome(&pushed, &wip.slug),
+        Some(BatchOutcome::Done(_))
+    ));
+    assert_eq!(
+        outcome(&pushed, &fresh.slug),
+        Some(BatchOutcome::Skipped("no commits to push".into()))
+    );
+    assert!(pushed.iter().all(|i| !i.is_failed()));
+    let again = mgr.push_all(Some("batch"), PushOptions::default()).unwrap();
+    assert_eq!(
+        outcome(&again, &wip.slug),
+        Some(BatchOutcome::Skipped("up to date".into()))
+    );
+
+    git(&["merge", "--ff-only", &landed.branch], &local);
+    let deleted = mgr.delete_merged(Some("batch")).unwrap();
+    assert_eq!(deleted.len(), 1, "{deleted:?}");
+    assert_eq!(deleted[0].slug, landed.slug);
+    assert_eq!(
+        mgr.get_by_id(&landed.id).unwrap().status,
+        WorktreeStatus::Merged
+    );
+    assert!(mgr.get_by_id(&wip.id).unwrap().is_active());
+    assert!(mgr.get_by_id(&fresh.id).unwrap().is_active());
+}
+
+#[test]
+fn test_check_missing_paths_flags_and_restore_clears() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "gone",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/gone").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, _) = mgr.create("gone", "vanish", Default::default()).unwrap();
+    commit_file(Path::new(&wt.path), "kept.txt", "kept");
+    // setup_db's fixture worktree points at a path that never existed.
+    let flagged_ids = |flagged: Vec<Worktree>| -> Vec<String> {
+        flagged
+            .into_iter()
+            .map(|w| w.id)
+            .filter(|id| id != "w1")
+            .collect()
+    };
+    assert!(flagged_ids(mgr.check_missing_paths().unwrap()).is_empty());
+
+    fs::remove_dir_all(&wt.path).unwrap();
+    assert_eq!(
+        flagged_ids(mgr.check_missing_paths().unwrap()),
+        vec![wt.id.clone()]
+    );
+    // Only newly missing worktrees are reported.
+    assert!(mgr.check_missing_paths().unwrap().is_empty());
+    let repo_id = mgr.get_by_id(&wt.id).unwrap().repo_id;
+    assert_eq!(mgr.list_missing(Some(&repo_id)).unwrap().len(), 1);
+
+    let restored = mgr.restore("gone", &wt.slug).unwrap();
+    assert!(restored.missing_since.is_none());
+    assert!(Path::new(&wt.path).join("kept.txt").exists());
+    assert!(mgr.get_by_id(&wt.id).unwrap().missing_since.is_none());
+    assert!(matches!(
+        mgr.restore("gone", &wt.slug),
+        Err(ConductorError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn agent_pr_description_uses_ticket_title_and_closes_github_issues() {
+    let mut ticket = crate::tickets::Ticket {
+        id: "t1".into(),
+        repo_id: "r1".into(),
+        source_type: "github".into(),
+        source_id: "42".into(),
+        title: " Fix login redirect ".into(),
+        body: String::new(),
+        state: "open".into(),
+        labels: String::new(),
+        assignee: None,
+        priority: None,
+        url: "https://github.com/o/r/issues/42".into(),
+        synced_at: "2024-01-01T00:00:00Z".into(),
+        raw_json: "{}".into(),
+        workflow: None,
+        agent_map: None,
+        sprint: None,
+        sprint_current: false,
+    };
+
+    let (title, body) =
+        manager::agent_pr_description(Some(&ticket), "do it", "Fixed the redirect.\n", &[]);
+    assert_eq!(title, "Fix login redirect");
+    assert_eq!(body, "Fixed the redirect.\n\nCloses #42");
+
+    ticket.source_type = "jira".into();
+    ticket.source_id = "ENG-7".into();
+    ticket.url = "https://x.atlassian.net/browse/ENG-7".into();
+    let (_, body) = manager::agent_pr_description(Some(&ticket), "do it", "Done.", &[]);
+    assert_eq!(
+        body,
+        "Done.\n\nTicket: [ENG-7](https://x.atlassian.net/browse/ENG-7)"
+    );
+
+    let (title, body) =
+        manager::agent_pr_description(None, "Add a --json flag\nmore detail", "Done.", &[]);
+    assert_eq!(title, "Add a --json flag");
+    assert_eq!(body, "Done.");
+
+    let todos = [crate::worktree::BranchTodo {
+        file: "src/cli.rs".into(),
+        line: 12,
+        marker: "TODO".into(),
+        text: "document the schema".into(),
+    }];
+    let (_, body) = manager::agent_pr_description(None, "p", "Done.", &todos);
+    assert_eq!(
+        body,
+        "Done.\n\n### Unfinished items\n\n- [ ] `src/cli.rs:12` TODO document the schema"
+    );
+}
+
+#[test]
+fn test_scan_todos_stores_comments_added_on_the_branch() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "todos",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/todos").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, _) = mgr
+        .create("todos", "todo-scan", Default::default())
+        .unwrap();
+    commit_file(
+        Path::new(&wt.path),
+        "lib.rs",
+        "fn a() {}\n// FIXME: handle overflow\n",
+    );
+    // Uncommitted edits to tracked files count too.
+    fs::write(
+        Path::new(&wt.path).join("lib.rs"),
+        "fn a() {}\n// FIXME: handle overflow\n# TODO\n",
+    )
+    .unwrap();
+
+    let todos = mgr.scan_todos("todos", &wt.slug).unwrap();
+    let found: Vec<String> = todos.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        found,
+        vec!["lib.rs:2 FIXME handle overflow", "lib.rs:3 TODO"]
+    );
+    assert_eq!(mgr.todos(&wt.id).unwrap(), todos);
+
+    fs::write(Path::new(&wt.path).join("lib.rs"), "fn a() {}\n").unwrap();
+    assert!(mgr.scan_todos_by_id(&wt.id).unwrap().is_empty());
+    assert!(mgr.todos(&wt.id).unwrap().is_empty());
+}
+
+#[test]
+fn agent_commit_message_uses_ticket_title_and_references_it() {
+    let ticket = crate::tickets::Ticket {
+        id: "t1".into(),
+        repo_id: "r1".into(),
+        source_type: "github".into(),
+        source_id: "42".into(),
+        title: "Fix login redirect".into(),
+        body: String::new(),
+        state: "open".into(),
+        labels: String::new(),
+        assignee: None,
+        priority: None,
+        url: "https://github.com/o/r/issues/42".into(),
+        synced_at: "2024-01-01T00:00:00Z".into(),
+        raw_json: "{}".into(),
+        workflow: None,
+        agent_map: None,
+        sprint: None,
+        sprint_current: false,
+    };
+    assert_eq!(
+        commit::agent_commit_message(Some(&ticket), "do it", "Fixed the redirect.\n"),
+        "Fix login redirect\n\nFixed the redirect.\n\nRefs #42"
+    );
+    assert_eq!(
+        commit::agent_commit_message(None, "Add a --json flag\nmore detail", " "),
+        "Add a --json flag"
+    );
+}
+
+#[test]
+fn test_commit_stages_and_commits_worktree_changes() {
+    let (tmp, remote, local) = setup_repo_with_remote();
+    let conn = crate::test_helpers::setup_db();
+    let config = Config::default();
+    crate::repo::RepoManager::new(&conn, &config)
+        .register(
+            "commits",
+            local.to_str().unwrap(),
+            remote.to_str().unwrap(),
+            Some(tmp.path().join("workspaces/commits").to_str().unwrap()),
+        )
+        .unwrap();
+    let mgr = WorktreeManager::new(&conn, &config);
+    let (wt, _) = mgr
+        .create("commits", "agent-work", Default::default())
+        .unwrap();
+    let wt_path = Path::new(&wt.path);
+    fs::write(wt_path.join("README.md"), "edited").unwrap();
+    fs::write(wt_path.join("new.txt"), "new").unwrap();
+
+    // Nothing is staged yet, so only `all` has anything to commit.
+    assert!(matches!(
+        mgr.commit("commits", &wt.slug, Some("Edit"), false),
+        Err(ConductorError::InvalidInput(_))
+    ));
+    let summary = mgr
+        .commit(
+            "commits",
+            &wt.slug,
+            Some("Edit the readme\n\nDetails."),
+            true,
+        )
+        .unwrap();
+    assert_eq!(summary.subject, "Edit the readme");
+    assert_eq!(summary.files, vec!["README.md", "new.txt"]);
+    assert_eq!(git_output(&["rev-parse", "HEAD"], wt_path), summary.sha);
+    // The worktree's own database stays out of the commit.
+    assert_eq!(
+        git_output(&["status", "--porcelain"], wt_path),
+        "?? .conductor.db"
+    );
+
+    assert!(matches!(
+        mgr.commit("commits", &wt.slug, Some("Again"), true),
+        Err(ConductorError::InvalidInput(_))
+    ));
+    // Without a message one is generated, which needs an agent result.
+    fs::write(wt_path.join("new.txt"), "newer").unwrap();
+    assert!(matches!(
+        mgr.commit("commits", &wt.slug, None, true),
+        Err(ConductorError::InvalidInput(_))
+    ));
+    // Pushing says what was left behind.
+    let pushed = mgr
+        .push("commits", &wt.slug, PushOptions::default())
+        .unwrap();
+    assert!(
+        pushed.ends_with("(1 uncommitted change not included; commit them first)"),
+        "{pushed}"
+    );
+}
@@ -0,0 +1,232 @@
+//! TODO and FIXME comments a worktree's branch introduced.
+//!
+//! A scan reads the branch diff ([`WorktreeManager::diff`]) and keeps the
+//! marker comments on added lines, so pre-existing TODOs in touched files
+//! are not reported. The latest scan is stored per worktree; it runs after
+//! each agent run and on demand.
+
+use chrono::Utc;
+use rusqlite::named_params;
+use serde::{Deserialize, Serialize};
+
+use crate::db::query_collect;
+use crate::error::Result;
+use crate::repo::RepoManager;
+
+use super::manager::WorktreeManager;
+
+/// Comment markers reported by a scan.
+const MARKERS: &[&str] = &["TODO", "FIXME"];
+
+/// Comment leaders one of which must precede a marker, so identifiers and
+/// string literals mentioning TODO are not reported.
+const COMMENT_LEADERS: &[&str] = &["//", "#", "/*", "*", "--", "<!--"];
+
+/// A TODO or FIXME comment added on a worktree's branch.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchTodo {
+    /// Path relative to the worktree root.
+    pub file: String,
+    /// 1-based line number in the worktree's copy of the file.
+    pub line: u32,
+    /// `TODO` or `FIXME`.
+    pub marker: String,
+    /// The comment text after the marker; may be empty.
+    pub text: String,
+}
+
+impl std::fmt::Display for BranchTodo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{} {}", self.file, self.line, self.marker)?;
+        if !self.text.is_empty() {
+            write!(f, " {}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// TODO and FIXME comments on the added lines of unified diff `diff`, in
+/// diff order.
+pub fn parse_branch_todos(diff: &str) -> Vec<BranchTodo> {
+    let mut todos = Vec::new();
+    let mut file: Option<String> = None;
+    let mut in_hunk = false;
+    let mut new_line = 0u32;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            file = None;
+            in_hunk = false;
+        } else if !in_hunk {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                // Deleted files have `+++ /dev/null` and no added lines.
+                file = path.strip_prefix("b/").map(str::to_string);
+            } else if let Some(start) = hunk_new_start(line) {
+                in_hunk = true;
+                new_line = start;
+            }
+        } else if let Some(start) = hunk_new_start(line) {
+            new_line = start;
+        } else if let Some(added) = line.strip_prefix('+') {
+            if let (Some(file), Some((marker, text))) = (&file, find_marker(added)) {
+                todos.push(BranchTodo {
+                    file: file.clone(),
+                    line: new_line,
+                    marker: marker.to_string(),
+                    text,
+                });
+            }
+            new_line += 1;
+        } else if line.starts_with(' ') {
+            new_line += 1;
+        }
+    }
+    todos
+}
+
+/// The new-file start line of hunk header `@@ -a,b +c,d @@`.
+fn hunk_new_start(line: &str) -> Option<u32> {
+    let rest = line.strip_prefix("@@ ")?;
+    let new_range = rest.split_whitespace().find(|r| r.starts_with('+'))?;
+    new_range[1..].split(',').next()?.parse().ok()
+}
+
+/// The marker in a comment on `line` and the text following it.
+fn find_marker(line: &str) -> Option<(&'static str, String)> {
+    let (pos, marker) = MARKERS
+        .iter()
+        .filter_map(|m| {
+            line.match_indices(m)
+                .find(|(i, _)| is_word_at(line, *i, m.len()))
+                .map(|(i, _)| (i, *m))
+        })
+        .min_by_key(|(i, _)| *i)?;
+    if !COMMENT_LEADERS.iter().any(|l| line[..pos].contains(l)) {
+        return None;
+    }
+    let mut rest = line[pos + marker.len()..].trim_start();
+    // `TODO(alice): ...`
+    if rest.starts_with('(') {
+        if let Some(end) = rest.find(')') {
+            rest = &rest[end + 1..];
+        }
+    }
+    let text = rest
+        .trim_end()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim()
+        .trim_start_matches([':', '-'])
+        .trim();
+    Some((marker, text.to_string()))
+}
+
+fn is_word_at(line: &str, start: usize, len: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !line[..start].chars().next_back().is_some_and(is_word)
+        && !line[start + len..].chars().next().is_some_and(is_word)
+}
+
+impl WorktreeManager<'_> {
+    /// Scan the branch of worktree `name` for TODO and FIXME comments it
+    /// added and store the result in place of the previous scan.
+    pub fn scan_todos(&self, repo_slug: &str, name: &str) -> Result<Vec<BranchTodo>> {
+        let (_, worktree) = self.get_active_worktree(repo_slug, name)?;
+        let todos = parse_branch_todos(&self.diff(repo_slug, name)?);
+        let found_at = Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM worktree_todos WHERE worktree_id = :id",
+            named_params! { ":id": worktree.id },
+        )?;
+        for todo in &todos {
+            tx.execute(
+                "INSERT INTO worktree_todos (worktree_id, file, line, marker, text, found_at) \
+                 VALUES (:worktree_id, :file, :line, :marker, :text, :found_at)",
+                named_params! {
+                    ":worktree_id": worktree.id,
+                    ":file": todo.file,
+                    ":line": todo.line,
+                    ":marker": todo.marker,
+                    ":text": todo.text,
+                    ":found_at": found_at,
+                },
+            )?;
+        }
+        tx.commit()?;
+        Ok(todos)
+    }
+
+    /// [`Self::scan_todos`] for a worktree looked up by ID.
+    pub fn scan_todos_by_id(&self, worktree_id: &str) -> Result<Vec<BranchTodo>> {
+        let worktree = self.get_by_id(worktree_id)?;
+        let repo = RepoManager::new(self.conn, self.config).get_by_id(&worktree.repo_id)?;
+        self.scan_todos(&repo.slug, &worktree.slug)
+    }
+
+    /// TODOs stored by the last scan of worktree `worktree_id`; empty when
+    /// it was never scanned.
+    pub fn todos(&self, worktree_id: &str) -> Result<Vec<BranchTodo>> {
+        query_collect(
+            self.conn,
+            "SELECT file, line, marker, text FROM worktree_todos \
+             WHERE worktree_id = :id ORDER BY rowid",
+            named_params! { ":id": worktree_id },
+            |row| {
+                Ok(BranchTodo {
+                    file: row.get("file")?,
+                    line: row.get("line")?,
+                    marker: row.get("marker")?,
+                    text: row.get("text")?,
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_branch_todos_reports_added_comments_with_new_line_numbers() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,5 @@ fn main() {
+ let a = 1;
+-// TODO: removed, not reported
++// TODO(alice): handle errors
+ let b = 2;
++let TODO_LIST = \"TODO\";
+@@ -40,2 +42,3 @@ fn other() {
+ x();
++    /* FIXME - leaks the handle */
+diff --git a/old.py b/old.py
+deleted file mode 100644
+--- a/old.py
++++ /dev/null
+@@ -1 +0,0 @@
+-# TODO gone
+diff --git a/notes.md b/notes.md
+new file mode 100644
+--- /dev/null
++++ b/notes.md
+@@ -0,0 +1,2 @@
++++ counter
++<!-- TODO -->
+";
+        let todos = parse_branch_todos(diff);
+        let lines: Vec<String> = todos.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "src/lib.rs:11 TODO handle errors",
+                "src/lib.rs:43 FIXME leaks the handle",
+                "notes.md:2 TODO",
+            ]
+        );
+    }
+}
@@ -2,28 +2,58 @@ use crate::agent::AgentRunStatus;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Typed representation of the three worktree lifecycle states stored in the DB.
+/// Typed representation of the worktree lifecycle states stored in the DB.
+///
+/// `Active`, `NeedsReview` and `Parked` are *live*: the checkout still exists on
+/// disk. `Merged`, `Abandoned` and `Promoted` are terminal.
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum WorktreeStatus {
     Active,
+    /// Work is finished and waiting on a human review before merge.
+    NeedsReview,
+    /// Set aside without being abandoned; the checkout is kept.
+    Parked,
     Merged,
     Abandoned,
+    /// Folded into another branch (e.g. a feature branch) rather than merged directly.
+    Promoted,
 }
 
 impl WorktreeStatus {
-    /// Returns `true` for terminal states (`Merged` or `Abandoned`).
+    /// Every status, live states first.
+    pub const ALL: [WorktreeStatus; 6] = [
+        Self::Active,
+        Self::NeedsReview,
+        Self::Parked,
+        Self::Merged,
+        Self::Abandoned,
+        Self::Promoted,
+    ];
+
+    /// Statuses whose checkout still exists on disk.
+    pub const LIVE: [WorktreeStatus; 3] = [Self::Active, Self::NeedsReview, Self::Parked];
+
+    /// Returns `true` for terminal states (`Merged`, `Abandoned` or `Promoted`).
     pub fn is_done(&self) -> bool {
-        matches!(self, Self::Merged | Self::Abandoned)
+        matches!(self, Self::Merged | Self::Abandoned | Self::Promoted)
+    }
+
+    /// Returns `true` for states whose checkout still exists on disk.
+    pub fn is_live(&self) -> bool {
+        !self.is_done()
     }
 
-    /// Return the canonical lowercase string stored in the database.
+    /// Return the canonical snake_case string stored in the database.
     pub fn as_str(&self) -> &'static str {
         match self {
             WorktreeStatus::Active => "active",
+            WorktreeStatus::NeedsReview => "needs_review",
+            WorktreeStatus::Parked => "parked",
             WorktreeStatus::Merged => "merged",
             WorktreeStatus::Abandoned => "abandoned",
+            WorktreeStatus::Promoted => "promoted",
         }
     }
 }
@@ -37,17 +67,110 @@ impl fmt::Display for WorktreeStatus {
 impl std::str::FromStr for WorktreeStatus {
     type Err = String;
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "active" => Ok(Self::Active),
-            "merged" => Ok(Self::Merged),
-            "abandoned" => Ok(Self::Abandoned),
-            _ => Err(format!("unknown WorktreeStatus: {s}")),
-        }
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| format!("unknown WorktreeStatus: {s}"))
     }
 }
 
 crate::impl_sql_enum!(WorktreeStatus);
 
+/// Why unmerged work was deleted. Stored on worktrees that end up
+/// [`WorktreeStatus::Abandoned`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbandonReason {
+    /// Replaced by another branch or approach.
+    Superseded,
+    /// The change is no longer wanted.
+    Wontfix,
+    /// Stuck on something outside the worktree.
+    Blocked,
+    /// Exploratory work that was never meant to merge.
+    Experiment,
+}
+
+impl AbandonReason {
+    pub const ALL: [AbandonReason; 4] = [
+        Self::Superseded,
+        Self::Wontfix,
+        Self::Blocked,
+        Self::Experiment,
+    ];
+
+    /// Return the canonical snake_case string stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AbandonReason::Superseded => "superseded",
+            AbandonReason::Wontfix => "wontfix",
+            AbandonReason::Blocked => "blocked",
+            AbandonReason::Experiment => "experiment",
+        }
+    }
+
+    /// One-line explanation for pickers and help text.
+    pub fn description(&self) -> &'static str {
+        match self {
+            AbandonReason::Superseded => "replaced by another branch or approach",
+            AbandonReason::Wontfix => "the change is no longer wanted",
+            AbandonReason::Blocked => "stuck on something outside this work",
+            AbandonReason::Experiment => "exploratory, never meant to merge",
+        }
+    }
+}
+
+impl fmt::Display for AbandonReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AbandonReason {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown abandon reason '{s}' (expected one of: superseded, wontfix, blocked, experiment)"
+                )
+            })
+    }
+}
+
+crate::impl_sql_enum!(AbandonReason);
+
+/// Progress stages reported by [`super::WorktreeManager::create`], in order.
+/// `Cloning` only occurs when the repo has not been cloned locally yet.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeCreateStage {
+    Cloning,
+    Fetching,
+    Branching,
+    CheckingOut,
+    InstallingDeps,
+    Finalizing,
+}
+
+impl WorktreeCreateStage {
+    /// Short human-readable description for progress displays.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cloning => "Cloning repository",
+            Self::Fetching => "Fetching latest changes",
+            Self::Branching => "Creating branch",
+            Self::CheckingOut => "Checking out worktree",
+            Self::InstallingDeps => "Installing dependencies",
+            Self::Finalizing => "Finalizing",
+        }
+    }
+}
+
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Worktree {
@@ -64,11 +187,32 @@ pub struct Worktree {
     pub model: Option<String>,
     /// The branch this worktree was created from. NULL means the repo's default branch.
     pub base_branch: Option<String>,
+    /// Why the worktree was abandoned, when a reason was given.
+    pub abandon_reason: Option<AbandonReason>,
+    /// When the periodic health check found the live worktree's directory
+    /// gone. Cleared once the directory is back.
+    pub missing_since: Option<String>,
+    /// Upstream changes to the linked ticket not yet acknowledged, e.g.
+    /// `reopened, priority P2 → P1`.
+    pub ticket_change: Option<String>,
 }
 
 impl Worktree {
+    /// Returns `true` while the worktree's checkout exists on disk
+    /// (`active`, `needs_review` or `parked`).
     pub fn is_active(&self) -> bool {
-        self.status == WorktreeStatus::Active
+        self.status.is_live()
+    }
+
+    /// Status for display, with the abandon reason when there is one,
+    /// e.g. `abandoned (superseded)`.
+    pub fn status_label(&self) -> String {
+        match self.abandon_reason {
+            Some(reason) if self.status == WorktreeStatus::Abandoned => {
+                format!("{} ({reason})", self.status)
+            }
+            _ => self.status.to_string(),
+        }
     }
 
     /// Returns true if this worktree is a child of the given feature
@@ -96,6 +240,67 @@ pub struct WorktreeWithStatus {
     pub ticket_url: Option<String>,
 }
 
+/// Where a resolved base branch came from; see
+/// [`super::WorktreeManager::resolve_base`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BaseBranchSource {
+    /// A live worktree of a ticket this one depends on.
+    ParentTicket,
+    /// A `[[base_branch_rules]]` entry matching this ticket label.
+    LabelRule { label: String },
+    /// No ticket-specific base applies.
+    RepoDefault,
+}
+
+/// Base branch a new worktree starts from, and why.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedBase {
+    pub branch: String,
+    pub source: BaseBranchSource,
+}
+
+impl ResolvedBase {
+    /// Short explanation of where the branch came from.
+    pub fn reason(&self) -> String {
+        match &self.source {
+            BaseBranchSource::ParentTicket => "parent ticket's worktree".to_string(),
+            BaseBranchSource::LabelRule { label } => format!("label '{label}'"),
+            BaseBranchSource::RepoDefault => "repo default".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ResolvedBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.branch, self.reason())
+    }
+}
+
+/// Number of abandoned worktrees given one reason.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbandonReasonCount {
+    pub reason: AbandonReason,
+    pub count: i64,
+}
+
+/// How finished worktrees ended; see [`super::WorktreeManager::outcomes`].
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeOutcomes {
+    pub merged: i64,
+    pub promoted: i64,
+    pub abandoned: i64,
+    /// Abandoned worktrees per reason, in [`AbandonReason::ALL`] order.
+    /// Reasons with no worktrees are included with a zero count.
+    pub by_reason: Vec<AbandonReasonCount>,
+    /// Abandoned worktrees deleted without a reason.
+    pub unspecified: i64,
+}
+
 pub(super) fn map_worktree_row(row: &rusqlite::Row) -> rusqlite::Result<Worktree> {
     Ok(Worktree {
         id: row.get("id")?,
@@ -109,5 +314,8 @@ pub(super) fn map_worktree_row(row: &rusqlite::Row) -> rusqlite::Result<Worktree
         completed_at: row.get("completed_at")?,
         model: row.get("model")?,
         base_branch: row.get("base_branch")?,
+        abandon_reason: row.get("abandon_reason")?,
+        missing_since: row.get("missing_since")?,
+        ticket_change: row.get("ticket_change")?,
     })
 }
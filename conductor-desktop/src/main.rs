@@ -49,6 +49,8 @@ fn main() {
 
             // Always use the global database — the desktop app manages all
             // repos, so worktree-local DB detection must be bypassed.
+            // Config first: it carries the [database] encryption settings.
+            let config = load_config().expect("Failed to load conductor config");
             let db_path_val = conductor_dir().join("conductor.db");
             let conn = open_database(&db_path_val).unwrap_or_else(|e| {
                 panic!(
@@ -56,7 +58,6 @@ fn main() {
                     db_path_val.display()
                 )
             });
-            let config = load_config().expect("Failed to load conductor config");
             let web_config = conductor_web::config::load_web_config().unwrap_or_else(|e| {
                 tracing::warn!("Failed to load web config, using defaults: {e}");
                 conductor_web::config::WebConfig::default()
@@ -1,14 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use conductor_core::agent::{AgentRun, AgentRunEvent, FeedbackRequest, TicketAgentTotals};
+use conductor_core::agent::{
+    AgentRun, AgentRunEvent, BudgetExceeded, FeedbackRequest, FileDiff, InboxItem, QueuedAgentRun,
+    TicketAgentTotals,
+};
 use conductor_core::github::DiscoveredRepo;
 use conductor_core::repo::Repo;
-use conductor_core::tickets::{Ticket, TicketDependencies, TicketLabel};
+use conductor_core::tickets::QueueTicket;
+use conductor_core::tickets::{
+    Ticket, TicketAttachment, TicketDependencies, TicketLabel, TicketProgress,
+};
+use conductor_core::today::TodayItem;
 use conductor_core::workflow::{
     ConductorWorkflowRun, FanOutItemRow, LiveEstimate, WorkflowDef, WorkflowRunStep,
     WorkflowStepSummary, WorkflowWarning,
 };
-use conductor_core::worktree::Worktree;
+use conductor_core::worktree::{
+    StackInfo, SyncResult, WorkspaceUsage, Worktree, WorktreeGitStatus,
+};
 use crossterm::event::KeyEvent;
 
 /// Payload for the DataRefreshed action (boxed to keep Action enum small).
@@ -43,12 +52,13 @@ pub struct WorkflowDataPayload {
 }
 
 /// Payload for the DataRefreshed action (boxed to keep Action enum small).
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DataRefreshedPayload {
     pub repos: Vec<Repo>,
     pub worktrees: Vec<Worktree>,
     pub tickets: Vec<Ticket>,
     pub ticket_labels: HashMap<String, Vec<TicketLabel>>,
+    pub ticket_attachments: HashMap<String, Vec<TicketAttachment>>,
     pub ticket_dependencies: HashMap<String, TicketDependencies>,
     pub latest_agent_runs: HashMap<String, AgentRun>,
     pub ticket_agent_totals: HashMap<String, TicketAgentTotals>,
@@ -79,6 +89,25 @@ pub struct DataRefreshedPayload {
     pub workflow_run_estimates: HashMap<String, LiveEstimate>,
     /// Cumulative completed token totals per worktree (worktree_id -> (input, output)).
     pub completed_token_totals_by_worktree: HashMap<String, (i64, i64)>,
+    /// repo_id -> workspace disk usage. `None` when this tick skipped the (throttled) scan.
+    pub workspace_usage: Option<HashMap<String, WorkspaceUsage>>,
+    /// worktree_id -> git status. `None` when this tick skipped the (throttled) scan.
+    pub git_statuses: Option<HashMap<String, WorktreeGitStatus>>,
+    /// Agent runs waiting to start, in dispatch order.
+    pub agent_queue: Vec<QueuedAgentRun>,
+    pub watched_worktrees: HashSet<String>,
+    pub watched_tickets: HashSet<String>,
+    /// worktree_id -> stack relationship for worktrees based on another worktree.
+    pub stack_infos: HashMap<String, StackInfo>,
+    /// Finished agent runs not yet reviewed, newest first.
+    pub agent_inbox: Vec<InboxItem>,
+    /// worktree_id -> spend, for worktrees that reached `[budget] worktree_usd`.
+    pub over_budget: HashMap<String, BudgetExceeded>,
+    /// ticket_id -> worktree/agent/PR progress.
+    pub ticket_progress: HashMap<String, TicketProgress>,
+    /// The database was locked by another writer, so this came from a
+    /// read-only connection and background maintenance was skipped.
+    pub read_only: bool,
 }
 
 /// Every user intent or background result flows through this enum.
@@ -99,8 +128,16 @@ pub enum Action {
     // CRUD triggers
     RegisterRepo,
     Create,
+    /// Create the selected ticket's worktree and start an agent on it.
+    CreateWithAgent,
     AdoptWorktree,
+    /// Prompt for an issue or PR URL to create a worktree from.
+    WorktreeFromUrl,
+    /// Resolve a pasted or entered URL to a repo and ticket, then create its worktree.
+    CreateWorktreeFromUrl(String),
     Delete,
+    /// Commit the selected worktree's changes, asking for a message.
+    CommitWorktree,
     #[allow(dead_code)]
     Push,
     #[allow(dead_code)]
@@ -168,9 +205,97 @@ pub enum Action {
     // Agent issue creation toggle (repo-level)
     ToggleAgentIssues,
 
+    // Re-detect the selected repo's toolchain
+    DetectToolchain,
+
+    // Watch/unwatch the selected worktree or ticket
+    ToggleWatch,
+    // Clear the selected worktree's upstream ticket change badge
+    AcknowledgeTicketChange,
+    // Comment on / close the selected ticket in its upstream tracker
+    CommentOnTicket,
+    CloseTicketUpstream,
+
+    // Rebase the selected stacked worktree onto its parent
+    Restack,
+    // Fetch origin and rebase the selected worktree onto its latest base
+    SyncWithBase,
+    // Rescan the selected worktree's branch for TODO/FIXME comments it added
+    ScanBranchTodos,
+
+    // Agent inbox: finished runs not yet reviewed
+    OpenAgentInbox,
+    InboxOpen,
+    InboxMarkReviewed,
+    InboxMarkAllReviewed,
+    InboxViewDiff,
+    InboxPush,
+    InboxCreatePr,
+    InboxRerun,
+    /// Background result: diff for the selected inbox run's worktree.
+    InboxDiffLoaded {
+        title: String,
+        result: Result<String, String>,
+    },
+
+    // Today: everything needing attention across repos
+    OpenToday,
+    RefreshToday,
+    /// Open the selected Today item's URL in the browser.
+    TodayOpenUrl,
+    /// Background result: the Today list.
+    TodayLoaded {
+        result: Result<Vec<TodayItem>, String>,
+    },
+    // My Queue: tickets assigned to or mentioning you across repos
+    OpenMyQueue,
+    RefreshMyQueue,
+    /// Open the selected queue ticket's URL in the browser.
+    MyQueueOpenUrl,
+    /// Background result: the personal ticket queue.
+    MyQueueLoaded {
+        result: Result<Vec<QueueTicket>, String>,
+    },
+
+    // Agent run history for the selected worktree
+    OpenAgentRunDetail,
+    /// Copy the selected run's session ID.
+    AgentRunDetailCopySessionId,
+
+    // Diff of the selected worktree against its base
+    OpenWorktreeDiff,
+    RefreshWorktreeDiff,
+    WorktreeDiffLoaded {
+        worktree_id: String,
+        result: Result<Vec<FileDiff>, String>,
+    },
+
+    // Status message history
+    OpenMessageLog,
+    ClearMessageLog,
+    /// Tickets of the current repo grouped by sprint/milestone.
+    OpenRoadmap,
+    /// Agent cost rollups over time.
+    OpenReports,
+    /// Reports modal: switch between repo, ticket, worktree and day rollups.
+    CycleReportGrouping,
+    /// Reports modal: switch between the 7, 30 and 90 day windows.
+    CycleReportWindow,
+    /// Reports modal: switch to the 90-day activity heat map.
+    OpenActivityHeatmap,
+    /// Activity heat map: switch between repo and worktree rows.
+    CycleActivityGrouping,
+    /// Pending grooming proposals for the current repo.
+    OpenGrooming,
+    AcceptGroomingProposal,
+    DismissGroomingProposal,
+
     // Toggle visibility of closed tickets in all ticket views
     ToggleClosedTickets,
 
+    // Restrict all ticket views to the current sprint / milestone
+    ToggleCurrentSprint,
+
     // Toggle visibility of completed/cancelled workflow runs in the workflow column
     ToggleCompletedRuns,
 
@@ -200,7 +325,13 @@ pub enum Action {
     RepoPromptTextAreaInput(crossterm::event::KeyEvent),
     PromptRepoAgent,
     StopAgent,
+    /// Ask to cancel every running agent run, across all repos.
+    StopAllAgents,
     RestartAgent,
+    /// Launch a read-only agent that reviews the worktree's diff against its base.
+    ReviewDiff,
+    /// Show the latest diff review for the selected worktree.
+    ViewDiffReview,
     #[allow(dead_code)]
     CopyLastCodeBlock,
     ExpandAgentEvent,
@@ -259,11 +390,26 @@ pub enum Action {
     FormToggle,
 
     // Background results
+    ToolsProbed(conductor_core::tools::ToolReport),
+    /// Probe the external tools again (Settings → Tools).
+    RefreshTools,
     PrsRefreshed {
         repo_id: String,
         prs: Vec<conductor_core::github::GithubPr>,
     },
     DataRefreshed(Box<DataRefreshedPayload>),
+    /// A ticket sync is starting for these repos.
+    TicketSyncStarted {
+        repo_slugs: Vec<String>,
+    },
+    /// A repo's issue sources are being synced.
+    TicketSyncRepoStarted {
+        repo_slug: String,
+    },
+    /// All of a repo's sources have reported `TicketSyncComplete` or `TicketSyncFailed`.
+    TicketSyncRepoFinished {
+        repo_slug: String,
+    },
     TicketSyncComplete {
         repo_slug: String,
         count: usize,
@@ -274,6 +420,10 @@ pub enum Action {
     },
     /// Sent after all repos have been processed in a manual one-shot sync.
     TicketSyncDone,
+    /// Something a running `conductor daemon` reported changing.
+    DaemonNotice {
+        message: String,
+    },
     #[allow(dead_code)]
     BackgroundError {
         message: String,
@@ -318,6 +468,18 @@ pub enum Action {
     AgentLaunchComplete {
         result: Result<String, String>,
     },
+    /// The worktree has uncommitted changes; ask before launching.
+    AgentLaunchDirty {
+        launch: Box<crate::state::PendingAgentLaunch>,
+        files: Vec<String>,
+    },
+    /// The worktree or resumed session has spent its budget; ask before launching.
+    AgentLaunchOverBudget {
+        launch: Box<crate::state::PendingAgentLaunch>,
+        message: String,
+    },
+    /// User's answer in the dirty-worktree modal.
+    ResolveDirtyWorktree(conductor_core::agent::DirtyWorktreeChoice),
     // Background result for worktree agent stop
     AgentStopComplete {
         result: Result<String, String>,
@@ -358,9 +520,21 @@ pub enum Action {
     PushComplete {
         result: Result<String, String>,
     },
+    CommitComplete {
+        result: Result<String, String>,
+    },
     PrCreateComplete {
         result: Result<String, String>,
     },
+    RestackComplete {
+        result: Result<String, String>,
+    },
+    SyncWithBaseComplete {
+        result: Result<SyncResult, String>,
+    },
+    ScanBranchTodosComplete {
+        result: Result<usize, String>,
+    },
     WorktreeDeleteComplete {
         wt_slug: String,
         result: Result<String, String>,
@@ -373,6 +547,10 @@ pub enum Action {
         imported: usize,
         errors: Vec<String>,
     },
+    /// Background result: a comment or close written back to a ticket's tracker.
+    TicketWriteBackComplete {
+        result: Result<String, String>,
+    },
     /// Background result: repo model set/cleared via file I/O.
     SetRepoModelComplete {
         slug: String,
@@ -387,11 +565,25 @@ pub enum Action {
         wt_name: String,
         ticket_id: Option<String>,
         items: Vec<crate::state::BranchPickerItem>,
+        /// Where the default entry branches from for this ticket, if not the
+        /// repo default.
+        resolved_base: Option<String>,
     },
     /// Background result: failed to load worktree branches.
     WorktreeBranchesFailed {
         error: String,
     },
+    /// Background result: a worktree URL resolved to its repo and ticket.
+    WorktreeUrlResolved {
+        repo_slug: String,
+        wt_name: String,
+        ticket_id: Option<String>,
+        from_pr: Option<u32>,
+    },
+    /// Background result: a worktree URL could not be resolved.
+    WorktreeUrlFailed {
+        error: String,
+    },
 
     /// Background result: candidate base-branches loaded for BaseBranchPicker.
     BaseBranchesLoaded {
@@ -1,13 +1,24 @@
 use std::time::Duration;
 
+use conductor_core::activity::{activity_heatmap, ActivityGroupBy, DEFAULT_ACTIVITY_DAYS};
+use conductor_core::agent::{AgentManager, CostGroupBy};
+use conductor_core::repo_visibility::Viewer;
+use conductor_core::tickets::{build_roadmap, ProposalStatus, TicketGrooming};
 use conductor_core::workflow::parse_workflow_str;
+use conductor_core::worktree::SyncStatus;
 
 use crate::action::Action;
-use crate::state::{model_picker_total, Modal, View, WorkflowDefFocus};
+use crate::state::{
+    model_picker_total, Modal, Severity, SyncProgress, View, WorkflowDefFocus, ABANDON_REASON_ROWS,
+    SYNC_PANEL_LINGER,
+};
 
 use super::helpers::{collapse_loop_iterations, max_scroll, workflow_parse_warning_message};
 use super::App;
 
+/// Windows the Reports modal cycles through, in days.
+const REPORT_WINDOWS: [i64; 3] = [7, 30, 90];
+
 impl App {
     /// Sync the selected worktree/repo IDs into the shared Arcs read by the background poller,
     /// and clear any now-stale cached events when the selection changes.
@@ -74,13 +85,7 @@ impl App {
                             {
                                 let remote_url = repo.remote_url.clone();
                                 let rid = repo_id.clone();
-                                if let Some(ref tx) = self.bg_tx {
-                                    crate::background::spawn_pr_fetch_once(
-                                        tx.clone(),
-                                        remote_url,
-                                        rid,
-                                    );
-                                }
+                                self.spawn_pr_fetch(remote_url, rid);
                             }
                         }
                     }
@@ -88,6 +93,14 @@ impl App {
                 // Auto-clear status messages after 4 seconds so the context hint
                 // bar is restored without requiring user navigation.
                 self.state.tick_status_message(Duration::from_secs(4));
+                if self
+                    .state
+                    .sync_progress
+                    .as_ref()
+                    .is_some_and(|p| p.is_expired(SYNC_PANEL_LINGER))
+                {
+                    self.state.sync_progress = None;
+                }
                 // Always redraw on tick so elapsed times, spinners, and other
                 // time-sensitive indicators update smoothly (ratatui diffs cells,
                 // so this is cheap).
@@ -193,12 +206,28 @@ impl App {
                 Modal::Input { ref mut value, .. } | Modal::ConfirmByName { ref mut value, .. } => {
                     value.push(c);
                 }
+                Modal::TicketLinkPicker {
+                    ref mut query,
+                    ref mut selected,
+                    ..
+                } => {
+                    query.push(c);
+                    *selected = 0;
+                }
                 _ => {}
             },
             Action::InputBackspace => match self.state.modal {
                 Modal::Input { ref mut value, .. } | Modal::ConfirmByName { ref mut value, .. } => {
                     value.pop();
                 }
+                Modal::TicketLinkPicker {
+                    ref mut query,
+                    ref mut selected,
+                    ..
+                } => {
+                    query.pop();
+                    *selected = 0;
+                }
                 Modal::ModelPicker { ref on_submit, .. } => {
                     // Backspace: clear the model (submit empty value)
                     let on_submit = on_submit.clone();
@@ -237,9 +266,13 @@ impl App {
             // CRUD
             Action::RegisterRepo => self.handle_register_repo(),
             Action::Create => self.handle_create(),
+            Action::CreateWithAgent => self.handle_create_with_agent(),
             Action::AdoptWorktree => self.handle_adopt_worktree(),
+            Action::WorktreeFromUrl => self.handle_worktree_from_url(),
+            Action::CreateWorktreeFromUrl(url) => self.resolve_worktree_url(url),
             Action::Delete => self.handle_delete(),
             Action::ClearConversation => self.handle_clear_conversation(),
+            Action::CommitWorktree => self.handle_commit_worktree(),
             Action::Push => self.handle_push(),
             Action::CreatePr => self.handle_create_pr(),
             Action::SyncTickets => self.handle_sync_tickets(),
@@ -253,6 +286,7 @@ impl App {
                 status,
             } => match status {
                 Err(e) => {
+                    self.state.auto_agent_ticket_id = None;
                     self.state.modal = Modal::Error {
                         message: format!("Main branch health check failed: {e}"),
                     };
@@ -309,10 +343,26 @@ impl App {
                 wt_name,
                 ticket_id,
                 items,
-            } => self.handle_worktree_branches_loaded(repo_slug, wt_name, ticket_id, items),
+                resolved_base,
+            } => self.handle_worktree_branches_loaded(
+                repo_slug,
+                wt_name,
+                ticket_id,
+                items,
+                resolved_base,
+            ),
             Action::WorktreeBranchesFailed { error } => {
                 self.state.modal = crate::state::Modal::Error { message: error };
             }
+            Action::WorktreeUrlResolved {
+                repo_slug,
+                wt_name,
+                ticket_id,
+                from_pr,
+            } => self.create_worktree_or_clone(repo_slug, wt_name, ticket_id, from_pr, None),
+            Action::WorktreeUrlFailed { error } => {
+                self.state.modal = Modal::Error { message: error };
+            }
             Action::SelectListItem(index) => {
                 if let Modal::WorkflowPicker {
                     ref items,
@@ -331,6 +381,12 @@ impl App {
                 {
                     *selected = index;
                     self.handle_template_picker_confirm();
+                } else if let Modal::AbandonReasonPicker {
+                    ref mut selected, ..
+                } = self.state.modal
+                {
+                    *selected = index;
+                    self.handle_abandon_reason_picker_confirm();
                 }
             }
             Action::WorkflowPickerDefsLoaded {
@@ -442,6 +498,68 @@ impl App {
 
             // Agent issue creation toggle
             Action::ToggleAgentIssues => self.handle_toggle_agent_issues(),
+            Action::DetectToolchain => self.handle_detect_toolchain(),
+            Action::ToggleWatch => self.handle_toggle_watch(),
+            Action::AcknowledgeTicketChange => self.handle_acknowledge_ticket_change(),
+            Action::CommentOnTicket => self.handle_comment_on_ticket(),
+            Action::CloseTicketUpstream => self.handle_close_ticket_upstream(),
+            Action::Restack => self.handle_restack(),
+            Action::SyncWithBase => self.handle_sync_with_base(),
+            Action::ScanBranchTodos => self.handle_scan_branch_todos(),
+            Action::OpenAgentInbox => self.handle_open_agent_inbox(),
+            Action::OpenToday => self.handle_open_today(),
+            Action::RefreshToday => self.refresh_today(),
+            Action::TodayOpenUrl => self.handle_today_open_url(),
+            Action::TodayLoaded { result } => self.handle_today_loaded(result),
+            Action::OpenMyQueue => self.handle_open_my_queue(),
+            Action::RefreshMyQueue => self.refresh_my_queue(),
+            Action::MyQueueOpenUrl => self.handle_my_queue_open_url(),
+            Action::MyQueueLoaded { result } => self.handle_my_queue_loaded(result),
+            Action::OpenAgentRunDetail => self.handle_open_agent_run_detail(),
+            Action::AgentRunDetailCopySessionId => self.agent_run_detail_copy_session_id(),
+            Action::OpenWorktreeDiff => self.handle_open_worktree_diff(),
+            Action::RefreshWorktreeDiff => self.refresh_worktree_diff(),
+            Action::WorktreeDiffLoaded {
+                worktree_id,
+                result,
+            } => self.handle_worktree_diff_loaded(worktree_id, result),
+            Action::OpenMessageLog => {
+                self.state.message_log.acknowledge_all();
+                self.state.modal = Modal::MessageLog { selected: 0 };
+            }
+            Action::ClearMessageLog => {
+                self.state.message_log.clear();
+                self.state.modal = Modal::MessageLog { selected: 0 };
+            }
+            Action::InboxOpen => self.handle_inbox_open(),
+            Action::InboxMarkReviewed => self.handle_inbox_mark_reviewed(),
+            Action::InboxMarkAllReviewed => self.handle_inbox_mark_all_reviewed(),
+            Action::InboxViewDiff => self.handle_inbox_view_diff(),
+            Action::InboxPush => self.handle_inbox_push(),
+            Action::InboxCreatePr => self.handle_inbox_create_pr(),
+            Action::InboxRerun => self.handle_inbox_rerun(),
+            Action::InboxDiffLoaded { title, result } => match result {
+                Ok(body) => {
+                    let body = if body.is_empty() {
+                        "No changes.".to_string()
+                    } else {
+                        body
+                    };
+                    let line_count = body.lines().count();
+                    self.state.modal = Modal::EventDetail {
+                        title,
+                        body,
+                        line_count,
+                        scroll_offset: 0,
+                        horizontal_offset: 0,
+                    };
+                }
+                Err(e) => {
+                    self.state.modal = Modal::Error {
+                        message: format!("Failed to load diff: {e}"),
+                    }
+                }
+            },
 
             // Ticket sort cycle (#: default → #↑ → #↓ → default)
             Action::CycleTicketSort => {
@@ -492,6 +610,14 @@ impl App {
                 self.state.detail_ticket_index = 0;
             }
 
+            // Current-sprint ticket filter toggle
+            Action::ToggleCurrentSprint => {
+                self.state.current_sprint_only = !self.state.current_sprint_only;
+                self.state.rebuild_filtered_tickets();
+                self.state.ticket_index = 0;
+                self.state.detail_ticket_index = 0;
+            }
+
             // Workflow completed/cancelled visibility toggle
             Action::ToggleCompletedRuns => {
                 self.state.show_completed_workflow_runs = !self.state.show_completed_workflow_runs;
@@ -565,7 +691,10 @@ impl App {
                     self.handle_stop_agent();
                 }
             }
+            Action::StopAllAgents => self.show_confirm_stop_all(),
             Action::RestartAgent => self.handle_restart_agent(),
+            Action::ReviewDiff => self.handle_review_diff(),
+            Action::ViewDiffReview => self.handle_view_diff_review(),
             Action::SubmitFeedback => {
                 if self.is_repo_agent_context() {
                     self.handle_submit_repo_feedback();
@@ -646,6 +775,23 @@ impl App {
                 | Modal::TemplatePicker {
                     ref mut selected, ..
                 }
+                | Modal::AbandonReasonPicker {
+                    ref mut selected, ..
+                }
+                | Modal::AgentInbox { ref mut selected }
+                | Modal::MessageLog { ref mut selected }
+                | Modal::Roadmap {
+                    ref mut selected, ..
+                }
+                | Modal::Grooming {
+                    ref mut selected, ..
+                }
+                | Modal::Reports {
+                    ref mut selected, ..
+                }
+                | Modal::ActivityHeatmap {
+                    ref mut selected, ..
+                }
                 | Modal::IssueSourceManager {
                     ref mut selected, ..
                 } => {
@@ -724,6 +870,45 @@ impl App {
                 } => {
                     *selected = items.len().saturating_sub(1);
                 }
+                Modal::AbandonReasonPicker {
+                    ref mut selected, ..
+                } => {
+                    *selected = ABANDON_REASON_ROWS - 1;
+                }
+                Modal::AgentInbox { ref mut selected } => {
+                    *selected = self.state.data.agent_inbox.len().saturating_sub(1);
+                }
+                Modal::MessageLog { ref mut selected } => {
+                    *selected = self.state.message_log.len().saturating_sub(1);
+                }
+                Modal::Roadmap {
+                    ref groups,
+                    ref mut selected,
+                    ..
+                } => {
+                    *selected = groups.len().saturating_sub(1);
+                }
+                Modal::Grooming {
+                    ref proposals,
+                    ref mut selected,
+                    ..
+                } => {
+                    *selected = proposals.len().saturating_sub(1);
+                }
+                Modal::Reports {
+                    ref rows,
+                    ref mut selected,
+                    ..
+                } => {
+                    *selected = rows.len().saturating_sub(1);
+                }
+                Modal::ActivityHeatmap {
+                    ref heatmap,
+                    ref mut selected,
+                    ..
+                } => {
+                    *selected = heatmap.rows.len().saturating_sub(1);
+                }
                 Modal::IssueSourceManager {
                     ref sources,
                     ref mut selected,
@@ -980,6 +1165,26 @@ impl App {
             }
 
             // Background results
+            Action::ToolsProbed(report) => {
+                let missing: Vec<String> =
+                    report.missing_required().map(|t| t.name.clone()).collect();
+                let first_probe = self.state.tools.is_none();
+                self.state.tools = Some(report);
+                if !missing.is_empty() {
+                    self.state.status_message = Some(format!(
+                        "Not installed: {} — see Settings → Tools",
+                        missing.join(", ")
+                    ));
+                } else if !first_probe {
+                    self.state.status_message = Some("All required tools found".into());
+                }
+            }
+            Action::RefreshTools => {
+                if let Some(tx) = self.require_bg_tx() {
+                    self.state.status_message = Some("Checking tools…".into());
+                    crate::background::spawn_tool_probe(tx, true);
+                }
+            }
             Action::PrsRefreshed { repo_id, mut prs } => {
                 if self.state.selected_repo_id.as_deref() == Some(&repo_id) {
                     prs.sort_by_key(|pr| {
@@ -1003,6 +1208,7 @@ impl App {
                 self.state.data.worktrees = payload.worktrees;
                 self.state.data.tickets = payload.tickets;
                 self.state.data.ticket_labels = payload.ticket_labels;
+                self.state.data.ticket_attachments = payload.ticket_attachments;
                 self.state.data.ticket_dependencies = payload.ticket_dependencies;
                 self.state.data.latest_agent_runs = payload.latest_agent_runs;
                 self.state.data.ticket_agent_totals = payload.ticket_agent_totals;
@@ -1024,24 +1230,82 @@ impl App {
                 self.state.data.workflow_run_estimates = payload.workflow_run_estimates;
                 self.state.data.completed_token_totals_by_worktree =
                     payload.completed_token_totals_by_worktree;
+                self.state.data.agent_queue = payload.agent_queue;
+                self.state.data.watched_worktrees = payload.watched_worktrees;
+                self.state.data.watched_tickets = payload.watched_tickets;
+                self.state.data.stack_infos = payload.stack_infos;
+                self.state.data.agent_inbox = payload.agent_inbox;
+                self.state.data.over_budget = payload.over_budget;
+                self.state.data.ticket_progress = payload.ticket_progress;
+                self.track_db_snapshot(payload.read_only);
+                self.clamp_inbox_selection();
+                if let Some(usage) = payload.workspace_usage {
+                    self.state.data.workspace_usage = usage;
+                }
+                if let Some(statuses) = payload.git_statuses {
+                    self.state.data.git_statuses = statuses;
+                }
                 self.refresh_pending_feedback();
                 self.refresh_pending_repo_feedback();
                 self.state.data.rebuild_maps();
                 self.reload_agent_events();
                 self.reload_repo_agent_events();
+                if self.state.view == View::AgentRunDetail {
+                    self.reload_agent_run_detail();
+                }
                 self.state.rebuild_filtered_tickets();
                 self.clamp_indices();
                 // Always redraw since workflow column is persistent across all views.
                 return true;
             }
+            Action::TicketSyncStarted { repo_slugs } => {
+                self.state.sync_progress = Some(SyncProgress::new(repo_slugs));
+            }
+            Action::TicketSyncRepoStarted { repo_slug } => {
+                if let Some(progress) = self.state.sync_progress.as_mut() {
+                    progress.repo_started(&repo_slug);
+                }
+            }
+            Action::TicketSyncRepoFinished { repo_slug } => {
+                if let Some(progress) = self.state.sync_progress.as_mut() {
+                    progress.repo_finished(&repo_slug);
+                }
+            }
             Action::TicketSyncComplete { repo_slug, count } => {
-                self.state.status_message = Some(format!("Synced {count} tickets for {repo_slug}"));
+                match self.state.sync_progress.as_mut() {
+                    Some(progress) => progress.record_synced(&repo_slug, count),
+                    None => {
+                        self.state.status_message =
+                            Some(format!("Synced {count} tickets for {repo_slug}"))
+                    }
+                }
             }
             Action::TicketSyncFailed { repo_slug, error } => {
-                self.state.status_message = Some(format!("Sync failed for {repo_slug}: {error}"));
+                match self.state.sync_progress.as_mut() {
+                    Some(progress) => {
+                        // The sync panel closes on its own; keep the failure
+                        // pinned in the footer until the message log is read.
+                        self.state.message_log.push(
+                            Severity::Error,
+                            format!("Sync failed for {repo_slug}: {error}"),
+                        );
+                        progress.record_failed(&repo_slug, error)
+                    }
+                    None => {
+                        self.state.status_message =
+                            Some(format!("Sync failed for {repo_slug}: {error}"))
+                    }
+                }
+            }
+            Action::DaemonNotice { message } => {
+                self.state.status_message = Some(message);
+                self.refresh_data();
             }
             Action::TicketSyncDone => {
                 self.state.ticket_sync_in_progress = false;
+                if let Some(progress) = self.state.sync_progress.as_mut() {
+                    progress.finish();
+                }
                 self.refresh_data();
             }
             Action::WorktreeCreated {
@@ -1071,10 +1335,18 @@ impl App {
                 self.state.status_message = Some(msg);
                 self.refresh_data();
                 if let Some(tid) = ticket_id {
-                    self.maybe_start_agent_for_worktree(wt_id, wt_path, wt_slug, tid, wt_repo_id);
+                    if self.state.auto_agent_ticket_id.as_ref() == Some(&tid) {
+                        self.state.auto_agent_ticket_id = None;
+                        self.start_agent_for_new_worktree(wt_id, wt_path, wt_slug, tid, wt_repo_id);
+                    } else {
+                        self.maybe_start_agent_for_worktree(
+                            wt_id, wt_path, wt_slug, tid, wt_repo_id,
+                        );
+                    }
                 }
             }
             Action::WorktreeCreateFailed { message } => {
+                self.state.auto_agent_ticket_id = None;
                 self.state.modal = Modal::Error { message };
             }
             Action::AgentLaunchComplete { result } | Action::AgentRestartComplete { result } => {
@@ -1089,6 +1361,19 @@ impl App {
                     }
                 }
             }
+            Action::AgentLaunchDirty { launch, files } => {
+                self.state.modal = Modal::DirtyWorktree { files, launch };
+            }
+            Action::AgentLaunchOverBudget { launch, message } => {
+                self.state.modal = Modal::Confirm {
+                    title: "Budget Exceeded".into(),
+                    message: format!("The {message}. Launch the agent anyway?"),
+                    on_confirm: crate::state::ConfirmAction::LaunchAgentOverBudget { launch },
+                };
+            }
+            Action::ResolveDirtyWorktree(choice) => {
+                self.handle_resolve_dirty_worktree(choice);
+            }
             Action::RepoAgentLaunched { result } | Action::RepoAgentStopComplete { result } => {
                 self.handle_repo_agent_result(result);
             }
@@ -1188,6 +1473,82 @@ impl App {
                     }
                 }
             }
+            Action::CommitComplete { result } => {
+                self.state.modal = Modal::None;
+                match result {
+                    Ok(msg) => {
+                        self.state.status_message = Some(msg);
+                        self.refresh_data();
+                    }
+                    Err(e) => {
+                        self.state.modal = Modal::Error {
+                            message: format!("Commit failed: {e}"),
+                        }
+                    }
+                }
+            }
+            Action::RestackComplete { result } => {
+                self.state.modal = Modal::None;
+                match result {
+                    Ok(msg) => {
+                        self.state.status_message = Some(msg);
+                        self.refresh_data();
+                    }
+                    Err(e) => {
+                        self.state.modal = Modal::Error {
+                            message: format!("Restack failed: {e}"),
+                        }
+                    }
+                }
+            }
+            Action::ScanBranchTodosComplete { result } => {
+                self.state.modal = Modal::None;
+                match result {
+                    Ok(0) => {
+                        self.state.status_message =
+                            Some("No TODO or FIXME comments added on this branch".to_string());
+                        self.reload_agent_events();
+                    }
+                    Ok(n) => {
+                        self.state.status_message =
+                            Some(format!("{n} TODO/FIXME comment(s) added on this branch"));
+                        self.reload_agent_events();
+                    }
+                    Err(e) => {
+                        self.state.modal = Modal::Error {
+                            message: format!("TODO scan failed: {e}"),
+                        }
+                    }
+                }
+            }
+            Action::SyncWithBaseComplete { result } => {
+                self.state.modal = Modal::None;
+                match result {
+                    Ok(sync) => match sync.status {
+                        SyncStatus::Conflicts { ref files } => {
+                            self.state.modal = Modal::Error {
+                                message: format!(
+                                    "Sync stopped on conflicts; nothing was changed.\n\n\
+                                     {} of {} onto {} conflicts in:\n  {}",
+                                    sync.strategy,
+                                    sync.slug,
+                                    sync.onto,
+                                    files.join("\n  ")
+                                ),
+                            }
+                        }
+                        SyncStatus::UpToDate | SyncStatus::Synced { .. } => {
+                            self.state.status_message = Some(sync.to_string());
+                            self.refresh_data();
+                        }
+                    },
+                    Err(e) => {
+                        self.state.modal = Modal::Error {
+                            message: format!("Sync failed: {e}"),
+                        }
+                    }
+                }
+            }
             Action::PrCreateComplete { result } => {
                 self.state.modal = Modal::None;
                 match result {
@@ -1319,6 +1680,20 @@ impl App {
                     }
                 }
             }
+            Action::TicketWriteBackComplete { result } => {
+                self.state.modal = Modal::None;
+                match result {
+                    Ok(msg) => {
+                        self.state.status_message = Some(msg);
+                        self.refresh_data();
+                    }
+                    Err(e) => {
+                        self.state.modal = Modal::Error {
+                            message: format!("Ticket write-back failed: {e}"),
+                        };
+                    }
+                }
+            }
             Action::SetRepoModelComplete { slug, result } => {
                 self.state.modal = Modal::None;
                 match result {
@@ -1356,6 +1731,61 @@ impl App {
             Action::OpenTicketGraphView => {
                 self.open_ticket_graph_view();
             }
+            Action::OpenRoadmap => {
+                self.open_roadmap();
+            }
+            Action::OpenReports => {
+                self.open_reports(CostGroupBy::default(), REPORT_WINDOWS[1]);
+            }
+            Action::CycleReportGrouping => {
+                if let Modal::Reports {
+                    group_by,
+                    window_days,
+                    ..
+                } = self.state.modal
+                {
+                    let next = CostGroupBy::ALL
+                        .iter()
+                        .position(|g| *g == group_by)
+                        .map_or(0, |i| (i + 1) % CostGroupBy::ALL.len());
+                    self.open_reports(CostGroupBy::ALL[next], window_days);
+                }
+            }
+            Action::CycleReportWindow => {
+                if let Modal::Reports {
+                    group_by,
+                    window_days,
+                    ..
+                } = self.state.modal
+                {
+                    let next = REPORT_WINDOWS
+                        .iter()
+                        .position(|d| *d == window_days)
+                        .map_or(0, |i| (i + 1) % REPORT_WINDOWS.len());
+                    self.open_reports(group_by, REPORT_WINDOWS[next]);
+                }
+            }
+            Action::OpenActivityHeatmap => {
+                self.open_activity_heatmap(ActivityGroupBy::default());
+            }
+            Action::CycleActivityGrouping => {
+                if let Modal::ActivityHeatmap { group_by, .. } = self.state.modal {
+                    let next = ActivityGroupBy::ALL
+                        .iter()
+                        .position(|g| *g == group_by)
+                        .map_or(0, |i| (i + 1) % ActivityGroupBy::ALL.len());
+                    self.open_activity_heatmap(ActivityGroupBy::ALL[next]);
+                }
+            }
+            Action::OpenGrooming => {
+                self.open_grooming();
+            }
+            Action::AcceptGroomingProposal => {
+                self.decide_grooming_proposal(true);
+            }
+            Action::DismissGroomingProposal => {
+                self.decide_grooming_proposal(false);
+            }
 
             Action::OpenWorkflowStepGraphView => {
                 self.state.status_message = Some("Workflow step graph view coming soon".into());
@@ -1467,6 +1897,134 @@ impl App {
         true
     }
 
+    /// Open the roadmap for the selected repo, or for all repos when none is
+    /// selected. Uses every synced ticket, closed ones included, so progress
+    /// reflects finished work regardless of the ticket list's filters.
+    fn open_roadmap(&mut self) {
+        let repo = self.state.selected_repo();
+        let title = match repo {
+            Some(r) => format!("Roadmap — {}", r.slug),
+            None => "Roadmap".to_string(),
+        };
+        let repo_id = repo.map(|r| r.id.clone());
+        let tickets: Vec<_> = self
+            .state
+            .data
+            .tickets
+            .iter()
+            .filter(|t| repo_id.as_ref().is_none_or(|id| &t.repo_id == id))
+            .cloned()
+            .collect();
+        let groups = build_roadmap(&tickets, &self.state.data.ticket_progress);
+        self.state.modal = Modal::Roadmap {
+            title,
+            groups,
+            selected: 0,
+        };
+    }
+
+    /// Open (or reload) the cost report for the last `window_days`.
+    fn open_reports(&mut self, group_by: CostGroupBy, window_days: i64) {
+        let since = chrono::Utc::now() - chrono::Duration::days(window_days);
+        match AgentManager::new(&self.conn).cost_report(Some(since), group_by) {
+            Ok(rows) => {
+                self.state.modal = Modal::Reports {
+                    group_by,
+                    window_days,
+                    rows,
+                    selected: 0,
+                };
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load cost report: {e}"));
+            }
+        }
+    }
+
+    /// Open (or reload) the activity heat map over the last
+    /// [`DEFAULT_ACTIVITY_DAYS`] days.
+    fn open_activity_heatmap(&mut self, group_by: ActivityGroupBy) {
+        match activity_heatmap(
+            &self.conn,
+            &self.config,
+            &Viewer::Local,
+            DEFAULT_ACTIVITY_DAYS,
+            group_by,
+        ) {
+            Ok(heatmap) => {
+                self.state.modal = Modal::ActivityHeatmap {
+                    group_by,
+                    heatmap,
+                    selected: 0,
+                };
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load activity: {e}"));
+            }
+        }
+    }
+
+    /// Open the pending grooming proposals for the selected repo, or for all
+    /// repos when none is selected.
+    fn open_grooming(&mut self) {
+        let repo = self.state.selected_repo();
+        let title = match repo {
+            Some(r) => format!("Grooming proposals — {}", r.slug),
+            None => "Grooming proposals".to_string(),
+        };
+        let repo_id = repo.map(|r| r.id.clone());
+        match TicketGrooming::new(&self.conn)
+            .list(repo_id.as_deref(), Some(ProposalStatus::Pending))
+        {
+            Ok(proposals) => {
+                self.state.modal = Modal::Grooming {
+                    title,
+                    proposals,
+                    selected: 0,
+                };
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load proposals: {e}"));
+            }
+        }
+    }
+
+    /// Accept or dismiss the selected grooming proposal and drop it from the list.
+    fn decide_grooming_proposal(&mut self, accept: bool) {
+        let Modal::Grooming {
+            ref mut proposals,
+            ref mut selected,
+            ..
+        } = self.state.modal
+        else {
+            return;
+        };
+        let Some(proposal) = proposals.get(*selected) else {
+            return;
+        };
+        let grooming = TicketGrooming::new(&self.conn);
+        let result = if accept {
+            grooming.accept(&proposal.id)
+        } else {
+            grooming.dismiss(&proposal.id)
+        };
+        match result {
+            Ok(p) => {
+                proposals.remove(*selected);
+                *selected = (*selected).min(proposals.len().saturating_sub(1));
+                let verb = if accept { "Accepted" } else { "Dismissed" };
+                self.state.status_message =
+                    Some(format!("{verb} proposal for #{}", p.ticket_source_id));
+                if accept {
+                    self.refresh_data();
+                }
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to update proposal: {e}"));
+            }
+        }
+    }
+
     /// Build and open the ticket dependency graph for the current repo.
     fn open_ticket_graph_view(&mut self) {
         use crate::ui::graph::{
@@ -1569,6 +2127,29 @@ impl App {
         };
     }
 
+    /// Keep the stale-data banner in step with the poller. Once the database
+    /// opens read-write again, also replace a read-only connection left over
+    /// from a locked startup.
+    pub(super) fn track_db_snapshot(&mut self, read_only: bool) {
+        if read_only {
+            self.state
+                .db_snapshot_since
+                .get_or_insert_with(|| chrono::Local::now().format("%H:%M:%S").to_string());
+            return;
+        }
+        self.state.db_snapshot_since = None;
+        if self
+            .conn
+            .is_readonly(rusqlite::DatabaseName::Main)
+            .unwrap_or(false)
+        {
+            match conductor_core::db::open_database(&conductor_core::config::db_path()) {
+                Ok(conn) => self.conn = conn,
+                Err(e) => tracing::warn!("reopening database read-write failed: {e}"),
+            }
+        }
+    }
+
     /// Handle the result of a repo-scoped agent launch or stop operation.
     fn handle_repo_agent_result(&mut self, result: Result<String, String>) {
         self.state.modal = Modal::None;
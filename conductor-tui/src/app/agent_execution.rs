@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use conductor_core::agent::{AgentManager, AgentRun, FeedbackRequest};
+use conductor_core::agent::{
+    launch_guard, AgentManager, AgentRun, DiffReviewRequest, DiffReviews, DirtyWorktreeChoice,
+    FeedbackRequest, LaunchGuards,
+};
 use conductor_core::agent_config::{AgentDef, AgentRole};
 use conductor_core::agent_runtime::{EventSink, RuntimeEvent};
 use conductor_core::config::AutoStartAgent;
@@ -12,7 +15,9 @@ use conductor_core::tickets::build_agent_prompt;
 use conductor_core::worktree::{WorktreeCreateOptions, WorktreeManager};
 
 use crate::action::Action;
-use crate::state::{InputAction, Modal, WorkflowPickerItem, WorktreeDetailFocus};
+use crate::state::{
+    InputAction, Modal, PendingAgentLaunch, WorkflowPickerItem, WorktreeDetailFocus,
+};
 
 use super::App;
 
@@ -215,6 +220,43 @@ impl App {
         }
     }
 
+    pub(super) fn handle_detect_toolchain(&mut self) {
+        let Some(slug) = self
+            .state
+            .selected_repo_id
+            .as_ref()
+            .and_then(|id| self.state.data.repos.iter().find(|r| &r.id == id))
+            .map(|r| r.slug.clone())
+        else {
+            self.state.status_message = Some("No repo selected".to_string());
+            return;
+        };
+        let mgr = conductor_core::repo::RepoManager::new(&self.conn, &self.config);
+        match mgr.detect_toolchain(&slug) {
+            Ok(repo) => {
+                self.state.status_message =
+                    Some(format!("Toolchain for {slug}: {}", repo.toolchain));
+                self.refresh_data();
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to detect toolchain: {e}"));
+            }
+        }
+    }
+
+    /// Agent prompt pre-fill for a ticket, listing its cached attachments.
+    fn ticket_agent_prompt(&self, ticket_id: &str) -> Option<String> {
+        let ticket = self.state.data.ticket_map.get(ticket_id)?;
+        let attachments = self
+            .state
+            .data
+            .ticket_attachments
+            .get(ticket_id)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        Some(build_agent_prompt(ticket, attachments))
+    }
+
     pub(super) fn selected_worktree_run(&self) -> Option<&AgentRun> {
         self.state
             .selected_worktree_id
@@ -311,8 +353,7 @@ impl App {
             let prefill = wt
                 .ticket_id
                 .as_ref()
-                .and_then(|tid| self.state.data.ticket_map.get(tid))
-                .map(build_agent_prompt)
+                .and_then(|tid| self.ticket_agent_prompt(tid))
                 .unwrap_or_default();
             ("Claude Agent".to_string(), prefill)
         };
@@ -395,6 +436,8 @@ impl App {
                 resume_session_id,
                 selected_model,
                 None, // Auto-suggest path uses the default claude runtime
+                false,
+                None,
             );
             return;
         }
@@ -800,6 +843,46 @@ impl App {
         });
     }
 
+    /// Launch an agent on a worktree just created from a ticket with `N`,
+    /// using the ticket prompt and the repo's default model. Falls back to
+    /// the prompt modal when the ticket is no longer cached.
+    pub(super) fn start_agent_for_new_worktree(
+        &mut self,
+        worktree_id: String,
+        worktree_path: String,
+        worktree_slug: String,
+        ticket_id: String,
+        repo_id: String,
+    ) {
+        let Some(prompt) = self.ticket_agent_prompt(&ticket_id) else {
+            self.show_agent_prompt_for_ticket(worktree_id, worktree_path, worktree_slug, ticket_id);
+            return;
+        };
+        let repo_model = self
+            .state
+            .data
+            .repos
+            .iter()
+            .find(|r| r.id == repo_id)
+            .and_then(|r| r.model.as_deref());
+        let model = conductor_core::models::resolve_model(
+            None,
+            repo_model,
+            self.config.general.model.as_deref(),
+        );
+        self.start_agent_headless(
+            prompt,
+            worktree_id,
+            worktree_path,
+            worktree_slug,
+            None,
+            model,
+            None,
+            false,
+            None,
+        );
+    }
+
     pub(super) fn show_agent_prompt_for_ticket(
         &mut self,
         worktree_id: String,
@@ -814,12 +897,7 @@ impl App {
         let prefill = if has_prior_runs {
             String::new()
         } else {
-            self.state
-                .data
-                .ticket_map
-                .get(&ticket_id)
-                .map(build_agent_prompt)
-                .unwrap_or_default()
+            self.ticket_agent_prompt(&ticket_id).unwrap_or_default()
         };
 
         self.open_agent_prompt_modal(
@@ -864,20 +942,28 @@ impl App {
         };
     }
 
+    /// Launch a headless agent on a background thread. When the worktree has
+    /// spent its `[budget]` and `override_budget` is false, the launch is
+    /// handed back as [`Action::AgentLaunchOverBudget`]; when it has
+    /// uncommitted changes and `dirty_choice` is `None`, as
+    /// [`Action::AgentLaunchDirty`], so the user can decide first.
     #[allow(clippy::too_many_arguments)]
     pub(super) fn start_agent_headless(
         &mut self,
         prompt: String,
         worktree_id: String,
         worktree_path: String,
-        _worktree_slug: String,
+        worktree_slug: String,
         resume_session_id: Option<String>,
         model: Option<String>,
         runtime: Option<String>,
+        override_budget: bool,
+        dirty_choice: Option<DirtyWorktreeChoice>,
     ) {
         let Some(ref tx) = self.bg_tx else { return };
         let tx = tx.clone();
         let stall_threshold = self.config.agents.stall_threshold();
+        let budget = self.config.budget.clone();
         // Capture runtimes on the main thread per the TUI threading rule —
         // drive_headless_run runs off-thread and must not re-read process state.
         let runtimes = self.config.runtimes.clone();
@@ -899,6 +985,59 @@ impl App {
             };
             let mgr = AgentManager::new(&conn);
 
+            if !override_budget {
+                let exceeded = match mgr.budget_exceeded(
+                    &budget,
+                    Some(&worktree_id),
+                    resume_session_id.as_deref(),
+                ) {
+                    Ok(exceeded) => exceeded,
+                    Err(e) => {
+                        let _ = tx.send(Action::AgentLaunchComplete {
+                            result: Err(format!("Failed to check the agent budget: {e}")),
+                        });
+                        return;
+                    }
+                };
+                if let Some(exceeded) = exceeded {
+                    let _ = tx.send(Action::AgentLaunchOverBudget {
+                        launch: Box::new(PendingAgentLaunch {
+                            prompt,
+                            worktree_id,
+                            worktree_path,
+                            worktree_slug,
+                            resume_session_id,
+                            model,
+                            runtime,
+                            override_budget: false,
+                        }),
+                        message: exceeded.to_string(),
+                    });
+                    return;
+                }
+            }
+
+            let dirty_files = launch_guard::dirty_files(&worktree_path).unwrap_or_else(|e| {
+                tracing::warn!("could not check {worktree_slug} for uncommitted changes: {e}");
+                Vec::new()
+            });
+            if !dirty_files.is_empty() && dirty_choice.is_none() {
+                let _ = tx.send(Action::AgentLaunchDirty {
+                    launch: Box::new(PendingAgentLaunch {
+                        prompt,
+                        worktree_id,
+                        worktree_path,
+                        worktree_slug,
+                        resume_session_id,
+                        model,
+                        runtime,
+                        override_budget,
+                    }),
+                    files: dirty_files,
+                });
+                return;
+            }
+
             let run = match mgr.create_run(Some(&worktree_id), &prompt, model.as_deref()) {
                 Ok(r) => r,
                 Err(e) => {
@@ -909,6 +1048,22 @@ impl App {
                 }
             };
 
+            if let Some(choice) = dirty_choice.filter(|_| !dirty_files.is_empty()) {
+                let recorded = launch_guard::prepare(&worktree_path, choice, dirty_files).and_then(
+                    |prepared| LaunchGuards::new(&conn).record(&run.id, &worktree_id, &prepared),
+                );
+                if let Err(e) = recorded {
+                    let message = format!("Could not {choice} uncommitted changes: {e}");
+                    if let Err(mark_err) = mgr.update_run_failed(&run.id, &message) {
+                        tracing::warn!("failed to mark run {} failed: {mark_err}", run.id);
+                    }
+                    let _ = tx.send(Action::AgentLaunchComplete {
+                        result: Err(message),
+                    });
+                    return;
+                }
+            }
+
             // Persist the runtime selection on the run so list/detail views show
             // which runtime serviced the request.
             if let Some(ref rt_name) = runtime {
@@ -937,9 +1092,221 @@ impl App {
                 |result| Action::AgentLaunchComplete { result },
                 "Agent launched (headless)",
             );
+
+            // The run has ended: put back anything stashed at launch.
+            match LaunchGuards::new(&conn).restore_finished() {
+                Ok(guards) => {
+                    for guard in guards {
+                        let action = match guard.restore_error {
+                            None => Action::BackgroundSuccess {
+                                message: format!("Restored stashed changes in {worktree_slug}"),
+                            },
+                            Some(e) => Action::BackgroundError {
+                                message: format!(
+                                    "Could not restore stashed changes in {worktree_slug}: {e}\n\
+                                     They are still in `git stash list`."
+                                ),
+                            },
+                        };
+                        let _ = tx.send(action);
+                    }
+                }
+                Err(e) => tracing::warn!("restoring pre-run stashes failed: {e}"),
+            }
+        });
+    }
+
+    /// Dirty-worktree modal answer: relaunch the held-back agent with `choice`.
+    pub(super) fn handle_resolve_dirty_worktree(&mut self, choice: DirtyWorktreeChoice) {
+        let Modal::DirtyWorktree { launch, .. } =
+            std::mem::replace(&mut self.state.modal, Modal::None)
+        else {
+            return;
+        };
+        let PendingAgentLaunch {
+            prompt,
+            worktree_id,
+            worktree_path,
+            worktree_slug,
+            resume_session_id,
+            model,
+            runtime,
+            override_budget,
+        } = *launch;
+        self.start_agent_headless(
+            prompt,
+            worktree_id,
+            worktree_path,
+            worktree_slug,
+            resume_session_id,
+            model,
+            runtime,
+            override_budget,
+            Some(choice),
+        );
+    }
+
+    /// Over-budget confirm accepted: relaunch the held-back agent past the budget.
+    pub(super) fn launch_agent_over_budget(&mut self, launch: PendingAgentLaunch) {
+        let PendingAgentLaunch {
+            prompt,
+            worktree_id,
+            worktree_path,
+            worktree_slug,
+            resume_session_id,
+            model,
+            runtime,
+            ..
+        } = launch;
+        self.start_agent_headless(
+            prompt,
+            worktree_id,
+            worktree_path,
+            worktree_slug,
+            resume_session_id,
+            model,
+            runtime,
+            true,
+            None,
+        );
+    }
+
+    /// `v` in WorktreeDetail: launch a read-only agent that reviews the
+    /// worktree's diff against its base. The diff is read and the run created
+    /// on a background thread per the TUI threading rule.
+    pub(super) fn handle_review_diff(&mut self) {
+        let wt = self
+            .state
+            .selected_worktree_id
+            .as_ref()
+            .and_then(|id| self.state.data.worktrees.iter().find(|w| &w.id == id))
+            .cloned();
+
+        let Some(wt) = wt else {
+            self.state.status_message = Some("Select a worktree first".to_string());
+            return;
+        };
+        if !wt.is_active() {
+            self.state.status_message = Some("Only active worktrees can be reviewed".to_string());
+            return;
+        }
+        if self.agent_busy_guard(&wt.id) {
+            return;
+        }
+
+        let Some(ref tx) = self.bg_tx else { return };
+        let tx = tx.clone();
+        let config = self.config.clone();
+        let stall_threshold = self.config.agents.stall_threshold();
+        let runtimes = self.config.runtimes.clone();
+        let repo_model = self
+            .state
+            .data
+            .repos
+            .iter()
+            .find(|r| r.id == wt.repo_id)
+            .and_then(|r| r.model.as_deref());
+        let model = conductor_core::models::resolve_model(
+            wt.model.as_deref(),
+            repo_model,
+            self.config.general.model.as_deref(),
+        );
+
+        self.state.modal = Modal::Progress {
+            message: format!("Preparing review of {}…", wt.slug),
+        };
+
+        std::thread::spawn(move || {
+            let result = (|| -> Result<(AgentRun, DiffReviewRequest), String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db).map_err(|e| e.to_string())?;
+                let agents = AgentManager::new(&conn);
+                agents
+                    .check_budget(&config.budget, Some(&wt.id), None)
+                    .map_err(|e| e.to_string())?;
+                let reviews = DiffReviews::new(&conn);
+                let request = reviews
+                    .prepare(&config, &wt.id)
+                    .map_err(|e| e.to_string())?;
+                let run = agents
+                    .create_run(Some(&wt.id), &request.prompt, model.as_deref())
+                    .map_err(|e| format!("Failed to create agent run: {e}"))?;
+                reviews
+                    .record(&request, &run.id)
+                    .map_err(|e| format!("Failed to record review: {e}"))?;
+                Ok((run, request))
+            })();
+            let (run, request) = match result {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Action::AgentLaunchComplete { result: Err(e) });
+                    return;
+                }
+            };
+
+            drive_headless_run(
+                run,
+                HeadlessRunConfig {
+                    working_dir: wt.path,
+                    prompt: request.prompt,
+                    resume_session_id: None,
+                    model,
+                    bot_name: None,
+                    permission_mode: Some(conductor_core::config::AgentPermissionMode::RepoSafe),
+                    stall_threshold,
+                    runtime: None,
+                    runtimes,
+                },
+                &tx,
+                |result| Action::AgentLaunchComplete { result },
+                "Diff review started — press V to view it when done",
+            );
         });
     }
 
+    /// `V` in WorktreeDetail: show the latest diff review.
+    pub(super) fn handle_view_diff_review(&mut self) {
+        let Some(review) = self.state.data.latest_diff_review.clone() else {
+            self.state.status_message =
+                Some("No diff review yet — press v to start one".to_string());
+            return;
+        };
+        let Some(body) = review.review else {
+            use conductor_core::agent::AgentRunStatus;
+            let running = matches!(
+                review.status,
+                AgentRunStatus::Running | AgentRunStatus::WaitingForFeedback
+            );
+            self.state.status_message = Some(if running {
+                "Diff review still running".to_string()
+            } else {
+                format!("Last diff review {} — press v to retry", review.status)
+            });
+            return;
+        };
+        let mut title = format!(
+            "Review vs {} ({} files{})",
+            review.base_ref,
+            review.files_changed,
+            if review.truncated {
+                ", diff trimmed"
+            } else {
+                ""
+            }
+        );
+        if let Some(ref ended) = review.ended_at {
+            title.push_str(&format!(" — {ended}"));
+        }
+        let line_count = body.lines().count();
+        self.state.modal = Modal::EventDetail {
+            title,
+            body,
+            line_count,
+            scroll_offset: 0,
+            horizontal_offset: 0,
+        };
+    }
+
     /// `p` in RepoDetail: jump column focus into the persistent repo-agent
     /// prompt input. (Previously opened a `Modal::AgentPrompt` — now consolidated
     /// onto the same persistent box used for the worktree agent.)
@@ -1028,13 +1395,18 @@ impl App {
             return;
         }
 
+        self.restart_agent_run(run.id, run.worktree_id.as_deref());
+    }
+
+    /// Re-run a finished agent run in its worktree with the same config.
+    pub(super) fn restart_agent_run(&mut self, run_id: String, worktree_id: Option<&str>) {
         // Resolve worktree path on the main thread (from cached data)
-        let worktree_path = run.worktree_id.as_ref().and_then(|wt_id| {
+        let worktree_path = worktree_id.and_then(|wt_id| {
             self.state
                 .data
                 .worktrees
                 .iter()
-                .find(|w| &w.id == wt_id)
+                .find(|w| w.id == wt_id)
                 .map(|w| w.path.clone())
         });
 
@@ -1046,7 +1418,6 @@ impl App {
 
         let Some(ref tx) = self.bg_tx else { return };
         let tx = tx.clone();
-        let run_id = run.id.clone();
         let stall_threshold = self.config.agents.stall_threshold();
         let runtimes = self.config.runtimes.clone();
 
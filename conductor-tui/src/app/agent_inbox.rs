@@ -0,0 +1,230 @@
+use conductor_core::agent::{AgentInbox, InboxItem};
+use conductor_core::worktree::{Worktree, WorktreeManager};
+
+use crate::action::Action;
+use crate::state::Modal;
+
+use super::App;
+
+impl App {
+    pub(super) fn handle_open_agent_inbox(&mut self) {
+        if self.state.data.agent_inbox.is_empty() {
+            self.state.status_message = Some("Agent inbox is empty".to_string());
+            return;
+        }
+        self.state.modal = Modal::AgentInbox { selected: 0 };
+    }
+
+    /// Keep the inbox cursor on a real row after the list shrinks.
+    pub(super) fn clamp_inbox_selection(&mut self) {
+        let len = self.state.data.agent_inbox.len();
+        if let Modal::AgentInbox { ref mut selected } = self.state.modal {
+            *selected = (*selected).min(len.saturating_sub(1));
+        }
+    }
+
+    fn selected_inbox_item(&self) -> Option<InboxItem> {
+        match self.state.modal {
+            Modal::AgentInbox { selected } => self.state.data.agent_inbox.get(selected).cloned(),
+            _ => None,
+        }
+    }
+
+    fn inbox_worktree(&mut self, item: &InboxItem) -> Option<Worktree> {
+        let wt = self
+            .state
+            .data
+            .worktrees
+            .iter()
+            .find(|w| w.id == item.worktree_id)
+            .cloned();
+        if wt.is_none() {
+            self.state.status_message = Some(format!(
+                "Worktree {} is no longer available",
+                item.worktree_slug
+            ));
+        }
+        wt
+    }
+
+    /// Mark `run_id` reviewed and drop it from the cached inbox.
+    fn mark_inbox_run_reviewed(&mut self, run_id: &str) -> bool {
+        match AgentInbox::new(&self.conn).mark_reviewed(run_id) {
+            Ok(()) => {
+                self.state.data.agent_inbox.retain(|i| i.run_id != run_id);
+                self.clamp_inbox_selection();
+                true
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to mark run reviewed: {e}"));
+                false
+            }
+        }
+    }
+
+    /// Open the run's worktree. Looking at it counts as reviewing it.
+    pub(super) fn handle_inbox_open(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        let Some(wt) = self.inbox_worktree(&item) else {
+            return;
+        };
+        self.mark_inbox_run_reviewed(&item.run_id);
+        self.state.modal = Modal::None;
+        let previous = self.state.view;
+        self.open_worktree_detail(&wt, previous);
+    }
+
+    pub(super) fn handle_inbox_mark_reviewed(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        if self.mark_inbox_run_reviewed(&item.run_id) && self.state.data.agent_inbox.is_empty() {
+            self.state.modal = Modal::None;
+            self.state.status_message = Some("Agent inbox cleared".to_string());
+        }
+    }
+
+    pub(super) fn handle_inbox_mark_all_reviewed(&mut self) {
+        match AgentInbox::new(&self.conn).mark_all_reviewed() {
+            Ok(cleared) => {
+                self.state.data.agent_inbox.clear();
+                self.state.modal = Modal::None;
+                self.state.status_message = Some(format!(
+                    "Marked {cleared} run{} reviewed",
+                    if cleared == 1 { "" } else { "s" }
+                ));
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to clear inbox: {e}"));
+            }
+        }
+    }
+
+    pub(super) fn handle_inbox_view_diff(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot load diff: background sender not ready.".into(),
+            };
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: format!("Loading diff for {}…", item.worktree_slug),
+        };
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                mgr.diff(&item.repo_slug, &item.worktree_slug)
+                    .map_err(anyhow::Error::from)
+            })();
+            let _ = bg_tx.send(Action::InboxDiffLoaded {
+                title: format!("Diff: {}/{}", item.repo_slug, item.worktree_slug),
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    pub(super) fn handle_inbox_push(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        if let Some(wt) = self.inbox_worktree(&item) {
+            self.push_worktree(&wt);
+        }
+    }
+
+    pub(super) fn handle_inbox_create_pr(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        if let Some(wt) = self.inbox_worktree(&item) {
+            self.create_pr_for(&wt);
+        }
+    }
+
+    /// Re-run the agent with the same prompt; the finished run is reviewed.
+    pub(super) fn handle_inbox_rerun(&mut self) {
+        let Some(item) = self.selected_inbox_item() else {
+            return;
+        };
+        if self.mark_inbox_run_reviewed(&item.run_id) {
+            self.state.modal = Modal::None;
+            self.restart_agent_run(item.run_id, Some(&item.worktree_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conductor_core::agent::AgentRunStatus;
+
+    fn make_app() -> App {
+        crate::test_support::isolate_conductor_home();
+        let conn = conductor_core::test_helpers::setup_db();
+        App::new(
+            conn,
+            conductor_core::config::Config::default(),
+            crate::config::TuiConfig::default(),
+            crate::theme::Theme::default(),
+        )
+    }
+
+    fn inbox_item(run_id: &str) -> InboxItem {
+        InboxItem {
+            run_id: run_id.into(),
+            worktree_id: "w1".into(),
+            repo_id: "r1".into(),
+            repo_slug: "test-repo".into(),
+            worktree_slug: "feat-test".into(),
+            branch: "feat/test".into(),
+            status: AgentRunStatus::Completed,
+            prompt: "fix it".into(),
+            result_text: None,
+            cost_usd: None,
+            num_turns: None,
+            started_at: "2024-01-01T00:00:00Z".into(),
+            ended_at: None,
+        }
+    }
+
+    #[test]
+    fn open_agent_inbox_requires_items() {
+        let mut app = make_app();
+        app.handle_open_agent_inbox();
+        assert!(matches!(app.state.modal, Modal::None));
+        assert_eq!(
+            app.state.status_message.as_deref(),
+            Some("Agent inbox is empty")
+        );
+
+        app.state.data.agent_inbox = vec![inbox_item("a1")];
+        app.handle_open_agent_inbox();
+        assert!(matches!(app.state.modal, Modal::AgentInbox { selected: 0 }));
+    }
+
+    #[test]
+    fn mark_reviewed_removes_item_and_closes_when_empty() {
+        let mut app = make_app();
+        for id in ["a1", "a2"] {
+            conductor_core::test_helpers::insert_test_agent_run(&app.conn, id, "w1");
+        }
+        app.state.data.agent_inbox = vec![inbox_item("a1"), inbox_item("a2")];
+        app.state.modal = Modal::AgentInbox { selected: 1 };
+
+        app.handle_inbox_mark_reviewed();
+        assert_eq!(app.state.data.agent_inbox.len(), 1);
+        assert!(matches!(app.state.modal, Modal::AgentInbox { selected: 0 }));
+
+        app.handle_inbox_mark_reviewed();
+        assert!(app.state.data.agent_inbox.is_empty());
+        assert!(matches!(app.state.modal, Modal::None));
+    }
+}
@@ -0,0 +1,111 @@
+use conductor_core::agent::AgentManager;
+
+use crate::background::build_fallback_events;
+use crate::state::{AgentRunDetailFocus, ColumnFocus, View};
+
+use super::App;
+
+impl App {
+    /// Open the run history of the selected worktree. Back returns to the
+    /// worktree detail, so `previous_view` is left as the worktree's own.
+    pub(super) fn handle_open_agent_run_detail(&mut self) {
+        if self.state.selected_worktree_id.is_none() {
+            return;
+        }
+        self.state.view = View::AgentRunDetail;
+        self.state.column_focus = ColumnFocus::Content;
+        self.state.agent_run_detail_focus = AgentRunDetailFocus::Runs;
+        self.state.agent_run_detail_index = 0;
+        self.state.agent_run_detail_runs = Vec::new();
+        self.reload_agent_run_detail();
+    }
+
+    /// Reload the run list, keeping the selected run when it still exists.
+    pub(super) fn reload_agent_run_detail(&mut self) {
+        let Some(wt_id) = self.state.selected_worktree_id.clone() else {
+            return;
+        };
+        let selected = self
+            .state
+            .agent_run_detail_runs
+            .get(self.state.agent_run_detail_index)
+            .map(|r| r.id.clone());
+        let runs = AgentManager::new(&self.conn)
+            .list_for_worktree(&wt_id)
+            .unwrap_or_else(|e| {
+                tracing::warn!("failed to load agent runs for worktree {wt_id}: {e}");
+                Vec::new()
+            });
+        let index = selected
+            .and_then(|id| runs.iter().position(|r| r.id == id))
+            .unwrap_or(self.state.agent_run_detail_index);
+        self.state.agent_run_detail_index = index.min(runs.len().saturating_sub(1));
+        self.state.agent_run_detail_runs = runs;
+        self.load_agent_run_detail_events();
+    }
+
+    /// Load the selected run's events, falling back to its log file for runs
+    /// recorded before events were stored.
+    fn load_agent_run_detail_events(&mut self) {
+        let Some(run) = self
+            .state
+            .agent_run_detail_runs
+            .get(self.state.agent_run_detail_index)
+        else {
+            self.state.agent_run_detail_events = Vec::new();
+            self.state.agent_run_detail_event_index = 0;
+            return;
+        };
+        let mut events = AgentManager::new(&self.conn)
+            .list_events_for_run(&run.id)
+            .unwrap_or_default();
+        if events.is_empty() {
+            events = build_fallback_events(std::slice::from_ref(run));
+        }
+        let len = events.len();
+        self.state.agent_run_detail_events = events;
+        self.state.agent_run_detail_event_index = self
+            .state
+            .agent_run_detail_event_index
+            .min(len.saturating_sub(1));
+    }
+
+    /// Move the run selection by `delta`, loading the new run's events.
+    pub(super) fn agent_run_detail_move(&mut self, delta: isize) {
+        match self.state.agent_run_detail_focus {
+            AgentRunDetailFocus::Runs => {
+                let len = self.state.agent_run_detail_runs.len();
+                let index = self
+                    .state
+                    .agent_run_detail_index
+                    .saturating_add_signed(delta)
+                    .min(len.saturating_sub(1));
+                if index != self.state.agent_run_detail_index {
+                    self.state.agent_run_detail_index = index;
+                    self.state.agent_run_detail_event_index = 0;
+                    self.load_agent_run_detail_events();
+                }
+            }
+            AgentRunDetailFocus::Events => {
+                let len = self.state.agent_run_detail_events.len();
+                self.state.agent_run_detail_event_index = self
+                    .state
+                    .agent_run_detail_event_index
+                    .saturating_add_signed(delta)
+                    .min(len.saturating_sub(1));
+            }
+        }
+    }
+
+    pub(super) fn agent_run_detail_copy_session_id(&mut self) {
+        let session = self
+            .state
+            .agent_run_detail_runs
+            .get(self.state.agent_run_detail_index)
+            .and_then(|r| r.session_id.clone());
+        match session {
+            Some(id) => self.copy_text_to_clipboard(id),
+            None => self.state.status_message = Some("Run has no session ID".to_string()),
+        }
+    }
+}
@@ -1,17 +1,22 @@
+use conductor_core::bitbucket;
 use conductor_core::github;
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::{derive_local_path, RepoManager};
+use conductor_core::tickets::{Ticket, TicketSyncer, MANUAL_SOURCE_TYPE};
+use conductor_core::watch::{WatchManager, WatchTarget};
+use conductor_core::worktree::{derive_worktree_slug, WorktreeManager};
 
 use crate::action::Action;
 use crate::state::{
-    ConfirmAction, FormAction, FormField, FormFieldType, InputAction, Modal, RepoDetailFocus, View,
+    abandon_reason_at, ConfirmAction, FormAction, FormField, FormFieldType, InputAction, Modal,
+    RepoDetailFocus, View,
 };
 
-use super::helpers::derive_worktree_slug;
 use super::App;
 
 impl App {
     pub(super) fn handle_create(&mut self) {
+        self.state.auto_agent_ticket_id = None;
         // Try to detect ticket context based on current view and focus
         let ticket_context = match self.state.view {
             View::RepoDetail if self.state.repo_detail_focus == RepoDetailFocus::Tickets => self
@@ -75,7 +80,35 @@ impl App {
         }
     }
 
+    /// Ticket-context create without prompts: use the derived worktree name,
+    /// then start an agent on the new worktree with the ticket prompt once
+    /// it exists (see the `WorktreeCreated` handler).
+    pub(super) fn handle_create_with_agent(&mut self) {
+        if self.state.view != View::RepoDetail
+            || self.state.repo_detail_focus != RepoDetailFocus::Tickets
+        {
+            return;
+        }
+        let Some(ticket) = self
+            .state
+            .filtered_detail_tickets
+            .get(self.state.detail_ticket_index)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(slug) = self.state.data.repo_slug_map.get(&ticket.repo_id).cloned() else {
+            self.state.status_message = Some("Repo not found for ticket".to_string());
+            return;
+        };
+        let labels: Vec<String> = serde_json::from_str(&ticket.labels).unwrap_or_default();
+        let name = derive_worktree_slug(&ticket.source_id, &ticket.title, &labels);
+        self.state.auto_agent_ticket_id = Some(ticket.id.clone());
+        self.spawn_main_health_check(slug, name, Some(ticket.id), None, None);
+    }
+
     pub(super) fn handle_adopt_worktree(&mut self) {
+        self.state.auto_agent_ticket_id = None;
         match self.state.view {
             View::Dashboard | View::RepoDetail => {
                 let repo_slug = self
@@ -228,11 +261,62 @@ impl App {
                 let json = serde_json::json!({"jql": jql, "url": url}).to_string();
                 (json, "jira")
             }
+            "linear" | "l" => {
+                let team_key = fields
+                    .get(1)
+                    .map(|f| f.value.trim().to_string())
+                    .unwrap_or_default();
+                if team_key.is_empty() {
+                    self.state.modal = Modal::Error {
+                        message: "Team key is required for Linear sources".to_string(),
+                    };
+                    return;
+                }
+                let json = serde_json::json!({ "team_key": team_key }).to_string();
+                (json, "linear")
+            }
+            "bitbucket" | "b" | "bb" => {
+                // Auto-infer from remote URL; the token is read from $BITBUCKET_TOKEN
+                match bitbucket::parse_bitbucket_remote(remote_url) {
+                    Some((workspace, repo_slug)) => {
+                        let json =
+                            serde_json::json!({"workspace": workspace, "repo_slug": repo_slug})
+                                .to_string();
+                        (json, "bitbucket")
+                    }
+                    None => {
+                        self.state.modal = Modal::Error {
+                            message: "Cannot infer Bitbucket workspace/repo from remote URL"
+                                .to_string(),
+                        };
+                        return;
+                    }
+                }
+            }
+            "asana" | "a" => {
+                let project_gid = fields
+                    .get(1)
+                    .map(|f| f.value.trim().to_string())
+                    .unwrap_or_default();
+                if project_gid.is_empty() {
+                    self.state.modal = Modal::Error {
+                        message: "Project GID is required for Asana sources".to_string(),
+                    };
+                    return;
+                }
+                let json = serde_json::json!({ "project_gid": project_gid }).to_string();
+                (json, "asana")
+            }
+            // Tickets are pushed to POST /api/repos/{id}/tickets/ingest
+            "webhook" | "w" => ("{}".to_string(), "webhook"),
             other => {
                 let msg = if other.is_empty() {
-                    "Type is required — enter 'github' or 'jira'".to_string()
+                    "Type is required — enter 'github', 'jira', 'linear', 'bitbucket', 'asana' or 'webhook'"
+                        .to_string()
                 } else {
-                    format!("Unknown source type '{other}' — use 'github' or 'jira'")
+                    format!(
+                        "Unknown source type '{other}' — use 'github', 'jira', 'linear', 'bitbucket', 'asana' or 'webhook'"
+                    )
                 };
                 self.state.modal = Modal::Error { message: msg };
                 return;
@@ -298,8 +382,12 @@ impl App {
         {
             let has_github = sources.iter().any(|s| s.source_type == "github");
             let has_jira = sources.iter().any(|s| s.source_type == "jira");
+            let has_linear = sources.iter().any(|s| s.source_type == "linear");
+            let has_bitbucket = sources.iter().any(|s| s.source_type == "bitbucket");
+            let has_asana = sources.iter().any(|s| s.source_type == "asana");
+            let has_webhook = sources.iter().any(|s| s.source_type == "webhook");
 
-            if has_github && has_jira {
+            if has_github && has_jira && has_linear && has_bitbucket && has_asana && has_webhook {
                 self.state.modal = Modal::IssueSourceManager {
                     repo_id,
                     repo_slug,
@@ -307,30 +395,34 @@ impl App {
                     sources,
                     selected: 0,
                 };
-                self.state.status_message =
-                    Some("Both source types already configured".to_string());
+                self.state.status_message = Some("All source types already configured".to_string());
                 return;
             }
 
-            let default_type = if has_github {
-                "jira".to_string()
-            } else if has_jira {
-                "github".to_string()
-            } else {
-                String::new()
-            };
+            let default_type = match (has_github, has_jira) {
+                (true, false) => "jira",
+                (false, true) => "github",
+                (true, true) if !has_linear => "linear",
+                (true, true) if !has_bitbucket => "bitbucket",
+                (true, true) if !has_asana => "asana",
+                (true, true) => "webhook",
+                (false, false) => "",
+            }
+            .to_string();
 
             let mut fields = vec![FormField {
                 label: "Type".to_string(),
                 value: default_type,
-                placeholder: "github or jira (Tab to next field)".to_string(),
+                placeholder:
+                    "github, jira, linear, bitbucket, asana or webhook (Tab to next field)"
+                        .to_string(),
                 manually_edited: false,
                 required: true,
                 readonly: false,
                 field_type: FormFieldType::Text,
             }];
 
-            // If type is pre-filled to jira, include the Jira fields up front
+            // If the type is pre-filled, include its fields up front
             Self::sync_issue_source_form_fields(&mut fields);
 
             self.state.modal = Modal::Form {
@@ -389,11 +481,6 @@ impl App {
         pr_merged: bool,
         has_ticket: bool,
     ) {
-        let on_confirm = ConfirmAction::DeleteWorktree {
-            repo_slug: repo_slug.to_string(),
-            wt_slug: wt_slug.to_string(),
-        };
-
         if issue_closed && pr_merged {
             // Work is done — simple confirm
             self.state.modal = Modal::Confirm {
@@ -402,7 +489,11 @@ impl App {
                     "Delete worktree {}/{}? Issue is closed and PR is merged.",
                     repo_slug, wt_slug
                 ),
-                on_confirm,
+                on_confirm: ConfirmAction::DeleteWorktree {
+                    repo_slug: repo_slug.to_string(),
+                    wt_slug: wt_slug.to_string(),
+                    reason: None,
+                },
             };
         } else {
             // Work may be in progress — require typing the slug
@@ -415,16 +506,48 @@ impl App {
             } else {
                 "This worktree has unmerged code."
             };
-            self.state.modal = Modal::ConfirmByName {
-                title: "Delete Worktree".to_string(),
-                message: format!("{reason} This removes the git worktree and branch."),
-                expected: wt_slug.to_string(),
-                value: String::new(),
-                on_confirm,
+            // Ask why the work is being dropped before confirming by name
+            self.state.modal = Modal::AbandonReasonPicker {
+                repo_slug: repo_slug.to_string(),
+                wt_slug: wt_slug.to_string(),
+                message: reason.to_string(),
+                selected: 0,
             };
         }
     }
 
+    /// Confirm the reason chosen in the abandon picker and move on to the
+    /// type-the-slug confirmation.
+    pub(super) fn handle_abandon_reason_picker_confirm(&mut self) {
+        let modal = std::mem::replace(&mut self.state.modal, Modal::None);
+        let Modal::AbandonReasonPicker {
+            repo_slug,
+            wt_slug,
+            message,
+            selected,
+        } = modal
+        else {
+            self.state.modal = modal;
+            return;
+        };
+        let reason = abandon_reason_at(selected);
+        let recorded = match reason {
+            Some(r) => format!(" It will be recorded as abandoned ({r})."),
+            None => String::new(),
+        };
+        self.state.modal = Modal::ConfirmByName {
+            title: "Delete Worktree".to_string(),
+            message: format!("{message}{recorded} This removes the git worktree and branch."),
+            expected: wt_slug.clone(),
+            value: String::new(),
+            on_confirm: ConfirmAction::DeleteWorktree {
+                repo_slug,
+                wt_slug,
+                reason,
+            },
+        };
+    }
+
     pub(super) fn handle_delete(&mut self) {
         match self.state.view {
             View::WorktreeDetail => {
@@ -571,23 +694,102 @@ impl App {
                     return;
                 }
             }
-            self.state.modal = Modal::Input {
-                title: "Link Ticket".to_string(),
-                prompt: "Enter ticket number (e.g., 42):".to_string(),
-                value: String::new(),
-                on_submit: InputAction::LinkTicket {
-                    worktree_id: wt_id.clone(),
-                },
+            let Some(repo_id) = self
+                .state
+                .data
+                .worktrees
+                .iter()
+                .find(|w| &w.id == wt_id)
+                .map(|w| w.repo_id.clone())
+            else {
+                return;
+            };
+            self.state.modal = Modal::TicketLinkPicker {
+                worktree_id: wt_id.clone(),
+                repo_id,
+                query: String::new(),
+                selected: 0,
             };
         } else {
             self.state.status_message = Some("Select a worktree first".to_string());
         }
     }
 
+    /// Link the ticket highlighted in [`Modal::TicketLinkPicker`] to its worktree.
+    pub(super) fn handle_ticket_link_picker_confirm(&mut self) {
+        let Modal::TicketLinkPicker {
+            ref worktree_id,
+            selected,
+            ..
+        } = self.state.modal
+        else {
+            return;
+        };
+        let worktree_id = worktree_id.clone();
+        let Some((ticket_id, source_id)) = self
+            .state
+            .ticket_link_matches()
+            .get(selected)
+            .map(|t| (t.id.clone(), t.source_id.clone()))
+        else {
+            // Nothing matches the query; keep the picker open to edit it.
+            return;
+        };
+        self.state.modal = Modal::None;
+        match TicketSyncer::new(&self.conn).link_to_worktree(&ticket_id, &worktree_id) {
+            Ok(()) => {
+                self.state.status_message = Some(format!("Linked ticket #{source_id}"));
+                self.refresh_data();
+            }
+            Err(e) => {
+                self.state.modal = Modal::Error {
+                    message: format!("Link failed: {e}"),
+                };
+            }
+        }
+    }
+
     /// Spawn a background thread to run `check_main_health()` before creating a worktree.
     ///
     /// Shows a non-dismissable `Modal::Progress`. On completion, sends
     /// `Action::MainHealthCheckComplete` which `action_dispatch.rs` handles.
+    /// Start creating a worktree, asking to clone the repo first when it
+    /// isn't on disk.
+    pub(super) fn create_worktree_or_clone(
+        &mut self,
+        repo_slug: String,
+        wt_name: String,
+        ticket_id: Option<String>,
+        from_pr: Option<u32>,
+        from_branch: Option<String>,
+    ) {
+        let needs_clone = self
+            .state
+            .data
+            .repos
+            .iter()
+            .find(|r| r.slug == repo_slug)
+            .map(|r| !std::path::Path::new(&r.local_path).exists())
+            .unwrap_or(false);
+
+        if needs_clone {
+            self.state.modal = Modal::Confirm {
+                title: "Clone Required".to_string(),
+                message: format!("Repo '{}' is not cloned locally. Clone it now?", repo_slug),
+                on_confirm: ConfirmAction::CreateWorktree {
+                    repo_slug,
+                    wt_name,
+                    ticket_id,
+                    from_pr,
+                    from_branch,
+                    force_dirty: false,
+                },
+            };
+        } else {
+            self.spawn_main_health_check(repo_slug, wt_name, ticket_id, from_pr, from_branch);
+        }
+    }
+
     pub(super) fn spawn_main_health_check(
         &mut self,
         repo_slug: String,
@@ -624,6 +826,155 @@ impl App {
             });
         });
     }
+
+    /// Clear the selected worktree's upstream ticket change badge.
+    pub(super) fn handle_acknowledge_ticket_change(&mut self) {
+        let Some(wt) = self.state.selected_worktree().cloned() else {
+            return;
+        };
+        if wt.ticket_change.is_none() {
+            self.state.status_message = Some(format!("No ticket changes on {}", wt.slug));
+            return;
+        }
+        match WorktreeManager::new(&self.conn, &self.config).acknowledge_ticket_change(&wt.id) {
+            Ok(_) => {
+                self.state.status_message =
+                    Some(format!("Acknowledged ticket changes on {}", wt.slug));
+                self.refresh_data();
+            }
+            Err(e) => {
+                self.state.status_message =
+                    Some(format!("Failed to acknowledge ticket changes: {e}"));
+            }
+        }
+    }
+
+    /// Prompt for a comment to post on the selected ticket's upstream issue.
+    pub(super) fn handle_comment_on_ticket(&mut self) {
+        let Some(ticket) = self.writable_selected_ticket() else {
+            return;
+        };
+        self.state.modal = Modal::Input {
+            title: format!("Comment on #{}", ticket.source_id),
+            prompt: format!("Posted to {}:", ticket.source_type),
+            value: String::new(),
+            on_submit: InputAction::TicketComment {
+                ticket_id: ticket.id,
+            },
+        };
+    }
+
+    /// Ask before closing the selected ticket in its upstream tracker.
+    pub(super) fn handle_close_ticket_upstream(&mut self) {
+        let Some(ticket) = self.writable_selected_ticket() else {
+            return;
+        };
+        self.state.modal = Modal::Confirm {
+            title: "Close Ticket".to_string(),
+            message: format!(
+                "Close #{} {} in {}?",
+                ticket.source_id, ticket.title, ticket.source_type
+            ),
+            on_confirm: ConfirmAction::CloseTicketUpstream {
+                ticket_id: ticket.id,
+            },
+        };
+    }
+
+    /// The focused ticket, unless it is manual and has nowhere to write back to.
+    fn writable_selected_ticket(&mut self) -> Option<Ticket> {
+        let Some(ticket) = self.selected_ticket().cloned() else {
+            self.state.status_message = Some("No ticket selected".to_string());
+            return None;
+        };
+        if ticket.source_type == MANUAL_SOURCE_TYPE {
+            self.state.status_message = Some("Manual tickets have no upstream tracker".to_string());
+            return None;
+        }
+        Some(ticket)
+    }
+
+    /// Post `comment` and/or close ticket `ticket_id` upstream on a background
+    /// thread; the gh/acli calls can take a few seconds.
+    pub(super) fn spawn_ticket_write_back(
+        &mut self,
+        ticket_id: String,
+        comment: Option<String>,
+        close: bool,
+    ) {
+        let Some(bg_tx) = self.require_bg_tx() else {
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: if close {
+                "Closing ticket…".to_string()
+            } else {
+                "Posting comment…".to_string()
+            },
+        };
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let syncer = TicketSyncer::new(&conn);
+                if let Some(body) = comment {
+                    syncer.post_comment(&ticket_id, &body)?;
+                }
+                if close {
+                    syncer.transition_state(&ticket_id, "closed")?;
+                    return Ok("Ticket closed".to_string());
+                }
+                Ok("Comment posted".to_string())
+            })();
+            let _ = bg_tx.send(Action::TicketWriteBackComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Watch or unwatch the selected worktree or ticket.
+    pub(super) fn handle_toggle_watch(&mut self) {
+        let target = match self.state.view {
+            View::WorktreeDetail => self
+                .state
+                .selected_worktree()
+                .map(|wt| (WatchTarget::Worktree, wt.id.clone(), wt.slug.clone())),
+            View::RepoDetail if self.state.repo_detail_focus == RepoDetailFocus::Worktrees => self
+                .state
+                .detail_worktrees
+                .get(self.state.detail_wt_index)
+                .map(|wt| (WatchTarget::Worktree, wt.id.clone(), wt.slug.clone())),
+            View::RepoDetail if self.state.repo_detail_focus == RepoDetailFocus::Tickets => self
+                .state
+                .filtered_detail_tickets
+                .get(self.state.detail_ticket_index)
+                .map(|t| {
+                    (
+                        WatchTarget::Ticket,
+                        t.id.clone(),
+                        format!("#{}", t.source_id),
+                    )
+                }),
+            _ => None,
+        };
+        let Some((target, id, label)) = target else {
+            self.state.status_message = Some("Select a worktree or ticket to watch".to_string());
+            return;
+        };
+        match WatchManager::new(&self.conn).toggle(target, &id) {
+            Ok(watched) => {
+                self.state.status_message = Some(if watched {
+                    format!("Watching {label}")
+                } else {
+                    format!("Stopped watching {label}")
+                });
+                self.refresh_watches();
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to toggle watch: {e}"));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -654,6 +1005,7 @@ mod tests {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         }
     }
 
@@ -674,6 +1026,9 @@ mod tests {
             completed_at: None,
             model: None,
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         }
     }
 
@@ -762,6 +1117,8 @@ mod tests {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         };
         app.state.filtered_detail_tickets = vec![ticket];
         app.state.detail_ticket_index = 0;
@@ -903,7 +1260,7 @@ mod tests {
     // ── handle_delete ─────────────────────────────────────────────────
 
     #[test]
-    fn handle_delete_worktree_detail_opens_confirm() {
+    fn handle_delete_worktree_detail_asks_reason_then_confirms() {
         let mut app = make_test_app();
         let wt = make_test_worktree("w1", "r1", "feat-test");
         app.state.data.worktrees = vec![wt];
@@ -914,7 +1271,10 @@ mod tests {
         app.state.selected_worktree_id = Some("w1".into());
         app.state.view = View::WorktreeDetail;
         app.handle_delete();
-        // Should open a ConfirmByName modal (no ticket → work may be in progress)
+        // No ticket → work may be in progress: ask for an abandon reason,
+        // then confirm by name
+        assert!(matches!(app.state.modal, Modal::AbandonReasonPicker { .. }));
+        app.handle_abandon_reason_picker_confirm();
         assert!(matches!(app.state.modal, Modal::ConfirmByName { .. }));
     }
 
@@ -969,4 +1329,52 @@ mod tests {
             Some("Cannot modify archived worktree")
         );
     }
+
+    #[test]
+    fn ticket_link_picker_searches_and_links() {
+        crate::test_support::isolate_conductor_home();
+        let conn = conductor_core::test_helpers::setup_db();
+        use conductor_core::test_helpers::make_ticket;
+        TicketSyncer::new(&conn)
+            .upsert_tickets(
+                "r1",
+                &[
+                    make_ticket("12", "Fix login redirect"),
+                    make_ticket("34", "Add dark mode"),
+                ],
+            )
+            .unwrap();
+        let mut app = App::new(
+            conn,
+            conductor_core::config::Config::default(),
+            crate::config::TuiConfig::default(),
+            crate::theme::Theme::default(),
+        );
+        app.refresh_data();
+        app.state.selected_worktree_id = Some("w1".into());
+
+        app.handle_link_ticket();
+        assert_eq!(app.state.ticket_link_matches().len(), 2);
+        for c in "drk".chars() {
+            app.handle_action(Action::InputChar(c));
+        }
+        let matches = app.state.ticket_link_matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].source_id, "34");
+
+        app.handle_action(Action::InputSubmit);
+        assert!(matches!(app.state.modal, Modal::None));
+        assert_eq!(
+            app.state.status_message.as_deref(),
+            Some("Linked ticket #34")
+        );
+        let wt = app
+            .state
+            .data
+            .worktrees
+            .iter()
+            .find(|w| w.id == "w1")
+            .unwrap();
+        assert!(wt.ticket_id.is_some());
+    }
 }
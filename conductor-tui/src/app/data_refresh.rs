@@ -1,7 +1,8 @@
-use conductor_core::agent::AgentManager;
+use conductor_core::agent::{AgentInbox, AgentManager, AgentQueue, DiffReviews};
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::RepoManager;
 use conductor_core::tickets::TicketSyncer;
+use conductor_core::watch::{WatchManager, WatchTarget};
 use conductor_core::worktree::WorktreeManager;
 
 use super::App;
@@ -15,7 +16,9 @@ impl App {
 
         self.state.data.repos = repo_mgr.list().unwrap_or_default();
         self.state.data.worktrees = wt_mgr.list(None, true).unwrap_or_default();
+        self.state.data.stack_infos = wt_mgr.stack_infos_by_worktree().unwrap_or_default();
         self.state.data.tickets = ticket_syncer.list(None).unwrap_or_default();
+        self.state.data.ticket_progress = ticket_syncer.progress_by_ticket().unwrap_or_default();
 
         let issue_source_mgr = IssueSourceManager::new(&self.conn);
         match issue_source_mgr.count_by_repo() {
@@ -31,6 +34,11 @@ impl App {
         }
 
         self.state.data.latest_agent_runs = agent_mgr.latest_runs_by_worktree().unwrap_or_default();
+        self.state.data.agent_queue = AgentQueue::new(&self.conn).list().unwrap_or_default();
+        self.state.data.agent_inbox = AgentInbox::new(&self.conn).list().unwrap_or_default();
+        self.state.data.over_budget =
+            crate::background::over_budget_by_worktree(&agent_mgr, &self.config.budget);
+        self.refresh_watches();
 
         self.refresh_pending_feedback();
 
@@ -79,6 +87,8 @@ impl App {
             self.state.data.agent_totals = AgentTotals::default();
             self.state.data.child_runs = Vec::new();
             self.state.data.agent_created_issues = Vec::new();
+            self.state.data.branch_todos = Vec::new();
+            self.state.data.latest_diff_review = None;
             return;
         };
 
@@ -154,6 +164,14 @@ impl App {
         self.state.data.agent_created_issues = mgr
             .list_created_issues_for_worktree(wt_id)
             .unwrap_or_default();
+
+        self.state.data.branch_todos = WorktreeManager::new(&self.conn, &self.config)
+            .todos(wt_id)
+            .unwrap_or_default();
+
+        self.state.data.latest_diff_review = DiffReviews::new(&self.conn)
+            .latest_for_worktree(wt_id)
+            .unwrap_or_default();
     }
 
     /// Reload repo-scoped agent events for the currently selected repo.
@@ -216,4 +234,15 @@ impl App {
             })
         });
     }
+
+    /// Reload the watched worktree and ticket IDs.
+    pub(super) fn refresh_watches(&mut self) {
+        let watch_mgr = WatchManager::new(&self.conn);
+        self.state.data.watched_worktrees = watch_mgr
+            .watched_ids(WatchTarget::Worktree)
+            .unwrap_or_default();
+        self.state.data.watched_tickets = watch_mgr
+            .watched_ids(WatchTarget::Ticket)
+            .unwrap_or_default();
+    }
 }
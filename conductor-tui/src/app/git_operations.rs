@@ -1,9 +1,9 @@
-use conductor_core::worktree::WorktreeManager;
+use conductor_core::worktree::{PrOptions, SyncResult, SyncStrategy, Worktree, WorktreeManager};
 
 use crate::action::Action;
 use crate::background;
-use crate::state::Modal;
-use crate::state::View;
+use crate::state::{InputAction, Modal};
+use crate::state::{RepoDetailFocus, View};
 
 use super::App;
 
@@ -17,38 +17,214 @@ impl App {
             .cloned();
 
         if let Some(wt) = wt {
-            let repo_slug = match self.state.data.repo_slug_map.get(&wt.repo_id) {
-                Some(s) => s.clone(),
-                None => {
-                    self.state.status_message = Some("Cannot find repo for worktree".to_string());
-                    return;
-                }
-            };
-            let Some(bg_tx) = self.bg_tx.clone() else {
-                self.state.modal = Modal::Error {
-                    message: "Cannot push: background sender not ready.".into(),
-                };
+            self.push_worktree(&wt);
+        } else {
+            self.state.status_message = Some("Select a worktree first".to_string());
+        }
+    }
+
+    /// Push `wt`'s branch to origin on a background thread.
+    pub(super) fn push_worktree(&mut self, wt: &Worktree) {
+        let repo_slug = match self.state.data.repo_slug_map.get(&wt.repo_id) {
+            Some(s) => s.clone(),
+            None => {
+                self.state.status_message = Some("Cannot find repo for worktree".to_string());
                 return;
+            }
+        };
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot push: background sender not ready.".into(),
             };
-            self.state.modal = Modal::Progress {
-                message: "Pushing branch…".to_string(),
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: "Pushing branch…".to_string(),
+        };
+        let config = self.config.clone();
+        let wt_slug = wt.slug.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                mgr.push(&repo_slug, &wt_slug, Default::default())
+                    .map_err(anyhow::Error::from)
+            })();
+            let _ = bg_tx.send(Action::PushComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Ask for a message to commit the selected worktree's changes with.
+    pub(super) fn handle_commit_worktree(&mut self) {
+        let Some(wt) = self.state.selected_worktree().cloned() else {
+            self.state.status_message = Some("Select a worktree first".to_string());
+            return;
+        };
+        let Some(repo_slug) = self.state.data.repo_slug_map.get(&wt.repo_id).cloned() else {
+            self.state.status_message = Some("Cannot find repo for worktree".to_string());
+            return;
+        };
+        self.state.modal = Modal::Input {
+            title: "Commit Changes".to_string(),
+            prompt: format!(
+                "Commit message for {} (blank: from the agent's result):",
+                wt.slug
+            ),
+            value: String::new(),
+            on_submit: InputAction::CommitWorktree {
+                repo_slug,
+                wt_slug: wt.slug,
+            },
+        };
+    }
+
+    /// Stage and commit all of a worktree's changes on a background thread.
+    pub(super) fn commit_worktree(&mut self, repo_slug: String, wt_slug: String, message: String) {
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: super::BG_TX_NOT_READY.into(),
             };
-            let config = self.config.clone();
-            let wt_slug = wt.slug.clone();
-            std::thread::spawn(move || {
-                let result = (|| -> anyhow::Result<String> {
-                    let db = conductor_core::config::db_path();
-                    let conn = conductor_core::db::open_database(&db)?;
-                    let mgr = WorktreeManager::new(&conn, &config);
-                    mgr.push(&repo_slug, &wt_slug).map_err(anyhow::Error::from)
-                })();
-                let _ = bg_tx.send(Action::PushComplete {
-                    result: result.map_err(|e| e.to_string()),
-                });
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: "Committing changes…".to_string(),
+        };
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let message = Some(message.trim()).filter(|m| !m.is_empty());
+                let commit = WorktreeManager::new(&conn, &config)
+                    .commit(&repo_slug, &wt_slug, message, true)?;
+                Ok(format!(
+                    "Committed {} file(s) to {wt_slug}: {}",
+                    commit.files.len(),
+                    commit.subject
+                ))
+            })();
+            let _ = bg_tx.send(Action::CommitComplete {
+                result: result.map_err(|e| e.to_string()),
             });
-        } else {
+        });
+    }
+
+    /// Rebase the selected stacked worktree onto its parent.
+    pub(super) fn handle_restack(&mut self) {
+        let wt = match self.state.view {
+            View::RepoDetail if self.state.repo_detail_focus == RepoDetailFocus::Worktrees => self
+                .state
+                .detail_worktrees
+                .get(self.state.detail_wt_index)
+                .cloned(),
+            _ => self.state.selected_worktree().cloned(),
+        };
+        let Some(wt) = wt else {
+            self.state.status_message = Some("Select a worktree first".to_string());
+            return;
+        };
+        if !self.state.data.stack_infos.contains_key(&wt.id) {
+            self.state.status_message =
+                Some(format!("{} is not stacked on another worktree", wt.slug));
+            return;
+        }
+        let Some(repo_slug) = self.state.data.repo_slug_map.get(&wt.repo_id).cloned() else {
+            self.state.status_message = Some("Cannot find repo for worktree".to_string());
+            return;
+        };
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot restack: background sender not ready.".into(),
+            };
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: format!("Rebasing {} onto its parent…", wt.slug),
+        };
+        let config = self.config.clone();
+        let wt_slug = wt.slug;
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                let onto = mgr.restack(&repo_slug, &wt_slug, Default::default())?;
+                Ok(format!("Rebased {wt_slug} onto {onto}"))
+            })();
+            let _ = bg_tx.send(Action::RestackComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Rescan the selected worktree's branch for TODO and FIXME comments.
+    pub(super) fn handle_scan_branch_todos(&mut self) {
+        let Some(wt) = self.state.selected_worktree().cloned() else {
             self.state.status_message = Some("Select a worktree first".to_string());
+            return;
+        };
+        if !wt.is_active() {
+            self.state.status_message = Some("Only active worktrees can be scanned".to_string());
+            return;
         }
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot scan: background sender not ready.".into(),
+            };
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: format!("Scanning {} for TODOs…", wt.slug),
+        };
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<usize> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                Ok(mgr.scan_todos_by_id(&wt.id)?.len())
+            })();
+            let _ = bg_tx.send(Action::ScanBranchTodosComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Fetch origin and rebase the selected worktree onto its latest base.
+    pub(super) fn handle_sync_with_base(&mut self) {
+        let Some(wt) = self.state.selected_worktree().cloned() else {
+            self.state.status_message = Some("Select a worktree first".to_string());
+            return;
+        };
+        let Some(repo_slug) = self.state.data.repo_slug_map.get(&wt.repo_id).cloned() else {
+            self.state.status_message = Some("Cannot find repo for worktree".to_string());
+            return;
+        };
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot sync: background sender not ready.".into(),
+            };
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: format!("Syncing {} with its base branch…", wt.slug),
+        };
+        let config = self.config.clone();
+        let wt_slug = wt.slug;
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<SyncResult> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                Ok(mgr.sync_with_base(&repo_slug, &wt_slug, SyncStrategy::Rebase)?)
+            })();
+            let _ = bg_tx.send(Action::SyncWithBaseComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
     }
 
     pub(super) fn handle_create_pr(&mut self) {
@@ -60,41 +236,46 @@ impl App {
             .cloned();
 
         if let Some(wt) = wt {
-            let repo_slug = match self.state.data.repo_slug_map.get(&wt.repo_id) {
-                Some(s) => s.clone(),
-                None => {
-                    self.state.status_message = Some("Cannot find repo for worktree".to_string());
-                    return;
-                }
-            };
-            let Some(bg_tx) = self.bg_tx.clone() else {
-                self.state.modal = Modal::Error {
-                    message: "Cannot create PR: background sender not ready.".into(),
-                };
-                return;
-            };
-            self.state.modal = Modal::Progress {
-                message: "Creating PR…".to_string(),
-            };
-            let config = self.config.clone();
-            let wt_slug = wt.slug.clone();
-            std::thread::spawn(move || {
-                let result = (|| -> anyhow::Result<String> {
-                    let db = conductor_core::config::db_path();
-                    let conn = conductor_core::db::open_database(&db)?;
-                    let mgr = WorktreeManager::new(&conn, &config);
-                    mgr.create_pr(&repo_slug, &wt_slug, false)
-                        .map_err(anyhow::Error::from)
-                })();
-                let _ = bg_tx.send(Action::PrCreateComplete {
-                    result: result.map_err(|e| e.to_string()),
-                });
-            });
+            self.create_pr_for(&wt);
         } else {
             self.state.status_message = Some("Select a worktree first".to_string());
         }
     }
 
+    /// Open a PR for `wt`'s branch on a background thread.
+    pub(super) fn create_pr_for(&mut self, wt: &Worktree) {
+        let repo_slug = match self.state.data.repo_slug_map.get(&wt.repo_id) {
+            Some(s) => s.clone(),
+            None => {
+                self.state.status_message = Some("Cannot find repo for worktree".to_string());
+                return;
+            }
+        };
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: "Cannot create PR: background sender not ready.".into(),
+            };
+            return;
+        };
+        self.state.modal = Modal::Progress {
+            message: "Creating PR…".to_string(),
+        };
+        let config = self.config.clone();
+        let wt_slug = wt.slug.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<String> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let mgr = WorktreeManager::new(&conn, &config);
+                mgr.create_pr(&repo_slug, &wt_slug, PrOptions::default())
+                    .map_err(anyhow::Error::from)
+            })();
+            let _ = bg_tx.send(Action::PrCreateComplete {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
     pub(super) fn handle_sync_tickets(&mut self) {
         if self.state.ticket_sync_in_progress {
             self.state.status_message = Some("Sync already in progress...".to_string());
@@ -201,53 +201,6 @@ pub(super) fn format_metadata_entries(entries: &[MetadataEntry]) -> String {
     parts.join("\n")
 }
 
-/// Derive a worktree slug from a ticket's source_id, title, and labels.
-/// Format: `{prefix}-{source_id}-{slugified-title}`, e.g. `feat-15-tui-create-worktree`.
-/// The prefix is inferred from labels via `label_to_branch_prefix`.
-/// Title portion is truncated to keep the total slug under ~40 chars.
-pub(super) fn derive_worktree_slug(source_id: &str, title: &str, labels: &[String]) -> String {
-    let refs: Vec<&str> = labels.iter().map(String::as_str).collect();
-    let prefix = conductor_core::worktree::label_to_branch_prefix(&refs);
-
-    let slug: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
-        .collect();
-    // Collapse consecutive dashes
-    let mut collapsed = String::with_capacity(slug.len());
-    let mut prev_dash = false;
-    for c in slug.chars() {
-        if c == '-' {
-            if !prev_dash {
-                collapsed.push('-');
-            }
-            prev_dash = true;
-        } else {
-            collapsed.push(c);
-            prev_dash = false;
-        }
-    }
-    let title_slug = collapsed.trim_matches('-');
-
-    // Budget: 40 chars total, minus prefix, separator, source_id, and separator
-    let budget = 40_usize.saturating_sub(prefix.len() + 1 + source_id.len() + 1);
-    let truncated = if title_slug.len() <= budget {
-        title_slug
-    } else {
-        match title_slug[..budget].rfind('-') {
-            Some(pos) => &title_slug[..pos],
-            None => &title_slug[..budget],
-        }
-    };
-
-    if truncated.is_empty() {
-        format!("{prefix}-{source_id}")
-    } else {
-        format!("{prefix}-{source_id}-{truncated}")
-    }
-}
-
 /// Send a workflow execution result through the background channel.
 ///
 /// Shared by all `spawn_*_workflow_in_background` helpers to avoid
@@ -431,79 +384,6 @@ mod tests {
         assert!(result.contains("── Body ──"));
     }
 
-    // ── derive_worktree_slug ────────────────────────────────────────────────
-
-    #[test]
-    fn derive_slug_normal() {
-        let slug = derive_worktree_slug("123", "Add login flow", &[]);
-        assert_eq!(slug, "feat-123-add-login-flow");
-    }
-
-    #[test]
-    fn derive_slug_special_chars() {
-        let slug = derive_worktree_slug("42", "Fix: null-ptr crash!!", &[]);
-        assert_eq!(slug, "feat-42-fix-null-ptr-crash");
-    }
-
-    #[test]
-    fn derive_slug_consecutive_dashes() {
-        let slug = derive_worktree_slug("7", "hello---world   test", &[]);
-        assert_eq!(slug, "feat-7-hello-world-test");
-    }
-
-    #[test]
-    fn derive_slug_long_title_truncation() {
-        let long_title = "a".repeat(100);
-        let slug = derive_worktree_slug("99", &long_title, &[]);
-        // Total should be ≤ 40 chars
-        assert!(slug.len() <= 40, "slug too long: {} chars", slug.len());
-        assert!(slug.starts_with("feat-99-"));
-    }
-
-    #[test]
-    fn derive_slug_empty_title() {
-        assert_eq!(derive_worktree_slug("123", "", &[]), "feat-123");
-    }
-
-    #[test]
-    fn derive_slug_all_special_chars() {
-        assert_eq!(derive_worktree_slug("42", "!!@@##", &[]), "feat-42");
-    }
-
-    #[test]
-    fn derive_slug_whitespace_only() {
-        assert_eq!(derive_worktree_slug("7", "   ", &[]), "feat-7");
-    }
-
-    #[test]
-    fn derive_slug_bug_label_produces_fix_prefix() {
-        let slug = derive_worktree_slug("42", "null ptr crash", &["bug".to_string()]);
-        assert!(slug.starts_with("fix-42-"), "got: {slug}");
-    }
-
-    #[test]
-    fn derive_slug_chore_label_produces_chore_prefix() {
-        let slug = derive_worktree_slug("7", "clean up deps", &["chore".to_string()]);
-        assert!(slug.starts_with("chore-7-"), "got: {slug}");
-    }
-
-    #[test]
-    fn derive_slug_unknown_label_falls_back_to_feat() {
-        let slug = derive_worktree_slug("5", "some work", &["wontfix".to_string()]);
-        assert!(slug.starts_with("feat-5-"), "got: {slug}");
-    }
-
-    #[test]
-    fn derive_slug_with_labels_total_length_within_40() {
-        let long_title = "a".repeat(100);
-        let slug = derive_worktree_slug("99", &long_title, &["refactor".to_string()]);
-        assert!(
-            slug.len() <= 40,
-            "slug too long: {} chars: {slug}",
-            slug.len()
-        );
-    }
-
     // ── build_form_fields ───────────────────────────────────────────────────
 
     #[test]
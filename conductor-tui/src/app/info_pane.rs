@@ -84,10 +84,19 @@ impl App {
         let row = self.state.worktree_detail_selected_row;
         match row {
             info_row::PATH => {
-                let Some(path) = self.state.selected_worktree().map(|wt| wt.path.clone()) else {
+                let Some(wt) = self.state.selected_worktree() else {
                     return;
                 };
-                self.open_terminal_at_path(&path);
+                let repo_slug = self
+                    .state
+                    .data
+                    .repo_slug_map
+                    .get(&wt.repo_id)
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                let window = conductor_core::tmux::worktree_window_name(repo_slug, &wt.slug);
+                let path = wt.path.clone();
+                self.open_terminal_at_path(&path, &window);
             }
             info_row::TICKET => {
                 let url = self
@@ -153,11 +162,13 @@ impl App {
             },
             repo_info_row::PATH => {
                 let path = repo.local_path.clone();
-                self.open_terminal_at_path(&path);
+                let window = conductor_core::tmux::repo_window_name(&repo.slug, None);
+                self.open_terminal_at_path(&path, &window);
             }
             repo_info_row::WORKTREES_DIR => {
                 let path = repo.workspace_dir.clone();
-                self.open_terminal_at_path(&path);
+                let window = conductor_core::tmux::repo_window_name(&repo.slug, Some("worktrees"));
+                self.open_terminal_at_path(&path, &window);
             }
             _ => {
                 self.state.status_message = Some("No action for this row".to_string());
@@ -183,6 +194,7 @@ impl App {
                 .model
                 .clone()
                 .unwrap_or_else(|| "(not set)".to_string()),
+            repo_info_row::TOOLCHAIN => repo.toolchain.to_string(),
             _ => return,
         };
         self.copy_text_to_clipboard(text);
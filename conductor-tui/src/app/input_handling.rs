@@ -1,12 +1,10 @@
 use conductor_core::agent::AgentManager;
 use conductor_core::config::Config;
 use conductor_core::repo::{derive_local_path, derive_slug_from_url, RepoManager};
-use conductor_core::tickets::TicketSyncer;
 use conductor_core::worktree::WorktreeManager;
 
 use crate::state::{
-    BranchPickerItem, ConfirmAction, FormAction, FormField, FormFieldType, InputAction, Modal,
-    RuntimeSection,
+    BranchPickerItem, FormAction, FormField, FormFieldType, InputAction, Modal, RuntimeSection,
 };
 
 use super::helpers::advance_form_field;
@@ -266,39 +264,47 @@ impl App {
         }
     }
 
-    /// Dynamically add or remove Jira-specific fields based on the current
-    /// value of the Type field (field 0).  Called whenever the type field changes.
+    /// Dynamically add or remove source-specific fields (Jira JQL/URL, Linear
+    /// team key, Asana project) based on the current value of the Type field (field 0).
+    /// Called whenever the type field changes.
     pub(super) fn sync_issue_source_form_fields(fields: &mut Vec<FormField>) {
         let type_val = fields
             .first()
             .map(|f| f.value.trim().to_lowercase())
             .unwrap_or_default();
 
-        let is_jira = type_val == "jira" || type_val == "j";
+        let extra: &[(&str, &str)] = match type_val.as_str() {
+            "jira" | "j" => &[
+                ("JQL", "e.g. project = PROJ AND status != Done"),
+                ("Jira URL", "e.g. https://mycompany.atlassian.net"),
+            ],
+            "linear" | "l" => &[(
+                "Team key",
+                "e.g. ENG (API key is read from $LINEAR_API_KEY)",
+            )],
+            "asana" | "a" => &[(
+                "Project GID",
+                "e.g. 1200000000000000 (token is read from $ASANA_TOKEN)",
+            )],
+            _ => &[],
+        };
 
-        if is_jira && fields.len() == 1 {
-            // Add JQL and URL fields
-            fields.push(FormField {
-                label: "JQL".to_string(),
-                value: String::new(),
-                placeholder: "e.g. project = PROJ AND status != Done".to_string(),
-                manually_edited: false,
-                required: true,
-                readonly: false,
-                field_type: FormFieldType::Text,
-            });
+        let current: Vec<&str> = fields.iter().skip(1).map(|f| f.label.as_str()).collect();
+        let wanted: Vec<&str> = extra.iter().map(|(label, _)| *label).collect();
+        if current == wanted {
+            return;
+        }
+        fields.truncate(1);
+        for (label, placeholder) in extra {
             fields.push(FormField {
-                label: "Jira URL".to_string(),
+                label: label.to_string(),
                 value: String::new(),
-                placeholder: "e.g. https://mycompany.atlassian.net".to_string(),
+                placeholder: placeholder.to_string(),
                 manually_edited: false,
                 required: true,
                 readonly: false,
                 field_type: FormFieldType::Text,
             });
-        } else if !is_jira && fields.len() > 1 {
-            // Remove extra fields when switching away from Jira
-            fields.truncate(1);
         }
     }
 
@@ -375,6 +381,18 @@ impl App {
             return;
         }
 
+        // AbandonReasonPicker: move on to the confirm-by-name step
+        if matches!(self.state.modal, Modal::AbandonReasonPicker { .. }) {
+            self.handle_abandon_reason_picker_confirm();
+            return;
+        }
+
+        // TicketLinkPicker: link the highlighted match
+        if matches!(self.state.modal, Modal::TicketLinkPicker { .. }) {
+            self.handle_ticket_link_picker_confirm();
+            return;
+        }
+
         // ConfirmByName: only proceed if typed value matches expected slug
         if let Modal::ConfirmByName {
             ref expected,
@@ -466,16 +484,29 @@ impl App {
                         use crate::action::Action;
                         use crate::state::BranchPickerItem;
                         use conductor_core::repo::RepoManager;
-                        use conductor_core::worktree::WorktreeManager;
+                        use conductor_core::worktree::{BaseBranchSource, WorktreeManager};
 
                         let result = (|| {
                             let (conn, config) = load_db_and_config()?;
                             let repo = RepoManager::new(&conn, &config)
                                 .get_by_slug(&slug)
                                 .map_err(|e| format!("Failed to get repo '{slug}': {e}"))?;
-                            let worktrees = WorktreeManager::new(&conn, &config)
+                            let wt_mgr = WorktreeManager::new(&conn, &config);
+                            let worktrees = wt_mgr
                                 .list_by_repo_id(&repo.id, true)
                                 .map_err(|e| format!("Failed to list worktrees: {e}"))?;
+                            // Shown on the default entry so the ticket's base is
+                            // visible before confirming.
+                            let resolved_base = match tid.as_deref() {
+                                Some(t) => {
+                                    Some(wt_mgr.resolve_base(&slug, Some(t)).map_err(|e| {
+                                        format!("Failed to resolve base branch: {e}")
+                                    })?)
+                                }
+                                None => None,
+                            }
+                            .filter(|b| b.source != BaseBranchSource::RepoDefault)
+                            .map(|b| b.to_string());
                             let items = worktrees
                                 .into_iter()
                                 .map(|wt| BranchPickerItem {
@@ -485,17 +516,19 @@ impl App {
                                     base_branch: None,
                                     stale_days: None,
                                     inferred_from: Some(wt.slug),
+                                    resolved_base: None,
                                 })
                                 .collect();
-                            Ok::<Vec<BranchPickerItem>, String>(items)
+                            Ok::<_, String>((items, resolved_base))
                         })();
                         match result {
-                            Ok(items) => {
+                            Ok((items, resolved_base)) => {
                                 let _ = tx.send(Action::WorktreeBranchesLoaded {
                                     repo_slug: slug,
                                     wt_name: name,
                                     ticket_id: tid,
                                     items,
+                                    resolved_base,
                                 });
                             }
                             Err(error) => {
@@ -534,79 +567,7 @@ impl App {
                     }
                 };
 
-                // Check if the repo needs to be cloned first.
-                let needs_clone = self
-                    .state
-                    .data
-                    .repos
-                    .iter()
-                    .find(|r| r.slug == repo_slug)
-                    .map(|r| !std::path::Path::new(&r.local_path).exists())
-                    .unwrap_or(false);
-
-                if needs_clone {
-                    self.state.modal = Modal::Confirm {
-                        title: "Clone Required".to_string(),
-                        message: format!(
-                            "Repo '{}' is not cloned locally. Clone it now?",
-                            repo_slug
-                        ),
-                        on_confirm: ConfirmAction::CreateWorktree {
-                            repo_slug,
-                            wt_name,
-                            ticket_id,
-                            from_pr,
-                            from_branch,
-                            force_dirty: false,
-                        },
-                    };
-                } else {
-                    self.spawn_main_health_check(
-                        repo_slug,
-                        wt_name,
-                        ticket_id,
-                        from_pr,
-                        from_branch,
-                    );
-                }
-            }
-            InputAction::LinkTicket { worktree_id } => {
-                if value.is_empty() {
-                    return;
-                }
-                let syncer = TicketSyncer::new(&self.conn);
-                // Find ticket by source_id, scoped to the worktree's repo
-                let wt_repo_id = self
-                    .state
-                    .data
-                    .worktrees
-                    .iter()
-                    .find(|w| w.id == worktree_id)
-                    .map(|w| w.repo_id.as_str());
-                let ticket = self
-                    .state
-                    .data
-                    .tickets
-                    .iter()
-                    .find(|t| t.source_id == value && Some(t.repo_id.as_str()) == wt_repo_id);
-                if let Some(t) = ticket {
-                    match syncer.link_to_worktree(&t.id, &worktree_id) {
-                        Ok(()) => {
-                            self.state.status_message =
-                                Some(format!("Linked ticket #{}", t.source_id));
-                            self.refresh_data();
-                        }
-                        Err(e) => {
-                            self.state.modal = Modal::Error {
-                                message: format!("Link failed: {e}"),
-                            };
-                        }
-                    }
-                } else {
-                    self.state.modal = Modal::Error {
-                        message: format!("Ticket #{value} not found"),
-                    };
-                }
+                self.create_worktree_or_clone(repo_slug, wt_name, ticket_id, from_pr, from_branch);
             }
             InputAction::AgentPrompt {
                 worktree_id,
@@ -704,6 +665,8 @@ impl App {
                     resume_session_id,
                     model,
                     runtime,
+                    false,
+                    None,
                 );
             }
             InputAction::WorkflowModelOverride {
@@ -761,6 +724,12 @@ impl App {
                 };
                 self.spawn_set_repo_model(slug, model);
             }
+            InputAction::TicketComment { ticket_id } => {
+                if value.trim().is_empty() {
+                    return;
+                }
+                self.spawn_ticket_write_back(ticket_id, Some(value), false);
+            }
             InputAction::FeedbackResponse { feedback_id } => {
                 if value.is_empty() {
                     return;
@@ -809,6 +778,14 @@ impl App {
             | InputAction::SettingsEditEnvValue { .. } => {
                 self.handle_settings_input_submit(on_submit, value);
             }
+            InputAction::CommitWorktree { repo_slug, wt_slug } => {
+                self.commit_worktree(repo_slug, wt_slug, value);
+            }
+            InputAction::WorktreeFromUrl => {
+                if !value.trim().is_empty() {
+                    self.resolve_worktree_url(value);
+                }
+            }
             InputAction::AdoptWorktree { repo_slug } => {
                 if value.trim().is_empty() {
                     return;
@@ -880,6 +857,8 @@ impl App {
                     resume_session_id,
                     model,
                     runtime,
+                    false,
+                    None,
                 );
             }
             InputAction::SettingsSetModel | InputAction::SettingsSetSyncInterval => {
@@ -1084,8 +1063,12 @@ impl App {
         wt_name: String,
         ticket_id: Option<String>,
         items: Vec<crate::state::BranchPickerItem>,
+        resolved_base: Option<String>,
     ) {
-        let mut items_with_sentinel = vec![crate::state::BranchPickerItem::default()];
+        let mut items_with_sentinel = vec![crate::state::BranchPickerItem {
+            resolved_base,
+            ..Default::default()
+        }];
         items_with_sentinel.extend(items);
         let (ordered, tree_positions) =
             crate::state::build_branch_picker_tree(&items_with_sentinel);
@@ -1780,6 +1763,7 @@ mod tests {
             "my-wt".to_string(),
             None,
             worktree_items,
+            None,
         );
         match &app.state.modal {
             Modal::BranchPicker { items, .. } => {
@@ -1793,10 +1777,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn worktree_branches_loaded_shows_ticket_base_on_sentinel() {
+        let mut app = make_app();
+        app.handle_worktree_branches_loaded(
+            "repo".to_string(),
+            "my-wt".to_string(),
+            Some("t1".to_string()),
+            vec![],
+            Some("release/1.x (label 'hotfix')".to_string()),
+        );
+        match &app.state.modal {
+            Modal::BranchPicker {
+                items, selected, ..
+            } => {
+                assert_eq!(*selected, 0);
+                assert!(items[0].branch.is_none());
+                assert_eq!(
+                    items[0].resolved_base.as_deref(),
+                    Some("release/1.x (label 'hotfix')")
+                );
+            }
+            other => panic!("expected BranchPicker modal, got {:?}", other),
+        }
+    }
+
     #[test]
     fn worktree_branches_loaded_no_worktrees_shows_picker_with_only_sentinel() {
         let mut app = make_app();
-        app.handle_worktree_branches_loaded("repo".to_string(), "my-wt".to_string(), None, vec![]);
+        app.handle_worktree_branches_loaded(
+            "repo".to_string(),
+            "my-wt".to_string(),
+            None,
+            vec![],
+            None,
+        );
         match &app.state.modal {
             Modal::BranchPicker { items, .. } => {
                 assert_eq!(items.len(), 1, "only the default-branch sentinel");
@@ -1851,6 +1866,9 @@ mod tests {
                 completed_at: None,
                 model: None,
                 base_branch: None,
+                abandon_reason: None,
+                missing_since: None,
+                ticket_change: None,
             });
         app.state.data.repos.push(conductor_core::repo::Repo {
             id: "r1".to_string(),
@@ -1863,6 +1881,7 @@ mod tests {
             model: None,
             allow_agent_issue_creation: true,
             runtime_overrides: None,
+            toolchain: Default::default(),
         });
         app.state
             .data
@@ -1915,6 +1934,9 @@ mod tests {
                 completed_at: None,
                 model: None,
                 base_branch: None,
+                abandon_reason: None,
+                missing_since: None,
+                ticket_change: None,
             });
         app.state.data.repos.push(conductor_core::repo::Repo {
             id: "r1".to_string(),
@@ -1927,6 +1949,7 @@ mod tests {
             model: None,
             allow_agent_issue_creation: true,
             runtime_overrides: None,
+            toolchain: Default::default(),
         });
         app.state
             .data
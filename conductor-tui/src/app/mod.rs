@@ -11,7 +11,7 @@ use conductor_core::config::Config;
 use crate::action::Action;
 use crate::background;
 use crate::config::TuiConfig;
-use crate::event::{BackgroundSender, EventLoop};
+use crate::event::{BackgroundSender, EventLoop, InputEvent};
 use crate::input;
 use crate::state::AppState;
 use crate::theme::Theme;
@@ -20,6 +20,8 @@ use crate::ui;
 mod action_dispatch;
 mod agent_events;
 mod agent_execution;
+mod agent_inbox;
+mod agent_run_detail;
 mod crud_operations;
 mod data_refresh;
 mod git_operations;
@@ -28,11 +30,15 @@ mod helpers;
 mod info_pane;
 mod input_handling;
 mod modal_dialog;
+mod my_queue;
 mod navigation;
 mod settings_management;
 mod theme_management;
+mod today;
 mod url_operations;
 mod workflow_management;
+mod worktree_diff;
+mod worktree_from_url;
 
 #[cfg(test)]
 mod tests;
@@ -60,7 +66,11 @@ impl App {
     pub fn new(conn: Connection, config: Config, tui_config: TuiConfig, theme: Theme) -> Self {
         let mut state = AppState::new();
         state.theme = theme;
-        Self {
+        // A read-only connection means the database was locked at startup.
+        let read_only = conn
+            .is_readonly(rusqlite::DatabaseName::Main)
+            .unwrap_or(false);
+        let mut app = Self {
             state,
             conn,
             config,
@@ -71,7 +81,11 @@ impl App {
             workflow_shutdown: Arc::new(AtomicBool::new(false)),
             selected_worktree_id_shared: Arc::new(Mutex::new(None)),
             selected_repo_id_shared: Arc::new(Mutex::new(None)),
+        };
+        if read_only {
+            app.track_db_snapshot(true);
         }
+        app
     }
 
     /// Main run loop.
@@ -90,7 +104,10 @@ impl App {
             Arc::clone(&self.selected_worktree_id_shared),
             Arc::clone(&self.selected_repo_id_shared),
         );
+        background::spawn_tool_probe(bg_tx.clone(), false);
         let sync_mins = self.config.general.sync_interval_minutes as u64;
+        #[cfg(unix)]
+        background::spawn_daemon_listener(bg_tx.clone());
         background::spawn_ticket_sync(bg_tx, Duration::from_secs(sync_mins * 60));
 
         let mut dirty = true; // tracks whether state changed since last draw
@@ -106,9 +123,14 @@ impl App {
             events.wait();
 
             // PRIORITY 1: Drain all key events first — input is never starved
-            for key in events.drain_input() {
-                let action = input::map_key(key, &self.state);
-                dirty |= self.update(action);
+            for event in events.drain_input() {
+                match event {
+                    InputEvent::Key(key) => {
+                        let action = input::map_key(key, &self.state);
+                        dirty |= self.update(action);
+                    }
+                    InputEvent::Paste(text) => dirty |= self.handle_paste(&text),
+                }
             }
 
             // PRIORITY 2: Drain all background events
@@ -143,6 +165,17 @@ impl App {
         Ok(())
     }
 
+    /// Fetch open PRs for a repo in the background, unless `gh` is known to
+    /// be missing (the PR pane says so instead).
+    pub(super) fn spawn_pr_fetch(&self, remote_url: String, repo_id: String) {
+        if self.state.tool_missing(conductor_core::tools::GH) {
+            return;
+        }
+        if let Some(ref tx) = self.bg_tx {
+            background::spawn_pr_fetch_once(tx.clone(), remote_url, repo_id);
+        }
+    }
+
     /// Return a cloned background sender, or show an error modal and return `None`.
     ///
     /// Use this as a guard at the top of any action that requires the background
@@ -163,12 +196,14 @@ impl App {
 
     /// Handle an action by mutating state. Returns true if the UI needs a redraw.
     ///
-    /// This thin wrapper delegates to `handle_action` and updates
-    /// `status_message_at` whenever the status message presence changes.
+    /// This thin wrapper delegates to `handle_action`, updates
+    /// `status_message_at` whenever the status message presence changes, and
+    /// records each new status message in the message log.
     pub(crate) fn update(&mut self, action: Action) -> bool {
-        let had_message = self.state.status_message.is_some();
+        let previous = self.state.status_message.clone();
         let dirty = self.handle_action(action);
-        self.state.track_status_message_change(had_message);
+        self.state.track_status_message_change(previous.is_some());
+        self.state.log_status_message_change(previous.as_deref());
         dirty
     }
 }
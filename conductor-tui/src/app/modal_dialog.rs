@@ -1,3 +1,4 @@
+use conductor_core::agent::AgentManager;
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::RepoManager;
 use conductor_core::worktree::{WorktreeCreateOptions, WorktreeManager};
@@ -37,7 +38,11 @@ impl App {
                     },
                 );
             }
-            ConfirmAction::DeleteWorktree { repo_slug, wt_slug } => {
+            ConfirmAction::DeleteWorktree {
+                repo_slug,
+                wt_slug,
+                reason,
+            } => {
                 let Some(bg_tx) = self.require_bg_tx() else {
                     return;
                 };
@@ -50,8 +55,8 @@ impl App {
                         let db = conductor_core::config::db_path();
                         let conn = conductor_core::db::open_database(&db)?;
                         let wt_mgr = WorktreeManager::new(&conn, &config);
-                        let wt = wt_mgr.delete(&repo_slug, &wt_slug)?;
-                        Ok(wt.status.to_string())
+                        let wt = wt_mgr.delete(&repo_slug, &wt_slug, reason)?;
+                        Ok(wt.status_label())
                     })();
                     let _ = bg_tx.send(Action::WorktreeDeleteComplete {
                         wt_slug,
@@ -80,6 +85,9 @@ impl App {
                     });
                 });
             }
+            ConfirmAction::CloseTicketUpstream { ticket_id } => {
+                self.spawn_ticket_write_back(ticket_id, None, true);
+            }
             ConfirmAction::CancelWorkflow { workflow_run_id } => {
                 let Some(bg_tx) = self.require_bg_tx() else {
                     return;
@@ -274,12 +282,65 @@ impl App {
                     }
                 }
             }
+            ConfirmAction::StopAllAgents => {
+                let Some(bg_tx) = self.require_bg_tx() else {
+                    return;
+                };
+                self.state.modal = Modal::Progress {
+                    message: "Stopping all agents…".to_string(),
+                };
+                std::thread::spawn(move || {
+                    let result = (|| -> anyhow::Result<String> {
+                        let db = conductor_core::config::db_path();
+                        let conn = conductor_core::db::open_database(&db)?;
+                        let summary = AgentManager::new(&conn).stop_all()?;
+                        let mut msg = format!("Stopped {} agent run(s)", summary.stopped.len());
+                        if summary.queued > 0 {
+                            msg.push_str(&format!(
+                                "; {} queued run(s) will still start",
+                                summary.queued
+                            ));
+                        }
+                        Ok(msg)
+                    })();
+                    let _ = bg_tx.send(Action::AgentStopComplete {
+                        result: result.map_err(|e| format!("Failed to stop agents: {e}")),
+                    });
+                });
+            }
+            ConfirmAction::LaunchAgentOverBudget { launch } => {
+                self.launch_agent_over_budget(*launch);
+            }
             ConfirmAction::Quit => {
                 self.state.should_quit = true;
             }
         }
     }
 
+    pub(super) fn show_confirm_stop_all(&mut self) {
+        let active = self
+            .state
+            .data
+            .latest_agent_runs
+            .values()
+            .chain(self.state.data.latest_repo_agent_runs.values())
+            .filter(|r| r.is_active())
+            .count();
+        if active == 0 {
+            self.state.status_message = Some("No running agents".to_string());
+            return;
+        }
+        self.state.modal = Modal::Confirm {
+            title: "Stop All Agents".to_string(),
+            message: format!(
+                "Stop {active} running agent{}? Runs are marked cancelled and their \
+                 processes killed; logs are kept.",
+                if active == 1 { "" } else { "s" }
+            ),
+            on_confirm: ConfirmAction::StopAllAgents,
+        };
+    }
+
     pub(super) fn show_confirm_quit(&mut self) {
         let running = self
             .state
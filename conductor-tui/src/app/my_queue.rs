@@ -0,0 +1,91 @@
+use conductor_core::tickets::{QueueScope, QueueTicket, TicketSyncer};
+
+use crate::action::Action;
+use crate::state::{ColumnFocus, View};
+
+use super::App;
+
+impl App {
+    /// Transition to the My Queue view and load it in the background.
+    pub(super) fn handle_open_my_queue(&mut self) {
+        if self.state.view != View::MyQueue {
+            self.state.previous_view = Some(self.state.view);
+            self.state.view = View::MyQueue;
+        }
+        self.state.column_focus = ColumnFocus::Content;
+        self.refresh_my_queue();
+    }
+
+    pub(super) fn refresh_my_queue(&mut self) {
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            return;
+        };
+        self.state.my_queue_loading = true;
+        let identity = self.config.identity.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<Vec<QueueTicket>> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                Ok(TicketSyncer::new(&conn).my_queue(&identity, QueueScope::All)?)
+            })();
+            let _ = bg_tx.send(Action::MyQueueLoaded {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    pub(super) fn handle_my_queue_loaded(&mut self, result: Result<Vec<QueueTicket>, String>) {
+        self.state.my_queue_loading = false;
+        match result {
+            Ok(items) => {
+                self.state.my_queue_items = items;
+                let len = self.state.my_queue_items.len();
+                self.state.my_queue_index = self.state.my_queue_index.min(len.saturating_sub(1));
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load My Queue: {e}"));
+            }
+        }
+    }
+
+    /// Jump to the selected ticket's active worktree, else its repo.
+    pub(super) fn my_queue_select(&mut self) {
+        let Some(item) = self.state.my_queue_items.get(self.state.my_queue_index) else {
+            return;
+        };
+        let ticket = &item.ticket;
+        if let Some(wt) = self
+            .state
+            .data
+            .worktrees
+            .iter()
+            .find(|w| w.is_active() && w.ticket_id.as_deref() == Some(ticket.id.as_str()))
+            .cloned()
+        {
+            self.open_worktree_detail(&wt, View::MyQueue);
+            return;
+        }
+        if let Some(idx) = self
+            .state
+            .data
+            .repos
+            .iter()
+            .position(|r| r.id == ticket.repo_id)
+        {
+            self.navigate_to_repo_detail(idx);
+        }
+    }
+
+    pub(super) fn handle_my_queue_open_url(&mut self) {
+        let url = self
+            .state
+            .my_queue_items
+            .get(self.state.my_queue_index)
+            .map(|i| i.ticket.url.clone())
+            .filter(|u| !u.is_empty());
+        match url {
+            Some(url) => self.open_url(&url, "ticket"),
+            None => self.state.status_message = Some("Ticket has no URL".to_string()),
+        }
+    }
+}
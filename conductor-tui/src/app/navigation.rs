@@ -1,9 +1,11 @@
+use conductor_core::worktree::Worktree;
 use ratatui::widgets::ListState;
 
 use crate::state::{
-    info_row, model_picker_total, repo_info_row, workflow_run_info_row, ConfirmAction,
-    DashboardRow, FormField, Modal, RepoDetailFocus, View, WorkflowDefFocus, WorkflowPickerItem,
-    WorkflowRunDetailFocus, WorkflowsFocus, WorktreeDetailFocus,
+    info_row, model_picker_total, repo_info_row, workflow_run_info_row, AgentRunDetailFocus,
+    ConfirmAction, DashboardRow, FormField, Modal, RepoDetailFocus, View, WorkflowDefFocus,
+    WorkflowPickerItem, WorkflowRunDetailFocus, WorkflowsFocus, WorktreeDetailFocus,
+    WorktreeDiffFocus, ABANDON_REASON_ROWS,
 };
 
 use super::helpers::{clamp_increment, max_scroll, wrap_decrement, wrap_increment};
@@ -155,6 +157,13 @@ impl App {
         }
         match self.state.view {
             View::Dashboard => self.show_confirm_quit(),
+            View::Today | View::MyQueue => {
+                self.state.view = self.state.previous_view.take().unwrap_or(View::Dashboard);
+            }
+            View::AgentRunDetail | View::WorktreeDiff => {
+                self.state.view = View::WorktreeDetail;
+                self.reload_agent_events();
+            }
             View::Settings => {
                 if self.state.settings_runtime_detail.is_some() {
                     self.exit_runtime_detail();
@@ -230,6 +239,13 @@ impl App {
                     self.state.worktree_detail_focus = self.state.worktree_detail_focus.next();
                 }
                 View::WorkflowDefDetail => {} // single panel — Tab is a no-op
+                View::Today | View::MyQueue => {} // single panel — Tab is a no-op
+                View::AgentRunDetail => {
+                    self.state.agent_run_detail_focus = self.state.agent_run_detail_focus.toggle();
+                }
+                View::WorktreeDiff => {
+                    self.state.worktree_diff_focus = self.state.worktree_diff_focus.toggle();
+                }
             },
         }
     }
@@ -261,6 +277,13 @@ impl App {
                     self.state.worktree_detail_focus = self.state.worktree_detail_focus.prev();
                 }
                 View::WorkflowDefDetail => {} // single panel — Tab is a no-op
+                View::Today | View::MyQueue => {} // single panel — Tab is a no-op
+                View::AgentRunDetail => {
+                    self.state.agent_run_detail_focus = self.state.agent_run_detail_focus.toggle();
+                }
+                View::WorktreeDiff => {
+                    self.state.worktree_diff_focus = self.state.worktree_diff_focus.toggle();
+                }
             },
         }
     }
@@ -439,6 +462,24 @@ impl App {
         }
     }
 
+    /// Switch to the detail view for `wt`, returning to `previous` on Esc.
+    pub(super) fn open_worktree_detail(&mut self, wt: &Worktree, previous: View) {
+        self.state.selected_worktree_id = Some(wt.id.clone());
+        self.state.selected_repo_id = Some(wt.repo_id.clone());
+        self.sync_selection_arcs();
+        self.state.previous_view = Some(previous);
+        self.state.detail_prs = Vec::new();
+        self.state.pr_last_fetched_at = None;
+        self.state.view = View::WorktreeDetail;
+        *self.state.agent_list_state.borrow_mut() = ListState::default();
+        self.reload_agent_events();
+        if let Some(repo) = self.state.data.repos.iter().find(|r| r.id == wt.repo_id) {
+            let remote_url = repo.remote_url.clone();
+            let repo_id = wt.repo_id.clone();
+            self.spawn_pr_fetch(remote_url, repo_id);
+        }
+    }
+
     fn enter_workflow_run_detail(&mut self, run_id: String, worktree_id: Option<String>) {
         self.state.previous_selected_worktree_id = Some(self.state.selected_worktree_id.clone());
         if self.state.selected_worktree_id.is_none() {
@@ -473,6 +514,7 @@ impl App {
     }
 
     pub(super) fn move_up(&mut self) {
+        let ticket_matches = self.state.ticket_link_matches().len();
         match self.state.modal {
             Modal::EventDetail {
                 ref mut scroll_offset,
@@ -535,6 +577,58 @@ impl App {
                 wrap_decrement(selected, items.len());
                 return;
             }
+            Modal::AbandonReasonPicker {
+                ref mut selected, ..
+            } => {
+                wrap_decrement(selected, ABANDON_REASON_ROWS);
+                return;
+            }
+            Modal::TicketLinkPicker {
+                ref mut selected, ..
+            } => {
+                wrap_decrement(selected, ticket_matches);
+                return;
+            }
+            Modal::AgentInbox { ref mut selected } => {
+                wrap_decrement(selected, self.state.data.agent_inbox.len());
+                return;
+            }
+            Modal::MessageLog { ref mut selected } => {
+                wrap_decrement(selected, self.state.message_log.len());
+                return;
+            }
+            Modal::Roadmap {
+                ref groups,
+                ref mut selected,
+                ..
+            } => {
+                wrap_decrement(selected, groups.len());
+                return;
+            }
+            Modal::Reports {
+                ref rows,
+                ref mut selected,
+                ..
+            } => {
+                wrap_decrement(selected, rows.len());
+                return;
+            }
+            Modal::ActivityHeatmap {
+                ref heatmap,
+                ref mut selected,
+                ..
+            } => {
+                wrap_decrement(selected, heatmap.rows.len());
+                return;
+            }
+            Modal::Grooming {
+                ref proposals,
+                ref mut selected,
+                ..
+            } => {
+                wrap_decrement(selected, proposals.len());
+                return;
+            }
             Modal::GithubDiscoverOrgs {
                 ref orgs,
                 ref mut cursor,
@@ -628,11 +722,20 @@ impl App {
             View::Settings => {
                 self.settings_move_up();
             }
+            View::Today => {
+                self.state.today_index = self.state.today_index.saturating_sub(1);
+            }
+            View::MyQueue => {
+                self.state.my_queue_index = self.state.my_queue_index.saturating_sub(1);
+            }
+            View::AgentRunDetail => self.agent_run_detail_move(-1),
+            View::WorktreeDiff => self.worktree_diff_move(-1),
             _ => {}
         }
     }
 
     pub(super) fn move_down(&mut self) {
+        let ticket_matches = self.state.ticket_link_matches().len();
         match self.state.modal {
             Modal::EventDetail {
                 ref mut scroll_offset,
@@ -696,6 +799,58 @@ impl App {
                 wrap_increment(selected, items.len());
                 return;
             }
+            Modal::AbandonReasonPicker {
+                ref mut selected, ..
+            } => {
+                wrap_increment(selected, ABANDON_REASON_ROWS);
+                return;
+            }
+            Modal::TicketLinkPicker {
+                ref mut selected, ..
+            } => {
+                wrap_increment(selected, ticket_matches);
+                return;
+            }
+            Modal::AgentInbox { ref mut selected } => {
+                wrap_increment(selected, self.state.data.agent_inbox.len());
+                return;
+            }
+            Modal::MessageLog { ref mut selected } => {
+                wrap_increment(selected, self.state.message_log.len());
+                return;
+            }
+            Modal::Roadmap {
+                ref groups,
+                ref mut selected,
+                ..
+            } => {
+                wrap_increment(selected, groups.len());
+                return;
+            }
+            Modal::Reports {
+                ref rows,
+                ref mut selected,
+                ..
+            } => {
+                wrap_increment(selected, rows.len());
+                return;
+            }
+            Modal::ActivityHeatmap {
+                ref heatmap,
+                ref mut selected,
+                ..
+            } => {
+                wrap_increment(selected, heatmap.rows.len());
+                return;
+            }
+            Modal::Grooming {
+                ref proposals,
+                ref mut selected,
+                ..
+            } => {
+                wrap_increment(selected, proposals.len());
+                return;
+            }
             Modal::GithubDiscoverOrgs {
                 ref orgs,
                 ref mut cursor,
@@ -817,11 +972,22 @@ impl App {
             View::Settings => {
                 self.settings_move_down();
             }
+            View::Today => {
+                clamp_increment(&mut self.state.today_index, self.state.today_items.len());
+            }
+            View::MyQueue => {
+                clamp_increment(
+                    &mut self.state.my_queue_index,
+                    self.state.my_queue_items.len(),
+                );
+            }
+            View::AgentRunDetail => self.agent_run_detail_move(1),
+            View::WorktreeDiff => self.worktree_diff_move(1),
             _ => {}
         }
     }
 
-    fn navigate_to_repo_detail(&mut self, repo_idx: usize) {
+    pub(super) fn navigate_to_repo_detail(&mut self, repo_idx: usize) {
         if let Some(repo) = self.state.data.repos.get(repo_idx).cloned() {
             let repo_id = repo.id.clone();
             let remote_url = repo.remote_url.clone();
@@ -841,13 +1007,7 @@ impl App {
             self.state.detail_prs = Vec::new();
             self.state.detail_pr_index = 0;
             self.state.pr_last_fetched_at = None;
-            if let Some(ref tx) = self.bg_tx {
-                crate::background::spawn_pr_fetch_once(
-                    tx.clone(),
-                    remote_url.clone(),
-                    repo_id.clone(),
-                );
-            }
+            self.spawn_pr_fetch(remote_url.clone(), repo_id.clone());
             // Auto-sync tickets (staleness check happens in the background thread).
             if !self.state.ticket_sync_in_progress {
                 if let Some(ref tx) = self.bg_tx {
@@ -913,28 +1073,7 @@ impl App {
                     }
                     Some(&DashboardRow::Worktree { idx: wt_idx, .. }) => {
                         if let Some(wt) = self.state.data.worktrees.get(wt_idx).cloned() {
-                            self.state.selected_worktree_id = Some(wt.id.clone());
-                            self.state.selected_repo_id = Some(wt.repo_id.clone());
-                            self.sync_selection_arcs();
-                            self.state.previous_view = Some(View::Dashboard);
-                            self.state.detail_prs = Vec::new();
-                            self.state.pr_last_fetched_at = None;
-                            self.state.view = View::WorktreeDetail;
-                            *self.state.agent_list_state.borrow_mut() = ListState::default();
-                            self.reload_agent_events();
-                            if let Some(repo) =
-                                self.state.data.repos.iter().find(|r| r.id == wt.repo_id)
-                            {
-                                let remote_url = repo.remote_url.clone();
-                                let repo_id = wt.repo_id.clone();
-                                if let Some(ref tx) = self.bg_tx {
-                                    crate::background::spawn_pr_fetch_once(
-                                        tx.clone(),
-                                        remote_url,
-                                        repo_id,
-                                    );
-                                }
-                            }
+                            self.open_worktree_detail(&wt, View::Dashboard);
                         }
                     }
                     None => {}
@@ -1085,6 +1224,14 @@ impl App {
             View::WorktreeDetail => {}
             View::WorkflowDefDetail => {}
             View::Settings => {}
+            View::Today => self.today_select(),
+            View::MyQueue => self.my_queue_select(),
+            View::AgentRunDetail => {
+                self.state.agent_run_detail_focus = AgentRunDetailFocus::Events;
+            }
+            View::WorktreeDiff => {
+                self.state.worktree_diff_focus = WorktreeDiffFocus::Diff;
+            }
         }
     }
 }
@@ -1122,6 +1269,7 @@ mod tests {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         }
     }
 
@@ -1162,6 +1310,9 @@ mod tests {
             completed_at: None,
             model: None,
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         }
     }
 
@@ -1217,6 +1368,8 @@ mod tests {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         }];
         app.state.ticket_index = 5;
         app.clamp_indices();
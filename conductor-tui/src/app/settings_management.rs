@@ -217,6 +217,9 @@ impl App {
                 // Enter on a runtime row drills into its detail view.
                 self.handle_runtimes_edit();
             }
+            SettingsCategory::Tools => {
+                // Read-only; [r] re-checks.
+            }
         }
     }
 
@@ -302,7 +305,7 @@ impl App {
         });
     }
 
-    /// Handle [o] — open the selected hook's local script file with `open`.
+    /// Handle [o] — open the selected hook's local script file with the system default app.
     pub(super) fn handle_settings_open_hook_script(&mut self, hook_index: usize) {
         let Some(hook) = self.config.notify.hooks.get(hook_index).cloned() else {
             self.state.status_message = Some(format!("Hook index {hook_index} not found."));
@@ -320,19 +323,19 @@ impl App {
             }
         };
 
-        if !run.starts_with("~/") && !run.starts_with('/') && !run.starts_with("./") {
+        if !run.starts_with("~/")
+            && !run.starts_with("./")
+            && !std::path::Path::new(&run).is_absolute()
+        {
             self.state.status_message =
                 Some("Not a local script — edit config.toml to modify".into());
             self.state.status_message_at = Some(std::time::Instant::now());
             return;
         }
 
-        let resolved = if run.starts_with("~/") {
-            let home = std::env::var("HOME").unwrap_or_else(|_| "~".into());
-            format!("{}{}", home, &run[1..])
-        } else {
-            run.clone()
-        };
+        let resolved = conductor_core::text_util::expand_tilde(&run)
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| run.clone());
 
         if !std::path::Path::new(&resolved).exists() {
             self.state.status_message = Some(format!("Script not found: {resolved}"));
@@ -342,10 +345,7 @@ impl App {
 
         let resolved_for_spawn = resolved.clone();
         std::thread::spawn(move || {
-            std::process::Command::new("open")
-                .arg(&resolved_for_spawn)
-                .status()
-                .ok();
+            let _ = conductor_core::platform::open(&resolved_for_spawn);
         });
 
         self.state.status_message = Some(format!("Opening {resolved}…"));
@@ -476,6 +476,12 @@ impl App {
             SettingsCategory::Appearance => appearance_row::COUNT,
             SettingsCategory::Notifications => self.state.settings_display.hooks.len().max(1),
             SettingsCategory::Runtimes => self.state.settings_display.runtimes.len().max(1),
+            SettingsCategory::Tools => self
+                .state
+                .tools
+                .as_ref()
+                .map_or(0, |r| r.tools.len())
+                .max(1),
         }
     }
 
@@ -95,6 +95,7 @@ fn data_refreshed_updates_repos() {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         },
         conductor_core::repo::Repo {
             id: "01BBB".to_string(),
@@ -107,6 +108,7 @@ fn data_refreshed_updates_repos() {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         },
     ];
 
@@ -116,6 +118,7 @@ fn data_refreshed_updates_repos() {
             worktrees: vec![],
             tickets: vec![],
             ticket_labels: std::collections::HashMap::new(),
+            ticket_attachments: std::collections::HashMap::new(),
             ticket_dependencies: std::collections::HashMap::new(),
             latest_agent_runs: std::collections::HashMap::new(),
             ticket_agent_totals: std::collections::HashMap::new(),
@@ -132,12 +135,44 @@ fn data_refreshed_updates_repos() {
             repo_agent_events_id: None,
             workflow_run_estimates: std::collections::HashMap::new(),
             completed_token_totals_by_worktree: std::collections::HashMap::new(),
+            workspace_usage: None,
+            git_statuses: None,
+            agent_queue: Vec::new(),
+            watched_worktrees: Default::default(),
+            watched_tickets: Default::default(),
+            stack_infos: Default::default(),
+            agent_inbox: Vec::new(),
+            over_budget: std::collections::HashMap::new(),
+            ticket_progress: Default::default(),
+            read_only: false,
         },
     )));
 
     assert_eq!(app.state.data.repos.len(), 2);
 }
 
+#[test]
+fn read_only_data_refresh_shows_banner_until_writable() {
+    let mut app = make_app();
+    let refresh = |read_only| {
+        Action::DataRefreshed(Box::new(crate::action::DataRefreshedPayload {
+            read_only,
+            ..Default::default()
+        }))
+    };
+
+    app.update(refresh(true));
+    let since = app.state.db_snapshot_since.clone();
+    assert!(since.is_some());
+
+    // The banner keeps the time the database first went busy.
+    app.update(refresh(true));
+    assert_eq!(app.state.db_snapshot_since, since);
+
+    app.update(refresh(false));
+    assert!(app.state.db_snapshot_since.is_none());
+}
+
 #[test]
 fn confirm_no_clears_modal_without_side_effect() {
     let mut app = make_app();
@@ -485,6 +520,8 @@ fn show_confirm_quit_with_running_agents_includes_count() {
             conversation_id: None,
             subprocess_pid: None,
             runtime: "claude".to_string(),
+            rotated_from_session_id: None,
+            environment: None,
         },
     );
     app.show_confirm_quit();
@@ -505,11 +542,73 @@ fn delete_worktree_no_bg_tx_no_crash() {
     app.execute_confirm_action(crate::state::ConfirmAction::DeleteWorktree {
         repo_slug: "test".into(),
         wt_slug: "test-wt".into(),
+        reason: None,
     });
     // No crash, modal should show error (bg_tx is None → user gets feedback)
     assert!(matches!(app.state.modal, Modal::Error { .. }));
 }
 
+#[test]
+fn delete_unfinished_worktree_asks_for_abandon_reason() {
+    let mut app = make_app();
+    app.show_delete_worktree_modal("test", "test-wt", false, false, true);
+    assert!(matches!(
+        app.state.modal,
+        Modal::AbandonReasonPicker { selected: 0, .. }
+    ));
+
+    app.update(Action::MoveDown);
+    app.update(Action::MoveDown);
+    app.update(Action::InputSubmit);
+    let Modal::ConfirmByName {
+        ref message,
+        ref expected,
+        ref on_confirm,
+        ..
+    } = app.state.modal
+    else {
+        panic!("expected ConfirmByName, got {:?}", app.state.modal);
+    };
+    assert_eq!(expected, "test-wt");
+    assert!(message.contains("abandoned (blocked)"), "{message}");
+    assert!(matches!(
+        on_confirm,
+        crate::state::ConfirmAction::DeleteWorktree {
+            reason: Some(conductor_core::worktree::AbandonReason::Blocked),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn abandon_reason_picker_last_row_skips_reason() {
+    let mut app = make_app();
+    app.show_delete_worktree_modal("test", "test-wt", true, false, true);
+    app.update(Action::SelectListItem(
+        crate::state::ABANDON_REASON_ROWS - 1,
+    ));
+    assert!(matches!(
+        app.state.modal,
+        Modal::ConfirmByName {
+            on_confirm: crate::state::ConfirmAction::DeleteWorktree { reason: None, .. },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn delete_finished_worktree_skips_abandon_reason() {
+    let mut app = make_app();
+    app.show_delete_worktree_modal("test", "test-wt", true, true, true);
+    assert!(matches!(
+        app.state.modal,
+        Modal::Confirm {
+            on_confirm: crate::state::ConfirmAction::DeleteWorktree { reason: None, .. },
+            ..
+        }
+    ));
+}
+
 #[test]
 fn unregister_repo_no_bg_tx_no_crash() {
     let mut app = make_app();
@@ -539,6 +638,9 @@ fn push_no_bg_tx_shows_error() {
         completed_at: None,
         model: None,
         base_branch: None,
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }];
     app.state
         .data
@@ -567,6 +669,9 @@ fn create_pr_no_bg_tx_shows_error() {
         completed_at: None,
         model: None,
         base_branch: None,
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }];
     app.state
         .data
@@ -679,6 +784,38 @@ fn push_complete_err_shows_error_modal() {
     }
 }
 
+#[test]
+fn commit_complete_err_shows_error_modal() {
+    let mut app = make_app();
+    app.state.modal = Modal::Progress {
+        message: "Committing changes…".into(),
+    };
+    app.update(Action::CommitComplete {
+        result: Err("worktree 'feat-x' has no changes to commit".into()),
+    });
+    if let Modal::Error { message } = &app.state.modal {
+        assert!(message.starts_with("Commit failed:"));
+    } else {
+        panic!("expected Error modal");
+    }
+}
+
+#[test]
+fn restack_complete_err_shows_error_modal() {
+    let mut app = make_app();
+    app.state.modal = Modal::Progress {
+        message: "Rebasing…".into(),
+    };
+    app.update(Action::RestackComplete {
+        result: Err("rebase of feat-child onto feat-parent hit conflicts".into()),
+    });
+    if let Modal::Error { message } = &app.state.modal {
+        assert!(message.starts_with("Restack failed"));
+    } else {
+        panic!("expected Error modal");
+    }
+}
+
 #[test]
 fn pr_create_complete_ok_sets_status() {
     let mut app = make_app();
@@ -1051,6 +1188,8 @@ fn selected_ticket_url_from_ticket_info_modal() {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         }),
     };
     assert_eq!(
@@ -1079,6 +1218,7 @@ fn repo_web_url_with_valid_github_remote() {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     };
     app.state.selected_repo_id = Some("r1".into());
     app.state.data.repos = vec![repo];
@@ -1140,6 +1280,8 @@ fn selected_ticket_url_from_repo_detail_tickets() {
         raw_json: "{}".into(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     }];
     app.state.detail_ticket_index = 0;
     assert_eq!(
@@ -1242,8 +1384,8 @@ fn input_char_appends_to_input_modal_value() {
         title: "Test".into(),
         prompt: "Enter:".into(),
         value: "ab".into(),
-        on_submit: crate::state::InputAction::LinkTicket {
-            worktree_id: "w1".into(),
+        on_submit: crate::state::InputAction::SetRepoModel {
+            slug: "repo".into(),
         },
     };
     app.handle_action(Action::InputChar('c'));
@@ -1261,8 +1403,8 @@ fn input_backspace_removes_from_input_modal_value() {
         title: "Test".into(),
         prompt: "Enter:".into(),
         value: "abc".into(),
-        on_submit: crate::state::InputAction::LinkTicket {
-            worktree_id: "w1".into(),
+        on_submit: crate::state::InputAction::SetRepoModel {
+            slug: "repo".into(),
         },
     };
     app.handle_action(Action::InputBackspace);
@@ -1337,6 +1479,7 @@ fn action_move_up_dispatches() {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }];
     app.state.data.worktrees = vec![conductor_core::worktree::Worktree {
         id: "w1".into(),
@@ -1350,6 +1493,9 @@ fn action_move_up_dispatches() {
         completed_at: None,
         model: None,
         base_branch: None,
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }];
     app.handle_action(Action::MoveUp);
     assert_eq!(app.state.dashboard_index, 0);
@@ -1451,6 +1597,7 @@ fn workflow_picker_defs_loaded_worktree_target() {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }];
     app.state.data.worktrees = vec![conductor_core::worktree::Worktree {
         id: "w1".into(),
@@ -1464,6 +1611,9 @@ fn workflow_picker_defs_loaded_worktree_target() {
         completed_at: None,
         model: None,
         base_branch: None,
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }];
     app.state.selected_worktree_id = Some("w1".into());
     app.state.view = View::WorktreeDetail;
@@ -2032,6 +2182,9 @@ fn submit_prompt_input_with_worktree_opens_model_picker_and_resets_textarea() {
         completed_at: None,
         model: None,
         base_branch: None,
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }];
     app.state.selected_worktree_id = Some("w1".into());
     app.handle_submit_prompt_input();
@@ -2092,6 +2245,7 @@ fn submit_repo_prompt_input_with_repo_resets_textarea_and_blurs_to_repo_agent()
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }];
     app.state.selected_repo_id = Some("r1".into());
     app.state.repo_detail_focus = crate::state::RepoDetailFocus::RepoAgentPromptInput;
@@ -2129,6 +2283,7 @@ fn prompt_repo_agent_focuses_prompt_input() {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }];
     app.state.selected_repo_id = Some("r1".into());
     app.handle_prompt_repo_agent();
@@ -2932,3 +3087,404 @@ fn is_secret_env_key_classifies_common_secret_names() {
     assert!(!is_secret_env_key("ANTHROPIC_BASE_URL"));
     assert!(!is_secret_env_key("PATH"));
 }
+
+#[test]
+fn ticket_sync_actions_drive_progress_panel() {
+    use crate::state::RepoSyncState;
+
+    let mut app = make_app();
+    app.handle_action(Action::TicketSyncStarted {
+        repo_slugs: vec!["api".into(), "web".into()],
+    });
+    app.handle_action(Action::TicketSyncRepoStarted {
+        repo_slug: "api".into(),
+    });
+    app.handle_action(Action::TicketSyncComplete {
+        repo_slug: "api".into(),
+        count: 4,
+    });
+    app.handle_action(Action::TicketSyncRepoFinished {
+        repo_slug: "api".into(),
+    });
+    app.handle_action(Action::TicketSyncRepoStarted {
+        repo_slug: "web".into(),
+    });
+    app.handle_action(Action::TicketSyncFailed {
+        repo_slug: "web".into(),
+        error: "rate limited".into(),
+    });
+
+    let progress = app.state.sync_progress.as_ref().unwrap();
+    assert_eq!(progress.repos[0].state, RepoSyncState::Done);
+    assert_eq!(progress.repos[0].synced, 4);
+    assert_eq!(progress.repos[1].state, RepoSyncState::Running);
+    assert_eq!(progress.repos[1].errors, ["rate limited"]);
+    assert!(
+        app.state.status_message.is_none(),
+        "per-repo results go to the panel, not the footer"
+    );
+
+    app.handle_action(Action::TicketSyncRepoFinished {
+        repo_slug: "web".into(),
+    });
+    app.handle_action(Action::TicketSyncDone);
+    let progress = app.state.sync_progress.as_mut().unwrap();
+    assert_eq!(progress.repos[1].state, RepoSyncState::Error);
+
+    // Once the linger time has passed, the next tick drops the panel.
+    progress.finished_at = Some(std::time::Instant::now() - crate::state::SYNC_PANEL_LINGER);
+    app.handle_action(Action::Tick);
+    assert!(app.state.sync_progress.is_none());
+}
+
+#[test]
+fn ticket_sync_without_panel_reports_in_footer() {
+    let mut app = make_app();
+    app.handle_action(Action::TicketSyncRepoStarted {
+        repo_slug: "api".into(),
+    });
+    app.handle_action(Action::TicketSyncComplete {
+        repo_slug: "api".into(),
+        count: 2,
+    });
+    assert!(app.state.sync_progress.is_none());
+    assert_eq!(
+        app.state.status_message.as_deref(),
+        Some("Synced 2 tickets for api")
+    );
+}
+
+#[test]
+fn update_logs_each_new_status_message() {
+    use crate::state::Severity;
+
+    let mut app = make_app();
+    app.state.data.agent_inbox.clear();
+    app.update(Action::OpenAgentInbox);
+    app.update(Action::Tick);
+    app.update(Action::OpenAgentInbox);
+
+    let entries: Vec<_> = app.state.message_log.newest_first().collect();
+    assert_eq!(entries.len(), 1, "an unchanged message is logged once");
+    assert_eq!(entries[0].text, "Agent inbox is empty");
+    assert_eq!(entries[0].severity, Severity::Info);
+}
+
+#[test]
+fn failed_panel_sync_stays_sticky_until_message_log_opened() {
+    let mut app = make_app();
+    app.update(Action::TicketSyncStarted {
+        repo_slugs: vec!["web".into()],
+    });
+    app.update(Action::TicketSyncFailed {
+        repo_slug: "web".into(),
+        error: "rate limited".into(),
+    });
+    assert_eq!(
+        app.state
+            .message_log
+            .sticky_error()
+            .map(|e| e.text.as_str()),
+        Some("Sync failed for web: rate limited")
+    );
+
+    app.update(Action::OpenMessageLog);
+    assert!(matches!(app.state.modal, Modal::MessageLog { selected: 0 }));
+    assert!(app.state.message_log.sticky_error().is_none());
+    assert_eq!(app.state.message_log.len(), 1);
+
+    app.update(Action::ClearMessageLog);
+    assert!(app.state.message_log.is_empty());
+}
+
+#[test]
+fn grooming_modal_accepts_and_dismisses_pending_proposals() {
+    use conductor_core::tickets::{
+        grooming::ProposalDraft, ProposalStatus, TicketGrooming, TicketSyncer,
+    };
+
+    let mut app = make_app();
+    app.conn
+        .execute(
+            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at) \
+             VALUES ('r1', 'web', '/tmp/web', 'https://github.com/test/web.git', '/tmp', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+    let first = conductor_core::test_helpers::make_ticket("1", "Login fails");
+    let second = conductor_core::test_helpers::make_ticket("2", "Login broken");
+    let syncer = TicketSyncer::new(&app.conn);
+    syncer.upsert_tickets("r1", &[first, second]).unwrap();
+    let grooming = TicketGrooming::new(&app.conn);
+    for source_id in ["1", "2"] {
+        let ticket = syncer.get_by_source_id("r1", source_id).unwrap();
+        let draft = ProposalDraft {
+            labels: vec!["bug".into()],
+            ..Default::default()
+        };
+        grooming.record(&ticket.id, &draft, None, 0.0).unwrap();
+    }
+
+    app.update(Action::OpenGrooming);
+    let Modal::Grooming { ref proposals, .. } = app.state.modal else {
+        panic!("expected grooming modal, got {:?}", app.state.modal);
+    };
+    assert_eq!(proposals.len(), 2);
+
+    app.update(Action::AcceptGroomingProposal);
+    app.update(Action::DismissGroomingProposal);
+    let Modal::Grooming {
+        ref proposals,
+        selected,
+        ..
+    } = app.state.modal
+    else {
+        panic!("expected grooming modal, got {:?}", app.state.modal);
+    };
+    assert!(proposals.is_empty());
+    assert_eq!(selected, 0);
+
+    let grooming = TicketGrooming::new(&app.conn);
+    assert_eq!(
+        grooming
+            .list(None, Some(ProposalStatus::Accepted))
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        grooming
+            .list(None, Some(ProposalStatus::Dismissed))
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn roadmap_groups_selected_repo_tickets_including_closed() {
+    use crate::state::tests::{make_repo, make_ticket};
+
+    let mut app = make_app();
+    app.state.data.repos = vec![make_repo("repo-1", "web")];
+    app.state.selected_repo_id = Some("repo-1".into());
+    let mut closed = make_ticket("1", "closed");
+    closed.sprint = Some("2.0".into());
+    let mut open = make_ticket("2", "open");
+    open.sprint = Some("2.0".into());
+    let mut other_repo = make_ticket("3", "open");
+    other_repo.repo_id = "repo-2".into();
+    app.state.data.tickets = vec![closed, open, other_repo];
+
+    app.update(Action::OpenRoadmap);
+    let Modal::Roadmap {
+        ref title,
+        ref groups,
+        selected,
+    } = app.state.modal
+    else {
+        panic!("expected roadmap modal, got {:?}", app.state.modal);
+    };
+    assert_eq!(title, "Roadmap — web");
+    assert_eq!(selected, 0);
+    assert_eq!(groups.len(), 1);
+    assert_eq!((groups[0].total, groups[0].done), (2, 1));
+
+    app.update(Action::GoToBottom);
+    assert!(matches!(
+        app.state.modal,
+        Modal::Roadmap { selected: 0, .. }
+    ));
+}
+
+#[test]
+fn reports_modal_loads_rollups_and_cycles_grouping_and_window() {
+    use conductor_core::agent::{AgentManager, CostGroupBy};
+
+    let mut app = make_app();
+    let run = AgentManager::new(&app.conn)
+        .create_run(None, "task", None)
+        .unwrap();
+    app.conn
+        .execute(
+            "UPDATE agent_runs SET cost_usd = 2.0, num_turns = 5 WHERE id = ?1",
+            [&run.id],
+        )
+        .unwrap();
+
+    app.update(Action::OpenReports);
+    let Modal::Reports {
+        group_by,
+        window_days,
+        ref rows,
+        ..
+    } = app.state.modal
+    else {
+        panic!("expected reports modal, got {:?}", app.state.modal);
+    };
+    assert_eq!((group_by, window_days), (CostGroupBy::Repo, 30));
+    assert_eq!(rows.len(), 1);
+    assert_eq!((rows[0].label.as_str(), rows[0].turns), ("(no repo)", 5));
+
+    app.update(Action::CycleReportGrouping);
+    app.update(Action::CycleReportWindow);
+    assert!(matches!(
+        app.state.modal,
+        Modal::Reports {
+            group_by: CostGroupBy::Ticket,
+            window_days: 90,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn activity_heatmap_opens_from_reports_and_cycles_rows() {
+    use conductor_core::activity::ActivityGroupBy;
+    use conductor_core::agent::AgentManager;
+
+    let mut app = make_app();
+    conductor_core::test_helpers::insert_test_repo(&app.conn, "r1", "test-repo", "/nonexistent");
+    conductor_core::test_helpers::insert_test_worktree(&app.conn, "w1", "r1", "feat-test", "/tmp");
+    AgentManager::new(&app.conn)
+        .create_run(Some("w1"), "task", None)
+        .unwrap();
+
+    app.update(Action::OpenReports);
+    app.update(Action::OpenActivityHeatmap);
+    let Modal::ActivityHeatmap {
+        group_by,
+        ref heatmap,
+        ..
+    } = app.state.modal
+    else {
+        panic!("expected activity heat map, got {:?}", app.state.modal);
+    };
+    assert_eq!(group_by, ActivityGroupBy::Repo);
+    assert_eq!(heatmap.days, 90);
+    assert_eq!(heatmap.rows.len(), 1);
+    assert_eq!(heatmap.rows[0].runs, 1);
+
+    app.update(Action::CycleActivityGrouping);
+    assert!(matches!(
+        app.state.modal,
+        Modal::ActivityHeatmap {
+            group_by: ActivityGroupBy::Worktree,
+            ..
+        }
+    ));
+    app.update(Action::OpenReports);
+    assert!(matches!(app.state.modal, Modal::Reports { .. }));
+}
+
+#[test]
+fn agent_launch_over_budget_asks_before_relaunching() {
+    let mut app = make_app();
+    app.update(Action::AgentLaunchOverBudget {
+        launch: Box::new(crate::state::PendingAgentLaunch {
+            prompt: "fix it".into(),
+            worktree_id: "w1".into(),
+            worktree_path: "/tmp/w1".into(),
+            worktree_slug: "feat-x".into(),
+            resume_session_id: None,
+            model: None,
+            runtime: None,
+            override_budget: false,
+        }),
+        message: "worktree feat-x has spent $12.00 of its $10.00 budget".into(),
+    });
+    let Modal::Confirm {
+        ref message,
+        ref on_confirm,
+        ..
+    } = app.state.modal
+    else {
+        panic!("expected confirm modal, got {:?}", app.state.modal);
+    };
+    assert!(message.starts_with("The worktree feat-x has spent $12.00"));
+    assert!(matches!(
+        on_confirm,
+        crate::state::ConfirmAction::LaunchAgentOverBudget { launch } if launch.prompt == "fix it"
+    ));
+}
+
+#[test]
+fn acknowledge_ticket_change_clears_the_worktree_badge() {
+    let mut app = make_app();
+    conductor_core::test_helpers::insert_test_repo(&app.conn, "r1", "test-repo", "/nonexistent");
+    conductor_core::test_helpers::insert_test_worktree(&app.conn, "w1", "r1", "feat-test", "/tmp");
+    app.conn
+        .execute_batch("UPDATE worktrees SET ticket_change = 'reopened' WHERE id = 'w1'")
+        .unwrap();
+    app.refresh_data();
+    app.state.view = View::WorktreeDetail;
+    app.state.selected_worktree_id = Some("w1".into());
+
+    app.update(Action::AcknowledgeTicketChange);
+
+    let change: Option<String> = app
+        .conn
+        .query_row(
+            "SELECT ticket_change FROM worktrees WHERE id = 'w1'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(change, None);
+    assert!(app
+        .state
+        .selected_worktree()
+        .unwrap()
+        .ticket_change
+        .is_none());
+    assert_eq!(
+        app.state.status_message.as_deref(),
+        Some("Acknowledged ticket changes on feat-test")
+    );
+}
+
+#[test]
+fn comment_on_ticket_prompts_only_for_tickets_with_a_tracker() {
+    let ticket = |source_type: &str| conductor_core::tickets::Ticket {
+        id: "t1".into(),
+        repo_id: "r1".into(),
+        source_type: source_type.into(),
+        source_id: "123".into(),
+        title: "Test".into(),
+        body: "body".into(),
+        state: "open".into(),
+        labels: "".into(),
+        assignee: None,
+        priority: None,
+        url: "https://github.com/x/y/issues/123".into(),
+        synced_at: "2024-01-01T00:00:00Z".into(),
+        raw_json: "{}".into(),
+        workflow: None,
+        agent_map: None,
+        sprint: None,
+        sprint_current: false,
+    };
+    let mut app = make_app();
+
+    app.state.modal = Modal::TicketInfo {
+        ticket: Box::new(ticket("github")),
+    };
+    app.update(Action::CommentOnTicket);
+    assert!(matches!(
+        app.state.modal,
+        Modal::Input {
+            on_submit: crate::state::InputAction::TicketComment { ref ticket_id },
+            ..
+        } if ticket_id == "t1"
+    ));
+
+    app.state.modal = Modal::TicketInfo {
+        ticket: Box::new(ticket("manual")),
+    };
+    app.update(Action::CloseTicketUpstream);
+    assert!(matches!(app.state.modal, Modal::TicketInfo { .. }));
+    assert_eq!(
+        app.state.status_message.as_deref(),
+        Some("Manual tickets have no upstream tracker")
+    );
+}
@@ -0,0 +1,144 @@
+//! Screen snapshots of real key sequences, driven through [`Harness`].
+//! Review changes with `cargo insta review`.
+
+use crossterm::event::KeyCode;
+
+use super::harness::Harness;
+use crate::action::Action;
+use crate::state::{RepoDetailFocus, View};
+
+/// From the dashboard, open `my-app` and focus its ticket list.
+fn open_my_app_tickets(h: &mut Harness) {
+    h.press(KeyCode::Char('j')).press(KeyCode::Enter);
+    assert_eq!(h.app.state.view, View::RepoDetail);
+    while h.app.state.repo_detail_focus != RepoDetailFocus::Tickets {
+        h.press(KeyCode::Tab);
+    }
+}
+
+#[test]
+fn dashboard_empty() {
+    let mut h = Harness::new();
+    insta::assert_snapshot!(h.render());
+}
+
+#[test]
+fn dashboard_populated() {
+    let mut h = Harness::populated();
+    insta::assert_snapshot!(h.render());
+}
+
+#[test]
+fn repo_detail_nests_tickets_under_epic() {
+    let mut h = Harness::populated();
+    open_my_app_tickets(&mut h);
+    insta::assert_snapshot!(h.render());
+}
+
+#[test]
+fn space_collapses_epic() {
+    let mut h = Harness::populated();
+    open_my_app_tickets(&mut h);
+    h.press(KeyCode::Char('j')).press(KeyCode::Char(' '));
+    insta::assert_snapshot!(h.render());
+
+    h.press(KeyCode::Char(' '));
+    assert!(h.app.state.collapsed_ticket_ids.is_empty());
+}
+
+#[test]
+fn ticket_filter() {
+    let mut h = Harness::populated();
+    open_my_app_tickets(&mut h);
+    h.press(KeyCode::Char('/')).type_str("typo");
+    insta::assert_snapshot!(h.render());
+
+    h.press(KeyCode::Esc);
+    assert!(!h.app.state.filter.active);
+}
+
+#[test]
+fn settings_tools_before_probe() {
+    let mut h = Harness::populated();
+    h.press(KeyCode::Char('S')).type_str("jjjj");
+    assert_eq!(h.app.state.view, View::Settings);
+    insta::assert_snapshot!(h.render());
+}
+
+#[test]
+fn help_then_escape_returns_to_dashboard() {
+    let mut h = Harness::populated();
+    let before = h.render();
+    h.press(KeyCode::Char('?')).press(KeyCode::Esc);
+    assert_eq!(h.render(), before);
+}
+
+#[test]
+fn agent_run_detail_lists_runs_and_events() {
+    let mut h = Harness::populated();
+    h.app
+        .conn
+        .execute_batch(
+            "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, ended_at, \
+                                     result_text, session_id, cost_usd, num_turns, duration_ms) \
+             VALUES ('a3', 'w1', 'try again', 'failed', '2024-01-11T09:00:00Z', \
+                     '2024-01-11T09:00:45Z', 'rate limited', 'sess-a3', 0.4, 3, 45000); \
+             INSERT INTO agent_run_events (id, run_id, kind, summary, started_at, ended_at) VALUES \
+                 ('e1', 'a1', 'tool', 'Read src/cart.rs', '2024-01-10T00:00:01Z', '2024-01-10T00:00:03Z'), \
+                 ('e2', 'a1', 'result', 'Added the cart page', '2024-01-10T00:04:59Z', NULL);",
+        )
+        .unwrap();
+    h.refresh();
+    h.type_str("jjj").press(KeyCode::Enter);
+    assert_eq!(h.app.state.view, View::WorktreeDetail);
+
+    h.press(KeyCode::Char('h'));
+    assert_eq!(h.app.state.view, View::AgentRunDetail);
+    assert_eq!(h.app.state.agent_run_detail_runs[0].id, "a3");
+    h.press(KeyCode::Char('j'));
+    insta::assert_snapshot!(h.render());
+
+    h.press(KeyCode::Esc);
+    assert_eq!(h.app.state.view, View::WorktreeDetail);
+}
+
+#[test]
+fn worktree_diff_lists_files_and_scrolls() {
+    let mut h = Harness::populated();
+    h.type_str("jjj")
+        .press(KeyCode::Enter)
+        .press(KeyCode::Char('D'));
+    assert_eq!(h.app.state.view, View::WorktreeDiff);
+
+    // The harness has no background channel; deliver what git would return.
+    let diff = "diff --git a/src/cart.rs b/src/cart.rs\n\
+                index 1111111..2222222 100644\n\
+                --- a/src/cart.rs\n\
+                +++ b/src/cart.rs\n\
+                @@ -1,3 +1,4 @@\n \
+                pub struct Cart {\n\
+                -    items: Vec<Item>,\n\
+                +    items: Vec<LineItem>,\n\
+                +    coupon: Option<String>,\n \
+                }\n\
+                diff --git a/README.md b/README.md\n\
+                index 3333333..4444444 100644\n\
+                --- a/README.md\n\
+                +++ b/README.md\n\
+                @@ -1 +1 @@\n\
+                -# My app\n\
+                +# My shop\n";
+    h.app.update(Action::WorktreeDiffLoaded {
+        worktree_id: "w1".into(),
+        result: Ok(conductor_core::agent::split_diff(diff)),
+    });
+    h.press(KeyCode::Enter).press(KeyCode::Char('j'));
+    assert_eq!(h.app.state.worktree_diff_scroll, 1);
+    insta::assert_snapshot!(h.render());
+
+    h.press(KeyCode::Tab).press(KeyCode::Char('j'));
+    assert_eq!(h.app.state.worktree_diff_index, 1);
+    assert_eq!(h.app.state.worktree_diff_scroll, 0);
+    h.press(KeyCode::Esc);
+    assert_eq!(h.app.state.view, View::WorktreeDetail);
+}
@@ -0,0 +1,188 @@
+//! Drives an [`App`] the way a terminal session does: keys go through
+//! [`input::map_key`] into [`App::update`], data is loaded from an in-memory
+//! database through the same `DataRefreshed` action the background poller
+//! sends, and screens render into a [`TestBackend`] for snapshotting.
+//!
+//! ```ignore
+//! let mut h = Harness::populated();
+//! h.press(KeyCode::Enter).type_str("login");
+//! insta::assert_snapshot!(h.render());
+//! ```
+
+use conductor_core::config::Config;
+use conductor_core::tickets::{TicketInput, TicketSyncer};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use rusqlite::{named_params, Connection};
+
+use super::super::App;
+use crate::theme::Theme;
+use crate::{background, input, ui};
+
+/// Rendered screen size, matching `tests/tui_snapshots.rs`.
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+
+pub(super) struct Harness {
+    pub app: App,
+    terminal: Terminal<TestBackend>,
+}
+
+impl Harness {
+    /// An app over an empty database.
+    pub fn new() -> Self {
+        crate::test_support::isolate_conductor_home();
+        let conn = conductor_core::db::open_database(std::path::Path::new(":memory:")).unwrap();
+        let app = App::new(
+            conn,
+            Config::default(),
+            crate::config::TuiConfig::default(),
+            Theme::default(),
+        );
+        let terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+        let mut harness = Self { app, terminal };
+        harness.refresh();
+        harness
+    }
+
+    /// An app over the [`seed_fixtures`] data.
+    pub fn populated() -> Self {
+        let mut harness = Self::new();
+        seed_fixtures(&harness.app.conn);
+        harness.refresh();
+        harness
+    }
+
+    /// Reload everything from the database, as startup and the background
+    /// poller do.
+    pub fn refresh(&mut self) -> &mut Self {
+        self.app.refresh_data();
+        let action = background::load_data(
+            &self.app.conn,
+            &self.app.config,
+            false,
+            self.app.state.selected_worktree_id.clone(),
+            self.app.state.selected_repo_id.clone(),
+        )
+        .expect("load data");
+        self.app.update(action);
+        self
+    }
+
+    /// Send one key without modifiers.
+    pub fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// Send a key with modifiers, e.g. Ctrl+S.
+    pub fn key(&mut self, key: KeyEvent) -> &mut Self {
+        let action = input::map_key(key, &self.app.state);
+        self.app.update(action);
+        self
+    }
+
+    /// Type `text` one character at a time.
+    pub fn type_str(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+        self
+    }
+
+    /// Draw the current state and return the screen, one quoted line per row.
+    pub fn render(&mut self) -> String {
+        self.terminal
+            .draw(|frame| ui::render(frame, &self.app.state))
+            .unwrap();
+        format!("{}", self.terminal.backend())
+    }
+}
+
+/// Two repos. `my-app` has a GitHub issue source, an epic with two child
+/// tickets (one closed), a standalone ticket, three worktrees and a completed
+/// agent run on each child's worktree.
+pub(super) fn seed_fixtures(conn: &Connection) {
+    for (id, slug) in [("r1", "my-app"), ("r2", "backend-api")] {
+        conn.execute(
+            "INSERT INTO repos (id, slug, local_path, remote_url, workspace_dir, created_at) \
+             VALUES (:id, :slug, :path, :remote, :ws, '2024-01-01T00:00:00Z')",
+            named_params! {
+                ":id": id,
+                ":slug": slug,
+                ":path": format!("/home/user/{slug}"),
+                ":remote": format!("https://github.com/user/{slug}"),
+                ":ws": format!("/home/user/.conductor/workspaces/{slug}"),
+            },
+        )
+        .unwrap();
+    }
+
+    conductor_core::issue_source::IssueSourceManager::new(conn)
+        .add(
+            "r1",
+            "github",
+            r#"{"owner":"user","repo":"my-app"}"#,
+            "my-app",
+        )
+        .unwrap();
+
+    let ticket = |source_id: &str, title: &str, parent: Option<&str>| TicketInput {
+        url: format!("https://github.com/user/my-app/issues/{source_id}"),
+        parent: parent.map(String::from),
+        ..conductor_core::test_helpers::make_ticket(source_id, title)
+    };
+    let mut cart = ticket("11", "Cart page", Some("10"));
+    cart.state = "closed".into();
+    let mut payment = ticket("12", "Payment form", Some("10"));
+    payment.assignee = Some("alice".into());
+    let syncer = TicketSyncer::new(conn);
+    syncer
+        .upsert_tickets(
+            "r1",
+            &[
+                ticket("10", "Checkout epic", None),
+                cart,
+                payment,
+                ticket("20", "Fix typo in README", None),
+            ],
+        )
+        .unwrap();
+    let ticket_id = |source_id: &str| -> String {
+        conn.query_row(
+            "SELECT id FROM tickets WHERE source_id = ?1",
+            [source_id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+
+    for (id, slug, ticket) in [
+        ("w1", "feat-11-cart-page", Some("11")),
+        ("w2", "feat-12-payment-form", Some("12")),
+        ("w3", "chore-deps", None),
+    ] {
+        conn.execute(
+            "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at, ticket_id) \
+             VALUES (:id, 'r1', :slug, :branch, :path, 'active', '2024-01-10T00:00:00Z', :ticket)",
+            named_params! {
+                ":id": id,
+                ":slug": slug,
+                ":branch": slug.replacen('-', "/", 1),
+                ":path": format!("/home/user/my-app/.worktrees/{slug}"),
+                ":ticket": ticket.map(ticket_id),
+            },
+        )
+        .unwrap();
+    }
+    for (id, worktree, cost) in [("a1", "w1", 1.5), ("a2", "w2", 2.7)] {
+        conn.execute(
+            "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, ended_at, \
+                                     cost_usd, num_turns, duration_ms, input_tokens, output_tokens) \
+             VALUES (:id, :worktree, 'implement it', 'completed', '2024-01-10T00:00:00Z', \
+                     '2024-01-10T00:05:00Z', :cost, 12, 300000, 42000, 3100)",
+            named_params! { ":id": id, ":worktree": worktree, ":cost": cost },
+        )
+        .unwrap();
+    }
+}
@@ -1,4 +1,6 @@
 mod action_handler_tests;
+mod flow_snapshots;
+mod harness;
 
 use super::agent_events::extract_last_code_block;
 use super::helpers::{clamp_increment, collapse_loop_iterations, wrap_decrement, wrap_increment};
@@ -0,0 +1,114 @@
+use conductor_core::notifications::{NotificationManager, LOCAL_READER};
+use conductor_core::repo_visibility::Viewer;
+use conductor_core::today::{Today, TodayItem};
+
+use crate::action::Action;
+use crate::state::{ColumnFocus, View};
+
+use super::App;
+
+impl App {
+    /// Transition to the Today view and load its items in the background.
+    pub(super) fn handle_open_today(&mut self) {
+        if self.state.view != View::Today {
+            self.state.previous_view = Some(self.state.view);
+            self.state.view = View::Today;
+        }
+        self.state.column_focus = ColumnFocus::Content;
+        self.refresh_today();
+    }
+
+    /// Rebuild the Today list on a background thread; checking CI calls `gh`.
+    pub(super) fn refresh_today(&mut self) {
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            return;
+        };
+        self.state.today_loading = true;
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<Vec<TodayItem>> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                Ok(Today::new(&conn, &config).items(LOCAL_READER, &Viewer::Local)?)
+            })();
+            let _ = bg_tx.send(Action::TodayLoaded {
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    pub(super) fn handle_today_loaded(&mut self, result: Result<Vec<TodayItem>, String>) {
+        self.state.today_loading = false;
+        match result {
+            Ok(items) => {
+                self.state.today_items = items;
+                self.clamp_today_selection();
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load Today: {e}"));
+            }
+        }
+    }
+
+    fn clamp_today_selection(&mut self) {
+        let len = self.state.today_items.len();
+        self.state.today_index = self.state.today_index.min(len.saturating_sub(1));
+    }
+
+    /// Jump to the selected item: its worktree, else its repo. Notifications
+    /// with neither are marked read and their URL opened.
+    pub(super) fn today_select(&mut self) {
+        let Some(item) = self.state.today_items.get(self.state.today_index).cloned() else {
+            return;
+        };
+        if let Some(wt) = item.worktree_id.as_ref().and_then(|id| {
+            self.state
+                .data
+                .worktrees
+                .iter()
+                .find(|w| &w.id == id)
+                .cloned()
+        }) {
+            self.open_worktree_detail(&wt, View::Today);
+            return;
+        }
+        if let Some(idx) = item
+            .repo_id
+            .as_ref()
+            .and_then(|id| self.state.data.repos.iter().position(|r| &r.id == id))
+        {
+            self.navigate_to_repo_detail(idx);
+            return;
+        }
+        if let Some(id) = item.notification_id.as_deref() {
+            match NotificationManager::new(&self.conn).mark_read(LOCAL_READER, id) {
+                Ok(()) => {
+                    self.state
+                        .today_items
+                        .retain(|i| i.notification_id.as_deref() != Some(id));
+                    self.clamp_today_selection();
+                }
+                Err(e) => {
+                    self.state.status_message =
+                        Some(format!("Failed to mark notification read: {e}"));
+                    return;
+                }
+            }
+        }
+        if let Some(url) = item.url.as_deref() {
+            self.open_url(url, "item");
+        }
+    }
+
+    pub(super) fn handle_today_open_url(&mut self) {
+        let url = self
+            .state
+            .today_items
+            .get(self.state.today_index)
+            .and_then(|i| i.url.clone());
+        match url {
+            Some(url) => self.open_url(&url, "item"),
+            None => self.state.status_message = Some("Item has no URL".to_string()),
+        }
+    }
+}
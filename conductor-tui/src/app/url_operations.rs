@@ -1,14 +1,16 @@
-use std::process::Command;
+use conductor_core::platform;
+use conductor_core::tickets::Ticket;
+use conductor_core::tmux;
 
 use crate::state::{Modal, RepoDetailFocus, View};
 
 use super::App;
 
 impl App {
-    /// Resolve the URL of the currently focused ticket, across all contexts.
-    pub(super) fn selected_ticket_url(&self) -> Option<String> {
+    /// Resolve the currently focused ticket, across all contexts.
+    pub(super) fn selected_ticket(&self) -> Option<&Ticket> {
         if let Modal::TicketInfo { ref ticket } = self.state.modal {
-            return Some(ticket.url.clone());
+            return Some(ticket);
         }
         if self.state.view == View::WorktreeDetail {
             return self
@@ -17,43 +19,29 @@ impl App {
                 .as_ref()
                 .and_then(|wt_id| self.state.data.worktrees.iter().find(|w| &w.id == wt_id))
                 .and_then(|wt| wt.ticket_id.as_ref())
-                .and_then(|tid| self.state.data.ticket_map.get(tid))
-                .map(|t| t.url.clone());
+                .and_then(|tid| self.state.data.ticket_map.get(tid));
         }
         // Ticket list views: RepoDetail Tickets pane
-        let ticket = match self.state.view {
+        match self.state.view {
             View::RepoDetail if self.state.repo_detail_focus == RepoDetailFocus::Tickets => self
                 .state
                 .filtered_detail_tickets
                 .get(self.state.detail_ticket_index),
             _ => None,
-        };
-        ticket.map(|t| t.url.clone())
+        }
+    }
+
+    /// Resolve the URL of the currently focused ticket, across all contexts.
+    pub(super) fn selected_ticket_url(&self) -> Option<String> {
+        self.selected_ticket().map(|t| t.url.clone())
     }
 
     /// Open a URL in the default browser, checking the exit code.
     pub(super) fn open_url(&mut self, url: &str, label: &str) {
-        match Command::new("open")
-            .arg(url)
-            .output()
-            .or_else(|_| Command::new("xdg-open").arg(url).output())
-        {
-            Ok(output) if output.status.success() => {
-                self.state.status_message = Some(format!("Opened {url}"));
-            }
-            Ok(output) => {
-                let code = output
-                    .status
-                    .code()
-                    .map(|c| c.to_string())
-                    .unwrap_or_else(|| "?".to_string());
-                self.state.status_message =
-                    Some(format!("Failed to open {label} URL (exit {code})"));
-            }
-            Err(e) => {
-                self.state.status_message = Some(format!("Failed to open {label} URL: {e}"));
-            }
-        }
+        self.state.status_message = Some(match platform::open(url) {
+            Ok(()) => format!("Opened {url}"),
+            Err(e) => format!("Failed to open {label} URL: {e}"),
+        });
     }
 
     pub(super) fn handle_open_ticket_url(&mut self) {
@@ -127,128 +115,60 @@ impl App {
     }
 
     /// Open a new terminal window/tab at `path`, using the best available method:
-    /// 1. Inside tmux → `tmux new-window -c {path}`
-    /// 2. TERM_PROGRAM=Apple_Terminal → AppleScript `do script "cd {path}"`
-    /// 3. TERM_PROGRAM=iTerm.app → AppleScript create iTerm2 window at path
-    /// 4. Fallback → status message with hint
-    pub(super) fn open_terminal_at_path(&mut self, path: &str) {
+    /// 1. Inside tmux → window `window_name` of the managed conductor session,
+    ///    reused when it exists (see [`conductor_core::tmux`])
+    /// 2. Otherwise the platform terminal (see [`platform::open_terminal`]):
+    ///    Terminal.app or iTerm2 on macOS, Windows Terminal or PowerShell on
+    ///    Windows
+    /// 3. Fallback → status message with hint
+    pub(super) fn open_terminal_at_path(&mut self, path: &str, window_name: &str) {
         // 1. tmux: preferred when the TUI is already running inside a tmux session
-        if std::env::var("TMUX").is_ok() {
-            match Command::new("tmux")
-                .args(["new-window", "-c", path])
-                .output()
-            {
-                Ok(out) if out.status.success() => {
-                    self.state.status_message = Some(format!("Opened tmux window at {path}"));
-                }
-                Ok(out) => {
-                    let stderr = String::from_utf8_lossy(&out.stderr);
-                    self.state.status_message = Some(format!("tmux error: {}", stderr.trim()));
-                }
-                Err(e) => {
-                    self.state.status_message = Some(format!("Failed to open tmux window: {e}"));
-                }
-            }
+        if tmux::inside_tmux() {
+            let session = tmux::session_name(&self.config);
+            let result = tmux::open_window(
+                &session,
+                &self.config.general.workspace_root,
+                window_name,
+                std::path::Path::new(path),
+                None,
+            )
+            .and_then(|window| {
+                tmux::select_window(&window)?;
+                tmux::switch_client(&session)
+            });
+            self.state.status_message = Some(match result {
+                Ok(()) => format!("Opened {window_name} in tmux session {session}"),
+                Err(e) => format!("Failed to open tmux window: {e}"),
+            });
             return;
         }
 
-        // 2 & 3. AppleScript for macOS terminal apps.
-        // Pass the path as an OS-level argv argument (after --) rather than
-        // interpolating it into the script source. The script receives it via
-        // `item 1 of argv`, so no AppleScript string escaping is needed at all.
-        let term = std::env::var("TERM_PROGRAM").unwrap_or_default();
-        let script: Option<&str> = match term.as_str() {
-            "Apple_Terminal" => Some(
-                "on run argv\n\
-                 set p to item 1 of argv\n\
-                 tell application \"Terminal\"\n\
-                 \tdo script \"cd \" & quoted form of p\n\
-                 \tactivate\n\
-                 end tell\n\
-                 end run",
-            ),
-            "iTerm.app" | "iTerm2" => Some(
-                "on run argv\n\
-                 set p to item 1 of argv\n\
-                 tell application \"iTerm\"\n\
-                 \tactivate\n\
-                 \tcreate window with default profile\n\
-                 \ttell current session of current window\n\
-                 \t\twrite text \"cd \" & quoted form of p\n\
-                 \tend tell\n\
-                 end tell\n\
-                 end run",
-            ),
-            _ => None,
-        };
-
-        if let Some(script) = script {
-            match Command::new("osascript")
-                .arg("-e")
-                .arg(script)
-                .arg("--")
-                .arg(path)
-                .output()
-            {
-                Ok(out) if out.status.success() => {
-                    self.state.status_message = Some(format!("Opened terminal at {path}"));
-                }
-                Ok(out) => {
-                    let stderr = String::from_utf8_lossy(&out.stderr);
-                    self.state.status_message =
-                        Some(format!("Failed to open terminal: {}", stderr.trim()));
-                }
-                Err(e) => {
-                    self.state.status_message = Some(format!("Failed to open terminal: {e}"));
-                }
-            }
-        } else {
-            // 4. Unknown environment — guide the user
-            let hint = if term.is_empty() {
-                "Run inside tmux or set TERM_PROGRAM".to_string()
-            } else {
-                format!("Terminal '{term}' not supported — run inside tmux")
-            };
-            self.state.status_message = Some(hint);
-        }
+        // 2 & 3.
+        self.state.status_message =
+            Some(match platform::open_terminal(std::path::Path::new(path)) {
+                Some(Ok(())) => format!("Opened terminal at {path}"),
+                Some(Err(e)) => format!("Failed to open terminal: {e}"),
+                None => match std::env::var("TERM_PROGRAM") {
+                    Ok(term) if !term.is_empty() => {
+                        format!("Terminal '{term}' not supported — run inside tmux")
+                    }
+                    _ => "Run inside tmux or set TERM_PROGRAM".to_string(),
+                },
+            });
     }
 
-    /// Copy arbitrary text to the system clipboard via pbcopy/xclip/xsel.
+    /// Copy arbitrary text to the system clipboard.
     pub(super) fn copy_text_to_clipboard(&mut self, text: String) {
-        let copy_result = Command::new("pbcopy")
-            .stdin(std::process::Stdio::piped())
-            .spawn()
-            .or_else(|_| {
-                Command::new("xclip")
-                    .args(["-selection", "clipboard"])
-                    .stdin(std::process::Stdio::piped())
-                    .spawn()
-            })
-            .or_else(|_| {
-                Command::new("xsel")
-                    .arg("--clipboard")
-                    .stdin(std::process::Stdio::piped())
-                    .spawn()
-            });
-
-        match copy_result {
-            Ok(mut child) => {
-                use std::io::Write;
-                if let Some(mut stdin) = child.stdin.take() {
-                    if stdin.write_all(text.as_bytes()).is_err() {
-                        self.state.status_message = Some("Clipboard write failed".to_string());
-                        return;
-                    }
-                    drop(stdin);
+        self.state.status_message = Some(match platform::copy_to_clipboard(&text) {
+            Ok(()) => "Copied to clipboard".to_string(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut msg = e.to_string();
+                if let Some(first) = msg.get_mut(..1) {
+                    first.make_ascii_uppercase();
                 }
-                // Fire-and-forget: pbcopy/xclip/xsel completes almost instantly.
-                drop(child);
-                self.state.status_message = Some("Copied to clipboard".to_string());
-            }
-            Err(_) => {
-                self.state.status_message =
-                    Some("No clipboard tool found (pbcopy/xclip/xsel)".to_string());
+                msg
             }
-        }
+            Err(_) => "Clipboard write failed".to_string(),
+        });
     }
 }
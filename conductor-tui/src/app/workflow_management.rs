@@ -890,10 +890,8 @@ impl App {
             }
             | WorkflowPickerTarget::PostCreate {
                 ref worktree_id, ..
-            } => {
-                if self.active_run_blocks_dispatch(worktree_id) {
-                    return;
-                }
+            } if self.active_run_blocks_dispatch(worktree_id) => {
+                return;
             }
             _ => {}
         }
@@ -1746,9 +1744,7 @@ impl App {
             return;
         };
 
-        let editor = std::env::var("EDITOR")
-            .or_else(|_| std::env::var("VISUAL"))
-            .unwrap_or_else(|_| "vi".to_string());
+        let editor = conductor_core::platform::editor();
 
         // Suspend the TUI, open the editor, then restore
         let _ = crossterm::terminal::disable_raw_mode();
@@ -1757,7 +1753,11 @@ impl App {
         let status = Command::new(&editor).arg(&def.source_path).status();
 
         let _ = crossterm::terminal::enable_raw_mode();
-        let _ = crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen);
+        let _ = crossterm::execute!(
+            std::io::stdout(),
+            crossterm::terminal::EnterAlternateScreen,
+            crossterm::event::EnableBracketedPaste
+        );
 
         match status {
             Ok(s) if s.success() => {
@@ -2201,6 +2201,9 @@ mod tests {
             completed_at: None,
             model: model.map(String::from),
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         }
     }
 
@@ -2216,6 +2219,7 @@ mod tests {
             model: model.map(String::from),
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         }
     }
 
@@ -0,0 +1,99 @@
+use conductor_core::agent::{split_diff, FileDiff};
+use conductor_core::worktree::WorktreeManager;
+
+use crate::action::Action;
+use crate::state::{ColumnFocus, View, WorktreeDiffFocus};
+
+use super::App;
+
+impl App {
+    /// Show the selected worktree's diff. Back returns to the worktree
+    /// detail, so `previous_view` is left as the worktree's own.
+    pub(super) fn handle_open_worktree_diff(&mut self) {
+        if self.state.selected_worktree_id.is_none() {
+            return;
+        }
+        self.state.view = View::WorktreeDiff;
+        self.state.column_focus = ColumnFocus::Content;
+        self.state.worktree_diff_focus = WorktreeDiffFocus::Files;
+        self.state.worktree_diff_files = Vec::new();
+        self.state.worktree_diff_index = 0;
+        self.state.worktree_diff_scroll = 0;
+        self.refresh_worktree_diff();
+    }
+
+    /// Run `git diff` on a background thread; large diffs take a while.
+    pub(super) fn refresh_worktree_diff(&mut self) {
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            return;
+        };
+        let Some(wt) = self.state.selected_worktree().cloned() else {
+            return;
+        };
+        let Some(repo_slug) = self
+            .state
+            .data
+            .repos
+            .iter()
+            .find(|r| r.id == wt.repo_id)
+            .map(|r| r.slug.clone())
+        else {
+            return;
+        };
+        self.state.worktree_diff_loading = true;
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<Vec<FileDiff>> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                let diff = WorktreeManager::new(&conn, &config).diff(&repo_slug, &wt.slug)?;
+                Ok(split_diff(&diff))
+            })();
+            let _ = bg_tx.send(Action::WorktreeDiffLoaded {
+                worktree_id: wt.id,
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    pub(super) fn handle_worktree_diff_loaded(
+        &mut self,
+        worktree_id: String,
+        result: Result<Vec<FileDiff>, String>,
+    ) {
+        // The user may have moved to another worktree while git was running.
+        if self.state.selected_worktree_id.as_deref() != Some(worktree_id.as_str()) {
+            return;
+        }
+        self.state.worktree_diff_loading = false;
+        match result {
+            Ok(files) => {
+                // Keep the selected file across a refresh when it is still
+                // in the diff.
+                let selected = self
+                    .state
+                    .worktree_diff_files
+                    .get(self.state.worktree_diff_index)
+                    .map(|f| f.path.clone());
+                let index = selected.and_then(|path| files.iter().position(|f| f.path == path));
+                if index.is_none() {
+                    self.state.worktree_diff_scroll = 0;
+                }
+                self.state.worktree_diff_index = index.unwrap_or(0);
+                self.state.worktree_diff_files = files;
+            }
+            Err(e) => {
+                self.state.status_message = Some(format!("Failed to load diff: {e}"));
+            }
+        }
+    }
+
+    /// Move the file selection, or scroll the selected file's diff, by `delta`.
+    pub(super) fn worktree_diff_move(&mut self, delta: isize) {
+        let (index, len) = self.state.focused_index_and_len();
+        let index = index
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+        self.state.set_focused_index(index);
+    }
+}
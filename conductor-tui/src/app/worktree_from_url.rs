@@ -0,0 +1,67 @@
+use conductor_core::worktree::WorktreeManager;
+
+use crate::action::Action;
+use crate::input;
+use crate::state::{InputAction, Modal};
+
+use super::App;
+
+impl App {
+    /// Ask for an issue or PR URL to start a worktree from.
+    pub(super) fn handle_worktree_from_url(&mut self) {
+        self.state.modal = Modal::Input {
+            title: "Worktree from URL".to_string(),
+            prompt: "GitHub issue/PR, GitLab issue or Jira issue URL:".to_string(),
+            value: String::new(),
+            on_submit: InputAction::WorktreeFromUrl,
+        };
+    }
+
+    /// Find the URL's repo and ticket on a background thread (the ticket may
+    /// need fetching), then continue with the normal create flow.
+    pub(super) fn resolve_worktree_url(&mut self, url: String) {
+        let Some(bg_tx) = self.bg_tx.clone() else {
+            self.state.modal = Modal::Error {
+                message: super::BG_TX_NOT_READY.into(),
+            };
+            return;
+        };
+        self.state.auto_agent_ticket_id = None;
+        self.state.modal = Modal::Progress {
+            message: "Resolving URL\u{2026}".into(),
+        };
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let result = (|| -> anyhow::Result<_> {
+                let db = conductor_core::config::db_path();
+                let conn = conductor_core::db::open_database(&db)?;
+                Ok(WorktreeManager::new(&conn, &config).resolve_url(&url)?)
+            })();
+            let _ = bg_tx.send(match result {
+                Ok(target) => Action::WorktreeUrlResolved {
+                    repo_slug: target.repo.slug,
+                    wt_name: target.name,
+                    ticket_id: target.ticket.map(|t| t.id),
+                    from_pr: target.from_pr,
+                },
+                Err(e) => Action::WorktreeUrlFailed {
+                    error: e.to_string(),
+                },
+            });
+        });
+    }
+
+    /// A paste is either an issue URL to start a worktree from, or text for
+    /// whatever has focus, replayed as key presses.
+    pub(super) fn handle_paste(&mut self, text: &str) -> bool {
+        if let Some(action) = input::map_paste(text, &self.state) {
+            return self.update(action);
+        }
+        let mut dirty = false;
+        for key in input::paste_keys(text) {
+            let action = input::map_key(key, &self.state);
+            dirty |= self.update(action);
+        }
+        dirty
+    }
+}
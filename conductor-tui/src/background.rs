@@ -3,16 +3,17 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use conductor_core::agent::AgentManager;
-use conductor_core::config::{db_path, load_config};
-use conductor_core::db::open_database;
+use conductor_core::agent::{AgentInbox, AgentManager, AgentQueue, BudgetExceeded};
+use conductor_core::config::{db_path, load_config, BudgetConfig};
+use conductor_core::db::{is_busy, open_database, open_database_readonly};
 use conductor_core::error::ConductorError;
 use conductor_core::github;
 use conductor_core::github_app;
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::RepoManager;
-use conductor_core::ticket_source::TicketSource;
-use conductor_core::tickets::{TicketInput, TicketSyncer};
+use conductor_core::ticket_source::{SourceContext, TicketSourceRegistry};
+use conductor_core::tickets::{TicketAttachments, TicketInput, TicketSyncer};
+use conductor_core::watch::{WatchManager, WatchTarget};
 use conductor_core::worktree::WorktreeManager;
 
 use crate::action::{Action, DataRefreshedPayload, WorkflowDataPayload};
@@ -94,6 +95,16 @@ pub fn spawn_db_poller(
 
                     payload.live_turns_by_worktree = live_turns;
 
+                    // Notification claims write to the database. While it is
+                    // only readable, leave transitions unseen so they fire once
+                    // it can be written again.
+                    if payload.read_only {
+                        if !tx.send(action) {
+                            break;
+                        }
+                        continue;
+                    }
+
                     // Reuse the connection returned by poll_data() — no need to open a
                     // second connection just for notification claims.
                     // Always pass the connection so dedup and hooks can run even when
@@ -402,6 +413,7 @@ pub fn spawn_db_poller(
                                     dedup_store.clone(),
                                     &crate::notify::AgentRunNotificationArgs {
                                         run_id: &t.run_id,
+                                        worktree_id: t.worktree_id.as_deref(),
                                         worktree_slug: t.worktree_slug.as_deref(),
                                         succeeded: t.succeeded,
                                         error_msg: t.error_msg.as_deref(),
@@ -414,6 +426,38 @@ pub fn spawn_db_poller(
                             }
                         }
 
+                        // Fire notifications for changes on watched tickets and worktrees.
+                        match conductor_core::watch::WatchManager::new(conn).poll_changes() {
+                            Ok(changes) => {
+                                for change in &changes {
+                                    crate::notify::fire_watch_notification(
+                                        &config.notifications,
+                                        &config.notify.hooks,
+                                        dedup_store.clone(),
+                                        change,
+                                    );
+                                }
+                            }
+                            Err(e) => tracing::warn!("watch poll_changes failed: {e}"),
+                        }
+
+                        // Raise and notify agent cost and progress alerts.
+                        match conductor_core::agent::AgentAlerts::new(conn)
+                            .check(&config.notifications.cost_alerts)
+                        {
+                            Ok(alerts) => {
+                                for alert in &alerts {
+                                    crate::notify::fire_agent_alert_notification(
+                                        &config.notifications,
+                                        &config.notify.hooks,
+                                        dedup_store.clone(),
+                                        alert,
+                                    );
+                                }
+                            }
+                            Err(e) => tracing::warn!("agent cost alert check failed: {e}"),
+                        }
+
                         // Prune resolved feedback requests to prevent unbounded growth.
                         notified_feedback_ids.retain(|id| {
                             payload
@@ -458,7 +502,7 @@ fn query_if_enabled<T>(enabled: bool, f: impl FnOnce() -> Vec<T>) -> Vec<T> {
 
 /// Build fallback `AgentRunEvent`s by parsing log files for runs that lack DB event records.
 /// Called on the background thread so file I/O never blocks the TUI main thread.
-fn build_fallback_events(
+pub(crate) fn build_fallback_events(
     runs: &[conductor_core::agent::AgentRun],
 ) -> Vec<conductor_core::agent::AgentRunEvent> {
     use conductor_core::agent::{parse_agent_log, AgentRunEvent};
@@ -483,6 +527,61 @@ fn build_fallback_events(
     fallback
 }
 
+/// Worktrees that reached `[budget] worktree_usd`, keyed by worktree ID.
+pub(crate) fn over_budget_by_worktree(
+    agent_mgr: &AgentManager,
+    budget: &BudgetConfig,
+) -> std::collections::HashMap<String, BudgetExceeded> {
+    agent_mgr
+        .worktrees_over_budget(budget)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|b| (b.target_id.clone(), b))
+        .collect()
+}
+
+/// Open the database for a poll, reporting whether the connection is
+/// read-only. When another writer (a long migration, a busy workflow) holds
+/// the lock, fall back to a read-only snapshot and back off before trying
+/// read-write again (5s, doubling up to a minute) so polls don't each wait
+/// out the busy timeout.
+fn open_for_poll(db: &std::path::Path) -> Option<(rusqlite::Connection, bool)> {
+    static RETRY_AT: AtomicI64 = AtomicI64::new(0);
+    static BACKOFF_SECS: AtomicI64 = AtomicI64::new(0);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    if now >= RETRY_AT.load(Ordering::Relaxed) {
+        match open_database(db) {
+            Ok(conn) => {
+                BACKOFF_SECS.store(0, Ordering::Relaxed);
+                return Some((conn, false));
+            }
+            Err(e) if is_busy(&e) => {
+                let backoff = (BACKOFF_SECS.load(Ordering::Relaxed) * 2).clamp(5, 60);
+                BACKOFF_SECS.store(backoff, Ordering::Relaxed);
+                RETRY_AT.store(now + backoff, Ordering::Relaxed);
+                tracing::warn!("database is locked; polling read-only for {backoff}s");
+            }
+            Err(e) => {
+                tracing::warn!("open_database failed: {e}");
+                return None;
+            }
+        }
+    }
+    open_database_readonly(db).ok().map(|conn| (conn, true))
+}
+
+/// Whether a `conductor daemon` is running; it then owns ticket syncing and
+/// maintenance, so the TUI skips its own.
+pub(crate) fn daemon_running() -> bool {
+    #[cfg(unix)]
+    return conductor_core::daemon::is_running();
+    #[cfg(not(unix))]
+    false
+}
+
 /// Poll all data from the database. Returns a DataRefreshed action, the loaded config, and the
 /// open DB connection so the caller can reuse it (e.g. for notification claims) without opening
 /// a second connection on the same tick.
@@ -494,125 +593,138 @@ pub fn poll_data(
     selected_repo_id: Option<String>,
 ) -> Option<PollResult> {
     let db = db_path();
-    let conn = open_database(&db).ok()?;
+    let (conn, read_only) = open_for_poll(&db)?;
     let config = load_config().unwrap_or_else(|e| {
         tracing::warn!("config parse error (using defaults): {e}");
         conductor_core::config::Config::default()
     });
 
-    let repo_mgr = RepoManager::new(&conn, &config);
-    let wt_mgr = WorktreeManager::new(&conn, &config);
-    let ticket_syncer = TicketSyncer::new(&conn);
-    let agent_mgr = AgentManager::new(&conn);
-
-    // Reap orphaned runs whose subprocess PID is no longer live and clean up
-    // stale worktrees whose artifacts persist on disk after merge/abandon.
-    // Throttle to at most once every 30 seconds to avoid subprocess liveness
-    // checks on every poll tick.
+    // Reap orphaned runs, stale worktrees and stuck workflows. Throttle to at
+    // most once every 30 seconds to avoid subprocess liveness checks on every
+    // poll tick. A running `conductor daemon` does this for us.
     {
         static LAST_REAP: AtomicI64 = AtomicI64::new(0);
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs() as i64;
-        if now - LAST_REAP.load(Ordering::Relaxed) >= 30 {
+        if !read_only && !daemon_running() && now - LAST_REAP.load(Ordering::Relaxed) >= 30 {
             LAST_REAP.store(now, Ordering::Relaxed);
-            if let Err(e) = agent_mgr.reap_orphaned_runs() {
-                tracing::warn!("reap_orphaned_runs failed: {e}");
-            }
-            if let Err(e) = agent_mgr.dismiss_expired_feedback_requests() {
-                tracing::warn!("dismiss_expired_feedback_requests failed: {e}");
-            }
-            if let Err(e) = wt_mgr.reap_stale_worktrees() {
-                tracing::warn!("reap_stale_worktrees failed: {e}");
-            }
-            if config.general.auto_cleanup_merged_branches {
-                match wt_mgr.cleanup_merged_worktrees(None) {
-                    Ok(n) if n > 0 => tracing::info!("Auto-cleaned {n} merged worktree(s)"),
-                    Ok(_) => {}
-                    Err(e) => tracing::warn!("cleanup_merged_worktrees failed: {e}"),
-                }
-            }
-            // recover_stuck_steps_from_db opens its own write connection internally,
-            // so the read `conn` and the write path don't deadlock on the same mutex
-            // inside SqliteWorkflowPersistence. Surfaces open_database failures as
-            // tracing::warn! instead of swallowing them silently.
-            match conductor_core::workflow::recover_stuck_steps_from_db(&conn, &db) {
-                Ok(n) if n > 0 => tracing::debug!("Recovered {n} stuck workflow step(s)"),
-                Ok(_) => {}
-                Err(e) => tracing::warn!("recover_stuck_steps_from_db failed: {e}"),
-            }
-            match conductor_core::workflow::reap_orphaned_workflow_runs(&conn) {
-                Ok(n) if n > 0 => tracing::debug!("Reaped {n} orphaned workflow run(s)"),
-                Ok(_) => {}
-                Err(e) => tracing::warn!("reap_orphaned_workflow_runs failed: {e}"),
-            }
-            match conductor_core::workflow::reap_orphaned_script_steps(&conn) {
-                Ok(n) if n > 0 => tracing::debug!("Reaped {n} orphaned script step(s)"),
-                Ok(_) => {}
-                Err(e) => tracing::warn!("reap_orphaned_script_steps failed: {e}"),
-            }
-            match conductor_core::workflow::reap_finalization_stuck_workflow_runs(&conn, 60) {
-                Ok(n) if n > 0 => {
-                    tracing::info!("Reaper finalized {n} stuck workflow run(s)")
-                }
+            conductor_core::maintenance::reap(&conn, &db, &config);
+        }
+    }
+
+    // Apply `[retention]` windows at most once an hour.
+    {
+        static LAST_PRUNE: AtomicI64 = AtomicI64::new(0);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        if !read_only && !daemon_running() && now - LAST_PRUNE.load(Ordering::Relaxed) >= 3600 {
+            LAST_PRUNE.store(now, Ordering::Relaxed);
+            match conductor_core::retention::prune(&conn, &config.retention, false) {
+                Ok(r) if r.total() > 0 => tracing::info!("Retention pruned {r:?}"),
                 Ok(_) => {}
-                Err(e) => tracing::warn!("reap_finalization_stuck_workflow_runs failed: {e}"),
-            }
-            {
-                let conductor_bin_dir = conductor_core::workflow::resolve_conductor_bin_dir();
-                let stale_mins = config.general.stale_workflow_minutes;
-                let configurable_threshold = if stale_mins > 0 {
-                    Some((stale_mins * 60) as i64)
-                } else {
-                    None
-                };
-                match conductor_core::workflow::claim_stuck_workflows(
-                    &conn,
-                    &config,
-                    configurable_threshold,
-                ) {
-                    Ok(claimed) => conductor_core::workflow::spawn_claimed_runs(
-                        claimed,
-                        Arc::new(config.clone()),
-                        conductor_bin_dir.clone(),
-                    ),
-                    Err(e) => tracing::warn!("claim_stuck_workflows failed: {e}"),
-                }
-                let auto_resume_limit = config.general.auto_resume_limit;
-                if auto_resume_limit > 0 {
-                    match conductor_core::workflow::classify_resumable_workflows(
-                        &conn,
-                        auto_resume_limit,
-                    ) {
-                        Ok(n) if n > 0 => {
-                            tracing::info!("Classifier flagged {n} workflow run(s) for auto-resume")
-                        }
-                        Ok(_) => {}
-                        Err(e) => tracing::warn!("classify_resumable_workflows failed: {e}"),
-                    }
-                    match conductor_core::workflow::claim_needs_resume_runs(&conn, &config) {
-                        Ok(claimed) => conductor_core::workflow::spawn_claimed_runs(
-                            claimed,
-                            Arc::new(config.clone()),
-                            conductor_bin_dir.clone(),
-                        ),
-                        Err(e) => tracing::warn!("claim_needs_resume_runs failed: {e}"),
-                    }
-                }
+                Err(e) => tracing::warn!("retention prune failed: {e}"),
             }
         }
     }
 
+    let action = load_data(
+        &conn,
+        &config,
+        read_only,
+        selected_worktree_id,
+        selected_repo_id,
+    )?;
+    Some(PollResult {
+        action,
+        config,
+        conn,
+    })
+}
+
+/// Load everything the UI shows from `conn` as a [`Action::DataRefreshed`].
+/// `None` if the core tables can't be read.
+pub(crate) fn load_data(
+    conn: &rusqlite::Connection,
+    config: &conductor_core::config::Config,
+    read_only: bool,
+    selected_worktree_id: Option<String>,
+    selected_repo_id: Option<String>,
+) -> Option<Action> {
+    let repo_mgr = RepoManager::new(conn, config);
+    let wt_mgr = WorktreeManager::new(conn, config);
+    let ticket_syncer = TicketSyncer::new(conn);
+    let agent_mgr = AgentManager::new(conn);
+
     let repos = repo_mgr.list().ok()?;
     let worktrees = wt_mgr.list(None, true).ok()?;
     let tickets = ticket_syncer.list(None).ok()?;
     let ticket_labels = ticket_syncer.get_all_labels().unwrap_or_default();
+    let ticket_attachments = TicketAttachments::new(conn)
+        .all_by_ticket()
+        .unwrap_or_default();
     let ticket_dependencies = ticket_syncer.get_all_dependencies().unwrap_or_default();
     let latest_agent_runs = agent_mgr.latest_runs_by_worktree().unwrap_or_default();
     let latest_repo_agent_runs = agent_mgr.latest_repo_scoped_runs_all().unwrap_or_default();
     let ticket_agent_totals = agent_mgr.totals_by_ticket_all().unwrap_or_default();
     let completed_token_totals_by_worktree = agent_mgr.totals_by_worktree().unwrap_or_default();
+    let agent_queue = AgentQueue::new(conn).list().unwrap_or_default();
+    let watch_mgr = WatchManager::new(conn);
+    let watched_worktrees = watch_mgr
+        .watched_ids(WatchTarget::Worktree)
+        .unwrap_or_default();
+    let watched_tickets = watch_mgr
+        .watched_ids(WatchTarget::Ticket)
+        .unwrap_or_default();
+    let stack_infos = wt_mgr.stack_infos_by_worktree().unwrap_or_default();
+    let agent_inbox = AgentInbox::new(conn).list().unwrap_or_default();
+    let over_budget = over_budget_by_worktree(&agent_mgr, &config.budget);
+    let ticket_progress = ticket_syncer.progress_by_ticket().unwrap_or_default();
+
+    // Workspace disk usage walks every workspace directory, so rescan at most
+    // once a minute and only when a quota is configured. `None` tells the main
+    // thread to keep the previous snapshot.
+    let workspace_usage = {
+        static LAST_QUOTA_SCAN: AtomicI64 = AtomicI64::new(0);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let quota_enabled = config.quota.max_gb.is_some() || !config.quota.repos.is_empty();
+        if !quota_enabled {
+            Some(std::collections::HashMap::new())
+        } else if now - LAST_QUOTA_SCAN.load(Ordering::Relaxed) >= 60 {
+            LAST_QUOTA_SCAN.store(now, Ordering::Relaxed);
+            match wt_mgr.workspace_usage_all() {
+                Ok(usage) => Some(usage.into_iter().map(|u| (u.repo_id.clone(), u)).collect()),
+                Err(e) => {
+                    tracing::warn!("workspace_usage_all failed: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    };
+
+    // Git status runs a few git commands per live worktree, so refresh it at
+    // most every 15 seconds. `None` keeps the previous snapshot.
+    let git_statuses = {
+        static LAST_GIT_STATUS_SCAN: AtomicI64 = AtomicI64::new(0);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        if now - LAST_GIT_STATUS_SCAN.load(Ordering::Relaxed) >= 15 {
+            LAST_GIT_STATUS_SCAN.store(now, Ordering::Relaxed);
+            Some(conductor_core::worktree::git_statuses(&worktrees))
+        } else {
+            None
+        }
+    };
 
     // Fetch agent events only for the currently-selected worktree (scoped query).
     // Fall back to log-file parsing for pre-DB-event runs.
@@ -650,7 +762,7 @@ pub fn poll_data(
     // rather than a concurrently-active child sub-workflow run.
     // Fetch recent runs sorted DESC; the first entry per worktree_id wins.
     let mut latest_workflow_runs_by_worktree = std::collections::HashMap::new();
-    for run in conductor_core::workflow::list_root_workflow_runs(&conn, 100).unwrap_or_default() {
+    for run in conductor_core::workflow::list_root_workflow_runs(conn, 100).unwrap_or_default() {
         // Skip ephemeral runs (no registered worktree) — they have no worktree
         // entry to display inline indicators for.
         if let Some(ref wt_id) = run.worktree_id {
@@ -662,7 +774,7 @@ pub fn poll_data(
 
     // Fetch active non-worktree workflow runs (repo/ticket-targeted).
     let active_non_worktree_workflow_runs =
-        conductor_core::workflow::list_active_non_worktree_workflow_runs(&conn, 50)
+        conductor_core::workflow::list_active_non_worktree_workflow_runs(conn, 50)
             .unwrap_or_default();
 
     // Collect IDs of active runs to fetch current step summaries in a single batch query.
@@ -683,7 +795,7 @@ pub fn poll_data(
         .collect();
     let active_run_id_refs: Vec<&str> = active_run_ids.iter().map(|s| s.as_str()).collect();
     let workflow_step_summaries =
-        conductor_core::workflow::get_step_summaries_for_runs(&conn, &active_run_id_refs)
+        conductor_core::workflow::get_step_summaries_for_runs(conn, &active_run_id_refs)
             .unwrap_or_default();
 
     // ── Time estimation for active workflow runs ──
@@ -708,7 +820,7 @@ pub fn poll_data(
         let active_est_run_id_refs: Vec<&str> =
             active_est_run_ids.iter().map(|s| s.as_str()).collect();
         let all_steps_by_run =
-            conductor_core::workflow::get_steps_for_runs(&conn, &active_est_run_id_refs)
+            conductor_core::workflow::get_steps_for_runs(conn, &active_est_run_id_refs)
                 .unwrap_or_default();
 
         // Cache step histories per workflow name to avoid redundant queries
@@ -722,7 +834,7 @@ pub fn poll_data(
                 .entry(run.workflow_name.clone())
                 .or_insert_with(|| {
                     conductor_core::workflow::get_completed_step_durations(
-                        &conn,
+                        conn,
                         &run.workflow_name,
                         20,
                     )
@@ -732,7 +844,7 @@ pub fn poll_data(
             if step_histories.is_empty() {
                 // Fall back to workflow-level estimate
                 let hist = conductor_core::workflow::get_completed_run_durations(
-                    &conn,
+                    conn,
                     &run.workflow_name,
                     20,
                 )
@@ -777,7 +889,7 @@ pub fn poll_data(
             })
     });
     let waiting_gate_steps = query_if_enabled(config.notifications.enabled, || {
-        conductor_core::workflow::list_all_waiting_gate_steps(&conn).unwrap_or_else(|e| {
+        conductor_core::workflow::list_all_waiting_gate_steps(conn).unwrap_or_else(|e| {
             tracing::warn!("list_all_waiting_gate_steps failed: {e}");
             vec![]
         })
@@ -787,11 +899,12 @@ pub fn poll_data(
     // Return an empty map here; the loop merges in the incremental state.
     let live_turns_by_worktree = std::collections::HashMap::new();
 
-    let action = Action::DataRefreshed(Box::new(DataRefreshedPayload {
+    Some(Action::DataRefreshed(Box::new(DataRefreshedPayload {
         repos,
         worktrees,
         tickets,
         ticket_labels,
+        ticket_attachments,
         ticket_dependencies,
         latest_agent_runs,
         ticket_agent_totals,
@@ -808,35 +921,82 @@ pub fn poll_data(
         repo_agent_events_id,
         workflow_run_estimates,
         completed_token_totals_by_worktree,
-    }));
-    Some(PollResult {
-        action,
-        config,
-        conn,
-    })
+        workspace_usage,
+        git_statuses,
+        agent_queue,
+        watched_worktrees,
+        watched_tickets,
+        stack_infos,
+        agent_inbox,
+        over_budget,
+        ticket_progress,
+        read_only,
+    })))
 }
 
-/// Spawn the ticket sync timer. Syncs all repos every `interval`.
+/// Spawn the ticket sync timer. Syncs all repos every `interval` unless a
+/// daemon is doing it.
 pub fn spawn_ticket_sync(tx: BackgroundSender, interval: Duration) {
     thread::spawn(move || loop {
         thread::sleep(interval);
-        sync_all_tickets(&tx);
+        if !daemon_running() {
+            sync_all_tickets(&tx, false);
+        }
+    });
+}
+
+/// Relay events from a running `conductor daemon` as status messages,
+/// reconnecting every 30 seconds while none is up.
+#[cfg(unix)]
+pub fn spawn_daemon_listener(tx: BackgroundSender) {
+    use conductor_core::daemon::{DaemonClient, DaemonEvent};
+
+    thread::spawn(move || loop {
+        if let Ok(events) = DaemonClient::connect().and_then(DaemonClient::subscribe) {
+            for event in events {
+                let message = match event {
+                    DaemonEvent::TicketSyncFinished { error: None } => {
+                        "Daemon synced tickets".into()
+                    }
+                    DaemonEvent::TicketSyncFinished { error: Some(e) } => {
+                        format!("Daemon ticket sync failed: {e}")
+                    }
+                    DaemonEvent::AgentDispatched { .. } => "Daemon started a queued agent".into(),
+                    DaemonEvent::MaintenanceFinished { reaped_runs, .. } if reaped_runs > 0 => {
+                        format!("Daemon reaped {reaped_runs} dead run(s)")
+                    }
+                    _ => continue,
+                };
+                if !tx.send(Action::DaemonNotice { message }) {
+                    return;
+                }
+            }
+            let message = "Daemon stopped; the TUI is syncing tickets again".to_string();
+            if !tx.send(Action::DaemonNotice { message }) {
+                return;
+            }
+        }
+        thread::sleep(Duration::from_secs(30));
     });
 }
 
-/// Spawn a one-shot ticket sync for all repos. Sends per-repo
+/// Spawn a one-shot ticket sync for all repos. Announces the repos with
+/// `TicketSyncStarted`, then sends per-repo progress and per-source
 /// `TicketSyncComplete`/`TicketSyncFailed` actions followed by a final
 /// `TicketSyncDone` when all repos have been processed.
 pub fn spawn_ticket_sync_once(tx: BackgroundSender) {
     thread::spawn(move || {
-        sync_all_tickets(&tx);
+        sync_all_tickets(&tx, true);
         if !tx.send(Action::TicketSyncDone) {
             tracing::warn!("failed to send TicketSyncDone: channel closed");
         }
     });
 }
 
-fn sync_all_tickets(tx: &BackgroundSender) {
+/// Sync every repo's sources. With `announce`, the repo list is sent as
+/// `TicketSyncStarted` first so the TUI can show the sync progress panel;
+/// periodic syncs run without it.
+fn sync_all_tickets(tx: &BackgroundSender, announce: bool) {
     let db = db_path();
     let Ok(conn) = open_database(&db) else { return };
     let Ok(config) = load_config() else { return };
@@ -844,6 +1004,13 @@ fn sync_all_tickets(tx: &BackgroundSender) {
     let repo_mgr = RepoManager::new(&conn, &config);
     let Ok(repos) = repo_mgr.list() else { return };
 
+    if announce {
+        let repo_slugs = repos.iter().map(|r| r.slug.clone()).collect();
+        if !tx.send(Action::TicketSyncStarted { repo_slugs }) {
+            return;
+        }
+    }
+
     let syncer = TicketSyncer::new(&conn);
     let source_mgr = IssueSourceManager::new(&conn);
 
@@ -866,6 +1033,30 @@ fn sync_all_tickets(tx: &BackgroundSender) {
             return;
         }
     }
+    maintain_ticket_attachments(&conn, &config);
+    groom_new_tickets(&conn, &config);
+}
+
+/// Download pending ticket attachments and apply the retention policy after a sync.
+fn maintain_ticket_attachments(
+    conn: &rusqlite::Connection,
+    config: &conductor_core::config::Config,
+) {
+    if let Err(e) = TicketAttachments::new(conn).maintain(&config.attachments) {
+        tracing::warn!("ticket attachment maintenance failed: {e}");
+    }
+}
+
+/// Run the `[grooming]` pass over newly synced tickets; a no-op unless enabled.
+fn groom_new_tickets(conn: &rusqlite::Connection, config: &conductor_core::config::Config) {
+    let model = config.grooming.model.clone();
+    let result =
+        conductor_core::tickets::TicketGrooming::new(conn).run_pass(config, None, |prompt| {
+            conductor_core::tickets::grooming::run_grooming_agent(&model, prompt, None)
+        });
+    if let Err(e) = result {
+        tracing::warn!("ticket grooming failed: {e}");
+    }
 }
 
 /// Sync issues for a single repo using the given fetch closure, returning the appropriate Action.
@@ -911,7 +1102,8 @@ fn sync_repo(
 /// Staleness threshold for auto-sync: skip sync if tickets were synced within this duration.
 pub const TICKET_SYNC_STALE_SECS: i64 = 300; // 5 minutes
 
-/// Sync sources for a single repo, sending per-source actions to `tx`.
+/// Sync sources for a single repo, sending per-source actions to `tx`
+/// between `TicketSyncRepoStarted` and `TicketSyncRepoFinished`.
 /// Returns `false` if the channel is closed (caller should stop).
 fn sync_sources_for_repo(
     tx: &BackgroundSender,
@@ -922,37 +1114,33 @@ fn sync_sources_for_repo(
     remote_url: &str,
     token: Option<&str>,
 ) -> bool {
+    if !tx.send(Action::TicketSyncRepoStarted {
+        repo_slug: repo_slug.to_string(),
+    }) {
+        return false;
+    }
     let sources = source_mgr.list(repo_id).unwrap_or_default();
+    let ctx = SourceContext {
+        repo_slug,
+        remote_url,
+        token,
+    };
 
-    if sources.is_empty() {
-        // Backward compat: auto-detect GitHub from remote_url
-        if let Some((owner, name)) = github::parse_github_remote(remote_url) {
-            let action = sync_repo(syncer, repo_id, repo_slug, "github", || {
-                github::sync_github_issues(&owner, &name, token)
-            });
-            if !tx.send(action) {
-                return false;
-            }
-        }
-    } else {
-        for source in sources {
-            let action = match TicketSource::from_issue_source(&source) {
-                Ok(ts) => {
-                    let ts = ts.with_repo_slug(repo_slug);
-                    let source_type = ts.source_type_str();
-                    sync_repo(syncer, repo_id, repo_slug, source_type, || ts.sync(token))
-                }
-                Err(e) => Action::TicketSyncFailed {
-                    repo_slug: repo_slug.to_string(),
-                    error: format!("unsupported source type {:?}: {e}", source.source_type),
-                },
-            };
-            if !tx.send(action) {
-                return false;
-            }
+    for fetcher in TicketSourceRegistry::default().fetchers(&sources, &ctx) {
+        let action = match fetcher {
+            Ok(f) => sync_repo(syncer, repo_id, repo_slug, f.source_type(), || f.fetch()),
+            Err(e) => Action::TicketSyncFailed {
+                repo_slug: repo_slug.to_string(),
+                error: e.to_string(),
+            },
+        };
+        if !tx.send(action) {
+            return false;
         }
     }
-    true
+    tx.send(Action::TicketSyncRepoFinished {
+        repo_slug: repo_slug.to_string(),
+    })
 }
 
 /// Spawn a one-shot ticket sync for a single repo. Checks staleness inside the
@@ -1008,6 +1196,9 @@ pub fn spawn_ticket_sync_for_repo(
             return;
         }
 
+        let _ = tx.send(Action::TicketSyncStarted {
+            repo_slugs: vec![repo_slug.clone()],
+        });
         let source_mgr = IssueSourceManager::new(&conn);
         let repo_owner = github::parse_github_remote(&remote_url)
             .map(|(o, _)| o)
@@ -1025,6 +1216,8 @@ pub fn spawn_ticket_sync_for_repo(
             &remote_url,
             token,
         );
+        maintain_ticket_attachments(&conn, &config);
+        groom_new_tickets(&conn, &config);
 
         let _ = tx.send(Action::TicketSyncDone);
     });
@@ -1397,6 +1590,19 @@ pub fn spawn_pr_fetch_once(tx: BackgroundSender, remote_url: String, repo_id: St
     });
 }
 
+/// Load the external tool report — the cached one unless `refresh` or it is
+/// stale — and send it as `Action::ToolsProbed`.
+pub fn spawn_tool_probe(tx: BackgroundSender, refresh: bool) {
+    thread::spawn(move || {
+        let report = if refresh {
+            conductor_core::tools::ToolReport::refresh()
+        } else {
+            conductor_core::tools::ToolReport::current(conductor_core::tools::CACHE_TTL)
+        };
+        let _ = tx.send(Action::ToolsProbed(report));
+    });
+}
+
 /// Spawn a one-shot background operation for blocking tasks.
 #[allow(dead_code)]
 pub fn spawn_blocking(tx: BackgroundSender, f: impl FnOnce() -> Action + Send + 'static) {
@@ -1458,6 +1664,8 @@ mod tests {
             blocked_by: vec![],
             children: vec![],
             parent: None,
+            sprint: None,
+            sprint_current: false,
         };
 
         let action = sync_repo(&syncer, "r1", "test-repo", "github", || Ok(vec![ticket]));
@@ -1508,6 +1716,8 @@ mod tests {
             blocked_by: vec![],
             children: vec![],
             parent: None,
+            sprint: None,
+            sprint_current: false,
         };
 
         let action = sync_repo(&syncer, "nonexistent-repo", "test-repo", "github", || {
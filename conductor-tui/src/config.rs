@@ -67,8 +67,8 @@ fn load_from(path: &Path) -> Result<TuiConfig> {
             .and_then(|g| g.get("theme"))
             .and_then(|t| t.as_str())
         {
-            // conductor-core already emits a deprecation warn for [general].theme —
-            // no need to duplicate it here.
+            // conductor-core's config migration normally moves this to [tui].theme
+            // on load; this covers files read before that has run.
             cfg.theme = Some(legacy.to_string());
         }
     }
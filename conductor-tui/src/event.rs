@@ -6,6 +6,13 @@ use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, KeyEventKind};
 
 use crate::action::Action;
 
+/// Terminal input forwarded to the main loop.
+pub enum InputEvent {
+    Key(KeyEvent),
+    /// Text from a bracketed paste, delivered in one piece.
+    Paste(String),
+}
+
 /// Notification sent to wake the main loop.
 enum Wake {
     Input,
@@ -46,14 +53,14 @@ impl BackgroundSender {
 /// agent output, DB polls, or other background work.
 ///
 /// Architecture:
-///   - `input_rx`: key events from crossterm (high priority)
+///   - `input_rx`: key and paste events from crossterm (high priority)
 ///   - `bg_rx`: background actions from agent threads, DB poller, ticks (low priority)
 ///   - `wake_rx`: notification channel to unblock the main loop
 ///
 /// The main loop blocks on `wake_rx`, then drains `input_rx` first (always),
 /// then `bg_rx`. This guarantees input is never starved by background work.
 pub struct EventLoop {
-    input_rx: mpsc::Receiver<KeyEvent>,
+    input_rx: mpsc::Receiver<InputEvent>,
     bg_rx: mpsc::Receiver<Action>,
     bg_tx: BackgroundSender,
     wake_rx: mpsc::Receiver<Wake>,
@@ -79,7 +86,13 @@ impl EventLoop {
                 loop {
                     match event::read() {
                         Ok(CrosstermEvent::Key(key)) if key.kind == KeyEventKind::Press => {
-                            if input_tx.send(key).is_err() {
+                            if input_tx.send(InputEvent::Key(key)).is_err() {
+                                return;
+                            }
+                            let _ = input_wake_tx.send(Wake::Input);
+                        }
+                        Ok(CrosstermEvent::Paste(text)) => {
+                            if input_tx.send(InputEvent::Paste(text)).is_err() {
                                 return;
                             }
                             let _ = input_wake_tx.send(Wake::Input);
@@ -118,13 +131,13 @@ impl EventLoop {
         while self.wake_rx.try_recv().is_ok() {}
     }
 
-    /// Drain all pending key events (high priority).
-    pub fn drain_input(&self) -> Vec<KeyEvent> {
-        let mut keys = Vec::new();
-        while let Ok(key) = self.input_rx.try_recv() {
-            keys.push(key);
+    /// Drain all pending key and paste events (high priority).
+    pub fn drain_input(&self) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = self.input_rx.try_recv() {
+            events.push(event);
         }
-        keys
+        events
     }
 
     /// Drain all pending background actions (low priority).
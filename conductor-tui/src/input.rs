@@ -1,9 +1,12 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use conductor_core::agent::DirtyWorktreeChoice;
+use conductor_core::worktree::TicketUrl;
+
 use crate::action::Action;
 use crate::state::{
     AppState, ColumnFocus, Modal, RepoDetailFocus, View, WorkflowRunDetailFocus,
-    WorktreeDetailFocus,
+    WorktreeDetailFocus, ABANDON_REASON_ROWS,
 };
 
 /// Map a key event to an action based on the current app state.
@@ -40,6 +43,18 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
                 _ => Action::None,
             };
         }
+        Modal::TicketLinkPicker { .. } => {
+            // Letters go to the search query, so only arrow keys move.
+            return match key.code {
+                KeyCode::Enter => Action::InputSubmit,
+                KeyCode::Esc => Action::DismissModal,
+                KeyCode::Up => Action::MoveUp,
+                KeyCode::Down => Action::MoveDown,
+                KeyCode::Backspace => Action::InputBackspace,
+                KeyCode::Char(c) => Action::InputChar(c),
+                _ => Action::None,
+            };
+        }
         Modal::AgentPrompt { .. } => {
             // Ctrl+S submits; Ctrl+D clears; Enter inserts a newline; Esc cancels
             if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -78,6 +93,8 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
                 KeyCode::Esc | KeyCode::Char('q') => Action::DismissModal,
                 KeyCode::Char('o') => Action::OpenTicketUrl,
                 KeyCode::Char('y') => Action::CopyTicketUrl,
+                KeyCode::Char('n') => Action::CommentOnTicket,
+                KeyCode::Char('X') => Action::CloseTicketUpstream,
                 _ => Action::None,
             };
         }
@@ -261,6 +278,25 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
                 };
             }
         }
+        Modal::AbandonReasonPicker { .. } => {
+            return match key.code {
+                KeyCode::Esc => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Enter => Action::InputSubmit,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    let n = c.to_digit(10).unwrap() as usize;
+                    if (1..=ABANDON_REASON_ROWS).contains(&n) {
+                        Action::SelectListItem(n - 1)
+                    } else {
+                        Action::None
+                    }
+                }
+                _ => Action::None,
+            };
+        }
         Modal::TemplatePicker { ref items, .. } => {
             return match key.code {
                 KeyCode::Esc => Action::DismissModal,
@@ -292,6 +328,90 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
                 _ => Action::None,
             };
         }
+        Modal::AgentInbox { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Enter => Action::InboxOpen,
+                KeyCode::Char('r') => Action::InboxMarkReviewed,
+                KeyCode::Char('a') => Action::InboxMarkAllReviewed,
+                KeyCode::Char('d') => Action::InboxViewDiff,
+                KeyCode::Char('P') => Action::InboxPush,
+                KeyCode::Char('p') => Action::InboxCreatePr,
+                KeyCode::Char('R') => Action::InboxRerun,
+                _ => Action::None,
+            };
+        }
+        Modal::MessageLog { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Char('c') => Action::ClearMessageLog,
+                _ => Action::None,
+            };
+        }
+        Modal::Roadmap { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('R') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                _ => Action::None,
+            };
+        }
+        Modal::Reports { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('$') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Tab => Action::CycleReportGrouping,
+                KeyCode::Char('w') => Action::CycleReportWindow,
+                KeyCode::Char('h') => Action::OpenActivityHeatmap,
+                _ => Action::None,
+            };
+        }
+        Modal::ActivityHeatmap { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('$') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Tab => Action::CycleActivityGrouping,
+                KeyCode::Char('h') => Action::OpenReports,
+                _ => Action::None,
+            };
+        }
+        Modal::Grooming { .. } => {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('P') => Action::DismissModal,
+                KeyCode::Up | KeyCode::Char('k') => Action::MoveUp,
+                KeyCode::Down | KeyCode::Char('j') => Action::MoveDown,
+                KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
+                KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
+                KeyCode::Char('a') | KeyCode::Enter => Action::AcceptGroomingProposal,
+                KeyCode::Char('d') => Action::DismissGroomingProposal,
+                _ => Action::None,
+            };
+        }
+        Modal::DirtyWorktree { .. } => {
+            return match key.code {
+                KeyCode::Char('s') => Action::ResolveDirtyWorktree(DirtyWorktreeChoice::Stash),
+                KeyCode::Char('c') => Action::ResolveDirtyWorktree(DirtyWorktreeChoice::CommitWip),
+                KeyCode::Char('p') => Action::ResolveDirtyWorktree(DirtyWorktreeChoice::Proceed),
+                KeyCode::Esc | KeyCode::Char('q') => Action::DismissModal,
+                _ => Action::None,
+            };
+        }
         Modal::Progress { .. } => {
             // Non-dismissable: swallow all keys while operation is in progress.
             return Action::None;
@@ -444,17 +564,85 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
         match key.code {
             KeyCode::Char('o') => return Action::OpenTicketUrl,
             KeyCode::Char('y') => return Action::CopyTicketUrl,
+            KeyCode::Char('n') => return Action::CommentOnTicket,
+            KeyCode::Char('X') => return Action::CloseTicketUpstream,
             KeyCode::Char('w') => return Action::PickWorkflow,
             KeyCode::Char('L') => return Action::EnterLabelFilter,
             KeyCode::Char('g') => return Action::OpenTicketGraphView,
+            KeyCode::Char('R') => return Action::OpenRoadmap,
+            KeyCode::Char('P') => return Action::OpenGrooming,
+            KeyCode::Char('W') => return Action::ToggleWatch,
             KeyCode::Char(' ') if state.column_focus == crate::state::ColumnFocus::Content => {
                 return Action::ToggleTicketCollapse;
             }
             KeyCode::Char('#') => return Action::CycleTicketSort,
+            KeyCode::Char('N') => return Action::CreateWithAgent,
             _ => {}
         }
     }
 
+    // View-specific keybindings (Today view)
+    if state.view == View::Today {
+        return match key.code {
+            KeyCode::Esc => Action::Back,
+            KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+            KeyCode::Enter => Action::Select,
+            KeyCode::Char('o') => Action::TodayOpenUrl,
+            KeyCode::Char('r') => Action::RefreshToday,
+            KeyCode::Char('?') => Action::ShowHelp,
+            KeyCode::Char('q') => Action::Quit,
+            _ => Action::None,
+        };
+    }
+
+    // View-specific keybindings (My Queue view)
+    if state.view == View::MyQueue {
+        return match key.code {
+            KeyCode::Esc => Action::Back,
+            KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+            KeyCode::Enter => Action::Select,
+            KeyCode::Char('o') => Action::MyQueueOpenUrl,
+            KeyCode::Char('r') => Action::RefreshMyQueue,
+            KeyCode::Char('?') => Action::ShowHelp,
+            KeyCode::Char('q') => Action::Quit,
+            _ => Action::None,
+        };
+    }
+
+    // View-specific keybindings (Agent Run Detail view)
+    if state.view == View::AgentRunDetail {
+        return match key.code {
+            KeyCode::Esc => Action::Back,
+            KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+            KeyCode::Tab => Action::NextPanel,
+            KeyCode::BackTab => Action::PrevPanel,
+            KeyCode::Enter => Action::Select,
+            KeyCode::Char('y') => Action::AgentRunDetailCopySessionId,
+            KeyCode::Char('?') => Action::ShowHelp,
+            KeyCode::Char('q') => Action::Quit,
+            _ => Action::None,
+        };
+    }
+
+    // View-specific keybindings (Worktree Diff view)
+    if state.view == View::WorktreeDiff {
+        return match key.code {
+            KeyCode::Esc => Action::Back,
+            KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+            KeyCode::Tab => Action::NextPanel,
+            KeyCode::BackTab => Action::PrevPanel,
+            KeyCode::Enter => Action::Select,
+            KeyCode::Char('r') => Action::RefreshWorktreeDiff,
+            KeyCode::Char('?') => Action::ShowHelp,
+            KeyCode::Char('q') => Action::Quit,
+            _ => Action::None,
+        };
+    }
+
     // View-specific keybindings (Settings view)
     if state.view == View::Settings {
         use crate::state::{RuntimeDetailFocus, SettingsCategory, SettingsFocus};
@@ -567,6 +755,8 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
             KeyCode::Char('r') => {
                 if in_runtime_detail && env_focused {
                     Action::RuntimeDetailEnvToggleReveal
+                } else if state.settings_category == SettingsCategory::Tools {
+                    Action::RefreshTools
                 } else {
                     Action::None
                 }
@@ -602,6 +792,9 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
             )
         });
 
+        let has_ticket_change = state
+            .selected_worktree()
+            .is_some_and(|wt| wt.ticket_change.is_some());
         let focus = state.worktree_detail_focus;
 
         // PromptInput capture is hoisted above the Ctrl+d/Ctrl+u scroll
@@ -611,6 +804,8 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
             KeyCode::Char('X') if !is_active => return Action::ClearConversation,
             KeyCode::Char('x') if is_active => return Action::StopAgent,
             KeyCode::Char('R') if is_failed => return Action::RestartAgent,
+            KeyCode::Char('v') if !is_active => return Action::ReviewDiff,
+            KeyCode::Char('V') => return Action::ViewDiffReview,
             KeyCode::Char('f') if is_waiting_for_feedback => return Action::SubmitFeedback,
             KeyCode::Char('F') if is_waiting_for_feedback => return Action::DismissFeedback,
             KeyCode::Char('r') => return Action::ResumeWorktreeWorkflow,
@@ -618,6 +813,14 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
             KeyCode::Char('t') => return Action::PickTemplate,
             KeyCode::Char('y') => return Action::WorktreeDetailCopy,
             KeyCode::Char('o') => return Action::WorktreeDetailOpen,
+            KeyCode::Char('W') => return Action::ToggleWatch,
+            KeyCode::Char('a') if has_ticket_change => return Action::AcknowledgeTicketChange,
+            KeyCode::Char('B') => return Action::Restack,
+            KeyCode::Char('s') => return Action::SyncWithBase,
+            KeyCode::Char('u') => return Action::ScanBranchTodos,
+            KeyCode::Char('h') => return Action::OpenAgentRunDetail,
+            KeyCode::Char('D') => return Action::OpenWorktreeDiff,
+            KeyCode::Char('c') if !is_active => return Action::CommitWorktree,
             KeyCode::Char('j')
                 if focus == WorktreeDetailFocus::InfoPanel
                     && state.column_focus == ColumnFocus::Content =>
@@ -746,12 +949,20 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
                 {
                     return Action::ToggleAgentIssues
                 }
+                KeyCode::Enter
+                    if state.repo_detail_info_row == crate::state::repo_info_row::TOOLCHAIN =>
+                {
+                    return Action::DetectToolchain
+                }
                 _ => {}
             }
         }
         if state.repo_detail_focus == crate::state::RepoDetailFocus::Worktrees {
-            if let KeyCode::Char('w') = key.code {
-                return Action::PickWorkflow;
+            match key.code {
+                KeyCode::Char('w') => return Action::PickWorkflow,
+                KeyCode::Char('W') => return Action::ToggleWatch,
+                KeyCode::Char('B') => return Action::Restack,
+                _ => {}
             }
         }
         if state.repo_detail_focus == crate::state::RepoDetailFocus::Prs {
@@ -811,6 +1022,8 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
 
         // Toggle closed tickets visibility (all ticket views)
         KeyCode::Char('A') => Action::ToggleClosedTickets,
+        // Toggle current-sprint ticket filter (all ticket views)
+        KeyCode::Char('C') => Action::ToggleCurrentSprint,
 
         // Toggle workflow column visibility
         KeyCode::Char('\\') => Action::ToggleWorkflowColumn,
@@ -822,10 +1035,24 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
         // Open the in-TUI theme picker
         KeyCode::Char('T') => Action::ShowThemePicker,
 
+        // Finished agent runs not yet reviewed
+        KeyCode::Char('U') => Action::OpenAgentInbox,
+        // Everything needing attention across repos
+        KeyCode::Char('!') => Action::OpenToday,
+        // Tickets assigned to or mentioning you across repos
+        KeyCode::Char('@') => Action::OpenMyQueue,
+        // Emergency stop for every running agent (asks first)
+        KeyCode::Char('K') => Action::StopAllAgents,
+        // Status message history
+        KeyCode::Char('m') => Action::OpenMessageLog,
+        // Agent cost rollups
+        KeyCode::Char('$') => Action::OpenReports,
+
         // CRUD actions
         KeyCode::Char('a') => Action::RegisterRepo,
         KeyCode::Char('c') => Action::Create,
         KeyCode::Char('i') => Action::AdoptWorktree,
+        KeyCode::Char('u') => Action::WorktreeFromUrl,
         KeyCode::Char('d') => Action::Delete,
         KeyCode::Char('s') => Action::SyncTickets,
         KeyCode::Char('S') => Action::OpenSettings,
@@ -835,6 +1062,36 @@ pub fn map_key(key: KeyEvent, state: &AppState) -> Action {
     }
 }
 
+/// Map a bracketed paste to an action when it should not be typed in as
+/// keys: an issue or PR URL pasted while no modal, filter or prompt box is
+/// taking text starts a worktree for it. Returns `None` otherwise.
+pub fn map_paste(text: &str, state: &AppState) -> Option<Action> {
+    let takes_text = !matches!(state.modal, Modal::None)
+        || state.any_filter_active()
+        || (state.column_focus == ColumnFocus::Content
+            && ((state.view == View::WorktreeDetail
+                && state.worktree_detail_focus == WorktreeDetailFocus::PromptInput)
+                || (state.view == View::RepoDetail
+                    && state.repo_detail_focus == RepoDetailFocus::RepoAgentPromptInput)));
+    if takes_text || TicketUrl::parse(text).is_err() {
+        return None;
+    }
+    Some(Action::CreateWorktreeFromUrl(text.trim().to_string()))
+}
+
+/// Replay pasted text as the key presses a terminal without bracketed paste
+/// would have sent. Line breaks become Enter.
+pub fn paste_keys(text: &str) -> Vec<KeyEvent> {
+    text.replace("\r\n", "\n")
+        .chars()
+        .map(|c| match c {
+            '\n' | '\r' => KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()),
+            '\t' => KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+            c => KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()),
+        })
+        .collect()
+}
+
 /// Shared prompt-input key router. Returns `Some(action)` for keys the
 /// persistent prompt textarea should NOT receive (submit, blur, panel-cycle);
 /// returns `None` to signal "forward to the textarea." Used by both the
@@ -967,6 +1224,7 @@ mod tests {
                 base_branch: None,
                 stale_days: None,
                 inferred_from: None,
+                resolved_base: None,
             });
         }
         for i in 1..item_count {
@@ -977,6 +1235,7 @@ mod tests {
                 base_branch: Some("main".into()),
                 stale_days: None,
                 inferred_from: None,
+                resolved_base: None,
             });
         }
         let (ordered, tree_positions) = crate::state::build_branch_picker_tree(&items);
@@ -1355,6 +1614,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn worktree_detail_v_keys_review_the_diff() {
+        let state = worktree_detail_state_with_focus(WorktreeDetailFocus::InfoPanel);
+        assert!(matches!(
+            map_key(key(KeyCode::Char('v')), &state),
+            Action::ReviewDiff
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('V')), &state),
+            Action::ViewDiffReview
+        ));
+    }
+
+    #[test]
+    fn worktree_detail_u_rescans_branch_todos() {
+        let state = worktree_detail_state_with_focus(WorktreeDetailFocus::InfoPanel);
+        assert!(matches!(
+            map_key(key(KeyCode::Char('u')), &state),
+            Action::ScanBranchTodos
+        ));
+    }
+
     // --- Removed global bindings (p, P, t, w, D) must not fire in Dashboard ---
 
     fn dashboard_state() -> AppState {
@@ -1497,6 +1778,355 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn shift_w_toggles_watch_in_worktree_detail_and_ticket_list() {
+        let state = worktree_detail_state_with_focus(WorktreeDetailFocus::InfoPanel);
+        assert!(matches!(
+            map_key(key(KeyCode::Char('W')), &state),
+            Action::ToggleWatch
+        ));
+
+        let mut state = AppState::new();
+        state.view = View::RepoDetail;
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Tickets;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('W')), &state),
+            Action::ToggleWatch
+        ));
+    }
+
+    #[test]
+    fn shift_b_restacks_in_worktree_detail_and_repo_worktrees() {
+        let state = worktree_detail_state_with_focus(WorktreeDetailFocus::InfoPanel);
+        assert!(matches!(
+            map_key(key(KeyCode::Char('B')), &state),
+            Action::Restack
+        ));
+
+        let mut state = AppState::new();
+        state.view = View::RepoDetail;
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Worktrees;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('B')), &state),
+            Action::Restack
+        ));
+    }
+
+    #[test]
+    fn s_syncs_worktree_with_base_only_in_worktree_detail() {
+        let state = worktree_detail_state_with_focus(WorktreeDetailFocus::InfoPanel);
+        assert!(matches!(
+            map_key(key(KeyCode::Char('s')), &state),
+            Action::SyncWithBase
+        ));
+
+        let state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('s')), &state),
+            Action::SyncTickets
+        ));
+    }
+
+    #[test]
+    fn bang_opens_today_and_today_keys_navigate() {
+        let mut state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('!')), &state),
+            Action::OpenToday
+        ));
+
+        state.view = View::Today;
+        assert!(matches!(
+            map_key(key(KeyCode::Enter), &state),
+            Action::Select
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('r')), &state),
+            Action::RefreshToday
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('o')), &state),
+            Action::TodayOpenUrl
+        ));
+        assert!(matches!(map_key(key(KeyCode::Esc), &state), Action::Back));
+        // Destructive global keys do nothing here.
+        assert!(matches!(
+            map_key(key(KeyCode::Char('d')), &state),
+            Action::None
+        ));
+    }
+
+    #[test]
+    fn at_opens_my_queue_and_its_keys_navigate() {
+        let mut state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('@')), &state),
+            Action::OpenMyQueue
+        ));
+
+        state.view = View::MyQueue;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('r')), &state),
+            Action::RefreshMyQueue
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('o')), &state),
+            Action::MyQueueOpenUrl
+        ));
+        assert!(matches!(map_key(key(KeyCode::Esc), &state), Action::Back));
+    }
+
+    #[test]
+    fn worktree_detail_opens_run_history_and_diff() {
+        let mut state = AppState::new();
+        state.view = View::WorktreeDetail;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('h')), &state),
+            Action::OpenAgentRunDetail
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('D')), &state),
+            Action::OpenWorktreeDiff
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('c')), &state),
+            Action::CommitWorktree
+        ));
+
+        state.view = View::AgentRunDetail;
+        assert!(matches!(
+            map_key(key(KeyCode::Tab), &state),
+            Action::NextPanel
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('y')), &state),
+            Action::AgentRunDetailCopySessionId
+        ));
+        assert!(matches!(map_key(key(KeyCode::Esc), &state), Action::Back));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('d')), &state),
+            Action::None
+        ));
+    }
+
+    #[test]
+    fn pasted_issue_url_starts_a_worktree_unless_text_has_focus() {
+        let url = "https://github.com/acme/widget/issues/42";
+        let mut state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('u')), &state),
+            Action::WorktreeFromUrl
+        ));
+        assert!(matches!(
+            map_paste(&format!("{url}\n"), &state),
+            Some(Action::CreateWorktreeFromUrl(u)) if u == url
+        ));
+        assert!(map_paste("fix the login page", &state).is_none());
+
+        state.view = View::WorktreeDetail;
+        state.worktree_detail_focus = WorktreeDetailFocus::PromptInput;
+        assert!(map_paste(url, &state).is_none());
+
+        state.view = View::Dashboard;
+        state.modal = Modal::Help;
+        assert!(map_paste(url, &state).is_none());
+    }
+
+    #[test]
+    fn paste_keys_replays_text_with_line_breaks_as_enter() {
+        let codes: Vec<KeyCode> = paste_keys("ab\r\nc").into_iter().map(|k| k.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                KeyCode::Char('a'),
+                KeyCode::Char('b'),
+                KeyCode::Enter,
+                KeyCode::Char('c')
+            ]
+        );
+    }
+
+    #[test]
+    fn shift_u_opens_agent_inbox_and_inbox_keys_map_to_quick_actions() {
+        let mut state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('U')), &state),
+            Action::OpenAgentInbox
+        ));
+
+        state.modal = Modal::AgentInbox { selected: 0 };
+        assert!(matches!(
+            map_key(key(KeyCode::Enter), &state),
+            Action::InboxOpen
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('r')), &state),
+            Action::InboxMarkReviewed
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('d')), &state),
+            Action::InboxViewDiff
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('P')), &state),
+            Action::InboxPush
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('p')), &state),
+            Action::InboxCreatePr
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('R')), &state),
+            Action::InboxRerun
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Esc), &state),
+            Action::DismissModal
+        ));
+    }
+
+    #[test]
+    fn m_opens_message_log_and_log_keys_scroll_clear_and_close() {
+        let mut state = AppState::new();
+        assert!(matches!(
+            map_key(key(KeyCode::Char('m')), &state),
+            Action::OpenMessageLog
+        ));
+
+        state.modal = Modal::MessageLog { selected: 0 };
+        assert!(matches!(
+            map_key(key(KeyCode::Char('j')), &state),
+            Action::MoveDown
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('c')), &state),
+            Action::ClearMessageLog
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('m')), &state),
+            Action::DismissModal
+        ));
+    }
+
+    #[test]
+    fn p_in_ticket_list_opens_grooming_and_a_accepts() {
+        let mut state = AppState::new();
+        state.view = View::RepoDetail;
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Tickets;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('P')), &state),
+            Action::OpenGrooming
+        ));
+
+        state.modal = Modal::Grooming {
+            title: "Grooming proposals".into(),
+            proposals: vec![],
+            selected: 0,
+        };
+        assert!(matches!(
+            map_key(key(KeyCode::Char('a')), &state),
+            Action::AcceptGroomingProposal
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('d')), &state),
+            Action::DismissGroomingProposal
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('P')), &state),
+            Action::DismissModal
+        ));
+    }
+
+    #[test]
+    fn shift_n_in_ticket_list_creates_with_agent_and_c_still_toggles_sprint() {
+        let mut state = AppState::new();
+        state.view = View::RepoDetail;
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Tickets;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('N')), &state),
+            Action::CreateWithAgent
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('c')), &state),
+            Action::Create
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('C')), &state),
+            Action::ToggleCurrentSprint
+        ));
+
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Worktrees;
+        assert!(!matches!(
+            map_key(key(KeyCode::Char('N')), &state),
+            Action::CreateWithAgent
+        ));
+    }
+
+    #[test]
+    fn r_in_tools_settings_rechecks_tools() {
+        let mut state = AppState::new();
+        state.view = View::Settings;
+        state.settings_category = crate::state::SettingsCategory::Tools;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('r')), &state),
+            Action::RefreshTools
+        ));
+
+        state.settings_category = crate::state::SettingsCategory::Notifications;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('r')), &state),
+            Action::None
+        ));
+    }
+
+    #[test]
+    fn r_in_ticket_list_opens_roadmap_and_r_closes_it() {
+        let mut state = AppState::new();
+        state.view = View::RepoDetail;
+        state.repo_detail_focus = crate::state::RepoDetailFocus::Tickets;
+        assert!(matches!(
+            map_key(key(KeyCode::Char('R')), &state),
+            Action::OpenRoadmap
+        ));
+
+        state.modal = Modal::Roadmap {
+            title: "Roadmap".into(),
+            groups: vec![],
+            selected: 0,
+        };
+        assert!(matches!(
+            map_key(key(KeyCode::Char('k')), &state),
+            Action::MoveUp
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('R')), &state),
+            Action::DismissModal
+        ));
+    }
+
+    #[test]
+    fn abandon_reason_picker_digits_select_within_range() {
+        let mut state = AppState::new();
+        state.modal = Modal::AbandonReasonPicker {
+            repo_slug: "r".into(),
+            wt_slug: "w".into(),
+            message: String::new(),
+            selected: 0,
+        };
+        assert!(matches!(
+            map_key(key(KeyCode::Char('5')), &state),
+            Action::SelectListItem(4)
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('6')), &state),
+            Action::None
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Enter), &state),
+            Action::InputSubmit
+        ));
+    }
+
     #[test]
     fn w_maps_to_pick_workflow_in_workflow_column_focus() {
         let mut state = AppState::new();
@@ -1939,4 +2569,38 @@ mod tests {
             Action::None
         ));
     }
+
+    #[test]
+    fn dirty_worktree_modal_keys_pick_a_choice() {
+        let mut state = AppState::new();
+        state.modal = Modal::DirtyWorktree {
+            files: vec!["src/lib.rs".into()],
+            launch: Box::new(crate::state::PendingAgentLaunch {
+                prompt: "fix it".into(),
+                worktree_id: "w1".into(),
+                worktree_path: "/tmp/w1".into(),
+                worktree_slug: "feat-x".into(),
+                resume_session_id: None,
+                model: None,
+                runtime: None,
+                override_budget: false,
+            }),
+        };
+        assert!(matches!(
+            map_key(key(KeyCode::Char('s')), &state),
+            Action::ResolveDirtyWorktree(DirtyWorktreeChoice::Stash)
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('c')), &state),
+            Action::ResolveDirtyWorktree(DirtyWorktreeChoice::CommitWip)
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Char('p')), &state),
+            Action::ResolveDirtyWorktree(DirtyWorktreeChoice::Proceed)
+        ));
+        assert!(matches!(
+            map_key(key(KeyCode::Esc), &state),
+            Action::DismissModal
+        ));
+    }
 }
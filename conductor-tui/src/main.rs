@@ -14,6 +14,8 @@ mod test_support;
 
 use anyhow::Result;
 
+use conductor_core::config::{db_path, load_config, Config};
+use conductor_core::db::{is_busy, open_database_readonly};
 use conductor_core::Conductor;
 use config::{ensure_tui_dirs, load_tui_config};
 use theme::Theme;
@@ -39,7 +41,7 @@ fn main() -> Result<()> {
             .init();
     }
 
-    let conductor = Conductor::open()?;
+    let (conn, config) = open_conductor()?;
 
     let tui_config = load_tui_config()?;
     // ensure_tui_dirs must run before Theme::from_name so custom themes can be found.
@@ -54,9 +56,35 @@ fn main() -> Result<()> {
     };
 
     let mut terminal = ratatui::init();
-    let result =
-        app::App::new(conductor.conn, conductor.config, tui_config, theme).run(&mut terminal);
+    // Pastes arrive as one event so a pasted issue URL can start a worktree.
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::event::EnableBracketedPaste);
+    let result = app::App::new(conn, config, tui_config, theme).run(&mut terminal);
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableBracketedPaste);
     ratatui::restore();
 
     result
 }
+
+/// Open the database, retrying with backoff while another writer (a long
+/// migration, a busy workflow) holds the lock. If it stays locked, start on a
+/// read-only snapshot so monitoring keeps working; the app switches to a
+/// read-write connection once the poller can open one.
+fn open_conductor() -> Result<(rusqlite::Connection, Config)> {
+    let mut delay = std::time::Duration::from_millis(250);
+    let mut attempt = 1;
+    loop {
+        match Conductor::open() {
+            Ok(conductor) => return Ok((conductor.conn, conductor.config)),
+            Err(e) if is_busy(&e) && attempt < 3 => {
+                std::thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) if is_busy(&e) && db_path().exists() => {
+                eprintln!("Database is locked ({e}); starting read-only");
+                return Ok((open_database_readonly(&db_path())?, load_config()?));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
@@ -1,8 +1,8 @@
 pub use conductor_core::notify::{
-    fire_agent_run_notification, fire_cost_spike_notification, fire_duration_spike_notification,
-    fire_feedback_notification, fire_gate_notification, fire_gate_pending_too_long_notification,
-    fire_grouped_gate_notification, fire_workflow_notification, AgentRunNotificationArgs,
-    CostSpikeArgs, DurationSpikeArgs, FeedbackNotificationParams, GateNotificationParams,
-    GatePendingTooLongArgs, GroupedGateNotificationParams, NotificationCtx,
-    WorkflowNotificationArgs,
+    fire_agent_alert_notification, fire_agent_run_notification, fire_cost_spike_notification,
+    fire_duration_spike_notification, fire_feedback_notification, fire_gate_notification,
+    fire_gate_pending_too_long_notification, fire_grouped_gate_notification,
+    fire_watch_notification, fire_workflow_notification, AgentRunNotificationArgs, CostSpikeArgs,
+    DurationSpikeArgs, FeedbackNotificationParams, GateNotificationParams, GatePendingTooLongArgs,
+    GroupedGateNotificationParams, NotificationCtx, WorkflowNotificationArgs,
 };
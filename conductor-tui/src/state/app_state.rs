@@ -2,9 +2,13 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
+use conductor_core::agent::{AgentRun, AgentRunEvent, FileDiff};
 use conductor_core::github::GithubPr;
 use conductor_core::repo::Repo;
+use conductor_core::tickets::QueueTicket;
 use conductor_core::tickets::Ticket;
+use conductor_core::today::TodayItem;
+use conductor_core::tools::{Tool, ToolReport};
 use conductor_core::workflow::{PendingGateRow, WorkflowRunStatus};
 use conductor_core::worktree::Worktree;
 use ratatui::widgets::ListState;
@@ -12,10 +16,11 @@ use ratatui::widgets::ListState;
 use super::workflow_rows::max_iteration_for_run;
 use super::{
     build_ticket_tree_indices_sorted_by, build_worktree_tree, build_worktree_tree_indices,
-    parse_target_label, push_children, push_steps_for_run, ColumnFocus, DashboardRow, DataCache,
-    FilterState, Modal, RepoDetailFocus, RuntimeDetailState, RuntimeDisplayRow, SettingsCategory,
-    SettingsFocus, TargetType, TicketSort, TreePosition, View, WorkflowDefFocus,
-    WorkflowRunDetailFocus, WorkflowRunRow, WorkflowsFocus,
+    parse_target_label, push_children, push_steps_for_run, AgentRunDetailFocus, ColumnFocus,
+    DashboardRow, DataCache, FilterState, MessageLog, Modal, RepoDetailFocus, RuntimeDetailState,
+    RuntimeDisplayRow, SettingsCategory, SettingsFocus, Severity, SyncProgress, TargetType,
+    TicketSort, TreePosition, View, WorkflowDefFocus, WorkflowRunDetailFocus, WorkflowRunRow,
+    WorkflowsFocus, WorktreeDiffFocus,
 };
 use crate::theme::Theme;
 
@@ -57,6 +62,37 @@ pub struct AppState {
     /// When the PR list was last successfully fetched (None = never).
     pub pr_last_fetched_at: Option<std::time::Instant>,
 
+    /// Rows of the Today view, as of its last load.
+    pub today_items: Vec<TodayItem>,
+    pub today_index: usize,
+    /// A Today load is running in the background.
+    pub today_loading: bool,
+
+    /// Rows of the My Queue view, as of its last load.
+    pub my_queue_items: Vec<QueueTicket>,
+    pub my_queue_index: usize,
+    pub my_queue_loading: bool,
+
+    /// Agent Run Detail view: the selected worktree's runs, newest first.
+    pub agent_run_detail_runs: Vec<AgentRun>,
+    pub agent_run_detail_index: usize,
+    /// Events of the selected run, oldest first.
+    pub agent_run_detail_events: Vec<AgentRunEvent>,
+    pub agent_run_detail_event_index: usize,
+    pub agent_run_detail_focus: AgentRunDetailFocus,
+
+    /// Worktree Diff view: the selected worktree's diff against its base.
+    pub worktree_diff_files: Vec<FileDiff>,
+    pub worktree_diff_index: usize,
+    /// First visible line of the selected file's diff.
+    pub worktree_diff_scroll: usize,
+    pub worktree_diff_focus: WorktreeDiffFocus,
+    /// A diff load is running in the background.
+    pub worktree_diff_loading: bool,
+    /// Ticket whose worktree is being created with `N`; once created, an
+    /// agent is launched on it with the ticket prompt instead of asking.
+    pub auto_agent_ticket_id: Option<String>,
+
     // Pre-filtered ticket lists (closed + text filter applied); index into these for nav/actions
     pub filtered_tickets: Vec<Ticket>,
     pub filtered_detail_tickets: Vec<Ticket>,
@@ -93,6 +129,8 @@ pub struct AppState {
     pub status_message: Option<String>,
     /// When `status_message` was last set; used to auto-clear after a timeout.
     pub status_message_at: Option<std::time::Instant>,
+    /// Every status message shown this session; see [`MessageLog`].
+    pub message_log: MessageLog,
 
     /// Cached org list so navigating back from repo modal doesn't re-fetch.
     pub github_orgs_cache: Vec<String>,
@@ -133,6 +171,9 @@ pub struct AppState {
     /// When false (default), closed tickets are hidden in all ticket views.
     pub show_closed_tickets: bool,
 
+    /// When true, ticket views only show tickets in the current sprint / milestone.
+    pub current_sprint_only: bool,
+
     /// When false (default), completed and cancelled workflow runs are hidden in the workflow column.
     pub show_completed_workflow_runs: bool,
 
@@ -148,6 +189,14 @@ pub struct AppState {
     /// True while a manual ticket sync is running in the background.
     pub ticket_sync_in_progress: bool,
 
+    /// Per-repo progress of the current (or just finished) ticket sync.
+    pub sync_progress: Option<SyncProgress>,
+
+    /// Local time (HH:MM:SS) the database was first found locked by another
+    /// writer. While set, data comes from a read-only snapshot and the footer
+    /// shows a stale-data banner.
+    pub db_snapshot_since: Option<String>,
+
     /// Which column currently has keyboard focus: Content (left) or Workflow (right).
     pub column_focus: ColumnFocus,
 
@@ -196,6 +245,9 @@ pub struct AppState {
     /// When `Some`, the Runtimes pane is showing the drill-in detail view
     /// for the named runtime instead of the list.
     pub settings_runtime_detail: Option<RuntimeDetailState>,
+    /// External tool availability, probed in the background at startup.
+    /// `None` until the first probe lands.
+    pub tools: Option<ToolReport>,
 }
 
 /// Displayable snapshot of conductor config values for the Settings view.
@@ -244,6 +296,23 @@ impl AppState {
             detail_pr_index: 0,
             detail_gate_index: 0,
             pr_last_fetched_at: None,
+            today_items: Vec::new(),
+            today_index: 0,
+            today_loading: false,
+            my_queue_items: Vec::new(),
+            my_queue_index: 0,
+            my_queue_loading: false,
+            agent_run_detail_runs: Vec::new(),
+            agent_run_detail_index: 0,
+            agent_run_detail_events: Vec::new(),
+            agent_run_detail_event_index: 0,
+            agent_run_detail_focus: AgentRunDetailFocus::default(),
+            worktree_diff_files: Vec::new(),
+            worktree_diff_index: 0,
+            worktree_diff_scroll: 0,
+            worktree_diff_focus: WorktreeDiffFocus::default(),
+            worktree_diff_loading: false,
+            auto_agent_ticket_id: None,
             filtered_tickets: Vec::new(),
             filtered_detail_tickets: Vec::new(),
             detail_ticket_tree_positions: Vec::new(),
@@ -261,6 +330,7 @@ impl AppState {
             detail_ticket_sort: TicketSort::default(),
             status_message: None,
             status_message_at: None,
+            message_log: MessageLog::default(),
             github_orgs_cache: Vec::new(),
             workflows_focus: WorkflowsFocus::Runs,
             workflow_defs_collapsed: false,
@@ -281,10 +351,13 @@ impl AppState {
             expanded_foreach_step_ids: HashSet::new(),
             should_quit: false,
             show_closed_tickets: false,
+            current_sprint_only: false,
             show_completed_workflow_runs: false,
             show_dismissed_workflow_runs: false,
             cached_workflow_run_rows: Vec::new(),
             ticket_sync_in_progress: false,
+            sync_progress: None,
+            db_snapshot_since: None,
             loading_workflow_picker_defs: false,
             column_focus: ColumnFocus::Content,
             workflow_column_visible: true,
@@ -304,6 +377,7 @@ impl AppState {
             settings_hook_test_results: HashMap::new(),
             settings_display: SettingsDisplayCache::default(),
             settings_runtime_detail: None,
+            tools: None,
         }
     }
 
@@ -338,6 +412,12 @@ impl AppState {
         }
     }
 
+    /// Whether the startup probe found `tool` missing. Unknown until the
+    /// probe lands, which counts as present.
+    pub fn tool_missing(&self, tool: Tool) -> bool {
+        self.tools.as_ref().is_some_and(|r| !r.has(tool))
+    }
+
     /// Returns whether any filter is currently active.
     pub fn any_filter_active(&self) -> bool {
         self.filter.active || self.detail_ticket_filter.active || self.label_filter.active
@@ -379,7 +459,8 @@ impl AppState {
     }
 
     /// Rebuild the pre-filtered ticket vecs from the current source data,
-    /// `show_closed_tickets`, and the active text/label filters.  Must be called
+    /// `show_closed_tickets`, `current_sprint_only`, and the active text/label
+    /// filters.  Must be called
     /// whenever any of those inputs change.
     pub fn rebuild_filtered_tickets(&mut self) {
         let filter_query = self.filter.as_query();
@@ -389,6 +470,7 @@ impl AppState {
             .tickets
             .iter()
             .filter(|t| self.show_closed_tickets || t.state != "closed")
+            .filter(|t| !self.current_sprint_only || t.sprint_current)
             .filter(|t| match filter_query.as_deref() {
                 Some(f) if !f.is_empty() => t.matches_filter(f),
                 _ => true,
@@ -469,6 +551,9 @@ impl AppState {
             if !self.show_closed_tickets && t.state == "closed" {
                 continue;
             }
+            if self.current_sprint_only && !t.sprint_current {
+                continue;
+            }
 
             // Apply text/include filter.
             if let Some(ref set) = include_set {
@@ -534,6 +619,29 @@ impl AppState {
             },
             View::WorkflowDefDetail => (self.workflow_def_detail_scroll, 0),
             View::Settings => (self.settings_row_index, 0),
+            View::Today => (self.today_index, self.today_items.len()),
+            View::MyQueue => (self.my_queue_index, self.my_queue_items.len()),
+            View::AgentRunDetail => match self.agent_run_detail_focus {
+                AgentRunDetailFocus::Runs => (
+                    self.agent_run_detail_index,
+                    self.agent_run_detail_runs.len(),
+                ),
+                AgentRunDetailFocus::Events => (
+                    self.agent_run_detail_event_index,
+                    self.agent_run_detail_events.len(),
+                ),
+            },
+            View::WorktreeDiff => match self.worktree_diff_focus {
+                WorktreeDiffFocus::Files => {
+                    (self.worktree_diff_index, self.worktree_diff_files.len())
+                }
+                WorktreeDiffFocus::Diff => (
+                    self.worktree_diff_scroll,
+                    self.worktree_diff_files
+                        .get(self.worktree_diff_index)
+                        .map_or(0, |f| f.text.lines().count()),
+                ),
+            },
         }
     }
 
@@ -575,6 +683,21 @@ impl AppState {
             View::Settings => {
                 self.settings_row_index = index;
             }
+            View::Today => self.today_index = index,
+            View::MyQueue => self.my_queue_index = index,
+            View::AgentRunDetail => match self.agent_run_detail_focus {
+                AgentRunDetailFocus::Runs => self.agent_run_detail_index = index,
+                AgentRunDetailFocus::Events => self.agent_run_detail_event_index = index,
+            },
+            View::WorktreeDiff => match self.worktree_diff_focus {
+                WorktreeDiffFocus::Files => {
+                    if index != self.worktree_diff_index {
+                        self.worktree_diff_scroll = 0;
+                    }
+                    self.worktree_diff_index = index;
+                }
+                WorktreeDiffFocus::Diff => self.worktree_diff_scroll = index,
+            },
         }
     }
 
@@ -1077,6 +1200,23 @@ impl AppState {
         }
     }
 
+    /// Tickets listed by an open [`Modal::TicketLinkPicker`], best match
+    /// first; empty for any other modal.
+    pub fn ticket_link_matches(&self) -> Vec<&Ticket> {
+        let Modal::TicketLinkPicker {
+            ref repo_id,
+            ref query,
+            ..
+        } = self.modal
+        else {
+            return Vec::new();
+        };
+        conductor_core::tickets::search::search(
+            self.data.tickets.iter().filter(|t| &t.repo_id == repo_id),
+            query,
+        )
+    }
+
     /// Get the currently selected ticket from the dashboard list.
     #[allow(dead_code)]
     pub fn selected_ticket(&self) -> Option<&Ticket> {
@@ -1116,6 +1256,17 @@ impl AppState {
         }
     }
 
+    /// Appends `status_message` to the message log if it differs from
+    /// `previous`, the message shown before the action ran.
+    #[allow(dead_code)]
+    pub(crate) fn log_status_message_change(&mut self, previous: Option<&str>) {
+        if let Some(msg) = self.status_message.as_deref() {
+            if previous != Some(msg) {
+                self.message_log.push(Severity::classify(msg), msg);
+            }
+        }
+    }
+
     /// Updates `status_message_at` to reflect a change in `status_message` presence.
     /// `had_message` is whether a message was present before the action ran.
     #[allow(dead_code)]
@@ -1,15 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use conductor_core::agent::{
-    AgentCreatedIssue, AgentRun, AgentRunEvent, FeedbackRequest, TicketAgentTotals,
+    AgentCreatedIssue, AgentRun, AgentRunEvent, BudgetExceeded, DiffReview, FeedbackRequest,
+    InboxItem, QueuedAgentRun, TicketAgentTotals,
 };
 use conductor_core::repo::Repo;
-use conductor_core::tickets::{Ticket, TicketDependencies, TicketLabel};
+use conductor_core::tickets::{
+    Ticket, TicketAttachment, TicketDependencies, TicketLabel, TicketProgress,
+};
 use conductor_core::workflow::{
     ConductorWorkflowRun, FanOutItemRow, InputDecl, LiveEstimate, WorkflowDef, WorkflowRunStep,
     WorkflowStepSummary,
 };
-use conductor_core::worktree::Worktree;
+use conductor_core::worktree::{
+    BranchTodo, StackInfo, WorkspaceUsage, Worktree, WorktreeGitStatus,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct DataCache {
@@ -18,6 +23,8 @@ pub struct DataCache {
     pub tickets: Vec<Ticket>,
     /// ticket_id -> labels with colors (populated by DB poller)
     pub ticket_labels: HashMap<String, Vec<TicketLabel>>,
+    /// ticket_id -> attachments found in the ticket body or source (populated by DB poller)
+    pub ticket_attachments: HashMap<String, Vec<TicketAttachment>>,
     /// ticket_id -> dependency relationships (populated by DB poller)
     pub ticket_dependencies: HashMap<String, TicketDependencies>,
     /// repo_id -> slug for display
@@ -42,6 +49,11 @@ pub struct DataCache {
     pub ticket_worktrees: HashMap<String, Vec<Worktree>>,
     /// Issues created by agents for the currently viewed worktree
     pub agent_created_issues: Vec<AgentCreatedIssue>,
+    /// TODO/FIXME comments the currently viewed worktree's branch added, as of
+    /// its last scan
+    pub branch_todos: Vec<BranchTodo>,
+    /// Latest agent review of the currently viewed worktree's diff
+    pub latest_diff_review: Option<DiffReview>,
     /// Pending feedback request for the currently viewed worktree (if any)
     pub pending_feedback: Option<FeedbackRequest>,
     /// Most recent workflow run per worktree (worktree_id → run), for inline indicators.
@@ -90,6 +102,27 @@ pub struct DataCache {
     /// repo_id -> true when the repo has at least one configured issue source.
     /// Missing entries should be treated as false (no source).
     pub repo_has_issue_source: HashMap<String, bool>,
+    /// repo_id -> workspace disk usage against the configured quota.
+    /// Empty when no quota is configured.
+    pub workspace_usage: HashMap<String, WorkspaceUsage>,
+    /// worktree_id -> uncommitted changes and ahead/behind vs origin, for
+    /// live worktrees (populated by DB poller).
+    pub git_statuses: HashMap<String, WorktreeGitStatus>,
+    /// Agent runs waiting to start, in dispatch order (populated by DB poller).
+    pub agent_queue: Vec<QueuedAgentRun>,
+    /// IDs of watched worktrees (populated by DB poller).
+    pub watched_worktrees: HashSet<String>,
+    /// IDs of watched tickets (populated by DB poller).
+    pub watched_tickets: HashSet<String>,
+    /// worktree_id -> stack parent and restack state (populated by DB poller).
+    pub stack_infos: HashMap<String, StackInfo>,
+    /// Finished agent runs not yet reviewed (populated by DB poller).
+    pub agent_inbox: Vec<InboxItem>,
+    /// worktree_id -> spend against `[budget] worktree_usd`, for worktrees
+    /// that reached it (populated by DB poller).
+    pub over_budget: HashMap<String, BudgetExceeded>,
+    /// ticket_id -> linked worktree, latest agent and PR status (populated by DB poller).
+    pub ticket_progress: HashMap<String, TicketProgress>,
 }
 
 /// Aggregated stats across all agent runs for a worktree.
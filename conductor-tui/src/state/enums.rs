@@ -6,6 +6,15 @@ pub enum View {
     WorkflowRunDetail,
     WorkflowDefDetail,
     Settings,
+    /// Everything needing attention across repos, highest priority first.
+    Today,
+    /// Open tickets across repos assigned to or mentioning your `[identity]`.
+    MyQueue,
+    /// Every agent run on the selected worktree, with the selected run's
+    /// metadata and events.
+    AgentRunDetail,
+    /// The selected worktree's diff against its base, file by file.
+    WorktreeDiff,
 }
 
 /// Which pane of the Settings view has keyboard focus.
@@ -26,6 +35,7 @@ pub enum SettingsCategory {
     Appearance,
     Notifications,
     Runtimes,
+    Tools,
 }
 
 impl SettingsCategory {
@@ -35,6 +45,7 @@ impl SettingsCategory {
             SettingsCategory::Appearance,
             SettingsCategory::Notifications,
             SettingsCategory::Runtimes,
+            SettingsCategory::Tools,
         ]
     }
 
@@ -44,6 +55,7 @@ impl SettingsCategory {
             Self::Appearance => "Appearance",
             Self::Notifications => "Notifications",
             Self::Runtimes => "Runtimes",
+            Self::Tools => "Tools",
         }
     }
 }
@@ -304,6 +316,40 @@ impl WorktreeDetailFocus {
     }
 }
 
+/// Which pane of the Agent Run Detail view has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgentRunDetailFocus {
+    #[default]
+    Runs,
+    Events,
+}
+
+impl AgentRunDetailFocus {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Runs => Self::Events,
+            Self::Events => Self::Runs,
+        }
+    }
+}
+
+/// Which pane of the Worktree Diff view has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorktreeDiffFocus {
+    #[default]
+    Files,
+    Diff,
+}
+
+impl WorktreeDiffFocus {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Files => Self::Diff,
+            Self::Diff => Self::Files,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ColumnFocus {
     #[default]
@@ -342,8 +388,9 @@ pub mod repo_info_row {
     pub const WORKTREES_DIR: usize = 4;
     pub const MODEL: usize = 5;
     pub const AGENT_ISSUES: usize = 6;
+    pub const TOOLCHAIN: usize = 7;
     /// Total number of navigable rows (used for bounds clamping).
-    pub const COUNT: usize = 7;
+    pub const COUNT: usize = 8;
 }
 
 /// Named row indices for the WorkflowRunDetail info panel.
@@ -419,6 +466,9 @@ pub struct BranchPickerItem {
     pub stale_days: Option<u64>,
     /// Milestone title when this item was inferred from ticket metadata (`None` otherwise).
     pub inferred_from: Option<String>,
+    /// Default branch entry only: the base the linked ticket resolves to
+    /// (e.g. `release/1.x (label 'hotfix')`), when it is not the repo default.
+    pub resolved_base: Option<String>,
 }
 
 /// Target context for the generic workflow picker.
@@ -476,6 +526,21 @@ impl WorkflowPickerTarget {
     }
 }
 
+/// An agent launch held back while the user decides what to do with the
+/// worktree's uncommitted changes or its spent budget.
+#[derive(Debug, Clone)]
+pub struct PendingAgentLaunch {
+    pub prompt: String,
+    pub worktree_id: String,
+    pub worktree_path: String,
+    pub worktree_slug: String,
+    pub resume_session_id: Option<String>,
+    pub model: Option<String>,
+    pub runtime: Option<String>,
+    /// The user already chose to launch past the `[budget]`.
+    pub override_budget: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     /// Carry creation params through the clone-warning confirm flow.
@@ -485,17 +550,22 @@ pub enum ConfirmAction {
         ticket_id: Option<String>,
         from_pr: Option<u32>,
         from_branch: Option<String>,
-        /// When true, skip the dirty-state error in `ensure_base_up_to_date()`.
-        /// Set after the user confirms they want to proceed despite uncommitted changes.
+        /// Set after the user confirms they want to proceed despite uncommitted
+        /// changes, so creation does not warn about them again.
         force_dirty: bool,
     },
     DeleteWorktree {
         repo_slug: String,
         wt_slug: String,
+        /// Recorded if the worktree ends up abandoned rather than merged.
+        reason: Option<conductor_core::worktree::AbandonReason>,
     },
     UnregisterRepo {
         repo_slug: String,
     },
+    CloseTicketUpstream {
+        ticket_id: String,
+    },
     DeleteIssueSource {
         source_id: String,
         repo_id: String,
@@ -532,6 +602,12 @@ pub enum ConfirmAction {
         repo_slug: String,
         remote_url: String,
     },
+    /// Cancel every running or feedback-waiting agent run.
+    StopAllAgents,
+    /// Launch an agent even though its worktree or session spent its budget.
+    LaunchAgentOverBudget {
+        launch: Box<PendingAgentLaunch>,
+    },
     Quit,
 }
 
@@ -611,9 +687,6 @@ pub enum InputAction {
         ticket_id: Option<String>,
         from_branch: Option<String>,
     },
-    LinkTicket {
-        worktree_id: String,
-    },
     AgentPrompt {
         worktree_id: String,
         worktree_path: String,
@@ -640,13 +713,11 @@ pub enum InputAction {
         slug: String,
     },
     /// Set (or clear) the default model for a repo.
-    SetRepoModel {
-        slug: String,
-    },
+    SetRepoModel { slug: String },
+    /// Post a comment on a ticket's upstream issue.
+    TicketComment { ticket_id: String },
     /// Submit a response to a pending feedback request.
-    FeedbackResponse {
-        feedback_id: String,
-    },
+    FeedbackResponse { feedback_id: String },
     /// Second step: model picker for workflow runs.
     /// Carries the workflow target + inputs through the modal roundtrip.
     /// `runtime` is set when the picker selection came from a non-default
@@ -667,25 +738,20 @@ pub enum InputAction {
     /// Settings view: set agents.max_turns (positive u32, blank to reset to None)
     SettingsSetMaxTurns,
     /// Adopt an existing on-disk git worktree: user enters the path.
-    AdoptWorktree {
-        repo_slug: String,
-    },
+    AdoptWorktree { repo_slug: String },
+    /// Create a worktree from an issue or PR URL.
+    WorktreeFromUrl,
+    /// Commit all of a worktree's changes; a blank message is generated
+    /// from the agent's result.
+    CommitWorktree { repo_slug: String, wt_slug: String },
     /// Settings → Runtimes: add a new runtime entry (first step: name).
     SettingsAddRuntime,
     /// Settings → Runtimes detail: append a single model to a runtime's
     /// `supported_models` list.
-    SettingsAddModel {
-        runtime: String,
-    },
+    SettingsAddModel { runtime: String },
     /// Settings → Runtimes detail: replace the model at `index` with a new value.
-    SettingsEditModel {
-        runtime: String,
-        index: usize,
-    },
+    SettingsEditModel { runtime: String, index: usize },
     /// Settings → Runtimes detail: edit the value of an existing env var.
     /// The key is fixed; only the value changes.
-    SettingsEditEnvValue {
-        runtime: String,
-        key: String,
-    },
+    SettingsEditEnvValue { runtime: String, key: String },
 }
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// Entries kept before the oldest is dropped.
+pub const MESSAGE_LOG_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Best-effort severity of a free-form status message. Most call sites
+    /// set `status_message` directly, so the wording is all there is to go on.
+    pub fn classify(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        if ["error", "fail", "cannot", "could not", "unable"]
+            .iter()
+            .any(|w| lower.contains(w))
+        {
+            Severity::Error
+        } else if lower.contains("warning") {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warn",
+            Severity::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub at: DateTime<Local>,
+    pub severity: Severity,
+    pub text: String,
+    /// Errors stay pinned in the footer until the log is opened.
+    pub acknowledged: bool,
+}
+
+/// Ring buffer of every status message shown this session, newest last.
+#[derive(Debug, Clone, Default)]
+pub struct MessageLog {
+    entries: VecDeque<LogEntry>,
+}
+
+impl MessageLog {
+    pub fn push(&mut self, severity: Severity, text: impl Into<String>) {
+        if self.entries.len() == MESSAGE_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            at: Local::now(),
+            severity,
+            text: text.into(),
+            // Only errors need acknowledging.
+            acknowledged: severity != Severity::Error,
+        });
+    }
+
+    /// Entries newest first.
+    pub fn newest_first(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Most recent error not yet seen in the log view.
+    pub fn sticky_error(&self) -> Option<&LogEntry> {
+        self.newest_first().find(|e| !e.acknowledged)
+    }
+
+    /// Number of errors not yet seen in the log view.
+    pub fn unacknowledged(&self) -> usize {
+        self.entries.iter().filter(|e| !e.acknowledged).count()
+    }
+
+    pub fn acknowledge_all(&mut self) {
+        for entry in &mut self.entries {
+            entry.acknowledged = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_by_wording() {
+        assert_eq!(
+            Severity::classify("Sync failed for app: boom"),
+            Severity::Error
+        );
+        assert_eq!(
+            Severity::classify("Warning: mark_worktrees failed"),
+            Severity::Error
+        );
+        assert_eq!(
+            Severity::classify("Warning: nothing to push"),
+            Severity::Warning
+        );
+        assert_eq!(Severity::classify("Synced 3 tickets"), Severity::Info);
+    }
+
+    #[test]
+    fn push_drops_oldest_at_capacity() {
+        let mut log = MessageLog::default();
+        for i in 0..MESSAGE_LOG_CAPACITY + 5 {
+            log.push(Severity::Info, format!("msg {i}"));
+        }
+        assert_eq!(log.len(), MESSAGE_LOG_CAPACITY);
+        assert_eq!(log.newest_first().last().unwrap().text, "msg 5");
+        assert_eq!(
+            log.newest_first().next().unwrap().text,
+            format!("msg {}", MESSAGE_LOG_CAPACITY + 4)
+        );
+    }
+
+    #[test]
+    fn errors_stay_sticky_until_acknowledged() {
+        let mut log = MessageLog::default();
+        log.push(Severity::Error, "first");
+        log.push(Severity::Info, "later info");
+        log.push(Severity::Error, "second");
+        log.push(Severity::Warning, "later warning");
+
+        assert_eq!(log.unacknowledged(), 2);
+        assert_eq!(log.sticky_error().unwrap().text, "second");
+
+        log.acknowledge_all();
+        assert_eq!(log.unacknowledged(), 0);
+        assert!(log.sticky_error().is_none());
+        assert_eq!(log.len(), 4);
+    }
+}
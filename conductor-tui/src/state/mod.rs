@@ -1,16 +1,20 @@
 mod app_state;
 mod data_cache;
 mod enums;
+mod message_log;
 mod modal;
 mod secrets;
+mod sync_progress;
 mod tree;
 mod workflow_rows;
 
 pub use app_state::*;
 pub use data_cache::*;
 pub use enums::*;
+pub use message_log::*;
 pub use modal::*;
 pub use secrets::is_secret_env_key;
+pub use sync_progress::*;
 pub use tree::*;
 pub use workflow_rows::*;
 
@@ -1,13 +1,16 @@
 use std::fmt;
 
+use conductor_core::activity::{ActivityGroupBy, ActivityHeatmap};
+use conductor_core::agent::{CostGroupBy, CostReportRow};
 use conductor_core::github::DiscoveredRepo;
 use conductor_core::issue_source::IssueSource;
-use conductor_core::tickets::Ticket;
+use conductor_core::tickets::{GroomingProposal, RoadmapGroup, Ticket};
+use conductor_core::worktree::AbandonReason;
 use tui_textarea::TextArea;
 
 use super::{
-    BranchPickerItem, ConfirmAction, FormAction, FormField, InputAction, RuntimeSection,
-    TreePosition, WorkflowPickerItem, WorkflowPickerTarget,
+    BranchPickerItem, ConfirmAction, FormAction, FormField, InputAction, PendingAgentLaunch,
+    RuntimeSection, TreePosition, WorkflowPickerItem, WorkflowPickerTarget,
 };
 
 pub use crate::ui::graph::{GraphData, GraphNavState, GraphNodeType};
@@ -141,6 +144,22 @@ pub enum Modal {
         repo_path: String,
         worktree_path: Option<String>,
     },
+    /// Finished agent runs not yet reviewed. Items live in `DataCache::agent_inbox`
+    /// so the list stays current while the modal is open.
+    AgentInbox {
+        selected: usize,
+    },
+    /// Status message history, newest first. Entries live in
+    /// `AppState::message_log`; opening the modal acknowledges sticky errors.
+    MessageLog {
+        selected: usize,
+    },
+    /// The worktree has uncommitted changes: stash, commit as WIP, or proceed
+    /// before launching the agent.
+    DirtyWorktree {
+        files: Vec<String>,
+        launch: Box<PendingAgentLaunch>,
+    },
     /// Non-dismissable progress indicator shown while a background operation runs.
     Progress {
         message: String,
@@ -180,6 +199,54 @@ pub enum Modal {
         /// re-opened after an Esc cancel.
         original_name: String,
     },
+    /// Asks why unfinished work is being dropped before the delete is
+    /// confirmed by name. `selected` indexes [`AbandonReason::ALL`], with one
+    /// extra row at the end for "no reason".
+    AbandonReasonPicker {
+        repo_slug: String,
+        wt_slug: String,
+        /// Why the worktree looks unfinished; carried into the confirm step.
+        message: String,
+        selected: usize,
+    },
+    /// Tickets grouped by sprint/milestone with progress bars. `groups` is
+    /// snapshotted when the modal opens.
+    Roadmap {
+        title: String,
+        groups: Vec<RoadmapGroup>,
+        selected: usize,
+    },
+    /// Agent cost, turns and duration over the last `window_days`, loaded
+    /// when the modal opens and again whenever the grouping or window changes.
+    Reports {
+        group_by: CostGroupBy,
+        window_days: i64,
+        rows: Vec<CostReportRow>,
+        selected: usize,
+    },
+    /// Per-day commits, agent runs and cost of each repo or worktree over the
+    /// last 90 days, reached from the Reports modal.
+    ActivityHeatmap {
+        group_by: ActivityGroupBy,
+        heatmap: ActivityHeatmap,
+        selected: usize,
+    },
+    /// Pending grooming proposals, loaded when the modal opens. Accepting or
+    /// dismissing one removes it from the list.
+    Grooming {
+        title: String,
+        proposals: Vec<GroomingProposal>,
+        selected: usize,
+    },
+    /// Searchable picker for linking a ticket to a worktree. Matches among the
+    /// repo's cached tickets are recomputed as `query` changes; see
+    /// [`crate::state::AppState::ticket_link_matches`].
+    TicketLinkPicker {
+        worktree_id: String,
+        repo_id: String,
+        query: String,
+        selected: usize,
+    },
     /// Full-screen DAG graph view for ticket dependencies or workflow step graphs.
     GraphView {
         data: GraphData<GraphNodeType>,
@@ -188,6 +255,15 @@ pub enum Modal {
     },
 }
 
+/// Reason chosen at `selected` in [`Modal::AbandonReasonPicker`]; the row
+/// past the last reason means none.
+pub fn abandon_reason_at(selected: usize) -> Option<AbandonReason> {
+    AbandonReason::ALL.get(selected).copied()
+}
+
+/// Rows in [`Modal::AbandonReasonPicker`]: every reason plus "no reason".
+pub const ABANDON_REASON_ROWS: usize = AbandonReason::ALL.len() + 1;
+
 impl fmt::Debug for Modal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -232,6 +308,67 @@ impl fmt::Debug for Modal {
             Modal::TemplatePicker { selected, .. } => {
                 write!(f, "Modal::TemplatePicker(selected={selected})")
             }
+            Modal::AgentInbox { selected } => {
+                write!(f, "Modal::AgentInbox(selected={selected})")
+            }
+            Modal::MessageLog { selected } => {
+                write!(f, "Modal::MessageLog(selected={selected})")
+            }
+            Modal::AbandonReasonPicker {
+                wt_slug, selected, ..
+            } => {
+                write!(
+                    f,
+                    "Modal::AbandonReasonPicker(wt={wt_slug}, selected={selected})"
+                )
+            }
+            Modal::Roadmap {
+                groups, selected, ..
+            } => {
+                write!(
+                    f,
+                    "Modal::Roadmap(groups={}, selected={selected})",
+                    groups.len()
+                )
+            }
+            Modal::Reports {
+                group_by,
+                window_days,
+                rows,
+                selected,
+            } => {
+                write!(
+                    f,
+                    "Modal::Reports(group_by={group_by}, window={window_days}d, rows={}, selected={selected})",
+                    rows.len()
+                )
+            }
+            Modal::ActivityHeatmap {
+                group_by,
+                heatmap,
+                selected,
+            } => {
+                write!(
+                    f,
+                    "Modal::ActivityHeatmap(group_by={group_by}, days={}, rows={}, selected={selected})",
+                    heatmap.days,
+                    heatmap.rows.len()
+                )
+            }
+            Modal::Grooming {
+                proposals,
+                selected,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Modal::Grooming(proposals={}, selected={selected})",
+                    proposals.len()
+                )
+            }
+            Modal::DirtyWorktree { files, .. } => {
+                write!(f, "Modal::DirtyWorktree(files={})", files.len())
+            }
             Modal::Progress { message } => {
                 write!(f, "Modal::Progress({message:?})")
             }
@@ -245,6 +382,14 @@ impl fmt::Debug for Modal {
                     "Modal::ThemePicker(selected={selected}, original={original_name:?})"
                 )
             }
+            Modal::TicketLinkPicker {
+                query, selected, ..
+            } => {
+                write!(
+                    f,
+                    "Modal::TicketLinkPicker(query={query:?}, selected={selected})"
+                )
+            }
             Modal::GraphView { ref title, .. } => {
                 write!(f, "Modal::GraphView(title={title:?})")
             }
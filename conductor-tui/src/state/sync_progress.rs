@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+
+/// How long the sync panel stays up after the last repo finishes.
+pub const SYNC_PANEL_LINGER: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoSyncState {
+    Pending,
+    Running,
+    Done,
+    Error,
+}
+
+/// Sync progress for one repo, accumulated over all of its issue sources.
+#[derive(Debug, Clone)]
+pub struct RepoSyncProgress {
+    pub repo_slug: String,
+    pub state: RepoSyncState,
+    /// Tickets upserted across the repo's sources so far.
+    pub synced: usize,
+    /// One entry per source that failed.
+    pub errors: Vec<String>,
+}
+
+/// Per-repo state of a ticket sync, shown in the transient sync panel.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgress {
+    pub repos: Vec<RepoSyncProgress>,
+    /// When every repo finished; the panel is dropped [`SYNC_PANEL_LINGER`]
+    /// after this.
+    pub finished_at: Option<Instant>,
+}
+
+impl SyncProgress {
+    pub fn new(repo_slugs: Vec<String>) -> Self {
+        Self {
+            repos: repo_slugs
+                .into_iter()
+                .map(|repo_slug| RepoSyncProgress {
+                    repo_slug,
+                    state: RepoSyncState::Pending,
+                    synced: 0,
+                    errors: Vec::new(),
+                })
+                .collect(),
+            finished_at: None,
+        }
+    }
+
+    /// Entry for `repo_slug`, added as pending if the sync did not announce it.
+    fn entry(&mut self, repo_slug: &str) -> &mut RepoSyncProgress {
+        let idx = match self.repos.iter().position(|r| r.repo_slug == repo_slug) {
+            Some(idx) => idx,
+            None => {
+                self.repos.push(RepoSyncProgress {
+                    repo_slug: repo_slug.to_string(),
+                    state: RepoSyncState::Pending,
+                    synced: 0,
+                    errors: Vec::new(),
+                });
+                self.repos.len() - 1
+            }
+        };
+        &mut self.repos[idx]
+    }
+
+    pub fn repo_started(&mut self, repo_slug: &str) {
+        self.finished_at = None;
+        self.entry(repo_slug).state = RepoSyncState::Running;
+    }
+
+    pub fn record_synced(&mut self, repo_slug: &str, count: usize) {
+        self.entry(repo_slug).synced += count;
+    }
+
+    pub fn record_failed(&mut self, repo_slug: &str, error: String) {
+        self.entry(repo_slug).errors.push(error);
+    }
+
+    pub fn repo_finished(&mut self, repo_slug: &str) {
+        let entry = self.entry(repo_slug);
+        entry.state = if entry.errors.is_empty() {
+            RepoSyncState::Done
+        } else {
+            RepoSyncState::Error
+        };
+        if self.finished() == self.repos.len() {
+            self.finished_at = Some(Instant::now());
+        }
+    }
+
+    /// Mark the sync over. Repos that never started stay pending.
+    pub fn finish(&mut self) {
+        self.finished_at.get_or_insert_with(Instant::now);
+    }
+
+    /// Number of repos whose sync has completed, successfully or not.
+    pub fn finished(&self) -> usize {
+        self.repos
+            .iter()
+            .filter(|r| matches!(r.state, RepoSyncState::Done | RepoSyncState::Error))
+            .count()
+    }
+
+    /// Whether the panel has lingered long enough to be dismissed.
+    pub fn is_expired(&self, linger: Duration) -> bool {
+        self.finished_at.is_some_and(|at| at.elapsed() >= linger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_each_repo_through_its_sources() {
+        let mut p = SyncProgress::new(vec!["api".into(), "web".into()]);
+        assert!(p.repos.iter().all(|r| r.state == RepoSyncState::Pending));
+
+        p.repo_started("api");
+        p.record_synced("api", 3);
+        p.record_synced("api", 2);
+        p.repo_finished("api");
+        assert_eq!(p.repos[0].state, RepoSyncState::Done);
+        assert_eq!(p.repos[0].synced, 5);
+        assert!(p.finished_at.is_none(), "web is still pending");
+
+        p.repo_started("web");
+        assert_eq!(p.repos[1].state, RepoSyncState::Running);
+        p.record_failed("web", "rate limited".into());
+        p.repo_finished("web");
+        assert_eq!(p.repos[1].state, RepoSyncState::Error);
+        assert_eq!(p.finished(), 2);
+        assert!(p.finished_at.is_some());
+        assert!(!p.is_expired(SYNC_PANEL_LINGER));
+        assert!(p.is_expired(Duration::ZERO));
+    }
+
+    #[test]
+    fn unannounced_repo_is_added() {
+        let mut p = SyncProgress::default();
+        p.repo_started("solo");
+        p.record_synced("solo", 1);
+        p.repo_finished("solo");
+        assert_eq!(p.repos.len(), 1);
+        assert_eq!(p.repos[0].state, RepoSyncState::Done);
+        assert!(p.finished_at.is_some());
+    }
+}
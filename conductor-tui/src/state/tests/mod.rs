@@ -39,6 +39,8 @@ pub(crate) fn make_ticket(id: &str, state: &str) -> conductor_core::tickets::Tic
         raw_json: String::new(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     }
 }
 
@@ -54,6 +56,7 @@ pub(crate) fn make_repo(id: &str, slug: &str) -> conductor_core::repo::Repo {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }
 }
 
@@ -75,6 +78,9 @@ pub(crate) fn make_worktree(
         completed_at: None,
         model: None,
         base_branch: base_branch.map(|s| s.to_string()),
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }
 }
 
@@ -158,6 +164,7 @@ pub(crate) fn make_picker_item(
         base_branch: base_branch.map(|s| s.to_string()),
         stale_days: None,
         inferred_from: None,
+        resolved_base: None,
     }
 }
 
@@ -177,6 +184,9 @@ pub(crate) fn make_wt(
         completed_at: None,
         model: None,
         base_branch: base_branch.map(|s| s.to_string()),
+        abandon_reason: None,
+        missing_since: None,
+        ticket_change: None,
     }
 }
 
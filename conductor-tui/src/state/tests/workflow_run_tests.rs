@@ -192,6 +192,7 @@ fn global_mode_pr_run_uses_repo_id_fallback() {
         model: None,
         allow_agent_issue_creation: false,
         runtime_overrides: None,
+        toolchain: Default::default(),
     }];
     state.data.workflow_runs = vec![make_wf_run_with_label(
         "pr1",
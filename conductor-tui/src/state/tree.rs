@@ -315,6 +315,8 @@ mod tests {
             raw_json: String::new(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         }
     }
 
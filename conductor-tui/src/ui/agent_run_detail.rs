@@ -0,0 +1,260 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use conductor_core::agent::AgentRun;
+
+use super::common::fmt_tokens_k;
+use super::helpers::shorten_paths;
+use super::worktree_detail::{event_style, run_status_label};
+use crate::state::{AgentRunDetailFocus, AppState};
+use crate::theme::Theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let [runs_area, detail_area] =
+        Layout::horizontal([Constraint::Length(44), Constraint::Min(0)]).areas(area);
+    render_runs(frame, runs_area, state);
+
+    let Some(run) = state
+        .agent_run_detail_runs
+        .get(state.agent_run_detail_index)
+    else {
+        return;
+    };
+    let metadata = metadata_lines(run, state);
+    let [meta_area, events_area] = Layout::vertical([
+        Constraint::Length(metadata.len() as u16 + 2),
+        Constraint::Min(0),
+    ])
+    .areas(detail_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.border_inactive))
+        .title(" Run ");
+    frame.render_widget(
+        Paragraph::new(metadata)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        meta_area,
+    );
+    render_events(frame, events_area, state);
+}
+
+fn pane_block(title: String, focused: bool, theme: &Theme) -> Block<'static> {
+    let color = if focused {
+        theme.border_focused
+    } else {
+        theme.border_inactive
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(title)
+}
+
+fn render_runs(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let runs = &state.agent_run_detail_runs;
+    let block = pane_block(
+        format!(" Runs ({}) ", runs.len()),
+        state.agent_run_detail_focus == AgentRunDetailFocus::Runs,
+        theme,
+    );
+    if runs.is_empty() {
+        let p = Paragraph::new(Span::styled(
+            "No agent runs",
+            Style::default().fg(theme.label_secondary),
+        ))
+        .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    // Runs are newest first; number them in start order.
+    let items: Vec<ListItem> = runs
+        .iter()
+        .enumerate()
+        .map(|(i, run)| {
+            let (status, color) = run_status_label(&run.status, theme);
+            let cost = run.cost_usd.map(|c| format!("${c:.2}")).unwrap_or_default();
+            let turns = run.num_turns.map(|t| format!("{t}t")).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("#{:<3}", runs.len() - i),
+                    Style::default().fg(theme.label_secondary),
+                ),
+                Span::styled(format!("{status:<10}"), Style::default().fg(color)),
+                Span::styled(
+                    format!("{:>7} {:>5} {:>7}", cost, turns, run_duration(run)),
+                    Style::default().fg(theme.label_primary),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+    let mut list_state = ListState::default().with_selected(Some(state.agent_run_detail_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// `1m 05s`, from `duration_ms` once the run has finished.
+fn run_duration(run: &AgentRun) -> String {
+    let Some(ms) = run.duration_ms else {
+        return String::new();
+    };
+    let secs = ms / 1000;
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn metadata_lines(run: &AgentRun, state: &AppState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{label:<10}"),
+                Style::default().fg(theme.label_secondary),
+            ),
+            Span::styled(value, Style::default().fg(theme.label_primary)),
+        ])
+    };
+    let (status, color) = run_status_label(&run.status, theme);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{:<10}", "Status"),
+            Style::default().fg(theme.label_secondary),
+        ),
+        Span::styled(status, Style::default().fg(color)),
+        Span::styled(
+            format!(
+                "  {} · {}",
+                run.runtime,
+                run.model.as_deref().unwrap_or("default model")
+            ),
+            Style::default().fg(theme.label_secondary),
+        ),
+    ])];
+    let ended = run
+        .ended_at
+        .as_deref()
+        .map(|e| format!(" → {}", timestamp(e)))
+        .unwrap_or_default();
+    lines.push(field(
+        "Time",
+        format!("{}{ended}", timestamp(&run.started_at)),
+    ));
+
+    let mut usage = Vec::new();
+    if let Some(cost) = run.cost_usd {
+        usage.push(format!("${cost:.2}"));
+    }
+    if let Some(turns) = run.num_turns {
+        usage.push(format!("{turns} turns"));
+    }
+    if run.duration_ms.is_some() {
+        usage.push(run_duration(run));
+    }
+    if !usage.is_empty() {
+        lines.push(field("Usage", usage.join(" · ")));
+    }
+    if run.input_tokens.is_some() || run.output_tokens.is_some() {
+        let mut tokens = format!(
+            "{} in · {} out",
+            fmt_tokens_k(run.input_tokens.unwrap_or(0)),
+            fmt_tokens_k(run.output_tokens.unwrap_or(0))
+        );
+        if let Some(read) = run.cache_read_input_tokens.filter(|n| *n > 0) {
+            tokens.push_str(&format!(" · {} cache read", fmt_tokens_k(read)));
+        }
+        if let Some(created) = run.cache_creation_input_tokens.filter(|n| *n > 0) {
+            tokens.push_str(&format!(" · {} cache write", fmt_tokens_k(created)));
+        }
+        lines.push(field("Tokens", tokens));
+    }
+    if let Some(ref session) = run.session_id {
+        lines.push(field("Session", session.clone()));
+    }
+    if let Some(ref previous) = run.rotated_from_session_id {
+        lines.push(field("Rotated", format!("from {previous}")));
+    }
+    if let Some(ref parent) = run.parent_run_id {
+        lines.push(field("Parent", parent.clone()));
+    }
+    if let Some(env) = run.environment.as_ref() {
+        lines.push(field("Env", env.summary()));
+    }
+    let prompt = run.prompt.lines().next().unwrap_or_default().to_string();
+    lines.push(field("Prompt", prompt));
+    if let Some(result) = run
+        .result_text
+        .as_deref()
+        .and_then(|r| r.lines().find(|l| !l.trim().is_empty()))
+    {
+        lines.push(field("Result", result.to_string()));
+    }
+    lines
+}
+
+/// `2024-01-10 00:05` from an RFC 3339 timestamp.
+fn timestamp(ts: &str) -> String {
+    ts.get(..16).unwrap_or(ts).replacen('T', " ", 1)
+}
+
+fn render_events(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let events = &state.agent_run_detail_events;
+    let focused = state.agent_run_detail_focus == AgentRunDetailFocus::Events;
+    let block = pane_block(format!(" Events ({}) ", events.len()), focused, theme);
+    if events.is_empty() {
+        let p = Paragraph::new(Span::styled(
+            "No events recorded",
+            Style::default().fg(theme.label_secondary),
+        ))
+        .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let worktree_path = state
+        .selected_worktree()
+        .map(|wt| wt.path.as_str())
+        .unwrap_or("");
+    let items: Vec<ListItem> = events
+        .iter()
+        .map(|ev| {
+            let mut spans = vec![Span::styled(
+                shorten_paths(&ev.summary, worktree_path, state.home_dir.as_deref()),
+                event_style(&ev.kind, theme),
+            )];
+            if let Some(dur) = ev.duration_ms().filter(|d| *d >= 100) {
+                spans.push(Span::styled(
+                    format!("  ({:.1}s)", dur as f64 / 1000.0),
+                    Style::default().fg(theme.label_secondary),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    // The selection keeps its scroll position while the run list has focus,
+    // but is only highlighted when this pane does.
+    let mut list = List::new(items).block(block);
+    if focused {
+        list = list.highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    }
+    let mut list_state =
+        ListState::default().with_selected(Some(state.agent_run_detail_event_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
@@ -1,4 +1,4 @@
-use conductor_core::tickets::TicketLabel;
+use conductor_core::tickets::{EpicRollup, TicketLabel};
 use conductor_core::workflow::GateType;
 use conductor_core::worktree::{Worktree, WorktreeStatus};
 use ratatui::layout::Rect;
@@ -95,23 +95,80 @@ pub fn render_footer(frame: &mut Frame, area: Rect, state: &AppState) {
             View::WorkflowRunDetail => "Workflow Run",
             View::WorkflowDefDetail => "Workflow Definition",
             View::Settings => "Settings",
+            View::Today => "Today",
+            View::MyQueue => "My Queue",
+            View::AgentRunDetail => "Agent Runs",
+            View::WorktreeDiff => "Diff",
         };
         if state.view == View::Settings {
             format!("[{view_name}]  Tab:pane  j/k:nav  Enter:edit  c:cycle  Esc:back")
+        } else if state.view == View::Today || state.view == View::MyQueue {
+            format!("[{view_name}]  j/k:nav  Enter:jump  o:open url  r:refresh  Esc:back")
+        } else if state.view == View::AgentRunDetail {
+            format!("[{view_name}]  Tab:pane  j/k:nav  Enter:events  y:copy session  Esc:back")
+        } else if state.view == View::WorktreeDiff {
+            format!("[{view_name}]  Tab:pane  j/k:file/scroll  Ctrl+d/u:page  r:refresh  Esc:back")
         } else {
             format!("[{view_name}]  Tab:panel  [/]:column  \\:workflows  [S]:settings  q:quit")
         }
     };
 
-    let spans: Vec<Span<'static>> = vec![Span::styled(
-        msg,
-        Style::default().fg(state.theme.label_secondary),
-    )];
+    // Errors stay pinned once their status message has cleared, until the
+    // message log is opened.
+    let sticky = state
+        .message_log
+        .sticky_error()
+        .filter(|_| state.active_filter().is_none() && state.status_message.is_none());
+    let mut spans: Vec<Span<'static>> = match sticky {
+        Some(err) => {
+            let more = state.message_log.unacknowledged().saturating_sub(1);
+            let more = if more > 0 {
+                format!(" (+{more} more)")
+            } else {
+                String::new()
+            };
+            vec![Span::styled(
+                format!("✗ {}{more}  [m]:messages", err.text),
+                Style::default().fg(state.theme.status_failed),
+            )]
+        }
+        None => vec![Span::styled(
+            msg,
+            Style::default().fg(state.theme.label_secondary),
+        )],
+    };
+    if let Some(ref since) = state.db_snapshot_since {
+        spans.insert(
+            0,
+            Span::styled(
+                format!("⚠ read-only: database busy since {since}, data may be stale  "),
+                Style::default().fg(state.theme.status_waiting),
+            ),
+        );
+    }
+    let inbox = state.data.agent_inbox.len();
+    if inbox > 0 {
+        spans.push(Span::styled(
+            format!("  ✉ {inbox} unreviewed [U]"),
+            Style::default().fg(state.theme.label_accent),
+        ));
+    }
 
     let bar = Paragraph::new(Line::from(spans));
     frame.render_widget(bar, area);
 }
 
+/// Theme colour for a worktree status label.
+pub fn worktree_status_color(status: WorktreeStatus, theme: &Theme) -> Color {
+    match status {
+        WorktreeStatus::Active => theme.status_completed,
+        WorktreeStatus::NeedsReview => theme.status_waiting,
+        WorktreeStatus::Parked => theme.status_cancelled,
+        WorktreeStatus::Merged | WorktreeStatus::Promoted => theme.label_info,
+        WorktreeStatus::Abandoned => theme.status_failed,
+    }
+}
+
 /// Build a `ListItem` for a worktree row.
 ///
 /// Both the dashboard and repo-detail worktree panes use this so the
@@ -127,11 +184,7 @@ pub fn worktree_list_item_with_prefix(
     list_prefix: &str,
 ) -> ListItem<'static> {
     let is_active = wt.is_active();
-    let status_color = match wt.status {
-        WorktreeStatus::Active => state.theme.status_completed,
-        WorktreeStatus::Merged => state.theme.label_info,
-        WorktreeStatus::Abandoned => state.theme.status_failed,
-    };
+    let status_color = worktree_status_color(wt.status, &state.theme);
     let text_style = if is_active {
         Style::default()
     } else {
@@ -178,6 +231,22 @@ pub fn worktree_list_item_with_prefix(
         ));
     }
 
+    // The directory was deleted behind conductor's back.
+    if wt.missing_since.is_some() {
+        spans.push(Span::styled(
+            "[missing]  ",
+            Style::default().fg(state.theme.label_error),
+        ));
+    }
+
+    // The linked ticket changed upstream since it was last acknowledged.
+    if wt.ticket_change.is_some() {
+        spans.push(Span::styled(
+            "[ticket changed]  ",
+            Style::default().fg(state.theme.label_warning),
+        ));
+    }
+
     // Combined status symbol + workflow name/step — surfaced before the slug.
     // Agent takes symbol precedence over workflow; workflow name provides the label text.
     use conductor_core::agent::AgentRunStatus;
@@ -270,6 +339,48 @@ pub fn worktree_list_item_with_prefix(
         ));
     }
 
+    // ◎ = watched, directly or through the linked ticket
+    let watched = state.data.watched_worktrees.contains(&wt.id)
+        || wt
+            .ticket_id
+            .as_ref()
+            .is_some_and(|t| state.data.watched_tickets.contains(t));
+    if watched {
+        spans.push(Span::styled(
+            " ◎",
+            Style::default().fg(state.theme.label_accent),
+        ));
+    }
+
+    // ⇡ = stacked on a parent that moved or merged
+    if let Some(info) = state
+        .data
+        .stack_infos
+        .get(&wt.id)
+        .filter(|i| i.state.needs_restack())
+    {
+        spans.push(Span::styled(
+            format!(" ⇡ {}", info.state.label()),
+            Style::default().fg(state.theme.label_warning),
+        ));
+    }
+
+    // Uncommitted changes and commits not yet pushed (↑) or pulled (↓)
+    if let Some(git) = state.data.git_statuses.get(&wt.id) {
+        let summary = git.summary();
+        if summary != "clean" {
+            let color = if git.has_unpushed_work() {
+                state.theme.label_warning
+            } else {
+                state.theme.label_secondary
+            };
+            spans.push(Span::styled(
+                format!("  {summary}"),
+                Style::default().fg(color),
+            ));
+        }
+    }
+
     // Show cumulative token totals: completed runs + active run overlay.
     let (mut total_in, mut total_out) = state
         .data
@@ -318,6 +429,44 @@ pub fn ticket_worktree_dot_span(state: &AppState, ticket_id: &str) -> Span<'stat
     }
 }
 
+/// Build the progress columns for a ticket row: worktree dot, latest agent
+/// status and PR state.
+///
+/// Every column is always emitted (blank when there is nothing to show) so
+/// ticket rows stay aligned.
+pub fn ticket_progress_spans(state: &AppState, ticket_id: &str) -> Vec<Span<'static>> {
+    use conductor_core::agent::AgentRunStatus;
+    use conductor_core::tickets::TicketPrState;
+
+    let theme = &state.theme;
+    let progress = state.data.ticket_progress.get(ticket_id);
+    let agent = progress
+        .and_then(|p| p.agent_status)
+        .map(|status| match status {
+            AgentRunStatus::Running => ("⚙ ", theme.status_running),
+            AgentRunStatus::WaitingForFeedback => ("⏸ ", theme.status_waiting),
+            AgentRunStatus::Completed => ("✓ ", theme.status_completed),
+            AgentRunStatus::Failed => ("✗ ", theme.status_failed),
+            AgentRunStatus::Cancelled => ("⊘ ", theme.status_cancelled),
+        })
+        .unwrap_or(("  ", theme.label_secondary));
+    // ◇ = draft PR, ◆ = open / merged / closed (by color)
+    let pr = progress
+        .and_then(|p| p.pr_state)
+        .map(|pr| match pr {
+            TicketPrState::Draft => ("◇ ", theme.label_secondary),
+            TicketPrState::Open => ("◆ ", theme.status_completed),
+            TicketPrState::Merged => ("◆ ", theme.label_keyword),
+            TicketPrState::Closed => ("◆ ", theme.status_failed),
+        })
+        .unwrap_or(("  ", theme.label_secondary));
+    vec![
+        ticket_worktree_dot_span(state, ticket_id),
+        Span::styled(agent.0, Style::default().fg(agent.1)),
+        Span::styled(pr.0, Style::default().fg(pr.1)),
+    ]
+}
+
 /// Format a token count as `X.Xk` for values ≥ 1000, or plain integer otherwise.
 pub(super) fn fmt_tokens_k(n: i64) -> String {
     if n >= 1000 {
@@ -327,6 +476,19 @@ pub(super) fn fmt_tokens_k(n: i64) -> String {
     }
 }
 
+/// Dim `Env:` line summarising where an agent run started (CLI version, model,
+/// commit, platform). `None` for runs recorded before environments were captured.
+pub(super) fn run_environment_line(
+    run: &conductor_core::agent::AgentRun,
+    theme: &Theme,
+) -> Option<Line<'static>> {
+    let env = run.environment.as_ref()?;
+    Some(Line::from(vec![
+        Span::styled("Env: ", Style::default().fg(theme.label_secondary)),
+        Span::styled(env.summary(), Style::default().fg(theme.label_secondary)),
+    ]))
+}
+
 /// Build a 3-span flow-format token display: `→ {in_k} ⊙ {out_k} →`
 ///
 /// The `⊙` separator is styled dim (label_secondary) to visually distinguish
@@ -382,6 +544,36 @@ pub fn ticket_agent_total_spans(
     ]
 }
 
+/// Progress and agent spend across a parent ticket's descendants, e.g.
+/// `  [3/7 done · $4.20]`. Empty for tickets without children.
+pub fn ticket_rollup_spans(state: &AppState, ticket_id: &str) -> Vec<Span<'static>> {
+    let has_children = state
+        .data
+        .ticket_dependencies
+        .get(ticket_id)
+        .is_some_and(|d| !d.children.is_empty());
+    if !has_children {
+        return Vec::new();
+    }
+    let rollup = EpicRollup::compute(
+        ticket_id,
+        &state.data.ticket_dependencies,
+        &state.data.ticket_progress,
+        &state.data.ticket_agent_totals,
+    );
+    let mut text = format!("  [{}/{} done", rollup.done, rollup.total);
+    if rollup.agent_runs > 0 {
+        text.push_str(&format!(" · ${:.2}", rollup.agent_cost));
+    }
+    text.push(']');
+    let color = if rollup.done == rollup.total {
+        state.theme.status_completed
+    } else {
+        state.theme.label_secondary
+    };
+    vec![Span::styled(text, Style::default().fg(color))]
+}
+
 /// Return the canonical (icon, color) pair for a gate type.
 ///
 /// Used by both the pending-gates panel and the workflow run list to ensure
@@ -607,4 +799,31 @@ mod tests {
         let d = chrono::Duration::days(2);
         assert_eq!(format_duration_compact(d), "2d");
     }
+
+    // ── ticket_progress_spans ───────────────────────────────────────────
+
+    #[test]
+    fn ticket_progress_spans_keep_columns_aligned() {
+        use conductor_core::agent::AgentRunStatus;
+        use conductor_core::tickets::{TicketPrState, TicketProgress};
+
+        let mut state = AppState::new();
+        let text = |spans: Vec<Span<'static>>| {
+            spans
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect::<String>()
+        };
+        assert_eq!(text(ticket_progress_spans(&state, "t1")), "○     ");
+
+        state.data.ticket_progress.insert(
+            "t1".into(),
+            TicketProgress {
+                worktree_status: None,
+                agent_status: Some(AgentRunStatus::Failed),
+                pr_state: Some(TicketPrState::Draft),
+            },
+        );
+        assert_eq!(text(ticket_progress_spans(&state, "t1")), "○ ✗ ◇ ");
+    }
 }
@@ -4,6 +4,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 use ratatui::Frame;
 
+use conductor_core::worktree::QuotaLevel;
 use tracing::warn;
 
 use crate::state::{AppState, ColumnFocus, DashboardRow};
@@ -63,6 +64,22 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                         Style::default().fg(state.theme.label_warning),
                     ));
                 }
+                if let Some(usage) = state
+                    .data
+                    .workspace_usage
+                    .get(&repo.id)
+                    .filter(|u| u.limit_bytes.is_some())
+                {
+                    let color = match usage.level {
+                        QuotaLevel::Ok => state.theme.label_secondary,
+                        QuotaLevel::Warning => state.theme.label_warning,
+                        QuotaLevel::Exceeded => state.theme.status_failed,
+                    };
+                    spans.push(Span::styled(
+                        format!("  ⛁ {}", usage.meter_label()),
+                        Style::default().fg(color),
+                    ));
+                }
                 ListItem::new(Line::from(spans))
             }
             DashboardRow::Worktree { idx, prefix } => {
@@ -0,0 +1,131 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::state::{AppState, WorktreeDiffFocus};
+use crate::theme::Theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let [files_area, diff_area] =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(area);
+
+    let base = state
+        .selected_worktree()
+        .and_then(|wt| {
+            let repo = state.data.repos.iter().find(|r| r.id == wt.repo_id)?;
+            Some(wt.effective_base(&repo.default_branch).to_string())
+        })
+        .unwrap_or_default();
+    let files = &state.worktree_diff_files;
+    let files_block = pane_block(
+        format!(" Files vs {base} ({}) ", files.len()),
+        state.worktree_diff_focus == WorktreeDiffFocus::Files,
+        theme,
+    );
+
+    if files.is_empty() {
+        let message = if state.worktree_diff_loading {
+            "Loading diff…".to_string()
+        } else {
+            format!("No changes against {base}")
+        };
+        let p = Paragraph::new(Span::styled(
+            message,
+            Style::default().fg(theme.label_secondary),
+        ))
+        .block(files_block);
+        frame.render_widget(p, files_area);
+        frame.render_widget(pane_block(" Diff ".to_string(), false, theme), diff_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = files
+        .iter()
+        .map(|f| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("+{:<4}", f.added),
+                    Style::default().fg(theme.status_completed),
+                ),
+                Span::styled(
+                    format!("-{:<4}", f.removed),
+                    Style::default().fg(theme.status_failed),
+                ),
+                Span::styled(f.path.clone(), Style::default().fg(theme.label_primary)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(files_block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+    let mut list_state = ListState::default().with_selected(Some(state.worktree_diff_index));
+    frame.render_stateful_widget(list, files_area, &mut list_state);
+
+    let Some(file) = files.get(state.worktree_diff_index) else {
+        return;
+    };
+    let lines: Vec<Line> = file
+        .text
+        .lines()
+        .map(|line| Line::from(Span::styled(line, diff_line_style(line, theme))))
+        .collect();
+    let scroll = u16::try_from(state.worktree_diff_scroll).unwrap_or(u16::MAX);
+    let diff = Paragraph::new(lines)
+        .block(pane_block(
+            format!(" {} ", file.path),
+            state.worktree_diff_focus == WorktreeDiffFocus::Diff,
+            theme,
+        ))
+        .scroll((scroll, 0));
+    frame.render_widget(diff, diff_area);
+}
+
+fn pane_block(title: String, focused: bool, theme: &Theme) -> Block<'static> {
+    let color = if focused {
+        theme.border_focused
+    } else {
+        theme.border_inactive
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(title)
+}
+
+/// Color a unified diff line by what it is: file header, hunk header,
+/// addition, removal or context.
+fn diff_line_style(line: &str, theme: &Theme) -> Style {
+    const HEADERS: &[&str] = &[
+        "diff --git ",
+        "index ",
+        "--- ",
+        "+++ ",
+        "new file mode",
+        "deleted file mode",
+        "similarity index",
+        "rename from",
+        "rename to",
+        "Binary files",
+    ];
+    if HEADERS.iter().any(|h| line.starts_with(h)) {
+        Style::default()
+            .fg(theme.label_secondary)
+            .add_modifier(Modifier::BOLD)
+    } else if line.starts_with("@@") {
+        Style::default().fg(theme.label_info)
+    } else if line.starts_with('+') {
+        Style::default().fg(theme.status_completed)
+    } else if line.starts_with('-') {
+        Style::default().fg(theme.status_failed)
+    } else {
+        Style::default().fg(theme.label_primary)
+    }
+}
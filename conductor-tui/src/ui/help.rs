@@ -45,13 +45,29 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &crate::theme::Theme) {
         help_line("a", "Register repository", theme),
         help_line("c", "Create worktree", theme),
         help_line("i", "Adopt existing worktree", theme),
+        help_line("u", "Worktree from issue/PR URL (or paste the URL)", theme),
         help_line("d", "Delete (worktree/repo)", theme),
         help_line("s", "Sync tickets", theme),
         help_line("S", "Open settings", theme),
         help_line("A", "Toggle closed tickets", theme),
+        help_line("C", "Toggle current-sprint tickets", theme),
         help_line("w", "Open workflow picker", theme),
         help_line("/", "Filter/search", theme),
         help_line("T", "Open theme picker", theme),
+        help_line("U", "Agent inbox: finished runs not yet reviewed", theme),
+        help_line("!", "Today: everything needing attention", theme),
+        help_line(
+            "@",
+            "My Queue: tickets assigned to or mentioning you",
+            theme,
+        ),
+        help_line("K", "Stop all running agents (asks first)", theme),
+        help_line("m", "Message log: past status messages and errors", theme),
+        help_line(
+            "$",
+            "Reports: agent cost, turns and time (Tab group, w window, h heat map)",
+            theme,
+        ),
         help_line("L", "Filter tickets by label (repo detail)", theme),
         Line::from(""),
         Line::from(Span::styled(
@@ -62,6 +78,8 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &crate::theme::Theme) {
         )),
         Line::from(""),
         help_line("M", "Manage issue sources", theme),
+        help_line("W", "Watch/unwatch selected ticket or worktree", theme),
+        help_line("B", "Rebase stacked worktree onto its parent", theme),
         Line::from(""),
         Line::from(Span::styled(
             "Repo Detail — Tickets",
@@ -73,6 +91,15 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &crate::theme::Theme) {
         help_line("Space", "Collapse/expand parent ticket (tree view)", theme),
         help_line("I", "Toggle agent-issues filter", theme),
         help_line("#", "Toggle ticket # sort (#\u{2191} / #\u{2193})", theme),
+        help_line("R", "Roadmap: progress by sprint/milestone", theme),
+        help_line("P", "Grooming proposals: accept or dismiss", theme),
+        help_line("n", "Comment on the ticket in its tracker", theme),
+        help_line(
+            "N",
+            "Create worktree and start an agent on the ticket",
+            theme,
+        ),
+        help_line("X", "Close the ticket in its tracker", theme),
         Line::from(""),
         Line::from(Span::styled(
             "Repo Detail — Repo Agent",
@@ -135,6 +162,49 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &crate::theme::Theme) {
         help_line("x", "Stop running agent", theme),
         help_line("f", "Submit feedback to agent", theme),
         help_line("F", "Dismiss feedback request", theme),
+        help_line("v", "Ask an agent to review the diff vs base", theme),
+        help_line("V", "View the latest diff review", theme),
+        help_line("s", "Fetch and rebase onto the latest base branch", theme),
+        help_line("u", "Rescan the branch for TODO/FIXME comments", theme),
+        help_line(
+            "a",
+            "Acknowledge upstream changes to the linked ticket",
+            theme,
+        ),
+        help_line("h", "Browse all agent runs on this worktree", theme),
+        help_line("D", "View the diff against the base branch", theme),
+        help_line(
+            "c",
+            "Commit all changes (blank message: from the agent)",
+            theme,
+        ),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Agent Run Detail",
+            Style::default()
+                .fg(theme.label_accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        help_line("Tab", "Switch between runs and events", theme),
+        help_line("j / k", "Select run / scroll events", theme),
+        help_line("Enter", "Focus the selected run's events", theme),
+        help_line("y", "Copy the run's session ID", theme),
+        help_line("Esc", "Back to worktree", theme),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Worktree Diff",
+            Style::default()
+                .fg(theme.label_accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        help_line("Tab", "Switch between files and diff", theme),
+        help_line("j / k", "Select file / scroll diff", theme),
+        help_line("Ctrl+d / Ctrl+u", "Scroll half a page", theme),
+        help_line("Enter", "Focus the selected file's diff", theme),
+        help_line("r", "Reload the diff", theme),
+        help_line("Esc", "Back to worktree", theme),
         Line::from(""),
         Line::from(Span::styled(
             "Workflow Run Detail",
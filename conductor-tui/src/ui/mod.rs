@@ -1,12 +1,17 @@
+mod agent_run_detail;
 mod common;
 mod dashboard;
+mod diff;
 pub mod graph;
 mod help;
 pub(crate) mod helpers;
 mod modal;
+mod my_queue;
 mod pending_gates;
 mod repo_detail;
 pub(crate) mod settings;
+mod sync_panel;
+mod today;
 mod workflow_column;
 mod workflow_def_detail;
 pub(crate) mod workflows;
@@ -39,10 +44,18 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         View::WorkflowRunDetail => workflows::render_run_detail(frame, body_area, state),
         View::WorkflowDefDetail => workflow_def_detail::render(frame, body_area, state),
         View::Settings => settings::render(frame, body_area, state),
+        View::Today => today::render(frame, body_area, state),
+        View::MyQueue => my_queue::render(frame, body_area, state),
+        View::AgentRunDetail => agent_run_detail::render(frame, body_area, state),
+        View::WorktreeDiff => diff::render(frame, body_area, state),
     }
 
     common::render_footer(frame, footer_area, state);
 
+    if let Some(ref progress) = state.sync_progress {
+        sync_panel::render(frame, body_area, progress, &state.theme);
+    }
+
     // Modal overlay on top
     match &state.modal {
         Modal::None => {}
@@ -50,6 +63,9 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         Modal::Confirm { title, message, .. } => {
             modal::render_confirm(frame, area, title, message, &state.theme)
         }
+        Modal::DirtyWorktree { files, launch } => {
+            modal::render_dirty_worktree(frame, area, &launch.worktree_slug, files, &state.theme)
+        }
         Modal::ConfirmByName {
             title,
             message,
@@ -94,6 +110,11 @@ pub fn render(frame: &mut Frame, state: &AppState) {
                     .ticket_labels
                     .get(&ticket.id)
                     .map(|v| v.as_slice()),
+                attachments: state
+                    .data
+                    .ticket_attachments
+                    .get(&ticket.id)
+                    .map(|v| v.as_slice()),
                 dependencies: state.data.ticket_dependencies.get(&ticket.id),
             };
             modal::render_ticket_info(frame, area, &data, &state.theme);
@@ -242,12 +263,76 @@ pub fn render(frame: &mut Frame, state: &AppState) {
                 &state.theme,
             )
         }
+        Modal::AbandonReasonPicker {
+            wt_slug,
+            message,
+            selected,
+            ..
+        } => modal::render_abandon_reason_picker(
+            frame,
+            area,
+            wt_slug,
+            message,
+            *selected,
+            &state.theme,
+        ),
         Modal::TemplatePicker {
             items,
             selected,
             repo_slug,
             ..
         } => modal::render_template_picker(frame, area, items, *selected, repo_slug, &state.theme),
+        Modal::AgentInbox { selected } => modal::render_agent_inbox(
+            frame,
+            area,
+            &state.data.agent_inbox,
+            *selected,
+            &state.theme,
+        ),
+        Modal::MessageLog { selected } => {
+            modal::render_message_log(frame, area, &state.message_log, *selected, &state.theme)
+        }
+        Modal::Roadmap {
+            title,
+            groups,
+            selected,
+        } => modal::render_roadmap(frame, area, title, groups, *selected, &state.theme),
+        Modal::Reports {
+            group_by,
+            window_days,
+            rows,
+            selected,
+        } => modal::render_reports(
+            frame,
+            area,
+            *group_by,
+            *window_days,
+            rows,
+            *selected,
+            &state.theme,
+        ),
+        Modal::ActivityHeatmap {
+            group_by,
+            heatmap,
+            selected,
+        } => {
+            modal::render_activity_heatmap(frame, area, *group_by, heatmap, *selected, &state.theme)
+        }
+        Modal::Grooming {
+            title,
+            proposals,
+            selected,
+        } => modal::render_grooming(frame, area, title, proposals, *selected, &state.theme),
+        Modal::TicketLinkPicker {
+            query, selected, ..
+        } => modal::render_ticket_link_picker(
+            frame,
+            area,
+            query,
+            &state.ticket_link_matches(),
+            *selected,
+            &state.theme,
+        ),
         Modal::Progress { message } => modal::render_progress(frame, area, message, &state.theme),
         Modal::ThemePicker {
             themes,
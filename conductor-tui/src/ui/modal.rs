@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use chrono::Datelike;
 use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -5,12 +8,19 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use ratatui::Frame;
 use tui_textarea::TextArea;
 
-use conductor_core::agent::TicketAgentTotals;
+use conductor_core::activity::{ActivityGroupBy, ActivityHeatmap};
+use conductor_core::agent::{
+    AgentRunStatus, CostGroupBy, CostReportRow, InboxItem, TicketAgentTotals,
+};
 use conductor_core::github::DiscoveredRepo;
 use conductor_core::issue_source::IssueSource;
-use conductor_core::tickets::{Ticket, TicketDependencies, TicketLabel};
-use conductor_core::worktree::Worktree;
+use conductor_core::tickets::{
+    GroomingProposal, RoadmapGroup, RoadmapStage, Ticket, TicketAttachment, TicketDependencies,
+    TicketLabel, MANUAL_SOURCE_TYPE,
+};
+use conductor_core::worktree::{AbandonReason, Worktree};
 
+use crate::state::{MessageLog, Severity};
 use crate::theme::Theme;
 
 /// Data bundle passed to [`render_ticket_info`], grouping the ticket and its
@@ -20,6 +30,7 @@ pub struct TicketInfoData<'a> {
     pub agent_totals: Option<&'a TicketAgentTotals>,
     pub worktrees: Option<&'a Vec<Worktree>>,
     pub labels: Option<&'a [TicketLabel]>,
+    pub attachments: Option<&'a [TicketAttachment]>,
     pub dependencies: Option<&'a TicketDependencies>,
 }
 
@@ -58,6 +69,75 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, title: &str, message: &str,
     frame.render_widget(content, popup);
 }
 
+/// How many dirty files the dirty-worktree modal lists before summarizing.
+const DIRTY_FILES_SHOWN: usize = 8;
+
+pub fn render_dirty_worktree(
+    frame: &mut Frame,
+    area: Rect,
+    worktree_slug: &str,
+    files: &[String],
+    theme: &Theme,
+) {
+    let popup = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup);
+
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.label_accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(format!(
+            " {worktree_slug} has {} uncommitted change(s):",
+            files.len()
+        )),
+    ];
+    lines.extend(files.iter().take(DIRTY_FILES_SHOWN).map(|f| {
+        Line::from(Span::styled(
+            format!("   {f}"),
+            Style::default().fg(theme.label_secondary),
+        ))
+    }));
+    if files.len() > DIRTY_FILES_SHOWN {
+        lines.push(Line::from(Span::styled(
+            format!("   … and {} more", files.len() - DIRTY_FILES_SHOWN),
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            key("s"),
+            Span::raw(" = stash, restore after the run"),
+        ]),
+        Line::from(vec![
+            Span::raw("  "),
+            key("c"),
+            Span::raw(" = commit as WIP"),
+        ]),
+        Line::from(vec![
+            Span::raw("  "),
+            key("p"),
+            Span::raw(" = proceed with changes in place"),
+        ]),
+        Line::from(vec![Span::raw("  "), key("Esc"), Span::raw(" = cancel")]),
+    ]);
+
+    let content = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.label_warning))
+            .title(" Uncommitted changes "),
+    );
+    frame.render_widget(content, popup);
+}
+
 pub fn render_confirm_by_name(
     frame: &mut Frame,
     area: Rect,
@@ -239,6 +319,7 @@ pub fn render_ticket_info(frame: &mut Frame, area: Rect, data: &TicketInfoData<'
     let agent_totals = data.agent_totals;
     let worktrees = data.worktrees;
     let labels = data.labels;
+    let attachments = data.attachments.unwrap_or(&[]);
     let dependencies = data.dependencies;
 
     let popup = centered_rect(60, 70, area);
@@ -437,6 +518,22 @@ pub fn render_ticket_info(frame: &mut Frame, area: Rect, data: &TicketInfoData<'
         }
     }
 
+    if !attachments.is_empty() {
+        lines.push(Line::from(Span::styled("  Attachments:", label_style)));
+        for a in attachments {
+            let (indicator, location) = match a.local_path.as_deref() {
+                Some(path) => ("●", path),
+                None => ("○", a.url.as_str()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {indicator} "), dim_style),
+                Span::styled(&a.filename, value_style),
+                Span::styled(format!("  {location}"), dim_style),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
     lines.push(Line::from(Span::styled("  Description:", label_style)));
 
     // Add body lines with word wrapping (indented)
@@ -448,14 +545,22 @@ pub fn render_ticket_info(frame: &mut Frame, area: Rect, data: &TicketInfoData<'
     }
 
     lines.push(Line::from(""));
-    lines.push(Line::from(vec![
-        Span::styled(
-            "  o",
-            Style::default()
-                .fg(theme.status_completed)
-                .add_modifier(Modifier::BOLD),
-        ),
+    let key_style = Style::default()
+        .fg(theme.status_completed)
+        .add_modifier(Modifier::BOLD);
+    let mut footer = vec![
+        Span::styled("  o", key_style),
         Span::styled(" = open in browser    ", dim_style),
+    ];
+    if ticket.source_type != MANUAL_SOURCE_TYPE {
+        footer.extend([
+            Span::styled("n", key_style),
+            Span::styled(" = comment  ", dim_style),
+            Span::styled("X", key_style),
+            Span::styled(" = close issue  ", dim_style),
+        ]);
+    }
+    footer.extend([
         Span::styled(
             "Esc",
             Style::default()
@@ -463,7 +568,8 @@ pub fn render_ticket_info(frame: &mut Frame, area: Rect, data: &TicketInfoData<'
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(" = close", dim_style),
-    ]));
+    ]);
+    lines.push(Line::from(footer));
 
     let title = format!(" #{} {} ", ticket.source_id, ticket.title);
     let title_display = if title.len() > (popup.width as usize).saturating_sub(2) {
@@ -676,7 +782,10 @@ pub fn render_branch_picker(
                 .map(|pos| pos.to_prefix())
                 .unwrap_or_default();
             match &item.branch {
-                None => "default branch".to_string(),
+                None => match &item.resolved_base {
+                    Some(base) => format!("default branch → {base}"),
+                    None => "default branch".to_string(),
+                },
                 Some(branch) => {
                     let mut parts = Vec::new();
                     if item.worktree_count > 0 {
@@ -1581,13 +1690,37 @@ fn format_source_config_lines(source: &IssueSource) -> Vec<String> {
             "github" => {
                 let owner = val["owner"].as_str().unwrap_or("?");
                 let repo = val["repo"].as_str().unwrap_or("?");
-                vec![format!("{owner}/{repo}")]
+                let prs = if val["sync_prs"].as_bool().unwrap_or(false) {
+                    " (+ pull requests)"
+                } else {
+                    ""
+                };
+                vec![format!("{owner}/{repo}{prs}")]
             }
             "jira" => {
                 let url = val["url"].as_str().unwrap_or("?");
                 let jql = val["jql"].as_str().unwrap_or("?");
                 vec![format!("URL: {url}"), format!("JQL: {jql}")]
             }
+            "gitlab" => {
+                let base_url = val["base_url"].as_str().unwrap_or("?");
+                let project = val["project_id"].as_str().unwrap_or("?");
+                vec![format!("{base_url} — {project}")]
+            }
+            "linear" => {
+                let team = val["team_key"].as_str().unwrap_or("?");
+                vec![format!("Team: {team}")]
+            }
+            "bitbucket" => {
+                let workspace = val["workspace"].as_str().unwrap_or("?");
+                let repo = val["repo_slug"].as_str().unwrap_or("?");
+                vec![format!("{workspace}/{repo}")]
+            }
+            "asana" => {
+                let project = val["project_gid"].as_str().unwrap_or("?");
+                vec![format!("Project: {project}")]
+            }
+            "webhook" => vec!["POST /api/repos/{id}/tickets/ingest".to_string()],
             _ => vec![source.config_json.clone()],
         }
     } else {
@@ -1849,6 +1982,826 @@ pub fn render_template_picker(
     frame.render_widget(content, popup);
 }
 
+pub fn render_abandon_reason_picker(
+    frame: &mut Frame,
+    area: Rect,
+    wt_slug: &str,
+    message: &str,
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(60, 40, area);
+    frame.render_widget(Clear, popup);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {message}"),
+            Style::default().fg(theme.label_warning),
+        )),
+        Line::from(Span::styled(
+            format!("  Why is {wt_slug} being abandoned?"),
+            Style::default().fg(theme.label_accent),
+        )),
+        Line::from(""),
+    ];
+
+    let rows = AbandonReason::ALL
+        .iter()
+        .map(|r| (r.as_str(), r.description()))
+        .chain(std::iter::once(("no reason", "skip recording a reason")));
+    for (i, (name, description)) in rows.enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.label_warning)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {prefix}{} ", i + 1), style),
+            Span::styled(name, style),
+            Span::styled(
+                format!("  — {description}"),
+                Style::default().fg(theme.label_secondary),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  1-5 select  Enter confirm  Esc cancel",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(" Delete Worktree "),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+/// Rows of matches shown at once in the ticket link picker.
+const TICKET_LINK_VISIBLE_ROWS: usize = 12;
+
+pub fn render_ticket_link_picker(
+    frame: &mut Frame,
+    area: Rect,
+    query: &str,
+    matches: &[&Ticket],
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  > ", Style::default().fg(theme.border_focused)),
+            Span::styled(query, Style::default().add_modifier(Modifier::UNDERLINED)),
+            Span::styled("_", Style::default().fg(theme.border_focused)),
+        ]),
+        Line::from(""),
+    ];
+
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No matching tickets — sync tickets or change the search",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    // Keep the selection in view by scrolling the window with it.
+    let first = selected.saturating_sub(TICKET_LINK_VISIBLE_ROWS - 1);
+    for (i, ticket) in matches
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(TICKET_LINK_VISIBLE_ROWS)
+    {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.label_warning)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        let state_color = match ticket.state.as_str() {
+            "open" => theme.status_completed,
+            "closed" => theme.status_failed,
+            _ => theme.label_warning,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {prefix}#{:<6}", ticket.source_id), style),
+            Span::styled(
+                format!("{:<7}", ticket.state),
+                Style::default().fg(state_color),
+            ),
+            Span::styled(ticket.title.clone(), style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Type to search  ↑/↓ select  Enter link  Esc cancel",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(format!(" Link Ticket ({}) ", matches.len())),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+pub fn render_agent_inbox(
+    frame: &mut Frame,
+    area: Rect,
+    items: &[InboxItem],
+    selected: usize,
+    theme: &Theme,
+) {
+    let height = (items.len() as u16 + 7).min(30);
+    let percent_y = ((height as f32 / area.height as f32) * 100.0) as u16;
+    let popup = centered_rect(80, percent_y.max(25), area);
+    frame.render_widget(Clear, popup);
+
+    let mut lines = vec![Line::from("")];
+    for (i, item) in items.iter().enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.label_warning)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        let (status, status_color) = match item.status {
+            AgentRunStatus::Failed => ("failed", theme.status_failed),
+            _ => ("done", theme.status_completed),
+        };
+        let age = item
+            .ended_at
+            .as_deref()
+            .map(super::common::format_elapsed)
+            .unwrap_or_default();
+        let prompt = item.prompt.lines().next().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {prefix}{}/{}", item.repo_slug, item.worktree_slug),
+                style,
+            ),
+            Span::styled(format!("  {status}"), Style::default().fg(status_color)),
+            Span::styled(
+                format!("  {age}  {prompt}"),
+                Style::default().fg(theme.label_secondary),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Enter open  d diff  P push  p PR  R re-run  r reviewed  a all reviewed  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(format!(" Agent Inbox ({}) ", items.len())),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+pub fn render_message_log(
+    frame: &mut Frame,
+    area: Rect,
+    log: &MessageLog,
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    // Borders, the blank lines around the list and the hint line.
+    let visible = (popup.height as usize).saturating_sub(5).max(1);
+    let offset = selected.saturating_sub(visible - 1);
+
+    let mut lines = vec![Line::from("")];
+    if log.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No messages yet",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    for (i, entry) in log.newest_first().enumerate().skip(offset).take(visible) {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let severity_color = match entry.severity {
+            Severity::Error => theme.status_failed,
+            Severity::Warning => theme.label_warning,
+            Severity::Info => theme.label_secondary,
+        };
+        let text_style = if is_selected {
+            Style::default()
+                .fg(theme.label_primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {prefix}{}", entry.at.format("%H:%M:%S")),
+                Style::default().fg(theme.label_secondary),
+            ),
+            Span::styled(
+                format!("  {:<5}  ", entry.severity.label()),
+                Style::default().fg(severity_color),
+            ),
+            Span::styled(entry.text.clone(), text_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  j/k scroll  c clear  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(format!(" Messages ({}) ", log.len())),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+/// Width of a roadmap group's progress bar, in cells.
+const ROADMAP_BAR_WIDTH: usize = 24;
+
+fn roadmap_stage_color(stage: RoadmapStage, theme: &Theme) -> ratatui::style::Color {
+    match stage {
+        RoadmapStage::Done => theme.status_completed,
+        RoadmapStage::InReview => theme.status_waiting,
+        RoadmapStage::InProgress => theme.status_running,
+        RoadmapStage::NotStarted => theme.label_secondary,
+    }
+}
+
+/// Stacked bar: done, then in review, then in progress, padded with empty cells.
+fn roadmap_bar(group: &RoadmapGroup, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut used = 0;
+    let mut cumulative = 0;
+    for (count, stage) in [
+        (group.done, RoadmapStage::Done),
+        (group.in_review, RoadmapStage::InReview),
+        (group.in_progress, RoadmapStage::InProgress),
+    ] {
+        // Round on the running total so the segments always add up.
+        cumulative += count;
+        let end = (cumulative * ROADMAP_BAR_WIDTH)
+            .checked_div(group.total)
+            .unwrap_or(0);
+        let cells = end.saturating_sub(used);
+        used += cells;
+        if cells > 0 {
+            spans.push(Span::styled(
+                "█".repeat(cells),
+                Style::default().fg(roadmap_stage_color(stage, theme)),
+            ));
+        }
+    }
+    spans.push(Span::styled(
+        "░".repeat(ROADMAP_BAR_WIDTH - used),
+        Style::default().fg(theme.border_inactive),
+    ));
+    spans
+}
+
+pub fn render_roadmap(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    groups: &[RoadmapGroup],
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let name_width = groups
+        .iter()
+        .map(|g| g.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(30);
+
+    let mut lines = vec![Line::from("")];
+    if groups.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No tickets. Sync tickets first.",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    for (i, group) in groups.iter().enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let name_style = if is_selected {
+            Style::default()
+                .fg(theme.label_primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        let name = super::common::truncate(group.display_name(), name_width);
+        let mut spans = vec![Span::styled(
+            format!("  {prefix}{name:<name_width$}  "),
+            name_style,
+        )];
+        spans.extend(roadmap_bar(group, theme));
+        spans.push(Span::styled(
+            format!(
+                " {:>3}%  {}/{} done",
+                group.percent_done(),
+                group.done,
+                group.total
+            ),
+            Style::default().fg(theme.label_accent),
+        ));
+        if group.current {
+            spans.push(Span::styled(
+                "  current",
+                Style::default().fg(theme.group_header),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(group) = groups.get(selected) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} — {} in review, {} in progress, {} not started",
+                group.display_name(),
+                group.in_review,
+                group.in_progress,
+                group.not_started
+            ),
+            Style::default().fg(theme.group_header),
+        )));
+        for item in &group.items {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<12}", item.stage.label()),
+                    Style::default().fg(roadmap_stage_color(item.stage, theme)),
+                ),
+                Span::styled(
+                    format!("#{:<6} ", item.source_id),
+                    Style::default().fg(theme.label_secondary),
+                ),
+                Span::styled(item.title.clone(), Style::default().fg(theme.label_primary)),
+            ]));
+        }
+    }
+
+    // Keep the hint pinned to the bottom border.
+    let inner_height = (popup.height as usize).saturating_sub(2);
+    let body_height = inner_height.saturating_sub(2);
+    lines.truncate(body_height);
+    lines.resize(body_height, Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  j/k select group  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(format!(" {title} ")),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+fn format_report_duration(ms: i64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+pub fn render_reports(
+    frame: &mut Frame,
+    area: Rect,
+    group_by: CostGroupBy,
+    window_days: i64,
+    rows: &[CostReportRow],
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let label_width = rows
+        .iter()
+        .map(|r| r.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(8, 40);
+    let max_cost = rows.iter().map(|r| r.cost_usd).fold(0.0, f64::max);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("  Group by: ", Style::default().fg(theme.label_secondary)),
+            Span::styled(
+                group_by.to_string(),
+                Style::default()
+                    .fg(theme.label_accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("   Window: ", Style::default().fg(theme.label_secondary)),
+            Span::styled(
+                format!("last {window_days}d"),
+                Style::default()
+                    .fg(theme.label_accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "    {:<label_width$}  {:<ROADMAP_BAR_WIDTH$} {:>9} {:>5} {:>6} {:>8}",
+                "", "", "cost", "runs", "turns", "time"
+            ),
+            Style::default().fg(theme.group_header),
+        )),
+    ];
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No agent runs in this window.",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let label_style = if is_selected {
+            Style::default()
+                .fg(theme.label_primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        let cells = if max_cost > 0.0 {
+            ((row.cost_usd / max_cost) * ROADMAP_BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        let label = super::common::truncate(&row.label, label_width);
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {prefix}{label:<label_width$}  "), label_style),
+            Span::styled("█".repeat(cells), Style::default().fg(theme.label_accent)),
+            Span::styled(
+                "░".repeat(ROADMAP_BAR_WIDTH - cells),
+                Style::default().fg(theme.border_inactive),
+            ),
+            Span::styled(
+                format!(
+                    " {:>9} {:>5} {:>6} {:>8}",
+                    format!("${:.2}", row.cost_usd),
+                    row.runs,
+                    row.turns,
+                    format_report_duration(row.duration_ms)
+                ),
+                Style::default().fg(theme.label_primary),
+            ),
+        ]));
+    }
+    if !rows.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    {:<label_width$}  {:<ROADMAP_BAR_WIDTH$} {:>9} {:>5} {:>6} {:>8}",
+                "Total",
+                "",
+                format!("${:.2}", rows.iter().map(|r| r.cost_usd).sum::<f64>()),
+                rows.iter().map(|r| r.runs).sum::<i64>(),
+                rows.iter().map(|r| r.turns).sum::<i64>(),
+                format_report_duration(rows.iter().map(|r| r.duration_ms).sum())
+            ),
+            Style::default()
+                .fg(theme.label_accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    // Keep the hint pinned to the bottom border.
+    let inner_height = (popup.height as usize).saturating_sub(2);
+    let body_height = inner_height.saturating_sub(2);
+    lines.truncate(body_height);
+    lines.resize(body_height, Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Tab grouping  w window  h heat map  j/k select  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(" Agent costs "),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+/// Heat map shades from lightest to darkest; idle days render as `·`.
+const HEATMAP_SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+pub fn render_activity_heatmap(
+    frame: &mut Frame,
+    area: Rect,
+    group_by: ActivityGroupBy,
+    heatmap: &ActivityHeatmap,
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let rows = &heatmap.rows;
+    let label_width = rows
+        .iter()
+        .map(|r| r.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(8, 30);
+    // Show as many of the most recent days as fit beside the label and cost.
+    let inner_width = (popup.width as usize).saturating_sub(2);
+    let grid_width = inner_width
+        .saturating_sub(4 + label_width + 2 + 10)
+        .min(heatmap.days as usize);
+    let start = chrono::NaiveDate::parse_from_str(&heatmap.start, "%Y-%m-%d")
+        .unwrap_or_else(|_| chrono::Utc::now().date_naive());
+    let columns: Vec<chrono::NaiveDate> = (heatmap.days as usize - grid_width..)
+        .take(grid_width)
+        .map(|offset| start + chrono::Duration::days(offset as i64))
+        .collect();
+    let max_events = rows
+        .iter()
+        .flat_map(|r| r.days.iter().map(|d| d.events()))
+        .max()
+        .unwrap_or(0);
+
+    // Month names above the first column of each month.
+    let mut axis = String::new();
+    for (i, date) in columns.iter().enumerate() {
+        if axis.chars().count() > i {
+            continue;
+        }
+        if i == 0 || date.day() == 1 {
+            axis.push_str(&date.format("%b").to_string());
+        } else {
+            axis.push(' ');
+        }
+    }
+    let axis: String = axis.chars().take(grid_width).collect();
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("  Rows: ", Style::default().fg(theme.label_secondary)),
+            Span::styled(
+                group_by.to_string(),
+                Style::default()
+                    .fg(theme.label_accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("   Window: ", Style::default().fg(theme.label_secondary)),
+            Span::styled(
+                format!("last {}d", heatmap.days),
+                Style::default()
+                    .fg(theme.label_accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "   less · ░ ▒ ▓ █ more (commits + runs)",
+                Style::default().fg(theme.label_secondary),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "    {:<label_width$}  {axis:<grid_width$} {:>9}",
+                "", "cost"
+            ),
+            Style::default().fg(theme.group_header),
+        )),
+    ];
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No commits or agent runs in this window.",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let label_style = if is_selected {
+            Style::default()
+                .fg(theme.label_primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        let by_day: HashMap<&str, i64> = row
+            .days
+            .iter()
+            .map(|d| (d.day.as_str(), d.events()))
+            .collect();
+        let label = super::common::truncate(&row.label, label_width);
+        let mut spans = vec![Span::styled(
+            format!("  {prefix}{label:<label_width$}  "),
+            label_style,
+        )];
+        for date in &columns {
+            let events = by_day
+                .get(date.format("%Y-%m-%d").to_string().as_str())
+                .copied()
+                .unwrap_or(0);
+            if events == 0 || max_events == 0 {
+                spans.push(Span::styled(
+                    "·",
+                    Style::default().fg(theme.border_inactive),
+                ));
+            } else {
+                let shades = HEATMAP_SHADES.len();
+                let level = ((events as usize * shades).saturating_sub(1) / max_events as usize)
+                    .min(shades - 1);
+                spans.push(Span::styled(
+                    HEATMAP_SHADES[level].to_string(),
+                    Style::default().fg(theme.label_accent),
+                ));
+            }
+        }
+        spans.push(Span::styled(
+            format!(" {:>9}", format!("${:.2}", row.cost_usd)),
+            Style::default().fg(theme.label_primary),
+        ));
+        lines.push(Line::from(spans));
+    }
+    if let Some(row) = rows.get(selected) {
+        let busiest = row
+            .days
+            .iter()
+            .max_by_key(|d| d.events())
+            .map(|d| {
+                format!(
+                    " — busiest {} ({} commits, {} runs)",
+                    d.day, d.commits, d.runs
+                )
+            })
+            .unwrap_or_default();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {}: {} commits, {} runs, ${:.2}{busiest}",
+                row.label, row.commits, row.runs, row.cost_usd
+            ),
+            Style::default()
+                .fg(theme.label_accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    // Keep the hint pinned to the bottom border.
+    let inner_height = (popup.height as usize).saturating_sub(2);
+    let body_height = inner_height.saturating_sub(2);
+    lines.truncate(body_height);
+    lines.resize(body_height, Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Tab repo/worktree  h costs  j/k select  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(" Activity "),
+    );
+
+    frame.render_widget(content, popup);
+}
+
+pub fn render_grooming(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    proposals: &[GroomingProposal],
+    selected: usize,
+    theme: &Theme,
+) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let mut lines = vec![Line::from("")];
+    if proposals.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No pending proposals. Run `conductor tickets groom` or enable [grooming].",
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+    for (i, p) in proposals.iter().enumerate() {
+        let is_selected = i == selected;
+        let prefix = if is_selected { "▸ " } else { "  " };
+        let title_style = if is_selected {
+            Style::default()
+                .fg(theme.label_primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.label_primary)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {prefix}#{:<6} ", p.ticket_source_id),
+                Style::default().fg(theme.label_secondary),
+            ),
+            Span::styled(p.ticket_title.clone(), title_style),
+        ]));
+    }
+
+    if let Some(p) = proposals.get(selected) {
+        lines.push(Line::from(""));
+        let detail = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(
+                    format!("    {label:<11}"),
+                    Style::default().fg(theme.group_header),
+                ),
+                Span::styled(value, Style::default().fg(theme.label_primary)),
+            ])
+        };
+        if !p.labels.is_empty() {
+            lines.push(detail("labels", p.labels.join(", ")));
+        }
+        if let Some(estimate) = &p.estimate {
+            lines.push(detail("estimate", estimate.clone()));
+        }
+        if !p.duplicates.is_empty() {
+            let ids: Vec<String> = p.duplicates.iter().map(|d| format!("#{d}")).collect();
+            lines.push(detail("duplicates", ids.join(", ")));
+        }
+        if let Some(workflow) = &p.workflow {
+            lines.push(detail("workflow", workflow.clone()));
+        }
+        if let Some(rationale) = &p.rationale {
+            lines.push(detail("why", rationale.clone()));
+        }
+    }
+
+    // Keep the hint pinned to the bottom border.
+    let inner_height = (popup.height as usize).saturating_sub(2);
+    let body_height = inner_height.saturating_sub(2);
+    lines.truncate(body_height);
+    lines.resize(body_height, Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  j/k select  a accept  d dismiss  Esc close",
+        Style::default().fg(theme.label_secondary),
+    )));
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(format!(" {title} ")),
+    );
+
+    frame.render_widget(content, popup);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Percentage(percent_y)])
         .flex(Flex::Center)
@@ -0,0 +1,78 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use conductor_core::tickets::QueueReason;
+
+use crate::state::AppState;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let title = if state.my_queue_loading {
+        " My Queue (loading…) ".to_string()
+    } else {
+        format!(" My Queue ({}) ", state.my_queue_items.len())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .title(title);
+
+    if state.my_queue_items.is_empty() {
+        let message = if state.my_queue_loading {
+            "Loading…"
+        } else {
+            "Nothing is assigned to or mentions you."
+        };
+        let p = Paragraph::new(Span::styled(
+            message,
+            Style::default().fg(theme.label_secondary),
+        ))
+        .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .my_queue_items
+        .iter()
+        .map(|item| {
+            let t = &item.ticket;
+            let (label, color) = match item.reason() {
+                QueueReason::Assigned => ("assigned", theme.label_warning),
+                QueueReason::Mentioned => ("mentioned", theme.label_info),
+            };
+            let repo = state
+                .data
+                .repo_slug_map
+                .get(&t.repo_id)
+                .map(|s| s.as_str())
+                .unwrap_or("?");
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{label:<11}"), Style::default().fg(color)),
+                Span::styled(
+                    format!("#{:<6} ", t.source_id),
+                    Style::default().fg(theme.label_secondary),
+                ),
+                Span::styled(t.title.clone(), Style::default().fg(theme.label_primary)),
+                Span::styled(
+                    format!("  {repo} · {}", t.state),
+                    Style::default().fg(theme.label_secondary),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+    let mut list_state = ListState::default().with_selected(Some(state.my_queue_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
@@ -86,7 +86,7 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(9),
+            Constraint::Length(10),
             Constraint::Length(wt_height),
             Constraint::Length(pr_height),
             Constraint::Min(3),
@@ -173,6 +173,24 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 Style::default().fg(state.theme.label_secondary),
             ),
         ]),
+        Line::from(vec![
+            Span::styled(
+                "Toolchain:     ",
+                Style::default().fg(state.theme.label_secondary),
+            ),
+            if repo.toolchain.is_empty() {
+                Span::styled(
+                    "(not detected)",
+                    Style::default().fg(state.theme.label_secondary),
+                )
+            } else {
+                Span::raw(repo.toolchain.to_string())
+            },
+            Span::styled(
+                " (press Enter to re-detect)",
+                Style::default().fg(state.theme.label_secondary),
+            ),
+        ]),
     ];
 
     // Apply highlight to the focused row when info pane is focused
@@ -313,14 +331,11 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
             if !toggle_glyph.is_empty() {
                 spans.push(Span::raw(toggle_glyph));
             }
+            let mut progress = super::common::ticket_progress_spans(state, &t.id);
             if is_blocked {
-                spans.push(Span::styled(
-                    "⊘ ",
-                    Style::default().fg(state.theme.label_error),
-                ));
-            } else {
-                spans.push(super::common::ticket_worktree_dot_span(state, &t.id));
+                progress[0] = Span::styled("⊘ ", Style::default().fg(state.theme.label_error));
             }
+            spans.extend(progress);
             let id_str = super::common::truncate(&t.source_id, id_width);
             spans.push(Span::styled(
                 format!("#{:<width$} ", id_str, width = id_width),
@@ -348,6 +363,15 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 }
             }
             spans.push(Span::raw(&t.title));
+            if is_parent {
+                spans.extend(super::common::ticket_rollup_spans(state, &t.id));
+            }
+            if state.data.watched_tickets.contains(&t.id) {
+                spans.push(Span::styled(
+                    " ◎",
+                    Style::default().fg(state.theme.label_accent),
+                ));
+            }
             let labels = state
                 .data
                 .ticket_labels
@@ -374,21 +398,23 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
         );
     }
 
-    let hiding = !state.show_closed_tickets;
-    let sort_frag = state.detail_ticket_sort.title_fragment();
-    let ticket_title = match (
-        detail_filter.as_deref().filter(|f| !f.is_empty()),
-        hiding,
-        sort_frag,
-    ) {
-        (None, false, None) => " Tickets ".to_string(),
-        (None, false, Some(s)) => format!(" Tickets ({s}) "),
-        (None, true, None) => " Tickets (hiding closed) ".to_string(),
-        (None, true, Some(s)) => format!(" Tickets (hiding closed, {s}) "),
-        (Some(f), false, None) => format!(" Tickets (filter: {f}) "),
-        (Some(f), false, Some(s)) => format!(" Tickets (filter: {f}, {s}) "),
-        (Some(f), true, None) => format!(" Tickets (filter: {f}, hiding closed) "),
-        (Some(f), true, Some(s)) => format!(" Tickets (filter: {f}, hiding closed, {s}) "),
+    let mut title_parts: Vec<String> = Vec::new();
+    if let Some(f) = detail_filter.as_deref().filter(|f| !f.is_empty()) {
+        title_parts.push(format!("filter: {f}"));
+    }
+    if !state.show_closed_tickets {
+        title_parts.push("hiding closed".to_string());
+    }
+    if state.current_sprint_only {
+        title_parts.push("current sprint".to_string());
+    }
+    if let Some(s) = state.detail_ticket_sort.title_fragment() {
+        title_parts.push(s.to_string());
+    }
+    let ticket_title = if title_parts.is_empty() {
+        " Tickets ".to_string()
+    } else {
+        format!(" Tickets ({}) ", title_parts.join(", "))
     };
 
     let ticket_list = List::new(ticket_items)
@@ -421,7 +447,9 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
     };
 
     let pr_items: Vec<ListItem> = if state.detail_prs.is_empty() {
-        let placeholder = if state.pr_last_fetched_at.is_some() {
+        let placeholder = if state.tool_missing(conductor_core::tools::GH) {
+            "(gh not installed \u{2014} PRs unavailable)"
+        } else if state.pr_last_fetched_at.is_some() {
             "(no open PRs)"
         } else {
             "(loading\u{2026})"
@@ -595,14 +623,22 @@ fn render_repo_agent_activity(frame: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
+    let env_line = super::common::run_environment_line(run, &state.theme);
     let pane_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(u16::from(env_line.is_some())),
+            Constraint::Min(0),
+        ])
         .split(inner);
 
     // Status line
     let status_line = render_repo_agent_status(run, &state.theme);
     frame.render_widget(Paragraph::new(status_line), pane_layout[0]);
+    if let Some(env_line) = env_line {
+        frame.render_widget(Paragraph::new(env_line), pane_layout[1]);
+    }
 
     // Event list
     let events = &state.data.repo_agent_events;
@@ -611,7 +647,7 @@ fn render_repo_agent_activity(frame: &mut Frame, area: Rect, state: &AppState) {
             "No events yet",
             Style::default().fg(state.theme.label_secondary),
         ));
-        frame.render_widget(empty, pane_layout[1]);
+        frame.render_widget(empty, pane_layout[2]);
         return;
     }
 
@@ -662,7 +698,7 @@ fn render_repo_agent_activity(frame: &mut Frame, area: Rect, state: &AppState) {
 
     frame.render_stateful_widget(
         list,
-        pane_layout[1],
+        pane_layout[2],
         &mut state.repo_agent_list_state.borrow_mut(),
     );
 }
@@ -126,6 +126,9 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         SettingsCategory::Runtimes => {
             render_runtimes(frame, right_area, right_block, state, right_focused)
         }
+        SettingsCategory::Tools => {
+            render_tools(frame, right_area, right_block, state, right_focused)
+        }
     }
 }
 
@@ -253,6 +256,68 @@ fn render_appearance(frame: &mut Frame, area: Rect, block: Block, state: &AppSta
     frame.render_widget(para, area);
 }
 
+fn render_tools(frame: &mut Frame, area: Rect, block: Block, state: &AppState, focused: bool) {
+    let theme = &state.theme;
+    let sel = state.settings_row_index;
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    match &state.tools {
+        None => lines.push(Line::from(Span::styled(
+            "  Checking\u{2026}",
+            Style::default().fg(theme.label_secondary),
+        ))),
+        Some(report) => {
+            let name_w = report.tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
+            for (i, tool) in report.tools.iter().enumerate() {
+                let style = row_style(i, sel, focused, state);
+                let (mark, color, detail) = match (&tool.version, tool.available) {
+                    (Some(v), _) => ("\u{2713}", theme.status_completed, v.clone()),
+                    (None, true) => ("\u{2713}", theme.status_completed, "installed".into()),
+                    (None, false) if tool.required => (
+                        "\u{2717}",
+                        theme.status_failed,
+                        format!("missing \u{2014} needed for {}", tool.purpose),
+                    ),
+                    (None, false) => (
+                        "-",
+                        theme.label_secondary,
+                        format!("not installed \u{2014} {}", tool.purpose),
+                    ),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {mark} "), style.fg(color)),
+                    Span::styled(format!("{:<name_w$}  ", tool.name), style),
+                    Span::styled(detail, style),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Checked {}",
+                    report
+                        .probed_at_time()
+                        .map_or(report.probed_at.clone(), |t| t
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string())
+                ),
+                Style::default().fg(theme.label_secondary),
+            )));
+        }
+    }
+    let hint = if focused {
+        "  [r] re-check  [Esc] back"
+    } else {
+        "  [Tab] switch pane"
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme.label_secondary),
+    )));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_notifications(
     frame: &mut Frame,
     area: Rect,
@@ -0,0 +1,92 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::state::{RepoSyncState, SyncProgress};
+use crate::theme::Theme;
+
+use super::common::truncate;
+
+const PANEL_WIDTH: u16 = 48;
+/// Repo rows shown before the rest collapse into "+N more".
+const MAX_ROWS: usize = 8;
+
+/// Transient panel in the bottom-right corner, above the footer, listing each
+/// repo's ticket sync state.
+pub fn render(frame: &mut Frame, body_area: Rect, progress: &SyncProgress, theme: &Theme) {
+    let shown = progress.repos.len().min(MAX_ROWS);
+    let overflow = progress.repos.len() - shown;
+    let rows = shown + usize::from(overflow > 0);
+
+    let width = PANEL_WIDTH.min(body_area.width);
+    let height = (rows as u16 + 2).min(body_area.height);
+    if width < 10 || height < 3 {
+        return;
+    }
+    let area = Rect {
+        x: body_area.x + body_area.width - width,
+        y: body_area.y + body_area.height - height,
+        width,
+        height,
+    };
+
+    let inner_width = usize::from(width - 2);
+    let mut lines: Vec<Line> = progress
+        .repos
+        .iter()
+        .take(shown)
+        .map(|repo| {
+            let (icon, color, detail) = match repo.state {
+                RepoSyncState::Pending => ("○", theme.label_secondary, "pending".to_string()),
+                RepoSyncState::Running => ("⟳", theme.status_running, running_detail(repo.synced)),
+                RepoSyncState::Done => (
+                    "✓",
+                    theme.status_completed,
+                    format!("{} synced", repo.synced),
+                ),
+                RepoSyncState::Error => (
+                    "✗",
+                    theme.status_failed,
+                    repo.errors.last().cloned().unwrap_or_default(),
+                ),
+            };
+            let slug = truncate(&repo.repo_slug, inner_width / 2);
+            let detail_width = inner_width.saturating_sub(slug.chars().count() + 3);
+            Line::from(vec![
+                Span::styled(format!("{icon} "), Style::default().fg(color)),
+                Span::styled(slug, Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" "),
+                Span::styled(truncate(&detail, detail_width), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if overflow > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("  +{overflow} more"),
+            Style::default().fg(theme.label_secondary),
+        )));
+    }
+
+    let title = format!(
+        " Ticket sync {}/{} ",
+        progress.finished(),
+        progress.repos.len()
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn running_detail(synced: usize) -> String {
+    if synced == 0 {
+        "syncing…".to_string()
+    } else {
+        format!("syncing… {synced} so far")
+    }
+}
@@ -0,0 +1,90 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use conductor_core::today::TodayItemKind;
+
+use crate::state::AppState;
+use crate::theme::Theme;
+
+use super::common::format_elapsed;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let title = if state.today_loading {
+        " Today (loading…) ".to_string()
+    } else {
+        format!(" Today ({}) ", state.today_items.len())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .title(title);
+
+    if state.today_items.is_empty() {
+        let message = if state.today_loading {
+            "Loading…"
+        } else {
+            "Nothing needs attention."
+        };
+        let p = Paragraph::new(Span::styled(
+            message,
+            Style::default().fg(theme.label_secondary),
+        ))
+        .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .today_items
+        .iter()
+        .map(|item| {
+            let mut context = item.repo_slug.clone().unwrap_or_default();
+            if !item.detail.is_empty() {
+                if !context.is_empty() {
+                    context.push_str(" · ");
+                }
+                context.push_str(&item.detail);
+            }
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<11}", item.kind.label()),
+                    Style::default().fg(kind_color(item.kind, theme)),
+                ),
+                Span::styled(
+                    format!("{:>4}  ", format_elapsed(&item.since)),
+                    Style::default().fg(theme.label_secondary),
+                ),
+                Span::styled(item.title.clone(), Style::default().fg(theme.label_primary)),
+                Span::styled(
+                    format!("  {context}"),
+                    Style::default().fg(theme.label_secondary),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+    let mut list_state = ListState::default().with_selected(Some(state.today_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn kind_color(kind: TodayItemKind, theme: &Theme) -> ratatui::style::Color {
+    match kind {
+        TodayItemKind::AwaitingFeedback => theme.status_waiting,
+        TodayItemKind::FailingCi | TodayItemKind::SlaBreach => theme.status_failed,
+        TodayItemKind::NeedsReview => theme.label_warning,
+        TodayItemKind::Notification => theme.label_info,
+        TodayItemKind::RunningAgent => theme.status_running,
+    }
+}
@@ -1967,10 +1967,16 @@ fn render_step_agent_activity(
         " Agent Activity ".to_string()
     };
 
-    let activity_block = Block::default()
+    let mut activity_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .title(title);
+    if let Some(env) = agent_run.as_ref().and_then(|r| r.environment.as_ref()) {
+        activity_block = activity_block.title_bottom(Span::styled(
+            format!(" {} ", env.summary()),
+            Style::default().fg(state.theme.label_secondary),
+        ));
+    }
 
     if events.is_empty() {
         let msg = if agent_run
@@ -4,11 +4,12 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-use conductor_core::worktree::WorktreeStatus;
-
 use super::helpers::shorten_paths;
 use crate::state::{AppState, ColumnFocus, VisualRow, WorktreeDetailFocus};
 
+/// Branch TODOs listed in the info panel; the rest are counted.
+const MAX_TODO_LINES: usize = 8;
+
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     super::workflow_column::render_with_workflow_column(frame, area, state, render_content);
 }
@@ -47,7 +48,7 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
             "in_progress" => state.theme.status_running,
             _ => state.theme.label_primary,
         };
-        vec![
+        let mut spans = vec![
             Span::styled("Ticket: ", Style::default().fg(state.theme.label_secondary)),
             Span::raw(format!("#{} — {}", ticket.source_id, ticket.title)),
             Span::raw("  "),
@@ -55,7 +56,14 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 format!("[{}]", ticket.state),
                 Style::default().fg(ticket_state_color),
             ),
-        ]
+        ];
+        if let Some(change) = &wt.ticket_change {
+            spans.push(Span::styled(
+                format!("  ⚑ changed: {change} (a to acknowledge)"),
+                Style::default().fg(state.theme.label_warning),
+            ));
+        }
+        spans
     } else {
         vec![
             Span::styled("Ticket: ", Style::default().fg(state.theme.label_secondary)),
@@ -66,11 +74,12 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
         ]
     };
 
-    let status_color = match wt.status {
-        WorktreeStatus::Active => state.theme.status_completed,
-        WorktreeStatus::Merged => state.theme.label_info,
-        WorktreeStatus::Abandoned => state.theme.status_failed,
-    };
+    let status_color = super::common::worktree_status_color(wt.status, &state.theme);
+    let watched = state.data.watched_worktrees.contains(&wt.id)
+        || wt
+            .ticket_id
+            .as_ref()
+            .is_some_and(|t| state.data.watched_tickets.contains(t));
 
     let mut lines = vec![
         Line::from(vec![
@@ -109,10 +118,22 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 "",
                 dirs::home_dir().as_deref().and_then(|p| p.to_str()),
             )),
+            Span::styled(
+                if wt.missing_since.is_some() {
+                    "  ✗ missing (conductor worktree restore)"
+                } else {
+                    ""
+                },
+                Style::default().fg(state.theme.label_error),
+            ),
         ]),
         Line::from(vec![
             Span::styled("Status: ", Style::default().fg(state.theme.label_secondary)),
-            Span::styled(wt.status.to_string(), Style::default().fg(status_color)),
+            Span::styled(wt.status_label(), Style::default().fg(status_color)),
+            Span::styled(
+                if watched { "  ◎ watching" } else { "" },
+                Style::default().fg(state.theme.label_accent),
+            ),
         ]),
         Line::from(vec![
             Span::styled("Model: ", Style::default().fg(state.theme.label_secondary)),
@@ -167,6 +188,48 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
         ]));
     }
 
+    if let Some(git) = state.data.git_statuses.get(&wt.id) {
+        let color = if git.has_unpushed_work() {
+            state.theme.label_warning
+        } else {
+            state.theme.status_completed
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Git: ", Style::default().fg(state.theme.label_secondary)),
+            Span::styled(git.summary(), Style::default().fg(color)),
+        ]));
+        if let Some(ref commit) = git.last_commit {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Last commit: ",
+                    Style::default().fg(state.theme.label_secondary),
+                ),
+                Span::raw(format!(
+                    "{} {}",
+                    &commit.sha[..7.min(commit.sha.len())],
+                    commit.summary
+                )),
+                Span::styled(
+                    format!("  ({}, {})", commit.author, commit.committed_at),
+                    Style::default().fg(state.theme.label_secondary),
+                ),
+            ]));
+        }
+    }
+
+    if let Some(budget) = state.data.over_budget.get(&wt.id) {
+        lines.push(Line::from(vec![
+            Span::styled("Budget: ", Style::default().fg(state.theme.label_secondary)),
+            Span::styled(
+                format!(
+                    "⚠ ${:.2} of ${:.2} spent — new agents need confirmation",
+                    budget.spent_usd, budget.limit_usd
+                ),
+                Style::default().fg(state.theme.label_warning),
+            ),
+        ]));
+    }
+
     lines.push(Line::from(""));
 
     // Agent status line and plan checklist from DB poll
@@ -177,6 +240,9 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
             &state.data.agent_totals,
             &state.theme,
         ));
+        if let Some(env_line) = super::common::run_environment_line(run, &state.theme) {
+            lines.push(env_line);
+        }
 
         // Show pending feedback request prompt
         if let Some(ref fb) = state.data.pending_feedback {
@@ -263,6 +329,33 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
         }
     }
 
+    // Agent runs queued for this worktree, in dispatch order
+    let queued: Vec<_> = state
+        .data
+        .agent_queue
+        .iter()
+        .filter(|q| q.worktree_id == wt.id)
+        .collect();
+    if !queued.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Queued:",
+            Style::default().fg(state.theme.label_secondary),
+        )));
+        for q in queued {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  [{}] ", q.priority),
+                    Style::default().fg(priority_color(q.priority, &state.theme)),
+                ),
+                Span::styled(
+                    super::common::truncate(q.prompt.lines().next().unwrap_or_default(), 60),
+                    Style::default().fg(state.theme.label_primary),
+                ),
+            ]));
+        }
+    }
+
     // Issues created by agents
     if !state.data.agent_created_issues.is_empty() {
         lines.push(Line::from(""));
@@ -283,6 +376,43 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
         }
     }
 
+    // TODO/FIXME comments the branch added, as of the last scan
+    if !state.data.branch_todos.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("Branch TODOs ({}):", state.data.branch_todos.len()),
+            Style::default().fg(state.theme.label_secondary),
+        )));
+        for todo in state.data.branch_todos.iter().take(MAX_TODO_LINES) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}:{} ", todo.file, todo.line),
+                    Style::default().fg(state.theme.label_secondary),
+                ),
+                Span::styled(
+                    format!("{} ", todo.marker),
+                    Style::default().fg(state.theme.label_warning),
+                ),
+                Span::raw(super::common::truncate(&todo.text, 60)),
+            ]));
+        }
+        if state.data.branch_todos.len() > MAX_TODO_LINES {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  …and {} more",
+                    state.data.branch_todos.len() - MAX_TODO_LINES
+                ),
+                Style::default().fg(state.theme.label_secondary),
+            )));
+        }
+    }
+
+    // Latest agent review of the diff against base
+    if let Some(ref review) = state.data.latest_diff_review {
+        lines.push(Line::from(""));
+        lines.push(render_diff_review_line(review, &state.theme));
+    }
+
     lines.push(Line::from(""));
 
     let actions_text = if wt.is_active() {
@@ -309,9 +439,9 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 "Tab=switch panel  y=copy  o=act  f=respond  F=dismiss  x=stop  w=workflow  d=del  Esc=back"
             }
         } else if has_resumable_wf {
-            "Tab=switch panel  y=copy  o=act  x=stop  X=clear conv  w=workflow  r=resume wf  d=del  Esc=back"
+            "Tab=switch panel  y=copy  o=act  x=stop  X=clear conv  v=review  w=workflow  r=resume wf  d=del  Esc=back"
         } else {
-            "Tab=switch panel  y=copy  o=act  x=stop  X=clear conv  w=workflow  d=del  Esc=back"
+            "Tab=switch panel  y=copy  o=act  x=stop  X=clear conv  v=review  w=workflow  d=del  Esc=back"
         }
     } else {
         "Tab=switch panel  y=copy  o=act  X=clear conv  Esc=back  (archived)"
@@ -516,7 +646,7 @@ fn extract_step_label(prompt: &str) -> Option<String> {
     Some(format!("STEP {step_num}/{total}"))
 }
 
-fn event_style(kind: &str, theme: &crate::theme::Theme) -> Style {
+pub(super) fn event_style(kind: &str, theme: &crate::theme::Theme) -> Style {
     match kind {
         "text" => Style::default().fg(theme.label_primary),
         "tool" => Style::default().fg(theme.label_warning),
@@ -530,6 +660,49 @@ fn event_style(kind: &str, theme: &crate::theme::Theme) -> Style {
 
 /// Render a single agent status line from the latest AgentRun for this worktree.
 /// Shows aggregate totals across all runs when there are multiple.
+fn priority_color(
+    priority: conductor_core::agent::AgentPriority,
+    theme: &crate::theme::Theme,
+) -> ratatui::style::Color {
+    use conductor_core::agent::AgentPriority;
+    match priority {
+        AgentPriority::Urgent => theme.label_error,
+        AgentPriority::Normal => theme.label_primary,
+        AgentPriority::Background => theme.label_secondary,
+    }
+}
+
+fn render_diff_review_line(
+    review: &conductor_core::agent::DiffReview,
+    theme: &crate::theme::Theme,
+) -> Line<'static> {
+    use conductor_core::agent::AgentRunStatus;
+    let (label, color, hint) = match review.status {
+        AgentRunStatus::Running | AgentRunStatus::WaitingForFeedback => {
+            ("[reviewing]", theme.status_running, "")
+        }
+        AgentRunStatus::Completed => ("[ready]", theme.status_completed, " — V to view"),
+        AgentRunStatus::Failed => ("[failed]", theme.status_failed, " — v to retry"),
+        AgentRunStatus::Cancelled => ("[cancelled]", theme.status_cancelled, " — v to retry"),
+    };
+    let trimmed = if review.truncated {
+        ", diff trimmed"
+    } else {
+        ""
+    };
+    Line::from(vec![
+        Span::styled("Review: ", Style::default().fg(theme.label_secondary)),
+        Span::styled(label, Style::default().fg(color)),
+        Span::styled(
+            format!(
+                " {} files vs {}{trimmed}{hint}",
+                review.files_changed, review.base_ref
+            ),
+            Style::default().fg(theme.label_secondary),
+        ),
+    ])
+}
+
 fn render_agent_status_line(
     run: &conductor_core::agent::AgentRun,
     totals: &crate::state::AgentTotals,
@@ -646,19 +819,27 @@ fn render_agent_status_line(
     }
 }
 
-/// Render a single child run as an indented line under the parent agent status.
-fn render_child_run_line(
-    run: &conductor_core::agent::AgentRun,
+/// Short status label and its color.
+pub(super) fn run_status_label(
+    status: &conductor_core::agent::AgentRunStatus,
     theme: &crate::theme::Theme,
-) -> Line<'static> {
+) -> (&'static str, ratatui::style::Color) {
     use conductor_core::agent::AgentRunStatus;
-    let (status_text, status_color) = match run.status {
+    match status {
         AgentRunStatus::Running => ("running", theme.status_running),
         AgentRunStatus::Completed => ("completed", theme.status_completed),
         AgentRunStatus::Failed => ("failed", theme.status_failed),
         AgentRunStatus::Cancelled => ("cancelled", theme.status_cancelled),
         AgentRunStatus::WaitingForFeedback => ("waiting", theme.status_waiting),
-    };
+    }
+}
+
+/// Render a single child run as an indented line under the parent agent status.
+fn render_child_run_line(
+    run: &conductor_core::agent::AgentRun,
+    theme: &crate::theme::Theme,
+) -> Line<'static> {
+    let (status_text, status_color) = run_status_label(&run.status, theme);
     let status_str = format!("[{status_text}]");
 
     let prompt = extract_step_label(&run.prompt).unwrap_or_else(|| {
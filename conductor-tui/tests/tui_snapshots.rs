@@ -33,6 +33,7 @@ fn make_repos() -> Vec<Repo> {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         },
         Repo {
             id: "01REPO0000000000000000000B".into(),
@@ -45,6 +46,7 @@ fn make_repos() -> Vec<Repo> {
             model: None,
             allow_agent_issue_creation: false,
             runtime_overrides: None,
+            toolchain: Default::default(),
         },
     ]
 }
@@ -63,6 +65,9 @@ fn make_worktrees(repos: &[Repo]) -> Vec<Worktree> {
             completed_at: None,
             model: None,
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         },
         Worktree {
             id: "01WT00000000000000000000A2".into(),
@@ -76,6 +81,9 @@ fn make_worktrees(repos: &[Repo]) -> Vec<Worktree> {
             completed_at: None,
             model: None,
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         },
         Worktree {
             id: "01WT00000000000000000000B1".into(),
@@ -89,6 +97,9 @@ fn make_worktrees(repos: &[Repo]) -> Vec<Worktree> {
             completed_at: Some("2024-01-12T00:00:00Z".into()),
             model: None,
             base_branch: None,
+            abandon_reason: None,
+            missing_since: None,
+            ticket_change: None,
         },
     ]
 }
@@ -111,6 +122,8 @@ fn make_tickets(repos: &[Repo]) -> Vec<Ticket> {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         },
         Ticket {
             id: "01TKT0000000000000000000B1".into(),
@@ -128,6 +141,8 @@ fn make_tickets(repos: &[Repo]) -> Vec<Ticket> {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         },
     ]
 }
@@ -172,6 +187,7 @@ fn snap_modal_confirm() {
         on_confirm: ConfirmAction::DeleteWorktree {
             repo_slug: "my-app".into(),
             wt_slug: "feat-123-add-login".into(),
+            reason: None,
         },
     };
     insta::assert_snapshot!(render_to_string(&state));
@@ -441,6 +457,8 @@ fn snap_workflow_run_detail_ticket_targeted() {
         raw_json: String::new(),
         workflow: None,
         agent_map: None,
+        sprint: None,
+        sprint_current: false,
     };
     state
         .data
@@ -570,6 +588,8 @@ fn snap_modal_ticket_info() {
             raw_json: "{}".into(),
             workflow: None,
             agent_map: None,
+            sprint: None,
+            sprint_current: false,
         }),
     };
     insta::assert_snapshot!(render_to_string(&state));
@@ -590,6 +610,7 @@ fn snap_modal_branch_picker() {
                 base_branch: None,
                 stale_days: None,
                 inferred_from: None,
+                resolved_base: None,
             },
             BranchPickerItem {
                 branch: Some("feat/auth-flow".into()),
@@ -598,6 +619,7 @@ fn snap_modal_branch_picker() {
                 base_branch: Some("main".into()),
                 stale_days: None,
                 inferred_from: None,
+                resolved_base: None,
             },
             BranchPickerItem {
                 branch: Some("feat/dashboard".into()),
@@ -606,6 +628,7 @@ fn snap_modal_branch_picker() {
                 base_branch: Some("main".into()),
                 stale_days: None,
                 inferred_from: None,
+                resolved_base: None,
             },
         ],
         tree_positions: vec![
@@ -10,10 +10,12 @@ import { WorkflowRunDetailPage } from "./pages/WorkflowRunDetailPage";
 import { WorkflowDefDetailPage } from "./pages/WorkflowDefDetailPage";
 import { WorkflowAnalyticsPage } from "./pages/WorkflowAnalyticsPage";
 import { TicketsPage } from "./pages/TicketsPage";
+import { ReportsPage } from "./pages/ReportsPage";
 import { SettingsPage } from "./pages/SettingsPage";
 import { HookMatrixPage } from "./pages/HookMatrixPage";
 import { GettingStartedPage } from "./pages/GettingStartedPage";
 import { NotFoundPage } from "./pages/NotFoundPage";
+import { SharedRunPage } from "./pages/SharedRunPage";
 
 const router = createBrowserRouter([
   {
@@ -24,6 +26,7 @@ const router = createBrowserRouter([
       { path: "workflows", element: <WorkflowsPage /> },
       { path: "workflows/analytics", element: <WorkflowAnalyticsPage /> },
       { path: "tickets", element: <TicketsPage /> },
+      { path: "reports", element: <ReportsPage /> },
       { path: "repos/:repoId", element: <RepoDetailPage /> },
       {
         path: "repos/:repoId/worktrees/:worktreeId",
@@ -43,6 +46,8 @@ const router = createBrowserRouter([
       { path: "*", element: <NotFoundPage /> },
     ],
   },
+  // Opened by people without an API token, so it skips the app shell.
+  { path: "share/:token", element: <SharedRunPage /> },
 ]);
 
 export default function App() {
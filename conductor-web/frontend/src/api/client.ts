@@ -1,20 +1,57 @@
 import type {
   Repo,
+  RepoVisibilityState,
+  CommandInfo,
   Worktree,
+  AbandonReason,
+  ResolvedBase,
+  WorktreeOutcomes,
   WorktreeWithStatus,
+  StackInfo,
+  Ticket,
   TicketLabel,
   TicketAgentTotals,
+  BudgetExceeded,
+  CostGroupBy,
+  CostReportRow,
+  TicketTotalsPage,
+  TicketTotalsPageQuery,
+  TicketTotalsQuery,
   TicketListResponse,
+  TicketTreeResponse,
   TicketDetail,
   CreateRepoRequest,
   CreateWorktreeRequest,
+  CreateWorktreeFromUrlRequest,
+  CreateWorktreeAccepted,
+  WorktreeJob,
   GithubPr,
   SyncResult,
+  GroomingProposal,
+  GroomingPass,
   AgentRun,
+  AgentChain,
+  ChainLink,
+  StopAllSummary,
+  AgentPriority,
+  QueuedAgentRun,
+  InboxItem,
+  TodayItem,
+  ToolReport,
+  AgentRunAlert,
+  Watch,
+  WatchTarget,
   AgentEvent,
   AgentPromptInfo,
   RunTreeTotals,
   AgentCreatedIssue,
+  DiffReview,
+  BranchTodo,
+  CommitSummary,
+  WorktreeDiff,
+  WorktreeListing,
+  ConflictResolution,
+  WorktreeConflicts,
   IssueSource,
   CreateIssueSourceRequest,
   DiscoverableRepo,
@@ -28,6 +65,7 @@ import type {
   RunWorkflowRequest,
   FeedbackRequest,
   ThemeUnlockStats,
+  WorkspaceUsage,
   PushSubscribeRequest,
   VapidPublicKeyResponse,
   PushSubscribeResponse,
@@ -44,23 +82,67 @@ import type {
   PendingGateAnalyticsRow,
   HookSummary,
   HookEvent,
+  ApiToken,
+  ApiTokenScope,
+  CreateApiTokenResponse,
+  CreateRunShareResponse,
+  SharedRun,
+  Notification,
+  DirtyWorktreeChoice,
 } from "./types";
-import { getApiBaseUrl } from "./transport";
+import { getApiBaseUrl, getStoredApiToken } from "./transport";
+
+/** A non-2xx API response, keeping the status and parsed body for callers that branch on them. */
+export class ApiRequestError extends Error {
+  constructor(
+    message: string,
+    readonly status: number,
+    readonly body: Record<string, unknown>,
+  ) {
+    super(message);
+    this.name = "ApiRequestError";
+  }
+}
 
 async function request<T>(path: string, options?: RequestInit): Promise<T> {
   const base = await getApiBaseUrl();
+  const token = getStoredApiToken();
   const res = await fetch(`${base}${path}`, {
-    headers: { "Content-Type": "application/json" },
     ...options,
+    headers: {
+      "Content-Type": "application/json",
+      ...(token ? { Authorization: `Bearer ${token}` } : {}),
+      ...options?.headers,
+    },
   });
   if (!res.ok) {
     const body = await res.json().catch(() => ({ error: res.statusText }));
-    throw new Error(body.error || `Request failed: ${res.status}`);
+    throw new ApiRequestError(
+      body.error || body.message || `Request failed: ${res.status}`,
+      res.status,
+      body,
+    );
   }
   if (res.status === 204) return undefined as T;
   return res.json();
 }
 
+function ticketTotalsPath(
+  path: string,
+  query: TicketTotalsQuery,
+  page?: TicketTotalsPageQuery,
+): string {
+  const params = new URLSearchParams();
+  if (query.ticketIds) params.set("ticket_ids", query.ticketIds.join(","));
+  if (page) {
+    // Either parameter makes the server answer with a TicketTotalsPage.
+    params.set("offset", String(page.offset ?? 0));
+    if (page.limit !== undefined) params.set("limit", String(page.limit));
+  }
+  const qs = params.toString();
+  return qs ? `${path}?${qs}` : path;
+}
+
 export const api = {
   // Repos
   listRepos: () => request<Repo[]>("/repos"),
@@ -73,6 +155,13 @@ export const api = {
       method: "PATCH",
       body: JSON.stringify({ model }),
     }),
+  getRepoVisibility: (id: string) =>
+    request<RepoVisibilityState>(`/repos/${id}/visibility`),
+  setRepoVisibility: (id: string, isPrivate: boolean) =>
+    request<RepoVisibilityState>(`/repos/${id}/visibility`, {
+      method: "PUT",
+      body: JSON.stringify({ private: isPrivate }),
+    }),
 
   // PRs
   listPrs: (repoId: string) => request<GithubPr[]>(`/repos/${repoId}/prs`),
@@ -89,12 +178,79 @@ export const api = {
         : `/repos/${repoId}/worktrees`,
     ),
   createWorktree: (repoId: string, data: CreateWorktreeRequest) =>
-    request<Worktree>(`/repos/${repoId}/worktrees`, {
+    request<CreateWorktreeAccepted>(`/repos/${repoId}/worktrees`, {
+      method: "POST",
+      body: JSON.stringify(data),
+    }),
+  createWorktreeFromUrl: (data: CreateWorktreeFromUrlRequest) =>
+    request<CreateWorktreeAccepted>(`/worktrees/from-url`, {
       method: "POST",
       body: JSON.stringify(data),
     }),
-  deleteWorktree: (id: string) =>
-    request<void>(`/worktrees/${id}`, { method: "DELETE" }),
+  listWorktreeStacks: (repoId: string) =>
+    request<StackInfo[]>(`/repos/${repoId}/worktrees/stacks`),
+  restackWorktree: (id: string, keepConflicts = false) =>
+    request<Worktree>(
+      `/worktrees/${id}/restack${keepConflicts ? "?keep_conflicts=true" : ""}`,
+      { method: "POST" },
+    ),
+  restoreWorktree: (id: string) =>
+    request<Worktree>(`/worktrees/${id}/restore`, { method: "POST" }),
+  acknowledgeTicketChange: (id: string) =>
+    request<Worktree>(`/worktrees/${id}/ticket/ack`, { method: "POST" }),
+  getWorktreeConflicts: (id: string) =>
+    request<WorktreeConflicts>(`/worktrees/${id}/conflicts`),
+  startConflictAgent: (id: string) =>
+    request<ConflictResolution>(`/worktrees/${id}/conflicts/agent`, { method: "POST" }),
+  verifyConflicts: (id: string) =>
+    request<ConflictResolution>(`/worktrees/${id}/conflicts/verify`, { method: "POST" }),
+  continueConflicts: (id: string) =>
+    request<ConflictResolution>(`/worktrees/${id}/conflicts/continue`, { method: "POST" }),
+  abortConflicts: (id: string) =>
+    request<ConflictResolution>(`/worktrees/${id}/conflicts/abort`, { method: "POST" }),
+  getWorktreeDiff: (id: string) =>
+    request<WorktreeDiff>(`/worktrees/${id}/diff`),
+  getWorktreeFiles: (id: string, path = "") =>
+    request<WorktreeListing>(
+      `/worktrees/${id}/files?path=${encodeURIComponent(path)}`,
+    ),
+  getWorktreeTodos: (id: string) =>
+    request<BranchTodo[]>(`/worktrees/${id}/todos`),
+  scanWorktreeTodos: (id: string) =>
+    request<BranchTodo[]>(`/worktrees/${id}/todos/scan`, { method: "POST" }),
+  /** Commit the worktree's changes; without a message one is built from the agent's result. */
+  commitWorktree: (id: string, message?: string, all = true) =>
+    request<CommitSummary>(`/worktrees/${id}/commit`, {
+      method: "POST",
+      body: JSON.stringify({ message: message || undefined, all }),
+    }),
+  pushWorktree: (id: string, skipChecks = false) =>
+    request<{ message: string }>(
+      `/worktrees/${id}/push${skipChecks ? "?skip_checks=true" : ""}`,
+      { method: "POST" },
+    ),
+  createWorktreePr: (id: string, draft = false, fromAgent = false) =>
+    request<{ url: string }>(`/worktrees/${id}/pr`, {
+      method: "POST",
+      body: JSON.stringify({ draft, from_agent: fromAgent }),
+    }),
+  getWorktreeJob: (jobId: string) =>
+    request<WorktreeJob>(`/worktree-jobs/${jobId}`),
+  deleteWorktree: (id: string, reason?: AbandonReason) =>
+    request<void>(
+      reason ? `/worktrees/${id}?reason=${reason}` : `/worktrees/${id}`,
+      { method: "DELETE" },
+    ),
+  resolveBaseBranch: (repoId: string, ticketId?: string) =>
+    request<ResolvedBase>(
+      ticketId
+        ? `/repos/${repoId}/worktrees/base-branch?ticket_id=${encodeURIComponent(ticketId)}`
+        : `/repos/${repoId}/worktrees/base-branch`,
+    ),
+  worktreeOutcomes: (repoId?: string) =>
+    request<WorktreeOutcomes>(
+      repoId ? `/repos/${repoId}/worktrees/outcomes` : "/worktrees/outcomes",
+    ),
   linkTicket: (id: string, ticketId: string) =>
     request<Worktree>(`/worktrees/${id}/ticket`, {
       method: "PUT",
@@ -108,28 +264,74 @@ export const api = {
 
   // Tickets
   ticketLabels: () => request<TicketLabel[]>("/ticket-labels"),
-  listAllTickets: (showClosed = false) =>
-    request<TicketListResponse>(showClosed ? "/tickets?show_closed=true" : "/tickets"),
-  listTickets: (repoId: string, showClosed = false) =>
-    request<TicketListResponse>(
+  listAllTickets: (showClosed = false, currentSprint = false, mine = false) => {
+    const params = new URLSearchParams();
+    if (showClosed) params.set("show_closed", "true");
+    if (currentSprint) params.set("current_sprint", "true");
+    if (mine) {
+      params.set("assigned", "me");
+      params.set("mentions", "me");
+    }
+    const qs = params.toString();
+    return request<TicketListResponse>(qs ? `/tickets?${qs}` : "/tickets");
+  },
+  listTickets: (repoId: string, showClosed = false, q?: string) => {
+    const params = new URLSearchParams();
+    if (showClosed) params.set("show_closed", "true");
+    if (q?.trim()) params.set("q", q.trim());
+    const qs = params.toString();
+    return request<TicketListResponse>(
+      qs ? `/repos/${repoId}/tickets?${qs}` : `/repos/${repoId}/tickets`,
+    );
+  },
+  getTicketTree: (repoId: string, showClosed = false) =>
+    request<TicketTreeResponse>(
       showClosed
-        ? `/repos/${repoId}/tickets?show_closed=true`
-        : `/repos/${repoId}/tickets`,
+        ? `/repos/${repoId}/tickets/tree?show_closed=true`
+        : `/repos/${repoId}/tickets/tree`,
     ),
   syncTickets: (repoId: string) =>
     request<SyncResult>(`/repos/${repoId}/tickets/sync`, { method: "POST" }),
   getTicketDetail: (ticketId: string) =>
     request<TicketDetail>(`/tickets/${ticketId}`),
+  commentOnTicket: (ticketId: string, body: string, close = false) =>
+    request<Ticket>(`/tickets/${ticketId}/comment`, {
+      method: "POST",
+      body: JSON.stringify({ body, close }),
+    }),
+  groomingProposals: (repoId: string) =>
+    request<GroomingProposal[]>(`/grooming?repo_id=${repoId}`),
+  runGrooming: (repoId: string) =>
+    request<GroomingPass>(`/repos/${repoId}/grooming`, { method: "POST" }),
+  acceptGroomingProposal: (id: string) =>
+    request<GroomingProposal>(`/grooming/${id}/accept`, { method: "POST" }),
+  dismissGroomingProposal: (id: string) =>
+    request<GroomingProposal>(`/grooming/${id}/dismiss`, { method: "POST" }),
 
   // Agent stats (aggregates)
   latestRunsByWorktree: () =>
     request<Record<string, AgentRun>>("/agent/latest-runs"),
-  ticketAgentTotals: () =>
-    request<Record<string, TicketAgentTotals>>("/agent/ticket-totals"),
+  ticketAgentTotals: (query: TicketTotalsQuery = {}) =>
+    request<Record<string, TicketAgentTotals>>(
+      ticketTotalsPath("/agent/ticket-totals", query),
+    ),
+  ticketAgentTotalsPage: (query: TicketTotalsPageQuery) =>
+    request<TicketTotalsPage>(
+      ticketTotalsPath("/agent/ticket-totals", query, query),
+    ),
   latestRunsByWorktreeForRepo: (repoId: string) =>
     request<Record<string, AgentRun>>(`/repos/${repoId}/agent/latest-runs`),
-  ticketAgentTotalsForRepo: (repoId: string) =>
-    request<Record<string, TicketAgentTotals>>(`/repos/${repoId}/agent/ticket-totals`),
+  ticketAgentTotalsForRepo: (repoId: string, query: TicketTotalsQuery = {}) =>
+    request<Record<string, TicketAgentTotals>>(
+      ticketTotalsPath(`/repos/${repoId}/agent/ticket-totals`, query),
+    ),
+  ticketAgentTotalsPageForRepo: (
+    repoId: string,
+    query: TicketTotalsPageQuery,
+  ) =>
+    request<TicketTotalsPage>(
+      ticketTotalsPath(`/repos/${repoId}/agent/ticket-totals`, query, query),
+    ),
 
   // Repo-scoped agents (read-only)
   startRepoAgent: (repoId: string, prompt: string, newSession?: boolean) =>
@@ -156,6 +358,9 @@ export const api = {
     prompt: string,
     resumeSessionId?: string,
     parentRunId?: string,
+    dirtyChoice?: DirtyWorktreeChoice,
+    chain?: AgentChain,
+    overrideBudget?: boolean,
   ) =>
     request<AgentRun>(`/worktrees/${worktreeId}/agent/start`, {
       method: "POST",
@@ -163,12 +368,63 @@ export const api = {
         prompt,
         resume_session_id: resumeSessionId ?? null,
         parent_run_id: parentRunId ?? null,
+        dirty_choice: dirtyChoice ?? null,
+        chain: chain ?? null,
+        override_budget: overrideBudget ?? false,
       }),
     }),
+  listOverBudget: () => request<BudgetExceeded[]>("/agent/budgets"),
+  enqueueAgent: (
+    worktreeId: string,
+    prompt: string,
+    priority: AgentPriority,
+    chain?: AgentChain,
+  ) =>
+    request<QueuedAgentRun>(`/worktrees/${worktreeId}/agent/queue`, {
+      method: "POST",
+      body: JSON.stringify({ prompt, priority, chain: chain ?? null }),
+    }),
+  listAgentQueue: () => request<QueuedAgentRun[]>("/agent-queue"),
+  setQueuePriority: (id: string, priority: AgentPriority) =>
+    request<QueuedAgentRun>(`/agent-queue/${id}`, {
+      method: "PATCH",
+      body: JSON.stringify({ priority }),
+    }),
+  removeFromQueue: (id: string) =>
+    request<void>(`/agent-queue/${id}`, { method: "DELETE" }),
+  listToday: () => request<TodayItem[]>("/today"),
+  getTools: (refresh = false) =>
+    request<ToolReport>(refresh ? "/tools?refresh=true" : "/tools"),
+  listAgentInbox: () => request<InboxItem[]>("/agent/inbox"),
+  listAgentAlerts: () => request<AgentRunAlert[]>("/agent/alerts"),
+  listRunAlerts: (runId: string) => request<AgentRunAlert[]>(`/agent/runs/${runId}/alerts`),
+  markRunReviewed: (runId: string) =>
+    request<void>(`/agent/inbox/${runId}/reviewed`, { method: "POST" }),
+  markAllRunsReviewed: () =>
+    request<{ cleared: number }>("/agent/inbox/reviewed", { method: "POST" }),
+  getAgentRunChain: (runId: string) =>
+    request<ChainLink[]>(`/agent/runs/${runId}/chain`),
+  shareAgentRun: (runId: string, ttlHours?: number) =>
+    request<CreateRunShareResponse>(`/agent/runs/${runId}/share`, {
+      method: "POST",
+      body: JSON.stringify({ ttl_hours: ttlHours ?? null }),
+    }),
+  getSharedRun: (token: string) => request<SharedRun>(`/shared/runs/${token}`),
+  restartAgentRun: (worktreeId: string, runId: string) =>
+    request<AgentRun>(`/worktrees/${worktreeId}/agent/runs/${runId}/restart`, {
+      method: "POST",
+    }),
+  listWatches: () => request<Watch[]>("/watches"),
+  watch: (targetType: WatchTarget, targetId: string) =>
+    request<Watch>(`/watches/${targetType}/${targetId}`, { method: "PUT" }),
+  unwatch: (targetType: WatchTarget, targetId: string) =>
+    request<void>(`/watches/${targetType}/${targetId}`, { method: "DELETE" }),
   stopAgent: (worktreeId: string) =>
     request<AgentRun>(`/worktrees/${worktreeId}/agent/stop`, {
       method: "POST",
     }),
+  stopAllAgents: () =>
+    request<StopAllSummary>("/agent/stop-all", { method: "POST" }),
   getAgentEvents: (worktreeId: string) =>
     request<AgentEvent[]>(`/worktrees/${worktreeId}/agent/events`),
   getRunEvents: (worktreeId: string, runId: string) =>
@@ -187,6 +443,13 @@ export const api = {
     ),
   getCreatedIssues: (worktreeId: string) =>
     request<AgentCreatedIssue[]>(`/worktrees/${worktreeId}/agent/created-issues`),
+  latestDiffReview: (worktreeId: string) =>
+    request<DiffReview | null>(`/worktrees/${worktreeId}/agent/review`),
+  startDiffReview: (worktreeId: string) =>
+    request<DiffReview>(`/worktrees/${worktreeId}/agent/review`, { method: "POST" }),
+  detectRepoToolchain: (repoId: string) =>
+    request<Repo>(`/repos/${repoId}/toolchain`, { method: "POST" }),
+
   updateRepoSettings: (repoId: string, settings: { allow_agent_issue_creation?: boolean }) =>
     request<Repo>(`/repos/${repoId}/settings`, {
       method: "PATCH",
@@ -327,6 +590,14 @@ export const api = {
   // Stats
   getThemeUnlockStats: () =>
     request<ThemeUnlockStats>("/stats/theme-unlocks"),
+  getWorkspaceQuota: () =>
+    request<WorkspaceUsage[]>("/stats/workspace-quota"),
+
+  // Reports
+  getCostReport: (since: string, groupBy: CostGroupBy) =>
+    request<CostReportRow[]>(
+      `/reports/costs?since=${encodeURIComponent(since)}&group_by=${groupBy}`,
+    ),
 
   // Push Notifications
   getPushVapidKey: () =>
@@ -355,6 +626,37 @@ export const api = {
       method: "PATCH",
       body: JSON.stringify({ on }),
     }),
+
+  // API tokens
+  listApiTokens: () => request<ApiToken[]>("/tokens"),
+  createApiToken: (name: string, scope: ApiTokenScope) =>
+    request<CreateApiTokenResponse>("/tokens", {
+      method: "POST",
+      body: JSON.stringify({ name, scope }),
+    }),
+  revokeApiToken: (id: string) =>
+    request<void>(`/tokens/${id}`, { method: "DELETE" }),
+
+  // Notification center
+  listNotifications: (limit = 50) =>
+    request<Notification[]>(`/notifications?limit=${limit}`),
+  getUnreadNotificationCount: () =>
+    request<{ count: number }>("/notifications/unread-count"),
+  markNotificationRead: (id: string) =>
+    request<void>(`/notifications/${id}/read`, { method: "POST" }),
+  markAllNotificationsRead: () =>
+    request<{ updated: number }>("/notifications/read-all", { method: "POST" }),
+  clearNotifications: () =>
+    request<{ cleared: number }>("/notifications", { method: "DELETE" }),
+
+  // Command palette
+  listCommands: () => request<CommandInfo[]>("/commands"),
+  /** Perform a command from `listCommands` with its path already filled in. */
+  runCommand: (method: string, path: string, body: Record<string, unknown>) =>
+    request<unknown>(path.replace(/^\/api/, ""), {
+      method,
+      body: method === "GET" || method === "DELETE" ? undefined : JSON.stringify(body),
+    }),
 };
 
 // Export as apiClient for consistency with hook usage
@@ -85,3 +85,27 @@ export async function getApiOrigin(): Promise<string> {
 export async function getApiBaseUrl(): Promise<string> {
   return `${await getApiOrigin()}/api`;
 }
+
+const API_TOKEN_STORAGE_KEY = "conductor-api-token";
+
+/**
+ * API token sent with every request, set from the Settings page.
+ * Needed only when the server runs with `[web.auth] required = true`.
+ */
+export function getStoredApiToken(): string | null {
+  try {
+    return localStorage.getItem(API_TOKEN_STORAGE_KEY);
+  } catch {
+    // localStorage unavailable
+    return null;
+  }
+}
+
+export function setStoredApiToken(token: string | null): void {
+  try {
+    if (token) localStorage.setItem(API_TOKEN_STORAGE_KEY, token);
+    else localStorage.removeItem(API_TOKEN_STORAGE_KEY);
+  } catch {
+    // localStorage unavailable
+  }
+}
@@ -8,8 +8,33 @@ export interface Repo {
   created_at: string;
   model: string | null;
   allow_agent_issue_creation: boolean;
+  toolchain: Toolchain;
 }
 
+export interface RepoVisibilityState {
+  /** Whether only the owning API token sees the repo. */
+  private: boolean;
+}
+
+/** Mirrors `Toolchain` in conductor-core/src/toolchain.rs. */
+export interface Toolchain {
+  language: string | null;
+  package_manager: string | null;
+  test_framework: string | null;
+}
+
+/** Mirrors `WorktreeStatus` in conductor-core/src/worktree/types.rs. */
+export type WorktreeStatus =
+  | "active"
+  | "needs_review"
+  | "parked"
+  | "merged"
+  | "abandoned"
+  | "promoted";
+
+/** Mirrors `AbandonReason` in conductor-core/src/worktree/types.rs. */
+export type AbandonReason = "superseded" | "wontfix" | "blocked" | "experiment";
+
 export interface Worktree {
   id: string;
   repo_id: string;
@@ -17,10 +42,56 @@ export interface Worktree {
   branch: string;
   path: string;
   ticket_id: string | null;
-  status: string;
+  status: WorktreeStatus;
   created_at: string;
   completed_at: string | null;
   model: string | null;
+  /** Branch this worktree was created from; null means the repo default. */
+  base_branch: string | null;
+  /** Why the work was dropped; only set on abandoned worktrees. */
+  abandon_reason: AbandonReason | null;
+  /** When the health check found this live worktree's directory gone. */
+  missing_since?: string | null;
+  /** Upstream changes to the linked ticket not yet acknowledged. */
+  ticket_change?: string | null;
+}
+
+export interface AbandonReasonCount {
+  reason: AbandonReason;
+  count: number;
+}
+
+/** Mirrors `BaseBranchSource` in conductor-core/src/worktree/types.rs. */
+export type BaseBranchSource =
+  | { kind: "parent_ticket" }
+  | { kind: "label_rule"; label: string }
+  | { kind: "repo_default" };
+
+/** Base branch a new worktree starts from, and why. */
+export interface ResolvedBase {
+  branch: string;
+  source: BaseBranchSource;
+}
+
+/** How finished worktrees ended. */
+export interface WorktreeOutcomes {
+  merged: number;
+  promoted: number;
+  abandoned: number;
+  by_reason: AbandonReasonCount[];
+  unspecified: number;
+}
+
+/** Mirrors `StackState` in conductor-core/src/worktree/stack.rs. */
+export type StackState = "up_to_date" | "parent_updated" | "parent_merged";
+
+/** A worktree stacked on another worktree's branch. */
+export interface StackInfo {
+  worktree_id: string;
+  parent_id: string;
+  parent_slug: string;
+  parent_branch: string;
+  state: StackState;
 }
 
 export interface WorktreeWithStatus extends Worktree {
@@ -28,6 +99,26 @@ export interface WorktreeWithStatus extends Worktree {
   ticket_title: string | null;
   ticket_number: string | null;
   ticket_url: string | null;
+  /** Only on `GET /repos/{id}/worktrees`, for live worktrees. */
+  git_status?: WorktreeGitStatus | null;
+}
+
+export interface AheadBehind {
+  ahead: number;
+  behind: number;
+}
+
+export interface WorktreeGitStatus {
+  /** Modified, staged and untracked paths. */
+  uncommitted: number;
+  /** Versus `origin/<branch>`; null when the branch was never pushed. */
+  upstream: AheadBehind | null;
+  last_commit: {
+    sha: string;
+    summary: string;
+    author: string;
+    committed_at: string;
+  } | null;
 }
 
 export interface Ticket {
@@ -44,6 +135,8 @@ export interface Ticket {
   url: string;
   synced_at: string;
   raw_json: string;
+  sprint: string | null;
+  sprint_current: boolean;
 }
 
 export interface TicketLabel {
@@ -62,6 +155,51 @@ export interface TicketAgentTotals {
   total_output_tokens: number;
 }
 
+export type BudgetScope = "worktree" | "session";
+
+/** A worktree or Claude session that has spent its `[budget]`. */
+export interface BudgetExceeded {
+  scope: BudgetScope;
+  /** Worktree ID or session ID */
+  target_id: string;
+  /** Worktree slug, or the session ID again */
+  target: string;
+  spent_usd: number;
+  limit_usd: number;
+}
+
+export type CostGroupBy = "repo" | "ticket" | "worktree" | "day";
+
+/** Agent cost, turns and time rolled up for one group of a cost report. */
+export interface CostReportRow {
+  /** Repo, ticket or worktree ID, or the YYYY-MM-DD day; empty for runs outside any */
+  key: string;
+  label: string;
+  runs: number;
+  cost_usd: number;
+  turns: number;
+  duration_ms: number;
+  input_tokens: number;
+  output_tokens: number;
+}
+
+/** Optional narrowing for the ticket totals endpoints. */
+export interface TicketTotalsQuery {
+  ticketIds?: string[];
+}
+
+/** Pagination for the ticket totals endpoints, ordered by ticket ID. */
+export interface TicketTotalsPageQuery extends TicketTotalsQuery {
+  limit?: number;
+  offset?: number;
+}
+
+/** One page of ticket totals; `total` counts every page. */
+export interface TicketTotalsPage {
+  totals: TicketAgentTotals[];
+  total: number;
+}
+
 export interface CreateRepoRequest {
   remote_url: string;
   slug?: string;
@@ -87,11 +225,73 @@ export interface CreateWorktreeRequest {
   ticket_id?: string;
 }
 
+export interface CreateWorktreeFromUrlRequest {
+  /** GitHub issue/PR, GitLab issue or Jira issue URL. */
+  url: string;
+  name?: string;
+  force?: boolean;
+}
+
+export type WorktreeCreateStage =
+  | "cloning"
+  | "fetching"
+  | "branching"
+  | "checking_out"
+  | "installing_deps"
+  | "finalizing";
+
+/** 202 body from `POST /repos/{id}/worktrees`; follow the job for the result. */
+export interface CreateWorktreeAccepted {
+  job_id: string;
+  repo_id: string;
+}
+
+export interface WorktreeJob {
+  id: string;
+  repo_id: string;
+  name: string;
+  status: "running" | "succeeded" | "failed";
+  stage: WorktreeCreateStage | null;
+  worktree: Worktree | null;
+  warnings?: string[];
+  commits_behind: number;
+  error: string | null;
+  started_at: string;
+  finished_at: string | null;
+}
+
 export interface SyncResult {
   synced: number;
   closed: number;
 }
 
+export type ProposalStatus = "pending" | "accepted" | "dismissed";
+
+export interface GroomingProposal {
+  id: string;
+  ticket_id: string;
+  repo_id: string;
+  ticket_source_id: string;
+  ticket_title: string;
+  labels: string[];
+  estimate: string | null;
+  duplicates: string[];
+  workflow: string | null;
+  rationale: string | null;
+  model: string | null;
+  cost_usd: number;
+  status: ProposalStatus;
+  created_at: string;
+  decided_at: string | null;
+}
+
+export interface GroomingPass {
+  groomed: number;
+  failed: number;
+  spent_usd: number;
+  budget_exhausted: boolean;
+}
+
 export interface PlanStep {
   id?: string;
   description: string;
@@ -102,6 +302,111 @@ export interface PlanStep {
   completed_at?: string | null;
 }
 
+export type AgentPriority = "urgent" | "normal" | "background";
+
+/** What to do with uncommitted changes when launching an agent on a dirty worktree. */
+export type DirtyWorktreeChoice = "stash" | "commit_wip" | "proceed";
+
+/** What to do after a chained run ends with a given outcome. */
+export type ChainStep =
+  | { action: "run"; prompt: string; then?: AgentChain }
+  | { action: "notify" };
+
+/** Follow-ups of one run, by outcome. A cancelled run ends its chain. */
+export interface AgentChain {
+  on_success?: ChainStep;
+  on_failure?: ChainStep;
+}
+
+export type ChainTrigger = "success" | "failure";
+
+export type ChainOutcome = "queued" | "notified" | "no_follow_up" | "cancelled" | "depth_limit";
+
+/** One run in a chain, with the follow-ups still declared below it. */
+export interface ChainLink {
+  id: string;
+  chain_id: string;
+  run_id: string | null;
+  queue_id: string | null;
+  previous_run_id: string | null;
+  depth: number;
+  trigger: ChainTrigger | null;
+  prompt: string;
+  run_status: AgentRun["status"] | null;
+  on_success: ChainStep | null;
+  on_failure: ChainStep | null;
+  evaluated_at: string | null;
+  outcome: ChainOutcome | null;
+  created_at: string;
+}
+
+/** One run cancelled by `POST /agent/stop-all`. */
+export interface StoppedRun {
+  run_id: string;
+  worktree_id: string | null;
+  repo_id: string | null;
+  repo_slug: string | null;
+  worktree_slug: string | null;
+  prompt: string;
+  log_file: string | null;
+  turns: number;
+  cost_usd: number | null;
+}
+
+export interface StopAllSummary {
+  stopped: StoppedRun[];
+  /** Queued runs left alone; they still start when a slot frees up. */
+  queued: number;
+}
+
+export interface QueuedAgentRun {
+  id: string;
+  worktree_id: string;
+  repo_slug: string;
+  worktree_slug: string;
+  prompt: string;
+  priority: AgentPriority;
+  created_at: string;
+}
+
+export interface InboxItem {
+  run_id: string;
+  worktree_id: string;
+  repo_id: string;
+  repo_slug: string;
+  worktree_slug: string;
+  branch: string;
+  status: "completed" | "failed";
+  prompt: string;
+  result_text: string | null;
+  cost_usd: number | null;
+  num_turns: number | null;
+  started_at: string;
+  ended_at: string | null;
+}
+
+export type AgentAlertKind = "run_cost" | "daily_spend" | "turns_without_commits";
+
+export interface AgentRunAlert {
+  run_id: string;
+  kind: AgentAlertKind;
+  detail: string;
+  created_at: string;
+  worktree_id: string | null;
+  repo_id: string | null;
+  repo_slug: string | null;
+  worktree_slug: string | null;
+  branch: string | null;
+}
+
+export type WatchTarget = "ticket" | "worktree";
+
+export interface Watch {
+  target_type: WatchTarget;
+  target_id: string;
+  created_at: string;
+}
+
 export interface AgentRun {
   id: string;
   worktree_id: string | null;
@@ -124,6 +429,21 @@ export interface AgentRun {
   model: string | null;
   plan: PlanStep[] | null;
   parent_run_id: string | null;
+  environment?: RunEnvironment | null;
+}
+
+/** Where an agent run started; captured once at launch. */
+export interface RunEnvironment {
+  conductor_version: string;
+  backend: string;
+  agent_version: string | null;
+  model: string | null;
+  git_commit: string | null;
+  git_branch: string | null;
+  os: string;
+  arch: string;
+  /** Container the agent ran in, e.g. `docker agent:1`; absent on the host. */
+  sandbox?: string | null;
 }
 
 export interface RunTreeTotals {
@@ -146,6 +466,23 @@ export interface AgentEvent {
   metadata: string | null;
 }
 
+/** Data of an `agent_event` message on `/api/worktrees/{id}/agent/stream`. */
+export interface AgentStreamEvent {
+  run_id: string;
+  /** Log byte offset just past this event; pass as `from_offset` to resume. */
+  offset: number;
+  kind: AgentEvent["kind"];
+  summary: string;
+  metadata: string | null;
+}
+
+/** Data of `heartbeat` and `done` messages on the agent stream. */
+export interface AgentStreamStatus {
+  run_id: string;
+  offset: number;
+  status: AgentRun["status"] | null;
+}
+
 export interface AgentPromptInfo {
   prompt: string;
   resume_session_id: string | null;
@@ -162,6 +499,102 @@ export interface AgentCreatedIssue {
   created_at: string;
 }
 
+/** An agent review of a worktree's diff; `review` is set once the run completes. */
+export interface DiffReview {
+  id: string;
+  worktree_id: string;
+  run_id: string;
+  base_ref: string;
+  files_changed: number;
+  truncated: boolean;
+  status: AgentRun["status"];
+  review: string | null;
+  created_at: string;
+  ended_at: string | null;
+}
+
+/** A file left unmerged by a stopped rebase or merge. */
+export interface ConflictedFile {
+  path: string;
+  hunks: string[];
+  truncated: boolean;
+}
+
+/** A TODO or FIXME comment added on a worktree's branch. */
+export interface BranchTodo {
+  file: string;
+  line: number;
+  marker: string;
+  text: string;
+}
+
+/** One file's part of a worktree diff. */
+export interface FileDiff {
+  path: string;
+  added: number;
+  removed: number;
+  text: string;
+}
+
+export interface CommitSummary {
+  sha: string;
+  subject: string;
+  files: string[];
+}
+
+export interface WorktreeDiff {
+  diff: string;
+  files: FileDiff[];
+}
+
+export interface WorktreeDirEntry {
+  name: string;
+  path: string;
+  is_dir: boolean;
+  size: number | null;
+}
+
+/** A directory listing or a file's content from `GET /worktrees/{id}/files`. */
+export type WorktreeListing =
+  | { kind: "dir"; path: string; entries: WorktreeDirEntry[] }
+  | {
+      kind: "file";
+      path: string;
+      size: number;
+      content: string | null;
+      binary: boolean;
+      truncated: boolean;
+    };
+
+export interface ConflictState {
+  operation: "rebase" | "merge";
+  onto: string | null;
+  files: ConflictedFile[];
+}
+
+/** Tracking of a conflict resolution; it may only be continued once `verified`. */
+export interface ConflictResolution {
+  id: string;
+  worktree_id: string;
+  run_id: string | null;
+  run_status: AgentRun["status"] | null;
+  operation: ConflictState["operation"];
+  onto: string | null;
+  files: string[];
+  status: "resolving" | "verified" | "check_failed" | "continued" | "aborted";
+  check_command: string | null;
+  check_output: string | null;
+  verified_tree: string | null;
+  created_at: string;
+  checked_at: string | null;
+  ended_at: string | null;
+}
+
+export interface WorktreeConflicts {
+  state: ConflictState | null;
+  resolution: ConflictResolution | null;
+}
+
 export interface TicketDependencies {
   blocked_by: Ticket[];
   blocks: Ticket[];
@@ -174,10 +607,47 @@ export interface TicketListResponse {
   dependencies: Record<string, TicketDependencies>;
 }
 
+export type RoadmapStage = "not_started" | "in_progress" | "in_review" | "done";
+
+export interface EpicRollup {
+  total: number;
+  done: number;
+  in_review: number;
+  in_progress: number;
+  agent_runs: number;
+  agent_cost: number;
+}
+
+export interface TicketNode {
+  ticket: Ticket;
+  parent_id: string | null;
+  stage: RoadmapStage;
+  rollup: EpicRollup | null;
+  children: TicketNode[];
+}
+
+export interface TicketTreeResponse {
+  tickets: TicketNode[];
+}
+
+export interface TicketAttachment {
+  id: string;
+  ticket_id: string;
+  url: string;
+  filename: string;
+  mime_type: string | null;
+  size_bytes: number | null;
+  local_path: string | null;
+  downloaded_at: string | null;
+  download_error: string | null;
+  created_at: string;
+}
+
 export interface TicketDetail {
   agent_totals: TicketAgentTotals | null;
   worktrees: Worktree[];
   dependencies: TicketDependencies;
+  attachments: TicketAttachment[];
 }
 
 export interface IssueSource {
@@ -512,6 +982,26 @@ export interface FeedbackRequest {
   timeout_secs?: number;
 }
 
+export type QuotaLevel = "ok" | "warning" | "exceeded";
+
+export interface PurgeCandidate {
+  path: string;
+  slug: string | null;
+  reason: string;
+  bytes: number;
+}
+
+export interface WorkspaceUsage {
+  repo_id: string;
+  repo_slug: string;
+  workspace_dir: string;
+  used_bytes: number;
+  limit_bytes: number | null;
+  warn_bytes: number | null;
+  level: QuotaLevel;
+  purge_candidates: PurgeCandidate[];
+}
+
 export interface ThemeUnlockStats {
   repos_registered: number;
   prs_merged: number;
@@ -559,3 +1049,144 @@ export interface HookEvent {
   is_workflow: boolean;
 }
 
+
+export type ApiTokenScope = "read-only" | "agent-control" | "admin";
+
+export interface ApiToken {
+  id: string;
+  name: string;
+  scope: ApiTokenScope;
+  /** Leading characters of the secret, for telling tokens apart. */
+  prefix: string;
+  created_at: string;
+  last_used_at: string | null;
+  revoked_at: string | null;
+}
+
+export interface CreateApiTokenResponse {
+  token: ApiToken;
+  /** Shown once; only its hash is stored on the server. */
+  secret: string;
+}
+
+export interface RunShare {
+  id: string;
+  run_id: string;
+  created_at: string;
+  expires_at: string;
+  revoked_at: string | null;
+}
+
+export interface CreateRunShareResponse {
+  share: RunShare;
+  /** Shown once; only its hash is stored on the server. */
+  token: string;
+  /** Path of the read-only page, e.g. `/share/crs_…`. */
+  url: string;
+}
+
+export interface SharedRunEvent {
+  kind: string;
+  summary: string;
+  started_at: string;
+}
+
+/** An agent run as a share link shows it: no cost, logs or local paths. */
+export interface SharedRun {
+  prompt: string;
+  status: AgentRun["status"];
+  model: string | null;
+  branch: string | null;
+  started_at: string;
+  ended_at: string | null;
+  duration_ms: number | null;
+  num_turns: number | null;
+  plan: PlanStep[] | null;
+  events: SharedRunEvent[];
+  diffstat: string | null;
+  result_text: string | null;
+  expires_at: string;
+}
+
+export type NotificationSeverity = "info" | "success" | "warning" | "error";
+
+export type TodayItemKind =
+  | "awaiting_feedback"
+  | "failing_ci"
+  | "sla_breach"
+  | "needs_review"
+  | "notification"
+  | "running_agent";
+
+/** One entry of the prioritized "today" list on the home page. */
+export interface TodayItem {
+  kind: TodayItemKind;
+  title: string;
+  detail: string;
+  repo_id: string | null;
+  repo_slug: string | null;
+  worktree_id: string | null;
+  run_id: string | null;
+  ticket_id: string | null;
+  notification_id: string | null;
+  /** External page (PR, ticket), or an in-app path for notifications. */
+  url: string | null;
+  /** When the item started needing attention. */
+  since: string;
+}
+
+/** Result of probing one external tool (`conductor doctor tools`). */
+export interface ToolStatus {
+  name: string;
+  available: boolean;
+  /** First line of the version output. */
+  version: string | null;
+  purpose: string;
+  install: string;
+  /** Core workflows break without it. */
+  required: boolean;
+}
+
+export interface ToolReport {
+  probed_at: string;
+  tools: ToolStatus[];
+}
+
+export interface Notification {
+  id: string;
+  /** Machine-readable source, e.g. `agent_run_failed`. */
+  kind: string;
+  severity: NotificationSeverity;
+  title: string;
+  body: string;
+  /** In-app path to open when clicked. */
+  url: string | null;
+  created_at: string;
+  read: boolean;
+}
+
+export type CommandParamKind =
+  | "repo"
+  | "worktree"
+  | "workflow_run"
+  | "workflow"
+  | "text"
+  | "bool";
+
+export interface CommandParam {
+  name: string;
+  label: string;
+  kind: CommandParamKind;
+  required: boolean;
+  location: "path" | "body";
+}
+
+export interface CommandInfo {
+  id: string;
+  label: string;
+  description: string;
+  method: string;
+  /** Endpoint path with `{param}` placeholders, including the `/api` prefix. */
+  path: string;
+  params: CommandParam[];
+}
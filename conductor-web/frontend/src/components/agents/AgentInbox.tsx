@@ -0,0 +1,165 @@
+import { useCallback, useEffect, useState } from "react";
+import { Link } from "react-router";
+import { api } from "../../api/client";
+import type { AgentRunAlert, InboxItem } from "../../api/types";
+import { StationHeader } from "../shared/StationHeader";
+import { TimeAgo } from "../shared/TimeAgo";
+
+interface AgentInboxProps {
+  /** Bumped by the parent whenever agent or worktree state may have changed. */
+  refreshKey: number;
+  onError: (message: string | null) => void;
+}
+
+/** Finished agent runs that have not been reviewed yet, with quick actions. */
+export function AgentInbox({ refreshKey, onError }: AgentInboxProps) {
+  const [items, setItems] = useState<InboxItem[]>([]);
+  const [alerts, setAlerts] = useState<Record<string, AgentRunAlert[]>>({});
+  const [diffs, setDiffs] = useState<Record<string, string>>({});
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const load = useCallback(() => {
+    api.listAgentInbox().then(setItems).catch(() => {});
+    api
+      .listAgentAlerts()
+      .then((list) => {
+        const byRun: Record<string, AgentRunAlert[]> = {};
+        for (const alert of list) (byRun[alert.run_id] ??= []).push(alert);
+        setAlerts(byRun);
+      })
+      .catch(() => {});
+  }, []);
+
+  useEffect(load, [load, refreshKey]);
+
+  const run = async (key: string, action: () => Promise<unknown>, fallback: string) => {
+    setBusy(key);
+    try {
+      await action();
+      onError(null);
+    } catch (err: unknown) {
+      onError(err instanceof Error ? err.message : fallback);
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const toggleDiff = (item: InboxItem) => {
+    if (item.run_id in diffs) {
+      setDiffs((prev) => {
+        const next = { ...prev };
+        delete next[item.run_id];
+        return next;
+      });
+      return;
+    }
+    run(`${item.run_id}:diff`, async () => {
+      const { diff } = await api.getWorktreeDiff(item.worktree_id);
+      setDiffs((prev) => ({ ...prev, [item.run_id]: diff }));
+    }, "Failed to load diff");
+  };
+
+  const markReviewed = (item: InboxItem) =>
+    run(`${item.run_id}:reviewed`, async () => {
+      await api.markRunReviewed(item.run_id);
+      load();
+    }, "Failed to mark run reviewed");
+
+  const markAllReviewed = () =>
+    run("all", async () => {
+      await api.markAllRunsReviewed();
+      load();
+    }, "Failed to clear inbox");
+
+  const commit = (item: InboxItem) =>
+    run(`${item.run_id}:commit`, () => api.commitWorktree(item.worktree_id), "Commit failed");
+
+  const push = (item: InboxItem) =>
+    run(`${item.run_id}:push`, () => api.pushWorktree(item.worktree_id), "Push failed");
+
+  const createPr = (item: InboxItem) =>
+    run(`${item.run_id}:pr`, async () => {
+      const { url } = await api.createWorktreePr(item.worktree_id);
+      window.open(url, "_blank", "noopener");
+    }, "PR creation failed");
+
+  const rerun = (item: InboxItem) =>
+    run(`${item.run_id}:rerun`, async () => {
+      await api.restartAgentRun(item.worktree_id, item.run_id);
+      await api.markRunReviewed(item.run_id);
+      load();
+    }, "Failed to re-run agent");
+
+  if (items.length === 0) return null;
+
+  const button = "px-2 py-1 text-xs border border-gray-300 rounded hover:bg-gray-50 disabled:opacity-50";
+
+  return (
+    <section className="shrink-0">
+      <div className="flex items-center justify-between">
+        <StationHeader count={items.length}>Agent Inbox</StationHeader>
+        <button onClick={markAllReviewed} disabled={busy !== null} className={button}>
+          Mark all reviewed
+        </button>
+      </div>
+      <div className="space-y-2">
+        {items.map((item) => (
+          <div key={item.run_id} className="rounded-lg border border-gray-200 bg-white p-3 space-y-2">
+            <div className="flex items-start justify-between gap-2">
+              <div className="min-w-0">
+                <p className="text-xs text-gray-500">
+                  {item.repo_slug} ·{" "}
+                  <Link to={`/repos/${item.repo_id}/worktrees/${item.worktree_id}`} className="text-indigo-600 hover:underline">
+                    {item.branch}
+                  </Link>
+                  {" · "}
+                  <span className={item.status === "failed" ? "text-red-600" : "text-green-700"}>{item.status}</span>
+                  {item.ended_at && <> · <TimeAgo date={item.ended_at} short /></>}
+                </p>
+                <p className="text-sm text-gray-800 mt-0.5 truncate" title={item.prompt}>
+                  {item.prompt.split("\n")[0]}
+                </p>
+                {alerts[item.run_id]?.map((alert) => (
+                  <p key={alert.kind} className="text-xs text-amber-700 mt-0.5">
+                    ⚠ {alert.detail}
+                  </p>
+                ))}
+                {item.result_text && (
+                  <p className="text-xs text-gray-500 mt-0.5 line-clamp-2">{item.result_text}</p>
+                )}
+              </div>
+            </div>
+            <div className="flex flex-wrap gap-2 justify-end">
+              <button onClick={() => toggleDiff(item)} disabled={busy !== null} className={button}>
+                {item.run_id in diffs ? "Hide diff" : "Diff"}
+              </button>
+              <button
+                onClick={() => commit(item)}
+                disabled={busy !== null}
+                className={button}
+                title="Commit all changes, described by the agent's result"
+              >
+                Commit
+              </button>
+              <button onClick={() => push(item)} disabled={busy !== null} className={button}>Push</button>
+              <button onClick={() => createPr(item)} disabled={busy !== null} className={button}>PR</button>
+              <button onClick={() => rerun(item)} disabled={busy !== null} className={button}>Re-run</button>
+              <button
+                onClick={() => markReviewed(item)}
+                disabled={busy !== null}
+                className="px-2 py-1 text-xs bg-indigo-600 text-white rounded hover:bg-indigo-500 disabled:opacity-50"
+              >
+                Reviewed
+              </button>
+            </div>
+            {item.run_id in diffs && (
+              <pre className="max-h-80 overflow-auto rounded bg-gray-50 p-2 text-[11px] font-mono text-gray-700">
+                {diffs[item.run_id] || "No changes."}
+              </pre>
+            )}
+          </div>
+        ))}
+      </div>
+    </section>
+  );
+}
@@ -1,6 +1,6 @@
 import { useState, useEffect, useMemo, useId } from "react";
 import { BaseModal } from "../shared/BaseModal";
-import type { KnownModel } from "../../api/types";
+import type { AgentChain, AgentPriority, KnownModel } from "../../api/types";
 import { api } from "../../api/client";
 
 /** Client-side keyword heuristics matching conductor-core's suggest_model(). */
@@ -23,6 +23,23 @@ const FALLBACK_MODELS: KnownModel[] = [
   { id: "claude-haiku-4-5", alias: "haiku",  tier: 1, tier_label: "Fast",     description: "Commit messages, formatting, quick edits" },
 ];
 
+type FailureAction = "none" | "notify" | "run";
+
+/** The follow-ups entered in the modal, or `undefined` when none were. */
+function buildChain(
+  onSuccess: string,
+  failureAction: FailureAction,
+  onFailure: string,
+): AgentChain | undefined {
+  const chain: AgentChain = {};
+  if (onSuccess.trim()) chain.on_success = { action: "run", prompt: onSuccess.trim() };
+  if (failureAction === "notify") chain.on_failure = { action: "notify" };
+  if (failureAction === "run" && onFailure.trim()) {
+    chain.on_failure = { action: "run", prompt: onFailure.trim() };
+  }
+  return chain.on_success || chain.on_failure ? chain : undefined;
+}
+
 function suggestModel(prompt: string): string {
   const lower = prompt.toLowerCase();
   for (const kw of HAIKU_KEYWORDS) {
@@ -39,7 +56,9 @@ interface AgentPromptModalProps {
   title: string;
   initialPrompt: string;
   resumeSessionId: string | null;
-  onSubmit: (prompt: string, resumeSessionId?: string) => void;
+  onSubmit: (prompt: string, resumeSessionId?: string, chain?: AgentChain) => void;
+  /** When set, the modal also offers queueing the run at a chosen priority. */
+  onQueue?: (prompt: string, priority: AgentPriority, chain?: AgentChain) => void;
   onCancel: () => void;
 }
 
@@ -49,11 +68,17 @@ export function AgentPromptModal({
   initialPrompt,
   resumeSessionId,
   onSubmit,
+  onQueue,
   onCancel,
 }: AgentPromptModalProps) {
   const [prompt, setPrompt] = useState(initialPrompt);
+  const [priority, setPriority] = useState<AgentPriority>("normal");
   const [useResume, setUseResume] = useState(!!resumeSessionId);
   const [models, setModels] = useState<KnownModel[]>([]);
+  const [showFollowUps, setShowFollowUps] = useState(false);
+  const [onSuccessPrompt, setOnSuccessPrompt] = useState("");
+  const [failureAction, setFailureAction] = useState<FailureAction>("none");
+  const [onFailurePrompt, setOnFailurePrompt] = useState("");
   const titleId = useId();
 
   useEffect(() => {
@@ -78,7 +103,11 @@ export function AgentPromptModal({
   function handleSubmit() {
     const trimmed = prompt.trim();
     if (!trimmed) return;
-    onSubmit(trimmed, useResume && resumeSessionId ? resumeSessionId : undefined);
+    onSubmit(
+      trimmed,
+      useResume && resumeSessionId ? resumeSessionId : undefined,
+      buildChain(onSuccessPrompt, failureAction, onFailurePrompt),
+    );
   }
 
   return (
@@ -146,7 +175,81 @@ export function AgentPromptModal({
           </div>
         )}
 
+        <div className="mt-3">
+          <button
+            type="button"
+            onClick={() => setShowFollowUps((v) => !v)}
+            className="text-xs text-indigo-600 hover:text-indigo-800"
+          >
+            {showFollowUps ? "Hide follow-ups" : "Add follow-ups\u2026"}
+          </button>
+          {showFollowUps && (
+            <div className="mt-2 space-y-2 text-sm text-gray-700">
+              <label className="block">
+                <span className="text-xs text-gray-500">If it succeeds, run:</span>
+                <input
+                  value={onSuccessPrompt}
+                  onChange={(e) => setOnSuccessPrompt(e.target.value)}
+                  placeholder="Follow-up prompt (optional)"
+                  className="mt-1 w-full rounded-md border border-gray-300 px-2 py-1 text-sm font-mono"
+                />
+              </label>
+              <div>
+                <span className="text-xs text-gray-500">If it fails:</span>
+                <div className="mt-1 flex gap-2">
+                  <select
+                    value={failureAction}
+                    onChange={(e) => setFailureAction(e.target.value as FailureAction)}
+                    aria-label="On failure"
+                    className="rounded-md border border-gray-300 px-2 py-1 text-sm"
+                  >
+                    <option value="none">Do nothing</option>
+                    <option value="notify">Notify</option>
+                    <option value="run">Run</option>
+                  </select>
+                  {failureAction === "run" && (
+                    <input
+                      value={onFailurePrompt}
+                      onChange={(e) => setOnFailurePrompt(e.target.value)}
+                      placeholder="Follow-up prompt"
+                      className="flex-1 rounded-md border border-gray-300 px-2 py-1 text-sm font-mono"
+                    />
+                  )}
+                </div>
+              </div>
+            </div>
+          )}
+        </div>
+
         <div className="mt-4 flex justify-end gap-2">
+          {onQueue && (
+            <div className="mr-auto flex items-center gap-1.5">
+              <select
+                value={priority}
+                onChange={(e) => setPriority(e.target.value as AgentPriority)}
+                aria-label="Queue priority"
+                className="rounded-md border border-gray-300 px-2 py-1.5 text-sm text-gray-700"
+              >
+                <option value="urgent">Urgent</option>
+                <option value="normal">Normal</option>
+                <option value="background">Background</option>
+              </select>
+              <button
+                onClick={() =>
+                  prompt.trim() &&
+                  onQueue(
+                    prompt.trim(),
+                    priority,
+                    buildChain(onSuccessPrompt, failureAction, onFailurePrompt),
+                  )
+                }
+                disabled={!prompt.trim()}
+                className="px-3 py-1.5 text-sm rounded-md border border-indigo-300 text-indigo-700 hover:bg-indigo-50 active:scale-95 transition-transform disabled:opacity-50 disabled:cursor-not-allowed"
+              >
+                Queue
+              </button>
+            </div>
+          )}
           <button
             onClick={onCancel}
             className="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 active:scale-95 transition-transform"
@@ -0,0 +1,55 @@
+import type { AgentPriority, QueuedAgentRun } from "../../api/types";
+import { TimeAgo } from "../shared/TimeAgo";
+
+const PRIORITY_STYLES: Record<AgentPriority, string> = {
+  urgent: "bg-red-100 text-red-700",
+  normal: "bg-gray-100 text-gray-700",
+  background: "bg-gray-50 text-gray-400",
+};
+
+interface AgentQueueListProps {
+  items: QueuedAgentRun[];
+  onPriorityChange: (id: string, priority: AgentPriority) => void;
+  onRemove: (id: string) => void;
+}
+
+/** Queued agent runs for a worktree, in dispatch order. */
+export function AgentQueueList({ items, onPriorityChange, onRemove }: AgentQueueListProps) {
+  if (items.length === 0) return null;
+  return (
+    <div className="rounded-lg border border-gray-200 bg-white p-4">
+      <h4 className="text-sm font-semibold uppercase tracking-wider text-gray-400">
+        Queued ({items.length})
+      </h4>
+      <ul className="mt-3 space-y-2">
+        {items.map((q) => (
+          <li key={q.id} className="flex items-center gap-2 text-sm">
+            <select
+              value={q.priority}
+              onChange={(e) => onPriorityChange(q.id, e.target.value as AgentPriority)}
+              aria-label="Priority"
+              className={`rounded px-1.5 py-0.5 text-xs font-medium border-0 ${PRIORITY_STYLES[q.priority]}`}
+            >
+              <option value="urgent">urgent</option>
+              <option value="normal">normal</option>
+              <option value="background">background</option>
+            </select>
+            <span className="flex-1 truncate text-gray-700" title={q.prompt}>
+              {q.prompt.split("\n")[0]}
+            </span>
+            <span className="text-xs text-gray-400">
+              <TimeAgo date={q.created_at} short />
+            </span>
+            <button
+              onClick={() => onRemove(q.id)}
+              className="text-xs text-gray-400 hover:text-red-600"
+              title="Remove from queue"
+            >
+              &times;
+            </button>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
@@ -1,8 +1,15 @@
 import type { AgentRun } from "../../api/types";
-import { formatTokens, isActiveRun, statusColors, statusLabels } from "../../utils/agentStats";
+import {
+  formatRunEnvironment,
+  formatTokens,
+  isActiveRun,
+  statusColors,
+  statusLabels,
+} from "../../utils/agentStats";
 import { StatusPulseBadge } from "../shared/StatusPulseBadge";
 import { TimeAgo } from "../shared/TimeAgo";
 import { ChildRunsList } from "./ChildRunsList";
+import { ShareRunButton } from "./ShareRunButton";
 
 function formatDuration(ms: number): string {
   const seconds = Math.floor(ms / 1000);
@@ -89,6 +96,7 @@ export function AgentStatusDisplay({
             </span>
           )}
         </div>
+        {!isActive && <ShareRunButton runId={run.id} />}
       </div>
 
       <dl className="grid grid-cols-2 sm:grid-cols-4 gap-x-4 gap-y-2 text-sm">
@@ -151,6 +159,12 @@ export function AgentStatusDisplay({
         )}
       </dl>
 
+      {run.environment && (
+        <p className="mt-3 font-mono text-xs text-gray-500" title="Environment the run started in">
+          {formatRunEnvironment(run.environment)}
+        </p>
+      )}
+
       {run.status === "failed" && run.result_text && (
         <div className="mt-3 rounded-md bg-red-50 p-3 text-sm text-red-700">
           {run.result_text}
@@ -0,0 +1,79 @@
+import type { ChainLink, ChainOutcome, ChainStep } from "../../api/types";
+import { statusColors, statusLabels } from "../../utils/agentStats";
+
+const OUTCOME_LABELS: Record<ChainOutcome, string> = {
+  queued: "follow-up queued",
+  notified: "notified",
+  no_follow_up: "chain ended",
+  cancelled: "cancelled, chain ended",
+  depth_limit: "chain length limit reached",
+};
+
+function firstLine(text: string): string {
+  const line = text.split("\n")[0];
+  return line.length > 80 ? line.slice(0, 80) + "..." : line;
+}
+
+function PendingStep({ label, step }: { label: string; step: ChainStep | null }) {
+  if (!step) return null;
+  return (
+    <div className="text-xs text-gray-400">
+      if {label}:{" "}
+      {step.action === "run" ? (
+        <span title={step.prompt}>run &ldquo;{firstLine(step.prompt)}&rdquo;</span>
+      ) : (
+        "notify"
+      )}
+    </div>
+  );
+}
+
+interface ChainTreeProps {
+  links: ChainLink[];
+}
+
+/** The runs of an agent chain, indented by depth, with follow-ups not yet reached. */
+export function ChainTree({ links }: ChainTreeProps) {
+  if (links.length === 0) return null;
+
+  return (
+    <div className="rounded-lg border border-gray-200 bg-white p-4">
+      <h4 className="text-sm font-semibold uppercase tracking-wider text-gray-400">
+        Chain ({links.length})
+      </h4>
+      <ul className="mt-3 space-y-2">
+        {links.map((link) => {
+          const status = link.run_status;
+          const color = status ? statusColors[status] : "bg-gray-100 text-gray-600";
+          return (
+            <li
+              key={link.id}
+              className={link.depth > 0 ? "border-l border-gray-200 pl-3" : undefined}
+              style={{ marginLeft: `${Math.max(link.depth - 1, 0) * 1.25}rem` }}
+            >
+              <div className="flex items-center gap-2 text-sm">
+                {link.trigger && (
+                  <span className="text-xs text-gray-400">on {link.trigger}</span>
+                )}
+                <span className={`inline-block px-2 py-0.5 text-xs font-medium rounded-full ${color}`}>
+                  {status ? (statusLabels[status] ?? status) : "queued"}
+                </span>
+                <span className="flex-1 truncate text-gray-700" title={link.prompt}>
+                  {firstLine(link.prompt)}
+                </span>
+              </div>
+              {link.outcome ? (
+                <div className="text-xs text-gray-400">&rarr; {OUTCOME_LABELS[link.outcome]}</div>
+              ) : (
+                <>
+                  <PendingStep label="success" step={link.on_success} />
+                  <PendingStep label="failure" step={link.on_failure} />
+                </>
+              )}
+            </li>
+          );
+        })}
+      </ul>
+    </div>
+  );
+}
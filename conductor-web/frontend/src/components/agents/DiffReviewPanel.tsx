@@ -0,0 +1,40 @@
+import type { DiffReview } from "../../api/types";
+import { StatusBadge } from "../shared/StatusBadge";
+import { TimeAgo } from "../shared/TimeAgo";
+
+interface DiffReviewPanelProps {
+  review: DiffReview | null;
+}
+
+/** The latest agent review of a worktree's diff against its base. */
+export function DiffReviewPanel({ review }: DiffReviewPanelProps) {
+  if (!review) return null;
+  const running = review.status === "running" || review.status === "waiting_for_feedback";
+  return (
+    <div className="rounded-lg border border-gray-200 bg-white p-4">
+      <div className="flex items-center gap-2">
+        <h4 className="text-sm font-semibold uppercase tracking-wider text-gray-400">
+          Diff Review
+        </h4>
+        <StatusBadge status={review.status} />
+        <span className="text-xs text-gray-500">
+          {review.files_changed} files vs {review.base_ref}
+          {review.truncated && ", diff trimmed"}
+          {" · "}
+          <TimeAgo date={review.ended_at ?? review.created_at} />
+        </span>
+      </div>
+      {review.review ? (
+        <pre className="mt-3 max-h-96 overflow-y-auto whitespace-pre-wrap break-words font-sans text-sm text-gray-800">
+          {review.review}
+        </pre>
+      ) : (
+        <p className="mt-2 text-sm text-gray-500">
+          {running
+            ? "The agent is reviewing the diff…"
+            : "The review did not finish. Start another to try again."}
+        </p>
+      )}
+    </div>
+  );
+}
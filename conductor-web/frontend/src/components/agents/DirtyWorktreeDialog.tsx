@@ -0,0 +1,64 @@
+import { useId } from "react";
+import { BaseModal } from "../shared/BaseModal";
+import type { DirtyWorktreeChoice } from "../../api/types";
+
+const FILES_SHOWN = 10;
+
+interface DirtyWorktreeDialogProps {
+  open: boolean;
+  files: string[];
+  onChoose: (choice: DirtyWorktreeChoice) => void;
+  onCancel: () => void;
+}
+
+/** Asks what to do with uncommitted changes before an agent is launched on them. */
+export function DirtyWorktreeDialog({ open, files, onChoose, onCancel }: DirtyWorktreeDialogProps) {
+  const titleId = useId();
+  const hidden = files.length - FILES_SHOWN;
+
+  return (
+    <BaseModal
+      open={open}
+      onClose={onCancel}
+      titleId={titleId}
+      className="bg-white rounded-lg shadow-lg p-6 max-w-md w-full mx-4 outline-none modal-panel"
+    >
+      <h3 id={titleId} className="text-lg font-semibold text-gray-900">Uncommitted changes</h3>
+      <p className="mt-2 text-sm text-gray-600">
+        The agent may overwrite these files. Stashed changes are restored when the run ends.
+      </p>
+      <ul className="mt-3 max-h-40 overflow-auto rounded border border-gray-200 bg-gray-50 px-3 py-2 font-mono text-xs text-gray-700">
+        {files.slice(0, FILES_SHOWN).map((f) => (
+          <li key={f} className="truncate">{f}</li>
+        ))}
+        {hidden > 0 && <li className="text-gray-400">…and {hidden} more</li>}
+      </ul>
+      <div className="mt-4 flex flex-wrap justify-end gap-2">
+        <button
+          onClick={onCancel}
+          className="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 active:scale-95 transition-transform"
+        >
+          Cancel
+        </button>
+        <button
+          onClick={() => onChoose("proceed")}
+          className="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 active:scale-95 transition-transform"
+        >
+          Proceed anyway
+        </button>
+        <button
+          onClick={() => onChoose("commit_wip")}
+          className="px-3 py-1.5 text-sm rounded-md border border-indigo-300 text-indigo-700 hover:bg-indigo-50 active:scale-95 transition-transform"
+        >
+          Commit WIP
+        </button>
+        <button
+          onClick={() => onChoose("stash")}
+          className="px-3 py-1.5 text-sm rounded-md bg-indigo-600 text-white hover:bg-indigo-700 active:scale-95 transition-transform"
+        >
+          Stash
+        </button>
+      </div>
+    </BaseModal>
+  );
+}
@@ -1,5 +1,6 @@
 import type { AgentRun } from "../../api/types";
 import { StatusBadge } from "../shared/StatusBadge";
+import { formatRunEnvironment } from "../../utils/agentStats";
 
 interface RepoAgentRunCardProps {
   run: AgentRun;
@@ -18,6 +19,11 @@ export function RepoAgentRunCard({ run }: RepoAgentRunCardProps) {
           {new Date(run.started_at).toLocaleString()}
         </span>
       </div>
+      {run.environment && (
+        <p className="mt-1 font-mono text-xs text-gray-400 truncate">
+          {formatRunEnvironment(run.environment)}
+        </p>
+      )}
     </div>
   );
 }
@@ -0,0 +1,46 @@
+import { useState } from "react";
+import { api } from "../../api/client";
+
+/** Creates a 24-hour read-only link to a finished run and copies it. */
+export function ShareRunButton({ runId }: { runId: string }) {
+  const [link, setLink] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  async function handleShare() {
+    setBusy(true);
+    setError(null);
+    try {
+      const res = await api.shareAgentRun(runId);
+      const url = `${window.location.origin}${res.url}`;
+      setLink(url);
+      await navigator.clipboard?.writeText(url);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <div className="flex items-center gap-2">
+      {link && (
+        <input
+          readOnly
+          value={link}
+          onFocus={(e) => e.target.select()}
+          title="Copied — expires in 24 hours"
+          className="w-48 px-2 py-0.5 text-xs font-mono rounded border border-gray-300 text-gray-600"
+        />
+      )}
+      {error && <span className="text-xs text-red-600">{error}</span>}
+      <button
+        onClick={handleShare}
+        disabled={busy}
+        className="px-2 py-0.5 text-xs rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 disabled:opacity-50"
+      >
+        {link ? "New link" : "Share"}
+      </button>
+    </div>
+  );
+}
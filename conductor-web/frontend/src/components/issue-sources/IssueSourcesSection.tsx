@@ -27,7 +27,7 @@ interface Props {
   onChanged: () => void;
 }
 
-function parseConfig(source: IssueSource): Record<string, string> {
+function parseConfig(source: IssueSource): Record<string, unknown> {
   try {
     return JSON.parse(source.config_json);
   } catch {
@@ -38,11 +38,27 @@ function parseConfig(source: IssueSource): Record<string, string> {
 function formatConfig(source: IssueSource): string {
   const cfg = parseConfig(source);
   if (source.source_type === "github") {
-    return `${cfg.owner}/${cfg.repo}`;
+    const slug = `${cfg.owner}/${cfg.repo}`;
+    return cfg.sync_prs ? `${slug} (+ pull requests)` : slug;
   }
   if (source.source_type === "jira") {
     return `${cfg.url} (${cfg.jql})`;
   }
+  if (source.source_type === "gitlab") {
+    return `${cfg.base_url} — ${cfg.project_id}`;
+  }
+  if (source.source_type === "linear") {
+    return `Team ${cfg.team_key}`;
+  }
+  if (source.source_type === "bitbucket") {
+    return `${cfg.workspace}/${cfg.repo_slug}`;
+  }
+  if (source.source_type === "asana") {
+    return `Project ${cfg.project_gid}`;
+  }
+  if (source.source_type === "webhook") {
+    return `POST /api/repos/${source.repo_id}/tickets/ingest`;
+  }
   if (source.source_type === "vantage") {
     return `${cfg.project_id} (${cfg.sdlc_root})`;
   }
@@ -57,13 +73,25 @@ export function IssueSourcesSection({
   onChanged,
 }: Props) {
   const [showAdd, setShowAdd] = useState(false);
-  const [sourceType, setSourceType] = useState<"github" | "jira" | "vantage">("github");
+  const [sourceType, setSourceType] = useState<"github" | "gitlab" | "jira" | "linear" | "bitbucket" | "asana" | "webhook" | "vantage">("github");
   const [jiraUrl, setJiraUrl] = useState("");
   const [jiraJql, setJiraJql] = useState("");
+  const [gitlabBaseUrl, setGitlabBaseUrl] = useState("");
+  const [gitlabProjectId, setGitlabProjectId] = useState("");
+  const [gitlabTokenEnv, setGitlabTokenEnv] = useState("");
+  const [linearTeamKey, setLinearTeamKey] = useState("");
+  const [linearApiKeyEnv, setLinearApiKeyEnv] = useState("");
+  const [bitbucketWorkspace, setBitbucketWorkspace] = useState("");
+  const [bitbucketRepoSlug, setBitbucketRepoSlug] = useState("");
+  const [bitbucketEmail, setBitbucketEmail] = useState("");
+  const [bitbucketTokenEnv, setBitbucketTokenEnv] = useState("");
+  const [asanaProjectGid, setAsanaProjectGid] = useState("");
+  const [asanaTokenEnv, setAsanaTokenEnv] = useState("");
   const [vantageProjectId, setVantageProjectId] = useState("");
   const [vantageSdlcRoot, setVantageSdlcRoot] = useState("");
   const [githubOwner, setGithubOwner] = useState("");
   const [githubRepo, setGithubRepo] = useState("");
+  const [githubSyncPrs, setGithubSyncPrs] = useState(false);
   const [autoInferred, setAutoInferred] = useState(false);
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -83,6 +111,26 @@ export function IssueSourcesSection({
     }
   }, [remoteUrl, sourceType]);
 
+  // Prefill GitLab instance and project path from a gitlab remote URL
+  useEffect(() => {
+    if (sourceType !== "gitlab") return;
+    const match = remoteUrl.match(/^(?:git@|https:\/\/)([^/:]*gitlab[^/:]*)[:/](.+?)(?:\.git)?$/);
+    if (match) {
+      setGitlabBaseUrl((v) => v || `https://${match[1]}`);
+      setGitlabProjectId((v) => v || match[2]);
+    }
+  }, [remoteUrl, sourceType]);
+
+  // Prefill Bitbucket workspace and repo from a bitbucket.org remote URL
+  useEffect(() => {
+    if (sourceType !== "bitbucket") return;
+    const match = remoteUrl.match(/bitbucket\.org[:/]([^/]+)\/([^/]+?)(?:\.git)?$/);
+    if (match) {
+      setBitbucketWorkspace((v) => v || match[1]);
+      setBitbucketRepoSlug((v) => v || match[2]);
+    }
+  }, [remoteUrl, sourceType]);
+
   useEffect(() => {
     if (!showAdd) return;
     function handleKeyDown(e: KeyboardEvent) {
@@ -101,6 +149,18 @@ export function IssueSourcesSection({
     setJiraJql("");
     setGithubOwner("");
     setGithubRepo("");
+    setGithubSyncPrs(false);
+    setGitlabBaseUrl("");
+    setGitlabProjectId("");
+    setGitlabTokenEnv("");
+    setLinearTeamKey("");
+    setLinearApiKeyEnv("");
+    setBitbucketWorkspace("");
+    setBitbucketRepoSlug("");
+    setBitbucketEmail("");
+    setBitbucketTokenEnv("");
+    setAsanaProjectGid("");
+    setAsanaTokenEnv("");
     setVantageProjectId("");
     setVantageSdlcRoot("");
     setAutoInferred(false);
@@ -113,10 +173,16 @@ export function IssueSourcesSection({
     setError(null);
     try {
       if (sourceType === "github") {
-        // If user hasn't modified the auto-inferred values, let the backend infer
-        const configJson = autoInferred
-          ? undefined
-          : JSON.stringify({ owner: githubOwner.trim(), repo: githubRepo.trim() });
+        // If user hasn't modified the auto-inferred values (and needs no extra
+        // options), let the backend infer
+        const configJson =
+          autoInferred && !githubSyncPrs
+            ? undefined
+            : JSON.stringify({
+                owner: githubOwner.trim(),
+                repo: githubRepo.trim(),
+                ...(githubSyncPrs ? { sync_prs: true } : {}),
+              });
         if (!autoInferred && (!githubOwner.trim() || !githubRepo.trim())) {
           setError("GitHub owner and repo are required");
           setSaving(false);
@@ -139,6 +205,66 @@ export function IssueSourcesSection({
             jql: jiraJql.trim(),
           }),
         });
+      } else if (sourceType === "gitlab") {
+        if (!gitlabBaseUrl.trim() || !gitlabProjectId.trim()) {
+          setError("GitLab URL and project are required");
+          setSaving(false);
+          return;
+        }
+        await api.createIssueSource(repoId, {
+          source_type: "gitlab",
+          config_json: JSON.stringify({
+            base_url: gitlabBaseUrl.trim(),
+            project_id: gitlabProjectId.trim(),
+            ...(gitlabTokenEnv.trim() ? { token_env: gitlabTokenEnv.trim() } : {}),
+          }),
+        });
+      } else if (sourceType === "linear") {
+        if (!linearTeamKey.trim()) {
+          setError("Team key is required");
+          setSaving(false);
+          return;
+        }
+        await api.createIssueSource(repoId, {
+          source_type: "linear",
+          config_json: JSON.stringify({
+            team_key: linearTeamKey.trim(),
+            ...(linearApiKeyEnv.trim() ? { api_key_env: linearApiKeyEnv.trim() } : {}),
+          }),
+        });
+      } else if (sourceType === "bitbucket") {
+        if (!bitbucketWorkspace.trim() || !bitbucketRepoSlug.trim()) {
+          setError("Bitbucket workspace and repository are required");
+          setSaving(false);
+          return;
+        }
+        await api.createIssueSource(repoId, {
+          source_type: "bitbucket",
+          config_json: JSON.stringify({
+            workspace: bitbucketWorkspace.trim(),
+            repo_slug: bitbucketRepoSlug.trim(),
+            ...(bitbucketEmail.trim() ? { email: bitbucketEmail.trim() } : {}),
+            ...(bitbucketTokenEnv.trim() ? { token_env: bitbucketTokenEnv.trim() } : {}),
+          }),
+        });
+      } else if (sourceType === "asana") {
+        if (!asanaProjectGid.trim()) {
+          setError("Project GID is required");
+          setSaving(false);
+          return;
+        }
+        await api.createIssueSource(repoId, {
+          source_type: "asana",
+          config_json: JSON.stringify({
+            project_gid: asanaProjectGid.trim(),
+            ...(asanaTokenEnv.trim() ? { token_env: asanaTokenEnv.trim() } : {}),
+          }),
+        });
+      } else if (sourceType === "webhook") {
+        await api.createIssueSource(repoId, {
+          source_type: "webhook",
+          config_json: "{}",
+        });
       } else if (sourceType === "vantage") {
         if (!vantageProjectId.trim() || !vantageSdlcRoot.trim()) {
           setError("Project ID and SDLC root path are required");
@@ -178,9 +304,22 @@ export function IssueSourcesSection({
   }
 
   const hasGithub = sources.some((s) => s.source_type === "github");
+  const hasGitlab = sources.some((s) => s.source_type === "gitlab");
   const hasJira = sources.some((s) => s.source_type === "jira");
+  const hasLinear = sources.some((s) => s.source_type === "linear");
+  const hasBitbucket = sources.some((s) => s.source_type === "bitbucket");
+  const hasAsana = sources.some((s) => s.source_type === "asana");
+  const hasWebhook = sources.some((s) => s.source_type === "webhook");
   const hasVantage = sources.some((s) => s.source_type === "vantage");
-  const canAdd = !hasGithub || !hasJira || !hasVantage;
+  const canAdd =
+    !hasGithub ||
+    !hasGitlab ||
+    !hasJira ||
+    !hasLinear ||
+    !hasBitbucket ||
+    !hasAsana ||
+    !hasWebhook ||
+    !hasVantage;
 
   return (
     <section>
@@ -199,6 +338,11 @@ export function IssueSourcesSection({
               // Default to whichever type isn't already added
               if (!hasVantage) setSourceType("vantage");
               else if (!hasJira) setSourceType("jira");
+              else if (!hasGitlab) setSourceType("gitlab");
+              else if (!hasLinear) setSourceType("linear");
+              else if (!hasBitbucket) setSourceType("bitbucket");
+              else if (!hasAsana) setSourceType("asana");
+              else if (!hasWebhook) setSourceType("webhook");
               else setSourceType("github");
               setShowAdd(true);
             }}
@@ -239,7 +383,17 @@ export function IssueSourcesSection({
                           ? "bg-gray-800 text-white"
                           : source.source_type === "vantage"
                             ? "bg-amber-100 text-amber-700"
-                            : "bg-blue-100 text-blue-700"
+                            : source.source_type === "gitlab"
+                              ? "bg-orange-100 text-orange-700"
+                              : source.source_type === "linear"
+                                ? "bg-violet-100 text-violet-700"
+                                : source.source_type === "bitbucket"
+                                  ? "bg-sky-100 text-sky-700"
+                                  : source.source_type === "asana"
+                                    ? "bg-rose-100 text-rose-700"
+                                    : source.source_type === "webhook"
+                                      ? "bg-emerald-100 text-emerald-700"
+                                      : "bg-blue-100 text-blue-700"
                       }`}
                     >
                       {source.source_type}
@@ -279,12 +433,17 @@ export function IssueSourcesSection({
                 <select
                   value={sourceType}
                   onChange={(e) =>
-                    setSourceType(e.target.value as "github" | "jira" | "vantage")
+                    setSourceType(e.target.value as "github" | "gitlab" | "jira" | "linear" | "bitbucket" | "asana" | "webhook" | "vantage")
                   }
                   className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
                 >
                   {!hasGithub && <option value="github">GitHub</option>}
+                  {!hasGitlab && <option value="gitlab">GitLab</option>}
                   {!hasJira && <option value="jira">Jira</option>}
+                  {!hasLinear && <option value="linear">Linear</option>}
+                  {!hasBitbucket && <option value="bitbucket">Bitbucket</option>}
+                  {!hasAsana && <option value="asana">Asana</option>}
+                  {!hasWebhook && <option value="webhook">Webhook (push)</option>}
                   {!hasVantage && <option value="vantage">Vantage</option>}
                 </select>
               </div>
@@ -326,6 +485,15 @@ export function IssueSourcesSection({
                       Auto-inferred from remote URL
                     </p>
                   )}
+                  <label className="flex items-center gap-2 text-sm text-gray-700">
+                    <input
+                      type="checkbox"
+                      checked={githubSyncPrs}
+                      onChange={(e) => setGithubSyncPrs(e.target.checked)}
+                      className="rounded border-gray-300"
+                    />
+                    Also sync open pull requests
+                  </label>
                 </>
               )}
 
@@ -359,6 +527,189 @@ export function IssueSourcesSection({
                 </>
               )}
 
+              {sourceType === "gitlab" && (
+                <>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      GitLab URL
+                    </label>
+                    <input
+                      type="text"
+                      value={gitlabBaseUrl}
+                      onChange={(e) => setGitlabBaseUrl(e.target.value)}
+                      placeholder="e.g. https://gitlab.mycompany.com"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                      autoFocus
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Project
+                    </label>
+                    <input
+                      type="text"
+                      value={gitlabProjectId}
+                      onChange={(e) => setGitlabProjectId(e.target.value)}
+                      placeholder="e.g. group/project or 1234"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Token Variable
+                    </label>
+                    <input
+                      type="text"
+                      value={gitlabTokenEnv}
+                      onChange={(e) => setGitlabTokenEnv(e.target.value)}
+                      placeholder="GITLAB_TOKEN"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      Environment variable holding a token with read_api scope
+                    </p>
+                  </div>
+                </>
+              )}
+
+              {sourceType === "linear" && (
+                <>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Team Key
+                    </label>
+                    <input
+                      type="text"
+                      value={linearTeamKey}
+                      onChange={(e) => setLinearTeamKey(e.target.value)}
+                      placeholder="e.g. ENG"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                      autoFocus
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      API Key Variable
+                    </label>
+                    <input
+                      type="text"
+                      value={linearApiKeyEnv}
+                      onChange={(e) => setLinearApiKeyEnv(e.target.value)}
+                      placeholder="LINEAR_API_KEY"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      Environment variable holding a Linear personal API key
+                    </p>
+                  </div>
+                </>
+              )}
+
+              {sourceType === "bitbucket" && (
+                <>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Workspace
+                    </label>
+                    <input
+                      type="text"
+                      value={bitbucketWorkspace}
+                      onChange={(e) => setBitbucketWorkspace(e.target.value)}
+                      placeholder="e.g. acme"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                      autoFocus
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Repository
+                    </label>
+                    <input
+                      type="text"
+                      value={bitbucketRepoSlug}
+                      onChange={(e) => setBitbucketRepoSlug(e.target.value)}
+                      placeholder="e.g. my-project"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Atlassian Email
+                    </label>
+                    <input
+                      type="text"
+                      value={bitbucketEmail}
+                      onChange={(e) => setBitbucketEmail(e.target.value)}
+                      placeholder="e.g. you@company.com"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      Needed for API tokens; leave empty for repository access tokens
+                    </p>
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Token Variable
+                    </label>
+                    <input
+                      type="text"
+                      value={bitbucketTokenEnv}
+                      onChange={(e) => setBitbucketTokenEnv(e.target.value)}
+                      placeholder="BITBUCKET_TOKEN"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      Environment variable holding a token with issue read access
+                    </p>
+                  </div>
+                </>
+              )}
+
+              {sourceType === "asana" && (
+                <>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Project GID
+                    </label>
+                    <input
+                      type="text"
+                      value={asanaProjectGid}
+                      onChange={(e) => setAsanaProjectGid(e.target.value)}
+                      placeholder="e.g. 1200000000000000"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                      autoFocus
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      The number after /0/ in the project URL
+                    </p>
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Token Variable
+                    </label>
+                    <input
+                      type="text"
+                      value={asanaTokenEnv}
+                      onChange={(e) => setAsanaTokenEnv(e.target.value)}
+                      placeholder="ASANA_TOKEN"
+                      className="w-full px-3 py-1.5 text-sm border border-gray-300 rounded-md focus:ring-indigo-500 focus:border-indigo-500"
+                    />
+                    <p className="text-xs text-gray-500 mt-1">
+                      Environment variable holding an Asana personal access token
+                    </p>
+                  </div>
+                </>
+              )}
+
+              {sourceType === "webhook" && (
+                <p className="text-xs text-gray-500">
+                  External systems push tickets as JSON to{" "}
+                  <code className="font-mono">POST /api/repos/{repoId}/tickets/ingest</code>. Pass{" "}
+                  <code className="font-mono">?close_missing=true</code> when the body is the full
+                  set of open tickets.
+                </p>
+              )}
+
               {sourceType === "vantage" && (
                 <>
                   <div>
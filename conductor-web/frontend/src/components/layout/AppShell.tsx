@@ -13,6 +13,7 @@ import {
 import { useHotkeys } from "../../hooks/useHotkeys";
 import { KeyboardShortcutHelp } from "../shared/KeyboardShortcutHelp";
 import { ToastContainer } from "../notifications/ToastContainer";
+import { NotificationBell } from "../notifications/NotificationBell";
 import { useToast } from "../../hooks/useToast";
 import { CommandPalette } from "../shared/CommandPalette";
 
@@ -131,7 +132,9 @@ export function AppShell() {
               ☰
             </button>
             <span className="font-semibold text-gray-900">Conductor</span>
-            <div className="ml-auto" />
+            <div className="ml-auto">
+              <NotificationBell />
+            </div>
           </div>
           <div className="p-3 md:p-4 pb-20 md:pb-4">
             <Outlet />
@@ -3,6 +3,7 @@ import { useRepos } from "./AppShell";
 import { StationClock } from "../shared/StationClock";
 import { ThemeLogo } from "../shared/ThemeLogo";
 import { useThemeId } from "../../themes/useTheme";
+import { NotificationBell } from "../notifications/NotificationBell";
 
 // Per-theme repo indicator colors (cycling palette)
 const indicatorColors = [
@@ -80,6 +81,9 @@ export function Sidebar({ open, onClose }: SidebarProps) {
           <ThemeLogo size={24} />
           <h1 className="text-base font-bold text-gray-900">Conductor</h1>
         </div>
+        <div className="hidden md:block">
+          <NotificationBell />
+        </div>
         {/* Close button only shown on mobile */}
         <button
           onClick={onClose}
@@ -107,6 +111,9 @@ export function Sidebar({ open, onClose }: SidebarProps) {
         <NavLink to="/tickets" className={linkClass}>
           Tickets <ShortcutHint keys="g t" />
         </NavLink>
+        <NavLink to="/reports" className={linkClass}>
+          Reports
+        </NavLink>
         <div className="pt-3 pb-1 px-2.5">
           <span className="text-xs font-semibold uppercase tracking-wider text-gray-400">
             Repos
@@ -0,0 +1,122 @@
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
+import { useNavigate } from "react-router";
+import { api } from "../../api/client";
+import type { Notification, NotificationSeverity } from "../../api/types";
+import { useConductorEvents } from "../../hooks/useConductorEvents";
+import { TimeAgo } from "../shared/TimeAgo";
+
+const severityDot: Record<NotificationSeverity, string> = {
+  info: "bg-blue-500",
+  success: "bg-green-500",
+  warning: "bg-amber-500",
+  error: "bg-red-500",
+};
+
+/** Bell with an unread badge that opens the notification center dropdown. */
+export function NotificationBell() {
+  const [open, setOpen] = useState(false);
+  const [unread, setUnread] = useState(0);
+  const [items, setItems] = useState<Notification[]>([]);
+  const containerRef = useRef<HTMLDivElement>(null);
+  const navigate = useNavigate();
+
+  const refreshCount = useCallback(() => {
+    api.getUnreadNotificationCount().then(({ count }) => setUnread(count)).catch(() => {});
+  }, []);
+
+  const refreshList = useCallback(() => {
+    api.listNotifications().then(setItems).catch(() => {});
+  }, []);
+
+  useEffect(refreshCount, [refreshCount]);
+
+  useEffect(() => {
+    if (open) refreshList();
+  }, [open, refreshList]);
+
+  const handlers = useMemo(() => {
+    const refresh = () => {
+      refreshCount();
+      if (open) refreshList();
+    };
+    return { notifications_changed: refresh, lagged: refresh };
+  }, [open, refreshCount, refreshList]);
+
+  useConductorEvents(handlers);
+
+  // Close on outside click
+  useEffect(() => {
+    if (!open) return;
+    const onClick = (e: MouseEvent) => {
+      if (!containerRef.current?.contains(e.target as Node)) setOpen(false);
+    };
+    document.addEventListener("mousedown", onClick);
+    return () => document.removeEventListener("mousedown", onClick);
+  }, [open]);
+
+  const openNotification = (n: Notification) => {
+    if (!n.read) api.markNotificationRead(n.id).catch(() => {});
+    setOpen(false);
+    if (n.url) navigate(n.url);
+  };
+
+  const markAllRead = () => api.markAllNotificationsRead().catch(() => {});
+  const clearAll = () => api.clearNotifications().catch(() => {});
+
+  return (
+    <div ref={containerRef} className="relative">
+      <button
+        onClick={() => setOpen((prev) => !prev)}
+        className="relative flex items-center justify-center rounded text-gray-600 hover:bg-gray-100"
+        style={{ minHeight: 36, minWidth: 36 }}
+        aria-label={unread > 0 ? `Notifications (${unread} unread)` : "Notifications"}
+      >
+        🔔
+        {unread > 0 && (
+          <span className="absolute top-0.5 right-0.5 min-w-4 h-4 px-1 rounded-full bg-red-600 text-white text-[10px] leading-4 text-center">
+            {unread > 99 ? "99+" : unread}
+          </span>
+        )}
+      </button>
+      {open && (
+        <div className="absolute right-0 md:left-0 md:right-auto mt-1 w-80 max-h-96 flex flex-col rounded-lg border border-gray-200 bg-white shadow-lg z-50">
+          <div className="flex items-center justify-between px-3 py-2 border-b border-gray-100">
+            <span className="text-sm font-semibold text-gray-900">Notifications</span>
+            <div className="flex gap-2 text-xs">
+              <button onClick={markAllRead} disabled={unread === 0} className="text-indigo-600 hover:underline disabled:opacity-50">
+                Mark all read
+              </button>
+              <button onClick={clearAll} disabled={items.length === 0} className="text-gray-500 hover:underline disabled:opacity-50">
+                Clear
+              </button>
+            </div>
+          </div>
+          <ul className="overflow-auto">
+            {items.length === 0 && (
+              <li className="px-3 py-6 text-center text-xs text-gray-400">No notifications</li>
+            )}
+            {items.map((n) => (
+              <li key={n.id}>
+                <button
+                  onClick={() => openNotification(n)}
+                  className={`w-full text-left px-3 py-2 flex gap-2 hover:bg-gray-50 ${n.read ? "" : "bg-indigo-50/50"}`}
+                >
+                  <span className={`mt-1.5 h-2 w-2 shrink-0 rounded-full ${severityDot[n.severity]}`} />
+                  <span className="min-w-0">
+                    <span className={`block text-sm truncate ${n.read ? "text-gray-600" : "text-gray-900 font-medium"}`}>
+                      {n.title}
+                    </span>
+                    {n.body && <span className="block text-xs text-gray-500 line-clamp-2">{n.body}</span>}
+                    <span className="block text-[10px] text-gray-400">
+                      <TimeAgo date={n.created_at} short />
+                    </span>
+                  </span>
+                </button>
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </div>
+  );
+}
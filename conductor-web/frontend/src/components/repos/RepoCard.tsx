@@ -1,13 +1,54 @@
 import { Link } from "react-router";
-import type { Repo } from "../../api/types";
+import type { Repo, WorkspaceUsage } from "../../api/types";
+import { toolchainLabel } from "../../utils/toolchain";
 import { TimeAgo } from "../shared/TimeAgo";
 
+function formatBytes(bytes: number): string {
+  const units = ["B", "KB", "MB", "GB", "TB"];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit += 1;
+  }
+  return unit === 0 ? `${value} B` : `${value.toFixed(1)} ${units[unit]}`;
+}
+
+const quotaBarColor = {
+  ok: "bg-green-500",
+  warning: "bg-amber-500",
+  exceeded: "bg-red-500",
+} as const;
+
+function QuotaMeter({ usage }: { usage: WorkspaceUsage }) {
+  if (usage.limit_bytes == null || usage.limit_bytes <= 0) return null;
+  const pct = Math.round((usage.used_bytes / usage.limit_bytes) * 100);
+  return (
+    <div
+      className="mt-2"
+      title={`Workspace disk usage: ${formatBytes(usage.used_bytes)} of ${formatBytes(usage.limit_bytes)}`}
+    >
+      <div className="h-1.5 rounded-full bg-gray-100 overflow-hidden">
+        <div
+          className={`h-full ${quotaBarColor[usage.level]}`}
+          style={{ width: `${Math.min(pct, 100)}%` }}
+        />
+      </div>
+      <p className="mt-0.5 text-[11px] text-gray-500">
+        {formatBytes(usage.used_bytes)} / {formatBytes(usage.limit_bytes)} ({pct}%)
+      </p>
+    </div>
+  );
+}
+
 export function RepoCard({
   repo,
   worktreeCount,
+  usage,
 }: {
   repo: Repo;
   worktreeCount: number;
+  usage?: WorkspaceUsage;
 }) {
   return (
     <Link
@@ -24,10 +65,12 @@ export function RepoCard({
             <span className="inline-block w-1.5 h-1.5 rounded-full bg-green-500" />
             {worktreeCount} worktree{worktreeCount !== 1 ? "s" : ""}
           </span>
+          {repo.toolchain?.language && <span>{toolchainLabel(repo.toolchain)}</span>}
           <span>
             Created <TimeAgo date={repo.created_at} />
           </span>
         </div>
+        {usage && <QuotaMeter usage={usage} />}
       </div>
     </Link>
   );
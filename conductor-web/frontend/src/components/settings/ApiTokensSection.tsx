@@ -0,0 +1,198 @@
+import { useCallback, useEffect, useState } from "react";
+import { api } from "../../api/client";
+import { getStoredApiToken, setStoredApiToken } from "../../api/transport";
+import type { ApiToken, ApiTokenScope } from "../../api/types";
+import { TimeAgo } from "../shared/TimeAgo";
+
+const SCOPES: { value: ApiTokenScope; label: string }[] = [
+  { value: "read-only", label: "Read-only" },
+  { value: "agent-control", label: "Agent control" },
+  { value: "admin", label: "Admin" },
+];
+
+/** Create, list and revoke API tokens, and set the token this browser sends. */
+export function ApiTokensSection() {
+  const [tokens, setTokens] = useState<ApiToken[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [name, setName] = useState("");
+  const [scope, setScope] = useState<ApiTokenScope>("read-only");
+  const [created, setCreated] = useState<string | null>(null);
+  const [browserToken, setBrowserToken] = useState(getStoredApiToken() ?? "");
+  const [busy, setBusy] = useState(false);
+
+  const load = useCallback(() => {
+    api
+      .listApiTokens()
+      .then((t) => {
+        setTokens(t);
+        setError(null);
+      })
+      .catch((err: unknown) =>
+        setError(err instanceof Error ? err.message : "Failed to load tokens"),
+      );
+  }, []);
+
+  useEffect(load, [load]);
+
+  async function handleCreate(e: React.FormEvent) {
+    e.preventDefault();
+    setBusy(true);
+    try {
+      const { secret } = await api.createApiToken(name, scope);
+      setCreated(secret);
+      setName("");
+      load();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to create token");
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleRevoke(token: ApiToken) {
+    if (!window.confirm(`Revoke "${token.name}"? Clients using it will get 401.`)) return;
+    try {
+      await api.revokeApiToken(token.id);
+      load();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to revoke token");
+    }
+  }
+
+  function saveBrowserToken(value: string) {
+    setStoredApiToken(value.trim() || null);
+    setBrowserToken(value.trim());
+    load();
+  }
+
+  const input = "px-2 py-1 text-sm border border-gray-300 rounded";
+  const button = "px-2 py-1 text-xs font-medium text-gray-700 bg-gray-100 rounded hover:bg-gray-200 disabled:opacity-50";
+
+  return (
+    <section>
+      <h3 className="text-sm font-semibold uppercase tracking-wider text-gray-400 mb-1">
+        API Tokens
+      </h3>
+      <p className="text-sm text-gray-500 mb-3">
+        Scoped tokens for scripts and automations, sent as{" "}
+        <code className="text-xs bg-gray-100 px-1 py-0.5 rounded">Authorization: Bearer …</code>.
+        Only a hash is stored, so a secret is shown once.
+      </p>
+
+      {error && (
+        <div className="mb-3 px-3 py-2 text-sm text-red-700 bg-red-50 rounded-md border border-red-200">
+          {error}
+        </div>
+      )}
+
+      <div className="mb-4 flex items-center gap-2">
+        <label className="text-sm text-gray-600 shrink-0" htmlFor="browser-token">
+          Token used by this browser
+        </label>
+        <input
+          id="browser-token"
+          type="password"
+          value={browserToken}
+          onChange={(e) => setBrowserToken(e.target.value)}
+          placeholder="cdt_…"
+          className={`${input} flex-1 font-mono`}
+        />
+        <button onClick={() => saveBrowserToken(browserToken)} className={button}>
+          Save
+        </button>
+        {getStoredApiToken() && (
+          <button onClick={() => saveBrowserToken("")} className={button}>
+            Clear
+          </button>
+        )}
+      </div>
+
+      <form onSubmit={handleCreate} className="mb-3 flex items-center gap-2">
+        <input
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          placeholder="Token name"
+          className={`${input} flex-1`}
+        />
+        <select
+          value={scope}
+          onChange={(e) => setScope(e.target.value as ApiTokenScope)}
+          className={input}
+        >
+          {SCOPES.map((s) => (
+            <option key={s.value} value={s.value}>
+              {s.label}
+            </option>
+          ))}
+        </select>
+        <button type="submit" disabled={busy || !name.trim()} className={button}>
+          Create
+        </button>
+      </form>
+
+      {created && (
+        <div className="mb-3 px-3 py-2 text-sm bg-yellow-50 rounded-md border border-yellow-200 space-y-2">
+          <p className="text-gray-700">Copy this secret now. It will not be shown again.</p>
+          <code className="block font-mono text-xs break-all text-gray-900">{created}</code>
+          <div className="flex gap-2">
+            <button onClick={() => navigator.clipboard?.writeText(created)} className={button}>
+              Copy
+            </button>
+            <button
+              onClick={() => {
+                saveBrowserToken(created);
+                setCreated(null);
+              }}
+              className={button}
+            >
+              Use in this browser
+            </button>
+            <button onClick={() => setCreated(null)} className={button}>
+              Done
+            </button>
+          </div>
+        </div>
+      )}
+
+      {tokens && tokens.length > 0 && (
+        <div className="overflow-hidden rounded-md border border-gray-200">
+          <table className="min-w-full divide-y divide-gray-200 text-sm">
+            <thead className="bg-gray-50">
+              <tr>
+                {["Name", "Scope", "Prefix", "Last used", ""].map((h) => (
+                  <th
+                    key={h}
+                    className="px-3 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider"
+                  >
+                    {h}
+                  </th>
+                ))}
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-gray-100 bg-white">
+              {tokens.map((t) => (
+                <tr key={t.id} className={t.revoked_at ? "text-gray-400" : undefined}>
+                  <td className="px-3 py-2">{t.name}</td>
+                  <td className="px-3 py-2 text-xs">{t.scope}</td>
+                  <td className="px-3 py-2 font-mono text-xs">{t.prefix}…</td>
+                  <td className="px-3 py-2 text-xs">
+                    {t.last_used_at ? <TimeAgo date={t.last_used_at} short /> : "never"}
+                  </td>
+                  <td className="px-3 py-2 text-right">
+                    {t.revoked_at ? (
+                      <span className="text-xs">revoked</span>
+                    ) : (
+                      <button onClick={() => handleRevoke(t)} className={button}>
+                        Revoke
+                      </button>
+                    )}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </section>
+  );
+}
@@ -0,0 +1,100 @@
+import { useCallback, useEffect, useState } from "react";
+import { api } from "../../api/client";
+import type { ToolReport } from "../../api/types";
+import { TimeAgo } from "../shared/TimeAgo";
+
+/** Which external tools conductor found on the server's PATH. */
+export function ToolsSection() {
+  const [report, setReport] = useState<ToolReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [checking, setChecking] = useState(false);
+
+  const load = useCallback((refresh: boolean) => {
+    setChecking(true);
+    api
+      .getTools(refresh)
+      .then((r) => {
+        setReport(r);
+        setError(null);
+      })
+      .catch((err: unknown) =>
+        setError(err instanceof Error ? err.message : "Failed to check tools"),
+      )
+      .finally(() => setChecking(false));
+  }, []);
+
+  useEffect(() => load(false), [load]);
+
+  return (
+    <section>
+      <div className="flex items-center justify-between mb-1">
+        <h3 className="text-sm font-semibold uppercase tracking-wider text-gray-400">
+          External Tools
+        </h3>
+        <button
+          onClick={() => load(true)}
+          disabled={checking}
+          className="px-2 py-1 text-xs font-medium text-gray-700 bg-gray-100 rounded hover:bg-gray-200 disabled:opacity-50"
+        >
+          {checking ? "Checking…" : "Re-check"}
+        </button>
+      </div>
+      <p className="text-sm text-gray-500 mb-3">
+        Command-line tools conductor shells out to. Features that need a missing tool are
+        unavailable until it is installed.
+      </p>
+
+      {error && (
+        <div className="mb-3 px-3 py-2 text-sm text-red-700 bg-red-50 rounded-md border border-red-200">
+          {error}
+        </div>
+      )}
+
+      {!report ? (
+        <div className="text-sm text-gray-400">Checking tools…</div>
+      ) : (
+        <div className="overflow-hidden rounded-md border border-gray-200">
+          <table className="min-w-full divide-y divide-gray-200 text-sm">
+            <tbody className="divide-y divide-gray-100 bg-white">
+              {report.tools.map((tool) => (
+                <tr key={tool.name}>
+                  <td className="px-3 py-2 font-mono text-xs text-gray-800">{tool.name}</td>
+                  <td className="px-3 py-2 text-xs">
+                    {tool.available ? (
+                      <span className="text-green-600">{tool.version ?? "installed"}</span>
+                    ) : (
+                      <span className={tool.required ? "text-red-600" : "text-gray-400"}>
+                        {tool.required ? "missing" : "not installed"}
+                      </span>
+                    )}
+                  </td>
+                  <td className="px-3 py-2 text-xs text-gray-500">
+                    {tool.purpose}
+                    {!tool.available && (
+                      <>
+                        {" — "}
+                        <a
+                          href={tool.install}
+                          target="_blank"
+                          rel="noreferrer"
+                          className="text-blue-600 hover:underline"
+                        >
+                          install
+                        </a>
+                      </>
+                    )}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+          <div className="px-3 py-2 bg-gray-50 border-t border-gray-200">
+            <p className="text-xs text-gray-400">
+              Checked <TimeAgo date={report.probed_at} />
+            </p>
+          </div>
+        </div>
+      )}
+    </section>
+  );
+}
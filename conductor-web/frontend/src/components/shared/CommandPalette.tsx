@@ -1,6 +1,8 @@
 import { useState, useEffect, useCallback, useRef, useMemo } from "react";
 import { useNavigate } from "react-router";
 import { useRepos } from "../layout/AppShell";
+import { api } from "../../api/client";
+import type { CommandInfo, CommandParam } from "../../api/types";
 
 interface CommandItem {
   id: string;
@@ -20,6 +22,67 @@ function fuzzyMatch(query: string, text: string): boolean {
   return qi === q.length;
 }
 
+/** An action from `GET /api/commands` whose parameters are being collected. */
+interface PendingCommand {
+  command: CommandInfo;
+  values: Record<string, unknown>;
+  index: number;
+}
+
+interface Choice {
+  id: string;
+  label: string;
+  value: unknown;
+  keywords?: string;
+}
+
+async function loadChoices(
+  param: CommandParam,
+  values: Record<string, unknown>,
+  repos: { id: string; slug: string }[],
+): Promise<Choice[]> {
+  switch (param.kind) {
+    case "repo":
+      return repos.map((r) => ({ id: r.id, label: r.slug, value: r.id }));
+    case "worktree": {
+      const worktrees = await api.listAllWorktrees();
+      return worktrees
+        .filter((wt) => !values.repo_id || wt.repo_id === values.repo_id)
+        .map((wt) => ({
+          id: wt.id,
+          label: wt.slug,
+          value: wt.id,
+          keywords: repos.find((r) => r.id === wt.repo_id)?.slug,
+        }));
+    }
+    case "workflow_run": {
+      const runs = await api.listAllWorkflowRuns(["pending", "running", "waiting", "failed"]);
+      return runs.map((run) => ({
+        id: run.id,
+        label: `${run.workflow_name} (${run.status})`,
+        value: run.id,
+      }));
+    }
+    case "workflow": {
+      const defs = await api.listWorkflowDefs(String(values.worktree_id));
+      return defs.map((d) => ({ id: d.name, label: d.name, value: d.name }));
+    }
+    case "bool":
+      return [
+        { id: "yes", label: "Yes", value: true },
+        { id: "no", label: "No", value: false },
+      ];
+    case "text":
+      return [];
+  }
+}
+
+function fillPath(command: CommandInfo, values: Record<string, unknown>): string {
+  return command.path.replace(/\{(\w+)\}/g, (_, name: string) =>
+    encodeURIComponent(String(values[name])),
+  );
+}
+
 interface CommandPaletteProps {
   open: boolean;
   onClose: () => void;
@@ -32,6 +95,13 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
   const listRef = useRef<HTMLDivElement>(null);
   const navigate = useNavigate();
   const { repos } = useRepos();
+  const [actions, setActions] = useState<CommandInfo[]>([]);
+  const [pending, setPending] = useState<PendingCommand | null>(null);
+  const [choices, setChoices] = useState<Choice[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [running, setRunning] = useState(false);
+
+  const param = pending ? pending.command.params[pending.index] : null;
 
   const go = useCallback(
     (path: string) => {
@@ -41,7 +111,75 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
     [navigate, onClose],
   );
 
+  const execute = useCallback(
+    async (command: CommandInfo, values: Record<string, unknown>) => {
+      const body: Record<string, unknown> = {};
+      for (const p of command.params) {
+        if (p.location === "body" && values[p.name] !== undefined) {
+          body[p.name] = values[p.name];
+        }
+      }
+      setRunning(true);
+      try {
+        await api.runCommand(command.method, fillPath(command, values), body);
+        onClose();
+      } catch (e) {
+        setError(`${command.label} failed: ${e instanceof Error ? e.message : String(e)}`);
+        setPending(null);
+      } finally {
+        setRunning(false);
+      }
+    },
+    [onClose],
+  );
+
+  /** Record `value` (or skip, when undefined) and move to the next param. */
+  const advance = useCallback(
+    (current: PendingCommand, value: unknown) => {
+      const name = current.command.params[current.index]?.name;
+      const values =
+        name !== undefined && value !== undefined ? { ...current.values, [name]: value } : current.values;
+      const index = current.index + 1;
+      setQuery("");
+      if (index >= current.command.params.length) {
+        void execute(current.command, values);
+      } else {
+        setPending({ command: current.command, values, index });
+      }
+    },
+    [execute],
+  );
+
+  const start = useCallback(
+    (command: CommandInfo) => {
+      setError(null);
+      const first = { command, values: {}, index: -1 };
+      advance(first, undefined);
+    },
+    [advance],
+  );
+
   const commands = useMemo<CommandItem[]>(() => {
+    if (pending && param) {
+      const section = `${pending.command.label}: ${param.label}${param.required ? "" : " (optional)"}`;
+      const items: CommandItem[] =
+        param.kind === "text"
+          ? query
+            ? [{ id: "text", label: `Use "${query}"`, section, action: () => advance(pending, query) }]
+            : []
+          : choices.map((c) => ({
+              id: c.id,
+              label: c.label,
+              section,
+              action: () => advance(pending, c.value),
+              keywords: c.keywords,
+            }));
+      if (!param.required) {
+        items.push({ id: "skip", label: "Skip", section, action: () => advance(pending, undefined) });
+      }
+      return items;
+    }
+
     const items: CommandItem[] = [
       { id: "nav-activity", label: "Activity", section: "Navigation", action: () => go("/"), keywords: "home dashboard" },
       { id: "nav-repos", label: "Repos", section: "Navigation", action: () => go("/repos"), keywords: "repositories stations" },
@@ -50,6 +188,16 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
       { id: "nav-settings", label: "Settings", section: "Navigation", action: () => go("/settings"), keywords: "config preferences" },
     ];
 
+    for (const command of actions) {
+      items.push({
+        id: `action-${command.id}`,
+        label: command.label,
+        section: "Actions",
+        action: () => start(command),
+        keywords: command.description,
+      });
+    }
+
     for (const repo of repos) {
       items.push({
         id: `repo-${repo.id}`,
@@ -61,10 +209,11 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
     }
 
     return items;
-  }, [repos, go]);
+  }, [repos, go, actions, start, pending, param, choices, query, advance]);
 
   const filtered = useMemo(() => {
-    if (!query) return commands;
+    // Free text is the value itself, not a filter.
+    if (!query || param?.kind === "text") return commands;
     return commands.filter(
       (c) =>
         fuzzyMatch(query, c.label) ||
@@ -78,15 +227,31 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
     setSelectedIndex(0);
   }, [filtered.length]);
 
-  // Focus input when opened
+  // Focus input and refresh the action list when opened
   useEffect(() => {
     if (open) {
       setQuery("");
       setSelectedIndex(0);
+      setPending(null);
+      setError(null);
+      api.listCommands().then(setActions).catch(() => setActions([]));
       requestAnimationFrame(() => inputRef.current?.focus());
     }
   }, [open]);
 
+  // Load the choices for the param being collected
+  useEffect(() => {
+    setChoices([]);
+    if (!pending || !param) return;
+    let cancelled = false;
+    loadChoices(param, pending.values, repos)
+      .then((list) => !cancelled && setChoices(list))
+      .catch((e) => !cancelled && setError(e instanceof Error ? e.message : String(e)));
+    return () => {
+      cancelled = true;
+    };
+  }, [pending, param, repos]);
+
   // Scroll selected item into view
   useEffect(() => {
     const el = listRef.current?.querySelector(`[data-index="${selectedIndex}"]`);
@@ -110,11 +275,16 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
           break;
         case "Escape":
           e.preventDefault();
-          onClose();
+          if (pending) {
+            setPending(null);
+            setQuery("");
+          } else {
+            onClose();
+          }
           break;
       }
     },
-    [filtered, selectedIndex, onClose],
+    [filtered, selectedIndex, onClose, pending],
   );
 
   if (!open) return null;
@@ -144,7 +314,8 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
             type="text"
             value={query}
             onChange={(e) => setQuery(e.target.value)}
-            placeholder="Search commands..."
+            placeholder={param ? `${param.label}...` : "Search commands..."}
+            disabled={running}
             className="flex-1 py-2.5 text-sm bg-transparent outline-none text-gray-900 placeholder-gray-400"
           />
           <kbd className="text-[10px] text-gray-400 font-mono border border-gray-200 rounded px-1 py-0.5">
@@ -152,9 +323,15 @@ export function CommandPalette({ open, onClose }: CommandPaletteProps) {
           </kbd>
         </div>
         <div ref={listRef} className="max-h-72 overflow-y-auto py-1">
-          {filtered.length === 0 && (
+          {error && (
+            <div className="px-3 py-2 text-sm text-red-600">{error}</div>
+          )}
+          {running && (
+            <div className="px-3 py-6 text-center text-sm text-gray-400">Running...</div>
+          )}
+          {!running && filtered.length === 0 && (
             <div className="px-3 py-6 text-center text-sm text-gray-400">
-              No results found
+              {param?.kind === "text" ? `Type the ${param.label.toLowerCase()}` : "No results found"}
             </div>
           )}
           {Array.from(sections.entries()).map(([section, items]) => (
@@ -2,6 +2,9 @@ const colors: Record<string, string> = {
   active: "bg-green-100 text-green-700",
   merged: "bg-blue-100 text-blue-700",
   abandoned: "bg-gray-100 text-gray-600",
+  needs_review: "bg-purple-100 text-purple-700",
+  parked: "bg-gray-100 text-gray-700",
+  promoted: "bg-blue-100 text-blue-700",
   open: "bg-green-100 text-green-700",
   closed: "bg-red-100 text-red-700",
   completed: "bg-green-100 text-green-800",
@@ -0,0 +1,90 @@
+import { useEffect, useState } from "react";
+import { Link } from "react-router";
+import { api } from "../../api/client";
+import type { TodayItem, TodayItemKind } from "../../api/types";
+import { StationHeader } from "./StationHeader";
+import { TimeAgo } from "./TimeAgo";
+
+interface TodayPanelProps {
+  /** Bumped by the parent whenever agent or worktree state may have changed. */
+  refreshKey: number;
+}
+
+const KIND_LABEL: Record<TodayItemKind, string> = {
+  awaiting_feedback: "Feedback",
+  failing_ci: "CI failing",
+  sla_breach: "SLA",
+  needs_review: "Review",
+  notification: "Notice",
+  running_agent: "Running",
+};
+
+const KIND_COLOR: Record<TodayItemKind, string> = {
+  awaiting_feedback: "bg-amber-100 text-amber-800",
+  failing_ci: "bg-red-100 text-red-700",
+  sla_breach: "bg-red-100 text-red-700",
+  needs_review: "bg-indigo-100 text-indigo-700",
+  notification: "bg-gray-100 text-gray-700",
+  running_agent: "bg-green-100 text-green-700",
+};
+
+/** In-app page for an item: its worktree, else its repo, else an in-app notification path. */
+function itemPath(item: TodayItem): string | null {
+  if (item.repo_id && item.worktree_id) {
+    return `/repos/${item.repo_id}/worktrees/${item.worktree_id}`;
+  }
+  if (item.repo_id) return `/repos/${item.repo_id}`;
+  if (item.url?.startsWith("/")) return item.url;
+  return null;
+}
+
+/** Everything needing attention across repos, highest priority first. */
+export function TodayPanel({ refreshKey }: TodayPanelProps) {
+  const [items, setItems] = useState<TodayItem[]>([]);
+
+  useEffect(() => {
+    api.listToday().then(setItems).catch(() => {});
+  }, [refreshKey]);
+
+  if (items.length === 0) return null;
+
+  return (
+    <section className="shrink-0">
+      <StationHeader count={items.length}>Today</StationHeader>
+      <ul className="rounded-lg border border-gray-200 bg-white divide-y divide-gray-100">
+        {items.map((item, i) => {
+          const path = itemPath(item);
+          const title = <span className="text-sm text-gray-800 truncate">{item.title}</span>;
+          return (
+            <li key={`${item.kind}:${item.run_id ?? item.ticket_id ?? item.notification_id ?? item.worktree_id ?? i}`}
+              className="flex items-center gap-2 px-3 py-2">
+              <span className={`shrink-0 px-1.5 py-0.5 rounded text-[10px] font-medium ${KIND_COLOR[item.kind]}`}>
+                {KIND_LABEL[item.kind]}
+              </span>
+              <div className="min-w-0 flex-1 flex flex-col">
+                {path ? (
+                  <Link to={path} className="hover:underline truncate">{title}</Link>
+                ) : (
+                  title
+                )}
+                <span className="text-xs text-gray-500 truncate">
+                  {item.repo_slug && <>{item.repo_slug} · </>}
+                  {item.detail}
+                </span>
+              </div>
+              {item.url && !item.url.startsWith("/") && (
+                <a href={item.url} target="_blank" rel="noopener noreferrer"
+                  className="shrink-0 text-xs text-indigo-600 hover:underline">
+                  Open
+                </a>
+              )}
+              <span className="shrink-0 text-xs text-gray-400">
+                <TimeAgo date={item.since} short />
+              </span>
+            </li>
+          );
+        })}
+      </ul>
+    </section>
+  );
+}
@@ -0,0 +1,63 @@
+import { useEffect, useState } from "react";
+import type { WatchTarget } from "../../api/types";
+import { api } from "../../api/client";
+
+interface WatchToggleProps {
+  targetType: WatchTarget;
+  targetId: string;
+}
+
+/** Watch/unwatch button. Watched items get state-change and PR-update notifications. */
+export function WatchToggle({ targetType, targetId }: WatchToggleProps) {
+  const [watched, setWatched] = useState<boolean | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    let cancelled = false;
+    api
+      .listWatches()
+      .then((watches) => {
+        if (cancelled) return;
+        setWatched(
+          watches.some((w) => w.target_type === targetType && w.target_id === targetId),
+        );
+      })
+      .catch(() => {
+        if (!cancelled) setWatched(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [targetType, targetId]);
+
+  async function toggle() {
+    if (watched === null) return;
+    setBusy(true);
+    try {
+      if (watched) {
+        await api.unwatch(targetType, targetId);
+      } else {
+        await api.watch(targetType, targetId);
+      }
+      setWatched(!watched);
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <button
+      onClick={toggle}
+      disabled={watched === null || busy}
+      aria-pressed={watched ?? false}
+      title={watched ? "Stop watching" : "Notify me about state and PR changes"}
+      className={`px-2 py-0.5 text-xs font-medium rounded border disabled:opacity-50 ${
+        watched
+          ? "border-indigo-300 bg-indigo-50 text-indigo-700"
+          : "border-gray-300 text-gray-600 hover:bg-gray-50"
+      }`}
+    >
+      {watched ? "Watching" : "Watch"}
+    </button>
+  );
+}
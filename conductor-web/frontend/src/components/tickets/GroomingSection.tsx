@@ -0,0 +1,134 @@
+import { useState } from "react";
+import { api } from "../../api/client";
+import { useApi } from "../../hooks/useApi";
+import type { GroomingProposal } from "../../api/types";
+import { LoadingSpinner } from "../shared/LoadingSpinner";
+
+interface Props {
+  repoId: string;
+  /** Called after a proposal is accepted, since that changes the ticket. */
+  onAccepted: () => void;
+}
+
+function ProposalDetails({ proposal }: { proposal: GroomingProposal }) {
+  const rows: [string, string][] = [];
+  if (proposal.labels.length > 0) rows.push(["Labels", proposal.labels.join(", ")]);
+  if (proposal.estimate) rows.push(["Estimate", proposal.estimate]);
+  if (proposal.duplicates.length > 0) {
+    rows.push(["Duplicates", proposal.duplicates.map((d) => `#${d}`).join(", ")]);
+  }
+  if (proposal.workflow) rows.push(["Workflow", proposal.workflow]);
+  return (
+    <dl className="mt-1 grid grid-cols-[6rem_1fr] gap-x-2 text-xs text-gray-600">
+      {rows.map(([label, value]) => (
+        <div key={label} className="contents">
+          <dt className="text-gray-400">{label}</dt>
+          <dd>{value}</dd>
+        </div>
+      ))}
+      {proposal.rationale && (
+        <p className="col-span-2 mt-1 italic text-gray-500">{proposal.rationale}</p>
+      )}
+    </dl>
+  );
+}
+
+export function GroomingSection({ repoId, onAccepted }: Props) {
+  const { data: proposals, loading, refetch } = useApi(
+    () => api.groomingProposals(repoId),
+    [repoId],
+  );
+  const [busyId, setBusyId] = useState<string | null>(null);
+  const [running, setRunning] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  async function handleRun() {
+    setRunning(true);
+    setMessage(null);
+    try {
+      const pass = await api.runGrooming(repoId);
+      setMessage(
+        `${pass.groomed} proposed, ${pass.failed} failed, $${pass.spent_usd.toFixed(2)} spent` +
+          (pass.budget_exhausted ? " — daily budget reached" : ""),
+      );
+      refetch();
+    } catch (err) {
+      setMessage(err instanceof Error ? err.message : "Grooming failed");
+    } finally {
+      setRunning(false);
+    }
+  }
+
+  async function handleDecide(id: string, accept: boolean) {
+    setBusyId(id);
+    setMessage(null);
+    try {
+      if (accept) {
+        await api.acceptGroomingProposal(id);
+        onAccepted();
+      } else {
+        await api.dismissGroomingProposal(id);
+      }
+      refetch();
+    } catch (err) {
+      setMessage(err instanceof Error ? err.message : "Update failed");
+    } finally {
+      setBusyId(null);
+    }
+  }
+
+  return (
+    <section>
+      <div className="flex items-center justify-between mb-3">
+        <h3 className="text-sm font-semibold uppercase tracking-wider text-gray-400">
+          Grooming Proposals
+        </h3>
+        <div className="flex items-center gap-3">
+          {message && <span className="text-xs text-gray-500">{message}</span>}
+          <button
+            onClick={handleRun}
+            disabled={running}
+            className="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 disabled:opacity-50"
+          >
+            {running ? "Grooming..." : "Groom New Tickets"}
+          </button>
+        </div>
+      </div>
+      {loading ? (
+        <LoadingSpinner />
+      ) : !proposals || proposals.length === 0 ? (
+        <p className="text-sm text-gray-400">No pending proposals.</p>
+      ) : (
+        <ul className="divide-y divide-gray-100 rounded-lg border border-gray-200 bg-white">
+          {proposals.map((p) => (
+            <li key={p.id} className="flex items-start justify-between gap-4 px-4 py-3">
+              <div className="min-w-0">
+                <p className="text-sm text-gray-800 truncate">
+                  <span className="text-gray-400 mr-2">#{p.ticket_source_id}</span>
+                  {p.ticket_title}
+                </p>
+                <ProposalDetails proposal={p} />
+              </div>
+              <div className="flex shrink-0 gap-2">
+                <button
+                  onClick={() => handleDecide(p.id, true)}
+                  disabled={busyId === p.id}
+                  className="px-2.5 py-1 text-xs rounded-md border border-indigo-300 text-indigo-700 hover:bg-indigo-50 disabled:opacity-50"
+                >
+                  Accept
+                </button>
+                <button
+                  onClick={() => handleDecide(p.id, false)}
+                  disabled={busyId === p.id}
+                  className="px-2.5 py-1 text-xs rounded-md border border-gray-300 text-gray-600 hover:bg-gray-50 disabled:opacity-50"
+                >
+                  Dismiss
+                </button>
+              </div>
+            </li>
+          ))}
+        </ul>
+      )}
+    </section>
+  );
+}
@@ -30,6 +30,9 @@ export function TicketCard({ ticket, agentTotals, repoSlug, onClick, selected, i
           <span className="text-gray-500">{repoSlug}</span>
         )}
         <span className="text-indigo-600 font-medium">{ticket.source_id}</span>
+        {ticket.source_type === "github_pr" && (
+          <span className="px-1 text-[10px] font-semibold rounded bg-purple-100 text-purple-700">PR</span>
+        )}
         <StatusBadge status={ticket.state} />
       </div>
       <p className="mt-1 text-sm text-gray-900">{ticket.title}</p>
@@ -3,9 +3,10 @@ import type { Ticket, TicketDependencies, TicketDetail } from "../../api/types";
 import { api } from "../../api/client";
 import { useApi } from "../../hooks/useApi";
 import { StatusBadge } from "../shared/StatusBadge";
+import { WatchToggle } from "../shared/WatchToggle";
 import { parseLabels, labelTextColor } from "../../utils/ticketUtils";
 import { formatDuration, formatTokens } from "../../utils/agentStats";
-import { deriveWorktreeSlug } from "../../utils/worktreeUtils";
+import { deriveWorktreeSlug, isLiveWorktree } from "../../utils/worktreeUtils";
 import { isSafeUrl } from "../../utils/urlUtils";
 import { CreateWorktreeForm } from "../worktrees/CreateWorktreeForm";
 
@@ -34,10 +35,29 @@ export function TicketDetailModal({ ticket, onClose, labelColorMap }: TicketDeta
   }, [onClose]);
 
   const [showCreateForm, setShowCreateForm] = useState(false);
+  const [comment, setComment] = useState("");
+  const [posting, setPosting] = useState(false);
+  const [commentError, setCommentError] = useState<string | null>(null);
+  const canWriteBack = ticket.source_type !== "manual";
+
+  async function handleComment(close: boolean) {
+    setPosting(true);
+    setCommentError(null);
+    try {
+      await api.commentOnTicket(ticket.id, comment, close);
+      setComment("");
+      refetch();
+    } catch (e) {
+      setCommentError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setPosting(false);
+    }
+  }
 
   const labels = parseLabels(ticket.labels);
   const totals = detail?.agent_totals;
   const worktrees = detail?.worktrees ?? [];
+  const attachments = detail?.attachments ?? [];
 
   return (
     <div
@@ -52,12 +72,15 @@ export function TicketDetailModal({ ticket, onClose, labelColorMap }: TicketDeta
           <h3 className="text-lg font-semibold text-gray-900 truncate pr-4">
             #{ticket.source_id} {ticket.title}
           </h3>
-          <button
-            onClick={onClose}
-            className="text-gray-400 hover:text-gray-600 text-xl leading-none"
-          >
-            &times;
-          </button>
+          <div className="flex items-center gap-3 shrink-0">
+            <WatchToggle targetType="ticket" targetId={ticket.id} />
+            <button
+              onClick={onClose}
+              className="text-gray-400 hover:text-gray-600 text-xl leading-none"
+            >
+              &times;
+            </button>
+          </div>
         </div>
 
         {/* Body */}
@@ -135,6 +158,43 @@ export function TicketDetailModal({ ticket, onClose, labelColorMap }: TicketDeta
             </div>
           )}
 
+          {/* Attachments */}
+          {attachments.length > 0 && (
+            <div>
+              <h4 className="text-xs font-semibold uppercase tracking-wider text-gray-400 mb-2">
+                Attachments
+              </h4>
+              <ul className="space-y-1.5">
+                {attachments.map((a) => (
+                  <li key={a.id} className="text-sm">
+                    <div className="flex items-center gap-2">
+                      {isSafeUrl(a.url) ? (
+                        <a
+                          href={a.url}
+                          target="_blank"
+                          rel="noopener noreferrer"
+                          className="text-indigo-600 hover:underline truncate"
+                        >
+                          {a.filename}
+                        </a>
+                      ) : (
+                        <span className="text-gray-900 truncate">{a.filename}</span>
+                      )}
+                      {a.mime_type && (
+                        <span className="text-xs text-gray-400">{a.mime_type}</span>
+                      )}
+                    </div>
+                    {a.local_path ? (
+                      <div className="font-mono text-xs text-gray-500 truncate">{a.local_path}</div>
+                    ) : a.download_error ? (
+                      <div className="text-xs text-gray-400">Not downloaded: {a.download_error}</div>
+                    ) : null}
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+
           {/* Dependencies */}
           {hasDependencies(detail?.dependencies) && (
             <div>
@@ -219,9 +279,9 @@ export function TicketDetailModal({ ticket, onClose, labelColorMap }: TicketDeta
                   >
                     <span
                       className={`inline-block w-2 h-2 rounded-full ${
-                        wt.status === "active"
+                        isLiveWorktree(wt)
                           ? "bg-green-500"
-                          : wt.status === "merged"
+                          : wt.status === "merged" || wt.status === "promoted"
                             ? "bg-blue-500"
                             : "bg-gray-400"
                       }`}
@@ -236,6 +296,39 @@ export function TicketDetailModal({ ticket, onClose, labelColorMap }: TicketDeta
               <p className="text-sm text-gray-400">No linked worktrees</p>
             )}
 
+            {canWriteBack && (
+              <div className="mt-4">
+                <h4 className="text-sm font-medium text-gray-700 mb-2">
+                  Comment on {ticket.source_type}
+                </h4>
+                <textarea
+                  value={comment}
+                  onChange={(e) => setComment(e.target.value)}
+                  rows={3}
+                  placeholder="Posted to the upstream tracker"
+                  className="w-full px-2 py-1.5 text-sm rounded-md border border-gray-300"
+                />
+                {commentError && (
+                  <p className="text-xs text-red-600 mt-1">{commentError}</p>
+                )}
+                <div className="flex justify-end gap-2 mt-2">
+                  <button
+                    disabled={posting || !comment.trim()}
+                    onClick={() => handleComment(true)}
+                    className="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 disabled:opacity-50"
+                  >
+                    Comment &amp; close
+                  </button>
+                  <button
+                    disabled={posting || !comment.trim()}
+                    onClick={() => handleComment(false)}
+                    className="px-3 py-1.5 text-sm rounded-md bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+                  >
+                    {posting ? "Posting..." : "Comment"}
+                  </button>
+                </div>
+              </div>
+            )}
           </div>
         </div>
 
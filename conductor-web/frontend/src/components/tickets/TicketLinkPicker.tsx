@@ -0,0 +1,103 @@
+import { useState, useEffect } from "react";
+import { api } from "../../api/client";
+import type { Ticket } from "../../api/types";
+
+/** Wait this long after the last keystroke before searching. */
+const SEARCH_DELAY_MS = 200;
+const MAX_RESULTS = 10;
+
+interface TicketLinkPickerProps {
+  repoId: string;
+  /** Ticket to leave out of the results, e.g. the one already linked */
+  excludeTicketId?: string | null;
+  /** Whether a link request is in flight */
+  disabled?: boolean;
+  /** Called with the ticket the user picked */
+  onSelect: (ticket: Ticket) => void;
+}
+
+export function TicketLinkPicker({
+  repoId,
+  excludeTicketId,
+  disabled,
+  onSelect,
+}: TicketLinkPickerProps) {
+  const [query, setQuery] = useState("");
+  const [results, setResults] = useState<Ticket[]>([]);
+  const [open, setOpen] = useState(false);
+
+  useEffect(() => {
+    if (!open) return;
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      api
+        .listTickets(repoId, false, query)
+        .then((list) => {
+          if (!cancelled) setResults(list.tickets);
+        })
+        .catch(() => {
+          if (!cancelled) setResults([]);
+        });
+    }, SEARCH_DELAY_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [repoId, query, open]);
+
+  const shown = results
+    .filter((t) => t.id !== excludeTicketId)
+    .slice(0, MAX_RESULTS);
+
+  function pick(ticket: Ticket) {
+    onSelect(ticket);
+    setQuery("");
+    setOpen(false);
+  }
+
+  return (
+    <span className="relative inline-block">
+      <input
+        type="search"
+        value={query}
+        disabled={disabled}
+        placeholder="Link ticket… (# or title)"
+        aria-label="Search tickets to link"
+        onFocus={() => setOpen(true)}
+        onBlur={() => setTimeout(() => setOpen(false), 150)}
+        onChange={(e) => setQuery(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && shown.length > 0) pick(shown[0]);
+          if (e.key === "Escape") setOpen(false);
+        }}
+        className="w-48 rounded border border-gray-300 bg-white text-gray-900 px-1 py-0.5 text-xs"
+      />
+      {open && (
+        <ul className="absolute z-10 mt-1 w-80 max-h-64 overflow-y-auto rounded border border-gray-200 bg-white shadow-lg">
+          {shown.length === 0 ? (
+            <li className="px-2 py-1 text-xs text-gray-400">No matching tickets</li>
+          ) : (
+            shown.map((t) => (
+              <li key={t.id}>
+                <button
+                  type="button"
+                  onMouseDown={(e) => e.preventDefault()}
+                  onClick={() => pick(t)}
+                  className="w-full flex items-center gap-2 px-2 py-1 text-xs text-left hover:bg-gray-50"
+                >
+                  <span className="font-mono text-gray-500">#{t.source_id}</span>
+                  <span
+                    className={t.state === "open" ? "text-green-600" : "text-gray-400"}
+                  >
+                    {t.state}
+                  </span>
+                  <span className="truncate text-gray-900">{t.title}</span>
+                </button>
+              </li>
+            ))
+          )}
+        </ul>
+      )}
+    </span>
+  );
+}
@@ -136,6 +136,9 @@ export function TicketRow({
             </Tooltip>
           )}
           <span className={depth > 0 ? "text-indigo-400" : "text-indigo-600"}>{ticket.source_id}</span>
+          {ticket.source_type === "github_pr" && (
+            <span className="px-1 text-[10px] font-semibold rounded bg-purple-100 text-purple-700">PR</span>
+          )}
         </span>
       </td>
       <td className="px-3 py-1.5 text-gray-900">{ticket.title}</td>
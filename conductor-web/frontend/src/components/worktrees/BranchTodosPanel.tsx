@@ -0,0 +1,75 @@
+import { useCallback, useEffect, useState } from "react";
+import { api } from "../../api/client";
+import type { BranchTodo } from "../../api/types";
+
+interface BranchTodosPanelProps {
+  worktreeId: string;
+  /** Changes when a run finishes, so the post-run scan is picked up. */
+  refreshKey?: string;
+  canScan: boolean;
+}
+
+/** TODO and FIXME comments the worktree's branch added, with a rescan button. */
+export function BranchTodosPanel({ worktreeId, refreshKey, canScan }: BranchTodosPanelProps) {
+  const [todos, setTodos] = useState<BranchTodo[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [scanning, setScanning] = useState(false);
+
+  const load = useCallback(async () => {
+    try {
+      setTodos(await api.getWorktreeTodos(worktreeId));
+    } catch {
+      // Stale entries are harmless; the next refresh retries.
+    }
+  }, [worktreeId]);
+
+  useEffect(() => { load(); }, [load, refreshKey]);
+
+  async function handleScan() {
+    setScanning(true);
+    setError(null);
+    try {
+      setTodos(await api.scanWorktreeTodos(worktreeId));
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setScanning(false);
+    }
+  }
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-1.5">
+        <h3 className="text-xs font-semibold uppercase tracking-wider text-gray-500">
+          Branch TODOs
+          <span className="ml-1.5 font-normal normal-case text-gray-600">({todos.length})</span>
+        </h3>
+        {canScan && (
+          <button
+            onClick={handleScan}
+            disabled={scanning}
+            className="text-xs text-indigo-600 hover:text-indigo-700 disabled:opacity-50"
+          >
+            {scanning ? "Scanning…" : "Rescan"}
+          </button>
+        )}
+      </div>
+      {error && <p className="mb-1.5 text-xs text-red-600">{error}</p>}
+      {todos.length > 0 && (
+        <div className="rounded-lg border border-gray-200 bg-white overflow-hidden">
+          <ul className="divide-y divide-gray-100">
+            {todos.map((todo) => (
+              <li key={`${todo.file}:${todo.line}`} className="px-3 py-2 flex items-baseline gap-2 text-sm">
+                <span className="text-xs font-mono text-gray-400 shrink-0">
+                  {todo.file}:{todo.line}
+                </span>
+                <span className="text-xs font-semibold text-amber-600">{todo.marker}</span>
+                <span className="text-gray-800 truncate">{todo.text}</span>
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,6 +1,21 @@
-import { useState } from "react";
+import { useEffect, useRef, useState } from "react";
 import { api } from "../../api/client";
+import { useConductorEvents } from "../../hooks/useConductorEvents";
+import { waitForWorktreeJob } from "../../utils/worktreeJobs";
 import { TrackSwitchIcon } from "../shared/RailwayIllustrations";
+import { isLiveWorktree } from "../../utils/worktreeUtils";
+import type { ResolvedBase, Worktree } from "../../api/types";
+
+function describeBase(base: ResolvedBase): string {
+  switch (base.source.kind) {
+    case "parent_ticket":
+      return `${base.branch} (parent ticket's worktree)`;
+    case "label_rule":
+      return `${base.branch} (label '${base.source.label}')`;
+    case "repo_default":
+      return `${base.branch} (repo default)`;
+  }
+}
 
 export function CreateWorktreeForm({
   repoId,
@@ -9,6 +24,7 @@ export function CreateWorktreeForm({
   onOpenChange,
   ticketId,
   initialName,
+  stackCandidates,
 }: {
   repoId: string;
   onCreated: () => void;
@@ -16,27 +32,64 @@ export function CreateWorktreeForm({
   onOpenChange?: (open: boolean) => void;
   ticketId?: string;
   initialName?: string;
+  /** Worktrees the new one may be stacked on (branched from their branch). */
+  stackCandidates?: Worktree[];
 }) {
   const [internalOpen, setInternalOpen] = useState(false);
   const open = controlledOpen ?? internalOpen;
   const setOpen = onOpenChange ?? setInternalOpen;
   const [name, setName] = useState(initialName ?? "");
   const [fromBranch, setFromBranch] = useState("");
+  const [stackOnId, setStackOnId] = useState("");
+  const stackParents = (stackCandidates ?? []).filter(isLiveWorktree);
+  const stackParent = stackParents.find((w) => w.id === stackOnId);
   const [error, setError] = useState<string | null>(null);
   const [submitting, setSubmitting] = useState(false);
+  const [progress, setProgress] = useState<string | null>(null);
+  const jobIdRef = useRef<string | null>(null);
+  const [resolvedBase, setResolvedBase] = useState<ResolvedBase | null>(null);
+
+  // Show which base the ticket resolves to before the user confirms.
+  useEffect(() => {
+    if (!open || !ticketId) {
+      setResolvedBase(null);
+      return;
+    }
+    let cancelled = false;
+    api
+      .resolveBaseBranch(repoId, ticketId)
+      .then((base) => {
+        if (!cancelled) setResolvedBase(base);
+      })
+      .catch(() => {
+        if (!cancelled) setResolvedBase(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, repoId, ticketId]);
+
+  useConductorEvents({
+    worktree_create_progress: (e) => {
+      if (e.data?.job_id === jobIdRef.current) setProgress(e.data?.label ?? null);
+    },
+  });
 
   async function handleSubmit(e: React.FormEvent) {
     e.preventDefault();
     setError(null);
     setSubmitting(true);
     try {
-      await api.createWorktree(repoId, {
+      const { job_id } = await api.createWorktree(repoId, {
         name,
-        from_branch: fromBranch || undefined,
+        from_branch: stackParent?.branch ?? (fromBranch || undefined),
         ticket_id: ticketId,
       });
+      jobIdRef.current = job_id;
+      await waitForWorktreeJob(job_id);
       setName(initialName ?? "");
       setFromBranch("");
+      setStackOnId("");
       setOpen(false);
       onCreated();
     } catch (err) {
@@ -44,6 +97,8 @@ export function CreateWorktreeForm({
         err instanceof Error ? err.message : "Failed to create worktree",
       );
     } finally {
+      jobIdRef.current = null;
+      setProgress(null);
       setSubmitting(false);
     }
   }
@@ -85,12 +140,37 @@ export function CreateWorktreeForm({
           </label>
           <input
             type="text"
-            value={fromBranch}
+            value={stackParent ? stackParent.branch : fromBranch}
             onChange={(e) => setFromBranch(e.target.value)}
-            placeholder="main"
-            className="mt-1 block w-full rounded-md border border-gray-300 px-3 py-2 text-sm focus:border-indigo-500 focus:ring-1 focus:ring-indigo-500"
+            disabled={!!stackParent}
+            placeholder={resolvedBase?.branch ?? "main"}
+            className="mt-1 block w-full rounded-md border border-gray-300 px-3 py-2 text-sm focus:border-indigo-500 focus:ring-1 focus:ring-indigo-500 disabled:bg-gray-50 disabled:text-gray-500"
           />
+          {resolvedBase && !stackParent && !fromBranch && (
+            <p className="mt-1 text-xs text-gray-500">
+              Base: {describeBase(resolvedBase)}
+            </p>
+          )}
         </div>
+        {stackParents.length > 0 && (
+          <div>
+            <label className="block text-sm font-medium text-gray-700">
+              Stack on worktree (optional)
+            </label>
+            <select
+              value={stackOnId}
+              onChange={(e) => setStackOnId(e.target.value)}
+              className="mt-1 block w-full rounded-md border border-gray-300 px-3 py-2 text-sm focus:border-indigo-500 focus:ring-1 focus:ring-indigo-500"
+            >
+              <option value="">None</option>
+              {stackParents.map((w) => (
+                <option key={w.id} value={w.id}>
+                  {w.slug} ({w.branch})
+                </option>
+              ))}
+            </select>
+          </div>
+        )}
       </div>
       {error && <p className="mt-2 text-sm text-red-600">{error}</p>}
       <div className="mt-3 flex gap-2">
@@ -99,7 +179,7 @@ export function CreateWorktreeForm({
           disabled={submitting}
           className="px-3 py-1.5 text-sm rounded-md bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
         >
-          {submitting ? "Creating..." : "Create"}
+          {submitting ? `${progress ?? "Creating"}...` : "Create"}
         </button>
         <button
           type="button"
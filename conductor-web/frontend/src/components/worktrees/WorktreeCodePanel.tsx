@@ -0,0 +1,207 @@
+import { useCallback, useEffect, useState } from "react";
+import { api } from "../../api/client";
+import type { WorktreeDiff, WorktreeListing } from "../../api/types";
+
+interface WorktreeCodePanelProps {
+  worktreeId: string;
+  /** Changes when a run finishes, so the diff is reloaded. */
+  refreshKey?: string;
+}
+
+function diffLineClass(line: string): string {
+  if (line.startsWith("+") && !line.startsWith("+++")) return "text-green-700 bg-green-50";
+  if (line.startsWith("-") && !line.startsWith("---")) return "text-red-700 bg-red-50";
+  if (line.startsWith("@@")) return "text-indigo-600";
+  return "text-gray-700";
+}
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+}
+
+/** Review a worktree's code: its diff against the base, or its files. */
+export function WorktreeCodePanel({ worktreeId, refreshKey }: WorktreeCodePanelProps) {
+  const [tab, setTab] = useState<"changes" | "files">("changes");
+  const [diff, setDiff] = useState<WorktreeDiff | null>(null);
+  const [openFile, setOpenFile] = useState<string | null>(null);
+  const [listing, setListing] = useState<WorktreeListing | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [commitMessage, setCommitMessage] = useState("");
+  const [committing, setCommitting] = useState(false);
+  const [committed, setCommitted] = useState<string | null>(null);
+
+  const loadDiff = useCallback(async () => {
+    try {
+      setDiff(await api.getWorktreeDiff(worktreeId));
+      setError(null);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  }, [worktreeId]);
+
+  const browse = useCallback(
+    async (path: string) => {
+      try {
+        setListing(await api.getWorktreeFiles(worktreeId, path));
+        setError(null);
+      } catch (e) {
+        setError(e instanceof Error ? e.message : String(e));
+      }
+    },
+    [worktreeId],
+  );
+
+  async function handleCommit(e: React.FormEvent) {
+    e.preventDefault();
+    setCommitting(true);
+    setCommitted(null);
+    try {
+      const commit = await api.commitWorktree(worktreeId, commitMessage.trim());
+      setCommitMessage("");
+      setCommitted(`Committed ${commit.files.length} file(s) as ${commit.sha.slice(0, 8)}`);
+      setError(null);
+      await loadDiff();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setCommitting(false);
+    }
+  }
+
+  useEffect(() => {
+    if (tab === "changes") loadDiff();
+  }, [tab, loadDiff, refreshKey]);
+
+  useEffect(() => {
+    if (tab === "files" && !listing) browse("");
+  }, [tab, listing, browse]);
+
+  const parent = listing?.path.includes("/")
+    ? listing.path.slice(0, listing.path.lastIndexOf("/"))
+    : "";
+
+  return (
+    <div>
+      <div className="flex items-center gap-3 mb-1.5">
+        {(["changes", "files"] as const).map((t) => (
+          <button
+            key={t}
+            onClick={() => setTab(t)}
+            className={`text-xs font-semibold uppercase tracking-wider ${
+              tab === t ? "text-gray-700" : "text-gray-400 hover:text-gray-600"
+            }`}
+          >
+            {t === "changes" ? `Changes${diff ? ` (${diff.files.length})` : ""}` : "Files"}
+          </button>
+        ))}
+      </div>
+      {error && <p className="mb-1.5 text-xs text-red-600">{error}</p>}
+
+      {tab === "changes" && diff && (
+        <div className="rounded-lg border border-gray-200 bg-white overflow-hidden">
+          {diff.files.length === 0 ? (
+            <p className="px-3 py-2 text-sm text-gray-500">No changes against the base branch.</p>
+          ) : (
+            <ul className="divide-y divide-gray-100">
+              {diff.files.map((file) => (
+                <li key={file.path}>
+                  <button
+                    onClick={() => setOpenFile(openFile === file.path ? null : file.path)}
+                    className="w-full px-3 py-2 flex items-baseline gap-2 text-left text-sm hover:bg-gray-50"
+                  >
+                    <span className="text-xs font-mono text-green-600 shrink-0">+{file.added}</span>
+                    <span className="text-xs font-mono text-red-600 shrink-0">-{file.removed}</span>
+                    <span className="font-mono text-gray-800 truncate">{file.path}</span>
+                  </button>
+                  {openFile === file.path && (
+                    <pre className="max-h-96 overflow-auto border-t border-gray-100 text-xs font-mono">
+                      {file.text.split("\n").map((line, i) => (
+                        <div key={i} className={`px-3 ${diffLineClass(line)}`}>
+                          {line || " "}
+                        </div>
+                      ))}
+                    </pre>
+                  )}
+                </li>
+              ))}
+            </ul>
+          )}
+          {diff.files.length > 0 && (
+            <form
+              onSubmit={handleCommit}
+              className="px-3 py-2 border-t border-gray-100 flex items-center gap-2"
+            >
+              <input
+                value={commitMessage}
+                onChange={(e) => setCommitMessage(e.target.value)}
+                placeholder="Commit message (blank: from the agent's result)"
+                className="block w-full rounded-md border border-gray-300 px-2 py-1 text-sm focus:border-indigo-500 focus:ring-1 focus:ring-indigo-500"
+              />
+              <button
+                type="submit"
+                disabled={committing}
+                className="shrink-0 px-2 py-1 text-xs rounded bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+              >
+                {committing ? "Committing..." : "Commit all"}
+              </button>
+            </form>
+          )}
+          {committed && <p className="px-3 pb-2 text-xs text-green-700">{committed}</p>}
+        </div>
+      )}
+
+      {tab === "files" && listing && (
+        <div className="rounded-lg border border-gray-200 bg-white overflow-hidden">
+          <div className="px-3 py-2 border-b border-gray-100 flex items-center gap-2 text-xs font-mono text-gray-500">
+            {listing.path !== "" && (
+              <button
+                onClick={() => browse(parent)}
+                className="text-indigo-600 hover:text-indigo-700"
+              >
+                ..
+              </button>
+            )}
+            <span className="truncate">/{listing.path}</span>
+          </div>
+          {listing.kind === "dir" ? (
+            <ul className="divide-y divide-gray-100">
+              {listing.entries.map((entry) => (
+                <li key={entry.path}>
+                  <button
+                    onClick={() => browse(entry.path)}
+                    className="w-full px-3 py-1.5 flex items-baseline justify-between gap-2 text-left text-sm hover:bg-gray-50"
+                  >
+                    <span className="font-mono text-gray-800 truncate">
+                      {entry.name}
+                      {entry.is_dir && "/"}
+                    </span>
+                    {entry.size != null && (
+                      <span className="text-xs text-gray-400 shrink-0">{formatSize(entry.size)}</span>
+                    )}
+                  </button>
+                </li>
+              ))}
+            </ul>
+          ) : listing.content == null ? (
+            <p className="px-3 py-2 text-sm text-gray-500">
+              Binary file, {formatSize(listing.size)}.
+            </p>
+          ) : (
+            <>
+              <pre className="max-h-[32rem] overflow-auto px-3 py-2 text-xs font-mono text-gray-800">
+                {listing.content}
+              </pre>
+              {listing.truncated && (
+                <p className="px-3 py-1.5 border-t border-gray-100 text-xs text-gray-500">
+                  Showing the first part of a {formatSize(listing.size)} file.
+                </p>
+              )}
+            </>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,71 @@
+import { useRef, useState } from "react";
+import { useNavigate } from "react-router";
+import { api } from "../../api/client";
+import { useConductorEvents } from "../../hooks/useConductorEvents";
+import { waitForWorktreeJob } from "../../utils/worktreeJobs";
+
+/**
+ * Paste a GitHub issue/PR, GitLab issue or Jira issue URL to create its
+ * worktree. The server picks the repo by remote and syncs the ticket, then
+ * this opens the new worktree.
+ */
+export function WorktreeFromUrlForm() {
+  const navigate = useNavigate();
+  const [url, setUrl] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [submitting, setSubmitting] = useState(false);
+  const [progress, setProgress] = useState<string | null>(null);
+  const jobIdRef = useRef<string | null>(null);
+
+  useConductorEvents({
+    worktree_create_progress: (e) => {
+      if (e.data?.job_id === jobIdRef.current) setProgress(e.data?.label ?? null);
+    },
+  });
+
+  async function handleSubmit(e: React.FormEvent) {
+    e.preventDefault();
+    setError(null);
+    setSubmitting(true);
+    try {
+      const { job_id, repo_id } = await api.createWorktreeFromUrl({
+        url: url.trim(),
+      });
+      jobIdRef.current = job_id;
+      const wt = await waitForWorktreeJob(job_id);
+      setUrl("");
+      navigate(`/repos/${repo_id}/worktrees/${wt.id}`);
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to create worktree",
+      );
+    } finally {
+      jobIdRef.current = null;
+      setProgress(null);
+      setSubmitting(false);
+    }
+  }
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-1">
+      <div className="flex gap-2">
+        <input
+          type="url"
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          placeholder="Paste an issue or PR URL to start a worktree"
+          required
+          className="block w-full rounded-md border border-gray-300 px-3 py-2 text-sm focus:border-indigo-500 focus:ring-1 focus:ring-indigo-500"
+        />
+        <button
+          type="submit"
+          disabled={submitting || !url.trim()}
+          className="shrink-0 px-3 py-2 text-sm rounded-md bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+        >
+          {submitting ? `${progress ?? "Creating"}...` : "Create"}
+        </button>
+      </div>
+      {error && <p className="text-sm text-red-600">{error}</p>}
+    </form>
+  );
+}
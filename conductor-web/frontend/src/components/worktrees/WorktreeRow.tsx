@@ -1,6 +1,6 @@
 import { useState, useEffect } from "react";
 import { Link } from "react-router";
-import type { Worktree, WorkflowRun } from "../../api/types";
+import type { StackInfo, Worktree, WorktreeGitStatus, WorkflowRun } from "../../api/types";
 import { TimeAgo } from "../shared/TimeAgo";
 import { Tooltip } from "../shared/Tooltip";
 import { formatDuration } from "../../utils/agentStats";
@@ -49,6 +49,28 @@ function LiveTimer({ startedAt, endedAt, estimatedMs }: { startedAt: string; end
   );
 }
 
+/** Uncommitted changes and commits not yet pushed (↑) or pulled (↓). */
+function GitStatusBadge({ status }: { status: WorktreeGitStatus }) {
+  const parts: string[] = [];
+  if (status.uncommitted > 0) parts.push(`${status.uncommitted} changed`);
+  if (!status.upstream) {
+    if (status.last_commit) parts.push("not pushed");
+  } else {
+    if (status.upstream.ahead > 0) parts.push(`↑${status.upstream.ahead}`);
+    if (status.upstream.behind > 0) parts.push(`↓${status.upstream.behind}`);
+  }
+  if (parts.length === 0) return null;
+  const commit = status.last_commit;
+  return (
+    <span
+      className="ml-1 text-amber-600"
+      title={commit ? `Last commit ${commit.sha.slice(0, 7)} ${commit.summary} (${commit.author})` : undefined}
+    >
+      · {parts.join(" ")}
+    </span>
+  );
+}
+
 export function WorktreeRow({
   worktree,
   workflowRun,
@@ -59,6 +81,11 @@ export function WorktreeRow({
   ticketSourceId,
   isMerged = false,
   isResuming = false,
+  stackDepth = 0,
+  stack,
+  onRestack,
+  isRestacking = false,
+  gitStatus,
 }: {
   worktree: Worktree;
   workflowRun?: WorkflowRun | null;
@@ -69,6 +96,12 @@ export function WorktreeRow({
   ticketSourceId?: string | null;
   isMerged?: boolean;
   isResuming?: boolean;
+  /** Nesting depth when stacked on another worktree (0 = not stacked). */
+  stackDepth?: number;
+  stack?: StackInfo | null;
+  onRestack?: (id: string) => void;
+  isRestacking?: boolean;
+  gitStatus?: WorktreeGitStatus | null;
 }) {
   const isRunning = workflowRun?.status === "running" || workflowRun?.status === "pending";
   const isWaiting = workflowRun?.status === "waiting";
@@ -119,16 +152,53 @@ export function WorktreeRow({
       data-list-index={index}
     >
       {/* Branch + Created */}
-      <td className="px-4 py-2">
+      <td className="px-4 py-2" style={stackDepth > 0 ? { paddingLeft: `${1 + stackDepth * 1.25}rem` } : undefined}>
         <Link
           to={`/repos/${worktree.repo_id}/worktrees/${worktree.id}`}
           className="text-indigo-600 hover:underline block"
         >
+          {stackDepth > 0 && <span className="text-gray-400 mr-1" aria-hidden="true">└</span>}
           {worktree.branch}
         </Link>
         <span className="text-[11px] text-gray-500">
           created <TimeAgo date={worktree.created_at} short /> ago
+          {stack && <> · on {stack.parent_slug}</>}
+          {gitStatus && <GitStatusBadge status={gitStatus} />}
+          {worktree.missing_since && (
+            <span
+              className="ml-1.5 text-red-600"
+              title={`Directory deleted outside conductor; noticed ${worktree.missing_since}`}
+            >
+              missing
+            </span>
+          )}
+          {worktree.ticket_change && (
+            <span
+              className="ml-1.5 text-amber-600"
+              title={`Linked ticket changed: ${worktree.ticket_change}`}
+            >
+              ticket changed
+            </span>
+          )}
         </span>
+        {stack && stack.state !== "up_to_date" && onRestack && (
+          <button
+            onClick={() => onRestack(worktree.id)}
+            disabled={isRestacking}
+            className="block mt-0.5 text-[11px] text-amber-600 hover:text-amber-800 disabled:opacity-50"
+            title={
+              stack.state === "parent_merged"
+                ? `${stack.parent_slug} was merged; rebase onto its base branch`
+                : `${stack.parent_slug} has new commits; rebase onto ${stack.parent_branch}`
+            }
+          >
+            {isRestacking
+              ? "Restacking…"
+              : stack.state === "parent_merged"
+                ? "Parent merged — restack"
+                : "Parent updated — restack"}
+          </button>
+        )}
       </td>
       {/* Ticket */}
       <td className="px-4 py-2">
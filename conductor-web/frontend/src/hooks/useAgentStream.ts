@@ -0,0 +1,56 @@
+import { useEffect, useRef } from "react";
+import { getApiOrigin, getStoredApiToken } from "../api/transport";
+import type { AgentStreamEvent, AgentStreamStatus } from "../api/types";
+
+interface AgentStreamHandlers {
+  onEvent: (event: AgentStreamEvent) => void;
+  onStatus?: (status: AgentStreamStatus, done: boolean) => void;
+}
+
+/**
+ * Streams a worktree's latest agent run log over SSE. The browser resumes
+ * from the last received offset on reconnect via `Last-Event-ID`; the stream
+ * closes once the server sends `done`.
+ */
+export function useAgentStream(
+  worktreeId: string | null,
+  handlers: AgentStreamHandlers,
+  runId?: string,
+) {
+  const handlersRef = useRef(handlers);
+  handlersRef.current = handlers;
+
+  useEffect(() => {
+    if (!worktreeId) return;
+    let source: EventSource | null = null;
+    let cancelled = false;
+
+    getApiOrigin().then((origin) => {
+      if (cancelled) return;
+      const params = new URLSearchParams();
+      // EventSource cannot send an Authorization header.
+      const token = getStoredApiToken();
+      if (token) params.set("token", token);
+      if (runId) params.set("run_id", runId);
+      const query = params.toString();
+      source = new EventSource(
+        `${origin}/api/worktrees/${worktreeId}/agent/stream${query ? `?${query}` : ""}`,
+      );
+      source.addEventListener("agent_event", (e) => {
+        handlersRef.current.onEvent(JSON.parse((e as MessageEvent).data));
+      });
+      source.addEventListener("heartbeat", (e) => {
+        handlersRef.current.onStatus?.(JSON.parse((e as MessageEvent).data), false);
+      });
+      source.addEventListener("done", (e) => {
+        handlersRef.current.onStatus?.(JSON.parse((e as MessageEvent).data), true);
+        source?.close();
+      });
+    });
+
+    return () => {
+      cancelled = true;
+      source?.close();
+    };
+  }, [worktreeId, runId]);
+}
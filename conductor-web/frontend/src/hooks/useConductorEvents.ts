@@ -1,21 +1,26 @@
 import { useEffect, useRef } from "react";
-import { getApiOrigin } from "../api/transport";
+import { getApiOrigin, getStoredApiToken } from "../api/transport";
 
 /** All SSE event types emitted by the backend. */
 export type ConductorEventType =
   | "repo_registered"
   | "repo_unregistered"
   | "worktree_created"
+  | "worktree_create_progress"
+  | "worktree_create_failed"
   | "worktree_deleted"
   | "tickets_synced"
   | "agent_started"
   | "agent_stopped"
+  | "agent_queue_changed"
   | "agent_event"
   | "repo_agent_started"
   | "repo_agent_stopped"
   | "feedback_requested"
   | "feedback_submitted"
+  | "budget_exceeded"
   | "issue_sources_changed"
+  | "notifications_changed"
   | "lagged";
 
 export interface ConductorEventData {
@@ -29,16 +34,21 @@ const ALL_EVENT_TYPES: ConductorEventType[] = [
   "repo_registered",
   "repo_unregistered",
   "worktree_created",
+  "worktree_create_progress",
+  "worktree_create_failed",
   "worktree_deleted",
   "tickets_synced",
   "agent_started",
   "agent_stopped",
+  "agent_queue_changed",
   "agent_event",
   "repo_agent_started",
   "repo_agent_stopped",
   "feedback_requested",
   "feedback_submitted",
+  "budget_exceeded",
   "issue_sources_changed",
+  "notifications_changed",
   "lagged",
 ];
 
@@ -69,7 +79,10 @@ function dispatch(eventType: ConductorEventType, e: MessageEvent) {
 }
 
 function connectSource(origin: string) {
-  const source = new EventSource(`${origin}/api/events`);
+  // EventSource cannot send an Authorization header.
+  const token = getStoredApiToken();
+  const query = token ? `?token=${encodeURIComponent(token)}` : "";
+  const source = new EventSource(`${origin}/api/events${query}`);
   sharedSource = source;
 
   for (const type of ALL_EVENT_TYPES) {
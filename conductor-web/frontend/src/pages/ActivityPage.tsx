@@ -15,7 +15,10 @@ import { TimeAgo } from "../components/shared/TimeAgo";
 import { LoadingSpinner } from "../components/shared/LoadingSpinner";
 import { WelcomeAboard } from "../components/shared/WelcomeAboard";
 import { ErrorBanner } from "../components/shared/ErrorBanner";
+import { AgentInbox } from "../components/agents/AgentInbox";
+import { TodayPanel } from "../components/shared/TodayPanel";
 import { agentStatusColor } from "../utils/agentStats";
+import { isLiveWorktree } from "../utils/worktreeUtils";
 import {
   useConductorEvents,
   type ConductorEventType,
@@ -60,7 +63,7 @@ export function ActivityPage() {
         const repoId = wt.repo_id;
         const slug = repoSlugById[repoId] ?? "";
         counts[repoId] = (counts[repoId] ?? 0) + 1;
-        if (wt.status === "active") {
+        if (isLiveWorktree(wt)) {
           active.push({ ...wt, repoId, repoSlug: slug });
         }
       }
@@ -69,7 +72,7 @@ export function ActivityPage() {
 
       const feedbackWorktrees: { worktreeId: string; ctx: WorktreeContext & { branch: string; worktreeId: string } }[] = [];
       for (const wt of allWorktrees) {
-        if (wt.status !== "active") continue;
+        if (!isLiveWorktree(wt)) continue;
         const repoId = wt.repo_id;
         const slug = repoSlugById[repoId] ?? "";
         if (wt.agent_status === "waiting_for_feedback") {
@@ -220,6 +223,12 @@ export function ActivityPage() {
 
       {/* Main content area */}
       <div className="flex-1 flex flex-col gap-3 min-h-0 overflow-y-auto">
+        {/* What needs attention, highest priority first */}
+        <TodayPanel refreshKey={wtTick} />
+
+        {/* Finished runs not yet reviewed */}
+        <AgentInbox refreshKey={wtTick} onError={setActionError} />
+
         {/* Active Worktrees */}
         <section className="flex flex-col shrink-0">
           <StationHeader count={activeWorktrees.length}>Active Worktrees</StationHeader>
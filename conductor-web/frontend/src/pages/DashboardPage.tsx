@@ -2,17 +2,20 @@ import { useEffect, useState, useCallback, useMemo } from "react";
 import { Link, useNavigate } from "react-router";
 import { useRepos } from "../components/layout/AppShell";
 import { api } from "../api/client";
-import type { WorktreeWithStatus } from "../api/types";
+import type { WorkspaceUsage, WorktreeWithStatus } from "../api/types";
 import { RepoCard } from "../components/repos/RepoCard";
 import { RegisterRepoForm } from "../components/repos/RegisterRepoForm";
+import { WorktreeFromUrlForm } from "../components/worktrees/WorktreeFromUrlForm";
 import { GitHubDiscoverModal } from "../components/repos/GitHubDiscoverModal";
 import { StatusBadge } from "../components/shared/StatusBadge";
 import { TimeAgo } from "../components/shared/TimeAgo";
 import { LoadingSpinner } from "../components/shared/LoadingSpinner";
 import { EmptyState } from "../components/shared/EmptyState";
 import { ErrorBanner } from "../components/shared/ErrorBanner";
+import { ConfirmDialog } from "../components/shared/ConfirmDialog";
 import { WelcomeAboard } from "../components/shared/WelcomeAboard";
-import { agentStatusColor } from "../utils/agentStats";
+import { agentStatusColor, isActiveRun } from "../utils/agentStats";
+import { isLiveWorktree } from "../utils/worktreeUtils";
 import {
   useConductorEvents,
   type ConductorEventType,
@@ -30,10 +33,16 @@ export function DashboardPage() {
   const [activeWorktrees, setActiveWorktrees] = useState<
     (WorktreeWithStatus & { repoSlug: string })[]
   >([]);
+  const [quotaByRepo, setQuotaByRepo] = useState<Record<string, WorkspaceUsage>>(
+    {},
+  );
   const [wtTick, setWtTick] = useState(0);
   const [loadError, setLoadError] = useState<string | null>(null);
   const [registerRepoOpen, setRegisterRepoOpen] = useState(false);
   const [discoverOpen, setDiscoverOpen] = useState(false);
+  const [stopAllConfirm, setStopAllConfirm] = useState(false);
+  const [stoppingAll, setStoppingAll] = useState(false);
+  const [stopAllResult, setStopAllResult] = useState<string | null>(null);
 
   const refreshWorktrees = useCallback(() => setWtTick((n) => n + 1), []);
 
@@ -48,7 +57,7 @@ export function DashboardPage() {
       const active: (WorktreeWithStatus & { repoSlug: string })[] = [];
       for (const wt of allWorktrees) {
         counts[wt.repo_id] = (counts[wt.repo_id] ?? 0) + 1;
-        if (wt.status === "active") {
+        if (isLiveWorktree(wt)) {
           active.push({ ...wt, repoSlug: repoSlugById[wt.repo_id] ?? "" });
         }
       }
@@ -62,6 +71,18 @@ export function DashboardPage() {
     });
   }, [repos, wtTick]);
 
+  // Workspace quota scans walk the disk, so only refresh when worktrees change.
+  useEffect(() => {
+    api
+      .getWorkspaceQuota()
+      .then((usage) => {
+        const byRepo: Record<string, WorkspaceUsage> = {};
+        for (const u of usage) byRepo[u.repo_id] = u;
+        setQuotaByRepo(byRepo);
+      })
+      .catch(() => setQuotaByRepo({}));
+  }, [repos, wtTick]);
+
   const handlers = useMemo(() => {
     const handleWorktreeChange = (_data: ConductorEventData) =>
       refreshWorktrees();
@@ -78,6 +99,28 @@ export function DashboardPage() {
 
   useConductorEvents(handlers);
 
+  const runningAgents = activeWorktrees.filter(
+    (wt) => wt.agent_status && isActiveRun({ status: wt.agent_status }),
+  ).length;
+
+  async function handleStopAll() {
+    setStoppingAll(true);
+    try {
+      const summary = await api.stopAllAgents();
+      const queued = summary.queued > 0
+        ? ` ${summary.queued} queued run(s) will still start.`
+        : "";
+      setStopAllResult(`Stopped ${summary.stopped.length} agent run(s).${queued}`);
+      setLoadError(null);
+      refreshWorktrees();
+    } catch (err) {
+      setLoadError(err instanceof Error ? err.message : "Failed to stop agents");
+    } finally {
+      setStoppingAll(false);
+      setStopAllConfirm(false);
+    }
+  }
+
   const { selectedIndex, moveDown, moveUp, reset } = useListNav(activeWorktrees.length);
 
   const openSelected = useCallback(() => {
@@ -112,6 +155,14 @@ export function DashboardPage() {
       <div className="flex flex-col sm:flex-row sm:items-center sm:justify-between gap-2">
         <h2 className="text-lg font-bold text-gray-900">Dashboard</h2>
         <div className="flex flex-wrap items-center gap-2">
+          {runningAgents > 0 && (
+            <button
+              onClick={() => setStopAllConfirm(true)}
+              className="px-3 py-2 text-sm rounded-md border border-red-300 text-red-600 hover:bg-red-50"
+            >
+              Stop all agents ({runningAgents})
+            </button>
+          )}
           <button
             onClick={() => setDiscoverOpen(true)}
             className="px-3 py-2 text-sm rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50"
@@ -127,7 +178,26 @@ export function DashboardPage() {
         onImported={refreshRepos}
       />
 
+      <ConfirmDialog
+        open={stopAllConfirm}
+        title="Stop All Agents"
+        message={`Stop all ${runningAgents} running agent(s)? Each run is marked cancelled and its process killed; logs are kept.`}
+        onConfirm={handleStopAll}
+        onCancel={() => setStopAllConfirm(false)}
+        loading={stoppingAll}
+      />
+
+      <WorktreeFromUrlForm />
+
       <ErrorBanner error={loadError} />
+      {stopAllResult && (
+        <div className="flex items-center justify-between rounded-md border border-gray-200 bg-gray-50 px-3 py-2 text-sm text-gray-700">
+          <span>{stopAllResult}</span>
+          <button onClick={() => setStopAllResult(null)} className="text-xs text-gray-500 hover:text-gray-700">
+            Dismiss
+          </button>
+        </div>
+      )}
 
       {/* Repos */}
       <section>
@@ -143,6 +213,7 @@ export function DashboardPage() {
                 key={repo.id}
                 repo={repo}
                 worktreeCount={worktreeCounts[repo.id] ?? 0}
+                usage={quotaByRepo[repo.id]}
               />
             ))}
           </div>
@@ -11,6 +11,7 @@ import { TicketCard } from "../components/tickets/TicketCard";
 import { RepoAgentRunCard } from "../components/agents/RepoAgentRunCard";
 import { TicketDetailModal } from "../components/tickets/TicketDetailModal";
 import { IssueSourcesSection } from "../components/issue-sources/IssueSourcesSection";
+import { GroomingSection } from "../components/tickets/GroomingSection";
 import { StatusBadge } from "../components/shared/StatusBadge";
 import { ColumnHeader, type SortDirection } from "../components/shared/ColumnHeader";
 import { parseLabels, getPipelineStatus, filterTicketsByColumns, sortTickets } from "../utils/ticketUtils";
@@ -19,7 +20,9 @@ import { LoadingSpinner } from "../components/shared/LoadingSpinner";
 import { EmptyState } from "../components/shared/EmptyState";
 import { ModelPicker } from "../components/shared/ModelPicker";
 import { buildTicketTree } from "../utils/ticketDeps";
-import { deriveWorktreeSlug } from "../utils/worktreeUtils";
+import { deriveWorktreeSlug, stackOrder } from "../utils/worktreeUtils";
+import { waitForWorktreeJob } from "../utils/worktreeJobs";
+import { toolchainLabel } from "../utils/toolchain";
 import {
   useConductorEvents,
   type ConductorEventType,
@@ -43,6 +46,31 @@ export function RepoDetailPage() {
     refetch: refetchWorktrees,
   } = useApi(() => api.listWorktrees(repoId!, showCompletedWorktrees), [repoId, showCompletedWorktrees]);
 
+  const { data: stacks, refetch: refetchStacks } = useApi(
+    () => api.listWorktreeStacks(repoId!),
+    [repoId],
+  );
+  const stackByWorktreeId = useMemo(
+    () => new Map((stacks ?? []).map((s) => [s.worktree_id, s])),
+    [stacks],
+  );
+  // Stacked worktrees are listed directly under their parent.
+  const orderedWorktrees = useMemo(() => stackOrder(worktrees ?? []), [worktrees]);
+  const [restackingId, setRestackingId] = useState<string | null>(null);
+
+  async function handleRestack(id: string) {
+    setRestackingId(id);
+    try {
+      await api.restackWorktree(id);
+      refetchWorktrees();
+      refetchStacks();
+    } catch (err) {
+      setActionError(err instanceof Error ? err.message : "Restack failed");
+    } finally {
+      setRestackingId(null);
+    }
+  }
+
   const {
     data: ticketList,
     loading: ticketsLoading,
@@ -160,7 +188,10 @@ export function RepoDetailPage() {
 
   const sseHandlers = useMemo(() => {
     const handleWorktreeChange = (ev: ConductorEventData) => {
-      if (!ev.data || ev.data.repo_id === repoId) refetchWorktrees();
+      if (!ev.data || ev.data.repo_id === repoId) {
+        refetchWorktrees();
+        refetchStacks();
+      }
     };
     const handleTicketsChange = (ev: ConductorEventData) => {
       if (!ev.data || ev.data.repo_id === repoId) refetchTickets();
@@ -188,7 +219,7 @@ export function RepoDetailPage() {
       },
     };
     return map;
-  }, [repoId, refetchWorktrees, refetchTickets, refetchRuns, refetchTotals, refetchSources, refetchRepoAgentRuns]);
+  }, [repoId, refetchWorktrees, refetchStacks, refetchTickets, refetchRuns, refetchTotals, refetchSources, refetchRepoAgentRuns]);
 
   useConductorEvents(sseHandlers);
 
@@ -196,6 +227,7 @@ export function RepoDetailPage() {
   const [syncing, setSyncing] = useState(false);
   const [syncResult, setSyncResult] = useState<string | null>(null);
   const [togglingAgentIssues, setTogglingAgentIssues] = useState(false);
+  const [detectingToolchain, setDetectingToolchain] = useState(false);
   const [deleteTarget, setDeleteTarget] = useState<string | null>(null);
   const [deleting, setDeleting] = useState(false);
   const [unregisterRepoConfirm, setUnregisterRepoConfirm] = useState(false);
@@ -302,11 +334,12 @@ export function RepoDetailPage() {
         const raw = JSON.parse(ticket.raw_json);
         if (raw.base_branch) fromBranch = raw.base_branch;
       } catch { /* ignore */ }
-      const wt = await api.createWorktree(repoId!, {
+      const { job_id } = await api.createWorktree(repoId!, {
         name: wtName,
         ticket_id: ticket.id,
         from_branch: fromBranch,
       });
+      const wt = await waitForWorktreeJob(job_id);
       const result = await api.runWorkflow(wt.id, {
         name: "ticket-to-pr",
         inputs: {
@@ -402,6 +435,18 @@ export function RepoDetailPage() {
     }
   }
 
+  async function handleDetectToolchain() {
+    setDetectingToolchain(true);
+    try {
+      await api.detectRepoToolchain(repoId!);
+      refreshRepos();
+    } catch (err) {
+      setActionError(err instanceof Error ? err.message : "Failed to detect toolchain");
+    } finally {
+      setDetectingToolchain(false);
+    }
+  }
+
   async function handleToggleAgentIssues() {
     if (!repo) return;
     setTogglingAgentIssues(true);
@@ -421,16 +466,16 @@ export function RepoDetailPage() {
   const { selectedIndex, moveDown, moveUp, reset } = useListNav(wtCount);
 
   const openSelectedWt = useCallback(() => {
-    const wt = worktrees?.[selectedIndex];
+    const wt = orderedWorktrees[selectedIndex]?.worktree;
     if (wt) navigate(`/repos/${repoId}/worktrees/${wt.id}`);
-  }, [worktrees, selectedIndex, navigate, repoId]);
+  }, [orderedWorktrees, selectedIndex, navigate, repoId]);
 
   const openCreateWt = useCallback(() => setCreateWtOpen(true), []);
 
   const deleteSelectedWt = useCallback(() => {
-    const wt = worktrees?.[selectedIndex];
+    const wt = orderedWorktrees[selectedIndex]?.worktree;
     if (wt) setDeleteTarget(wt.id);
-  }, [worktrees, selectedIndex]);
+  }, [orderedWorktrees, selectedIndex]);
 
   const handleEscape = useCallback(() => {
     if (selectedTicket) {
@@ -525,6 +570,21 @@ export function RepoDetailPage() {
           <dd className="truncate">{repo.local_path}</dd>
           <dt className="font-medium text-gray-500">Default Branch</dt>
           <dd>{repo.default_branch}</dd>
+          <dt className="font-medium text-gray-500">Toolchain</dt>
+          <dd>
+            <span className="flex items-center gap-2">
+              <span className={repo.toolchain?.language ? "" : "text-gray-400"}>
+                {repo.toolchain?.language ? toolchainLabel(repo.toolchain) : "Not detected"}
+              </span>
+              <button
+                onClick={handleDetectToolchain}
+                disabled={detectingToolchain}
+                className="px-2 py-0.5 text-xs rounded border border-gray-300 text-gray-600 hover:bg-gray-50 disabled:opacity-50"
+              >
+                Re-detect
+              </button>
+            </span>
+          </dd>
           <dt className="font-medium text-gray-500">Model</dt>
           <dd>
             {editingModel ? (
@@ -751,7 +811,7 @@ export function RepoDetailPage() {
               {showCompletedWorktrees ? "Hiding active only" : "Show completed"}
             </button>
             <OnboardingHint target="create-worktree" label="Start here">
-              <CreateWorktreeForm repoId={repoId!} onCreated={refetchWorktrees} open={createWtOpen} onOpenChange={setCreateWtOpen} />
+              <CreateWorktreeForm repoId={repoId!} onCreated={refetchWorktrees} open={createWtOpen} onOpenChange={setCreateWtOpen} stackCandidates={worktrees ?? undefined} />
             </OnboardingHint>
           </div>
         </div>
@@ -773,7 +833,7 @@ export function RepoDetailPage() {
                 </tr>
               </thead>
               <tbody className="divide-y divide-gray-100">
-                {worktrees.map((wt, index) => (
+                {orderedWorktrees.map(({ worktree: wt, depth }, index) => (
                   <WorktreeRow
                     key={wt.id}
                     worktree={wt}
@@ -785,6 +845,11 @@ export function RepoDetailPage() {
                     ticketSourceId={wt.ticket_id ? ticketSourceIdMap.get(wt.ticket_id) : null}
                     isMerged={prs?.some((pr) => pr.head_ref_name === wt.branch && pr.state === "MERGED") ?? false}
                     isResuming={resumingWorkflowId === workflowRunByWorktreeId.get(wt.id)?.id}
+                    stackDepth={depth}
+                    stack={stackByWorktreeId.get(wt.id)}
+                    onRestack={handleRestack}
+                    isRestacking={restackingId === wt.id}
+                    gitStatus={wt.git_status}
                   />
                 ))}
               </tbody>
@@ -880,6 +945,10 @@ export function RepoDetailPage() {
         )}
       </section>
 
+      {issueSources && issueSources.length > 0 && (
+        <GroomingSection repoId={repoId!} onAccepted={refetchTickets} />
+      )}
+
       {/* Error toast — fixed at top so it's always visible */}
       {actionError && (
         <div className="fixed top-4 left-1/2 -translate-x-1/2 z-50 max-w-lg w-full px-4 py-3 text-sm text-red-700 bg-red-50 border border-red-200 rounded-lg shadow-lg flex items-center justify-between">
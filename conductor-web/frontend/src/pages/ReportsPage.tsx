@@ -0,0 +1,125 @@
+import { useState, useEffect } from "react";
+import { api } from "../api/client";
+import type { CostGroupBy, CostReportRow } from "../api/types";
+import { LoadingSpinner } from "../components/shared/LoadingSpinner";
+
+const WINDOWS = ["7d", "30d", "90d"] as const;
+const GROUPINGS: CostGroupBy[] = ["repo", "ticket", "worktree", "day"];
+
+function fmtDuration(ms: number): string {
+  const s = Math.round(ms / 1000);
+  if (s >= 3600) return `${Math.floor(s / 3600)}h${Math.floor((s % 3600) / 60).toString().padStart(2, "0")}m`;
+  return `${Math.floor(s / 60)}m${(s % 60).toString().padStart(2, "0")}s`;
+}
+
+function fmtCost(usd: number): string {
+  return `$${usd.toFixed(2)}`;
+}
+
+export function ReportsPage() {
+  const [since, setSince] = useState<(typeof WINDOWS)[number]>("30d");
+  const [groupBy, setGroupBy] = useState<CostGroupBy>("repo");
+  const [rows, setRows] = useState<CostReportRow[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setLoading(true);
+    setError(null);
+    api
+      .getCostReport(since, groupBy)
+      .then(setRows)
+      .catch((e) => setError(e instanceof Error ? e.message : String(e)))
+      .finally(() => setLoading(false));
+  }, [since, groupBy]);
+
+  const maxCost = Math.max(0, ...rows.map((r) => r.cost_usd));
+  const total = rows.reduce(
+    (acc, r) => ({
+      runs: acc.runs + r.runs,
+      cost_usd: acc.cost_usd + r.cost_usd,
+      turns: acc.turns + r.turns,
+      duration_ms: acc.duration_ms + r.duration_ms,
+    }),
+    { runs: 0, cost_usd: 0, turns: 0, duration_ms: 0 },
+  );
+
+  const toggleClass = (active: boolean) =>
+    `px-2 py-0.5 rounded ${active ? "bg-indigo-100 text-indigo-700 font-medium" : "text-gray-500 hover:text-gray-700"}`;
+
+  return (
+    <div className="space-y-6">
+      <div>
+        <h2 className="text-xl font-bold text-gray-900">Agent Costs</h2>
+        <p className="text-sm text-gray-500 mt-1">
+          Cost, turns and time of every agent run started in the window.
+        </p>
+      </div>
+
+      <div className="flex items-center gap-4 text-xs">
+        <div className="flex items-center gap-2">
+          {GROUPINGS.map((g) => (
+            <button key={g} onClick={() => setGroupBy(g)} className={toggleClass(groupBy === g)}>
+              {g}
+            </button>
+          ))}
+        </div>
+        <div className="flex items-center gap-2 ml-auto">
+          {WINDOWS.map((w) => (
+            <button key={w} onClick={() => setSince(w)} className={toggleClass(since === w)}>
+              {w}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      {loading ? (
+        <LoadingSpinner />
+      ) : error ? (
+        <p className="text-sm text-red-500">{error}</p>
+      ) : rows.length === 0 ? (
+        <p className="text-sm text-gray-500">No agent runs in the last {since}.</p>
+      ) : (
+        <div className="rounded-lg border border-gray-200 bg-white overflow-hidden">
+          <table className="w-full text-xs">
+            <thead>
+              <tr className="border-b border-gray-200 bg-gray-50 text-gray-500 text-left">
+                <th className="px-4 py-2 font-medium capitalize">{groupBy}</th>
+                <th className="px-4 py-2 font-medium tabular-nums">Runs</th>
+                <th className="px-4 py-2 font-medium tabular-nums">Cost</th>
+                <th className="px-4 py-2 font-medium tabular-nums">Turns</th>
+                <th className="px-4 py-2 font-medium tabular-nums">Duration</th>
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-gray-100">
+              {rows.map((row) => (
+                <tr key={row.key} className="hover:bg-gray-50">
+                  <td className="px-4 py-2 font-medium text-gray-800">
+                    <div>{row.label}</div>
+                    <div className="mt-1 h-1 rounded bg-gray-100">
+                      <div
+                        className="h-1 rounded bg-indigo-400"
+                        style={{ width: `${maxCost > 0 ? (row.cost_usd / maxCost) * 100 : 0}%` }}
+                      />
+                    </div>
+                  </td>
+                  <td className="px-4 py-2 tabular-nums text-gray-600">{row.runs}</td>
+                  <td className="px-4 py-2 tabular-nums text-gray-600">{fmtCost(row.cost_usd)}</td>
+                  <td className="px-4 py-2 tabular-nums text-gray-600">{row.turns}</td>
+                  <td className="px-4 py-2 tabular-nums text-gray-600">{fmtDuration(row.duration_ms)}</td>
+                </tr>
+              ))}
+              <tr className="bg-gray-50 font-medium text-gray-800">
+                <td className="px-4 py-2">Total</td>
+                <td className="px-4 py-2 tabular-nums">{total.runs}</td>
+                <td className="px-4 py-2 tabular-nums">{fmtCost(total.cost_usd)}</td>
+                <td className="px-4 py-2 tabular-nums">{total.turns}</td>
+                <td className="px-4 py-2 tabular-nums">{fmtDuration(total.duration_ms)}</td>
+              </tr>
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -5,6 +5,8 @@ import { api } from "../api/client";
 import type { HookSummary } from "../api/types";
 import { ModelPicker } from "../components/shared/ModelPicker";
 import { ThemePicker } from "../components/shared/ThemePicker";
+import { ApiTokensSection } from "../components/settings/ApiTokensSection";
+import { ToolsSection } from "../components/settings/ToolsSection";
 
 export function SettingsPage() {
   const { data: globalConfig, refetch: refetchGlobalConfig } = useApi(
@@ -192,6 +194,12 @@ export function SettingsPage() {
           </div>
         )}
       </section>
+
+      {/* External Tools Section */}
+      <ToolsSection />
+
+      {/* API Tokens Section */}
+      <ApiTokensSection />
     </div>
   );
 }
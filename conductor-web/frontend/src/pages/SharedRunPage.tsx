@@ -0,0 +1,112 @@
+import { useParams } from "react-router";
+import { api } from "../api/client";
+import type { SharedRun } from "../api/types";
+import { useApi } from "../hooks/useApi";
+import { statusColors, statusLabels } from "../utils/agentStats";
+
+function formatDuration(ms: number): string {
+  const seconds = Math.floor(ms / 1000);
+  if (seconds < 60) return `${seconds}s`;
+  const minutes = Math.floor(seconds / 60);
+  return `${minutes}m ${seconds % 60}s`;
+}
+
+/**
+ * Read-only view of one agent run opened from a share link. Rendered outside
+ * the app shell so it works without an API token.
+ */
+export function SharedRunPage() {
+  const { token } = useParams<{ token: string }>();
+  const { data: run, loading, error } = useApi<SharedRun>(
+    () => api.getSharedRun(token ?? ""),
+    [token],
+  );
+
+  if (loading) {
+    return <p className="p-8 text-sm text-gray-400">Loading...</p>;
+  }
+  if (error || !run) {
+    return (
+      <div className="flex flex-col items-center justify-center py-24">
+        <h2 className="text-2xl font-bold text-gray-900">Link unavailable</h2>
+        <p className="mt-2 text-gray-500">
+          This share link does not exist, has expired or was revoked.
+        </p>
+      </div>
+    );
+  }
+
+  return (
+    <div className="max-w-3xl mx-auto px-4 py-8 space-y-6">
+      <header>
+        <div className="flex items-center gap-2">
+          <h1 className="text-lg font-semibold text-gray-900">Agent run</h1>
+          <span
+            className={`inline-block px-2 py-0.5 text-xs font-medium rounded-full ${
+              statusColors[run.status] ?? "bg-gray-100 text-gray-600"
+            }`}
+          >
+            {statusLabels[run.status] ?? run.status}
+          </span>
+        </div>
+        <p className="mt-1 text-xs text-gray-500">
+          {run.branch && <span className="font-mono">{run.branch} · </span>}
+          {run.model && <span>{run.model} · </span>}
+          {run.num_turns != null && <span>{run.num_turns} turns · </span>}
+          {run.duration_ms != null && <span>{formatDuration(run.duration_ms)} · </span>}
+          started {new Date(run.started_at).toLocaleString()} · link expires{" "}
+          {new Date(run.expires_at).toLocaleString()}
+        </p>
+      </header>
+
+      <section>
+        <h2 className="text-sm font-semibold uppercase tracking-wider text-gray-400 mb-2">
+          Prompt
+        </h2>
+        <pre className="whitespace-pre-wrap rounded-md bg-gray-50 p-3 text-sm text-gray-800">
+          {run.prompt}
+        </pre>
+      </section>
+
+      {run.result_text && (
+        <section>
+          <h2 className="text-sm font-semibold uppercase tracking-wider text-gray-400 mb-2">
+            Result
+          </h2>
+          <pre className="whitespace-pre-wrap rounded-md bg-gray-50 p-3 text-sm text-gray-800">
+            {run.result_text}
+          </pre>
+        </section>
+      )}
+
+      {run.diffstat && (
+        <section>
+          <h2 className="text-sm font-semibold uppercase tracking-wider text-gray-400 mb-2">
+            Changes
+          </h2>
+          <pre className="overflow-x-auto rounded-md bg-gray-50 p-3 text-xs font-mono text-gray-700">
+            {run.diffstat}
+          </pre>
+        </section>
+      )}
+
+      {run.events.length > 0 && (
+        <section>
+          <h2 className="text-sm font-semibold uppercase tracking-wider text-gray-400 mb-2">
+            Transcript
+          </h2>
+          <ol className="space-y-1 text-sm">
+            {run.events.map((event, i) => (
+              <li key={i} className="flex gap-2">
+                <span className="shrink-0 w-20 text-xs font-mono text-gray-400">
+                  {event.kind}
+                </span>
+                <span className="text-gray-700 break-words min-w-0">{event.summary}</span>
+              </li>
+            ))}
+          </ol>
+        </section>
+      )}
+    </div>
+  );
+}
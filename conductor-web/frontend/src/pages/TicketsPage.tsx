@@ -19,9 +19,11 @@ type SortColumn = "repo" | "source_id" | "title" | "state" | "assignee" | "pipel
 export function TicketsPage() {
   const { repos } = useRepos();
   const [showClosed, setShowClosed] = useState(false);
-  const { data: ticketList, loading } = useApi(
-    () => api.listAllTickets(showClosed),
-    [showClosed],
+  const [currentSprint, setCurrentSprint] = useState(false);
+  const [mine, setMine] = useState(false);
+  const { data: ticketList, loading, error } = useApi(
+    () => api.listAllTickets(showClosed, currentSprint, mine),
+    [showClosed, currentSprint, mine],
   );
   const tickets = ticketList?.tickets ?? null;
   const dependencies = ticketList?.dependencies ?? {};
@@ -229,6 +231,27 @@ export function TicketsPage() {
           >
             {showClosed ? "Hiding open only" : "Show closed"}
           </button>
+          <button
+            onClick={() => setCurrentSprint((v) => !v)}
+            className={`px-3 py-2 text-sm rounded-md border ${
+              currentSprint
+                ? "border-indigo-300 text-indigo-700 bg-indigo-50 hover:bg-indigo-100"
+                : "border-gray-300 text-gray-600 hover:bg-gray-50"
+            }`}
+          >
+            {currentSprint ? "Current sprint only" : "All sprints"}
+          </button>
+          <button
+            onClick={() => setMine((v) => !v)}
+            title="Tickets assigned to or mentioning your configured identity"
+            className={`px-3 py-2 text-sm rounded-md border ${
+              mine
+                ? "border-indigo-300 text-indigo-700 bg-indigo-50 hover:bg-indigo-100"
+                : "border-gray-300 text-gray-600 hover:bg-gray-50"
+            }`}
+          >
+            {mine ? "My queue" : "Everyone's"}
+          </button>
           <input
             ref={filterRef}
             type="text"
@@ -240,12 +263,13 @@ export function TicketsPage() {
         </div>
       </div>
 
+      {error && <p className="mb-3 text-sm text-red-600">{error}</p>}
       {loading ? (
         <LoadingSpinner />
       ) : filtered.length === 0 ? (
         <EmptyState
           message={
-            filter || hasActiveFilters ? "No tickets match your filter" : "No tickets issued. Sync your issues to start the journey."
+            filter || hasActiveFilters || currentSprint || mine ? "No tickets match your filter" : "No tickets issued. Sync your issues to start the journey."
           }
         />
       ) : (
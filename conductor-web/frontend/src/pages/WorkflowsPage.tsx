@@ -12,6 +12,7 @@ import { getWorkflowDisplayName } from "../utils/workflow";
 import { WorkflowRunTree } from "../components/workflows/WorkflowRunTree";
 import { formatDuration, liveElapsedMs } from "../utils/agentStats";
 import { formatWorkflowProgress } from "../utils/workflowProgress";
+import { isLiveWorktree } from "../utils/worktreeUtils";
 
 interface WorktreeContext {
   repoId: string;
@@ -60,7 +61,7 @@ export function WorkflowsPage() {
       const repoMap = new Map(repos.map((r) => [r.id, r]));
       const ctxMap = new Map<string, WorktreeContext>();
       for (const wt of allWorktrees) {
-        if (wt.status !== "active") continue;
+        if (!isLiveWorktree(wt)) continue;
         const repo = repoMap.get(wt.repo_id);
         if (repo) {
           ctxMap.set(wt.id, { repoId: wt.repo_id, repoSlug: repo.slug, branch: wt.branch, worktreeId: wt.id });
@@ -210,7 +211,7 @@ export function WorkflowsPage() {
     try {
       setPickerRepo(repo);
       const wts = await api.listWorktrees(repo.id);
-      setPickerWorktrees(wts.filter((wt) => wt.status === "active"));
+      setPickerWorktrees(wts.filter(isLiveWorktree));
       setPickerStep("worktree");
     } catch (err: unknown) {
       setActionError(err instanceof Error ? err.message : "Failed to load worktrees");
@@ -1,9 +1,21 @@
 import { useState, useEffect, useCallback, useMemo } from "react";
 import { useParams, Link, useNavigate } from "react-router";
 import { useApi } from "../hooks/useApi";
-import { api } from "../api/client";
+import { api, ApiRequestError } from "../api/client";
 import { TransitBreadcrumb } from "../components/shared/TransitBreadcrumb";
-import type { AgentRun, AgentEvent, AgentCreatedIssue, Ticket } from "../api/types";
+import type {
+  AgentRun,
+  AgentChain,
+  ChainLink,
+  AgentEvent,
+  AgentCreatedIssue,
+  AgentPriority,
+  BudgetExceeded,
+  DiffReview,
+  DirtyWorktreeChoice,
+  QueuedAgentRun,
+  Ticket,
+} from "../api/types";
 import { StatusBadge } from "../components/shared/StatusBadge";
 import { TimeAgo } from "../components/shared/TimeAgo";
 import { ConfirmDialog } from "../components/shared/ConfirmDialog";
@@ -11,10 +23,19 @@ import { ErrorBanner } from "../components/shared/ErrorBanner";
 import { LoadingSpinner } from "../components/shared/LoadingSpinner";
 import { AgentPromptModal } from "../components/agents/AgentPromptModal";
 import { isActiveRun } from "../utils/agentStats";
+import { isLiveWorktree } from "../utils/worktreeUtils";
 import { ModelPicker } from "../components/shared/ModelPicker";
+import { TicketLinkPicker } from "../components/tickets/TicketLinkPicker";
 import { AgentStatusDisplay } from "../components/agents/AgentStatusDisplay";
 import { AgentActivityLog } from "../components/agents/AgentActivityLog";
 import { AgentPlanChecklist } from "../components/agents/AgentPlanChecklist";
+import { AgentQueueList } from "../components/agents/AgentQueueList";
+import { ChainTree } from "../components/agents/ChainTree";
+import { DiffReviewPanel } from "../components/agents/DiffReviewPanel";
+import { DirtyWorktreeDialog } from "../components/agents/DirtyWorktreeDialog";
+import { BranchTodosPanel } from "../components/worktrees/BranchTodosPanel";
+import { WorktreeCodePanel } from "../components/worktrees/WorktreeCodePanel";
+import { WatchToggle } from "../components/shared/WatchToggle";
 import { AgentFeedbackModal } from "../components/agents/AgentFeedbackModal";
 import {
   useConductorEvents,
@@ -51,15 +72,17 @@ export function WorktreeDetailPage() {
   const [deleteConfirm, setDeleteConfirm] = useState(false);
   const [pathCopied, setPathCopied] = useState(false);
   const [linkingTicket, setLinkingTicket] = useState(false);
-  const [selectedTicketId, setSelectedTicketId] = useState("");
   const [editingModel, setEditingModel] = useState(false);
 
   // Agent state
   const [latestRun, setLatestRun] = useState<AgentRun | null>(null);
   const [agentRuns, setAgentRuns] = useState<AgentRun[]>([]);
   const [childRuns, setChildRuns] = useState<AgentRun[]>([]);
+  const [chainLinks, setChainLinks] = useState<ChainLink[]>([]);
   const [agentEvents, setAgentEvents] = useState<AgentEvent[]>([]);
   const [createdIssues, setCreatedIssues] = useState<AgentCreatedIssue[]>([]);
+  const [queuedRuns, setQueuedRuns] = useState<QueuedAgentRun[]>([]);
+  const [diffReview, setDiffReview] = useState<DiffReview | null>(null);
   const [promptModalOpen, setPromptModalOpen] = useState(false);
   const [promptInfo, setPromptInfo] = useState({
     prompt: "",
@@ -67,6 +90,20 @@ export function WorktreeDetailPage() {
   });
   const [agentLoading, setAgentLoading] = useState(false);
   const [stopConfirm, setStopConfirm] = useState(false);
+  const [dirtyLaunch, setDirtyLaunch] = useState<{
+    files: string[];
+    prompt: string;
+    resumeSessionId?: string;
+    chain?: AgentChain;
+  } | null>(null);
+  const [budgetLaunch, setBudgetLaunch] = useState<{
+    message: string;
+    prompt: string;
+    resumeSessionId?: string;
+    chain?: AgentChain;
+    dirtyChoice?: DirtyWorktreeChoice;
+  } | null>(null);
+  const [overBudget, setOverBudget] = useState<BudgetExceeded | null>(null);
   const [feedbackModalOpen, setFeedbackModalOpen] = useState(false);
 
   // Error state
@@ -79,11 +116,12 @@ export function WorktreeDetailPage() {
   // Sidebar collapsed state
   const [sidebarOpen, setSidebarOpen] = useState(true);
 
-  const noModalsOpen = !deleteConfirm && !promptModalOpen && !stopConfirm;
+  const noModalsOpen = !deleteConfirm && !promptModalOpen && !stopConfirm && !budgetLaunch;
 
   useHotkeys([
     { key: "d", handler: () => setDeleteConfirm(true), description: "Delete worktree", enabled: noModalsOpen },
     { key: "l", handler: () => handleLaunchClick(), description: "Launch agent", enabled: noModalsOpen },
+    { key: "v", handler: () => handleReviewDiff(), description: "Review diff with an agent", enabled: noModalsOpen && !(latestRun && isActiveRun(latestRun)) },
     { key: "w", handler: () => setSidebarOpen((v) => !v), description: "Toggle workflows sidebar", enabled: noModalsOpen },
     { key: "c", handler: async () => {
       if (worktree) {
@@ -105,7 +143,7 @@ export function WorktreeDetailPage() {
     ? tickets?.find((t) => t.id === worktree.ticket_id)
     : null;
 
-  const isActive = worktree?.status === "active";
+  const isActive = worktree ? isLiveWorktree(worktree) : false;
   const isRunning = latestRun ? isActiveRun(latestRun) : false;
   const isWaitingForFeedback = latestRun?.status === "waiting_for_feedback";
 
@@ -121,16 +159,20 @@ export function WorktreeDetailPage() {
   const refreshAgent = useCallback(async () => {
     if (!worktreeId) return;
     try {
-      const [latest, runs, events, issues] = await Promise.all([
+      const [latest, runs, events, issues, queue, review] = await Promise.all([
         api.latestAgentRun(worktreeId),
         api.listAgentRuns(worktreeId),
         api.getAgentEvents(worktreeId),
         api.getCreatedIssues(worktreeId),
+        api.listAgentQueue(),
+        api.latestDiffReview(worktreeId),
       ]);
       setLatestRun(latest);
       setAgentRuns(runs);
       setAgentEvents(events);
       setCreatedIssues(issues);
+      setQueuedRuns(queue.filter((q) => q.worktree_id === worktreeId));
+      setDiffReview(review);
       setPageError(null);
 
       if (latest && !latest.parent_run_id) {
@@ -143,6 +185,16 @@ export function WorktreeDetailPage() {
       } else {
         setChildRuns([]);
       }
+
+      if (latest) {
+        try {
+          setChainLinks(await api.getAgentRunChain(latest.id));
+        } catch {
+          setChainLinks([]);
+        }
+      } else {
+        setChainLinks([]);
+      }
     } catch (e) {
       setPageError({ message: getErrorMessage(e, "Failed to load agent data"), retry: refreshAgent });
     }
@@ -150,6 +202,17 @@ export function WorktreeDetailPage() {
 
   useEffect(() => { refreshAgent(); }, [refreshAgent]);
 
+  const refreshBudget = useCallback(async () => {
+    try {
+      const over = await api.listOverBudget();
+      setOverBudget(over.find((b) => b.target_id === worktreeId) ?? null);
+    } catch {
+      // The banner is advisory; starting an agent still enforces the budget.
+    }
+  }, [worktreeId]);
+
+  useEffect(() => { refreshBudget(); }, [refreshBudget]);
+
   useEffect(() => {
     if (!isRunning) return;
     const interval = setInterval(refreshAgent, 5000);
@@ -175,12 +238,14 @@ export function WorktreeDetailPage() {
       tickets_synced: handleTickets,
       agent_started: handleAgentChange,
       agent_stopped: handleAgentChange,
+      agent_queue_changed: handleAgentChange,
       agent_event: handleAgentChange,
       feedback_requested: handleAgentChange,
       feedback_submitted: handleAgentChange,
+      budget_exceeded: () => refreshBudget(),
     };
     return map;
-  }, [repoId, worktreeId, refetchWorktrees, refetchTickets, refreshAgent]);
+  }, [repoId, worktreeId, refetchWorktrees, refetchTickets, refreshAgent, refreshBudget]);
 
   useConductorEvents(sseHandlers);
 
@@ -199,22 +264,95 @@ export function WorktreeDetailPage() {
     }
   }
 
-  async function handleAgentSubmit(prompt: string, resumeSessionId?: string) {
+  async function handleAgentSubmit(
+    prompt: string,
+    resumeSessionId?: string,
+    chain?: AgentChain,
+    dirtyChoice?: DirtyWorktreeChoice,
+    overrideBudget?: boolean,
+  ) {
     if (!worktreeId) return;
     setPromptModalOpen(false);
+    setDirtyLaunch(null);
+    setBudgetLaunch(null);
     setAgentLoading(true);
     setPageError(null);
     try {
-      await api.startAgent(worktreeId, prompt, resumeSessionId);
+      await api.startAgent(
+        worktreeId,
+        prompt,
+        resumeSessionId,
+        undefined,
+        dirtyChoice,
+        chain,
+        overrideBudget,
+      );
       await refreshAgent();
     } catch (e) {
+      if (e instanceof ApiRequestError && e.body.code === "worktree_dirty") {
+        setDirtyLaunch({ files: e.body.dirty_files as string[], prompt, resumeSessionId, chain });
+        return;
+      }
+      if (e instanceof ApiRequestError && e.status === 402) {
+        setBudgetLaunch({ message: e.message, prompt, resumeSessionId, chain, dirtyChoice });
+        return;
+      }
       const msg = getErrorMessage(e, "Failed to start agent");
-      setPageError({ message: msg, retry: () => handleAgentSubmit(prompt, resumeSessionId) });
+      setPageError({ message: msg, retry: () => handleAgentSubmit(prompt, resumeSessionId, chain) });
     } finally {
       setAgentLoading(false);
     }
   }
 
+  async function handleReviewDiff() {
+    if (!worktreeId) return;
+    setAgentLoading(true);
+    setPageError(null);
+    try {
+      await api.startDiffReview(worktreeId);
+      await refreshAgent();
+    } catch (e) {
+      const msg = getErrorMessage(e, "Failed to start diff review");
+      setPageError({ message: msg, retry: handleReviewDiff });
+    } finally {
+      setAgentLoading(false);
+    }
+  }
+
+  async function handleAgentQueue(prompt: string, priority: AgentPriority, chain?: AgentChain) {
+    if (!worktreeId) return;
+    setPromptModalOpen(false);
+    setPageError(null);
+    try {
+      await api.enqueueAgent(worktreeId, prompt, priority, chain);
+      await refreshAgent();
+    } catch (e) {
+      const msg = getErrorMessage(e, "Failed to queue agent");
+      setPageError({ message: msg, retry: () => handleAgentQueue(prompt, priority, chain) });
+    }
+  }
+
+  async function handleQueuePriority(id: string, priority: AgentPriority) {
+    try {
+      await api.setQueuePriority(id, priority);
+      await refreshAgent();
+    } catch (e) {
+      // The run may have started since the list was fetched.
+      setPageError({ message: getErrorMessage(e, "Failed to change priority") });
+      await refreshAgent();
+    }
+  }
+
+  async function handleQueueRemove(id: string) {
+    try {
+      await api.removeFromQueue(id);
+      await refreshAgent();
+    } catch (e) {
+      setPageError({ message: getErrorMessage(e, "Failed to remove queued run") });
+      await refreshAgent();
+    }
+  }
+
   async function handleStopAgent() {
     if (!worktreeId) return;
     setStopConfirm(false);
@@ -241,17 +379,37 @@ export function WorktreeDetailPage() {
     }
   }
 
-  async function handleLinkTicket() {
-    if (!selectedTicketId) return;
+  async function handleRestore() {
+    setPageError(null);
+    try {
+      await api.restoreWorktree(worktreeId!);
+      refetchWorktrees();
+    } catch (err) {
+      const msg = getErrorMessage(err, "Failed to restore worktree");
+      setPageError({ message: msg, retry: handleRestore });
+    }
+  }
+
+  async function handleAcknowledgeTicketChange() {
+    setPageError(null);
+    try {
+      await api.acknowledgeTicketChange(worktreeId!);
+      refetchWorktrees();
+    } catch (err) {
+      const msg = getErrorMessage(err, "Failed to acknowledge ticket change");
+      setPageError({ message: msg, retry: handleAcknowledgeTicketChange });
+    }
+  }
+
+  async function handleLinkTicket(ticket: Ticket) {
     setLinkingTicket(true);
     setPageError(null);
     try {
-      await api.linkTicket(worktreeId!, selectedTicketId);
-      setSelectedTicketId("");
+      await api.linkTicket(worktreeId!, ticket.id);
       refetchWorktrees();
     } catch (err) {
       const msg = getErrorMessage(err, "Failed to link ticket");
-      setPageError({ message: msg, retry: handleLinkTicket });
+      setPageError({ message: msg, retry: () => handleLinkTicket(ticket) });
     } finally {
       setLinkingTicket(false);
     }
@@ -300,6 +458,12 @@ export function WorktreeDetailPage() {
           <div className="flex items-center gap-3 min-w-0">
             <h2 className="text-lg font-bold text-gray-900 truncate">{worktree.branch}</h2>
             <StatusBadge status={worktree.status} />
+            {worktree.abandon_reason && (
+              <span className="text-xs text-gray-500 shrink-0">
+                {worktree.abandon_reason}
+              </span>
+            )}
+            <WatchToggle targetType="worktree" targetId={worktree.id} />
             {linkedTicket && getSafeUrl(linkedTicket.url) && (
               <a
                 href={getSafeUrl(linkedTicket.url)}
@@ -332,6 +496,14 @@ export function WorktreeDetailPage() {
                 </button>
               ) : (
                 <>
+                  <button
+                    onClick={handleReviewDiff}
+                    disabled={agentLoading}
+                    title="Ask an agent to review the diff against the base branch (v)"
+                    className="px-3 py-1.5 text-sm font-medium rounded-md border border-gray-300 text-gray-700 hover:bg-gray-50 active:scale-95 transition-transform disabled:opacity-50"
+                  >
+                    Review Diff
+                  </button>
                   <button
                     onClick={handleLaunchClick}
                     disabled={agentLoading}
@@ -362,26 +534,12 @@ export function WorktreeDetailPage() {
           <span>Created <TimeAgo date={worktree.created_at} /></span>
           {worktree.completed_at && <span>Completed <TimeAgo date={worktree.completed_at} /></span>}
           {isActive && !linkedTicket && availableTickets && availableTickets.length > 0 && (
-            <span className="flex items-center gap-1">
-              <select
-                value={selectedTicketId}
-                onChange={(e) => setSelectedTicketId(e.target.value)}
-                aria-label="Select a ticket to link"
-                className="rounded border border-gray-300 bg-white text-gray-900 px-1 py-0.5 text-xs"
-              >
-                <option value="">Link ticket...</option>
-                {availableTickets.map((t: Ticket) => (
-                  <option key={t.id} value={t.id}>#{t.source_id}</option>
-                ))}
-              </select>
-              <button
-                onClick={handleLinkTicket}
-                disabled={!selectedTicketId || linkingTicket}
-                className="px-1.5 py-0.5 text-xs rounded border border-gray-300 text-gray-700 hover:bg-gray-50 active:scale-95 transition-transform disabled:opacity-50"
-              >
-                {linkingTicket ? "..." : "Link"}
-              </button>
-            </span>
+            <TicketLinkPicker
+              repoId={repoId!}
+              excludeTicketId={worktree.ticket_id}
+              disabled={linkingTicket}
+              onSelect={handleLinkTicket}
+            />
           )}
           <span className="flex items-center gap-1">
             Model:
@@ -406,6 +564,47 @@ export function WorktreeDetailPage() {
           </span>
         </div>
 
+        {worktree.missing_since && (
+          <div className="flex flex-wrap items-center gap-2 rounded-md border border-red-200 bg-red-50 px-3 py-2 text-sm text-red-700">
+            <span className="flex-1">
+              The worktree directory was deleted outside conductor. Recreate it from {worktree.branch}, or delete the worktree.
+            </span>
+            <button
+              onClick={handleRestore}
+              className="px-3 py-1 text-sm font-medium rounded-md bg-white border border-red-300 text-red-700 hover:bg-red-100 active:scale-95 transition-transform"
+            >
+              Restore
+            </button>
+            <button
+              onClick={() => setDeleteConfirm(true)}
+              className="px-3 py-1 text-sm font-medium rounded-md border border-red-300 text-red-700 hover:bg-red-100 active:scale-95 transition-transform"
+            >
+              Delete
+            </button>
+          </div>
+        )}
+
+        {overBudget && (
+          <div className="rounded-md border border-amber-300 bg-amber-50 px-3 py-2 text-sm text-amber-800">
+            Over budget: this worktree has spent ${overBudget.spent_usd.toFixed(2)} of its $
+            {overBudget.limit_usd.toFixed(2)} agent budget. New agents need an explicit override.
+          </div>
+        )}
+
+        {worktree.ticket_change && (
+          <div className="flex flex-wrap items-center gap-2 rounded-md border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-800">
+            <span className="flex-1">
+              The linked ticket changed upstream: {worktree.ticket_change}.
+            </span>
+            <button
+              onClick={handleAcknowledgeTicketChange}
+              className="px-3 py-1 text-sm font-medium rounded-md bg-white border border-amber-300 text-amber-800 hover:bg-amber-100 active:scale-95 transition-transform"
+            >
+              Acknowledge
+            </button>
+          </div>
+        )}
+
         <ErrorBanner error={pageError?.message ?? null} onDismiss={() => setPageError(null)} onRetry={pageError?.retry} />
       </div>
 
@@ -437,6 +636,16 @@ export function WorktreeDetailPage() {
             <AgentPlanChecklist steps={latestRun.plan} />
           )}
 
+          <ChainTree links={chainLinks} />
+
+          <DiffReviewPanel review={diffReview} />
+
+          <AgentQueueList
+            items={queuedRuns}
+            onPriorityChange={handleQueuePriority}
+            onRemove={handleQueueRemove}
+          />
+
           {/* Activity Log — hero element */}
           {(agentEvents.length > 0 || isRunning) && (
             <div className="flex flex-col flex-1 min-h-0">
@@ -486,6 +695,19 @@ export function WorktreeDetailPage() {
             </div>
           )}
 
+          <BranchTodosPanel
+            worktreeId={worktree.id}
+            refreshKey={latestRun ? `${latestRun.id}:${latestRun.status}` : undefined}
+            canScan={isActive}
+          />
+
+          {isActive && (
+            <WorktreeCodePanel
+              worktreeId={worktree.id}
+              refreshKey={latestRun ? `${latestRun.id}:${latestRun.status}` : undefined}
+            />
+          )}
+
           {/* Danger Zone */}
           <details className="mt-2">
             <summary className="text-xs font-semibold uppercase tracking-wider text-red-400 cursor-pointer select-none list-none flex items-center gap-1">
@@ -554,9 +776,37 @@ export function WorktreeDetailPage() {
         initialPrompt={promptInfo.prompt}
         resumeSessionId={promptInfo.resumeSessionId}
         onSubmit={handleAgentSubmit}
+        onQueue={handleAgentQueue}
         onCancel={() => setPromptModalOpen(false)}
       />
 
+      <DirtyWorktreeDialog
+        open={dirtyLaunch !== null}
+        files={dirtyLaunch?.files ?? []}
+        onChoose={(choice) =>
+          dirtyLaunch &&
+          handleAgentSubmit(dirtyLaunch.prompt, dirtyLaunch.resumeSessionId, dirtyLaunch.chain, choice)
+        }
+        onCancel={() => setDirtyLaunch(null)}
+      />
+
+      <ConfirmDialog
+        open={budgetLaunch !== null}
+        title="Budget Exceeded"
+        message={`${budgetLaunch?.message ?? ""}. Start the agent anyway?`}
+        onConfirm={() =>
+          budgetLaunch &&
+          handleAgentSubmit(
+            budgetLaunch.prompt,
+            budgetLaunch.resumeSessionId,
+            budgetLaunch.chain,
+            budgetLaunch.dirtyChoice,
+            true,
+          )
+        }
+        onCancel={() => setBudgetLaunch(null)}
+      />
+
       <ConfirmDialog
         open={stopConfirm}
         title="Stop Agent"
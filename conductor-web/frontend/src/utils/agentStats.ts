@@ -1,4 +1,4 @@
-import type { AgentRun, TicketAgentTotals } from "../api/types";
+import type { AgentRun, RunEnvironment, TicketAgentTotals } from "../api/types";
 
 /** CSS class mapping for agent run status badges. */
 export const statusColors: Record<string, string> = {
@@ -86,3 +86,13 @@ export function formatRunStats(run: AgentRun, durationMs: number): string {
   }
   return `${turns} turns, ${dur}`;
 }
+
+/** One-line run environment: "claude 1.0.51 · sonnet · a1b2c3d4 (main) · linux/x86_64 · conductor 0.3.0". */
+export function formatRunEnvironment(env: RunEnvironment): string {
+  const version = env.agent_version?.replace(/ \(Claude Code\)$/, "") ?? "unknown";
+  const model = env.model ?? "default model";
+  const sha = env.git_commit?.slice(0, 8);
+  const commit = sha ? (env.git_branch ? `${sha} (${env.git_branch})` : sha) : "no commit";
+  const summary = `${env.backend} ${version} · ${model} · ${commit} · ${env.os}/${env.arch} · conductor ${env.conductor_version}`;
+  return env.sandbox ? `${summary} · in ${env.sandbox}` : summary;
+}
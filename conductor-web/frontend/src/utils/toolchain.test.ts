@@ -0,0 +1,22 @@
+import { describe, it, expect } from "vitest";
+import { toolchainLabel } from "./toolchain";
+
+describe("toolchainLabel", () => {
+  it("lists the package manager and test framework after the language", () => {
+    expect(
+      toolchainLabel({ language: "rust", package_manager: "cargo", test_framework: "nextest" }),
+    ).toBe("rust (cargo, nextest)");
+  });
+
+  it("omits missing tools", () => {
+    expect(
+      toolchainLabel({ language: "ruby", package_manager: "bundler", test_framework: null }),
+    ).toBe("ruby (bundler)");
+  });
+
+  it("reports unknown when nothing was detected", () => {
+    expect(
+      toolchainLabel({ language: null, package_manager: null, test_framework: null }),
+    ).toBe("unknown");
+  });
+});
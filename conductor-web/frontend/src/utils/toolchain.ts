@@ -0,0 +1,11 @@
+import type { Toolchain } from "../api/types";
+
+/**
+ * `rust (cargo, nextest)`, or `unknown` when nothing was detected.
+ * Mirrors `impl Display for Toolchain` in conductor-core/src/toolchain.rs.
+ */
+export function toolchainLabel(toolchain: Toolchain): string {
+  if (!toolchain.language) return "unknown";
+  const tools = [toolchain.package_manager, toolchain.test_framework].filter(Boolean);
+  return tools.length ? `${toolchain.language} (${tools.join(", ")})` : toolchain.language;
+}
@@ -0,0 +1,40 @@
+import { describe, it, expect } from "vitest";
+import type { WorktreeJob } from "../api/types";
+import { waitForWorktreeJob } from "./worktreeJobs";
+
+function job(overrides: Partial<WorktreeJob>): WorktreeJob {
+  return {
+    id: "j1",
+    repo_id: "r1",
+    name: "feat-x",
+    status: "running",
+    stage: null,
+    worktree: null,
+    commits_behind: 0,
+    error: null,
+    started_at: "2026-01-01T00:00:00Z",
+    finished_at: null,
+    ...overrides,
+  };
+}
+
+describe("waitForWorktreeJob", () => {
+  it("polls until the job succeeds and returns the worktree", async () => {
+    const wt = { id: "w1" } as WorktreeJob["worktree"];
+    const states = [
+      job({ stage: "fetching" }),
+      job({ stage: "installing_deps" }),
+      job({ status: "succeeded", worktree: wt }),
+    ];
+    let calls = 0;
+    const result = await waitForWorktreeJob("j1", async () => states[calls++], 0);
+    expect(result).toBe(wt);
+    expect(calls).toBe(3);
+  });
+
+  it("rejects with the job error when creation fails", async () => {
+    await expect(
+      waitForWorktreeJob("j1", async () => job({ status: "failed", error: "git exploded" }), 0),
+    ).rejects.toThrow("git exploded");
+  });
+});
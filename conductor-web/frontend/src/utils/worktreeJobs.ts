@@ -0,0 +1,23 @@
+import { api } from "../api/client";
+import type { Worktree, WorktreeJob } from "../api/types";
+
+/**
+ * Poll a worktree-creation job until it finishes. Resolves with the created
+ * worktree, or rejects with the job's error. Live stage updates arrive over SSE
+ * (`worktree_create_progress`); polling only covers the final result so a
+ * dropped event stream cannot leave the caller waiting forever.
+ */
+export async function waitForWorktreeJob(
+  jobId: string,
+  getJob: (id: string) => Promise<WorktreeJob> = api.getWorktreeJob,
+  intervalMs = 1000,
+): Promise<Worktree> {
+  for (;;) {
+    const job = await getJob(jobId);
+    if (job.status === "succeeded" && job.worktree) return job.worktree;
+    if (job.status === "failed") {
+      throw new Error(job.error ?? "Failed to create worktree");
+    }
+    await new Promise((resolve) => setTimeout(resolve, intervalMs));
+  }
+}
@@ -1,5 +1,5 @@
 import { describe, it, expect } from "vitest";
-import { deriveWorktreeSlug } from "./worktreeUtils";
+import { deriveWorktreeSlug, isLiveWorktree, stackOrder } from "./worktreeUtils";
 
 describe("deriveWorktreeSlug", () => {
   // ── no-label (fallback to feat) ─────────────────────────────────────────
@@ -127,3 +127,45 @@ describe("deriveWorktreeSlug", () => {
     expect(slug.length).toBeLessThanOrEqual(40);
   });
 });
+
+describe("isLiveWorktree", () => {
+  it("treats active, needs_review and parked as live", () => {
+    expect(isLiveWorktree({ status: "active" })).toBe(true);
+    expect(isLiveWorktree({ status: "needs_review" })).toBe(true);
+    expect(isLiveWorktree({ status: "parked" })).toBe(true);
+  });
+
+  it("treats terminal states as not live", () => {
+    expect(isLiveWorktree({ status: "merged" })).toBe(false);
+    expect(isLiveWorktree({ status: "abandoned" })).toBe(false);
+    expect(isLiveWorktree({ status: "promoted" })).toBe(false);
+  });
+});
+
+describe("stackOrder", () => {
+  const wt = (id: string, branch: string, base_branch: string | null) => ({
+    id,
+    branch,
+    base_branch,
+  });
+
+  it("nests children under their parent regardless of input order", () => {
+    const order = stackOrder([
+      wt("g", "feat/grandchild", "feat/child"),
+      wt("o", "feat/other", null),
+      wt("c", "feat/child", "feat/parent"),
+      wt("p", "feat/parent", "main"),
+    ]);
+    expect(order.map((e) => [e.worktree.id, e.depth])).toEqual([
+      ["o", 0],
+      ["p", 0],
+      ["c", 1],
+      ["g", 2],
+    ]);
+  });
+
+  it("keeps worktrees in a branch cycle", () => {
+    const order = stackOrder([wt("a", "a", "b"), wt("b", "b", "a")]);
+    expect(order).toHaveLength(2);
+  });
+});
@@ -1,3 +1,51 @@
+import type { Worktree, WorktreeStatus } from "../api/types";
+
+/**
+ * Whether the worktree's checkout still exists on disk.
+ * Mirrors `WorktreeStatus::is_live()` in conductor-core/src/worktree/types.rs.
+ */
+export function isLiveWorktree(wt: { status: WorktreeStatus }): boolean {
+  return (
+    wt.status === "active" ||
+    wt.status === "needs_review" ||
+    wt.status === "parked"
+  );
+}
+
+/**
+ * Order worktrees so each stacked worktree follows its parent (the worktree
+ * whose branch is its base branch), with its nesting depth.
+ * Mirrors `stack_order()` in conductor-core/src/worktree/stack.rs.
+ */
+export function stackOrder<T extends Pick<Worktree, "id" | "branch" | "base_branch">>(
+  worktrees: T[],
+): { worktree: T; depth: number }[] {
+  const byBranch = new Map(worktrees.map((w) => [w.branch, w]));
+  const children = new Map<string, T[]>();
+  const roots: T[] = [];
+  for (const wt of worktrees) {
+    const parent = wt.base_branch ? byBranch.get(wt.base_branch) : undefined;
+    if (parent && parent.id !== wt.id) {
+      children.set(parent.id, [...(children.get(parent.id) ?? []), wt]);
+    } else {
+      roots.push(wt);
+    }
+  }
+
+  const out: { worktree: T; depth: number }[] = [];
+  const visited = new Set<string>();
+  const visit = (wt: T, depth: number) => {
+    if (visited.has(wt.id)) return;
+    visited.add(wt.id);
+    out.push({ worktree: wt, depth });
+    for (const child of children.get(wt.id) ?? []) visit(child, depth + 1);
+  };
+  roots.forEach((wt) => visit(wt, 0));
+  // Branch cycles have no root; keep them visible at the top level.
+  worktrees.forEach((wt) => visit(wt, 0));
+  return out;
+}
+
 /**
  * Map ticket labels to conventional-commit branch prefixes.
  * Mirrors label_to_branch_prefix() in conductor-core/src/worktree/manager.rs.
@@ -23,7 +71,7 @@ const LABEL_PREFIX_MAP: Record<string, string> = {
 
 /**
  * Derive a worktree slug from a ticket's source_id, title, and optional labels.
- * Mirrors the TUI's `derive_worktree_slug()` in helpers.rs.
+ * Mirrors `derive_worktree_slug()` in conductor-core/src/worktree/manager.rs.
  * Format: `{prefix}-{sourceId}-{slugified-title}`
  */
 export function deriveWorktreeSlug(
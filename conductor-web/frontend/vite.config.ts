@@ -1,8 +1,51 @@
+import { readdirSync, readFileSync, writeFileSync } from "node:fs";
+import { join, resolve } from "node:path";
+import { brotliCompressSync, constants, gzipSync } from "node:zlib";
 import { defineConfig } from "vitest/config";
+import type { Plugin } from "vite";
 import react from "@vitejs/plugin-react";
 import tailwindcss from "@tailwindcss/vite";
 import { VitePWA } from "vite-plugin-pwa";
 
+const COMPRESSIBLE = /\.(js|mjs|css|html|svg|json|webmanifest|txt)$/;
+const MIN_COMPRESS_BYTES = 1024;
+
+// Writes `.br` and `.gz` siblings for text assets so the embedded server
+// (conductor-web/src/assets.rs) can serve them without compressing per request.
+function precompress(): Plugin {
+  let outDir = "dist";
+  const walk = (dir: string): string[] =>
+    readdirSync(dir, { withFileTypes: true }).flatMap((entry) =>
+      entry.isDirectory()
+        ? walk(join(dir, entry.name))
+        : [join(dir, entry.name)],
+    );
+  return {
+    name: "conductor-precompress",
+    apply: "build",
+    configResolved(config) {
+      outDir = resolve(config.root, config.build.outDir);
+    },
+    closeBundle: {
+      sequential: true,
+      order: "post",
+      handler() {
+        for (const file of walk(outDir)) {
+          if (!COMPRESSIBLE.test(file)) continue;
+          const data = readFileSync(file);
+          if (data.length < MIN_COMPRESS_BYTES) continue;
+          const br = brotliCompressSync(data, {
+            params: { [constants.BROTLI_PARAM_QUALITY]: 11 },
+          });
+          if (br.length < data.length) writeFileSync(`${file}.br`, br);
+          const gz = gzipSync(data, { level: 9 });
+          if (gz.length < data.length) writeFileSync(`${file}.gz`, gz);
+        }
+      },
+    },
+  };
+}
+
 export default defineConfig({
   plugins: [
     react(),
@@ -18,6 +61,7 @@ export default defineConfig({
       },
       manifest: false,
     }),
+    precompress(),
   ],
   test: {
     exclude: ["e2e/**", "node_modules/**"],
@@ -1,26 +1,152 @@
-use axum::http::{header, StatusCode, Uri};
-use axum::response::{Html, IntoResponse, Response};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
 use rust_embed::Embed;
 
 #[derive(Embed)]
 #[folder = "frontend/dist/"]
 struct Assets;
 
-pub async fn static_handler(uri: Uri) -> Response {
+/// Vite emits content-hashed bundles under `assets/`; their URL changes
+/// whenever their bytes do, so browsers may keep them forever.
+const HASHED_ASSET_PREFIX: &str = "assets/";
+const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// index.html, the service worker and icons keep stable URLs and must be
+/// revalidated on every load; the ETag turns that into a cheap 304.
+const CACHE_REVALIDATE: &str = "no-cache";
+
+/// Pre-compressed siblings written by the frontend build (`<file>.br`,
+/// `<file>.gz`), as `(content-coding, extension)` in order of preference.
+const PRECOMPRESSED: &[(&str, &str)] = &[("br", "br"), ("gzip", "gz")];
+
+pub async fn static_handler(uri: Uri, headers: HeaderMap) -> Response {
     let path = uri.path().trim_start_matches('/');
 
-    if let Some(content) = Assets::get(path) {
+    serve(path, &headers)
+        // SPA fallback: serve index.html for any unmatched path
+        .or_else(|| serve("index.html", &headers))
+        .unwrap_or_else(|| StatusCode::NOT_FOUND.into_response())
+}
+
+/// Serve one embedded file, picking the best pre-compressed variant the
+/// client accepts and answering `If-None-Match` with 304.
+fn serve(path: &str, headers: &HeaderMap) -> Option<Response> {
+    let original = Assets::get(path)?;
+    let accept_encoding = headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let (file, encoding) = PRECOMPRESSED
+        .iter()
+        .filter(|(coding, _)| accepts_encoding(accept_encoding, coding))
+        .find_map(|(coding, ext)| Assets::get(&format!("{path}.{ext}")).map(|f| (f, Some(*coding))))
+        .unwrap_or((original, None));
+
+    // Each variant hashes differently, so a cached gzip body is never
+    // validated against the brotli one.
+    let etag = format!("\"{}\"", hex::encode(file.metadata.sha256_hash()));
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok());
+
+    let mut response = if if_none_match.is_some_and(|v| etag_matches(v, &etag)) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
         let mime = mime_guess::from_path(path).first_or_octet_stream();
         (
             [(header::CONTENT_TYPE, mime.as_ref())],
-            content.data.into_owned(),
+            file.data.into_owned(),
         )
             .into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control(path)),
+    );
+    response_headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    if let Some(coding) = encoding {
+        response_headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(coding));
+    }
+    Some(response)
+}
+
+fn cache_control(path: &str) -> &'static str {
+    if path.starts_with(HASHED_ASSET_PREFIX) {
+        CACHE_IMMUTABLE
     } else {
-        // SPA fallback: serve index.html for any unmatched path
-        match Assets::get("index.html") {
-            Some(content) => Html(content.data.into_owned()).into_response(),
-            None => StatusCode::NOT_FOUND.into_response(),
-        }
+        CACHE_REVALIDATE
+    }
+}
+
+/// Whether an `Accept-Encoding` header allows `coding` (explicitly or via
+/// `*`), treating `q=0` as a refusal.
+fn accepts_encoding(accept_encoding: &str, coding: &str) -> bool {
+    accept_encoding.split(',').any(|entry| {
+        let mut parts = entry.split(';').map(str::trim);
+        let name = parts.next().unwrap_or("");
+        let refused = parts.any(|p| {
+            p.strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        (name.eq_ignore_ascii_case(coding) || name == "*") && !refused
+    })
+}
+
+/// `If-None-Match` uses weak comparison: `W/` prefixes are ignored.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashed_assets_are_immutable_and_everything_else_revalidates() {
+        assert_eq!(cache_control("assets/index-3f9a1c.js"), CACHE_IMMUTABLE);
+        assert_eq!(cache_control("index.html"), CACHE_REVALIDATE);
+        assert_eq!(cache_control("sw.js"), CACHE_REVALIDATE);
+        assert_eq!(cache_control("favicon.svg"), CACHE_REVALIDATE);
+    }
+
+    #[test]
+    fn accepts_encoding_honours_wildcards_and_zero_quality() {
+        assert!(accepts_encoding("gzip, deflate, br", "br"));
+        assert!(accepts_encoding("GZIP;q=0.5", "gzip"));
+        assert!(accepts_encoding("*", "br"));
+        assert!(!accepts_encoding("gzip", "br"));
+        assert!(!accepts_encoding("br;q=0, gzip", "br"));
+        assert!(!accepts_encoding("", "gzip"));
+    }
+
+    #[test]
+    fn etag_matches_lists_wildcards_and_weak_tags() {
+        let etag = "\"abc\"";
+        assert!(etag_matches("\"abc\"", etag));
+        assert!(etag_matches("\"zzz\", \"abc\"", etag));
+        assert!(etag_matches("W/\"abc\"", etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches("\"zzz\"", etag));
+    }
+
+    #[tokio::test]
+    async fn index_is_revalidated_with_etag() {
+        let first = static_handler(Uri::from_static("/"), HeaderMap::new()).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[header::CACHE_CONTROL], CACHE_REVALIDATE);
+        let etag = first.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let second = static_handler(Uri::from_static("/some/spa/route"), headers).await;
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
     }
 }
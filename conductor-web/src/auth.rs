@@ -0,0 +1,291 @@
+//! API token authentication.
+//!
+//! Requests to `/api` may carry a token as `Authorization: Bearer <secret>`.
+//! Event streams also accept `?token=<secret>` because `EventSource` cannot
+//! set headers. A token that is sent must be valid (401 otherwise) and its
+//! scope must cover the request (403 otherwise). Requests without a token
+//! are let through unless `[web.auth] required` is set.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use conductor_core::api_token::{ApiTokenManager, ApiTokenScope};
+
+use crate::state::AppState;
+
+/// Paths under `/api` that never require a token. Slack requests are
+/// verified by their own signature; shared runs by their link token.
+const PUBLIC_PREFIXES: &[&str] = &[
+    "/api/slack/",
+    "/api/shared/",
+    "/api/docs",
+    "/api/openapi.json",
+];
+
+/// The scope a token needs to make this request.
+///
+/// Reads need `read-only`; mutations on agents and conversations need
+/// `agent-control`; every other mutation, and anything under `/api/tokens`,
+/// needs `admin`.
+pub fn required_scope(method: &Method, path: &str) -> ApiTokenScope {
+    if path == "/api/tokens" || path.starts_with("/api/tokens/") {
+        return ApiTokenScope::Admin;
+    }
+    // Marking notifications read or clearing them only touches the caller's
+    // own read state, so any token may do it.
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || path == "/api/notifications"
+        || path.starts_with("/api/notifications/")
+    {
+        return ApiTokenScope::ReadOnly;
+    }
+    let agent_path = path.starts_with("/api/conversations")
+        || path
+            .split('/')
+            .any(|segment| segment == "agent" || segment.starts_with("agent-"));
+    if agent_path {
+        ApiTokenScope::AgentControl
+    } else {
+        ApiTokenScope::Admin
+    }
+}
+
+fn bearer_token(req: &Request) -> Option<String> {
+    let from_header = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string);
+    if from_header.is_some() {
+        return from_header;
+    }
+    if req.method() != Method::GET || !req.uri().path().ends_with("/events") {
+        return None;
+    }
+    req.uri().query().and_then(|q| {
+        serde_urlencoded::from_str::<Vec<(String, String)>>(q)
+            .ok()?
+            .into_iter()
+            .find(|(k, v)| k == "token" && !v.is_empty())
+            .map(|(_, v)| v)
+    })
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    let mut resp = (status, axum::Json(serde_json::json!({ "error": message }))).into_response();
+    if status == StatusCode::UNAUTHORIZED {
+        resp.headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    }
+    resp
+}
+
+/// Middleware enforcing API token authentication and scopes on `/api` routes.
+/// The authenticated [`conductor_core::api_token::ApiToken`] is added to the
+/// request extensions.
+pub async fn authenticate(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    let path = req.uri().path();
+    if !path.starts_with("/api/") || PUBLIC_PREFIXES.iter().any(|p| path.starts_with(p)) {
+        return next.run(req).await;
+    }
+
+    let Some(secret) = bearer_token(&req) else {
+        if state.web_config.read().await.auth.required {
+            return error_response(StatusCode::UNAUTHORIZED, "an API token is required");
+        }
+        return next.run(req).await;
+    };
+
+    let token = {
+        let db = state.db.lock().await;
+        ApiTokenManager::new(&db).authenticate(&secret)
+    };
+    let token = match token {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            if let Some(resp) = crate::limits::charge_peer(&state.limiter, &req) {
+                return resp;
+            }
+            return error_response(StatusCode::UNAUTHORIZED, "invalid or revoked API token");
+        }
+        Err(e) => {
+            tracing::error!("API token lookup failed: {e}");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "API token lookup failed");
+        }
+    };
+
+    let required = required_scope(req.method(), req.uri().path());
+    if !token.scope.allows(required) {
+        tracing::warn!(
+            token = %token.prefix,
+            scope = %token.scope,
+            required = %required,
+            path = %req.uri().path(),
+            "API token scope too narrow"
+        );
+        return error_response(
+            StatusCode::FORBIDDEN,
+            &format!("this request needs a token with the {required} scope"),
+        );
+    }
+    req.extensions_mut().insert(token);
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::test_helpers::seeded_state;
+
+    #[test]
+    fn scopes_required_by_route() {
+        use ApiTokenScope::*;
+        assert_eq!(required_scope(&Method::GET, "/api/repos"), ReadOnly);
+        assert_eq!(required_scope(&Method::GET, "/api/tokens"), Admin);
+        assert_eq!(
+            required_scope(&Method::POST, "/api/worktrees/w1/agent/start"),
+            AgentControl
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/agent-queue/q1"),
+            AgentControl
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/conversations/c1/messages"),
+            AgentControl
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/notifications/read-all"),
+            ReadOnly
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/notifications"),
+            ReadOnly
+        );
+        assert_eq!(required_scope(&Method::DELETE, "/api/repos/r1"), Admin);
+        assert_eq!(required_scope(&Method::POST, "/api/agents-fake"), Admin);
+    }
+
+    async fn status(app: &Router, method: Method, uri: &str, token: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        app.clone()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn tokens_are_validated_and_scoped() {
+        let (state, _db) = seeded_state();
+        let (read_only, agent) = {
+            let db = state.db.lock().await;
+            let mgr = ApiTokenManager::new(&db);
+            let (_, read_only) = mgr.create("ro", ApiTokenScope::ReadOnly).unwrap();
+            let (_, agent) = mgr.create("bot", ApiTokenScope::AgentControl).unwrap();
+            (read_only, agent)
+        };
+        let app = crate::routes::api_router()
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                authenticate,
+            ))
+            .with_state(state.clone());
+
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", Some("cdt_bogus")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", Some(&read_only)).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(
+                &app,
+                Method::DELETE,
+                "/api/agent-queue/missing",
+                Some(&read_only)
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(
+                &app,
+                Method::DELETE,
+                "/api/agent-queue/missing",
+                Some(&agent)
+            )
+            .await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(&app, Method::GET, "/api/tokens", Some(&agent)).await,
+            StatusCode::FORBIDDEN
+        );
+
+        state.web_config.write().await.auth.required = true;
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, Method::GET, "/health", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(
+                &app,
+                Method::GET,
+                &format!("/api/events?token={read_only}"),
+                None
+            )
+            .await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_token_attempts_are_rate_limited_per_peer() {
+        let (mut state, _db) = seeded_state();
+        state.limiter = std::sync::Arc::new(crate::limits::RequestLimiter::new(
+            crate::config::WebLimitsConfig {
+                requests_per_minute: 1,
+                burst: 1,
+                ..Default::default()
+            },
+        ));
+        let app = crate::routes::api_router()
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                authenticate,
+            ))
+            .with_state(state);
+
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", Some("cdt_guess1")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, Method::GET, "/api/repos", Some("cdt_guess2")).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Bridges `conductor_core::changes` onto the event bus, so `/api/events`
+//! also reports what the TUI, CLI and daemon change in the database.
+//!
+//! Once a second the feed reads new `change_log` rows and emits the matching
+//! [`ConductorEvent`]s. Events the web API already emitted for its own writes
+//! are skipped: the feed watches the bus and remembers their [`dedup_key`]s.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use conductor_core::changes::{self, Change, ChangeOp};
+use conductor_core::worktree::WorktreeStatus;
+
+use crate::events::ConductorEvent;
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const BATCH: usize = 500;
+/// How long an event seen on the bus suppresses the same event from the
+/// change log. Most route handlers emit while still holding the database
+/// lock, so their event reaches the bus before the feed can read the change;
+/// where it does not, the browser just refetches twice.
+const DEDUP_WINDOW: Duration = Duration::from_secs(10);
+
+/// Poll the change log and forward changes to the event bus until the bus
+/// closes.
+pub fn spawn(state: AppState) {
+    let mut rx = state.events.subscribe();
+    tokio::spawn(async move {
+        let mut recent = RecentKeys::default();
+        let mut cursor = {
+            let db = state.db.lock().await;
+            match changes::latest_id(&db) {
+                Ok(id) => id,
+                Err(e) => {
+                    tracing::warn!("change feed disabled: {e}");
+                    return;
+                }
+            }
+        };
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            loop {
+                match rx.try_recv() {
+                    Ok(event) => recent.insert(dedup_key(&event)),
+                    Err(tokio::sync::broadcast::error::TryRecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::TryRecvError::Empty) => break,
+                    Err(tokio::sync::broadcast::error::TryRecvError::Closed) => return,
+                }
+            }
+            let batch = {
+                let db = state.db.lock().await;
+                changes::since(&db, cursor, BATCH)
+            };
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    tracing::warn!("reading change log failed: {e}");
+                    continue;
+                }
+            };
+            let Some(last) = batch.last() else {
+                continue;
+            };
+            cursor = last.id;
+            for event in events_for(&batch) {
+                let key = dedup_key(&event);
+                if !recent.contains(&key) {
+                    state.events.emit(event);
+                }
+            }
+        }
+    });
+}
+
+/// Event keys seen on the bus within the last [`DEDUP_WINDOW`].
+#[derive(Default)]
+struct RecentKeys {
+    order: VecDeque<(Instant, String)>,
+    set: HashSet<String>,
+}
+
+impl RecentKeys {
+    fn insert(&mut self, key: String) {
+        let now = Instant::now();
+        while let Some((at, _)) = self.order.front() {
+            if now.duration_since(*at) < DEDUP_WINDOW {
+                break;
+            }
+            if let Some((_, old)) = self.order.pop_front() {
+                self.set.remove(&old);
+            }
+        }
+        if self.set.insert(key.clone()) {
+            self.order.push_back((now, key));
+        }
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.set.contains(key)
+    }
+}
+
+/// Identifies an event by what it is about, so the same change reported by a
+/// route handler and by the change log is only emitted once.
+fn dedup_key(event: &ConductorEvent) -> String {
+    let subject = match event {
+        ConductorEvent::RepoRegistered { id } | ConductorEvent::RepoUnregistered { id } => {
+            id.clone()
+        }
+        ConductorEvent::WorktreeCreated { id, .. } | ConductorEvent::WorktreeDeleted { id, .. } => {
+            id.clone()
+        }
+        ConductorEvent::TicketsSynced { repo_id } => repo_id.clone(),
+        ConductorEvent::AgentStarted { run_id, .. }
+        | ConductorEvent::AgentStopped { run_id, .. }
+        | ConductorEvent::RepoAgentStarted { run_id, .. }
+        | ConductorEvent::RepoAgentStopped { run_id, .. } => run_id.clone(),
+        ConductorEvent::FeedbackRequested { feedback_id, .. }
+        | ConductorEvent::FeedbackSubmitted { feedback_id, .. } => feedback_id.clone(),
+        ConductorEvent::WorkflowRunStatusChanged { run_id, status, .. } => {
+            format!("{run_id}:{status}")
+        }
+        ConductorEvent::WorkflowStepStatusChanged {
+            step_id, status, ..
+        } => format!("{step_id}:{status}"),
+        ConductorEvent::WorkflowGateWaiting { step_id, .. } => step_id.clone(),
+        _ => String::new(),
+    };
+    format!("{}:{subject}", event.event_name())
+}
+
+/// The events a batch of changes stands for. Ticket changes are reported as
+/// one `tickets_synced` per repo.
+fn events_for(batch: &[Change]) -> Vec<ConductorEvent> {
+    let mut events = Vec::new();
+    let mut synced_repos = HashSet::new();
+    for change in batch {
+        let status = change.status.as_deref().unwrap_or_default();
+        match (change.table.as_str(), change.op) {
+            ("repos", ChangeOp::Insert) => events.push(ConductorEvent::RepoRegistered {
+                id: change.row_id.clone(),
+            }),
+            ("repos", ChangeOp::Delete) => events.push(ConductorEvent::RepoUnregistered {
+                id: change.row_id.clone(),
+            }),
+            ("worktrees", op) => {
+                let Some(repo_id) = change.repo_id.clone() else {
+                    continue;
+                };
+                let done = status.parse::<WorktreeStatus>().is_ok_and(|s| s.is_done());
+                let id = change.row_id.clone();
+                match op {
+                    ChangeOp::Insert => events.push(ConductorEvent::WorktreeCreated {
+                        id,
+                        repo_id,
+                        job_id: None,
+                    }),
+                    // Merged and abandoned worktrees leave the UI just like
+                    // deleted ones.
+                    ChangeOp::Delete => {
+                        events.push(ConductorEvent::WorktreeDeleted { id, repo_id })
+                    }
+                    ChangeOp::Update if done => {
+                        events.push(ConductorEvent::WorktreeDeleted { id, repo_id })
+                    }
+                    ChangeOp::Update => {}
+                }
+            }
+            ("tickets", _) => {
+                if let Some(repo_id) = &change.repo_id {
+                    if synced_repos.insert(repo_id.clone()) {
+                        events.push(ConductorEvent::TicketsSynced {
+                            repo_id: repo_id.clone(),
+                        });
+                    }
+                }
+            }
+            ("agent_runs", op) => {
+                let run_id = change.row_id.clone();
+                let finished = matches!(status, "completed" | "failed" | "cancelled");
+                let event = match (op, &change.worktree_id, &change.repo_id) {
+                    (ChangeOp::Insert, Some(wt), _) => ConductorEvent::AgentStarted {
+                        run_id,
+                        worktree_id: wt.clone(),
+                    },
+                    (ChangeOp::Insert, None, Some(repo)) => ConductorEvent::RepoAgentStarted {
+                        run_id,
+                        repo_id: repo.clone(),
+                    },
+                    (ChangeOp::Update, Some(wt), _) if finished => ConductorEvent::AgentStopped {
+                        run_id,
+                        worktree_id: wt.clone(),
+                    },
+                    (ChangeOp::Update, None, Some(repo)) if finished => {
+                        ConductorEvent::RepoAgentStopped {
+                            run_id,
+                            repo_id: repo.clone(),
+                        }
+                    }
+                    _ => continue,
+                };
+                events.push(event);
+            }
+            ("workflow_runs", _) => events.push(ConductorEvent::WorkflowRunStatusChanged {
+                run_id: change.row_id.clone(),
+                worktree_id: change.worktree_id.clone(),
+                status: status.to_string(),
+            }),
+            ("workflow_run_steps", _) => {
+                let Some(run_id) = change.run_id.clone() else {
+                    continue;
+                };
+                events.push(ConductorEvent::WorkflowStepStatusChanged {
+                    run_id: run_id.clone(),
+                    step_id: change.row_id.clone(),
+                    status: status.to_string(),
+                });
+                if status == "waiting" {
+                    events.push(ConductorEvent::WorkflowGateWaiting {
+                        run_id,
+                        step_id: change.row_id.clone(),
+                    });
+                }
+            }
+            ("feedback_requests", op) => {
+                let (Some(run_id), Some(worktree_id)) =
+                    (change.run_id.clone(), change.worktree_id.clone())
+                else {
+                    continue;
+                };
+                let feedback_id = change.row_id.clone();
+                match op {
+                    ChangeOp::Insert => events.push(ConductorEvent::FeedbackRequested {
+                        run_id,
+                        worktree_id,
+                        feedback_id,
+                    }),
+                    ChangeOp::Update if status == "responded" => {
+                        events.push(ConductorEvent::FeedbackSubmitted {
+                            run_id,
+                            worktree_id,
+                            feedback_id,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(table: &str, op: ChangeOp, row_id: &str) -> Change {
+        Change {
+            id: 0,
+            table: table.into(),
+            op,
+            row_id: row_id.into(),
+            repo_id: Some("r1".into()),
+            worktree_id: None,
+            run_id: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn changes_map_to_bus_events() {
+        let batch = [
+            Change {
+                status: Some("active".into()),
+                ..change("worktrees", ChangeOp::Insert, "w1")
+            },
+            Change {
+                status: Some("parked".into()),
+                ..change("worktrees", ChangeOp::Update, "w1")
+            },
+            Change {
+                status: Some("merged".into()),
+                ..change("worktrees", ChangeOp::Update, "w1")
+            },
+            change("tickets", ChangeOp::Insert, "t1"),
+            change("tickets", ChangeOp::Update, "t2"),
+            Change {
+                worktree_id: Some("w1".into()),
+                status: Some("running".into()),
+                ..change("agent_runs", ChangeOp::Insert, "a1")
+            },
+            Change {
+                worktree_id: Some("w1".into()),
+                status: Some("completed".into()),
+                ..change("agent_runs", ChangeOp::Update, "a1")
+            },
+            Change {
+                run_id: Some("wf1".into()),
+                status: Some("waiting".into()),
+                ..change("workflow_run_steps", ChangeOp::Update, "s1")
+            },
+        ];
+        let names: Vec<_> = events_for(&batch).iter().map(|e| e.event_name()).collect();
+        assert_eq!(
+            names,
+            [
+                "worktree_created",
+                "worktree_deleted",
+                "tickets_synced",
+                "agent_started",
+                "agent_stopped",
+                "workflow_step_status_changed",
+                "workflow_gate_waiting",
+            ]
+        );
+    }
+
+    #[test]
+    fn route_and_change_log_events_share_a_key() {
+        let from_route = ConductorEvent::WorktreeCreated {
+            id: "w1".into(),
+            repo_id: "r1".into(),
+            job_id: Some("job".into()),
+        };
+        let from_log = &events_for(&[change("worktrees", ChangeOp::Insert, "w1")])[0];
+        assert_eq!(dedup_key(&from_route), dedup_key(from_log));
+    }
+}
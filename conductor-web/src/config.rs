@@ -18,6 +18,61 @@ pub struct WebPushConfig {
     pub vapid_subject: Option<String>,
 }
 
+/// Request protection for the API, stored under `[web.limits]`. Read at startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebLimitsConfig {
+    /// Sustained requests per minute allowed per client (bearer token, else IP).
+    /// `0` disables rate limiting.
+    #[serde(default = "default_requests_per_minute")]
+    pub requests_per_minute: u32,
+    /// Requests a client may make back-to-back before the per-minute rate applies.
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+    /// Maximum size of a JSON (non-multipart) request body, in bytes.
+    #[serde(default = "default_max_json_body_bytes")]
+    pub max_json_body_bytes: usize,
+    /// Maximum size of a multipart upload (message attachments), in bytes.
+    #[serde(default = "default_max_upload_bytes")]
+    pub max_upload_bytes: usize,
+}
+
+fn default_requests_per_minute() -> u32 {
+    600
+}
+
+fn default_burst() -> u32 {
+    120
+}
+
+fn default_max_json_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_max_upload_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+impl Default for WebLimitsConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: default_requests_per_minute(),
+            burst: default_burst(),
+            max_json_body_bytes: default_max_json_body_bytes(),
+            max_upload_bytes: default_max_upload_bytes(),
+        }
+    }
+}
+
+/// API token enforcement, stored under `[web.auth]`. Read per request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebAuthConfig {
+    /// Reject `/api` requests that carry no token. When `false`, requests
+    /// without a token are allowed, but any token that is sent must be valid
+    /// and its scope is enforced.
+    #[serde(default)]
+    pub required: bool,
+}
+
 /// Web-specific configuration stored under `[web]` in `~/.conductor/config.toml`.
 ///
 /// Mirrors the `[tui]` parent-section pattern from #2679/#2838.
@@ -25,12 +80,16 @@ pub struct WebPushConfig {
 pub struct WebConfig {
     #[serde(default)]
     pub push: WebPushConfig,
+    #[serde(default)]
+    pub limits: WebLimitsConfig,
+    #[serde(default)]
+    pub auth: WebAuthConfig,
 }
 
 /// Load web config from `~/.conductor/config.toml`, reading the `[web]` section.
 ///
 /// Falls back to top-level `[web_push]` when `[web]` is absent (legacy migration path).
-/// The deprecation warn for `[web_push]` is emitted by `conductor-core::load_config_from`.
+/// `conductor-core::load_config_from` migrates `[web_push]` to `[web].push` on load.
 pub fn load_web_config() -> Result<WebConfig> {
     load_from(&conductor_core::config::config_path())
 }
@@ -64,10 +123,13 @@ fn load_from(path: &Path) -> Result<WebConfig> {
     }
 
     // Legacy fallback: top-level [web_push] section (deprecated).
-    // conductor-core::load_config_from emits the deprecation warn — no duplicate here.
+    // Normally migrated to [web].push by conductor-core::load_config_from.
     if let Some(legacy) = raw.get("web_push") {
         if let Ok(push_cfg) = legacy.clone().try_into::<WebPushConfig>() {
-            return Ok(WebConfig { push: push_cfg });
+            return Ok(WebConfig {
+                push: push_cfg,
+                ..WebConfig::default()
+            });
         }
     }
 
@@ -125,6 +187,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_reads_limits_section_with_defaults() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[web.limits]\nrequests_per_minute = 30\n").unwrap();
+        let cfg = load_from(&path).unwrap();
+        assert_eq!(cfg.limits.requests_per_minute, 30);
+        assert_eq!(cfg.limits.burst, 120);
+        assert_eq!(cfg.limits.max_json_body_bytes, 1024 * 1024);
+    }
+
+    #[test]
+    fn test_load_reads_auth_section() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[web.auth]\nrequired = true\n").unwrap();
+        assert!(load_from(&path).unwrap().auth.required);
+
+        std::fs::write(&path, "[web.limits]\nburst = 5\n").unwrap();
+        assert!(!load_from(&path).unwrap().auth.required);
+    }
+
     #[test]
     fn test_load_legacy_web_push_fallback() {
         let dir = tempdir().unwrap();
@@ -197,6 +281,8 @@ mod tests {
                 vapid_private_key: Some("priv_key".to_string()),
                 vapid_subject: Some("mailto:test@example.com".to_string()),
             },
+            limits: Default::default(),
+            auth: Default::default(),
         };
         save_to(&cfg, &path).unwrap();
         let reloaded = load_from(&path).unwrap();
@@ -224,6 +310,8 @@ mod tests {
                 vapid_private_key: None,
                 vapid_subject: None,
             },
+            limits: Default::default(),
+            auth: Default::default(),
         };
         save_to(&cfg, &path).unwrap();
 
@@ -278,6 +366,8 @@ mod tests {
                 vapid_private_key: Some("priv".to_string()),
                 vapid_subject: Some("mailto:test@example.com".to_string()),
             },
+            limits: Default::default(),
+            auth: Default::default(),
         };
         save_to(&cfg, &path).unwrap();
 
@@ -41,21 +41,34 @@ impl IntoResponse for ApiError {
                     | ConductorError::WorkflowStepNotFound { .. }
                     | ConductorError::WorkflowStepNotInRun { .. }
                     | ConductorError::AgentRunNotFound { .. }
+                    | ConductorError::QueuedAgentRunNotFound { .. }
+                    | ConductorError::ApiTokenNotFound { .. }
+                    | ConductorError::NotificationNotFound { .. }
+                    | ConductorError::GroomingProposalNotFound { .. }
+                    | ConductorError::RunShareNotFound { .. }
                     | ConductorError::FeedbackNotFound { .. }
                     | ConductorError::AgentRunNotInConversation { .. }
                     | ConductorError::FeedbackRunMismatch { .. }
                     | ConductorError::ConversationNotFound { .. } => StatusCode::NOT_FOUND,
                     ConductorError::RepoAlreadyExists { .. }
                     | ConductorError::WorktreeAlreadyExists { .. }
+                    | ConductorError::WorktreeCreationInProgress { .. }
                     | ConductorError::IssueSourceAlreadyExists { .. }
                     | ConductorError::TicketAlreadyLinked
                     | ConductorError::WorkflowRunAlreadyActive { .. }
-                    | ConductorError::ConversationHasActiveRun { .. } => StatusCode::CONFLICT,
+                    | ConductorError::ConversationHasActiveRun { .. }
+                    | ConductorError::RebaseConflict { .. } => StatusCode::CONFLICT,
                     ConductorError::TicketSync(_) => StatusCode::BAD_GATEWAY,
                     ConductorError::Agent(_)
                     | ConductorError::InvalidInput(_)
                     | ConductorError::UnknownSourceType(_)
                     | ConductorError::NoPendingFeedbackForRun { .. } => StatusCode::BAD_REQUEST,
+                    ConductorError::PrePushCheckFailed { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                    ConductorError::WorkspaceQuotaExceeded { .. } => {
+                        StatusCode::INSUFFICIENT_STORAGE
+                    }
+                    ConductorError::AgentBudgetExceeded(_) => StatusCode::PAYMENT_REQUIRED,
+                    ConductorError::CommandTimedOut { .. } => StatusCode::GATEWAY_TIMEOUT,
                     _ => StatusCode::INTERNAL_SERVER_ERROR,
                 };
                 let msg = err.to_string();
@@ -113,6 +126,42 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn workspace_quota_exceeded_maps_to_507() {
+        let err = ApiError::Core(ConductorError::WorkspaceQuotaExceeded {
+            repo_slug: "r".into(),
+            used_bytes: 2048,
+            limit_bytes: 1024,
+            hint: String::new(),
+        });
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::INSUFFICIENT_STORAGE);
+    }
+
+    #[test]
+    fn agent_budget_exceeded_maps_to_402() {
+        let err = ApiError::Core(ConductorError::AgentBudgetExceeded(
+            conductor_core::agent::BudgetExceeded {
+                scope: conductor_core::agent::BudgetScope::Worktree,
+                target_id: "w1".into(),
+                target: "feat-test".into(),
+                spent_usd: 12.0,
+                limit_usd: 10.0,
+            },
+        ));
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    }
+
+    #[test]
+    fn command_timed_out_maps_to_504() {
+        let err = ApiError::Core(ConductorError::CommandTimedOut {
+            command: "`git fetch origin`".into(),
+            timeout_secs: 300,
+        });
+        assert_eq!(err.into_response().status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
     #[tokio::test]
     async fn join_error_panic_sanitized_to_generic_message() {
         // Verify that a panicking spawn_blocking task does NOT leak the panic
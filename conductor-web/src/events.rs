@@ -1,3 +1,6 @@
+use conductor_core::agent::BudgetScope;
+use conductor_core::repo_visibility::RepoScoped;
+use conductor_core::worktree::WorktreeCreateStage;
 use serde::Serialize;
 use tokio::sync::broadcast;
 
@@ -10,7 +13,26 @@ pub enum ConductorEvent {
     #[serde(rename = "repo_unregistered")]
     RepoUnregistered { id: String },
     #[serde(rename = "worktree_created")]
-    WorktreeCreated { id: String, repo_id: String },
+    WorktreeCreated {
+        id: String,
+        repo_id: String,
+        /// Set when the worktree was created by a background job.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        job_id: Option<String>,
+    },
+    #[serde(rename = "worktree_create_progress")]
+    WorktreeCreateProgress {
+        job_id: String,
+        repo_id: String,
+        stage: WorktreeCreateStage,
+        label: String,
+    },
+    #[serde(rename = "worktree_create_failed")]
+    WorktreeCreateFailed {
+        job_id: String,
+        repo_id: String,
+        error: String,
+    },
     #[serde(rename = "worktree_deleted")]
     WorktreeDeleted { id: String, repo_id: String },
     #[serde(rename = "tickets_synced")]
@@ -19,6 +41,8 @@ pub enum ConductorEvent {
     AgentStarted { run_id: String, worktree_id: String },
     #[serde(rename = "agent_stopped")]
     AgentStopped { run_id: String, worktree_id: String },
+    #[serde(rename = "agent_queue_changed")]
+    AgentQueueChanged { worktree_id: String },
     #[serde(rename = "repo_agent_started")]
     RepoAgentStarted { run_id: String, repo_id: String },
     #[serde(rename = "repo_agent_stopped")]
@@ -74,6 +98,37 @@ pub enum ConductorEvent {
         kind: String,
         summary: String,
     },
+    /// An agent run reached a terminal status, whoever launched it.
+    #[serde(rename = "agent_run_finished")]
+    AgentRunFinished {
+        run_id: String,
+        worktree_id: Option<String>,
+        succeeded: bool,
+        error: Option<String>,
+    },
+    /// A root workflow run reached a terminal status, whoever launched it.
+    #[serde(rename = "workflow_run_finished")]
+    WorkflowRunFinished {
+        run_id: String,
+        workflow_name: String,
+        worktree_id: Option<String>,
+        succeeded: bool,
+        error: Option<String>,
+    },
+    /// A finished run pushed its worktree or Claude session past its
+    /// `[budget]`; further starts are refused until overridden.
+    #[serde(rename = "budget_exceeded")]
+    BudgetExceeded {
+        run_id: String,
+        worktree_id: Option<String>,
+        scope: BudgetScope,
+        /// Worktree slug or session ID.
+        target: String,
+        spent_usd: f64,
+        limit_usd: f64,
+    },
+    #[serde(rename = "notifications_changed")]
+    NotificationsChanged,
 }
 
 impl ConductorEvent {
@@ -83,10 +138,13 @@ impl ConductorEvent {
             Self::RepoRegistered { .. } => "repo_registered",
             Self::RepoUnregistered { .. } => "repo_unregistered",
             Self::WorktreeCreated { .. } => "worktree_created",
+            Self::WorktreeCreateProgress { .. } => "worktree_create_progress",
+            Self::WorktreeCreateFailed { .. } => "worktree_create_failed",
             Self::WorktreeDeleted { .. } => "worktree_deleted",
             Self::TicketsSynced { .. } => "tickets_synced",
             Self::AgentStarted { .. } => "agent_started",
             Self::AgentStopped { .. } => "agent_stopped",
+            Self::AgentQueueChanged { .. } => "agent_queue_changed",
             Self::RepoAgentStarted { .. } => "repo_agent_started",
             Self::RepoAgentStopped { .. } => "repo_agent_stopped",
             Self::AgentRestarted { .. } => "agent_restarted",
@@ -99,8 +157,44 @@ impl ConductorEvent {
             Self::WorkflowGateWaiting { .. } => "workflow_gate_waiting",
             Self::AgentStep { .. } => "agent_step",
             Self::AgentLiveEvent { .. } => "agent_live_event",
+            Self::AgentRunFinished { .. } => "agent_run_finished",
+            Self::WorkflowRunFinished { .. } => "workflow_run_finished",
+            Self::BudgetExceeded { .. } => "budget_exceeded",
+            Self::NotificationsChanged => "notifications_changed",
         }
     }
+
+    /// The row the event is about, so it is only sent to subscribers who can
+    /// see that row's repo. `None` for events not tied to a repo.
+    pub fn scoped_target(&self) -> Option<RepoScoped<'_>> {
+        Some(match self {
+            Self::RepoRegistered { id } | Self::RepoUnregistered { id } => RepoScoped::Repo(id),
+            Self::WorktreeCreated { repo_id, .. }
+            | Self::WorktreeCreateProgress { repo_id, .. }
+            | Self::WorktreeCreateFailed { repo_id, .. }
+            | Self::WorktreeDeleted { repo_id, .. }
+            | Self::TicketsSynced { repo_id }
+            | Self::RepoAgentStarted { repo_id, .. }
+            | Self::RepoAgentStopped { repo_id, .. }
+            | Self::IssueSourcesChanged { repo_id } => RepoScoped::Repo(repo_id),
+            Self::AgentStarted { worktree_id, .. }
+            | Self::AgentStopped { worktree_id, .. }
+            | Self::AgentQueueChanged { worktree_id }
+            | Self::AgentRestarted { worktree_id, .. }
+            | Self::AgentEvent { worktree_id, .. }
+            | Self::FeedbackRequested { worktree_id, .. }
+            | Self::FeedbackSubmitted { worktree_id, .. } => RepoScoped::Worktree(worktree_id),
+            Self::AgentStep { agent_run_id, .. } => RepoScoped::AgentRun(agent_run_id),
+            Self::AgentLiveEvent { run_id, .. }
+            | Self::AgentRunFinished { run_id, .. }
+            | Self::BudgetExceeded { run_id, .. } => RepoScoped::AgentRun(run_id),
+            Self::WorkflowRunStatusChanged { run_id, .. }
+            | Self::WorkflowStepStatusChanged { run_id, .. }
+            | Self::WorkflowGateWaiting { run_id, .. }
+            | Self::WorkflowRunFinished { run_id, .. } => RepoScoped::WorkflowRun(run_id),
+            Self::NotificationsChanged => return None,
+        })
+    }
 }
 
 /// Fan-out event bus built on `tokio::sync::broadcast`.
@@ -152,11 +246,22 @@ mod tests {
         let event = ConductorEvent::WorktreeCreated {
             id: "wt1".into(),
             repo_id: "r1".into(),
+            job_id: None,
         };
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"event\":\"worktree_created\""));
         assert!(json.contains("\"id\":\"wt1\""));
         assert!(json.contains("\"repo_id\":\"r1\""));
+        assert!(!json.contains("job_id"));
+
+        let progress = ConductorEvent::WorktreeCreateProgress {
+            job_id: "j1".into(),
+            repo_id: "r1".into(),
+            stage: WorktreeCreateStage::InstallingDeps,
+            label: WorktreeCreateStage::InstallingDeps.label().into(),
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains("\"stage\":\"installing_deps\""));
     }
 
     #[test]
@@ -174,9 +279,27 @@ mod tests {
                 ConductorEvent::WorktreeCreated {
                     id: "".into(),
                     repo_id: "".into(),
+                    job_id: None,
                 },
                 "worktree_created",
             ),
+            (
+                ConductorEvent::WorktreeCreateProgress {
+                    job_id: "".into(),
+                    repo_id: "".into(),
+                    stage: WorktreeCreateStage::Fetching,
+                    label: "".into(),
+                },
+                "worktree_create_progress",
+            ),
+            (
+                ConductorEvent::WorktreeCreateFailed {
+                    job_id: "".into(),
+                    repo_id: "".into(),
+                    error: "".into(),
+                },
+                "worktree_create_failed",
+            ),
             (
                 ConductorEvent::WorktreeDeleted {
                     id: "".into(),
@@ -195,6 +318,12 @@ mod tests {
                 },
                 "agent_started",
             ),
+            (
+                ConductorEvent::AgentQueueChanged {
+                    worktree_id: "".into(),
+                },
+                "agent_queue_changed",
+            ),
             (
                 ConductorEvent::AgentStopped {
                     run_id: "".into(),
@@ -291,6 +420,40 @@ mod tests {
                 },
                 "agent_live_event",
             ),
+            (
+                ConductorEvent::AgentRunFinished {
+                    run_id: "".into(),
+                    worktree_id: None,
+                    succeeded: true,
+                    error: None,
+                },
+                "agent_run_finished",
+            ),
+            (
+                ConductorEvent::WorkflowRunFinished {
+                    run_id: "".into(),
+                    workflow_name: "".into(),
+                    worktree_id: None,
+                    succeeded: false,
+                    error: None,
+                },
+                "workflow_run_finished",
+            ),
+            (
+                ConductorEvent::BudgetExceeded {
+                    run_id: "".into(),
+                    worktree_id: None,
+                    scope: BudgetScope::Worktree,
+                    target: "".into(),
+                    spent_usd: 0.0,
+                    limit_usd: 0.0,
+                },
+                "budget_exceeded",
+            ),
+            (
+                ConductorEvent::NotificationsChanged,
+                "notifications_changed",
+            ),
         ];
         for (event, expected) in cases {
             assert_eq!(event.event_name(), expected);
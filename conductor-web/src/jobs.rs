@@ -0,0 +1,160 @@
+//! In-memory registry of background worktree-creation jobs.
+//!
+//! `POST /api/repos/{id}/worktrees` returns 202 with a job id and runs the
+//! fetch/branch/checkout/install work in the background. Progress is pushed over
+//! SSE (`worktree_create_progress`, then `worktree_created` or
+//! `worktree_create_failed`); clients that miss events can poll
+//! `GET /api/worktree-jobs/{id}`. Jobs live only for the lifetime of the server.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::Serialize;
+
+use conductor_core::worktree::{Worktree, WorktreeCreateStage};
+
+/// Finished jobs beyond this count are dropped, oldest first.
+const MAX_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Snapshot of a worktree-creation job.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct WorktreeJob {
+    pub id: String,
+    pub repo_id: String,
+    pub name: String,
+    pub status: JobStatus,
+    /// Most recent stage reported; `None` until the first stage starts.
+    pub stage: Option<WorktreeCreateStage>,
+    pub worktree: Option<Worktree>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    pub commits_behind: u32,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Default)]
+pub struct WorktreeJobs {
+    jobs: Mutex<HashMap<String, WorktreeJob>>,
+}
+
+impl WorktreeJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new running job and return its id.
+    pub fn start(&self, repo_id: &str, name: &str, commits_behind: u32) -> String {
+        let id = conductor_core::new_id();
+        let job = WorktreeJob {
+            id: id.clone(),
+            repo_id: repo_id.to_string(),
+            name: name.to_string(),
+            status: JobStatus::Running,
+            stage: None,
+            worktree: None,
+            warnings: Vec::new(),
+            commits_behind,
+            error: None,
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+        let mut jobs = self.lock();
+        jobs.insert(id.clone(), job);
+        prune_finished(&mut jobs);
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<WorktreeJob> {
+        self.lock().get(id).cloned()
+    }
+
+    pub fn set_stage(&self, id: &str, stage: WorktreeCreateStage) {
+        if let Some(job) = self.lock().get_mut(id) {
+            job.stage = Some(stage);
+        }
+    }
+
+    pub fn succeed(&self, id: &str, worktree: Worktree, warnings: Vec<String>) {
+        if let Some(job) = self.lock().get_mut(id) {
+            job.status = JobStatus::Succeeded;
+            job.worktree = Some(worktree);
+            job.warnings = warnings;
+            job.finished_at = Some(Utc::now().to_rfc3339());
+        }
+    }
+
+    pub fn fail(&self, id: &str, error: String) {
+        if let Some(job) = self.lock().get_mut(id) {
+            job.status = JobStatus::Failed;
+            job.error = Some(error);
+            job.finished_at = Some(Utc::now().to_rfc3339());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, WorktreeJob>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn prune_finished(jobs: &mut HashMap<String, WorktreeJob>) {
+    let mut finished: Vec<(String, String)> = jobs
+        .values()
+        .filter_map(|j| j.finished_at.clone().map(|at| (at, j.id.clone())))
+        .collect();
+    if finished.len() <= MAX_FINISHED_JOBS {
+        return;
+    }
+    finished.sort();
+    let excess = finished.len() - MAX_FINISHED_JOBS;
+    for (_, id) in finished.into_iter().take(excess) {
+        jobs.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_lifecycle_and_pruning() {
+        let jobs = WorktreeJobs::new();
+        let id = jobs.start("r1", "feat-x", 2);
+        let job = jobs.get(&id).unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.stage, None);
+        assert_eq!(job.commits_behind, 2);
+
+        jobs.set_stage(&id, WorktreeCreateStage::Fetching);
+        assert_eq!(
+            jobs.get(&id).unwrap().stage,
+            Some(WorktreeCreateStage::Fetching)
+        );
+        jobs.fail(&id, "boom".into());
+        let job = jobs.get(&id).unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("boom"));
+        assert!(job.finished_at.is_some());
+
+        for _ in 0..MAX_FINISHED_JOBS + 5 {
+            let id = jobs.start("r1", "feat-y", 0);
+            jobs.fail(&id, "x".into());
+        }
+        let running = jobs.start("r1", "feat-z", 0);
+        assert!(
+            jobs.get(&running).is_some(),
+            "running jobs are never pruned"
+        );
+        assert_eq!(jobs.lock().len(), MAX_FINISHED_JOBS + 1);
+    }
+}
@@ -1,13 +1,19 @@
 pub mod assets;
 pub mod attachments;
+pub mod auth;
+pub mod change_feed;
 pub mod config;
 pub mod error;
 pub mod events;
+pub mod jobs;
+pub mod limits;
+pub mod notification_center;
 pub mod notify;
 pub mod openapi;
 pub mod push;
 pub mod routes;
 pub mod state;
+pub mod visibility;
 
 #[cfg(test)]
 pub mod test_helpers;
@@ -0,0 +1,356 @@
+//! Per-client rate limiting and request body size limits.
+//!
+//! The middleware runs inside `auth::authenticate`, so clients are keyed by
+//! the verified API token id when there is one (several users behind one proxy
+//! do not share a budget) and by peer IP otherwise. Made-up tokens never reach
+//! the limiter as tokens: `authenticate` charges failed attempts to the peer
+//! IP's bucket via [`charge_peer`]. Each key gets a token bucket holding
+//! `burst` requests that refills at `requests_per_minute`. Rejections are
+//! counted and exposed via `GET /api/stats/request-limits`.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use conductor_core::api_token::ApiToken;
+
+use crate::config::WebLimitsConfig;
+use crate::state::AppState;
+
+/// Buckets beyond this count trigger a sweep of fully-refilled (idle) entries.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Rejection counters and current limits, returned by the stats endpoint.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RequestLimitMetrics {
+    pub rate_limited_total: u64,
+    pub body_too_large_total: u64,
+    pub tracked_clients: usize,
+    pub requests_per_minute: u32,
+    pub burst: u32,
+    pub max_json_body_bytes: usize,
+    pub max_upload_bytes: usize,
+}
+
+pub struct RequestLimiter {
+    config: WebLimitsConfig,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    rate_limited: AtomicU64,
+    body_too_large: AtomicU64,
+}
+
+impl RequestLimiter {
+    pub fn new(config: WebLimitsConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            rate_limited: AtomicU64::new(0),
+            body_too_large: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &WebLimitsConfig {
+        &self.config
+    }
+
+    /// Take one token from `key`'s bucket. Returns the wait until a token is
+    /// available when the bucket is empty.
+    pub fn check(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        if self.config.requests_per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(self.config.burst.max(1));
+        let per_sec = f64::from(self.config.requests_per_minute) / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(key) {
+            buckets.retain(|_, b| {
+                b.tokens + now.saturating_duration_since(b.updated).as_secs_f64() * per_sec
+                    < capacity
+            });
+        }
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_sec).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            self.rate_limited.fetch_add(1, Ordering::Relaxed);
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+        }
+    }
+
+    fn record_body_too_large(&self) {
+        self.body_too_large.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn metrics(&self) -> RequestLimitMetrics {
+        let tracked_clients = self.buckets.lock().unwrap_or_else(|e| e.into_inner()).len();
+        RequestLimitMetrics {
+            rate_limited_total: self.rate_limited.load(Ordering::Relaxed),
+            body_too_large_total: self.body_too_large.load(Ordering::Relaxed),
+            tracked_clients,
+            requests_per_minute: self.config.requests_per_minute,
+            burst: self.config.burst,
+            max_json_body_bytes: self.config.max_json_body_bytes,
+            max_upload_bytes: self.config.max_upload_bytes,
+        }
+    }
+}
+
+/// Rate-limit key: the verified token's id when authenticated, else the peer IP.
+fn client_key(token: Option<&ApiToken>, peer: Option<SocketAddr>) -> String {
+    match (token, peer) {
+        (Some(t), _) => format!("token:{}", t.id),
+        (None, Some(addr)) => format!("ip:{}", addr.ip()),
+        (None, None) => "ip:unknown".to_string(),
+    }
+}
+
+fn peer_addr(req: &Request) -> Option<SocketAddr> {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0)
+}
+
+/// Take one token from `key`'s bucket, or build the 429 response.
+fn take_or_reject(limiter: &RequestLimiter, key: &str) -> Option<Response> {
+    let wait = limiter.check(key, Instant::now()).err()?;
+    let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    tracing::warn!(client = %key, retry_after = secs, "rate limit exceeded");
+    let mut resp = error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        format!("rate limit exceeded; retry in {secs}s"),
+    );
+    if let Ok(v) = HeaderValue::from_str(&secs.to_string()) {
+        resp.headers_mut().insert(header::RETRY_AFTER, v);
+    }
+    Some(resp)
+}
+
+/// Charge a request that failed authentication to its peer IP's bucket.
+/// Returns the 429 response to send instead once that bucket is empty.
+pub fn charge_peer(limiter: &RequestLimiter, req: &Request) -> Option<Response> {
+    take_or_reject(limiter, &client_key(None, peer_addr(req)))
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, axum::Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn payload_too_large(limiter: &RequestLimiter, limit: usize) -> Response {
+    limiter.record_body_too_large();
+    error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!("request body exceeds the {limit}-byte limit"),
+    )
+}
+
+/// Middleware enforcing `[web.limits]`: 429 with `Retry-After` when a client
+/// exceeds its rate, 413 when a body exceeds the size limit.
+///
+/// JSON bodies are buffered up to `max_json_body_bytes` so chunked requests
+/// without a `Content-Length` are still bounded; multipart bodies are left to
+/// the extractor, which is capped by the router's `DefaultBodyLimit`.
+pub async fn limit_requests(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let limiter = state.limiter.clone();
+    let key = client_key(req.extensions().get::<ApiToken>(), peer_addr(&req));
+    if let Some(resp) = take_or_reject(&limiter, &key) {
+        return resp;
+    }
+
+    let is_multipart = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("multipart/"));
+    let limit = if is_multipart {
+        limiter.config().max_upload_bytes
+    } else {
+        limiter.config().max_json_body_bytes
+    };
+    let declared_len = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_len.is_some_and(|len| len > limit) {
+        return payload_too_large(&limiter, limit);
+    }
+
+    if is_multipart || matches!(*req.method(), Method::GET | Method::HEAD) {
+        return next.run(req).await;
+    }
+    let (parts, body) = req.into_parts();
+    match axum::body::to_bytes(body, limit).await {
+        Ok(bytes) => {
+            next.run(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        }
+        Err(_) => payload_too_large(&limiter, limit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn limiter(rpm: u32, burst: u32) -> RequestLimiter {
+        RequestLimiter::new(WebLimitsConfig {
+            requests_per_minute: rpm,
+            burst,
+            ..WebLimitsConfig::default()
+        })
+    }
+
+    #[test]
+    fn bucket_allows_burst_then_rejects_with_retry_after() {
+        let l = limiter(60, 2);
+        let t0 = Instant::now();
+        assert!(l.check("a", t0).is_ok());
+        assert!(l.check("a", t0).is_ok());
+        let wait = l.check("a", t0).unwrap_err();
+        assert!(wait <= Duration::from_secs(1), "wait was {wait:?}");
+        // Other clients have their own budget.
+        assert!(l.check("b", t0).is_ok());
+        // One second at 60/min refills one token.
+        assert!(l.check("a", t0 + Duration::from_secs(1)).is_ok());
+        assert_eq!(l.metrics().rate_limited_total, 1);
+        assert_eq!(l.metrics().tracked_clients, 2);
+    }
+
+    #[test]
+    fn zero_rate_disables_limiting() {
+        let l = limiter(0, 1);
+        let t0 = Instant::now();
+        for _ in 0..10 {
+            assert!(l.check("a", t0).is_ok());
+        }
+    }
+
+    #[test]
+    fn client_key_prefers_verified_token_over_ip() {
+        let peer: SocketAddr = "10.0.0.5:4000".parse().unwrap();
+        assert_eq!(client_key(None, Some(peer)), "ip:10.0.0.5");
+        let token = ApiToken {
+            id: "tok1".into(),
+            name: "ci".into(),
+            scope: conductor_core::api_token::ApiTokenScope::ReadOnly,
+            prefix: "cdt_1a2b".into(),
+            created_at: String::new(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        assert_eq!(client_key(Some(&token), Some(peer)), "token:tok1");
+    }
+
+    #[tokio::test]
+    async fn made_up_bearer_tokens_share_the_peer_budget() {
+        let (app, _tmp) = limited_app(WebLimitsConfig {
+            requests_per_minute: 1,
+            burst: 1,
+            ..WebLimitsConfig::default()
+        });
+        let with_bearer = |secret: &str| {
+            let mut req = post_json("{}");
+            req.headers_mut().insert(
+                header::AUTHORIZATION,
+                format!("Bearer {secret}").parse().unwrap(),
+            );
+            req
+        };
+        let first = app.clone().oneshot(with_bearer("cdt_one")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let second = app.oneshot(with_bearer("cdt_two")).await.unwrap();
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    fn limited_app(config: WebLimitsConfig) -> (Router, tempfile::NamedTempFile) {
+        let (mut state, tmp) = crate::test_helpers::empty_state();
+        state.limiter = Arc::new(RequestLimiter::new(config));
+        let app = Router::new()
+            .route("/echo", post(|body: String| async move { body }))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                limit_requests,
+            ))
+            .with_state(state);
+        (app, tmp)
+    }
+
+    fn post_json(body: &str) -> Request {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn middleware_returns_429_with_retry_after() {
+        let (app, _tmp) = limited_app(WebLimitsConfig {
+            requests_per_minute: 1,
+            burst: 1,
+            ..WebLimitsConfig::default()
+        });
+        let first = app.clone().oneshot(post_json("{}")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let second = app.oneshot(post_json("{}")).await.unwrap();
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry: u64 = second.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry), "retry-after was {retry}");
+    }
+
+    #[tokio::test]
+    async fn middleware_rejects_oversized_json_body() {
+        let (app, _tmp) = limited_app(WebLimitsConfig {
+            max_json_body_bytes: 8,
+            ..WebLimitsConfig::default()
+        });
+        let ok = app.clone().oneshot(post_json("{}")).await.unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let body = http_body_util::BodyExt::collect(ok.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(&body[..], b"{}", "buffered body must reach the handler");
+
+        let big = app
+            .oneshot(post_json(r#"{"prompt":"far too long"}"#))
+            .await
+            .unwrap();
+        assert_eq!(big.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use anyhow::Result;
+use axum::extract::DefaultBodyLimit;
 use axum::http::{header, HeaderValue, Method};
+use axum::middleware;
 use conductor_core::agent::AgentManager;
 use conductor_core::config::db_path;
 use conductor_core::Conductor;
@@ -12,14 +14,26 @@ use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
 use conductor_web::assets::static_handler;
+use conductor_web::auth;
 use conductor_web::events::{ConductorEvent, EventBus};
+use conductor_web::limits::{self, RequestLimiter};
 use conductor_web::openapi::ApiDoc;
 use conductor_web::push::{self, PushPayload};
 use conductor_web::routes::api_router;
 use conductor_web::state::AppState;
+use conductor_web::visibility;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+/// Whether a `conductor daemon` is running; it then owns queue dispatch,
+/// reaping and retention, so the server skips its own.
+fn daemon_running() -> bool {
+    #[cfg(unix)]
+    return conductor_core::daemon::is_running();
+    #[cfg(not(unix))]
+    false
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -158,6 +172,7 @@ async fn main() -> Result<()> {
 
     // Install the conductor's connection and config in Axum router state.
     let Conductor { conn, config } = conductor;
+    let limit_cfg = web_cfg.limits.clone();
     let state = AppState {
         db: Arc::new(Mutex::new(conn)),
         config: Arc::new(RwLock::new(config)),
@@ -165,8 +180,54 @@ async fn main() -> Result<()> {
         events: EventBus::new(64),
         db_path: db_path(),
         workflow_done_notify: None,
+        limiter: Arc::new(RequestLimiter::new(limit_cfg.clone())),
+        worktree_jobs: Arc::new(conductor_web::jobs::WorktreeJobs::new()),
+        ticket_totals: Default::default(),
     };
 
+    // Start queued agent runs as slots free up (runs finishing, or
+    // `[agents] max_concurrent_runs` being raised), unless a daemon does.
+    let queue_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            if tokio::task::spawn_blocking(daemon_running)
+                .await
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            conductor_web::routes::agent_queue::dispatch_queued_agents(&queue_state).await;
+        }
+    });
+
+    // Apply `[retention]` windows once an hour, unless a daemon does.
+    let prune_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            let db = prune_state.db.clone();
+            let cfg = prune_state.config.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                if daemon_running() {
+                    return Ok(Default::default());
+                }
+                let conn = db.blocking_lock();
+                let retention = cfg.blocking_read().retention.clone();
+                conductor_core::retention::prune(&conn, &retention, false)
+            })
+            .await;
+            match result {
+                Ok(Ok(r)) if r.total() > 0 => tracing::info!("Retention pruned {r:?}"),
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::warn!("retention prune failed: {e}"),
+                Err(e) => tracing::warn!("retention prune task panicked: {e}"),
+            }
+        }
+    });
+
     // Spawn a background task that periodically reaps orphaned runs,
     // stale worktrees, and detects agent run terminal transitions for
     // notifications. Uses spawn_blocking to avoid blocking the tokio
@@ -192,6 +253,7 @@ async fn main() -> Result<()> {
         loop {
             interval.tick().await;
             let db = reaper_state.db.clone();
+            let events_bus = reaper_state.events.clone();
             let cfg = reaper_config.clone();
             let mut seen = std::mem::take(&mut seen_agent_statuses);
             let mut init = agent_initialized;
@@ -200,75 +262,86 @@ async fn main() -> Result<()> {
             let result = tokio::task::spawn_blocking(move || {
                 let conn = db.blocking_lock();
                 let mgr = AgentManager::new(&conn);
-                mgr.reap_orphaned_runs()?;
-                mgr.dismiss_expired_feedback_requests()?;
                 let cfg = cfg.blocking_read();
                 let wt_mgr = conductor_core::worktree::WorktreeManager::new(&conn, &cfg);
-                wt_mgr.reap_stale_worktrees()?;
-                if cfg.general.auto_cleanup_merged_branches {
-                    match wt_mgr.cleanup_merged_worktrees(None) {
-                        Ok(n) if n > 0 => {
-                            tracing::info!("Auto-cleaned {n} merged worktree(s)")
-                        }
-                        Ok(_) => {}
-                        Err(e) => tracing::warn!("cleanup_merged_worktrees failed: {e}"),
+                // A running `conductor daemon` does the reaping; notifications
+                // are still detected here.
+                if !daemon_running() {
+                    mgr.reap_orphaned_runs()?;
+                    mgr.dismiss_expired_feedback_requests()?;
+                    wt_mgr.reap_stale_worktrees()?;
+                    if let Err(e) = wt_mgr.check_missing_paths() {
+                        tracing::warn!("check_missing_paths failed: {e}");
                     }
-                }
-                conductor_core::workflow::reap_orphaned_workflow_runs(&conn)?;
-                conductor_core::workflow::reap_orphaned_script_steps(&conn)?;
-                match conductor_core::workflow::reap_finalization_stuck_workflow_runs(&conn, 60) {
-                    Ok(n) if n > 0 => {
-                        tracing::info!("Reaper finalized {n} stuck workflow run(s)")
+                    if cfg.general.auto_cleanup_merged_branches {
+                        match wt_mgr.cleanup_merged_worktrees(None) {
+                            Ok(n) if n > 0 => {
+                                tracing::info!("Auto-cleaned {n} merged worktree(s)")
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("cleanup_merged_worktrees failed: {e}"),
+                        }
                     }
-                    Ok(_) => {}
-                    Err(e) => {
-                        tracing::warn!("reap_finalization_stuck_workflow_runs failed: {e}")
+                    conductor_core::workflow::reap_orphaned_workflow_runs(&conn)?;
+                    conductor_core::workflow::reap_orphaned_script_steps(&conn)?;
+                    if let Err(e) = conductor_core::changes::prune(&conn) {
+                        tracing::warn!("pruning change_log failed: {e}");
                     }
-                }
-                if cfg.general.stale_workflow_minutes > 0 {
-                    match conductor_core::workflow::reap_stale_workflow_runs(
-                        &conn,
-                        cfg.general.stale_workflow_minutes as i64,
-                    ) {
-                        Ok(reaped) if !reaped.is_empty() => {
-                            tracing::info!("Reaped {} stale workflow run(s)", reaped.len());
+                    conductor_core::tickets::post_agent_results(&conn, &cfg);
+                    match conductor_core::workflow::reap_finalization_stuck_workflow_runs(&conn, 60) {
+                        Ok(n) if n > 0 => {
+                            tracing::info!("Reaper finalized {n} stuck workflow run(s)")
                         }
                         Ok(_) => {}
-                        Err(e) => tracing::warn!("reap_stale_workflow_runs failed: {e}"),
+                        Err(e) => {
+                            tracing::warn!("reap_finalization_stuck_workflow_runs failed: {e}")
+                        }
                     }
-                }
-                {
-                    let conductor_bin_dir = conductor_core::workflow::resolve_conductor_bin_dir();
-                    conductor_core::workflow::claim_and_resume_expired_leases(
-                        &conn,
-                        &cfg,
-                        conductor_bin_dir.clone(),
-                    );
-                    let auto_resume_limit = cfg.general.auto_resume_limit;
-                    if auto_resume_limit > 0 {
-                        match conductor_core::workflow::classify_resumable_workflows(
+                    if cfg.general.stale_workflow_minutes > 0 {
+                        match conductor_core::workflow::reap_stale_workflow_runs(
                             &conn,
-                            auto_resume_limit,
+                            cfg.general.stale_workflow_minutes as i64,
                         ) {
-                            Ok(n) if n > 0 => {
-                                tracing::info!(
-                                    "Classifier flagged {n} workflow run(s) for auto-resume"
-                                )
+                            Ok(reaped) if !reaped.is_empty() => {
+                                tracing::info!("Reaped {} stale workflow run(s)", reaped.len());
                             }
                             Ok(_) => {}
-                            Err(e) => tracing::warn!("classify_resumable_workflows failed: {e}"),
+                            Err(e) => tracing::warn!("reap_stale_workflow_runs failed: {e}"),
                         }
-                        match conductor_core::workflow::claim_needs_resume_runs(&conn, &cfg) {
-                            Ok(claimed) => {
-                                for run_id in claimed {
-                                    conductor_core::workflow::spawn_workflow_resume(
-                                        run_id,
-                                        Arc::new((*cfg).clone()),
-                                        conductor_bin_dir.clone(),
-                                    );
+                    }
+                    {
+                        let conductor_bin_dir = conductor_core::workflow::resolve_conductor_bin_dir();
+                        conductor_core::workflow::claim_and_resume_expired_leases(
+                            &conn,
+                            &cfg,
+                            conductor_bin_dir.clone(),
+                        );
+                        let auto_resume_limit = cfg.general.auto_resume_limit;
+                        if auto_resume_limit > 0 {
+                            match conductor_core::workflow::classify_resumable_workflows(
+                                &conn,
+                                auto_resume_limit,
+                            ) {
+                                Ok(n) if n > 0 => {
+                                    tracing::info!(
+                                        "Classifier flagged {n} workflow run(s) for auto-resume"
+                                    )
+                                }
+                                Ok(_) => {}
+                                Err(e) => tracing::warn!("classify_resumable_workflows failed: {e}"),
+                            }
+                            match conductor_core::workflow::claim_needs_resume_runs(&conn, &cfg) {
+                                Ok(claimed) => {
+                                    for run_id in claimed {
+                                        conductor_core::workflow::spawn_workflow_resume(
+                                            run_id,
+                                            Arc::new((*cfg).clone()),
+                                            conductor_bin_dir.clone(),
+                                        );
+                                    }
                                 }
+                                Err(e) => tracing::warn!("claim_needs_resume_runs failed: {e}"),
                             }
-                            Err(e) => tracing::warn!("claim_needs_resume_runs failed: {e}"),
                         }
                     }
                 }
@@ -295,6 +368,7 @@ async fn main() -> Result<()> {
                         Arc::new(conductor_core::notify::SqliteDedupStore::default_db()),
                         &conductor_web::notify::AgentRunNotificationArgs {
                             run_id: &t.run_id,
+                            worktree_id: t.worktree_id.as_deref(),
                             worktree_slug: t.worktree_slug.as_deref(),
                             succeeded: t.succeeded,
                             error_msg: t.error_msg.as_deref(),
@@ -306,6 +380,35 @@ async fn main() -> Result<()> {
                     );
                 }
 
+                for t in &transitions {
+                    events_bus.emit(ConductorEvent::AgentRunFinished {
+                        run_id: t.run_id.clone(),
+                        worktree_id: t.worktree_id.clone(),
+                        succeeded: t.succeeded,
+                        error: t.error_msg.clone(),
+                    });
+                    match mgr.budget_crossed_by(&cfg.budget, &t.run_id) {
+                        Ok(crossed) => {
+                            for b in crossed {
+                                events_bus.emit(ConductorEvent::BudgetExceeded {
+                                    run_id: t.run_id.clone(),
+                                    worktree_id: t.worktree_id.clone(),
+                                    scope: b.scope,
+                                    target: b.target,
+                                    spent_usd: b.spent_usd,
+                                    limit_usd: b.limit_usd,
+                                });
+                            }
+                        }
+                        Err(e) => tracing::warn!(run_id = %t.run_id, "budget check failed: {e}"),
+                    }
+                }
+
+                // Pop stashes taken at launch for agent runs that have now ended.
+                if let Err(e) = conductor_core::agent::LaunchGuards::new(&conn).restore_finished() {
+                    tracing::warn!("restoring pre-run stashes failed: {e}");
+                }
+
                 // Collect push payloads for agent run transitions (sent in outer async context).
                 let mut agent_push_payloads: Vec<PushPayload> = Vec::new();
                 for t in &transitions {
@@ -368,6 +471,48 @@ async fn main() -> Result<()> {
                     );
                 }
 
+                for t in &wf_transitions {
+                    events_bus.emit(ConductorEvent::WorkflowRunFinished {
+                        run_id: t.run_id.clone(),
+                        workflow_name: t.workflow_name.clone(),
+                        worktree_id: t.worktree_id.clone(),
+                        succeeded: t.succeeded,
+                        error: t.error.clone(),
+                    });
+                }
+
+                // Fire notifications for changes on watched tickets and worktrees.
+                match conductor_core::watch::WatchManager::new(&conn).poll_changes() {
+                    Ok(changes) => {
+                        for change in &changes {
+                            conductor_web::notify::fire_watch_notification(
+                                &cfg.notifications,
+                                &cfg.notify.hooks,
+                                Arc::new(conductor_core::notify::SqliteDedupStore::default_db()),
+                                change,
+                            );
+                        }
+                    }
+                    Err(e) => tracing::warn!("watch poll_changes failed: {e}"),
+                }
+
+                // Raise and notify agent cost and progress alerts.
+                match conductor_core::agent::AgentAlerts::new(&conn)
+                    .check(&cfg.notifications.cost_alerts)
+                {
+                    Ok(alerts) => {
+                        for alert in &alerts {
+                            conductor_web::notify::fire_agent_alert_notification(
+                                &cfg.notifications,
+                                &cfg.notify.hooks,
+                                Arc::new(conductor_core::notify::SqliteDedupStore::default_db()),
+                                alert,
+                            );
+                        }
+                    }
+                    Err(e) => tracing::warn!("agent cost alert check failed: {e}"),
+                }
+
                 // Collect push payloads for workflow run transitions (sent in outer async context).
                 let mut wf_push_payloads: Vec<PushPayload> = Vec::new();
                 for t in &wf_transitions {
@@ -651,6 +796,13 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Record agent/workflow outcomes and other notable events in the
+    // notification center.
+    conductor_web::notification_center::spawn_recorder(state.clone());
+
+    // Forward changes made by the TUI, CLI and daemon to the event bus.
+    conductor_web::change_feed::spawn(state.clone());
+
     // Spawn a task that subscribes to the EventBus and sends push notifications
     // for high-urgency gate-waiting and feedback-requested events in real time.
     let gate_state = state.clone();
@@ -716,13 +868,14 @@ async fn main() -> Result<()> {
         .parse()
         .map_err(|e| anyhow::anyhow!("invalid CONDUCTOR_PORT: {e}"))?;
 
-    if !host.is_loopback() {
+    if !host.is_loopback() && !state.web_config.read().await.auth.required {
         tracing::warn!(
             host = %host,
-            "CONDUCTOR_HOST is bound to a non-loopback address; the conductor-web API has no \
-             authentication and will be reachable by anyone who can reach this host. Use \
-             127.0.0.1 (the default) unless an external auth layer (reverse proxy, VPN, etc.) \
-             is in front of this server."
+            "CONDUCTOR_HOST is bound to a non-loopback address and [web.auth] required is off; \
+             the conductor-web API will be reachable by anyone who can reach this host. Use \
+             127.0.0.1 (the default), enable [web.auth] required and create a token with \
+             `conductor token create`, or put an external auth layer (reverse proxy, VPN, etc.) \
+             in front of this server."
         );
     }
 
@@ -755,6 +908,19 @@ async fn main() -> Result<()> {
     let app = api_router()
         .merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()))
         .fallback(static_handler)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            visibility::hide_private_repos,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            limits::limit_requests,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::authenticate,
+        ))
+        .layer(DefaultBodyLimit::max(limit_cfg.max_upload_bytes))
         .layer(TraceLayer::new_for_http())
         .layer(cors)
         .with_state(state);
@@ -890,6 +1056,8 @@ mod tests {
             conversation_id: None,
             subprocess_pid: None,
             runtime: "claude".to_string(),
+            rotated_from_session_id: None,
+            environment: None,
         }
     }
 
@@ -0,0 +1,274 @@
+//! Feeds the notification center from the event bus.
+//!
+//! Agent and workflow outcomes, feedback requests, gate waits and failed
+//! worktree creations are stored via [`NotificationManager`]; every other
+//! event is ignored. A [`ConductorEvent::NotificationsChanged`] follows each
+//! stored notification so open browsers can refresh their unread count.
+
+use rusqlite::Connection;
+
+use conductor_core::config::Config;
+use conductor_core::notifications::{NewNotification, NotificationManager, NotificationSeverity};
+use conductor_core::repo_visibility::{RepoScoped, RepoVisibility};
+use conductor_core::worktree::WorktreeManager;
+
+use crate::events::ConductorEvent;
+use crate::state::AppState;
+
+/// Subscribe to the event bus and record notable events until the bus closes.
+pub fn spawn_recorder(state: AppState) {
+    let mut rx = state.events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if is_notable(&event) => {
+                    let recorded = {
+                        let db = state.db.lock().await;
+                        let config = state.config.read().await;
+                        notification_for(&db, &config, &event)
+                            .map(|new| NotificationManager::new(&db).record(&new))
+                    };
+                    match recorded {
+                        Some(Ok(_)) => state.events.emit(ConductorEvent::NotificationsChanged),
+                        Some(Err(e)) => tracing::warn!("failed to record notification: {e}"),
+                        None => {}
+                    }
+                }
+                Ok(_) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::warn!("notification recorder lagged by {n} events");
+                }
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+fn is_notable(event: &ConductorEvent) -> bool {
+    matches!(
+        event,
+        ConductorEvent::AgentRunFinished { .. }
+            | ConductorEvent::WorkflowRunFinished { .. }
+            | ConductorEvent::FeedbackRequested { .. }
+            | ConductorEvent::WorkflowGateWaiting { .. }
+            | ConductorEvent::WorktreeCreateFailed { .. }
+    )
+}
+
+/// Build the notification for `event`, looking up worktree and workflow
+/// details for its title and link, and the repo it is about so readers who
+/// cannot see that repo do not get it. `None` for events that are not notable.
+pub fn notification_for(
+    conn: &Connection,
+    config: &Config,
+    event: &ConductorEvent,
+) -> Option<NewNotification> {
+    // (label, web UI path) for a worktree, e.g. ("test-repo/feat-x", "/repos/r1/worktrees/w1").
+    let worktree = |id: Option<&str>| {
+        let wt = WorktreeManager::new(conn, config).get_by_id(id?).ok()?;
+        let repo_slug = conductor_core::repo::RepoManager::new(conn, config)
+            .get_by_id(&wt.repo_id)
+            .map(|r| r.slug)
+            .unwrap_or_default();
+        Some((
+            format!("{repo_slug}/{}", wt.slug),
+            format!("/repos/{}/worktrees/{}", wt.repo_id, wt.id),
+        ))
+    };
+
+    let repo_of = |target: RepoScoped<'_>| {
+        RepoVisibility::new(conn)
+            .repo_of(target)
+            .unwrap_or_else(|e| {
+                tracing::warn!("notification repo lookup failed: {e}");
+                None
+            })
+    };
+
+    let notification = match event {
+        ConductorEvent::AgentRunFinished {
+            run_id,
+            worktree_id,
+            succeeded,
+            error,
+        } => {
+            let (label, url) = worktree(worktree_id.as_deref()).unzip();
+            let label = label.unwrap_or_else(|| "repo agent".to_string());
+            let repo_id = repo_of(
+                worktree_id
+                    .as_deref()
+                    .map_or(RepoScoped::AgentRun(run_id), RepoScoped::Worktree),
+            );
+            if *succeeded {
+                NewNotification {
+                    kind: "agent_run_completed".into(),
+                    severity: NotificationSeverity::Success,
+                    title: format!("Agent finished in {label}"),
+                    body: String::new(),
+                    url,
+                    repo_id,
+                }
+            } else {
+                NewNotification {
+                    kind: "agent_run_failed".into(),
+                    severity: NotificationSeverity::Error,
+                    title: format!("Agent failed in {label}"),
+                    body: error.clone().unwrap_or_default(),
+                    url,
+                    repo_id,
+                }
+            }
+        }
+        ConductorEvent::WorkflowRunFinished {
+            run_id,
+            workflow_name,
+            worktree_id,
+            succeeded,
+            error,
+        } => {
+            let url = worktree(worktree_id.as_deref())
+                .map(|(_, path)| format!("{path}/workflows/runs/{run_id}"))
+                .or_else(|| Some("/workflows".to_string()));
+            let repo_id = repo_of(
+                worktree_id
+                    .as_deref()
+                    .map_or(RepoScoped::WorkflowRun(run_id), RepoScoped::Worktree),
+            );
+            if *succeeded {
+                NewNotification {
+                    kind: "workflow_run_completed".into(),
+                    severity: NotificationSeverity::Success,
+                    title: format!("Workflow '{workflow_name}' completed"),
+                    body: String::new(),
+                    url,
+                    repo_id,
+                }
+            } else {
+                NewNotification {
+                    kind: "workflow_run_failed".into(),
+                    severity: NotificationSeverity::Error,
+                    title: format!("Workflow '{workflow_name}' failed"),
+                    body: error.clone().unwrap_or_default(),
+                    url,
+                    repo_id,
+                }
+            }
+        }
+        ConductorEvent::FeedbackRequested { worktree_id, .. } => {
+            let (label, url) = worktree(Some(worktree_id)).unzip();
+            NewNotification {
+                kind: "feedback_requested".into(),
+                severity: NotificationSeverity::Warning,
+                title: format!(
+                    "Agent needs your input in {}",
+                    label.as_deref().unwrap_or("a worktree")
+                ),
+                body: String::new(),
+                url,
+                repo_id: repo_of(RepoScoped::Worktree(worktree_id)),
+            }
+        }
+        ConductorEvent::WorkflowGateWaiting { run_id, .. } => {
+            let run = conductor_core::workflow::get_workflow_run(conn, run_id)
+                .ok()
+                .flatten();
+            let name = run
+                .as_ref()
+                .map(|r| r.workflow_name.clone())
+                .unwrap_or_else(|| "workflow".to_string());
+            let url = run
+                .and_then(|r| worktree(r.worktree_id.as_deref()))
+                .map(|(_, path)| format!("{path}/workflows/runs/{run_id}"))
+                .or_else(|| Some("/workflows".to_string()));
+            NewNotification {
+                kind: "workflow_gate_waiting".into(),
+                severity: NotificationSeverity::Warning,
+                title: format!("Workflow '{name}' is waiting for approval"),
+                body: String::new(),
+                url,
+                repo_id: repo_of(RepoScoped::WorkflowRun(run_id)),
+            }
+        }
+        ConductorEvent::WorktreeCreateFailed { repo_id, error, .. } => NewNotification {
+            kind: "worktree_create_failed".into(),
+            severity: NotificationSeverity::Error,
+            title: "Worktree creation failed".into(),
+            body: error.clone(),
+            url: Some(format!("/repos/{repo_id}")),
+            repo_id: Some(repo_id.clone()),
+        },
+        _ => return None,
+    };
+    Some(notification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::seeded_state;
+
+    #[tokio::test]
+    async fn agent_failure_links_to_the_worktree() {
+        let (state, _db) = seeded_state();
+        let db = state.db.lock().await;
+        let new = notification_for(
+            &db,
+            &Config::default(),
+            &ConductorEvent::AgentRunFinished {
+                run_id: "run1".into(),
+                worktree_id: Some("w1".into()),
+                succeeded: false,
+                error: Some("exit 1".into()),
+            },
+        )
+        .unwrap();
+        assert_eq!(new.kind, "agent_run_failed");
+        assert_eq!(new.severity, NotificationSeverity::Error);
+        assert_eq!(new.title, "Agent failed in test-repo/feat-test");
+        assert_eq!(new.body, "exit 1");
+        assert_eq!(new.url.as_deref(), Some("/repos/r1/worktrees/w1"));
+        assert_eq!(new.repo_id.as_deref(), Some("r1"));
+    }
+
+    #[tokio::test]
+    async fn routine_events_are_not_recorded() {
+        let (state, _db) = seeded_state();
+        let event = ConductorEvent::AgentStarted {
+            run_id: "run1".into(),
+            worktree_id: "w1".into(),
+        };
+        assert!(!is_notable(&event));
+        let db = state.db.lock().await;
+        assert!(notification_for(&db, &Config::default(), &event).is_none());
+    }
+
+    #[tokio::test]
+    async fn recorder_stores_notable_events() {
+        let (state, _db) = seeded_state();
+        let mut rx = state.events.subscribe();
+        spawn_recorder(state.clone());
+        state.events.emit(ConductorEvent::WorktreeCreateFailed {
+            job_id: "j1".into(),
+            repo_id: "r1".into(),
+            error: "branch exists".into(),
+        });
+        let changed = async {
+            loop {
+                match rx.recv().await {
+                    Ok(ConductorEvent::NotificationsChanged) => break,
+                    Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(e) => panic!("event bus closed: {e}"),
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), changed)
+            .await
+            .expect("notification recorded");
+        let db = state.db.lock().await;
+        let list = NotificationManager::new(&db)
+            .list(conductor_core::notifications::LOCAL_READER, false, 10)
+            .unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].body, "branch exists");
+    }
+}
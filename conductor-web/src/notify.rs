@@ -1,7 +1,8 @@
 pub use conductor_core::notify::{
     detect_agent_terminal_transitions, detect_workflow_terminal_transitions,
-    fire_agent_run_notification, fire_cost_spike_notification, fire_duration_spike_notification,
-    fire_gate_pending_too_long_notification, fire_orphan_resumed_notification,
-    fire_workflow_notification, AgentRunNotificationArgs, CostSpikeArgs, DurationSpikeArgs,
-    GatePendingTooLongArgs, NotificationCtx, WorkflowNotificationArgs,
+    fire_agent_alert_notification, fire_agent_run_notification, fire_cost_spike_notification,
+    fire_duration_spike_notification, fire_gate_pending_too_long_notification,
+    fire_orphan_resumed_notification, fire_watch_notification, fire_workflow_notification,
+    AgentRunNotificationArgs, CostSpikeArgs, DurationSpikeArgs, GatePendingTooLongArgs,
+    NotificationCtx, WorkflowNotificationArgs,
 };
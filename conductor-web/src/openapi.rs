@@ -1,20 +1,44 @@
 use utoipa::OpenApi;
 
+#[allow(unused_imports)]
+use crate::jobs::{JobStatus, WorktreeJob};
+#[allow(unused_imports)]
+use conductor_core::actions::{ActionId, ParamKind};
+#[allow(unused_imports)]
+use conductor_core::activity::{ActivityDay, ActivityGroupBy, ActivityHeatmap, ActivityRow};
 #[allow(unused_imports)]
 use conductor_core::agent::{
-    AgentCreatedIssue, AgentRun, AgentRunEvent, AgentRunStatus, FeedbackOption, FeedbackRequest,
-    FeedbackStatus, FeedbackType, PlanStep, RunTreeTotals, StepStatus, TicketAgentTotals,
+    AgentAlertKind, AgentChain, AgentCreatedIssue, AgentPriority, AgentRun, AgentRunAlert,
+    AgentRunEvent, AgentRunStatus, BudgetExceeded, BudgetScope, ChainLink, ChainOutcome, ChainStep,
+    ChainTrigger, ConflictResolution, ConflictResolutionStatus, CostGroupBy, CostReportRow,
+    DiffReview, FeedbackOption, FeedbackRequest, FeedbackStatus, FeedbackType, FileDiff, InboxItem,
+    PlanStep, QueuedAgentRun, RunShare, RunTreeTotals, SharedRun, SharedRunEvent, StepStatus,
+    StopAllSummary, StoppedRun, TicketAgentTotals,
 };
 #[allow(unused_imports)]
+use conductor_core::api_token::{ApiToken, ApiTokenScope};
+#[allow(unused_imports)]
 use conductor_core::conversation::{Conversation, ConversationScope, ConversationWithRuns};
 #[allow(unused_imports)]
 use conductor_core::github::{DiscoveredRepo, GithubPr};
 #[allow(unused_imports)]
 use conductor_core::issue_source::IssueSource;
 #[allow(unused_imports)]
+use conductor_core::notifications::{Notification, NotificationSeverity};
+#[allow(unused_imports)]
 use conductor_core::repo::Repo;
 #[allow(unused_imports)]
-use conductor_core::tickets::{Ticket, TicketLabel};
+use conductor_core::tickets::{
+    EpicRollup, GroomingPass, GroomingProposal, ProposalStatus, RoadmapGroup, RoadmapItem,
+    RoadmapStage, Ticket, TicketLabel, TicketNode,
+};
+#[allow(unused_imports)]
+use conductor_core::today::{TodayItem, TodayItemKind};
+use conductor_core::toolchain::Toolchain;
+#[allow(unused_imports)]
+use conductor_core::tools::{ToolReport, ToolStatus};
+#[allow(unused_imports)]
+use conductor_core::watch::{Watch, WatchTarget};
 #[allow(unused_imports)]
 use conductor_core::workflow::{
     BlockedOn, GateAnalyticsRow, PendingGateAnalyticsRow, StepFailureHeatmapRow,
@@ -23,8 +47,30 @@ use conductor_core::workflow::{
     WorkflowRunStep, WorkflowStepStatus, WorkflowTokenAggregate, WorkflowTokenTrendRow,
 };
 #[allow(unused_imports)]
-use conductor_core::worktree::{Worktree, WorktreeStatus, WorktreeWithStatus};
+use conductor_core::worktree::{
+    AbandonReason, AbandonReasonCount, AheadBehind, BaseBranchSource, BranchTodo,
+    ConflictOperation, ConflictState, ConflictedFile, LastCommit, ResolvedBase, StackInfo,
+    StackState, Worktree, WorktreeCreateStage, WorktreeDirEntry, WorktreeGitStatus,
+    WorktreeListEntry, WorktreeListing, WorktreeOutcomes, WorktreeSort, WorktreeStatus,
+    WorktreeWithStatus,
+};
 
+#[allow(unused_imports)]
+use crate::limits::RequestLimitMetrics;
+#[allow(unused_imports)]
+use crate::routes::agent_inbox::MarkAllReviewedResponse;
+#[allow(unused_imports)]
+use crate::routes::agent_queue::{EnqueueAgentRequest, SetQueuePriorityRequest};
+#[allow(unused_imports)]
+use crate::routes::agent_stream::{AgentStreamEvent, AgentStreamStatus};
+#[allow(unused_imports)]
+use crate::routes::agents::TicketTotalsPage;
+#[allow(unused_imports)]
+use crate::routes::api_tokens::{CreateApiTokenRequest, CreateApiTokenResponse};
+#[allow(unused_imports)]
+use crate::routes::commands::{CommandInfo, CommandParam};
+#[allow(unused_imports)]
+use crate::routes::conflicts::WorktreeConflicts;
 #[allow(unused_imports)]
 use crate::routes::conversations::{
     CreateConversationRequest, ListConversationsQuery, RespondToFeedbackByIdRequest,
@@ -40,14 +86,23 @@ use crate::routes::model_config::{
     SuggestModelResponse,
 };
 #[allow(unused_imports)]
+use crate::routes::notifications::{
+    ClearNotificationsResponse, MarkAllReadResponse, UnreadCountResponse,
+};
+#[allow(unused_imports)]
 use crate::routes::push::{PushSubscribeRequest, VapidPublicKeyResponse};
 #[allow(unused_imports)]
 use crate::routes::repos::{
-    DiscoverReposQuery, DiscoverableRepo, RegisterRepoRequest,
-    SetModelRequest as RepoSetModelRequest, UpdateRepoSettingsRequest,
+    DiscoverReposQuery, DiscoverableRepo, RegisterRepoRequest, RepoVisibilityState,
+    SetModelRequest as RepoSetModelRequest, SetRepoVisibilityRequest, UpdateRepoSettingsRequest,
 };
 #[allow(unused_imports)]
-use crate::routes::tickets::{SyncResult, TicketDetail, TicketListQuery, TicketListResponse};
+use crate::routes::run_shares::{CreateRunShareRequest, CreateRunShareResponse};
+#[allow(unused_imports)]
+use crate::routes::tickets::{
+    GroomingQuery, IngestQuery, RoadmapResponse, SyncResult, TicketCommentRequest, TicketDetail,
+    TicketListQuery, TicketListResponse, TicketTreeResponse,
+};
 #[allow(unused_imports)]
 use crate::routes::workflows::{
     InputDeclSummary, InstantiateTemplateRequest, PostWorkflowRunRequest, RunWorkflowRequest,
@@ -55,11 +110,14 @@ use crate::routes::workflows::{
 };
 #[allow(unused_imports)]
 use crate::routes::worktrees::{
-    CreateWorktreeRequest, CreateWorktreeResponse, LinkTicketRequest,
-    SetModelRequest as WorktreeSetModelRequest, WorktreeListQuery,
+    CommitWorktreeRequest, CreatePrRequest, CreatePrResponse, CreateWorktreeAccepted,
+    CreateWorktreeFromUrlRequest, CreateWorktreeRequest, LinkTicketRequest, PushWorktreeResponse,
+    SetModelRequest as WorktreeSetModelRequest, WorktreeDiff, WorktreeListQuery,
 };
 #[allow(unused_imports)]
 use conductor_core::stats::ThemeUnlockStats;
+#[allow(unused_imports)]
+use conductor_core::worktree::{CommitSummary, PurgeCandidate, QuotaLevel, WorkspaceUsage};
 
 /// OpenAPI documentation for the Conductor REST API.
 #[derive(OpenApi)]
@@ -78,6 +136,9 @@ use conductor_core::stats::ThemeUnlockStats;
         crate::routes::repos::unregister_repo,
         crate::routes::repos::patch_repo_model,
         crate::routes::repos::update_repo_settings,
+        crate::routes::repos::detect_repo_toolchain,
+        crate::routes::repos::get_repo_visibility,
+        crate::routes::repos::set_repo_visibility,
         crate::routes::repos::list_github_orgs_handler,
         crate::routes::repos::discover_github_repos_handler,
         crate::routes::repos::list_prs,
@@ -85,26 +146,59 @@ use conductor_core::stats::ThemeUnlockStats;
         crate::routes::worktrees::list_all_worktrees,
         crate::routes::worktrees::list_worktrees,
         crate::routes::worktrees::create_worktree,
+        crate::routes::worktrees::create_worktree_from_url,
+        crate::routes::worktrees::get_worktree_job,
         crate::routes::worktrees::get_worktree,
         crate::routes::worktrees::delete_worktree,
         crate::routes::worktrees::get_worktree_for_repo,
         crate::routes::worktrees::delete_worktree_for_repo,
         crate::routes::worktrees::patch_worktree_model,
         crate::routes::worktrees::link_ticket,
+        crate::routes::worktrees::acknowledge_ticket_change,
+        crate::routes::worktrees::list_worktree_stacks,
+        crate::routes::worktrees::resolve_base_branch,
+        crate::routes::worktrees::all_worktree_outcomes,
+        crate::routes::worktrees::repo_worktree_outcomes,
+        crate::routes::worktrees::restack_worktree,
+        crate::routes::worktrees::restore_worktree,
+        crate::routes::conflicts::get_conflicts,
+        crate::routes::conflicts::start_conflict_agent,
+        crate::routes::conflicts::verify_conflicts,
+        crate::routes::conflicts::continue_conflicts,
+        crate::routes::conflicts::abort_conflicts,
+        crate::routes::worktrees::worktree_diff,
+        crate::routes::worktrees::worktree_files,
+        crate::routes::worktrees::worktree_todos,
+        crate::routes::worktrees::scan_worktree_todos,
+        crate::routes::worktrees::commit_worktree,
+        crate::routes::worktrees::push_worktree,
+        crate::routes::worktrees::create_worktree_pr,
         // Tickets
         crate::routes::tickets::list_ticket_labels,
         crate::routes::tickets::list_all_tickets,
         crate::routes::tickets::list_tickets,
+        crate::routes::tickets::ticket_tree,
         crate::routes::tickets::sync_tickets,
+        crate::routes::tickets::ingest_tickets,
         crate::routes::tickets::ticket_detail,
+        crate::routes::tickets::comment_on_ticket,
+        crate::routes::tickets::all_roadmap,
+        crate::routes::tickets::repo_roadmap,
+        crate::routes::tickets::list_grooming_proposals,
+        crate::routes::tickets::run_grooming,
+        crate::routes::tickets::accept_grooming_proposal,
+        crate::routes::tickets::dismiss_grooming_proposal,
         // Agents
         crate::routes::agents::list_agent_runs,
         crate::routes::agents::list_all_agent_runs,
         crate::routes::agents::get_agent_run_by_id,
+        crate::routes::agents::get_agent_run_chain,
+        crate::routes::agents::stop_all_agents,
         crate::routes::agents::get_agent_run_feedback_by_run_id,
         crate::routes::agents::get_agent_run_events_by_id,
         crate::routes::agents::latest_runs_by_worktree,
         crate::routes::agents::ticket_totals,
+        crate::routes::agents::list_over_budget,
         crate::routes::agents::latest_runs_by_worktree_for_repo,
         crate::routes::agents::ticket_totals_for_repo,
         crate::routes::agents::start_repo_agent,
@@ -114,8 +208,37 @@ use conductor_core::stats::ThemeUnlockStats;
         crate::routes::agents::list_runs,
         crate::routes::agents::latest_run,
         crate::routes::agents::start_agent,
+        crate::routes::agents::latest_diff_review,
+        crate::routes::agents::start_diff_review,
         crate::routes::agents::stop_agent,
+        crate::routes::agent_queue::list_queue,
+        crate::routes::agent_queue::enqueue_agent,
+        crate::routes::agent_queue::set_queue_priority,
+        crate::routes::agent_queue::remove_from_queue,
+        crate::routes::agent_alerts::list_alerts,
+        crate::routes::agent_alerts::list_run_alerts,
+        crate::routes::agent_inbox::list_inbox,
+        crate::routes::agent_inbox::mark_reviewed,
+        crate::routes::agent_inbox::mark_all_reviewed,
+        crate::routes::watches::list_watches,
+        crate::routes::watches::watch,
+        crate::routes::watches::unwatch,
+        crate::routes::api_tokens::list_tokens,
+        crate::routes::api_tokens::create_token,
+        crate::routes::api_tokens::revoke_token,
+        crate::routes::run_shares::create_run_share,
+        crate::routes::run_shares::list_run_shares,
+        crate::routes::run_shares::revoke_run_share,
+        crate::routes::run_shares::get_shared_run,
+        crate::routes::notifications::list_notifications,
+        crate::routes::notifications::unread_count,
+        crate::routes::notifications::mark_read,
+        crate::routes::notifications::mark_all_read,
+        crate::routes::notifications::clear_notifications,
+        crate::routes::today::list_today,
+        crate::routes::tools::get_tools,
         crate::routes::agents::get_events,
+        crate::routes::agent_stream::stream_agent_events,
         crate::routes::agents::restart_agent,
         crate::routes::agents::get_run_events,
         crate::routes::agents::list_child_runs,
@@ -169,8 +292,14 @@ use conductor_core::stats::ThemeUnlockStats;
         crate::routes::issue_sources::list_issue_sources,
         crate::routes::issue_sources::create_issue_source,
         crate::routes::issue_sources::delete_issue_source,
+        // Command palette
+        crate::routes::commands::list_commands,
         // Stats
         crate::routes::stats::theme_unlock_stats,
+        crate::routes::stats::workspace_quota,
+        crate::routes::stats::request_limits,
+        crate::routes::reports::cost_report,
+        crate::routes::reports::activity_report,
         // Push Notifications
         crate::routes::push::get_vapid_public_key,
         crate::routes::push::subscribe_push,
@@ -204,7 +333,47 @@ use conductor_core::stats::ThemeUnlockStats;
             StepStatus,
             AgentCreatedIssue,
             TicketAgentTotals,
+            TicketTotalsPage,
             RunTreeTotals,
+            BudgetExceeded,
+            BudgetScope,
+            CostGroupBy,
+            CostReportRow,
+            ActivityGroupBy,
+            ActivityDay,
+            ActivityRow,
+            ActivityHeatmap,
+            AgentPriority,
+            QueuedAgentRun,
+            EnqueueAgentRequest,
+            AgentChain,
+            ChainStep,
+            ChainLink,
+            ChainTrigger,
+            ChainOutcome,
+            StopAllSummary,
+            StoppedRun,
+            AgentStreamEvent,
+            AgentStreamStatus,
+            SetQueuePriorityRequest,
+            InboxItem,
+            DiffReview,
+            AgentRunAlert,
+            AgentAlertKind,
+            MarkAllReviewedResponse,
+            Watch,
+            WatchTarget,
+            // API token types
+            ApiToken,
+            ApiTokenScope,
+            CreateApiTokenRequest,
+            CreateApiTokenResponse,
+            // Agent run share link types
+            RunShare,
+            SharedRun,
+            SharedRunEvent,
+            CreateRunShareRequest,
+            CreateRunShareResponse,
             // Conversation types
             Conversation,
             ConversationScope,
@@ -229,28 +398,76 @@ use conductor_core::stats::ThemeUnlockStats;
             // Ticket types
             Ticket,
             TicketLabel,
+            RoadmapGroup,
+            RoadmapItem,
+            RoadmapStage,
+            TicketNode,
+            EpicRollup,
+            GroomingProposal,
+            GroomingPass,
+            ProposalStatus,
             // Repo types
             Repo,
+            Toolchain,
             GithubPr,
             DiscoveredRepo,
             // Worktree types
             Worktree,
             WorktreeStatus,
+            AbandonReason,
+            AbandonReasonCount,
+            WorktreeOutcomes,
+            BaseBranchSource,
+            ResolvedBase,
             WorktreeWithStatus,
+            WorktreeListEntry,
+            WorktreeSort,
+            AheadBehind,
+            WorktreeGitStatus,
+            LastCommit,
+            StackInfo,
+            StackState,
+            ConflictOperation,
+            ConflictState,
+            ConflictedFile,
+            BranchTodo,
+            ConflictResolution,
+            ConflictResolutionStatus,
+            WorktreeConflicts,
             // Issue source types
             IssueSource,
             // Web layer request/response types
             RegisterRepoRequest,
+            RepoVisibilityState,
+            SetRepoVisibilityRequest,
             DiscoverableRepo,
             DiscoverReposQuery,
             CreateWorktreeRequest,
-            CreateWorktreeResponse,
+            CreateWorktreeFromUrlRequest,
+            CreateWorktreeAccepted,
+            WorktreeDiff,
+            FileDiff,
+            WorktreeListing,
+            WorktreeDirEntry,
+            CommitWorktreeRequest,
+            CommitSummary,
+            PushWorktreeResponse,
+            CreatePrRequest,
+            CreatePrResponse,
+            WorktreeJob,
+            JobStatus,
+            WorktreeCreateStage,
             WorktreeListQuery,
             LinkTicketRequest,
             TicketListQuery,
             TicketListResponse,
             SyncResult,
+            IngestQuery,
+            TicketCommentRequest,
             TicketDetail,
+            RoadmapResponse,
+            TicketTreeResponse,
+            GroomingQuery,
             CreateConversationRequest,
             ListConversationsQuery,
             SendMessageRequest,
@@ -264,6 +481,10 @@ use conductor_core::stats::ThemeUnlockStats;
             InstantiateTemplateRequest,
             CreateIssueSourceRequest,
             ThemeUnlockStats,
+            WorkspaceUsage,
+            PurgeCandidate,
+            QuotaLevel,
+            RequestLimitMetrics,
             VapidPublicKeyResponse,
             PushSubscribeRequest,
             GlobalModelResponse,
@@ -275,6 +496,19 @@ use conductor_core::stats::ThemeUnlockStats;
             TestHookRequest,
             PatchHookOnRequest,
             HookEventEntry,
+            Notification,
+            NotificationSeverity,
+            TodayItem,
+            TodayItemKind,
+            ToolReport,
+            ToolStatus,
+            UnreadCountResponse,
+            MarkAllReadResponse,
+            ClearNotificationsResponse,
+            CommandInfo,
+            CommandParam,
+            ActionId,
+            ParamKind,
         )
     )
 )]
@@ -0,0 +1,128 @@
+use axum::extract::{Path, Query, State};
+use axum::{Extension, Json};
+
+use conductor_core::agent::{AgentAlerts, AgentRunAlert};
+use conductor_core::api_token::ApiToken;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// Most alerts returned by `GET /api/agent/alerts` when no limit is given.
+const DEFAULT_ALERT_LIMIT: usize = 50;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct ListAlertsQuery {
+    /// Maximum number of alerts to return (default 50).
+    pub limit: Option<usize>,
+}
+
+/// Recent cost and progress alerts across all agent runs, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/agent/alerts",
+    params(ListAlertsQuery),
+    responses(
+        (status = 200, description = "Recent agent alerts", body = Vec<AgentRunAlert>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_alerts(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Query(params): Query<ListAlertsQuery>,
+) -> Result<Json<Vec<AgentRunAlert>>, ApiError> {
+    let db = state.db.lock().await;
+    let limit = params.limit.unwrap_or(DEFAULT_ALERT_LIMIT);
+    Ok(Json(
+        AgentAlerts::new(&db).list_recent_visible(&viewer(&token), limit)?,
+    ))
+}
+
+/// Alerts raised on one agent run, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/agent/runs/{id}/alerts",
+    params(
+        ("id" = String, Path, description = "Agent run ID"),
+    ),
+    responses(
+        (status = 200, description = "Alerts on the run", body = Vec<AgentRunAlert>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_run_alerts(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+) -> Result<Json<Vec<AgentRunAlert>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(AgentAlerts::new(&db).list_for_run(&run_id)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::seeded_state;
+
+    async fn get_json(state: &crate::state::AppState, uri: &str) -> Vec<serde_json::Value> {
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn alerts_are_listed_globally_and_per_run() {
+        let (state, _db) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at) \
+                 VALUES ('run1', 'w1', 'fix it', 'running', '2024-01-01T00:00:00Z'); \
+                 INSERT INTO agent_run_alerts (run_id, kind, detail, created_at) \
+                 VALUES ('run1', 'run_cost', 'run cost $7.20 is over $5.00', \
+                         '2024-01-01T01:00:00Z');",
+            )
+            .unwrap();
+        }
+
+        let alerts = get_json(&state, "/api/agent/alerts").await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0]["kind"], "run_cost");
+        assert_eq!(alerts[0]["worktree_id"], "w1");
+
+        let alerts = get_json(&state, "/api/agent/runs/run1/alerts").await;
+        assert_eq!(alerts[0]["detail"], "run cost $7.20 is over $5.00");
+        assert!(get_json(&state, "/api/agent/runs/other/alerts")
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn alerts_hide_runs_of_repos_private_to_others() {
+        let (state, _db) = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at) \
+                 VALUES ('run1', 'w1', 'fix it', 'running', '2024-01-01T00:00:00Z'); \
+                 INSERT INTO agent_run_alerts (run_id, kind, detail, created_at) \
+                 VALUES ('run1', 'run_cost', 'expensive', '2024-01-01T01:00:00Z');",
+            )
+            .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+        let alerts = crate::test_helpers::get_json_as(&state, "/api/agent/alerts", outsider).await;
+        assert_eq!(alerts, serde_json::json!([]));
+    }
+}
@@ -0,0 +1,180 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::Serialize;
+
+use conductor_core::agent::{AgentInbox, InboxItem};
+use conductor_core::api_token::ApiToken;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct MarkAllReviewedResponse {
+    /// Number of runs cleared from the inbox.
+    pub cleared: usize,
+}
+
+/// Finished agent runs that have not been reviewed yet, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/agent/inbox",
+    responses(
+        (status = 200, description = "Unreviewed finished agent runs", body = Vec<InboxItem>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_inbox(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<InboxItem>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(AgentInbox::new(&db).list_visible(&viewer(&token))?))
+}
+
+/// Mark a finished run as reviewed, removing it from the inbox.
+#[utoipa::path(
+    post,
+    path = "/api/agent/inbox/{run_id}/reviewed",
+    params(
+        ("run_id" = String, Path, description = "Agent run ID"),
+    ),
+    responses(
+        (status = 204, description = "Marked reviewed"),
+        (status = 404, description = "Agent run not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn mark_reviewed(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let db = state.db.lock().await;
+    AgentInbox::new(&db).mark_reviewed(&run_id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mark every run currently in the inbox as reviewed.
+#[utoipa::path(
+    post,
+    path = "/api/agent/inbox/reviewed",
+    responses(
+        (status = 200, description = "Inbox cleared", body = MarkAllReviewedResponse),
+    ),
+    tag = "agents",
+)]
+pub async fn mark_all_reviewed(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<MarkAllReviewedResponse>, ApiError> {
+    let db = state.db.lock().await;
+    let cleared = AgentInbox::new(&db).mark_all_visible_reviewed(&viewer(&token))?;
+    Ok(Json(MarkAllReviewedResponse { cleared }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::seeded_state;
+
+    #[tokio::test]
+    async fn inbox_lists_and_clears_finished_runs() {
+        let (state, _db) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute(
+                "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, ended_at) \
+                 VALUES ('run1', 'w1', 'fix it', 'completed', '2024-01-01T00:00:00Z', \
+                         '2024-01-01T01:00:00Z')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let app = api_router().with_state(state.clone());
+        let resp = app
+            .oneshot(
+                Request::get("/api/agent/inbox")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["run_id"], "run1");
+
+        let app = api_router().with_state(state.clone());
+        let resp = app
+            .oneshot(
+                Request::post("/api/agent/inbox/run1/reviewed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let app = api_router().with_state(state);
+        let resp = app
+            .oneshot(
+                Request::post("/api/agent/inbox/missing/reviewed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn inbox_hides_runs_of_repos_private_to_others() {
+        let (state, _db) = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            db.execute(
+                "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, ended_at) \
+                 VALUES ('run1', 'w1', 'fix it', 'completed', '2024-01-01T00:00:00Z', \
+                         '2024-01-01T01:00:00Z')",
+                [],
+            )
+            .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        let mut req = Request::get("/api/agent/inbox")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(outsider.clone());
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert!(items.is_empty(), "private run leaked: {items:?}");
+
+        let mut req = Request::post("/api/agent/inbox/reviewed")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(outsider);
+        let resp = api_router().with_state(state).oneshot(req).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let cleared: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(cleared["cleared"], 0);
+    }
+}
@@ -0,0 +1,281 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::Deserialize;
+use tracing::warn;
+
+use conductor_core::agent::{
+    AgentChain, AgentChains, AgentPriority, AgentQueue, DirtyWorktreeChoice, QueuedAgentRun,
+};
+use conductor_core::api_token::ApiToken;
+use conductor_core::worktree::WorktreeManager;
+
+use crate::error::ApiError;
+use crate::events::ConductorEvent;
+use crate::routes::agents::launch_worktree_agent;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// Serializes dispatch passes so two passes cannot both see the same free slot.
+static DISPATCH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct EnqueueAgentRequest {
+    pub prompt: String,
+    #[serde(default)]
+    pub priority: AgentPriority,
+    /// Follow-up runs to queue when this run succeeds or fails.
+    pub chain: Option<AgentChain>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct SetQueuePriorityRequest {
+    pub priority: AgentPriority,
+}
+
+/// List queued agent runs in dispatch order (highest priority first).
+#[utoipa::path(
+    get,
+    path = "/api/agent-queue",
+    responses(
+        (status = 200, description = "Queued agent runs", body = Vec<QueuedAgentRun>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_queue(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<QueuedAgentRun>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(AgentQueue::new(&db).list_visible(&viewer(&token))?))
+}
+
+/// Queue an agent run for a worktree. It starts once a slot is free
+/// (`[agents] max_concurrent_runs`) and the worktree has no active agent.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/agent/queue",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    request_body(content = EnqueueAgentRequest, description = "Prompt and priority"),
+    responses(
+        (status = 201, description = "Agent run queued", body = QueuedAgentRun),
+        (status = 400, description = "Invalid chain"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn enqueue_agent(
+    State(state): State<AppState>,
+    Path(worktree_id): Path<String>,
+    Json(body): Json<EnqueueAgentRequest>,
+) -> Result<(StatusCode, Json<QueuedAgentRun>), ApiError> {
+    let queued = {
+        let db = state.db.lock().await;
+        let config = state.config.read().await;
+        WorktreeManager::new(&db, &config).get_by_id(&worktree_id)?;
+        if let Some(ref chain) = body.chain {
+            chain.validate()?;
+        }
+        let queued = AgentQueue::new(&db).enqueue(&worktree_id, &body.prompt, body.priority)?;
+        if let Some(ref chain) = body.chain {
+            AgentChains::new(&db).attach_queued(&queued.id, chain)?;
+        }
+        queued
+    };
+    state.events.emit(ConductorEvent::AgentQueueChanged {
+        worktree_id: worktree_id.clone(),
+    });
+    let dispatch_state = state.clone();
+    tokio::spawn(async move { dispatch_queued_agents(&dispatch_state).await });
+    Ok((StatusCode::CREATED, Json(queued)))
+}
+
+/// Change the priority of a queued run. Runs that have already started
+/// are not in the queue and return 404.
+#[utoipa::path(
+    patch,
+    path = "/api/agent-queue/{id}",
+    params(
+        ("id" = String, Path, description = "Queued run ID"),
+    ),
+    request_body(content = SetQueuePriorityRequest, description = "New priority"),
+    responses(
+        (status = 200, description = "Updated queued run", body = QueuedAgentRun),
+        (status = 404, description = "Not in the queue"),
+    ),
+    tag = "agents",
+)]
+pub async fn set_queue_priority(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<SetQueuePriorityRequest>,
+) -> Result<Json<QueuedAgentRun>, ApiError> {
+    let queued = {
+        let db = state.db.lock().await;
+        AgentQueue::new(&db).set_priority(&id, body.priority)?
+    };
+    state.events.emit(ConductorEvent::AgentQueueChanged {
+        worktree_id: queued.worktree_id.clone(),
+    });
+    Ok(Json(queued))
+}
+
+/// Remove a run from the queue without starting it.
+#[utoipa::path(
+    delete,
+    path = "/api/agent-queue/{id}",
+    params(
+        ("id" = String, Path, description = "Queued run ID"),
+    ),
+    responses(
+        (status = 204, description = "Removed from the queue"),
+        (status = 404, description = "Not in the queue"),
+    ),
+    tag = "agents",
+)]
+pub async fn remove_from_queue(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let worktree_id = {
+        let db = state.db.lock().await;
+        let queue = AgentQueue::new(&db);
+        let queued = queue.get(&id)?;
+        queue.remove(&id)?;
+        queued.worktree_id
+    };
+    state
+        .events
+        .emit(ConductorEvent::AgentQueueChanged { worktree_id });
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Start queued runs until the queue is empty or no slot is free.
+///
+/// Called after each enqueue and periodically from the server's background
+/// loop, which picks up slots freed by runs finishing. Chained runs that
+/// ended are evaluated first so their follow-ups join the queue.
+pub async fn dispatch_queued_agents(state: &AppState) {
+    let _guard = DISPATCH_LOCK.lock().await;
+    {
+        let db = state.db.lock().await;
+        if let Err(e) = AgentChains::new(&db).evaluate_due() {
+            warn!("agent chains: evaluation failed: {e}");
+        }
+    }
+    loop {
+        let next = {
+            let db = state.db.lock().await;
+            let limit = state.config.read().await.agents.max_concurrent_runs;
+            AgentQueue::new(&db).take_next(limit)
+        };
+        let queued = match next {
+            Ok(Some(queued)) => queued,
+            Ok(None) => break,
+            Err(e) => {
+                warn!("agent queue: failed to take next run: {e}");
+                break;
+            }
+        };
+        state.events.emit(ConductorEvent::AgentQueueChanged {
+            worktree_id: queued.worktree_id.clone(),
+        });
+        // Queued runs start unattended, so uncommitted changes are left as they are.
+        match launch_worktree_agent(
+            state,
+            &queued.worktree_id,
+            &queued.prompt,
+            None,
+            None,
+            None,
+            Some(DirtyWorktreeChoice::Proceed),
+            false,
+        )
+        .await
+        {
+            Ok(run) => {
+                let db = state.db.lock().await;
+                if let Err(e) = AgentChains::new(&db).bind_queued(&queued.id, &run.id) {
+                    warn!(queued_id = %queued.id, "agent chains: failed to link queued run: {e}");
+                }
+            }
+            Err(e) => warn!(
+                queued_id = %queued.id,
+                worktree = %queued.worktree_slug,
+                "agent queue: failed to start queued run: {e:?}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn priority_can_be_changed_while_queued() {
+        let (state, _tmp) = crate::test_helpers::seeded_state();
+        let queued = {
+            let db = state.db.lock().await;
+            // An active run keeps the queued item from being dispatched.
+            conductor_core::agent::AgentManager::new(&db)
+                .create_run(Some("w1"), "busy", None)
+                .unwrap();
+            AgentQueue::new(&db)
+                .enqueue("w1", "later", AgentPriority::Background)
+                .unwrap()
+        };
+        let app = crate::routes::api_router().with_state(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::PATCH)
+                    .uri(format!("/api/agent-queue/{}", queued.id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"priority":"urgent"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/agent-queue")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["priority"], "urgent");
+    }
+
+    #[tokio::test]
+    async fn queue_hides_worktrees_of_repos_private_to_others() {
+        let (state, _tmp) = crate::test_helpers::seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            AgentQueue::new(&db)
+                .enqueue("w1", "later", AgentPriority::Normal)
+                .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+        let items = crate::test_helpers::get_json_as(&state, "/api/agent-queue", outsider).await;
+        assert_eq!(items, serde_json::json!([]));
+    }
+}
@@ -0,0 +1,171 @@
+//! `GET /api/worktrees/{id}/agent/stream` tails the stream-json log of a
+//! worktree's latest agent run and pushes each parsed event over SSE as it is
+//! written, instead of the browser re-fetching `/agent/events`.
+//!
+//! Every `agent_event` carries the log byte offset just past its line, also
+//! sent as the SSE `id`, so a reconnect resumes with `?from_offset=` or the
+//! browser's automatic `Last-Event-ID` header. A `heartbeat` with the run's
+//! status goes out when the log is quiet, and `done` closes the stream once
+//! the run has finished and its log is fully sent.
+
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::ReceiverStream;
+
+use conductor_core::agent::{parse_events_since, AgentManager, AgentRunStatus};
+use conductor_core::error::ConductorError;
+use conductor_core::worktree::WorktreeManager;
+
+use super::agents::strip_worktree_prefix;
+use crate::error::ApiError;
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct AgentStreamQuery {
+    /// Stream a specific run instead of the worktree's latest one.
+    pub run_id: Option<String>,
+    /// Log byte offset to resume from: the `offset` of the last event
+    /// received. Takes precedence over `Last-Event-ID`.
+    pub from_offset: Option<u64>,
+}
+
+/// Data of an `agent_event` SSE message.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AgentStreamEvent {
+    pub run_id: String,
+    /// Log byte offset just past this event's line.
+    pub offset: u64,
+    pub kind: String,
+    pub summary: String,
+    pub metadata: Option<String>,
+}
+
+/// Data of `heartbeat` and `done` SSE messages.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AgentStreamStatus {
+    pub run_id: String,
+    pub offset: u64,
+    /// `None` when the run was deleted while streaming.
+    pub status: Option<AgentRunStatus>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/agent/stream",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+        AgentStreamQuery,
+    ),
+    responses(
+        (status = 200, description = "SSE stream of `agent_event`, `heartbeat` and `done` messages", body = String, content_type = "text/event-stream"),
+        (status = 404, description = "Worktree or agent run not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn stream_agent_events(
+    State(state): State<AppState>,
+    Path(worktree_id): Path<String>,
+    Query(query): Query<AgentStreamQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let (run, wt_path) = {
+        let db = state.db.lock().await;
+        let config = state.config.read().await;
+        let wt_path = WorktreeManager::new(&db, &config)
+            .get_by_id(&worktree_id)?
+            .path;
+        let mgr = AgentManager::new(&db);
+        let run = match &query.run_id {
+            Some(run_id) => mgr
+                .get_run(run_id)?
+                .filter(|r| r.worktree_id.as_deref() == Some(worktree_id.as_str())),
+            None => mgr.latest_for_worktree(&worktree_id)?,
+        };
+        let run = run.ok_or_else(|| ConductorError::AgentRunNotFound {
+            id: query.run_id.clone().unwrap_or_else(|| worktree_id.clone()),
+        })?;
+        (run, wt_path)
+    };
+    let log_path = run.log_path()?.to_string_lossy().into_owned();
+    let offset = query.from_offset.unwrap_or_else(|| {
+        headers
+            .get("last-event-id")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    });
+
+    let (tx, rx) = tokio::sync::mpsc::channel(64);
+    tokio::spawn(async move {
+        let run_id = run.id;
+        let mut offset = offset;
+        let mut last_sent = Instant::now();
+        loop {
+            // Check the status before reading so the final lines written
+            // before the run ended are always sent before `done`.
+            let status = {
+                let db = state.db.lock().await;
+                AgentManager::new(&db)
+                    .get_run(&run_id)
+                    .ok()
+                    .flatten()
+                    .map(|r| r.status)
+            };
+            let path = log_path.clone();
+            let Ok((next, events)) =
+                tokio::task::spawn_blocking(move || parse_events_since(&path, offset)).await
+            else {
+                return;
+            };
+            offset = next;
+            for (event_offset, e) in events {
+                let data = AgentStreamEvent {
+                    run_id: run_id.clone(),
+                    offset: event_offset,
+                    kind: e.kind,
+                    summary: strip_worktree_prefix(&e.summary, &wt_path),
+                    metadata: e.metadata,
+                };
+                let event = Event::default()
+                    .event("agent_event")
+                    .id(event_offset.to_string())
+                    .data(serde_json::to_string(&data).unwrap_or_default());
+                if tx.send(Ok(event)).await.is_err() {
+                    return;
+                }
+                last_sent = Instant::now();
+            }
+
+            let finished = !matches!(
+                status,
+                Some(AgentRunStatus::Running | AgentRunStatus::WaitingForFeedback)
+            );
+            if finished || last_sent.elapsed() >= HEARTBEAT_INTERVAL {
+                let data = AgentStreamStatus {
+                    run_id: run_id.clone(),
+                    offset,
+                    status,
+                };
+                let event = Event::default()
+                    .event(if finished { "done" } else { "heartbeat" })
+                    .data(serde_json::to_string(&data).unwrap_or_default());
+                if tx.send(Ok(event)).await.is_err() || finished {
+                    return;
+                }
+                last_sent = Instant::now();
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+
+    Ok(Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default()))
+}
@@ -2,16 +2,21 @@ use std::collections::HashMap;
 
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 
 use conductor_core::agent::{
-    parse_agent_log, AgentCreatedIssue, AgentEvent, AgentManager, AgentRun, AgentRunEvent,
-    AgentRunStatus, FeedbackRequest, RunTreeTotals, TicketAgentTotals,
+    launch_guard, parse_agent_log, AgentChain, AgentChains, AgentCreatedIssue, AgentEvent,
+    AgentManager, AgentRun, AgentRunEvent, AgentRunStatus, BudgetExceeded, ChainLink, DiffReview,
+    DiffReviews, DirtyWorktreeChoice, FeedbackRequest, LaunchGuards, RunTreeTotals, StopAllSummary,
+    TicketAgentTotals,
 };
+use conductor_core::api_token::ApiToken;
+use conductor_core::config::AgentPermissionMode;
 use conductor_core::error::ConductorError;
 use conductor_core::repo::RepoManager;
-use conductor_core::tickets::{build_agent_prompt, TicketSyncer};
+use conductor_core::repo_visibility::Viewer;
+use conductor_core::tickets::{build_agent_prompt, TicketAttachments, TicketSyncer};
 use conductor_core::worktree::WorktreeManager;
 
 use tracing::warn;
@@ -19,6 +24,7 @@ use tracing::warn;
 use crate::error::ApiError;
 use crate::events::ConductorEvent;
 use crate::state::AppState;
+use crate::visibility::viewer;
 
 /// Spawn a blocking task that calls `AgentManager::cancel_run()`.
 ///
@@ -270,6 +276,7 @@ pub struct ListAllAgentRunsQuery {
 )]
 pub async fn list_all_agent_runs(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
     Query(params): Query<ListAllAgentRunsQuery>,
 ) -> Result<Json<Vec<AgentRun>>, ApiError> {
     use std::str::FromStr;
@@ -284,7 +291,7 @@ pub async fn list_all_agent_runs(
 
     let db = state.db.lock().await;
     let mgr = AgentManager::new(&db);
-    let runs = mgr.list_agent_runs(None, None, status.as_ref(), 500, 0)?;
+    let runs = mgr.list_visible_agent_runs(&viewer(&token), None, None, status.as_ref(), 500, 0)?;
     Ok(Json(runs))
 }
 
@@ -298,28 +305,129 @@ pub async fn list_all_agent_runs(
 )]
 pub async fn latest_runs_by_worktree(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
 ) -> Result<Json<HashMap<String, AgentRun>>, ApiError> {
     let db = state.db.lock().await;
     let mgr = AgentManager::new(&db);
-    let map = mgr.latest_runs_by_worktree()?;
+    let map = mgr.latest_visible_runs_by_worktree(&viewer(&token))?;
     Ok(Json(map))
 }
 
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct TicketTotalsQuery {
+    /// Comma-separated ticket IDs. When set, only these tickets are
+    /// aggregated and the cache is bypassed.
+    pub ticket_ids: Option<String>,
+    /// Return at most this many tickets, ordered by ticket ID. Setting
+    /// `limit` or `offset` returns a [`TicketTotalsPage`] instead of a map.
+    pub limit: Option<usize>,
+    /// Skip this many tickets, ordered by ticket ID.
+    pub offset: Option<usize>,
+}
+
+/// One page of ticket totals, ordered by ticket ID.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TicketTotalsPage {
+    pub totals: Vec<TicketAgentTotals>,
+    /// Tickets with totals across all pages.
+    pub total: usize,
+}
+
+/// The ticket totals endpoints' body: a map of ticket ID to totals, or a
+/// page of them when `limit` or `offset` is given.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum TicketTotalsResponse {
+    Map(HashMap<String, TicketAgentTotals>),
+    Page(TicketTotalsPage),
+}
+
+/// Totals for `scope` (`None` for all tickets, or a repo id), narrowed and
+/// paginated per `query`. Whole-scope results are cached until the next run
+/// completes or a ticket link or repo's visibility changes.
+fn scoped_ticket_totals(
+    state: &AppState,
+    conn: &rusqlite::Connection,
+    viewer: &Viewer,
+    scope: Option<&str>,
+    query: &TicketTotalsQuery,
+) -> Result<TicketTotalsResponse, ApiError> {
+    let mgr = AgentManager::new(conn);
+    let ticket_ids: Option<Vec<String>> = query.ticket_ids.as_deref().map(|ids| {
+        ids.split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+    let totals = match &ticket_ids {
+        Some(ids) => std::sync::Arc::new(mgr.visible_totals_by_ticket_ids(viewer, scope, ids)?),
+        None => {
+            let version = mgr.ticket_totals_version()?;
+            match state.ticket_totals.get(viewer, scope, &version) {
+                Some(totals) => totals,
+                None => {
+                    let totals = mgr.visible_totals_by_ticket(viewer, scope)?;
+                    state.ticket_totals.put(viewer, scope, version, totals)
+                }
+            }
+        }
+    };
+
+    if query.offset.is_none() && query.limit.is_none() {
+        return Ok(TicketTotalsResponse::Map((*totals).clone()));
+    }
+    let mut ids: Vec<&String> = totals.keys().collect();
+    ids.sort();
+    Ok(TicketTotalsResponse::Page(TicketTotalsPage {
+        total: ids.len(),
+        totals: ids
+            .into_iter()
+            .skip(query.offset.unwrap_or(0))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(|id| totals[id].clone())
+            .collect(),
+    }))
+}
+
 #[utoipa::path(
     get,
     path = "/api/agent/ticket-totals",
+    params(TicketTotalsQuery),
     responses(
-        (status = 200, description = "Map of ticket ID to agent totals"),
+        (status = 200, description = "Map of ticket ID to agent totals, or a TicketTotalsPage when paginated"),
     ),
     tag = "agents",
 )]
 pub async fn ticket_totals(
     State(state): State<AppState>,
-) -> Result<Json<HashMap<String, TicketAgentTotals>>, ApiError> {
+    token: Option<Extension<ApiToken>>,
+    Query(query): Query<TicketTotalsQuery>,
+) -> Result<Json<TicketTotalsResponse>, ApiError> {
     let db = state.db.lock().await;
-    let mgr = AgentManager::new(&db);
-    let map = mgr.totals_by_ticket_all()?;
-    Ok(Json(map))
+    let totals = scoped_ticket_totals(&state, &db, &viewer(&token), None, &query)?;
+    Ok(Json(totals))
+}
+
+/// Live worktrees that have spent their `[budget] worktree_usd`, biggest
+/// spenders first. Empty when no worktree budget is configured.
+#[utoipa::path(
+    get,
+    path = "/api/agent/budgets",
+    responses(
+        (status = 200, description = "Worktrees over budget", body = Vec<BudgetExceeded>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_over_budget(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<BudgetExceeded>>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let over =
+        AgentManager::new(&db).visible_worktrees_over_budget(&viewer(&token), &config.budget)?;
+    Ok(Json(over))
 }
 
 #[utoipa::path(
@@ -348,20 +456,22 @@ pub async fn latest_runs_by_worktree_for_repo(
     path = "/api/repos/{id}/agent/ticket-totals",
     params(
         ("id" = String, Path, description = "Repo ID"),
+        TicketTotalsQuery,
     ),
     responses(
-        (status = 200, description = "Map of ticket ID to agent totals for repo"),
+        (status = 200, description = "Map of ticket ID to agent totals for repo, or a TicketTotalsPage when paginated"),
     ),
     tag = "agents",
 )]
 pub async fn ticket_totals_for_repo(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
     Path(repo_id): Path<String>,
-) -> Result<Json<HashMap<String, TicketAgentTotals>>, ApiError> {
+    Query(query): Query<TicketTotalsQuery>,
+) -> Result<Json<TicketTotalsResponse>, ApiError> {
     let db = state.db.lock().await;
-    let mgr = AgentManager::new(&db);
-    let map = mgr.totals_by_ticket_for_repo(&repo_id)?;
-    Ok(Json(map))
+    let totals = scoped_ticket_totals(&state, &db, &viewer(&token), Some(&repo_id), &query)?;
+    Ok(Json(totals))
 }
 
 // ── Agent orchestration ───────────────────────────────────────────────
@@ -415,6 +525,24 @@ pub struct StartAgentRequest {
     pub prompt: String,
     pub resume_session_id: Option<String>,
     pub parent_run_id: Option<String>,
+    /// What to do with uncommitted changes in the worktree. Required when
+    /// the worktree is dirty; otherwise the start is refused with a 409.
+    pub dirty_choice: Option<DirtyWorktreeChoice>,
+    /// Follow-up runs to queue when this run succeeds or fails.
+    pub chain: Option<AgentChain>,
+    /// Start even if the worktree or resumed session has spent its
+    /// `[budget]`; otherwise the start is refused with a 402.
+    #[serde(default)]
+    pub override_budget: bool,
+}
+
+/// Body of the 409 returned by `start_agent` when the worktree has
+/// uncommitted changes and no `dirty_choice` was given.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct WorktreeDirtyConflict {
+    pub code: &'static str,
+    pub message: &'static str,
+    pub dirty_files: Vec<String>,
 }
 
 /// Start an agent for a worktree. Creates a DB record and spawns a headless subprocess.
@@ -427,7 +555,10 @@ pub struct StartAgentRequest {
     request_body(content = StartAgentRequest, description = "Agent start parameters"),
     responses(
         (status = 201, description = "Agent run created", body = AgentRun),
+        (status = 400, description = "Invalid chain"),
+        (status = 402, description = "Budget spent and override_budget was not set"),
         (status = 404, description = "Worktree not found"),
+        (status = 409, description = "Worktree has uncommitted changes and no dirty_choice was given", body = WorktreeDirtyConflict),
     ),
     tag = "agents",
 )]
@@ -436,18 +567,55 @@ pub async fn start_agent(
     Path(worktree_id): Path<String>,
     Json(body): Json<StartAgentRequest>,
 ) -> Result<(StatusCode, Json<AgentRun>), ApiError> {
+    if let Some(ref chain) = body.chain {
+        chain.validate()?;
+    }
+    let run = launch_worktree_agent(
+        &state,
+        &worktree_id,
+        &body.prompt,
+        body.resume_session_id.as_deref(),
+        body.parent_run_id.as_deref(),
+        None,
+        body.dirty_choice,
+        body.override_budget,
+    )
+    .await?;
+    if let Some(chain) = body.chain {
+        let db = state.db.lock().await;
+        AgentChains::new(&db).attach(&run.id, &chain)?;
+    }
+    Ok((StatusCode::CREATED, Json(run)))
+}
+
+/// Create an agent run for a worktree and spawn its headless subprocess.
+///
+/// Shared by `start_agent`, `start_diff_review` and the agent queue dispatcher.
+/// When the worktree has uncommitted changes, `dirty_choice` decides what
+/// happens to them; `None` refuses the launch with a [`WorktreeDirtyConflict`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn launch_worktree_agent(
+    state: &AppState,
+    worktree_id: &str,
+    prompt: &str,
+    resume_session_id: Option<&str>,
+    parent_run_id: Option<&str>,
+    permission_mode: Option<AgentPermissionMode>,
+    dirty_choice: Option<DirtyWorktreeChoice>,
+    override_budget: bool,
+) -> Result<AgentRun, ApiError> {
     // Scope DB + config access so locks are dropped before the blocking spawn.
-    let (run, wt_path, wt_id, prompt, resume_session_id, model) = {
+    let (run, wt_path, wt_id, model, dirty) = {
         let db = state.db.lock().await;
         let config = state.config.read().await;
 
         // Look up the worktree to get slug and path
         let wt_mgr = WorktreeManager::new(&db, &config);
-        let wt = wt_mgr.get_by_id(&worktree_id)?;
+        let wt = wt_mgr.get_by_id(worktree_id)?;
 
         // Check if there's already a running agent
         let agent_mgr = AgentManager::new(&db);
-        if let Some(existing) = agent_mgr.latest_for_worktree(&worktree_id)? {
+        if let Some(existing) = agent_mgr.latest_for_worktree(worktree_id)? {
             if existing.is_active() {
                 return Err(conductor_core::error::ConductorError::Agent(
                     "Agent already running for this worktree".to_string(),
@@ -455,6 +623,30 @@ pub async fn start_agent(
                 .into());
             }
         }
+        if !override_budget {
+            agent_mgr.check_budget(&config.budget, Some(worktree_id), resume_session_id)?;
+        }
+
+        // `git status` in the worktree is quick, so it runs under the lock.
+        // If it fails the launch goes ahead; spawning reports a broken worktree.
+        let dirty_files = launch_guard::dirty_files(&wt.path).unwrap_or_else(|e| {
+            warn!(worktree = %wt.slug, "could not check for uncommitted changes: {e}");
+            Vec::new()
+        });
+        let dirty = match dirty_choice {
+            _ if dirty_files.is_empty() => None,
+            Some(choice) => Some((choice, dirty_files)),
+            None => {
+                let conflict = serde_json::to_value(WorktreeDirtyConflict {
+                    code: "worktree_dirty",
+                    message: "worktree has uncommitted changes; pass dirty_choice to stash, \
+                              commit them as WIP, or proceed anyway",
+                    dirty_files,
+                })
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+                return Err(ApiError::Conflict(conflict));
+            }
+        };
 
         // Resolve model: per-worktree → per-repo config → global config
         let repo = RepoManager::new(&db, &config).get_by_id(&wt.repo_id)?;
@@ -466,47 +658,127 @@ pub async fn start_agent(
             .map(str::to_string);
 
         // Create DB record (child or top-level)
-        let run = if let Some(ref parent_id) = body.parent_run_id {
+        let run = if let Some(parent_id) = parent_run_id {
             agent_mgr.create_child_run(
-                Some(&worktree_id),
-                &body.prompt,
+                Some(worktree_id),
+                prompt,
                 model.as_deref(),
                 parent_id,
                 None,
             )?
         } else {
-            agent_mgr.create_run(Some(&worktree_id), &body.prompt, model.as_deref())?
+            agent_mgr.create_run(Some(worktree_id), prompt, model.as_deref())?
         };
 
-        (
-            run,
-            wt.path.clone(),
-            wt.id.clone(),
-            body.prompt.clone(),
-            body.resume_session_id.clone(),
-            model,
-        )
+        (run, wt.path.clone(), wt.id.clone(), model, dirty)
     };
     // DB and config locks are now dropped.
 
+    if let Some((choice, files)) = dirty {
+        let path = wt_path.clone();
+        let prepared =
+            tokio::task::spawn_blocking(move || launch_guard::prepare(&path, choice, files))
+                .await?;
+        let db = state.db.lock().await;
+        match prepared {
+            Ok(prepared) => {
+                LaunchGuards::new(&db).record(&run.id, &wt_id, &prepared)?;
+            }
+            Err(e) => {
+                AgentManager::new(&db)
+                    .update_run_failed(&run.id, &format!("could not {choice} changes: {e}"))?;
+                return Err(e.into());
+            }
+        }
+    }
+
     // Spawn headless subprocess and wire stdout to the SSE event bus.
+    let permission_mode = permission_mode.map(|m| m.to_runtime_permission_mode());
     let spawn_params = conductor_core::agent_runtime::SpawnHeadlessParams {
         run_id: &run.id,
         working_dir: &wt_path,
-        prompt: &prompt,
-        resume_session_id: resume_session_id.as_deref(),
+        prompt,
+        resume_session_id,
         model: model.as_deref(),
         extra_cli_args: &[],
-        permission_mode: None,
+        permission_mode: permission_mode.as_ref(),
         plugin_dirs: &[],
     };
-    spawn_headless_agent(&state, &spawn_params, Some(wt_id.as_str())).await?;
+    spawn_headless_agent(state, &spawn_params, Some(wt_id.as_str())).await?;
 
     state.events.emit(ConductorEvent::AgentStarted {
         run_id: run.id.clone(),
         worktree_id: wt_id,
     });
-    Ok((StatusCode::CREATED, Json(run)))
+    Ok(run)
+}
+
+/// Latest agent review of a worktree's diff, if one was ever requested.
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/agent/review",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Latest diff review, or null", body = Option<DiffReview>),
+    ),
+    tag = "agents",
+)]
+pub async fn latest_diff_review(
+    State(state): State<AppState>,
+    Path(worktree_id): Path<String>,
+) -> Result<Json<Option<DiffReview>>, ApiError> {
+    let db = state.db.lock().await;
+    let review = DiffReviews::new(&db).latest_for_worktree(&worktree_id)?;
+    Ok(Json(review))
+}
+
+/// Ask a read-only agent to review the worktree's diff against its base.
+/// The review appears on `GET .../agent/review` once the run finishes.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/agent/review",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 201, description = "Review started", body = DiffReview),
+        (status = 400, description = "Worktree has no changes to review"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn start_diff_review(
+    State(state): State<AppState>,
+    Path(worktree_id): Path<String>,
+) -> Result<(StatusCode, Json<DiffReview>), ApiError> {
+    // Reading the diff shells out to git, so it runs off the async runtime.
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let id = worktree_id.clone();
+    let request = tokio::task::spawn_blocking(move || {
+        let conn = conductor_core::db::open_database(&db_path)?;
+        DiffReviews::new(&conn).prepare(&config, &id)
+    })
+    .await??;
+
+    // The review reads the uncommitted changes, so they stay in place.
+    let run = launch_worktree_agent(
+        &state,
+        &worktree_id,
+        &request.prompt,
+        None,
+        None,
+        Some(AgentPermissionMode::RepoSafe),
+        Some(DirtyWorktreeChoice::Proceed),
+        false,
+    )
+    .await?;
+
+    let db = state.db.lock().await;
+    let review = DiffReviews::new(&db).record(&request, &run.id)?;
+    Ok((StatusCode::CREATED, Json(review)))
 }
 
 /// Stop a running agent: mark cancelled under lock, then signal the subprocess
@@ -798,7 +1070,12 @@ pub async fn get_prompt(
     } else if let Some(ref ticket_id) = wt.ticket_id {
         let syncer = TicketSyncer::new(&db);
         match syncer.get_by_id(ticket_id) {
-            Ok(ticket) => build_agent_prompt(&ticket),
+            Ok(ticket) => {
+                let attachments = TicketAttachments::new(&db)
+                    .list(&ticket.id)
+                    .unwrap_or_default();
+                build_agent_prompt(&ticket, &attachments)
+            }
             Err(_) => String::new(),
         }
     } else {
@@ -1110,7 +1387,7 @@ fn verify_feedback_ownership(
     Ok(())
 }
 
-fn strip_worktree_prefix(summary: &str, worktree_path: &str) -> String {
+pub(crate) fn strip_worktree_prefix(summary: &str, worktree_path: &str) -> String {
     if worktree_path.is_empty() {
         return summary.to_string();
     }
@@ -1442,6 +1719,64 @@ pub async fn get_agent_run_by_id(
     Ok(Json(run))
 }
 
+/// Stop every running or feedback-waiting agent run, across all repos.
+/// Subprocesses are signalled off the async runtime; the summary lists each
+/// stopped run with its log file.
+#[utoipa::path(
+    post,
+    path = "/api/agent/stop-all",
+    responses(
+        (status = 200, description = "Runs that were stopped", body = StopAllSummary),
+    ),
+    tag = "agents",
+)]
+pub async fn stop_all_agents(
+    State(state): State<AppState>,
+) -> Result<Json<StopAllSummary>, ApiError> {
+    let db_path = state.db_path.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        let conn = conductor_core::db::open_database(&db_path)?;
+        AgentManager::new(&conn).stop_all()
+    })
+    .await??;
+
+    for run in &summary.stopped {
+        match (&run.worktree_id, &run.repo_id) {
+            (Some(worktree_id), _) => state.events.emit(ConductorEvent::AgentStopped {
+                run_id: run.run_id.clone(),
+                worktree_id: worktree_id.clone(),
+            }),
+            (None, Some(repo_id)) => state.events.emit(ConductorEvent::RepoAgentStopped {
+                run_id: run.run_id.clone(),
+                repo_id: repo_id.clone(),
+            }),
+            (None, None) => {}
+        }
+    }
+    Ok(Json(summary))
+}
+
+/// Get the chain of follow-up runs an agent run belongs to, first run first.
+/// Empty when the run is not chained.
+#[utoipa::path(
+    get,
+    path = "/api/agent/runs/{id}/chain",
+    params(
+        ("id" = String, Path, description = "Agent run ID"),
+    ),
+    responses(
+        (status = 200, description = "Links of the run's chain", body = Vec<ChainLink>),
+    ),
+    tag = "agents",
+)]
+pub async fn get_agent_run_chain(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+) -> Result<Json<Vec<ChainLink>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(AgentChains::new(&db).chain_for_run(&run_id)?))
+}
+
 /// List all feedback requests for a given agent run ID (globally scoped).
 #[utoipa::path(
     get,
@@ -1587,6 +1922,143 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn start_agent_on_dirty_worktree_is_conflict() {
+        let (state, _tmp) = seeded_state();
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.path().join("notes.txt"), "manual edit").unwrap();
+        {
+            let db = state.db.lock().await;
+            conductor_core::test_helpers::insert_test_worktree(
+                &db,
+                "w-dirty",
+                "r1",
+                "dirty-wt",
+                repo.path().to_str().unwrap(),
+            );
+        }
+
+        let response = api_router()
+            .with_state(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/worktrees/w-dirty/agent/start")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"prompt":"do something"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "worktree_dirty");
+        assert_eq!(json["dirty_files"], serde_json::json!(["notes.txt"]));
+
+        let db = state.db.lock().await;
+        assert!(AgentManager::new(&db)
+            .list_for_worktree("w-dirty")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn start_agent_over_budget_is_payment_required() {
+        let (state, _tmp) = seeded_state();
+        state.config.write().await.budget.worktree_usd = Some(5.0);
+        {
+            let db = state.db.lock().await;
+            let run = AgentManager::new(&db)
+                .create_run(Some("w1"), "earlier", None)
+                .unwrap();
+            db.execute(
+                "UPDATE agent_runs SET status = 'completed', cost_usd = 6.0 WHERE id = ?1",
+                [&run.id],
+            )
+            .unwrap();
+        }
+
+        let response = api_router()
+            .with_state(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/worktrees/w1/agent/start")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"prompt":"do something"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let db = state.db.lock().await;
+        assert_eq!(
+            AgentManager::new(&db)
+                .list_for_worktree("w1")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn latest_diff_review_is_null_until_a_review_is_recorded() {
+        let (state, _tmp) = seeded_state();
+        let get_review = || async {
+            let response = api_router()
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .uri("/api/worktrees/w1/agent/review")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        assert!(get_review().await.is_null());
+
+        {
+            let db = state.db.lock().await;
+            conductor_core::test_helpers::insert_test_agent_run(&db, "review-run", "w1");
+            let request = conductor_core::agent::DiffReviewRequest {
+                worktree_id: "w1".into(),
+                base_ref: "main".into(),
+                prompt: "review".into(),
+                files_changed: 2,
+                truncated: false,
+            };
+            conductor_core::agent::DiffReviews::new(&db)
+                .record(&request, "review-run")
+                .unwrap();
+        }
+
+        let review = get_review().await;
+        assert_eq!(review["run_id"], "review-run");
+        assert_eq!(review["status"], "running");
+        assert!(review["review"].is_null());
+    }
+
     /// Regression test: drain-thread DB-open failure must not deadlock.
     ///
     /// **Bug (pre-fix):** `handle.child.wait()` was called while `handle.stdout`
@@ -1643,6 +2115,9 @@ mod tests {
             // Deliberately bad path so the drain thread's DB open fails.
             db_path: std::path::PathBuf::from("/nonexistent/__conductor_drain_test.db"),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
 
         // wire_headless_drain returns Ok quickly (persists PID, spawns tasks).
@@ -1755,6 +2230,9 @@ mod tests {
             // reached) would also fail — but it won't be reached here.
             db_path: std::path::PathBuf::from("/nonexistent/__conductor_pid_test.db"),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
 
         // wire_headless_drain should return Err quickly (PID-persist fails).
@@ -1860,6 +2338,9 @@ mod tests {
             events: EventBus::new(8),
             db_path: tmp.path().to_path_buf(),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
 
         // wire_headless_drain returns Ok quickly (persists PID, spawns tasks).
@@ -1956,6 +2437,9 @@ mod tests {
             events: EventBus::new(8),
             db_path: tmp.path().to_path_buf(),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
 
         // 100ms stall threshold so the stall fires almost immediately.
@@ -2018,4 +2502,95 @@ mod tests {
 
         let _ = tmp;
     }
+
+    #[tokio::test]
+    async fn ticket_totals_and_budgets_hide_repos_private_to_others() {
+        let (state, _tmp) = seeded_state();
+        state.config.write().await.budget.worktree_usd = Some(1.0);
+        let outsider = {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, state, \
+                     labels, url, synced_at, raw_json) \
+                 VALUES ('t1', 'r1', 'github', '1', 'T', '', 'open', '[]', '', \
+                         '2024-01-01T00:00:00Z', '{}'); \
+                 UPDATE worktrees SET ticket_id = 't1' WHERE id = 'w1'; \
+                 INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, ended_at, \
+                     cost_usd) \
+                 VALUES ('run1', 'w1', 'fix it', 'completed', '2024-01-01T00:00:00Z', \
+                         '2024-01-01T01:00:00Z', 5.0);",
+            )
+            .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        let totals =
+            crate::test_helpers::get_json_as(&state, "/api/agent/ticket-totals", outsider.clone())
+                .await;
+        assert_eq!(totals, serde_json::json!({}));
+        let over = crate::test_helpers::get_json_as(&state, "/api/agent/budgets", outsider).await;
+        assert_eq!(over, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn ticket_totals_pages_are_ordered_with_a_total() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            for (ticket, wt) in [("t3", "w3"), ("t1", "w1"), ("t2", "w2")] {
+                if wt != "w1" {
+                    conductor_core::test_helpers::insert_test_worktree(
+                        &db,
+                        wt,
+                        "r1",
+                        wt,
+                        &format!("/tmp/ws/{wt}"),
+                    );
+                }
+                db.execute(
+                    "INSERT INTO tickets (id, repo_id, source_type, source_id, title, body, \
+                         state, labels, url, synced_at, raw_json) \
+                     VALUES (?1, 'r1', 'github', ?1, 'T', '', 'open', '[]', '', \
+                             '2024-01-01T00:00:00Z', '{}')",
+                    [ticket],
+                )
+                .unwrap();
+                db.execute(
+                    "UPDATE worktrees SET ticket_id = ?1 WHERE id = ?2",
+                    [ticket, wt],
+                )
+                .unwrap();
+                db.execute(
+                    "INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, \
+                         ended_at) \
+                     VALUES ('run-' || ?1, ?1, 'fix it', 'completed', \
+                             '2024-01-01T00:00:00Z', '2024-01-01T01:00:00Z')",
+                    [wt],
+                )
+                .unwrap();
+            }
+        }
+
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(
+                Request::get("/api/agent/ticket-totals?offset=1&limit=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["total"], 3);
+        let ids: Vec<&str> = page["totals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["ticket_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["t2"]);
+    }
 }
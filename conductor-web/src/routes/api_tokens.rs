@@ -0,0 +1,150 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use conductor_core::api_token::{ApiToken, ApiTokenManager, ApiTokenScope};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scope: ApiTokenScope,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CreateApiTokenResponse {
+    pub token: ApiToken,
+    /// The secret to send as `Authorization: Bearer <secret>`. It is not
+    /// stored and cannot be shown again.
+    pub secret: String,
+}
+
+/// List API tokens, revoked ones included. Secrets are never returned.
+#[utoipa::path(
+    get,
+    path = "/api/tokens",
+    responses(
+        (status = 200, description = "API tokens", body = Vec<ApiToken>),
+    ),
+    tag = "tokens",
+)]
+pub async fn list_tokens(State(state): State<AppState>) -> Result<Json<Vec<ApiToken>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(ApiTokenManager::new(&db).list()?))
+}
+
+/// Create an API token. The response holds the only copy of its secret.
+#[utoipa::path(
+    post,
+    path = "/api/tokens",
+    request_body(content = CreateApiTokenRequest, description = "Token name and scope"),
+    responses(
+        (status = 201, description = "Token created", body = CreateApiTokenResponse),
+        (status = 400, description = "Empty name"),
+    ),
+    tag = "tokens",
+)]
+pub async fn create_token(
+    State(state): State<AppState>,
+    Json(body): Json<CreateApiTokenRequest>,
+) -> Result<(StatusCode, Json<CreateApiTokenResponse>), ApiError> {
+    let db = state.db.lock().await;
+    let (token, secret) = ApiTokenManager::new(&db).create(&body.name, body.scope)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateApiTokenResponse { token, secret }),
+    ))
+}
+
+/// Revoke an API token. It stays listed but no longer authenticates.
+#[utoipa::path(
+    delete,
+    path = "/api/tokens/{id}",
+    params(
+        ("id" = String, Path, description = "Token ID"),
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 404, description = "Token not found"),
+    ),
+    tag = "tokens",
+)]
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let db = state.db.lock().await;
+    ApiTokenManager::new(&db).revoke(&id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::empty_state;
+
+    #[tokio::test]
+    async fn create_list_and_revoke() {
+        let (state, _db) = empty_state();
+
+        let app = api_router().with_state(state.clone());
+        let resp = app
+            .oneshot(
+                Request::post("/api/tokens")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"name":"ci","scope":"read-only"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(created["secret"].as_str().unwrap().starts_with("cdt_"));
+        let id = created["token"]["id"].as_str().unwrap().to_string();
+
+        let app = api_router().with_state(state.clone());
+        let resp = app
+            .oneshot(Request::get("/api/tokens").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let tokens: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0]["scope"], "read-only");
+        assert!(tokens[0].get("secret").is_none());
+
+        let app = api_router().with_state(state.clone());
+        let resp = app
+            .oneshot(
+                Request::delete(format!("/api/tokens/{id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let app = api_router().with_state(state);
+        let resp = app
+            .oneshot(
+                Request::delete("/api/tokens/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,117 @@
+//! `GET /api/commands` lists the actions in `conductor_core::actions::ACTIONS`
+//! together with the endpoint that performs each one, so the web command
+//! palette stays in sync with what the server can do.
+
+use axum::Json;
+use serde::Serialize;
+
+use conductor_core::actions::{ActionId, ParamKind, ACTIONS};
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CommandParam {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: ParamKind,
+    pub required: bool,
+    /// `"path"` when the value fills a `{name}` placeholder in the command's
+    /// path, `"body"` when it goes in the JSON request body.
+    pub location: &'static str,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CommandInfo {
+    pub id: ActionId,
+    pub label: &'static str,
+    pub description: &'static str,
+    pub method: &'static str,
+    /// Endpoint path with `{param}` placeholders for the path parameters.
+    pub path: &'static str,
+    pub params: Vec<CommandParam>,
+}
+
+/// The HTTP method and path that perform `id`.
+fn endpoint(id: ActionId) -> (&'static str, &'static str) {
+    match id {
+        ActionId::SyncTickets => ("POST", "/api/repos/{repo_id}/tickets/sync"),
+        ActionId::GroomTickets => ("POST", "/api/repos/{repo_id}/grooming"),
+        ActionId::CreateWorktree => ("POST", "/api/repos/{repo_id}/worktrees"),
+        ActionId::DeleteWorktree => ("DELETE", "/api/worktrees/{worktree_id}"),
+        ActionId::CommitWorktree => ("POST", "/api/worktrees/{worktree_id}/commit"),
+        ActionId::PushWorktree => ("POST", "/api/worktrees/{worktree_id}/push"),
+        ActionId::CreatePr => ("POST", "/api/worktrees/{worktree_id}/pr"),
+        ActionId::StartAgent => ("POST", "/api/worktrees/{worktree_id}/agent/start"),
+        ActionId::StopAgent => ("POST", "/api/worktrees/{worktree_id}/agent/stop"),
+        ActionId::RunWorkflow => ("POST", "/api/worktrees/{worktree_id}/workflows/run"),
+        ActionId::CancelWorkflowRun => ("POST", "/api/workflows/runs/{run_id}/cancel"),
+        ActionId::ResumeWorkflowRun => ("POST", "/api/workflows/runs/{run_id}/resume"),
+        ActionId::ApproveGate => ("POST", "/api/workflows/runs/{run_id}/gate/approve"),
+        ActionId::RejectGate => ("POST", "/api/workflows/runs/{run_id}/gate/reject"),
+    }
+}
+
+fn commands() -> Vec<CommandInfo> {
+    ACTIONS
+        .iter()
+        .map(|action| {
+            let (method, path) = endpoint(action.id);
+            let params = action
+                .params
+                .iter()
+                .map(|p| CommandParam {
+                    name: p.name,
+                    label: p.label,
+                    kind: p.kind,
+                    required: p.required,
+                    location: if path.contains(&format!("{{{}}}", p.name)) {
+                        "path"
+                    } else {
+                        "body"
+                    },
+                })
+                .collect();
+            CommandInfo {
+                id: action.id,
+                label: action.label,
+                description: action.description,
+                method,
+                path,
+                params,
+            }
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/commands",
+    responses(
+        (status = 200, description = "Actions available to the command palette", body = Vec<CommandInfo>),
+    ),
+    tag = "commands",
+)]
+pub async fn list_commands() -> Json<Vec<CommandInfo>> {
+    Json(commands())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_placeholders_are_required_params() {
+        for command in commands() {
+            let mut rest = command.path;
+            while let Some(start) = rest.find('{') {
+                let end = rest[start..].find('}').unwrap() + start;
+                let name = &rest[start + 1..end];
+                let param = command.params.iter().find(|p| p.name == name);
+                assert!(
+                    param.is_some_and(|p| p.required && p.location == "path"),
+                    "{:?}: {{{name}}} is not a required param",
+                    command.id
+                );
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Resolving a rebase or merge that stopped on conflicts in a worktree.
+//!
+//! A restack with `keep_conflicts` leaves a conflicting rebase in progress.
+//! From there the conflicts are resolved by hand or by a conflict resolution
+//! agent (`POST .../conflicts/agent`), the staged result is checked with the
+//! project's test command (`.../verify`), and only a resolution that passed
+//! its check on exactly what is staged may be continued (`.../continue`).
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+
+use conductor_core::agent::{ConflictResolution, ConflictResolutions, DirtyWorktreeChoice};
+use conductor_core::db::open_database;
+use conductor_core::worktree::{conflict_state, ConflictState, WorktreeManager};
+
+use super::agents::launch_worktree_agent;
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WorktreeConflicts {
+    /// The rebase or merge in progress, if any.
+    pub state: Option<ConflictState>,
+    /// The worktree's most recent conflict resolution.
+    pub resolution: Option<ConflictResolution>,
+}
+
+/// The rebase or merge in progress in a worktree and its latest resolution.
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/conflicts",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Conflict state", body = WorktreeConflicts),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn get_conflicts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WorktreeConflicts>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let wt = WorktreeManager::new(&db, &config).get_by_id(&id)?;
+    let conflicts = WorktreeConflicts {
+        state: conflict_state(std::path::Path::new(&wt.path))?,
+        resolution: ConflictResolutions::new(&db, &config).latest_for_worktree(&id)?,
+    };
+    Ok(Json(conflicts))
+}
+
+/// Start a conflict resolution agent on the conflicting hunks. The agent
+/// stages its resolution but never continues the rebase itself.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/conflicts/agent",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 201, description = "Resolution agent started", body = ConflictResolution),
+        (status = 400, description = "No rebase or merge stopped on conflicts, or an agent is already running"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn start_conflict_agent(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<ConflictResolution>), ApiError> {
+    let request = {
+        let db = state.db.lock().await;
+        let config = state.config.read().await;
+        ConflictResolutions::new(&db, &config).prepare(&id)?
+    };
+
+    // The conflicted files are uncommitted by nature; they are what the
+    // agent works on.
+    let run = launch_worktree_agent(
+        &state,
+        &id,
+        &request.prompt,
+        None,
+        None,
+        None,
+        Some(DirtyWorktreeChoice::Proceed),
+        false,
+    )
+    .await?;
+
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let resolution = ConflictResolutions::new(&db, &config).record(&request, Some(&run.id))?;
+    Ok((StatusCode::CREATED, Json(resolution)))
+}
+
+/// Run the project's test command on the staged resolution. Continuing is
+/// only allowed once this passed.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/conflicts/verify",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Check ran; see `status` and `check_output`", body = ConflictResolution),
+        (status = 400, description = "Conflicts unresolved or unstaged, agent still running, or no check command"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn verify_conflicts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ConflictResolution>, ApiError> {
+    with_resolution(&state, id, |resolutions, resolution| {
+        resolutions.verify(&resolution.id)
+    })
+    .await
+}
+
+/// Continue the rebase or merge with the verified resolution. When a later
+/// commit conflicts too, the new resolution is returned.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/conflicts/continue",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Continued, or the next resolution when more conflicts came up", body = ConflictResolution),
+        (status = 400, description = "The resolution has not passed its check on what is staged"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn continue_conflicts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ConflictResolution>, ApiError> {
+    with_resolution(&state, id, |resolutions, resolution| {
+        resolutions.continue_operation(&resolution.id)
+    })
+    .await
+}
+
+/// Abort the rebase or merge, restoring the branch to where it was.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/conflicts/abort",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Aborted", body = ConflictResolution),
+        (status = 400, description = "No rebase or merge in progress"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn abort_conflicts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ConflictResolution>, ApiError> {
+    with_resolution(&state, id, |resolutions, resolution| {
+        resolutions.abort(&resolution.id)
+    })
+    .await
+}
+
+/// Run `f` on the worktree's open resolution on a blocking thread with its
+/// own connection: checks and rebases can take a while.
+async fn with_resolution<F>(
+    state: &AppState,
+    worktree_id: String,
+    f: F,
+) -> Result<Json<ConflictResolution>, ApiError>
+where
+    F: FnOnce(
+            &ConflictResolutions<'_>,
+            ConflictResolution,
+        ) -> conductor_core::error::Result<ConflictResolution>
+        + Send
+        + 'static,
+{
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let resolution = tokio::task::spawn_blocking(move || {
+        let conn = open_database(&db_path)?;
+        let resolutions = ConflictResolutions::new(&conn, &config);
+        let current = resolutions.current(&worktree_id)?;
+        f(&resolutions, current)
+    })
+    .await??;
+    Ok(Json(resolution))
+}
@@ -2,11 +2,32 @@ use std::convert::Infallible;
 
 use axum::extract::State;
 use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::Extension;
 use futures_util::Stream;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
 
+use conductor_core::api_token::ApiToken;
+use conductor_core::repo_visibility::{RepoVisibility, Viewer};
+
+use crate::events::ConductorEvent;
 use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// Whether `viewer` may receive `event`: events about a repo private to
+/// someone else are dropped, as is any event whose repo cannot be looked up.
+async fn may_receive(state: &AppState, viewer: &Viewer, event: &ConductorEvent) -> bool {
+    let Some(target) = event.scoped_target() else {
+        return true;
+    };
+    let db = state.db.lock().await;
+    RepoVisibility::new(&db)
+        .can_see(viewer, target)
+        .unwrap_or_else(|e| {
+            tracing::warn!("event visibility lookup failed: {e}");
+            false
+        })
+}
 
 #[utoipa::path(
     get,
@@ -18,22 +39,64 @@ use crate::state::AppState;
 )]
 pub async fn event_stream(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
     let rx = state.events.subscribe();
+    let viewer = viewer(&token);
 
-    let stream = BroadcastStream::new(rx).filter_map(|result| match result {
-        Ok(event) => {
-            let data = serde_json::to_string(&event).unwrap_or_default();
-            Some(Ok(Event::default().event(event.event_name()).data(data)))
-        }
-        Err(_) => {
-            // Client fell behind the broadcast buffer. Send a hint so the
-            // frontend knows to re-fetch all data, then continue the stream.
-            Some(Ok(Event::default()
-                .event("lagged")
-                .data("{\"missed\":true}")))
-        }
-    });
+    let stream = BroadcastStream::new(rx)
+        .then(move |result| {
+            let state = state.clone();
+            let viewer = viewer.clone();
+            async move {
+                match result {
+                    Ok(event) => {
+                        if !may_receive(&state, &viewer, &event).await {
+                            return None;
+                        }
+                        let data = serde_json::to_string(&event).unwrap_or_default();
+                        Some(Ok(Event::default().event(event.event_name()).data(data)))
+                    }
+                    Err(_) => {
+                        // Client fell behind the broadcast buffer. Send a hint so the
+                        // frontend knows to re-fetch all data, then continue the stream.
+                        Some(Ok(Event::default()
+                            .event("lagged")
+                            .data("{\"missed\":true}")))
+                    }
+                }
+            }
+        })
+        .filter_map(|event| event);
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{outsider_token, seeded_state};
+
+    #[tokio::test]
+    async fn events_about_repos_private_to_others_are_dropped() {
+        let (state, _db) = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            conductor_core::test_helpers::insert_test_repo(&db, "r2", "other-repo", "/tmp/other");
+            outsider_token(&db)
+        };
+        let outsider = viewer(&Some(Extension(outsider)));
+
+        let private = ConductorEvent::AgentStarted {
+            run_id: "run1".into(),
+            worktree_id: "w1".into(),
+        };
+        let shared = ConductorEvent::TicketsSynced {
+            repo_id: "r2".into(),
+        };
+        assert!(!may_receive(&state, &outsider, &private).await);
+        assert!(may_receive(&state, &outsider, &shared).await);
+        assert!(may_receive(&state, &outsider, &ConductorEvent::NotificationsChanged).await);
+        assert!(may_receive(&state, &Viewer::Local, &private).await);
+    }
+}
@@ -339,6 +339,9 @@ mod tests {
             events: EventBus::new(1),
             db_path: tmp.path().to_path_buf(),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
         (state, tmp)
     }
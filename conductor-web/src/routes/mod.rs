@@ -1,15 +1,28 @@
+pub mod agent_alerts;
+pub mod agent_inbox;
+pub mod agent_queue;
+pub mod agent_stream;
 pub mod agents;
+pub mod api_tokens;
+pub mod commands;
+pub mod conflicts;
 pub mod conversations;
 pub mod events;
 pub mod health;
 pub mod hooks;
 pub mod issue_sources;
 pub mod model_config;
+pub mod notifications;
 pub mod push;
+pub mod reports;
 pub mod repos;
+pub mod run_shares;
 pub mod slack;
 pub mod stats;
 pub mod tickets;
+pub mod today;
+pub mod tools;
+pub mod watches;
 pub mod workflows;
 pub mod worktrees;
 
@@ -52,6 +65,14 @@ pub fn api_router() -> Router<AppState> {
         )
         .route("/api/repos/{id}", delete(repos::unregister_repo))
         .route("/api/repos/{id}/model", patch(repos::patch_repo_model))
+        .route(
+            "/api/repos/{id}/visibility",
+            get(repos::get_repo_visibility).put(repos::set_repo_visibility),
+        )
+        .route(
+            "/api/repos/{id}/toolchain",
+            post(repos::detect_repo_toolchain),
+        )
         .route(
             "/api/repos/{id}/settings",
             patch(repos::update_repo_settings),
@@ -70,6 +91,27 @@ pub fn api_router() -> Router<AppState> {
             "/api/repos/{id}/worktrees",
             get(worktrees::list_worktrees).post(worktrees::create_worktree),
         )
+        .route(
+            "/api/repos/{id}/worktrees/stacks",
+            get(worktrees::list_worktree_stacks),
+        )
+        .route(
+            "/api/repos/{id}/worktrees/base-branch",
+            get(worktrees::resolve_base_branch),
+        )
+        .route(
+            "/api/repos/{id}/worktrees/outcomes",
+            get(worktrees::repo_worktree_outcomes),
+        )
+        .route(
+            "/api/worktrees/outcomes",
+            get(worktrees::all_worktree_outcomes),
+        )
+        .route(
+            "/api/worktrees/from-url",
+            post(worktrees::create_worktree_from_url),
+        )
+        .route("/api/worktree-jobs/{id}", get(worktrees::get_worktree_job))
         .route(
             "/api/worktrees/{id}",
             get(worktrees::get_worktree).delete(worktrees::delete_worktree),
@@ -83,23 +125,110 @@ pub fn api_router() -> Router<AppState> {
             patch(worktrees::patch_worktree_model),
         )
         .route("/api/worktrees/{id}/ticket", put(worktrees::link_ticket))
+        .route(
+            "/api/worktrees/{id}/ticket/ack",
+            post(worktrees::acknowledge_ticket_change),
+        )
+        .route(
+            "/api/worktrees/{id}/restack",
+            post(worktrees::restack_worktree),
+        )
+        .route(
+            "/api/worktrees/{id}/conflicts",
+            get(conflicts::get_conflicts),
+        )
+        .route(
+            "/api/worktrees/{id}/conflicts/agent",
+            post(conflicts::start_conflict_agent),
+        )
+        .route(
+            "/api/worktrees/{id}/conflicts/verify",
+            post(conflicts::verify_conflicts),
+        )
+        .route(
+            "/api/worktrees/{id}/conflicts/continue",
+            post(conflicts::continue_conflicts),
+        )
+        .route(
+            "/api/worktrees/{id}/conflicts/abort",
+            post(conflicts::abort_conflicts),
+        )
+        .route("/api/worktrees/{id}/diff", get(worktrees::worktree_diff))
+        .route("/api/worktrees/{id}/files", get(worktrees::worktree_files))
+        .route("/api/worktrees/{id}/todos", get(worktrees::worktree_todos))
+        .route(
+            "/api/worktrees/{id}/todos/scan",
+            post(worktrees::scan_worktree_todos),
+        )
+        .route(
+            "/api/worktrees/{id}/restore",
+            post(worktrees::restore_worktree),
+        )
+        .route(
+            "/api/worktrees/{id}/commit",
+            post(worktrees::commit_worktree),
+        )
+        .route("/api/worktrees/{id}/push", post(worktrees::push_worktree))
+        .route(
+            "/api/worktrees/{id}/pr",
+            post(worktrees::create_worktree_pr),
+        )
         // Tickets
         .route("/api/ticket-labels", get(tickets::list_ticket_labels))
         .route("/api/tickets", get(tickets::list_all_tickets))
         .route("/api/repos/{id}/tickets", get(tickets::list_tickets))
+        .route("/api/repos/{id}/tickets/tree", get(tickets::ticket_tree))
         .route("/api/repos/{id}/tickets/sync", post(tickets::sync_tickets))
+        .route(
+            "/api/repos/{id}/tickets/ingest",
+            post(tickets::ingest_tickets),
+        )
+        .route("/api/roadmap", get(tickets::all_roadmap))
+        .route("/api/repos/{id}/roadmap", get(tickets::repo_roadmap))
+        .route("/api/grooming", get(tickets::list_grooming_proposals))
+        .route("/api/repos/{id}/grooming", post(tickets::run_grooming))
+        .route(
+            "/api/grooming/{id}/accept",
+            post(tickets::accept_grooming_proposal),
+        )
+        .route(
+            "/api/grooming/{id}/dismiss",
+            post(tickets::dismiss_grooming_proposal),
+        )
         .route(
             "/api/repos/{id}/workflows",
             get(workflows::list_repo_workflow_defs),
         )
         .route("/api/tickets/{id}", get(tickets::ticket_detail))
+        .route(
+            "/api/tickets/{id}/comment",
+            post(tickets::comment_on_ticket),
+        )
         // Agent stats (aggregates)
         .route(
             "/api/worktrees/{id}/agent-runs",
             get(agents::list_agent_runs),
         )
         .route("/api/agent/runs", get(agents::list_all_agent_runs))
+        .route("/api/agent/stop-all", post(agents::stop_all_agents))
         .route("/api/agent/runs/{id}", get(agents::get_agent_run_by_id))
+        .route(
+            "/api/agent/runs/{id}/chain",
+            get(agents::get_agent_run_chain),
+        )
+        .route(
+            "/api/agent/runs/{id}/share",
+            post(run_shares::create_run_share),
+        )
+        .route(
+            "/api/agent/runs/{id}/shares",
+            get(run_shares::list_run_shares),
+        )
+        .route(
+            "/api/agent/shares/{id}",
+            delete(run_shares::revoke_run_share),
+        )
+        .route("/api/shared/runs/{token}", get(run_shares::get_shared_run))
         .route(
             "/api/agent/runs/{id}/feedback",
             get(agents::get_agent_run_feedback_by_run_id),
@@ -134,6 +263,7 @@ pub fn api_router() -> Router<AppState> {
             get(agents::latest_runs_by_worktree),
         )
         .route("/api/agent/ticket-totals", get(agents::ticket_totals))
+        .route("/api/agent/budgets", get(agents::list_over_budget))
         .route(
             "/api/repos/{id}/agent/latest-runs",
             get(agents::latest_runs_by_worktree_for_repo),
@@ -163,8 +293,45 @@ pub fn api_router() -> Router<AppState> {
         .route("/api/worktrees/{id}/agent/runs", get(agents::list_runs))
         .route("/api/worktrees/{id}/agent/latest", get(agents::latest_run))
         .route("/api/worktrees/{id}/agent/start", post(agents::start_agent))
+        .route(
+            "/api/worktrees/{id}/agent/review",
+            get(agents::latest_diff_review).post(agents::start_diff_review),
+        )
+        .route(
+            "/api/worktrees/{id}/agent/queue",
+            post(agent_queue::enqueue_agent),
+        )
+        .route("/api/agent-queue", get(agent_queue::list_queue))
+        .route("/api/agent/alerts", get(agent_alerts::list_alerts))
+        .route(
+            "/api/agent/runs/{id}/alerts",
+            get(agent_alerts::list_run_alerts),
+        )
+        .route("/api/agent/inbox", get(agent_inbox::list_inbox))
+        .route(
+            "/api/agent/inbox/reviewed",
+            post(agent_inbox::mark_all_reviewed),
+        )
+        .route(
+            "/api/agent/inbox/{run_id}/reviewed",
+            post(agent_inbox::mark_reviewed),
+        )
+        .route(
+            "/api/agent-queue/{id}",
+            patch(agent_queue::set_queue_priority).delete(agent_queue::remove_from_queue),
+        )
+        // Watch subscriptions
+        .route("/api/watches", get(watches::list_watches))
+        .route(
+            "/api/watches/{target_type}/{target_id}",
+            put(watches::watch).delete(watches::unwatch),
+        )
         .route("/api/worktrees/{id}/agent/stop", post(agents::stop_agent))
         .route("/api/worktrees/{id}/agent/events", get(agents::get_events))
+        .route(
+            "/api/worktrees/{id}/agent/stream",
+            get(agent_stream::stream_agent_events),
+        )
         .route(
             "/api/worktrees/{id}/agent/runs/{run_id}/restart",
             post(agents::restart_agent),
@@ -321,8 +488,15 @@ pub fn api_router() -> Router<AppState> {
             "/api/repos/{id}/sources/{source_id}",
             delete(issue_sources::delete_issue_source),
         )
+        // Command palette
+        .route("/api/commands", get(commands::list_commands))
         // Stats
         .route("/api/stats/theme-unlocks", get(stats::theme_unlock_stats))
+        .route("/api/stats/workspace-quota", get(stats::workspace_quota))
+        .route("/api/stats/request-limits", get(stats::request_limits))
+        // Reports
+        .route("/api/reports/costs", get(reports::cost_report))
+        .route("/api/reports/activity", get(reports::activity_report))
         // Push Notifications
         .route(
             "/api/push/vapid-public-key",
@@ -332,6 +506,33 @@ pub fn api_router() -> Router<AppState> {
             "/api/push/subscribe",
             post(push::subscribe_push).delete(push::unsubscribe_push),
         )
+        // API tokens
+        .route(
+            "/api/tokens",
+            get(api_tokens::list_tokens).post(api_tokens::create_token),
+        )
+        .route("/api/tokens/{id}", delete(api_tokens::revoke_token))
+        // Notification center
+        .route(
+            "/api/notifications",
+            get(notifications::list_notifications).delete(notifications::clear_notifications),
+        )
+        .route(
+            "/api/notifications/unread-count",
+            get(notifications::unread_count),
+        )
+        .route(
+            "/api/notifications/read-all",
+            post(notifications::mark_all_read),
+        )
+        .route(
+            "/api/notifications/{id}/read",
+            post(notifications::mark_read),
+        )
+        // Today: prioritized attention list for the home page
+        .route("/api/today", get(today::list_today))
+        // External tool availability
+        .route("/api/tools", get(tools::get_tools))
         // Slack slash commands
         .route("/api/slack/commands", post(slack::handle_slash_command))
         // Model Config
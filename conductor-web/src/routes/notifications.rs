@@ -0,0 +1,293 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::Serialize;
+
+use conductor_core::api_token::ApiToken;
+use conductor_core::notifications::{Notification, NotificationManager, LOCAL_READER};
+
+use crate::error::ApiError;
+use crate::events::ConductorEvent;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// Most notifications returned by `GET /api/notifications` when no limit is given.
+const DEFAULT_NOTIFICATION_LIMIT: usize = 50;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct ListNotificationsQuery {
+    /// Only return notifications this reader has not read.
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Maximum number of notifications to return (default 50).
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct UnreadCountResponse {
+    pub count: i64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct MarkAllReadResponse {
+    /// Number of notifications newly marked read.
+    pub updated: usize,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ClearNotificationsResponse {
+    /// Number of notifications hidden from this reader.
+    pub cleared: usize,
+}
+
+/// Read state is tracked per API token; unauthenticated requests share one reader.
+pub(crate) fn reader(token: &Option<Extension<ApiToken>>) -> &str {
+    token
+        .as_ref()
+        .map(|Extension(t)| t.id.as_str())
+        .unwrap_or(LOCAL_READER)
+}
+
+/// Recent notifications for the caller, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    params(ListNotificationsQuery),
+    responses(
+        (status = 200, description = "Notifications", body = Vec<Notification>),
+    ),
+    tag = "notifications",
+)]
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Query(params): Query<ListNotificationsQuery>,
+) -> Result<Json<Vec<Notification>>, ApiError> {
+    let db = state.db.lock().await;
+    let limit = params.limit.unwrap_or(DEFAULT_NOTIFICATION_LIMIT);
+    Ok(Json(NotificationManager::new(&db).list_visible(
+        &viewer(&token),
+        reader(&token),
+        params.unread_only,
+        limit,
+    )?))
+}
+
+/// Number of notifications the caller has not read.
+#[utoipa::path(
+    get,
+    path = "/api/notifications/unread-count",
+    responses(
+        (status = 200, description = "Unread notification count", body = UnreadCountResponse),
+    ),
+    tag = "notifications",
+)]
+pub async fn unread_count(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<UnreadCountResponse>, ApiError> {
+    let db = state.db.lock().await;
+    let count =
+        NotificationManager::new(&db).unread_count_visible(&viewer(&token), reader(&token))?;
+    Ok(Json(UnreadCountResponse { count }))
+}
+
+/// Mark one notification read for the caller.
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{id}/read",
+    params(
+        ("id" = String, Path, description = "Notification ID"),
+    ),
+    responses(
+        (status = 204, description = "Marked read"),
+        (status = 404, description = "Notification not found"),
+    ),
+    tag = "notifications",
+)]
+pub async fn mark_read(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    {
+        let db = state.db.lock().await;
+        NotificationManager::new(&db).mark_read(reader(&token), &id)?;
+    }
+    state.events.emit(ConductorEvent::NotificationsChanged);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mark every notification read for the caller.
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read-all",
+    responses(
+        (status = 200, description = "All notifications marked read", body = MarkAllReadResponse),
+    ),
+    tag = "notifications",
+)]
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<MarkAllReadResponse>, ApiError> {
+    let updated = {
+        let db = state.db.lock().await;
+        NotificationManager::new(&db).mark_all_read(reader(&token))?
+    };
+    state.events.emit(ConductorEvent::NotificationsChanged);
+    Ok(Json(MarkAllReadResponse { updated }))
+}
+
+/// Clear the caller's notification list. Notifications recorded afterwards
+/// still appear.
+#[utoipa::path(
+    delete,
+    path = "/api/notifications",
+    responses(
+        (status = 200, description = "Notifications cleared", body = ClearNotificationsResponse),
+    ),
+    tag = "notifications",
+)]
+pub async fn clear_notifications(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<ClearNotificationsResponse>, ApiError> {
+    let cleared = {
+        let db = state.db.lock().await;
+        NotificationManager::new(&db).clear(reader(&token))?
+    };
+    state.events.emit(ConductorEvent::NotificationsChanged);
+    Ok(Json(ClearNotificationsResponse { cleared }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use conductor_core::notifications::{
+        NewNotification, NotificationManager, NotificationSeverity,
+    };
+
+    use crate::routes::api_router;
+    use crate::test_helpers::empty_state;
+
+    async fn json(state: &crate::state::AppState, req: Request<Body>) -> serde_json::Value {
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert!(resp.status().is_success(), "status {}", resp.status());
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null)
+    }
+
+    #[tokio::test]
+    async fn list_read_and_clear() {
+        let (state, _db) = empty_state();
+        let id = {
+            let db = state.db.lock().await;
+            NotificationManager::new(&db)
+                .record(&NewNotification {
+                    kind: "agent_run_failed".into(),
+                    severity: NotificationSeverity::Error,
+                    title: "Agent failed".into(),
+                    body: String::new(),
+                    url: None,
+                    repo_id: None,
+                })
+                .unwrap()
+                .id
+        };
+
+        let count = json(
+            &state,
+            Request::get("/api/notifications/unread-count")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(count["count"], 1);
+
+        json(
+            &state,
+            Request::post(format!("/api/notifications/{id}/read"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        let list = json(
+            &state,
+            Request::get("/api/notifications")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(list[0]["read"], true);
+        assert_eq!(list[0]["severity"], "error");
+
+        let cleared = json(
+            &state,
+            Request::delete("/api/notifications")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(cleared["cleared"], 1);
+        let list = json(
+            &state,
+            Request::get("/api/notifications")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(list.as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn notifications_about_repos_private_to_others_are_hidden() {
+        let (state, _db) = crate::test_helpers::seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            NotificationManager::new(&db)
+                .record(&NewNotification {
+                    kind: "agent_run_failed".into(),
+                    severity: NotificationSeverity::Error,
+                    title: "Agent failed in test-repo/feat-test".into(),
+                    body: String::new(),
+                    url: None,
+                    repo_id: Some("r1".into()),
+                })
+                .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        let list =
+            crate::test_helpers::get_json_as(&state, "/api/notifications", outsider.clone()).await;
+        assert_eq!(list.as_array().unwrap().len(), 0, "leaked: {list}");
+        let count =
+            crate::test_helpers::get_json_as(&state, "/api/notifications/unread-count", outsider)
+                .await;
+        assert_eq!(count["count"], 0);
+    }
+
+    #[tokio::test]
+    async fn mark_read_unknown_is_404() {
+        let (state, _db) = empty_state();
+        let resp = api_router()
+            .with_state(state)
+            .oneshot(
+                Request::post("/api/notifications/missing/read")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
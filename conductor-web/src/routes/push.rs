@@ -139,6 +139,8 @@ mod tests {
                 vapid_private_key: Some("test_private_key".to_string()),
                 vapid_subject: Some("mailto:test@example.com".to_string()),
             },
+            limits: Default::default(),
+            auth: Default::default(),
         };
         let db_path = tmp.path().to_path_buf();
         (AppState::new(db, config, web_config, db_path, 100), tmp)
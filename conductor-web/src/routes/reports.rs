@@ -0,0 +1,227 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use serde::Deserialize;
+
+use conductor_core::activity::{
+    activity_heatmap, ActivityGroupBy, ActivityHeatmap, DEFAULT_ACTIVITY_DAYS,
+};
+use conductor_core::agent::{parse_since, AgentManager, CostGroupBy, CostReportRow};
+use conductor_core::api_token::ApiToken;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+fn default_since() -> String {
+    "30d".to_string()
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CostReportQuery {
+    /// Window to report on: `12h`, `30d`, `2w`, or a `YYYY-MM-DD` start date.
+    #[serde(default = "default_since")]
+    pub since: String,
+    /// Grouping: `repo` (default), `ticket`, `worktree` or `day`.
+    #[serde(default)]
+    pub group_by: CostGroupBy,
+}
+
+/// GET /api/reports/costs
+///
+/// Agent cost, turns and duration rolled up over a time window.
+#[utoipa::path(
+    get,
+    path = "/api/reports/costs",
+    params(CostReportQuery),
+    responses(
+        (status = 200, description = "One row per group", body = Vec<CostReportRow>),
+        (status = 400, description = "Invalid since window"),
+    ),
+    tag = "reports",
+)]
+pub async fn cost_report(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Query(query): Query<CostReportQuery>,
+) -> Result<Json<Vec<CostReportRow>>, ApiError> {
+    let since = parse_since(&query.since)?;
+    let db = state.db.lock().await;
+    let rows =
+        AgentManager::new(&db).visible_cost_report(&viewer(&token), Some(since), query.group_by)?;
+    Ok(Json(rows))
+}
+
+fn default_activity_days() -> i64 {
+    DEFAULT_ACTIVITY_DAYS
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ActivityReportQuery {
+    /// Days to cover, ending today (default 90, at most 366).
+    #[serde(default = "default_activity_days")]
+    pub days: i64,
+    /// Grouping: `repo` (default) or `worktree`.
+    #[serde(default)]
+    pub group_by: ActivityGroupBy,
+}
+
+/// GET /api/reports/activity
+///
+/// Per-day commits, agent runs and agent cost of each repo or worktree, for a
+/// calendar heat map.
+#[utoipa::path(
+    get,
+    path = "/api/reports/activity",
+    params(ActivityReportQuery),
+    responses(
+        (status = 200, description = "Heat map rows with their active days", body = ActivityHeatmap),
+        (status = 400, description = "Invalid days"),
+    ),
+    tag = "reports",
+)]
+pub async fn activity_report(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Query(query): Query<ActivityReportQuery>,
+) -> Result<Json<ActivityHeatmap>, ApiError> {
+    let config = state.config.read().await.clone();
+    let db = state.db.lock().await;
+    let heatmap = activity_heatmap(&db, &config, &viewer(&token), query.days, query.group_by)?;
+    Ok(Json(heatmap))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use conductor_core::agent::AgentManager;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::seeded_state;
+
+    #[tokio::test]
+    async fn cost_report_groups_runs_and_rejects_bad_windows() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            let run = AgentManager::new(&db)
+                .create_run(Some("w1"), "task", None)
+                .unwrap();
+            db.execute(
+                "UPDATE agent_runs SET cost_usd = 1.25, num_turns = 3 WHERE id = ?1",
+                [&run.id],
+            )
+            .unwrap();
+        }
+
+        let get = |uri: &'static str| {
+            api_router()
+                .with_state(state.clone())
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = get("/api/reports/costs?since=7d&group_by=day")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(rows.as_array().unwrap().len(), 1);
+        assert_eq!(rows[0]["runs"], 1);
+        assert_eq!(rows[0]["cost_usd"], 1.25);
+        assert_eq!(rows[0]["turns"], 3);
+
+        let response = get("/api/reports/costs?since=soon").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn cost_report_hides_runs_of_repos_private_to_others() {
+        let (state, _tmp) = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            AgentManager::new(&db)
+                .create_run(Some("w1"), "task", None)
+                .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        let mut req = Request::get("/api/reports/costs")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(outsider);
+        let response = api_router().with_state(state).oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(rows, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn activity_report_returns_run_days_and_rejects_bad_windows() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            AgentManager::new(&db)
+                .create_run(Some("w1"), "task", None)
+                .unwrap();
+        }
+
+        let get = |uri: &'static str| {
+            api_router()
+                .with_state(state.clone())
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = get("/api/reports/activity?group_by=worktree")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let heatmap: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(heatmap["days"], 90);
+        assert_eq!(heatmap["rows"][0]["key"], "w1");
+        assert_eq!(heatmap["rows"][0]["runs"], 1);
+
+        let response = get("/api/reports/activity?days=0").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn activity_report_hides_repos_private_to_others() {
+        let (state, _tmp) = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            AgentManager::new(&db)
+                .create_run(Some("w1"), "task", None)
+                .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        for group_by in ["repo", "worktree"] {
+            let mut req = Request::get(format!("/api/reports/activity?group_by={group_by}"))
+                .body(Body::empty())
+                .unwrap();
+            req.extensions_mut().insert(outsider.clone());
+            let response = api_router()
+                .with_state(state.clone())
+                .oneshot(req)
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let heatmap: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(heatmap["rows"], serde_json::json!([]), "{group_by}");
+        }
+    }
+}
@@ -1,16 +1,20 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 
+use conductor_core::api_token::ApiToken;
+use conductor_core::error::ConductorError;
 use conductor_core::github::{
     discover_github_repos, list_github_orgs, list_open_prs, DiscoveredRepo, GithubPr,
 };
 use conductor_core::repo::{derive_local_path, derive_slug_from_url, Repo, RepoManager};
+use conductor_core::repo_visibility::RepoVisibility;
 
 use crate::error::ApiError;
 use crate::events::ConductorEvent;
 use crate::state::AppState;
+use crate::visibility::viewer;
 
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct RegisterRepoRequest {
@@ -28,11 +32,14 @@ pub struct RegisterRepoRequest {
     ),
     tag = "repos",
 )]
-pub async fn list_repos(State(state): State<AppState>) -> Result<Json<Vec<Repo>>, ApiError> {
+pub async fn list_repos(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<Repo>>, ApiError> {
     let db = state.db.lock().await;
     let config = state.config.read().await;
     let mgr = RepoManager::new(&db, &config);
-    let repos = mgr.list()?;
+    let repos = mgr.list_visible(&viewer(&token))?;
     Ok(Json(repos))
 }
 
@@ -161,6 +168,104 @@ pub async fn update_repo_settings(
     Ok(Json(repo))
 }
 
+/// Re-detect a repo's language, package manager and test framework.
+#[utoipa::path(
+    post,
+    path = "/api/repos/{id}/toolchain",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+    ),
+    responses(
+        (status = 200, description = "Repo with the re-detected toolchain", body = Repo),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "repos",
+)]
+pub async fn detect_repo_toolchain(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Repo>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let mgr = RepoManager::new(&db, &config);
+    let repo = mgr.get_by_id(&id)?;
+    Ok(Json(mgr.detect_toolchain(&repo.slug)?))
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RepoVisibilityState {
+    /// Whether only the owning API token sees the repo.
+    pub private: bool,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetRepoVisibilityRequest {
+    /// `true` makes the repo private to the calling token; `false` shares it
+    /// with everyone.
+    pub private: bool,
+}
+
+/// Whether a repo is private. Private repos of other users are not found.
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/visibility",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+    ),
+    responses(
+        (status = 200, description = "Repo visibility", body = RepoVisibilityState),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "repos",
+)]
+pub async fn get_repo_visibility(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<RepoVisibilityState>, ApiError> {
+    let db = state.db.lock().await;
+    let private = RepoVisibility::new(&db).get(&id)?.is_some();
+    Ok(Json(RepoVisibilityState { private }))
+}
+
+/// Make a repo private to the calling API token, hiding it and its
+/// worktrees, tickets and agent runs from everyone else, or share it again.
+#[utoipa::path(
+    put,
+    path = "/api/repos/{id}/visibility",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+    ),
+    request_body(content = SetRepoVisibilityRequest, description = "Visibility to set"),
+    responses(
+        (status = 200, description = "Updated visibility", body = RepoVisibilityState),
+        (status = 400, description = "Making a repo private needs an API token"),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "repos",
+)]
+pub async fn set_repo_visibility(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    token: Option<Extension<ApiToken>>,
+    Json(body): Json<SetRepoVisibilityRequest>,
+) -> Result<Json<RepoVisibilityState>, ApiError> {
+    let db = state.db.lock().await;
+    let visibility = RepoVisibility::new(&db);
+    if body.private {
+        let Some(Extension(token)) = token else {
+            return Err(ApiError::Core(ConductorError::InvalidInput(
+                "a repo can only be made private to an API token; send one".to_string(),
+            )));
+        };
+        visibility.set_private(&id, &token.id)?;
+    } else {
+        visibility.set_shared(&id)?;
+    }
+    Ok(Json(RepoVisibilityState {
+        private: body.private,
+    }))
+}
+
 /// A repo discovered via GitHub with a flag indicating if it's already registered.
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct DiscoverableRepo {
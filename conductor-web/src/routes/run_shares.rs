@@ -0,0 +1,252 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use conductor_core::agent::share::DEFAULT_SHARE_TTL_HOURS;
+use conductor_core::agent::{RunShare, RunShares, SharedRun};
+use conductor_core::api_token::ApiToken;
+use conductor_core::error::ConductorError;
+use conductor_core::repo_visibility::{RepoScoped, RepoVisibility};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateRunShareRequest {
+    /// Hours until the link expires (1 to 720); defaults to 24.
+    #[serde(default)]
+    pub ttl_hours: Option<u32>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CreateRunShareResponse {
+    pub share: RunShare,
+    /// The link token. It is not stored and cannot be shown again.
+    pub token: String,
+    /// Path of the read-only page, relative to the web UI's origin.
+    pub url: String,
+}
+
+/// Create an expiring read-only link to an agent run. The response holds the
+/// only copy of its token.
+#[utoipa::path(
+    post,
+    path = "/api/agent/runs/{id}/share",
+    params(
+        ("id" = String, Path, description = "Agent run ID"),
+    ),
+    request_body(content = CreateRunShareRequest, description = "Link lifetime"),
+    responses(
+        (status = 201, description = "Share link created", body = CreateRunShareResponse),
+        (status = 400, description = "Lifetime out of range"),
+        (status = 404, description = "Agent run not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn create_run_share(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+    Json(body): Json<CreateRunShareRequest>,
+) -> Result<(StatusCode, Json<CreateRunShareResponse>), ApiError> {
+    // The diffstat snapshot shells out to git, so it runs off the async runtime.
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let ttl_hours = body.ttl_hours.unwrap_or(DEFAULT_SHARE_TTL_HOURS);
+    let (share, token) = tokio::task::spawn_blocking(move || {
+        let conn = conductor_core::db::open_database(&db_path)?;
+        RunShares::new(&conn).create(&config, &run_id, ttl_hours)
+    })
+    .await??;
+    let url = format!("/share/{token}");
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateRunShareResponse { share, token, url }),
+    ))
+}
+
+/// List an agent run's share links, expired and revoked ones included.
+#[utoipa::path(
+    get,
+    path = "/api/agent/runs/{id}/shares",
+    params(
+        ("id" = String, Path, description = "Agent run ID"),
+    ),
+    responses(
+        (status = 200, description = "Share links", body = Vec<RunShare>),
+    ),
+    tag = "agents",
+)]
+pub async fn list_run_shares(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+) -> Result<Json<Vec<RunShare>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(RunShares::new(&db).list_for_run(&run_id)?))
+}
+
+/// Revoke a share link. It stays listed but no longer opens.
+#[utoipa::path(
+    delete,
+    path = "/api/agent/shares/{id}",
+    params(
+        ("id" = String, Path, description = "Share link ID"),
+    ),
+    responses(
+        (status = 204, description = "Share link revoked"),
+        (status = 404, description = "Share link not found"),
+    ),
+    tag = "agents",
+)]
+pub async fn revoke_run_share(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let db = state.db.lock().await;
+    let shares = RunShares::new(&db);
+    // The path names the share, not the run, so the visibility middleware
+    // can't see whose repo it belongs to.
+    let share = shares.get(&id)?;
+    if !RepoVisibility::new(&db).can_see(&viewer(&token), RepoScoped::AgentRun(&share.run_id))? {
+        return Err(ConductorError::RunShareNotFound { id }.into());
+    }
+    shares.revoke(&id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The agent run behind a share link. Needs no API token: the link token is
+/// the credential.
+#[utoipa::path(
+    get,
+    path = "/api/shared/runs/{token}",
+    params(
+        ("token" = String, Path, description = "Share link token"),
+    ),
+    responses(
+        (status = 200, description = "Shared agent run", body = SharedRun),
+        (status = 404, description = "Unknown, expired or revoked link"),
+    ),
+    tag = "agents",
+)]
+pub async fn get_shared_run(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Json<SharedRun>, ApiError> {
+    let db = state.db.lock().await;
+    RunShares::new(&db)
+        .resolve(&token)?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("share link not found or expired".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::seeded_state;
+
+    async fn send(
+        state: &crate::state::AppState,
+        req: Request<Body>,
+    ) -> (StatusCode, serde_json::Value) {
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn share_links_open_until_revoked() {
+        let (state, _db) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            conductor_core::test_helpers::insert_test_agent_run(&db, "run1", "w1");
+        }
+
+        let (status, created) = send(
+            &state,
+            Request::post("/api/agent/runs/run1/share")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"ttl_hours": 2}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let token = created["token"].as_str().unwrap().to_string();
+        assert_eq!(created["url"], format!("/share/{token}"));
+
+        let shared_uri = format!("/api/shared/runs/{token}");
+        let (status, shared) = send(
+            &state,
+            Request::get(&shared_uri).body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(shared.get("cost_usd").is_none());
+
+        let share_id = created["share"]["id"].as_str().unwrap();
+        let (status, _) = send(
+            &state,
+            Request::delete(format!("/api/agent/shares/{share_id}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(
+            &state,
+            Request::get(&shared_uri).body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = send(
+            &state,
+            Request::post("/api/agent/runs/run1/share")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"ttl_hours": 0}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn share_links_of_repos_private_to_others_cannot_be_revoked() {
+        let (state, _db) = seeded_state();
+        let (share_id, outsider) = {
+            let db = state.db.lock().await;
+            conductor_core::test_helpers::insert_test_agent_run(&db, "run1", "w1");
+            let config = conductor_core::config::Config::default();
+            let (share, _) = conductor_core::agent::RunShares::new(&db)
+                .create(&config, "run1", 2)
+                .unwrap();
+            (share.id, crate::test_helpers::outsider_token(&db))
+        };
+
+        let mut req = Request::delete(format!("/api/agent/shares/{share_id}"))
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(outsider);
+        let (status, _) = send(&state, req).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let share = conductor_core::agent::RunShares::new(&*state.db.lock().await)
+            .get(&share_id)
+            .unwrap();
+        assert!(share.revoked_at.is_none());
+    }
+}
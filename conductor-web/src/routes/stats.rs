@@ -1,10 +1,15 @@
 use axum::extract::State;
-use axum::Json;
+use axum::{Extension, Json};
 
+use conductor_core::api_token::ApiToken;
+use conductor_core::db::open_database;
 use conductor_core::stats::{StatsManager, ThemeUnlockStats};
+use conductor_core::worktree::{WorkspaceUsage, WorktreeManager};
 
 use crate::error::ApiError;
+use crate::limits::RequestLimitMetrics;
 use crate::state::AppState;
+use crate::visibility::viewer;
 
 /// GET /api/stats/theme-unlocks
 ///
@@ -24,3 +29,60 @@ pub async fn theme_unlock_stats(
     let stats = StatsManager::new(&db).theme_unlock_stats()?;
     Ok(Json(stats))
 }
+
+/// GET /api/stats/workspace-quota
+///
+/// Returns the disk usage of every repo's workspace directory measured against
+/// the `[quota]` config, including the largest reclaimable directories.
+#[utoipa::path(
+    get,
+    path = "/api/stats/workspace-quota",
+    responses(
+        (status = 200, description = "Per-repo workspace disk usage", body = Vec<WorkspaceUsage>),
+    ),
+    tag = "stats",
+)]
+pub async fn workspace_quota(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<WorkspaceUsage>>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let viewer = viewer(&token);
+    // The scan walks every workspace on disk — keep it off the async runtime.
+    let usage = tokio::task::spawn_blocking(move || {
+        let conn = open_database(&db_path)?;
+        WorktreeManager::new(&conn, &config).visible_workspace_usage(&viewer)
+    })
+    .await??;
+    Ok(Json(usage))
+}
+
+/// GET /api/stats/request-limits
+///
+/// Returns the configured `[web.limits]` and how many requests have been
+/// rejected for exceeding the rate or body size limits since startup.
+#[utoipa::path(
+    get,
+    path = "/api/stats/request-limits",
+    responses(
+        (status = 200, description = "Request limit counters", body = RequestLimitMetrics),
+    ),
+    tag = "stats",
+)]
+pub async fn request_limits(State(state): State<AppState>) -> Json<RequestLimitMetrics> {
+    Json(state.limiter.metrics())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::{get_json_as, outsider_token, seeded_state};
+
+    #[tokio::test]
+    async fn workspace_quota_hides_repos_private_to_others() {
+        let (state, _tmp) = seeded_state();
+        let outsider = outsider_token(&*state.db.lock().await);
+        let usage = get_json_as(&state, "/api/stats/workspace-quota", outsider).await;
+        assert_eq!(usage, serde_json::json!([]));
+    }
+}
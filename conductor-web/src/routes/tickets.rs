@@ -1,22 +1,33 @@
 use axum::extract::{Path, Query, State};
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::warn;
 
 use conductor_core::agent::{AgentManager, TicketAgentTotals};
+use conductor_core::api_token::ApiToken;
+use conductor_core::config::IdentityConfig;
 use conductor_core::error::ConductorError;
 use conductor_core::github;
 use conductor_core::github_app;
 use conductor_core::issue_source::IssueSourceManager;
 use conductor_core::repo::RepoManager;
-use conductor_core::ticket_source::TicketSource;
-use conductor_core::tickets::{Ticket, TicketDependencies, TicketInput, TicketLabel, TicketSyncer};
+use conductor_core::ticket_source::{SourceContext, TicketSourceRegistry};
+use conductor_core::tickets::grooming::run_grooming_agent;
+use conductor_core::tickets::import::parse_json_tickets;
+use conductor_core::tickets::search;
+use conductor_core::tickets::{
+    build_ticket_tree, queue_reasons, require_identity, GroomingPass, GroomingProposal,
+    ProposalStatus, QueueScope, RoadmapGroup, Ticket, TicketAttachment, TicketAttachments,
+    TicketDependencies, TicketGrooming, TicketInput, TicketLabel, TicketNode, TicketSyncer,
+    WEBHOOK_SOURCE_TYPE,
+};
 use conductor_core::worktree::{Worktree, WorktreeManager};
 
 use crate::error::ApiError;
 use crate::events::ConductorEvent;
 use crate::state::AppState;
+use crate::visibility::viewer;
 
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct TicketListResponse {
@@ -35,6 +46,7 @@ pub struct TicketDetail {
     pub agent_totals: Option<TicketAgentTotals>,
     pub worktrees: Vec<Worktree>,
     pub dependencies: TicketDependencies,
+    pub attachments: Vec<TicketAttachment>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
@@ -42,6 +54,60 @@ pub struct TicketListQuery {
     /// When true, include closed tickets. Defaults to false (closed tickets hidden).
     #[serde(default)]
     pub show_closed: bool,
+    /// When true, only return tickets in the source's current sprint / milestone.
+    #[serde(default)]
+    pub current_sprint: bool,
+    /// Fuzzy search over ticket number and title; matches come best first.
+    pub q: Option<String>,
+    /// `me`: only tickets assigned to the configured `[identity]`.
+    pub assigned: Option<String>,
+    /// `me`: only tickets mentioning the configured `[identity]`. Combined
+    /// with `assigned=me`, the whole personal queue.
+    pub mentions: Option<String>,
+}
+
+impl TicketListQuery {
+    fn apply(
+        &self,
+        mut tickets: Vec<Ticket>,
+        identity: &IdentityConfig,
+    ) -> Result<Vec<Ticket>, ApiError> {
+        if !self.show_closed {
+            tickets.retain(|t| t.state != "closed");
+        }
+        if self.current_sprint {
+            tickets.retain(|t| t.sprint_current);
+        }
+        if let Some(scope) = self.queue_scope()? {
+            require_identity(identity)?;
+            tickets.retain(|t| scope.admits(&queue_reasons(t, identity)));
+        }
+        Ok(match self.q.as_deref().map(str::trim) {
+            Some(q) if !q.is_empty() => search::search(&tickets, q).into_iter().cloned().collect(),
+            _ => tickets,
+        })
+    }
+
+    fn queue_scope(&self) -> Result<Option<QueueScope>, ConductorError> {
+        let is_me = |param: &str, value: Option<&str>| match value {
+            None => Ok(false),
+            Some("me") => Ok(true),
+            Some(other) => Err(ConductorError::InvalidInput(format!(
+                "{param}={other} is not supported; only {param}=me is"
+            ))),
+        };
+        Ok(
+            match (
+                is_me("assigned", self.assigned.as_deref())?,
+                is_me("mentions", self.mentions.as_deref())?,
+            ) {
+                (true, true) => Some(QueueScope::All),
+                (true, false) => Some(QueueScope::Assigned),
+                (false, true) => Some(QueueScope::Mentioned),
+                (false, false) => None,
+            },
+        )
+    }
 }
 
 #[utoipa::path(
@@ -55,15 +121,17 @@ pub struct TicketListQuery {
 )]
 pub async fn list_all_tickets(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
     Query(params): Query<TicketListQuery>,
 ) -> Result<Json<TicketListResponse>, ApiError> {
     let db = state.db.lock().await;
+    let config = state.config.read().await;
     let syncer = TicketSyncer::new(&db);
-    let mut tickets = syncer.list(None)?;
-    if !params.show_closed {
-        tickets.retain(|t| t.state != "closed");
-    }
-    let dependencies = syncer.get_all_dependencies()?;
+    let tickets = params.apply(
+        syncer.list_visible(None, &viewer(&token))?,
+        &config.identity,
+    )?;
+    let dependencies = syncer.get_visible_dependencies(&viewer(&token))?;
     Ok(Json(TicketListResponse {
         tickets,
         dependencies,
@@ -92,10 +160,7 @@ pub async fn list_tickets(
     let config = state.config.read().await;
     RepoManager::new(&db, &config).get_by_id(&repo_id)?;
     let syncer = TicketSyncer::new(&db);
-    let mut tickets = syncer.list(Some(&repo_id))?;
-    if !params.show_closed {
-        tickets.retain(|t| t.state != "closed");
-    }
+    let tickets = params.apply(syncer.list(Some(&repo_id))?, &config.identity)?;
     let dependencies = syncer.get_all_dependencies_for_repo(&repo_id)?;
     Ok(Json(TicketListResponse {
         tickets,
@@ -103,6 +168,87 @@ pub async fn list_tickets(
     }))
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TicketTreeResponse {
+    /// Top-level tickets, each with its children nested below it.
+    pub tickets: Vec<TicketNode>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/tickets/tree",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+        TicketListQuery,
+    ),
+    responses(
+        (status = 200, description = "Repo tickets nested under their epic/parent, with rolled-up progress and agent cost", body = TicketTreeResponse),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn ticket_tree(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+    Query(params): Query<TicketListQuery>,
+) -> Result<Json<TicketTreeResponse>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    let syncer = TicketSyncer::new(&db);
+    let tickets = params.apply(syncer.list(Some(&repo_id))?, &config.identity)?;
+    let dependencies = syncer.get_all_dependencies_for_repo(&repo_id)?;
+    let progress = syncer.progress_by_ticket()?;
+    let totals = AgentManager::new(&db).totals_by_ticket_for_repo(&repo_id)?;
+    Ok(Json(TicketTreeResponse {
+        tickets: build_ticket_tree(&tickets, &dependencies, &progress, &totals),
+    }))
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RoadmapResponse {
+    pub groups: Vec<RoadmapGroup>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/roadmap",
+    responses(
+        (status = 200, description = "Tickets across all repos grouped by sprint/milestone", body = RoadmapResponse),
+    ),
+    tag = "tickets",
+)]
+pub async fn all_roadmap(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<RoadmapResponse>, ApiError> {
+    let db = state.db.lock().await;
+    let groups = TicketSyncer::new(&db).roadmap(None, &viewer(&token))?;
+    Ok(Json(RoadmapResponse { groups }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/roadmap",
+    params(("id" = String, Path, description = "Repo ID")),
+    responses(
+        (status = 200, description = "Repo tickets grouped by sprint/milestone", body = RoadmapResponse),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn repo_roadmap(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Path(repo_id): Path<String>,
+) -> Result<Json<RoadmapResponse>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    let groups = TicketSyncer::new(&db).roadmap(Some(&repo_id), &viewer(&token))?;
+    Ok(Json(RoadmapResponse { groups }))
+}
+
 /// Fetch tickets using `fetch`, then apply the sync (upsert + close + mark worktrees).
 /// Returns `(synced, closed)` counts. Fetch errors are logged as warnings.
 fn sync_source(
@@ -152,28 +298,39 @@ pub async fn sync_tickets(
     let mut total_synced = 0usize;
     let mut total_closed = 0usize;
 
-    if sources.is_empty() {
-        // Backward compat: auto-detect GitHub from remote URL
-        if let Some((owner, name)) = github::parse_github_remote(&repo.remote_url) {
-            let (synced, closed) = sync_source(&syncer, &repo.id, "github", || {
-                github::sync_github_issues(&owner, &name, token)
-            });
-            total_synced += synced;
-            total_closed += closed;
-        }
-    } else {
-        for source in sources {
-            if let Ok(ts) = TicketSource::from_issue_source(&source) {
-                let ts = ts.with_repo_slug(&repo.slug);
-                let source_type_str = ts.source_type_str();
+    let ctx = SourceContext {
+        repo_slug: &repo.slug,
+        remote_url: &repo.remote_url,
+        token,
+    };
+    for fetcher in TicketSourceRegistry::default().fetchers(&sources, &ctx) {
+        match fetcher {
+            Ok(f) => {
                 let (synced, closed) =
-                    sync_source(&syncer, &repo.id, source_type_str, || ts.sync(token));
+                    sync_source(&syncer, &repo.id, f.source_type(), || f.fetch());
                 total_synced += synced;
                 total_closed += closed;
             }
+            Err(e) => warn!("skipping issue source for {}: {e}", repo.slug),
         }
     }
 
+    if let Err(e) = TicketAttachments::new(&db).maintain(&config.attachments) {
+        warn!("ticket attachment maintenance failed: {e}");
+    }
+
+    // Grooming calls an agent per ticket, so it runs after the response.
+    if config.grooming.enabled {
+        let db_path = state.db_path.clone();
+        let config = config.clone();
+        let repo_id = repo.id.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = groom(&db_path, &config, &repo_id) {
+                warn!("ticket grooming failed for {repo_id}: {e}");
+            }
+        });
+    }
+
     state.events.emit(ConductorEvent::TicketsSynced {
         repo_id: repo.id.clone(),
     });
@@ -183,6 +340,76 @@ pub async fn sync_tickets(
     }))
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+pub struct IngestQuery {
+    /// When true, the body is the full set of open tickets: webhook tickets
+    /// missing from it are closed.
+    #[serde(default)]
+    pub close_missing: bool,
+}
+
+/// Accept tickets pushed by an external system for a repo with a `webhook`
+/// issue source. The body is one ticket object or an array of them, using the
+/// field names of a JSON ticket import (`id`, `title`, `body`, `state`,
+/// `labels`, `assignee`, `priority`, `url`, `parent`, `blocked_by`, `sprint`).
+#[utoipa::path(
+    post,
+    path = "/api/repos/{id}/tickets/ingest",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+        IngestQuery,
+    ),
+    request_body(content = serde_json::Value, description = "A ticket object or an array of them"),
+    responses(
+        (status = 200, description = "Ingest result", body = SyncResult),
+        (status = 400, description = "Invalid tickets, or the repo has no webhook source"),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn ingest_tickets(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+    Query(query): Query<IngestQuery>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<SyncResult>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let repo = RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    let has_webhook = IssueSourceManager::new(&db)
+        .list(&repo.id)?
+        .iter()
+        .any(|s| s.source_type == WEBHOOK_SOURCE_TYPE);
+    if !has_webhook {
+        return Err(ConductorError::InvalidInput(format!(
+            "{} has no webhook issue source; add one with `conductor repo sources add {} --type webhook`",
+            repo.slug, repo.slug
+        ))
+        .into());
+    }
+
+    let tickets = parse_json_tickets(body, WEBHOOK_SOURCE_TYPE)?;
+    let syncer = TicketSyncer::new(&db);
+    let synced = syncer.upsert_tickets(&repo.id, &tickets)?;
+    let closed = if query.close_missing {
+        let ids: Vec<&str> = tickets.iter().map(|t| t.source_id.as_str()).collect();
+        syncer.close_missing_tickets(&repo.id, WEBHOOK_SOURCE_TYPE, &ids)?
+    } else {
+        0
+    };
+    if let Err(e) = syncer.mark_worktrees_for_closed_tickets(&repo.id) {
+        warn!(
+            "marking worktrees for closed tickets failed for {}: {e}",
+            repo.slug
+        );
+    }
+
+    state.events.emit(ConductorEvent::TicketsSynced {
+        repo_id: repo.id.clone(),
+    });
+    Ok(Json(SyncResult { synced, closed }))
+}
+
 #[utoipa::path(
     get,
     path = "/api/ticket-labels",
@@ -193,10 +420,11 @@ pub async fn sync_tickets(
 )]
 pub async fn list_ticket_labels(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
 ) -> Result<Json<Vec<TicketLabel>>, ApiError> {
     let db = state.db.lock().await;
     let syncer = TicketSyncer::new(&db);
-    let map = syncer.get_all_labels()?;
+    let map = syncer.get_visible_labels(&viewer(&token))?;
     let labels: Vec<TicketLabel> = map.into_values().flatten().collect();
     Ok(Json(labels))
 }
@@ -221,22 +449,183 @@ pub async fn ticket_detail(
     let config = state.config.read().await;
 
     let agent_mgr = AgentManager::new(&db);
-    let all_totals = agent_mgr.totals_by_ticket_all()?;
-    let agent_totals = all_totals.get(&id).cloned();
+    let agent_totals = agent_mgr
+        .totals_by_ticket_ids(None, std::slice::from_ref(&id))?
+        .remove(&id);
 
     let wt_mgr = WorktreeManager::new(&db, &config);
     let worktrees = wt_mgr.list_by_ticket(&id)?;
 
     let syncer = TicketSyncer::new(&db);
     let dependencies = syncer.get_dependencies(&id)?;
+    let attachments = TicketAttachments::new(&db).list(&id)?;
 
     Ok(Json(TicketDetail {
         agent_totals,
         worktrees,
         dependencies,
+        attachments,
     }))
 }
 
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct TicketCommentRequest {
+    /// Comment text; Markdown for GitHub.
+    pub body: String,
+    /// Also close the ticket upstream (and locally) after commenting.
+    #[serde(default)]
+    pub close: bool,
+}
+
+/// Comment on a ticket in the tracker it was synced from (GitHub or Jira),
+/// optionally closing it there too.
+#[utoipa::path(
+    post,
+    path = "/api/tickets/{id}/comment",
+    params(
+        ("id" = String, Path, description = "Ticket ID"),
+    ),
+    request_body = TicketCommentRequest,
+    responses(
+        (status = 200, description = "The ticket after the write-back", body = Ticket),
+        (status = 400, description = "Empty comment, or the ticket's source does not support write-back"),
+        (status = 404, description = "Ticket not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn comment_on_ticket(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<TicketCommentRequest>,
+) -> Result<Json<Ticket>, ApiError> {
+    let db = state.db.lock().await;
+    let syncer = TicketSyncer::new(&db);
+    syncer.post_comment(&id, &req.body)?;
+    if req.close {
+        syncer.transition_state(&id, "closed")?;
+    }
+    let ticket = syncer.get_by_id(&id)?;
+    if req.close {
+        state.events.emit(ConductorEvent::TicketsSynced {
+            repo_id: ticket.repo_id.clone(),
+        });
+    }
+    Ok(Json(ticket))
+}
+
+/// Run a grooming pass for one repo on its own connection.
+fn groom(
+    db_path: &std::path::Path,
+    config: &conductor_core::config::Config,
+    repo_id: &str,
+) -> conductor_core::error::Result<GroomingPass> {
+    let conn = conductor_core::db::open_database(db_path)?;
+    let model = config.grooming.model.clone();
+    TicketGrooming::new(&conn).run_pass(config, Some(repo_id), |prompt| {
+        run_grooming_agent(&model, prompt, None)
+    })
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+pub struct GroomingQuery {
+    /// Only proposals for tickets in this repo.
+    pub repo_id: Option<String>,
+    /// Only proposals with this status. Defaults to `pending`.
+    pub status: Option<ProposalStatus>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/grooming",
+    params(GroomingQuery),
+    responses(
+        (status = 200, description = "Grooming proposals, newest first", body = Vec<GroomingProposal>),
+    ),
+    tag = "tickets",
+)]
+pub async fn list_grooming_proposals(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Query(query): Query<GroomingQuery>,
+) -> Result<Json<Vec<GroomingProposal>>, ApiError> {
+    let db = state.db.lock().await;
+    let status = query.status.unwrap_or(ProposalStatus::Pending);
+    let proposals = TicketGrooming::new(&db).list_visible(
+        &viewer(&token),
+        query.repo_id.as_deref(),
+        Some(status),
+    )?;
+    Ok(Json(proposals))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/repos/{id}/grooming",
+    params(("id" = String, Path, description = "Repo ID")),
+    responses(
+        (status = 200, description = "Groomed the repo's ungroomed tickets within the daily budget", body = GroomingPass),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn run_grooming(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+) -> Result<Json<GroomingPass>, ApiError> {
+    let mut config = state.config.read().await.clone();
+    {
+        let db = state.db.lock().await;
+        RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    }
+    // Asking explicitly grooms even when the post-sync pass is off.
+    config.grooming.enabled = true;
+    let db_path = state.db_path.clone();
+    let pass = tokio::task::spawn_blocking(move || groom(&db_path, &config, &repo_id)).await??;
+    Ok(Json(pass))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/grooming/{id}/accept",
+    params(("id" = String, Path, description = "Proposal ID")),
+    responses(
+        (status = 200, description = "Proposal applied to its ticket", body = GroomingProposal),
+        (status = 400, description = "Proposal is not pending"),
+        (status = 404, description = "Proposal not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn accept_grooming_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<GroomingProposal>, ApiError> {
+    let db = state.db.lock().await;
+    let proposal = TicketGrooming::new(&db).accept(&id)?;
+    state.events.emit(ConductorEvent::TicketsSynced {
+        repo_id: proposal.repo_id.clone(),
+    });
+    Ok(Json(proposal))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/grooming/{id}/dismiss",
+    params(("id" = String, Path, description = "Proposal ID")),
+    responses(
+        (status = 200, description = "Proposal dismissed", body = GroomingProposal),
+        (status = 400, description = "Proposal is not pending"),
+        (status = 404, description = "Proposal not found"),
+    ),
+    tag = "tickets",
+)]
+pub async fn dismiss_grooming_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<GroomingProposal>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(TicketGrooming::new(&db).dismiss(&id)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,6 +663,8 @@ mod tests {
                 blocked_by: vec![],
                 children: vec![],
                 parent: None,
+                sprint: None,
+                sprint_current: false,
             },
             TicketInput {
                 source_type: "github".to_string(),
@@ -290,6 +681,8 @@ mod tests {
                 blocked_by: vec![],
                 children: vec![],
                 parent: None,
+                sprint: None,
+                sprint_current: false,
             },
         ];
         syncer.upsert_tickets("r1", &tickets).unwrap();
@@ -304,6 +697,9 @@ mod tests {
             events: EventBus::new(1),
             db_path: std::path::PathBuf::new(),
             workflow_done_notify: None,
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         }
     }
 
@@ -363,4 +759,252 @@ mod tests {
             "show_closed=true must include closed tickets"
         );
     }
+
+    #[tokio::test]
+    async fn list_repo_tickets_fuzzy_search() {
+        let (status, body) = get_ticket_list(
+            "/api/repos/r1/tickets?show_closed=true&q=clsd",
+            seeded_state(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let tickets = body["tickets"].as_array().unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0]["source_id"], "11");
+
+        let (_, body) = get_ticket_list("/api/repos/r1/tickets?q=%2310", seeded_state()).await;
+        assert_eq!(body["tickets"][0]["source_id"], "10");
+    }
+
+    #[tokio::test]
+    async fn list_all_tickets_filters_to_my_queue() {
+        let state = seeded_state();
+        {
+            let db = state.db.lock().await;
+            let mine = TicketInput {
+                assignee: Some("octocat".to_string()),
+                ..conductor_core::test_helpers::make_ticket("12", "Mine")
+            };
+            let mentioned = TicketInput {
+                body: "cc @octocat".to_string(),
+                ..conductor_core::test_helpers::make_ticket("13", "Mentions me")
+            };
+            let both = TicketInput {
+                assignee: Some("octocat".to_string()),
+                body: "@octocat can you take this?".to_string(),
+                ..conductor_core::test_helpers::make_ticket("14", "Mine and mentions me")
+            };
+            TicketSyncer::new(&db)
+                .upsert_tickets("r1", &[mine, mentioned, both])
+                .unwrap();
+        }
+
+        let (status, _) = get_ticket_list("/api/tickets?assigned=me", state.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "no identity configured");
+
+        state.config.write().await.identity.github = Some("octocat".to_string());
+        let (status, body) = get_ticket_list("/api/tickets?assigned=me", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tickets"].as_array().unwrap().len(), 2);
+
+        // Being assigned does not hide a ticket from the mentions filter.
+        let (_, body) = get_ticket_list("/api/tickets?mentions=me", state.clone()).await;
+        let mut ids: Vec<&str> = body["tickets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["source_id"].as_str().unwrap())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["13", "14"]);
+
+        let (_, body) =
+            get_ticket_list("/api/tickets?assigned=me&mentions=me", state.clone()).await;
+        assert_eq!(body["tickets"].as_array().unwrap().len(), 3);
+
+        let (status, _) = get_ticket_list("/api/tickets?assigned=alice", state).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn accept_grooming_proposal_removes_it_from_pending() {
+        let state = seeded_state();
+        let proposal_id = {
+            let db = state.db.lock().await;
+            let ticket = TicketSyncer::new(&db).get_by_source_id("r1", "10").unwrap();
+            let draft = conductor_core::tickets::grooming::ProposalDraft {
+                labels: vec!["bug".into()],
+                ..Default::default()
+            };
+            TicketGrooming::new(&db)
+                .record(&ticket.id, &draft, None, 0.0)
+                .unwrap()
+                .id
+        };
+
+        let (status, body) = get_ticket_list("/api/grooming?repo_id=r1", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+
+        let app = api_router().with_state(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/grooming/{proposal_id}/accept"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (_, body) = get_ticket_list("/api/grooming?repo_id=r1", state.clone()).await;
+        assert!(body.as_array().unwrap().is_empty());
+        let (_, body) = get_ticket_list("/api/grooming?status=accepted", state).await;
+        assert_eq!(body[0]["labels"], serde_json::json!(["bug"]));
+    }
+
+    async fn post_json(uri: &str, body: serde_json::Value, state: AppState) -> StatusCode {
+        api_router()
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn ingest_tickets_requires_a_webhook_source_and_closes_missing() {
+        let state = seeded_state();
+        let tickets = serde_json::json!([
+            {"id": "OPS-1", "title": "Rotate keys"},
+            {"id": "OPS-2", "title": "Patch hosts", "status": "in progress"},
+        ]);
+        let uri = "/api/repos/r1/tickets/ingest";
+        assert_eq!(
+            post_json(uri, tickets.clone(), state.clone()).await,
+            StatusCode::BAD_REQUEST
+        );
+
+        {
+            let db = state.db.lock().await;
+            IssueSourceManager::new(&db)
+                .add("r1", "webhook", "{}", "test-repo")
+                .unwrap();
+        }
+        assert_eq!(post_json(uri, tickets, state.clone()).await, StatusCode::OK);
+        assert_eq!(
+            post_json(
+                &format!("{uri}?close_missing=true"),
+                serde_json::json!({"id": "OPS-2", "title": "Patch hosts"}),
+                state.clone(),
+            )
+            .await,
+            StatusCode::OK
+        );
+
+        let db = state.db.lock().await;
+        let syncer = TicketSyncer::new(&db);
+        assert_eq!(
+            syncer.get_by_source_id("r1", "OPS-1").unwrap().state,
+            "closed"
+        );
+        assert_eq!(
+            syncer.get_by_source_id("r1", "OPS-2").unwrap().state,
+            "open"
+        );
+        // Tickets of other sources are untouched.
+        assert_eq!(syncer.get_by_source_id("r1", "10").unwrap().state, "open");
+    }
+
+    #[tokio::test]
+    async fn comment_on_ticket_rejects_sources_without_write_back() {
+        let state = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO tickets (id, repo_id, source_type, source_id, title, state, synced_at, raw_json)
+                 VALUES ('t-manual', 'r1', 'manual', 'm1', 'Manual', 'open', '2024-01-01T00:00:00Z', '{}');",
+            )
+            .unwrap();
+        }
+        let body = serde_json::json!({"body": "Done", "close": true});
+        assert_eq!(
+            post_json("/api/tickets/t-manual/comment", body.clone(), state.clone()).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post_json("/api/tickets/missing/comment", body, state.clone()).await,
+            StatusCode::NOT_FOUND
+        );
+        let db = state.db.lock().await;
+        assert_eq!(
+            TicketSyncer::new(&db).get_by_id("t-manual").unwrap().state,
+            "open"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_roadmap_counts_closed_tickets_as_done() {
+        let (status, body) = get_ticket_list("/api/repos/r1/roadmap", seeded_state()).await;
+        assert_eq!(status, StatusCode::OK);
+        let groups = body["groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0]["name"], serde_json::Value::Null);
+        assert_eq!(groups[0]["total"], 2);
+        assert_eq!(groups[0]["done"], 1);
+    }
+
+    #[tokio::test]
+    async fn all_roadmap_hides_tickets_of_repos_private_to_others() {
+        let state = seeded_state();
+        let outsider = crate::test_helpers::outsider_token(&*state.db.lock().await);
+        let mut req = Request::get("/api/roadmap").body(Body::empty()).unwrap();
+        req.extensions_mut().insert(outsider);
+        let resp = api_router().with_state(state).oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["groups"], serde_json::json!([]), "{body}");
+    }
+
+    #[tokio::test]
+    async fn ticket_aggregates_hide_repos_private_to_others() {
+        let state = seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO ticket_labels (ticket_id, label) \
+                     SELECT id, 'bug' FROM tickets WHERE source_id = '10'; \
+                 INSERT INTO ticket_dependencies (from_ticket_id, to_ticket_id, dep_type) \
+                     SELECT f.id, t.id, 'blocks' FROM tickets f, tickets t \
+                     WHERE f.source_id = '10' AND t.source_id = '11'; \
+                 INSERT INTO ticket_grooming_proposals (id, ticket_id, created_at) \
+                     SELECT 'g1', id, '2024-01-01T00:00:00Z' FROM tickets \
+                     WHERE source_id = '10';",
+            )
+            .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+
+        let labels =
+            crate::test_helpers::get_json_as(&state, "/api/ticket-labels", outsider.clone()).await;
+        assert_eq!(labels, serde_json::json!([]));
+        let list = crate::test_helpers::get_json_as(&state, "/api/tickets", outsider.clone()).await;
+        assert_eq!(list["tickets"], serde_json::json!([]));
+        assert_eq!(list["dependencies"], serde_json::json!({}), "{list}");
+        let proposals =
+            crate::test_helpers::get_json_as(&state, "/api/grooming?repo_id=r1", outsider).await;
+        assert_eq!(proposals, serde_json::json!([]));
+    }
 }
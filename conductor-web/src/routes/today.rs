@@ -0,0 +1,92 @@
+use axum::extract::State;
+use axum::{Extension, Json};
+
+use conductor_core::api_token::ApiToken;
+use conductor_core::db::open_database;
+use conductor_core::today::{Today, TodayItem};
+
+use crate::error::ApiError;
+use crate::routes::notifications::reader;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// Everything needing attention across repos, highest priority first:
+/// agents awaiting feedback, failing CI, SLA breaches, runs to review,
+/// unread notifications and running agents.
+#[utoipa::path(
+    get,
+    path = "/api/today",
+    responses(
+        (status = 200, description = "Prioritized attention list", body = Vec<TodayItem>),
+    ),
+    tag = "today",
+)]
+pub async fn list_today(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<TodayItem>>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let reader = reader(&token).to_string();
+    let viewer = viewer(&token);
+    // CI status comes from `gh`, one call per repo, so keep it off the runtime.
+    let items = tokio::task::spawn_blocking(move || {
+        let conn = open_database(&db_path)?;
+        Today::new(&conn, &config).items(&reader, &viewer)
+    })
+    .await??;
+    Ok(Json(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use crate::routes::api_router;
+    use crate::test_helpers::seeded_state_with_agent_run;
+
+    async fn today_as(
+        state: &crate::state::AppState,
+        token: Option<conductor_core::api_token::ApiToken>,
+    ) -> Vec<serde_json::Value> {
+        let mut req = Request::get("/api/today").body(Body::empty()).unwrap();
+        if let Some(token) = token {
+            req.extensions_mut().insert(token);
+        }
+        let resp = api_router()
+            .with_state(state.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn today_hides_items_of_repos_private_to_others() {
+        let (state, _db) = seeded_state_with_agent_run();
+        {
+            let db = state.db.lock().await;
+            // No live worktrees, so no `gh` lookups for failing CI.
+            db.execute("UPDATE worktrees SET status = 'merged'", [])
+                .unwrap();
+            db.execute(
+                "UPDATE agent_runs SET status = 'waiting_for_feedback' WHERE id = 'ar1'",
+                [],
+            )
+            .unwrap();
+        }
+        let items = today_as(&state, None).await;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["run_id"], "ar1");
+
+        let outsider = crate::test_helpers::outsider_token(&*state.db.lock().await);
+        let items = today_as(&state, Some(outsider)).await;
+        assert!(items.is_empty(), "private run leaked: {items:?}");
+    }
+}
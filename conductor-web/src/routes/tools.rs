@@ -0,0 +1,37 @@
+use axum::extract::Query;
+use axum::Json;
+
+use conductor_core::tools::{self, ToolReport};
+
+use crate::error::ApiError;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct ToolsQuery {
+    /// Probe again instead of using the cached report.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// Which external tools (git, gh, claude, tmux, package managers, ...) are
+/// installed, from the cached probe unless `refresh` is set.
+#[utoipa::path(
+    get,
+    path = "/api/tools",
+    params(ToolsQuery),
+    responses(
+        (status = 200, description = "External tool availability", body = ToolReport),
+    ),
+    tag = "health",
+)]
+pub async fn get_tools(Query(query): Query<ToolsQuery>) -> Result<Json<ToolReport>, ApiError> {
+    // Probing spawns a process per tool.
+    let report = tokio::task::spawn_blocking(move || {
+        if query.refresh {
+            ToolReport::refresh()
+        } else {
+            ToolReport::current(tools::CACHE_TTL)
+        }
+    })
+    .await?;
+    Ok(Json(report))
+}
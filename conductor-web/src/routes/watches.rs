@@ -0,0 +1,141 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+
+use conductor_core::api_token::ApiToken;
+use conductor_core::watch::{Watch, WatchManager, WatchTarget};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::visibility::viewer;
+
+/// List watched tickets and worktrees.
+#[utoipa::path(
+    get,
+    path = "/api/watches",
+    responses(
+        (status = 200, description = "Watched items", body = Vec<Watch>),
+    ),
+    tag = "watches",
+)]
+pub async fn list_watches(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+) -> Result<Json<Vec<Watch>>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(WatchManager::new(&db).list_visible(&viewer(&token))?))
+}
+
+/// Watch a ticket or worktree. Idempotent.
+#[utoipa::path(
+    put,
+    path = "/api/watches/{target_type}/{target_id}",
+    params(
+        ("target_type" = WatchTarget, Path, description = "`ticket` or `worktree`"),
+        ("target_id" = String, Path, description = "Ticket or worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Item is watched", body = Watch),
+        (status = 404, description = "Ticket or worktree not found"),
+    ),
+    tag = "watches",
+)]
+pub async fn watch(
+    State(state): State<AppState>,
+    Path((target_type, target_id)): Path<(WatchTarget, String)>,
+) -> Result<Json<Watch>, ApiError> {
+    let db = state.db.lock().await;
+    Ok(Json(WatchManager::new(&db).watch(target_type, &target_id)?))
+}
+
+/// Stop watching a ticket or worktree. Idempotent.
+#[utoipa::path(
+    delete,
+    path = "/api/watches/{target_type}/{target_id}",
+    params(
+        ("target_type" = WatchTarget, Path, description = "`ticket` or `worktree`"),
+        ("target_id" = String, Path, description = "Ticket or worktree ID"),
+    ),
+    responses(
+        (status = 204, description = "Item is not watched"),
+    ),
+    tag = "watches",
+)]
+pub async fn unwatch(
+    State(state): State<AppState>,
+    Path((target_type, target_id)): Path<(WatchTarget, String)>,
+) -> Result<StatusCode, ApiError> {
+    let db = state.db.lock().await;
+    WatchManager::new(&db).unwatch(target_type, &target_id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn request(method: Method, uri: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn watch_and_unwatch_worktree() {
+        let (state, _tmp) = crate::test_helpers::seeded_state();
+        let app = crate::routes::api_router().with_state(state);
+
+        let resp = app
+            .clone()
+            .oneshot(request(Method::PUT, "/api/watches/worktree/w1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(request(Method::GET, "/api/watches"))
+            .await
+            .unwrap();
+        let body = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["target_type"], "worktree");
+
+        let resp = app
+            .clone()
+            .oneshot(request(Method::DELETE, "/api/watches/worktree/w1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let resp = app
+            .oneshot(request(Method::PUT, "/api/watches/ticket/missing"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn watches_hide_repos_private_to_others() {
+        let (state, _tmp) = crate::test_helpers::seeded_state();
+        let outsider = {
+            let db = state.db.lock().await;
+            WatchManager::new(&db)
+                .watch(WatchTarget::Worktree, "w1")
+                .unwrap();
+            crate::test_helpers::outsider_token(&db)
+        };
+        let items = crate::test_helpers::get_json_as(&state, "/api/watches", outsider).await;
+        assert_eq!(items, serde_json::json!([]));
+    }
+}
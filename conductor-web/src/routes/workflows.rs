@@ -2297,6 +2297,8 @@ mod tests {
             }),
             slack: conductor_core::config::SlackConfig::default(),
             web_url: None,
+            watch: Default::default(),
+            cost_alerts: Default::default(),
         }
     }
 
@@ -2520,6 +2522,9 @@ mod tests {
             events: EventBus::new(1),
             db_path: test_db_path.clone(),
             workflow_done_notify: Some(Arc::clone(&notify)),
+            limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+            worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+            ticket_totals: Default::default(),
         };
         {
             let db = state.db.lock().await;
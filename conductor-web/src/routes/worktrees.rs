@@ -1,19 +1,28 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 
+use conductor_core::agent::{split_diff, FileDiff};
+use conductor_core::api_token::ApiToken;
 use conductor_core::config::Config;
 use conductor_core::db::open_database;
+use conductor_core::error::ConductorError;
 use conductor_core::repo::RepoManager;
+use conductor_core::repo_visibility::{RepoScoped, RepoVisibility};
 use conductor_core::tickets::TicketSyncer;
 use conductor_core::worktree::{
-    Worktree, WorktreeCreateOptions, WorktreeManager, WorktreeWithStatus,
+    parse_status_list, AbandonReason, BranchTodo, CommitSummary, CreateProgress, PrOptions,
+    PushOptions, ResolvedBase, RestackOptions, StackInfo, Worktree, WorktreeCreateOptions,
+    WorktreeListEntry, WorktreeListFilter, WorktreeListing, WorktreeManager, WorktreeOutcomes,
+    WorktreeSort, WorktreeStatus, WorktreeWithStatus,
 };
 
 use crate::error::ApiError;
 use crate::events::ConductorEvent;
+use crate::jobs::WorktreeJob;
 use crate::state::AppState;
+use crate::visibility::viewer;
 
 /// Open a fresh SQLite connection inside a `spawn_blocking` closure.
 /// Reduces boilerplate shared by all three worktree mutation handlers.
@@ -44,20 +53,12 @@ pub struct MainDirtyConflict {
     pub commits_behind: u32,
 }
 
-/// Typed success body returned as HTTP 201 when a worktree is created.
-/// Extends the core `Worktree` fields with optional runtime metadata.
+/// Body returned as HTTP 202 when worktree creation has been queued. Progress
+/// arrives as `worktree_create_progress` SSE events carrying the same `job_id`.
 #[derive(Serialize, utoipa::ToSchema)]
-pub struct CreateWorktreeResponse {
-    #[serde(flatten)]
-    pub worktree: Worktree,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub warnings: Vec<String>,
-    #[serde(skip_serializing_if = "is_zero")]
-    pub commits_behind: u32,
-}
-
-fn is_zero(n: &u32) -> bool {
-    *n == 0
+pub struct CreateWorktreeAccepted {
+    pub job_id: String,
+    pub repo_id: String,
 }
 
 #[derive(Deserialize, utoipa::ToSchema)]
@@ -65,11 +66,88 @@ pub struct LinkTicketRequest {
     pub ticket_id: String,
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct WorktreeDiff {
+    /// Unified diff against the worktree's merge base with its base branch.
+    pub diff: String,
+    /// The same diff split per file, with added/removed line counts.
+    pub files: Vec<FileDiff>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct WorktreeFilesQuery {
+    /// Path relative to the worktree root; omit for the root.
+    #[serde(default)]
+    pub path: String,
+}
+
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub struct CommitWorktreeRequest {
+    /// Commit message. When omitted, one is built from the linked ticket and
+    /// the latest agent run's result.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Stage every change first, untracked files included, instead of
+    /// committing only what is already staged.
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PushWorktreeResponse {
+    pub message: String,
+}
+
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub struct CreatePrRequest {
+    #[serde(default)]
+    pub draft: bool,
+    /// Build the title and body from the linked ticket and the latest agent
+    /// run's result instead of the commit messages.
+    #[serde(default)]
+    pub from_agent: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CreatePrResponse {
+    pub url: String,
+}
+
 #[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
 pub struct WorktreeListQuery {
     /// When true, include merged/abandoned worktrees. Defaults to false (completed worktrees hidden).
+    /// Ignored when `status` is given.
     #[serde(default)]
     pub show_completed: bool,
+    /// Only these statuses, comma-separated (e.g. `active,parked`).
+    pub status: Option<String>,
+    /// Only worktrees linked to this ticket (internal ID or source ID).
+    pub ticket: Option<String>,
+    /// Only live worktrees with no agent activity for this many days.
+    pub stale_days: Option<u32>,
+    /// Sort order (default `created`).
+    pub sort: Option<WorktreeSort>,
+}
+
+impl WorktreeListQuery {
+    fn into_filter(
+        self,
+        repo_id: Option<String>,
+    ) -> conductor_core::error::Result<WorktreeListFilter> {
+        let statuses = match self.status {
+            Some(ref status) => parse_status_list(status)?,
+            None if self.show_completed => Vec::new(),
+            None => WorktreeStatus::LIVE.to_vec(),
+        };
+        Ok(WorktreeListFilter {
+            repo_id,
+            statuses,
+            ticket: self.ticket,
+            stale_days: self.stale_days,
+            sort: self.sort.unwrap_or_default(),
+            ..Default::default()
+        })
+    }
 }
 
 #[utoipa::path(
@@ -77,19 +155,23 @@ pub struct WorktreeListQuery {
     path = "/api/worktrees",
     params(WorktreeListQuery),
     responses(
-        (status = 200, description = "List of all worktrees", body = Vec<WorktreeWithStatus>),
+        (status = 200, description = "List of all worktrees", body = Vec<WorktreeListEntry>),
+        (status = 400, description = "Invalid status filter"),
     ),
     tag = "worktrees",
 )]
 pub async fn list_all_worktrees(
     State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
     Query(params): Query<WorktreeListQuery>,
-) -> Result<Json<Vec<WorktreeWithStatus>>, ApiError> {
+) -> Result<Json<Vec<WorktreeListEntry>>, ApiError> {
+    let filter = WorktreeListFilter {
+        viewer: viewer(&token),
+        ..params.into_filter(None)?
+    };
     let db = state.db.lock().await;
     let config = state.config.read().await;
-    let mgr = WorktreeManager::new(&db, &config);
-    let active_only = !params.show_completed;
-    let worktrees = mgr.list_all_with_status(active_only)?;
+    let worktrees = WorktreeManager::new(&db, &config).list_entries(&filter)?;
     Ok(Json(worktrees))
 }
 
@@ -101,7 +183,8 @@ pub async fn list_all_worktrees(
         WorktreeListQuery,
     ),
     responses(
-        (status = 200, description = "List of worktrees for repo", body = Vec<WorktreeWithStatus>),
+        (status = 200, description = "List of worktrees for repo, with the git status of live checkouts", body = Vec<WorktreeListEntry>),
+        (status = 400, description = "Invalid status filter"),
         (status = 404, description = "Repo not found"),
     ),
     tag = "worktrees",
@@ -110,17 +193,54 @@ pub async fn list_worktrees(
     State(state): State<AppState>,
     Path(repo_id): Path<String>,
     Query(params): Query<WorktreeListQuery>,
-) -> Result<Json<Vec<WorktreeWithStatus>>, ApiError> {
-    let db = state.db.lock().await;
-    let config = state.config.read().await;
-    // Verify repo exists
-    RepoManager::new(&db, &config).get_by_id(&repo_id)?;
-    let mgr = WorktreeManager::new(&db, &config);
-    let active_only = !params.show_completed;
-    let worktrees = mgr.list_by_repo_id_enriched(&repo_id, active_only)?;
+) -> Result<Json<Vec<WorktreeListEntry>>, ApiError> {
+    let filter = params.into_filter(Some(repo_id.clone()))?;
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    // Git status shells out per worktree, so keep it off the async runtime.
+    let worktrees = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        // Verify repo exists
+        RepoManager::new(&conn, &config).get_by_id(&repo_id)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let mut worktrees = mgr.list_entries(&filter)?;
+        mgr.fill_git_status(&mut worktrees);
+        Ok::<_, conductor_core::error::ConductorError>(worktrees)
+    })
+    .await??;
     Ok(Json(worktrees))
 }
 
+/// Stack links for the repo's live worktrees that are based on another
+/// worktree's branch. Worktrees that are not stacked are omitted.
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/worktrees/stacks",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+    ),
+    responses(
+        (status = 200, description = "Stacked worktrees and their parent state", body = Vec<StackInfo>),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn list_worktree_stacks(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+) -> Result<Json<Vec<StackInfo>>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    // Each stacked worktree costs a `git merge-base`, so keep it off the shared connection.
+    let infos = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        RepoManager::new(&conn, &config).get_by_id(&repo_id)?;
+        WorktreeManager::new(&conn, &config).stack_infos_for_repo(&repo_id)
+    })
+    .await??;
+    Ok(Json(infos))
+}
+
 #[utoipa::path(
     post,
     path = "/api/repos/{id}/worktrees",
@@ -129,9 +249,9 @@ pub async fn list_worktrees(
     ),
     request_body(content = CreateWorktreeRequest, description = "Worktree creation parameters"),
     responses(
-        (status = 201, description = "Worktree created", body = CreateWorktreeResponse),
+        (status = 202, description = "Worktree creation started", body = CreateWorktreeAccepted),
         (status = 404, description = "Repo not found"),
-        (status = 409, description = "Base branch is dirty", body = MainDirtyConflict),
+        (status = 409, description = "Base branch is dirty, or a worktree with this name already exists", body = MainDirtyConflict),
     ),
     tag = "worktrees",
 )]
@@ -139,30 +259,71 @@ pub async fn create_worktree(
     State(state): State<AppState>,
     Path(repo_id): Path<String>,
     Json(body): Json<CreateWorktreeRequest>,
-) -> Result<(StatusCode, Json<CreateWorktreeResponse>), ApiError> {
+) -> Result<(StatusCode, Json<CreateWorktreeAccepted>), ApiError> {
     // Look up repo slug quickly before spawning the blocking work.
     let repo_slug = {
         let db = state.db.lock().await;
         let config = state.config.read().await;
         RepoManager::new(&db, &config).get_by_id(&repo_id)?.slug
     };
+    start_create_job(
+        &state,
+        CreateJob {
+            repo_id,
+            repo_slug,
+            name: body.name,
+            from_branch: body.from_branch,
+            ticket_id: body.ticket_id,
+            from_pr: None,
+            force: body.force.unwrap_or(false),
+        },
+    )
+    .await
+}
+
+/// What a create job needs once the repo is known.
+struct CreateJob {
+    repo_id: String,
+    repo_slug: String,
+    name: String,
+    from_branch: Option<String>,
+    ticket_id: Option<String>,
+    from_pr: Option<u32>,
+    force: bool,
+}
+
+/// Check the slug and base branch, then create the worktree as a background
+/// job. Shared by the create and create-from-URL handlers.
+async fn start_create_job(
+    state: &AppState,
+    job: CreateJob,
+) -> Result<(StatusCode, Json<CreateWorktreeAccepted>), ApiError> {
+    let CreateJob {
+        repo_id,
+        repo_slug,
+        name,
+        from_branch,
+        ticket_id,
+        from_pr,
+        force,
+    } = job;
     let db_path = state.db_path.clone();
     let config = state.config.read().await.clone();
-    let name = body.name.clone();
-    let from_branch = body.from_branch.clone();
-    let ticket_id = body.ticket_id.clone();
-    let force = body.force.unwrap_or(false);
 
-    // Run health check off-thread before creating the worktree.
+    // The health check is quick (local `git status`), so it stays synchronous:
+    // a dirty base branch is still reported as a 409 rather than a failed job.
     let health_result = {
         let db_path2 = db_path.clone();
         let config2 = config.clone();
         let repo_slug2 = repo_slug.clone();
         let from_branch2 = from_branch.clone();
+        let name2 = name.clone();
         tokio::task::spawn_blocking(move || {
             let (conn, config) = open_db_and_config(&db_path2, config2)?;
-            WorktreeManager::new(&conn, &config)
-                .check_main_health(&repo_slug2, from_branch2.as_deref())
+            let mgr = WorktreeManager::new(&conn, &config);
+            // An existing worktree is a 409 here rather than a failed job.
+            mgr.check_slug_available(&repo_slug2, &name2)?;
+            mgr.check_main_health(&repo_slug2, from_branch2.as_deref())
         })
         .await??
     };
@@ -179,38 +340,162 @@ pub async fn create_worktree(
         return Err(ApiError::Conflict(conflict_body));
     }
 
-    let commits_behind = health_result.commits_behind;
-    let (wt, warnings) = tokio::task::spawn_blocking(move || {
-        let (conn, config) = open_db_and_config(&db_path, config)?;
-        WorktreeManager::new(&conn, &config).create(
-            &repo_slug,
-            &name,
-            WorktreeCreateOptions {
-                from_branch,
-                ticket_id,
-                force_dirty: force,
-                pre_health: Some(health_result),
-                ..Default::default()
-            },
-        )
-    })
-    .await??;
+    let job_id = state
+        .worktree_jobs
+        .start(&repo_id, &name, health_result.commits_behind);
 
-    state.events.emit(ConductorEvent::WorktreeCreated {
-        id: wt.id.clone(),
-        repo_id: wt.repo_id.clone(),
+    // Fetch, branch, checkout and dependency install can take a minute; run them
+    // in the background and report progress over SSE.
+    let jobs = state.worktree_jobs.clone();
+    let events = state.events.clone();
+    let (job, repo) = (job_id.clone(), repo_id.clone());
+    tokio::spawn(async move {
+        let progress = {
+            let (jobs, events, job, repo) =
+                (jobs.clone(), events.clone(), job.clone(), repo.clone());
+            CreateProgress::new(move |stage| {
+                jobs.set_stage(&job, stage);
+                events.emit(ConductorEvent::WorktreeCreateProgress {
+                    job_id: job.clone(),
+                    repo_id: repo.clone(),
+                    stage,
+                    label: stage.label().to_string(),
+                });
+            })
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            let (conn, config) = open_db_and_config(&db_path, config)?;
+            WorktreeManager::new(&conn, &config).create(
+                &repo_slug,
+                &name,
+                WorktreeCreateOptions {
+                    from_pr,
+                    from_branch,
+                    ticket_id,
+                    force_dirty: force,
+                    pre_health: Some(health_result),
+                    progress: Some(progress),
+                    ..Default::default()
+                },
+            )
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()));
+
+        match result {
+            Ok((wt, warnings)) => {
+                let worktree_id = wt.id.clone();
+                jobs.succeed(&job, wt, warnings);
+                events.emit(ConductorEvent::WorktreeCreated {
+                    id: worktree_id,
+                    repo_id: repo,
+                    job_id: Some(job),
+                });
+            }
+            Err(error) => {
+                tracing::warn!(job_id = %job, "worktree creation failed: {error}");
+                jobs.fail(&job, error.clone());
+                events.emit(ConductorEvent::WorktreeCreateFailed {
+                    job_id: job,
+                    repo_id: repo,
+                    error,
+                });
+            }
+        }
     });
 
     Ok((
-        StatusCode::CREATED,
-        Json(CreateWorktreeResponse {
-            worktree: wt,
-            warnings,
-            commits_behind,
-        }),
+        StatusCode::ACCEPTED,
+        Json(CreateWorktreeAccepted { job_id, repo_id }),
     ))
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateWorktreeFromUrlRequest {
+    /// GitHub issue or pull request, GitLab issue or Jira issue URL.
+    pub url: String,
+    /// Worktree name; defaults to one derived from the ticket, or `pr-<n>`.
+    pub name: Option<String>,
+    /// When `true`, proceed even if the base branch has uncommitted changes.
+    pub force: Option<bool>,
+}
+
+/// Create a worktree from an issue or pull request URL. The repo is matched
+/// by its remote (or, for Jira, its issue source) and an unsynced issue is
+/// fetched first.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/from-url",
+    request_body(content = CreateWorktreeFromUrlRequest, description = "Issue or PR URL"),
+    responses(
+        (status = 202, description = "Worktree creation started", body = CreateWorktreeAccepted),
+        (status = 400, description = "Unrecognised URL, or no single repo matches it"),
+        (status = 409, description = "Base branch is dirty, or a worktree with this name already exists", body = MainDirtyConflict),
+    ),
+    tag = "worktrees",
+)]
+pub async fn create_worktree_from_url(
+    State(state): State<AppState>,
+    token: Option<Extension<ApiToken>>,
+    Json(body): Json<CreateWorktreeFromUrlRequest>,
+) -> Result<(StatusCode, Json<CreateWorktreeAccepted>), ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let viewer = viewer(&token);
+    // Resolving may fetch the ticket from its tracker.
+    let target = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let target = WorktreeManager::new(&conn, &config).resolve_url(&body.url)?;
+        // The repo is picked here rather than named in the path, so the
+        // visibility middleware can't see it.
+        if !RepoVisibility::new(&conn).can_see(&viewer, RepoScoped::Repo(&target.repo.id))? {
+            return Err(ConductorError::InvalidInput(format!(
+                "no registered repo matches {}",
+                body.url
+            )));
+        }
+        Ok(target)
+    })
+    .await??;
+    start_create_job(
+        &state,
+        CreateJob {
+            repo_id: target.repo.id,
+            repo_slug: target.repo.slug,
+            name: body.name.unwrap_or(target.name),
+            from_branch: None,
+            ticket_id: target.ticket.map(|t| t.id),
+            from_pr: target.from_pr,
+            force: body.force.unwrap_or(false),
+        },
+    )
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/worktree-jobs/{id}",
+    params(
+        ("id" = String, Path, description = "Job ID returned by worktree creation"),
+    ),
+    responses(
+        (status = 200, description = "Worktree creation job", body = WorktreeJob),
+        (status = 404, description = "Job not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn get_worktree_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WorktreeJob>, ApiError> {
+    state
+        .worktree_jobs
+        .get(&id)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("worktree job {id} not found")))
+}
+
 #[utoipa::path(
     get,
     path = "/api/worktrees/{id}",
@@ -234,11 +519,19 @@ pub async fn get_worktree(
     Ok(Json(wt))
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DeleteWorktreeQuery {
+    /// Why the work is being dropped. Only recorded when the worktree ends up
+    /// abandoned rather than merged.
+    pub reason: Option<AbandonReason>,
+}
+
 #[utoipa::path(
     delete,
     path = "/api/worktrees/{id}",
     params(
         ("id" = String, Path, description = "Worktree ID"),
+        DeleteWorktreeQuery,
     ),
     responses(
         (status = 204, description = "Worktree deleted"),
@@ -249,12 +542,13 @@ pub async fn get_worktree(
 pub async fn delete_worktree(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(query): Query<DeleteWorktreeQuery>,
 ) -> Result<StatusCode, ApiError> {
     let db_path = state.db_path.clone();
     let config = state.config.read().await.clone();
     let wt = tokio::task::spawn_blocking(move || {
         let (conn, config) = open_db_and_config(&db_path, config)?;
-        WorktreeManager::new(&conn, &config).delete_by_id(&id)
+        WorktreeManager::new(&conn, &config).delete_by_id(&id, query.reason)
     })
     .await??;
     state.events.emit(ConductorEvent::WorktreeDeleted {
@@ -294,6 +588,7 @@ pub async fn get_worktree_for_repo(
     params(
         ("repo_id" = String, Path, description = "Repo ID"),
         ("id" = String, Path, description = "Worktree ID"),
+        DeleteWorktreeQuery,
     ),
     responses(
         (status = 204, description = "Worktree deleted"),
@@ -304,12 +599,13 @@ pub async fn get_worktree_for_repo(
 pub async fn delete_worktree_for_repo(
     State(state): State<AppState>,
     Path((repo_id, id)): Path<(String, String)>,
+    Query(query): Query<DeleteWorktreeQuery>,
 ) -> Result<StatusCode, ApiError> {
     let db_path = state.db_path.clone();
     let config = state.config.read().await.clone();
     let wt = tokio::task::spawn_blocking(move || {
         let (conn, config) = open_db_and_config(&db_path, config)?;
-        WorktreeManager::new(&conn, &config).delete_by_id_for_repo(&id, &repo_id)
+        WorktreeManager::new(&conn, &config).delete_by_id_for_repo(&id, &repo_id, query.reason)
     })
     .await??;
     state.events.emit(ConductorEvent::WorktreeDeleted {
@@ -319,6 +615,82 @@ pub async fn delete_worktree_for_repo(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct BaseBranchQuery {
+    /// Ticket the new worktree will be linked to.
+    pub ticket_id: Option<String>,
+}
+
+/// Base branch a new worktree would start from when no branch is given,
+/// after parent-ticket and `[[base_branch_rules]]` resolution.
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/worktrees/base-branch",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+        BaseBranchQuery,
+    ),
+    responses(
+        (status = 200, description = "Resolved base branch", body = ResolvedBase),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn resolve_base_branch(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+    Query(query): Query<BaseBranchQuery>,
+) -> Result<Json<ResolvedBase>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let repo = RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    let base =
+        WorktreeManager::new(&db, &config).resolve_base(&repo.slug, query.ticket_id.as_deref())?;
+    Ok(Json(base))
+}
+
+/// How finished worktrees ended across all repos, with abandoned ones broken
+/// down by reason.
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/outcomes",
+    responses(
+        (status = 200, description = "Worktree outcomes", body = WorktreeOutcomes),
+    ),
+    tag = "worktrees",
+)]
+pub async fn all_worktree_outcomes(
+    State(state): State<AppState>,
+) -> Result<Json<WorktreeOutcomes>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    Ok(Json(WorktreeManager::new(&db, &config).outcomes(None)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/repos/{id}/worktrees/outcomes",
+    params(
+        ("id" = String, Path, description = "Repo ID"),
+    ),
+    responses(
+        (status = 200, description = "Worktree outcomes for repo", body = WorktreeOutcomes),
+        (status = 404, description = "Repo not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn repo_worktree_outcomes(
+    State(state): State<AppState>,
+    Path(repo_id): Path<String>,
+) -> Result<Json<WorktreeOutcomes>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    RepoManager::new(&db, &config).get_by_id(&repo_id)?;
+    Ok(Json(
+        WorktreeManager::new(&db, &config).outcomes(Some(&repo_id))?,
+    ))
+}
+
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct SetModelRequest {
     pub model: Option<String>,
@@ -389,6 +761,350 @@ pub async fn link_ticket(
     Ok(Json(updated))
 }
 
+/// Clear the worktree's badge for upstream changes to its linked ticket
+/// (reopened, priority, assignee).
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/ticket/ack",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Updated worktree", body = Worktree),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn acknowledge_ticket_change(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Worktree>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    let wt = WorktreeManager::new(&db, &config).acknowledge_ticket_change(&id)?;
+    Ok(Json(wt))
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct RestackQuery {
+    /// Leave a conflicting rebase in progress (409) so it can be resolved
+    /// through `/api/worktrees/{id}/conflicts` instead of aborting it.
+    #[serde(default)]
+    pub keep_conflicts: bool,
+}
+
+/// Rebase a stacked worktree onto its parent, or onto the parent's base once
+/// the parent has been merged.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/restack",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+        RestackQuery,
+    ),
+    responses(
+        (status = 200, description = "Restacked worktree", body = Worktree),
+        (status = 400, description = "Worktree is not stacked or has uncommitted changes"),
+        (status = 404, description = "Worktree not found"),
+        (status = 409, description = "Rebase stopped on conflicts and was left in progress (keep_conflicts)"),
+        (status = 500, description = "Rebase failed (conflicts); the worktree was left unchanged"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn restack_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<RestackQuery>,
+) -> Result<Json<Worktree>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let wt = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.restack(
+            &repo.slug,
+            &wt.slug,
+            RestackOptions {
+                keep_conflicts: query.keep_conflicts,
+            },
+        )?;
+        mgr.get_by_id(&id)
+    })
+    .await??;
+    Ok(Json(wt))
+}
+
+/// Recreate a live worktree's directory, deleted outside conductor, from its
+/// branch.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Restored worktree", body = Worktree),
+        (status = 400, description = "Worktree directory exists, or its branch is gone locally and on origin"),
+        (status = 404, description = "Worktree not found"),
+        (status = 500, description = "git worktree add failed"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn restore_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Worktree>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let wt = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        WorktreeManager::new(&conn, &config).restore_by_id(&id)
+    })
+    .await??;
+    Ok(Json(wt))
+}
+
+/// Diff of a worktree against the point where it branched off its base,
+/// including uncommitted changes to tracked files.
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/diff",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Worktree diff", body = WorktreeDiff),
+        (status = 400, description = "Worktree is not active"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn worktree_diff(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WorktreeDiff>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let diff = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.diff(&repo.slug, &wt.slug)
+    })
+    .await??;
+    let files = split_diff(&diff);
+    Ok(Json(WorktreeDiff { diff, files }))
+}
+
+/// Browse a worktree's files: a directory listing, or a file's content
+/// (truncated past 512 KiB, omitted for binary files).
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/files",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+        WorktreeFilesQuery,
+    ),
+    responses(
+        (status = 200, description = "Directory listing or file content", body = WorktreeListing),
+        (status = 400, description = "Worktree is not active, or the path is missing or outside the worktree"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn worktree_files(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<WorktreeFilesQuery>,
+) -> Result<Json<WorktreeListing>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let listing = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.browse(&repo.slug, &wt.slug, &params.path)
+    })
+    .await??;
+    Ok(Json(listing))
+}
+
+/// TODO and FIXME comments the worktree's branch added, as of the last scan.
+#[utoipa::path(
+    get,
+    path = "/api/worktrees/{id}/todos",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Branch TODOs from the last scan", body = Vec<BranchTodo>),
+    ),
+    tag = "worktrees",
+)]
+pub async fn worktree_todos(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<BranchTodo>>, ApiError> {
+    let db = state.db.lock().await;
+    let config = state.config.read().await;
+    Ok(Json(WorktreeManager::new(&db, &config).todos(&id)?))
+}
+
+/// Rescan the worktree's branch diff for TODO and FIXME comments it added.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/todos/scan",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    responses(
+        (status = 200, description = "Branch TODOs", body = Vec<BranchTodo>),
+        (status = 400, description = "Worktree is not active"),
+        (status = 404, description = "Worktree not found"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn scan_worktree_todos(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<BranchTodo>>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let todos = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        WorktreeManager::new(&conn, &config).scan_todos_by_id(&id)
+    })
+    .await??;
+    Ok(Json(todos))
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct PushQuery {
+    /// Push without running the repo's `[project] pre_push` checks.
+    #[serde(default)]
+    pub skip_checks: bool,
+}
+
+/// Commit the worktree's changes on its branch.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/commit",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    request_body(content = CommitWorktreeRequest, description = "Message and whether to stage everything"),
+    responses(
+        (status = 200, description = "Changes committed", body = CommitSummary),
+        (status = 400, description = "Worktree is not active, nothing to commit, or no message and no agent result"),
+        (status = 404, description = "Worktree not found"),
+        (status = 500, description = "git commit failed, e.g. a commit hook rejected it"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn commit_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: Option<Json<CommitWorktreeRequest>>,
+) -> Result<Json<CommitSummary>, ApiError> {
+    let req = body.map(|Json(b)| b).unwrap_or_default();
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let commit = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.commit(&repo.slug, &wt.slug, req.message.as_deref(), req.all)
+    })
+    .await??;
+    Ok(Json(commit))
+}
+
+/// Push the worktree branch to origin, after the repo's pre-push checks pass.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/push",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+        PushQuery,
+    ),
+    responses(
+        (status = 200, description = "Branch pushed", body = PushWorktreeResponse),
+        (status = 400, description = "Worktree is not active"),
+        (status = 404, description = "Worktree not found"),
+        (status = 422, description = "A pre-push check failed; the error carries its output and nothing was pushed"),
+        (status = 500, description = "git push failed"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn push_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<PushQuery>,
+) -> Result<Json<PushWorktreeResponse>, ApiError> {
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let message = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.push(
+            &repo.slug,
+            &wt.slug,
+            PushOptions {
+                skip_checks: query.skip_checks,
+            },
+        )
+    })
+    .await??;
+    Ok(Json(PushWorktreeResponse { message }))
+}
+
+/// Open a pull request for the worktree branch against its base branch.
+#[utoipa::path(
+    post,
+    path = "/api/worktrees/{id}/pr",
+    params(
+        ("id" = String, Path, description = "Worktree ID"),
+    ),
+    request_body(content = CreatePrRequest, description = "Draft flag and where the description comes from"),
+    responses(
+        (status = 200, description = "Pull request created", body = CreatePrResponse),
+        (status = 400, description = "Worktree is not active, or `from_agent` without an agent result"),
+        (status = 404, description = "Worktree not found"),
+        (status = 500, description = "gh pr create failed"),
+    ),
+    tag = "worktrees",
+)]
+pub async fn create_worktree_pr(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: Option<Json<CreatePrRequest>>,
+) -> Result<Json<CreatePrResponse>, ApiError> {
+    let opts = body
+        .map(|Json(b)| PrOptions {
+            draft: b.draft,
+            from_agent: b.from_agent,
+        })
+        .unwrap_or_default();
+    let db_path = state.db_path.clone();
+    let config = state.config.read().await.clone();
+    let url = tokio::task::spawn_blocking(move || {
+        let (conn, config) = open_db_and_config(&db_path, config)?;
+        let mgr = WorktreeManager::new(&conn, &config);
+        let wt = mgr.get_by_id(&id)?;
+        let repo = RepoManager::new(&conn, &config).get_by_id(&wt.repo_id)?;
+        mgr.create_pr(&repo.slug, &wt.slug, opts)
+    })
+    .await??;
+    Ok(Json(CreatePrResponse { url }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,7 +1114,9 @@ mod tests {
     use tower::ServiceExt;
 
     use crate::routes::api_router;
-    use crate::test_helpers::{seeded_state, seeded_state_with_dirty_repo};
+    use crate::test_helpers::{
+        seeded_state, seeded_state_with_dirty_repo, seeded_state_with_git_repo,
+    };
 
     async fn send_get(uri: &str, state: AppState) -> (StatusCode, Vec<u8>) {
         let app = api_router().with_state(state);
@@ -425,6 +1143,110 @@ mod tests {
         assert_eq!(json["slug"], "feat-test");
     }
 
+    #[tokio::test]
+    async fn worktree_files_lists_and_reads_inside_the_worktree_only() {
+        let (state, _tmp) = seeded_state();
+        let wt_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(wt_dir.path().join("src")).unwrap();
+        std::fs::write(wt_dir.path().join("src/lib.rs"), "pub fn f() {}").unwrap();
+        {
+            let db = state.db.lock().await;
+            db.execute(
+                "UPDATE worktrees SET path = ?1 WHERE id = 'w1'",
+                [wt_dir.path().to_str().unwrap()],
+            )
+            .unwrap();
+        }
+
+        let (status, body) = send_get("/api/worktrees/w1/files", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["kind"], "dir");
+        assert_eq!(json["entries"][0]["path"], "src");
+
+        let (status, body) =
+            send_get("/api/worktrees/w1/files?path=src/lib.rs", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["kind"], "file");
+        assert_eq!(json["content"], "pub fn f() {}");
+
+        let (status, _) = send_get("/api/worktrees/w1/files?path=../secret", state).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn acknowledge_ticket_change_clears_the_badge() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute_batch("UPDATE worktrees SET ticket_change = 'reopened' WHERE id = 'w1'")
+                .unwrap();
+        }
+        let (_, body) = send_get("/api/worktrees/w1", state.clone()).await;
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["ticket_change"], "reopened");
+
+        let response = api_router()
+            .with_state(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/worktrees/w1/ticket/ack")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let (_, body) = send_get("/api/worktrees/w1", state).await;
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["ticket_change"].is_null());
+    }
+
+    #[tokio::test]
+    async fn list_worktrees_applies_filters() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute_batch(
+                "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at) \
+                 VALUES ('w2', 'r1', 'feat-old', 'feat/old', '/tmp/old', 'merged', \
+                         '2024-01-02T00:00:00Z'); \
+                 INSERT INTO agent_runs (id, worktree_id, prompt, status, started_at, cost_usd) \
+                 VALUES ('a1', 'w2', 'p', 'completed', '2024-01-03T00:00:00Z', 1.25);",
+            )
+            .unwrap();
+        }
+
+        let slugs = |body: &[u8]| -> Vec<String> {
+            let json: Vec<serde_json::Value> = serde_json::from_slice(body).unwrap();
+            json.iter()
+                .map(|w| w["slug"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let (status, body) = send_get("/api/worktrees", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(slugs(&body), ["feat-test"], "completed hidden by default");
+
+        let (_, body) = send_get("/api/repos/r1/worktrees?status=merged", state.clone()).await;
+        assert_eq!(slugs(&body), ["feat-old"]);
+        let json: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json[0]["repo_slug"], "test-repo");
+        assert_eq!(json[0]["total_cost_usd"], 1.25);
+
+        let (_, body) = send_get(
+            "/api/worktrees?show_completed=true&sort=cost",
+            state.clone(),
+        )
+        .await;
+        assert_eq!(slugs(&body), ["feat-old", "feat-test"]);
+
+        let (status, _) = send_get("/api/worktrees?status=bogus", state).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn get_worktree_returns_404_when_not_found() {
         let (state, _tmp) = seeded_state();
@@ -522,6 +1344,43 @@ mod tests {
         assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn delete_worktree_records_reason_in_outcomes() {
+        let (state, _tmp) = seeded_state();
+        let (status, _) = send_delete("/api/worktrees/w1?reason=blocked", state.clone()).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, body) = send_get("/api/repos/r1/worktrees/outcomes", state).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["abandoned"], 1);
+        assert_eq!(json["unspecified"], 0);
+        let blocked = json["by_reason"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["reason"] == "blocked")
+            .unwrap();
+        assert_eq!(blocked["count"], 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_base_branch_defaults_without_ticket() {
+        let (state, _tmp) = seeded_state();
+        let (status, body) = send_get("/api/repos/r1/worktrees/base-branch", state).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["branch"], "main");
+        assert_eq!(json["source"]["kind"], "repo_default");
+    }
+
+    #[tokio::test]
+    async fn delete_worktree_rejects_unknown_reason() {
+        let (state, _tmp) = seeded_state();
+        let (status, _) = send_delete("/api/worktrees/w1?reason=bored", state).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     async fn send_post(uri: &str, body: &str, state: AppState) -> (StatusCode, Vec<u8>) {
         let app = api_router().with_state(state);
         let response = app
@@ -543,20 +1402,67 @@ mod tests {
         (status, bytes)
     }
 
-    /// Verify that the spawn_blocking path in create_worktree propagates git errors correctly.
-    /// /tmp/repo is not a real git repo, so WorktreeManager::create will fail at the git layer,
-    /// exercising the full spawn_blocking → error-propagation → ApiError path.
+    /// Creation runs as a background job: the POST returns 202 immediately, and a git
+    /// failure (/tmp/repo is not a real repo) surfaces as a `worktree_create_failed`
+    /// event and a failed job rather than a 500 on the request.
     #[tokio::test]
-    async fn create_worktree_propagates_error_when_git_fails() {
+    async fn create_worktree_returns_202_and_reports_job_failure() {
         let (state, _tmp) = seeded_state();
-        let (status, _) = send_post(
+        let mut rx = state.events.subscribe();
+        let (status, body) = send_post(
             "/api/repos/r1/worktrees",
             r#"{"name":"new-feature"}"#,
-            state,
+            state.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let job_id = json["job_id"].as_str().unwrap().to_string();
+        assert_eq!(json["repo_id"], "r1");
+
+        let failed = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            loop {
+                if let ConductorEvent::WorktreeCreateFailed {
+                    job_id: j, error, ..
+                } = rx.recv().await.unwrap()
+                {
+                    break (j, error);
+                }
+            }
+        })
+        .await
+        .expect("job should fail");
+        assert_eq!(failed.0, job_id);
+        assert!(!failed.1.is_empty());
+
+        let (status, body) = send_get(&format!("/api/worktree-jobs/{job_id}"), state).await;
+        assert_eq!(status, StatusCode::OK);
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["status"], "failed");
+        assert!(job["worktree"].is_null());
+        assert!(job["finished_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn get_worktree_job_returns_404_for_unknown_job() {
+        let (state, _tmp) = seeded_state();
+        let (status, _) = send_get("/api/worktree-jobs/nope", state).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn commit_worktree_rejects_blank_messages_and_unknown_worktrees() {
+        let (state, _tmp) = seeded_state();
+        let (status, _) = send_post(
+            "/api/worktrees/w1/commit",
+            r#"{"message":"  ","all":true}"#,
+            state.clone(),
         )
         .await;
-        // Git will fail since /tmp/repo is not a git repo — expect 500, not 201 and not a panic.
-        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send_post("/api/worktrees/nope/commit", "{}", state).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
@@ -604,4 +1510,102 @@ mod tests {
         // failing to create the worktree in a temp dir) doesn't matter here.
         assert_ne!(status, StatusCode::CONFLICT);
     }
+
+    /// Poll a worktree creation job until it leaves `running`.
+    async fn wait_for_job(job_id: &str, state: AppState) -> serde_json::Value {
+        tokio::time::timeout(std::time::Duration::from_secs(60), async {
+            loop {
+                let (_, body) =
+                    send_get(&format!("/api/worktree-jobs/{job_id}"), state.clone()).await;
+                let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                if job["status"] != "running" {
+                    break job;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("job should finish")
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_creates_for_same_name_get_distinct_slugs() {
+        let (state, _tmp, _git_dir) = seeded_state_with_git_repo();
+        let body = r#"{"name":"login"}"#;
+        let (a, b) = tokio::join!(
+            send_post("/api/repos/r1/worktrees", body, state.clone()),
+            send_post("/api/repos/r1/worktrees", body, state.clone()),
+        );
+        assert_eq!(a.0, StatusCode::ACCEPTED);
+        assert_eq!(b.0, StatusCode::ACCEPTED);
+
+        let mut slugs = Vec::new();
+        for (_, bytes) in [a, b] {
+            let accepted: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            let job = wait_for_job(accepted["job_id"].as_str().unwrap(), state.clone()).await;
+            assert_eq!(job["status"], "succeeded", "job failed: {job}");
+            slugs.push(job["worktree"]["slug"].as_str().unwrap().to_string());
+        }
+        slugs.sort();
+        assert_eq!(slugs, ["feat-login", "feat-login-2"]);
+
+        let db = state.db.lock().await;
+        let branches: Vec<String> = conductor_core::db::query_collect(
+            &db,
+            "SELECT branch FROM worktrees WHERE repo_id = 'r1' ORDER BY branch",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+        assert_eq!(branches, ["feat/login", "feat/login-2"]);
+        let reserved: i64 = db
+            .query_row(
+                "SELECT COUNT(*) FROM worktree_slug_reservations",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(reserved, 0, "reservations are released after create");
+    }
+
+    #[tokio::test]
+    async fn create_worktree_returns_409_when_slug_is_taken() {
+        let (state, _tmp) = seeded_state();
+        // w1 already uses slug "feat-test".
+        let (status, body) =
+            send_post("/api/repos/r1/worktrees", r#"{"name":"test"}"#, state).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(
+            json["error"]
+                .as_str()
+                .is_some_and(|e| e.contains("feat-test")),
+            "expected conflict naming the slug, got: {json}"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_worktree_stacks_reports_parent() {
+        let (state, _tmp) = seeded_state();
+        {
+            let db = state.db.lock().await;
+            db.execute(
+                "INSERT INTO worktrees (id, repo_id, slug, branch, path, status, created_at, base_branch) \
+                 VALUES ('w2', 'r1', 'feat-child', 'feat/child', '/tmp/ws/feat-child', 'active', \
+                         '2024-01-02T00:00:00Z', 'feat/test')",
+                [],
+            )
+            .unwrap();
+        }
+        let (status, body) = send_get("/api/repos/r1/worktrees/stacks", state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["worktree_id"], "w2");
+        assert_eq!(json[0]["parent_id"], "w1");
+        assert_eq!(json[0]["state"], "up_to_date");
+
+        let (status, _) = send_get("/api/repos/missing/worktrees/stacks", state).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
 }
@@ -1,12 +1,17 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use conductor_core::agent::{TicketAgentTotals, TicketTotalsVersion};
 use conductor_core::config::Config;
+use conductor_core::repo_visibility::Viewer;
 use rusqlite::Connection;
 use tokio::sync::{Mutex, Notify, RwLock};
 
 use crate::config::WebConfig;
 use crate::events::EventBus;
+use crate::jobs::WorktreeJobs;
+use crate::limits::RequestLimiter;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -20,6 +25,54 @@ pub struct AppState {
     /// Signalled by `run_workflow`'s background task when `execute_workflow` returns.
     /// `None` in production. Populated in tests that need deterministic synchronization.
     pub workflow_done_notify: Option<Arc<Notify>>,
+    /// Rate limiter and rejection counters built from `[web.limits]` at startup.
+    pub limiter: Arc<RequestLimiter>,
+    /// Background worktree-creation jobs, polled via `GET /api/worktree-jobs/{id}`.
+    pub worktree_jobs: Arc<WorktreeJobs>,
+    /// Per-ticket agent totals served by `/api/agent/ticket-totals`.
+    pub ticket_totals: Arc<TicketTotalsCache>,
+}
+
+/// Per-ticket agent totals by viewer and scope (`None` for every ticket,
+/// otherwise a repo id), each kept with the [`TicketTotalsVersion`] it was
+/// computed under so a refresh only re-aggregates after a run completes.
+#[derive(Default)]
+pub struct TicketTotalsCache {
+    entries: std::sync::Mutex<HashMap<(Viewer, Option<String>), CachedTotals>>,
+}
+
+type CachedTotals = (TicketTotalsVersion, Arc<HashMap<String, TicketAgentTotals>>);
+
+impl TicketTotalsCache {
+    /// The cached totals of `viewer` for `scope`, if computed under `version`.
+    pub fn get(
+        &self,
+        viewer: &Viewer,
+        scope: Option<&str>,
+        version: &TicketTotalsVersion,
+    ) -> Option<Arc<HashMap<String, TicketAgentTotals>>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&(viewer.clone(), scope.map(str::to_string)))
+            .filter(|(v, _)| v == version)
+            .map(|(_, totals)| Arc::clone(totals))
+    }
+
+    pub fn put(
+        &self,
+        viewer: &Viewer,
+        scope: Option<&str>,
+        version: TicketTotalsVersion,
+        totals: HashMap<String, TicketAgentTotals>,
+    ) -> Arc<HashMap<String, TicketAgentTotals>> {
+        let totals = Arc::new(totals);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            (viewer.clone(), scope.map(str::to_string)),
+            (version, Arc::clone(&totals)),
+        );
+        totals
+    }
 }
 
 impl AppState {
@@ -31,6 +84,7 @@ impl AppState {
         db_path: PathBuf,
         event_capacity: usize,
     ) -> Self {
+        let limiter = Arc::new(RequestLimiter::new(web_config.limits.clone()));
         Self {
             db: Arc::new(Mutex::new(conn)),
             config: Arc::new(RwLock::new(config)),
@@ -38,6 +92,9 @@ impl AppState {
             events: EventBus::new(event_capacity),
             db_path,
             workflow_done_notify: None,
+            limiter,
+            worktree_jobs: Arc::new(WorktreeJobs::new()),
+            ticket_totals: Arc::default(),
         }
     }
 }
@@ -26,6 +26,9 @@ fn state_with_file_db(setup: impl FnOnce(&rusqlite::Connection)) -> (AppState, N
         events: EventBus::new(1),
         db_path,
         workflow_done_notify: None,
+        limiter: Arc::new(crate::limits::RequestLimiter::new(Default::default())),
+        worktree_jobs: Arc::new(crate::jobs::WorktreeJobs::new()),
+        ticket_totals: Default::default(),
     };
     (state, tmp)
 }
@@ -92,6 +95,87 @@ pub fn seeded_state_with_dirty_repo() -> (AppState, NamedTempFile, TempDir) {
     (state, tmp, git_dir)
 }
 
+/// AppState with repo `r1` pointing at a clean git TempDir with one commit on
+/// `main` and its workspace inside the same TempDir. `base_fetch` is
+/// `never`, so worktrees can be created without an origin.
+///
+/// The caller must keep all three returned values alive for the duration of the
+/// test.
+pub fn seeded_state_with_git_repo() -> (AppState, NamedTempFile, TempDir) {
+    let git_dir = TempDir::new().expect("create temp git dir");
+    let repo_path = git_dir.path().join("repo");
+    std::fs::create_dir_all(&repo_path).expect("create repo dir");
+    let run = |args: &[&str]| {
+        let out = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .expect("git command failed");
+        assert!(out.status.success(), "git {args:?} failed");
+    };
+    run(&["init", "-b", "main"]);
+    std::fs::write(repo_path.join("README.md"), "hello").expect("write README");
+    run(&["add", "README.md"]);
+    run(&[
+        "-c",
+        "user.email=test@test.com",
+        "-c",
+        "user.name=Test",
+        "commit",
+        "-m",
+        "init",
+    ]);
+
+    let repo_str = repo_path.to_str().unwrap().to_owned();
+    let ws_str = git_dir.path().join("ws").to_str().unwrap().to_owned();
+    let (state, tmp) = state_with_file_db(move |conn| {
+        conductor_core::test_helpers::insert_test_repo(conn, "r1", "test-repo", &repo_str);
+        conn.execute(
+            "UPDATE repos SET workspace_dir = ?1 WHERE id = 'r1'",
+            [&ws_str],
+        )
+        .expect("set workspace_dir");
+    });
+    state.config.try_write().unwrap().general.base_fetch = conductor_core::config::BaseFetch::Never;
+    (state, tmp, git_dir)
+}
+
+/// Make repo `r1` private to one new API token and return another, whose
+/// requests must not see anything of `r1`. Insert it as a request extension
+/// to act as that web user.
+pub fn outsider_token(conn: &rusqlite::Connection) -> conductor_core::api_token::ApiToken {
+    use conductor_core::api_token::{ApiTokenManager, ApiTokenScope};
+    let tokens = ApiTokenManager::new(conn);
+    let (owner, _) = tokens.create("owner", ApiTokenScope::Admin).unwrap();
+    conductor_core::repo_visibility::RepoVisibility::new(conn)
+        .set_private("r1", &owner.id)
+        .unwrap();
+    tokens.create("outsider", ApiTokenScope::Admin).unwrap().0
+}
+
+/// `GET uri` as the web user of `token`, expecting 200, and the JSON body.
+pub async fn get_json_as(
+    state: &AppState,
+    uri: &str,
+    token: conductor_core::api_token::ApiToken,
+) -> serde_json::Value {
+    use tower::ServiceExt;
+    let mut req = axum::http::Request::get(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    req.extensions_mut().insert(token);
+    let resp = crate::routes::api_router()
+        .with_state(state.clone())
+        .oneshot(req)
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), axum::http::StatusCode::OK, "GET {uri}");
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
 /// AppState with repo `r1`, worktree `w1`, and agent_run `ar1` pre-seeded.
 pub fn seeded_state_with_agent_run() -> (AppState, NamedTempFile) {
     state_with_file_db(|conn| {
@@ -0,0 +1,106 @@
+//! Hiding repos that are private to another user.
+//!
+//! List endpoints filter with the caller's [`Viewer`] in their queries. This
+//! middleware covers the rest: any `/api` path naming a repo, worktree,
+//! ticket or agent run that belongs to a repo private to someone else is
+//! answered with 404, exactly as if it did not exist.
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+
+use conductor_core::api_token::ApiToken;
+use conductor_core::repo_visibility::{RepoScoped, RepoVisibility, Viewer};
+
+use crate::state::AppState;
+
+/// The viewer for a request: its API token, or anonymous.
+pub fn viewer(token: &Option<Extension<ApiToken>>) -> Viewer {
+    Viewer::web(token.as_ref().map(|Extension(t)| t.id.as_str()))
+}
+
+/// The repo-scoped rows a path names, as `<collection>/<id>` pairs.
+/// Segments that are not ids (`/api/worktrees/outcomes`) are looked up too;
+/// rows that do not exist count as visible.
+fn scoped_targets(path: &str) -> Vec<RepoScoped<'_>> {
+    let segments: Vec<&str> = path.trim_start_matches("/api/").split('/').collect();
+    segments
+        .windows(2)
+        .filter_map(|pair| match pair[0] {
+            "repos" => Some(RepoScoped::Repo(pair[1])),
+            "worktrees" => Some(RepoScoped::Worktree(pair[1])),
+            "tickets" => Some(RepoScoped::Ticket(pair[1])),
+            "runs" | "agent" | "inbox" => Some(RepoScoped::AgentRun(pair[1])),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Middleware answering 404 for rows of repos private to someone other than
+/// the caller. Runs after [`crate::auth::authenticate`].
+pub async fn hide_private_repos(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req.uri().path();
+    if !path.starts_with("/api/") {
+        return next.run(req).await;
+    }
+    let targets = scoped_targets(path);
+    if targets.is_empty() {
+        return next.run(req).await;
+    }
+
+    let viewer = Viewer::web(req.extensions().get::<ApiToken>().map(|t| t.id.as_str()));
+    let visible = {
+        let db = state.db.lock().await;
+        let visibility = RepoVisibility::new(&db);
+        targets
+            .into_iter()
+            .map(|target| visibility.can_see(&viewer, target))
+            .collect::<conductor_core::error::Result<Vec<bool>>>()
+    };
+    let response = |status: StatusCode, message: &str| {
+        (status, axum::Json(serde_json::json!({ "error": message }))).into_response()
+    };
+    match visible {
+        Ok(visible) if visible.iter().all(|v| *v) => next.run(req).await,
+        Ok(_) => response(StatusCode::NOT_FOUND, "not found"),
+        Err(e) => {
+            tracing::error!("repo visibility lookup failed: {e}");
+            response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "repo visibility lookup failed",
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_targets_picks_every_collection_id_pair() {
+        assert_eq!(
+            scoped_targets("/api/repos/r1/agent/runs"),
+            [RepoScoped::Repo("r1"), RepoScoped::AgentRun("runs")]
+        );
+        assert_eq!(
+            scoped_targets("/api/worktrees/w1/agent/runs/run1/events"),
+            [
+                RepoScoped::Worktree("w1"),
+                RepoScoped::AgentRun("runs"),
+                RepoScoped::AgentRun("run1")
+            ]
+        );
+        assert_eq!(
+            scoped_targets("/api/tickets/t1"),
+            [RepoScoped::Ticket("t1")]
+        );
+        assert!(scoped_targets("/api/notifications").is_empty());
+    }
+}
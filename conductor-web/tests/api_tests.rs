@@ -33,6 +33,11 @@ async fn spawn_test_server_with_setup(setup: impl Fn(&Connection)) -> String {
         events: EventBus::new(64),
         db_path,
         workflow_done_notify: None,
+        limiter: std::sync::Arc::new(conductor_web::limits::RequestLimiter::new(
+            Default::default(),
+        )),
+        worktree_jobs: std::sync::Arc::new(conductor_web::jobs::WorktreeJobs::new()),
+        ticket_totals: Default::default(),
     };
 
     let app = api_router().with_state(state);
@@ -180,6 +185,45 @@ async fn test_delete_repo() {
     assert!(repos.is_empty());
 }
 
+#[tokio::test]
+async fn test_detect_repo_toolchain() {
+    let base = spawn_test_server().await;
+    let client = reqwest::Client::new();
+    let dir = tempfile::tempdir().unwrap();
+
+    let resp = client
+        .post(format!("{base}/api/repos"))
+        .json(&serde_json::json!({
+            "remote_url": "https://github.com/test/tc.git",
+            "slug": "tc",
+            "local_path": dir.path().to_str().unwrap()
+        }))
+        .send()
+        .await
+        .unwrap();
+    let repo: serde_json::Value = resp.json().await.unwrap();
+    assert!(repo["toolchain"]["language"].is_null());
+
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+    let id = repo["id"].as_str().unwrap();
+    let resp = client
+        .post(format!("{base}/api/repos/{id}/toolchain"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let repo: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(repo["toolchain"]["language"], "rust");
+    assert_eq!(repo["toolchain"]["package_manager"], "cargo");
+
+    let resp = client
+        .post(format!("{base}/api/repos/nonexistent-id/toolchain"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
 #[tokio::test]
 async fn test_delete_nonexistent_repo_404() {
     let base = spawn_test_server().await;
@@ -958,6 +1002,23 @@ async fn test_ticket_totals_for_repo() {
     assert!(body.is_object());
 }
 
+#[tokio::test]
+async fn test_ticket_totals_scoped_and_paginated() {
+    let base = spawn_test_server_with_setup(seed_repo_and_worktree).await;
+    for query in [
+        "ticket_ids=t1,t2",
+        "limit=10&offset=5",
+        "ticket_ids=&limit=0",
+    ] {
+        let resp = reqwest::get(format!("{base}/api/repos/r1/agent/ticket-totals?{query}"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "{query}");
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body, serde_json::json!({}), "{query}");
+    }
+}
+
 #[tokio::test]
 async fn test_get_agent_prompt_no_ticket() {
     let base = spawn_test_server_with_setup(seed_repo_and_worktree).await;
@@ -1611,3 +1672,33 @@ async fn test_delete_conversation_returns_409_when_active_run_exists() {
         .unwrap();
     assert_eq!(resp.status(), 200);
 }
+
+#[tokio::test]
+async fn test_workspace_quota_reports_each_repo() {
+    let workspace = tempfile::tempdir().unwrap();
+    let ws_path = workspace.path().to_string_lossy().to_string();
+    std::fs::write(workspace.path().join("blob"), vec![0u8; 100]).unwrap();
+    let base = spawn_test_server_with_setup(move |conn| {
+        let config = Config::default();
+        RepoManager::new(conn, &config)
+            .register(
+                "quota-repo",
+                "/tmp/quota-repo",
+                "https://github.com/test/quota.git",
+                Some(&ws_path),
+            )
+            .unwrap();
+    })
+    .await;
+
+    let resp = reqwest::get(format!("{base}/api/stats/workspace-quota"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Vec<serde_json::Value> = resp.json().await.unwrap();
+    assert_eq!(body.len(), 1);
+    assert_eq!(body[0]["repo_slug"], "quota-repo");
+    assert_eq!(body[0]["used_bytes"], 100);
+    assert_eq!(body[0]["level"], "ok");
+    assert!(body[0]["limit_bytes"].is_null());
+}